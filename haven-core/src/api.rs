@@ -1,6 +1,15 @@
 //! API module for Haven core functionality.
 
-use crate::location::{LocationMessage, LocationSettings};
+use crate::environment::{
+    active_environment, install_environment, Environment, EnvironmentProfile,
+};
+use std::sync::{Mutex, PoisonError};
+
+use crate::location::precision::reduce_to;
+use crate::location::{
+    DwellPolicy, Fix, LocationHistory, LocationMessage, LocationSettings, PrecisionPolicy,
+};
+use crate::self_test::{run_self_test, SelfTestReport};
 
 /// Core interface for Haven functionality.
 ///
@@ -10,6 +19,8 @@ use crate::location::{LocationMessage, LocationSettings};
 pub struct HavenCore {
     initialized: bool,
     location_settings: LocationSettings,
+    location_history: Mutex<LocationHistory>,
+    precision_policy: Box<dyn PrecisionPolicy>,
 }
 
 #[allow(clippy::derivable_impls)] // initialized field differs from new()
@@ -18,6 +29,8 @@ impl Default for HavenCore {
         Self {
             initialized: false, // Default is uninitialized, new() creates initialized
             location_settings: LocationSettings::default(),
+            location_history: Mutex::new(LocationHistory::new()),
+            precision_policy: Box::new(DwellPolicy::default()),
         }
     }
 }
@@ -37,6 +50,8 @@ impl HavenCore {
         Self {
             initialized: true,
             location_settings: LocationSettings::default(),
+            location_history: Mutex::new(LocationHistory::new()),
+            precision_policy: Box::new(DwellPolicy::default()),
         }
     }
 
@@ -68,10 +83,41 @@ impl HavenCore {
         Ok(())
     }
 
+    /// Initializes the core for a deployment environment.
+    ///
+    /// Installs `profile` process-wide (default relays, log verbosity, relay
+    /// timing, mock-relay opt-in; see [`crate::environment`]) and marks the
+    /// core initialized. A QA build passes a staging profile here instead of
+    /// patching Rust.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Err` if the profile is invalid for this build (for example a
+    /// mock relay in a release build) or a different profile was already
+    /// installed in this process.
+    pub fn initialize_with_environment(
+        &mut self,
+        profile: EnvironmentProfile,
+    ) -> Result<(), String> {
+        install_environment(profile)?;
+        self.initialize()
+    }
+
+    /// Returns the active deployment environment (production until a
+    /// profile is installed).
+    #[must_use]
+    pub fn environment() -> Environment {
+        active_environment().environment
+    }
+
     /// Processes raw location data and returns a `LocationMessage` with
     /// exact GPS coordinates. Privacy-sensitive metadata (altitude, speed,
     /// device ID, etc.) is stripped from the serialized form.
     ///
+    /// With [`LocationSettings::adaptive_precision`] on, the fix is also
+    /// remembered and the precision policy (see [`Self::set_precision_policy`])
+    /// may round the location down while the device stays in one spot.
+    ///
     /// # Examples
     ///
     /// ```
@@ -84,7 +130,23 @@ impl HavenCore {
     /// ```
     #[must_use]
     pub fn update_location(&self, latitude: f64, longitude: f64) -> LocationMessage {
-        LocationMessage::new(latitude, longitude)
+        let mut location = LocationMessage::new(latitude, longitude);
+        if self.location_settings.adaptive_precision {
+            let mut history = self
+                .location_history
+                .lock()
+                .unwrap_or_else(PoisonError::into_inner);
+            history.record(Fix::of(&location));
+            let cap = self.precision_policy.precision(history.fixes());
+            reduce_to(&mut location, cap);
+        }
+        location
+    }
+
+    /// Replaces the policy deciding how far adaptive precision coarsens
+    /// (a [`DwellPolicy`] by default).
+    pub fn set_precision_policy(&mut self, policy: Box<dyn PrecisionPolicy>) {
+        self.precision_policy = policy;
     }
 
     /// Gets the current location settings.
@@ -103,7 +165,8 @@ impl HavenCore {
         self.location_settings.clone()
     }
 
-    /// Updates the location settings.
+    /// Updates the location settings. Turning adaptive precision off
+    /// forgets the remembered fixes.
     ///
     /// # Examples
     ///
@@ -115,9 +178,22 @@ impl HavenCore {
     /// settings.update_interval_minutes = 10;
     /// core.set_location_settings(settings);
     /// ```
-    pub const fn set_location_settings(&mut self, settings: LocationSettings) {
+    pub fn set_location_settings(&mut self, settings: LocationSettings) {
+        if !settings.adaptive_precision {
+            self.location_history
+                .get_mut()
+                .unwrap_or_else(PoisonError::into_inner)
+                .clear();
+        }
         self.location_settings = settings;
     }
+
+    /// Checks that this device can do NIP-44, MLS, encrypted database and
+    /// keyring operations, using throwaway keys and files under
+    /// `scratch_dir` (see [`crate::self_test`]).
+    pub async fn run_self_test(scratch_dir: &std::path::Path) -> SelfTestReport {
+        run_self_test(scratch_dir).await
+    }
 }
 
 #[cfg(test)]
@@ -147,6 +223,19 @@ mod tests {
         assert!(core.is_initialized());
     }
 
+    #[test]
+    fn environment_defaults_to_production() {
+        assert_eq!(HavenCore::environment(), Environment::Production);
+    }
+
+    #[test]
+    fn initialize_with_invalid_environment_fails() {
+        let mut core = HavenCore::default();
+        let result = core.initialize_with_environment(EnvironmentProfile::staging(vec![]));
+        assert!(result.is_err());
+        assert!(!core.is_initialized());
+    }
+
     #[test]
     fn debug_trait_implementation() {
         let core = HavenCore::new();
@@ -200,4 +289,42 @@ mod tests {
         let updated = core.get_location_settings();
         assert_eq!(updated.update_interval_minutes, 10);
     }
+
+    #[derive(Debug)]
+    struct AfterFixes(usize);
+
+    impl PrecisionPolicy for AfterFixes {
+        fn precision(&self, history: &[Fix]) -> crate::location::LocationPrecision {
+            if history.len() > self.0 {
+                crate::location::LocationPrecision::Coarse
+            } else {
+                crate::location::LocationPrecision::Exact
+            }
+        }
+    }
+
+    #[test]
+    fn adaptive_precision_follows_the_policy_only_when_enabled() {
+        let mut core = HavenCore::new();
+        core.set_precision_policy(Box::new(AfterFixes(1)));
+        let _ = core.update_location(37.774_929_5, -122.419_415_5);
+        let location = core.update_location(37.774_929_5, -122.419_415_5);
+        assert_eq!(location.latitude, 37.774_929_5);
+
+        let mut settings = core.get_location_settings();
+        settings.adaptive_precision = true;
+        core.set_location_settings(settings.clone());
+        let first = core.update_location(37.774_929_5, -122.419_415_5);
+        assert_eq!(first.latitude, 37.774_929_5);
+        let second = core.update_location(37.774_929_5, -122.419_415_5);
+        assert_eq!(second.latitude, 37.77);
+        assert_eq!(second.geohash.len(), 5);
+
+        settings.adaptive_precision = false;
+        core.set_location_settings(settings.clone());
+        settings.adaptive_precision = true;
+        core.set_location_settings(settings);
+        let fresh = core.update_location(37.774_929_5, -122.419_415_5);
+        assert_eq!(fresh.latitude, 37.774_929_5);
+    }
 }
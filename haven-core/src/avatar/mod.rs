@@ -3,7 +3,8 @@
 //! This module is the pure, local image pipeline: decode → strip metadata
 //! (EXIF/GPS/XMP) → center-crop → downscale → re-encode to JPEG, with
 //! decode-bomb defenses, content hashing, and thumbnail derivation. It has no
-//! network path and no group/MLS awareness.
+//! network path and no group/MLS awareness. [`store`] keeps the contact
+//! pictures the user picks for their address book, sealed on disk.
 //!
 //! After the public-profile migration (see
 //! `docs/PUBLIC_PROFILE_MIGRATION_PLAN.md`) the old MLS in-group avatar
@@ -24,6 +25,7 @@
 pub mod config;
 pub mod error;
 pub mod image;
+pub mod store;
 
 pub use config::{
     DecodeLimits, AVATAR_CANONICAL_MAX_BYTES, AVATAR_JPEG_QUALITY_FLOOR, AVATAR_JPEG_QUALITY_START,
@@ -31,3 +33,4 @@ pub use config::{
 };
 pub use error::{AvatarError, Result};
 pub use image::{content_hash, process_inbound_avatar, process_own_avatar, ProcessedAvatar};
+pub use store::{avatar_store_key, is_avatar_id, AvatarStore, AVATAR_STORE_DIR};
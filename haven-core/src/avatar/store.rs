@@ -0,0 +1,279 @@
+//! Encrypted on-disk store for contact pictures.
+//!
+//! Contact pictures are chosen by the user for their own address book and
+//! never leave the device. [`AvatarStore::put`] runs the raw bytes through
+//! [`process_own_avatar`] (downscale, structural EXIF/GPS strip, JPEG
+//! re-encode) and writes the canonical JPEG, sealed, to one file per picture
+//! under `<data_dir>/avatars/`.
+//!
+//! # Ids
+//!
+//! An avatar id is the hex SHA-256 of the canonical bytes keyed with the store
+//! key. It is stable (the same picture always gets the same id, so two
+//! contacts sharing a picture share one file) but, unlike a bare content hash,
+//! it cannot be matched against a known public picture by someone who only
+//! sees the file names.
+//!
+//! # File format
+//!
+//! `version (1 byte) || nonce (24 bytes) || ciphertext`, XChaCha20-Poly1305
+//! with the id as associated data, so a file renamed to another id does not
+//! open. The key is derived from the identity secret key
+//! ([`avatar_store_key`]).
+
+use std::path::{Path, PathBuf};
+
+use chacha20poly1305::aead::{Aead, KeyInit, Payload};
+use chacha20poly1305::{Key, XChaCha20Poly1305, XNonce};
+use nostr::Keys;
+use rand::rngs::OsRng;
+use rand::RngCore;
+use sha2::{Digest, Sha256};
+use zeroize::Zeroizing;
+
+use super::error::{AvatarError, Result};
+use super::image::process_own_avatar;
+
+/// Directory, under the data dir, holding the sealed pictures.
+pub const AVATAR_STORE_DIR: &str = "avatars";
+
+/// Current file format version.
+const AVATAR_FILE_VERSION: u8 = 1;
+
+/// Domain separator for [`avatar_store_key`].
+const KEY_DOMAIN: &[u8] = b"haven/avatar-store/v1";
+
+/// Domain separator for avatar ids.
+const ID_DOMAIN: &[u8] = b"haven/avatar-id/v1";
+
+/// XChaCha20 nonce length.
+const NONCE_LEN: usize = 24;
+
+/// Derives the avatar sealing key from the identity secret key.
+#[must_use]
+pub fn avatar_store_key(keys: &Keys) -> Zeroizing<[u8; 32]> {
+    let secret = Zeroizing::new(keys.secret_key().to_secret_bytes());
+    let mut hasher = Sha256::new();
+    hasher.update(KEY_DOMAIN);
+    hasher.update(secret.as_slice());
+    Zeroizing::new(hasher.finalize().into())
+}
+
+/// Whether `id` has the shape of an avatar id (64 lowercase hex characters).
+///
+/// Checked before an id is turned into a path, so a stored or caller-supplied
+/// id can never point outside the store directory.
+#[must_use]
+pub fn is_avatar_id(id: &str) -> bool {
+    id.len() == 64 && id.bytes().all(|b| matches!(b, b'0'..=b'9' | b'a'..=b'f'))
+}
+
+/// Sealed, content-addressed picture files under one directory.
+pub struct AvatarStore {
+    dir: PathBuf,
+    key: Zeroizing<[u8; 32]>,
+}
+
+impl std::fmt::Debug for AvatarStore {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("AvatarStore")
+            .field("key", &"<redacted>")
+            .finish_non_exhaustive()
+    }
+}
+
+impl AvatarStore {
+    /// A store under `<data_dir>/avatars/`, sealed with `key`. The directory
+    /// is created on the first [`Self::put`].
+    #[must_use]
+    pub fn new(data_dir: &Path, key: Zeroizing<[u8; 32]>) -> Self {
+        Self {
+            dir: data_dir.join(AVATAR_STORE_DIR),
+            key,
+        }
+    }
+
+    /// Sanitizes `raw` and stores the result. Returns its id; storing the
+    /// same picture again returns the same id and writes nothing.
+    ///
+    /// # Errors
+    ///
+    /// Returns the pipeline's [`AvatarError`] for an unusable image, or
+    /// [`AvatarError::Storage`] if the file cannot be written.
+    pub fn put(&self, raw: &[u8]) -> Result<String> {
+        let processed = process_own_avatar(raw)?;
+        let id = self.id_for(&processed.canonical);
+        let path = self.dir.join(format!("{id}.bin"));
+        if path.exists() {
+            return Ok(id);
+        }
+
+        let mut nonce = [0u8; NONCE_LEN];
+        OsRng.fill_bytes(&mut nonce);
+        let ciphertext = XChaCha20Poly1305::new(Key::from_slice(self.key.as_ref()))
+            .encrypt(
+                XNonce::from_slice(&nonce),
+                Payload {
+                    msg: processed.canonical.as_slice(),
+                    aad: id.as_bytes(),
+                },
+            )
+            .map_err(|_| AvatarError::Storage("Failed to seal avatar".to_string()))?;
+        let mut file = Vec::with_capacity(1 + NONCE_LEN + ciphertext.len());
+        file.push(AVATAR_FILE_VERSION);
+        file.extend_from_slice(&nonce);
+        file.extend_from_slice(&ciphertext);
+
+        // Write-then-rename so a crash never leaves a truncated file under a
+        // valid id.
+        std::fs::create_dir_all(&self.dir).map_err(io_error)?;
+        let tmp = self.dir.join(format!("{id}.tmp"));
+        std::fs::write(&tmp, &file).map_err(io_error)?;
+        std::fs::rename(&tmp, &path).map_err(io_error)?;
+        Ok(id)
+    }
+
+    /// The canonical JPEG stored under `id`, or `None` if there is none.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`AvatarError::InvalidInput`] for a malformed id, or
+    /// [`AvatarError::Storage`] if the file cannot be read or opened.
+    pub fn get(&self, id: &str) -> Result<Option<Zeroizing<Vec<u8>>>> {
+        let path = self.path_for(id)?;
+        let file = match std::fs::read(&path) {
+            Ok(file) => file,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+            Err(e) => return Err(io_error(e)),
+        };
+        let unreadable = || AvatarError::Storage("Avatar file could not be opened".to_string());
+        let (&version, rest) = file.split_first().ok_or_else(unreadable)?;
+        if version != AVATAR_FILE_VERSION || rest.len() < NONCE_LEN {
+            return Err(unreadable());
+        }
+        let (nonce, ciphertext) = rest.split_at(NONCE_LEN);
+        let canonical = XChaCha20Poly1305::new(Key::from_slice(self.key.as_ref()))
+            .decrypt(
+                XNonce::from_slice(nonce),
+                Payload {
+                    msg: ciphertext,
+                    aad: id.as_bytes(),
+                },
+            )
+            .map_err(|_| unreadable())?;
+        Ok(Some(Zeroizing::new(canonical)))
+    }
+
+    /// Deletes the picture stored under `id`. Deleting a missing picture is
+    /// not an error.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`AvatarError::InvalidInput`] for a malformed id, or
+    /// [`AvatarError::Storage`] if the file cannot be removed.
+    pub fn remove(&self, id: &str) -> Result<()> {
+        match std::fs::remove_file(self.path_for(id)?) {
+            Err(e) if e.kind() != std::io::ErrorKind::NotFound => Err(io_error(e)),
+            _ => Ok(()),
+        }
+    }
+
+    fn id_for(&self, canonical: &[u8]) -> String {
+        let mut hasher = Sha256::new();
+        hasher.update(ID_DOMAIN);
+        hasher.update(self.key.as_slice());
+        hasher.update(canonical);
+        hex::encode(hasher.finalize())
+    }
+
+    fn path_for(&self, id: &str) -> Result<PathBuf> {
+        if !is_avatar_id(id) {
+            return Err(AvatarError::InvalidInput);
+        }
+        Ok(self.dir.join(format!("{id}.bin")))
+    }
+}
+
+/// IO errors carry the failure kind only, never a path or file content.
+fn io_error(e: std::io::Error) -> AvatarError {
+    AvatarError::Storage(format!("Avatar file operation failed: {}", e.kind()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use image::{ImageEncoder, Rgb, RgbImage};
+
+    fn flat_png(color: [u8; 3]) -> Vec<u8> {
+        let img = RgbImage::from_pixel(64, 48, Rgb(color));
+        let mut out = Vec::new();
+        image::codecs::png::PngEncoder::new(std::io::Cursor::new(&mut out))
+            .write_image(&img, 64, 48, image::ExtendedColorType::Rgb8)
+            .unwrap();
+        out
+    }
+
+    fn store(dir: &Path) -> AvatarStore {
+        AvatarStore::new(dir, avatar_store_key(&Keys::generate()))
+    }
+
+    #[test]
+    fn put_is_content_addressed_and_round_trips() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = store(dir.path());
+
+        let id = store.put(&flat_png([200, 10, 10])).unwrap();
+        assert!(is_avatar_id(&id));
+        assert_eq!(store.put(&flat_png([200, 10, 10])).unwrap(), id);
+        assert_ne!(store.put(&flat_png([10, 200, 10])).unwrap(), id);
+
+        let jpeg = store.get(&id).unwrap().unwrap();
+        assert_eq!(&jpeg[..2], &[0xFF, 0xD8]);
+
+        // Sealed at rest: the file does not hold the JPEG.
+        let raw =
+            std::fs::read(dir.path().join(AVATAR_STORE_DIR).join(format!("{id}.bin"))).unwrap();
+        assert_eq!(raw[0], AVATAR_FILE_VERSION);
+        assert_ne!(&raw[1 + NONCE_LEN..1 + NONCE_LEN + 4], &jpeg[..4]);
+
+        store.remove(&id).unwrap();
+        assert!(store.get(&id).unwrap().is_none());
+        store.remove(&id).unwrap();
+    }
+
+    #[test]
+    fn another_key_or_a_renamed_file_does_not_open() {
+        let dir = tempfile::tempdir().unwrap();
+        let id = store(dir.path()).put(&flat_png([1, 2, 3])).unwrap();
+        assert!(matches!(
+            store(dir.path()).get(&id),
+            Err(AvatarError::Storage(_))
+        ));
+
+        let keys = Keys::generate();
+        let owner = AvatarStore::new(dir.path(), avatar_store_key(&keys));
+        let a = owner.put(&flat_png([9, 9, 9])).unwrap();
+        let b = "0".repeat(64);
+        let avatars = dir.path().join(AVATAR_STORE_DIR);
+        std::fs::copy(
+            avatars.join(format!("{a}.bin")),
+            avatars.join(format!("{b}.bin")),
+        )
+        .unwrap();
+        assert!(owner.get(&b).is_err());
+    }
+
+    #[test]
+    fn malformed_ids_are_rejected_before_touching_the_disk() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = store(dir.path());
+        assert!(matches!(
+            store.get("../circles.db"),
+            Err(AvatarError::InvalidInput)
+        ));
+        assert!(matches!(
+            store.remove(&"A".repeat(64)),
+            Err(AvatarError::InvalidInput)
+        ));
+    }
+}
@@ -0,0 +1,30 @@
+//! Check-in ("ping me") requests.
+//!
+//! A check-in request asks one circle member to share their current position
+//! now, instead of waiting for their next scheduled update. It is an ordinary
+//! MLS application message whose inner rumor:
+//!
+//! - is tagged `["t","checkin_request"]` so receivers surface it as
+//!   [`LocationMessageResult::CheckinRequest`] instead of a location;
+//! - names the member being asked in a `["p", <hex pubkey>]` tag — the whole
+//!   circle sees the request (it is one group message), and only the target's
+//!   client prompts;
+//! - carries a [`HavenPayload::CheckinRequest`] content naming the same
+//!   target, and no position of its own;
+//! - expires after [`CHECKIN_REQUEST_EXPIRATION_SECS`], so a request read
+//!   hours later (e.g. after a long offline stretch) is not acted on.
+//!
+//! Nothing is sent automatically in response. The target's app decides
+//! whether to answer, and answers with a normal location update
+//! ([`CircleManager::respond_to_checkin`]), so the response is
+//! indistinguishable on the wire from any other location send.
+//!
+//! [`LocationMessageResult::CheckinRequest`]: crate::nostr::mls::LocationMessageResult::CheckinRequest
+//! [`CircleManager::respond_to_checkin`]: crate::circle::CircleManager::respond_to_checkin
+//! [`HavenPayload::CheckinRequest`]: crate::payload::HavenPayload::CheckinRequest
+
+/// Inner-rumor hashtag marking a check-in request.
+pub const CHECKIN_REQUEST_TAG: &str = "checkin_request";
+
+/// Expiration window of a check-in request, in seconds (10 minutes).
+pub const CHECKIN_REQUEST_EXPIRATION_SECS: u64 = 10 * 60;
@@ -0,0 +1,139 @@
+//! Who would receive the next location update.
+//!
+//! [`CircleManager::get_current_audience`] answers "who can see me right
+//! now" in one call: every member of every circle the app shares with, with
+//! the local name the user gave them, merged across circles. Per circle it
+//! says how precise the location they get is and until when a precise
+//! session lifts the circle's setting, so the answer matches what
+//! [`CircleManager::encrypt_location`] would actually publish.
+//!
+//! [`CircleManager::get_current_audience`]: super::CircleManager::get_current_audience
+//! [`CircleManager::encrypt_location`]: super::CircleManager::encrypt_location
+
+use std::collections::BTreeMap;
+
+use crate::location::LocationPrecision;
+use crate::nostr::mls::types::GroupId;
+
+/// One circle through which a member would receive the next update.
+#[derive(Clone, PartialEq, Eq)]
+pub struct AudienceCircle {
+    /// MLS group ID of the circle.
+    pub mls_group_id: GroupId,
+    /// The circle's local name.
+    pub name: String,
+    /// Precision the circle receives.
+    pub precision: LocationPrecision,
+    /// End of a running precise session (Unix seconds), during which the
+    /// circle receives exact locations.
+    pub precise_until: Option<i64>,
+}
+
+impl std::fmt::Debug for AudienceCircle {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("AudienceCircle")
+            .field("mls_group_id", &"<redacted>")
+            .field("name", &"<redacted>")
+            .field("precision", &self.precision)
+            .field("precise_until", &self.precise_until)
+            .finish()
+    }
+}
+
+/// Someone who would receive the next update.
+#[derive(Clone, PartialEq, Eq)]
+pub struct AudienceMember {
+    /// The member's public key (hex).
+    pub pubkey: String,
+    /// The user's local name for them, if any.
+    pub display_name: Option<String>,
+    /// The finest precision they receive through any circle.
+    pub precision: LocationPrecision,
+    /// Every circle they receive it through, in the order given.
+    pub circles: Vec<AudienceCircle>,
+}
+
+impl std::fmt::Debug for AudienceMember {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("AudienceMember")
+            .field("pubkey", &"<redacted>")
+            .field("display_name", &"<redacted>")
+            .field("precision", &self.precision)
+            .field("circles", &self.circles)
+            .finish()
+    }
+}
+
+/// Merges per-circle rosters into one entry per member.
+///
+/// `circles` pairs each circle with its members' `(pubkey, display name)`,
+/// the local user already left out. Members are ordered by display name
+/// (case-insensitive, unnamed last), then pubkey.
+#[must_use]
+pub fn build_audience(
+    circles: Vec<(AudienceCircle, Vec<(String, Option<String>)>)>,
+) -> Vec<AudienceMember> {
+    let mut members: BTreeMap<String, AudienceMember> = BTreeMap::new();
+    for (circle, roster) in circles {
+        for (pubkey, display_name) in roster {
+            let entry = members
+                .entry(pubkey.clone())
+                .or_insert_with(|| AudienceMember {
+                    pubkey,
+                    display_name,
+                    precision: circle.precision,
+                    circles: Vec::new(),
+                });
+            entry.precision = entry.precision.max(circle.precision);
+            entry.circles.push(circle.clone());
+        }
+    }
+    let mut audience: Vec<AudienceMember> = members.into_values().collect();
+    audience.sort_by(|a, b| {
+        let key = |m: &AudienceMember| {
+            (
+                m.display_name.is_none(),
+                m.display_name.as_deref().map(str::to_lowercase),
+            )
+        };
+        key(a).cmp(&key(b)).then_with(|| a.pubkey.cmp(&b.pubkey))
+    });
+    audience
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn circle(id: u8, precision: LocationPrecision) -> AudienceCircle {
+        AudienceCircle {
+            mls_group_id: GroupId::from_slice(&[id; 32]),
+            name: format!("circle {id}"),
+            precision,
+            precise_until: None,
+        }
+    }
+
+    #[test]
+    fn merges_members_across_circles_at_their_finest_precision() {
+        let named = |pubkey: &str, name: Option<&str>| (pubkey.to_string(), name.map(String::from));
+        let audience = build_audience(vec![
+            (
+                circle(1, LocationPrecision::Coarse),
+                vec![named("bb", Some("bob")), named("cc", None)],
+            ),
+            (
+                circle(2, LocationPrecision::Exact),
+                vec![named("bb", Some("bob")), named("aa", Some("Alice"))],
+            ),
+        ]);
+
+        let order: Vec<&str> = audience.iter().map(|m| m.pubkey.as_str()).collect();
+        assert_eq!(order, ["aa", "bb", "cc"]);
+        let bob = &audience[1];
+        assert_eq!(bob.precision, LocationPrecision::Exact);
+        assert_eq!(bob.circles.len(), 2);
+        assert_eq!(audience[2].precision, LocationPrecision::Coarse);
+        assert!(build_audience(Vec::new()).is_empty());
+    }
+}
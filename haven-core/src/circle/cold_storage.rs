@@ -0,0 +1,249 @@
+//! Cold storage for archived circles.
+//!
+//! An archived (seasonal, rarely opened) circle still owns hot rows: the
+//! last-known location of every member and the per-sender precision
+//! baselines. Freezing the circle moves those rows out of the hot tables into
+//! one sealed blob in `cold_circles`, so the active set's tables and indexes
+//! stay small. Thawing puts them back.
+//!
+//! # Blob format
+//!
+//! `version (1 byte) || nonce (24 bytes) || ciphertext`, where the ciphertext
+//! is XChaCha20-Poly1305 over the deflate-compressed JSON [`ColdSnapshot`],
+//! with the circle's MLS group id as associated data (a blob cannot be thawed
+//! into another circle). The key is derived from the identity secret key
+//! ([`cold_storage_key`]), so the blob stays sealed even when `circles.db` is
+//! opened without `SQLCipher`.
+//!
+//! # Retention
+//!
+//! Frozen locations keep their `purge_after`. The blob records the latest of
+//! them and is dropped by the regular last-known prune once all have passed;
+//! rows that lapsed while frozen are skipped on thaw.
+//!
+//! # Not covered
+//!
+//! The circle's MLS state (epoch secrets, ratchet trees, retained past epochs)
+//! lives in the engine's own database. The Dark Matter v0.9.4 public API does
+//! not expose exporting or evicting a group's state (GAP, plan §5.2 #18), so
+//! only Haven-owned rows are frozen. Meet pins are short-lived and are dropped
+//! rather than frozen.
+
+use std::io::{Read, Write};
+
+use chacha20poly1305::aead::{Aead, KeyInit, Payload};
+use chacha20poly1305::{Key, XChaCha20Poly1305, XNonce};
+use flate2::read::DeflateDecoder;
+use flate2::write::DeflateEncoder;
+use flate2::Compression;
+use nostr::Keys;
+use rand::rngs::OsRng;
+use rand::RngCore;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use zeroize::Zeroizing;
+
+use super::error::{CircleError, Result};
+
+/// Current blob format version.
+pub const COLD_BLOB_VERSION: u8 = 1;
+
+/// Domain separator for [`cold_storage_key`].
+const KEY_DOMAIN: &[u8] = b"haven/circle-cold-storage/v1";
+
+/// XChaCha20 nonce length.
+const NONCE_LEN: usize = 24;
+
+/// Upper bound on a thawed snapshot's JSON size, so a corrupted or hostile
+/// blob cannot decompress into an unbounded allocation.
+const MAX_SNAPSHOT_BYTES: u64 = 64 * 1024 * 1024;
+
+/// Summary of a frozen circle.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ColdCircleInfo {
+    /// Number of rows held in the blob.
+    pub row_count: u64,
+    /// Size of the snapshot before compression and sealing, in bytes.
+    pub raw_bytes: u64,
+    /// Size of the sealed blob, in bytes.
+    pub sealed_bytes: u64,
+    /// Unix timestamp the circle was frozen.
+    pub frozen_at: i64,
+}
+
+/// One frozen `last_known_locations` row (the `nostr_group_id` is implied).
+#[derive(Clone, PartialEq, Serialize, Deserialize)]
+pub(crate) struct ColdLocationRow {
+    pub sender_pubkey: String,
+    pub latitude: f64,
+    pub longitude: f64,
+    pub geohash: String,
+    pub display_name: Option<String>,
+    pub timestamp: i64,
+    pub expires_at: i64,
+    pub purge_after: i64,
+    pub updated_at: i64,
+}
+
+/// One frozen `precision_baselines` row (the `mls_group_id` is implied).
+#[derive(Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub(crate) struct ColdPrecisionRow {
+    pub sender_pubkey: String,
+    pub class: String,
+    pub samples: i64,
+    pub updated_at: i64,
+}
+
+/// Everything frozen for one circle.
+#[derive(Clone, Default, PartialEq, Serialize, Deserialize)]
+pub(crate) struct ColdSnapshot {
+    pub locations: Vec<ColdLocationRow>,
+    pub precision: Vec<ColdPrecisionRow>,
+}
+
+impl ColdSnapshot {
+    /// Number of rows in the snapshot.
+    pub(crate) fn row_count(&self) -> usize {
+        self.locations.len() + self.precision.len()
+    }
+
+    /// Latest `purge_after` of the frozen locations, if any.
+    pub(crate) fn purge_after(&self) -> Option<i64> {
+        self.locations.iter().map(|r| r.purge_after).max()
+    }
+}
+
+/// Derives the cold-storage sealing key from the identity secret key.
+#[must_use]
+pub fn cold_storage_key(keys: &Keys) -> Zeroizing<[u8; 32]> {
+    let secret = Zeroizing::new(keys.secret_key().to_secret_bytes());
+    let mut hasher = Sha256::new();
+    hasher.update(KEY_DOMAIN);
+    hasher.update(secret.as_slice());
+    Zeroizing::new(hasher.finalize().into())
+}
+
+/// Compresses and seals a snapshot. Returns the blob and the uncompressed
+/// snapshot size.
+pub(crate) fn seal(
+    snapshot: &ColdSnapshot,
+    key: &[u8; 32],
+    mls_group_id: &[u8],
+) -> Result<(Vec<u8>, usize)> {
+    let json = Zeroizing::new(
+        serde_json::to_vec(snapshot)
+            .map_err(|e| CircleError::Storage(format!("Failed to encode cold snapshot: {e}")))?,
+    );
+    let mut encoder = DeflateEncoder::new(Vec::new(), Compression::default());
+    encoder
+        .write_all(&json)
+        .map_err(|e| CircleError::Storage(format!("Failed to compress cold snapshot: {e}")))?;
+    let compressed = Zeroizing::new(
+        encoder
+            .finish()
+            .map_err(|e| CircleError::Storage(format!("Failed to compress cold snapshot: {e}")))?,
+    );
+
+    let mut nonce = [0u8; NONCE_LEN];
+    OsRng.fill_bytes(&mut nonce);
+    let ciphertext = XChaCha20Poly1305::new(Key::from_slice(key))
+        .encrypt(
+            XNonce::from_slice(&nonce),
+            Payload {
+                msg: &compressed,
+                aad: mls_group_id,
+            },
+        )
+        .map_err(|_| CircleError::Storage("Failed to seal cold snapshot".to_string()))?;
+
+    let mut blob = Vec::with_capacity(1 + NONCE_LEN + ciphertext.len());
+    blob.push(COLD_BLOB_VERSION);
+    blob.extend_from_slice(&nonce);
+    blob.extend_from_slice(&ciphertext);
+    Ok((blob, json.len()))
+}
+
+/// Opens a blob sealed by [`seal`] for the same circle.
+pub(crate) fn open(blob: &[u8], key: &[u8; 32], mls_group_id: &[u8]) -> Result<ColdSnapshot> {
+    let unreadable = || CircleError::Storage("Cold storage blob could not be opened".to_string());
+    let (&version, rest) = blob.split_first().ok_or_else(unreadable)?;
+    if version != COLD_BLOB_VERSION || rest.len() < NONCE_LEN {
+        return Err(unreadable());
+    }
+    let (nonce, ciphertext) = rest.split_at(NONCE_LEN);
+    let compressed = Zeroizing::new(
+        XChaCha20Poly1305::new(Key::from_slice(key))
+            .decrypt(
+                XNonce::from_slice(nonce),
+                Payload {
+                    msg: ciphertext,
+                    aad: mls_group_id,
+                },
+            )
+            .map_err(|_| unreadable())?,
+    );
+    let mut json = Zeroizing::new(Vec::new());
+    DeflateDecoder::new(compressed.as_slice())
+        .take(MAX_SNAPSHOT_BYTES)
+        .read_to_end(&mut json)
+        .map_err(|_| unreadable())?;
+    serde_json::from_slice(&json).map_err(|_| unreadable())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn snapshot() -> ColdSnapshot {
+        ColdSnapshot {
+            locations: (0..50)
+                .map(|i| ColdLocationRow {
+                    sender_pubkey: format!("{i:064x}"),
+                    latitude: 37.0 + f64::from(i) / 1000.0,
+                    longitude: -122.0,
+                    geohash: "9q8yyk8".to_string(),
+                    display_name: Some("Alice".to_string()),
+                    timestamp: 1_000 + i64::from(i),
+                    expires_at: 2_000,
+                    purge_after: 3_000 + i64::from(i),
+                    updated_at: 1_000,
+                })
+                .collect(),
+            precision: vec![ColdPrecisionRow {
+                sender_pubkey: "aa".repeat(32),
+                class: "approximate".to_string(),
+                samples: 4,
+                updated_at: 900,
+            }],
+        }
+    }
+
+    #[test]
+    fn seal_roundtrips_and_compresses() {
+        let key = [7u8; 32];
+        let snap = snapshot();
+        let (blob, raw) = seal(&snap, &key, b"group").unwrap();
+        assert!(blob.len() < raw, "sealed {} >= raw {raw}", blob.len());
+        assert!(open(&blob, &key, b"group").unwrap() == snap);
+        assert_eq!(snap.row_count(), 51);
+        assert_eq!(snap.purge_after(), Some(3_049));
+    }
+
+    #[test]
+    fn blob_is_bound_to_key_and_circle() {
+        let (blob, _) = seal(&snapshot(), &[7u8; 32], b"group").unwrap();
+        assert!(open(&blob, &[8u8; 32], b"group").is_err());
+        assert!(open(&blob, &[7u8; 32], b"other").is_err());
+        let mut tampered = blob.clone();
+        *tampered.last_mut().unwrap() ^= 1;
+        assert!(open(&tampered, &[7u8; 32], b"group").is_err());
+        assert!(open(&blob[..10], &[7u8; 32], b"group").is_err());
+    }
+
+    #[test]
+    fn key_is_per_identity() {
+        let a = Keys::generate();
+        assert_eq!(*cold_storage_key(&a), *cold_storage_key(&a));
+        assert_ne!(*cold_storage_key(&a), *cold_storage_key(&Keys::generate()));
+    }
+}
@@ -92,14 +92,123 @@ pub enum CircleError {
     /// cannot leak a pubkey, relay URL, or MLS group ID (Security Rule #8).
     #[error("No reachable relay for welcome delivery")]
     MissingWelcomeRelays,
+
+    /// The operation needs circle admin rights this device does not hold
+    /// (adding or removing members, changing the admin set).
+    ///
+    /// Checked locally before anything is staged; the engine enforces the
+    /// same rule against live MLS state. Data-free so `Debug`/`Display`
+    /// cannot leak the MLS group ID.
+    #[error("Not a circle admin")]
+    NotAdmin,
+
+    /// Location sharing to the circle is paused, globally or for the circle
+    /// (see [`CircleManager::set_location_paused`]); no location event was
+    /// produced.
+    ///
+    /// Data-free so `Debug`/`Display` cannot leak the MLS group ID.
+    ///
+    /// [`CircleManager::set_location_paused`]: crate::circle::CircleManager::set_location_paused
+    #[error("Location sharing is paused")]
+    SharingPaused,
+
+    /// The circle's Nostr group ID (the `h` tag on its kind 445 events) is
+    /// already used by a different circle on this device.
+    ///
+    /// Returned from [`CircleStorage::save_circle`]. Two circles sharing an
+    /// `h` tag would have each other's messages routed to them, so the save
+    /// is refused rather than letting one overwrite the other's routing. See
+    /// [`CircleStorage::nostr_group_id_collisions`] for reconciliation.
+    /// Data-free so `Debug`/`Display` cannot leak either group ID.
+    ///
+    /// [`CircleStorage::save_circle`]: crate::circle::storage::CircleStorage::save_circle
+    /// [`CircleStorage::nostr_group_id_collisions`]: crate::circle::storage::CircleStorage::nostr_group_id_collisions
+    #[error("Nostr group ID already used by another circle")]
+    NostrGroupIdCollision,
+
+    /// A lifecycle change not allowed by [`CircleLifecycle`]'s transition
+    /// table (e.g. sending into a circle that was left).
+    ///
+    /// [`CircleLifecycle`]: crate::circle::CircleLifecycle
+    #[error("Illegal circle transition: {from} -> {to}")]
+    IllegalTransition {
+        /// State the circle was in.
+        from: super::lifecycle::CircleLifecycle,
+        /// State that was requested.
+        to: super::lifecycle::CircleLifecycle,
+    },
+
+    /// An invitation was resent too recently or too often (see
+    /// [`CircleManager::resend_invite`]).
+    ///
+    /// [`CircleManager::resend_invite`]: crate::circle::CircleManager::resend_invite
+    #[error("Invitation resend limited")]
+    ResendLimited {
+        /// When the invitation may be resent, or `None` if it may not be
+        /// resent again.
+        retry_at: Option<i64>,
+    },
+
+    /// The MLS database on this device is in a storage format this build
+    /// cannot open, e.g. after installing an older app version.
+    #[error("MLS storage format {found} is not supported (this build reads up to {supported})")]
+    IncompatibleMlsStorage {
+        /// Format recorded for the database.
+        found: u32,
+        /// Newest format this build reads.
+        supported: u32,
+    },
+
+    /// An incoming kind 445 failed [`validate_group_event`] and was not
+    /// handed to MLS.
+    ///
+    /// [`validate_group_event`]: crate::nostr::validate_group_event
+    #[error("Rejected group event: {0}")]
+    RejectedEvent(crate::nostr::GroupEventRejection),
 }
 
 /// Result type alias for circle operations.
 pub type Result<T> = std::result::Result<T, CircleError>;
 
+impl CircleError {
+    /// Returns a fixed, content-free slug naming the variant.
+    ///
+    /// Used for [`crate::diagnostics::Breadcrumb`]s, which must never carry
+    /// the payload of `Display`.
+    #[must_use]
+    pub const fn kind(&self) -> &'static str {
+        match self {
+            Self::Storage(_) => "storage",
+            Self::Database(_) => "database",
+            Self::NotFound(_) => "not_found",
+            Self::ContactNotFound(_) => "contact_not_found",
+            Self::InvalidData(_) => "invalid_data",
+            Self::Mls(_) => "mls",
+            Self::AlreadyExists(_) => "already_exists",
+            Self::MembershipConflict(_) => "membership_conflict",
+            Self::OrphanedCircleRemoved => "orphaned_circle_removed",
+            Self::LastMemberAbandon => "last_member_abandon",
+            Self::AlreadyProcessed => "already_processed",
+            Self::MissingWelcomeRelays => "missing_welcome_relays",
+            Self::NotAdmin => "not_admin",
+            Self::SharingPaused => "sharing_paused",
+            Self::NostrGroupIdCollision => "nostr_group_id_collision",
+            Self::IllegalTransition { .. } => "illegal_transition",
+            Self::ResendLimited { .. } => "resend_limited",
+            Self::IncompatibleMlsStorage { .. } => "incompatible_mls_storage",
+            Self::RejectedEvent(_) => "rejected_event",
+        }
+    }
+}
+
 impl From<crate::nostr::NostrError> for CircleError {
     fn from(err: crate::nostr::NostrError) -> Self {
-        Self::Mls(crate::nostr::mls::redact_hex_sequences(&err.to_string()))
+        match err {
+            crate::nostr::NostrError::IncompatibleMlsStorage { found, supported } => {
+                Self::IncompatibleMlsStorage { found, supported }
+            }
+            other => Self::Mls(crate::nostr::mls::redact_hex_sequences(&other.to_string())),
+        }
     }
 }
 
@@ -202,6 +311,30 @@ mod tests {
         }
     }
 
+    #[test]
+    fn nostr_group_id_collision_display_is_opaque() {
+        let err = CircleError::NostrGroupIdCollision;
+        assert_eq!(
+            err.to_string(),
+            "Nostr group ID already used by another circle"
+        );
+        assert_eq!(format!("{err:?}"), "NostrGroupIdCollision");
+    }
+
+    #[test]
+    fn kind_is_a_valid_breadcrumb_slug() {
+        let errs = [
+            CircleError::Storage("s".into()),
+            CircleError::NotFound("group123".into()),
+            CircleError::Mls("m".into()),
+            CircleError::MissingWelcomeRelays,
+        ];
+        for err in errs {
+            assert!(crate::diagnostics::is_valid_slug(err.kind()));
+            assert!(!err.kind().contains("group123"));
+        }
+    }
+
     #[test]
     fn nostr_error_conversion_redacts_long_hex_from_surfaced_message() {
         use crate::nostr::mls::redact_hex_sequences;
@@ -0,0 +1,148 @@
+-- circles.db as the last release before versioned schema migrations
+-- (schema v1) created it: the tables of that release's initialize_schema,
+-- the sentinels its one-shot migrations left in user_settings, and no
+-- schema_migrations table, circle_memberships.lifecycle or
+-- contacts.verified/verified_at/avatar_id. Used by the migration tests in
+-- storage_migrations.rs.
+
+CREATE TABLE circles (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    mls_group_id BLOB NOT NULL UNIQUE,
+    nostr_group_id BLOB NOT NULL,
+    display_name TEXT NOT NULL,
+    circle_type TEXT NOT NULL DEFAULT 'location_sharing',
+    relays TEXT NOT NULL DEFAULT '[]',
+    created_at INTEGER NOT NULL,
+    updated_at INTEGER NOT NULL
+);
+
+CREATE TABLE circle_memberships (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    mls_group_id BLOB NOT NULL UNIQUE,
+    status TEXT NOT NULL DEFAULT 'pending',
+    inviter_pubkey TEXT,
+    invited_at INTEGER NOT NULL,
+    responded_at INTEGER,
+    FOREIGN KEY (mls_group_id) REFERENCES circles(mls_group_id)
+);
+
+CREATE TABLE contacts (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    pubkey TEXT NOT NULL UNIQUE,
+    display_name TEXT,
+    avatar_path TEXT,
+    notes TEXT,
+    created_at INTEGER NOT NULL,
+    updated_at INTEGER NOT NULL
+);
+
+CREATE TABLE circle_ui_state (
+    mls_group_id BLOB PRIMARY KEY,
+    last_read_message_id TEXT,
+    pin_order INTEGER,
+    is_muted INTEGER NOT NULL DEFAULT 0
+);
+
+CREATE TABLE last_known_locations (
+    nostr_group_id  BLOB NOT NULL,
+    sender_pubkey   TEXT NOT NULL,
+    latitude        REAL NOT NULL,
+    longitude       REAL NOT NULL,
+    geohash         TEXT NOT NULL,
+    precision_label TEXT NOT NULL DEFAULT '',
+    display_name    TEXT,
+    timestamp       INTEGER NOT NULL,
+    expires_at      INTEGER NOT NULL,
+    purge_after     INTEGER NOT NULL,
+    updated_at      INTEGER NOT NULL,
+    PRIMARY KEY (nostr_group_id, sender_pubkey)
+);
+CREATE INDEX idx_lkl_purge_after
+    ON last_known_locations(purge_after);
+CREATE INDEX idx_lkl_group
+    ON last_known_locations(nostr_group_id);
+
+CREATE TABLE processed_gift_wraps (
+    wrapper_event_id BLOB PRIMARY KEY,
+    mls_group_id     BLOB NOT NULL,
+    processed_at     INTEGER NOT NULL
+);
+
+CREATE TABLE user_relays (
+    id          INTEGER PRIMARY KEY AUTOINCREMENT,
+    url         TEXT NOT NULL,
+    relay_type  TEXT NOT NULL,
+    created_at  INTEGER NOT NULL,
+    UNIQUE (url, relay_type)
+);
+CREATE INDEX idx_user_relays_type
+    ON user_relays(relay_type);
+
+CREATE TABLE user_settings (
+    key   TEXT PRIMARY KEY,
+    value TEXT NOT NULL
+);
+
+CREATE TABLE published_events (
+    kind          INTEGER NOT NULL,
+    d_tag         TEXT NOT NULL DEFAULT '',
+    event_id      BLOB NOT NULL,
+    pubkey        BLOB NOT NULL,
+    published_at  INTEGER NOT NULL,
+    PRIMARY KEY (kind, d_tag, pubkey)
+);
+
+CREATE TABLE sync_cursors (
+    stream         TEXT PRIMARY KEY,
+    last_synced_ms INTEGER NOT NULL
+);
+
+CREATE TABLE published_key_packages (
+    id           INTEGER PRIMARY KEY AUTOINCREMENT,
+    event_id     TEXT NOT NULL,
+    d_tag        TEXT NOT NULL,
+    key_package  BLOB NOT NULL,
+    created_at   INTEGER NOT NULL,
+    UNIQUE (event_id)
+);
+CREATE INDEX idx_published_key_packages_slot
+    ON published_key_packages(d_tag, created_at DESC);
+
+CREATE TABLE profiles (
+    pubkey            TEXT PRIMARY KEY,
+    metadata_json     TEXT NOT NULL DEFAULT '{}',
+    state             INTEGER NOT NULL DEFAULT 0,
+    event_created_at  INTEGER NOT NULL DEFAULT 0,
+    fetched_at        INTEGER NOT NULL
+);
+CREATE INDEX idx_profiles_fetched
+    ON profiles(fetched_at);
+
+CREATE TABLE profile_pictures (
+    pubkey     TEXT PRIMARY KEY,
+    url        TEXT NOT NULL,
+    sha256     BLOB NOT NULL,
+    canonical  BLOB NOT NULL,
+    thumbnail  BLOB NOT NULL,
+    updated_at INTEGER NOT NULL
+);
+
+INSERT INTO user_settings (key, value) VALUES
+    ('avatar_path_migrated_v1', '1'),
+    ('profile_migration_v1', '1'),
+    ('dm_published_key_packages_reset_v1', '1');
+
+INSERT INTO circles (mls_group_id, nostr_group_id, display_name, created_at, updated_at)
+VALUES (X'0101010101010101010101010101010101010101010101010101010101010101',
+        X'0202020202020202020202020202020202020202020202020202020202020202',
+        'Family', 1000, 1000);
+
+INSERT INTO circle_memberships (mls_group_id, status, invited_at, responded_at)
+VALUES (X'0101010101010101010101010101010101010101010101010101010101010101',
+        'accepted', 1000, 1100);
+
+INSERT INTO contacts (pubkey, display_name, notes, created_at, updated_at)
+VALUES ('aaaa', 'Alice', 'neighbour', 1000, 1200);
+
+INSERT INTO published_key_packages (event_id, d_tag, key_package, created_at)
+VALUES ('kp1', 'slot', X'00', 1000);
@@ -0,0 +1,260 @@
+//! Minimal per-circle snapshot for watch complications and home-screen widgets.
+//!
+//! Those surfaces keep their data in their own, less protected storage (a
+//! widget extension's shared container, a paired watch). The snapshot built
+//! here is therefore deliberately lossy: per member it carries two initials, a
+//! freshness bucket and a coarse distance bucket from the local user, and
+//! never coordinates, geohashes, pubkeys or exact timestamps.
+
+use std::collections::HashMap;
+
+use super::types::LastKnownLocation;
+use crate::location::haversine_distance_m;
+
+/// Longest circle name carried in a snapshot, in characters.
+pub const MAX_GLANCE_NAME_CHARS: usize = 24;
+
+/// Locations older than this (but past their freshness window) are
+/// [`FreshnessBucket::Recent`]; anything older is [`FreshnessBucket::Stale`].
+pub const GLANCE_RECENT_SECS: i64 = 60 * 60;
+
+/// How current a member's last-known location is.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FreshnessBucket {
+    /// Within the sender's freshness window.
+    Live,
+    /// Expired, but captured within [`GLANCE_RECENT_SECS`].
+    Recent,
+    /// Older than that, but still cached.
+    Stale,
+    /// No location cached.
+    Unknown,
+}
+
+/// How far a member is from the local user, coarsely.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DistanceBucket {
+    /// Under 1 km.
+    Nearby,
+    /// 1–10 km.
+    Local,
+    /// 10–100 km.
+    Regional,
+    /// 100 km or more.
+    Far,
+    /// No own position, or no location for the member.
+    Unknown,
+}
+
+impl DistanceBucket {
+    /// Buckets a distance in meters.
+    #[must_use]
+    pub fn from_meters(meters: f64) -> Self {
+        if meters < 1_000.0 {
+            Self::Nearby
+        } else if meters < 10_000.0 {
+            Self::Local
+        } else if meters < 100_000.0 {
+            Self::Regional
+        } else {
+            Self::Far
+        }
+    }
+}
+
+/// One member in a [`GlanceCircle`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GlanceMember {
+    /// Up to two uppercase initials, or `?` without a name.
+    pub initials: String,
+    /// How current the member's location is.
+    pub freshness: FreshnessBucket,
+    /// How far the member is from the local user.
+    pub distance: DistanceBucket,
+}
+
+/// One circle in a [`GlanceableSnapshot`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GlanceCircle {
+    /// The circle's local name, cut to [`MAX_GLANCE_NAME_CHARS`].
+    pub name: String,
+    /// Every member except the local user, in roster order.
+    pub members: Vec<GlanceMember>,
+}
+
+/// Everything a widget or complication needs, and nothing more.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GlanceableSnapshot {
+    /// When the snapshot was assembled (Unix seconds).
+    pub generated_at: i64,
+    /// Visible circles, in the app's display order.
+    pub circles: Vec<GlanceCircle>,
+}
+
+/// Up to two uppercase initials from `name` (first letter of the first and
+/// last word), or `?` if it has none.
+#[must_use]
+pub fn initials(name: Option<&str>) -> String {
+    let mut words = name
+        .unwrap_or_default()
+        .split_whitespace()
+        .filter_map(|w| w.chars().find(|c| c.is_alphanumeric()));
+    let Some(first) = words.next() else {
+        return "?".to_string();
+    };
+    let mut out: String = first.to_uppercase().collect();
+    if let Some(last) = words.last() {
+        out.extend(last.to_uppercase());
+    }
+    out
+}
+
+/// Assembles one circle's entry.
+///
+/// `members` are `(pubkey, display name)` pairs, excluding the local user. A
+/// member without a display name falls back to the name carried in their
+/// newest cached location. When a member has several rows, the newest wins.
+#[must_use]
+pub fn build_glance_circle(
+    name: &str,
+    members: &[(String, Option<String>)],
+    locations: &[LastKnownLocation],
+    own_position: Option<(f64, f64)>,
+    now_unix_secs: i64,
+) -> GlanceCircle {
+    let mut latest: HashMap<&str, &LastKnownLocation> = HashMap::new();
+    for loc in locations {
+        let newer = latest
+            .get(loc.sender_pubkey.as_str())
+            .is_none_or(|existing| loc.timestamp > existing.timestamp);
+        if newer {
+            latest.insert(loc.sender_pubkey.as_str(), loc);
+        }
+    }
+
+    let members = members
+        .iter()
+        .map(|(pubkey, display_name)| {
+            let loc = latest.get(pubkey.as_str()).copied();
+            let name = display_name
+                .as_deref()
+                .or_else(|| loc.and_then(|l| l.display_name.as_deref()));
+            let freshness = match loc {
+                None => FreshnessBucket::Unknown,
+                Some(l) if now_unix_secs <= l.expires_at => FreshnessBucket::Live,
+                Some(l) if now_unix_secs.saturating_sub(l.timestamp) < GLANCE_RECENT_SECS => {
+                    FreshnessBucket::Recent
+                }
+                Some(_) => FreshnessBucket::Stale,
+            };
+            let distance =
+                match (own_position, loc) {
+                    (Some((lat, lon)), Some(l)) => DistanceBucket::from_meters(
+                        haversine_distance_m(lat, lon, l.latitude, l.longitude),
+                    ),
+                    _ => DistanceBucket::Unknown,
+                };
+            GlanceMember {
+                initials: initials(name),
+                freshness,
+                distance,
+            }
+        })
+        .collect();
+
+    GlanceCircle {
+        name: name.chars().take(MAX_GLANCE_NAME_CHARS).collect(),
+        members,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const NOW: i64 = 1_000_000;
+
+    fn loc(sender: &str, lat: f64, lon: f64, timestamp: i64) -> LastKnownLocation {
+        LastKnownLocation {
+            nostr_group_id: [1; 32],
+            sender_pubkey: sender.to_string(),
+            latitude: lat,
+            longitude: lon,
+            geohash: String::new(),
+            display_name: Some("Carol Danvers".to_string()),
+            timestamp,
+            expires_at: timestamp + 900,
+            purge_after: timestamp + 86_400,
+            updated_at: timestamp,
+        }
+    }
+
+    #[test]
+    fn initials_from_names() {
+        assert_eq!(initials(Some("alice")), "A");
+        assert_eq!(initials(Some("  Bob  van der Berg ")), "BB");
+        assert_eq!(initials(Some("  ")), "?");
+        assert_eq!(initials(None), "?");
+    }
+
+    #[test]
+    fn distance_buckets() {
+        assert_eq!(DistanceBucket::from_meters(10.0), DistanceBucket::Nearby);
+        assert_eq!(DistanceBucket::from_meters(5_000.0), DistanceBucket::Local);
+        assert_eq!(
+            DistanceBucket::from_meters(50_000.0),
+            DistanceBucket::Regional
+        );
+        assert_eq!(DistanceBucket::from_meters(1e6), DistanceBucket::Far);
+    }
+
+    #[test]
+    fn buckets_each_member() {
+        let members = vec![
+            ("a".to_string(), Some("Alice Smith".to_string())),
+            ("b".to_string(), None),
+            ("c".to_string(), Some("Carl".to_string())),
+            ("d".to_string(), None),
+        ];
+        let locs = vec![
+            loc("a", 37.7749, -122.4194, NOW),
+            loc("b", 37.8044, -122.2712, NOW - 1_800),
+            loc("c", 40.0, -100.0, NOW - 10_000),
+            loc("stranger", 37.7749, -122.4194, NOW),
+        ];
+        let circle = build_glance_circle(
+            "A very long family circle name indeed",
+            &members,
+            &locs,
+            Some((37.7749, -122.4194)),
+            NOW,
+        );
+
+        assert_eq!(circle.name.chars().count(), MAX_GLANCE_NAME_CHARS);
+        assert_eq!(circle.members.len(), 4);
+        let a = &circle.members[0];
+        assert_eq!(a.initials, "AS");
+        assert_eq!(a.freshness, FreshnessBucket::Live);
+        assert_eq!(a.distance, DistanceBucket::Nearby);
+        let b = &circle.members[1];
+        assert_eq!(b.initials, "CD");
+        assert_eq!(b.freshness, FreshnessBucket::Recent);
+        assert_eq!(b.distance, DistanceBucket::Regional);
+        let c = &circle.members[2];
+        assert_eq!(c.freshness, FreshnessBucket::Stale);
+        assert_eq!(c.distance, DistanceBucket::Far);
+        let d = &circle.members[3];
+        assert_eq!(d.initials, "?");
+        assert_eq!(d.freshness, FreshnessBucket::Unknown);
+        assert_eq!(d.distance, DistanceBucket::Unknown);
+    }
+
+    #[test]
+    fn no_own_position_means_unknown_distance() {
+        let members = vec![("a".to_string(), None)];
+        let locs = vec![loc("a", 37.7749, -122.4194, NOW)];
+        let circle = build_glance_circle("Family", &members, &locs, None, NOW);
+        assert_eq!(circle.members[0].distance, DistanceBucket::Unknown);
+        assert_eq!(circle.members[0].freshness, FreshnessBucket::Live);
+    }
+}
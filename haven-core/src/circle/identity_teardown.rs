@@ -0,0 +1,108 @@
+//! Guarded identity deletion.
+//!
+//! Deleting the Nostr identity used to remove only the key, stranding every
+//! circle it belonged to: the MLS leaf stays in each group (peers keep
+//! encrypting to it) and the local circle rows point at state no key can ever
+//! drive again. The guarded flow runs in three steps:
+//!
+//! 1. [`CircleManager::identity_deletion_report`] lists the circles that still
+//!    depend on the identity, with the leave route each one would take.
+//! 2. [`CircleManager::prepare_identity_deletion`] refuses to proceed while
+//!    any remain unless `force` is set, optionally issues a `SelfRemove` for
+//!    every circle it can leave, then wipes the identity-scoped rows of
+//!    `circles.db` in one pass.
+//! 3. The caller publishes the returned leave events (best effort), then
+//!    deletes the key and the MLS session database.
+//!
+//! # Admin circles
+//!
+//! An admin cannot leave today (see
+//! [`CircleManager::propose_admin_handoff`]), so with `leave_first` such a
+//! circle is reported in [`IdentityTeardown::not_left`] rather than failing
+//! the whole deletion. Peers will see the leaf go silent.
+//!
+//! [`CircleManager::identity_deletion_report`]: super::CircleManager::identity_deletion_report
+//! [`CircleManager::prepare_identity_deletion`]: super::CircleManager::prepare_identity_deletion
+//! [`CircleManager::propose_admin_handoff`]: super::CircleManager::propose_admin_handoff
+
+use nostr::Event;
+
+use super::leave::LeavePlan;
+use super::lifecycle::CircleLifecycle;
+use crate::nostr::mls::types::GroupId;
+
+/// A circle that still depends on the identity.
+pub struct DependentCircle {
+    /// The circle's MLS group id.
+    pub mls_group_id: GroupId,
+    /// The circle's local display name.
+    pub name: String,
+    /// Where the membership is in its lifecycle.
+    pub lifecycle: CircleLifecycle,
+    /// How the identity would leave it.
+    pub plan: LeavePlan,
+}
+
+impl std::fmt::Debug for DependentCircle {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("DependentCircle")
+            .field("mls_group_id", &"<redacted>")
+            .field("lifecycle", &self.lifecycle)
+            .field("plan", &self.plan)
+            .finish_non_exhaustive()
+    }
+}
+
+/// What deleting the identity would orphan.
+#[derive(Debug, Default)]
+pub struct IdentityDeletionReport {
+    /// Circles the identity is a member of.
+    pub circles: Vec<DependentCircle>,
+    /// Invitations received but not yet answered (discarded on deletion).
+    pub pending_invitations: usize,
+}
+
+impl IdentityDeletionReport {
+    /// Returns `true` if deletion needs `force`.
+    #[must_use]
+    pub fn has_dependents(&self) -> bool {
+        !self.circles.is_empty()
+    }
+}
+
+/// A `SelfRemove` proposal to publish before the identity is gone.
+pub struct LeaveEvent {
+    /// The circle being left.
+    pub mls_group_id: GroupId,
+    /// The signed kind-445 proposal.
+    pub event: Event,
+    /// The circle's relays, to publish to.
+    pub relays: Vec<String>,
+}
+
+impl std::fmt::Debug for LeaveEvent {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("LeaveEvent")
+            .field("mls_group_id", &"<redacted>")
+            .field("event_id", &self.event.id)
+            .field("relays", &self.relays.len())
+            .finish()
+    }
+}
+
+/// The outcome of [`CircleManager::prepare_identity_deletion`].
+///
+/// [`CircleManager::prepare_identity_deletion`]: super::CircleManager::prepare_identity_deletion
+#[derive(Debug, Default)]
+pub struct IdentityTeardown {
+    /// Leave proposals to publish (only with `leave_first`).
+    pub leave_events: Vec<LeaveEvent>,
+    /// Circles dropped locally with nothing to publish (sole member, or
+    /// unknown to the engine).
+    pub abandoned: Vec<GroupId>,
+    /// Circles that could not be left (admin, or the engine refused); their
+    /// local rows are wiped regardless.
+    pub not_left: Vec<GroupId>,
+    /// Circle rows removed from local storage.
+    pub circles_wiped: usize,
+}
@@ -0,0 +1,300 @@
+//! Out-of-band invitation payloads (links and QR codes).
+//!
+//! To be invited, a person hands the inviter their public key and the relays
+//! their `KeyPackage` can be fetched from. [`InvitePayload`] packs that — plus
+//! optionally the circle it is about — into one compact string an app can
+//! render as a QR code or share as a link:
+//!
+//! ```text
+//! haven:haven1qyq...   (bech32m, HRP "haven", behind the "haven:" URI scheme)
+//! ```
+//!
+//! # Format
+//!
+//! The bech32m data is a version byte ([`INVITE_PAYLOAD_VERSION`]) followed by
+//! NIP-19-style TLV records (`type: u8`, `length: u8`, `value`):
+//!
+//! | type | value                                   | count     |
+//! |------|-----------------------------------------|-----------|
+//! | 0    | x-only public key (32 bytes)            | exactly 1 |
+//! | 1    | `KeyPackage` relay URL (UTF-8)          | 0..=[`MAX_INVITE_RELAYS`] |
+//! | 2    | circle's Nostr group id (32 bytes)      | 0 or 1    |
+//!
+//! Unknown record types are skipped, so a later version can add records that
+//! this one ignores; a payload with a newer version byte is rejected.
+//!
+//! Parsing is case-insensitive: an all-uppercase payload fits the denser
+//! alphanumeric QR mode.
+//!
+//! # Privacy
+//!
+//! Everything here is public on relays already (the Nostr group id is the
+//! kind-445 `h` tag), but whoever sees the code learns that this key is
+//! reachable on these relays. No secret, name or MLS group id is carried.
+
+use nostr::bech32::{Bech32m, Hrp};
+use nostr::{PublicKey, RelayUrl};
+
+use super::error::{CircleError, Result};
+use super::manager::short_id;
+
+/// URI scheme prefixed to an encoded payload.
+pub const INVITE_URI_SCHEME: &str = "haven:";
+
+/// Bech32 human-readable part of an encoded payload.
+pub const INVITE_HRP: &str = "haven";
+
+/// The payload version this build writes (and the newest it reads).
+pub const INVITE_PAYLOAD_VERSION: u8 = 1;
+
+/// Most relay hints carried, to keep the QR code scannable.
+pub const MAX_INVITE_RELAYS: usize = 3;
+
+const TLV_PUBKEY: u8 = 0;
+const TLV_RELAY: u8 = 1;
+const TLV_CIRCLE: u8 = 2;
+
+/// What an inviter needs to invite someone, decoded from a link or QR code.
+#[derive(Clone, PartialEq, Eq)]
+pub struct InvitePayload {
+    /// The key to invite.
+    pub pubkey: PublicKey,
+    /// Relays the key's `KeyPackage` can be fetched from (`wss://` only).
+    pub relays: Vec<String>,
+    /// The circle the invitation is about, as its Nostr group id.
+    pub circle_id: Option<[u8; 32]>,
+}
+
+impl std::fmt::Debug for InvitePayload {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("InvitePayload")
+            .field("pubkey", &short_id(&self.pubkey.to_bytes()))
+            .field("relays", &self.relays.len())
+            .field("circle_id", &self.circle_id.is_some())
+            .finish()
+    }
+}
+
+impl InvitePayload {
+    /// Builds a payload, validating the relay hints.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`CircleError::InvalidData`] if more than [`MAX_INVITE_RELAYS`]
+    /// relays are given, or one is not a valid `wss://` URL.
+    pub fn new(
+        pubkey: PublicKey,
+        relays: Vec<String>,
+        circle_id: Option<[u8; 32]>,
+    ) -> Result<Self> {
+        if relays.len() > MAX_INVITE_RELAYS {
+            return Err(CircleError::InvalidData(format!(
+                "at most {MAX_INVITE_RELAYS} relay hints fit an invite"
+            )));
+        }
+        for relay in &relays {
+            validate_relay(relay)?;
+        }
+        Ok(Self {
+            pubkey,
+            relays,
+            circle_id,
+        })
+    }
+
+    /// Encodes the payload as a `haven:` URI.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`CircleError::InvalidData`] if the payload does not fit a
+    /// bech32 string.
+    pub fn to_uri(&self) -> Result<String> {
+        let mut data = vec![INVITE_PAYLOAD_VERSION];
+        push_tlv(&mut data, TLV_PUBKEY, &self.pubkey.to_bytes())?;
+        for relay in &self.relays {
+            push_tlv(&mut data, TLV_RELAY, relay.as_bytes())?;
+        }
+        if let Some(circle_id) = &self.circle_id {
+            push_tlv(&mut data, TLV_CIRCLE, circle_id)?;
+        }
+        let encoded = nostr::bech32::encode::<Bech32m>(Hrp::parse_unchecked(INVITE_HRP), &data)
+            .map_err(|e| CircleError::InvalidData(format!("invite does not encode: {e}")))?;
+        Ok(format!("{INVITE_URI_SCHEME}{encoded}"))
+    }
+
+    /// Parses a payload, with or without the `haven:` scheme, in either case.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`CircleError::InvalidData`] if the string is not a Haven
+    /// invite, is from a newer version, or carries a malformed record.
+    pub fn parse(input: &str) -> Result<Self> {
+        let trimmed = input.trim();
+        let body = trimmed
+            .get(..INVITE_URI_SCHEME.len())
+            .filter(|scheme| scheme.eq_ignore_ascii_case(INVITE_URI_SCHEME))
+            .map_or(trimmed, |_| &trimmed[INVITE_URI_SCHEME.len()..]);
+        let (hrp, data) = nostr::bech32::decode(body)
+            .map_err(|_| CircleError::InvalidData("not a Haven invite".to_string()))?;
+        if !hrp.as_str().eq_ignore_ascii_case(INVITE_HRP) {
+            return Err(CircleError::InvalidData("not a Haven invite".to_string()));
+        }
+
+        let (&version, mut rest) = data
+            .split_first()
+            .ok_or_else(|| CircleError::InvalidData("empty invite".to_string()))?;
+        if version == 0 || version > INVITE_PAYLOAD_VERSION {
+            return Err(CircleError::InvalidData(format!(
+                "unsupported invite version {version}"
+            )));
+        }
+
+        let mut pubkey = None;
+        let mut relays = Vec::new();
+        let mut circle_id = None;
+        while let [kind, len, tail @ ..] = rest {
+            let len = usize::from(*len);
+            if tail.len() < len {
+                return Err(CircleError::InvalidData(
+                    "truncated invite record".to_string(),
+                ));
+            }
+            let (value, next) = tail.split_at(len);
+            match *kind {
+                TLV_PUBKEY => {
+                    let key = PublicKey::from_slice(value)
+                        .map_err(|_| CircleError::InvalidData("invalid invite key".to_string()))?;
+                    if pubkey.replace(key).is_some() {
+                        return Err(CircleError::InvalidData(
+                            "invite names two keys".to_string(),
+                        ));
+                    }
+                }
+                TLV_RELAY if relays.len() < MAX_INVITE_RELAYS => {
+                    let relay = std::str::from_utf8(value)
+                        .map_err(|_| CircleError::InvalidData("invalid relay hint".to_string()))?;
+                    validate_relay(relay)?;
+                    relays.push(relay.to_string());
+                }
+                TLV_CIRCLE => {
+                    let id: [u8; 32] = value
+                        .try_into()
+                        .map_err(|_| CircleError::InvalidData("invalid circle id".to_string()))?;
+                    circle_id = Some(id);
+                }
+                // Extra relay hints and record types from newer versions.
+                _ => {}
+            }
+            rest = next;
+        }
+        if !rest.is_empty() {
+            return Err(CircleError::InvalidData(
+                "truncated invite record".to_string(),
+            ));
+        }
+
+        let pubkey =
+            pubkey.ok_or_else(|| CircleError::InvalidData("invite names no key".to_string()))?;
+        Ok(Self {
+            pubkey,
+            relays,
+            circle_id,
+        })
+    }
+}
+
+fn push_tlv(data: &mut Vec<u8>, kind: u8, value: &[u8]) -> Result<()> {
+    let len = u8::try_from(value.len())
+        .map_err(|_| CircleError::InvalidData("invite record too long".to_string()))?;
+    data.push(kind);
+    data.push(len);
+    data.extend_from_slice(value);
+    Ok(())
+}
+
+fn validate_relay(relay: &str) -> Result<()> {
+    let valid = relay.starts_with("wss://") && RelayUrl::parse(relay).is_ok();
+    if valid {
+        Ok(())
+    } else {
+        Err(CircleError::InvalidData(
+            "relay hints must be wss:// URLs".to_string(),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample() -> InvitePayload {
+        InvitePayload::new(
+            nostr::Keys::generate().public_key(),
+            vec![
+                "wss://relay.example.com".to_string(),
+                "wss://kp.example.org".to_string(),
+            ],
+            Some([7u8; 32]),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn roundtrips_with_and_without_scheme() {
+        let payload = sample();
+        let uri = payload.to_uri().unwrap();
+        assert!(uri.starts_with("haven:haven1"));
+        assert_eq!(InvitePayload::parse(&uri).unwrap(), payload);
+        assert_eq!(
+            InvitePayload::parse(&uri[INVITE_URI_SCHEME.len()..]).unwrap(),
+            payload
+        );
+        assert_eq!(InvitePayload::parse(&uri.to_uppercase()).unwrap(), payload);
+
+        let minimal = InvitePayload::new(payload.pubkey, Vec::new(), None).unwrap();
+        assert_eq!(
+            InvitePayload::parse(&minimal.to_uri().unwrap()).unwrap(),
+            minimal
+        );
+    }
+
+    #[test]
+    fn rejects_bad_relays() {
+        let pk = nostr::Keys::generate().public_key();
+        assert!(InvitePayload::new(pk, vec!["ws://plain.example".to_string()], None).is_err());
+        let many = vec!["wss://a.example".to_string(); MAX_INVITE_RELAYS + 1];
+        assert!(InvitePayload::new(pk, many, None).is_err());
+    }
+
+    fn encode_raw(data: &[u8]) -> String {
+        nostr::bech32::encode::<Bech32m>(Hrp::parse_unchecked(INVITE_HRP), data).unwrap()
+    }
+
+    #[test]
+    fn skips_unknown_records_and_rejects_newer_versions() {
+        let pk = nostr::Keys::generate().public_key();
+        let mut data = vec![INVITE_PAYLOAD_VERSION];
+        push_tlv(&mut data, 9, b"future").unwrap();
+        push_tlv(&mut data, TLV_PUBKEY, &pk.to_bytes()).unwrap();
+        assert_eq!(InvitePayload::parse(&encode_raw(&data)).unwrap().pubkey, pk);
+
+        data[0] = INVITE_PAYLOAD_VERSION + 1;
+        assert!(InvitePayload::parse(&encode_raw(&data)).is_err());
+    }
+
+    #[test]
+    fn rejects_garbage() {
+        assert!(InvitePayload::parse("").is_err());
+        assert!(InvitePayload::parse("haven:not-bech32").is_err());
+        let npub = nostr::Keys::generate().public_key();
+        let npub = nostr::nips::nip19::ToBech32::to_bech32(&npub).unwrap();
+        assert!(InvitePayload::parse(&npub).is_err());
+        // Version byte but no key.
+        assert!(InvitePayload::parse(&encode_raw(&[INVITE_PAYLOAD_VERSION])).is_err());
+        // Truncated record.
+        assert!(
+            InvitePayload::parse(&encode_raw(&[INVITE_PAYLOAD_VERSION, TLV_PUBKEY, 32, 1]))
+                .is_err()
+        );
+    }
+}
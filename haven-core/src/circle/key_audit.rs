@@ -0,0 +1,162 @@
+//! Key-transparency style audit of circle member credentials.
+//!
+//! Every MLS leaf carries a signature key. A member's Nostr identity stays the
+//! same across circles, but someone who has taken over (or is impersonating)
+//! an account and gets re-added shows up with a *different* signature key.
+//! The audit remembers the signature key last seen for each member pubkey and
+//! records a [`MemberKeyChange`] whenever a different one appears, for the user
+//! to review and acknowledge.
+//!
+//! # What is observed
+//!
+//! The signature key is read from the `KeyPackage` (kind 30443) of every
+//! member added to a circle on this device ([`leaf_signature_key`]). Members
+//! who joined through someone else's invite are observed only once this device
+//! adds them somewhere: the Dark Matter v0.9.4 public API does not expose the
+//! leaf nodes of an existing group (GAP, plan §5.2 #18).
+//!
+//! A legitimate key rotation also records a change; the audit cannot tell the
+//! two apart, which is why changes are acknowledged rather than blocked.
+//!
+//! The audit is opt-in ([`KEY_AUDIT_ENABLED_KEY`], default off). Storage lives
+//! in `circle/storage_key_audit.rs`.
+
+use base64::engine::general_purpose::STANDARD as BASE64;
+use base64::Engine as _;
+use nostr::Event;
+use sha2::{Digest, Sha256};
+
+use crate::messages::{MessageCode, UserMessage};
+use crate::nostr::mls::types::GroupId;
+
+/// `user_settings` key for the key audit opt-in (default off).
+pub const KEY_AUDIT_ENABLED_KEY: &str = "member_key_audit_enabled";
+
+/// A member whose signature key differs from the one previously recorded.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MemberKeyChange {
+    /// Row id, used to acknowledge the change.
+    pub id: i64,
+    /// Hex pubkey of the member.
+    pub member_pubkey: String,
+    /// Circle the new key was observed in, if any.
+    pub mls_group_id: Option<GroupId>,
+    /// Fingerprint of the previously recorded key.
+    pub previous_fingerprint: String,
+    /// Fingerprint of the newly observed key.
+    pub observed_fingerprint: String,
+    /// Unix timestamp of the observation.
+    pub observed_at: i64,
+    /// Unix timestamp the user acknowledged the change, if they have.
+    pub acknowledged_at: Option<i64>,
+}
+
+impl MemberKeyChange {
+    /// The notification to show for this change.
+    #[must_use]
+    pub fn notification(&self) -> UserMessage {
+        UserMessage::new(MessageCode::NotificationMemberKeyChanged)
+            .with("member_pubkey", self.member_pubkey.clone())
+    }
+}
+
+/// Result of recording one observed key.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum KeyObservation {
+    /// First key seen for this member; it becomes the baseline.
+    FirstSeen,
+    /// Same key as recorded.
+    Unchanged,
+    /// A different key; the change was recorded.
+    Changed(MemberKeyChange),
+}
+
+/// Fingerprint of a signature key: lowercase hex of its SHA-256.
+#[must_use]
+pub fn key_fingerprint(signature_key: &[u8]) -> String {
+    hex::encode(Sha256::digest(signature_key))
+}
+
+/// Reads the leaf signature key out of a TLS-serialized MLS `KeyPackage`
+/// (RFC 9420 §10: version, cipher suite, `init_key`, then the leaf node's
+/// `encryption_key` and `signature_key`).
+///
+/// Returns `None` if the bytes are too short or a length prefix is malformed.
+#[must_use]
+pub fn leaf_signature_key(key_package: &[u8]) -> Option<&[u8]> {
+    let rest = key_package.get(4..)?; // version + cipher_suite
+    let (_init_key, rest) = read_opaque(rest)?;
+    let (_encryption_key, rest) = read_opaque(rest)?;
+    let (signature_key, _) = read_opaque(rest)?;
+    (!signature_key.is_empty()).then_some(signature_key)
+}
+
+/// Fingerprint of the signature key in a `KeyPackage` event, if it parses.
+#[must_use]
+pub fn key_package_fingerprint(event: &Event) -> Option<String> {
+    let bytes = BASE64.decode(event.content.as_bytes()).ok()?;
+    leaf_signature_key(&bytes).map(key_fingerprint)
+}
+
+/// Reads an `opaque<V>` (RFC 9420 §2.1.2 variable-length integer prefix).
+fn read_opaque(bytes: &[u8]) -> Option<(&[u8], &[u8])> {
+    let first = *bytes.first()?;
+    let (prefix_len, mut len) = match first >> 6 {
+        0 => (1, usize::from(first & 0x3f)),
+        1 => (2, usize::from(first & 0x3f)),
+        2 => (4, usize::from(first & 0x3f)),
+        _ => return None,
+    };
+    for byte in bytes.get(1..prefix_len)? {
+        len = (len << 8) | usize::from(*byte);
+    }
+    let body = bytes.get(prefix_len..)?;
+    (body.len() >= len).then(|| body.split_at(len))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn opaque(body: &[u8]) -> Vec<u8> {
+        let mut out = if body.len() < 64 {
+            vec![u8::try_from(body.len()).unwrap()]
+        } else {
+            let len = u16::try_from(body.len()).unwrap() | 0x4000;
+            len.to_be_bytes().to_vec()
+        };
+        out.extend_from_slice(body);
+        out
+    }
+
+    fn key_package(signature_key: &[u8]) -> Vec<u8> {
+        let mut kp = vec![0x00, 0x01, 0x00, 0x01];
+        kp.extend(opaque(&[7; 32]));
+        kp.extend(opaque(&[8; 100]));
+        kp.extend(opaque(signature_key));
+        kp.extend(opaque(b"credential and the rest"));
+        kp
+    }
+
+    #[test]
+    fn reads_the_leaf_signature_key() {
+        let kp = key_package(&[9; 32]);
+        assert_eq!(leaf_signature_key(&kp), Some(&[9u8; 32][..]));
+        assert_eq!(
+            key_fingerprint(&[9; 32]),
+            key_fingerprint(leaf_signature_key(&kp).unwrap())
+        );
+        assert_ne!(key_fingerprint(&[9; 32]), key_fingerprint(&[10; 32]));
+    }
+
+    #[test]
+    fn malformed_key_packages_yield_none() {
+        let kp = key_package(&[9; 32]);
+        assert_eq!(leaf_signature_key(&kp[..3]), None);
+        assert_eq!(leaf_signature_key(&kp[..60]), None, "truncated body");
+        let mut bad_prefix = kp.clone();
+        bad_prefix[4] = 0xc0;
+        assert_eq!(leaf_signature_key(&bad_prefix), None);
+        assert_eq!(leaf_signature_key(&key_package(&[])), None);
+    }
+}
@@ -25,6 +25,7 @@ use std::collections::BTreeSet;
 use nostr::PublicKey;
 
 use super::error::{CircleError, Result};
+use super::identity_teardown::LeaveEvent;
 use super::manager::short_id;
 use crate::nostr::mls::types::GroupId;
 use crate::nostr::mls::SessionManager;
@@ -67,6 +68,34 @@ impl std::fmt::Debug for LeavePlan {
     }
 }
 
+/// Outbox note recorded on leave proposals queued by
+/// [`CircleManager::leave_all_circles`].
+///
+/// [`CircleManager::leave_all_circles`]: super::CircleManager::leave_all_circles
+pub(crate) const LEAVE_ALL_QUEUE_NOTE: &str = "queued by leave-all";
+
+/// Outbox note recorded on leave proposals queued by
+/// [`CircleManager::leave_and_archive_circle`].
+///
+/// [`CircleManager::leave_and_archive_circle`]: super::CircleManager::leave_and_archive_circle
+pub(crate) const ARCHIVE_LEAVE_QUEUE_NOTE: &str = "queued by leave-and-archive";
+
+/// The outcome of [`CircleManager::leave_all_circles`].
+///
+/// [`CircleManager::leave_all_circles`]: super::CircleManager::leave_all_circles
+#[derive(Debug, Default)]
+pub struct LeaveAllReport {
+    /// Circles left, with the `SelfRemove` proposal to publish.
+    pub left: Vec<LeaveEvent>,
+    /// Circles dropped locally with nothing to publish (sole member, or
+    /// unknown to the engine).
+    pub abandoned: Vec<GroupId>,
+    /// Circles that could not be left (admin, or the engine refused); kept.
+    pub not_left: Vec<GroupId>,
+    /// Leave proposals newly queued in the offline outbox.
+    pub queued: usize,
+}
+
 /// Chooses the [`LeavePlan`] for `self_pubkey` leaving `group_id`.
 ///
 /// Maps the engine's `SelfRemove` admin gating (`AdminCannotSelfRemove` /
@@ -0,0 +1,236 @@
+//! Circle lifecycle state machine.
+//!
+//! Every circle the local user knows about is in exactly one
+//! [`CircleLifecycle`] state. State changes go through
+//! [`CircleLifecycle::transition`], which rejects anything not in the table
+//! below with [`CircleError::IllegalTransition`] — so a half-applied flow
+//! (e.g. sending into a circle that was already left, or archiving a declined
+//! invitation) fails loudly instead of leaving storage in a mixed state.
+//!
+//! ```text
+//! Invited ──accept──▶ Pending ──joined──▶ Active ◀──unarchive── Archived
+//!    │                   │                  │ │ └──archive──────▶   │
+//!    └──decline──▶ Declined ◀──decline──────┘ │                     │
+//!                        ▲                    ├──leave──▶ Left ◀────┤
+//!                 (join failed: Pending ──▶ Invited)               │
+//!                                             └──removed─▶ Removed ◀┘
+//! ```
+//!
+//! `Left`, `Removed` and `Declined` are terminal.
+//!
+//! # Storage
+//!
+//! The state lives in `circle_memberships.lifecycle`. The older `status`
+//! column ([`MembershipStatus`]) is kept in sync by
+//! [`CircleLifecycle::membership_status`] so existing readers keep working; a
+//! row written before the column existed derives its state from `status`
+//! ([`CircleLifecycle::from_status`]).
+
+use super::error::{CircleError, Result};
+use super::types::MembershipStatus;
+
+/// Where a circle is in its lifecycle, from the local user's point of view.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CircleLifecycle {
+    /// A welcome was received and is held; the user has not responded.
+    Invited,
+    /// The user accepted; the join has not completed yet.
+    Pending,
+    /// The user is a member and the circle is in use.
+    Active,
+    /// The user is still a member but has hidden the circle.
+    Archived,
+    /// The user left the circle.
+    Left,
+    /// An admin removed the user from the circle.
+    Removed,
+    /// The user declined the invitation.
+    Declined,
+}
+
+impl CircleLifecycle {
+    /// Converts to string representation for storage.
+    #[must_use]
+    pub const fn as_str(&self) -> &'static str {
+        match self {
+            Self::Invited => "invited",
+            Self::Pending => "pending",
+            Self::Active => "active",
+            Self::Archived => "archived",
+            Self::Left => "left",
+            Self::Removed => "removed",
+            Self::Declined => "declined",
+        }
+    }
+
+    /// Parses from string representation.
+    #[must_use]
+    pub fn parse(s: &str) -> Option<Self> {
+        match s {
+            "invited" => Some(Self::Invited),
+            "pending" => Some(Self::Pending),
+            "active" => Some(Self::Active),
+            "archived" => Some(Self::Archived),
+            "left" => Some(Self::Left),
+            "removed" => Some(Self::Removed),
+            "declined" => Some(Self::Declined),
+            _ => None,
+        }
+    }
+
+    /// Derives the state of a membership row that predates the lifecycle
+    /// column.
+    #[must_use]
+    pub const fn from_status(status: MembershipStatus) -> Self {
+        match status {
+            MembershipStatus::Pending => Self::Invited,
+            MembershipStatus::Accepted => Self::Active,
+            MembershipStatus::Declined => Self::Declined,
+        }
+    }
+
+    /// The legacy [`MembershipStatus`] written alongside this state.
+    ///
+    /// Only [`Self::Active`] and [`Self::Archived`] map to `Accepted`; every
+    /// state that is no longer a membership maps to `Declined` so it never
+    /// shows up as a visible circle.
+    #[must_use]
+    pub const fn membership_status(self) -> MembershipStatus {
+        match self {
+            Self::Invited | Self::Pending => MembershipStatus::Pending,
+            Self::Active | Self::Archived => MembershipStatus::Accepted,
+            Self::Left | Self::Removed | Self::Declined => MembershipStatus::Declined,
+        }
+    }
+
+    /// Returns `true` for states with no outgoing transitions.
+    #[must_use]
+    pub const fn is_terminal(self) -> bool {
+        matches!(self, Self::Left | Self::Removed | Self::Declined)
+    }
+
+    /// Returns `true` while the user is a member of the MLS group.
+    #[must_use]
+    pub const fn is_member(self) -> bool {
+        matches!(self, Self::Active | Self::Archived)
+    }
+
+    /// Returns `true` if moving from `self` to `to` is allowed.
+    #[must_use]
+    pub const fn can_transition_to(self, to: Self) -> bool {
+        matches!(
+            (self, to),
+            (Self::Invited, Self::Pending | Self::Declined)
+                | (Self::Pending, Self::Active | Self::Invited | Self::Declined)
+                | (
+                    Self::Active,
+                    Self::Archived | Self::Left | Self::Removed | Self::Declined
+                )
+                | (Self::Archived, Self::Active | Self::Left | Self::Removed)
+        )
+    }
+
+    /// Moves from `self` to `to`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`CircleError::IllegalTransition`] if the move is not allowed.
+    pub const fn transition(self, to: Self) -> Result<Self> {
+        if self.can_transition_to(to) {
+            Ok(to)
+        } else {
+            Err(CircleError::IllegalTransition { from: self, to })
+        }
+    }
+}
+
+impl std::fmt::Display for CircleLifecycle {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ALL: [CircleLifecycle; 7] = [
+        CircleLifecycle::Invited,
+        CircleLifecycle::Pending,
+        CircleLifecycle::Active,
+        CircleLifecycle::Archived,
+        CircleLifecycle::Left,
+        CircleLifecycle::Removed,
+        CircleLifecycle::Declined,
+    ];
+
+    #[test]
+    fn string_round_trip() {
+        for state in ALL {
+            assert_eq!(CircleLifecycle::parse(state.as_str()), Some(state));
+        }
+        assert_eq!(CircleLifecycle::parse("accepted"), None);
+    }
+
+    #[test]
+    fn happy_path_is_allowed() {
+        let s = CircleLifecycle::Invited
+            .transition(CircleLifecycle::Pending)
+            .and_then(|s| s.transition(CircleLifecycle::Active))
+            .and_then(|s| s.transition(CircleLifecycle::Archived))
+            .and_then(|s| s.transition(CircleLifecycle::Active))
+            .and_then(|s| s.transition(CircleLifecycle::Left))
+            .unwrap();
+        assert_eq!(s, CircleLifecycle::Left);
+    }
+
+    #[test]
+    fn terminal_states_have_no_exits() {
+        for from in ALL.into_iter().filter(|s| s.is_terminal()) {
+            for to in ALL {
+                assert!(!from.can_transition_to(to), "{from} -> {to}");
+            }
+        }
+    }
+
+    #[test]
+    fn illegal_transition_is_typed() {
+        let err = CircleLifecycle::Invited
+            .transition(CircleLifecycle::Active)
+            .unwrap_err();
+        assert!(matches!(
+            err,
+            CircleError::IllegalTransition {
+                from: CircleLifecycle::Invited,
+                to: CircleLifecycle::Active,
+            }
+        ));
+        assert_eq!(
+            err.to_string(),
+            "Illegal circle transition: invited -> active"
+        );
+    }
+
+    #[test]
+    fn no_self_transitions() {
+        for state in ALL {
+            assert!(!state.can_transition_to(state), "{state}");
+        }
+    }
+
+    #[test]
+    fn legacy_status_round_trips_through_lifecycle() {
+        for status in [
+            MembershipStatus::Pending,
+            MembershipStatus::Accepted,
+            MembershipStatus::Declined,
+        ] {
+            assert_eq!(
+                CircleLifecycle::from_status(status).membership_status(),
+                status
+            );
+        }
+        assert!(!CircleLifecycle::Left.membership_status().is_visible());
+        assert!(!CircleLifecycle::Removed.membership_status().is_visible());
+    }
+}
@@ -28,27 +28,83 @@
 //! - [`CircleMember`]: A member with resolved contact info
 //! - [`Invitation`]: A pending invitation to join a circle
 
+pub mod audience;
+pub mod cold_storage;
 mod error;
+pub mod glance;
+pub mod identity_teardown;
+pub mod invite_link;
+pub mod key_audit;
 mod leave;
+pub mod lifecycle;
 mod manager;
+pub mod read_only;
 pub mod relay_prefs;
+pub mod safety_number;
+pub mod status;
 mod storage;
+mod storage_breadcrumbs;
+mod storage_checkin_rules;
+mod storage_circle_settings;
+mod storage_cold;
+mod storage_config;
+mod storage_contact_labels;
+mod storage_group_cursors;
+mod storage_group_health;
+mod storage_invites;
+mod storage_key_audit;
 mod storage_key_packages;
+mod storage_location_history;
+mod storage_location_pause;
+mod storage_meet_pins;
+mod storage_member_presence;
+mod storage_migrations;
+mod storage_outbox;
+mod storage_precision;
+mod storage_processed_events;
 mod storage_profile;
+mod storage_proximity_rules;
+mod storage_relay_blacklist;
+mod storage_relay_info;
 mod storage_relay_prefs;
+mod storage_relay_stats;
+mod storage_remote_signer;
+mod storage_roster;
 pub mod types;
 
+pub use audience::{AudienceCircle, AudienceMember};
+pub use cold_storage::ColdCircleInfo;
 pub use error::{CircleError, Result};
-pub use leave::LeavePlan;
+pub use glance::{DistanceBucket, FreshnessBucket, GlanceCircle, GlanceMember, GlanceableSnapshot};
+pub use identity_teardown::{
+    DependentCircle, IdentityDeletionReport, IdentityTeardown, LeaveEvent,
+};
+pub use invite_link::InvitePayload;
+pub use key_audit::{KeyObservation, MemberKeyChange};
+pub use leave::{LeaveAllReport, LeavePlan};
+pub use lifecycle::CircleLifecycle;
 pub use manager::{
-    AddMembersResult, CircleCreationResult, CircleManager, CommitToPublish, DecryptedIngest,
+    AddMembersResult, CircleCreationResult, CircleManager, CommitToPublish, ConsumedKeyPackage,
+    DecryptedIngest, InviteResend, LabeledCircleCreation,
 };
+pub use read_only::ReadOnlyCircleStorage;
 pub use relay_prefs::RelayType;
+pub use status::{compute_circle_status, CircleStatus, GeofenceOccupancy};
 pub use storage::CircleStorage;
 pub use storage_key_packages::{PublishedKeyPackageRow, KEY_PACKAGE_KIND};
+pub use storage_location_history::{
+    HISTORY_CELL_LEN, MAX_COVERING_CELLS, MAX_DAY_POINTS, MAX_VIEWPORT_POINTS,
+};
+pub use storage_processed_events::{ProcessedAt, ProcessedOutcome};
 pub use storage_relay_prefs::{PublishedEventRecord, UserRelayRow};
 pub use types::{
-    default_relays, set_default_relays_for_test, Circle, CircleConfig, CircleMember,
-    CircleMembership, CircleType, CircleUiState, CircleWithMembers, Contact, GiftWrappedWelcome,
-    Invitation, LastKnownLocation, MemberKeyPackage, MembershipStatus, PRODUCTION_DEFAULT_RELAYS,
+    default_relays, set_default_relays_for_test, Circle, CircleConfig, CircleLocationSettings,
+    CircleMember, CircleMembership, CircleType, CircleUiState, CircleWithMembers, Contact,
+    GiftWrappedWelcome, GroupHealth, HistoryPoint, Invitation, LastKnownLocation, MemberKeyPackage,
+    MemberLocation, MemberPresence, MembershipStatus, PresenceStatus, RepairOutcome, SentInvite,
+    SharePreview, SharingSession, TripMode, UnjoinedMember, DEFAULT_DISPLAY_MAX_AGE_SECS,
+    INVITE_JOIN_GRACE_SECS, INVITE_RESEND_INTERVAL_SECS, MAX_INVITE_RESENDS,
+    MAX_PRECISE_SESSION_SECS, MAX_TRIP_MODE_SECS, MIN_DISPLAY_MAX_AGE_SECS,
+    PRESENCE_OFFLINE_AFTER_SECS, PRESENCE_STALE_AFTER_SECS, PRODUCTION_DEFAULT_RELAYS,
+    UNPROCESSABLE_REPAIR_THRESHOLD,
 };
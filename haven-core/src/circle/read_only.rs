@@ -0,0 +1,194 @@
+//! Read-only inspection of a live data directory.
+//!
+//! Diagnostic tools, the CLI and backup verifiers need to look at
+//! `circles.db` while the app may be running. [`ReadOnlyCircleStorage`] opens
+//! it with a read-only connection that never creates or migrates the schema
+//! and only exposes queries, so an inspection cannot race the app into a
+//! half-migrated or corrupted file.
+//!
+//! # MLS state
+//!
+//! The MLS session database is deliberately not opened: Rule 14 allows one
+//! live session per `session.sqlite`, and hydrating a session writes to it.
+//! The Dark Matter v0.9.4 public API has no read-only session open (GAP, plan
+//! §5.2 #18), so group state (epochs, members) cannot be inspected this way;
+//! the circle rows mirror what the app last synced from it.
+
+use std::path::Path;
+
+use super::error::{CircleError, Result};
+use super::lifecycle::CircleLifecycle;
+use super::storage::CircleStorage;
+use super::types::{Circle, Contact};
+use crate::nostr::mls::types::GroupId;
+use crate::relay::publish_queue::OutboxEntry;
+use crate::safety::CheckinRule;
+
+/// A read-only view of a data directory's `circles.db`.
+pub struct ReadOnlyCircleStorage {
+    inner: CircleStorage,
+}
+
+impl std::fmt::Debug for ReadOnlyCircleStorage {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ReadOnlyCircleStorage")
+            .field("encrypted", &self.inner.is_encrypted())
+            .finish_non_exhaustive()
+    }
+}
+
+impl ReadOnlyCircleStorage {
+    /// Opens `circles.db` in `data_dir` read-only.
+    ///
+    /// `circle_db_hex_key` is the key the app opens it with (`None` for an
+    /// unencrypted database).
+    ///
+    /// # Errors
+    ///
+    /// Returns [`CircleError::Storage`] if the database does not exist or
+    /// cannot be read with the key, or [`CircleError::InvalidData`] for a
+    /// malformed key.
+    pub fn open(data_dir: &Path, circle_db_hex_key: Option<&str>) -> Result<Self> {
+        let inner = CircleStorage::open_read_only(&data_dir.join("circles.db"), circle_db_hex_key)?;
+        Ok(Self { inner })
+    }
+
+    /// Returns whether the database is encrypted at rest with `SQLCipher`.
+    #[must_use]
+    pub const fn is_encrypted(&self) -> bool {
+        self.inner.is_encrypted()
+    }
+
+    /// Every circle row.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the query fails (e.g. the app has not yet
+    /// migrated the file to this build's schema).
+    pub fn circles(&self) -> Result<Vec<Circle>> {
+        self.inner.get_all_circles()
+    }
+
+    /// One circle row.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the query fails.
+    pub fn circle(&self, mls_group_id: &GroupId) -> Result<Option<Circle>> {
+        self.inner.get_circle(mls_group_id)
+    }
+
+    /// A circle's lifecycle state.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the query fails.
+    pub fn lifecycle(&self, mls_group_id: &GroupId) -> Result<Option<CircleLifecycle>> {
+        self.inner.get_lifecycle(mls_group_id)
+    }
+
+    /// Every contact.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the query fails.
+    pub fn contacts(&self) -> Result<Vec<Contact>> {
+        self.inner.get_all_contacts()
+    }
+
+    /// Every outbox entry.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the query fails.
+    pub fn outbox(&self) -> Result<Vec<OutboxEntry>> {
+        self.inner.list_outbox()
+    }
+
+    /// Every scheduled check-in rule, for evaluating them with
+    /// [`crate::safety::evaluate`] while the app is not running.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the query fails.
+    pub fn checkin_rules(&self) -> Result<Vec<CheckinRule>> {
+        self.inner.checkin_rules(None)
+    }
+
+    /// Runs `PRAGMA quick_check`, returning the problems found (empty if the
+    /// file is consistent).
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the check cannot run.
+    pub fn quick_check(&self) -> Result<Vec<String>> {
+        let conn =
+            self.inner.conn().lock().map_err(|e| {
+                CircleError::Storage(format!("Failed to acquire database lock: {e}"))
+            })?;
+        let mut stmt = conn.prepare("PRAGMA quick_check")?;
+        let rows = stmt.query_map([], |row| row.get::<_, String>(0))?;
+        let mut problems = Vec::new();
+        for row in rows {
+            let line = row?;
+            if line != "ok" {
+                problems.push(line);
+            }
+        }
+        Ok(problems)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn contact() -> Contact {
+        Contact {
+            pubkey: format!("{:064x}", 7),
+            display_name: Some("Read Only".to_string()),
+            notes: None,
+            created_at: 1_000,
+            updated_at: 1_000,
+            verified_at: None,
+            avatar_id: None,
+        }
+    }
+
+    #[test]
+    fn reads_alongside_a_live_writer_without_writing() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let key = "0123456789abcdef0123456789abcdef0123456789abcdef0123456789abcdef";
+        let writer = CircleStorage::new(&dir.path().join("circles.db"), Some(key)).unwrap();
+        writer.save_contact(&contact()).unwrap();
+
+        let reader = ReadOnlyCircleStorage::open(dir.path(), Some(key)).unwrap();
+        assert!(reader.is_encrypted());
+        let contacts = reader.contacts().unwrap();
+        assert_eq!(contacts.len(), 1);
+        assert_eq!(contacts[0].display_name.as_deref(), Some("Read Only"));
+        assert!(reader.circles().unwrap().is_empty());
+        assert!(reader.quick_check().unwrap().is_empty());
+
+        // The connection refuses writes.
+        let conn = reader.inner.conn().lock().unwrap();
+        assert!(conn.execute("DELETE FROM contacts", []).is_err());
+        drop(conn);
+
+        // The writer is unaffected.
+        writer.save_contact(&contact()).unwrap();
+    }
+
+    #[test]
+    fn never_creates_or_opens_with_a_wrong_key() {
+        let dir = tempfile::TempDir::new().unwrap();
+        assert!(ReadOnlyCircleStorage::open(dir.path(), None).is_err());
+        assert!(!dir.path().join("circles.db").exists());
+
+        let key = "0123456789abcdef0123456789abcdef0123456789abcdef0123456789abcdef";
+        let _writer = CircleStorage::new(&dir.path().join("circles.db"), Some(key)).unwrap();
+        let wrong = "f".repeat(64);
+        assert!(ReadOnlyCircleStorage::open(dir.path(), Some(&wrong)).is_err());
+        assert!(ReadOnlyCircleStorage::open(dir.path(), Some("short")).is_err());
+    }
+}
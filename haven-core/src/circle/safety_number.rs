@@ -0,0 +1,72 @@
+//! Safety numbers for in-person contact verification.
+//!
+//! Two people compare a short number on their screens; if it matches, each
+//! holds the other's real key and no one substituted a key in between (an
+//! inviter's relay, a compromised share link). The number is derived from the
+//! *pair* of keys, sorted, so both sides see the same digits.
+//!
+//! # Derivation
+//!
+//! `SHA-256("haven-safety-number-v1" || min(a, b) || max(a, b))` over the
+//! 32-byte x-only keys. The first 30 bytes of the digest are cut into six
+//! 5-byte chunks; each chunk, read big-endian modulo 100 000, gives one
+//! zero-padded 5-digit group (30 digits, about 100 bits).
+
+use nostr::PublicKey;
+use sha2::{Digest, Sha256};
+
+const DOMAIN: &[u8] = b"haven-safety-number-v1";
+
+/// Digit groups in a safety number.
+pub const SAFETY_NUMBER_GROUPS: usize = 6;
+
+/// The safety number for the pair `a`, `b`, as space-separated 5-digit
+/// groups (e.g. `"04821 99310 ..."`). Symmetric in its arguments.
+#[must_use]
+pub fn safety_number(a: &PublicKey, b: &PublicKey) -> String {
+    let (a, b) = (a.to_bytes(), b.to_bytes());
+    let (low, high) = if a <= b { (a, b) } else { (b, a) };
+    let digest = Sha256::new()
+        .chain_update(DOMAIN)
+        .chain_update(low)
+        .chain_update(high)
+        .finalize();
+
+    digest
+        .chunks_exact(5)
+        .take(SAFETY_NUMBER_GROUPS)
+        .map(|chunk| {
+            let value = chunk.iter().fold(0u64, |acc, b| (acc << 8) | u64::from(*b));
+            format!("{:05}", value % 100_000)
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use nostr::Keys;
+
+    #[test]
+    fn symmetric_and_well_formed() {
+        let a = Keys::generate().public_key();
+        let b = Keys::generate().public_key();
+        let number = safety_number(&a, &b);
+        assert_eq!(number, safety_number(&b, &a));
+
+        let groups: Vec<&str> = number.split(' ').collect();
+        assert_eq!(groups.len(), SAFETY_NUMBER_GROUPS);
+        assert!(groups
+            .iter()
+            .all(|g| g.len() == 5 && g.chars().all(|c| c.is_ascii_digit())));
+    }
+
+    #[test]
+    fn differs_per_pair() {
+        let a = Keys::generate().public_key();
+        let b = Keys::generate().public_key();
+        let c = Keys::generate().public_key();
+        assert_ne!(safety_number(&a, &b), safety_number(&a, &c));
+    }
+}
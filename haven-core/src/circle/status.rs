@@ -0,0 +1,246 @@
+//! Aggregate "where is everyone" status for a circle.
+//!
+//! Widgets and watch complications only need a one-line summary ("3 of 4 at
+//! Home, 1 elsewhere"). Computing it here means those surfaces receive a
+//! compact count struct and never the per-member coordinates.
+
+use std::collections::HashMap;
+
+use super::types::LastKnownLocation;
+use crate::location::Geofence;
+
+/// How many members are currently inside one geofence.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GeofenceOccupancy {
+    /// The geofence label, as supplied by the caller.
+    pub label: String,
+    /// Number of members whose fresh location is inside this geofence.
+    pub member_count: u32,
+}
+
+/// A compact summary of a circle's member whereabouts.
+///
+/// Every counted member appears in exactly one bucket: the first matching
+/// geofence (in the caller's order), `elsewhere`, or `unknown`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CircleStatus {
+    /// Members counted (the roster minus the local user).
+    pub total_members: u32,
+    /// Per-geofence counts, in the order the geofences were supplied.
+    pub geofences: Vec<GeofenceOccupancy>,
+    /// Members with a fresh location outside every geofence.
+    pub elsewhere: u32,
+    /// Members with no location, or only one older than its freshness window.
+    pub unknown: u32,
+}
+
+impl CircleStatus {
+    /// Returns `true` if every counted member is inside the geofence labelled
+    /// `label` (the "everyone is home" check).
+    ///
+    /// An empty circle is never "all at" anywhere.
+    #[must_use]
+    pub fn all_at(&self, label: &str) -> bool {
+        self.total_members > 0
+            && self
+                .geofences
+                .iter()
+                .any(|g| g.label == label && g.member_count == self.total_members)
+    }
+}
+
+/// Computes a [`CircleStatus`] from the roster and the last-known locations.
+///
+/// A location counts only while `now_unix_secs <= expires_at` and it is
+/// within the circle's display threshold (`display_max_age_secs`, see
+/// [`LastKnownLocation::is_displayable`]); older rows are bucketed as
+/// `unknown` so a member who stopped sharing at home does not keep reporting
+/// "at home" forever, and a marker the map has blanked is never counted.
+/// Locations from pubkeys not in `members` are ignored. When a member has
+/// several rows, the newest one wins.
+#[must_use]
+pub fn compute_circle_status(
+    members: &[String],
+    locations: &[LastKnownLocation],
+    geofences: &[Geofence],
+    display_max_age_secs: u64,
+    now_unix_secs: i64,
+) -> CircleStatus {
+    let mut latest: HashMap<&str, &LastKnownLocation> = HashMap::new();
+    for loc in locations {
+        let newer = latest
+            .get(loc.sender_pubkey.as_str())
+            .is_none_or(|existing| loc.timestamp > existing.timestamp);
+        if newer {
+            latest.insert(loc.sender_pubkey.as_str(), loc);
+        }
+    }
+
+    let mut counts = vec![0u32; geofences.len()];
+    let mut elsewhere = 0u32;
+    let mut unknown = 0u32;
+
+    for member in members {
+        let fresh = latest.get(member.as_str()).filter(|loc| {
+            now_unix_secs <= loc.expires_at
+                && loc.is_displayable(display_max_age_secs, now_unix_secs)
+        });
+        let Some(loc) = fresh else {
+            unknown += 1;
+            continue;
+        };
+        match geofences
+            .iter()
+            .position(|g| g.contains(loc.latitude, loc.longitude))
+        {
+            Some(idx) => counts[idx] += 1,
+            None => elsewhere += 1,
+        }
+    }
+
+    CircleStatus {
+        total_members: u32::try_from(members.len()).unwrap_or(u32::MAX),
+        geofences: geofences
+            .iter()
+            .zip(counts)
+            .map(|(g, member_count)| GeofenceOccupancy {
+                label: g.label.clone(),
+                member_count,
+            })
+            .collect(),
+        elsewhere,
+        unknown,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::circle::DEFAULT_DISPLAY_MAX_AGE_SECS;
+
+    const NOW: i64 = 1_000_000;
+
+    fn loc(sender: &str, lat: f64, lon: f64, timestamp: i64) -> LastKnownLocation {
+        LastKnownLocation {
+            nostr_group_id: [1; 32],
+            sender_pubkey: sender.to_string(),
+            latitude: lat,
+            longitude: lon,
+            geohash: String::new(),
+            display_name: None,
+            timestamp,
+            expires_at: timestamp + 900,
+            purge_after: timestamp + 86_400,
+            updated_at: timestamp,
+        }
+    }
+
+    fn members(names: &[&str]) -> Vec<String> {
+        names.iter().map(ToString::to_string).collect()
+    }
+
+    fn home() -> Geofence {
+        Geofence::new("Home", 37.7749, -122.4194, 150.0)
+    }
+
+    fn school() -> Geofence {
+        Geofence::new("School", 37.8044, -122.2712, 200.0)
+    }
+
+    #[test]
+    fn counts_members_per_bucket() {
+        let roster = members(&["a", "b", "c", "d"]);
+        let locs = vec![
+            loc("a", 37.7749, -122.4194, NOW),
+            loc("b", 37.7750, -122.4195, NOW),
+            loc("c", 37.8044, -122.2712, NOW),
+            loc("d", 40.0, -100.0, NOW),
+        ];
+        let status = compute_circle_status(
+            &roster,
+            &locs,
+            &[home(), school()],
+            DEFAULT_DISPLAY_MAX_AGE_SECS,
+            NOW,
+        );
+
+        assert_eq!(status.total_members, 4);
+        assert_eq!(status.geofences[0].label, "Home");
+        assert_eq!(status.geofences[0].member_count, 2);
+        assert_eq!(status.geofences[1].member_count, 1);
+        assert_eq!(status.elsewhere, 1);
+        assert_eq!(status.unknown, 0);
+        assert!(!status.all_at("Home"));
+    }
+
+    #[test]
+    fn everyone_home() {
+        let roster = members(&["a", "b"]);
+        let locs = vec![
+            loc("a", 37.7749, -122.4194, NOW),
+            loc("b", 37.7749, -122.4194, NOW),
+        ];
+        let status =
+            compute_circle_status(&roster, &locs, &[home()], DEFAULT_DISPLAY_MAX_AGE_SECS, NOW);
+        assert!(status.all_at("Home"));
+        assert!(!status.all_at("School"));
+    }
+
+    #[test]
+    fn stale_and_missing_locations_are_unknown() {
+        let roster = members(&["a", "b"]);
+        let locs = vec![loc("a", 37.7749, -122.4194, NOW - 10_000)];
+        let status =
+            compute_circle_status(&roster, &locs, &[home()], DEFAULT_DISPLAY_MAX_AGE_SECS, NOW);
+        assert_eq!(status.unknown, 2);
+        assert_eq!(status.geofences[0].member_count, 0);
+    }
+
+    #[test]
+    fn newest_row_wins_and_non_members_ignored() {
+        let roster = members(&["a"]);
+        let locs = vec![
+            loc("a", 37.7749, -122.4194, NOW - 60),
+            loc("a", 40.0, -100.0, NOW),
+            loc("stranger", 37.7749, -122.4194, NOW),
+        ];
+        let status =
+            compute_circle_status(&roster, &locs, &[home()], DEFAULT_DISPLAY_MAX_AGE_SECS, NOW);
+        assert_eq!(status.total_members, 1);
+        assert_eq!(status.elsewhere, 1);
+        assert_eq!(status.geofences[0].member_count, 0);
+    }
+
+    #[test]
+    fn first_matching_geofence_wins_for_overlaps() {
+        let roster = members(&["a"]);
+        let locs = vec![loc("a", 37.7749, -122.4194, NOW)];
+        let wide = Geofence::new("Neighborhood", 37.7749, -122.4194, 2_000.0);
+        let status = compute_circle_status(
+            &roster,
+            &locs,
+            &[home(), wide],
+            DEFAULT_DISPLAY_MAX_AGE_SECS,
+            NOW,
+        );
+        assert_eq!(status.geofences[0].member_count, 1);
+        assert_eq!(status.geofences[1].member_count, 0);
+    }
+
+    #[test]
+    fn locations_past_the_display_threshold_are_unknown() {
+        let roster = members(&["a"]);
+        let locs = vec![loc("a", 37.7749, -122.4194, NOW - 120)];
+        let status = compute_circle_status(&roster, &locs, &[home()], 60, NOW);
+        assert_eq!(status.unknown, 1);
+        let status = compute_circle_status(&roster, &locs, &[home()], 600, NOW);
+        assert!(status.all_at("Home"));
+    }
+
+    #[test]
+    fn empty_circle_is_never_all_at() {
+        let status = compute_circle_status(&[], &[], &[home()], DEFAULT_DISPLAY_MAX_AGE_SECS, NOW);
+        assert_eq!(status.total_members, 0);
+        assert!(!status.all_at("Home"));
+    }
+}
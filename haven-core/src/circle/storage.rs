@@ -15,17 +15,23 @@
 #![allow(clippy::significant_drop_tightening)]
 
 use std::path::Path;
-use std::sync::Mutex;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Mutex, MutexGuard, TryLockError};
+use std::time::Duration;
 
-use rusqlite::{params, Connection, OptionalExtension};
+use rusqlite::{params, Connection, OpenFlags, OptionalExtension};
 
 use nostr::EventId;
 
 use super::error::{CircleError, Result};
+use super::lifecycle::CircleLifecycle;
+use super::storage_location_history::HISTORY_CELL_LEN;
+use super::storage_migrations::Migration;
 use super::types::{
     Circle, CircleMembership, CircleType, CircleUiState, Contact, LastKnownLocation,
     MembershipStatus,
 };
+use crate::diagnostics::{slow_ops, statement_name, SLOW_STORAGE_MODULE};
 use crate::nostr::mls::types::{GroupId, GroupIdExt};
 
 /// `SQLite`-based storage for circle data.
@@ -34,6 +40,13 @@ use crate::nostr::mls::types::{GroupId, GroupIdExt};
 /// circle metadata, membership state, contacts, and UI preferences.
 pub struct CircleStorage {
     conn: Mutex<Connection>,
+    /// Extra query-only connections for reads (see [`Self::reader`]); empty
+    /// for in-memory and read-only databases, which then read through `conn`.
+    readers: Vec<Mutex<Connection>>,
+    /// Round-robin start for [`Self::reader`].
+    next_reader: AtomicUsize,
+    /// Whether the database was opened with a `SQLCipher` key.
+    encrypted: bool,
 }
 
 impl CircleStorage {
@@ -67,10 +80,83 @@ impl CircleStorage {
         &self.conn
     }
 
+    /// Query-only connections opened next to the writer. In WAL mode they
+    /// read the last committed state without waiting for the writer.
+    const READ_POOL_SIZE: usize = 2;
+
+    /// A connection for a read-only query: a free pooled reader, else the
+    /// next one in turn, else (no pool) the writer.
+    ///
+    /// Readers are opened with `query_only`, so a write through one fails
+    /// rather than bypassing the writer lock. Use [`Self::conn`] for anything
+    /// that writes, or that must see its own uncommitted transaction.
+    pub(crate) fn reader(&self) -> Result<MutexGuard<'_, Connection>> {
+        fn poisoned<T>(e: &std::sync::PoisonError<T>) -> CircleError {
+            CircleError::Storage(format!("Failed to acquire database lock: {e}"))
+        }
+        if self.readers.is_empty() {
+            return self.conn.lock().map_err(|e| poisoned(&e));
+        }
+        let start = self.next_reader.fetch_add(1, Ordering::Relaxed) % self.readers.len();
+        for offset in 0..self.readers.len() {
+            match self.readers[(start + offset) % self.readers.len()].try_lock() {
+                Ok(conn) => return Ok(conn),
+                Err(TryLockError::WouldBlock) => {}
+                Err(TryLockError::Poisoned(e)) => return Err(poisoned(&e)),
+            }
+        }
+        self.readers[start].lock().map_err(|e| poisoned(&e))
+    }
+
+    /// Opens one pooled reader on an already initialized database.
+    fn open_reader(path: &Path, encryption_hex_key: Option<&str>) -> Result<Connection> {
+        let conn = Connection::open_with_flags(
+            path,
+            OpenFlags::SQLITE_OPEN_READ_WRITE
+                | OpenFlags::SQLITE_OPEN_URI
+                | OpenFlags::SQLITE_OPEN_NO_MUTEX,
+        )?;
+        Self::apply_hardening_pragmas(&conn)?;
+        if let Some(hex_key) = encryption_hex_key {
+            // Validated by `open_writer` (64 hex chars).
+            conn.execute_batch(&format!("PRAGMA key = \"x'{hex_key}'\""))?;
+        }
+        conn.execute_batch("PRAGMA query_only = ON;")?;
+        conn.profile(Some(Self::profile_statement));
+        Ok(conn)
+    }
+
+    /// Copies the write-ahead log into the database file and truncates it.
+    ///
+    /// `SQLite` checkpoints on its own as the log grows; this is for quiet
+    /// moments (app backgrounded, after a large sync) so the `-wal` file does
+    /// not keep holding pages. Returns `false` if a reader kept part of the
+    /// log in use; the rest is checkpointed next time.
+    ///
+    /// # Errors
+    ///
+    /// Returns a database error on failure.
+    pub fn checkpoint(&self) -> Result<bool> {
+        let conn = self
+            .conn
+            .lock()
+            .map_err(|e| CircleError::Storage(format!("Failed to acquire database lock: {e}")))?;
+        let busy: i64 = conn.query_row("PRAGMA wal_checkpoint(TRUNCATE)", [], |r| r.get(0))?;
+        Ok(busy == 0)
+    }
+
+    /// Returns whether this database is encrypted at rest with `SQLCipher`.
+    #[must_use]
+    pub const fn is_encrypted(&self) -> bool {
+        self.encrypted
+    }
+
     /// Creates a new storage instance at the given path.
     ///
     /// Creates the database file and tables if they don't exist.
     /// If `encryption_hex_key` is provided, enables `SQLCipher` encryption.
+    /// The database runs in WAL mode, with a small pool of query-only
+    /// connections next to the writer (see [`Self::reader`]).
     ///
     /// # Arguments
     ///
@@ -82,6 +168,15 @@ impl CircleStorage {
     ///
     /// Returns an error if the database cannot be created or initialized.
     pub fn new(path: &Path, encryption_hex_key: Option<&str>) -> Result<Self> {
+        let mut storage = Self::open_writer(path, encryption_hex_key)?;
+        storage.readers = (0..Self::READ_POOL_SIZE)
+            .map(|_| Self::open_reader(path, encryption_hex_key).map(Mutex::new))
+            .collect::<Result<_>>()?;
+        Ok(storage)
+    }
+
+    /// Opens (creating or migrating as needed) the writer connection.
+    fn open_writer(path: &Path, encryption_hex_key: Option<&str>) -> Result<Self> {
         let db_exists = path.exists();
 
         if let Some(hex_key) = encryption_hex_key {
@@ -113,6 +208,9 @@ impl CircleStorage {
                     // Key works (DB already encrypted with this key, or new)
                     let storage = Self {
                         conn: Mutex::new(conn),
+                        readers: Vec::new(),
+                        next_reader: AtomicUsize::new(0),
+                        encrypted: true,
                     };
                     storage.initialize_schema()?;
                     return Ok(storage);
@@ -125,6 +223,9 @@ impl CircleStorage {
             // New database — schema will be created encrypted
             let storage = Self {
                 conn: Mutex::new(conn),
+                readers: Vec::new(),
+                next_reader: AtomicUsize::new(0),
+                encrypted: true,
             };
             storage.initialize_schema()?;
             return Ok(storage);
@@ -135,11 +236,65 @@ impl CircleStorage {
         Self::apply_hardening_pragmas(&conn)?;
         let storage = Self {
             conn: Mutex::new(conn),
+            readers: Vec::new(),
+            next_reader: AtomicUsize::new(0),
+            encrypted: false,
         };
         storage.initialize_schema()?;
         Ok(storage)
     }
 
+    /// Opens an existing database read-only (see [`super::read_only`]).
+    ///
+    /// The connection is opened with `SQLITE_OPEN_READ_ONLY` and
+    /// `query_only`, and the schema is neither created nor migrated, so the
+    /// file is never written. Readers take only shared locks; a concurrent
+    /// writer waits at most `busy_timeout`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`CircleError::InvalidData`] for a malformed key, or
+    /// [`CircleError::Storage`] if the file is missing or cannot be read with
+    /// the given key.
+    pub(crate) fn open_read_only(path: &Path, encryption_hex_key: Option<&str>) -> Result<Self> {
+        if !path.exists() {
+            return Err(CircleError::Storage("Database file not found".to_string()));
+        }
+        if let Some(hex_key) = encryption_hex_key {
+            if hex_key.len() != 64 || !hex_key.bytes().all(|b| b.is_ascii_hexdigit()) {
+                return Err(CircleError::InvalidData(
+                    "Encryption key must be exactly 64 hex characters".to_string(),
+                ));
+            }
+        }
+
+        let conn = Connection::open_with_flags(
+            path,
+            OpenFlags::SQLITE_OPEN_READ_ONLY
+                | OpenFlags::SQLITE_OPEN_URI
+                | OpenFlags::SQLITE_OPEN_NO_MUTEX,
+        )?;
+        Self::apply_hardening_pragmas(&conn)?;
+        if let Some(hex_key) = encryption_hex_key {
+            // Validated above (64 hex chars).
+            conn.execute_batch(&format!("PRAGMA key = \"x'{hex_key}'\""))?;
+        }
+        conn.execute_batch("PRAGMA query_only = ON;")?;
+        conn.query_row("SELECT count(*) FROM sqlite_master", [], |r| {
+            r.get::<_, i64>(0)
+        })
+        .map_err(|_| {
+            CircleError::Storage("Database cannot be read with the given key".to_string())
+        })?;
+
+        Ok(Self {
+            conn: Mutex::new(conn),
+            readers: Vec::new(),
+            next_reader: AtomicUsize::new(0),
+            encrypted: encryption_hex_key.is_some(),
+        })
+    }
+
     /// Migrates an existing unencrypted database to encrypted storage.
     ///
     /// Uses `SQLCipher`'s `ATTACH` + `sqlcipher_export()` to copy all data
@@ -210,6 +365,9 @@ impl CircleStorage {
 
         let storage = Self {
             conn: Mutex::new(conn),
+            readers: Vec::new(),
+            next_reader: AtomicUsize::new(0),
+            encrypted: true,
         };
         storage.initialize_schema()?;
         Ok(storage)
@@ -270,6 +428,21 @@ impl CircleStorage {
         Ok(())
     }
 
+    /// Statement profiler: reports `sql` to the slow-operation log by name.
+    ///
+    /// Statements on `error_breadcrumbs` are left out, since writing the slow
+    /// operations there would otherwise report itself.
+    fn profile_statement(sql: &str, elapsed: Duration) {
+        let log = slow_ops();
+        if log.threshold().is_none_or(|threshold| elapsed < threshold) {
+            return;
+        }
+        let name = statement_name(sql);
+        if !name.ends_with(".error_breadcrumbs") {
+            log.observe(SLOW_STORAGE_MODULE, &name, elapsed);
+        }
+    }
+
     /// Creates an in-memory storage instance for testing.
     ///
     /// # Errors
@@ -280,14 +453,18 @@ impl CircleStorage {
         let conn = Connection::open_in_memory()?;
         let storage = Self {
             conn: Mutex::new(conn),
+            readers: Vec::new(),
+            next_reader: AtomicUsize::new(0),
+            encrypted: false,
         };
         storage.initialize_schema()?;
         Ok(storage)
     }
 
     /// Test-only: writes a legacy `avatar_path` onto a contact row and clears
-    /// the migration sentinel, so the next `initialize_schema` run re-triggers
-    /// the legacy-avatar migration. Used to exercise the migration path.
+    /// the migration sentinel and its `schema_migrations` row, so the next
+    /// `initialize_schema` run re-triggers the legacy-avatar migration. Used
+    /// to exercise the migration path.
     #[cfg(test)]
     fn inject_legacy_avatar_path_for_test(&self, pubkey: &str, path: &str) -> Result<()> {
         let conn = self
@@ -302,6 +479,7 @@ impl CircleStorage {
             "DELETE FROM user_settings WHERE key = ?1",
             params![Self::AVATAR_PATH_MIGRATED_KEY],
         )?;
+        conn.execute("DELETE FROM schema_migrations WHERE version = 1", [])?;
         Ok(())
     }
 
@@ -335,7 +513,7 @@ impl CircleStorage {
         reason = "single CREATE TABLE block; splitting hides the schema in fragments"
     )]
     fn initialize_schema(&self) -> Result<()> {
-        let conn = self
+        let mut conn = self
             .conn
             .lock()
             .map_err(|e| CircleError::Storage(format!("Failed to acquire database lock: {e}")))?;
@@ -358,13 +536,21 @@ impl CircleStorage {
         // `busy_timeout` on `circles.db`. On the encrypted path this runs AFTER
         // `PRAGMA key` (as before), preserving the codec binding.
         //
-        // We deliberately do NOT touch `PRAGMA journal_mode`: SQLCipher
-        // encrypts the WAL/rollback journal by default, and overriding it
-        // could bypass the cipher. (No `journal_mode` override exists anywhere
-        // in this file — verified; the M4 assertion test pins that circles.db
-        // stays rollback-journal so the concurrency reasoning cannot drift.)
         Self::apply_hardening_pragmas(&conn)?;
 
+        // WAL lets the foreground and the background isolate read while the
+        // other writes, instead of failing with "database is locked" once
+        // `busy_timeout` runs out. SQLCipher encrypts the `-wal` pages like
+        // the main file (as for tiles.db), and the wipe deletes the
+        // `-wal`/`-shm` sidecars. By now the key has been verified (or the
+        // database is new), so the header rewrite cannot hit a wrong key.
+        // In-memory databases stay in `memory` mode.
+        conn.query_row("PRAGMA journal_mode = WAL", [], |r| r.get::<_, String>(0))?;
+
+        // Every statement is timed; slow ones become breadcrumbs once a
+        // threshold is set (see `diagnostics::SlowOpLog`).
+        conn.profile(Some(Self::profile_statement));
+
         conn.execute_batch(
             r"
             -- Circle metadata (app-level, not MLS state)
@@ -379,7 +565,10 @@ impl CircleStorage {
                 updated_at INTEGER NOT NULL
             );
 
-            -- Membership state (pending/accepted/declined invitations)
+            -- Membership state. `lifecycle` is the authoritative
+            -- CircleLifecycle state; `status` (pending/accepted/declined) is
+            -- kept in sync for older readers. A NULL lifecycle (row written
+            -- before the column existed) is derived from `status`.
             CREATE TABLE IF NOT EXISTS circle_memberships (
                 id INTEGER PRIMARY KEY AUTOINCREMENT,
                 mls_group_id BLOB NOT NULL UNIQUE,
@@ -387,6 +576,7 @@ impl CircleStorage {
                 inviter_pubkey TEXT,
                 invited_at INTEGER NOT NULL,
                 responded_at INTEGER,
+                lifecycle TEXT,
                 FOREIGN KEY (mls_group_id) REFERENCES circles(mls_group_id)
             );
 
@@ -398,8 +588,22 @@ impl CircleStorage {
                 avatar_path TEXT,
                 notes TEXT,
                 created_at INTEGER NOT NULL,
-                updated_at INTEGER NOT NULL
+                updated_at INTEGER NOT NULL,
+                verified INTEGER NOT NULL DEFAULT 0,
+                verified_at INTEGER,
+                avatar_id TEXT
+            );
+
+            -- User-chosen labels on contacts (family, kids, ...), many
+            -- per contact. Wiped with the contact.
+            CREATE TABLE IF NOT EXISTS contact_labels (
+                pubkey     TEXT NOT NULL,
+                label      TEXT NOT NULL,
+                created_at INTEGER NOT NULL,
+                PRIMARY KEY (pubkey, label)
             );
+            CREATE INDEX IF NOT EXISTS idx_contact_labels_label
+                ON contact_labels(label);
 
             -- UI state per circle
             CREATE TABLE IF NOT EXISTS circle_ui_state (
@@ -437,6 +641,31 @@ impl CircleStorage {
             CREATE INDEX IF NOT EXISTS idx_lkl_group
                 ON last_known_locations(nostr_group_id);
 
+            -- Every location written to last_known_locations, not just the
+            -- newest, for map viewport queries (see
+            -- storage_location_history.rs). `cell` is the 8-character
+            -- geohash of the exact coordinates — not the sender's geohash,
+            -- which follows the shared precision — so a prefix range on it
+            -- finds the points under a covering cell. Rows share the
+            -- last-known retention (`purge_after`, at most 1 day) and are
+            -- deleted alongside them.
+            CREATE TABLE IF NOT EXISTS location_history (
+                nostr_group_id BLOB NOT NULL,
+                sender_pubkey  TEXT NOT NULL,
+                latitude       REAL NOT NULL,
+                longitude      REAL NOT NULL,
+                cell           TEXT NOT NULL,
+                timestamp      INTEGER NOT NULL,
+                purge_after    INTEGER NOT NULL,
+                PRIMARY KEY (nostr_group_id, sender_pubkey, timestamp)
+            );
+            CREATE INDEX IF NOT EXISTS idx_location_history_cell
+                ON location_history(cell);
+            CREATE INDEX IF NOT EXISTS idx_location_history_group_time
+                ON location_history(nostr_group_id, timestamp);
+            CREATE INDEX IF NOT EXISTS idx_location_history_purge_after
+                ON location_history(purge_after);
+
             -- Idempotency cache for NIP-59 gift-wrap (kind 1059) invitation
             -- processing. The invitation poller uses a 2-day lookback window,
             -- so the same gift wrap is re-fetched on every poll cycle. MDK
@@ -468,13 +697,310 @@ impl CircleStorage {
                 ON user_relays(relay_type);
 
             -- Generic key/value table for user settings (privacy toggles,
-            -- seeding sentinels, UI state). Schema versioning sentinels
-            -- live here too if we ever need them.
+            -- seeding sentinels, UI state). Schema changes are versioned in
+            -- schema_migrations instead (see storage_migrations.rs).
             CREATE TABLE IF NOT EXISTS user_settings (
                 key   TEXT PRIMARY KEY,
                 value TEXT NOT NULL
             );
 
+            -- Applied schema migrations, one row per version, written in the
+            -- same transaction as the migration itself.
+            CREATE TABLE IF NOT EXISTS schema_migrations (
+                version    INTEGER PRIMARY KEY,
+                name       TEXT NOT NULL,
+                applied_at INTEGER NOT NULL
+            );
+
+            -- Relay blacklist (see crate::relay::blacklist). The verified
+            -- community list (NIP-51 kind 10006 from a pinned maintainer) is
+            -- replaced wholesale on each newer ingest; per-relay user
+            -- decisions ('block' / 'allow') live in their own table so a
+            -- community refresh never touches them. The opt-in flag and the
+            -- ingested list's created_at live in user_settings.
+            CREATE TABLE IF NOT EXISTS community_relay_blacklist (
+                url TEXT PRIMARY KEY
+            );
+            CREATE TABLE IF NOT EXISTS relay_overrides (
+                url        TEXT PRIMARY KEY,
+                decision   TEXT NOT NULL,
+                created_at INTEGER NOT NULL
+            );
+
+            -- Cached NIP-11 relay information documents (see
+            -- crate::relay::relay_info), keyed by canonical relay URL. The
+            -- document is the parsed RelayInfo as JSON; rows older than
+            -- RELAY_INFO_TTL_SECS are ignored and pruned.
+            CREATE TABLE IF NOT EXISTS relay_info (
+                url        TEXT PRIMARY KEY,
+                document   TEXT NOT NULL,
+                fetched_at INTEGER NOT NULL
+            );
+
+            -- Per-relay health counters (see crate::relay::relay_stats): running
+            -- totals since install, overwritten on every flush. Relay URLs,
+            -- counts and redacted error reasons only; never events or circles.
+            CREATE TABLE IF NOT EXISTS relay_stats (
+                url              TEXT PRIMARY KEY,
+                publish_ok       INTEGER NOT NULL DEFAULT 0,
+                publish_failed   INTEGER NOT NULL DEFAULT 0,
+                latency_ms_total INTEGER NOT NULL DEFAULT 0,
+                latency_samples  INTEGER NOT NULL DEFAULT 0,
+                bytes_sent       INTEGER NOT NULL DEFAULT 0,
+                bytes_received   INTEGER NOT NULL DEFAULT 0,
+                last_ok_at       INTEGER,
+                last_error_at    INTEGER,
+                last_error       TEXT
+            );
+
+            -- Local error breadcrumbs (see crate::diagnostics). A ring buffer:
+            -- inserts prune everything past MAX_BREADCRUMBS by id. Only slugs
+            -- are stored, never error messages. Never leaves the device
+            -- unless the user exports a health snapshot.
+            CREATE TABLE IF NOT EXISTS error_breadcrumbs (
+                id         INTEGER PRIMARY KEY AUTOINCREMENT,
+                module     TEXT NOT NULL,
+                error_kind TEXT NOT NULL,
+                created_at INTEGER NOT NULL
+            );
+
+            -- Per-(circle, sender) receive-side precision baseline (see
+            -- crate::location::precision). Wiped with the circle.
+            CREATE TABLE IF NOT EXISTS precision_baselines (
+                mls_group_id  BLOB NOT NULL,
+                sender_pubkey TEXT NOT NULL,
+                class         TEXT NOT NULL,
+                samples       INTEGER NOT NULL,
+                updated_at    INTEGER NOT NULL,
+                PRIMARY KEY (mls_group_id, sender_pubkey)
+            );
+
+            -- Per-circle location sharing overrides (see
+            -- CircleLocationSettings). Absent row: send as the caller built
+            -- the location. NULL display_max_age_secs: the default display
+            -- threshold. Wiped with the circle.
+            CREATE TABLE IF NOT EXISTS circle_settings (
+                mls_group_id          BLOB PRIMARY KEY,
+                precision             TEXT NOT NULL,
+                share_expiration_secs INTEGER NOT NULL,
+                updated_at            INTEGER NOT NULL,
+                display_max_age_secs  INTEGER
+            );
+
+            -- Per-circle trip mode (see TripMode). Rows past `ends_at` are
+            -- deleted on read and wiped with the circle.
+            CREATE TABLE IF NOT EXISTS trip_modes (
+                mls_group_id          BLOB PRIMARY KEY,
+                update_interval_secs  INTEGER NOT NULL,
+                share_expiration_secs INTEGER NOT NULL,
+                ends_at               INTEGER NOT NULL
+            );
+
+            -- Precise-sharing sessions (see SharingSession). Rows past
+            -- `ends_at` are deleted on read and wiped with the circle.
+            CREATE TABLE IF NOT EXISTS precise_sessions (
+                mls_group_id BLOB PRIMARY KEY,
+                started_at   INTEGER NOT NULL,
+                ends_at      INTEGER NOT NULL
+            );
+
+            -- Circles the user paused location sharing to (see
+            -- storage_location_pause). Wiped with the circle.
+            CREATE TABLE IF NOT EXISTS location_pauses (
+                mls_group_id BLOB PRIMARY KEY,
+                paused_at    INTEGER NOT NULL
+            );
+
+            -- Per-circle receive health (see GroupHealth), keyed by the
+            -- routing id the receive path sees. Wiped with the circle.
+            CREATE TABLE IF NOT EXISTS group_health (
+                nostr_group_id        BLOB PRIMARY KEY,
+                unprocessable_count   INTEGER NOT NULL,
+                last_unprocessable_at INTEGER NOT NULL
+            );
+
+            -- What ingesting each kind-445 event produced (see
+            -- storage_processed_events), so an event the engine already
+            -- consumed can be shown again. JSON `payload`; expires with the
+            -- last-known rows and is wiped with the circle.
+            CREATE TABLE IF NOT EXISTS processed_events (
+                event_id       BLOB PRIMARY KEY,
+                nostr_group_id BLOB NOT NULL,
+                outcome        TEXT NOT NULL,
+                payload        TEXT NOT NULL,
+                processed_at   INTEGER NOT NULL,
+                purge_after    INTEGER NOT NULL
+            );
+            CREATE INDEX IF NOT EXISTS idx_processed_events_group
+                ON processed_events(nostr_group_id);
+            CREATE INDEX IF NOT EXISTS idx_processed_events_purge_after
+                ON processed_events(purge_after);
+
+            -- When each member of a circle was last heard from (see
+            -- MemberPresence). Wiped with the circle or the member.
+            CREATE TABLE IF NOT EXISTS member_presence (
+                mls_group_id     BLOB NOT NULL,
+                member_pubkey    TEXT NOT NULL,
+                last_seen        INTEGER NOT NULL,
+                last_location_at INTEGER,
+                PRIMARY KEY (mls_group_id, member_pubkey)
+            );
+
+            -- Time-limited meet pins (see crate::meet), the local user's and
+            -- received ones alike. Rows are deleted once `expires_at` passes
+            -- and wiped with the circle.
+            CREATE TABLE IF NOT EXISTS meet_pins (
+                mls_group_id  BLOB NOT NULL,
+                pin_id        TEXT NOT NULL,
+                sender_pubkey TEXT NOT NULL,
+                latitude      REAL NOT NULL,
+                longitude     REAL NOT NULL,
+                label         TEXT,
+                created_at    INTEGER NOT NULL,
+                expires_at    INTEGER NOT NULL,
+                PRIMARY KEY (mls_group_id, pin_id)
+            );
+            CREATE INDEX IF NOT EXISTS idx_meet_pins_expires_at
+                ON meet_pins(expires_at);
+
+            -- Cold storage (see crate::circle::cold_storage): the sealed,
+            -- compressed hot rows of a frozen archived circle. `purge_after`
+            -- is the latest retention deadline of the frozen locations (NULL
+            -- if none); the blob is dropped once it passes. Wiped with the
+            -- circle.
+            CREATE TABLE IF NOT EXISTS cold_circles (
+                mls_group_id BLOB PRIMARY KEY,
+                blob         BLOB NOT NULL,
+                row_count    INTEGER NOT NULL,
+                raw_bytes    INTEGER NOT NULL,
+                purge_after  INTEGER,
+                frozen_at    INTEGER NOT NULL
+            );
+
+            -- Member key audit (see crate::circle::key_audit). The signature
+            -- key fingerprint last seen per member pubkey, and every change
+            -- from it awaiting (or given) user review. Kept across circles:
+            -- the history is about the member, not the circle.
+            CREATE TABLE IF NOT EXISTS member_keys (
+                member_pubkey TEXT PRIMARY KEY,
+                fingerprint   TEXT NOT NULL,
+                first_seen    INTEGER NOT NULL,
+                last_seen     INTEGER NOT NULL
+            );
+            CREATE TABLE IF NOT EXISTS member_key_changes (
+                id                   INTEGER PRIMARY KEY AUTOINCREMENT,
+                member_pubkey        TEXT NOT NULL,
+                mls_group_id         BLOB,
+                previous_fingerprint TEXT NOT NULL,
+                observed_fingerprint TEXT NOT NULL,
+                observed_at          INTEGER NOT NULL,
+                acknowledged_at      INTEGER
+            );
+
+            -- Last MLS roster seen per circle (see storage_roster), diffed
+            -- against the engine after each group update to find who joined
+            -- or left.
+            CREATE TABLE IF NOT EXISTS circle_roster (
+                mls_group_id  BLOB NOT NULL,
+                member_pubkey TEXT NOT NULL,
+                PRIMARY KEY (mls_group_id, member_pubkey)
+            );
+
+            -- Welcomes this device sent (see storage_invites), kept until the
+            -- member first sends in the circle so a lost invitation can be
+            -- resent. `epoch` is the epoch the Welcome joins at, NULL until
+            -- the adding commit is confirmed; `relays` and `fallback_relays`
+            -- are JSON arrays of delivery targets. Rows go with the member.
+            CREATE TABLE IF NOT EXISTS sent_invites (
+                mls_group_id    BLOB NOT NULL,
+                member_pubkey   TEXT NOT NULL,
+                welcome_json    TEXT NOT NULL,
+                relays          TEXT NOT NULL,
+                fallback_relays TEXT NOT NULL,
+                epoch           INTEGER,
+                invited_at      INTEGER NOT NULL,
+                resend_count    INTEGER NOT NULL DEFAULT 0,
+                last_resent_at  INTEGER,
+                joined_at       INTEGER,
+                PRIMARY KEY (mls_group_id, member_pubkey)
+            );
+
+            -- Scheduled check-ins this device watches for (see
+            -- crate::safety::schedule). Local to the device, never sent.
+            -- `weekdays` is a Monday-first bitmask; `last_seen_at` is when the
+            -- member's latest location was taken. Rows go with the member.
+            CREATE TABLE IF NOT EXISTS checkin_rules (
+                rule_id               TEXT PRIMARY KEY,
+                mls_group_id          BLOB NOT NULL,
+                member_pubkey         TEXT NOT NULL,
+                deadline_minute       INTEGER NOT NULL,
+                window_minutes        INTEGER NOT NULL,
+                weekdays              INTEGER NOT NULL,
+                created_at            INTEGER NOT NULL,
+                last_seen_at          INTEGER,
+                acknowledged_deadline INTEGER
+            );
+            CREATE INDEX IF NOT EXISTS idx_checkin_rules_member
+                ON checkin_rules(mls_group_id, member_pubkey);
+
+            -- Proximity alerts this device watches for (see
+            -- crate::location::proximity). Local to the device, never sent.
+            -- The target is a member (`target_pubkey`) or a place (the
+            -- `place_*` columns). Rows go with either member.
+            CREATE TABLE IF NOT EXISTS proximity_rules (
+                rule_id         TEXT PRIMARY KEY,
+                mls_group_id    BLOB NOT NULL,
+                member_pubkey   TEXT NOT NULL,
+                target_pubkey   TEXT,
+                place_label     TEXT,
+                place_latitude  REAL,
+                place_longitude REAL,
+                range_m         REAL NOT NULL,
+                created_at      INTEGER NOT NULL,
+                within          INTEGER NOT NULL DEFAULT 0
+            );
+
+            -- Offline outbox (see crate::relay::publish_queue): signed events
+            -- whose publish failed on every relay, kept for retry with
+            -- backoff. `relays` is a JSON array of target URLs; `status` is
+            -- 'pending', 'sent', 'expired', or 'abandoned'. A row is never
+            -- sent past `expires_at` (the event's NIP-40 expiration, capped at
+            -- the priority's delivery deadline after queueing). `priority` is
+            -- OutboxPriority::rank; higher ranks are retried first. Finished
+            -- rows are kept briefly for status reporting, then pruned.
+            -- Identity-level, not per-circle: the rows are opaque signed
+            -- events.
+            CREATE TABLE IF NOT EXISTS outbox (
+                id              INTEGER PRIMARY KEY AUTOINCREMENT,
+                event_id        TEXT NOT NULL UNIQUE,
+                kind            INTEGER NOT NULL,
+                event_json      TEXT NOT NULL,
+                relays          TEXT NOT NULL,
+                priority        INTEGER NOT NULL DEFAULT 1,
+                status          TEXT NOT NULL,
+                attempts        INTEGER NOT NULL,
+                next_attempt_at INTEGER NOT NULL,
+                expires_at      INTEGER NOT NULL,
+                last_error      TEXT,
+                created_at      INTEGER NOT NULL,
+                updated_at      INTEGER NOT NULL
+            );
+            CREATE INDEX IF NOT EXISTS idx_outbox_due
+                ON outbox(status, next_attempt_at);
+
+            -- Per-relay group sync cursors (see crate::relay::sync): the
+            -- newest kind:445 event each relay has served for a circle that
+            -- was ingested without error. `last_event_id` breaks ties between
+            -- events in the same second. Wiped with the circle.
+            CREATE TABLE IF NOT EXISTS group_relay_cursors (
+                nostr_group_id  BLOB NOT NULL,
+                relay_url       TEXT NOT NULL,
+                last_created_at INTEGER NOT NULL,
+                last_event_id   TEXT NOT NULL,
+                updated_at      INTEGER NOT NULL,
+                PRIMARY KEY (nostr_group_id, relay_url)
+            );
+
             -- Tracks the last published replaceable event id per (kind, d_tag,
             -- pubkey) tuple. Used by `unpublish_relay_list` to construct
             -- best-effort NIP-09 deletions, and by future audit/republish
@@ -489,6 +1015,16 @@ impl CircleStorage {
                 PRIMARY KEY (kind, d_tag, pubkey)
             );
 
+            -- The relay set each of the user's relay lists was last published
+            -- with (JSON array), keyed by wire kind: the old side of the
+            -- debounced republish's old ∪ new targets (see
+            -- crate::relay::relay_list_publisher).
+            CREATE TABLE IF NOT EXISTS published_relay_sets (
+                kind          INTEGER PRIMARY KEY,
+                relays_json   TEXT NOT NULL,
+                published_at  INTEGER NOT NULL
+            );
+
             -- NOTE: the legacy MLS in-group avatar tables (`avatar_blobs`,
             -- `avatar_assignments`, `circle_salts`) were removed at the
             -- public-profile migration cutover (see
@@ -551,6 +1087,19 @@ impl CircleStorage {
             CREATE INDEX IF NOT EXISTS idx_published_key_packages_slot
                 ON published_key_packages(d_tag, created_at DESC);
 
+            -- Latest fetch-back verification of the tracked KeyPackage (see
+            -- crate::relay::maintenance::kp_verify): one row per KeyPackage
+            -- relay, `status` is 'served', 'missing', or 'unreachable'.
+            -- Replaced wholesale on each check; identity-scoped like
+            -- published_key_packages and wiped with it.
+            CREATE TABLE IF NOT EXISTS key_package_verifications (
+                relay_url  TEXT PRIMARY KEY,
+                position   INTEGER NOT NULL,
+                event_id   TEXT NOT NULL,
+                status     TEXT NOT NULL,
+                checked_at INTEGER NOT NULL
+            );
+
             -- Public-profile (kind-0) metadata cache. Keyed by pubkey (hex);
             -- there is deliberately NO circle/group column (a group id must
             -- never touch a profile row — asserted by a PRAGMA table_info test
@@ -585,18 +1134,123 @@ impl CircleStorage {
             ",
         )?;
 
-        // Best-effort migration off the legacy plaintext `contacts.avatar_path`
-        // column. Sentinel-gated so it runs at most once per database.
-        Self::migrate_legacy_avatar_paths(&conn)?;
+        // Versioned migrations: each runs once per database, in order, in
+        // its own transaction (see `storage_migrations`).
+        Self::run_migrations(&mut conn)?;
+
+        // Routing safeguard: make `circles.nostr_group_id` unique, unless a
+        // legacy database already holds a collision that needs reconciling.
+        // Not versioned: it is retried on every open until the collision is
+        // reconciled.
+        Self::migrate_add_nostr_group_id_index(&conn)?;
 
-        // One-shot drop of the legacy MLS in-group avatar tables at the
-        // public-profile cutover. Sentinel-gated so it runs at most once.
-        Self::migrate_drop_legacy_avatar_tables(&conn)?;
+        Ok(())
+    }
 
+    /// Schema migrations, in the order they run. Append new entries with the
+    /// next version; never renumber, reorder or remove one, since the version
+    /// is what a database records as applied.
+    ///
+    /// Tables and columns are also declared in [`Self::initialize_schema`] so
+    /// a fresh database starts in the latest shape; every migration must
+    /// therefore be a no-op on a database that already has it.
+    pub(super) const MIGRATIONS: &'static [Migration] = &[
+        // Best-effort migration off the legacy plaintext `contacts.avatar_path`
+        // column.
+        Migration {
+            version: 1,
+            name: "null_legacy_avatar_paths",
+            up: Self::migrate_legacy_avatar_paths,
+        },
+        // Drop of the legacy MLS in-group avatar tables at the public-profile
+        // cutover.
+        Migration {
+            version: 2,
+            name: "drop_legacy_avatar_tables",
+            up: Self::migrate_drop_legacy_avatar_tables,
+        },
         // Dark Matter cutover: drop the pre-migration `published_key_packages`
         // schema (hash_ref/kind columns) so the fresh DM schema takes over.
-        Self::migrate_reset_published_key_packages(&conn)?;
+        Migration {
+            version: 3,
+            name: "reset_published_key_packages",
+            up: Self::migrate_reset_published_key_packages,
+        },
+        // Circle lifecycle: existing rows keep NULL and derive their state
+        // from `status`.
+        Migration {
+            version: 4,
+            name: "add_membership_lifecycle",
+            up: Self::migrate_add_membership_lifecycle,
+        },
+        // Safety-number verification: existing contacts start unverified.
+        Migration {
+            version: 5,
+            name: "add_contact_verification",
+            up: Self::migrate_add_contact_verification,
+        },
+        // Contact pictures: existing contacts start without a picture.
+        Migration {
+            version: 6,
+            name: "add_contact_avatar_id",
+            up: Self::migrate_add_contact_avatar_id,
+        },
+    ];
+
+    /// Adds `contacts.avatar_id` to a database created before the column
+    /// existed. Idempotent.
+    fn migrate_add_contact_avatar_id(conn: &Connection) -> Result<()> {
+        if !Self::table_has_column(conn, "contacts", "avatar_id")? {
+            conn.execute_batch("ALTER TABLE contacts ADD COLUMN avatar_id TEXT;")?;
+        }
+        Ok(())
+    }
 
+    /// Creates the unique index on `circles.nostr_group_id`. Idempotent.
+    ///
+    /// Skipped (with a warning) while two circles share a Nostr group ID,
+    /// since creating the index would fail the open; see
+    /// [`Self::nostr_group_id_collisions`].
+    fn migrate_add_nostr_group_id_index(conn: &Connection) -> Result<()> {
+        let duplicates: i64 = conn.query_row(
+            "SELECT COUNT(*) FROM (
+                SELECT 1 FROM circles GROUP BY nostr_group_id HAVING COUNT(*) > 1
+            )",
+            [],
+            |r| r.get(0),
+        )?;
+        if duplicates > 0 {
+            log::warn!(
+                "{duplicates} nostr group id(s) shared by several circles; \
+                 unique index not created until reconciled"
+            );
+            return Ok(());
+        }
+        conn.execute_batch(
+            "CREATE UNIQUE INDEX IF NOT EXISTS idx_circles_nostr_group_id
+                ON circles(nostr_group_id);",
+        )?;
+        Ok(())
+    }
+
+    /// Adds `contacts.verified` and `contacts.verified_at` to a database
+    /// created before the columns existed. Idempotent.
+    fn migrate_add_contact_verification(conn: &Connection) -> Result<()> {
+        if !Self::table_has_column(conn, "contacts", "verified")? {
+            conn.execute_batch(
+                "ALTER TABLE contacts ADD COLUMN verified INTEGER NOT NULL DEFAULT 0;
+                 ALTER TABLE contacts ADD COLUMN verified_at INTEGER;",
+            )?;
+        }
+        Ok(())
+    }
+
+    /// Adds `circle_memberships.lifecycle` to a database created before the
+    /// column existed. Idempotent: a no-op once the column is present.
+    fn migrate_add_membership_lifecycle(conn: &Connection) -> Result<()> {
+        if !Self::table_has_column(conn, "circle_memberships", "lifecycle")? {
+            conn.execute_batch("ALTER TABLE circle_memberships ADD COLUMN lifecycle TEXT;")?;
+        }
         Ok(())
     }
 
@@ -700,9 +1354,9 @@ impl CircleStorage {
     /// are dropped BEFORE the referenced `avatar_blobs` — dropping the child
     /// first is FK-safe regardless of whether `foreign_keys` enforcement is on
     /// (this connection leaves it at `SQLite`'s default OFF; the ordering is the
-    /// guard the security review asked for, F10). The drops run in one
-    /// transaction so a mid-migration crash cannot leave a partially-dropped
-    /// set. Idempotent via the [`PROFILE_MIGRATION_KEY`] sentinel in
+    /// guard the security review asked for, F10). The drops run in the
+    /// migration's transaction so a mid-migration crash cannot leave a
+    /// partially-dropped set. Idempotent via the [`PROFILE_MIGRATION_KEY`] sentinel in
     /// `user_settings`; `DROP TABLE IF EXISTS` also makes a brand-new database
     /// (which never created these tables) a clean no-op.
     fn migrate_drop_legacy_avatar_tables(conn: &Connection) -> Result<()> {
@@ -719,11 +1373,9 @@ impl CircleStorage {
 
         conn.execute_batch(
             r"
-            BEGIN;
             DROP TABLE IF EXISTS avatar_assignments;
             DROP TABLE IF EXISTS circle_salts;
             DROP TABLE IF EXISTS avatar_blobs;
-            COMMIT;
             ",
         )?;
 
@@ -809,13 +1461,30 @@ impl CircleStorage {
     ///
     /// # Errors
     ///
-    /// Returns an error if the database operation fails.
+    /// Returns [`CircleError::NostrGroupIdCollision`] if a different circle
+    /// already uses `circle.nostr_group_id`, or an error if the database
+    /// operation fails.
     pub fn save_circle(&self, circle: &Circle) -> Result<()> {
         let conn = self
             .conn
             .lock()
             .map_err(|e| CircleError::Storage(format!("Failed to acquire database lock: {e}")))?;
 
+        // Incoming kind 445 events are routed by `h` tag, so two circles must
+        // never share one. Checked under the same lock as the write; the
+        // unique index is the backstop.
+        let collides = conn
+            .query_row(
+                "SELECT 1 FROM circles WHERE nostr_group_id = ?1 AND mls_group_id != ?2",
+                params![&circle.nostr_group_id[..], circle.mls_group_id.as_slice()],
+                |_| Ok(()),
+            )
+            .optional()?
+            .is_some();
+        if collides {
+            return Err(CircleError::NostrGroupIdCollision);
+        }
+
         // Serialize relays as JSON array
         let relays_json = serde_json::to_string(&circle.relays)
             .map_err(|e| CircleError::Storage(format!("Failed to serialize relays: {e}")))?;
@@ -845,17 +1514,62 @@ impl CircleStorage {
         Ok(())
     }
 
-    /// Retrieves a circle by its MLS group ID.
+    /// Returns the MLS group IDs of circles that share a Nostr group ID, one
+    /// set per shared ID.
+    ///
+    /// Empty on any database written since [`Self::save_circle`] refused
+    /// collisions. A legacy database may hold some, in which case the unique
+    /// index on `circles.nostr_group_id` was not created and messages for the
+    /// shared `h` tag cannot be told apart. To reconcile, keep the circle the
+    /// user still takes part in and leave (or abandon locally) the others;
+    /// the index is created on the next open once no collision remains.
     ///
     /// # Errors
     ///
     /// Returns an error if the database operation fails.
-    pub fn get_circle(&self, mls_group_id: &GroupId) -> Result<Option<Circle>> {
+    pub fn nostr_group_id_collisions(&self) -> Result<Vec<Vec<GroupId>>> {
         let conn = self
             .conn
             .lock()
             .map_err(|e| CircleError::Storage(format!("Failed to acquire database lock: {e}")))?;
 
+        let mut stmt = conn.prepare(
+            r"
+            SELECT nostr_group_id, mls_group_id FROM circles
+            WHERE nostr_group_id IN (
+                SELECT nostr_group_id FROM circles
+                GROUP BY nostr_group_id HAVING COUNT(*) > 1
+            )
+            ORDER BY nostr_group_id, id
+            ",
+        )?;
+        let rows = stmt.query_map([], |row| {
+            Ok((row.get::<_, Vec<u8>>(0)?, row.get::<_, Vec<u8>>(1)?))
+        })?;
+
+        let mut collisions: Vec<Vec<GroupId>> = Vec::new();
+        let mut current: Option<Vec<u8>> = None;
+        for row in rows {
+            let (nostr_group_id, mls_group_id) = row?;
+            if current.as_ref() != Some(&nostr_group_id) {
+                collisions.push(Vec::new());
+                current = Some(nostr_group_id);
+            }
+            if let Some(set) = collisions.last_mut() {
+                set.push(GroupId::from_slice(&mls_group_id));
+            }
+        }
+        Ok(collisions)
+    }
+
+    /// Retrieves a circle by its MLS group ID.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database operation fails.
+    pub fn get_circle(&self, mls_group_id: &GroupId) -> Result<Option<Circle>> {
+        let conn = self.reader()?;
+
         let result = conn
             .query_row(
                 r"
@@ -927,10 +1641,7 @@ impl CircleStorage {
     ///
     /// Returns an error if the database operation fails.
     pub fn get_all_circles(&self) -> Result<Vec<Circle>> {
-        let conn = self
-            .conn
-            .lock()
-            .map_err(|e| CircleError::Storage(format!("Failed to acquire database lock: {e}")))?;
+        let conn = self.reader()?;
 
         let mut stmt = conn.prepare(
             r"
@@ -1010,7 +1721,8 @@ impl CircleStorage {
     /// Deletes a circle and every related row atomically: UI state,
     /// memberships, the per-group gift-wrap dedup rows (`processed_gift_wraps`,
     /// except the empty-blob failure sentinels — see below), the M7
-    /// staged-commit marker, the per-group sync cursor (`sync_cursors`), and
+    /// staged-commit marker, the per-group sync cursors (`sync_cursors`,
+    /// `group_relay_cursors`), and
     /// last-known locations.
     ///
     /// Idempotent: deleting a circle that does not exist is not an error,
@@ -1072,6 +1784,54 @@ impl CircleStorage {
             "DELETE FROM processed_gift_wraps WHERE mls_group_id = ?1",
             params![mls_group_id.as_slice()],
         )?;
+        tx.execute(
+            "DELETE FROM precision_baselines WHERE mls_group_id = ?1",
+            params![mls_group_id.as_slice()],
+        )?;
+        tx.execute(
+            "DELETE FROM circle_settings WHERE mls_group_id = ?1",
+            params![mls_group_id.as_slice()],
+        )?;
+        tx.execute(
+            "DELETE FROM trip_modes WHERE mls_group_id = ?1",
+            params![mls_group_id.as_slice()],
+        )?;
+        tx.execute(
+            "DELETE FROM precise_sessions WHERE mls_group_id = ?1",
+            params![mls_group_id.as_slice()],
+        )?;
+        tx.execute(
+            "DELETE FROM location_pauses WHERE mls_group_id = ?1",
+            params![mls_group_id.as_slice()],
+        )?;
+        tx.execute(
+            "DELETE FROM meet_pins WHERE mls_group_id = ?1",
+            params![mls_group_id.as_slice()],
+        )?;
+        tx.execute(
+            "DELETE FROM cold_circles WHERE mls_group_id = ?1",
+            params![mls_group_id.as_slice()],
+        )?;
+        tx.execute(
+            "DELETE FROM circle_roster WHERE mls_group_id = ?1",
+            params![mls_group_id.as_slice()],
+        )?;
+        tx.execute(
+            "DELETE FROM member_presence WHERE mls_group_id = ?1",
+            params![mls_group_id.as_slice()],
+        )?;
+        tx.execute(
+            "DELETE FROM sent_invites WHERE mls_group_id = ?1",
+            params![mls_group_id.as_slice()],
+        )?;
+        tx.execute(
+            "DELETE FROM checkin_rules WHERE mls_group_id = ?1",
+            params![mls_group_id.as_slice()],
+        )?;
+        tx.execute(
+            "DELETE FROM proximity_rules WHERE mls_group_id = ?1",
+            params![mls_group_id.as_slice()],
+        )?;
         if let Some(ngid) = nostr_group_id {
             // Wipe-on-LEAVE for the per-group sync cursor so a returning
             // circle with the same nostr_group_id re-seeds cleanly instead of
@@ -1086,10 +1846,26 @@ impl CircleStorage {
                 "DELETE FROM sync_cursors WHERE stream = ?1",
                 params![group_cursor_stream],
             )?;
+            tx.execute(
+                "DELETE FROM group_relay_cursors WHERE nostr_group_id = ?1",
+                params![ngid],
+            )?;
             tx.execute(
                 "DELETE FROM last_known_locations WHERE nostr_group_id = ?1",
                 params![ngid],
             )?;
+            tx.execute(
+                "DELETE FROM location_history WHERE nostr_group_id = ?1",
+                params![ngid],
+            )?;
+            tx.execute(
+                "DELETE FROM group_health WHERE nostr_group_id = ?1",
+                params![ngid],
+            )?;
+            tx.execute(
+                "DELETE FROM processed_events WHERE nostr_group_id = ?1",
+                params![ngid],
+            )?;
         }
 
         tx.commit()?;
@@ -1120,13 +1896,15 @@ impl CircleStorage {
 
         conn.execute(
             r"
-            INSERT INTO circle_memberships (mls_group_id, status, inviter_pubkey, invited_at, responded_at)
-            VALUES (?1, ?2, ?3, ?4, ?5)
+            INSERT INTO circle_memberships
+                (mls_group_id, status, inviter_pubkey, invited_at, responded_at, lifecycle)
+            VALUES (?1, ?2, ?3, ?4, ?5, ?6)
             ON CONFLICT(mls_group_id) DO UPDATE SET
                 status = excluded.status,
                 inviter_pubkey = excluded.inviter_pubkey,
                 invited_at = excluded.invited_at,
-                responded_at = excluded.responded_at
+                responded_at = excluded.responded_at,
+                lifecycle = excluded.lifecycle
             ",
             params![
                 membership.mls_group_id.as_slice(),
@@ -1134,6 +1912,7 @@ impl CircleStorage {
                 &membership.inviter_pubkey,
                 membership.invited_at,
                 membership.responded_at,
+                CircleLifecycle::from_status(membership.status).as_str(),
             ],
         )?;
 
@@ -1198,6 +1977,8 @@ impl CircleStorage {
     /// Updates the membership status for a circle.
     ///
     /// Also updates the `responded_at` timestamp if transitioning from pending.
+    /// This is a raw overwrite: the lifecycle is reset to the state implied by
+    /// `status` without validation. Prefer [`Self::transition_lifecycle`].
     ///
     /// # Errors
     ///
@@ -1216,10 +1997,15 @@ impl CircleStorage {
         let rows = conn.execute(
             r"
             UPDATE circle_memberships
-            SET status = ?1, responded_at = ?2
-            WHERE mls_group_id = ?3
+            SET status = ?1, responded_at = ?2, lifecycle = ?3
+            WHERE mls_group_id = ?4
             ",
-            params![status.as_str(), responded_at, mls_group_id.as_slice()],
+            params![
+                status.as_str(),
+                responded_at,
+                CircleLifecycle::from_status(status).as_str(),
+                mls_group_id.as_slice()
+            ],
         )?;
 
         if rows == 0 {
@@ -1231,6 +2017,95 @@ impl CircleStorage {
         Ok(())
     }
 
+    /// Returns the lifecycle state of a circle, or `None` if there is no
+    /// membership row.
+    ///
+    /// Rows written before the lifecycle column existed derive their state
+    /// from the legacy `status` column.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`CircleError::InvalidData`] if a stored value is unknown, or a
+    /// database error.
+    pub fn get_lifecycle(&self, mls_group_id: &GroupId) -> Result<Option<CircleLifecycle>> {
+        let conn = self
+            .conn
+            .lock()
+            .map_err(|e| CircleError::Storage(format!("Failed to acquire database lock: {e}")))?;
+        Self::read_lifecycle(&conn, mls_group_id)
+    }
+
+    /// Moves a circle to `to`, validating the move against
+    /// [`CircleLifecycle::transition`] and keeping the legacy `status` column
+    /// in sync. The read, check and write are one transaction, so two racing
+    /// transitions cannot both succeed from the same state.
+    ///
+    /// Returns the state the circle was in before the move.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`CircleError::NotFound`] if there is no membership row,
+    /// [`CircleError::IllegalTransition`] if the move is not allowed, or a
+    /// database error.
+    pub fn transition_lifecycle(
+        &self,
+        mls_group_id: &GroupId,
+        to: CircleLifecycle,
+        now: i64,
+    ) -> Result<CircleLifecycle> {
+        let mut conn = self
+            .conn
+            .lock()
+            .map_err(|e| CircleError::Storage(format!("Failed to acquire database lock: {e}")))?;
+        let tx = conn.transaction()?;
+
+        let from = Self::read_lifecycle(&tx, mls_group_id)?.ok_or_else(|| {
+            CircleError::NotFound("Membership not found for group: <redacted>".to_string())
+        })?;
+        from.transition(to)?;
+
+        tx.execute(
+            r"
+            UPDATE circle_memberships
+            SET lifecycle = ?1, status = ?2, responded_at = COALESCE(responded_at, ?3)
+            WHERE mls_group_id = ?4
+            ",
+            params![
+                to.as_str(),
+                to.membership_status().as_str(),
+                now,
+                mls_group_id.as_slice()
+            ],
+        )?;
+        tx.commit()?;
+        Ok(from)
+    }
+
+    fn read_lifecycle(
+        conn: &Connection,
+        mls_group_id: &GroupId,
+    ) -> Result<Option<CircleLifecycle>> {
+        let row: Option<(String, Option<String>)> = conn
+            .query_row(
+                "SELECT status, lifecycle FROM circle_memberships WHERE mls_group_id = ?1",
+                params![mls_group_id.as_slice()],
+                |r| Ok((r.get(0)?, r.get(1)?)),
+            )
+            .optional()?;
+        let Some((status, lifecycle)) = row else {
+            return Ok(None);
+        };
+        let state = match lifecycle {
+            Some(l) => CircleLifecycle::parse(&l)
+                .ok_or_else(|| CircleError::InvalidData(format!("Invalid lifecycle: {l}")))?,
+            None => CircleLifecycle::from_status(
+                MembershipStatus::parse(&status)
+                    .ok_or_else(|| CircleError::InvalidData(format!("Invalid status: {status}")))?,
+            ),
+        };
+        Ok(Some(state))
+    }
+
     // ==================== Contact Operations ====================
 
     /// Saves a contact to the database.
@@ -1248,7 +2123,8 @@ impl CircleStorage {
 
         // `avatar_path` is a legacy, orphaned column (left in place rather than
         // dropped — see `migrate_legacy_avatar_paths`). New writes never touch
-        // it; avatars live in the SQLCipher BLOB avatar store now.
+        // it; pictures live in the sealed `AvatarStore`, referenced by
+        // `avatar_id`, which only `set_contact_avatar_id` writes.
         conn.execute(
             r"
             INSERT INTO contacts (pubkey, display_name, notes, created_at, updated_at)
@@ -1276,15 +2152,13 @@ impl CircleStorage {
     ///
     /// Returns an error if the database operation fails.
     pub fn get_contact(&self, pubkey: &str) -> Result<Option<Contact>> {
-        let conn = self
-            .conn
-            .lock()
-            .map_err(|e| CircleError::Storage(format!("Failed to acquire database lock: {e}")))?;
+        let conn = self.reader()?;
 
         let result = conn
             .query_row(
                 r"
-                SELECT pubkey, display_name, notes, created_at, updated_at
+                SELECT pubkey, display_name, notes, created_at, updated_at, verified_at,
+                       avatar_id
                 FROM contacts
                 WHERE pubkey = ?1
                 ",
@@ -1296,6 +2170,8 @@ impl CircleStorage {
                         notes: row.get(2)?,
                         created_at: row.get(3)?,
                         updated_at: row.get(4)?,
+                        verified_at: row.get(5)?,
+                        avatar_id: row.get(6)?,
                     })
                 },
             )
@@ -1310,14 +2186,12 @@ impl CircleStorage {
     ///
     /// Returns an error if the database operation fails.
     pub fn get_all_contacts(&self) -> Result<Vec<Contact>> {
-        let conn = self
-            .conn
-            .lock()
-            .map_err(|e| CircleError::Storage(format!("Failed to acquire database lock: {e}")))?;
+        let conn = self.reader()?;
 
         let mut stmt = conn.prepare(
             r"
-            SELECT pubkey, display_name, notes, created_at, updated_at
+            SELECT pubkey, display_name, notes, created_at, updated_at, verified_at,
+                   avatar_id
             FROM contacts
             ORDER BY display_name NULLS LAST, pubkey
             ",
@@ -1331,6 +2205,8 @@ impl CircleStorage {
                     notes: row.get(2)?,
                     created_at: row.get(3)?,
                     updated_at: row.get(4)?,
+                    verified_at: row.get(5)?,
+                    avatar_id: row.get(6)?,
                 })
             })?
             .collect::<std::result::Result<Vec<_>, _>>()?;
@@ -1338,6 +2214,89 @@ impl CircleStorage {
         Ok(contacts)
     }
 
+    /// Sets or clears a contact's safety-number verification, creating a
+    /// bare contact row if none exists. Never touches the name or notes.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database operation fails.
+    pub fn set_contact_verified(
+        &self,
+        pubkey: &str,
+        verified_at: Option<i64>,
+        now: i64,
+    ) -> Result<()> {
+        let conn = self
+            .conn
+            .lock()
+            .map_err(|e| CircleError::Storage(format!("Failed to acquire database lock: {e}")))?;
+
+        conn.execute(
+            r"
+            INSERT INTO contacts (pubkey, created_at, updated_at, verified, verified_at)
+            VALUES (?1, ?2, ?2, ?3, ?4)
+            ON CONFLICT(pubkey) DO UPDATE SET
+                verified = excluded.verified,
+                verified_at = excluded.verified_at,
+                updated_at = excluded.updated_at
+            ",
+            params![pubkey, now, verified_at.is_some(), verified_at],
+        )?;
+
+        Ok(())
+    }
+
+    /// Sets or clears a contact's picture, creating a bare contact row if
+    /// none exists. Never touches the name, notes or verification.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database operation fails.
+    pub fn set_contact_avatar_id(
+        &self,
+        pubkey: &str,
+        avatar_id: Option<&str>,
+        now: i64,
+    ) -> Result<()> {
+        let conn = self
+            .conn
+            .lock()
+            .map_err(|e| CircleError::Storage(format!("Failed to acquire database lock: {e}")))?;
+
+        conn.execute(
+            r"
+            INSERT INTO contacts (pubkey, created_at, updated_at, avatar_id)
+            VALUES (?1, ?2, ?2, ?3)
+            ON CONFLICT(pubkey) DO UPDATE SET
+                avatar_id = excluded.avatar_id,
+                updated_at = excluded.updated_at
+            ",
+            params![pubkey, now, avatar_id],
+        )?;
+
+        Ok(())
+    }
+
+    /// Number of contacts whose picture is `avatar_id`; the picture file can
+    /// be deleted once this reaches zero.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database operation fails.
+    pub fn count_contacts_with_avatar(&self, avatar_id: &str) -> Result<usize> {
+        let conn = self
+            .conn
+            .lock()
+            .map_err(|e| CircleError::Storage(format!("Failed to acquire database lock: {e}")))?;
+
+        let count: i64 = conn.query_row(
+            "SELECT COUNT(*) FROM contacts WHERE avatar_id = ?1",
+            params![avatar_id],
+            |r| r.get(0),
+        )?;
+        Ok(usize::try_from(count).unwrap_or(0))
+    }
+
     /// Deletes a contact by pubkey.
     ///
     /// # Errors
@@ -1350,6 +2309,10 @@ impl CircleStorage {
             .map_err(|e| CircleError::Storage(format!("Failed to acquire database lock: {e}")))?;
 
         conn.execute("DELETE FROM contacts WHERE pubkey = ?1", params![pubkey])?;
+        conn.execute(
+            "DELETE FROM contact_labels WHERE pubkey = ?1",
+            params![pubkey],
+        )?;
 
         Ok(())
     }
@@ -1434,11 +2397,13 @@ impl CircleStorage {
 
     // ==================== Last-Known Location Operations ====================
 
-    /// Upserts a last-known location row.
+    /// Upserts a last-known location row and records it in the location
+    /// history.
     ///
     /// If a row already exists for `(nostr_group_id, sender_pubkey)`, it is
     /// updated **only** when the incoming `timestamp` is strictly newer than
-    /// the stored one. Stale / out-of-order events are silently ignored.
+    /// the stored one. Stale / out-of-order events are silently ignored
+    /// there, but still land in the history (once per timestamp).
     ///
     /// Callers are expected to have already derived
     /// `purge_after = timestamp + LOCATION_RETENTION_SECS` (1 day) — the
@@ -1448,13 +2413,14 @@ impl CircleStorage {
     ///
     /// Returns an error if the database operation fails.
     pub fn upsert_last_known_location(&self, location: &LastKnownLocation) -> Result<()> {
-        let conn = self
+        let mut conn = self
             .conn
             .lock()
             .map_err(|e| CircleError::Storage(format!("Failed to acquire database lock: {e}")))?;
+        let tx = conn.transaction()?;
 
         // `precision_label` is a legacy column (always empty on new writes).
-        conn.execute(
+        tx.execute(
             r"
             INSERT INTO last_known_locations (
                 nostr_group_id, sender_pubkey, latitude, longitude, geohash,
@@ -1486,6 +2452,27 @@ impl CircleStorage {
                 location.updated_at,
             ],
         )?;
+        tx.execute(
+            "INSERT OR IGNORE INTO location_history (
+                 nostr_group_id, sender_pubkey, latitude, longitude, cell,
+                 timestamp, purge_after
+             )
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+            params![
+                &location.nostr_group_id[..],
+                &location.sender_pubkey,
+                location.latitude,
+                location.longitude,
+                crate::location::location_to_geohash(
+                    location.latitude,
+                    location.longitude,
+                    HISTORY_CELL_LEN
+                ),
+                location.timestamp,
+                location.purge_after,
+            ],
+        )?;
+        tx.commit()?;
 
         Ok(())
     }
@@ -1582,9 +2569,11 @@ impl CircleStorage {
             .collect()
     }
 
-    /// Removes the last-known location for a single sender in a circle.
+    /// Removes the last-known location and location history for a single
+    /// sender in a circle.
     ///
-    /// Called when a member is removed from a circle.
+    /// Called when a member is removed from a circle. The circle's cached
+    /// decrypt results go too: they are not indexed by sender.
     ///
     /// # Errors
     ///
@@ -1604,11 +2593,21 @@ impl CircleStorage {
              WHERE nostr_group_id = ?1 AND sender_pubkey = ?2",
             params![&nostr_group_id[..], sender_pubkey],
         )?;
+        conn.execute(
+            "DELETE FROM location_history
+             WHERE nostr_group_id = ?1 AND sender_pubkey = ?2",
+            params![&nostr_group_id[..], sender_pubkey],
+        )?;
+        conn.execute(
+            "DELETE FROM processed_events WHERE nostr_group_id = ?1",
+            params![&nostr_group_id[..]],
+        )?;
 
         Ok(())
     }
 
-    /// Removes every last-known location row for a circle.
+    /// Removes every last-known location and location history row for a
+    /// circle.
     ///
     /// Called when the user leaves / deletes a circle so that no residual
     /// location data for former co-members remains on disk.
@@ -1626,11 +2625,20 @@ impl CircleStorage {
             "DELETE FROM last_known_locations WHERE nostr_group_id = ?1",
             params![&nostr_group_id[..]],
         )?;
+        conn.execute(
+            "DELETE FROM location_history WHERE nostr_group_id = ?1",
+            params![&nostr_group_id[..]],
+        )?;
+        conn.execute(
+            "DELETE FROM processed_events WHERE nostr_group_id = ?1",
+            params![&nostr_group_id[..]],
+        )?;
 
         Ok(())
     }
 
-    /// Wipes every last-known location row.
+    /// Wipes every last-known location row, frozen ones included, and the
+    /// location history.
     ///
     /// Called from the identity-deletion path so no stale location data
     /// survives a full account wipe.
@@ -1645,6 +2653,9 @@ impl CircleStorage {
             .map_err(|e| CircleError::Storage(format!("Failed to acquire database lock: {e}")))?;
 
         conn.execute("DELETE FROM last_known_locations", [])?;
+        conn.execute("DELETE FROM location_history", [])?;
+        conn.execute("DELETE FROM cold_circles", [])?;
+        conn.execute("DELETE FROM processed_events", [])?;
 
         Ok(())
     }
@@ -1668,6 +2679,22 @@ impl CircleStorage {
             "DELETE FROM last_known_locations WHERE purge_after < ?1",
             params![now_unix_secs],
         )?;
+        // History rows expire on the same clock. Not counted either.
+        conn.execute(
+            "DELETE FROM location_history WHERE purge_after < ?1",
+            params![now_unix_secs],
+        )?;
+        // A frozen circle's blob goes once every location in it is past
+        // retention (see `cold_storage`). Not counted in the return value.
+        conn.execute(
+            "DELETE FROM cold_circles WHERE purge_after < ?1",
+            params![now_unix_secs],
+        )?;
+        // Cached decrypt results hold locations too.
+        conn.execute(
+            "DELETE FROM processed_events WHERE purge_after < ?1",
+            params![now_unix_secs],
+        )?;
 
         Ok(rows)
     }
@@ -1773,7 +2800,8 @@ impl CircleStorage {
         Ok(())
     }
 
-    /// Removes ALL sync-cursor rows (bulk reset) for the wipe-on-logout path.
+    /// Removes ALL sync-cursor rows, including the per-relay group cursors
+    /// (bulk reset) for the wipe-on-logout path.
     ///
     /// Idempotent. Complements the per-stream [`reset_sync_cursor`] so a full
     /// account wipe leaves no stale cursor that would resume a returning (or a
@@ -1788,6 +2816,7 @@ impl CircleStorage {
             .lock()
             .map_err(|e| CircleError::Storage(format!("Failed to acquire database lock: {e}")))?;
         conn.execute("DELETE FROM sync_cursors", [])?;
+        conn.execute("DELETE FROM group_relay_cursors", [])?;
         Ok(())
     }
 
@@ -1868,13 +2897,15 @@ impl CircleStorage {
 
         tx.execute(
             r"
-            INSERT INTO circle_memberships (mls_group_id, status, inviter_pubkey, invited_at, responded_at)
-            VALUES (?1, ?2, ?3, ?4, ?5)
+            INSERT INTO circle_memberships
+                (mls_group_id, status, inviter_pubkey, invited_at, responded_at, lifecycle)
+            VALUES (?1, ?2, ?3, ?4, ?5, ?6)
             ON CONFLICT(mls_group_id) DO UPDATE SET
                 status = excluded.status,
                 inviter_pubkey = excluded.inviter_pubkey,
                 invited_at = excluded.invited_at,
-                responded_at = excluded.responded_at
+                responded_at = excluded.responded_at,
+                lifecycle = excluded.lifecycle
             ",
             params![
                 membership.mls_group_id.as_slice(),
@@ -1882,6 +2913,7 @@ impl CircleStorage {
                 &membership.inviter_pubkey,
                 membership.invited_at,
                 membership.responded_at,
+                CircleLifecycle::from_status(membership.status).as_str(),
             ],
         )?;
 
@@ -2120,6 +3152,8 @@ mod tests {
             notes: Some(format!("Notes for contact {id}")),
             created_at: 1_000_000,
             updated_at: 2_000_000,
+            verified_at: None,
+            avatar_id: None,
         }
     }
 
@@ -2167,6 +3201,67 @@ mod tests {
         assert_eq!(retrieved.updated_at, 3_000_000);
     }
 
+    #[test]
+    fn save_circle_refuses_a_shared_nostr_group_id() {
+        let storage = CircleStorage::in_memory().unwrap();
+        storage.save_circle(&create_test_circle(1)).unwrap();
+
+        let clash = Circle {
+            nostr_group_id: [1; 32],
+            ..create_test_circle(2)
+        };
+        assert!(matches!(
+            storage.save_circle(&clash),
+            Err(CircleError::NostrGroupIdCollision)
+        ));
+        assert!(storage.get_circle(&clash.mls_group_id).unwrap().is_none());
+        // Re-saving the owner of the ID is an update, not a collision.
+        storage.save_circle(&create_test_circle(1)).unwrap();
+        assert!(storage.nostr_group_id_collisions().unwrap().is_empty());
+    }
+
+    #[test]
+    fn legacy_collisions_are_reported_and_skip_the_index() {
+        let storage = CircleStorage::in_memory().unwrap();
+        {
+            let conn = storage.conn().lock().unwrap();
+            conn.execute_batch("DROP INDEX idx_circles_nostr_group_id;")
+                .unwrap();
+            for id in [1u8, 2] {
+                conn.execute(
+                    "INSERT INTO circles (mls_group_id, nostr_group_id, display_name, created_at, updated_at)
+                     VALUES (?1, ?2, 'c', 0, 0)",
+                    params![&[id; 32][..], &[7u8; 32][..]],
+                )
+                .unwrap();
+            }
+            CircleStorage::migrate_add_nostr_group_id_index(&conn).unwrap();
+        }
+
+        let collisions = storage.nostr_group_id_collisions().unwrap();
+        assert_eq!(
+            collisions,
+            vec![vec![
+                GroupId::from_slice(&[1; 32]),
+                GroupId::from_slice(&[2; 32])
+            ]]
+        );
+
+        storage
+            .delete_circle(&GroupId::from_slice(&[2; 32]))
+            .unwrap();
+        let conn = storage.conn().lock().unwrap();
+        CircleStorage::migrate_add_nostr_group_id_index(&conn).unwrap();
+        let indexed: i64 = conn
+            .query_row(
+                "SELECT COUNT(*) FROM sqlite_master WHERE name = 'idx_circles_nostr_group_id'",
+                [],
+                |r| r.get(0),
+            )
+            .unwrap();
+        assert_eq!(indexed, 1);
+    }
+
     #[test]
     fn get_all_circles_ordered_by_updated_at() {
         let storage = CircleStorage::in_memory().unwrap();
@@ -2315,6 +3410,89 @@ mod tests {
         assert_eq!(retrieved.responded_at, Some(2_000_000));
     }
 
+    #[test]
+    fn lifecycle_follows_saved_status() {
+        let storage = CircleStorage::in_memory().unwrap();
+        let circle = create_test_circle(1);
+        storage.save_circle(&circle).unwrap();
+        storage.save_membership(&create_test_membership(1)).unwrap();
+
+        assert_eq!(
+            storage.get_lifecycle(&circle.mls_group_id).unwrap(),
+            Some(CircleLifecycle::Invited)
+        );
+        assert_eq!(
+            storage
+                .get_lifecycle(&GroupId::from_slice(&[99; 32]))
+                .unwrap(),
+            None
+        );
+    }
+
+    #[test]
+    fn transition_lifecycle_validates_and_syncs_status() {
+        let storage = CircleStorage::in_memory().unwrap();
+        let circle = create_test_circle(1);
+        let mut membership = create_test_membership(1);
+        membership.status = MembershipStatus::Accepted;
+        storage.save_circle(&circle).unwrap();
+        storage.save_membership(&membership).unwrap();
+        let id = &circle.mls_group_id;
+
+        let from = storage
+            .transition_lifecycle(id, CircleLifecycle::Archived, 10)
+            .unwrap();
+        assert_eq!(from, CircleLifecycle::Active);
+        let stored = storage.get_membership(id).unwrap().unwrap();
+        assert_eq!(stored.status, MembershipStatus::Accepted);
+
+        storage
+            .transition_lifecycle(id, CircleLifecycle::Removed, 20)
+            .unwrap();
+        assert_eq!(
+            storage.get_membership(id).unwrap().unwrap().status,
+            MembershipStatus::Declined
+        );
+
+        let err = storage
+            .transition_lifecycle(id, CircleLifecycle::Active, 30)
+            .unwrap_err();
+        assert!(matches!(
+            err,
+            CircleError::IllegalTransition {
+                from: CircleLifecycle::Removed,
+                to: CircleLifecycle::Active,
+            }
+        ));
+        assert_eq!(
+            storage.get_lifecycle(id).unwrap(),
+            Some(CircleLifecycle::Removed)
+        );
+    }
+
+    #[test]
+    fn legacy_membership_rows_derive_lifecycle_from_status() {
+        let storage = CircleStorage::in_memory().unwrap();
+        let circle = create_test_circle(1);
+        let mut membership = create_test_membership(1);
+        membership.status = MembershipStatus::Accepted;
+        storage.save_circle(&circle).unwrap();
+        storage.save_membership(&membership).unwrap();
+        storage
+            .conn
+            .lock()
+            .unwrap()
+            .execute("UPDATE circle_memberships SET lifecycle = NULL", [])
+            .unwrap();
+
+        // Re-running the schema (and its migration) is a no-op.
+        storage.reinitialize_for_test().unwrap();
+        assert_eq!(
+            storage.get_lifecycle(&circle.mls_group_id).unwrap(),
+            Some(CircleLifecycle::Active)
+        );
+    }
+
     // ==================== Contact Tests ====================
 
     #[test]
@@ -2362,6 +3540,8 @@ mod tests {
             notes: None,
             created_at: 1_000_000,
             updated_at: 2_000_000,
+            verified_at: None,
+            avatar_id: None,
         };
 
         storage.save_contact(&contact).unwrap();
@@ -2371,6 +3551,35 @@ mod tests {
         assert!(retrieved.notes.is_none());
     }
 
+    #[test]
+    fn contact_verification_survives_edits() {
+        let storage = CircleStorage::in_memory().unwrap();
+        storage
+            .set_contact_verified("abc123", Some(5_000), 5_000)
+            .unwrap();
+        let bare = storage.get_contact("abc123").unwrap().unwrap();
+        assert!(bare.is_verified());
+        assert!(bare.display_name.is_none());
+
+        storage
+            .save_contact(&Contact {
+                pubkey: "abc123".to_string(),
+                display_name: Some("Alice".to_string()),
+                ..create_test_contact(1)
+            })
+            .unwrap();
+        let named = storage.get_contact("abc123").unwrap().unwrap();
+        assert_eq!(named.verified_at, Some(5_000));
+        assert_eq!(named.display_name.as_deref(), Some("Alice"));
+
+        storage.set_contact_verified("abc123", None, 6_000).unwrap();
+        assert!(!storage
+            .get_contact("abc123")
+            .unwrap()
+            .unwrap()
+            .is_verified());
+    }
+
     #[test]
     fn get_all_contacts_ordered() {
         let storage = CircleStorage::in_memory().unwrap();
@@ -2518,6 +3727,7 @@ mod tests {
 
         let storage = CircleStorage::new(&db_path, Some(&test_hex_key()))
             .expect("should create encrypted DB");
+        assert!(storage.is_encrypted());
 
         // Verify basic operations work
         let circles = storage.get_all_circles().unwrap();
@@ -2589,34 +3799,44 @@ mod tests {
         assert_eq!(pragma_i64(&storage, "busy_timeout"), 2000);
     }
 
-    /// M7-B / M4 assertion (must precede any native background wake): `circles.db`
-    /// is ROLLBACK-JOURNAL, NOT WAL. The whole M7 concurrency argument
-    /// (`busy_timeout` + the process-global `write_lock`, with MDK's DB kept
-    /// pristine at `busy_timeout=0`) rests on `circles.db` NOT being WAL; if a
-    /// future change flips it to WAL this fails loudly so the reasoning cannot
-    /// silently drift.
-    ///
-    /// # MDK's `haven_mdk.db` invariant (documented, not asserted here)
+    /// `circles.db` runs in WAL mode, encrypted or not, so the foreground and
+    /// the background isolate can read while the other writes.
     ///
-    /// This assertion covers `circles.db` only. The Dark Matter MLS store
+    /// This covers `circles.db` only. The Dark Matter MLS store
     /// (`storage-sqlite`'s `session.sqlite`) is a SEPARATE database owned by the
     /// single process-global `SessionManager`; its concurrent-writer safety comes
     /// from the one `tokio::sync::Mutex<AccountDeviceSession>` (Rule 14), not from
     /// this crate's PRAGMAs — this crate never opens the MLS DB directly.
     #[test]
-    fn m7b_m4_circles_db_is_rollback_journal_not_wal() {
+    fn circles_db_is_wal() {
+        let dir = tempfile::TempDir::new().unwrap();
+        for key in [Some(test_hex_key()), None] {
+            let db_path = dir.path().join(format!("journal_{}.db", key.is_some()));
+            let storage = CircleStorage::new(&db_path, key.as_deref()).expect("create DB");
+            let mode = pragma_string(&storage, "journal_mode").to_ascii_lowercase();
+            assert_eq!(mode, "wal");
+        }
+    }
+
+    #[test]
+    fn pooled_readers_see_commits_and_cannot_write() {
         let dir = tempfile::TempDir::new().unwrap();
-        let db_path = dir.path().join("journal_mode.db");
         let storage =
-            CircleStorage::new(&db_path, Some(&test_hex_key())).expect("create encrypted DB");
-        let mode = pragma_string(&storage, "journal_mode").to_ascii_lowercase();
-        assert!(
-            mode == "delete" || mode == "truncate",
-            "circles.db MUST be rollback-journal (delete|truncate), got `{mode}`. \
-             The M7 concurrency reasoning assumes NON-WAL; do not flip journal_mode \
-             without revisiting the write_lock design."
-        );
-        assert_ne!(mode, "wal", "circles.db must not be WAL (M4 assertion)");
+            CircleStorage::new(&dir.path().join("pool.db"), Some(&test_hex_key())).unwrap();
+        assert_eq!(storage.readers.len(), CircleStorage::READ_POOL_SIZE);
+
+        // Hold one reader; the next call gets the other, not a deadlock.
+        let held = storage.reader().unwrap();
+        storage.save_contact(&create_test_contact(1)).unwrap();
+        assert!(storage
+            .get_contact(&create_test_contact(1).pubkey)
+            .unwrap()
+            .is_some());
+        assert!(held.execute("DELETE FROM contacts", []).is_err());
+        drop(held);
+
+        assert!(storage.checkpoint().unwrap());
+        assert_eq!(storage.get_all_contacts().unwrap().len(), 1);
     }
 
     #[test]
@@ -2686,6 +3906,7 @@ mod tests {
         let db_path = dir.path().join("unencrypted_test.db");
 
         let storage = CircleStorage::new(&db_path, None).expect("should create unencrypted DB");
+        assert!(!storage.is_encrypted());
         let circle = create_test_circle(1);
         storage.save_circle(&circle).unwrap();
 
@@ -2794,6 +4015,32 @@ mod tests {
         );
     }
 
+    /// A crash mid-migration can leave a partial `.db.encrypting` export next
+    /// to the still-plaintext original. The next keyed open must discard that
+    /// leftover and redo the migration from the intact original.
+    #[test]
+    fn migration_recovers_from_stale_temp_file() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let db_path = dir.path().join("interrupted_migrate.db");
+        let temp_path = db_path.with_extension("db.encrypting");
+        let key = test_hex_key();
+
+        {
+            let storage = CircleStorage::new(&db_path, None).expect("should create unencrypted DB");
+            storage.save_circle(&create_test_circle(1)).unwrap();
+            storage.save_contact(&create_test_contact(2)).unwrap();
+        }
+
+        // Simulate a half-written export from an interrupted earlier attempt.
+        std::fs::write(&temp_path, b"partial sqlcipher export").unwrap();
+
+        let storage = CircleStorage::new(&db_path, Some(&key))
+            .expect("migration should succeed despite stale temp file");
+        assert_eq!(storage.get_all_circles().unwrap().len(), 1);
+        assert_eq!(storage.get_all_contacts().unwrap().len(), 1);
+        assert!(!temp_path.exists(), "stale temp file must be gone");
+    }
+
     // ==================== Last-Known Location Tests ====================
 
     fn create_test_last_known(
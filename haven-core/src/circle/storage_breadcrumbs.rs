@@ -0,0 +1,166 @@
+//! Storage methods for local error breadcrumbs.
+//!
+//! Extends [`CircleStorage`] with the `error_breadcrumbs` ring buffer defined
+//! in [`CircleStorage::initialize_schema`]. See [`crate::diagnostics`] for
+//! what a breadcrumb may contain.
+//!
+//! Slow operations waiting in [`crate::diagnostics::slow_ops`] are written
+//! ahead of the next breadcrumb recorded, and before breadcrumbs are listed.
+
+// Single-shot SQLite ops naturally hold the lock to completion; the parent
+// module already disables this lint at the file level for storage.rs.
+#![allow(clippy::significant_drop_tightening)]
+
+use rusqlite::params;
+
+use super::error::{CircleError, Result};
+use super::storage::CircleStorage;
+use crate::diagnostics::{is_valid_slug, slow_ops, Breadcrumb, MAX_BREADCRUMBS};
+
+impl CircleStorage {
+    /// Appends a breadcrumb and evicts the oldest entries past
+    /// [`MAX_BREADCRUMBS`].
+    ///
+    /// # Errors
+    ///
+    /// Returns [`CircleError::InvalidData`] if `module` or `error_kind` is not
+    /// a valid slug, and a database error otherwise.
+    pub fn record_breadcrumb(&self, module: &str, error_kind: &str, timestamp: i64) -> Result<()> {
+        if !is_valid_slug(module) || !is_valid_slug(error_kind) {
+            return Err(CircleError::InvalidData(
+                "Breadcrumb fields must be short lowercase slugs".to_string(),
+            ));
+        }
+        self.write_breadcrumbs(Some(Breadcrumb {
+            module: module.to_string(),
+            error_kind: error_kind.to_string(),
+            timestamp,
+        }))
+    }
+
+    /// Writes pending slow operations, then `crumb`, and evicts the oldest
+    /// entries past [`MAX_BREADCRUMBS`], in one transaction.
+    fn write_breadcrumbs(&self, crumb: Option<Breadcrumb>) -> Result<()> {
+        let mut crumbs = slow_ops().take_pending();
+        crumbs.extend(crumb);
+        if crumbs.is_empty() {
+            return Ok(());
+        }
+        let mut conn = self
+            .conn()
+            .lock()
+            .map_err(|e| CircleError::Storage(format!("Failed to acquire database lock: {e}")))?;
+        let tx = conn.transaction()?;
+        for crumb in &crumbs {
+            tx.execute(
+                "INSERT INTO error_breadcrumbs (module, error_kind, created_at) VALUES (?1, ?2, ?3)",
+                params![crumb.module, crumb.error_kind, crumb.timestamp],
+            )?;
+        }
+        tx.execute(
+            "DELETE FROM error_breadcrumbs WHERE id NOT IN (
+                 SELECT id FROM error_breadcrumbs ORDER BY id DESC LIMIT ?1
+             )",
+            params![i64::try_from(MAX_BREADCRUMBS).unwrap_or(i64::MAX)],
+        )?;
+        tx.commit()?;
+        Ok(())
+    }
+
+    /// Lists stored breadcrumbs, oldest first.
+    ///
+    /// # Errors
+    ///
+    /// Returns a database error on failure.
+    pub fn list_breadcrumbs(&self) -> Result<Vec<Breadcrumb>> {
+        self.write_breadcrumbs(None)?;
+        let conn = self
+            .conn()
+            .lock()
+            .map_err(|e| CircleError::Storage(format!("Failed to acquire database lock: {e}")))?;
+        let mut stmt = conn.prepare(
+            "SELECT module, error_kind, created_at FROM error_breadcrumbs ORDER BY id ASC",
+        )?;
+        let rows = stmt.query_map([], |r| {
+            Ok(Breadcrumb {
+                module: r.get(0)?,
+                error_kind: r.get(1)?,
+                timestamp: r.get(2)?,
+            })
+        })?;
+        let mut out = Vec::new();
+        for row in rows {
+            out.push(row?);
+        }
+        Ok(out)
+    }
+
+    /// Deletes every stored breadcrumb, and drops pending slow operations.
+    ///
+    /// # Errors
+    ///
+    /// Returns a database error on failure.
+    pub fn clear_breadcrumbs(&self) -> Result<()> {
+        slow_ops().take_pending();
+        let conn = self
+            .conn()
+            .lock()
+            .map_err(|e| CircleError::Storage(format!("Failed to acquire database lock: {e}")))?;
+        conn.execute("DELETE FROM error_breadcrumbs", [])?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn make_storage() -> CircleStorage {
+        CircleStorage::in_memory().expect("in_memory")
+    }
+
+    #[test]
+    fn record_and_list_in_order() {
+        let storage = make_storage();
+        storage
+            .record_breadcrumb("circle.manager", "mls", 10)
+            .unwrap();
+        storage.record_breadcrumb("relay", "timeout", 20).unwrap();
+
+        let crumbs = storage.list_breadcrumbs().unwrap();
+        assert_eq!(crumbs.len(), 2);
+        assert_eq!(crumbs[0].module, "circle.manager");
+        assert_eq!(crumbs[0].error_kind, "mls");
+        assert_eq!(crumbs[1].timestamp, 20);
+    }
+
+    #[test]
+    fn ring_buffer_evicts_oldest() {
+        let storage = make_storage();
+        let max = i64::try_from(MAX_BREADCRUMBS).unwrap();
+        for i in 0..(max + 5) {
+            storage.record_breadcrumb("relay", "timeout", i).unwrap();
+        }
+        let crumbs = storage.list_breadcrumbs().unwrap();
+        assert_eq!(crumbs.len(), MAX_BREADCRUMBS);
+        assert_eq!(crumbs[0].timestamp, 5);
+    }
+
+    #[test]
+    fn rejects_free_form_text() {
+        let storage = make_storage();
+        assert!(matches!(
+            storage.record_breadcrumb("relay", "failed to reach wss://relay.example", 1),
+            Err(CircleError::InvalidData(_))
+        ));
+        assert!(storage.list_breadcrumbs().unwrap().is_empty());
+    }
+
+    #[test]
+    fn clear_removes_everything() {
+        let storage = make_storage();
+        storage.record_breadcrumb("relay", "timeout", 1).unwrap();
+        storage.clear_breadcrumbs().unwrap();
+        assert!(storage.list_breadcrumbs().unwrap().is_empty());
+    }
+}
@@ -0,0 +1,203 @@
+//! Storage methods for scheduled check-ins.
+//!
+//! Extends [`CircleStorage`] with the `checkin_rules` table defined in
+//! [`CircleStorage::initialize_schema`]. See [`crate::safety::schedule`] for
+//! how rules are evaluated.
+
+// Single-shot SQLite ops naturally hold the lock to completion; the parent
+// module already disables this lint at the file level for storage.rs.
+#![allow(clippy::significant_drop_tightening)]
+
+use rusqlite::{params, Row};
+
+use super::error::{CircleError, Result};
+use super::storage::CircleStorage;
+use crate::nostr::mls::types::GroupId;
+use crate::safety::CheckinRule;
+
+fn read_rule(row: &Row<'_>) -> rusqlite::Result<CheckinRule> {
+    Ok(CheckinRule {
+        rule_id: row.get(0)?,
+        mls_group_id: GroupId::new(row.get(1)?),
+        member_pubkey: row.get(2)?,
+        deadline_minute: row.get(3)?,
+        window_minutes: row.get(4)?,
+        weekdays: row.get(5)?,
+        created_at: row.get(6)?,
+        last_seen_at: row.get(7)?,
+        acknowledged_deadline: row.get(8)?,
+    })
+}
+
+impl CircleStorage {
+    /// Stores a check-in rule. Saving the same rule id again overwrites it.
+    ///
+    /// # Errors
+    ///
+    /// Returns a database error on failure.
+    pub fn save_checkin_rule(&self, rule: &CheckinRule) -> Result<()> {
+        let conn = self
+            .conn()
+            .lock()
+            .map_err(|e| CircleError::Storage(format!("Failed to acquire database lock: {e}")))?;
+        conn.execute(
+            "INSERT OR REPLACE INTO checkin_rules
+                 (rule_id, mls_group_id, member_pubkey, deadline_minute, window_minutes,
+                  weekdays, created_at, last_seen_at, acknowledged_deadline)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)",
+            params![
+                rule.rule_id,
+                rule.mls_group_id.as_slice(),
+                rule.member_pubkey,
+                rule.deadline_minute,
+                rule.window_minutes,
+                rule.weekdays,
+                rule.created_at,
+                rule.last_seen_at,
+                rule.acknowledged_deadline
+            ],
+        )?;
+        Ok(())
+    }
+
+    /// Lists check-in rules, of one circle or (with `None`) of all circles,
+    /// oldest first.
+    ///
+    /// # Errors
+    ///
+    /// Returns a database error on failure.
+    pub fn checkin_rules(&self, mls_group_id: Option<&GroupId>) -> Result<Vec<CheckinRule>> {
+        let conn = self
+            .conn()
+            .lock()
+            .map_err(|e| CircleError::Storage(format!("Failed to acquire database lock: {e}")))?;
+        let mut stmt = conn.prepare(
+            "SELECT rule_id, mls_group_id, member_pubkey, deadline_minute, window_minutes,
+                    weekdays, created_at, last_seen_at, acknowledged_deadline
+             FROM checkin_rules
+             WHERE ?1 IS NULL OR mls_group_id = ?1
+             ORDER BY created_at ASC, rule_id ASC",
+        )?;
+        let rows = stmt.query_map(params![mls_group_id.map(GroupId::as_slice)], read_rule)?;
+        Ok(rows.collect::<rusqlite::Result<Vec<_>>>()?)
+    }
+
+    /// Deletes a check-in rule. Returns whether it existed.
+    ///
+    /// # Errors
+    ///
+    /// Returns a database error on failure.
+    pub fn delete_checkin_rule(&self, rule_id: &str) -> Result<bool> {
+        let conn = self
+            .conn()
+            .lock()
+            .map_err(|e| CircleError::Storage(format!("Failed to acquire database lock: {e}")))?;
+        let deleted = conn.execute(
+            "DELETE FROM checkin_rules WHERE rule_id = ?1",
+            params![rule_id],
+        )?;
+        Ok(deleted > 0)
+    }
+
+    /// Records that a location `member_pubkey` took at `seen_at` arrived in a
+    /// circle. Only ever moves a rule's `last_seen_at` forward.
+    ///
+    /// # Errors
+    ///
+    /// Returns a database error on failure.
+    pub fn note_checkin_seen(
+        &self,
+        mls_group_id: &GroupId,
+        member_pubkey: &str,
+        seen_at: i64,
+    ) -> Result<()> {
+        let conn = self
+            .conn()
+            .lock()
+            .map_err(|e| CircleError::Storage(format!("Failed to acquire database lock: {e}")))?;
+        conn.execute(
+            "UPDATE checkin_rules
+             SET last_seen_at = ?3
+             WHERE mls_group_id = ?1 AND member_pubkey = ?2
+               AND (last_seen_at IS NULL OR last_seen_at < ?3)",
+            params![mls_group_id.as_slice(), member_pubkey, seen_at],
+        )?;
+        Ok(())
+    }
+
+    /// Acknowledges a rule's missed check-ins up to `deadline_at`. Returns
+    /// whether the rule exists.
+    ///
+    /// # Errors
+    ///
+    /// Returns a database error on failure.
+    pub fn acknowledge_checkin(&self, rule_id: &str, deadline_at: i64) -> Result<bool> {
+        let conn = self
+            .conn()
+            .lock()
+            .map_err(|e| CircleError::Storage(format!("Failed to acquire database lock: {e}")))?;
+        let updated = conn.execute(
+            "UPDATE checkin_rules
+             SET acknowledged_deadline = MAX(COALESCE(acknowledged_deadline, ?2), ?2)
+             WHERE rule_id = ?1",
+            params![rule_id, deadline_at],
+        )?;
+        Ok(updated > 0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::nostr::mls::types::GroupIdExt;
+    use crate::safety::EVERY_DAY;
+
+    #[test]
+    fn rules_track_the_latest_sighting_and_go_with_the_member() {
+        let storage = CircleStorage::in_memory().unwrap();
+        let gid = GroupId::from_slice(&[1; 32]);
+        let other = GroupId::from_slice(&[2; 32]);
+        let rule =
+            CheckinRule::new(gid.clone(), "bob".to_string(), 1_320, 120, EVERY_DAY, 100).unwrap();
+        storage.save_checkin_rule(&rule).unwrap();
+        storage
+            .save_checkin_rule(
+                &CheckinRule::new(other.clone(), "bob".to_string(), 480, 60, EVERY_DAY, 200)
+                    .unwrap(),
+            )
+            .unwrap();
+        assert_eq!(storage.checkin_rules(None).unwrap().len(), 2);
+        assert_eq!(
+            storage.checkin_rules(Some(&gid)).unwrap(),
+            vec![rule.clone()]
+        );
+
+        storage.note_checkin_seen(&gid, "bob", 500).unwrap();
+        storage.note_checkin_seen(&gid, "bob", 400).unwrap();
+        storage.note_checkin_seen(&gid, "carol", 900).unwrap();
+        let stored = &storage.checkin_rules(Some(&gid)).unwrap()[0];
+        assert_eq!(stored.last_seen_at, Some(500));
+        assert_eq!(
+            storage.checkin_rules(Some(&other)).unwrap()[0].last_seen_at,
+            None
+        );
+
+        assert!(storage.acknowledge_checkin(&rule.rule_id, 700).unwrap());
+        assert!(storage.acknowledge_checkin(&rule.rule_id, 600).unwrap());
+        assert!(!storage.acknowledge_checkin("missing", 600).unwrap());
+        let stored = &storage.checkin_rules(Some(&gid)).unwrap()[0];
+        assert_eq!(stored.acknowledged_deadline, Some(700));
+
+        storage
+            .reconcile_roster(&gid, &["alice".to_string(), "bob".to_string()])
+            .unwrap();
+        storage
+            .reconcile_roster(&gid, &["alice".to_string()])
+            .unwrap();
+        assert!(storage.checkin_rules(Some(&gid)).unwrap().is_empty());
+        assert!(storage
+            .delete_checkin_rule(&storage.checkin_rules(None).unwrap()[0].rule_id)
+            .unwrap());
+        assert!(storage.checkin_rules(None).unwrap().is_empty());
+    }
+}
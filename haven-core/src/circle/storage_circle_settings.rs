@@ -0,0 +1,371 @@
+//! Storage methods for per-circle location sharing overrides.
+//!
+//! Extends [`CircleStorage`] with the `circle_settings`, `trip_modes` and
+//! `precise_sessions` tables defined in [`CircleStorage::initialize_schema`].
+
+// Single-shot SQLite ops naturally hold the lock to completion; the parent
+// module already disables this lint at the file level for storage.rs.
+#![allow(clippy::significant_drop_tightening)]
+
+use rusqlite::{params, OptionalExtension};
+
+use super::error::{CircleError, Result};
+use super::storage::CircleStorage;
+use super::types::{CircleLocationSettings, SharingSession, TripMode};
+use crate::location::{LocationPrecision, ShareExpiration};
+use crate::nostr::mls::types::{GroupId, GroupIdExt};
+
+impl CircleStorage {
+    /// Returns the location sharing overrides for a circle, if any are set.
+    ///
+    /// # Errors
+    ///
+    /// Returns a database error on failure.
+    pub fn get_circle_location_settings(
+        &self,
+        mls_group_id: &GroupId,
+    ) -> Result<Option<CircleLocationSettings>> {
+        let conn = self
+            .conn()
+            .lock()
+            .map_err(|e| CircleError::Storage(format!("Failed to acquire database lock: {e}")))?;
+        let row: Option<(String, i64, Option<i64>)> = conn
+            .query_row(
+                "SELECT precision, share_expiration_secs, display_max_age_secs
+                 FROM circle_settings WHERE mls_group_id = ?1",
+                params![mls_group_id.as_slice()],
+                |r| Ok((r.get(0)?, r.get(1)?, r.get(2)?)),
+            )
+            .optional()?;
+        let Some((precision, secs, display_max_age)) = row else {
+            return Ok(None);
+        };
+        let precision = LocationPrecision::parse(&precision).ok_or_else(|| {
+            CircleError::Storage(format!("Unknown circle precision: {precision}"))
+        })?;
+        let secs = u64::try_from(secs)
+            .map_err(|_| CircleError::Storage("Negative share expiration".to_string()))?;
+        let display_max_age_secs = display_max_age
+            .map(|v| {
+                u64::try_from(v)
+                    .map_err(|_| CircleError::Storage("Negative display threshold".to_string()))
+            })
+            .transpose()?;
+        Ok(Some(CircleLocationSettings {
+            precision,
+            share_expiration: ShareExpiration::from_secs(secs),
+            display_max_age_secs,
+        }))
+    }
+
+    /// Stores the location sharing overrides for a circle, replacing any
+    /// previous ones.
+    ///
+    /// # Errors
+    ///
+    /// Returns a database error on failure.
+    pub fn set_circle_location_settings(
+        &self,
+        mls_group_id: &GroupId,
+        settings: &CircleLocationSettings,
+        now: i64,
+    ) -> Result<()> {
+        let conn = self
+            .conn()
+            .lock()
+            .map_err(|e| CircleError::Storage(format!("Failed to acquire database lock: {e}")))?;
+        // `as_secs` is clamped to at most one day, so it fits in i64.
+        let secs = i64::try_from(settings.share_expiration.as_secs()).unwrap_or(i64::MAX);
+        let display_max_age = settings
+            .display_max_age_secs
+            .map(|v| i64::try_from(v).unwrap_or(i64::MAX));
+        conn.execute(
+            "INSERT INTO circle_settings
+                 (mls_group_id, precision, share_expiration_secs, updated_at,
+                  display_max_age_secs)
+             VALUES (?1, ?2, ?3, ?4, ?5)
+             ON CONFLICT(mls_group_id) DO UPDATE SET
+                 precision = excluded.precision,
+                 share_expiration_secs = excluded.share_expiration_secs,
+                 updated_at = excluded.updated_at,
+                 display_max_age_secs = excluded.display_max_age_secs",
+            params![
+                mls_group_id.as_slice(),
+                settings.precision.as_str(),
+                secs,
+                now,
+                display_max_age
+            ],
+        )?;
+        Ok(())
+    }
+
+    /// Returns the circle's trip mode if it is still running at `now`.
+    ///
+    /// A lapsed trip mode is deleted first, so it is never returned even if
+    /// nothing ended it explicitly.
+    ///
+    /// # Errors
+    ///
+    /// Returns a database error on failure.
+    pub fn get_active_trip_mode(
+        &self,
+        mls_group_id: &GroupId,
+        now: i64,
+    ) -> Result<Option<TripMode>> {
+        let conn = self
+            .conn()
+            .lock()
+            .map_err(|e| CircleError::Storage(format!("Failed to acquire database lock: {e}")))?;
+        conn.execute(
+            "DELETE FROM trip_modes WHERE mls_group_id = ?1 AND ends_at <= ?2",
+            params![mls_group_id.as_slice(), now],
+        )?;
+        let row: Option<(i64, i64, i64)> = conn
+            .query_row(
+                "SELECT update_interval_secs, share_expiration_secs, ends_at
+                 FROM trip_modes WHERE mls_group_id = ?1",
+                params![mls_group_id.as_slice()],
+                |r| Ok((r.get(0)?, r.get(1)?, r.get(2)?)),
+            )
+            .optional()?;
+        let Some((interval, secs, ends_at)) = row else {
+            return Ok(None);
+        };
+        let to_u64 = |v: i64| {
+            u64::try_from(v)
+                .map_err(|_| CircleError::Storage("Negative trip mode value".to_string()))
+        };
+        Ok(Some(TripMode {
+            update_interval_secs: to_u64(interval)?,
+            share_expiration: ShareExpiration::from_secs(to_u64(secs)?),
+            ends_at,
+        }))
+    }
+
+    /// Starts (or replaces) a circle's trip mode.
+    ///
+    /// # Errors
+    ///
+    /// Returns a database error on failure.
+    pub fn set_trip_mode(&self, mls_group_id: &GroupId, trip: &TripMode) -> Result<()> {
+        let conn = self
+            .conn()
+            .lock()
+            .map_err(|e| CircleError::Storage(format!("Failed to acquire database lock: {e}")))?;
+        // Both are clamped to at most one day by the caller, so they fit in i64.
+        let interval = i64::try_from(trip.update_interval_secs).unwrap_or(i64::MAX);
+        let secs = i64::try_from(trip.share_expiration.as_secs()).unwrap_or(i64::MAX);
+        conn.execute(
+            "INSERT INTO trip_modes
+                 (mls_group_id, update_interval_secs, share_expiration_secs, ends_at)
+             VALUES (?1, ?2, ?3, ?4)
+             ON CONFLICT(mls_group_id) DO UPDATE SET
+                 update_interval_secs = excluded.update_interval_secs,
+                 share_expiration_secs = excluded.share_expiration_secs,
+                 ends_at = excluded.ends_at",
+            params![mls_group_id.as_slice(), interval, secs, trip.ends_at],
+        )?;
+        Ok(())
+    }
+
+    /// Ends a circle's trip mode early. Returns whether one was running.
+    ///
+    /// # Errors
+    ///
+    /// Returns a database error on failure.
+    pub fn delete_trip_mode(&self, mls_group_id: &GroupId) -> Result<bool> {
+        let conn = self
+            .conn()
+            .lock()
+            .map_err(|e| CircleError::Storage(format!("Failed to acquire database lock: {e}")))?;
+        let removed = conn.execute(
+            "DELETE FROM trip_modes WHERE mls_group_id = ?1",
+            params![mls_group_id.as_slice()],
+        )?;
+        Ok(removed > 0)
+    }
+
+    /// Starts (or restarts) a precise-sharing session for a circle.
+    ///
+    /// # Errors
+    ///
+    /// Returns a database error on failure.
+    pub fn set_precise_session(&self, session: &SharingSession) -> Result<()> {
+        let conn = self
+            .conn()
+            .lock()
+            .map_err(|e| CircleError::Storage(format!("Failed to acquire database lock: {e}")))?;
+        conn.execute(
+            "INSERT INTO precise_sessions (mls_group_id, started_at, ends_at)
+             VALUES (?1, ?2, ?3)
+             ON CONFLICT(mls_group_id) DO UPDATE SET
+                 started_at = excluded.started_at,
+                 ends_at = excluded.ends_at",
+            params![
+                session.mls_group_id.as_slice(),
+                session.started_at,
+                session.ends_at
+            ],
+        )?;
+        Ok(())
+    }
+
+    /// Ends a circle's precise-sharing session early. Returns whether one was
+    /// running.
+    ///
+    /// # Errors
+    ///
+    /// Returns a database error on failure.
+    pub fn delete_precise_session(&self, mls_group_id: &GroupId) -> Result<bool> {
+        let conn = self
+            .conn()
+            .lock()
+            .map_err(|e| CircleError::Storage(format!("Failed to acquire database lock: {e}")))?;
+        let removed = conn.execute(
+            "DELETE FROM precise_sessions WHERE mls_group_id = ?1",
+            params![mls_group_id.as_slice()],
+        )?;
+        Ok(removed > 0)
+    }
+
+    /// Lists precise-sharing sessions still running at `now`, soonest to end
+    /// first.
+    ///
+    /// Lapsed sessions are deleted first, so one is never returned even if
+    /// nothing ended it explicitly.
+    ///
+    /// # Errors
+    ///
+    /// Returns a database error on failure.
+    pub fn list_active_precise_sessions(&self, now: i64) -> Result<Vec<SharingSession>> {
+        let conn = self
+            .conn()
+            .lock()
+            .map_err(|e| CircleError::Storage(format!("Failed to acquire database lock: {e}")))?;
+        conn.execute(
+            "DELETE FROM precise_sessions WHERE ends_at <= ?1",
+            params![now],
+        )?;
+        let mut stmt = conn.prepare(
+            "SELECT mls_group_id, started_at, ends_at FROM precise_sessions
+             ORDER BY ends_at ASC",
+        )?;
+        let rows = stmt.query_map([], |r| {
+            Ok(SharingSession {
+                mls_group_id: GroupId::from_slice(&r.get::<_, Vec<u8>>(0)?),
+                started_at: r.get(1)?,
+                ends_at: r.get(2)?,
+            })
+        })?;
+        Ok(rows.collect::<std::result::Result<Vec<_>, _>>()?)
+    }
+
+    /// Whether a precise-sharing session is running for a circle at `now`.
+    ///
+    /// # Errors
+    ///
+    /// Returns a database error on failure.
+    pub fn has_active_precise_session(&self, mls_group_id: &GroupId, now: i64) -> Result<bool> {
+        let conn = self
+            .conn()
+            .lock()
+            .map_err(|e| CircleError::Storage(format!("Failed to acquire database lock: {e}")))?;
+        let found = conn
+            .query_row(
+                "SELECT 1 FROM precise_sessions WHERE mls_group_id = ?1 AND ends_at > ?2",
+                params![mls_group_id.as_slice(), now],
+                |_| Ok(()),
+            )
+            .optional()?;
+        Ok(found.is_some())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn settings_roundtrip_per_circle() {
+        let storage = CircleStorage::in_memory().expect("in_memory");
+        let a = GroupId::from_slice(&[1; 32]);
+        let b = GroupId::from_slice(&[2; 32]);
+        assert!(storage.get_circle_location_settings(&a).unwrap().is_none());
+
+        let settings = CircleLocationSettings {
+            precision: LocationPrecision::Approximate,
+            share_expiration: ShareExpiration::EightHours,
+            display_max_age_secs: None,
+        };
+        storage
+            .set_circle_location_settings(&a, &settings, 1)
+            .unwrap();
+        assert_eq!(
+            storage.get_circle_location_settings(&a).unwrap(),
+            Some(settings)
+        );
+        assert!(storage.get_circle_location_settings(&b).unwrap().is_none());
+
+        let custom = CircleLocationSettings {
+            precision: LocationPrecision::Coarse,
+            share_expiration: ShareExpiration::Custom(7_200),
+            display_max_age_secs: Some(600),
+        };
+        storage
+            .set_circle_location_settings(&a, &custom, 2)
+            .unwrap();
+        assert_eq!(
+            storage.get_circle_location_settings(&a).unwrap(),
+            Some(custom)
+        );
+    }
+
+    #[test]
+    fn trip_mode_reverts_after_it_ends() {
+        let storage = CircleStorage::in_memory().expect("in_memory");
+        let gid = GroupId::from_slice(&[3; 32]);
+        let trip = TripMode {
+            update_interval_secs: 60,
+            share_expiration: ShareExpiration::Day,
+            ends_at: 1_000,
+        };
+        storage.set_trip_mode(&gid, &trip).unwrap();
+        assert_eq!(storage.get_active_trip_mode(&gid, 999).unwrap(), Some(trip));
+        assert!(storage.get_active_trip_mode(&gid, 1_000).unwrap().is_none());
+        // The lapsed row is gone, not just hidden.
+        assert!(!storage.delete_trip_mode(&gid).unwrap());
+
+        storage.set_trip_mode(&gid, &trip).unwrap();
+        assert!(storage.delete_trip_mode(&gid).unwrap());
+        assert!(storage.get_active_trip_mode(&gid, 0).unwrap().is_none());
+    }
+
+    #[test]
+    fn precise_sessions_expire_by_themselves() {
+        let storage = CircleStorage::in_memory().expect("in_memory");
+        let a = GroupId::from_slice(&[1; 32]);
+        let b = GroupId::from_slice(&[2; 32]);
+        for (gid, ends_at) in [(&a, 200), (&b, 100)] {
+            storage
+                .set_precise_session(&SharingSession {
+                    mls_group_id: gid.clone(),
+                    started_at: 0,
+                    ends_at,
+                })
+                .unwrap();
+        }
+        let active = storage.list_active_precise_sessions(50).unwrap();
+        assert_eq!(active.len(), 2);
+        assert_eq!(active[0].mls_group_id, b);
+
+        assert!(!storage.has_active_precise_session(&b, 100).unwrap());
+        let active = storage.list_active_precise_sessions(100).unwrap();
+        assert_eq!(active.len(), 1);
+        assert_eq!(active[0].mls_group_id, a);
+        assert!(!storage.delete_precise_session(&b).unwrap());
+
+        assert!(storage.delete_precise_session(&a).unwrap());
+        assert!(!storage.has_active_precise_session(&a, 0).unwrap());
+    }
+}
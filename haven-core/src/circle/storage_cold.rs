@@ -0,0 +1,398 @@
+//! Storage methods for cold (frozen) circles.
+//!
+//! Extends [`CircleStorage`] with the `cold_circles` table defined in
+//! [`CircleStorage::initialize_schema`]. See [`super::cold_storage`] for the
+//! blob format and what is frozen.
+
+// Single-shot SQLite ops naturally hold the lock to completion; the parent
+// module already disables this lint at the file level for storage.rs.
+#![allow(clippy::significant_drop_tightening)]
+
+use rusqlite::{params, OptionalExtension};
+
+use super::cold_storage::{self, ColdCircleInfo, ColdLocationRow, ColdPrecisionRow, ColdSnapshot};
+use super::error::{CircleError, Result};
+use super::storage::CircleStorage;
+use crate::nostr::mls::types::GroupId;
+
+impl CircleStorage {
+    /// Moves a circle's last-known locations and precision baselines into a
+    /// sealed blob and deletes them from the hot tables, in one transaction.
+    /// The circle's meet pins are dropped.
+    ///
+    /// Returns `None` if the circle is already frozen.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`CircleError::NotFound`] if the circle is unknown, or a
+    /// database / sealing error.
+    pub fn freeze_circle(
+        &self,
+        mls_group_id: &GroupId,
+        key: &[u8; 32],
+        now: i64,
+    ) -> Result<Option<ColdCircleInfo>> {
+        let mut conn = self
+            .conn()
+            .lock()
+            .map_err(|e| CircleError::Storage(format!("Failed to acquire database lock: {e}")))?;
+        let tx = conn.transaction()?;
+        let gid = mls_group_id.as_slice();
+
+        let ngid: Vec<u8> = tx
+            .query_row(
+                "SELECT nostr_group_id FROM circles WHERE mls_group_id = ?1",
+                params![gid],
+                |r| r.get(0),
+            )
+            .optional()?
+            .ok_or_else(|| CircleError::NotFound("Circle not found: <redacted>".to_string()))?;
+        let frozen: bool = tx.query_row(
+            "SELECT EXISTS(SELECT 1 FROM cold_circles WHERE mls_group_id = ?1)",
+            params![gid],
+            |r| r.get(0),
+        )?;
+        if frozen {
+            return Ok(None);
+        }
+
+        let locations = {
+            let mut stmt = tx.prepare(
+                "SELECT sender_pubkey, latitude, longitude, geohash, display_name,
+                        timestamp, expires_at, purge_after, updated_at
+                 FROM last_known_locations
+                 WHERE nostr_group_id = ?1 AND purge_after >= ?2",
+            )?;
+            let rows = stmt.query_map(params![ngid, now], |r| {
+                Ok(ColdLocationRow {
+                    sender_pubkey: r.get(0)?,
+                    latitude: r.get(1)?,
+                    longitude: r.get(2)?,
+                    geohash: r.get(3)?,
+                    display_name: r.get(4)?,
+                    timestamp: r.get(5)?,
+                    expires_at: r.get(6)?,
+                    purge_after: r.get(7)?,
+                    updated_at: r.get(8)?,
+                })
+            })?;
+            rows.collect::<std::result::Result<Vec<_>, _>>()?
+        };
+        let precision = {
+            let mut stmt = tx.prepare(
+                "SELECT sender_pubkey, class, samples, updated_at
+                 FROM precision_baselines WHERE mls_group_id = ?1",
+            )?;
+            let rows = stmt.query_map(params![gid], |r| {
+                Ok(ColdPrecisionRow {
+                    sender_pubkey: r.get(0)?,
+                    class: r.get(1)?,
+                    samples: r.get(2)?,
+                    updated_at: r.get(3)?,
+                })
+            })?;
+            rows.collect::<std::result::Result<Vec<_>, _>>()?
+        };
+        let snapshot = ColdSnapshot {
+            locations,
+            precision,
+        };
+
+        let (blob, raw_bytes) = cold_storage::seal(&snapshot, key, gid)?;
+        let info = ColdCircleInfo {
+            row_count: u64::try_from(snapshot.row_count()).unwrap_or(u64::MAX),
+            raw_bytes: u64::try_from(raw_bytes).unwrap_or(u64::MAX),
+            sealed_bytes: u64::try_from(blob.len()).unwrap_or(u64::MAX),
+            frozen_at: now,
+        };
+        tx.execute(
+            "INSERT INTO cold_circles
+                 (mls_group_id, blob, row_count, raw_bytes, purge_after, frozen_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+            params![
+                gid,
+                blob,
+                i64::try_from(info.row_count).unwrap_or(i64::MAX),
+                i64::try_from(info.raw_bytes).unwrap_or(i64::MAX),
+                snapshot.purge_after(),
+                now
+            ],
+        )?;
+        tx.execute(
+            "DELETE FROM last_known_locations WHERE nostr_group_id = ?1",
+            params![ngid],
+        )?;
+        tx.execute(
+            "DELETE FROM location_history WHERE nostr_group_id = ?1",
+            params![ngid],
+        )?;
+        tx.execute(
+            "DELETE FROM precision_baselines WHERE mls_group_id = ?1",
+            params![gid],
+        )?;
+        tx.execute(
+            "DELETE FROM meet_pins WHERE mls_group_id = ?1",
+            params![gid],
+        )?;
+        tx.commit()?;
+        Ok(Some(info))
+    }
+
+    /// Restores a frozen circle's rows into the hot tables and deletes the
+    /// blob, in one transaction.
+    ///
+    /// Locations whose `purge_after` passed while frozen are skipped. A
+    /// location that arrived while the circle was frozen is kept if it is
+    /// newer than the frozen one, and a baseline re-learned while frozen wins
+    /// over the frozen baseline.
+    ///
+    /// Returns `false` if the circle is not frozen.
+    ///
+    /// # Errors
+    ///
+    /// Returns a database error, or [`CircleError::Storage`] if the blob
+    /// cannot be opened with `key` (it is then left in place).
+    pub fn thaw_circle(&self, mls_group_id: &GroupId, key: &[u8; 32], now: i64) -> Result<bool> {
+        let mut conn = self
+            .conn()
+            .lock()
+            .map_err(|e| CircleError::Storage(format!("Failed to acquire database lock: {e}")))?;
+        let tx = conn.transaction()?;
+        let gid = mls_group_id.as_slice();
+
+        let Some(blob) = tx
+            .query_row(
+                "SELECT blob FROM cold_circles WHERE mls_group_id = ?1",
+                params![gid],
+                |r| r.get::<_, Vec<u8>>(0),
+            )
+            .optional()?
+        else {
+            return Ok(false);
+        };
+        let snapshot = cold_storage::open(&blob, key, gid)?;
+        let ngid: Option<Vec<u8>> = tx
+            .query_row(
+                "SELECT nostr_group_id FROM circles WHERE mls_group_id = ?1",
+                params![gid],
+                |r| r.get(0),
+            )
+            .optional()?;
+
+        if let Some(ngid) = ngid {
+            for row in snapshot.locations.iter().filter(|r| r.purge_after >= now) {
+                tx.execute(
+                    "INSERT INTO last_known_locations
+                         (nostr_group_id, sender_pubkey, latitude, longitude, geohash,
+                          display_name, timestamp, expires_at, purge_after, updated_at)
+                     VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10)
+                     ON CONFLICT(nostr_group_id, sender_pubkey) DO UPDATE SET
+                         latitude     = excluded.latitude,
+                         longitude    = excluded.longitude,
+                         geohash      = excluded.geohash,
+                         display_name = excluded.display_name,
+                         timestamp    = excluded.timestamp,
+                         expires_at   = excluded.expires_at,
+                         purge_after  = excluded.purge_after,
+                         updated_at   = excluded.updated_at
+                     WHERE excluded.timestamp > last_known_locations.timestamp",
+                    params![
+                        ngid,
+                        row.sender_pubkey,
+                        row.latitude,
+                        row.longitude,
+                        row.geohash,
+                        row.display_name,
+                        row.timestamp,
+                        row.expires_at,
+                        row.purge_after,
+                        row.updated_at
+                    ],
+                )?;
+            }
+        }
+        for row in &snapshot.precision {
+            tx.execute(
+                "INSERT INTO precision_baselines
+                     (mls_group_id, sender_pubkey, class, samples, updated_at)
+                 VALUES (?1, ?2, ?3, ?4, ?5)
+                 ON CONFLICT(mls_group_id, sender_pubkey) DO NOTHING",
+                params![
+                    gid,
+                    row.sender_pubkey,
+                    row.class,
+                    row.samples,
+                    row.updated_at
+                ],
+            )?;
+        }
+        tx.execute(
+            "DELETE FROM cold_circles WHERE mls_group_id = ?1",
+            params![gid],
+        )?;
+        tx.commit()?;
+        Ok(true)
+    }
+
+    /// Returns the summary of a frozen circle, or `None` if it is not frozen.
+    ///
+    /// # Errors
+    ///
+    /// Returns a database error on failure.
+    pub fn cold_circle_info(&self, mls_group_id: &GroupId) -> Result<Option<ColdCircleInfo>> {
+        let conn = self
+            .conn()
+            .lock()
+            .map_err(|e| CircleError::Storage(format!("Failed to acquire database lock: {e}")))?;
+        Ok(conn
+            .query_row(
+                "SELECT row_count, raw_bytes, length(blob), frozen_at
+                 FROM cold_circles WHERE mls_group_id = ?1",
+                params![mls_group_id.as_slice()],
+                |r| {
+                    Ok(ColdCircleInfo {
+                        row_count: u64::try_from(r.get::<_, i64>(0)?).unwrap_or_default(),
+                        raw_bytes: u64::try_from(r.get::<_, i64>(1)?).unwrap_or_default(),
+                        sealed_bytes: u64::try_from(r.get::<_, i64>(2)?).unwrap_or_default(),
+                        frozen_at: r.get(3)?,
+                    })
+                },
+            )
+            .optional()?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::circle::types::{Circle, CircleType, LastKnownLocation};
+    use crate::location::PrecisionClass;
+
+    const KEY: [u8; 32] = [9; 32];
+
+    fn setup() -> (CircleStorage, GroupId) {
+        let storage = CircleStorage::in_memory().expect("in_memory");
+        let gid = GroupId::from_slice(&[1; 32]);
+        storage
+            .save_circle(&Circle {
+                mls_group_id: gid.clone(),
+                nostr_group_id: [1; 32],
+                display_name: "Summer".to_string(),
+                circle_type: CircleType::LocationSharing,
+                relays: vec![],
+                created_at: 0,
+                updated_at: 0,
+            })
+            .unwrap();
+        (storage, gid)
+    }
+
+    fn location(sender: &str, timestamp: i64, purge_after: i64) -> LastKnownLocation {
+        LastKnownLocation {
+            nostr_group_id: [1; 32],
+            sender_pubkey: sender.to_string(),
+            latitude: 43.7,
+            longitude: 7.26,
+            geohash: "spv2bd".to_string(),
+            display_name: None,
+            timestamp,
+            expires_at: timestamp + 60,
+            purge_after,
+            updated_at: timestamp,
+        }
+    }
+
+    fn hot_senders(storage: &CircleStorage) -> Vec<String> {
+        let mut senders: Vec<_> = storage
+            .snapshot_last_known_for_circle(&[1; 32], 0)
+            .unwrap()
+            .into_iter()
+            .map(|l| l.sender_pubkey)
+            .collect();
+        senders.sort();
+        senders
+    }
+
+    #[test]
+    fn freeze_moves_rows_out_and_thaw_restores_them() {
+        let (storage, gid) = setup();
+        storage
+            .upsert_last_known_location(&location("alice", 100, 10_000))
+            .unwrap();
+        storage
+            .upsert_last_known_location(&location("bob", 100, 10_000))
+            .unwrap();
+        storage
+            .observe_precision(&gid, "alice", PrecisionClass::Approximate, 100)
+            .unwrap();
+
+        let info = storage.freeze_circle(&gid, &KEY, 200).unwrap().unwrap();
+        assert_eq!(info.row_count, 3);
+        assert_eq!(info.frozen_at, 200);
+        assert!(hot_senders(&storage).is_empty());
+        assert!(storage
+            .get_precision_baseline(&gid, "alice")
+            .unwrap()
+            .is_none());
+        assert_eq!(storage.cold_circle_info(&gid).unwrap(), Some(info));
+        assert!(storage.freeze_circle(&gid, &KEY, 300).unwrap().is_none());
+
+        assert!(storage.thaw_circle(&gid, &KEY, 300).unwrap());
+        assert_eq!(hot_senders(&storage), ["alice", "bob"]);
+        assert!(storage
+            .get_precision_baseline(&gid, "alice")
+            .unwrap()
+            .is_some());
+        assert!(storage.cold_circle_info(&gid).unwrap().is_none());
+        assert!(!storage.thaw_circle(&gid, &KEY, 300).unwrap());
+    }
+
+    #[test]
+    fn thaw_keeps_newer_rows_and_skips_lapsed_ones() {
+        let (storage, gid) = setup();
+        storage
+            .upsert_last_known_location(&location("alice", 100, 10_000))
+            .unwrap();
+        storage
+            .upsert_last_known_location(&location("bob", 100, 500))
+            .unwrap();
+        storage.freeze_circle(&gid, &KEY, 200).unwrap();
+
+        // A location that arrived while frozen is newer than the frozen one.
+        let fresh = location("alice", 900, 20_000);
+        storage.upsert_last_known_location(&fresh).unwrap();
+
+        storage.thaw_circle(&gid, &KEY, 1_000).unwrap();
+        let rows = storage.snapshot_last_known_for_circle(&[1; 32], 0).unwrap();
+        assert_eq!(rows.len(), 1, "bob lapsed while frozen");
+        assert_eq!(rows[0].timestamp, 900);
+    }
+
+    #[test]
+    fn wrong_key_leaves_the_blob_in_place() {
+        let (storage, gid) = setup();
+        storage
+            .upsert_last_known_location(&location("alice", 100, 10_000))
+            .unwrap();
+        storage.freeze_circle(&gid, &KEY, 200).unwrap();
+        assert!(storage.thaw_circle(&gid, &[0; 32], 300).is_err());
+        assert!(storage.cold_circle_info(&gid).unwrap().is_some());
+    }
+
+    #[test]
+    fn cold_blob_is_pruned_and_wiped() {
+        let (storage, gid) = setup();
+        storage
+            .upsert_last_known_location(&location("alice", 100, 500))
+            .unwrap();
+        storage.freeze_circle(&gid, &KEY, 200).unwrap();
+        storage.prune_expired_last_known(400).unwrap();
+        assert!(storage.cold_circle_info(&gid).unwrap().is_some());
+        storage.prune_expired_last_known(600).unwrap();
+        assert!(storage.cold_circle_info(&gid).unwrap().is_none());
+
+        storage.freeze_circle(&gid, &KEY, 700).unwrap();
+        storage.delete_circle(&gid).unwrap();
+        assert!(storage.cold_circle_info(&gid).unwrap().is_none());
+    }
+}
@@ -0,0 +1,132 @@
+//! Storage for [`crate::config::HavenConfig`] settings with no table of
+//! their own.
+//!
+//! Extends [`CircleStorage`] with `user_settings` rows. Relay, blacklist and
+//! key-audit settings keep their existing keys; only the location defaults
+//! and the privacy settings live here.
+
+// Single-shot SQLite ops naturally hold the lock to completion; the parent
+// module already disables this lint at the file level for storage.rs.
+#![allow(clippy::significant_drop_tightening)]
+
+use rand::rngs::OsRng;
+use rand::RngCore;
+use rusqlite::params;
+
+use super::error::{CircleError, Result};
+use super::storage::CircleStorage;
+use crate::location::LocationSettings;
+use crate::privacy::PrivacySettings;
+
+/// `user_settings` key holding the location defaults (JSON).
+const LOCATION_DEFAULTS_KEY: &str = "location_defaults";
+
+/// `user_settings` key holding the privacy settings (JSON).
+const PRIVACY_SETTINGS_KEY: &str = "privacy_settings";
+
+/// `user_settings` key holding the coordinate jitter seed (hex).
+const JITTER_SEED_KEY: &str = "coordinate_jitter_seed";
+
+impl CircleStorage {
+    /// Returns the stored location defaults, or `None` if none were saved.
+    ///
+    /// # Errors
+    ///
+    /// Returns a database error on failure, or [`CircleError::InvalidData`]
+    /// if the stored value does not parse.
+    pub fn get_location_defaults(&self) -> Result<Option<LocationSettings>> {
+        self.get_setting(LOCATION_DEFAULTS_KEY)?
+            .map(|json| {
+                serde_json::from_str(&json).map_err(|e| {
+                    CircleError::InvalidData(format!("Invalid location defaults: {e}"))
+                })
+            })
+            .transpose()
+    }
+
+    /// Saves the location defaults.
+    ///
+    /// # Errors
+    ///
+    /// Returns a database error on failure.
+    pub fn set_location_defaults(&self, settings: &LocationSettings) -> Result<()> {
+        let json = serde_json::to_string(settings).map_err(|e| {
+            CircleError::InvalidData(format!("Failed to encode location defaults: {e}"))
+        })?;
+        let conn = self
+            .conn()
+            .lock()
+            .map_err(|e| CircleError::Storage(format!("Failed to acquire database lock: {e}")))?;
+        conn.execute(
+            "INSERT INTO user_settings (key, value) VALUES (?1, ?2)
+             ON CONFLICT(key) DO UPDATE SET value = excluded.value",
+            params![LOCATION_DEFAULTS_KEY, json],
+        )?;
+        Ok(())
+    }
+
+    /// Returns the device's secret seed for
+    /// [`crate::location::precision::jitter`], creating it on first use.
+    ///
+    /// # Errors
+    ///
+    /// Returns a database error on failure, or [`CircleError::InvalidData`]
+    /// if the stored value does not parse.
+    pub fn coordinate_jitter_seed(&self) -> Result<[u8; 32]> {
+        if self.get_setting(JITTER_SEED_KEY)?.is_none() {
+            let mut seed = [0u8; 32];
+            OsRng.fill_bytes(&mut seed);
+            let conn = self.conn().lock().map_err(|e| {
+                CircleError::Storage(format!("Failed to acquire database lock: {e}"))
+            })?;
+            // A seed written concurrently wins; it is read back below.
+            conn.execute(
+                "INSERT INTO user_settings (key, value) VALUES (?1, ?2)
+                 ON CONFLICT(key) DO NOTHING",
+                params![JITTER_SEED_KEY, hex::encode(seed)],
+            )?;
+        }
+        self.get_setting(JITTER_SEED_KEY)?
+            .and_then(|value| hex::decode(value).ok())
+            .and_then(|bytes| <[u8; 32]>::try_from(bytes).ok())
+            .ok_or_else(|| CircleError::InvalidData("Invalid coordinate jitter seed".to_string()))
+    }
+
+    /// Returns the stored privacy settings, or the defaults if none were
+    /// saved.
+    ///
+    /// # Errors
+    ///
+    /// Returns a database error on failure, or [`CircleError::InvalidData`]
+    /// if the stored value does not parse.
+    pub fn get_privacy_settings(&self) -> Result<PrivacySettings> {
+        self.get_setting(PRIVACY_SETTINGS_KEY)?.map_or_else(
+            || Ok(PrivacySettings::default()),
+            |json| {
+                serde_json::from_str(&json)
+                    .map_err(|e| CircleError::InvalidData(format!("Invalid privacy settings: {e}")))
+            },
+        )
+    }
+
+    /// Saves the privacy settings.
+    ///
+    /// # Errors
+    ///
+    /// Returns a database error on failure.
+    pub fn set_privacy_settings(&self, settings: &PrivacySettings) -> Result<()> {
+        let json = serde_json::to_string(settings).map_err(|e| {
+            CircleError::InvalidData(format!("Failed to encode privacy settings: {e}"))
+        })?;
+        let conn = self
+            .conn()
+            .lock()
+            .map_err(|e| CircleError::Storage(format!("Failed to acquire database lock: {e}")))?;
+        conn.execute(
+            "INSERT INTO user_settings (key, value) VALUES (?1, ?2)
+             ON CONFLICT(key) DO UPDATE SET value = excluded.value",
+            params![PRIVACY_SETTINGS_KEY, json],
+        )?;
+        Ok(())
+    }
+}
@@ -0,0 +1,183 @@
+//! Storage methods for contact labels.
+//!
+//! Extends [`CircleStorage`] with the `contact_labels` table defined in
+//! [`CircleStorage::initialize_schema`]: user-chosen labels ("family",
+//! "kids", "trip-2025") grouping contacts, e.g. to create a circle from all
+//! of them at once. Labels are normalized by [`normalize_contact_label`]
+//! before they are stored or looked up.
+
+// Single-shot SQLite ops naturally hold the lock to completion; the parent
+// module already disables this lint at the file level for storage.rs.
+#![allow(clippy::significant_drop_tightening)]
+
+use rusqlite::params;
+
+use super::error::{CircleError, Result};
+use super::storage::CircleStorage;
+
+/// Longest label accepted, in characters.
+pub const MAX_CONTACT_LABEL_CHARS: usize = 64;
+
+/// Trims and lowercases `label`, so "Family " and "family" are one label.
+///
+/// # Errors
+///
+/// Returns [`CircleError::InvalidData`] if the label is empty or longer than
+/// [`MAX_CONTACT_LABEL_CHARS`].
+pub fn normalize_contact_label(label: &str) -> Result<String> {
+    let label = label.trim().to_lowercase();
+    if label.is_empty() {
+        return Err(CircleError::InvalidData("Label is empty".to_string()));
+    }
+    if label.chars().count() > MAX_CONTACT_LABEL_CHARS {
+        return Err(CircleError::InvalidData(format!(
+            "Label is longer than {MAX_CONTACT_LABEL_CHARS} characters"
+        )));
+    }
+    Ok(label)
+}
+
+impl CircleStorage {
+    /// Adds `label` to the contact `pubkey`. Returns `false` if it was
+    /// already there.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`CircleError::InvalidData`] for an invalid label, or a
+    /// database error.
+    pub fn add_contact_label(&self, pubkey: &str, label: &str, now: i64) -> Result<bool> {
+        let label = normalize_contact_label(label)?;
+        let conn = self
+            .conn()
+            .lock()
+            .map_err(|e| CircleError::Storage(format!("Failed to acquire database lock: {e}")))?;
+        let added = conn.execute(
+            "INSERT OR IGNORE INTO contact_labels (pubkey, label, created_at)
+             VALUES (?1, ?2, ?3)",
+            params![pubkey, label, now],
+        )?;
+        Ok(added > 0)
+    }
+
+    /// Removes `label` from the contact `pubkey`. Returns `false` if it was
+    /// not there.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`CircleError::InvalidData`] for an invalid label, or a
+    /// database error.
+    pub fn remove_contact_label(&self, pubkey: &str, label: &str) -> Result<bool> {
+        let label = normalize_contact_label(label)?;
+        let conn = self
+            .conn()
+            .lock()
+            .map_err(|e| CircleError::Storage(format!("Failed to acquire database lock: {e}")))?;
+        let removed = conn.execute(
+            "DELETE FROM contact_labels WHERE pubkey = ?1 AND label = ?2",
+            params![pubkey, label],
+        )?;
+        Ok(removed > 0)
+    }
+
+    /// Labels on the contact `pubkey`, alphabetically.
+    ///
+    /// # Errors
+    ///
+    /// Returns a database error on failure.
+    pub fn contact_labels(&self, pubkey: &str) -> Result<Vec<String>> {
+        let conn = self
+            .conn()
+            .lock()
+            .map_err(|e| CircleError::Storage(format!("Failed to acquire database lock: {e}")))?;
+        let mut stmt =
+            conn.prepare("SELECT label FROM contact_labels WHERE pubkey = ?1 ORDER BY label")?;
+        let rows = stmt.query_map(params![pubkey], |r| r.get(0))?;
+        Ok(rows.collect::<std::result::Result<Vec<_>, _>>()?)
+    }
+
+    /// Pubkeys of the contacts labeled `label`, ordered by pubkey.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`CircleError::InvalidData`] for an invalid label, or a
+    /// database error.
+    pub fn contacts_with_label(&self, label: &str) -> Result<Vec<String>> {
+        let label = normalize_contact_label(label)?;
+        let conn = self
+            .conn()
+            .lock()
+            .map_err(|e| CircleError::Storage(format!("Failed to acquire database lock: {e}")))?;
+        let mut stmt =
+            conn.prepare("SELECT pubkey FROM contact_labels WHERE label = ?1 ORDER BY pubkey")?;
+        let rows = stmt.query_map(params![label], |r| r.get(0))?;
+        Ok(rows.collect::<std::result::Result<Vec<_>, _>>()?)
+    }
+
+    /// Every label in use with how many contacts carry it, alphabetically.
+    ///
+    /// # Errors
+    ///
+    /// Returns a database error on failure.
+    pub fn all_contact_labels(&self) -> Result<Vec<(String, usize)>> {
+        let conn = self
+            .conn()
+            .lock()
+            .map_err(|e| CircleError::Storage(format!("Failed to acquire database lock: {e}")))?;
+        let mut stmt = conn
+            .prepare("SELECT label, COUNT(*) FROM contact_labels GROUP BY label ORDER BY label")?;
+        let rows = stmt.query_map([], |r| {
+            let count: i64 = r.get(1)?;
+            Ok((r.get(0)?, usize::try_from(count).unwrap_or(0)))
+        })?;
+        Ok(rows.collect::<std::result::Result<Vec<_>, _>>()?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn labels_are_normalized_and_queryable_both_ways() {
+        let storage = CircleStorage::in_memory().unwrap();
+        assert!(storage.add_contact_label("bob", " Family ", 1).unwrap());
+        assert!(!storage.add_contact_label("bob", "family", 2).unwrap());
+        assert!(storage.add_contact_label("bob", "trip-2025", 3).unwrap());
+        assert!(storage.add_contact_label("alice", "family", 4).unwrap());
+
+        assert_eq!(
+            storage.contact_labels("bob").unwrap(),
+            ["family", "trip-2025"]
+        );
+        assert_eq!(
+            storage.contacts_with_label("FAMILY").unwrap(),
+            ["alice", "bob"]
+        );
+        assert_eq!(
+            storage.all_contact_labels().unwrap(),
+            [("family".to_string(), 2), ("trip-2025".to_string(), 1)]
+        );
+
+        assert!(storage.remove_contact_label("bob", "family").unwrap());
+        assert!(!storage.remove_contact_label("bob", "family").unwrap());
+        assert_eq!(storage.contacts_with_label("family").unwrap(), ["alice"]);
+
+        assert!(matches!(
+            storage.add_contact_label("bob", "  ", 5),
+            Err(CircleError::InvalidData(_))
+        ));
+        assert!(matches!(
+            storage.add_contact_label("bob", &"x".repeat(MAX_CONTACT_LABEL_CHARS + 1), 5),
+            Err(CircleError::InvalidData(_))
+        ));
+    }
+
+    #[test]
+    fn deleting_a_contact_drops_its_labels() {
+        let storage = CircleStorage::in_memory().unwrap();
+        storage.add_contact_label("bob", "kids", 1).unwrap();
+        storage.delete_contact("bob").unwrap();
+        assert!(storage.contact_labels("bob").unwrap().is_empty());
+        assert!(storage.all_contact_labels().unwrap().is_empty());
+    }
+}
@@ -0,0 +1,126 @@
+//! Storage methods for per-relay group sync cursors.
+//!
+//! Extends [`CircleStorage`] with the `group_relay_cursors` table defined in
+//! [`CircleStorage::initialize_schema`]. See [`crate::relay::sync`] for how
+//! the cursors are used.
+
+// Single-shot SQLite ops naturally hold the lock to completion; the parent
+// module already disables this lint at the file level for storage.rs.
+#![allow(clippy::significant_drop_tightening)]
+
+use rusqlite::{params, OptionalExtension};
+
+use super::error::{CircleError, Result};
+use super::storage::CircleStorage;
+
+impl CircleStorage {
+    /// Reads the cursor for one circle on one relay as
+    /// `(last_created_at, last_event_id)`.
+    ///
+    /// # Errors
+    ///
+    /// Returns a database error on failure.
+    pub fn read_group_relay_cursor(
+        &self,
+        nostr_group_id: &[u8; 32],
+        relay_url: &str,
+    ) -> Result<Option<(i64, String)>> {
+        let conn = self
+            .conn()
+            .lock()
+            .map_err(|e| CircleError::Storage(format!("Failed to acquire database lock: {e}")))?;
+        Ok(conn
+            .query_row(
+                "SELECT last_created_at, last_event_id FROM group_relay_cursors
+                 WHERE nostr_group_id = ?1 AND relay_url = ?2",
+                params![nostr_group_id.as_slice(), relay_url],
+                |r| Ok((r.get(0)?, r.get(1)?)),
+            )
+            .optional()?)
+    }
+
+    /// Advances the cursor for one circle on one relay. Monotonic: a
+    /// `(created_at, event_id)` at or behind the stored one is ignored.
+    ///
+    /// # Errors
+    ///
+    /// Returns a database error on failure.
+    pub fn advance_group_relay_cursor(
+        &self,
+        nostr_group_id: &[u8; 32],
+        relay_url: &str,
+        created_at: i64,
+        event_id: &str,
+        now: i64,
+    ) -> Result<()> {
+        let conn = self
+            .conn()
+            .lock()
+            .map_err(|e| CircleError::Storage(format!("Failed to acquire database lock: {e}")))?;
+        conn.execute(
+            "INSERT INTO group_relay_cursors
+                 (nostr_group_id, relay_url, last_created_at, last_event_id, updated_at)
+             VALUES (?1, ?2, ?3, ?4, ?5)
+             ON CONFLICT(nostr_group_id, relay_url) DO UPDATE SET
+                 last_created_at = excluded.last_created_at,
+                 last_event_id = excluded.last_event_id,
+                 updated_at = excluded.updated_at
+             WHERE excluded.last_created_at > group_relay_cursors.last_created_at
+                OR (excluded.last_created_at = group_relay_cursors.last_created_at
+                    AND excluded.last_event_id > group_relay_cursors.last_event_id)",
+            params![
+                nostr_group_id.as_slice(),
+                relay_url,
+                created_at,
+                event_id,
+                now
+            ],
+        )?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const NGID: [u8; 32] = [7u8; 32];
+    const RELAY: &str = "wss://relay.example.com";
+
+    #[test]
+    fn cursor_only_moves_forward() {
+        let storage = CircleStorage::in_memory().expect("in_memory");
+        assert_eq!(storage.read_group_relay_cursor(&NGID, RELAY).unwrap(), None);
+
+        storage
+            .advance_group_relay_cursor(&NGID, RELAY, 100, "bb", 1)
+            .unwrap();
+        storage
+            .advance_group_relay_cursor(&NGID, RELAY, 90, "ff", 2)
+            .unwrap();
+        storage
+            .advance_group_relay_cursor(&NGID, RELAY, 100, "aa", 3)
+            .unwrap();
+        assert_eq!(
+            storage.read_group_relay_cursor(&NGID, RELAY).unwrap(),
+            Some((100, "bb".to_string()))
+        );
+
+        storage
+            .advance_group_relay_cursor(&NGID, RELAY, 100, "cc", 4)
+            .unwrap();
+        assert_eq!(
+            storage.read_group_relay_cursor(&NGID, RELAY).unwrap(),
+            Some((100, "cc".to_string()))
+        );
+        assert_eq!(
+            storage
+                .read_group_relay_cursor(&NGID, "wss://other.example.com")
+                .unwrap(),
+            None
+        );
+
+        storage.reset_all_sync_cursors().unwrap();
+        assert_eq!(storage.read_group_relay_cursor(&NGID, RELAY).unwrap(), None);
+    }
+}
@@ -0,0 +1,96 @@
+//! Storage methods for per-circle receive health.
+//!
+//! Extends [`CircleStorage`] with the `group_health` table defined in
+//! [`CircleStorage::initialize_schema`]. See [`super::GroupHealth`].
+
+// Single-shot SQLite ops naturally hold the lock to completion; the parent
+// module already disables this lint at the file level for storage.rs.
+#![allow(clippy::significant_drop_tightening)]
+
+use rusqlite::{params, OptionalExtension};
+
+use super::error::{CircleError, Result};
+use super::storage::CircleStorage;
+
+impl CircleStorage {
+    /// Counts one message for the circle routed at `nostr_group_id` that the
+    /// engine could not ingest, returning the new count.
+    ///
+    /// # Errors
+    ///
+    /// Returns a database error on failure.
+    pub fn record_unprocessable(&self, nostr_group_id: &[u8], now: i64) -> Result<u32> {
+        let conn = self
+            .conn()
+            .lock()
+            .map_err(|e| CircleError::Storage(format!("Failed to acquire database lock: {e}")))?;
+        let count: i64 = conn.query_row(
+            "INSERT INTO group_health
+                 (nostr_group_id, unprocessable_count, last_unprocessable_at)
+             VALUES (?1, 1, ?2)
+             ON CONFLICT(nostr_group_id) DO UPDATE SET
+                 unprocessable_count = unprocessable_count + 1,
+                 last_unprocessable_at = excluded.last_unprocessable_at
+             RETURNING unprocessable_count",
+            params![nostr_group_id, now],
+            |r| r.get(0),
+        )?;
+        Ok(u32::try_from(count).unwrap_or(u32::MAX))
+    }
+
+    /// Returns how many messages for the circle have failed to ingest since
+    /// the last one that applied.
+    ///
+    /// # Errors
+    ///
+    /// Returns a database error on failure.
+    pub fn unprocessable_count(&self, nostr_group_id: &[u8]) -> Result<u32> {
+        let conn = self
+            .conn()
+            .lock()
+            .map_err(|e| CircleError::Storage(format!("Failed to acquire database lock: {e}")))?;
+        let count: Option<i64> = conn
+            .query_row(
+                "SELECT unprocessable_count FROM group_health WHERE nostr_group_id = ?1",
+                params![nostr_group_id],
+                |r| r.get(0),
+            )
+            .optional()?;
+        Ok(count.map_or(0, |c| u32::try_from(c).unwrap_or(u32::MAX)))
+    }
+
+    /// Resets the circle's count, e.g. once a message applies again.
+    ///
+    /// # Errors
+    ///
+    /// Returns a database error on failure.
+    pub fn clear_unprocessable(&self, nostr_group_id: &[u8]) -> Result<()> {
+        let conn = self
+            .conn()
+            .lock()
+            .map_err(|e| CircleError::Storage(format!("Failed to acquire database lock: {e}")))?;
+        conn.execute(
+            "DELETE FROM group_health WHERE nostr_group_id = ?1",
+            params![nostr_group_id],
+        )?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn counts_and_clears() {
+        let storage = CircleStorage::in_memory().unwrap();
+        let ngid = [7u8; 32];
+        assert_eq!(storage.unprocessable_count(&ngid).unwrap(), 0);
+        assert_eq!(storage.record_unprocessable(&ngid, 100).unwrap(), 1);
+        assert_eq!(storage.record_unprocessable(&ngid, 200).unwrap(), 2);
+        assert_eq!(storage.unprocessable_count(&ngid).unwrap(), 2);
+        assert_eq!(storage.unprocessable_count(&[8u8; 32]).unwrap(), 0);
+        storage.clear_unprocessable(&ngid).unwrap();
+        assert_eq!(storage.unprocessable_count(&ngid).unwrap(), 0);
+    }
+}
@@ -0,0 +1,343 @@
+//! Storage methods for the Welcomes this device sent.
+//!
+//! Extends [`CircleStorage`] with the `sent_invites` table defined in
+//! [`CircleStorage::initialize_schema`]. A row is kept from the moment a
+//! Welcome is staged until its member leaves the circle; `joined_at` is set
+//! the first time the member sends anything in the circle. See
+//! [`crate::circle::CircleManager::resend_invite`] for the resend rules.
+
+// Single-shot SQLite ops naturally hold the lock to completion; the parent
+// module already disables this lint at the file level for storage.rs.
+#![allow(clippy::significant_drop_tightening)]
+
+use nostr::Event;
+use rusqlite::{params, OptionalExtension, Row};
+
+use super::error::{CircleError, Result};
+use super::storage::CircleStorage;
+use super::types::{GiftWrappedWelcome, SentInvite};
+use crate::nostr::mls::types::GroupId;
+
+const SENT_INVITE_COLUMNS: &str = "member_pubkey, welcome_json, relays, fallback_relays, epoch,
+     invited_at, resend_count, last_resent_at, joined_at";
+
+/// Raw `sent_invites` columns, decoded outside the row closure so JSON
+/// errors surface as [`CircleError::InvalidData`].
+struct SentInviteRow {
+    member_pubkey: String,
+    welcome_json: String,
+    relays: String,
+    fallback_relays: String,
+    epoch: Option<i64>,
+    invited_at: i64,
+    resend_count: i64,
+    last_resent_at: Option<i64>,
+    joined_at: Option<i64>,
+}
+
+impl SentInviteRow {
+    fn read(row: &Row<'_>) -> rusqlite::Result<Self> {
+        Ok(Self {
+            member_pubkey: row.get(0)?,
+            welcome_json: row.get(1)?,
+            relays: row.get(2)?,
+            fallback_relays: row.get(3)?,
+            epoch: row.get(4)?,
+            invited_at: row.get(5)?,
+            resend_count: row.get(6)?,
+            last_resent_at: row.get(7)?,
+            joined_at: row.get(8)?,
+        })
+    }
+
+    fn decode(self) -> Result<SentInvite> {
+        let decode_relays = |json: &str| -> Result<Vec<String>> {
+            serde_json::from_str(json)
+                .map_err(|e| CircleError::InvalidData(format!("Invalid relays JSON: {e}")))
+        };
+        let event: Event = serde_json::from_str(&self.welcome_json)
+            .map_err(|e| CircleError::InvalidData(format!("Invalid welcome JSON: {e}")))?;
+        Ok(SentInvite {
+            welcome: GiftWrappedWelcome {
+                recipient_pubkey: self.member_pubkey.clone(),
+                recipient_relays: decode_relays(&self.relays)?,
+                fallback_relays: decode_relays(&self.fallback_relays)?,
+                event,
+            },
+            member_pubkey: self.member_pubkey,
+            epoch: self.epoch.and_then(|e| u64::try_from(e).ok()),
+            invited_at: self.invited_at,
+            resend_count: u32::try_from(self.resend_count).unwrap_or(u32::MAX),
+            last_resent_at: self.last_resent_at,
+            joined_at: self.joined_at,
+        })
+    }
+}
+
+impl CircleStorage {
+    /// Records Welcomes staged for a circle at `now`, awaiting
+    /// [`Self::confirm_sent_invites`].
+    ///
+    /// A member invited again (a reissued Welcome) keeps their first
+    /// `invited_at` and resend count; the Welcome and its relays are
+    /// replaced.
+    ///
+    /// # Errors
+    ///
+    /// Returns a database error on failure.
+    pub fn record_sent_invites(
+        &self,
+        mls_group_id: &GroupId,
+        welcomes: &[GiftWrappedWelcome],
+        now: i64,
+    ) -> Result<()> {
+        let mut conn = self
+            .conn()
+            .lock()
+            .map_err(|e| CircleError::Storage(format!("Failed to acquire database lock: {e}")))?;
+        let tx = conn.transaction()?;
+        for welcome in welcomes {
+            let welcome_json = serde_json::to_string(&welcome.event)
+                .map_err(|e| CircleError::InvalidData(format!("Failed to encode welcome: {e}")))?;
+            let relays = serde_json::to_string(&welcome.recipient_relays)
+                .map_err(|e| CircleError::InvalidData(format!("Failed to encode relays: {e}")))?;
+            let fallback_relays = serde_json::to_string(&welcome.fallback_relays)
+                .map_err(|e| CircleError::InvalidData(format!("Failed to encode relays: {e}")))?;
+            tx.execute(
+                "INSERT INTO sent_invites
+                     (mls_group_id, member_pubkey, welcome_json, relays, fallback_relays,
+                      epoch, invited_at)
+                 VALUES (?1, ?2, ?3, ?4, ?5, NULL, ?6)
+                 ON CONFLICT(mls_group_id, member_pubkey) DO UPDATE SET
+                     welcome_json = excluded.welcome_json,
+                     relays = excluded.relays,
+                     fallback_relays = excluded.fallback_relays,
+                     epoch = NULL,
+                     joined_at = NULL",
+                params![
+                    mls_group_id.as_slice(),
+                    welcome.recipient_pubkey,
+                    welcome_json,
+                    relays,
+                    fallback_relays,
+                    now
+                ],
+            )?;
+        }
+        tx.commit()?;
+        Ok(())
+    }
+
+    /// Stamps a circle's unconfirmed Welcomes with the epoch they join at,
+    /// once the commit that added their members was confirmed.
+    ///
+    /// # Errors
+    ///
+    /// Returns a database error on failure.
+    pub fn confirm_sent_invites(&self, mls_group_id: &GroupId, epoch: u64) -> Result<()> {
+        let conn = self
+            .conn()
+            .lock()
+            .map_err(|e| CircleError::Storage(format!("Failed to acquire database lock: {e}")))?;
+        conn.execute(
+            "UPDATE sent_invites SET epoch = ?2
+             WHERE mls_group_id = ?1 AND epoch IS NULL",
+            params![
+                mls_group_id.as_slice(),
+                i64::try_from(epoch).unwrap_or(i64::MAX)
+            ],
+        )?;
+        Ok(())
+    }
+
+    /// Notes that an invited member sent something in the circle at `now`.
+    /// Returns `true` the first time.
+    ///
+    /// # Errors
+    ///
+    /// Returns a database error on failure.
+    pub fn mark_invite_joined(
+        &self,
+        mls_group_id: &GroupId,
+        member_pubkey: &str,
+        now: i64,
+    ) -> Result<bool> {
+        let conn = self
+            .conn()
+            .lock()
+            .map_err(|e| CircleError::Storage(format!("Failed to acquire database lock: {e}")))?;
+        let updated = conn.execute(
+            "UPDATE sent_invites SET joined_at = ?3
+             WHERE mls_group_id = ?1 AND member_pubkey = ?2 AND joined_at IS NULL",
+            params![mls_group_id.as_slice(), member_pubkey, now],
+        )?;
+        Ok(updated > 0)
+    }
+
+    /// Returns the Welcome sent to a member of a circle, if any.
+    ///
+    /// # Errors
+    ///
+    /// Returns a database error on failure, or [`CircleError::InvalidData`]
+    /// if the stored Welcome does not decode.
+    pub fn sent_invite(
+        &self,
+        mls_group_id: &GroupId,
+        member_pubkey: &str,
+    ) -> Result<Option<SentInvite>> {
+        let conn = self
+            .conn()
+            .lock()
+            .map_err(|e| CircleError::Storage(format!("Failed to acquire database lock: {e}")))?;
+        let row = conn
+            .query_row(
+                &format!(
+                    "SELECT {SENT_INVITE_COLUMNS} FROM sent_invites
+                     WHERE mls_group_id = ?1 AND member_pubkey = ?2"
+                ),
+                params![mls_group_id.as_slice(), member_pubkey],
+                SentInviteRow::read,
+            )
+            .optional()?;
+        row.map(SentInviteRow::decode).transpose()
+    }
+
+    /// Returns the Welcomes of a circle whose member is on its stored roster
+    /// but has not sent anything since being invited at or before
+    /// `invited_before`, oldest invitation first.
+    ///
+    /// # Errors
+    ///
+    /// Returns a database error on failure, or [`CircleError::InvalidData`]
+    /// if a stored Welcome does not decode.
+    pub fn unjoined_invites(
+        &self,
+        mls_group_id: &GroupId,
+        invited_before: i64,
+    ) -> Result<Vec<SentInvite>> {
+        let conn = self
+            .conn()
+            .lock()
+            .map_err(|e| CircleError::Storage(format!("Failed to acquire database lock: {e}")))?;
+        let mut stmt = conn.prepare(&format!(
+            "SELECT {SENT_INVITE_COLUMNS} FROM sent_invites
+             WHERE mls_group_id = ?1 AND joined_at IS NULL AND invited_at <= ?2
+               AND member_pubkey IN
+                   (SELECT member_pubkey FROM circle_roster WHERE mls_group_id = ?1)
+             ORDER BY invited_at, member_pubkey"
+        ))?;
+        let rows = stmt
+            .query_map(
+                params![mls_group_id.as_slice(), invited_before],
+                SentInviteRow::read,
+            )?
+            .collect::<std::result::Result<Vec<_>, _>>()?;
+        rows.into_iter().map(SentInviteRow::decode).collect()
+    }
+
+    /// Counts a resend of a member's invitation at `now`.
+    ///
+    /// # Errors
+    ///
+    /// Returns a database error on failure.
+    pub fn record_invite_resend(
+        &self,
+        mls_group_id: &GroupId,
+        member_pubkey: &str,
+        now: i64,
+    ) -> Result<()> {
+        let conn = self
+            .conn()
+            .lock()
+            .map_err(|e| CircleError::Storage(format!("Failed to acquire database lock: {e}")))?;
+        conn.execute(
+            "UPDATE sent_invites
+             SET resend_count = resend_count + 1, last_resent_at = ?3
+             WHERE mls_group_id = ?1 AND member_pubkey = ?2",
+            params![mls_group_id.as_slice(), member_pubkey, now],
+        )?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::nostr::mls::types::GroupIdExt;
+    use nostr::{EventBuilder, Keys, Kind};
+
+    fn welcome(recipient: &str) -> GiftWrappedWelcome {
+        GiftWrappedWelcome {
+            recipient_pubkey: recipient.to_string(),
+            recipient_relays: vec!["wss://inbox.example.com".to_string()],
+            fallback_relays: vec!["wss://fallback.example.com".to_string()],
+            event: EventBuilder::new(Kind::GiftWrap, "sealed")
+                .sign_with_keys(&Keys::generate())
+                .unwrap(),
+        }
+    }
+
+    #[test]
+    fn invites_stay_unjoined_until_the_member_sends() {
+        let storage = CircleStorage::in_memory().unwrap();
+        let gid = GroupId::from_slice(&[1; 32]);
+        storage
+            .record_sent_invites(&gid, &[welcome("bob"), welcome("carol")], 1_000)
+            .unwrap();
+        // Only members the roster shows are reported.
+        assert!(storage.unjoined_invites(&gid, 2_000).unwrap().is_empty());
+
+        storage
+            .reconcile_roster(&gid, &["alice".to_string(), "bob".to_string()])
+            .unwrap();
+        storage.confirm_sent_invites(&gid, 4).unwrap();
+        let unjoined = storage.unjoined_invites(&gid, 2_000).unwrap();
+        assert_eq!(unjoined.len(), 1);
+        assert_eq!(unjoined[0].member_pubkey, "bob");
+        assert_eq!(unjoined[0].epoch, Some(4));
+        assert_eq!(
+            unjoined[0].welcome.recipient_relays,
+            vec!["wss://inbox.example.com".to_string()]
+        );
+        assert!(storage.unjoined_invites(&gid, 999).unwrap().is_empty());
+
+        assert!(storage.mark_invite_joined(&gid, "bob", 1_500).unwrap());
+        assert!(!storage.mark_invite_joined(&gid, "bob", 1_600).unwrap());
+        assert!(storage.unjoined_invites(&gid, 2_000).unwrap().is_empty());
+        assert_eq!(
+            storage.sent_invite(&gid, "bob").unwrap().unwrap().joined_at,
+            Some(1_500)
+        );
+    }
+
+    #[test]
+    fn reissued_invites_keep_their_history() {
+        let storage = CircleStorage::in_memory().unwrap();
+        let gid = GroupId::from_slice(&[1; 32]);
+        storage
+            .record_sent_invites(&gid, &[welcome("bob")], 1_000)
+            .unwrap();
+        storage.confirm_sent_invites(&gid, 2).unwrap();
+        storage.record_invite_resend(&gid, "bob", 30_000).unwrap();
+
+        let reissued = welcome("bob");
+        storage
+            .record_sent_invites(&gid, std::slice::from_ref(&reissued), 30_000)
+            .unwrap();
+        let invite = storage.sent_invite(&gid, "bob").unwrap().unwrap();
+        assert_eq!(invite.invited_at, 1_000);
+        assert_eq!(invite.resend_count, 1);
+        assert_eq!(invite.last_resent_at, Some(30_000));
+        assert_eq!(invite.epoch, None);
+        assert_eq!(invite.welcome.event.id, reissued.event.id);
+
+        // Leaving the roster drops the row.
+        storage
+            .reconcile_roster(&gid, &["alice".to_string(), "bob".to_string()])
+            .unwrap();
+        storage
+            .reconcile_roster(&gid, &["alice".to_string()])
+            .unwrap();
+        assert!(storage.sent_invite(&gid, "bob").unwrap().is_none());
+    }
+}
@@ -0,0 +1,250 @@
+//! Storage methods for the member key audit.
+//!
+//! Extends [`CircleStorage`] with the `member_keys` and `member_key_changes`
+//! tables defined in [`CircleStorage::initialize_schema`], plus the opt-in
+//! flag. See [`super::key_audit`] for what is observed.
+
+// Single-shot SQLite ops naturally hold the lock to completion; the parent
+// module already disables this lint at the file level for storage.rs.
+#![allow(clippy::significant_drop_tightening)]
+
+use rusqlite::{params, OptionalExtension};
+
+use super::error::{CircleError, Result};
+use super::key_audit::{KeyObservation, MemberKeyChange, KEY_AUDIT_ENABLED_KEY};
+use super::storage::CircleStorage;
+use crate::nostr::mls::types::GroupId;
+
+impl CircleStorage {
+    /// Returns whether the member key audit is enabled.
+    ///
+    /// Defaults to `false` when the setting has never been written.
+    ///
+    /// # Errors
+    ///
+    /// Returns a database error on failure.
+    pub fn get_key_audit_enabled(&self) -> Result<bool> {
+        Ok(self.get_setting(KEY_AUDIT_ENABLED_KEY)?.as_deref() == Some("true"))
+    }
+
+    /// Enables or disables the member key audit. Recorded keys and changes
+    /// are kept either way.
+    ///
+    /// # Errors
+    ///
+    /// Returns a database error on failure.
+    pub fn set_key_audit_enabled(&self, enabled: bool) -> Result<()> {
+        let conn = self
+            .conn()
+            .lock()
+            .map_err(|e| CircleError::Storage(format!("Failed to acquire database lock: {e}")))?;
+        conn.execute(
+            "INSERT INTO user_settings (key, value) VALUES (?1, ?2)
+             ON CONFLICT(key) DO UPDATE SET value = excluded.value",
+            params![
+                KEY_AUDIT_ENABLED_KEY,
+                if enabled { "true" } else { "false" }
+            ],
+        )?;
+        Ok(())
+    }
+
+    /// Records the signature key `fingerprint` observed for `member_pubkey`.
+    ///
+    /// A different fingerprint than the recorded one is stored as a
+    /// [`MemberKeyChange`] and becomes the new baseline, so the same new key
+    /// is not reported twice.
+    ///
+    /// # Errors
+    ///
+    /// Returns a database error on failure.
+    pub fn observe_member_key(
+        &self,
+        member_pubkey: &str,
+        fingerprint: &str,
+        mls_group_id: Option<&GroupId>,
+        now: i64,
+    ) -> Result<KeyObservation> {
+        let mut conn = self
+            .conn()
+            .lock()
+            .map_err(|e| CircleError::Storage(format!("Failed to acquire database lock: {e}")))?;
+        let tx = conn.transaction()?;
+        let previous: Option<String> = tx
+            .query_row(
+                "SELECT fingerprint FROM member_keys WHERE member_pubkey = ?1",
+                params![member_pubkey],
+                |r| r.get(0),
+            )
+            .optional()?;
+        tx.execute(
+            "INSERT INTO member_keys (member_pubkey, fingerprint, first_seen, last_seen)
+             VALUES (?1, ?2, ?3, ?3)
+             ON CONFLICT(member_pubkey) DO UPDATE SET
+                 fingerprint = excluded.fingerprint,
+                 first_seen = CASE WHEN member_keys.fingerprint = excluded.fingerprint
+                                   THEN member_keys.first_seen ELSE excluded.first_seen END,
+                 last_seen = excluded.last_seen",
+            params![member_pubkey, fingerprint, now],
+        )?;
+        let observation = match previous {
+            None => KeyObservation::FirstSeen,
+            Some(previous) if previous == fingerprint => KeyObservation::Unchanged,
+            Some(previous) => {
+                tx.execute(
+                    "INSERT INTO member_key_changes
+                         (member_pubkey, mls_group_id, previous_fingerprint,
+                          observed_fingerprint, observed_at)
+                     VALUES (?1, ?2, ?3, ?4, ?5)",
+                    params![
+                        member_pubkey,
+                        mls_group_id.map(GroupId::as_slice),
+                        previous,
+                        fingerprint,
+                        now
+                    ],
+                )?;
+                KeyObservation::Changed(MemberKeyChange {
+                    id: tx.last_insert_rowid(),
+                    member_pubkey: member_pubkey.to_string(),
+                    mls_group_id: mls_group_id.cloned(),
+                    previous_fingerprint: previous,
+                    observed_fingerprint: fingerprint.to_string(),
+                    observed_at: now,
+                    acknowledged_at: None,
+                })
+            }
+        };
+        tx.commit()?;
+        Ok(observation)
+    }
+
+    /// Lists recorded key changes, newest first.
+    ///
+    /// With `include_acknowledged == false` only changes still awaiting
+    /// review are returned.
+    ///
+    /// # Errors
+    ///
+    /// Returns a database error on failure.
+    pub fn list_member_key_changes(
+        &self,
+        include_acknowledged: bool,
+    ) -> Result<Vec<MemberKeyChange>> {
+        let conn = self
+            .conn()
+            .lock()
+            .map_err(|e| CircleError::Storage(format!("Failed to acquire database lock: {e}")))?;
+        let mut stmt = conn.prepare(
+            "SELECT id, member_pubkey, mls_group_id, previous_fingerprint,
+                    observed_fingerprint, observed_at, acknowledged_at
+             FROM member_key_changes
+             WHERE ?1 OR acknowledged_at IS NULL
+             ORDER BY observed_at DESC, id DESC",
+        )?;
+        let rows = stmt.query_map(params![include_acknowledged], |r| {
+            Ok(MemberKeyChange {
+                id: r.get(0)?,
+                member_pubkey: r.get(1)?,
+                mls_group_id: r
+                    .get::<_, Option<Vec<u8>>>(2)?
+                    .map(|b| GroupId::from_slice(&b)),
+                previous_fingerprint: r.get(3)?,
+                observed_fingerprint: r.get(4)?,
+                observed_at: r.get(5)?,
+                acknowledged_at: r.get(6)?,
+            })
+        })?;
+        Ok(rows.collect::<std::result::Result<Vec<_>, _>>()?)
+    }
+
+    /// Marks a key change as reviewed by the user.
+    ///
+    /// Returns `false` if no unacknowledged change has that id.
+    ///
+    /// # Errors
+    ///
+    /// Returns a database error on failure.
+    pub fn acknowledge_member_key_change(&self, id: i64, now: i64) -> Result<bool> {
+        let conn = self
+            .conn()
+            .lock()
+            .map_err(|e| CircleError::Storage(format!("Failed to acquire database lock: {e}")))?;
+        let updated = conn.execute(
+            "UPDATE member_key_changes SET acknowledged_at = ?2
+             WHERE id = ?1 AND acknowledged_at IS NULL",
+            params![id, now],
+        )?;
+        Ok(updated > 0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn make_storage() -> CircleStorage {
+        CircleStorage::in_memory().expect("in_memory")
+    }
+
+    #[test]
+    fn audit_is_opt_in() {
+        let storage = make_storage();
+        assert!(!storage.get_key_audit_enabled().unwrap());
+        storage.set_key_audit_enabled(true).unwrap();
+        assert!(storage.get_key_audit_enabled().unwrap());
+        storage.set_key_audit_enabled(false).unwrap();
+        assert!(!storage.get_key_audit_enabled().unwrap());
+    }
+
+    #[test]
+    fn a_changed_key_is_recorded_once_and_acknowledged() {
+        let storage = make_storage();
+        let gid = GroupId::from_slice(&[1; 32]);
+
+        assert_eq!(
+            storage.observe_member_key("alice", "aa", None, 1).unwrap(),
+            KeyObservation::FirstSeen
+        );
+        assert_eq!(
+            storage.observe_member_key("alice", "aa", None, 2).unwrap(),
+            KeyObservation::Unchanged
+        );
+        let KeyObservation::Changed(change) = storage
+            .observe_member_key("alice", "bb", Some(&gid), 3)
+            .unwrap()
+        else {
+            panic!("expected a change");
+        };
+        assert_eq!(change.previous_fingerprint, "aa");
+        assert_eq!(change.observed_fingerprint, "bb");
+        assert_eq!(change.mls_group_id, Some(gid));
+        // The new key is the baseline now.
+        assert_eq!(
+            storage.observe_member_key("alice", "bb", None, 4).unwrap(),
+            KeyObservation::Unchanged
+        );
+
+        assert_eq!(
+            storage.list_member_key_changes(false).unwrap(),
+            [change.clone()]
+        );
+        assert!(storage.acknowledge_member_key_change(change.id, 5).unwrap());
+        assert!(!storage.acknowledge_member_key_change(change.id, 6).unwrap());
+        assert!(storage.list_member_key_changes(false).unwrap().is_empty());
+        let all = storage.list_member_key_changes(true).unwrap();
+        assert_eq!(all.len(), 1);
+        assert_eq!(all[0].acknowledged_at, Some(5));
+    }
+
+    #[test]
+    fn keys_are_tracked_per_member() {
+        let storage = make_storage();
+        storage.observe_member_key("alice", "aa", None, 1).unwrap();
+        assert_eq!(
+            storage.observe_member_key("bob", "bb", None, 2).unwrap(),
+            KeyObservation::FirstSeen
+        );
+        assert!(storage.list_member_key_changes(true).unwrap().is_empty());
+    }
+}
@@ -41,6 +41,7 @@ use rusqlite::{params, OptionalExtension};
 
 use super::error::{CircleError, Result};
 use super::storage::CircleStorage;
+use crate::relay::maintenance::{KpRelayStatus, KpRelayVerification, KpVerification};
 
 /// The Marmot `KeyPackage` event kind (NIP-33 addressable, single kind).
 pub const KEY_PACKAGE_KIND: u16 = 30443;
@@ -159,6 +160,47 @@ impl CircleStorage {
         Ok(self.latest_published_key_package()?.map(|r| r.d_tag))
     }
 
+    /// Removes and returns the tracking row for the published event
+    /// `event_id` (lowercase hex), if tracked.
+    ///
+    /// Used when the package was consumed: the row must not survive, or a
+    /// later heal would re-publish bytes whose private material is gone.
+    ///
+    /// # Errors
+    ///
+    /// Returns a database error on failure.
+    pub fn take_published_key_package(
+        &self,
+        event_id: &str,
+    ) -> Result<Option<PublishedKeyPackageRow>> {
+        let conn = self
+            .conn()
+            .lock()
+            .map_err(|e| CircleError::Storage(format!("Failed to acquire database lock: {e}")))?;
+        let row = conn
+            .query_row(
+                "SELECT event_id, d_tag, key_package, created_at
+                 FROM published_key_packages WHERE event_id = ?1",
+                params![event_id],
+                |r| {
+                    Ok(PublishedKeyPackageRow {
+                        event_id: r.get(0)?,
+                        d_tag: r.get(1)?,
+                        key_package: r.get(2)?,
+                        created_at: r.get(3)?,
+                    })
+                },
+            )
+            .optional()?;
+        if row.is_some() {
+            conn.execute(
+                "DELETE FROM published_key_packages WHERE event_id = ?1",
+                params![event_id],
+            )?;
+        }
+        Ok(row)
+    }
+
     /// Clears all published-`KeyPackage` tracking (cutover / logout wipe).
     ///
     /// # Errors
@@ -170,9 +212,87 @@ impl CircleStorage {
             .lock()
             .map_err(|e| CircleError::Storage(format!("Failed to acquire database lock: {e}")))?;
         conn.execute("DELETE FROM published_key_packages", [])?;
+        conn.execute("DELETE FROM key_package_verifications", [])?;
+        Ok(())
+    }
+
+    /// Replaces the stored `KeyPackage` verification with `verification`.
+    ///
+    /// # Errors
+    ///
+    /// Returns a database error on failure.
+    pub fn record_key_package_verification(&self, verification: &KpVerification) -> Result<()> {
+        let mut conn = self
+            .conn()
+            .lock()
+            .map_err(|e| CircleError::Storage(format!("Failed to acquire database lock: {e}")))?;
+        let tx = conn.transaction()?;
+        tx.execute("DELETE FROM key_package_verifications", [])?;
+        for (position, relay) in verification.relays.iter().enumerate() {
+            tx.execute(
+                "INSERT OR REPLACE INTO key_package_verifications
+                     (relay_url, position, event_id, status, checked_at)
+                 VALUES (?1, ?2, ?3, ?4, ?5)",
+                params![
+                    relay.relay_url,
+                    i64::try_from(position).unwrap_or(i64::MAX),
+                    verification.event_id,
+                    relay.status.as_str(),
+                    verification.checked_at
+                ],
+            )?;
+        }
+        tx.commit()?;
         Ok(())
     }
 
+    /// Reads the stored `KeyPackage` verification, if any.
+    ///
+    /// # Errors
+    ///
+    /// Returns a database error on failure, or [`CircleError::InvalidData`]
+    /// for an unknown status.
+    pub fn latest_key_package_verification(&self) -> Result<Option<KpVerification>> {
+        let conn = self
+            .conn()
+            .lock()
+            .map_err(|e| CircleError::Storage(format!("Failed to acquire database lock: {e}")))?;
+        let mut stmt = conn.prepare(
+            "SELECT relay_url, event_id, status, checked_at
+             FROM key_package_verifications
+             ORDER BY position ASC",
+        )?;
+        let rows = stmt
+            .query_map([], |r| {
+                Ok((
+                    r.get::<_, String>(0)?,
+                    r.get::<_, String>(1)?,
+                    r.get::<_, String>(2)?,
+                    r.get::<_, i64>(3)?,
+                ))
+            })?
+            .collect::<std::result::Result<Vec<_>, _>>()?;
+        let Some((_, event_id, _, checked_at)) = rows.first().cloned() else {
+            return Ok(None);
+        };
+        let relays = rows
+            .into_iter()
+            .map(|(relay_url, _, status, _)| {
+                Ok(KpRelayVerification {
+                    relay_url,
+                    status: KpRelayStatus::parse(&status).ok_or_else(|| {
+                        CircleError::InvalidData(format!("Unknown verification status: {status}"))
+                    })?,
+                })
+            })
+            .collect::<Result<Vec<_>>>()?;
+        Ok(Some(KpVerification {
+            event_id,
+            checked_at,
+            relays,
+        }))
+    }
+
     /// Returns whether the one-time legacy (443 / kind-10051) retraction has run.
     ///
     /// Defaults to `false` (not yet run) when the sentinel has never been
@@ -241,6 +361,28 @@ mod tests {
         }
     }
 
+    #[test]
+    fn take_removes_only_the_named_row() {
+        let storage = CircleStorage::in_memory().expect("in_memory");
+        storage
+            .record_published_key_package(&row("aa", "d-first", &[1], 100))
+            .expect("record");
+        storage
+            .record_published_key_package(&row("bb", "d-second", &[2], 200))
+            .expect("record");
+
+        let taken = storage
+            .take_published_key_package("aa")
+            .expect("take")
+            .expect("tracked");
+        assert_eq!(taken.d_tag, "d-first");
+        assert!(storage
+            .take_published_key_package("aa")
+            .expect("take")
+            .is_none());
+        assert_eq!(storage.count_published_key_packages().expect("count"), 1);
+    }
+
     #[test]
     fn record_and_latest_round_trip() {
         let storage = CircleStorage::in_memory().expect("in_memory");
@@ -330,4 +472,52 @@ mod tests {
         assert!(dbg.contains("event_id"));
         assert!(dbg.contains('d'));
     }
+
+    #[test]
+    fn verification_is_replaced_and_wiped() {
+        let storage = CircleStorage::in_memory().expect("in_memory");
+        assert!(storage
+            .latest_key_package_verification()
+            .expect("empty")
+            .is_none());
+
+        let verdict = |relay_url: &str, status| KpRelayVerification {
+            relay_url: relay_url.to_string(),
+            status,
+        };
+        let first = KpVerification {
+            event_id: "aa".repeat(32),
+            checked_at: 10,
+            relays: vec![
+                verdict("wss://b", KpRelayStatus::Missing),
+                verdict("wss://a", KpRelayStatus::Served),
+            ],
+        };
+        storage
+            .record_key_package_verification(&first)
+            .expect("first");
+        assert_eq!(
+            storage.latest_key_package_verification().expect("read"),
+            Some(first)
+        );
+
+        let second = KpVerification {
+            event_id: "bb".repeat(32),
+            checked_at: 20,
+            relays: vec![verdict("wss://c", KpRelayStatus::Unreachable)],
+        };
+        storage
+            .record_key_package_verification(&second)
+            .expect("second");
+        assert_eq!(
+            storage.latest_key_package_verification().expect("read"),
+            Some(second)
+        );
+
+        storage.wipe_published_key_packages().expect("wipe");
+        assert!(storage
+            .latest_key_package_verification()
+            .expect("wiped")
+            .is_none());
+    }
 }
@@ -0,0 +1,408 @@
+//! Storage methods for the location history.
+//!
+//! Extends [`CircleStorage`] with the `location_history` table defined in
+//! [`CircleStorage::initialize_schema`]. Rows are written by
+//! [`CircleStorage::upsert_last_known_location`] and deleted with the
+//! last-known rows; this file only reads them.
+//!
+//! Each row carries the [`HISTORY_CELL_LEN`]-character geohash of its exact
+//! coordinates. A viewport query covers the box with at most
+//! [`MAX_COVERING_CELLS`] geohash cells (see [`covering_geohashes`]) and
+//! turns each into a range scan on the `cell` index; the exact bounds are
+//! then checked on the few rows the cells pick up outside the box.
+
+// Single-shot SQLite ops naturally hold the lock to completion; the parent
+// module already disables this lint at the file level for storage.rs.
+#![allow(clippy::significant_drop_tightening)]
+
+use chrono::NaiveDate;
+use rusqlite::types::Value;
+
+use super::error::{CircleError, Result};
+use super::storage::CircleStorage;
+use super::types::HistoryPoint;
+use crate::location::trips::{self, DaySummary};
+use crate::location::{covering_geohashes, Fix, GeohashBounds};
+
+/// Length of the geohash stored with each history row (about 38 m × 19 m).
+pub const HISTORY_CELL_LEN: u8 = 8;
+
+/// Most cells a viewport is covered with, and so most range scans per query.
+pub const MAX_COVERING_CELLS: usize = 32;
+
+/// Most points one viewport query returns through
+/// [`CircleManager::get_points_in_viewport`](super::CircleManager::get_points_in_viewport).
+pub const MAX_VIEWPORT_POINTS: u32 = 1_000;
+
+/// Most positions one day's summary is built from; later ones are left out.
+pub const MAX_DAY_POINTS: u32 = 5_000;
+
+impl CircleStorage {
+    /// Returns history points inside `bounds` captured between `from` and
+    /// `to` (Unix seconds, inclusive), newest first, at most `limit`.
+    ///
+    /// Restricted to one circle when `nostr_group_id` is given. Rows past
+    /// their `purge_after` at `now_unix_secs` are left out. A box with
+    /// `min_lon > max_lon` crosses the antimeridian.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`CircleError::InvalidData`] if `bounds` is not a valid box,
+    /// and a database error otherwise.
+    pub fn points_in_viewport(
+        &self,
+        nostr_group_id: Option<&[u8; 32]>,
+        bounds: &GeohashBounds,
+        from: i64,
+        to: i64,
+        limit: u32,
+        now_unix_secs: i64,
+    ) -> Result<Vec<HistoryPoint>> {
+        let cells = covering_geohashes(bounds, MAX_COVERING_CELLS, HISTORY_CELL_LEN);
+        if cells.is_empty() {
+            return Err(CircleError::InvalidData(
+                "Invalid viewport bounds".to_string(),
+            ));
+        }
+
+        let mut args: Vec<Value> = Vec::new();
+        let mut ranges = Vec::with_capacity(cells.len());
+        for cell in cells {
+            // Geohash characters sort below '~', so [cell, cell~) holds
+            // exactly the geohashes starting with `cell`.
+            ranges.push(format!(
+                "(cell >= ?{} AND cell < ?{})",
+                args.len() + 1,
+                args.len() + 2
+            ));
+            let end = format!("{cell}~");
+            args.extend([Value::Text(cell), Value::Text(end)]);
+        }
+        let mut sql = format!(
+            "SELECT nostr_group_id, sender_pubkey, latitude, longitude, timestamp
+             FROM location_history
+             WHERE ({})
+               AND latitude BETWEEN ?{} AND ?{}",
+            ranges.join(" OR "),
+            args.len() + 1,
+            args.len() + 2
+        );
+        args.extend([Value::Real(bounds.min_lat), Value::Real(bounds.max_lat)]);
+        let lon_op = if bounds.min_lon <= bounds.max_lon {
+            "AND"
+        } else {
+            "OR"
+        };
+        sql.push_str(&format!(
+            " AND (longitude >= ?{} {lon_op} longitude <= ?{})",
+            args.len() + 1,
+            args.len() + 2
+        ));
+        args.extend([Value::Real(bounds.min_lon), Value::Real(bounds.max_lon)]);
+        sql.push_str(&format!(
+            " AND timestamp BETWEEN ?{} AND ?{} AND purge_after >= ?{}",
+            args.len() + 1,
+            args.len() + 2,
+            args.len() + 3
+        ));
+        args.extend([
+            Value::Integer(from),
+            Value::Integer(to),
+            Value::Integer(now_unix_secs),
+        ]);
+        if let Some(ngid) = nostr_group_id {
+            sql.push_str(&format!(" AND nostr_group_id = ?{}", args.len() + 1));
+            args.push(Value::Blob(ngid.to_vec()));
+        }
+        sql.push_str(&format!(
+            " ORDER BY timestamp DESC LIMIT ?{}",
+            args.len() + 1
+        ));
+        args.push(Value::Integer(i64::from(limit)));
+
+        let conn = self
+            .conn()
+            .lock()
+            .map_err(|e| CircleError::Storage(format!("Failed to acquire database lock: {e}")))?;
+        let mut stmt = conn.prepare(&sql)?;
+        let rows = stmt
+            .query_map(rusqlite::params_from_iter(args), |row| {
+                Ok((
+                    row.get::<_, Vec<u8>>(0)?,
+                    row.get::<_, String>(1)?,
+                    row.get::<_, f64>(2)?,
+                    row.get::<_, f64>(3)?,
+                    row.get::<_, i64>(4)?,
+                ))
+            })?
+            .collect::<std::result::Result<Vec<_>, _>>()?;
+
+        rows.into_iter()
+            .map(|(ngid, sender_pubkey, latitude, longitude, timestamp)| {
+                let nostr_group_id: [u8; 32] = ngid.try_into().map_err(|_| {
+                    CircleError::InvalidData("Invalid nostr_group_id length".to_string())
+                })?;
+                Ok(HistoryPoint {
+                    nostr_group_id,
+                    sender_pubkey,
+                    latitude,
+                    longitude,
+                    timestamp,
+                })
+            })
+            .collect()
+    }
+
+    /// Returns one sender's history points in a circle captured at or after
+    /// `since`, newest first, at most `limit`. Rows past their `purge_after`
+    /// at `now_unix_secs` are left out.
+    ///
+    /// # Errors
+    ///
+    /// Returns a database error on failure.
+    pub fn member_track(
+        &self,
+        nostr_group_id: &[u8; 32],
+        sender_pubkey: &str,
+        since: i64,
+        limit: u32,
+        now_unix_secs: i64,
+    ) -> Result<Vec<HistoryPoint>> {
+        let conn = self
+            .conn()
+            .lock()
+            .map_err(|e| CircleError::Storage(format!("Failed to acquire database lock: {e}")))?;
+        let mut stmt = conn.prepare(
+            "SELECT latitude, longitude, timestamp
+             FROM location_history
+             WHERE nostr_group_id = ?1 AND sender_pubkey = ?2
+               AND timestamp >= ?3 AND purge_after >= ?4
+             ORDER BY timestamp DESC
+             LIMIT ?5",
+        )?;
+        let rows = stmt.query_map(
+            rusqlite::params![
+                nostr_group_id.as_slice(),
+                sender_pubkey,
+                since,
+                now_unix_secs,
+                limit
+            ],
+            |row| {
+                Ok(HistoryPoint {
+                    nostr_group_id: *nostr_group_id,
+                    sender_pubkey: sender_pubkey.to_string(),
+                    latitude: row.get(0)?,
+                    longitude: row.get(1)?,
+                    timestamp: row.get(2)?,
+                })
+            },
+        )?;
+        Ok(rows.collect::<std::result::Result<Vec<_>, _>>()?)
+    }
+
+    /// Summarizes one sender's trips on `date`, the local day at
+    /// `utc_offset_secs` east of UTC, from their history in every circle.
+    /// Rows past their `purge_after` at `now_unix_secs` are left out. See
+    /// [`crate::location::trips`].
+    ///
+    /// # Errors
+    ///
+    /// Returns a database error on failure.
+    pub fn summarize_day(
+        &self,
+        sender_pubkey: &str,
+        date: NaiveDate,
+        utc_offset_secs: i32,
+        now_unix_secs: i64,
+    ) -> Result<DaySummary> {
+        let (from, to) = trips::day_bounds(date, utc_offset_secs);
+        let conn = self
+            .conn()
+            .lock()
+            .map_err(|e| CircleError::Storage(format!("Failed to acquire database lock: {e}")))?;
+        // One row per timestamp: a location shared to several circles is
+        // stored once for each.
+        let mut stmt = conn.prepare(
+            "SELECT latitude, longitude, timestamp
+             FROM location_history
+             WHERE sender_pubkey = ?1 AND timestamp >= ?2 AND timestamp < ?3
+               AND purge_after >= ?4
+             GROUP BY timestamp
+             ORDER BY timestamp ASC
+             LIMIT ?5",
+        )?;
+        let rows = stmt.query_map(
+            rusqlite::params![sender_pubkey, from, to, now_unix_secs, MAX_DAY_POINTS],
+            |row| {
+                Ok(Fix {
+                    latitude: row.get(0)?,
+                    longitude: row.get(1)?,
+                    timestamp: row.get(2)?,
+                })
+            },
+        )?;
+        let track = rows.collect::<std::result::Result<Vec<_>, _>>()?;
+        Ok(trips::summarize_day(&track, date, utc_offset_secs))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::circle::LastKnownLocation;
+
+    const SF: GeohashBounds = GeohashBounds {
+        min_lat: 37.70,
+        min_lon: -122.52,
+        max_lat: 37.83,
+        max_lon: -122.35,
+    };
+
+    fn record(storage: &CircleStorage, ngid: u8, sender: &str, at: (f64, f64), timestamp: i64) {
+        storage
+            .upsert_last_known_location(&LastKnownLocation {
+                nostr_group_id: [ngid; 32],
+                sender_pubkey: sender.to_string(),
+                latitude: at.0,
+                longitude: at.1,
+                geohash: String::new(),
+                display_name: None,
+                timestamp,
+                expires_at: timestamp + 600,
+                purge_after: timestamp + 86_400,
+                updated_at: timestamp,
+            })
+            .unwrap();
+    }
+
+    #[test]
+    fn member_track_is_one_sender_newest_first() {
+        let storage = CircleStorage::in_memory().unwrap();
+        record(&storage, 1, "alice", (37.7749, -122.4194), 1_000);
+        record(&storage, 1, "alice", (37.7790, -122.4100), 2_000);
+        record(&storage, 1, "alice", (37.7700, -122.4300), 1_500);
+        record(&storage, 1, "bob", (37.8000, -122.4000), 3_000);
+        record(&storage, 2, "alice", (37.8000, -122.4000), 3_000);
+
+        let track = storage
+            .member_track(&[1; 32], "alice", 1_200, 10, 0)
+            .unwrap();
+        let times: Vec<i64> = track.iter().map(|p| p.timestamp).collect();
+        assert_eq!(times, vec![2_000, 1_500]);
+        assert_eq!(
+            storage
+                .member_track(&[1; 32], "alice", 0, 1, 0)
+                .unwrap()
+                .len(),
+            1
+        );
+    }
+
+    #[test]
+    fn day_summary_merges_circles_and_keeps_to_the_day() {
+        let storage = CircleStorage::in_memory().unwrap();
+        // 1_700_006_400 is 2023-11-15 00:00 UTC.
+        let at = |minute: i64| 1_700_006_400 + minute * 60;
+        for ngid in [1, 2] {
+            record(&storage, ngid, "alice", (37.7749, -122.4194), at(-60));
+            for minute in [0, 10, 20] {
+                record(&storage, ngid, "alice", (37.7749, -122.4194), at(minute));
+            }
+            record(&storage, ngid, "alice", (37.7900, -122.4194), at(30));
+            for minute in [40, 50, 60] {
+                record(&storage, ngid, "alice", (37.8049, -122.4194), at(minute));
+            }
+        }
+        record(&storage, 1, "bob", (37.7000, -122.4194), at(45));
+
+        let date = NaiveDate::from_ymd_opt(2023, 11, 15).unwrap();
+        let summary = storage.summarize_day("alice", date, 0, 0).unwrap();
+        assert_eq!(summary.first_seen_at, Some(at(0)));
+        assert_eq!(summary.last_seen_at, Some(at(60)));
+        assert_eq!(summary.trips.len(), 1);
+        assert_eq!(summary.moving_secs(), 20 * 60);
+        assert!((summary.distance_m() - 3_336.0).abs() < 20.0);
+
+        // An hour west of UTC the day starts an hour later, on the last
+        // point.
+        let west = storage.summarize_day("alice", date, -3_600, 0).unwrap();
+        assert_eq!(west.first_seen_at, Some(at(60)));
+        assert!(west.trips.is_empty());
+    }
+
+    #[test]
+    fn viewport_returns_points_inside_the_box_and_window() {
+        let storage = CircleStorage::in_memory().unwrap();
+        record(&storage, 1, "alice", (37.7749, -122.4194), 1_000);
+        record(&storage, 1, "alice", (37.7790, -122.4100), 2_000);
+        // Older than the last-known row: still part of the history.
+        record(&storage, 1, "alice", (37.7700, -122.4300), 1_500);
+        record(&storage, 2, "bob", (37.8000, -122.4000), 3_000);
+        // Oakland, just east of the box.
+        record(&storage, 1, "carol", (37.8044, -122.2712), 2_500);
+
+        let all = storage
+            .points_in_viewport(None, &SF, 0, 10_000, 100, 0)
+            .unwrap();
+        let times: Vec<i64> = all.iter().map(|p| p.timestamp).collect();
+        assert_eq!(times, vec![3_000, 2_000, 1_500, 1_000]);
+
+        let circle = storage
+            .points_in_viewport(Some(&[1; 32]), &SF, 1_200, 10_000, 1, 0)
+            .unwrap();
+        assert_eq!(circle.len(), 1);
+        assert_eq!(circle[0].timestamp, 2_000);
+
+        // Purged rows are left out, and the sweep deletes them.
+        assert!(storage
+            .points_in_viewport(None, &SF, 0, 10_000, 100, 2_000 + 86_401)
+            .unwrap()
+            .iter()
+            .all(|p| p.timestamp == 3_000));
+        storage.prune_expired_last_known(1_000_000).unwrap();
+        assert!(storage
+            .points_in_viewport(None, &SF, 0, 10_000, 100, 0)
+            .unwrap()
+            .is_empty());
+
+        let inverted = GeohashBounds {
+            min_lat: 38.0,
+            ..SF
+        };
+        assert!(matches!(
+            storage.points_in_viewport(None, &inverted, 0, 10_000, 100, 0),
+            Err(CircleError::InvalidData(_))
+        ));
+    }
+
+    #[test]
+    fn viewport_crossing_the_antimeridian_and_member_removal() {
+        let storage = CircleStorage::in_memory().unwrap();
+        record(&storage, 1, "alice", (-17.7, 178.0), 1_000);
+        record(&storage, 1, "bob", (-17.7, -179.0), 1_000);
+        record(&storage, 1, "carol", (-17.7, 170.0), 1_000);
+        let pacific = GeohashBounds {
+            min_lat: -20.0,
+            min_lon: 175.0,
+            max_lat: -15.0,
+            max_lon: -175.0,
+        };
+
+        let mut senders: Vec<String> = storage
+            .points_in_viewport(None, &pacific, 0, 10_000, 100, 0)
+            .unwrap()
+            .into_iter()
+            .map(|p| p.sender_pubkey)
+            .collect();
+        senders.sort();
+        assert_eq!(senders, vec!["alice", "bob"]);
+
+        storage.remove_last_known_member(&[1; 32], "alice").unwrap();
+        let after = storage
+            .points_in_viewport(None, &pacific, 0, 10_000, 100, 0)
+            .unwrap();
+        assert_eq!(after.len(), 1);
+        assert_eq!(after[0].sender_pubkey, "bob");
+    }
+}
@@ -0,0 +1,222 @@
+//! Storage for the location sharing pause switch.
+//!
+//! Extends [`CircleStorage`] with a global pause (one `user_settings` row
+//! holding when it started) and per-circle pauses (the `location_pauses`
+//! table defined in [`CircleStorage::initialize_schema`]). A circle is paused
+//! while either is set. See [`super::CircleManager::set_location_paused`] for
+//! where the pause is enforced.
+
+// Single-shot SQLite ops naturally hold the lock to completion; the parent
+// module already disables this lint at the file level for storage.rs.
+#![allow(clippy::significant_drop_tightening)]
+
+use rusqlite::{params, OptionalExtension};
+
+use super::error::{CircleError, Result};
+use super::storage::CircleStorage;
+use crate::nostr::mls::types::GroupId;
+
+/// `user_settings` key holding when the global pause started (JSON number).
+const LOCATION_PAUSED_KEY: &str = "location_paused";
+
+impl CircleStorage {
+    /// When sharing was paused for every circle, or `None` if it is not.
+    ///
+    /// # Errors
+    ///
+    /// Returns a database error on failure, or [`CircleError::InvalidData`]
+    /// if the stored value does not parse.
+    pub fn location_paused_at(&self) -> Result<Option<i64>> {
+        self.get_setting(LOCATION_PAUSED_KEY)?
+            .map(|json| {
+                serde_json::from_str(&json)
+                    .map_err(|e| CircleError::InvalidData(format!("Invalid location pause: {e}")))
+            })
+            .transpose()
+    }
+
+    /// Pauses sharing for every circle as of `paused_at`, or resumes it with
+    /// `None`. Pausing while paused keeps the original start.
+    ///
+    /// # Errors
+    ///
+    /// Returns a database error on failure.
+    pub fn set_location_paused(&self, paused_at: Option<i64>) -> Result<()> {
+        let conn = self
+            .conn()
+            .lock()
+            .map_err(|e| CircleError::Storage(format!("Failed to acquire database lock: {e}")))?;
+        match paused_at {
+            Some(at) => conn.execute(
+                "INSERT INTO user_settings (key, value) VALUES (?1, ?2)
+                 ON CONFLICT(key) DO NOTHING",
+                params![LOCATION_PAUSED_KEY, at.to_string()],
+            )?,
+            None => conn.execute(
+                "DELETE FROM user_settings WHERE key = ?1",
+                params![LOCATION_PAUSED_KEY],
+            )?,
+        };
+        Ok(())
+    }
+
+    /// When sharing was paused for one circle, or `None` if it is not. The
+    /// global pause is not taken into account.
+    ///
+    /// # Errors
+    ///
+    /// Returns a database error on failure.
+    pub fn circle_location_paused_at(&self, mls_group_id: &GroupId) -> Result<Option<i64>> {
+        let conn = self
+            .conn()
+            .lock()
+            .map_err(|e| CircleError::Storage(format!("Failed to acquire database lock: {e}")))?;
+        Ok(conn
+            .query_row(
+                "SELECT paused_at FROM location_pauses WHERE mls_group_id = ?1",
+                params![mls_group_id.as_slice()],
+                |r| r.get(0),
+            )
+            .optional()?)
+    }
+
+    /// Pauses sharing for one circle as of `paused_at`, or resumes it with
+    /// `None`. Pausing while paused keeps the original start.
+    ///
+    /// # Errors
+    ///
+    /// Returns a database error on failure.
+    pub fn set_circle_location_paused(
+        &self,
+        mls_group_id: &GroupId,
+        paused_at: Option<i64>,
+    ) -> Result<()> {
+        let conn = self
+            .conn()
+            .lock()
+            .map_err(|e| CircleError::Storage(format!("Failed to acquire database lock: {e}")))?;
+        match paused_at {
+            Some(at) => conn.execute(
+                "INSERT INTO location_pauses (mls_group_id, paused_at) VALUES (?1, ?2)
+                 ON CONFLICT(mls_group_id) DO NOTHING",
+                params![mls_group_id.as_slice(), at],
+            )?,
+            None => conn.execute(
+                "DELETE FROM location_pauses WHERE mls_group_id = ?1",
+                params![mls_group_id.as_slice()],
+            )?,
+        };
+        Ok(())
+    }
+
+    /// Circles paused individually, oldest pause first.
+    ///
+    /// # Errors
+    ///
+    /// Returns a database error on failure.
+    pub fn list_paused_circles(&self) -> Result<Vec<(GroupId, i64)>> {
+        let conn = self
+            .conn()
+            .lock()
+            .map_err(|e| CircleError::Storage(format!("Failed to acquire database lock: {e}")))?;
+        let mut stmt = conn.prepare(
+            "SELECT mls_group_id, paused_at FROM location_pauses
+             ORDER BY paused_at ASC, mls_group_id ASC",
+        )?;
+        let rows = stmt.query_map([], |r| {
+            Ok((GroupId::from_slice(&r.get::<_, Vec<u8>>(0)?), r.get(1)?))
+        })?;
+        Ok(rows.collect::<std::result::Result<Vec<_>, _>>()?)
+    }
+
+    /// Whether sharing to a circle is paused, globally or for the circle.
+    ///
+    /// # Errors
+    ///
+    /// Returns a database error on failure.
+    pub fn is_location_paused(&self, mls_group_id: &GroupId) -> Result<bool> {
+        Ok(self.location_paused_at()?.is_some()
+            || self.circle_location_paused_at(mls_group_id)?.is_some())
+    }
+
+    /// Whether sharing to the circle routed by `nostr_group_id` (the `h` tag
+    /// of its kind 445 events) is paused, globally or for the circle.
+    ///
+    /// # Errors
+    ///
+    /// Returns a database error on failure.
+    pub(crate) fn is_location_paused_for_nostr_group(&self, nostr_group_id: &[u8]) -> Result<bool> {
+        if self.location_paused_at()?.is_some() {
+            return Ok(true);
+        }
+        let conn = self
+            .conn()
+            .lock()
+            .map_err(|e| CircleError::Storage(format!("Failed to acquire database lock: {e}")))?;
+        let found = conn
+            .query_row(
+                "SELECT 1 FROM location_pauses p
+                 JOIN circles c ON c.mls_group_id = p.mls_group_id
+                 WHERE c.nostr_group_id = ?1",
+                params![nostr_group_id],
+                |_| Ok(()),
+            )
+            .optional()?;
+        Ok(found.is_some())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::circle::types::{Circle, CircleType};
+
+    #[test]
+    fn global_and_circle_pauses_combine() {
+        let storage = CircleStorage::in_memory().expect("in_memory");
+        let a = GroupId::from_slice(&[1; 32]);
+        let b = GroupId::from_slice(&[2; 32]);
+        storage
+            .save_circle(&Circle {
+                mls_group_id: a.clone(),
+                nostr_group_id: [7; 32],
+                display_name: "Family".to_string(),
+                circle_type: CircleType::LocationSharing,
+                relays: vec![],
+                created_at: 0,
+                updated_at: 0,
+            })
+            .unwrap();
+        assert!(!storage.is_location_paused(&a).unwrap());
+        assert!(!storage
+            .is_location_paused_for_nostr_group(&[7; 32])
+            .unwrap());
+
+        storage.set_circle_location_paused(&a, Some(10)).unwrap();
+        storage.set_circle_location_paused(&a, Some(20)).unwrap();
+        assert_eq!(storage.circle_location_paused_at(&a).unwrap(), Some(10));
+        assert!(storage.is_location_paused(&a).unwrap());
+        assert!(!storage.is_location_paused(&b).unwrap());
+        assert!(storage
+            .is_location_paused_for_nostr_group(&[7; 32])
+            .unwrap());
+        assert_eq!(
+            storage.list_paused_circles().unwrap(),
+            vec![(a.clone(), 10)]
+        );
+
+        storage.set_location_paused(Some(30)).unwrap();
+        storage.set_location_paused(Some(40)).unwrap();
+        assert_eq!(storage.location_paused_at().unwrap(), Some(30));
+        assert!(storage.is_location_paused(&b).unwrap());
+        assert!(storage
+            .is_location_paused_for_nostr_group(&[8; 32])
+            .unwrap());
+
+        storage.set_location_paused(None).unwrap();
+        storage.set_circle_location_paused(&a, None).unwrap();
+        assert!(storage.location_paused_at().unwrap().is_none());
+        assert!(!storage.is_location_paused(&a).unwrap());
+        assert!(storage.list_paused_circles().unwrap().is_empty());
+    }
+}
@@ -0,0 +1,174 @@
+//! Storage methods for time-limited meet pins.
+//!
+//! Extends [`CircleStorage`] with the `meet_pins` table defined in
+//! [`CircleStorage::initialize_schema`]. See [`crate::meet`] for the payload
+//! and its expiry rules.
+
+// Single-shot SQLite ops naturally hold the lock to completion; the parent
+// module already disables this lint at the file level for storage.rs.
+#![allow(clippy::significant_drop_tightening)]
+
+use rusqlite::params;
+
+use super::error::{CircleError, Result};
+use super::storage::CircleStorage;
+use crate::meet::{MeetPin, StoredMeetPin};
+use crate::nostr::mls::types::GroupId;
+
+impl CircleStorage {
+    /// Stores a pin for a circle. Saving the same pin id again overwrites it.
+    ///
+    /// # Errors
+    ///
+    /// Returns a database error on failure.
+    pub fn save_meet_pin(&self, mls_group_id: &GroupId, stored: &StoredMeetPin) -> Result<()> {
+        let conn = self
+            .conn()
+            .lock()
+            .map_err(|e| CircleError::Storage(format!("Failed to acquire database lock: {e}")))?;
+        let pin = &stored.pin;
+        conn.execute(
+            "INSERT INTO meet_pins
+                 (mls_group_id, pin_id, sender_pubkey, latitude, longitude,
+                  label, created_at, expires_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)
+             ON CONFLICT(mls_group_id, pin_id) DO UPDATE SET
+                 sender_pubkey = excluded.sender_pubkey,
+                 latitude = excluded.latitude,
+                 longitude = excluded.longitude,
+                 label = excluded.label,
+                 created_at = excluded.created_at,
+                 expires_at = excluded.expires_at",
+            params![
+                mls_group_id.as_slice(),
+                pin.pin_id,
+                stored.sender_pubkey,
+                pin.lat,
+                pin.lon,
+                pin.label,
+                pin.created_at,
+                pin.expires_at
+            ],
+        )?;
+        Ok(())
+    }
+
+    /// Lists a circle's pins that are still live at `now`, oldest first.
+    ///
+    /// Pins that have lapsed are deleted first, so an expired pin is never
+    /// returned even if no prune has run.
+    ///
+    /// # Errors
+    ///
+    /// Returns a database error on failure.
+    pub fn list_meet_pins(&self, mls_group_id: &GroupId, now: i64) -> Result<Vec<StoredMeetPin>> {
+        let conn = self
+            .conn()
+            .lock()
+            .map_err(|e| CircleError::Storage(format!("Failed to acquire database lock: {e}")))?;
+        conn.execute("DELETE FROM meet_pins WHERE expires_at <= ?1", params![now])?;
+        let mut stmt = conn.prepare(
+            "SELECT pin_id, sender_pubkey, latitude, longitude, label,
+                    created_at, expires_at
+             FROM meet_pins
+             WHERE mls_group_id = ?1
+             ORDER BY created_at ASC, pin_id ASC",
+        )?;
+        let rows = stmt.query_map(params![mls_group_id.as_slice()], |r| {
+            Ok(StoredMeetPin {
+                sender_pubkey: r.get(1)?,
+                pin: MeetPin {
+                    pin_id: r.get(0)?,
+                    lat: r.get(2)?,
+                    lon: r.get(3)?,
+                    label: r.get(4)?,
+                    created_at: r.get(5)?,
+                    expires_at: r.get(6)?,
+                },
+            })
+        })?;
+        Ok(rows.collect::<std::result::Result<Vec<_>, _>>()?)
+    }
+
+    /// Deletes every pin, in any circle, that has lapsed at `now`.
+    ///
+    /// Returns the number of pins removed.
+    ///
+    /// # Errors
+    ///
+    /// Returns a database error on failure.
+    pub fn delete_expired_meet_pins(&self, now: i64) -> Result<usize> {
+        let conn = self
+            .conn()
+            .lock()
+            .map_err(|e| CircleError::Storage(format!("Failed to acquire database lock: {e}")))?;
+        Ok(conn.execute("DELETE FROM meet_pins WHERE expires_at <= ?1", params![now])?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn make_storage() -> CircleStorage {
+        CircleStorage::in_memory().expect("in_memory")
+    }
+
+    fn stored(sender: &str, created_at: i64, ttl_secs: i64) -> StoredMeetPin {
+        StoredMeetPin {
+            sender_pubkey: sender.to_string(),
+            pin: MeetPin::new(
+                37.774_929_5,
+                -122.419_415_5,
+                Some("gate B"),
+                ttl_secs,
+                created_at,
+            )
+            .unwrap(),
+        }
+    }
+
+    #[test]
+    fn saved_pins_roundtrip_per_circle() {
+        let storage = make_storage();
+        let gid = GroupId::from_slice(&[1; 32]);
+        let other = GroupId::from_slice(&[2; 32]);
+        let first = stored("alice", 100, 600);
+        let second = stored("bob", 200, 600);
+        storage.save_meet_pin(&gid, &second).unwrap();
+        storage.save_meet_pin(&gid, &first).unwrap();
+        storage
+            .save_meet_pin(&other, &stored("carol", 100, 600))
+            .unwrap();
+
+        assert_eq!(storage.list_meet_pins(&gid, 300).unwrap(), [first, second]);
+        assert_eq!(storage.list_meet_pins(&other, 300).unwrap().len(), 1);
+    }
+
+    #[test]
+    fn expired_pins_are_removed() {
+        let storage = make_storage();
+        let gid = GroupId::from_slice(&[1; 32]);
+        let short = stored("alice", 0, 300);
+        let long = stored("bob", 0, 3_600);
+        storage.save_meet_pin(&gid, &short).unwrap();
+        storage.save_meet_pin(&gid, &long).unwrap();
+
+        // Listing never returns a lapsed pin, and deletes it.
+        assert_eq!(storage.list_meet_pins(&gid, 300).unwrap(), [long]);
+        assert_eq!(storage.delete_expired_meet_pins(300).unwrap(), 0);
+        assert_eq!(storage.delete_expired_meet_pins(3_600).unwrap(), 1);
+        assert!(storage.list_meet_pins(&gid, 0).unwrap().is_empty());
+    }
+
+    #[test]
+    fn pins_are_wiped_with_the_circle() {
+        let storage = make_storage();
+        let gid = GroupId::from_slice(&[1; 32]);
+        storage
+            .save_meet_pin(&gid, &stored("alice", 0, 600))
+            .unwrap();
+        storage.delete_circle(&gid).unwrap();
+        assert!(storage.list_meet_pins(&gid, 0).unwrap().is_empty());
+    }
+}
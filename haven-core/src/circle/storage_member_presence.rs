@@ -0,0 +1,125 @@
+//! Storage methods for member presence.
+//!
+//! Extends [`CircleStorage`] with the `member_presence` table defined in
+//! [`CircleStorage::initialize_schema`]: when each member of a circle was
+//! last heard from. See [`MemberPresence`].
+
+// Single-shot SQLite ops naturally hold the lock to completion; the parent
+// module already disables this lint at the file level for storage.rs.
+#![allow(clippy::significant_drop_tightening)]
+
+use rusqlite::params;
+
+use super::error::{CircleError, Result};
+use super::storage::CircleStorage;
+use super::types::{MemberPresence, PresenceStatus};
+use crate::nostr::mls::types::GroupId;
+
+impl CircleStorage {
+    /// Records a message from `member_pubkey` sent at `seen_at`, a location
+    /// if `is_location`. Timestamps only move forward.
+    ///
+    /// # Errors
+    ///
+    /// Returns a database error on failure.
+    pub fn record_member_seen(
+        &self,
+        mls_group_id: &GroupId,
+        member_pubkey: &str,
+        seen_at: i64,
+        is_location: bool,
+    ) -> Result<()> {
+        let conn = self
+            .conn()
+            .lock()
+            .map_err(|e| CircleError::Storage(format!("Failed to acquire database lock: {e}")))?;
+        let location_at = is_location.then_some(seen_at);
+        conn.execute(
+            "INSERT INTO member_presence
+                 (mls_group_id, member_pubkey, last_seen, last_location_at)
+             VALUES (?1, ?2, ?3, ?4)
+             ON CONFLICT(mls_group_id, member_pubkey) DO UPDATE SET
+                 last_seen = MAX(last_seen, excluded.last_seen),
+                 last_location_at = CASE
+                     WHEN excluded.last_location_at IS NULL THEN last_location_at
+                     WHEN last_location_at IS NULL THEN excluded.last_location_at
+                     ELSE MAX(last_location_at, excluded.last_location_at)
+                 END",
+            params![mls_group_id.as_slice(), member_pubkey, seen_at, location_at],
+        )?;
+        Ok(())
+    }
+
+    /// Returns every recorded member of a circle, classified at
+    /// `now_unix_secs`, ordered by pubkey.
+    ///
+    /// # Errors
+    ///
+    /// Returns a database error on failure.
+    pub fn member_presence(
+        &self,
+        mls_group_id: &GroupId,
+        now_unix_secs: i64,
+    ) -> Result<Vec<MemberPresence>> {
+        let conn = self
+            .conn()
+            .lock()
+            .map_err(|e| CircleError::Storage(format!("Failed to acquire database lock: {e}")))?;
+        let mut stmt = conn.prepare(
+            "SELECT member_pubkey, last_seen, last_location_at FROM member_presence
+             WHERE mls_group_id = ?1 ORDER BY member_pubkey",
+        )?;
+        let rows = stmt.query_map(params![mls_group_id.as_slice()], |r| {
+            let last_seen: i64 = r.get(1)?;
+            Ok(MemberPresence {
+                pubkey: r.get(0)?,
+                last_seen: Some(last_seen),
+                last_location_at: r.get(2)?,
+                status: PresenceStatus::classify(Some(last_seen), now_unix_secs),
+            })
+        })?;
+        Ok(rows.collect::<std::result::Result<Vec<_>, _>>()?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn timestamps_only_move_forward_and_go_with_the_member() {
+        let storage = CircleStorage::in_memory().unwrap();
+        let gid = GroupId::from_slice(&[1; 32]);
+        storage
+            .record_member_seen(&gid, "bob", 1_000, true)
+            .unwrap();
+        storage
+            .record_member_seen(&gid, "bob", 2_000, false)
+            .unwrap();
+        storage.record_member_seen(&gid, "bob", 500, true).unwrap();
+        storage
+            .record_member_seen(&gid, "carol", 100, false)
+            .unwrap();
+
+        let rows = storage.member_presence(&gid, 2_000).unwrap();
+        assert_eq!(rows.len(), 2);
+        assert_eq!(rows[0].pubkey, "bob");
+        assert_eq!(rows[0].last_seen, Some(2_000));
+        assert_eq!(rows[0].last_location_at, Some(1_000));
+        assert_eq!(rows[0].status, PresenceStatus::Active);
+        assert_eq!(rows[1].last_location_at, None);
+        assert!(storage
+            .member_presence(&GroupId::from_slice(&[2; 32]), 0)
+            .unwrap()
+            .is_empty());
+
+        storage
+            .reconcile_roster(&gid, &["bob".to_string(), "carol".to_string()])
+            .unwrap();
+        storage
+            .reconcile_roster(&gid, &["bob".to_string()])
+            .unwrap();
+        let rows = storage.member_presence(&gid, 2_000).unwrap();
+        assert_eq!(rows.len(), 1, "a departed member's presence goes");
+    }
+}
@@ -0,0 +1,223 @@
+//! Versioned schema migrations for `circles.db`.
+//!
+//! `CREATE TABLE IF NOT EXISTS` gives a fresh database the latest shape but
+//! never alters an existing table, so a column added later needs a migration
+//! on installs that predate it. Migrations are listed, in order, in
+//! [`CircleStorage::MIGRATIONS`]; [`CircleStorage::new`] runs every one the
+//! database has not recorded in `schema_migrations`, each in its own
+//! transaction together with its `schema_migrations` row, so a crash
+//! mid-migration leaves it unapplied rather than half-applied.
+//!
+//! Databases created before this table existed (schema v1, see
+//! `fixtures/circles_v1.sql`) have no rows and run every migration once; the
+//! migrations are written to be no-ops where their change is already present.
+
+// Single-shot SQLite ops naturally hold the lock to completion; the parent
+// module already disables this lint at the file level for storage.rs.
+#![allow(clippy::significant_drop_tightening)]
+
+use std::collections::HashSet;
+
+use rusqlite::{params, Connection};
+
+use super::error::{CircleError, Result};
+use super::storage::CircleStorage;
+
+/// One schema migration.
+pub(super) struct Migration {
+    /// Position in [`CircleStorage::MIGRATIONS`], starting at 1. Recorded in
+    /// `schema_migrations` once applied.
+    pub version: u32,
+    /// Short name, recorded alongside the version for diagnostics.
+    pub name: &'static str,
+    /// Applies the change. Runs inside a transaction.
+    pub up: fn(&Connection) -> Result<()>,
+}
+
+impl CircleStorage {
+    /// The schema version this build migrates databases to.
+    #[must_use]
+    pub fn latest_schema_version() -> u32 {
+        Self::MIGRATIONS.last().map_or(0, |m| m.version)
+    }
+
+    /// The highest migration version applied to this database.
+    ///
+    /// # Errors
+    ///
+    /// Returns a database error on failure.
+    pub fn schema_version(&self) -> Result<u32> {
+        let conn = self
+            .conn()
+            .lock()
+            .map_err(|e| CircleError::Storage(format!("Failed to acquire database lock: {e}")))?;
+        let version: Option<u32> =
+            conn.query_row("SELECT MAX(version) FROM schema_migrations", [], |r| {
+                r.get(0)
+            })?;
+        Ok(version.unwrap_or(0))
+    }
+
+    /// Runs every migration not yet recorded in `schema_migrations`, in
+    /// order.
+    pub(super) fn run_migrations(conn: &mut Connection) -> Result<()> {
+        let applied: HashSet<u32> = {
+            let mut stmt = conn.prepare("SELECT version FROM schema_migrations")?;
+            let rows = stmt.query_map([], |r| r.get(0))?;
+            rows.collect::<std::result::Result<_, _>>()?
+        };
+        let latest = Self::latest_schema_version();
+        if let Some(newer) = applied.iter().copied().filter(|&v| v > latest).max() {
+            // A downgrade: the newer build's migrations only ever add, so the
+            // tables this build knows are still usable.
+            log::warn!("circles.db is at schema v{newer}, newer than this build's v{latest}");
+        }
+
+        for migration in Self::MIGRATIONS {
+            if applied.contains(&migration.version) {
+                continue;
+            }
+            let tx = conn.transaction()?;
+            (migration.up)(&tx).map_err(|e| {
+                CircleError::Storage(format!(
+                    "Migration {} ({}) failed: {e}",
+                    migration.version, migration.name
+                ))
+            })?;
+            tx.execute(
+                "INSERT INTO schema_migrations (version, name, applied_at) VALUES (?1, ?2, ?3)",
+                params![
+                    migration.version,
+                    migration.name,
+                    chrono::Utc::now().timestamp()
+                ],
+            )?;
+            tx.commit()?;
+            log::info!(
+                "circles.db migrated to schema v{} ({})",
+                migration.version,
+                migration.name
+            );
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::circle::lifecycle::CircleLifecycle;
+    use crate::nostr::mls::types::{GroupId, GroupIdExt};
+
+    const V1_FIXTURE: &str = include_str!("fixtures/circles_v1.sql");
+
+    fn v1_database(dir: &tempfile::TempDir) -> std::path::PathBuf {
+        let path = dir.path().join("circles.db");
+        Connection::open(&path)
+            .unwrap()
+            .execute_batch(V1_FIXTURE)
+            .unwrap();
+        path
+    }
+
+    fn has_column(storage: &CircleStorage, table: &str, column: &str) -> bool {
+        let conn = storage.conn().lock().unwrap();
+        let mut stmt = conn
+            .prepare(&format!("PRAGMA table_info({table})"))
+            .unwrap();
+        let names: Vec<String> = stmt
+            .query_map([], |r| r.get(1))
+            .unwrap()
+            .collect::<std::result::Result<_, _>>()
+            .unwrap();
+        names.iter().any(|n| n == column)
+    }
+
+    #[test]
+    fn versions_are_consecutive_from_one() {
+        for (i, m) in CircleStorage::MIGRATIONS.iter().enumerate() {
+            assert_eq!(usize::try_from(m.version).unwrap(), i + 1, "{}", m.name);
+        }
+        let names: HashSet<_> = CircleStorage::MIGRATIONS.iter().map(|m| m.name).collect();
+        assert_eq!(names.len(), CircleStorage::MIGRATIONS.len());
+    }
+
+    #[test]
+    fn fresh_database_is_at_the_latest_version() {
+        let storage = CircleStorage::in_memory().unwrap();
+        assert_eq!(
+            storage.schema_version().unwrap(),
+            CircleStorage::latest_schema_version()
+        );
+    }
+
+    #[test]
+    fn v1_database_migrates_and_keeps_its_data() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = v1_database(&dir);
+
+        let storage = CircleStorage::new(&path, None).unwrap();
+        assert_eq!(
+            storage.schema_version().unwrap(),
+            CircleStorage::latest_schema_version()
+        );
+
+        for (table, column) in [
+            ("circle_memberships", "lifecycle"),
+            ("contacts", "verified_at"),
+            ("contacts", "avatar_id"),
+            ("circle_settings", "display_max_age_secs"),
+            ("outbox", "priority"),
+            ("published_key_packages", "key_package"),
+        ] {
+            assert!(has_column(&storage, table, column), "{table}.{column}");
+        }
+        assert!(!has_column(&storage, "published_key_packages", "kind"));
+        {
+            let conn = storage.conn().lock().unwrap();
+            let legacy: i64 = conn
+                .query_row(
+                    "SELECT COUNT(*) FROM sqlite_master
+                     WHERE name IN ('avatar_blobs', 'avatar_assignments', 'circle_salts')",
+                    [],
+                    |r| r.get(0),
+                )
+                .unwrap();
+            assert_eq!(legacy, 0);
+        }
+
+        let alice = storage.get_contact("aaaa").unwrap().unwrap();
+        assert_eq!(alice.display_name.as_deref(), Some("Alice"));
+        assert_eq!(alice.notes.as_deref(), Some("neighbour"));
+        assert!(!alice.is_verified());
+        assert!(alice.avatar_id.is_none());
+        assert_eq!(
+            storage
+                .get_lifecycle(&GroupId::from_slice(&[1; 32]))
+                .unwrap(),
+            Some(CircleLifecycle::Active)
+        );
+        // Tables the v1 release never had are created in their latest shape.
+        assert!(storage.list_outbox().unwrap().is_empty());
+    }
+
+    #[test]
+    fn reopening_a_migrated_database_runs_nothing() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = v1_database(&dir);
+        drop(CircleStorage::new(&path, None).unwrap());
+
+        let applied_at = |storage: &CircleStorage| -> Vec<(u32, i64)> {
+            let conn = storage.conn().lock().unwrap();
+            let mut stmt = conn
+                .prepare("SELECT version, applied_at FROM schema_migrations ORDER BY version")
+                .unwrap();
+            let rows = stmt.query_map([], |r| Ok((r.get(0)?, r.get(1)?))).unwrap();
+            rows.collect::<std::result::Result<_, _>>().unwrap()
+        };
+        let first = applied_at(&CircleStorage::new(&path, None).unwrap());
+        let second = applied_at(&CircleStorage::new(&path, None).unwrap());
+        assert_eq!(first, second);
+        assert_eq!(first.len(), CircleStorage::MIGRATIONS.len());
+    }
+}
@@ -0,0 +1,371 @@
+//! Storage methods for the offline outbox.
+//!
+//! Extends [`CircleStorage`] with the `outbox` table defined in
+//! [`CircleStorage::initialize_schema`]. See [`crate::relay::publish_queue`]
+//! for the retry rules.
+
+// Single-shot SQLite ops naturally hold the lock to completion; the parent
+// module already disables this lint at the file level for storage.rs.
+#![allow(clippy::significant_drop_tightening)]
+
+use nostr::Event;
+use rusqlite::params;
+
+use super::error::{CircleError, Result};
+use super::storage::CircleStorage;
+use crate::relay::publish_queue::{
+    outbox_expires_at, OutboxEntry, OutboxPriority, OutboxStatus, QueuedEvent,
+};
+
+/// Decodes the `relays` JSON column.
+fn decode_relays(relays_json: &str) -> Result<Vec<String>> {
+    serde_json::from_str(relays_json)
+        .map_err(|e| CircleError::InvalidData(format!("Invalid relays JSON: {e}")))
+}
+
+impl CircleStorage {
+    /// Queues a signed event whose publish failed at `priority`, counting
+    /// that publish as the first attempt.
+    ///
+    /// Returns `false` if the event is already queued.
+    ///
+    /// # Errors
+    ///
+    /// Returns a database error on failure.
+    pub fn enqueue_outbox_event(
+        &self,
+        event: &Event,
+        relays: &[String],
+        priority: OutboxPriority,
+        last_error: &str,
+        next_attempt_at: i64,
+        now: i64,
+    ) -> Result<bool> {
+        let event_json = serde_json::to_string(event)
+            .map_err(|e| CircleError::InvalidData(format!("Failed to encode event: {e}")))?;
+        let relays_json = serde_json::to_string(relays)
+            .map_err(|e| CircleError::InvalidData(format!("Failed to encode relays: {e}")))?;
+        let conn = self
+            .conn()
+            .lock()
+            .map_err(|e| CircleError::Storage(format!("Failed to acquire database lock: {e}")))?;
+        let inserted = conn.execute(
+            "INSERT INTO outbox
+                 (event_id, kind, event_json, relays, priority, status, attempts,
+                  next_attempt_at, expires_at, last_error, created_at, updated_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, 1, ?7, ?8, ?9, ?10, ?10)
+             ON CONFLICT(event_id) DO NOTHING",
+            params![
+                event.id.to_hex(),
+                event.kind.as_u16(),
+                event_json,
+                relays_json,
+                priority.rank(),
+                OutboxStatus::Pending.as_str(),
+                next_attempt_at,
+                outbox_expires_at(event, priority, now),
+                last_error,
+                now
+            ],
+        )?;
+        Ok(inserted > 0)
+    }
+
+    /// Pending events whose next attempt is due at or before `due_before`,
+    /// highest priority first, then oldest schedule first, at most `limit`.
+    ///
+    /// # Errors
+    ///
+    /// Returns a database error on failure.
+    pub(crate) fn due_outbox_events(
+        &self,
+        due_before: i64,
+        limit: usize,
+    ) -> Result<Vec<QueuedEvent>> {
+        let conn = self
+            .conn()
+            .lock()
+            .map_err(|e| CircleError::Storage(format!("Failed to acquire database lock: {e}")))?;
+        let mut stmt = conn.prepare(
+            "SELECT id, event_json, relays, attempts, expires_at, priority
+             FROM outbox
+             WHERE status = ?1 AND next_attempt_at <= ?2
+             ORDER BY priority DESC, next_attempt_at ASC, id ASC
+             LIMIT ?3",
+        )?;
+        let rows = stmt
+            .query_map(
+                params![
+                    OutboxStatus::Pending.as_str(),
+                    due_before,
+                    i64::try_from(limit).unwrap_or(i64::MAX)
+                ],
+                |r| {
+                    Ok((
+                        r.get::<_, i64>(0)?,
+                        r.get::<_, String>(1)?,
+                        r.get::<_, String>(2)?,
+                        r.get::<_, u32>(3)?,
+                        r.get::<_, i64>(4)?,
+                        r.get::<_, i64>(5)?,
+                    ))
+                },
+            )?
+            .collect::<std::result::Result<Vec<_>, _>>()?;
+        rows.into_iter()
+            .map(
+                |(id, event_json, relays_json, attempts, expires_at, rank)| {
+                    Ok(QueuedEvent {
+                        id,
+                        event_json,
+                        relays: decode_relays(&relays_json)?,
+                        attempts,
+                        expires_at,
+                        priority: OutboxPriority::from_rank(rank).ok_or_else(|| {
+                            CircleError::InvalidData(format!("Unknown outbox priority: {rank}"))
+                        })?,
+                    })
+                },
+            )
+            .collect()
+    }
+
+    /// Moves an entry to a final `status`.
+    ///
+    /// # Errors
+    ///
+    /// Returns a database error on failure.
+    pub(crate) fn finish_outbox_event(
+        &self,
+        id: i64,
+        status: OutboxStatus,
+        last_error: Option<&str>,
+        now: i64,
+    ) -> Result<()> {
+        let conn = self
+            .conn()
+            .lock()
+            .map_err(|e| CircleError::Storage(format!("Failed to acquire database lock: {e}")))?;
+        conn.execute(
+            "UPDATE outbox
+             SET status = ?2, last_error = COALESCE(?3, last_error), updated_at = ?4
+             WHERE id = ?1",
+            params![id, status.as_str(), last_error, now],
+        )?;
+        Ok(())
+    }
+
+    /// Records another failed send and schedules the next attempt.
+    ///
+    /// # Errors
+    ///
+    /// Returns a database error on failure.
+    pub(crate) fn reschedule_outbox_event(
+        &self,
+        id: i64,
+        attempts: u32,
+        next_attempt_at: i64,
+        last_error: &str,
+        now: i64,
+    ) -> Result<()> {
+        let conn = self
+            .conn()
+            .lock()
+            .map_err(|e| CircleError::Storage(format!("Failed to acquire database lock: {e}")))?;
+        conn.execute(
+            "UPDATE outbox
+             SET attempts = ?2, next_attempt_at = ?3, last_error = ?4, updated_at = ?5
+             WHERE id = ?1",
+            params![id, attempts, next_attempt_at, last_error, now],
+        )?;
+        Ok(())
+    }
+
+    /// Lists every outbox entry, newest first.
+    ///
+    /// # Errors
+    ///
+    /// Returns a database error on failure.
+    pub fn list_outbox(&self) -> Result<Vec<OutboxEntry>> {
+        let conn = self
+            .conn()
+            .lock()
+            .map_err(|e| CircleError::Storage(format!("Failed to acquire database lock: {e}")))?;
+        let mut stmt = conn.prepare(
+            "SELECT id, event_id, kind, relays, status, attempts, next_attempt_at,
+                    expires_at, last_error, created_at, updated_at, priority
+             FROM outbox
+             ORDER BY created_at DESC, id DESC",
+        )?;
+        let rows = stmt
+            .query_map([], |r| {
+                Ok((
+                    (
+                        r.get::<_, i64>(0)?,
+                        r.get::<_, String>(1)?,
+                        r.get::<_, u16>(2)?,
+                        r.get::<_, String>(3)?,
+                        r.get::<_, String>(4)?,
+                        r.get::<_, u32>(5)?,
+                    ),
+                    (
+                        r.get::<_, i64>(6)?,
+                        r.get::<_, i64>(7)?,
+                        r.get::<_, Option<String>>(8)?,
+                        r.get::<_, i64>(9)?,
+                        r.get::<_, i64>(10)?,
+                        r.get::<_, i64>(11)?,
+                    ),
+                ))
+            })?
+            .collect::<std::result::Result<Vec<_>, _>>()?;
+        rows.into_iter()
+            .map(
+                |(
+                    (id, event_id, kind, relays_json, status, attempts),
+                    (next_attempt_at, expires_at, last_error, created_at, updated_at, priority),
+                )| {
+                    Ok(OutboxEntry {
+                        id,
+                        event_id,
+                        kind,
+                        relays: decode_relays(&relays_json)?,
+                        priority: OutboxPriority::from_rank(priority).ok_or_else(|| {
+                            CircleError::InvalidData(format!("Unknown outbox priority: {priority}"))
+                        })?,
+                        status: OutboxStatus::parse(&status).ok_or_else(|| {
+                            CircleError::InvalidData(format!("Unknown outbox status: {status}"))
+                        })?,
+                        attempts,
+                        next_attempt_at,
+                        expires_at,
+                        last_error,
+                        created_at,
+                        updated_at,
+                    })
+                },
+            )
+            .collect()
+    }
+
+    /// Deletes sent, expired, and abandoned entries last updated before
+    /// `before`. Pending entries are kept.
+    ///
+    /// Returns the number of entries removed.
+    ///
+    /// # Errors
+    ///
+    /// Returns a database error on failure.
+    pub fn prune_outbox(&self, before: i64) -> Result<usize> {
+        let conn = self
+            .conn()
+            .lock()
+            .map_err(|e| CircleError::Storage(format!("Failed to acquire database lock: {e}")))?;
+        Ok(conn.execute(
+            "DELETE FROM outbox WHERE status != ?1 AND updated_at < ?2",
+            params![OutboxStatus::Pending.as_str(), before],
+        )?)
+    }
+
+    /// Deletes every outbox entry, pending or not.
+    ///
+    /// # Errors
+    ///
+    /// Returns a database error on failure.
+    pub fn wipe_outbox(&self) -> Result<()> {
+        let conn = self
+            .conn()
+            .lock()
+            .map_err(|e| CircleError::Storage(format!("Failed to acquire database lock: {e}")))?;
+        conn.execute("DELETE FROM outbox", [])?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use nostr::{EventBuilder, Keys, Kind};
+
+    fn make_storage() -> CircleStorage {
+        CircleStorage::in_memory().expect("in_memory")
+    }
+
+    fn event() -> Event {
+        EventBuilder::new(Kind::Custom(445), "ciphertext")
+            .sign_with_keys(&Keys::generate())
+            .unwrap()
+    }
+
+    #[test]
+    fn due_events_follow_the_schedule() {
+        let storage = make_storage();
+        let relays = vec!["wss://relay.example.com".to_string()];
+        let late = event();
+        let early = event();
+        storage
+            .enqueue_outbox_event(&late, &relays, OutboxPriority::Location, "down", 200, 0)
+            .unwrap();
+        storage
+            .enqueue_outbox_event(&early, &relays, OutboxPriority::Location, "down", 100, 0)
+            .unwrap();
+
+        assert!(storage.due_outbox_events(99, 10).unwrap().is_empty());
+        let due = storage.due_outbox_events(200, 10).unwrap();
+        assert_eq!(due.len(), 2);
+        assert_eq!(due[0].event_json, serde_json::to_string(&early).unwrap());
+        assert_eq!(due[0].relays, relays);
+        assert_eq!(storage.due_outbox_events(200, 1).unwrap().len(), 1);
+
+        storage
+            .finish_outbox_event(due[0].id, OutboxStatus::Sent, None, 150)
+            .unwrap();
+        assert_eq!(storage.due_outbox_events(200, 10).unwrap().len(), 1);
+    }
+
+    #[test]
+    fn due_events_put_priority_before_schedule() {
+        let storage = make_storage();
+        let relays = vec!["wss://relay.example.com".to_string()];
+        let location = event();
+        let sos = event();
+        storage
+            .enqueue_outbox_event(&location, &relays, OutboxPriority::Location, "down", 100, 0)
+            .unwrap();
+        storage
+            .enqueue_outbox_event(&sos, &relays, OutboxPriority::Sos, "down", 200, 0)
+            .unwrap();
+
+        let due = storage.due_outbox_events(200, 10).unwrap();
+        assert_eq!(due[0].event_json, serde_json::to_string(&sos).unwrap());
+        let listed = storage.list_outbox().unwrap();
+        assert!(listed
+            .iter()
+            .any(|e| e.event_id == sos.id.to_hex() && e.priority == OutboxPriority::Sos));
+    }
+
+    #[test]
+    fn prune_keeps_pending_entries() {
+        let storage = make_storage();
+        let relays = vec!["wss://relay.example.com".to_string()];
+        storage
+            .enqueue_outbox_event(&event(), &relays, OutboxPriority::Location, "down", 10, 0)
+            .unwrap();
+        storage
+            .enqueue_outbox_event(&event(), &relays, OutboxPriority::Location, "down", 10, 0)
+            .unwrap();
+        let id = storage.due_outbox_events(10, 1).unwrap()[0].id;
+        storage
+            .finish_outbox_event(id, OutboxStatus::Expired, None, 5)
+            .unwrap();
+
+        assert_eq!(storage.prune_outbox(5).unwrap(), 0);
+        assert_eq!(storage.prune_outbox(6).unwrap(), 1);
+        let remaining = storage.list_outbox().unwrap();
+        assert_eq!(remaining.len(), 1);
+        assert_eq!(remaining[0].status, OutboxStatus::Pending);
+
+        storage.wipe_outbox().unwrap();
+        assert!(storage.list_outbox().unwrap().is_empty());
+    }
+}
@@ -0,0 +1,152 @@
+//! Storage methods for receive-side precision baselines.
+//!
+//! Extends [`CircleStorage`] with the `precision_baselines` table defined in
+//! [`CircleStorage::initialize_schema`]. See [`crate::location::precision`]
+//! for the detection rules.
+
+// Single-shot SQLite ops naturally hold the lock to completion; the parent
+// module already disables this lint at the file level for storage.rs.
+#![allow(clippy::significant_drop_tightening)]
+
+use rusqlite::{params, OptionalExtension};
+
+use super::error::{CircleError, Result};
+use super::storage::CircleStorage;
+use crate::location::precision::{observe, PrecisionAnomaly, PrecisionBaseline, PrecisionClass};
+use crate::nostr::mls::types::GroupId;
+
+impl CircleStorage {
+    /// Returns the stored precision baseline for `sender_pubkey` in a circle.
+    ///
+    /// # Errors
+    ///
+    /// Returns a database error on failure.
+    pub fn get_precision_baseline(
+        &self,
+        mls_group_id: &GroupId,
+        sender_pubkey: &str,
+    ) -> Result<Option<PrecisionBaseline>> {
+        let conn = self
+            .conn()
+            .lock()
+            .map_err(|e| CircleError::Storage(format!("Failed to acquire database lock: {e}")))?;
+        read_baseline(&conn, mls_group_id, sender_pubkey)
+    }
+
+    /// Feeds one received location's precision into the sender's baseline and
+    /// stores the result.
+    ///
+    /// Returns the anomaly to report, if the location is finer than an
+    /// established baseline.
+    ///
+    /// # Errors
+    ///
+    /// Returns a database error on failure.
+    pub fn observe_precision(
+        &self,
+        mls_group_id: &GroupId,
+        sender_pubkey: &str,
+        observed: PrecisionClass,
+        now: i64,
+    ) -> Result<Option<PrecisionAnomaly>> {
+        let mut conn = self
+            .conn()
+            .lock()
+            .map_err(|e| CircleError::Storage(format!("Failed to acquire database lock: {e}")))?;
+        let tx = conn.transaction()?;
+        let previous = read_baseline(&tx, mls_group_id, sender_pubkey)?;
+        let (baseline, anomaly) = observe(previous, observed);
+        tx.execute(
+            "INSERT INTO precision_baselines
+                 (mls_group_id, sender_pubkey, class, samples, updated_at)
+             VALUES (?1, ?2, ?3, ?4, ?5)
+             ON CONFLICT(mls_group_id, sender_pubkey) DO UPDATE SET
+                 class = excluded.class,
+                 samples = excluded.samples,
+                 updated_at = excluded.updated_at",
+            params![
+                mls_group_id.as_slice(),
+                sender_pubkey,
+                baseline.class.as_str(),
+                i64::from(baseline.samples),
+                now
+            ],
+        )?;
+        tx.commit()?;
+        Ok(anomaly)
+    }
+}
+
+fn read_baseline(
+    conn: &rusqlite::Connection,
+    mls_group_id: &GroupId,
+    sender_pubkey: &str,
+) -> Result<Option<PrecisionBaseline>> {
+    let row: Option<(String, i64)> = conn
+        .query_row(
+            "SELECT class, samples FROM precision_baselines
+             WHERE mls_group_id = ?1 AND sender_pubkey = ?2",
+            params![mls_group_id.as_slice(), sender_pubkey],
+            |r| Ok((r.get(0)?, r.get(1)?)),
+        )
+        .optional()?;
+    // An unreadable row is treated as no baseline: the next location simply
+    // starts a fresh one.
+    Ok(row.and_then(|(class, samples)| {
+        Some(PrecisionBaseline {
+            class: PrecisionClass::parse(&class)?,
+            samples: u32::try_from(samples).ok()?,
+        })
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::location::precision::BASELINE_MIN_SAMPLES;
+
+    fn make_storage() -> CircleStorage {
+        CircleStorage::in_memory().expect("in_memory")
+    }
+
+    #[test]
+    fn observe_persists_and_flags_upgrade() {
+        let storage = make_storage();
+        let gid = GroupId::from_slice(&[1; 32]);
+        for i in 0..BASELINE_MIN_SAMPLES {
+            let anomaly = storage
+                .observe_precision(&gid, "alice", PrecisionClass::Coarse, i64::from(i))
+                .unwrap();
+            assert!(anomaly.is_none());
+        }
+        assert_eq!(
+            storage.get_precision_baseline(&gid, "alice").unwrap(),
+            Some(PrecisionBaseline {
+                class: PrecisionClass::Coarse,
+                samples: BASELINE_MIN_SAMPLES,
+            })
+        );
+
+        let anomaly = storage
+            .observe_precision(&gid, "alice", PrecisionClass::Exact, 10)
+            .unwrap()
+            .expect("flagged");
+        assert_eq!(anomaly.baseline, PrecisionClass::Coarse);
+        assert_eq!(anomaly.observed, PrecisionClass::Exact);
+    }
+
+    #[test]
+    fn baselines_are_per_circle_and_sender() {
+        let storage = make_storage();
+        let a = GroupId::from_slice(&[1; 32]);
+        let b = GroupId::from_slice(&[2; 32]);
+        storage
+            .observe_precision(&a, "alice", PrecisionClass::Coarse, 1)
+            .unwrap();
+        assert!(storage
+            .get_precision_baseline(&b, "alice")
+            .unwrap()
+            .is_none());
+        assert!(storage.get_precision_baseline(&a, "bob").unwrap().is_none());
+    }
+}
@@ -0,0 +1,374 @@
+//! Storage methods for the processed-event cache.
+//!
+//! Extends [`CircleStorage`] with the `processed_events` table defined in
+//! [`CircleStorage::initialize_schema`]: what ingesting each `kind:445` event
+//! produced, keyed by event id. The engine reports an event it has already
+//! ingested as stale and surfaces nothing, so without this a location
+//! re-fetched after a restart could never be shown again.
+//!
+//! Only results worth re-rendering are kept — locations, SOS broadcasts, meet
+//! pins and group updates. One-shot signals (check-in prompts, precision
+//! anomalies, joins) are not replayed. Rows expire on the last-known clock
+//! ([`crate::location::LOCATION_RETENTION_SECS`]) and go with their circle.
+
+// Single-shot SQLite ops naturally hold the lock to completion; the parent
+// module already disables this lint at the file level for storage.rs.
+#![allow(clippy::significant_drop_tightening)]
+
+use nostr::EventId;
+use rusqlite::{params, OptionalExtension};
+use serde::{Deserialize, Serialize};
+
+use super::error::{CircleError, Result};
+use super::storage::CircleStorage;
+use crate::nostr::mls::types::{GroupId, LocationMessageResult, MembershipDelta};
+
+/// What ingesting an event produced, as remembered in `processed_events`.
+#[derive(Debug)]
+pub enum ProcessedOutcome {
+    /// The replayable results it surfaced, in order.
+    Results(Vec<LocationMessageResult>),
+    /// The engine rejected it, with the (redacted) error.
+    Failed(String),
+}
+
+/// Stored form of one replayable [`LocationMessageResult`].
+#[derive(Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum StoredResult {
+    Location {
+        sender_pubkey: String,
+        content: String,
+        group_id: String,
+        epoch: u64,
+    },
+    Sos {
+        sender_pubkey: String,
+        content: String,
+        group_id: String,
+        epoch: u64,
+    },
+    MeetPin {
+        sender_pubkey: String,
+        content: String,
+        group_id: String,
+        epoch: u64,
+    },
+    GroupUpdate {
+        group_id: String,
+        added: Vec<String>,
+        removed: Vec<String>,
+    },
+}
+
+impl StoredResult {
+    fn from_result(result: &LocationMessageResult) -> Option<Self> {
+        Some(match result {
+            LocationMessageResult::Location {
+                sender_pubkey,
+                content,
+                group_id,
+                epoch,
+            } => Self::Location {
+                sender_pubkey: sender_pubkey.clone(),
+                content: content.clone(),
+                group_id: hex::encode(group_id.as_slice()),
+                epoch: *epoch,
+            },
+            LocationMessageResult::Sos {
+                sender_pubkey,
+                content,
+                group_id,
+                epoch,
+            } => Self::Sos {
+                sender_pubkey: sender_pubkey.clone(),
+                content: content.clone(),
+                group_id: hex::encode(group_id.as_slice()),
+                epoch: *epoch,
+            },
+            LocationMessageResult::MeetPin {
+                sender_pubkey,
+                content,
+                group_id,
+                epoch,
+            } => Self::MeetPin {
+                sender_pubkey: sender_pubkey.clone(),
+                content: content.clone(),
+                group_id: hex::encode(group_id.as_slice()),
+                epoch: *epoch,
+            },
+            LocationMessageResult::GroupUpdate {
+                group_id,
+                membership,
+            } => Self::GroupUpdate {
+                group_id: hex::encode(group_id.as_slice()),
+                added: membership.added.clone(),
+                removed: membership.removed.clone(),
+            },
+            _ => return None,
+        })
+    }
+
+    fn into_result(self) -> Result<LocationMessageResult> {
+        let group = |hex_id: &str| {
+            hex::decode(hex_id)
+                .map(|bytes| GroupId::from_slice(&bytes))
+                .map_err(|e| CircleError::InvalidData(format!("Invalid cached group id: {e}")))
+        };
+        Ok(match self {
+            Self::Location {
+                sender_pubkey,
+                content,
+                group_id,
+                epoch,
+            } => LocationMessageResult::Location {
+                sender_pubkey,
+                content,
+                group_id: group(&group_id)?,
+                epoch,
+            },
+            Self::Sos {
+                sender_pubkey,
+                content,
+                group_id,
+                epoch,
+            } => LocationMessageResult::Sos {
+                sender_pubkey,
+                content,
+                group_id: group(&group_id)?,
+                epoch,
+            },
+            Self::MeetPin {
+                sender_pubkey,
+                content,
+                group_id,
+                epoch,
+            } => LocationMessageResult::MeetPin {
+                sender_pubkey,
+                content,
+                group_id: group(&group_id)?,
+                epoch,
+            },
+            Self::GroupUpdate {
+                group_id,
+                added,
+                removed,
+            } => LocationMessageResult::GroupUpdate {
+                group_id: group(&group_id)?,
+                membership: MembershipDelta { added, removed },
+            },
+        })
+    }
+}
+
+/// `outcome` column value for a row holding results.
+const OUTCOME_RESULTS: &str = "results";
+
+/// `outcome` column value for a row holding an ingest failure.
+const OUTCOME_FAILED: &str = "failed";
+
+/// When a remembered event was ingested and when its row expires.
+#[derive(Debug, Clone, Copy)]
+pub struct ProcessedAt {
+    /// Unix seconds the event was ingested.
+    pub processed_at: i64,
+    /// Unix seconds after which the row is no longer returned.
+    pub purge_after: i64,
+}
+
+impl CircleStorage {
+    /// Remembers the results ingesting `event_id` surfaced. Results that are
+    /// not replayable are left out; nothing is stored when none remain. An
+    /// event already remembered keeps its first outcome.
+    ///
+    /// # Errors
+    ///
+    /// Returns a database error on failure.
+    pub fn record_processed_results(
+        &self,
+        event_id: &EventId,
+        nostr_group_id: &[u8],
+        results: &[LocationMessageResult],
+        at: ProcessedAt,
+    ) -> Result<()> {
+        let stored: Vec<StoredResult> = results
+            .iter()
+            .filter_map(StoredResult::from_result)
+            .collect();
+        if stored.is_empty() {
+            return Ok(());
+        }
+        let json = serde_json::to_string(&stored)
+            .map_err(|e| CircleError::InvalidData(format!("Failed to encode results: {e}")))?;
+        self.insert_processed_event(event_id, nostr_group_id, OUTCOME_RESULTS, &json, at)
+    }
+
+    /// Remembers that the engine rejected `event_id` with `error`, which
+    /// must already be redacted. An event already remembered keeps its first
+    /// outcome.
+    ///
+    /// # Errors
+    ///
+    /// Returns a database error on failure.
+    pub fn record_processed_failure(
+        &self,
+        event_id: &EventId,
+        nostr_group_id: &[u8],
+        error: &str,
+        at: ProcessedAt,
+    ) -> Result<()> {
+        self.insert_processed_event(event_id, nostr_group_id, OUTCOME_FAILED, error, at)
+    }
+
+    fn insert_processed_event(
+        &self,
+        event_id: &EventId,
+        nostr_group_id: &[u8],
+        outcome: &str,
+        payload: &str,
+        at: ProcessedAt,
+    ) -> Result<()> {
+        let conn = self
+            .conn()
+            .lock()
+            .map_err(|e| CircleError::Storage(format!("Failed to acquire database lock: {e}")))?;
+        conn.execute(
+            "INSERT OR IGNORE INTO processed_events
+                 (event_id, nostr_group_id, outcome, payload, processed_at, purge_after)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+            params![
+                event_id.as_bytes(),
+                nostr_group_id,
+                outcome,
+                payload,
+                at.processed_at,
+                at.purge_after
+            ],
+        )?;
+        Ok(())
+    }
+
+    /// What ingesting `event_id` produced, if it is remembered and its row
+    /// has not expired at `now`.
+    ///
+    /// # Errors
+    ///
+    /// Returns a database error on failure, or [`CircleError::InvalidData`]
+    /// for a row that does not decode.
+    pub fn processed_event(
+        &self,
+        event_id: &EventId,
+        now: i64,
+    ) -> Result<Option<ProcessedOutcome>> {
+        let row: Option<(String, String)> = {
+            let conn = self.conn().lock().map_err(|e| {
+                CircleError::Storage(format!("Failed to acquire database lock: {e}"))
+            })?;
+            conn.query_row(
+                "SELECT outcome, payload FROM processed_events
+                 WHERE event_id = ?1 AND purge_after >= ?2",
+                params![event_id.as_bytes(), now],
+                |r| Ok((r.get(0)?, r.get(1)?)),
+            )
+            .optional()?
+        };
+        let Some((kind, payload)) = row else {
+            return Ok(None);
+        };
+        if kind == OUTCOME_FAILED {
+            return Ok(Some(ProcessedOutcome::Failed(payload)));
+        }
+        let stored: Vec<StoredResult> = serde_json::from_str(&payload)
+            .map_err(|e| CircleError::InvalidData(format!("Invalid cached results: {e}")))?;
+        stored
+            .into_iter()
+            .map(StoredResult::into_result)
+            .collect::<Result<Vec<_>>>()
+            .map(|results| Some(ProcessedOutcome::Results(results)))
+    }
+
+    /// Forgets every remembered event of the circle routed at
+    /// `nostr_group_id`, e.g. after the engine withdrew earlier output.
+    ///
+    /// # Errors
+    ///
+    /// Returns a database error on failure.
+    pub fn forget_processed_events(&self, nostr_group_id: &[u8]) -> Result<usize> {
+        let conn = self
+            .conn()
+            .lock()
+            .map_err(|e| CircleError::Storage(format!("Failed to acquire database lock: {e}")))?;
+        Ok(conn.execute(
+            "DELETE FROM processed_events WHERE nostr_group_id = ?1",
+            params![nostr_group_id],
+        )?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn location(content: &str) -> LocationMessageResult {
+        LocationMessageResult::Location {
+            sender_pubkey: "aa".repeat(32),
+            content: content.to_string(),
+            group_id: GroupId::from_slice(&[1; 32]),
+            epoch: 3,
+        }
+    }
+
+    #[test]
+    fn remembers_replayable_results_and_failures() {
+        let storage = CircleStorage::in_memory().unwrap();
+        let ngid = [7u8; 32];
+        let (seen, failed, quiet) = (
+            EventId::all_zeros(),
+            EventId::from_slice(&[1; 32]).unwrap(),
+            EventId::from_slice(&[2; 32]).unwrap(),
+        );
+        let at = ProcessedAt {
+            processed_at: 100,
+            purge_after: 200,
+        };
+        let joined = LocationMessageResult::Joined {
+            group_id: GroupId::from_slice(&[1; 32]),
+        };
+        storage
+            .record_processed_results(&seen, &ngid, &[location("first"), joined], at)
+            .unwrap();
+        // The first outcome sticks.
+        storage
+            .record_processed_results(&seen, &ngid, &[location("second")], at)
+            .unwrap();
+        storage
+            .record_processed_failure(&failed, &ngid, "bad", at)
+            .unwrap();
+        storage
+            .record_processed_results(&quiet, &ngid, &[], at)
+            .unwrap();
+
+        match storage.processed_event(&seen, 150).unwrap() {
+            Some(ProcessedOutcome::Results(results)) => {
+                assert_eq!(results.len(), 1, "joins are not replayed");
+                assert!(matches!(
+                    &results[0],
+                    LocationMessageResult::Location { content, epoch: 3, .. } if content == "first"
+                ));
+            }
+            other => panic!("expected results, got {other:?}"),
+        }
+        assert!(matches!(
+            storage.processed_event(&failed, 150).unwrap(),
+            Some(ProcessedOutcome::Failed(error)) if error == "bad"
+        ));
+        assert!(storage.processed_event(&quiet, 150).unwrap().is_none());
+        assert!(
+            storage.processed_event(&seen, 201).unwrap().is_none(),
+            "expired"
+        );
+
+        assert_eq!(storage.forget_processed_events(&ngid).unwrap(), 2);
+        assert!(storage.processed_event(&seen, 150).unwrap().is_none());
+    }
+}
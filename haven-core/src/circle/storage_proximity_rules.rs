@@ -0,0 +1,195 @@
+//! Storage methods for proximity alerts.
+//!
+//! Extends [`CircleStorage`] with the `proximity_rules` table defined in
+//! [`CircleStorage::initialize_schema`]. See [`crate::location::proximity`]
+//! for how rules are judged.
+
+// Single-shot SQLite ops naturally hold the lock to completion; the parent
+// module already disables this lint at the file level for storage.rs.
+#![allow(clippy::significant_drop_tightening)]
+
+use rusqlite::{params, Row};
+
+use super::error::{CircleError, Result};
+use super::storage::CircleStorage;
+use crate::location::{ProximityRule, ProximityTarget};
+use crate::nostr::mls::types::GroupId;
+
+fn read_rule(row: &Row<'_>) -> rusqlite::Result<ProximityRule> {
+    let target = match row.get::<_, Option<String>>(3)? {
+        Some(pubkey) => ProximityTarget::Member(pubkey),
+        None => ProximityTarget::Place {
+            label: row.get(4)?,
+            latitude: row.get(5)?,
+            longitude: row.get(6)?,
+        },
+    };
+    Ok(ProximityRule {
+        rule_id: row.get(0)?,
+        mls_group_id: GroupId::new(row.get(1)?),
+        member_pubkey: row.get(2)?,
+        target,
+        range_m: row.get(7)?,
+        created_at: row.get(8)?,
+        within: row.get(9)?,
+    })
+}
+
+impl CircleStorage {
+    /// Stores a proximity rule. Saving the same rule id again overwrites it.
+    ///
+    /// # Errors
+    ///
+    /// Returns a database error on failure.
+    pub fn save_proximity_rule(&self, rule: &ProximityRule) -> Result<()> {
+        let (target_pubkey, place) = match &rule.target {
+            ProximityTarget::Member(pubkey) => (Some(pubkey.as_str()), None),
+            ProximityTarget::Place {
+                label,
+                latitude,
+                longitude,
+            } => (None, Some((label.as_str(), *latitude, *longitude))),
+        };
+        let conn = self
+            .conn()
+            .lock()
+            .map_err(|e| CircleError::Storage(format!("Failed to acquire database lock: {e}")))?;
+        conn.execute(
+            "INSERT OR REPLACE INTO proximity_rules
+                 (rule_id, mls_group_id, member_pubkey, target_pubkey, place_label,
+                  place_latitude, place_longitude, range_m, created_at, within)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10)",
+            params![
+                rule.rule_id,
+                rule.mls_group_id.as_slice(),
+                rule.member_pubkey,
+                target_pubkey,
+                place.map(|p| p.0),
+                place.map(|p| p.1),
+                place.map(|p| p.2),
+                rule.range_m,
+                rule.created_at,
+                rule.within
+            ],
+        )?;
+        Ok(())
+    }
+
+    /// Lists proximity rules, of one circle or (with `None`) of all circles,
+    /// oldest first.
+    ///
+    /// # Errors
+    ///
+    /// Returns a database error on failure.
+    pub fn proximity_rules(&self, mls_group_id: Option<&GroupId>) -> Result<Vec<ProximityRule>> {
+        let conn = self
+            .conn()
+            .lock()
+            .map_err(|e| CircleError::Storage(format!("Failed to acquire database lock: {e}")))?;
+        let mut stmt = conn.prepare(
+            "SELECT rule_id, mls_group_id, member_pubkey, target_pubkey, place_label,
+                    place_latitude, place_longitude, range_m, created_at, within
+             FROM proximity_rules
+             WHERE ?1 IS NULL OR mls_group_id = ?1
+             ORDER BY created_at ASC, rule_id ASC",
+        )?;
+        let rows = stmt.query_map(params![mls_group_id.map(GroupId::as_slice)], read_rule)?;
+        Ok(rows.collect::<rusqlite::Result<Vec<_>>>()?)
+    }
+
+    /// Deletes a proximity rule. Returns whether it existed.
+    ///
+    /// # Errors
+    ///
+    /// Returns a database error on failure.
+    pub fn delete_proximity_rule(&self, rule_id: &str) -> Result<bool> {
+        let conn = self
+            .conn()
+            .lock()
+            .map_err(|e| CircleError::Storage(format!("Failed to acquire database lock: {e}")))?;
+        let deleted = conn.execute(
+            "DELETE FROM proximity_rules WHERE rule_id = ?1",
+            params![rule_id],
+        )?;
+        Ok(deleted > 0)
+    }
+
+    /// Records whether a rule's member was within range at the last
+    /// evaluation.
+    ///
+    /// # Errors
+    ///
+    /// Returns a database error on failure.
+    pub fn set_proximity_within(&self, rule_id: &str, within: bool) -> Result<()> {
+        let conn = self
+            .conn()
+            .lock()
+            .map_err(|e| CircleError::Storage(format!("Failed to acquire database lock: {e}")))?;
+        conn.execute(
+            "UPDATE proximity_rules SET within = ?2 WHERE rule_id = ?1",
+            params![rule_id, within],
+        )?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::nostr::mls::types::GroupIdExt;
+
+    #[test]
+    fn rules_roundtrip_and_go_with_either_member() {
+        let storage = CircleStorage::in_memory().unwrap();
+        let gid = GroupId::from_slice(&[1; 32]);
+        let home = ProximityRule::new(
+            gid.clone(),
+            "bob".to_string(),
+            ProximityTarget::Place {
+                label: "Home".to_string(),
+                latitude: 48.858_37,
+                longitude: 2.294_48,
+            },
+            2_000.0,
+            100,
+        )
+        .unwrap();
+        let carol = ProximityRule::new(
+            gid.clone(),
+            "dave".to_string(),
+            ProximityTarget::Member("carol".to_string()),
+            500.0,
+            200,
+        )
+        .unwrap();
+        storage.save_proximity_rule(&home).unwrap();
+        storage.save_proximity_rule(&carol).unwrap();
+        assert_eq!(
+            storage.proximity_rules(Some(&gid)).unwrap(),
+            vec![home.clone(), carol.clone()]
+        );
+        assert!(storage
+            .proximity_rules(Some(&GroupId::from_slice(&[2; 32])))
+            .unwrap()
+            .is_empty());
+
+        storage.set_proximity_within(&home.rule_id, true).unwrap();
+        assert!(storage.proximity_rules(None).unwrap()[0].within);
+
+        // Removing the target member drops the rule watching for them.
+        storage
+            .reconcile_roster(
+                &gid,
+                &["bob".to_string(), "carol".to_string(), "dave".to_string()],
+            )
+            .unwrap();
+        storage
+            .reconcile_roster(&gid, &["bob".to_string(), "dave".to_string()])
+            .unwrap();
+        assert_eq!(storage.proximity_rules(None).unwrap().len(), 1);
+
+        assert!(storage.delete_proximity_rule(&home.rule_id).unwrap());
+        assert!(!storage.delete_proximity_rule(&home.rule_id).unwrap());
+        assert!(storage.proximity_rules(None).unwrap().is_empty());
+    }
+}
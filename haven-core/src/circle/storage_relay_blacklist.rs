@@ -0,0 +1,319 @@
+//! Storage methods for the relay blacklist.
+//!
+//! Extends [`CircleStorage`] with persistence for the
+//! `community_relay_blacklist` and `relay_overrides` tables defined in
+//! [`CircleStorage::initialize_schema`], plus the opt-in flag. The merged,
+//! enforceable view is [`CircleStorage::load_relay_blacklist`]; see
+//! [`crate::relay::blacklist`] for the precedence rules.
+//!
+//! # Privacy and security notes
+//!
+//! * The community list is **opt-in**: the flag defaults to `false`, and a
+//!   stored list has no effect until the user enables it.
+//! * Only lists already verified by
+//!   [`crate::relay::blacklist::verify_community_list`] reach
+//!   [`CircleStorage::replace_community_blacklist`]; storage trusts its input.
+//! * A community refresh replaces only the community table — the user's own
+//!   block/allow decisions are never overwritten.
+
+// Single-shot SQLite ops naturally hold the lock to completion; the parent
+// module already disables this lint at the file level for storage.rs.
+#![allow(clippy::significant_drop_tightening)]
+
+use chrono::Utc;
+use rusqlite::{params, OptionalExtension};
+
+use super::error::{CircleError, Result};
+use super::storage::CircleStorage;
+use crate::relay::blacklist::canonical_relay_url;
+use crate::relay::{LocalRelayOverride, RelayBlacklist};
+
+/// `user_settings` key for the community-blacklist opt-in (default off).
+pub const COMMUNITY_BLACKLIST_ENABLED_KEY: &str = "relay_blacklist_community_enabled";
+
+/// `user_settings` key holding the `created_at` of the stored community list.
+pub const COMMUNITY_BLACKLIST_CREATED_AT_KEY: &str = "relay_blacklist_community_created_at";
+
+impl CircleStorage {
+    /// Returns whether the user opted in to the community relay blacklist.
+    ///
+    /// Defaults to `false` when the setting has never been written.
+    ///
+    /// # Errors
+    ///
+    /// Returns a database error on failure.
+    pub fn get_community_blacklist_enabled(&self) -> Result<bool> {
+        Ok(self
+            .get_setting(COMMUNITY_BLACKLIST_ENABLED_KEY)?
+            .as_deref()
+            == Some("true"))
+    }
+
+    /// Sets the community relay blacklist opt-in.
+    ///
+    /// # Errors
+    ///
+    /// Returns a database error on failure.
+    pub fn set_community_blacklist_enabled(&self, enabled: bool) -> Result<()> {
+        let conn = self
+            .conn()
+            .lock()
+            .map_err(|e| CircleError::Storage(format!("Failed to acquire database lock: {e}")))?;
+        conn.execute(
+            "INSERT INTO user_settings (key, value) VALUES (?1, ?2)
+             ON CONFLICT(key) DO UPDATE SET value = excluded.value",
+            params![
+                COMMUNITY_BLACKLIST_ENABLED_KEY,
+                if enabled { "true" } else { "false" }
+            ],
+        )?;
+        Ok(())
+    }
+
+    /// Returns the `created_at` of the stored community list, if any.
+    ///
+    /// # Errors
+    ///
+    /// Returns a database error on failure.
+    pub fn community_blacklist_created_at(&self) -> Result<Option<i64>> {
+        Ok(self
+            .get_setting(COMMUNITY_BLACKLIST_CREATED_AT_KEY)?
+            .and_then(|v| v.parse().ok()))
+    }
+
+    /// Replaces the stored community list with `urls` if `created_at` is
+    /// strictly newer than the stored one.
+    ///
+    /// Returns `true` if the list was replaced, `false` if it was stale (a
+    /// relay serving an older copy can never roll the list back).
+    ///
+    /// # Errors
+    ///
+    /// Returns a database error on failure; the replacement is atomic.
+    pub fn replace_community_blacklist(&self, urls: &[String], created_at: i64) -> Result<bool> {
+        let mut conn = self
+            .conn()
+            .lock()
+            .map_err(|e| CircleError::Storage(format!("Failed to acquire database lock: {e}")))?;
+        let tx = conn.transaction()?;
+
+        let stored: Option<i64> = tx
+            .query_row(
+                "SELECT value FROM user_settings WHERE key = ?1",
+                params![COMMUNITY_BLACKLIST_CREATED_AT_KEY],
+                |r| r.get::<_, String>(0),
+            )
+            .optional()?
+            .and_then(|v| v.parse().ok());
+        if stored.is_some_and(|s| created_at <= s) {
+            return Ok(false);
+        }
+
+        tx.execute("DELETE FROM community_relay_blacklist", [])?;
+        for url in urls {
+            if let Some(url) = canonical_relay_url(url) {
+                tx.execute(
+                    "INSERT OR IGNORE INTO community_relay_blacklist (url) VALUES (?1)",
+                    params![url],
+                )?;
+            }
+        }
+        tx.execute(
+            "INSERT INTO user_settings (key, value) VALUES (?1, ?2)
+             ON CONFLICT(key) DO UPDATE SET value = excluded.value",
+            params![COMMUNITY_BLACKLIST_CREATED_AT_KEY, created_at.to_string()],
+        )?;
+        tx.commit()?;
+        Ok(true)
+    }
+
+    /// Records the user's decision for one relay; `None` clears it.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`CircleError::InvalidData`] if `url` is not a relay URL, and
+    /// a database error otherwise.
+    pub fn set_relay_override(
+        &self,
+        url: &str,
+        decision: Option<LocalRelayOverride>,
+    ) -> Result<()> {
+        let url = canonical_relay_url(url)
+            .ok_or_else(|| CircleError::InvalidData("Invalid relay URL".to_string()))?;
+        let conn = self
+            .conn()
+            .lock()
+            .map_err(|e| CircleError::Storage(format!("Failed to acquire database lock: {e}")))?;
+        match decision {
+            Some(decision) => {
+                conn.execute(
+                    "INSERT INTO relay_overrides (url, decision, created_at) VALUES (?1, ?2, ?3)
+                     ON CONFLICT(url) DO UPDATE SET decision = excluded.decision",
+                    params![url, decision.as_str(), Utc::now().timestamp()],
+                )?;
+            }
+            None => {
+                conn.execute("DELETE FROM relay_overrides WHERE url = ?1", params![url])?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Lists the user's relay decisions, oldest first. Rows with an unknown
+    /// decision slug are skipped.
+    ///
+    /// # Errors
+    ///
+    /// Returns a database error on failure.
+    pub fn list_relay_overrides(&self) -> Result<Vec<(String, LocalRelayOverride)>> {
+        let conn = self
+            .conn()
+            .lock()
+            .map_err(|e| CircleError::Storage(format!("Failed to acquire database lock: {e}")))?;
+        let mut stmt = conn.prepare(
+            "SELECT url, decision FROM relay_overrides ORDER BY created_at ASC, url ASC",
+        )?;
+        let rows = stmt.query_map([], |r| Ok((r.get::<_, String>(0)?, r.get::<_, String>(1)?)))?;
+        let mut out = Vec::new();
+        for row in rows {
+            let (url, decision) = row?;
+            if let Some(decision) = LocalRelayOverride::parse(&decision) {
+                out.push((url, decision));
+            }
+        }
+        Ok(out)
+    }
+
+    /// Loads the merged, enforceable [`RelayBlacklist`].
+    ///
+    /// # Errors
+    ///
+    /// Returns a database error on failure.
+    pub fn load_relay_blacklist(&self) -> Result<RelayBlacklist> {
+        let enabled = self.get_community_blacklist_enabled()?;
+        let overrides = self.list_relay_overrides()?;
+        let community = self.list_community_blacklist()?;
+        Ok(RelayBlacklist::new(enabled, community, overrides))
+    }
+
+    /// Lists the stored community list entries (honored or not).
+    ///
+    /// # Errors
+    ///
+    /// Returns a database error on failure.
+    pub fn list_community_blacklist(&self) -> Result<Vec<String>> {
+        let conn = self
+            .conn()
+            .lock()
+            .map_err(|e| CircleError::Storage(format!("Failed to acquire database lock: {e}")))?;
+        let mut stmt = conn.prepare("SELECT url FROM community_relay_blacklist ORDER BY url")?;
+        let rows = stmt.query_map([], |r| r.get::<_, String>(0))?;
+        let mut out = Vec::new();
+        for row in rows {
+            out.push(row?);
+        }
+        Ok(out)
+    }
+
+    /// Reads a raw `user_settings` value.
+    pub(super) fn get_setting(&self, key: &str) -> Result<Option<String>> {
+        let conn = self
+            .conn()
+            .lock()
+            .map_err(|e| CircleError::Storage(format!("Failed to acquire database lock: {e}")))?;
+        Ok(conn
+            .query_row(
+                "SELECT value FROM user_settings WHERE key = ?1",
+                params![key],
+                |r| r.get::<_, String>(0),
+            )
+            .optional()?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn make_storage() -> CircleStorage {
+        CircleStorage::in_memory().expect("in_memory")
+    }
+
+    #[test]
+    fn community_opt_in_defaults_off() {
+        let storage = make_storage();
+        assert!(!storage.get_community_blacklist_enabled().unwrap());
+        storage.set_community_blacklist_enabled(true).unwrap();
+        assert!(storage.get_community_blacklist_enabled().unwrap());
+    }
+
+    #[test]
+    fn stored_community_list_has_no_effect_until_opted_in() {
+        let storage = make_storage();
+        storage
+            .replace_community_blacklist(&["wss://evil.example".to_string()], 100)
+            .unwrap();
+        assert!(!storage
+            .load_relay_blacklist()
+            .unwrap()
+            .is_blocked("wss://evil.example"));
+
+        storage.set_community_blacklist_enabled(true).unwrap();
+        assert!(storage
+            .load_relay_blacklist()
+            .unwrap()
+            .is_blocked("wss://evil.example"));
+    }
+
+    #[test]
+    fn replace_community_blacklist_is_newer_wins() {
+        let storage = make_storage();
+        storage.set_community_blacklist_enabled(true).unwrap();
+        assert!(storage
+            .replace_community_blacklist(&["wss://a.example".to_string()], 200)
+            .unwrap());
+        assert!(!storage
+            .replace_community_blacklist(&["wss://b.example".to_string()], 100)
+            .unwrap());
+        assert!(!storage
+            .replace_community_blacklist(&["wss://b.example".to_string()], 200)
+            .unwrap());
+        assert_eq!(storage.community_blacklist_created_at().unwrap(), Some(200));
+
+        let bl = storage.load_relay_blacklist().unwrap();
+        assert!(bl.is_blocked("wss://a.example"));
+        assert!(!bl.is_blocked("wss://b.example"));
+    }
+
+    #[test]
+    fn overrides_survive_community_refresh_and_win() {
+        let storage = make_storage();
+        storage.set_community_blacklist_enabled(true).unwrap();
+        storage
+            .set_relay_override("wss://mine.example/", Some(LocalRelayOverride::Allow))
+            .unwrap();
+        storage
+            .replace_community_blacklist(&["wss://mine.example".to_string()], 10)
+            .unwrap();
+
+        let bl = storage.load_relay_blacklist().unwrap();
+        assert!(!bl.is_blocked("wss://mine.example"));
+
+        storage
+            .set_relay_override("wss://mine.example", None)
+            .unwrap();
+        assert!(storage
+            .load_relay_blacklist()
+            .unwrap()
+            .is_blocked("wss://mine.example"));
+    }
+
+    #[test]
+    fn set_relay_override_rejects_invalid_url() {
+        let storage = make_storage();
+        assert!(matches!(
+            storage.set_relay_override("not a url", Some(LocalRelayOverride::Block)),
+            Err(CircleError::InvalidData(_))
+        ));
+    }
+}
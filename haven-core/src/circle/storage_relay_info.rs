@@ -0,0 +1,159 @@
+//! Storage methods for cached relay information documents.
+//!
+//! Extends [`CircleStorage`] with the `relay_info` table defined in
+//! [`CircleStorage::initialize_schema`]: one NIP-11 document per relay,
+//! keyed by canonical URL, with the time it was fetched. A document older
+//! than [`RELAY_INFO_TTL_SECS`] is stale; readers ignore it and
+//! [`CircleStorage::prune_relay_info`] deletes it. See
+//! [`crate::relay::relay_info`] for how documents are scored.
+
+// Single-shot SQLite ops naturally hold the lock to completion; the parent
+// module already disables this lint at the file level for storage.rs.
+#![allow(clippy::significant_drop_tightening)]
+
+use std::collections::HashMap;
+
+use rusqlite::{params, OptionalExtension};
+
+use super::error::{CircleError, Result};
+use super::storage::CircleStorage;
+use crate::relay::blacklist::canonical_relay_url;
+use crate::relay::{RelayInfo, RELAY_INFO_TTL_SECS};
+
+impl CircleStorage {
+    /// Stores `info` as `url`'s document, fetched at `fetched_at`, replacing
+    /// any previous one.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`CircleError::InvalidData`] if `url` is not a relay URL, and
+    /// a database error otherwise.
+    pub fn save_relay_info(&self, url: &str, info: &RelayInfo, fetched_at: i64) -> Result<()> {
+        let url = canonical_relay_url(url)
+            .ok_or_else(|| CircleError::InvalidData("Invalid relay URL".to_string()))?;
+        let document = serde_json::to_string(info)
+            .map_err(|e| CircleError::Storage(format!("Failed to encode relay info: {e}")))?;
+        let conn = self
+            .conn()
+            .lock()
+            .map_err(|e| CircleError::Storage(format!("Failed to acquire database lock: {e}")))?;
+        conn.execute(
+            "INSERT INTO relay_info (url, document, fetched_at) VALUES (?1, ?2, ?3)
+             ON CONFLICT(url) DO UPDATE SET
+                 document = excluded.document,
+                 fetched_at = excluded.fetched_at",
+            params![url, document, fetched_at],
+        )?;
+        Ok(())
+    }
+
+    /// Returns `url`'s document if one was fetched within
+    /// [`RELAY_INFO_TTL_SECS`] of `now`.
+    ///
+    /// # Errors
+    ///
+    /// Returns a database error on failure.
+    pub fn fresh_relay_info(&self, url: &str, now: i64) -> Result<Option<RelayInfo>> {
+        let Some(url) = canonical_relay_url(url) else {
+            return Ok(None);
+        };
+        let conn = self
+            .conn()
+            .lock()
+            .map_err(|e| CircleError::Storage(format!("Failed to acquire database lock: {e}")))?;
+        let document: Option<String> = conn
+            .query_row(
+                "SELECT document FROM relay_info WHERE url = ?1 AND fetched_at > ?2",
+                params![url, now.saturating_sub(RELAY_INFO_TTL_SECS)],
+                |r| r.get(0),
+            )
+            .optional()?;
+        // A document this build cannot read is treated as missing and
+        // refetched.
+        Ok(document.and_then(|d| serde_json::from_str(&d).ok()))
+    }
+
+    /// Returns the fresh documents among `urls`, keyed by canonical URL (the
+    /// shape [`crate::relay::rank_relays`] takes).
+    ///
+    /// # Errors
+    ///
+    /// Returns a database error on failure.
+    pub fn fresh_relay_infos(
+        &self,
+        urls: &[String],
+        now: i64,
+    ) -> Result<HashMap<String, RelayInfo>> {
+        let mut infos = HashMap::new();
+        for url in urls {
+            if let (Some(key), Some(info)) =
+                (canonical_relay_url(url), self.fresh_relay_info(url, now)?)
+            {
+                infos.insert(key, info);
+            }
+        }
+        Ok(infos)
+    }
+
+    /// Deletes documents that are stale at `now`, returning how many.
+    ///
+    /// # Errors
+    ///
+    /// Returns a database error on failure.
+    pub fn prune_relay_info(&self, now: i64) -> Result<usize> {
+        let conn = self
+            .conn()
+            .lock()
+            .map_err(|e| CircleError::Storage(format!("Failed to acquire database lock: {e}")))?;
+        Ok(conn.execute(
+            "DELETE FROM relay_info WHERE fetched_at <= ?1",
+            params![now.saturating_sub(RELAY_INFO_TTL_SECS)],
+        )?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn documents_expire_after_ttl() {
+        let storage = CircleStorage::in_memory().unwrap();
+        let info = RelayInfo {
+            supported_nips: vec![1, 11],
+            max_message_length: Some(65_536),
+            ..RelayInfo::default()
+        };
+        storage
+            .save_relay_info("wss://Relay.Example.com/", &info, 1_000)
+            .unwrap();
+
+        assert_eq!(
+            storage
+                .fresh_relay_info("wss://relay.example.com", 1_000 + RELAY_INFO_TTL_SECS - 1)
+                .unwrap(),
+            Some(info.clone())
+        );
+        let infos = storage
+            .fresh_relay_infos(
+                &[
+                    "wss://relay.example.com".to_string(),
+                    "wss://other.example.com".to_string(),
+                ],
+                2_000,
+            )
+            .unwrap();
+        assert_eq!(infos.len(), 1);
+        assert_eq!(infos.get("wss://relay.example.com"), Some(&info));
+
+        let later = 1_000 + RELAY_INFO_TTL_SECS;
+        assert_eq!(
+            storage
+                .fresh_relay_info("wss://relay.example.com", later)
+                .unwrap(),
+            None
+        );
+        assert_eq!(storage.prune_relay_info(later).unwrap(), 1);
+        assert!(storage.save_relay_info("not a url", &info, 0).is_err());
+    }
+}
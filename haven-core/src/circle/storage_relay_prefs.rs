@@ -531,6 +531,64 @@ impl CircleStorage {
         }
     }
 
+    /// Records the relay set a relay list of wire `kind` was published with.
+    ///
+    /// Like [`Self::record_published_event`], an older `published_at` never
+    /// overwrites a newer record.
+    ///
+    /// # Errors
+    ///
+    /// Returns a database error on failure.
+    pub fn record_published_relay_set(
+        &self,
+        kind: u16,
+        relays: &[String],
+        published_at: i64,
+    ) -> Result<()> {
+        let relays_json = serde_json::to_string(relays)
+            .map_err(|e| CircleError::Storage(format!("Failed to encode relay set: {e}")))?;
+        let conn = self
+            .conn()
+            .lock()
+            .map_err(|e| CircleError::Storage(format!("Failed to acquire database lock: {e}")))?;
+        conn.execute(
+            "INSERT INTO published_relay_sets (kind, relays_json, published_at)
+             VALUES (?1, ?2, ?3)
+             ON CONFLICT(kind) DO UPDATE SET
+                relays_json = excluded.relays_json,
+                published_at = excluded.published_at
+             WHERE excluded.published_at >= published_relay_sets.published_at",
+            params![i64::from(kind), relays_json, published_at],
+        )?;
+        Ok(())
+    }
+
+    /// The relay set a relay list of wire `kind` was last published with.
+    ///
+    /// # Errors
+    ///
+    /// Returns a database error on failure. Never published is `Ok(None)`.
+    pub fn published_relay_set(&self, kind: u16) -> Result<Option<Vec<String>>> {
+        let conn = self
+            .conn()
+            .lock()
+            .map_err(|e| CircleError::Storage(format!("Failed to acquire database lock: {e}")))?;
+        let relays_json: Option<String> = conn
+            .query_row(
+                "SELECT relays_json FROM published_relay_sets WHERE kind = ?1",
+                params![i64::from(kind)],
+                |r| r.get(0),
+            )
+            .optional()?;
+        relays_json
+            .map(|json| {
+                serde_json::from_str(&json).map_err(|e| {
+                    CircleError::InvalidData(format!("stored relay set is malformed: {e}"))
+                })
+            })
+            .transpose()
+    }
+
     /// Reads a boolean setting; defaults to `true` when missing.
     fn get_bool_setting(&self, key: &str) -> Result<bool> {
         let conn = self
@@ -959,4 +1017,19 @@ mod tests {
             "newer published_at must be preserved"
         );
     }
+
+    #[test]
+    fn published_relay_set_roundtrips_and_keeps_newest() {
+        let storage = make_storage();
+        assert_eq!(storage.published_relay_set(10050).unwrap(), None);
+        let newer = vec!["wss://a.example".to_string(), "wss://b.example".to_string()];
+        storage
+            .record_published_relay_set(10050, &newer, 2_000)
+            .unwrap();
+        storage
+            .record_published_relay_set(10050, &["wss://old.example".to_string()], 500)
+            .unwrap();
+        assert_eq!(storage.published_relay_set(10050).unwrap(), Some(newer));
+        assert_eq!(storage.published_relay_set(10002).unwrap(), None);
+    }
 }
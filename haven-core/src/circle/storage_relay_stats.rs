@@ -0,0 +1,152 @@
+//! Storage methods for persisted relay health counters.
+//!
+//! Extends [`CircleStorage`] with the `relay_stats` table defined in
+//! [`CircleStorage::initialize_schema`]: one row of counters per relay URL,
+//! overwritten with the running totals kept by [`crate::relay::relay_stats`].
+
+// Single-shot SQLite ops naturally hold the lock to completion; the parent
+// module already disables this lint at the file level for storage.rs.
+#![allow(clippy::significant_drop_tightening)]
+
+use rusqlite::params;
+
+use super::error::{CircleError, Result};
+use super::storage::CircleStorage;
+use crate::relay::RelayStats;
+
+/// Reads a stored counter, treating a corrupt negative value as zero.
+fn counter(value: i64) -> u64 {
+    u64::try_from(value).unwrap_or(0)
+}
+
+/// Converts a counter for storage, saturating at `i64::MAX`.
+fn stored(value: u64) -> i64 {
+    i64::try_from(value).unwrap_or(i64::MAX)
+}
+
+impl CircleStorage {
+    /// Returns every relay's stored counters, by URL.
+    ///
+    /// # Errors
+    ///
+    /// Returns a database error on failure.
+    pub fn load_relay_stats(&self) -> Result<Vec<RelayStats>> {
+        let conn = self
+            .conn()
+            .lock()
+            .map_err(|e| CircleError::Storage(format!("Failed to acquire database lock: {e}")))?;
+        let mut stmt = conn.prepare(
+            "SELECT url, publish_ok, publish_failed, latency_ms_total, latency_samples,
+                    bytes_sent, bytes_received, last_ok_at, last_error_at, last_error
+             FROM relay_stats ORDER BY url",
+        )?;
+        let rows = stmt.query_map([], |r| {
+            Ok(RelayStats {
+                url: r.get(0)?,
+                publish_ok: counter(r.get(1)?),
+                publish_failed: counter(r.get(2)?),
+                latency_ms_total: counter(r.get(3)?),
+                latency_samples: counter(r.get(4)?),
+                bytes_sent: counter(r.get(5)?),
+                bytes_received: counter(r.get(6)?),
+                last_ok_at: r.get(7)?,
+                last_error_at: r.get(8)?,
+                last_error: r.get(9)?,
+            })
+        })?;
+        rows.collect::<std::result::Result<Vec<_>, _>>()
+            .map_err(Into::into)
+    }
+
+    /// Stores `stats` as the running totals of their relays, replacing the
+    /// previous rows. Relays not in `stats` keep their rows.
+    ///
+    /// # Errors
+    ///
+    /// Returns a database error on failure; nothing is written then.
+    pub fn save_relay_stats(&self, stats: &[RelayStats]) -> Result<()> {
+        let mut conn = self
+            .conn()
+            .lock()
+            .map_err(|e| CircleError::Storage(format!("Failed to acquire database lock: {e}")))?;
+        let tx = conn.transaction()?;
+        for s in stats {
+            tx.execute(
+                "INSERT INTO relay_stats (url, publish_ok, publish_failed, latency_ms_total,
+                     latency_samples, bytes_sent, bytes_received, last_ok_at, last_error_at,
+                     last_error)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10)
+                 ON CONFLICT(url) DO UPDATE SET
+                     publish_ok = excluded.publish_ok,
+                     publish_failed = excluded.publish_failed,
+                     latency_ms_total = excluded.latency_ms_total,
+                     latency_samples = excluded.latency_samples,
+                     bytes_sent = excluded.bytes_sent,
+                     bytes_received = excluded.bytes_received,
+                     last_ok_at = excluded.last_ok_at,
+                     last_error_at = excluded.last_error_at,
+                     last_error = excluded.last_error",
+                params![
+                    s.url,
+                    stored(s.publish_ok),
+                    stored(s.publish_failed),
+                    stored(s.latency_ms_total),
+                    stored(s.latency_samples),
+                    stored(s.bytes_sent),
+                    stored(s.bytes_received),
+                    s.last_ok_at,
+                    s.last_error_at,
+                    s.last_error,
+                ],
+            )?;
+        }
+        tx.commit()?;
+        Ok(())
+    }
+
+    /// Deletes every relay's counters.
+    ///
+    /// # Errors
+    ///
+    /// Returns a database error on failure.
+    pub fn wipe_relay_stats(&self) -> Result<()> {
+        let conn = self
+            .conn()
+            .lock()
+            .map_err(|e| CircleError::Storage(format!("Failed to acquire database lock: {e}")))?;
+        conn.execute("DELETE FROM relay_stats", [])?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn saves_overwrites_and_wipes() {
+        let storage = CircleStorage::in_memory().unwrap();
+        let mut stats = RelayStats {
+            url: "wss://relay.example.com".to_string(),
+            publish_ok: 3,
+            latency_ms_total: 600,
+            latency_samples: 3,
+            bytes_sent: u64::MAX,
+            last_error_at: Some(100),
+            last_error: Some("rate limited".to_string()),
+            ..RelayStats::default()
+        };
+        storage.save_relay_stats(&[stats.clone()]).unwrap();
+        stats.publish_ok = 4;
+        storage.save_relay_stats(&[stats.clone()]).unwrap();
+
+        let loaded = storage.load_relay_stats().unwrap();
+        assert_eq!(loaded.len(), 1);
+        assert_eq!(loaded[0].publish_ok, 4);
+        assert_eq!(loaded[0].bytes_sent, u64::try_from(i64::MAX).unwrap());
+        assert_eq!(loaded[0].last_error.as_deref(), Some("rate limited"));
+
+        storage.wipe_relay_stats().unwrap();
+        assert!(storage.load_relay_stats().unwrap().is_empty());
+    }
+}
@@ -0,0 +1,107 @@
+//! Storage for the paired NIP-46 remote signer.
+//!
+//! Extends [`CircleStorage`] with one `user_settings` row holding the
+//! [`RemoteSignerConfig`] as JSON. Its presence is what routes event signing
+//! through the bunker (see [`crate::nostr::nip46`]); clearing it goes back to
+//! the local key.
+
+// Single-shot SQLite ops naturally hold the lock to completion; the parent
+// module already disables this lint at the file level for storage.rs.
+#![allow(clippy::significant_drop_tightening)]
+
+use rusqlite::params;
+use zeroize::Zeroizing;
+
+use super::error::{CircleError, Result};
+use super::storage::CircleStorage;
+use crate::nostr::nip46::RemoteSignerConfig;
+
+/// `user_settings` key holding the remote signer pairing (JSON).
+const REMOTE_SIGNER_KEY: &str = "remote_signer";
+
+impl CircleStorage {
+    /// Returns the paired remote signer, or `None` if signing is local.
+    ///
+    /// # Errors
+    ///
+    /// Returns a database error on failure, or [`CircleError::InvalidData`]
+    /// if the stored value does not parse.
+    pub fn get_remote_signer(&self) -> Result<Option<RemoteSignerConfig>> {
+        self.get_setting(REMOTE_SIGNER_KEY)?
+            .map(|json| {
+                let json = Zeroizing::new(json);
+                serde_json::from_str(&json).map_err(|e| {
+                    CircleError::InvalidData(format!("Invalid remote signer pairing: {e}"))
+                })
+            })
+            .transpose()
+    }
+
+    /// Saves the remote signer pairing, replacing any previous one.
+    ///
+    /// # Errors
+    ///
+    /// Returns a database error on failure.
+    pub fn set_remote_signer(&self, config: &RemoteSignerConfig) -> Result<()> {
+        let json = Zeroizing::new(serde_json::to_string(config).map_err(|e| {
+            CircleError::InvalidData(format!("Failed to encode remote signer pairing: {e}"))
+        })?);
+        let conn = self
+            .conn()
+            .lock()
+            .map_err(|e| CircleError::Storage(format!("Failed to acquire database lock: {e}")))?;
+        conn.execute(
+            "INSERT INTO user_settings (key, value) VALUES (?1, ?2)
+             ON CONFLICT(key) DO UPDATE SET value = excluded.value",
+            params![REMOTE_SIGNER_KEY, json.as_str()],
+        )?;
+        Ok(())
+    }
+
+    /// Forgets the remote signer pairing. Returns `false` if there was none.
+    ///
+    /// # Errors
+    ///
+    /// Returns a database error on failure.
+    pub fn clear_remote_signer(&self) -> Result<bool> {
+        let conn = self
+            .conn()
+            .lock()
+            .map_err(|e| CircleError::Storage(format!("Failed to acquire database lock: {e}")))?;
+        let removed = conn.execute(
+            "DELETE FROM user_settings WHERE key = ?1",
+            params![REMOTE_SIGNER_KEY],
+        )?;
+        Ok(removed > 0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pairing_round_trips_and_clears() {
+        let storage = CircleStorage::in_memory().unwrap();
+        assert!(storage.get_remote_signer().unwrap().is_none());
+
+        let config = RemoteSignerConfig {
+            remote_signer: "aa".repeat(32),
+            relays: vec!["wss://bunker.example".to_string()],
+            client_secret: zeroize::Zeroizing::new("11".repeat(32)),
+            user_pubkey: "bb".repeat(32),
+            connected_at: 42,
+        };
+        storage.set_remote_signer(&config).unwrap();
+        assert_eq!(storage.get_remote_signer().unwrap(), Some(config.clone()));
+
+        let mut repaired = config.clone();
+        repaired.connected_at = 43;
+        storage.set_remote_signer(&repaired).unwrap();
+        assert_eq!(storage.get_remote_signer().unwrap(), Some(repaired));
+
+        assert!(storage.clear_remote_signer().unwrap());
+        assert!(!storage.clear_remote_signer().unwrap());
+        assert!(storage.get_remote_signer().unwrap().is_none());
+    }
+}
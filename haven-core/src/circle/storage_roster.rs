@@ -0,0 +1,215 @@
+//! Storage methods for the last-seen circle roster.
+//!
+//! Extends [`CircleStorage`] with the `circle_roster` table defined in
+//! [`CircleStorage::initialize_schema`]. The engine only reports that a
+//! group's state changed; diffing its roster against the stored one tells
+//! who joined or left, so per-member rows of a departed member can go.
+
+// Single-shot SQLite ops naturally hold the lock to completion; the parent
+// module already disables this lint at the file level for storage.rs.
+#![allow(clippy::significant_drop_tightening)]
+
+use std::collections::BTreeSet;
+
+use rusqlite::{params, OptionalExtension};
+
+use super::error::{CircleError, Result};
+use super::storage::CircleStorage;
+use crate::nostr::mls::types::{GroupId, MembershipDelta};
+
+impl CircleStorage {
+    /// Returns the stored roster of a circle (hex pubkeys, sorted), empty if
+    /// none was recorded yet.
+    ///
+    /// # Errors
+    ///
+    /// Returns a database error on failure.
+    pub fn roster(&self, mls_group_id: &GroupId) -> Result<Vec<String>> {
+        let conn = self
+            .conn()
+            .lock()
+            .map_err(|e| CircleError::Storage(format!("Failed to acquire database lock: {e}")))?;
+        let mut stmt = conn.prepare(
+            "SELECT member_pubkey FROM circle_roster
+             WHERE mls_group_id = ?1 ORDER BY member_pubkey",
+        )?;
+        let members = stmt
+            .query_map(params![mls_group_id.as_slice()], |r| r.get(0))?
+            .collect::<std::result::Result<Vec<String>, _>>()?;
+        Ok(members)
+    }
+
+    /// Replaces the stored roster of a circle with `members` and returns who
+    /// joined or left since the last one, in one transaction.
+    ///
+    /// The first roster recorded for a circle is a baseline: the delta is
+    /// empty. For each member who left, their last-known location and
+    /// precision baseline in the circle are deleted with it, and so is the
+    /// circle's processed-event cache, which is not indexed by sender.
+    ///
+    /// # Errors
+    ///
+    /// Returns a database error on failure.
+    pub fn reconcile_roster(
+        &self,
+        mls_group_id: &GroupId,
+        members: &[String],
+    ) -> Result<MembershipDelta> {
+        let mut conn = self
+            .conn()
+            .lock()
+            .map_err(|e| CircleError::Storage(format!("Failed to acquire database lock: {e}")))?;
+        let tx = conn.transaction()?;
+        let gid = mls_group_id.as_slice();
+
+        let stored: BTreeSet<String> = {
+            let mut stmt =
+                tx.prepare("SELECT member_pubkey FROM circle_roster WHERE mls_group_id = ?1")?;
+            let rows = stmt.query_map(params![gid], |r| r.get(0))?;
+            rows.collect::<std::result::Result<_, _>>()?
+        };
+        let current: BTreeSet<String> = members.iter().cloned().collect();
+
+        let mut delta = MembershipDelta::default();
+        if !stored.is_empty() {
+            delta.added = current.difference(&stored).cloned().collect();
+            delta.removed = stored.difference(&current).cloned().collect();
+        }
+
+        let nostr_group_id: Option<Vec<u8>> = tx
+            .query_row(
+                "SELECT nostr_group_id FROM circles WHERE mls_group_id = ?1",
+                params![gid],
+                |r| r.get(0),
+            )
+            .optional()?;
+        for pubkey in &delta.removed {
+            if let Some(ngid) = &nostr_group_id {
+                tx.execute(
+                    "DELETE FROM last_known_locations
+                     WHERE nostr_group_id = ?1 AND sender_pubkey = ?2",
+                    params![ngid, pubkey],
+                )?;
+                tx.execute(
+                    "DELETE FROM location_history
+                     WHERE nostr_group_id = ?1 AND sender_pubkey = ?2",
+                    params![ngid, pubkey],
+                )?;
+                tx.execute(
+                    "DELETE FROM processed_events WHERE nostr_group_id = ?1",
+                    params![ngid],
+                )?;
+            }
+            tx.execute(
+                "DELETE FROM precision_baselines
+                 WHERE mls_group_id = ?1 AND sender_pubkey = ?2",
+                params![gid, pubkey],
+            )?;
+            tx.execute(
+                "DELETE FROM sent_invites WHERE mls_group_id = ?1 AND member_pubkey = ?2",
+                params![gid, pubkey],
+            )?;
+            tx.execute(
+                "DELETE FROM checkin_rules WHERE mls_group_id = ?1 AND member_pubkey = ?2",
+                params![gid, pubkey],
+            )?;
+            tx.execute(
+                "DELETE FROM proximity_rules
+                 WHERE mls_group_id = ?1 AND (member_pubkey = ?2 OR target_pubkey = ?2)",
+                params![gid, pubkey],
+            )?;
+            tx.execute(
+                "DELETE FROM member_presence WHERE mls_group_id = ?1 AND member_pubkey = ?2",
+                params![gid, pubkey],
+            )?;
+        }
+
+        tx.execute(
+            "DELETE FROM circle_roster WHERE mls_group_id = ?1",
+            params![gid],
+        )?;
+        for pubkey in &current {
+            tx.execute(
+                "INSERT INTO circle_roster (mls_group_id, member_pubkey) VALUES (?1, ?2)",
+                params![gid, pubkey],
+            )?;
+        }
+        tx.commit()?;
+        Ok(delta)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::circle::types::{Circle, CircleType, LastKnownLocation};
+    use crate::nostr::mls::types::GroupIdExt;
+
+    fn keys(names: &[&str]) -> Vec<String> {
+        names.iter().map(ToString::to_string).collect()
+    }
+
+    fn location(sender: &str) -> LastKnownLocation {
+        LastKnownLocation {
+            nostr_group_id: [1; 32],
+            sender_pubkey: sender.to_string(),
+            latitude: 1.0,
+            longitude: 2.0,
+            geohash: "s0000000".to_string(),
+            display_name: None,
+            timestamp: 1_000,
+            expires_at: 2_000,
+            purge_after: 10_000,
+            updated_at: 1_000,
+        }
+    }
+
+    #[test]
+    fn reports_who_joined_and_left_after_a_baseline() {
+        let storage = CircleStorage::in_memory().unwrap();
+        let gid = GroupId::from_slice(&[1; 32]);
+        storage
+            .save_circle(&Circle {
+                mls_group_id: gid.clone(),
+                nostr_group_id: [1; 32],
+                display_name: "Home".to_string(),
+                circle_type: CircleType::LocationSharing,
+                relays: vec![],
+                created_at: 0,
+                updated_at: 0,
+            })
+            .unwrap();
+        storage
+            .upsert_last_known_location(&location("bob"))
+            .unwrap();
+        storage
+            .upsert_last_known_location(&location("carol"))
+            .unwrap();
+
+        let baseline = storage
+            .reconcile_roster(&gid, &keys(&["alice", "bob", "carol"]))
+            .unwrap();
+        assert!(baseline.is_empty());
+
+        let delta = storage
+            .reconcile_roster(&gid, &keys(&["alice", "carol", "dave"]))
+            .unwrap();
+        assert_eq!(delta.added, keys(&["dave"]));
+        assert_eq!(delta.removed, keys(&["bob"]));
+        assert_eq!(
+            storage.roster(&gid).unwrap(),
+            keys(&["alice", "carol", "dave"])
+        );
+
+        let left: Vec<String> = storage
+            .snapshot_last_known_for_circle(&[1; 32], 0)
+            .unwrap()
+            .into_iter()
+            .map(|l| l.sender_pubkey)
+            .collect();
+        assert_eq!(left, keys(&["carol"]));
+
+        storage.delete_circle(&gid).unwrap();
+        assert!(storage.roster(&gid).unwrap().is_empty());
+    }
+}
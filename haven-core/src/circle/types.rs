@@ -12,6 +12,9 @@
 
 use std::sync::OnceLock;
 
+use crate::location::{
+    GeohashBounds, LocationMessage, LocationPrecision, ShareExpiration, LOCATION_RETENTION_SECS,
+};
 use crate::nostr::mls::types::GroupId;
 
 /// Production **account-creation seed** relay URLs.
@@ -46,21 +49,20 @@ static DEFAULT_RELAYS_OVERRIDE: OnceLock<Vec<String>> = OnceLock::new();
 /// Returns the account-creation seed relay list for the current process.
 ///
 /// This is the set a brand-new account is seeded with — NOT a runtime
-/// publish/fetch fallback (see [`PRODUCTION_DEFAULT_RELAYS`]). In production
-/// it is always a fresh `Vec<String>` materialized from
-/// [`PRODUCTION_DEFAULT_RELAYS`]. In debug builds, if
-/// [`set_default_relays_for_test`] has been called, the override list is
-/// returned instead. The function always returns at least one entry — an
-/// empty override is rejected at install time.
+/// publish/fetch fallback (see [`PRODUCTION_DEFAULT_RELAYS`]). It is the
+/// active [environment profile](crate::environment)'s relay list, which is
+/// [`PRODUCTION_DEFAULT_RELAYS`] unless a dev or staging profile was
+/// installed. In debug builds, if [`set_default_relays_for_test`] has been
+/// called, the override list wins over the profile. The function always
+/// returns at least one entry — empty lists are rejected at install time.
 #[must_use]
 pub fn default_relays() -> Vec<String> {
     if let Some(over) = DEFAULT_RELAYS_OVERRIDE.get() {
         return over.clone();
     }
-    PRODUCTION_DEFAULT_RELAYS
-        .iter()
-        .map(|s| (*s).to_string())
-        .collect()
+    crate::environment::active_environment()
+        .default_relays
+        .clone()
 }
 
 /// Override the default relay list for E2E tests.
@@ -274,6 +276,19 @@ pub struct Contact {
     pub created_at: i64,
     /// When this contact was last updated (Unix timestamp).
     pub updated_at: i64,
+    /// When the user verified this contact's safety number in person (Unix
+    /// timestamp), or `None` if unverified.
+    pub verified_at: Option<i64>,
+    /// Id of the contact's picture in the device's [`crate::avatar::AvatarStore`].
+    pub avatar_id: Option<String>,
+}
+
+impl Contact {
+    /// Whether the user has verified this contact's safety number.
+    #[must_use]
+    pub const fn is_verified(&self) -> bool {
+        self.verified_at.is_some()
+    }
 }
 
 impl std::fmt::Debug for Contact {
@@ -287,6 +302,8 @@ impl std::fmt::Debug for Contact {
             .field("notes", &"<redacted>")
             .field("created_at", &self.created_at)
             .field("updated_at", &self.updated_at)
+            .field("verified_at", &self.verified_at)
+            .field("avatar_id", &self.avatar_id.as_ref().map(|_| "<redacted>"))
             .finish()
     }
 }
@@ -353,11 +370,12 @@ impl std::fmt::Debug for CircleUiState {
 ///
 /// # Privacy / retention
 ///
-/// Receivers persistently cache last-known locations for a fixed
-/// 1-day window (see `LOCATION_RETENTION_SECS`). The window is hard-coded
-/// — senders cannot influence how long their stale location is retained
-/// by other circle members. `purge_after` records the absolute moment
-/// the row must be deleted; the receiver computes it on insert.
+/// Receivers persistently cache last-known locations for at most a
+/// 1-day window (see `LOCATION_RETENTION_SECS`). The ceiling is hard-coded
+/// — a sender can shorten how long other members keep its location (a
+/// [`crate::location::ShareExpiration`]) but never extend it. `purge_after`
+/// records the absolute moment the row must be deleted; the receiver
+/// computes it on insert.
 #[derive(Clone, PartialEq)]
 pub struct LastKnownLocation {
     /// Nostr group ID of the circle this location belongs to.
@@ -382,6 +400,48 @@ pub struct LastKnownLocation {
     pub updated_at: i64,
 }
 
+/// A location from the history, as returned by viewport queries (see
+/// [`CircleManager::get_points_in_viewport`](super::CircleManager::get_points_in_viewport)).
+#[derive(Clone, PartialEq)]
+pub struct HistoryPoint {
+    /// Nostr group ID of the circle the location was shared in.
+    pub nostr_group_id: [u8; 32],
+    /// Sender's Nostr public key (hex encoded).
+    pub sender_pubkey: String,
+    /// Latitude (exact GPS reading).
+    pub latitude: f64,
+    /// Longitude (exact GPS reading).
+    pub longitude: f64,
+    /// When the location was captured (Unix seconds, from the sender's clock).
+    pub timestamp: i64,
+}
+
+impl std::fmt::Debug for HistoryPoint {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("HistoryPoint")
+            .field("nostr_group_id", &hex::encode(self.nostr_group_id))
+            .field("sender_pubkey", &"<redacted>")
+            .field("latitude", &"<redacted>")
+            .field("longitude", &"<redacted>")
+            .field("timestamp", &self.timestamp)
+            .finish()
+    }
+}
+
+impl LastKnownLocation {
+    /// Whether the location is young enough to show at `now`: captured at
+    /// most `max_age_secs` ago (see
+    /// [`CircleLocationSettings::display_max_age_secs`]).
+    ///
+    /// Independent of retention: a row can still be stored (before
+    /// `purge_after`) and yet too old to put on a map.
+    #[must_use]
+    pub fn is_displayable(&self, max_age_secs: u64, now: i64) -> bool {
+        let max_age = i64::try_from(max_age_secs).unwrap_or(i64::MAX);
+        now.saturating_sub(self.timestamp) <= max_age
+    }
+}
+
 impl std::fmt::Debug for LastKnownLocation {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("LastKnownLocation")
@@ -399,6 +459,281 @@ impl std::fmt::Debug for LastKnownLocation {
     }
 }
 
+/// A member's newest location, with what the UI should show for it.
+#[derive(Debug, Clone)]
+pub struct MemberLocation {
+    /// The newest stored location, however old (until retention purges it).
+    pub latest: LastKnownLocation,
+    /// `latest` while it is within the circle's display threshold, `None`
+    /// once it is older. UIs draw this one, so stale markers blank out
+    /// everywhere at the same moment.
+    pub effective_display_location: Option<LastKnownLocation>,
+}
+
+impl MemberLocation {
+    /// Pairs `latest` with its display location under `max_age_secs`.
+    #[must_use]
+    pub fn new(latest: LastKnownLocation, max_age_secs: u64, now: i64) -> Self {
+        let effective_display_location = latest
+            .is_displayable(max_age_secs, now)
+            .then(|| latest.clone());
+        Self {
+            latest,
+            effective_display_location,
+        }
+    }
+}
+
+/// Consecutive un-ingestable messages after which a circle is reported as
+/// needing repair ([`GroupHealth::needs_repair`]).
+pub const UNPROCESSABLE_REPAIR_THRESHOLD: u32 = 3;
+
+/// Receive-side health of a circle's MLS group.
+///
+/// Messages the engine cannot ingest at all (as opposed to ones it buffers
+/// for a future epoch) usually mean this device's epoch has fallen out of
+/// step with the group, e.g. after missing a commit. A few in a row mark the
+/// circle as needing repair; see [`CircleManager::repair_group`].
+///
+/// [`CircleManager::repair_group`]: super::CircleManager::repair_group
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct GroupHealth {
+    /// Current MLS epoch, or `None` if the engine no longer holds the group.
+    pub epoch: Option<u64>,
+    /// Commits this device staged for the circle and has not yet confirmed
+    /// or rolled back.
+    pub pending_commits: u32,
+    /// Messages that failed to ingest since the last one that applied.
+    pub unprocessable_count: u32,
+    /// Whether the app should show the circle as needing repair.
+    pub needs_repair: bool,
+}
+
+/// What [`CircleManager::repair_group`] did.
+///
+/// [`CircleManager::repair_group`]: super::CircleManager::repair_group
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RepairOutcome {
+    /// Nothing needed repair.
+    Healthy,
+    /// Stored convergence was re-driven and the failure count reset; the next
+    /// sync shows whether messages apply again.
+    Resynced,
+    /// The engine no longer holds the group: an admin has to re-invite this
+    /// device.
+    NeedsRejoin,
+}
+
+/// Silence after which a member shows as [`PresenceStatus::Stale`]
+/// (30 minutes).
+pub const PRESENCE_STALE_AFTER_SECS: i64 = 30 * 60;
+
+/// Silence after which a member shows as [`PresenceStatus::Offline`]
+/// (6 hours).
+pub const PRESENCE_OFFLINE_AFTER_SECS: i64 = 6 * 60 * 60;
+
+/// How recently a member was heard from in a circle.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PresenceStatus {
+    /// A message within [`PRESENCE_STALE_AFTER_SECS`].
+    Active,
+    /// Quiet for longer, but heard from within
+    /// [`PRESENCE_OFFLINE_AFTER_SECS`].
+    Stale,
+    /// Quiet for longer than that.
+    Offline,
+    /// Never heard from on this device.
+    Unknown,
+}
+
+impl PresenceStatus {
+    /// Classifies a member last heard from at `last_seen` (Unix seconds).
+    #[must_use]
+    pub fn classify(last_seen: Option<i64>, now_unix_secs: i64) -> Self {
+        let Some(last_seen) = last_seen else {
+            return Self::Unknown;
+        };
+        let silence = now_unix_secs.saturating_sub(last_seen);
+        if silence <= PRESENCE_STALE_AFTER_SECS {
+            Self::Active
+        } else if silence <= PRESENCE_OFFLINE_AFTER_SECS {
+            Self::Stale
+        } else {
+            Self::Offline
+        }
+    }
+}
+
+/// When a circle member was last heard from, from
+/// [`CircleManager::get_member_presence`](super::CircleManager::get_member_presence).
+///
+/// Any decrypted application message counts — locations, check-in requests,
+/// meet pins, cover traffic. Commits do not: the engine does not say who
+/// sent one.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MemberPresence {
+    /// The member's public key (hex).
+    pub pubkey: String,
+    /// Send time of the member's latest message (Unix seconds).
+    pub last_seen: Option<i64>,
+    /// Send time of the member's latest location or SOS (Unix seconds).
+    pub last_location_at: Option<i64>,
+    /// `last_seen` classified at the time of the query.
+    pub status: PresenceStatus,
+}
+
+/// Exactly what a circle would receive for a location, from
+/// [`CircleManager::preview_share`](super::CircleManager::preview_share).
+///
+/// Holds the user's own (reduced) position, so it MUST NOT be logged.
+#[derive(Clone, PartialEq)]
+pub struct SharePreview {
+    /// Latitude as published.
+    pub latitude: f64,
+    /// Longitude as published.
+    pub longitude: f64,
+    /// Geohash as published.
+    pub geohash: String,
+    /// The geohash cell members could place the sender in.
+    pub cell: Option<GeohashBounds>,
+    /// Precision applied for this circle.
+    pub precision: LocationPrecision,
+    /// When members show the location as stale (Unix seconds).
+    pub expires_at: i64,
+    /// When members drop the location (Unix seconds), if sooner than the
+    /// 1-day default.
+    pub share_expires_at: Option<i64>,
+}
+
+impl std::fmt::Debug for SharePreview {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SharePreview")
+            .field("latitude", &"<redacted>")
+            .field("longitude", &"<redacted>")
+            .field("geohash", &"<redacted>")
+            .field("precision", &self.precision)
+            .field("expires_at", &self.expires_at)
+            .field("share_expires_at", &self.share_expires_at)
+            .finish_non_exhaustive()
+    }
+}
+
+/// How old a member's location may be and still be shown, when a circle sets
+/// no threshold (1 hour).
+pub const DEFAULT_DISPLAY_MAX_AGE_SECS: u64 = 60 * 60;
+
+/// Shortest display threshold a circle can set (1 minute).
+pub const MIN_DISPLAY_MAX_AGE_SECS: u64 = 60;
+
+/// Per-circle location sharing overrides.
+///
+/// Applied to every location sent to the circle by
+/// [`CircleManager::encrypt_location`](super::CircleManager::encrypt_location),
+/// on top of whatever the caller built from the global
+/// [`crate::location::LocationSettings`]. Both only ever make a location
+/// coarser or shorter-lived, so a circle can be more private than the global
+/// settings but never less.
+///
+/// `display_max_age_secs` is the one receive-side setting: how old a member's
+/// location may be before every UI stops showing it, while retention still
+/// keeps the row (see [`super::MemberLocation`]).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct CircleLocationSettings {
+    /// Precision shared with this circle.
+    pub precision: LocationPrecision,
+    /// How long this circle's members keep each shared location.
+    pub share_expiration: ShareExpiration,
+    /// How old a member's location may be and still be shown, in seconds;
+    /// `None` for [`DEFAULT_DISPLAY_MAX_AGE_SECS`].
+    pub display_max_age_secs: Option<u64>,
+}
+
+impl CircleLocationSettings {
+    /// The display threshold in effect, clamped to
+    /// [`MIN_DISPLAY_MAX_AGE_SECS`]..=[`LOCATION_RETENTION_SECS`] (nothing
+    /// older is kept anyway).
+    #[must_use]
+    pub fn display_max_age_secs(&self) -> u64 {
+        self.display_max_age_secs
+            .unwrap_or(DEFAULT_DISPLAY_MAX_AGE_SECS)
+            .clamp(MIN_DISPLAY_MAX_AGE_SECS, LOCATION_RETENTION_SECS)
+    }
+
+    /// Reduces `location` to this circle's precision and expiration.
+    pub fn apply(&self, location: &mut LocationMessage) {
+        crate::location::precision::reduce_to(location, self.precision);
+        location.limit_share_expiration(self.share_expiration);
+    }
+}
+
+/// Longest a trip mode may run before it reverts on its own (1 day).
+pub const MAX_TRIP_MODE_SECS: i64 = 24 * 60 * 60;
+
+/// A temporary per-circle override for a trip (e.g. a road-trip day).
+///
+/// While active it replaces the circle's share expiration (usually with a
+/// longer one, so members keep the route visible) and shortens the publish
+/// interval. It reverts by itself at `ends_at`: storage never returns a
+/// lapsed trip mode.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TripMode {
+    /// Nominal publish interval while the trip lasts, in seconds.
+    pub update_interval_secs: u64,
+    /// How long members keep each location shared during the trip.
+    pub share_expiration: ShareExpiration,
+    /// Unix timestamp at which the trip mode ends.
+    pub ends_at: i64,
+}
+
+impl TripMode {
+    /// Whether the trip mode is still running at `now`.
+    #[must_use]
+    pub const fn is_active(&self, now: i64) -> bool {
+        now < self.ends_at
+    }
+}
+
+/// Longest a precise-sharing session may run (1 day).
+pub const MAX_PRECISE_SESSION_SECS: i64 = 24 * 60 * 60;
+
+/// A time-limited session sharing exact location with one circle ("share
+/// exact location for 1 hour").
+///
+/// While active, the circle's [`CircleLocationSettings::precision`] is not
+/// applied. It ends by itself at `ends_at`: storage never returns a lapsed
+/// session, so obfuscation resumes even if the app never ends it.
+#[derive(Clone, PartialEq, Eq)]
+pub struct SharingSession {
+    /// MLS group ID of the circle.
+    pub mls_group_id: GroupId,
+    /// Unix timestamp the session started.
+    pub started_at: i64,
+    /// Unix timestamp the session ends.
+    pub ends_at: i64,
+}
+
+impl SharingSession {
+    /// Seconds left at `now` (zero once ended), for a UI countdown.
+    #[must_use]
+    pub const fn remaining_secs(&self, now: i64) -> i64 {
+        if self.ends_at > now {
+            self.ends_at - now
+        } else {
+            0
+        }
+    }
+}
+
+impl std::fmt::Debug for SharingSession {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SharingSession")
+            .field("mls_group_id", &"<redacted>")
+            .field("started_at", &self.started_at)
+            .field("ends_at", &self.ends_at)
+            .finish()
+    }
+}
+
 /// Configuration for creating a new circle.
 #[derive(Debug, Clone)]
 pub struct CircleConfig {
@@ -477,6 +812,10 @@ pub struct Invitation {
     pub member_count: usize,
     /// When we were invited (Unix timestamp).
     pub invited_at: i64,
+    /// The inviter's self-chosen name from the Welcome, if they sent one.
+    /// **Unverified** — the inviter picks it freely; show it only as a
+    /// suggested contact name, never as an established identity.
+    pub suggested_name: Option<String>,
 }
 
 impl std::fmt::Debug for Invitation {
@@ -487,6 +826,10 @@ impl std::fmt::Debug for Invitation {
             .field("inviter_pubkey", &self.inviter_pubkey)
             .field("member_count", &self.member_count)
             .field("invited_at", &self.invited_at)
+            .field(
+                "suggested_name",
+                &self.suggested_name.as_ref().map(|_| "<redacted>"),
+            )
             .finish()
     }
 }
@@ -528,6 +871,10 @@ pub struct GiftWrappedWelcome {
     pub recipient_pubkey: String,
     /// Relay URLs to publish this Welcome to (recipient's inbox relays).
     pub recipient_relays: Vec<String>,
+    /// Later tiers of the delivery cascade, tried only if no relay in
+    /// `recipient_relays` takes the Welcome (see
+    /// [`crate::relay::welcome_delivery`]).
+    pub fallback_relays: Vec<String>,
     /// The gift-wrapped event (kind 1059), ready to publish.
     pub event: nostr::Event,
 }
@@ -537,11 +884,95 @@ impl std::fmt::Debug for GiftWrappedWelcome {
         f.debug_struct("GiftWrappedWelcome")
             .field("recipient_pubkey", &"<redacted>")
             .field("relay_count", &self.recipient_relays.len())
+            .field("fallback_relay_count", &self.fallback_relays.len())
             .field("event", &"<redacted>")
             .finish()
     }
 }
 
+/// How long an invited member may go without appearing in a circle before
+/// they are reported as not joined (2 days).
+pub const INVITE_JOIN_GRACE_SECS: i64 = 2 * 24 * 60 * 60;
+
+/// Shortest time between two resends of one invitation (6 hours).
+pub const INVITE_RESEND_INTERVAL_SECS: i64 = 6 * 60 * 60;
+
+/// Most times one invitation may be resent.
+pub const MAX_INVITE_RESENDS: u32 = 3;
+
+/// A Welcome this device sent, tracked until its recipient appears in the
+/// circle by sending anything in it.
+#[derive(Clone)]
+pub struct SentInvite {
+    /// The invited member's public key (hex).
+    pub member_pubkey: String,
+    /// The Welcome as last sent.
+    pub welcome: GiftWrappedWelcome,
+    /// The epoch the Welcome joins at; `None` until the commit that added the
+    /// member was confirmed.
+    pub epoch: Option<u64>,
+    /// When the member was first invited (Unix timestamp).
+    pub invited_at: i64,
+    /// How often the invitation was resent.
+    pub resend_count: u32,
+    /// When it was last resent (Unix timestamp).
+    pub last_resent_at: Option<i64>,
+    /// When the member first appeared in the circle (Unix timestamp).
+    pub joined_at: Option<i64>,
+}
+
+impl SentInvite {
+    /// When the invitation may next be resent, or `None` once
+    /// [`MAX_INVITE_RESENDS`] is reached.
+    #[must_use]
+    pub fn next_resend_at(&self) -> Option<i64> {
+        (self.resend_count < MAX_INVITE_RESENDS).then(|| {
+            self.last_resent_at
+                .unwrap_or(self.invited_at)
+                .saturating_add(INVITE_RESEND_INTERVAL_SECS)
+        })
+    }
+}
+
+impl std::fmt::Debug for SentInvite {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SentInvite")
+            .field("member_pubkey", &"<redacted>")
+            .field("welcome", &self.welcome)
+            .field("epoch", &self.epoch)
+            .field("invited_at", &self.invited_at)
+            .field("resend_count", &self.resend_count)
+            .field("last_resent_at", &self.last_resent_at)
+            .field("joined_at", &self.joined_at)
+            .finish()
+    }
+}
+
+/// A member invited by this device who has not appeared in the circle yet.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnjoinedMember {
+    /// The member's public key (hex).
+    pub member_pubkey: String,
+    /// When they were invited (Unix timestamp).
+    pub invited_at: i64,
+    /// How often the invitation was resent.
+    pub resend_count: u32,
+    /// When the invitation may next be resent, or `None` once
+    /// [`MAX_INVITE_RESENDS`] is reached.
+    pub next_resend_at: Option<i64>,
+}
+
+impl From<&SentInvite> for UnjoinedMember {
+    fn from(invite: &SentInvite) -> Self {
+        Self {
+            member_pubkey: invite.member_pubkey.clone(),
+            invited_at: invite.invited_at,
+            resend_count: invite.resend_count,
+            next_resend_at: invite.next_resend_at(),
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -624,6 +1055,8 @@ mod tests {
             notes: Some("Test notes".to_string()),
             created_at: 1000,
             updated_at: 2000,
+            verified_at: None,
+            avatar_id: None,
         };
 
         let contact2 = contact.clone();
@@ -715,6 +1148,7 @@ mod tests {
             inviter_pubkey: "pubkey456".to_string(),
             member_count: 5,
             invited_at: 9000,
+            suggested_name: Some("Dad".to_string()),
         };
 
         let debug_str = format!("{invitation:?}");
@@ -722,6 +1156,7 @@ mod tests {
         assert!(debug_str.contains("Family Circle"));
         assert!(debug_str.contains("pubkey456"));
         assert!(debug_str.contains("member_count: 5"));
+        assert!(!debug_str.contains("Dad"));
     }
 
     #[test]
@@ -826,6 +1261,7 @@ mod tests {
         let gww = GiftWrappedWelcome {
             recipient_pubkey: "recipient-pubkey-hex".to_string(),
             recipient_relays: vec!["wss://relay.example.com".to_string()],
+            fallback_relays: Vec::new(),
             event: signed_event,
         };
 
@@ -843,6 +1279,62 @@ mod tests {
         );
     }
 
+    #[test]
+    fn circle_location_settings_only_coarsen() {
+        let settings = CircleLocationSettings {
+            precision: LocationPrecision::Coarse,
+            share_expiration: ShareExpiration::OneHour,
+            display_max_age_secs: None,
+        };
+        let mut loc = LocationMessage::new(37.774_929_5, -122.419_415_5);
+        settings.apply(&mut loc);
+        assert!((loc.latitude - 37.77).abs() < f64::EPSILON);
+        assert_eq!(
+            loc.share_expires_at,
+            Some(loc.timestamp + chrono::Duration::hours(1))
+        );
+
+        // The default leaves an exact, already-shortened location alone.
+        let mut loc =
+            LocationMessage::with_expiration(1.234_567, 2.345_678, ShareExpiration::OneHour);
+        let before = loc.clone();
+        CircleLocationSettings::default().apply(&mut loc);
+        assert!((loc.latitude - before.latitude).abs() < f64::EPSILON);
+        assert_eq!(loc.geohash, before.geohash);
+        assert_eq!(loc.share_expires_at, before.share_expires_at);
+    }
+
+    #[test]
+    fn display_location_blanks_past_the_threshold() {
+        let latest = LastKnownLocation {
+            nostr_group_id: [1; 32],
+            sender_pubkey: "a".to_string(),
+            latitude: 1.0,
+            longitude: 2.0,
+            geohash: String::new(),
+            display_name: None,
+            timestamp: 1_000,
+            expires_at: 1_900,
+            purge_after: 87_400,
+            updated_at: 1_000,
+        };
+        let shown = MemberLocation::new(latest.clone(), 600, 1_600);
+        assert!(shown.effective_display_location.is_some());
+        let blanked = MemberLocation::new(latest, 600, 1_601);
+        assert!(blanked.effective_display_location.is_none());
+        assert_eq!(blanked.latest.timestamp, 1_000);
+
+        assert_eq!(
+            CircleLocationSettings::default().display_max_age_secs(),
+            DEFAULT_DISPLAY_MAX_AGE_SECS
+        );
+        let extreme = CircleLocationSettings {
+            display_max_age_secs: Some(1),
+            ..CircleLocationSettings::default()
+        };
+        assert_eq!(extreme.display_max_age_secs(), MIN_DISPLAY_MAX_AGE_SECS);
+    }
+
     #[test]
     fn circle_config_new_defaults() {
         let config = CircleConfig::new("My Circle");
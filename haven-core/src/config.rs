@@ -0,0 +1,311 @@
+//! One configuration object for the core's user-facing settings.
+//!
+//! [`HavenConfig`] gathers settings that each had a setter of their own:
+//!
+//! - [`RelayPolicy`]: which relay lists are published and whether the
+//!   community relay blacklist applies;
+//! - [`LocationSettings`]: the location defaults — update cadence, precision,
+//!   how long members keep a shared location, device status;
+//! - [`PrivacyPolicy`]: the member key audit;
+//! - [`PrivacySettings`]: what published events reveal — timestamp fuzzing;
+//! - [`DiagnosticsPolicy`]: slow-operation timing.
+//!
+//! There is no proxy or Tor section: Haven ships no Tor client (see
+//! [`crate::relay::relay_info`]).
+//!
+//! The config round-trips through JSON for export and import. Fields missing
+//! from a document take their defaults and unknown fields are ignored, so a
+//! file written by another version still loads. Changes travel as JSON merge
+//! patches (RFC 7386): [`HavenConfig::diff`] produces one,
+//! [`HavenConfig::apply_patch`] applies one, and [`HavenConfig::layered`]
+//! stacks several on the defaults — for example a staged rollout of new
+//! defaults beneath the user's own changes. A `null` in a patch resets that
+//! setting to its default.
+
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+use crate::location::{
+    LocationSettings, ShareExpiration, LOCATION_RETENTION_SECS, MIN_SHARE_EXPIRATION_SECS,
+};
+use crate::privacy::PrivacySettings;
+
+/// Shortest location update interval, in minutes.
+const MIN_UPDATE_INTERVAL_MINUTES: u32 = 5;
+
+/// Longest location update interval, in minutes.
+const MAX_UPDATE_INTERVAL_MINUTES: u32 = 60;
+
+/// Relay list publishing and filtering.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RelayPolicy {
+    /// Publish the `KeyPackage` relay list (kind 10051).
+    pub publish_key_package_relay_list: bool,
+    /// Publish the inbox relay list (kind 10050).
+    pub publish_inbox_relay_list: bool,
+    /// Apply the community relay blacklist.
+    pub community_blacklist: bool,
+}
+
+impl Default for RelayPolicy {
+    fn default() -> Self {
+        Self {
+            publish_key_package_relay_list: true,
+            publish_inbox_relay_list: true,
+            community_blacklist: false,
+        }
+    }
+}
+
+/// Privacy opt-ins.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PrivacyPolicy {
+    /// Record members' MLS signature keys to flag changes
+    /// (see [`crate::circle::key_audit`]).
+    pub key_audit: bool,
+}
+
+/// Diagnostics settings.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DiagnosticsPolicy {
+    /// Record storage and MLS calls taking at least this long as
+    /// breadcrumbs; `None` turns timing off.
+    pub slow_op_threshold_ms: Option<u64>,
+}
+
+/// The core's user-facing settings.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HavenConfig {
+    /// Relay list publishing and filtering.
+    pub relays: RelayPolicy,
+    /// Location sharing defaults.
+    pub location: LocationSettings,
+    /// Privacy opt-ins.
+    pub privacy: PrivacyPolicy,
+    /// What published events reveal.
+    pub metadata: PrivacySettings,
+    /// Diagnostics settings.
+    pub diagnostics: DiagnosticsPolicy,
+}
+
+impl HavenConfig {
+    /// Checks every setting.
+    ///
+    /// # Errors
+    ///
+    /// Returns a description of the first invalid setting.
+    pub fn validate(&self) -> Result<(), String> {
+        let interval = self.location.update_interval_minutes;
+        if !(MIN_UPDATE_INTERVAL_MINUTES..=MAX_UPDATE_INTERVAL_MINUTES).contains(&interval) {
+            return Err(format!(
+                "Update interval must be between {MIN_UPDATE_INTERVAL_MINUTES} and \
+                 {MAX_UPDATE_INTERVAL_MINUTES} minutes"
+            ));
+        }
+        if let ShareExpiration::Custom(secs) = self.location.share_expiration {
+            if !(MIN_SHARE_EXPIRATION_SECS..=LOCATION_RETENTION_SECS).contains(&secs) {
+                return Err(format!(
+                    "Share expiration must be between {MIN_SHARE_EXPIRATION_SECS} and \
+                     {LOCATION_RETENTION_SECS} seconds"
+                ));
+            }
+        }
+        self.metadata.validate()?;
+        if self.diagnostics.slow_op_threshold_ms == Some(0) {
+            return Err("Slow-operation threshold must be at least 1 ms".to_string());
+        }
+        Ok(())
+    }
+
+    /// Parses and validates an exported config. Missing fields take their
+    /// defaults.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the JSON is malformed or a setting is invalid.
+    pub fn from_json(json: &str) -> Result<Self, String> {
+        let doc: Value = serde_json::from_str(json).map_err(|e| e.to_string())?;
+        Self::default().apply_patch(&doc)
+    }
+
+    /// Serializes the config for export.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if serialization fails (extremely rare).
+    pub fn to_json(&self) -> Result<String, serde_json::Error> {
+        serde_json::to_string_pretty(self)
+    }
+
+    /// The merge patch that turns `base` into this config: only the
+    /// settings that differ. Empty (`{}`) when they are equal.
+    #[must_use]
+    pub fn diff(&self, base: &Self) -> Value {
+        let (Ok(this), Ok(base)) = (serde_json::to_value(self), serde_json::to_value(base)) else {
+            return Value::Object(Map::new());
+        };
+        diff_values(&base, &this).unwrap_or_else(|| Value::Object(Map::new()))
+    }
+
+    /// This config with the merge patch `patch` applied, validated. `null`
+    /// resets a setting to its default.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the patch does not fit the config or leaves a
+    /// setting invalid.
+    pub fn apply_patch(&self, patch: &Value) -> Result<Self, String> {
+        let mut doc = serde_json::to_value(self).map_err(|e| e.to_string())?;
+        let defaults = serde_json::to_value(Self::default()).map_err(|e| e.to_string())?;
+        merge_patch(&mut doc, patch, Some(&defaults));
+        let config: Self = serde_json::from_value(doc).map_err(|e| e.to_string())?;
+        config.validate()?;
+        Ok(config)
+    }
+
+    /// The defaults with each of `patches` applied in order, later ones
+    /// winning.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a patch does not fit the config or leaves a
+    /// setting invalid.
+    pub fn layered<'a>(patches: impl IntoIterator<Item = &'a Value>) -> Result<Self, String> {
+        patches
+            .into_iter()
+            .try_fold(Self::default(), |config, patch| config.apply_patch(patch))
+    }
+}
+
+/// Applies an RFC 7386 merge patch to `target`. A `null` member resets the
+/// key to its value in `defaults`, or removes it when there is none.
+fn merge_patch(target: &mut Value, patch: &Value, defaults: Option<&Value>) {
+    let Value::Object(patch) = patch else {
+        *target = patch.clone();
+        return;
+    };
+    if !target.is_object() {
+        *target = Value::Object(Map::new());
+    }
+    let Value::Object(target) = target else {
+        return;
+    };
+    for (key, value) in patch {
+        let default = defaults.and_then(|d| d.get(key));
+        if value.is_null() {
+            match default {
+                Some(default) => {
+                    target.insert(key.clone(), default.clone());
+                }
+                None => {
+                    target.remove(key);
+                }
+            }
+        } else {
+            merge_patch(
+                target.entry(key.clone()).or_insert(Value::Null),
+                value,
+                default,
+            );
+        }
+    }
+}
+
+/// The merge patch turning `from` into `to`, or `None` when they are equal.
+fn diff_values(from: &Value, to: &Value) -> Option<Value> {
+    if from == to {
+        return None;
+    }
+    let (Value::Object(from), Value::Object(to)) = (from, to) else {
+        return Some(to.clone());
+    };
+    let mut patch = Map::new();
+    for (key, value) in to {
+        let changed = from
+            .get(key)
+            .map_or_else(|| Some(value.clone()), |old| diff_values(old, value));
+        if let Some(changed) = changed {
+            patch.insert(key.clone(), changed);
+        }
+    }
+    for key in from.keys().filter(|k| !to.contains_key(*k)) {
+        patch.insert(key.clone(), Value::Null);
+    }
+    Some(Value::Object(patch))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::location::LocationPrecision;
+    use serde_json::json;
+
+    #[test]
+    fn roundtrips_and_fills_in_missing_fields() {
+        let mut config = HavenConfig::default();
+        config.location.precision = LocationPrecision::Coarse;
+        config.diagnostics.slow_op_threshold_ms = Some(250);
+        let parsed = HavenConfig::from_json(&config.to_json().unwrap()).unwrap();
+        assert_eq!(parsed, config);
+
+        let partial = HavenConfig::from_json(
+            r#"{"relays":{"community_blacklist":true},"from_the_future":1}"#,
+        )
+        .unwrap();
+        assert!(partial.relays.community_blacklist);
+        assert!(partial.relays.publish_inbox_relay_list);
+        assert_eq!(partial.location, LocationSettings::default());
+    }
+
+    #[test]
+    fn diff_and_patch_are_inverse() {
+        let base = HavenConfig::default();
+        let mut changed = base.clone();
+        changed.location.update_interval_minutes = 15;
+        changed.location.share_expiration = ShareExpiration::Custom(7_200);
+        changed.privacy.key_audit = true;
+
+        let patch = changed.diff(&base);
+        assert_eq!(
+            patch,
+            json!({
+                "location": {"update_interval_minutes": 15, "share_expiration": {"Custom": 7200}},
+                "privacy": {"key_audit": true},
+            })
+        );
+        assert_eq!(base.apply_patch(&patch).unwrap(), changed);
+        assert_eq!(changed.diff(&changed), json!({}));
+    }
+
+    #[test]
+    fn layers_apply_in_order_and_null_resets() {
+        let rollout = json!({"location": {"update_interval_minutes": 10}});
+        let user =
+            json!({"location": {"update_interval_minutes": 30}, "privacy": {"key_audit": true}});
+        let config = HavenConfig::layered([&rollout, &user]).unwrap();
+        assert_eq!(config.location.update_interval_minutes, 30);
+
+        let reset = config
+            .apply_patch(&json!({"location": {"update_interval_minutes": null}, "privacy": null}))
+            .unwrap();
+        assert_eq!(
+            reset.location.update_interval_minutes,
+            LocationSettings::default().update_interval_minutes
+        );
+        assert!(!reset.privacy.key_audit);
+    }
+
+    #[test]
+    fn rejects_invalid_settings() {
+        let base = HavenConfig::default();
+        for patch in [
+            json!({"location": {"update_interval_minutes": 1}}),
+            json!({"location": {"share_expiration": {"Custom": 60}}}),
+            json!({"diagnostics": {"slow_op_threshold_ms": 0}}),
+            json!({"metadata": {"timestamp_fuzz_minutes": 60}}),
+            json!({"relays": {"community_blacklist": "yes"}}),
+        ] {
+            assert!(base.apply_patch(&patch).is_err(), "{patch}");
+        }
+    }
+}
@@ -0,0 +1,630 @@
+//! Linking a second device to the same identity.
+//!
+//! The user sets Haven up on a new phone without giving up the old one. The
+//! flow moves the identity key device-to-device and gets the new device back
+//! into every circle:
+//!
+//! ```text
+//! new device                          old device                 circle admin
+//! ──────────                          ──────────                 ────────────
+//! DeviceLinkSession::start
+//!   QR: haven-link:<link pubkey>?exp=…&relay=…
+//!                         ── scan ──▶ send_device_link
+//!                                       kind 1059 to the link key
+//!                                         └─ seal (identity key)
+//!                                              └─ kind 9 rumor ["t","device_link"]
+//!                                                   content: secret + circle list
+//! receive_device_link ◀──────────────
+//! IdentityManager::import_linked
+//! publish a KeyPackage
+//! request_rejoin ──────────────────────────────────────────────▶ InboxProcessor
+//!   one gift wrap per circle admin                               surfaces a
+//!                                                                RejoinRequest;
+//!                                                                the admin re-adds
+//! ```
+//!
+//! The link key is ephemeral and lives only in the [`DeviceLinkSession`]: the
+//! bundle can be opened by whoever holds that session, and by no one once it
+//! is dropped. Everything expires after [`DEVICE_LINK_TTL_SECS`].
+//!
+//! # Trust
+//!
+//! Anyone who sees the QR code can send the new device an identity of their
+//! own. The new device shows the linked npub before importing it, and the
+//! user confirms it is theirs. A [`RejoinRequest`] is only surfaced to an
+//! admin when the requester is already a member of the circle
+//! ([`CircleManager::check_rejoin_request`](crate::circle::CircleManager::check_rejoin_request)),
+//! so knowing a circle's routing id does not get a stranger in.
+//!
+//! Circles are identified in the bundle and in rejoin requests by their
+//! Nostr group ID, never the MLS group ID (Rule 4).
+
+use std::time::Duration;
+
+use nostr::nips::nip59::UnwrappedGift;
+use nostr::{Event, EventBuilder, EventId, Filter, Keys, Kind, PublicKey, Tag, Timestamp};
+use serde::{Deserialize, Serialize};
+use zeroize::Zeroizing;
+
+use crate::nostr::identity::{gift_wrap, IdentityKeypair, Signer};
+use crate::nostr::{NostrError, Result};
+use crate::relay::{PublishResult, RelayError, RelayManager, RelayResult};
+
+/// URI scheme of a device-link QR payload.
+pub const DEVICE_LINK_SCHEME: &str = "haven-link";
+
+/// Rumor kind of device-link and rejoin messages; told apart by their `t` tag.
+pub const KIND_DEVICE_LINK: u16 = 9;
+
+/// Rumor hashtag marking a device-link bundle.
+pub const DEVICE_LINK_TAG: &str = "device_link";
+
+/// Rumor hashtag marking a rejoin request.
+pub const REJOIN_REQUEST_TAG: &str = "rejoin_request";
+
+/// How long a link offer, its bundle and rejoin requests live, in seconds
+/// (10 minutes).
+pub const DEVICE_LINK_TTL_SECS: u64 = 10 * 60;
+
+/// Most relays carried in a QR payload, to keep the code scannable.
+pub const MAX_DEVICE_LINK_RELAYS: usize = 3;
+
+/// Max gift wraps fetched per relay when waiting for a bundle.
+const DEVICE_LINK_FETCH_LIMIT: usize = 50;
+
+/// What the new device shows as a QR code: where and to whom to send the
+/// bundle.
+#[derive(Clone, PartialEq, Eq)]
+pub struct DeviceLinkOffer {
+    /// The ephemeral link public key.
+    pub link_pubkey: PublicKey,
+    /// Relays the new device reads the bundle from.
+    pub relays: Vec<String>,
+    /// Unix timestamp after which the offer is void.
+    pub expires_at: i64,
+}
+
+impl DeviceLinkOffer {
+    /// Whether the offer has lapsed at `now`.
+    #[must_use]
+    pub const fn is_expired(&self, now: i64) -> bool {
+        now >= self.expires_at
+    }
+
+    /// Encodes the offer as a `haven-link:` URI for a QR code.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`NostrError::InvalidEvent`] if the URI cannot be built.
+    pub fn to_payload(&self) -> Result<String> {
+        let mut url = url::Url::parse(&format!(
+            "{DEVICE_LINK_SCHEME}:{}",
+            self.link_pubkey.to_hex()
+        ))
+        .map_err(|e| NostrError::InvalidEvent(format!("Failed to encode device link: {e}")))?;
+        {
+            let mut query = url.query_pairs_mut();
+            query.append_pair("exp", &self.expires_at.to_string());
+            for relay in &self.relays {
+                query.append_pair("relay", relay);
+            }
+        }
+        Ok(url.to_string())
+    }
+
+    /// Parses a `haven-link:` URI.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`NostrError::InvalidEvent`] if the payload is not a device
+    /// link, its key is invalid, or it names no relay.
+    pub fn parse(payload: &str) -> Result<Self> {
+        let invalid = || NostrError::InvalidEvent("Not a device link".to_string());
+        let url = url::Url::parse(payload.trim()).map_err(|_| invalid())?;
+        if url.scheme() != DEVICE_LINK_SCHEME {
+            return Err(invalid());
+        }
+        let link_pubkey = PublicKey::from_hex(url.path()).map_err(|_| invalid())?;
+        let mut expires_at = None;
+        let mut relays = Vec::new();
+        for (key, value) in url.query_pairs() {
+            match key.as_ref() {
+                "exp" => expires_at = value.parse::<i64>().ok(),
+                "relay" if relays.len() < MAX_DEVICE_LINK_RELAYS => {
+                    relays.push(value.into_owned());
+                }
+                _ => {}
+            }
+        }
+        let expires_at = expires_at.ok_or_else(invalid)?;
+        if relays.is_empty() {
+            return Err(invalid());
+        }
+        Ok(Self {
+            link_pubkey,
+            relays,
+            expires_at,
+        })
+    }
+}
+
+impl std::fmt::Debug for DeviceLinkOffer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("DeviceLinkOffer")
+            .field("link_pubkey", &"<redacted>")
+            .field("relays", &self.relays.len())
+            .field("expires_at", &self.expires_at)
+            .finish()
+    }
+}
+
+/// One circle as the old device describes it to the new one.
+#[derive(Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LinkedCircle {
+    /// The circle's Nostr group ID (hex).
+    pub nostr_group_id: String,
+    /// The circle's local display name.
+    pub display_name: String,
+    /// The circle's relays.
+    pub relays: Vec<String>,
+    /// Admin public keys (hex), who can re-add the new device.
+    pub admins: Vec<String>,
+}
+
+impl std::fmt::Debug for LinkedCircle {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("LinkedCircle")
+            .field("nostr_group_id", &"<redacted>")
+            .field("display_name", &self.display_name)
+            .field("relays", &self.relays)
+            .field("admins", &self.admins.len())
+            .finish()
+    }
+}
+
+/// The bundle's plaintext, inside the seal.
+#[derive(Serialize, Deserialize)]
+struct LinkBundle {
+    /// The identity secret key (hex).
+    #[serde(with = "crate::util::zeroizing_string")]
+    secret: Zeroizing<String>,
+    circles: Vec<LinkedCircle>,
+}
+
+/// An identity received over a device link, ready for
+/// [`IdentityManager::import_linked`](crate::nostr::identity::IdentityManager::import_linked).
+pub struct LinkedIdentity {
+    /// The identity's public key (hex). Show it before importing.
+    pub pubkey_hex: String,
+    /// The old device's circles.
+    pub circles: Vec<LinkedCircle>,
+    pub(crate) keypair: IdentityKeypair,
+}
+
+impl std::fmt::Debug for LinkedIdentity {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("LinkedIdentity")
+            .field("pubkey_hex", &"<redacted>")
+            .field("circles", &self.circles.len())
+            .finish_non_exhaustive()
+    }
+}
+
+/// The new device's side of a link: the ephemeral link key and its offer.
+pub struct DeviceLinkSession {
+    keys: Keys,
+    offer: DeviceLinkOffer,
+}
+
+impl DeviceLinkSession {
+    /// Starts a link that reads the bundle from `relays` (at most
+    /// [`MAX_DEVICE_LINK_RELAYS`] are kept).
+    ///
+    /// # Errors
+    ///
+    /// Returns [`NostrError::InvalidEvent`] if `relays` is empty.
+    pub fn start(relays: &[String]) -> Result<Self> {
+        if relays.is_empty() {
+            return Err(NostrError::InvalidEvent(
+                "A device link needs at least one relay".to_string(),
+            ));
+        }
+        let keys = Keys::generate();
+        let ttl = i64::try_from(DEVICE_LINK_TTL_SECS).unwrap_or(i64::MAX);
+        let offer = DeviceLinkOffer {
+            link_pubkey: keys.public_key(),
+            relays: relays
+                .iter()
+                .take(MAX_DEVICE_LINK_RELAYS)
+                .cloned()
+                .collect(),
+            expires_at: chrono::Utc::now().timestamp().saturating_add(ttl),
+        };
+        Ok(Self { keys, offer })
+    }
+
+    /// The offer to show as a QR code.
+    #[must_use]
+    pub const fn offer(&self) -> &DeviceLinkOffer {
+        &self.offer
+    }
+
+    /// Opens a bundle sent to this session.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`NostrError::GiftUnwrap`] if the event is not a bundle for
+    /// this session, and [`NostrError::Expired`] if it or the offer lapsed.
+    pub async fn open(&self, gift_wrap_event: &Event) -> Result<LinkedIdentity> {
+        let now = chrono::Utc::now().timestamp();
+        if self.offer.is_expired(now) {
+            return Err(NostrError::Expired);
+        }
+        let (sender, content) =
+            unwrap_tagged(&self.keys, gift_wrap_event, DEVICE_LINK_TAG, now).await?;
+        let content = Zeroizing::new(content);
+        let bundle: LinkBundle = serde_json::from_str(&content)
+            .map_err(|_| NostrError::GiftUnwrap("Malformed device link".to_string()))?;
+        drop(content);
+        let mut bytes = Zeroizing::new([0u8; 32]);
+        hex::decode_to_slice(bundle.secret.as_str(), bytes.as_mut())
+            .map_err(|_| NostrError::GiftUnwrap("Malformed device link".to_string()))?;
+        let keypair = IdentityKeypair::from_secret_bytes(*bytes)
+            .map_err(|_| NostrError::GiftUnwrap("Malformed device link".to_string()))?;
+        // The seal is signed by the identity being handed over.
+        if keypair.pubkey_bytes() != sender.to_bytes() {
+            return Err(NostrError::GiftUnwrap(
+                "Device link not sealed by its identity".to_string(),
+            ));
+        }
+        Ok(LinkedIdentity {
+            pubkey_hex: sender.to_hex(),
+            circles: bundle.circles,
+            keypair,
+        })
+    }
+}
+
+impl std::fmt::Debug for DeviceLinkSession {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("DeviceLinkSession")
+            .field("offer", &self.offer)
+            .finish_non_exhaustive()
+    }
+}
+
+/// A request from the user's new device to be added back to a circle.
+#[derive(Clone)]
+pub struct RejoinRequest {
+    /// The requesting identity (the same user's, on a new device).
+    pub requester_pubkey: PublicKey,
+    /// The circle's Nostr group ID.
+    pub nostr_group_id: [u8; 32],
+    /// The new device's `KeyPackage` event, signed by the requester.
+    pub key_package_event: Event,
+    /// The event id of the gift wrap it arrived in.
+    pub wrapper_event_id: EventId,
+}
+
+impl std::fmt::Debug for RejoinRequest {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RejoinRequest")
+            .field("requester_pubkey", &"<redacted>")
+            .field("nostr_group_id", &"<redacted>")
+            .field("wrapper_event_id", &self.wrapper_event_id)
+            .finish_non_exhaustive()
+    }
+}
+
+/// A rejoin request's plaintext, inside the seal.
+#[derive(Serialize, Deserialize)]
+struct RejoinBody {
+    nostr_group_id: String,
+    key_package: String,
+}
+
+/// The expiration stamped on link messages created now.
+fn link_expiration() -> Timestamp {
+    Timestamp::now() + Duration::from_secs(DEVICE_LINK_TTL_SECS)
+}
+
+/// Gift-wraps `content` to `recipient` as a kind 9 rumor tagged `tag`.
+fn wrap_tagged(
+    sender: &dyn Signer,
+    recipient: &PublicKey,
+    tag: &str,
+    content: &str,
+) -> Result<Event> {
+    let expiration = link_expiration();
+    let rumor = EventBuilder::new(Kind::Custom(KIND_DEVICE_LINK), content)
+        .tags([Tag::hashtag(tag), Tag::expiration(expiration)])
+        .build(sender.public_key());
+    gift_wrap(sender, recipient, rumor, [Tag::expiration(expiration)])
+        .map_err(|e| NostrError::GiftWrap(e.to_string()))
+}
+
+/// Unwraps a gift wrap whose rumor is tagged `tag`, returning the seal's
+/// sender and the rumor content.
+async fn unwrap_tagged(
+    keys: &Keys,
+    gift_wrap_event: &Event,
+    tag: &str,
+    now: i64,
+) -> Result<(PublicKey, String)> {
+    let not_ours = || NostrError::GiftUnwrap(format!("Gift wrap does not contain a {tag}"));
+    if gift_wrap_event.kind != Kind::GiftWrap {
+        return Err(not_ours());
+    }
+    let unwrapped = UnwrappedGift::from_gift_wrap(keys, gift_wrap_event)
+        .await
+        .map_err(|e| NostrError::GiftUnwrap(e.to_string()))?;
+    let rumor = unwrapped.rumor;
+    if rumor.kind != Kind::Custom(KIND_DEVICE_LINK) || rumor.pubkey != unwrapped.sender {
+        return Err(not_ours());
+    }
+    let tagged = rumor.tags.iter().any(|t| {
+        let v = t.as_slice();
+        v.len() >= 2 && v[0] == "t" && v[1] == tag
+    });
+    let expires_at = rumor.tags.iter().find_map(|t| {
+        let v = t.as_slice();
+        (v.len() >= 2 && v[0] == "expiration").then(|| v[1].clone())
+    });
+    let expires_at = expires_at.and_then(|v| v.parse::<i64>().ok());
+    let (true, Some(expires_at)) = (tagged, expires_at) else {
+        return Err(not_ours());
+    };
+    if now >= expires_at {
+        return Err(NostrError::Expired);
+    }
+    Ok((unwrapped.sender, rumor.content))
+}
+
+/// Gift-wraps the identity and `circles` for the device behind `offer`.
+///
+/// # Errors
+///
+/// Returns [`NostrError::Expired`] if the offer lapsed, and
+/// [`NostrError::GiftWrap`] if encryption fails.
+pub fn wrap_device_link(
+    identity_keys: &Keys,
+    offer: &DeviceLinkOffer,
+    circles: &[LinkedCircle],
+) -> Result<Event> {
+    if offer.is_expired(chrono::Utc::now().timestamp()) {
+        return Err(NostrError::Expired);
+    }
+    let bundle = LinkBundle {
+        secret: Zeroizing::new(identity_keys.secret_key().to_secret_hex()),
+        circles: circles.to_vec(),
+    };
+    let content = Zeroizing::new(
+        serde_json::to_string(&bundle)
+            .map_err(|e| NostrError::GiftWrap(format!("Failed to encode device link: {e}")))?,
+    );
+    drop(bundle);
+    wrap_tagged(identity_keys, &offer.link_pubkey, DEVICE_LINK_TAG, &content)
+}
+
+/// Gift-wraps a request to re-add this device to a circle, for one of its
+/// admins.
+///
+/// # Errors
+///
+/// Returns [`NostrError::InvalidEvent`] if the `KeyPackage` event is not the
+/// sender's, and [`NostrError::GiftWrap`] if encryption fails.
+pub fn wrap_rejoin_request(
+    identity: &dyn Signer,
+    admin: &PublicKey,
+    nostr_group_id: &[u8; 32],
+    key_package_event: &Event,
+) -> Result<Event> {
+    if key_package_event.pubkey != identity.public_key() {
+        return Err(NostrError::InvalidEvent(
+            "KeyPackage is not signed by this identity".to_string(),
+        ));
+    }
+    let body = RejoinBody {
+        nostr_group_id: hex::encode(nostr_group_id),
+        key_package: nostr::JsonUtil::as_json(key_package_event),
+    };
+    let content = serde_json::to_string(&body)
+        .map_err(|e| NostrError::GiftWrap(format!("Failed to encode rejoin request: {e}")))?;
+    wrap_tagged(identity, admin, REJOIN_REQUEST_TAG, &content)
+}
+
+/// Unwraps a rejoin request addressed to `keys`.
+///
+/// Checks that the `KeyPackage` event is validly signed by the requester.
+/// Whether the requester may rejoin is up to
+/// [`CircleManager::check_rejoin_request`](crate::circle::CircleManager::check_rejoin_request).
+///
+/// # Errors
+///
+/// Returns [`NostrError::GiftUnwrap`] if the event is not a rejoin request
+/// or its `KeyPackage` is invalid, and [`NostrError::Expired`] if it lapsed.
+pub async fn unwrap_rejoin_request(keys: &Keys, gift_wrap_event: &Event) -> Result<RejoinRequest> {
+    let now = chrono::Utc::now().timestamp();
+    let (requester_pubkey, content) =
+        unwrap_tagged(keys, gift_wrap_event, REJOIN_REQUEST_TAG, now).await?;
+    let malformed = || NostrError::GiftUnwrap("Malformed rejoin request".to_string());
+    let body: RejoinBody = serde_json::from_str(&content).map_err(|_| malformed())?;
+    let mut nostr_group_id = [0u8; 32];
+    hex::decode_to_slice(&body.nostr_group_id, &mut nostr_group_id).map_err(|_| malformed())?;
+    let key_package_event: Event =
+        nostr::JsonUtil::from_json(&body.key_package).map_err(|_| malformed())?;
+    if key_package_event.pubkey != requester_pubkey || key_package_event.verify().is_err() {
+        return Err(malformed());
+    }
+    Ok(RejoinRequest {
+        requester_pubkey,
+        nostr_group_id,
+        key_package_event,
+        wrapper_event_id: gift_wrap_event.id,
+    })
+}
+
+/// Sends the identity and `circles` to the device that showed `offer`.
+///
+/// # Errors
+///
+/// Returns [`RelayError::InvalidEvent`] if wrapping fails or the offer
+/// lapsed, and otherwise the errors of [`RelayManager::publish_event`].
+pub async fn send_device_link(
+    relays: &RelayManager,
+    identity_keys: &Keys,
+    offer: &DeviceLinkOffer,
+    circles: &[LinkedCircle],
+) -> RelayResult<PublishResult> {
+    let wrap = wrap_device_link(identity_keys, offer, circles)
+        .map_err(|e| RelayError::InvalidEvent(e.to_string()))?;
+    relays.publish_event(&wrap, &offer.relays).await
+}
+
+/// Looks for the bundle sent to `session` on its offer's relays.
+///
+/// Returns `Ok(None)` while nothing has arrived; call again until the offer
+/// expires.
+///
+/// # Errors
+///
+/// Returns [`RelayError::InvalidEvent`] once the offer lapsed, and otherwise
+/// the errors of [`RelayManager::fetch_events_per_relay`].
+pub async fn receive_device_link(
+    relays: &RelayManager,
+    session: &DeviceLinkSession,
+) -> RelayResult<Option<LinkedIdentity>> {
+    if session.offer.is_expired(chrono::Utc::now().timestamp()) {
+        return Err(RelayError::InvalidEvent(NostrError::Expired.to_string()));
+    }
+    let filter = Filter::new()
+        .kind(Kind::GiftWrap)
+        .pubkey(session.offer.link_pubkey)
+        .limit(DEVICE_LINK_FETCH_LIMIT);
+    let outcomes = relays
+        .fetch_events_per_relay(filter, &session.offer.relays)
+        .await?;
+    for event in outcomes.into_iter().flat_map(|o| o.events) {
+        if let Ok(linked) = session.open(&event).await {
+            return Ok(Some(linked));
+        }
+    }
+    Ok(None)
+}
+
+/// Asks each circle's admins to re-add this device, with `key_package_event`.
+///
+/// When the user is an admin themselves the request goes to their own inbox,
+/// where the old device picks it up. Each request goes to the admin's inbox
+/// relays, else their NIP-65 read relays, else the circle's relays.
+/// Best-effort: returns how many requests at least one relay accepted.
+pub async fn request_rejoin(
+    relays: &RelayManager,
+    identity_keys: &Keys,
+    circles: &[LinkedCircle],
+    key_package_event: &Event,
+) -> usize {
+    let mut delivered = 0;
+    for circle in circles {
+        let mut nostr_group_id = [0u8; 32];
+        if hex::decode_to_slice(&circle.nostr_group_id, &mut nostr_group_id).is_err() {
+            continue;
+        }
+        for admin_hex in &circle.admins {
+            let Ok(admin) = PublicKey::from_hex(admin_hex) else {
+                continue;
+            };
+            let mut targets = relays
+                .fetch_inbox_relays(admin_hex)
+                .await
+                .unwrap_or_default();
+            if targets.is_empty() {
+                targets = relays
+                    .fetch_nip65_relays(admin_hex)
+                    .await
+                    .unwrap_or_default();
+            }
+            if targets.is_empty() {
+                targets.clone_from(&circle.relays);
+            }
+            let Ok(wrap) =
+                wrap_rejoin_request(identity_keys, &admin, &nostr_group_id, key_package_event)
+            else {
+                continue;
+            };
+            match relays.publish_event(&wrap, &targets).await {
+                Ok(result) if !result.accepted_by.is_empty() => delivered += 1,
+                Ok(_) => {}
+                Err(e) => log::debug!("[request_rejoin] publish failed: {e}"),
+            }
+        }
+    }
+    delivered
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn circle() -> LinkedCircle {
+        LinkedCircle {
+            nostr_group_id: hex::encode([7u8; 32]),
+            display_name: "Family".to_string(),
+            relays: vec!["wss://relay.example.com".to_string()],
+            admins: vec![Keys::generate().public_key().to_hex()],
+        }
+    }
+
+    #[test]
+    fn offer_payload_roundtrips() {
+        let session = DeviceLinkSession::start(&[
+            "wss://a.example.com".to_string(),
+            "wss://b.example.com/path".to_string(),
+        ])
+        .unwrap();
+        let payload = session.offer().to_payload().unwrap();
+        assert!(payload.starts_with("haven-link:"));
+        assert_eq!(&DeviceLinkOffer::parse(&payload).unwrap(), session.offer());
+
+        assert!(DeviceLinkOffer::parse("https://example.com").is_err());
+        assert!(DeviceLinkSession::start(&[]).is_err());
+    }
+
+    #[tokio::test]
+    async fn bundle_reaches_only_the_session() {
+        let identity = Keys::generate();
+        let session = DeviceLinkSession::start(&["wss://r.example.com".to_string()]).unwrap();
+        let circles = vec![circle()];
+        let wrap = wrap_device_link(&identity, session.offer(), &circles).unwrap();
+
+        let linked = session.open(&wrap).await.unwrap();
+        assert_eq!(linked.pubkey_hex, identity.public_key().to_hex());
+        assert_eq!(
+            linked.keypair.pubkey_bytes(),
+            identity.public_key().to_bytes()
+        );
+        assert_eq!(linked.circles, circles);
+
+        let other = DeviceLinkSession::start(&["wss://r.example.com".to_string()]).unwrap();
+        assert!(other.open(&wrap).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn rejoin_request_roundtrips() {
+        let (identity, admin) = (Keys::generate(), Keys::generate());
+        let key_package = EventBuilder::new(Kind::Custom(30443), "kp")
+            .sign_with_keys(&identity)
+            .unwrap();
+        let wrap =
+            wrap_rejoin_request(&identity, &admin.public_key(), &[7; 32], &key_package).unwrap();
+        let request = unwrap_rejoin_request(&admin, &wrap).await.unwrap();
+        assert_eq!(request.requester_pubkey, identity.public_key());
+        assert_eq!(request.nostr_group_id, [7; 32]);
+        assert_eq!(request.key_package_event.id, key_package.id);
+
+        let foreign = EventBuilder::new(Kind::Custom(30443), "kp")
+            .sign_with_keys(&Keys::generate())
+            .unwrap();
+        assert!(wrap_rejoin_request(&identity, &admin.public_key(), &[7; 32], &foreign).is_err());
+    }
+}
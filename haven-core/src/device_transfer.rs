@@ -0,0 +1,483 @@
+//! Moving to a new device with the same identity.
+//!
+//! Where [`crate::device_link`] adds a device next to the old one, a
+//! transfer replaces it: the user exports one passphrase-protected file on
+//! the old phone and restores it on the new one.
+//!
+//! # What moves, and why only once
+//!
+//! A circle's MLS state (epoch secrets, the ratchet tree, this member's leaf
+//! and its message counters) may live on exactly one device. Two devices
+//! running a copy would both send from the same leaf, reusing ratchet
+//! generations and forking epochs: a confidentiality loss, not just a sync
+//! problem. So the MLS state is *moved*, never copied:
+//!
+//! - With `carry_state`, [`CircleManager::export_for_transfer`] copies the
+//!   engine's database under the session lock and retires the session
+//!   ([`SessionManager::export_for_transfer`]). The old device can still show
+//!   its circles but never sends in them again.
+//! - Without it (or for a circle whose state did not make it across), the
+//!   bundle still lists every circle, and the new device asks an admin to
+//!   re-add it with a fresh `KeyPackage` — the same rejoin flow a linked
+//!   device uses ([`crate::device_link::request_rejoin`]).
+//!
+//! # Restoring
+//!
+//! ```text
+//! TransferBundle::parse
+//!   IdentityManager::import_encrypted(identity_backup, passphrase)
+//!   TransferBundle::open(identity keys)   -> TransferContents
+//!   TransferContents::install_session     (before the CircleManager opens)
+//! CircleManager::new
+//!   CircleManager::adopt_transferred      -> adopted + needs_rejoin
+//!   request_rejoin(needs_rejoin)
+//! ```
+//!
+//! # Bundle format
+//!
+//! `version (1 byte) || backup length (2 bytes, big endian) || identity
+//! backup || nonce (24 bytes) || ciphertext`.
+//!
+//! The identity backup is the NIP-49 `ncryptsec` the user already knows from
+//! key backup, so one passphrase opens the whole bundle and the file doubles
+//! as an identity backup. The ciphertext is XChaCha20-Poly1305 over the
+//! deflate-compressed JSON contents, under a key derived from the identity
+//! secret, with everything before the nonce as associated data.
+//!
+//! The contents carry circles' MLS group IDs and the engine database's
+//! passphrase; both stay inside the seal and never reach a relay (Rule 4).
+//!
+//! [`CircleManager::export_for_transfer`]: crate::circle::CircleManager::export_for_transfer
+//! [`SessionManager::export_for_transfer`]: crate::nostr::mls::SessionManager::export_for_transfer
+
+use std::io::{Read, Write};
+use std::path::Path;
+
+use base64::engine::general_purpose::STANDARD as BASE64;
+use base64::Engine as _;
+use chacha20poly1305::aead::{Aead, KeyInit, Payload};
+use chacha20poly1305::{Key, XChaCha20Poly1305, XNonce};
+use flate2::read::DeflateDecoder;
+use flate2::write::DeflateEncoder;
+use flate2::Compression;
+use nostr::Keys;
+use rand::rngs::OsRng;
+use rand::RngCore;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use zeroize::{Zeroize, ZeroizeOnDrop, Zeroizing};
+
+use crate::circle::CircleWithMembers;
+use crate::device_link::LinkedCircle;
+use crate::nostr::mls::storage::UNMARKED_MLS_STORAGE_FORMAT;
+use crate::nostr::mls::{SessionSnapshot, StorageConfig};
+use crate::nostr::{NostrError, Result};
+
+/// Current bundle format version.
+pub const TRANSFER_BUNDLE_VERSION: u8 = 1;
+
+/// Domain separator for the sealing key.
+const KEY_DOMAIN: &[u8] = b"haven/device-transfer/v1";
+
+/// Prefix every NIP-49 identity backup starts with.
+const NCRYPTSEC_PREFIX: &str = "ncryptsec1";
+
+/// XChaCha20 nonce length.
+const NONCE_LEN: usize = 24;
+
+/// Upper bound on the opened contents' JSON size, so a corrupted or hostile
+/// bundle cannot decompress into an unbounded allocation.
+const MAX_CONTENTS_BYTES: u64 = 512 * 1024 * 1024;
+
+/// One circle in a transfer.
+#[derive(Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TransferredCircle {
+    /// The circle as a rejoin request describes it.
+    pub circle: LinkedCircle,
+    /// The circle type slug (see [`crate::circle::CircleType::as_str`]).
+    pub circle_type: String,
+    /// The circle's MLS group ID (hex).
+    pub(crate) mls_group_id: String,
+}
+
+impl std::fmt::Debug for TransferredCircle {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("TransferredCircle")
+            .field("circle", &self.circle)
+            .field("circle_type", &self.circle_type)
+            .field("mls_group_id", &"<redacted>")
+            .finish()
+    }
+}
+
+/// What a transfer bundle holds.
+pub struct TransferContents {
+    /// The identity's public key (hex).
+    pub pubkey_hex: String,
+    /// Unix timestamp of the export.
+    pub exported_at: i64,
+    /// The old device's circles.
+    pub circles: Vec<TransferredCircle>,
+    /// The engine's state, when it was carried.
+    pub(crate) session: Option<SessionSnapshot>,
+}
+
+impl TransferContents {
+    /// Whether the bundle carries the MLS state. Without it every circle
+    /// needs a rejoin.
+    #[must_use]
+    pub const fn carries_state(&self) -> bool {
+        self.session.is_some()
+    }
+
+    /// Installs the carried MLS state into `data_dir`. Returns `false` when
+    /// the bundle carries none.
+    ///
+    /// Call before a `CircleManager` opens on `data_dir`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`NostrError::StorageError`] if the directory already holds
+    /// MLS state, a session is open on it, or the state or its key cannot be
+    /// stored.
+    pub fn install_session(&self, data_dir: &Path) -> Result<bool> {
+        let Some(session) = &self.session else {
+            return Ok(false);
+        };
+        StorageConfig::new(data_dir).install_transferred_session(session)?;
+        Ok(true)
+    }
+}
+
+impl std::fmt::Debug for TransferContents {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("TransferContents")
+            .field("pubkey_hex", &"<redacted>")
+            .field("exported_at", &self.exported_at)
+            .field("circles", &self.circles.len())
+            .field("carries_state", &self.carries_state())
+            .finish()
+    }
+}
+
+/// What restoring a transfer did with each circle.
+#[derive(Debug, Default)]
+pub struct TransferRestoreReport {
+    /// Circles whose carried MLS state was adopted; they work at once.
+    pub adopted: Vec<CircleWithMembers>,
+    /// Circles the new device is not in yet. Send each admin a rejoin
+    /// request ([`crate::device_link::request_rejoin`]).
+    pub needs_rejoin: Vec<LinkedCircle>,
+}
+
+/// The sealed contents' plaintext.
+#[derive(Serialize, Deserialize)]
+struct ContentsWire {
+    pubkey: String,
+    exported_at: i64,
+    circles: Vec<TransferredCircle>,
+    session: Option<SessionWire>,
+}
+
+/// A [`SessionSnapshot`] inside the seal.
+#[derive(Serialize, Deserialize, Zeroize, ZeroizeOnDrop)]
+struct SessionWire {
+    /// The database (base64).
+    database: String,
+    /// The write-ahead log (base64).
+    wal: String,
+    /// The database passphrase.
+    passphrase: String,
+    /// The database's storage format; bundles from before it was recorded
+    /// carry the first one.
+    #[serde(default = "unmarked_storage_format")]
+    format: u32,
+}
+
+const fn unmarked_storage_format() -> u32 {
+    UNMARKED_MLS_STORAGE_FORMAT
+}
+
+/// Derives the sealing key from the identity secret key.
+fn transfer_key(keys: &Keys) -> Zeroizing<[u8; 32]> {
+    let secret = Zeroizing::new(keys.secret_key().to_secret_bytes());
+    let mut hasher = Sha256::new();
+    hasher.update(KEY_DOMAIN);
+    hasher.update(secret.as_slice());
+    Zeroizing::new(hasher.finalize().into())
+}
+
+/// Seals `contents` into a transfer bundle for the identity `keys`.
+///
+/// `identity_backup` is the identity's NIP-49 `ncryptsec` (see
+/// [`IdentityManager::export_encrypted`](crate::nostr::identity::IdentityManager::export_encrypted));
+/// its passphrase is the one the user restores with.
+///
+/// # Errors
+///
+/// Returns [`NostrError::InvalidEvent`] if `identity_backup` is not an
+/// `ncryptsec` or `contents` belong to another identity, and
+/// [`NostrError::Encryption`] if sealing fails.
+pub fn seal_transfer_bundle(
+    keys: &Keys,
+    identity_backup: &str,
+    contents: &TransferContents,
+) -> Result<Vec<u8>> {
+    if !identity_backup.starts_with(NCRYPTSEC_PREFIX) {
+        return Err(NostrError::InvalidEvent(
+            "Identity backup must be an ncryptsec".to_string(),
+        ));
+    }
+    if contents.pubkey_hex != keys.public_key().to_hex() {
+        return Err(NostrError::InvalidEvent(
+            "Transfer belongs to another identity".to_string(),
+        ));
+    }
+    let backup_len = u16::try_from(identity_backup.len())
+        .map_err(|_| NostrError::InvalidEvent("Identity backup is too long".to_string()))?;
+
+    let wire = ContentsWire {
+        pubkey: contents.pubkey_hex.clone(),
+        exported_at: contents.exported_at,
+        circles: contents.circles.clone(),
+        session: contents.session.as_ref().map(|s| SessionWire {
+            database: BASE64.encode(s.database.as_slice()),
+            wal: BASE64.encode(s.wal.as_slice()),
+            passphrase: s.passphrase.to_string(),
+            format: s.format,
+        }),
+    };
+    let json = Zeroizing::new(
+        serde_json::to_vec(&wire)
+            .map_err(|e| NostrError::Encryption(format!("Failed to encode transfer: {e}")))?,
+    );
+    drop(wire);
+    let mut encoder = DeflateEncoder::new(Vec::new(), Compression::default());
+    encoder
+        .write_all(&json)
+        .map_err(|e| NostrError::Encryption(format!("Failed to compress transfer: {e}")))?;
+    let compressed = Zeroizing::new(
+        encoder
+            .finish()
+            .map_err(|e| NostrError::Encryption(format!("Failed to compress transfer: {e}")))?,
+    );
+
+    let mut bundle = Vec::with_capacity(3 + identity_backup.len() + NONCE_LEN + compressed.len());
+    bundle.push(TRANSFER_BUNDLE_VERSION);
+    bundle.extend_from_slice(&backup_len.to_be_bytes());
+    bundle.extend_from_slice(identity_backup.as_bytes());
+    let header_len = bundle.len();
+
+    let mut nonce = [0u8; NONCE_LEN];
+    OsRng.fill_bytes(&mut nonce);
+    let key = transfer_key(keys);
+    let ciphertext = XChaCha20Poly1305::new(Key::from_slice(key.as_ref()))
+        .encrypt(
+            XNonce::from_slice(&nonce),
+            Payload {
+                msg: &compressed,
+                aad: &bundle[..header_len],
+            },
+        )
+        .map_err(|_| NostrError::Encryption("Failed to seal transfer".to_string()))?;
+    bundle.extend_from_slice(&nonce);
+    bundle.extend_from_slice(&ciphertext);
+    Ok(bundle)
+}
+
+/// A parsed, still sealed transfer bundle.
+#[derive(Clone)]
+pub struct TransferBundle {
+    bytes: Vec<u8>,
+    header_len: usize,
+}
+
+impl TransferBundle {
+    /// Parses a bundle produced by [`seal_transfer_bundle`].
+    ///
+    /// # Errors
+    ///
+    /// Returns [`NostrError::InvalidEvent`] if `bytes` are not a transfer
+    /// bundle of a supported version.
+    pub fn parse(bytes: &[u8]) -> Result<Self> {
+        let invalid = || NostrError::InvalidEvent("Not a transfer bundle".to_string());
+        let (&version, rest) = bytes.split_first().ok_or_else(invalid)?;
+        if version != TRANSFER_BUNDLE_VERSION || rest.len() < 2 {
+            return Err(invalid());
+        }
+        let backup_len = usize::from(u16::from_be_bytes([rest[0], rest[1]]));
+        let header_len = 3 + backup_len;
+        if bytes.len() < header_len + NONCE_LEN {
+            return Err(invalid());
+        }
+        let backup = std::str::from_utf8(&bytes[3..header_len]).map_err(|_| invalid())?;
+        if !backup.starts_with(NCRYPTSEC_PREFIX) {
+            return Err(invalid());
+        }
+        Ok(Self {
+            bytes: bytes.to_vec(),
+            header_len,
+        })
+    }
+
+    /// The identity's NIP-49 backup. Import it with the user's passphrase
+    /// ([`IdentityManager::import_encrypted`](crate::nostr::identity::IdentityManager::import_encrypted)),
+    /// then [`Self::open`] the rest with the imported keys.
+    #[must_use]
+    pub fn identity_backup(&self) -> &str {
+        // Checked to be UTF-8 by `parse`.
+        std::str::from_utf8(&self.bytes[3..self.header_len]).unwrap_or_default()
+    }
+
+    /// Opens the bundle with the identity `keys`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`NostrError::Decryption`] if the keys are not the bundle's
+    /// identity or the bundle is damaged.
+    pub fn open(&self, keys: &Keys) -> Result<TransferContents> {
+        let unreadable =
+            || NostrError::Decryption("Transfer bundle could not be opened".to_string());
+        let (header, sealed) = self.bytes.split_at(self.header_len);
+        let (nonce, ciphertext) = sealed.split_at(NONCE_LEN);
+        let key = transfer_key(keys);
+        let compressed = Zeroizing::new(
+            XChaCha20Poly1305::new(Key::from_slice(key.as_ref()))
+                .decrypt(
+                    XNonce::from_slice(nonce),
+                    Payload {
+                        msg: ciphertext,
+                        aad: header,
+                    },
+                )
+                .map_err(|_| unreadable())?,
+        );
+        let mut json = Zeroizing::new(Vec::new());
+        DeflateDecoder::new(compressed.as_slice())
+            .take(MAX_CONTENTS_BYTES)
+            .read_to_end(&mut json)
+            .map_err(|_| unreadable())?;
+        let wire: ContentsWire = serde_json::from_slice(&json).map_err(|_| unreadable())?;
+        if wire.pubkey != keys.public_key().to_hex() {
+            return Err(unreadable());
+        }
+        let session = match &wire.session {
+            Some(s) => Some(SessionSnapshot {
+                database: Zeroizing::new(BASE64.decode(&s.database).map_err(|_| unreadable())?),
+                wal: Zeroizing::new(BASE64.decode(&s.wal).map_err(|_| unreadable())?),
+                passphrase: Zeroizing::new(s.passphrase.clone()),
+                format: s.format,
+            }),
+            None => None,
+        };
+        Ok(TransferContents {
+            pubkey_hex: wire.pubkey,
+            exported_at: wire.exported_at,
+            circles: wire.circles,
+            session,
+        })
+    }
+}
+
+impl std::fmt::Debug for TransferBundle {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("TransferBundle")
+            .field("bytes", &self.bytes.len())
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use nostr::nips::nip49::{EncryptedSecretKey, KeySecurity};
+    use nostr::prelude::ToBech32;
+
+    fn backup(keys: &Keys) -> String {
+        EncryptedSecretKey::new(keys.secret_key(), "pw", 16, KeySecurity::Unknown)
+            .unwrap()
+            .to_bech32()
+            .unwrap()
+    }
+
+    fn contents(keys: &Keys, session: Option<SessionSnapshot>) -> TransferContents {
+        TransferContents {
+            pubkey_hex: keys.public_key().to_hex(),
+            exported_at: 1_000,
+            circles: vec![TransferredCircle {
+                circle: LinkedCircle {
+                    nostr_group_id: "11".repeat(32),
+                    display_name: "Family".to_string(),
+                    relays: vec!["wss://relay.example.com".to_string()],
+                    admins: vec![keys.public_key().to_hex()],
+                },
+                circle_type: "location_sharing".to_string(),
+                mls_group_id: "22".repeat(32),
+            }],
+            session,
+        }
+    }
+
+    #[test]
+    fn seals_and_opens_with_the_identity() {
+        let keys = Keys::generate();
+        let session = SessionSnapshot {
+            database: Zeroizing::new(vec![7; 4096]),
+            wal: Zeroizing::new(vec![8; 16]),
+            passphrase: Zeroizing::new("ab".repeat(32)),
+            format: 1,
+        };
+        let identity_backup = backup(&keys);
+        let sealed =
+            seal_transfer_bundle(&keys, &identity_backup, &contents(&keys, Some(session))).unwrap();
+        assert!(!sealed.windows(64).any(|w| w == "22".repeat(32).as_bytes()));
+
+        let bundle = TransferBundle::parse(&sealed).unwrap();
+        assert_eq!(bundle.identity_backup(), identity_backup);
+        let opened = bundle.open(&keys).unwrap();
+        assert_eq!(opened.pubkey_hex, keys.public_key().to_hex());
+        assert_eq!(opened.circles, contents(&keys, None).circles);
+        let session = opened.session.as_ref().unwrap();
+        assert_eq!(session.database.as_slice(), [7; 4096]);
+        assert_eq!(session.wal.as_slice(), [8; 16]);
+        assert_eq!(session.passphrase.as_str(), "ab".repeat(32));
+        assert_eq!(session.format, 1);
+
+        assert!(matches!(
+            bundle.open(&Keys::generate()),
+            Err(NostrError::Decryption(_))
+        ));
+    }
+
+    #[test]
+    fn tampered_headers_do_not_open() {
+        let keys = Keys::generate();
+        let mut sealed =
+            seal_transfer_bundle(&keys, &backup(&keys), &contents(&keys, None)).unwrap();
+        let last = sealed.len() - 1;
+        sealed[last] ^= 1;
+        assert!(TransferBundle::parse(&sealed).unwrap().open(&keys).is_err());
+
+        assert!(TransferBundle::parse(b"").is_err());
+        assert!(TransferBundle::parse(&[2, 0, 0]).is_err());
+    }
+
+    #[test]
+    fn refuses_foreign_contents_and_plain_backups() {
+        let keys = Keys::generate();
+        let other = Keys::generate();
+        assert!(seal_transfer_bundle(&keys, &backup(&keys), &contents(&other, None)).is_err());
+        assert!(seal_transfer_bundle(&keys, "nsec1abc", &contents(&keys, None)).is_err());
+    }
+
+    #[test]
+    fn a_bundle_without_state_installs_nothing() {
+        let keys = Keys::generate();
+        let contents = contents(&keys, None);
+        assert!(!contents.carries_state());
+        assert!(!contents
+            .install_session(Path::new("/nonexistent/haven-transfer"))
+            .unwrap());
+        assert!(!format!("{:?}", contents.circles[0]).contains(&"22".repeat(32)));
+    }
+}
@@ -0,0 +1,366 @@
+//! Local, telemetry-free error breadcrumbs and the exportable health snapshot.
+//!
+//! When something goes wrong, the most useful bug report says *where* and
+//! *what kind* of failure happened, and in what order. A [`Breadcrumb`]
+//! records exactly that — a module slug, an error-kind slug and a timestamp —
+//! and nothing else. Breadcrumbs are kept in a bounded ring buffer in
+//! `circles.db` (the oldest are evicted past [`MAX_BREADCRUMBS`]) so they
+//! survive the crash or restart that usually precedes a report.
+//!
+//! # Privacy
+//!
+//! - Nothing here is ever sent anywhere. The user exports a
+//!   [`HealthSnapshot`] explicitly and decides where to share it.
+//! - Both fields are restricted to short slugs (see [`is_valid_slug`]), so a
+//!   free-form error message — which may carry a relay URL, pubkey, group id
+//!   or coordinate — cannot be stored by accident. Callers pass a fixed kind
+//!   such as [`crate::circle::CircleError::kind`], never `to_string()`.
+//!
+//! # Slow operations
+//!
+//! With a threshold set ([`SlowOpLog::set_threshold`]), every `circles.db`
+//! statement and every MLS engine call (which does the engine's own storage
+//! I/O) is timed. One that takes at least the threshold is recorded as a
+//! breadcrumb: module [`SLOW_STORAGE_MODULE`] or [`SLOW_MLS_MODULE`], and the
+//! operation's name as the kind — for a statement its verb and table (see
+//! [`statement_name`]), never its parameters. Slow operations are buffered in
+//! memory and written with the next breadcrumb write or read, since the
+//! statement being timed may still hold the database.
+
+use std::future::Future;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use serde::Serialize;
+
+use crate::privacy::PrivacyFacts;
+
+/// Maximum number of breadcrumbs kept; older entries are evicted first.
+pub const MAX_BREADCRUMBS: usize = 200;
+
+/// Maximum length of a module or error-kind slug, in bytes.
+pub const MAX_SLUG_LEN: usize = 64;
+
+/// Schema version of the serialized [`HealthSnapshot`].
+pub const HEALTH_SNAPSHOT_VERSION: u32 = 1;
+
+/// Breadcrumb module of a slow `circles.db` statement.
+pub const SLOW_STORAGE_MODULE: &str = "storage.slow";
+
+/// Breadcrumb module of a slow MLS engine call.
+pub const SLOW_MLS_MODULE: &str = "mls.slow";
+
+/// One recorded failure: where, what kind, and when.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Breadcrumb {
+    /// Module slug, e.g. `"circle.manager"`.
+    pub module: String,
+    /// Error-kind slug, e.g. `"mls"`.
+    pub error_kind: String,
+    /// Unix timestamp (seconds) when the error was recorded.
+    pub timestamp: i64,
+}
+
+/// Returns `true` if `s` is an acceptable breadcrumb slug.
+///
+/// A slug is 1..=[`MAX_SLUG_LEN`] bytes of lowercase ASCII letters, digits,
+/// `_`, `.`, `:` or `-`. This rules out whitespace, so a sentence-like error
+/// message is always rejected, and rules out `/`, so a relay URL is too.
+#[must_use]
+pub fn is_valid_slug(s: &str) -> bool {
+    !s.is_empty()
+        && s.len() <= MAX_SLUG_LEN
+        && s.bytes().all(|b| {
+            b.is_ascii_lowercase() || b.is_ascii_digit() || matches!(b, b'_' | b'.' | b':' | b'-')
+        })
+}
+
+/// Times operations and keeps the slow ones as pending breadcrumbs.
+///
+/// Off until a threshold is set. Use the process-wide [`slow_ops`].
+#[derive(Debug)]
+pub struct SlowOpLog {
+    /// Threshold in milliseconds; 0 when off.
+    threshold_ms: AtomicU64,
+    /// Slow operations not yet written, oldest first.
+    pending: Mutex<Vec<Breadcrumb>>,
+}
+
+impl SlowOpLog {
+    /// A log with timing off.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            threshold_ms: AtomicU64::new(0),
+            pending: Mutex::new(Vec::new()),
+        }
+    }
+
+    /// Sets the slow threshold, or turns timing off with `None`. A
+    /// sub-millisecond threshold counts as one millisecond.
+    pub fn set_threshold(&self, threshold: Option<Duration>) {
+        let ms = threshold.map_or(0, |t| {
+            u64::try_from(t.as_millis()).unwrap_or(u64::MAX).max(1)
+        });
+        self.threshold_ms.store(ms, Ordering::Relaxed);
+    }
+
+    /// The slow threshold, `None` when timing is off.
+    #[must_use]
+    pub fn threshold(&self) -> Option<Duration> {
+        match self.threshold_ms.load(Ordering::Relaxed) {
+            0 => None,
+            ms => Some(Duration::from_millis(ms)),
+        }
+    }
+
+    /// Records `name` under `module` if `elapsed` reached the threshold.
+    ///
+    /// A `name` that is not a valid slug is dropped. At most
+    /// [`MAX_BREADCRUMBS`] are kept pending; the oldest go first.
+    pub fn observe(&self, module: &'static str, name: &str, elapsed: Duration) {
+        let Some(threshold) = self.threshold() else {
+            return;
+        };
+        if elapsed < threshold || !is_valid_slug(name) {
+            return;
+        }
+        let mut pending = self
+            .pending
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner);
+        if pending.len() >= MAX_BREADCRUMBS {
+            pending.remove(0);
+        }
+        pending.push(Breadcrumb {
+            module: module.to_string(),
+            error_kind: name.to_string(),
+            timestamp: chrono::Utc::now().timestamp(),
+        });
+    }
+
+    /// Awaits `fut`, recording it as `name` under `module` if it was slow.
+    pub async fn time_async<F: Future>(
+        &self,
+        module: &'static str,
+        name: &str,
+        fut: F,
+    ) -> F::Output {
+        let started = Instant::now();
+        let output = fut.await;
+        self.observe(module, name, started.elapsed());
+        output
+    }
+
+    /// Removes and returns the pending slow operations, oldest first.
+    pub fn take_pending(&self) -> Vec<Breadcrumb> {
+        std::mem::take(
+            &mut *self
+                .pending
+                .lock()
+                .unwrap_or_else(std::sync::PoisonError::into_inner),
+        )
+    }
+}
+
+impl Default for SlowOpLog {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+static SLOW_OPS: SlowOpLog = SlowOpLog::new();
+
+/// The process-wide slow-operation log.
+#[must_use]
+pub fn slow_ops() -> &'static SlowOpLog {
+    &SLOW_OPS
+}
+
+/// Names a SQL statement by its verb and, for reads and writes, its table:
+/// `"select.circles"`, `"insert.last_known_locations"`, `"pragma"`.
+///
+/// Nothing past the table name is looked at, so literals and parameters
+/// never reach the name.
+#[must_use]
+pub fn statement_name(sql: &str) -> String {
+    let mut words = sql.split_whitespace().map(|word| {
+        word.trim_start_matches(['"', '`', '['])
+            .chars()
+            .take_while(|c| c.is_ascii_alphanumeric() || *c == '_')
+            .map(|c| c.to_ascii_lowercase())
+            .collect::<String>()
+    });
+    let verb = words.next().unwrap_or_default();
+    let table = match verb.as_str() {
+        "select" | "delete" => words.find(|w| w == "from").and_then(|_| words.next()),
+        "insert" | "replace" => words.find(|w| w == "into").and_then(|_| words.next()),
+        "update" => words.find(|w| {
+            !matches!(
+                w.as_str(),
+                "or" | "replace" | "ignore" | "abort" | "rollback" | "fail"
+            )
+        }),
+        _ => None,
+    };
+    let name = match table {
+        Some(table) if !table.is_empty() => format!("{verb}.{table}"),
+        _ => verb,
+    };
+    name.chars().take(MAX_SLUG_LEN).collect()
+}
+
+/// A user-exportable diagnostics bundle.
+///
+/// Contains only what the user could already see in the app: the privacy
+/// facts and the local breadcrumbs.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct HealthSnapshot {
+    /// Schema version ([`HEALTH_SNAPSHOT_VERSION`]).
+    pub version: u32,
+    /// Unix timestamp (seconds) when the snapshot was taken.
+    pub generated_at: i64,
+    /// The crate version that produced the snapshot.
+    pub core_version: &'static str,
+    /// The effective privacy configuration.
+    pub privacy: PrivacyFacts,
+    /// Recent breadcrumbs, oldest first.
+    pub breadcrumbs: Vec<Breadcrumb>,
+}
+
+impl HealthSnapshot {
+    /// Assembles a snapshot.
+    #[must_use]
+    pub const fn new(
+        generated_at: i64,
+        privacy: PrivacyFacts,
+        breadcrumbs: Vec<Breadcrumb>,
+    ) -> Self {
+        Self {
+            version: HEALTH_SNAPSHOT_VERSION,
+            generated_at,
+            core_version: env!("CARGO_PKG_VERSION"),
+            privacy,
+            breadcrumbs,
+        }
+    }
+
+    /// Serializes the snapshot as JSON.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if serialization fails (extremely rare).
+    pub fn to_json(&self) -> Result<String, serde_json::Error> {
+        serde_json::to_string(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn slugs_accept_identifiers() {
+        for s in [
+            "mls",
+            "circle.manager",
+            "relay:publish",
+            "no_relays",
+            "e2e-1",
+        ] {
+            assert!(is_valid_slug(s), "{s}");
+        }
+    }
+
+    #[test]
+    fn slugs_reject_free_form_messages() {
+        for s in [
+            "",
+            "Storage error: disk full",
+            "wss://relay.example",
+            "Mls",
+            "café",
+            &"a".repeat(MAX_SLUG_LEN + 1),
+        ] {
+            assert!(!is_valid_slug(s), "{s}");
+        }
+    }
+
+    #[test]
+    fn snapshot_json_shape() {
+        let privacy = PrivacyFacts::collect(true, vec![], vec![], vec![]);
+        let snapshot = HealthSnapshot::new(
+            1_000,
+            privacy,
+            vec![Breadcrumb {
+                module: "circle.manager".into(),
+                error_kind: "mls".into(),
+                timestamp: 900,
+            }],
+        );
+        let v: serde_json::Value = serde_json::from_str(&snapshot.to_json().unwrap()).unwrap();
+        assert_eq!(v["version"], HEALTH_SNAPSHOT_VERSION);
+        assert_eq!(v["generated_at"], 1_000);
+        assert_eq!(v["breadcrumbs"][0]["error_kind"], "mls");
+        assert_eq!(
+            v["privacy"]["version"],
+            crate::privacy::PRIVACY_FACTS_VERSION
+        );
+    }
+
+    #[test]
+    fn statement_names_never_include_parameters() {
+        for (sql, name) in [
+            (
+                "SELECT * FROM circles WHERE mls_group_id = ?1",
+                "select.circles",
+            ),
+            (
+                "INSERT OR REPLACE INTO last_known_locations (a) VALUES ('secret')",
+                "insert.last_known_locations",
+            ),
+            (
+                "INSERT INTO circle_roster(a, b) VALUES (?1, ?2)",
+                "insert.circle_roster",
+            ),
+            ("UPDATE circles SET display_name = 'Home'", "update.circles"),
+            (
+                "DELETE FROM circle_roster WHERE x = 1",
+                "delete.circle_roster",
+            ),
+            ("  PRAGMA journal_mode = WAL", "pragma"),
+            ("", ""),
+        ] {
+            assert_eq!(statement_name(sql), name, "{sql}");
+        }
+    }
+
+    #[test]
+    fn slow_ops_only_records_past_the_threshold() {
+        let log = SlowOpLog::new();
+        log.observe(
+            SLOW_STORAGE_MODULE,
+            "select.circles",
+            Duration::from_secs(9),
+        );
+        assert!(log.take_pending().is_empty());
+
+        log.set_threshold(Some(Duration::from_millis(100)));
+        log.observe(
+            SLOW_STORAGE_MODULE,
+            "select.circles",
+            Duration::from_millis(99),
+        );
+        log.observe(SLOW_MLS_MODULE, "process_event", Duration::from_millis(100));
+        log.observe(SLOW_STORAGE_MODULE, "Not a slug", Duration::from_secs(1));
+        let pending = log.take_pending();
+        assert_eq!(pending.len(), 1);
+        assert_eq!(pending[0].module, SLOW_MLS_MODULE);
+        assert_eq!(pending[0].error_kind, "process_event");
+        assert!(log.take_pending().is_empty());
+
+        log.set_threshold(None);
+        assert_eq!(log.threshold(), None);
+    }
+}
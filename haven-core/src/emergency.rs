@@ -0,0 +1,221 @@
+//! Emergency (SOS) broadcasts.
+//!
+//! An SOS is an ordinary MLS application message with three differences from a
+//! routine location update:
+//!
+//! - the inner rumor is tagged `["t","sos"]` so receivers surface it as
+//!   [`LocationMessageResult::Sos`] instead of a silent pin move;
+//! - it always carries the exact GPS fix — it never goes through any
+//!   precision reduction a routine share may apply;
+//! - it expires quickly ([`SOS_EXPIRATION_SECS`]): an alert about where
+//!   someone *was* stops being actionable after a few minutes, and the next
+//!   SOS supersedes it.
+//!
+//! The payload ([`SosMessage`]) is a [`LocationMessage`] with an optional short
+//! free-text note flattened alongside, so a receiver that only understands
+//! `LocationMessage` still parses the position.
+//!
+//! Fan-out to several circles is done by
+//! [`CircleManager::send_sos`](crate::circle::CircleManager::send_sos).
+//!
+//! [`LocationMessageResult::Sos`]: crate::nostr::mls::LocationMessageResult::Sos
+
+use chrono::Duration;
+use nostr::Event;
+use serde::{Deserialize, Serialize};
+
+use crate::location::LocationMessage;
+use crate::nostr::mls::types::GroupId;
+
+/// Inner-rumor hashtag marking an emergency broadcast.
+pub const SOS_TAG: &str = "sos";
+
+/// Freshness / expiration window of an SOS, in seconds (5 minutes).
+pub const SOS_EXPIRATION_SECS: i64 = 5 * 60;
+
+/// Maximum length of the optional SOS note, in characters.
+pub const MAX_SOS_MESSAGE_CHARS: usize = 280;
+
+/// The encrypted payload of an SOS broadcast.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct SosMessage {
+    /// The sender's exact position at the time of the alert.
+    #[serde(flatten)]
+    pub location: LocationMessage,
+
+    /// Optional short note ("fell on the trail, ankle hurt").
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
+}
+
+impl SosMessage {
+    /// Builds an SOS payload from a location fix and an optional note.
+    ///
+    /// The location's `expires_at` is shortened to
+    /// [`SOS_EXPIRATION_SECS`] after its `timestamp`, and the note is
+    /// sanitized (see [`sanitize_sos_message`]).
+    #[must_use]
+    pub fn new(location: &LocationMessage, message: Option<&str>) -> Self {
+        let mut location = location.clone();
+        location.expires_at = location.timestamp + Duration::seconds(SOS_EXPIRATION_SECS);
+        // Legacy field: new clients never populate it (see LocationMessage).
+        location.display_name = None;
+        Self {
+            location,
+            message: sanitize_sos_message(message),
+        }
+    }
+
+    /// Parses an SOS payload.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the JSON is not a valid SOS / location payload.
+    pub fn from_string(json: &str) -> Result<Self, serde_json::Error> {
+        let mut parsed: Self = serde_json::from_str(json)?;
+        parsed.message = sanitize_sos_message(parsed.message.as_deref());
+        Ok(parsed)
+    }
+
+    /// Serializes the payload.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if serialization fails (extremely rare).
+    pub fn to_string(&self) -> Result<String, serde_json::Error> {
+        serde_json::to_string(self)
+    }
+}
+
+impl std::fmt::Debug for SosMessage {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SosMessage")
+            .field("location", &self.location)
+            .field("message", &self.message.as_ref().map(|_| "<redacted>"))
+            .finish()
+    }
+}
+
+/// Trims the note, strips control characters other than newlines, and caps it
+/// at [`MAX_SOS_MESSAGE_CHARS`]. Returns `None` if nothing is left.
+#[must_use]
+pub fn sanitize_sos_message(message: Option<&str>) -> Option<String> {
+    let cleaned: String = message?
+        .trim()
+        .chars()
+        .filter(|c| *c == '\n' || !c.is_control())
+        .take(MAX_SOS_MESSAGE_CHARS)
+        .collect();
+    let cleaned = cleaned.trim_end().to_string();
+    (!cleaned.is_empty()).then_some(cleaned)
+}
+
+/// One circle an SOS was encrypted for, ready to publish.
+pub struct SosDelivery {
+    /// The circle's MLS group id (local handle, never published).
+    pub mls_group_id: GroupId,
+    /// The kind-445 event to publish.
+    pub event: Event,
+    /// The circle's pseudonymous `nostr_group_id`.
+    pub nostr_group_id: [u8; 32],
+    /// The circle's relays.
+    pub relays: Vec<String>,
+}
+
+impl std::fmt::Debug for SosDelivery {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SosDelivery")
+            .field("mls_group_id", &"<redacted>")
+            .field("event_id", &self.event.id)
+            .field("nostr_group_id", &hex::encode(self.nostr_group_id))
+            .field("relays", &self.relays)
+            .finish()
+    }
+}
+
+/// Result of fanning an SOS out to several circles.
+///
+/// Fan-out is best-effort: one broken circle must not stop the alert from
+/// reaching the others, so per-circle failures are reported here rather than
+/// aborting the whole send.
+#[derive(Debug, Default)]
+pub struct SosFanout {
+    /// Circles the SOS was encrypted for.
+    pub deliveries: Vec<SosDelivery>,
+    /// Circles the SOS could not be encrypted for.
+    pub failed: Vec<GroupId>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sos_message_shortens_expiration() {
+        let loc = LocationMessage::new(37.7749, -122.4194);
+        let sos = SosMessage::new(&loc, Some("help"));
+        assert_eq!(
+            (sos.location.expires_at - sos.location.timestamp).num_seconds(),
+            SOS_EXPIRATION_SECS
+        );
+        assert!(sos.location.expires_at < loc.expires_at);
+    }
+
+    #[test]
+    fn sos_message_keeps_exact_coordinates() {
+        let loc = LocationMessage::new(37.774_929_5, -122.419_415_5);
+        let sos = SosMessage::new(&loc, None);
+        assert_eq!(sos.location.latitude, 37.774_929_5);
+        assert_eq!(sos.location.longitude, -122.419_415_5);
+    }
+
+    #[test]
+    fn sos_message_roundtrip() {
+        let loc = LocationMessage::new(51.5, -0.12);
+        let sos = SosMessage::new(&loc, Some("  need a ride  "));
+        let json = sos.to_string().unwrap();
+        let parsed = SosMessage::from_string(&json).unwrap();
+        assert_eq!(parsed.message.as_deref(), Some("need a ride"));
+        assert!((parsed.location.latitude - 51.5).abs() < 1e-9);
+    }
+
+    #[test]
+    fn sos_payload_parses_as_plain_location() {
+        // Older receivers only know LocationMessage; the flattened layout
+        // keeps the position readable for them.
+        let sos = SosMessage::new(&LocationMessage::new(10.0, 20.0), Some("hi"));
+        let json = sos.to_string().unwrap();
+        let loc = LocationMessage::from_string(&json).expect("parses as location");
+        assert!((loc.latitude - 10.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn sos_message_omits_absent_note() {
+        let sos = SosMessage::new(&LocationMessage::new(1.0, 2.0), Some("   "));
+        assert!(sos.message.is_none());
+        assert!(!sos.to_string().unwrap().contains("message"));
+    }
+
+    #[test]
+    fn sanitize_sos_message_strips_controls_and_caps() {
+        assert_eq!(
+            sanitize_sos_message(Some("a\u{0}b\nc")).as_deref(),
+            Some("ab\nc")
+        );
+        let long = "x".repeat(MAX_SOS_MESSAGE_CHARS + 50);
+        assert_eq!(
+            sanitize_sos_message(Some(&long)).unwrap().chars().count(),
+            MAX_SOS_MESSAGE_CHARS
+        );
+        assert!(sanitize_sos_message(None).is_none());
+    }
+
+    #[test]
+    fn sos_message_debug_redacts_note() {
+        let sos = SosMessage::new(&LocationMessage::new(37.7749, -122.4194), Some("secret"));
+        let debug = format!("{sos:?}");
+        assert!(!debug.contains("secret"));
+        assert!(!debug.contains("37.7749"));
+        assert!(debug.contains("<redacted>"));
+    }
+}
@@ -0,0 +1,321 @@
+//! Deployment environment profiles (dev / staging / production).
+//!
+//! A profile bundles everything that differs between a developer build, a QA
+//! build pointed at test infrastructure, and the shipped app: the relays a new
+//! account is seeded with, the log verbosity, relay timing, and whether the
+//! local mock relay (plaintext `ws://` on loopback) may be used. The app picks
+//! one when it initializes [`HavenCore`](crate::HavenCore); nothing in Rust
+//! needs editing to point a QA build at staging relays.
+//!
+//! # Install-once
+//!
+//! A profile is installed at most once per process
+//! ([`install_environment`]). Until then, and in any process that never
+//! installs one, [`active_environment`] is the production profile, so library
+//! users and tests see production behaviour by default.
+//!
+//! # Release builds
+//!
+//! Two guards hold regardless of the profile:
+//!
+//! - The mock relay is never allowed: a profile with
+//!   [`EnvironmentProfile::allow_mock_relay`] set is rejected.
+//! - Log verbosity is capped at [`RELEASE_MAX_LOG_LEVEL`], so a staging QA
+//!   build cannot stream internal state (relay URLs, event ids) to a
+//!   world-readable log.
+
+use std::sync::{LazyLock, OnceLock};
+use std::time::Duration;
+
+use log::LevelFilter;
+
+use crate::circle::PRODUCTION_DEFAULT_RELAYS;
+use crate::relay::maintenance::KP_VERIFY_DELAY_SECS;
+use crate::relay::pool::POOL_IDLE_TIMEOUT;
+
+/// The most verbose log level a release build will accept.
+pub const RELEASE_MAX_LOG_LEVEL: LevelFilter = LevelFilter::Warn;
+
+/// The default local mock relay (Android emulator host loopback).
+pub const DEV_MOCK_RELAY: &str = "ws://10.0.2.2:7777";
+
+/// A deployment environment.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Environment {
+    /// A developer build, usually against a local mock relay.
+    Dev,
+    /// A QA build against staging relays.
+    Staging,
+    /// The shipped app.
+    #[default]
+    Production,
+}
+
+impl Environment {
+    /// Stable lowercase name.
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Dev => "dev",
+            Self::Staging => "staging",
+            Self::Production => "production",
+        }
+    }
+
+    /// Parses a name produced by [`Self::as_str`] (also accepts `prod`).
+    #[must_use]
+    pub fn parse(s: &str) -> Option<Self> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "dev" => Some(Self::Dev),
+            "staging" => Some(Self::Staging),
+            "production" | "prod" => Some(Self::Production),
+            _ => None,
+        }
+    }
+}
+
+/// Relay timing for a profile.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Timing {
+    /// Timeout for a single relay fetch or publish.
+    pub relay_timeout: Duration,
+    /// How long an unused relay connection stays open.
+    pub pool_idle_timeout: Duration,
+    /// Wait between publishing a `KeyPackage` and fetching it back.
+    pub kp_verify_delay: Duration,
+}
+
+impl Timing {
+    /// Production timing.
+    #[must_use]
+    pub const fn production() -> Self {
+        Self {
+            relay_timeout: Duration::from_secs(10),
+            pool_idle_timeout: POOL_IDLE_TIMEOUT,
+            kp_verify_delay: Duration::from_secs(KP_VERIFY_DELAY_SECS),
+        }
+    }
+
+    /// Timing for test infrastructure: short idle and verification windows so
+    /// QA sees reconnects and verification results quickly.
+    #[must_use]
+    pub const fn fast() -> Self {
+        Self {
+            relay_timeout: Duration::from_secs(10),
+            pool_idle_timeout: Duration::from_secs(60),
+            kp_verify_delay: Duration::from_secs(2),
+        }
+    }
+}
+
+/// Everything that differs between environments.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EnvironmentProfile {
+    /// Which environment this is.
+    pub environment: Environment,
+    /// Relays a new account is seeded with.
+    pub default_relays: Vec<String>,
+    /// Requested log verbosity (capped in release builds).
+    pub log_level: LevelFilter,
+    /// Relay timing.
+    pub timing: Timing,
+    /// Whether plaintext `ws://` loopback relays may be used.
+    pub allow_mock_relay: bool,
+}
+
+impl EnvironmentProfile {
+    /// The shipped-app profile.
+    #[must_use]
+    pub fn production() -> Self {
+        Self {
+            environment: Environment::Production,
+            default_relays: PRODUCTION_DEFAULT_RELAYS
+                .iter()
+                .map(|s| (*s).to_string())
+                .collect(),
+            log_level: RELEASE_MAX_LOG_LEVEL,
+            timing: Timing::production(),
+            allow_mock_relay: false,
+        }
+    }
+
+    /// A QA profile against the given staging relays (`wss://` only).
+    #[must_use]
+    pub const fn staging(relays: Vec<String>) -> Self {
+        Self {
+            environment: Environment::Staging,
+            default_relays: relays,
+            log_level: LevelFilter::Debug,
+            timing: Timing::fast(),
+            allow_mock_relay: false,
+        }
+    }
+
+    /// A developer profile against `relays`, or [`DEV_MOCK_RELAY`] if empty.
+    #[must_use]
+    pub fn dev(relays: Vec<String>) -> Self {
+        let default_relays = if relays.is_empty() {
+            vec![DEV_MOCK_RELAY.to_string()]
+        } else {
+            relays
+        };
+        Self {
+            environment: Environment::Dev,
+            default_relays,
+            log_level: LevelFilter::Trace,
+            timing: Timing::fast(),
+            allow_mock_relay: true,
+        }
+    }
+
+    /// The standard profile for `environment`. `relays` overrides the
+    /// default relays for dev and staging; production ignores it.
+    #[must_use]
+    pub fn for_environment(environment: Environment, relays: Vec<String>) -> Self {
+        match environment {
+            Environment::Dev => Self::dev(relays),
+            Environment::Staging => Self::staging(relays),
+            Environment::Production => Self::production(),
+        }
+    }
+
+    /// The log level this build will actually use.
+    #[must_use]
+    pub fn effective_log_level(&self) -> LevelFilter {
+        if cfg!(debug_assertions) {
+            self.log_level
+        } else {
+            self.log_level.min(RELEASE_MAX_LOG_LEVEL)
+        }
+    }
+
+    /// Checks the profile can be installed in this build.
+    ///
+    /// # Errors
+    ///
+    /// Returns a description of the first problem: no default relays, a
+    /// mock relay in a release build, or a non-`wss://` relay without the
+    /// mock relay allowed.
+    pub fn validate(&self) -> Result<(), String> {
+        if self.default_relays.is_empty() {
+            return Err(format!(
+                "{} profile has no default relays",
+                self.environment.as_str()
+            ));
+        }
+        if self.allow_mock_relay && !cfg!(debug_assertions) {
+            return Err("the mock relay is not allowed in release builds".to_string());
+        }
+        for relay in &self.default_relays {
+            let secure = relay.starts_with("wss://");
+            if !secure && !(self.allow_mock_relay && relay.starts_with("ws://")) {
+                return Err(format!(
+                    "{} profile relay must use wss://",
+                    self.environment.as_str()
+                ));
+            }
+        }
+        Ok(())
+    }
+}
+
+static ACTIVE: OnceLock<EnvironmentProfile> = OnceLock::new();
+
+static PRODUCTION: LazyLock<EnvironmentProfile> = LazyLock::new(EnvironmentProfile::production);
+
+/// Installs `profile` for this process: applies its log level and, for a
+/// profile that allows it, opens the mock-relay opt-in.
+///
+/// Installing the profile that is already active is a no-op, so a Flutter hot
+/// restart (which re-runs app initialization in the same process) succeeds.
+///
+/// # Errors
+///
+/// Returns an error if the profile is invalid for this build
+/// ([`EnvironmentProfile::validate`]) or a different profile is already
+/// installed.
+pub fn install_environment(profile: EnvironmentProfile) -> Result<(), String> {
+    profile.validate()?;
+    if let Some(existing) = ACTIVE.get() {
+        return if *existing == profile {
+            Ok(())
+        } else {
+            Err(format!(
+                "{} environment profile already installed",
+                existing.environment.as_str()
+            ))
+        };
+    }
+    let level = profile.effective_log_level();
+    let allow_mock_relay = profile.allow_mock_relay;
+    ACTIVE
+        .set(profile)
+        .map_err(|_existing| "environment profile already installed".to_string())?;
+    log::set_max_level(level);
+    if allow_mock_relay {
+        // Already-installed is fine: a harness may have opened it first.
+        let _ = crate::relay::allow_ws_loopback_for_test();
+    }
+    Ok(())
+}
+
+/// The installed profile, or production if none has been installed.
+#[must_use]
+pub fn active_environment() -> &'static EnvironmentProfile {
+    ACTIVE.get().unwrap_or(&PRODUCTION)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn environment_names_roundtrip() {
+        for env in [
+            Environment::Dev,
+            Environment::Staging,
+            Environment::Production,
+        ] {
+            assert_eq!(Environment::parse(env.as_str()), Some(env));
+        }
+        assert_eq!(Environment::parse(" PROD "), Some(Environment::Production));
+        assert_eq!(Environment::parse("qa"), None);
+    }
+
+    #[test]
+    fn production_is_the_default_and_valid() {
+        let prod = EnvironmentProfile::production();
+        assert!(prod.validate().is_ok());
+        assert!(!prod.allow_mock_relay);
+        assert_eq!(prod.default_relays.len(), PRODUCTION_DEFAULT_RELAYS.len());
+        assert_eq!(
+            EnvironmentProfile::for_environment(Environment::Production, vec!["x".into()]),
+            prod
+        );
+    }
+
+    #[test]
+    fn staging_requires_secure_relays() {
+        assert!(EnvironmentProfile::staging(vec![]).validate().is_err());
+        assert!(
+            EnvironmentProfile::staging(vec!["ws://staging.example.com".into()])
+                .validate()
+                .is_err()
+        );
+        assert!(
+            EnvironmentProfile::staging(vec!["wss://staging.example.com".into()])
+                .validate()
+                .is_ok()
+        );
+    }
+
+    #[test]
+    fn dev_defaults_to_the_mock_relay() {
+        let dev = EnvironmentProfile::dev(vec![]);
+        assert_eq!(dev.default_relays, [DEV_MOCK_RELAY]);
+        assert!(dev.allow_mock_relay);
+        // Test binaries are debug builds, so the mock relay is accepted.
+        assert!(dev.validate().is_ok());
+        assert_eq!(dev.effective_log_level(), LevelFilter::Trace);
+    }
+}
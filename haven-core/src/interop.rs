@@ -0,0 +1,243 @@
+//! Canonical test vectors for the Haven location payload.
+//!
+//! Another Marmot client that wants to render Haven locations needs the exact
+//! wire format: the JSON `content` of the inner rumor, the rumor's kind and
+//! tags, and how optional fields appear. This module publishes that as golden
+//! vectors ([`LOCATION_VECTORS`]) and a checker ([`verify_location_vector`])
+//! that Haven's own tests run, so any change to the format fails here first.
+//!
+//! # What a vector pins
+//!
+//! - **Parsing**: the content decodes, through
+//!   [`HavenPayload::decode`](crate::payload::HavenPayload::decode), to a
+//!   location with the listed field values and schema version.
+//! - **Encoding**: for a `canonical` vector, re-encoding yields the content
+//!   byte for byte (envelope, field order, timestamp format, omitted
+//!   optionals). Non-canonical vectors are inputs Haven must still read, such
+//!   as content from before the [`crate::payload`] envelope or a legacy
+//!   `display_name`, but never emits.
+//! - **Size**: `content_len`. Haven does not pad location content (see
+//!   `SECURITY.md`, predictable ciphertext length), so the content length
+//!   is what the MLS ciphertext length tracks; a change to it is visible on
+//!   the wire and must be deliberate.
+//! - **Rumor**: content is carried in a kind-[`RUMOR_KIND`] inner event with
+//!   exactly [`RUMOR_TAGS`], signed by nobody (a rumor), `pubkey` = sender.
+//!
+//! Receivers must ignore unknown keys: `deny_unknown_fields` is never used,
+//! so newer senders can add fields without breaking older readers.
+
+use nostr::PublicKey;
+
+use crate::location::{DeviceStatus, MotionState};
+use crate::nostr::mls::location_rumor;
+use crate::payload::{HavenPayload, LOCATION_TOPIC};
+
+/// Kind of the inner rumor carrying a location.
+pub const RUMOR_KIND: u16 = 9;
+
+/// Tags of the inner location rumor, in order.
+pub const RUMOR_TAGS: &[&[&str]] = &[&["t", "location"]];
+
+/// Sender used when checking the rumor shape (the secp256k1 generator's
+/// x coordinate: a valid key nobody holds).
+pub const VECTOR_SENDER_PUBKEY: &str =
+    "79be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798";
+
+/// One golden location payload.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LocationVector {
+    /// Short description.
+    pub name: &'static str,
+    /// The rumor content, exactly as sent.
+    pub content: &'static str,
+    /// Whether Haven emits exactly this encoding (else read-only).
+    pub canonical: bool,
+    /// Expected payload schema version (`0` for un-enveloped content).
+    pub schema_version: u32,
+    /// Length of `content` in bytes.
+    pub content_len: usize,
+    /// Expected latitude.
+    pub latitude: f64,
+    /// Expected longitude.
+    pub longitude: f64,
+    /// Expected geohash.
+    pub geohash: &'static str,
+    /// Expected capture time (Unix seconds).
+    pub timestamp: i64,
+    /// Expected freshness expiry (Unix seconds).
+    pub expires_at: i64,
+    /// Expected share expiry (Unix seconds), if any.
+    pub share_expires_at: Option<i64>,
+    /// Expected device status, if any.
+    pub device_status: Option<DeviceStatus>,
+}
+
+/// The published vectors.
+pub const LOCATION_VECTORS: &[LocationVector] = &[
+    LocationVector {
+        name: "exact location, defaults",
+        content: r#"{"v":1,"type":"location","latitude":57.64911,"longitude":10.40744,"geohash":"u4pruydq","timestamp":"2026-06-30T12:00:00Z","expires_at":"2026-06-30T12:15:00Z"}"#,
+        canonical: true,
+        schema_version: 1,
+        content_len: 158,
+        latitude: 57.649_11,
+        longitude: 10.407_44,
+        geohash: "u4pruydq",
+        timestamp: 1_782_820_800,
+        expires_at: 1_782_821_700,
+        share_expires_at: None,
+        device_status: None,
+    },
+    LocationVector {
+        name: "coarse location, 1-hour share expiration, device status",
+        content: r#"{"v":1,"type":"location","latitude":57.65,"longitude":10.41,"geohash":"u4pru","timestamp":"2026-06-30T12:00:00Z","expires_at":"2026-06-30T12:15:00Z","share_expires_at":"2026-06-30T13:00:00Z","device_status":{"battery_percent":12,"charging":false,"motion":"driving"}}"#,
+        canonical: true,
+        schema_version: 1,
+        content_len: 266,
+        latitude: 57.65,
+        longitude: 10.41,
+        geohash: "u4pru",
+        timestamp: 1_782_820_800,
+        expires_at: 1_782_821_700,
+        share_expires_at: Some(1_782_824_400),
+        device_status: Some(DeviceStatus {
+            battery_percent: Some(12),
+            charging: Some(false),
+            motion: Some(MotionState::Driving),
+        }),
+    },
+    LocationVector {
+        name: "legacy un-enveloped location (read-only)",
+        content: r#"{"latitude":57.64911,"longitude":10.40744,"geohash":"u4pruydq","timestamp":"2026-06-30T12:00:00Z","expires_at":"2026-06-30T12:15:00Z"}"#,
+        canonical: false,
+        schema_version: 0,
+        content_len: 134,
+        latitude: 57.649_11,
+        longitude: 10.407_44,
+        geohash: "u4pruydq",
+        timestamp: 1_782_820_800,
+        expires_at: 1_782_821_700,
+        share_expires_at: None,
+        device_status: None,
+    },
+    LocationVector {
+        name: "legacy sender with display_name (read-only)",
+        content: r#"{"latitude":57.64911,"longitude":10.40744,"geohash":"u4pruydq","timestamp":"2026-06-30T12:00:00Z","expires_at":"2026-06-30T12:15:00Z","display_name":"Alice"}"#,
+        canonical: false,
+        schema_version: 0,
+        content_len: 157,
+        latitude: 57.649_11,
+        longitude: 10.407_44,
+        geohash: "u4pruydq",
+        timestamp: 1_782_820_800,
+        expires_at: 1_782_821_700,
+        share_expires_at: None,
+        device_status: None,
+    },
+];
+
+/// Checks this build against one vector.
+///
+/// # Errors
+///
+/// Returns a description of the first mismatch.
+pub fn verify_location_vector(vector: &LocationVector) -> Result<(), String> {
+    let name = vector.name;
+    if vector.content.len() != vector.content_len {
+        return Err(format!(
+            "{name}: content is {} bytes, expected {}",
+            vector.content.len(),
+            vector.content_len
+        ));
+    }
+
+    let decoded = HavenPayload::decode(vector.content, Some(LOCATION_TOPIC))
+        .map_err(|e| format!("{name}: content does not parse: {e}"))?;
+    if decoded.schema_version != vector.schema_version {
+        return Err(format!(
+            "{name}: schema version is {}, expected {}",
+            decoded.schema_version, vector.schema_version
+        ));
+    }
+    let HavenPayload::Location(parsed) = decoded.payload else {
+        return Err(format!("{name}: content is not a location"));
+    };
+    // Bit equality: the decimal text must decode to exactly this f64.
+    if parsed.latitude.to_bits() != vector.latitude.to_bits()
+        || parsed.longitude.to_bits() != vector.longitude.to_bits()
+    {
+        return Err(format!("{name}: coordinates differ"));
+    }
+    if parsed.geohash != vector.geohash {
+        return Err(format!("{name}: geohash differs"));
+    }
+    if parsed.timestamp.timestamp() != vector.timestamp
+        || parsed.expires_at.timestamp() != vector.expires_at
+        || parsed.share_expires_at.map(|t| t.timestamp()) != vector.share_expires_at
+    {
+        return Err(format!("{name}: timestamps differ"));
+    }
+    if parsed.device_status != vector.device_status {
+        return Err(format!("{name}: device status differs"));
+    }
+
+    if vector.canonical {
+        let encoded = HavenPayload::Location(parsed)
+            .encode()
+            .map_err(|e| format!("{name}: re-encoding failed: {e}"))?;
+        if encoded != vector.content {
+            return Err(format!("{name}: re-encoded as {encoded}"));
+        }
+    }
+
+    let sender = PublicKey::from_hex(VECTOR_SENDER_PUBKEY)
+        .map_err(|e| format!("invalid vector sender: {e}"))?;
+    let rumor = location_rumor(sender, vector.content.to_string());
+    let tags_match = rumor.tags.len() == RUMOR_TAGS.len()
+        && rumor.tags.iter().zip(RUMOR_TAGS).all(|(tag, expected)| {
+            tag.as_slice()
+                .iter()
+                .map(String::as_str)
+                .eq(expected.iter().copied())
+        });
+    if rumor.kind.as_u16() != RUMOR_KIND
+        || !tags_match
+        || rumor.content != vector.content
+        || rumor.pubkey != sender
+    {
+        return Err(format!("{name}: inner rumor shape differs"));
+    }
+    Ok(())
+}
+
+/// Checks this build against every vector in [`LOCATION_VECTORS`].
+///
+/// # Errors
+///
+/// Returns the first mismatch.
+pub fn verify_all() -> Result<(), String> {
+    LOCATION_VECTORS.iter().try_for_each(verify_location_vector)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn all_vectors_verify() {
+        verify_all().unwrap();
+    }
+
+    #[test]
+    fn tampered_vector_is_rejected() {
+        let mut vector = LOCATION_VECTORS[0];
+        vector.geohash = "u4pruydr";
+        assert!(verify_location_vector(&vector)
+            .unwrap_err()
+            .contains("geohash"));
+
+        let mut vector = LOCATION_VECTORS[0];
+        vector.content_len += 1;
+        assert!(verify_location_vector(&vector).is_err());
+    }
+}
@@ -18,14 +18,31 @@ compile_error!(
 
 mod api;
 pub mod avatar;
+pub mod checkin;
 pub mod circle;
+pub mod config;
+pub mod device_link;
+pub mod device_transfer;
+pub mod diagnostics;
+pub mod emergency;
+pub mod environment;
+pub mod interop;
 pub mod keyring_policy;
 pub mod location;
+pub mod meet;
+pub mod messages;
 pub mod nostr;
+pub mod one_shot;
+pub mod payload;
+pub mod privacy;
 pub mod profile;
 pub mod relay;
+pub mod safety;
+pub mod self_test;
 pub mod tiles;
 pub mod util;
 pub mod validation;
 
 pub use api::HavenCore;
+pub use config::HavenConfig;
+pub use environment::{Environment, EnvironmentProfile};
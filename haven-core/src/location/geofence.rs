@@ -0,0 +1,176 @@
+//! Circular geofences and great-circle distance.
+//!
+//! Geofences are defined locally on the device (e.g. "Home", "School") and are
+//! never transmitted. They are evaluated against decrypted member locations to
+//! produce aggregate circle summaries without exposing raw coordinates to the
+//! caller.
+
+/// Mean Earth radius in meters (IUGG).
+pub const EARTH_RADIUS_M: f64 = 6_371_008.8;
+
+/// Largest accepted geofence radius (50 km).
+///
+/// Anything larger stops being a "place" and turns every member into an
+/// occupant, which defeats the point of the summary.
+pub const MAX_GEOFENCE_RADIUS_M: f64 = 50_000.0;
+
+/// Returns the great-circle distance between two points in meters, using the
+/// haversine formula.
+///
+/// # Examples
+///
+/// ```
+/// use haven_core::location::geofence::haversine_distance_m;
+///
+/// // San Francisco → Oakland, roughly 13 km.
+/// let d = haversine_distance_m(37.7749, -122.4194, 37.8044, -122.2712);
+/// assert!((12_000.0..14_000.0).contains(&d));
+/// ```
+#[must_use]
+pub fn haversine_distance_m(lat1: f64, lon1: f64, lat2: f64, lon2: f64) -> f64 {
+    let phi1 = lat1.to_radians();
+    let phi2 = lat2.to_radians();
+    let d_phi = (lat2 - lat1).to_radians();
+    let d_lambda = (lon2 - lon1).to_radians();
+
+    let a = (d_phi / 2.0).sin().powi(2) + phi1.cos() * phi2.cos() * (d_lambda / 2.0).sin().powi(2);
+    // Clamp guards against `a` drifting a hair above 1.0 for antipodal points.
+    let c = 2.0 * a.sqrt().min(1.0).asin();
+    EARTH_RADIUS_M * c
+}
+
+/// A named circular region on the map.
+#[derive(Clone, PartialEq)]
+pub struct Geofence {
+    /// User-facing label (e.g. "Home").
+    pub label: String,
+    /// Center latitude in degrees.
+    pub latitude: f64,
+    /// Center longitude in degrees.
+    pub longitude: f64,
+    /// Radius in meters.
+    pub radius_m: f64,
+}
+
+impl Geofence {
+    /// Creates a new geofence.
+    #[must_use]
+    pub fn new(label: impl Into<String>, latitude: f64, longitude: f64, radius_m: f64) -> Self {
+        Self {
+            label: label.into(),
+            latitude,
+            longitude,
+            radius_m,
+        }
+    }
+
+    /// Checks that the center is a valid coordinate and the radius is a
+    /// positive, finite value no larger than [`MAX_GEOFENCE_RADIUS_M`].
+    ///
+    /// # Errors
+    ///
+    /// Returns a description of the first invalid field.
+    pub fn validate(&self) -> Result<(), String> {
+        if !self.latitude.is_finite() || !(-90.0..=90.0).contains(&self.latitude) {
+            return Err("Geofence latitude must be between -90 and 90".to_string());
+        }
+        if !self.longitude.is_finite() || !(-180.0..=180.0).contains(&self.longitude) {
+            return Err("Geofence longitude must be between -180 and 180".to_string());
+        }
+        if !self.radius_m.is_finite()
+            || self.radius_m <= 0.0
+            || self.radius_m > MAX_GEOFENCE_RADIUS_M
+        {
+            return Err(format!(
+                "Geofence radius must be greater than 0 and at most {MAX_GEOFENCE_RADIUS_M} m"
+            ));
+        }
+        Ok(())
+    }
+
+    /// Returns `true` if the point lies inside (or on the edge of) the fence.
+    #[must_use]
+    pub fn contains(&self, latitude: f64, longitude: f64) -> bool {
+        haversine_distance_m(self.latitude, self.longitude, latitude, longitude) <= self.radius_m
+    }
+}
+
+impl std::fmt::Debug for Geofence {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Geofence")
+            .field("label", &self.label)
+            .field("latitude", &"<redacted>")
+            .field("longitude", &"<redacted>")
+            .field("radius_m", &self.radius_m)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn haversine_zero_for_same_point() {
+        assert!(haversine_distance_m(51.5, -0.12, 51.5, -0.12).abs() < 1e-6);
+    }
+
+    #[test]
+    fn haversine_one_degree_latitude() {
+        // One degree of latitude is ~111.2 km everywhere.
+        let d = haversine_distance_m(0.0, 0.0, 1.0, 0.0);
+        assert!((d - 111_195.0).abs() < 50.0, "got {d}");
+    }
+
+    #[test]
+    fn haversine_is_symmetric() {
+        let a = haversine_distance_m(40.7128, -74.0060, 34.0522, -118.2437);
+        let b = haversine_distance_m(34.0522, -118.2437, 40.7128, -74.0060);
+        assert!((a - b).abs() < 1e-6);
+        // NYC → LA is ~3936 km.
+        assert!((3_900_000.0..3_970_000.0).contains(&a), "got {a}");
+    }
+
+    #[test]
+    fn haversine_antipodal_does_not_nan() {
+        let d = haversine_distance_m(0.0, 0.0, 0.0, 180.0);
+        assert!(d.is_finite());
+        assert!((d - std::f64::consts::PI * EARTH_RADIUS_M).abs() < 1.0);
+    }
+
+    #[test]
+    fn geofence_contains_inside_and_outside() {
+        let home = Geofence::new("Home", 37.7749, -122.4194, 100.0);
+        assert!(home.contains(37.7749, -122.4194));
+        // ~55 m north.
+        assert!(home.contains(37.7754, -122.4194));
+        // ~1.1 km north.
+        assert!(!home.contains(37.7849, -122.4194));
+    }
+
+    #[test]
+    fn geofence_validate_rejects_bad_values() {
+        assert!(Geofence::new("ok", 10.0, 10.0, 150.0).validate().is_ok());
+        assert!(Geofence::new("lat", 91.0, 10.0, 150.0).validate().is_err());
+        assert!(Geofence::new("lon", 10.0, -181.0, 150.0)
+            .validate()
+            .is_err());
+        assert!(Geofence::new("nan", f64::NAN, 10.0, 150.0)
+            .validate()
+            .is_err());
+        assert!(Geofence::new("zero", 10.0, 10.0, 0.0).validate().is_err());
+        assert!(Geofence::new("huge", 10.0, 10.0, 50_001.0)
+            .validate()
+            .is_err());
+    }
+
+    #[test]
+    fn geofence_debug_redacts_center() {
+        let fence = Geofence::new("Home", 37.7749, -122.4194, 100.0);
+        let debug = format!("{fence:?}");
+        assert!(debug.contains("Home"));
+        assert!(debug.contains("<redacted>"));
+        assert!(!debug.contains("37.7749"));
+        assert!(!debug.contains("122.4194"));
+    }
+}
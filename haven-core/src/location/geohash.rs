@@ -3,6 +3,12 @@
 //! Geohash is a geocoding system that encodes geographic coordinates into a
 //! short string. Each additional character provides ~5x more precision.
 
+use super::geofence::EARTH_RADIUS_M;
+use crate::messages::{MessageCode, UserMessage};
+
+/// Longest geohash the cell-size helpers accept.
+const MAX_GEOHASH_LEN: u8 = 12;
+
 /// Converts latitude/longitude to a geohash string.
 ///
 /// # Arguments
@@ -56,10 +62,239 @@ pub fn geohash_to_location(geohash: &str) -> (f64, f64) {
     geohash::decode(geohash).map_or((0.0, 0.0), |(coord, _, _)| (coord.y, coord.x))
 }
 
+/// The bounding box of a geohash cell, in degrees.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GeohashBounds {
+    /// Southern edge.
+    pub min_lat: f64,
+    /// Western edge.
+    pub min_lon: f64,
+    /// Northern edge.
+    pub max_lat: f64,
+    /// Eastern edge.
+    pub max_lon: f64,
+}
+
+/// Returns the bounding box of the cell `geohash` names.
+///
+/// Returns `None` if the geohash is empty or invalid.
+#[must_use]
+pub fn geohash_bounds(geohash: &str) -> Option<GeohashBounds> {
+    if geohash.is_empty() {
+        return None;
+    }
+    let rect = geohash::decode_bbox(geohash).ok()?;
+    Some(GeohashBounds {
+        min_lat: rect.min().y,
+        min_lon: rect.min().x,
+        max_lat: rect.max().y,
+        max_lon: rect.max().x,
+    })
+}
+
+/// The ground size of a geohash cell, in meters.
+///
+/// A cell spans a fixed number of degrees, so its east-west width shrinks
+/// with the cosine of the latitude: a 5-character cell is ~4.9 km wide at the
+/// equator but ~2.4 km at 60°N. Height is the same everywhere.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CellSize {
+    /// East-west extent at the latitude it was computed for.
+    pub width_m: f64,
+    /// North-south extent.
+    pub height_m: f64,
+}
+
+impl CellSize {
+    /// A [`MessageCode::LocationCellArea`] message. Its `width` and `height`
+    /// parameters are meters rounded to two significant figures and written
+    /// as plain numbers ("2400", "4.8"), so the app formats them for the
+    /// locale and picks the unit from the already-rounded value.
+    #[must_use]
+    pub fn user_message(&self) -> UserMessage {
+        UserMessage::new(MessageCode::LocationCellArea)
+            .with("width", round_distance(self.width_m).to_string())
+            .with("height", round_distance(self.height_m).to_string())
+    }
+}
+
+/// The ground size of a geohash cell of `len` characters at `lat`.
+///
+/// `len` is clamped to 1..=12 and `lat` to ±90° (a non-finite latitude is
+/// treated as the equator).
+#[must_use]
+pub fn geohash_cell_size(len: u8, lat: f64) -> CellSize {
+    let (lat_span, lon_span) = cell_spans(len);
+
+    let lat = if lat.is_finite() {
+        lat.clamp(-90.0, 90.0)
+    } else {
+        0.0
+    };
+    let meters_per_degree = EARTH_RADIUS_M.to_radians();
+    CellSize {
+        width_m: lon_span * meters_per_degree * lat.to_radians().cos().max(0.0),
+        height_m: lat_span * meters_per_degree,
+    }
+}
+
+/// The height and width in degrees of a geohash cell of `len` characters
+/// (clamped to 1..=12).
+fn cell_spans(len: u8) -> (f64, f64) {
+    let bits = u32::from(len.clamp(1, MAX_GEOHASH_LEN)) * 5;
+    // Bits alternate starting with longitude, which gets the odd one out.
+    let lon_bits = bits.div_ceil(2);
+    let lat_bits = bits / 2;
+    (
+        180.0 / f64::from(1u32 << lat_bits),
+        360.0 / f64::from(1u32 << lon_bits),
+    )
+}
+
+/// The geohash cells covering `bounds`, all of one length: the longest
+/// length up to `max_len` that needs at most `max_cells` cells, or the
+/// single-character cells when even those are too many.
+///
+/// A point inside `bounds` has a geohash starting with one of the returned
+/// cells, so a prefix lookup on them finds every point in the box (and some
+/// just outside it). A box with `min_lon > max_lon` crosses the
+/// antimeridian and is covered in two parts.
+///
+/// Returns an empty list for a box with a non-finite or out-of-range edge,
+/// `min_lat > max_lat`, or a zero `max_cells`.
+#[must_use]
+pub fn covering_geohashes(bounds: &GeohashBounds, max_cells: usize, max_len: u8) -> Vec<String> {
+    let valid_lat = |v: f64| v.is_finite() && (-90.0..=90.0).contains(&v);
+    let valid_lon = |v: f64| v.is_finite() && (-180.0..=180.0).contains(&v);
+    if !(valid_lat(bounds.min_lat)
+        && valid_lat(bounds.max_lat)
+        && valid_lon(bounds.min_lon)
+        && valid_lon(bounds.max_lon))
+        || bounds.min_lat > bounds.max_lat
+        || max_cells == 0
+    {
+        return Vec::new();
+    }
+    let lon_ranges = if bounds.min_lon <= bounds.max_lon {
+        vec![(bounds.min_lon, bounds.max_lon)]
+    } else {
+        vec![(bounds.min_lon, 180.0), (-180.0, bounds.max_lon)]
+    };
+    (1..=max_len.clamp(1, MAX_GEOHASH_LEN))
+        .rev()
+        .find_map(|len| cells_in(bounds, &lon_ranges, len, Some(max_cells)))
+        .or_else(|| cells_in(bounds, &lon_ranges, 1, None))
+        .unwrap_or_default()
+}
+
+/// The `len`-character cells overlapping `bounds`' latitudes and
+/// `lon_ranges`, or `None` once there are more than `limit`.
+fn cells_in(
+    bounds: &GeohashBounds,
+    lon_ranges: &[(f64, f64)],
+    len: u8,
+    limit: Option<usize>,
+) -> Option<Vec<String>> {
+    let (lat_span, lon_span) = cell_spans(len);
+    let snap =
+        |value: f64, origin: f64, span: f64| origin + ((value - origin) / span).floor() * span;
+    let mut cells = Vec::new();
+    let first_lat = snap(bounds.min_lat, -90.0, lat_span);
+    let mut row = 0u32;
+    loop {
+        let lat = first_lat + f64::from(row) * lat_span;
+        if lat > bounds.max_lat || lat >= 90.0 {
+            break;
+        }
+        for &(west, east) in lon_ranges {
+            let first_lon = snap(west, -180.0, lon_span);
+            let mut column = 0u32;
+            loop {
+                let lon = first_lon + f64::from(column) * lon_span;
+                if lon > east || lon >= 180.0 {
+                    break;
+                }
+                cells.push(location_to_geohash(
+                    lat + lat_span / 2.0,
+                    lon + lon_span / 2.0,
+                    len,
+                ));
+                if limit.is_some_and(|limit| cells.len() > limit) {
+                    return None;
+                }
+                column += 1;
+            }
+        }
+        row += 1;
+    }
+    cells.sort();
+    cells.dedup();
+    Some(cells)
+}
+
+/// Rounds a distance in meters to two significant figures (4.77 → 4.8,
+/// 996 → 1000, 39 135 → 39 000).
+fn round_distance(m: f64) -> f64 {
+    if !m.is_finite() || m <= 0.0 {
+        return 0.0;
+    }
+    // Scale into 10..100, round, and scale back by the same power of ten.
+    let (mut digits, mut exp) = (m, 0);
+    while digits >= 100.0 {
+        digits /= 10.0;
+        exp += 1;
+    }
+    while digits < 10.0 {
+        digits *= 10.0;
+        exp -= 1;
+    }
+    if exp >= 0 {
+        digits.round() * 10f64.powi(exp)
+    } else {
+        digits.round() / 10f64.powi(-exp)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn cell_size_narrows_with_latitude() {
+        let equator = geohash_cell_size(5, 0.0);
+        assert!((equator.width_m - 4_886.0).abs() < 5.0);
+        assert!((equator.height_m - 4_886.0).abs() < 5.0);
+
+        let north = geohash_cell_size(5, 60.0);
+        assert!((north.width_m - equator.width_m / 2.0).abs() < 1.0);
+        assert!((north.height_m - equator.height_m).abs() < f64::EPSILON);
+
+        let six = geohash_cell_size(6, 0.0);
+        assert!((six.width_m / six.height_m - 2.0).abs() < 1e-9);
+
+        assert!(geohash_cell_size(5, 90.0).width_m < 1e-6);
+        assert_eq!(geohash_cell_size(5, f64::NAN), equator);
+    }
+
+    #[test]
+    fn cell_size_message_reads_naturally() {
+        assert_eq!(
+            geohash_cell_size(5, 60.0).user_message().english(),
+            "~2400 m × 4900 m area"
+        );
+        assert_eq!(
+            geohash_cell_size(7, 0.0).user_message().english(),
+            "~150 m × 150 m area"
+        );
+        assert_eq!(round_distance(4.77).to_string(), "4.8");
+        assert_eq!(round_distance(39_135.0).to_string(), "39000");
+        // Rounding happens before any unit is chosen, so values just under
+        // a boundary land on it rather than reading "1000 m" next to "1.0 km".
+        assert_eq!(round_distance(996.0).to_string(), "1000");
+        assert_eq!(round_distance(9_960.0).to_string(), "10000");
+        assert_eq!(round_distance(0.0).to_string(), "0");
+    }
+
     #[test]
     fn geohash_length_matches_precision() {
         let geohash = location_to_geohash(37.7749, -122.4194, 8);
@@ -102,6 +337,73 @@ mod tests {
         assert_eq!(lon, 0.0);
     }
 
+    #[test]
+    fn bounds_contain_the_encoded_point() {
+        let geohash = location_to_geohash(37.7749, -122.4194, 5);
+        let b = geohash_bounds(&geohash).unwrap();
+        assert!(b.min_lat <= 37.7749 && 37.7749 <= b.max_lat);
+        assert!(b.min_lon <= -122.4194 && -122.4194 <= b.max_lon);
+        assert!(geohash_bounds("").is_none());
+        assert!(geohash_bounds("not-a-geohash").is_none());
+    }
+
+    #[test]
+    fn covering_cells_contain_every_point_of_the_box() {
+        let viewport = GeohashBounds {
+            min_lat: 37.70,
+            min_lon: -122.52,
+            max_lat: 37.83,
+            max_lon: -122.35,
+        };
+        let cells = covering_geohashes(&viewport, 16, 8);
+        assert!(!cells.is_empty() && cells.len() <= 16);
+        let len = cells[0].len();
+        assert!(cells.iter().all(|c| c.len() == len));
+        assert!(covering_geohashes(&viewport, 64, 8)[0].len() >= len);
+
+        for (lat, lon) in [(37.70, -122.52), (37.83, -122.35), (37.7749, -122.4194)] {
+            let geohash = location_to_geohash(lat, lon, 8);
+            assert!(cells.iter().any(|c| geohash.starts_with(c.as_str())));
+        }
+    }
+
+    #[test]
+    fn covering_handles_the_antimeridian_and_bad_boxes() {
+        let pacific = GeohashBounds {
+            min_lat: -20.0,
+            min_lon: 175.0,
+            max_lat: -15.0,
+            max_lon: -175.0,
+        };
+        let cells = covering_geohashes(&pacific, 16, 8);
+        for lon in [178.0, -178.0] {
+            let geohash = location_to_geohash(-17.0, lon, 8);
+            assert!(cells.iter().any(|c| geohash.starts_with(c.as_str())));
+        }
+        let west_only = location_to_geohash(-17.0, 0.0, 8);
+        assert!(!cells.iter().any(|c| west_only.starts_with(c.as_str())));
+
+        let world = GeohashBounds {
+            min_lat: -90.0,
+            min_lon: -180.0,
+            max_lat: 90.0,
+            max_lon: 180.0,
+        };
+        assert_eq!(covering_geohashes(&world, 4, 8).len(), 32);
+
+        let inverted = GeohashBounds {
+            min_lat: 10.0,
+            max_lat: 5.0,
+            ..world
+        };
+        assert!(covering_geohashes(&inverted, 16, 8).is_empty());
+        let nan = GeohashBounds {
+            min_lat: f64::NAN,
+            ..world
+        };
+        assert!(covering_geohashes(&nan, 16, 8).is_empty());
+    }
+
     #[test]
     fn invalid_geohash_returns_zero() {
         let (lat, lon) = geohash_to_location("not-a-geohash");
@@ -21,13 +21,30 @@
 //! let _ = json;
 //! ```
 
+pub mod geofence;
 pub mod geohash;
 pub mod nostr;
+pub mod precision;
+pub mod privacy;
+pub mod proximity;
+pub mod throttle;
+pub mod trips;
 pub(crate) mod ttl;
 pub mod types;
 
-pub use geohash::{geohash_to_location, location_to_geohash};
+pub use geofence::{haversine_distance_m, Geofence};
+pub use geohash::{
+    covering_geohashes, geohash_bounds, geohash_cell_size, geohash_to_location,
+    location_to_geohash, CellSize, GeohashBounds,
+};
+pub use precision::{LocationPrecision, PrecisionAnomaly, PrecisionClass, WirePrecision};
+pub use privacy::{DwellPolicy, Fix, LocationHistory, PrecisionPolicy};
+pub use proximity::{ProximityAlert, ProximityEstimate, ProximityRule, ProximityTarget};
+pub use throttle::{PublishThrottle, ThrottleDecision};
+pub use trips::{DaySummary, Trip};
 pub use ttl::{compute_jittered_publish_interval_secs, PUBLISH_INTERVAL_JITTER_FRACTION_BP};
 pub use types::{
-    LocationMessage, LocationSettings, LOCATION_FRESHNESS_TTL_SECS, LOCATION_RETENTION_SECS,
+    DeviceStatus, LocationMessage, LocationSettings, MotionState, ShareExpiration,
+    VerticalPosition, LOCATION_FRESHNESS_TTL_SECS, LOCATION_RETENTION_SECS,
+    MIN_SHARE_EXPIRATION_SECS,
 };
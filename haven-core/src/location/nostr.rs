@@ -32,6 +32,7 @@ use super::types::LocationMessage;
 use crate::nostr::mls::types::LocationMessageResult;
 use crate::nostr::mls::SessionManager;
 use crate::nostr::{MlsGroupContext, NostrError, Result};
+use crate::payload::HavenPayload;
 use cgka_session::SessionEffects;
 
 /// Event kind for application messages (inner event).
@@ -111,8 +112,8 @@ impl LocationEventBuilder {
         group: &MlsGroupContext,
         sender_pubkey: &PublicKey,
     ) -> Result<SessionEffects> {
-        // Step 1: Serialize location to string
-        let content = location.to_string()?;
+        // Step 1: Serialize location into the typed payload envelope
+        let content = HavenPayload::Location(location.clone()).encode()?;
 
         // Step 2: Create unsigned event (rumor) with location data
         // Tag ["t", "location"] distinguishes location messages from chat per MIP-03
@@ -163,11 +164,16 @@ impl LocationEventBuilder {
         // message carries the inner content. Commits/proposals/state changes
         // emit no `Location`, and stale/buffered outcomes emit no event at all.
         for group_event in &ingest.effects.events {
-            if let Some(LocationMessageResult::Location { content, .. }) =
+            if let Some(result @ LocationMessageResult::Location { .. }) =
                 SessionManager::location_result_from_event(group_event)
             {
-                // Step 3: Deserialize location
-                return LocationMessage::from_string(&content).map_err(NostrError::from);
+                // Step 3: Decode the payload (enveloped or legacy)
+                return match result.payload() {
+                    Some(HavenPayload::Location(location)) => Ok(location),
+                    _ => Err(NostrError::InvalidEvent(
+                        "application message is not a location".to_string(),
+                    )),
+                };
             }
         }
 
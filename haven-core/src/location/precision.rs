@@ -0,0 +1,561 @@
+//! Location precision: receive-side baselines, downgrade detection, and send-side rounding.
+//!
+//! Haven shares exact GPS by default, but a sender may share coarsely (rounded
+//! coordinates). If someone who has been sharing coarse positions suddenly
+//! starts sending exact ones, that is worth a look: their device may be
+//! compromised, or a setting was flipped by mistake. Either way the people
+//! receiving the new precision should know, so they can check in.
+//!
+//! The receiver keeps one [`PrecisionBaseline`] per (circle, sender). Each
+//! decrypted location is [`classify`]-ed and fed to [`observe`]; once a sender
+//! has shared at least [`BASELINE_MIN_SAMPLES`] locations at one precision, a
+//! *finer* location is reported as a [`PrecisionAnomaly`]. The baseline then
+//! moves to the new precision, so a deliberate change is flagged exactly once.
+//! Getting coarser is never an anomaly.
+//!
+//! # Sending coarsely
+//!
+//! The same scale is the sender's choice: [`LocationPrecision`] is a
+//! [`PrecisionClass`], and [`reduce_to`] rounds an outgoing location so that
+//! receivers classify it as exactly that class. The precision is chosen per
+//! circle (see `CircleManager::set_circle_location_settings`), falling back to
+//! [`LocationSettings::precision`](super::LocationSettings::precision).
+//!
+//! # Jitter
+//!
+//! Rounding snaps every position to the same grid, so someone who lives near
+//! a grid line flips between two points and gives away roughly where the
+//! line runs through their home. With
+//! [`LocationSettings::coordinate_jitter`](super::LocationSettings::coordinate_jitter)
+//! on, [`jitter`] also moves a rounded location by up to
+//! [`JITTER_MAX_STEPS`] grid steps on each axis. The shift is keyed by a
+//! secret seed kept on the device, the rounded position and the
+//! [`JITTER_EPOCH_SECS`] epoch, so repeated shares from one spot land on the
+//! same point for the whole epoch and cannot be averaged back to the grid
+//! cell. The result stays on the grid, so receivers classify it at the same
+//! precision.
+//!
+//! # What is measured
+//!
+//! Only what the receiver actually sees: the number of decimal places in the
+//! coordinates. The payload's geohash is ignored — a sender could pair exact
+//! coordinates with a short geohash, and the coordinates are what gets
+//! displayed.
+//!
+//! # On the wire
+//!
+//! A sender also declares the precision it shared at, as a
+//! [`WirePrecision`] (see [`LocationMessage::precision`]). The declaration is
+//! informational — the classified coordinates stay authoritative — and it is
+//! decoded leniently: a class this build does not know (from a newer sender)
+//! becomes [`WirePrecision::Unknown`] rather than failing the whole message.
+
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use super::geohash::{geohash_cell_size, CellSize};
+use super::types::LocationMessage;
+
+/// Locations at one precision needed before a finer one is flagged.
+///
+/// Below this the sender has no established habit yet, so a change is just
+/// their first few shares settling.
+pub const BASELINE_MIN_SAMPLES: u32 = 3;
+
+/// How long a jittered spot keeps the same shift (1 day).
+pub const JITTER_EPOCH_SECS: i64 = 24 * 60 * 60;
+
+/// Most grid steps [`jitter`] moves a coordinate, on each axis.
+pub const JITTER_MAX_STEPS: u8 = 1;
+
+/// Domain separator for the jitter hash.
+const JITTER_DOMAIN: &[u8] = b"haven-coordinate-jitter-v1";
+
+/// How precise a received location is, coarsest first.
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default, Serialize, Deserialize,
+)]
+#[serde(rename_all = "lowercase")]
+pub enum PrecisionClass {
+    /// At most 2 decimal places (~1 km or coarser).
+    Coarse,
+    /// 3–4 decimal places (~10–100 m).
+    Approximate,
+    /// 5 or more decimal places (~1 m): a raw GPS fix.
+    #[default]
+    Exact,
+}
+
+impl PrecisionClass {
+    /// Converts to string representation for storage.
+    #[must_use]
+    pub const fn as_str(&self) -> &'static str {
+        match self {
+            Self::Coarse => "coarse",
+            Self::Approximate => "approximate",
+            Self::Exact => "exact",
+        }
+    }
+
+    /// Parses from string representation.
+    #[must_use]
+    pub fn parse(s: &str) -> Option<Self> {
+        match s {
+            "coarse" => Some(Self::Coarse),
+            "approximate" => Some(Self::Approximate),
+            "exact" => Some(Self::Exact),
+            _ => None,
+        }
+    }
+}
+
+impl PrecisionClass {
+    /// Stable integer code (`0` coarse, `1` approximate, `2` exact). Never
+    /// renumbered; a new class takes the next free code.
+    #[must_use]
+    pub const fn code(self) -> u8 {
+        match self {
+            Self::Coarse => 0,
+            Self::Approximate => 1,
+            Self::Exact => 2,
+        }
+    }
+
+    /// Parses a [`Self::code`].
+    #[must_use]
+    pub const fn from_code(code: u64) -> Option<Self> {
+        match code {
+            0 => Some(Self::Coarse),
+            1 => Some(Self::Approximate),
+            2 => Some(Self::Exact),
+            _ => None,
+        }
+    }
+}
+
+impl std::fmt::Display for PrecisionClass {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// The precision a sender shares at. The same scale receivers [`classify`]
+/// by, so what is sent is what is seen.
+pub type LocationPrecision = PrecisionClass;
+
+/// A precision as declared on the wire, possibly by a newer sender.
+///
+/// Written as the stable lowercase name ([`PrecisionClass::as_str`]). Read
+/// from a name or a [`PrecisionClass::code`]; anything else is kept verbatim
+/// in [`Self::Unknown`], so decoding never fails.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum WirePrecision {
+    /// A class this build knows.
+    Known(PrecisionClass),
+    /// A value this build does not know, as received.
+    Unknown(String),
+}
+
+impl WirePrecision {
+    /// Parses a precision name, keeping unknown ones.
+    #[must_use]
+    pub fn parse(s: &str) -> Self {
+        PrecisionClass::parse(s).map_or_else(|| Self::Unknown(s.to_string()), Self::Known)
+    }
+
+    /// The stable name, or the original value if unknown.
+    #[must_use]
+    pub fn as_str(&self) -> &str {
+        match self {
+            Self::Known(class) => class.as_str(),
+            Self::Unknown(raw) => raw,
+        }
+    }
+
+    /// The class, if this build knows it.
+    #[must_use]
+    pub const fn known(&self) -> Option<PrecisionClass> {
+        match self {
+            Self::Known(class) => Some(*class),
+            Self::Unknown(_) => None,
+        }
+    }
+}
+
+impl From<PrecisionClass> for WirePrecision {
+    fn from(class: PrecisionClass) -> Self {
+        Self::Known(class)
+    }
+}
+
+impl Serialize for WirePrecision {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+impl<'de> Deserialize<'de> for WirePrecision {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let value = serde_json::Value::deserialize(deserializer)?;
+        Ok(match value {
+            serde_json::Value::String(s) => Self::parse(&s),
+            serde_json::Value::Number(ref n) => n
+                .as_u64()
+                .and_then(PrecisionClass::from_code)
+                .map_or_else(|| Self::Unknown(value.to_string()), Self::Known),
+            other => Self::Unknown(other.to_string()),
+        })
+    }
+}
+
+impl PrecisionClass {
+    /// Decimal places kept when sending at this precision (`None`: all).
+    #[must_use]
+    pub const fn decimal_places(self) -> Option<i32> {
+        match self {
+            Self::Coarse => Some(2),
+            Self::Approximate => Some(4),
+            Self::Exact => None,
+        }
+    }
+
+    /// Geohash length matching this precision (cells no finer than the
+    /// rounded coordinates).
+    #[must_use]
+    pub const fn geohash_len(self) -> u8 {
+        match self {
+            Self::Coarse => 5,
+            Self::Approximate => 7,
+            Self::Exact => 8,
+        }
+    }
+
+    /// The ground size of this precision's geohash cell at `lat`.
+    #[must_use]
+    pub fn cell_size_at(self, lat: f64) -> CellSize {
+        geohash_cell_size(self.geohash_len(), lat)
+    }
+}
+
+/// Rounds an outgoing location down to `precision` and shortens its geohash
+/// to match. Never makes a location finer than it already is. A coarse
+/// location also loses its altitude and floor, which only mean something
+/// for a known building.
+pub fn reduce_to(location: &mut LocationMessage, precision: LocationPrecision) {
+    if precision == PrecisionClass::Coarse {
+        location.vertical_position = None;
+    }
+    if classify(location) <= precision {
+        return;
+    }
+    let Some(places) = precision.decimal_places() else {
+        return;
+    };
+    let scale = 10f64.powi(places);
+    location.latitude = (location.latitude * scale).round() / scale;
+    location.longitude = (location.longitude * scale).round() / scale;
+    location.geohash = super::geohash::location_to_geohash(
+        location.latitude,
+        location.longitude,
+        precision.geohash_len(),
+    );
+}
+
+/// Moves a rounded outgoing location by up to [`JITTER_MAX_STEPS`] steps of
+/// its grid on each axis, the same way for every share from the same spot
+/// within one [`JITTER_EPOCH_SECS`] epoch of `now`. `class` is the precision
+/// the location was rounded to — taken from the caller rather than
+/// re-derived with [`classify`], which reads `37.1000` as coarse. `seed` is
+/// the device's secret jitter seed. Exact locations have no grid and are
+/// left alone.
+///
+/// Each coordinate ends up at most `JITTER_MAX_STEPS + 0.5` steps of the
+/// `class` grid from the true one.
+pub fn jitter(location: &mut LocationMessage, class: PrecisionClass, seed: &[u8; 32], now: i64) {
+    let Some(places) = class.decimal_places() else {
+        return;
+    };
+    let scale = 10f64.powi(places);
+    let lat = (location.latitude * scale).round();
+    let lon = (location.longitude * scale).round();
+    let digest = Sha256::new()
+        .chain_update(JITTER_DOMAIN)
+        .chain_update(seed)
+        .chain_update(now.div_euclid(JITTER_EPOCH_SECS).to_be_bytes())
+        .chain_update(lat.to_bits().to_be_bytes())
+        .chain_update(lon.to_bits().to_be_bytes())
+        .finalize();
+    let span = 2 * u16::from(JITTER_MAX_STEPS) + 1;
+    let steps =
+        |bytes: [u8; 2]| f64::from(u16::from_be_bytes(bytes) % span) - f64::from(JITTER_MAX_STEPS);
+    location.latitude =
+        (lat + steps([digest[0], digest[1]])).clamp(-90.0 * scale, 90.0 * scale) / scale;
+    location.longitude =
+        (lon + steps([digest[2], digest[3]])).clamp(-180.0 * scale, 180.0 * scale) / scale;
+    location.geohash = super::geohash::location_to_geohash(
+        location.latitude,
+        location.longitude,
+        class.geohash_len(),
+    );
+}
+
+/// A sender's established precision in one circle.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PrecisionBaseline {
+    /// The precision the sender has been sharing at.
+    pub class: PrecisionClass,
+    /// Consecutive locations seen at `class`.
+    pub samples: u32,
+}
+
+/// A sender started sharing finer locations than their baseline.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PrecisionAnomaly {
+    /// The precision the sender had established.
+    pub baseline: PrecisionClass,
+    /// The finer precision just received.
+    pub observed: PrecisionClass,
+}
+
+/// Classifies a received location by the decimal places of its coordinates.
+#[must_use]
+pub fn classify(location: &LocationMessage) -> PrecisionClass {
+    classify_coordinates(location.latitude, location.longitude)
+}
+
+/// Classifies a coordinate pair as [`classify`] does a location.
+#[must_use]
+pub fn classify_coordinates(latitude: f64, longitude: f64) -> PrecisionClass {
+    let places = decimal_places(latitude).max(decimal_places(longitude));
+    match places {
+        0..=2 => PrecisionClass::Coarse,
+        3..=4 => PrecisionClass::Approximate,
+        _ => PrecisionClass::Exact,
+    }
+}
+
+/// Feeds one observed precision into a sender's baseline.
+///
+/// Returns the updated baseline and, if the observation is finer than an
+/// established baseline, the anomaly to report.
+#[must_use]
+pub fn observe(
+    baseline: Option<PrecisionBaseline>,
+    observed: PrecisionClass,
+) -> (PrecisionBaseline, Option<PrecisionAnomaly>) {
+    let restart = PrecisionBaseline {
+        class: observed,
+        samples: 1,
+    };
+    match baseline {
+        Some(b) if b.class == observed => (
+            PrecisionBaseline {
+                class: observed,
+                samples: b.samples.saturating_add(1),
+            },
+            None,
+        ),
+        Some(b) if observed > b.class && b.samples >= BASELINE_MIN_SAMPLES => (
+            restart,
+            Some(PrecisionAnomaly {
+                baseline: b.class,
+                observed,
+            }),
+        ),
+        _ => (restart, None),
+    }
+}
+
+/// Number of digits after the decimal point in the shortest round-trip
+/// representation of `value`.
+fn decimal_places(value: f64) -> usize {
+    let repr = value.abs().to_string();
+    repr.split_once('.').map_or(0, |(_, frac)| frac.len())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use super::*;
+
+    fn at(lat: f64, lon: f64) -> PrecisionClass {
+        classify(&LocationMessage::new(lat, lon))
+    }
+
+    #[test]
+    fn classify_by_decimal_places() {
+        assert_eq!(at(37.77, -122.42), PrecisionClass::Coarse);
+        assert_eq!(at(37.0, -122.0), PrecisionClass::Coarse);
+        assert_eq!(at(37.775, -122.4194), PrecisionClass::Approximate);
+        assert_eq!(at(37.774_929_5, -122.42), PrecisionClass::Exact);
+    }
+
+    #[test]
+    fn finer_after_established_baseline_is_flagged_once() {
+        let mut baseline = None;
+        for _ in 0..BASELINE_MIN_SAMPLES {
+            let (b, anomaly) = observe(baseline, PrecisionClass::Coarse);
+            assert!(anomaly.is_none());
+            baseline = Some(b);
+        }
+
+        let (b, anomaly) = observe(baseline, PrecisionClass::Exact);
+        assert_eq!(
+            anomaly,
+            Some(PrecisionAnomaly {
+                baseline: PrecisionClass::Coarse,
+                observed: PrecisionClass::Exact,
+            })
+        );
+        let (_, again) = observe(Some(b), PrecisionClass::Exact);
+        assert!(again.is_none(), "the new precision becomes the baseline");
+    }
+
+    #[test]
+    fn young_baseline_and_coarsening_are_not_flagged() {
+        let (b, _) = observe(None, PrecisionClass::Coarse);
+        let (_, anomaly) = observe(Some(b), PrecisionClass::Exact);
+        assert!(anomaly.is_none());
+
+        let established = PrecisionBaseline {
+            class: PrecisionClass::Exact,
+            samples: 10,
+        };
+        let (b, anomaly) = observe(Some(established), PrecisionClass::Coarse);
+        assert!(anomaly.is_none());
+        assert_eq!(b.class, PrecisionClass::Coarse);
+    }
+
+    #[test]
+    fn reduce_rounds_to_the_chosen_class_and_never_refines() {
+        let mut loc = LocationMessage::new(37.774_929_5, -122.419_415_5);
+        loc.vertical_position = Some(crate::location::VerticalPosition {
+            altitude_m: Some(40.0),
+            floor: Some(3),
+        });
+        reduce_to(&mut loc, PrecisionClass::Approximate);
+        assert_eq!(classify(&loc), PrecisionClass::Approximate);
+        assert!(loc.vertical_position.is_some());
+        assert!((loc.latitude - 37.7749).abs() < f64::EPSILON);
+        assert_eq!(loc.geohash.len(), 7);
+
+        reduce_to(&mut loc, PrecisionClass::Coarse);
+        assert_eq!(classify(&loc), PrecisionClass::Coarse);
+        assert!(loc.vertical_position.is_none());
+        assert!((loc.latitude - 37.77).abs() < f64::EPSILON);
+        assert!((loc.longitude - -122.42).abs() < f64::EPSILON);
+        assert_eq!(loc.geohash.len(), 5);
+
+        reduce_to(&mut loc, PrecisionClass::Exact);
+        assert!((loc.latitude - 37.77).abs() < f64::EPSILON);
+        assert_eq!(loc.geohash.len(), 5);
+    }
+
+    #[test]
+    fn jitter_stays_within_bounds_and_on_the_grid() {
+        let (lat, lon) = (37.774_929_5, -122.419_415_5);
+        for class in [PrecisionClass::Coarse, PrecisionClass::Approximate] {
+            let step = 1.0 / 10f64.powi(class.decimal_places().unwrap());
+            let bound = (f64::from(JITTER_MAX_STEPS) + 0.5) * step + 1e-9;
+            let mut shifted = HashSet::new();
+            for i in 0..64u8 {
+                let mut loc = LocationMessage::with_precision(lat, lon, class);
+                jitter(&mut loc, class, &[i; 32], 0);
+                assert_eq!(classify(&loc), class);
+                assert!((loc.latitude - lat).abs() <= bound);
+                assert!((loc.longitude - lon).abs() <= bound);
+                assert_eq!(loc.geohash.len(), usize::from(class.geohash_len()));
+                shifted.insert((loc.latitude.to_bits(), loc.longitude.to_bits()));
+            }
+            // Not every seed lands on the plain rounded point.
+            assert!(shifted.len() > 1);
+        }
+    }
+
+    #[test]
+    fn jitter_is_stable_for_a_spot_within_an_epoch() {
+        let seed = [7; 32];
+        let shared = |lat: f64, lon: f64, now: i64| {
+            let mut loc = LocationMessage::with_precision(lat, lon, PrecisionClass::Coarse);
+            jitter(&mut loc, PrecisionClass::Coarse, &seed, now);
+            (loc.latitude.to_bits(), loc.longitude.to_bits())
+        };
+        let first = shared(37.774_929_5, -122.419_415_5, 1_000);
+        // Same grid cell, later in the same epoch.
+        assert_eq!(shared(37.771_2, -122.418_8, JITTER_EPOCH_SECS - 1), first);
+        // Over many epochs the shift changes.
+        assert!((1..32)
+            .map(|e| shared(37.774_929_5, -122.419_415_5, e * JITTER_EPOCH_SECS))
+            .any(|p| p != first));
+
+        let mut exact = LocationMessage::new(37.774_929_5, -122.419_415_5);
+        jitter(&mut exact, PrecisionClass::Exact, &seed, 0);
+        assert!((exact.latitude - 37.774_929_5).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn jitter_uses_the_applied_grid_despite_trailing_zeros() {
+        // Rounded to 3 places, but the zeros make it classify as coarse.
+        let step = 1e-3;
+        let bound = (f64::from(JITTER_MAX_STEPS) + 0.5) * step + 1e-9;
+        for i in 0..64u8 {
+            let mut loc =
+                LocationMessage::with_precision(37.1, -122.2, PrecisionClass::Approximate);
+            assert_eq!(classify(&loc), PrecisionClass::Coarse);
+            jitter(&mut loc, PrecisionClass::Approximate, &[i; 32], 0);
+            assert!((loc.latitude - 37.1).abs() <= bound);
+            assert!((loc.longitude - -122.2).abs() <= bound);
+            assert_eq!(
+                loc.geohash.len(),
+                usize::from(PrecisionClass::Approximate.geohash_len())
+            );
+        }
+    }
+
+    #[test]
+    fn string_round_trip() {
+        for c in [
+            PrecisionClass::Coarse,
+            PrecisionClass::Approximate,
+            PrecisionClass::Exact,
+        ] {
+            assert_eq!(PrecisionClass::parse(c.as_str()), Some(c));
+        }
+        assert_eq!(PrecisionClass::parse("fine"), None);
+    }
+
+    #[test]
+    fn wire_precision_decodes_leniently() {
+        let decode = |json: &str| serde_json::from_str::<WirePrecision>(json).unwrap();
+        assert_eq!(
+            decode(r#""approximate""#),
+            WirePrecision::Known(PrecisionClass::Approximate)
+        );
+        assert_eq!(decode("0"), WirePrecision::Known(PrecisionClass::Coarse));
+        assert_eq!(
+            decode(r#""neighborhood""#),
+            WirePrecision::Unknown("neighborhood".to_string())
+        );
+        assert_eq!(decode("7"), WirePrecision::Unknown("7".to_string()));
+        assert_eq!(decode("-1"), WirePrecision::Unknown("-1".to_string()));
+        assert_eq!(
+            decode(r#"{"m":50}"#).known(),
+            None,
+            "structured values from a future sender are kept, not rejected"
+        );
+
+        for class in [
+            PrecisionClass::Coarse,
+            PrecisionClass::Approximate,
+            PrecisionClass::Exact,
+        ] {
+            assert_eq!(
+                PrecisionClass::from_code(u64::from(class.code())),
+                Some(class)
+            );
+            let json = serde_json::to_string(&WirePrecision::from(class)).unwrap();
+            assert_eq!(json, format!("\"{}\"", class.as_str()));
+        }
+    }
+}
@@ -0,0 +1,273 @@
+//! Adaptive precision: coarsen while stationary, refine when moving.
+//!
+//! A position shared exactly from the same spot night after night gives away
+//! where someone lives. When [`LocationSettings::adaptive_precision`] is on,
+//! [`HavenCore::update_location`](crate::HavenCore::update_location) keeps a
+//! short [`LocationHistory`] of the device's own fixes and asks a
+//! [`PrecisionPolicy`] how precise the next location may be. The answer is a
+//! cap: it only ever rounds a location down (see
+//! [`reduce_to`](super::precision::reduce_to)), and a circle's own precision
+//! still applies on top.
+//!
+//! The default policy, [`DwellPolicy`], looks at how long the device has
+//! stayed within [`DWELL_RADIUS_M`] of where it is now: after
+//! [`APPROXIMATE_AFTER_SECS`] locations are shared approximately, after
+//! [`COARSE_AFTER_SECS`] coarsely. Leaving the spot brings exact locations
+//! back with the first fix outside the radius.
+//!
+//! The history never leaves the device and is lost on restart, which starts
+//! every stay over — a restart shares exact positions until the dwell builds
+//! up again.
+//!
+//! [`LocationSettings::adaptive_precision`]: super::LocationSettings::adaptive_precision
+
+use std::collections::VecDeque;
+
+use super::geofence::haversine_distance_m;
+use super::precision::LocationPrecision;
+use super::types::LocationMessage;
+
+/// How far the device may drift and still count as staying put (150 m).
+pub const DWELL_RADIUS_M: f64 = 150.0;
+
+/// Time in one spot after which locations are shared approximately
+/// (10 minutes).
+pub const APPROXIMATE_AFTER_SECS: i64 = 10 * 60;
+
+/// Time in one spot after which locations are shared coarsely (30 minutes).
+pub const COARSE_AFTER_SECS: i64 = 30 * 60;
+
+/// How far back a [`LocationHistory`] remembers fixes (2 hours).
+pub const HISTORY_WINDOW_SECS: i64 = 2 * 60 * 60;
+
+/// Most fixes a [`LocationHistory`] keeps.
+pub const HISTORY_MAX_FIXES: usize = 256;
+
+/// One of the device's own positions.
+#[derive(Clone, Copy, PartialEq)]
+pub struct Fix {
+    /// Latitude in degrees.
+    pub latitude: f64,
+    /// Longitude in degrees.
+    pub longitude: f64,
+    /// Unix timestamp the position was taken.
+    pub timestamp: i64,
+}
+
+impl Fix {
+    /// The position and time of `location`.
+    #[must_use]
+    pub fn of(location: &LocationMessage) -> Self {
+        Self {
+            latitude: location.latitude,
+            longitude: location.longitude,
+            timestamp: location.timestamp.timestamp(),
+        }
+    }
+}
+
+impl std::fmt::Debug for Fix {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Fix")
+            .field("latitude", &"<redacted>")
+            .field("longitude", &"<redacted>")
+            .field("timestamp", &self.timestamp)
+            .finish()
+    }
+}
+
+/// Decides how precise the next outgoing location may be.
+pub trait PrecisionPolicy: std::fmt::Debug + Send + Sync {
+    /// The finest precision to share at, given the device's recent fixes
+    /// oldest first, the newest being the location about to go out.
+    /// [`LocationPrecision::Exact`] leaves the location as it is.
+    fn precision(&self, history: &[Fix]) -> LocationPrecision;
+}
+
+/// The default policy: coarser the longer the device stays in one spot.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DwellPolicy {
+    /// How far the device may drift and still count as staying put.
+    pub radius_m: f64,
+    /// Stay after which locations are shared approximately.
+    pub approximate_after_secs: i64,
+    /// Stay after which locations are shared coarsely.
+    pub coarse_after_secs: i64,
+}
+
+impl Default for DwellPolicy {
+    fn default() -> Self {
+        Self {
+            radius_m: DWELL_RADIUS_M,
+            approximate_after_secs: APPROXIMATE_AFTER_SECS,
+            coarse_after_secs: COARSE_AFTER_SECS,
+        }
+    }
+}
+
+impl DwellPolicy {
+    /// How long the device has stayed within the radius of its newest fix:
+    /// the time since the oldest fix of the unbroken run ending there.
+    #[must_use]
+    pub fn dwell_secs(&self, history: &[Fix]) -> i64 {
+        let Some(latest) = history.last() else {
+            return 0;
+        };
+        let since = history
+            .iter()
+            .rev()
+            .take_while(|fix| {
+                haversine_distance_m(
+                    fix.latitude,
+                    fix.longitude,
+                    latest.latitude,
+                    latest.longitude,
+                ) <= self.radius_m
+            })
+            .last()
+            .map_or(latest.timestamp, |fix| fix.timestamp);
+        latest.timestamp.saturating_sub(since).max(0)
+    }
+}
+
+impl PrecisionPolicy for DwellPolicy {
+    fn precision(&self, history: &[Fix]) -> LocationPrecision {
+        let dwell = self.dwell_secs(history);
+        if dwell >= self.coarse_after_secs {
+            LocationPrecision::Coarse
+        } else if dwell >= self.approximate_after_secs {
+            LocationPrecision::Approximate
+        } else {
+            LocationPrecision::Exact
+        }
+    }
+}
+
+/// The device's recent fixes, bounded by [`HISTORY_WINDOW_SECS`] and
+/// [`HISTORY_MAX_FIXES`].
+#[derive(Default)]
+pub struct LocationHistory {
+    fixes: VecDeque<Fix>,
+}
+
+impl LocationHistory {
+    /// Creates an empty history.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a fix and forgets those that fell out of the window. A fix
+    /// older than the newest one is ignored.
+    pub fn record(&mut self, fix: Fix) {
+        if self
+            .fixes
+            .back()
+            .is_some_and(|last| fix.timestamp < last.timestamp)
+        {
+            return;
+        }
+        self.fixes.push_back(fix);
+        let cutoff = fix.timestamp.saturating_sub(HISTORY_WINDOW_SECS);
+        while self
+            .fixes
+            .front()
+            .is_some_and(|oldest| oldest.timestamp < cutoff)
+        {
+            self.fixes.pop_front();
+        }
+        while self.fixes.len() > HISTORY_MAX_FIXES {
+            self.fixes.pop_front();
+        }
+    }
+
+    /// The remembered fixes, oldest first.
+    pub fn fixes(&mut self) -> &[Fix] {
+        self.fixes.make_contiguous()
+    }
+
+    /// Number of remembered fixes.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.fixes.len()
+    }
+
+    /// Whether no fixes are remembered.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.fixes.is_empty()
+    }
+
+    /// Forgets every fix.
+    pub fn clear(&mut self) {
+        self.fixes.clear();
+    }
+}
+
+impl std::fmt::Debug for LocationHistory {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("LocationHistory")
+            .field("fixes", &self.fixes.len())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fix(latitude: f64, longitude: f64, timestamp: i64) -> Fix {
+        Fix {
+            latitude,
+            longitude,
+            timestamp,
+        }
+    }
+
+    #[test]
+    fn coarsens_with_the_stay_and_refines_on_leaving() {
+        let policy = DwellPolicy::default();
+        let mut history = LocationHistory::new();
+        let at = |history: &mut LocationHistory, lat: f64, minute: i64| {
+            history.record(fix(lat, -122.4194, minute * 60));
+            policy.precision(history.fixes())
+        };
+
+        assert_eq!(at(&mut history, 37.7749, 0), LocationPrecision::Exact);
+        assert_eq!(at(&mut history, 37.7750, 5), LocationPrecision::Exact);
+        assert_eq!(
+            at(&mut history, 37.7749, 10),
+            LocationPrecision::Approximate
+        );
+        assert_eq!(at(&mut history, 37.7751, 30), LocationPrecision::Coarse);
+        // About 1 km north: a new stay starts.
+        assert_eq!(at(&mut history, 37.7840, 35), LocationPrecision::Exact);
+        assert_eq!(
+            at(&mut history, 37.7841, 45),
+            LocationPrecision::Approximate
+        );
+    }
+
+    #[test]
+    fn history_is_bounded_and_ignores_stale_fixes() {
+        let mut history = LocationHistory::new();
+        history.record(fix(37.0, -122.0, 1_000));
+        history.record(fix(37.0, -122.0, 500));
+        assert_eq!(history.len(), 1);
+
+        history.record(fix(37.0, -122.0, 1_000 + HISTORY_WINDOW_SECS + 1));
+        assert_eq!(history.len(), 1);
+
+        for second in 20_000..20_000 + HISTORY_MAX_FIXES + 10 {
+            history.record(fix(37.0, -122.0, i64::try_from(second).unwrap()));
+        }
+        assert_eq!(history.len(), HISTORY_MAX_FIXES);
+    }
+
+    #[test]
+    fn debug_redacts_coordinates() {
+        let debug = format!("{:?}", fix(37.774_929_5, -122.419_415_5, 0));
+        assert!(!debug.contains("37.77"), "{debug}");
+        assert!(!debug.contains("122.41"), "{debug}");
+    }
+}
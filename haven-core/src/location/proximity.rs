@@ -0,0 +1,429 @@
+//! Distance, rough ETA and approach alerts between circle members.
+//!
+//! Works only on what this device has already decrypted: each member's
+//! last-known location and the short location history kept with it. Nothing
+//! here is sent anywhere.
+//!
+//! # Error bounds
+//!
+//! A sender may round their coordinates (see [`super::precision`]), so the
+//! distance between two shared positions is only known up to the rounding of
+//! each. [`precision_error_m`] is the furthest a rounded position can be from
+//! the true one; a [`ProximityEstimate`] carries the sum for both ends (a
+//! place's coordinates are taken as exact), and
+//! [`ProximityEstimate::min_distance_m`] and
+//! [`ProximityEstimate::max_distance_m`] bound the true distance.
+//!
+//! # ETA
+//!
+//! The ETA is a straight-line guess: the rate at which the member closed the
+//! distance to the target between their latest position and the oldest one
+//! up to [`ETA_WINDOW_SECS`] before it, applied to what is left. There is
+//! none while they approach slower than [`MIN_APPROACH_SPEED_MPS`]. A moving
+//! target is taken where it was last seen.
+//!
+//! # Alert rules
+//!
+//! A [`ProximityRule`] ("notify when Dad is within 2 km of home") is set up
+//! on this device for one member of one circle, like a scheduled check-in
+//! ([`crate::safety::schedule`]), and is never sent. Its target is another
+//! member of the circle or a place; Haven keeps no places store, so the
+//! place's label and coordinates are kept with the rule.
+//! [`ProximityRule::within_after`] enters the range at the estimated distance
+//! but leaves it only once the member is certainly outside (farther than the
+//! range plus the error bound), so a position jittering at the edge alerts
+//! once.
+
+use std::time::Duration;
+
+use rand::rngs::OsRng;
+use rand::RngCore;
+
+use super::geofence::{haversine_distance_m, EARTH_RADIUS_M};
+use super::precision::classify_coordinates;
+use super::privacy::Fix;
+use crate::nostr::mls::types::GroupId;
+
+/// Smallest range a rule can watch, in meters.
+pub const MIN_PROXIMITY_RANGE_M: f64 = 100.0;
+
+/// Largest range a rule can watch, in meters (100 km).
+pub const MAX_PROXIMITY_RANGE_M: f64 = 100_000.0;
+
+/// How far before the latest position the ETA looks for an earlier one, in
+/// seconds (30 minutes).
+pub const ETA_WINDOW_SECS: i64 = 30 * 60;
+
+/// Slowest approach that still gets an ETA, in meters per second (a slow
+/// walk).
+pub const MIN_APPROACH_SPEED_MPS: f64 = 0.5;
+
+/// Shortest time between the two positions an ETA is taken from, in seconds.
+const MIN_ETA_SPAN_SECS: i64 = 30;
+
+/// The furthest a shared position can be from the sender's true one, in
+/// meters, judging by how its coordinates were rounded. Zero for exact
+/// coordinates.
+#[must_use]
+pub fn precision_error_m(latitude: f64, longitude: f64) -> f64 {
+    let Some(places) = classify_coordinates(latitude, longitude).decimal_places() else {
+        return 0.0;
+    };
+    let half_step = 0.5 / 10f64.powi(places);
+    let north_south = half_step * EARTH_RADIUS_M.to_radians();
+    let east_west = north_south * latitude.to_radians().cos().abs();
+    north_south.hypot(east_west)
+}
+
+/// How far a member is from a target, and how soon they may get there.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ProximityEstimate {
+    /// Distance between the two shared positions, in meters.
+    pub distance_m: f64,
+    /// How far the true distance may be from `distance_m`, in meters.
+    pub error_m: f64,
+    /// Seconds until the member reaches the target at their recent approach
+    /// speed; `None` while they are not approaching.
+    pub eta_secs: Option<u64>,
+}
+
+impl ProximityEstimate {
+    /// The shortest the true distance can be, in meters.
+    #[must_use]
+    pub fn min_distance_m(&self) -> f64 {
+        (self.distance_m - self.error_m).max(0.0)
+    }
+
+    /// The longest the true distance can be, in meters.
+    #[must_use]
+    pub fn max_distance_m(&self) -> f64 {
+        self.distance_m + self.error_m
+    }
+}
+
+/// Estimates how far the member whose positions are `track` (newest first)
+/// is from `target`. `target_error_m` is how far the target's position may
+/// be off: [`precision_error_m`] of a member's shared position, zero for a
+/// place. `None` for an empty track.
+#[must_use]
+pub fn estimate(track: &[Fix], target: &Fix, target_error_m: f64) -> Option<ProximityEstimate> {
+    let latest = track.first()?;
+    let distance_m = distance(latest, target);
+    let error_m = precision_error_m(latest.latitude, latest.longitude) + target_error_m;
+    let eta_secs = if distance_m <= error_m {
+        Some(0)
+    } else {
+        track
+            .iter()
+            .rev()
+            .find(|earlier| {
+                (MIN_ETA_SPAN_SECS..=ETA_WINDOW_SECS)
+                    .contains(&latest.timestamp.saturating_sub(earlier.timestamp))
+            })
+            .and_then(|earlier| {
+                let span = i32::try_from(latest.timestamp - earlier.timestamp).ok()?;
+                let speed = (distance(earlier, target) - distance_m) / f64::from(span);
+                if speed < MIN_APPROACH_SPEED_MPS {
+                    return None;
+                }
+                Duration::try_from_secs_f64(distance_m / speed)
+                    .ok()
+                    .map(|eta| eta.as_secs())
+            })
+    };
+    Some(ProximityEstimate {
+        distance_m,
+        error_m,
+        eta_secs,
+    })
+}
+
+fn distance(a: &Fix, b: &Fix) -> f64 {
+    haversine_distance_m(a.latitude, a.longitude, b.latitude, b.longitude)
+}
+
+/// What a [`ProximityRule`] measures the member's distance to.
+#[derive(Clone, PartialEq)]
+pub enum ProximityTarget {
+    /// Another member of the same circle (hex pubkey).
+    Member(String),
+    /// A place picked on this device, e.g. "Home".
+    Place {
+        /// User-facing label.
+        label: String,
+        /// Latitude in degrees.
+        latitude: f64,
+        /// Longitude in degrees.
+        longitude: f64,
+    },
+}
+
+impl std::fmt::Debug for ProximityTarget {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Member(_) => f.debug_tuple("Member").field(&"<redacted>").finish(),
+            Self::Place { label, .. } => f
+                .debug_struct("Place")
+                .field("label", label)
+                .field("latitude", &"<redacted>")
+                .field("longitude", &"<redacted>")
+                .finish(),
+        }
+    }
+}
+
+/// An alert for when one member of one circle comes near a target.
+#[derive(Clone, PartialEq)]
+pub struct ProximityRule {
+    /// Random id (hex), unique per rule.
+    pub rule_id: String,
+    /// The circle the member shares with.
+    pub mls_group_id: GroupId,
+    /// Hex pubkey of the member watched.
+    pub member_pubkey: String,
+    /// What the member's distance is measured to.
+    pub target: ProximityTarget,
+    /// Alert when the member comes within this many meters.
+    pub range_m: f64,
+    /// Unix timestamp the rule was set up.
+    pub created_at: i64,
+    /// Whether the member was within range at the last evaluation. No new
+    /// alert is raised until they have left it.
+    pub within: bool,
+}
+
+impl ProximityRule {
+    /// Builds a rule with a fresh id, set up at `now`.
+    ///
+    /// # Errors
+    ///
+    /// Returns a description of the problem if the rule is invalid.
+    pub fn new(
+        mls_group_id: GroupId,
+        member_pubkey: String,
+        target: ProximityTarget,
+        range_m: f64,
+        now: i64,
+    ) -> Result<Self, String> {
+        let mut id = [0u8; 16];
+        OsRng.fill_bytes(&mut id);
+        let rule = Self {
+            rule_id: hex::encode(id),
+            mls_group_id,
+            member_pubkey,
+            target,
+            range_m,
+            created_at: now,
+            within: false,
+        };
+        rule.validate()?;
+        Ok(rule)
+    }
+
+    /// Checks the range and target.
+    ///
+    /// # Errors
+    ///
+    /// Returns a description of the first invalid field.
+    pub fn validate(&self) -> Result<(), String> {
+        if !self.range_m.is_finite()
+            || !(MIN_PROXIMITY_RANGE_M..=MAX_PROXIMITY_RANGE_M).contains(&self.range_m)
+        {
+            return Err(format!(
+                "Proximity range must be between {MIN_PROXIMITY_RANGE_M} and \
+                 {MAX_PROXIMITY_RANGE_M} m"
+            ));
+        }
+        match &self.target {
+            ProximityTarget::Member(pubkey) if *pubkey == self.member_pubkey => {
+                Err("A member cannot be their own proximity target".to_string())
+            }
+            ProximityTarget::Member(_) => Ok(()),
+            ProximityTarget::Place {
+                label,
+                latitude,
+                longitude,
+            } => {
+                if label.trim().is_empty() {
+                    return Err("Place label must not be empty".to_string());
+                }
+                if !latitude.is_finite() || !(-90.0..=90.0).contains(latitude) {
+                    return Err("Place latitude must be between -90 and 90".to_string());
+                }
+                if !longitude.is_finite() || !(-180.0..=180.0).contains(longitude) {
+                    return Err("Place longitude must be between -180 and 180".to_string());
+                }
+                Ok(())
+            }
+        }
+    }
+
+    /// Whether the member counts as within range given `estimate` (`None`
+    /// when either position is unknown, which keeps the current state).
+    ///
+    /// Entering takes an estimated distance within the range; leaving takes
+    /// a shortest possible distance beyond it.
+    #[must_use]
+    pub fn within_after(&self, estimate: Option<&ProximityEstimate>) -> bool {
+        match estimate {
+            None => self.within,
+            Some(e) if self.within => e.min_distance_m() <= self.range_m,
+            Some(e) => e.distance_m <= self.range_m,
+        }
+    }
+}
+
+impl std::fmt::Debug for ProximityRule {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ProximityRule")
+            .field("rule_id", &self.rule_id)
+            .field("member_pubkey", &"<redacted>")
+            .field("target", &self.target)
+            .field("range_m", &self.range_m)
+            .field("created_at", &self.created_at)
+            .field("within", &self.within)
+            .finish_non_exhaustive()
+    }
+}
+
+/// A member came within range of a rule's target.
+#[derive(Clone, PartialEq)]
+pub struct ProximityAlert {
+    /// The rule that raised it.
+    pub rule_id: String,
+    /// The circle the member shares with.
+    pub mls_group_id: GroupId,
+    /// Hex pubkey of the member who came near.
+    pub member_pubkey: String,
+    /// The rule's target.
+    pub target: ProximityTarget,
+    /// Where the member stands relative to the target.
+    pub estimate: ProximityEstimate,
+    /// When the member's position was taken (Unix timestamp).
+    pub seen_at: i64,
+}
+
+impl std::fmt::Debug for ProximityAlert {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ProximityAlert")
+            .field("rule_id", &self.rule_id)
+            .field("member_pubkey", &"<redacted>")
+            .field("target", &self.target)
+            .field("estimate", &self.estimate)
+            .field("seen_at", &self.seen_at)
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::nostr::mls::types::GroupIdExt;
+
+    fn fix(latitude: f64, longitude: f64, timestamp: i64) -> Fix {
+        Fix {
+            latitude,
+            longitude,
+            timestamp,
+        }
+    }
+
+    fn home() -> ProximityTarget {
+        ProximityTarget::Place {
+            label: "Home".to_string(),
+            latitude: 48.858_37,
+            longitude: 2.294_48,
+        }
+    }
+
+    #[test]
+    fn rounded_positions_widen_the_error_bound() {
+        assert!(precision_error_m(48.858_37, 2.294_48).abs() < f64::EPSILON);
+        let approximate = precision_error_m(48.8584, 2.2945);
+        let coarse = precision_error_m(48.86, 2.29);
+        // Half a 0.01° step north-south alone is ~556 m.
+        assert!((556.0..800.0).contains(&coarse), "got {coarse}");
+        assert!(approximate > 0.0 && approximate < coarse / 50.0);
+    }
+
+    #[test]
+    fn eta_follows_the_recent_approach() {
+        let target = fix(48.858_37, 2.294_48, 0);
+        let track = [
+            fix(48.876_37, 2.294_48, 1_200),
+            fix(48.886_37, 2.294_48, 600),
+            fix(48.986_37, 2.294_48, 0),
+        ];
+        let e = estimate(&track, &target, 0.0).unwrap();
+        assert!((e.distance_m - 2_001.5).abs() < 5.0, "got {}", e.distance_m);
+        assert!(e.error_m.abs() < f64::EPSILON);
+        // Taken from the oldest fix in the window: ~12.2 km closer in 20
+        // minutes, ~10.2 m/s.
+        let eta = e.eta_secs.unwrap();
+        assert!((190..=200).contains(&eta), "got {eta}");
+
+        // Moving away, or a single position, gives no ETA.
+        let leaving = [fix(48.886_37, 2.294_48, 600), fix(48.876_37, 2.294_48, 0)];
+        assert!(estimate(&leaving, &target, 0.0).unwrap().eta_secs.is_none());
+        assert!(estimate(&track[..1], &target, 0.0)
+            .unwrap()
+            .eta_secs
+            .is_none());
+        assert!(estimate(&[], &target, 0.0).is_none());
+    }
+
+    #[test]
+    fn rules_enter_at_the_range_and_leave_beyond_the_error() {
+        let gid = GroupId::from_slice(&[1; 32]);
+        let mut rule = ProximityRule::new(gid, "aa".repeat(32), home(), 2_000.0, 0).unwrap();
+        let at = |distance_m: f64, error_m: f64| ProximityEstimate {
+            distance_m,
+            error_m,
+            eta_secs: None,
+        };
+
+        assert!(!rule.within_after(None));
+        assert!(!rule.within_after(Some(&at(2_100.0, 600.0))));
+        assert!(rule.within_after(Some(&at(1_900.0, 600.0))));
+
+        rule.within = true;
+        assert!(rule.within_after(None));
+        assert!(rule.within_after(Some(&at(2_500.0, 600.0))));
+        assert!(!rule.within_after(Some(&at(2_700.0, 600.0))));
+    }
+
+    #[test]
+    fn invalid_rules_are_rejected() {
+        let gid = GroupId::from_slice(&[1; 32]);
+        let member = "aa".repeat(32);
+        let new =
+            |target, range_m| ProximityRule::new(gid.clone(), member.clone(), target, range_m, 0);
+        assert!(new(home(), 2_000.0).is_ok());
+        assert!(new(home(), 10.0).is_err());
+        assert!(new(home(), f64::NAN).is_err());
+        assert!(new(ProximityTarget::Member(member.clone()), 2_000.0).is_err());
+        assert!(new(ProximityTarget::Member("bb".repeat(32)), 2_000.0).is_ok());
+        let nowhere = ProximityTarget::Place {
+            label: "Nowhere".to_string(),
+            latitude: 91.0,
+            longitude: 0.0,
+        };
+        assert!(new(nowhere, 2_000.0).is_err());
+    }
+
+    #[test]
+    fn debug_redacts_members_and_places() {
+        let rule = ProximityRule::new(
+            GroupId::from_slice(&[1; 32]),
+            "ab".repeat(32),
+            home(),
+            500.0,
+            0,
+        )
+        .unwrap();
+        let debug = format!("{rule:?}");
+        assert!(debug.contains("Home"));
+        assert!(!debug.contains(&"ab".repeat(32)));
+        assert!(!debug.contains("48.85"));
+    }
+}
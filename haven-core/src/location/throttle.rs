@@ -0,0 +1,249 @@
+//! Throttling of outgoing location updates.
+//!
+//! Every location update is a kind 445 on the circle's relays. Relays cannot
+//! read it, but they see when it arrives, and the publish rhythm is part of
+//! the traffic-analysis surface the jittered cadence in [`super::ttl`] is
+//! there to blur. A misbehaving app timer (a duplicated callback, a loop on
+//! resume) would both spam the relays and hand them a rhythm of its own. The
+//! core therefore keeps two limits per circle, in memory:
+//!
+//! - **Cadence** ([`PublishThrottle::check`]): an update is due once the
+//!   user's update interval has passed since the last one, or earlier if the
+//!   shared position moved more than [`DISTANCE_OVERRIDE_M`]. The interval is
+//!   shortened by the publish jitter spread ([`min_spacing_secs`]) so the
+//!   app's own jittered timer is never refused.
+//! - **Burst** ([`PublishThrottle::take_send`]): whatever the cadence, at most
+//!   [`BURST_LIMIT`] scheduled sends in a row, refilled one per
+//!   [`BURST_REFILL_SECS`], so moving fast cannot turn the distance override
+//!   into a stream.
+//!
+//! Explicit sends (a check-in response) are not limited, but are recorded
+//! and so restart the cadence.
+//!
+//! Positions are compared after the circle's precision was applied, so a
+//! coarse circle is not sent a new update for a move it cannot see. State
+//! is lost on restart, which allows one update straight away — the same as
+//! the app's timer starting up.
+
+use std::collections::HashMap;
+use std::sync::{Mutex, PoisonError};
+
+use super::geofence::haversine_distance_m;
+use super::ttl::PUBLISH_INTERVAL_JITTER_FRACTION_BP;
+
+/// How far the shared position must move for an update to go out before the
+/// interval has passed (250 m).
+pub const DISTANCE_OVERRIDE_M: f64 = 250.0;
+
+/// Most sends a circle accepts in a row before the burst limit applies.
+pub const BURST_LIMIT: u32 = 6;
+
+/// How often one send is returned to a circle's burst allowance (1 minute).
+pub const BURST_REFILL_SECS: i64 = 60;
+
+/// Shortest update interval the settings allow, in minutes.
+const MIN_INTERVAL_MINUTES: u32 = 5;
+
+/// Longest update interval the settings allow, in minutes.
+const MAX_INTERVAL_MINUTES: u32 = 60;
+
+/// The shortest spacing between scheduled updates for a
+/// [`LocationSettings::update_interval_minutes`](super::LocationSettings::update_interval_minutes)
+/// of `update_interval_minutes` (clamped to 5..=60): the interval less the
+/// publish jitter spread.
+#[must_use]
+pub fn min_spacing_secs(update_interval_minutes: u32) -> i64 {
+    let interval =
+        i64::from(update_interval_minutes.clamp(MIN_INTERVAL_MINUTES, MAX_INTERVAL_MINUTES)) * 60;
+    interval - interval * i64::from(PUBLISH_INTERVAL_JITTER_FRACTION_BP) / 10_000
+}
+
+/// Whether an update may go out now (see [`PublishThrottle::check`]).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ThrottleDecision {
+    /// Nothing was sent yet or the interval has passed.
+    Due,
+    /// The interval has not passed, but the position moved far enough.
+    Moved,
+    /// Too soon; the next scheduled update is due at `next_at`.
+    NotYet {
+        /// Unix timestamp the interval runs out.
+        next_at: i64,
+    },
+}
+
+impl ThrottleDecision {
+    /// Whether the update should be sent.
+    #[must_use]
+    pub const fn is_due(self) -> bool {
+        !matches!(self, Self::NotYet { .. })
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+struct CircleSends {
+    last: Option<(i64, f64, f64)>,
+    tokens: u32,
+    refilled_at: i64,
+}
+
+impl CircleSends {
+    const fn new(now: i64) -> Self {
+        Self {
+            last: None,
+            tokens: BURST_LIMIT,
+            refilled_at: now,
+        }
+    }
+
+    fn refill(&mut self, now: i64) {
+        let earned = now.saturating_sub(self.refilled_at) / BURST_REFILL_SECS;
+        if earned <= 0 {
+            return;
+        }
+        let earned = u32::try_from(earned).unwrap_or(u32::MAX);
+        self.tokens = self.tokens.saturating_add(earned).min(BURST_LIMIT);
+        self.refilled_at = if self.tokens == BURST_LIMIT {
+            now
+        } else {
+            self.refilled_at + i64::from(earned) * BURST_REFILL_SECS
+        };
+    }
+}
+
+/// Per-circle send history for the cadence and burst limits, keyed by the
+/// circle's Nostr group ID.
+#[derive(Debug, Default)]
+pub struct PublishThrottle {
+    circles: Mutex<HashMap<[u8; 32], CircleSends>>,
+}
+
+impl PublishThrottle {
+    /// Creates a throttle with no history.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Whether a scheduled update at (`latitude`, `longitude`) is due for
+    /// `circle` at `now`, given a minimum spacing of `min_spacing_secs`.
+    #[must_use]
+    pub fn check(
+        &self,
+        circle: &[u8; 32],
+        latitude: f64,
+        longitude: f64,
+        min_spacing_secs: i64,
+        now: i64,
+    ) -> ThrottleDecision {
+        let circles = self.circles.lock().unwrap_or_else(PoisonError::into_inner);
+        let Some((last_at, last_lat, last_lon)) = circles.get(circle).and_then(|c| c.last) else {
+            return ThrottleDecision::Due;
+        };
+        let next_at = last_at.saturating_add(min_spacing_secs);
+        if now >= next_at {
+            ThrottleDecision::Due
+        } else if haversine_distance_m(last_lat, last_lon, latitude, longitude)
+            > DISTANCE_OVERRIDE_M
+        {
+            ThrottleDecision::Moved
+        } else {
+            ThrottleDecision::NotYet { next_at }
+        }
+    }
+
+    /// Takes one send from `circle`'s burst allowance. Returns `false`, taking
+    /// nothing, when it is used up.
+    pub fn take_send(&self, circle: &[u8; 32], now: i64) -> bool {
+        let mut circles = self.circles.lock().unwrap_or_else(PoisonError::into_inner);
+        let sends = circles
+            .entry(*circle)
+            .or_insert_with(|| CircleSends::new(now));
+        sends.refill(now);
+        if sends.tokens == 0 {
+            return false;
+        }
+        sends.tokens -= 1;
+        true
+    }
+
+    /// Records an update sent to `circle` at (`latitude`, `longitude`).
+    pub fn record(&self, circle: &[u8; 32], latitude: f64, longitude: f64, now: i64) {
+        let mut circles = self.circles.lock().unwrap_or_else(PoisonError::into_inner);
+        circles
+            .entry(*circle)
+            .or_insert_with(|| CircleSends::new(now))
+            .last = Some((now, latitude, longitude));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CIRCLE: [u8; 32] = [7; 32];
+
+    #[test]
+    fn spacing_leaves_room_for_the_jittered_timer() {
+        assert_eq!(min_spacing_secs(5), 180);
+        assert_eq!(min_spacing_secs(1), 180);
+        assert_eq!(min_spacing_secs(60), 2160);
+        assert_eq!(min_spacing_secs(600), 2160);
+    }
+
+    #[test]
+    fn updates_wait_for_the_interval() {
+        let throttle = PublishThrottle::new();
+        assert_eq!(
+            throttle.check(&CIRCLE, 52.0, 13.0, 180, 1_000),
+            ThrottleDecision::Due
+        );
+        throttle.record(&CIRCLE, 52.0, 13.0, 1_000);
+
+        assert_eq!(
+            throttle.check(&CIRCLE, 52.0, 13.0, 180, 1_100),
+            ThrottleDecision::NotYet { next_at: 1_180 }
+        );
+        assert_eq!(
+            throttle.check(&CIRCLE, 52.0, 13.0, 180, 1_180),
+            ThrottleDecision::Due
+        );
+        assert_eq!(
+            throttle.check(&[8; 32], 52.0, 13.0, 180, 1_100),
+            ThrottleDecision::Due
+        );
+    }
+
+    #[test]
+    fn moving_far_enough_overrides_the_interval() {
+        let throttle = PublishThrottle::new();
+        throttle.record(&CIRCLE, 52.0, 13.0, 1_000);
+
+        // ~110 m north: not far enough.
+        assert!(!throttle.check(&CIRCLE, 52.001, 13.0, 180, 1_010).is_due());
+        // ~1.1 km north.
+        assert_eq!(
+            throttle.check(&CIRCLE, 52.01, 13.0, 180, 1_010),
+            ThrottleDecision::Moved
+        );
+    }
+
+    #[test]
+    fn bursts_are_capped_and_refill_over_time() {
+        let throttle = PublishThrottle::new();
+        for _ in 0..BURST_LIMIT {
+            assert!(throttle.take_send(&CIRCLE, 1_000));
+        }
+        assert!(!throttle.take_send(&CIRCLE, 1_000));
+        assert!(!throttle.take_send(&CIRCLE, 1_000 + BURST_REFILL_SECS - 1));
+        assert!(throttle.take_send(&CIRCLE, 1_000 + BURST_REFILL_SECS));
+        assert!(!throttle.take_send(&CIRCLE, 1_000 + BURST_REFILL_SECS));
+
+        // A long pause refills the allowance, never beyond the limit.
+        let later = 1_000 + 100 * BURST_REFILL_SECS;
+        for _ in 0..BURST_LIMIT {
+            assert!(throttle.take_send(&CIRCLE, later));
+        }
+        assert!(!throttle.take_send(&CIRCLE, later));
+    }
+}
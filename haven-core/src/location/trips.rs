@@ -0,0 +1,251 @@
+//! Trip detection and daily summaries from a member's location history.
+//!
+//! Runs on this device over locations it has already decrypted (see
+//! [`crate::circle::CircleStorage::summarize_day`]). A summary holds times
+//! and distances only, never coordinates, so showing or keeping it reveals
+//! nothing beyond what the member already shared.
+//!
+//! # Stays and trips
+//!
+//! A stay is an unbroken run of positions within [`STAY_RADIUS_M`] of its
+//! first one lasting at least [`MIN_STAY_SECS`]; a long gap between two
+//! positions in the same spot counts as staying there. A trip is the
+//! movement between one stay and the next. Movement before the first stay
+//! or after the last is a trip too, started or ended by the day's first or
+//! last position, as long as it goes farther than the stay radius.
+
+use chrono::NaiveDate;
+
+use super::geofence::haversine_distance_m;
+use super::privacy::{Fix, DWELL_RADIUS_M};
+
+/// How far a member may drift and still count as staying put, in meters.
+pub const STAY_RADIUS_M: f64 = DWELL_RADIUS_M;
+
+/// Shortest time in one spot that counts as a stay (5 minutes).
+pub const MIN_STAY_SECS: i64 = 5 * 60;
+
+/// Seconds in a day.
+const DAY_SECS: i64 = 24 * 60 * 60;
+
+/// One journey between two stays.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Trip {
+    /// When the member set off (Unix timestamp).
+    pub started_at: i64,
+    /// When they arrived (Unix timestamp).
+    pub ended_at: i64,
+    /// Distance along the shared positions, in meters.
+    pub distance_m: f64,
+}
+
+impl Trip {
+    /// How long the trip took, in seconds.
+    #[must_use]
+    pub const fn duration_secs(&self) -> i64 {
+        self.ended_at - self.started_at
+    }
+}
+
+/// A member's movement over one local day.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DaySummary {
+    /// The day summarized.
+    pub date: NaiveDate,
+    /// The day's first position (Unix timestamp), `None` without any.
+    pub first_seen_at: Option<i64>,
+    /// The day's last position (Unix timestamp), `None` without any.
+    pub last_seen_at: Option<i64>,
+    /// The day's trips, earliest first.
+    pub trips: Vec<Trip>,
+}
+
+impl DaySummary {
+    /// Distance covered by all trips, in meters.
+    #[must_use]
+    pub fn distance_m(&self) -> f64 {
+        self.trips.iter().map(|t| t.distance_m).sum()
+    }
+
+    /// Time spent on trips, in seconds.
+    #[must_use]
+    pub fn moving_secs(&self) -> i64 {
+        self.trips.iter().map(Trip::duration_secs).sum()
+    }
+}
+
+/// Parses a `YYYY-MM-DD` date.
+#[must_use]
+pub fn parse_date(date: &str) -> Option<NaiveDate> {
+    NaiveDate::parse_from_str(date, "%Y-%m-%d").ok()
+}
+
+/// The Unix timestamps `[start, end)` of `date` at `utc_offset_secs` east of
+/// UTC.
+#[must_use]
+pub fn day_bounds(date: NaiveDate, utc_offset_secs: i32) -> (i64, i64) {
+    let midnight = date
+        .and_hms_opt(0, 0, 0)
+        .map_or(0, |d| d.and_utc().timestamp());
+    let start = midnight - i64::from(utc_offset_secs);
+    (start, start + DAY_SECS)
+}
+
+/// Finds the trips in `track` (oldest first).
+#[must_use]
+pub fn detect_trips(track: &[Fix]) -> Vec<Trip> {
+    if track.is_empty() {
+        return Vec::new();
+    }
+    // Each stay as the indices of its first and last position, with the
+    // ends of the track standing in for stays at either end.
+    let mut stays = vec![(0, 0)];
+    stays.extend(stays_in(track));
+    stays.push((track.len() - 1, track.len() - 1));
+
+    stays
+        .windows(2)
+        .filter_map(|pair| {
+            let (from, to) = (pair[0].1, pair[1].0);
+            if to <= from || distance(&track[from], &track[to]) <= STAY_RADIUS_M {
+                return None;
+            }
+            let distance_m = track[from..=to]
+                .windows(2)
+                .map(|leg| distance(&leg[0], &leg[1]))
+                .sum();
+            Some(Trip {
+                started_at: track[from].timestamp,
+                ended_at: track[to].timestamp,
+                distance_m,
+            })
+        })
+        .collect()
+}
+
+/// Summarizes `date` from `track` (oldest first), using only the positions
+/// taken that day at `utc_offset_secs` east of UTC.
+#[must_use]
+pub fn summarize_day(track: &[Fix], date: NaiveDate, utc_offset_secs: i32) -> DaySummary {
+    let (start, end) = day_bounds(date, utc_offset_secs);
+    let day: Vec<Fix> = track
+        .iter()
+        .filter(|f| (start..end).contains(&f.timestamp))
+        .copied()
+        .collect();
+    DaySummary {
+        date,
+        first_seen_at: day.first().map(|f| f.timestamp),
+        last_seen_at: day.last().map(|f| f.timestamp),
+        trips: detect_trips(&day),
+    }
+}
+
+/// The stays in `track` as the indices of their first and last position.
+fn stays_in(track: &[Fix]) -> Vec<(usize, usize)> {
+    let mut stays = Vec::new();
+    let mut i = 0;
+    while i < track.len() {
+        let anchor = &track[i];
+        let run = track[i..]
+            .iter()
+            .take_while(|f| distance(anchor, f) <= STAY_RADIUS_M)
+            .count();
+        let end = i + run - 1;
+        if track[end].timestamp - anchor.timestamp >= MIN_STAY_SECS {
+            stays.push((i, end));
+            i = end + 1;
+        } else {
+            i += 1;
+        }
+    }
+    stays
+}
+
+fn distance(a: &Fix, b: &Fix) -> f64 {
+    haversine_distance_m(a.latitude, a.longitude, b.latitude, b.longitude)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fix(latitude: f64, minute: i64) -> Fix {
+        Fix {
+            latitude,
+            longitude: 13.405,
+            timestamp: 1_700_006_400 + minute * 60,
+        }
+    }
+
+    #[test]
+    fn trips_run_between_stays() {
+        // Home for an hour, ~5.6 km north in 20 minutes, work for an hour,
+        // then back.
+        let mut track: Vec<Fix> = (0..=60).step_by(10).map(|m| fix(52.50, m)).collect();
+        track.extend((1..=4).map(|i| fix(52.50 + 0.01 * f64::from(i), 60 + 5 * i64::from(i))));
+        track.extend((0..=60).step_by(15).map(|m| fix(52.55, 85 + m)));
+        track.push(fix(52.525, 160));
+        track.extend((0..=30).step_by(10).map(|m| fix(52.50, 175 + m)));
+
+        let trips = detect_trips(&track);
+        assert_eq!(trips.len(), 2);
+        assert_eq!(trips[0].started_at, fix(0.0, 60).timestamp);
+        assert_eq!(trips[0].ended_at, fix(0.0, 85).timestamp);
+        assert!(
+            (trips[0].distance_m - 5_560.0).abs() < 50.0,
+            "got {}",
+            trips[0].distance_m
+        );
+        assert_eq!(trips[1].duration_secs(), 30 * 60);
+    }
+
+    #[test]
+    fn staying_put_or_drifting_is_no_trip() {
+        let home: Vec<Fix> = (0..=120).step_by(10).map(|m| fix(52.50, m)).collect();
+        assert!(detect_trips(&home).is_empty());
+        let drift: Vec<Fix> = (0..10)
+            .map(|m| fix(52.50 + 0.0001 * f64::from(m), i64::from(m)))
+            .collect();
+        assert!(detect_trips(&drift).is_empty());
+        assert!(detect_trips(&[]).is_empty());
+    }
+
+    #[test]
+    fn moving_at_either_end_of_the_data_counts() {
+        // Already on the way when sharing started, then home.
+        let mut track: Vec<Fix> = (0..4)
+            .map(|i| fix(52.46 + 0.01 * f64::from(i), 5 * i64::from(i)))
+            .collect();
+        track.extend((0..=30).step_by(10).map(|m| fix(52.50, 20 + m)));
+        let trips = detect_trips(&track);
+        assert_eq!(trips.len(), 1);
+        assert_eq!(trips[0].started_at, fix(0.0, 0).timestamp);
+        assert_eq!(trips[0].ended_at, fix(0.0, 20).timestamp);
+    }
+
+    #[test]
+    fn summary_keeps_to_the_local_day() {
+        let date = parse_date("2023-11-15").unwrap();
+        assert!(parse_date("2023-11-31").is_none());
+        // 1_700_006_400 is 2023-11-15 00:00 UTC.
+        assert_eq!(day_bounds(date, 0), (1_700_006_400, 1_700_092_800));
+        assert_eq!(day_bounds(date, 3_600).0, 1_700_002_800);
+
+        let track = [
+            fix(52.50, -30),
+            fix(52.50, 10),
+            fix(52.55, 40),
+            fix(52.55, 60),
+        ];
+        let summary = summarize_day(&track, date, 0);
+        assert_eq!(summary.first_seen_at, Some(fix(0.0, 10).timestamp));
+        assert_eq!(summary.last_seen_at, Some(fix(0.0, 60).timestamp));
+        assert_eq!(summary.trips.len(), 1);
+        assert_eq!(summary.moving_secs(), 30 * 60);
+        assert!(summary.distance_m() > 5_000.0);
+
+        let empty = summarize_day(&track, date.succ_opt().unwrap(), 0);
+        assert!(empty.first_seen_at.is_none() && empty.trips.is_empty());
+    }
+}
@@ -5,6 +5,8 @@ use std::fmt;
 use chrono::{DateTime, Duration, Utc};
 use serde::{Deserialize, Serialize};
 
+use super::precision::{LocationPrecision, WirePrecision};
+
 /// Freshness window for a location update, in seconds.
 ///
 /// Used as the offset for `LocationMessage::expires_at` (client-side
@@ -20,16 +22,190 @@ pub const LOCATION_FRESHNESS_TTL_SECS: i64 = 15 * 60;
 
 /// Receiver-side persistent retention window, in seconds.
 ///
-/// Every receiver hard-codes this value when computing `purge_after`
-/// for cached last-known-location rows. It is not configurable: stale
-/// rows are dropped from disk after exactly 1 day, regardless of any
-/// hint embedded in the encrypted payload.
+/// Every receiver hard-codes this value as the ceiling of `purge_after`
+/// for cached last-known-location rows: stale rows are dropped from disk
+/// after at most 1 day. A sender's [`ShareExpiration`] can only bring that
+/// moment forward, never push it back.
 pub const LOCATION_RETENTION_SECS: u64 = 24 * 60 * 60;
 
+/// Shortest share expiration a sender may choose, in seconds (1 hour).
+pub const MIN_SHARE_EXPIRATION_SECS: u64 = 60 * 60;
+
+/// How long circle members keep a shared location before dropping it.
+///
+/// Receivers cache last-known locations for at most
+/// [`LOCATION_RETENTION_SECS`]; a shorter choice rides inside the encrypted
+/// payload ([`LocationMessage::share_expires_at`]) and receivers purge the
+/// cached row at that moment instead. A sender can only shorten retention,
+/// never extend it, so [`Self::Day`] (the ceiling) is the default and adds
+/// nothing to the payload.
+///
+/// This does not change the outer kind-445 NIP-40 `expiration` tag: the
+/// engine derives that from the circle's `message-retention.v1` component
+/// (`LOCATION_MESSAGE_RETENTION_SECS`, a few minutes),
+/// so relays already drop the event long before any of these choices.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum ShareExpiration {
+    /// 1 hour.
+    OneHour,
+    /// 8 hours.
+    EightHours,
+    /// 24 hours (the receiver-side ceiling).
+    #[default]
+    Day,
+    /// A custom duration in seconds, clamped to
+    /// `[MIN_SHARE_EXPIRATION_SECS, LOCATION_RETENTION_SECS]`.
+    Custom(u64),
+}
+
+impl ShareExpiration {
+    /// The expiration in seconds.
+    #[must_use]
+    pub const fn as_secs(self) -> u64 {
+        match self {
+            Self::OneHour => 60 * 60,
+            Self::EightHours => 8 * 60 * 60,
+            Self::Day => LOCATION_RETENTION_SECS,
+            Self::Custom(secs) => {
+                if secs < MIN_SHARE_EXPIRATION_SECS {
+                    MIN_SHARE_EXPIRATION_SECS
+                } else if secs > LOCATION_RETENTION_SECS {
+                    LOCATION_RETENTION_SECS
+                } else {
+                    secs
+                }
+            }
+        }
+    }
+
+    /// Maps a duration in seconds to a preset when it matches one, else to
+    /// [`Self::Custom`].
+    #[must_use]
+    pub const fn from_secs(secs: u64) -> Self {
+        match secs {
+            3_600 => Self::OneHour,
+            28_800 => Self::EightHours,
+            LOCATION_RETENTION_SECS => Self::Day,
+            other => Self::Custom(other),
+        }
+    }
+}
+
+/// What the sender's device is doing, as reported by the platform's motion
+/// activity API.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MotionState {
+    /// Not moving.
+    Stationary,
+    /// Walking.
+    Walking,
+    /// Running.
+    Running,
+    /// Cycling.
+    Cycling,
+    /// In a vehicle.
+    Driving,
+    /// A state this version does not know (sent by a newer client).
+    #[serde(other)]
+    Unknown,
+}
+
+/// Optional device status shared alongside a location ("Bob, 12% battery").
+///
+/// Only attached when [`LocationSettings::share_device_status`] is on (see
+/// [`LocationMessage::attach_device_status`]); absent otherwise, so the
+/// payload is unchanged for users who keep it off. Every field is optional so
+/// a platform that cannot read one simply leaves it out.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub struct DeviceStatus {
+    /// Battery level, 0–100.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub battery_percent: Option<u8>,
+    /// Whether the device is charging.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub charging: Option<bool>,
+    /// What the device is doing.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub motion: Option<MotionState>,
+}
+
+impl DeviceStatus {
+    /// Returns the status with an out-of-range battery level clamped to 100.
+    /// Applied on both send and receive, since a peer's payload is untrusted.
+    #[must_use]
+    pub fn sanitized(self) -> Self {
+        Self {
+            battery_percent: self.battery_percent.map(|p| p.min(100)),
+            ..self
+        }
+    }
+
+    /// Whether no field is set.
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.battery_percent.is_none() && self.charging.is_none() && self.motion.is_none()
+    }
+}
+
+/// Lowest altitude a [`VerticalPosition`] carries, in meters.
+pub const MIN_ALTITUDE_M: f64 = -500.0;
+
+/// Highest altitude a [`VerticalPosition`] carries, in meters.
+pub const MAX_ALTITUDE_M: f64 = 9_000.0;
+
+/// Highest floor level a [`VerticalPosition`] carries, above or below the
+/// ground floor.
+pub const MAX_FLOOR_LEVEL: i16 = 200;
+
+/// Optional height shared alongside a location, to tell floors apart in an
+/// apartment building.
+///
+/// Only attached when [`LocationSettings::share_vertical_position`] is on
+/// (see [`LocationMessage::attach_vertical_position`]); absent otherwise, so
+/// the payload is unchanged for users who keep it off. Either field may be
+/// missing: not every platform reports a floor.
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+pub struct VerticalPosition {
+    /// Altitude above sea level, in whole meters.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub altitude_m: Option<f64>,
+    /// Floor level, `0` for the ground floor and negative below it.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub floor: Option<i16>,
+}
+
+impl VerticalPosition {
+    /// Returns the position with the altitude rounded to the meter and
+    /// dropped if not finite or outside [`MIN_ALTITUDE_M`]..=[`MAX_ALTITUDE_M`],
+    /// and the floor clamped to [`MAX_FLOOR_LEVEL`] either way. Applied on
+    /// both send and receive, since a peer's payload is untrusted.
+    #[must_use]
+    pub fn sanitized(self) -> Self {
+        Self {
+            altitude_m: self
+                .altitude_m
+                .filter(|a| a.is_finite() && (MIN_ALTITUDE_M..=MAX_ALTITUDE_M).contains(a))
+                .map(f64::round),
+            floor: self
+                .floor
+                .map(|f| f.clamp(-MAX_FLOOR_LEVEL, MAX_FLOOR_LEVEL)),
+        }
+    }
+
+    /// Whether no field is set.
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.altitude_m.is_none() && self.floor.is_none()
+    }
+}
+
 /// A location message shared with circle members.
 ///
 /// Coordinates are sent at full GPS precision. Privacy-sensitive metadata
-/// (device ID, altitude, speed, heading, raw accuracy) is never serialized.
+/// (device ID, raw altitude, speed, heading, raw accuracy) is never
+/// serialized; altitude and floor level go out only as an opted-in
+/// [`VerticalPosition`].
 ///
 /// # Example
 ///
@@ -76,6 +252,28 @@ pub struct LocationMessage {
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub display_name: Option<String>,
 
+    /// When receivers must drop this location from their last-known cache,
+    /// if the sender chose a [`ShareExpiration`] shorter than the 1-day
+    /// ceiling. Absent for the default, so the payload is unchanged, and
+    /// ignored by receivers if it is later than the ceiling.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub share_expires_at: Option<DateTime<Utc>>,
+
+    /// Battery, charging and motion state, if the sender shares them.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub device_status: Option<DeviceStatus>,
+
+    /// Altitude and floor level, if the sender shares them.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub vertical_position: Option<VerticalPosition>,
+
+    /// The precision the sender declares it shared at. Informational:
+    /// receivers trust [`classify`](super::precision::classify) on the
+    /// coordinates. A value from a newer sender that this build does not
+    /// know decodes to [`WirePrecision::Unknown`] instead of failing.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub precision: Option<WirePrecision>,
+
     // Privacy-sensitive fields - NEVER serialized
     /// Device ID (not serialized for privacy)
     #[serde(skip)]
@@ -107,6 +305,13 @@ impl fmt::Debug for LocationMessage {
             .field("timestamp", &self.timestamp)
             .field("expires_at", &self.expires_at)
             .field("display_name", &"<redacted>")
+            .field("share_expires_at", &self.share_expires_at)
+            .field("device_status", &self.device_status.map(|_| "<redacted>"))
+            .field(
+                "vertical_position",
+                &self.vertical_position.map(|_| "<redacted>"),
+            )
+            .field("precision", &self.precision)
             .field("device_id", &"<redacted>")
             .field("raw_accuracy", &"<redacted>")
             .field("altitude", &"<redacted>")
@@ -163,6 +368,10 @@ impl LocationMessage {
             timestamp: Utc::now(),
             expires_at: Utc::now() + Duration::seconds(LOCATION_FRESHNESS_TTL_SECS),
             display_name: None,
+            share_expires_at: None,
+            device_status: None,
+            vertical_position: None,
+            precision: None,
             device_id: None,
             raw_accuracy: None,
             altitude: None,
@@ -171,6 +380,75 @@ impl LocationMessage {
         }
     }
 
+    /// Creates a `LocationMessage` as [`Self::new`] does, with members asked
+    /// to drop it after `expiration`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use haven_core::location::{LocationMessage, ShareExpiration};
+    ///
+    /// let location = LocationMessage::with_expiration(37.7749, -122.4194, ShareExpiration::OneHour);
+    /// assert!(location.share_expires_at.is_some());
+    /// ```
+    #[must_use]
+    pub fn with_expiration(lat: f64, lon: f64, expiration: ShareExpiration) -> Self {
+        let mut location = Self::new(lat, lon);
+        location.limit_share_expiration(expiration);
+        location
+    }
+
+    /// Shortens [`Self::share_expires_at`] to `expiration` after the capture
+    /// time, if that is sooner. Never extends an expiration already set.
+    pub fn limit_share_expiration(&mut self, expiration: ShareExpiration) {
+        let secs = expiration.as_secs();
+        if secs >= LOCATION_RETENTION_SECS {
+            return;
+        }
+        // `secs` is below LOCATION_RETENTION_SECS, so it fits in i64.
+        let secs = i64::try_from(secs).unwrap_or(i64::MAX);
+        let at = self.timestamp + Duration::seconds(secs);
+        self.share_expires_at = Some(self.share_expires_at.map_or(at, |t| t.min(at)));
+    }
+
+    /// Attaches `status` if `settings` allow sharing device status, and
+    /// clears any status otherwise. An empty status is never attached.
+    pub fn attach_device_status(&mut self, status: DeviceStatus, settings: &LocationSettings) {
+        let status = status.sanitized();
+        self.device_status = (settings.share_device_status && !status.is_empty()).then_some(status);
+    }
+
+    /// Attaches `position` if `settings` allow sharing altitude and floor,
+    /// and clears any position otherwise. An empty position is never
+    /// attached.
+    pub fn attach_vertical_position(
+        &mut self,
+        position: VerticalPosition,
+        settings: &LocationSettings,
+    ) {
+        let position = position.sanitized();
+        self.vertical_position =
+            (settings.share_vertical_position && !position.is_empty()).then_some(position);
+    }
+
+    /// Creates a `LocationMessage` as [`Self::new`] does, rounded to
+    /// `precision` (see [`crate::location::precision::reduce_to`]).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use haven_core::location::{LocationMessage, LocationPrecision};
+    ///
+    /// let location = LocationMessage::with_precision(37.7749295, -122.4194155, LocationPrecision::Coarse);
+    /// assert!((location.latitude - 37.77).abs() < f64::EPSILON);
+    /// ```
+    #[must_use]
+    pub fn with_precision(lat: f64, lon: f64, precision: LocationPrecision) -> Self {
+        let mut location = Self::new(lat, lon);
+        crate::location::precision::reduce_to(&mut location, precision);
+        location
+    }
+
     /// Checks if this location has expired.
     ///
     /// # Examples
@@ -229,12 +507,46 @@ pub fn sanitize_display_name(name: Option<String>) -> Option<String> {
 pub struct LocationSettings {
     /// Update interval in minutes (5-60)
     pub update_interval_minutes: u32,
+
+    /// How long members keep each shared location.
+    #[serde(default)]
+    pub share_expiration: ShareExpiration,
+
+    /// Precision shared with circles that have no override of their own.
+    #[serde(default)]
+    pub precision: LocationPrecision,
+
+    /// Whether battery, charging and motion state are shared with locations.
+    /// Off by default.
+    #[serde(default)]
+    pub share_device_status: bool,
+
+    /// Whether precision coarsens while the device stays in one spot and
+    /// refines when it moves (see [`super::privacy`]). Off by default.
+    #[serde(default)]
+    pub adaptive_precision: bool,
+
+    /// Whether rounded locations are also shifted by a seeded random amount
+    /// (see [`super::precision::jitter`]). Off by default.
+    #[serde(default)]
+    pub coordinate_jitter: bool,
+
+    /// Whether altitude and floor level are shared with locations. Off by
+    /// default.
+    #[serde(default)]
+    pub share_vertical_position: bool,
 }
 
 impl Default for LocationSettings {
     fn default() -> Self {
         Self {
             update_interval_minutes: 5,
+            share_expiration: ShareExpiration::default(),
+            precision: LocationPrecision::default(),
+            share_device_status: false,
+            adaptive_precision: false,
+            coordinate_jitter: false,
+            share_vertical_position: false,
         }
     }
 }
@@ -344,10 +656,141 @@ mod tests {
         assert_eq!(original.geohash, deserialized.geohash);
     }
 
+    #[test]
+    fn device_status_is_opt_in_and_round_trips() {
+        let status = DeviceStatus {
+            battery_percent: Some(12),
+            charging: Some(false),
+            motion: Some(MotionState::Driving),
+        };
+        let mut location = LocationMessage::new(37.7749, -122.4194);
+        location.attach_device_status(status, &LocationSettings::default());
+        assert!(location.device_status.is_none());
+        assert!(!location.to_string().unwrap().contains("device_status"));
+
+        let settings = LocationSettings {
+            share_device_status: true,
+            ..LocationSettings::default()
+        };
+        location.attach_device_status(status, &settings);
+        let decoded = LocationMessage::from_string(&location.to_string().unwrap()).unwrap();
+        assert_eq!(decoded.device_status, Some(status));
+
+        location.attach_device_status(DeviceStatus::default(), &settings);
+        assert!(location.device_status.is_none());
+    }
+
+    #[test]
+    fn vertical_position_is_sent_only_when_opted_in() {
+        let position = VerticalPosition {
+            altitude_m: Some(42.4),
+            floor: Some(7),
+        };
+        let mut location = LocationMessage::new(37.7749, -122.4194);
+        location.altitude = Some(42.4);
+        location.attach_vertical_position(position, &LocationSettings::default());
+        assert!(location.vertical_position.is_none());
+        let json = location.to_string().unwrap();
+        assert!(!json.contains("vertical_position") && !json.contains("altitude"));
+
+        let settings = LocationSettings {
+            share_vertical_position: true,
+            ..LocationSettings::default()
+        };
+        location.attach_vertical_position(position, &settings);
+        let decoded = LocationMessage::from_string(&location.to_string().unwrap()).unwrap();
+        assert_eq!(
+            decoded.vertical_position,
+            Some(VerticalPosition {
+                altitude_m: Some(42.0),
+                floor: Some(7),
+            })
+        );
+        // The raw reading stays local.
+        assert!(decoded.altitude.is_none());
+
+        location.attach_vertical_position(VerticalPosition::default(), &settings);
+        assert!(location.vertical_position.is_none());
+    }
+
+    #[test]
+    fn vertical_position_tolerates_hostile_peers() {
+        let json = r#"{"altitude_m":1e300,"floor":32000}"#;
+        let position: VerticalPosition = serde_json::from_str(json).unwrap();
+        assert_eq!(
+            position.sanitized(),
+            VerticalPosition {
+                altitude_m: None,
+                floor: Some(MAX_FLOOR_LEVEL),
+            }
+        );
+    }
+
+    #[test]
+    fn device_status_tolerates_newer_and_hostile_peers() {
+        let json = r#"{"battery_percent":250,"motion":"skateboarding"}"#;
+        let status: DeviceStatus = serde_json::from_str(json).unwrap();
+        assert_eq!(status.motion, Some(MotionState::Unknown));
+        assert_eq!(status.sanitized().battery_percent, Some(100));
+    }
+
     #[test]
     fn location_settings_default_values() {
         let settings = LocationSettings::default();
         assert_eq!(settings.update_interval_minutes, 5);
+        assert_eq!(settings.share_expiration, ShareExpiration::Day);
+    }
+
+    #[test]
+    fn location_settings_without_share_expiration_deserialize_to_default() {
+        let settings: LocationSettings =
+            serde_json::from_str(r#"{"update_interval_minutes":10}"#).unwrap();
+        assert_eq!(settings.share_expiration, ShareExpiration::Day);
+    }
+
+    // SHARE EXPIRATION TESTS
+
+    #[test]
+    fn share_expiration_presets_and_clamping() {
+        assert_eq!(ShareExpiration::OneHour.as_secs(), 3_600);
+        assert_eq!(ShareExpiration::EightHours.as_secs(), 28_800);
+        assert_eq!(ShareExpiration::Day.as_secs(), LOCATION_RETENTION_SECS);
+        assert_eq!(
+            ShareExpiration::Custom(60).as_secs(),
+            MIN_SHARE_EXPIRATION_SECS
+        );
+        assert_eq!(
+            ShareExpiration::Custom(7 * 86_400).as_secs(),
+            LOCATION_RETENTION_SECS
+        );
+        assert_eq!(ShareExpiration::Custom(7_200).as_secs(), 7_200);
+        for preset in [
+            ShareExpiration::OneHour,
+            ShareExpiration::EightHours,
+            ShareExpiration::Day,
+        ] {
+            assert_eq!(ShareExpiration::from_secs(preset.as_secs()), preset);
+        }
+        assert_eq!(
+            ShareExpiration::from_secs(7_200),
+            ShareExpiration::Custom(7_200)
+        );
+    }
+
+    #[test]
+    fn with_expiration_sets_share_expires_at_only_when_shorter_than_ceiling() {
+        let short = LocationMessage::with_expiration(0.0, 0.0, ShareExpiration::OneHour);
+        let at = short
+            .share_expires_at
+            .expect("one hour is shorter than a day");
+        assert_eq!((at - short.timestamp).num_seconds(), 3_600);
+        let json = short.to_string().unwrap();
+        let parsed = LocationMessage::from_string(&json).unwrap();
+        assert_eq!(parsed.share_expires_at, Some(at));
+
+        let day = LocationMessage::with_expiration(0.0, 0.0, ShareExpiration::Day);
+        assert!(day.share_expires_at.is_none());
+        assert!(!day.to_string().unwrap().contains("share_expires_at"));
     }
 
     // SECURITY TESTS - Input Validation
@@ -0,0 +1,240 @@
+//! Time-limited "meet me here" pins.
+//!
+//! A meet pin is a single exact point a member deliberately drops for the
+//! circle ("the café on the corner, until 3pm"). It is separate from
+//! continuous sharing, which may be precision-reduced: the pin carries exact
+//! coordinates, so sending one requires the sender's explicit consent
+//! ([`CircleManager::send_meet_pin`]).
+//!
+//! On the wire it is an ordinary MLS application message whose inner rumor:
+//!
+//! - is tagged `["t","meet_pin"]` so receivers surface it as
+//!   [`LocationMessageResult::MeetPin`] instead of a location;
+//! - carries a [`MeetPin`] payload whose field names deliberately differ from
+//!   `LocationMessage`, so an older client never mistakes the meeting point
+//!   for the sender's own position;
+//! - carries an inner NIP-40 `expiration` at the pin's `expires_at`.
+//!
+//! Expiry is enforced on both sides: the sender's and every receiver's store
+//! drop a pin once it lapses, and an already-expired pin is never surfaced
+//! (e.g. one read after a long offline stretch). Pins live
+//! [`DEFAULT_MEET_PIN_TTL_SECS`] unless the sender picks another lifetime,
+//! capped at [`MAX_MEET_PIN_TTL_SECS`].
+//!
+//! [`CircleManager::send_meet_pin`]: crate::circle::CircleManager::send_meet_pin
+//! [`LocationMessageResult::MeetPin`]: crate::nostr::mls::LocationMessageResult::MeetPin
+
+use rand::rngs::OsRng;
+use rand::RngCore;
+use serde::{Deserialize, Serialize};
+
+/// Inner-rumor hashtag marking a meet pin.
+pub const MEET_PIN_TAG: &str = "meet_pin";
+
+/// Default lifetime of a meet pin, in seconds (2 hours).
+pub const DEFAULT_MEET_PIN_TTL_SECS: i64 = 2 * 60 * 60;
+
+/// Shortest lifetime a meet pin can be given, in seconds (5 minutes).
+pub const MIN_MEET_PIN_TTL_SECS: i64 = 5 * 60;
+
+/// Longest lifetime a meet pin can be given, in seconds (24 hours).
+pub const MAX_MEET_PIN_TTL_SECS: i64 = 24 * 60 * 60;
+
+/// Maximum length of a pin label, in characters.
+pub const MAX_MEET_PIN_LABEL_CHARS: usize = 80;
+
+/// The encrypted payload of a meet pin.
+#[derive(Clone, PartialEq, Serialize, Deserialize)]
+pub struct MeetPin {
+    /// Random id (hex), unique per pin.
+    pub pin_id: String,
+    /// Exact latitude of the meeting point.
+    pub lat: f64,
+    /// Exact longitude of the meeting point.
+    pub lon: f64,
+    /// Optional short label ("north entrance").
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub label: Option<String>,
+    /// Unix timestamp the pin was dropped.
+    pub created_at: i64,
+    /// Unix timestamp the pin lapses.
+    pub expires_at: i64,
+}
+
+impl MeetPin {
+    /// Builds a pin at the given point, living `ttl_secs` from `now`
+    /// (clamped to [`MIN_MEET_PIN_TTL_SECS`]..=[`MAX_MEET_PIN_TTL_SECS`]).
+    ///
+    /// # Errors
+    ///
+    /// Returns a description of the problem if the coordinates are invalid.
+    pub fn new(
+        lat: f64,
+        lon: f64,
+        label: Option<&str>,
+        ttl_secs: i64,
+        now: i64,
+    ) -> Result<Self, String> {
+        let mut id = [0u8; 16];
+        OsRng.fill_bytes(&mut id);
+        let pin = Self {
+            pin_id: hex::encode(id),
+            lat,
+            lon,
+            label: sanitize_label(label),
+            created_at: now,
+            expires_at: now
+                .saturating_add(ttl_secs.clamp(MIN_MEET_PIN_TTL_SECS, MAX_MEET_PIN_TTL_SECS)),
+        };
+        pin.validate()?;
+        Ok(pin)
+    }
+
+    /// Checks the coordinates and the lifetime.
+    ///
+    /// # Errors
+    ///
+    /// Returns a description of the first invalid field.
+    pub fn validate(&self) -> Result<(), String> {
+        if !self.lat.is_finite() || !(-90.0..=90.0).contains(&self.lat) {
+            return Err("Meet pin latitude must be between -90 and 90".to_string());
+        }
+        if !self.lon.is_finite() || !(-180.0..=180.0).contains(&self.lon) {
+            return Err("Meet pin longitude must be between -180 and 180".to_string());
+        }
+        if self.pin_id.is_empty() {
+            return Err("Meet pin has no id".to_string());
+        }
+        let lifetime = self.expires_at.saturating_sub(self.created_at);
+        if !(0..=MAX_MEET_PIN_TTL_SECS).contains(&lifetime) {
+            return Err(format!(
+                "Meet pin lifetime must be at most {MAX_MEET_PIN_TTL_SECS} seconds"
+            ));
+        }
+        Ok(())
+    }
+
+    /// Whether the pin has lapsed at `now`.
+    #[must_use]
+    pub const fn is_expired(&self, now: i64) -> bool {
+        now >= self.expires_at
+    }
+
+    /// Parses and validates a pin payload. The label is re-sanitized.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the JSON is not a pin or the pin is invalid.
+    pub fn from_string(json: &str) -> Result<Self, String> {
+        let mut pin: Self = serde_json::from_str(json).map_err(|e| e.to_string())?;
+        pin.label = sanitize_label(pin.label.as_deref());
+        pin.validate()?;
+        Ok(pin)
+    }
+
+    /// Serializes the payload.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if serialization fails (extremely rare).
+    pub fn to_string(&self) -> Result<String, serde_json::Error> {
+        serde_json::to_string(self)
+    }
+}
+
+impl std::fmt::Debug for MeetPin {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("MeetPin")
+            .field("pin_id", &self.pin_id)
+            .field("lat", &"<redacted>")
+            .field("lon", &"<redacted>")
+            .field("label", &self.label.as_ref().map(|_| "<redacted>"))
+            .field("created_at", &self.created_at)
+            .field("expires_at", &self.expires_at)
+            .finish()
+    }
+}
+
+/// A pin as held in a circle's store: the payload plus who dropped it.
+#[derive(Clone, PartialEq)]
+pub struct StoredMeetPin {
+    /// Hex pubkey of the member who dropped the pin.
+    pub sender_pubkey: String,
+    /// The pin.
+    pub pin: MeetPin,
+}
+
+impl std::fmt::Debug for StoredMeetPin {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("StoredMeetPin")
+            .field("sender_pubkey", &"<redacted>")
+            .field("pin", &self.pin)
+            .finish()
+    }
+}
+
+/// Trims the label, strips control characters, and caps it at
+/// [`MAX_MEET_PIN_LABEL_CHARS`]. Returns `None` if nothing is left.
+#[must_use]
+pub fn sanitize_label(label: Option<&str>) -> Option<String> {
+    let cleaned: String = label?
+        .trim()
+        .chars()
+        .filter(|c| !c.is_control())
+        .take(MAX_MEET_PIN_LABEL_CHARS)
+        .collect();
+    let cleaned = cleaned.trim_end().to_string();
+    (!cleaned.is_empty()).then_some(cleaned)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::location::LocationMessage;
+
+    #[test]
+    fn lifetime_defaults_and_is_clamped() {
+        let pin = MeetPin::new(1.0, 2.0, None, DEFAULT_MEET_PIN_TTL_SECS, 100).unwrap();
+        assert_eq!(pin.expires_at, 100 + DEFAULT_MEET_PIN_TTL_SECS);
+        let long = MeetPin::new(1.0, 2.0, None, 7 * 24 * 60 * 60, 100).unwrap();
+        assert_eq!(long.expires_at, 100 + MAX_MEET_PIN_TTL_SECS);
+        let short = MeetPin::new(1.0, 2.0, None, 1, 100).unwrap();
+        assert_eq!(short.expires_at, 100 + MIN_MEET_PIN_TTL_SECS);
+
+        assert!(!pin.is_expired(pin.expires_at - 1));
+        assert!(pin.is_expired(pin.expires_at));
+    }
+
+    #[test]
+    fn keeps_exact_coordinates_and_roundtrips() {
+        let pin = MeetPin::new(37.774_929_5, -122.419_415_5, Some("  gate B "), 600, 0).unwrap();
+        let parsed = MeetPin::from_string(&pin.to_string().unwrap()).unwrap();
+        assert_eq!(parsed, pin);
+        assert_eq!(parsed.lat, 37.774_929_5);
+        assert_eq!(parsed.label.as_deref(), Some("gate B"));
+    }
+
+    #[test]
+    fn rejects_invalid_pins() {
+        assert!(MeetPin::new(91.0, 0.0, None, 600, 0).is_err());
+        assert!(MeetPin::new(0.0, f64::NAN, None, 600, 0).is_err());
+        let mut pin = MeetPin::new(0.0, 0.0, None, 600, 0).unwrap();
+        pin.expires_at = MAX_MEET_PIN_TTL_SECS + 1;
+        assert!(MeetPin::from_string(&pin.to_string().unwrap()).is_err());
+    }
+
+    #[test]
+    fn pin_payload_is_not_a_location() {
+        // An older client must not plot the meeting point as the sender.
+        let pin = MeetPin::new(10.0, 20.0, None, 600, 0).unwrap();
+        assert!(LocationMessage::from_string(&pin.to_string().unwrap()).is_err());
+    }
+
+    #[test]
+    fn debug_redacts_coordinates_and_label() {
+        let pin = MeetPin::new(37.7749, -122.4194, Some("secret spot"), 600, 0).unwrap();
+        let debug = format!("{pin:?}");
+        assert!(!debug.contains("37.7749"));
+        assert!(!debug.contains("secret"));
+    }
+}
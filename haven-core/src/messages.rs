@@ -0,0 +1,690 @@
+//! Localizable user-facing messages.
+//!
+//! Error and notification text used to be English `format!` strings built
+//! deep inside the core and shown verbatim by the app. This module separates
+//! what the user reads from what goes in the logs:
+//!
+//! - A [`UserMessage`] is a stable [`MessageCode`] plus named parameters. The
+//!   Flutter layer looks the code up in its own translation catalog and fills
+//!   in the parameters; [`UserMessage::english`] is the built-in fallback.
+//! - The detailed English `Display` of each error type is unchanged and stays
+//!   the thing to log. It can carry internal detail that has no place in the
+//!   UI, so parameters are limited to values the user already knows (relay
+//!   URLs they configured, a member's pubkey, a circle state).
+//!
+//! Every core error type implements [`Localize`]; live-sync events that the
+//! app surfaces as notifications map through [`notification_for`].
+//!
+//! Codes are part of the FFI contract: never rename one, add a new code
+//! instead.
+
+use crate::avatar::AvatarError;
+use crate::circle::CircleError;
+use crate::nostr::nip46::Nip46Error;
+use crate::nostr::{GroupEventRejection, IdentityError, NostrError};
+use crate::profile::ProfileError;
+use crate::relay::live_sync::{LiveSyncError, LiveSyncEvent};
+use crate::relay::{PublisherError, RelayError};
+use crate::tiles::TileCacheError;
+
+/// Stable identifier of a user-facing message.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum MessageCode {
+    /// An unexpected internal failure.
+    Internal,
+    /// The request was malformed (bad key, bad JSON, out-of-range value).
+    InvalidInput,
+    /// Local storage could not be read or written.
+    Storage,
+    /// The feature is not available in this build.
+    Unsupported,
+    /// The circle does not exist (or no longer exists) on this device.
+    CircleNotFound,
+    /// The contact does not exist.
+    ContactNotFound,
+    /// A circle with the same identity already exists.
+    CircleAlreadyExists,
+    /// The membership change conflicts with the circle's current roster.
+    MembershipConflict,
+    /// The circle was removed because this device is no longer in it.
+    CircleRemoved,
+    /// The last member cannot leave; the circle would be abandoned.
+    LastMember,
+    /// The circle cannot move from `from` to `to`.
+    IllegalTransition,
+    /// The circle's identity clashes with another circle on this device.
+    CircleIdCollision,
+    /// An admin must hand over admin rights before leaving.
+    AdminMustStepDown,
+    /// Only a circle admin may do this.
+    NotAdmin,
+    /// Location sharing is paused.
+    SharingPaused,
+    /// The encrypted group operation failed.
+    GroupError,
+    /// The invitation was already handled.
+    InvitationAlreadyProcessed,
+    /// No relay can deliver the invitation.
+    InvitationNoRelays,
+    /// Encrypting failed.
+    EncryptionFailed,
+    /// Decrypting failed.
+    DecryptionFailed,
+    /// A signature did not verify.
+    InvalidSignature,
+    /// The message expired before it was read.
+    MessageExpired,
+    /// An event from a relay was malformed.
+    InvalidEvent,
+    /// No identity has been created or imported yet.
+    IdentityMissing,
+    /// An identity already exists on this device.
+    IdentityExists,
+    /// The key (nsec / pubkey) is not valid.
+    InvalidKey,
+    /// The encrypted key backup could not be opened (wrong passphrase or
+    /// corrupted).
+    InvalidKeyBackup,
+    /// A relay could not be reached (`relay`).
+    RelayUnreachable,
+    /// No relay accepted the event.
+    PublishFailed,
+    /// A relay refused the event (`relay`, `reason`).
+    RelayRejected,
+    /// The relay URL is not valid or not secure (`url`).
+    InvalidRelayUrl,
+    /// The relay is on the blacklist (`relay`).
+    RelayBlacklisted,
+    /// Fetching from relays failed.
+    FetchFailed,
+    /// No relays are configured for the operation.
+    NoRelays,
+    /// The proof of work a relay asked for could not be produced.
+    ProofOfWorkFailed,
+    /// Live sync is not running.
+    SyncNotRunning,
+    /// A network operation timed out.
+    Timeout,
+    /// A network request failed.
+    NetworkError,
+    /// The URL is not secure or not allowed.
+    InsecureUrl,
+    /// The image is too large.
+    ImageTooLarge,
+    /// The image format is not supported.
+    ImageUnsupported,
+    /// The image could not be read.
+    ImageInvalid,
+    /// Notification: a member sent an SOS (`sender_pubkey`).
+    NotificationSos,
+    /// Notification: a member dropped a meet pin (`sender_pubkey`).
+    NotificationMeetPin,
+    /// Notification: a member asked someone to check in (`sender_pubkey`,
+    /// `target_pubkey`).
+    NotificationCheckinRequest,
+    /// Notification: a member's location became more precise than usual
+    /// (`sender_pubkey`, `baseline`, `observed`).
+    NotificationPrecisionChanged,
+    /// Notification: a circle invitation arrived.
+    NotificationInvitation,
+    /// Notification: a member's MLS signature key changed (`member_pubkey`).
+    NotificationMemberKeyChanged,
+    /// The ground area a shared location cell covers (`width`, `height`:
+    /// plain meter counts, e.g. `2400`, for the app to format and pick a
+    /// unit for).
+    LocationCellArea,
+    /// An invitation cannot be resent yet, or not again.
+    InvitationResendLimited,
+    /// This device's data was written by a newer (or much older) app
+    /// version and cannot be opened.
+    StorageIncompatible,
+    /// The remote signer refused the request.
+    RemoteSignerRefused,
+    /// The remote signer holds another identity than this device's.
+    RemoteSignerWrongIdentity,
+    /// The remote signer wants this device approved first (`url`).
+    RemoteSignerApproval,
+}
+
+impl MessageCode {
+    /// The stable identifier the app keys its translations on.
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Internal => "internal",
+            Self::InvalidInput => "invalid_input",
+            Self::Storage => "storage",
+            Self::Unsupported => "unsupported",
+            Self::CircleNotFound => "circle.not_found",
+            Self::ContactNotFound => "contact.not_found",
+            Self::CircleAlreadyExists => "circle.already_exists",
+            Self::MembershipConflict => "circle.membership_conflict",
+            Self::CircleRemoved => "circle.removed",
+            Self::LastMember => "circle.last_member",
+            Self::IllegalTransition => "circle.illegal_transition",
+            Self::CircleIdCollision => "circle.id_collision",
+            Self::AdminMustStepDown => "circle.admin_must_step_down",
+            Self::NotAdmin => "circle.not_admin",
+            Self::SharingPaused => "circle.sharing_paused",
+            Self::GroupError => "circle.group_error",
+            Self::InvitationAlreadyProcessed => "invitation.already_processed",
+            Self::InvitationNoRelays => "invitation.no_relays",
+            Self::EncryptionFailed => "crypto.encryption_failed",
+            Self::DecryptionFailed => "crypto.decryption_failed",
+            Self::InvalidSignature => "crypto.invalid_signature",
+            Self::MessageExpired => "message.expired",
+            Self::InvalidEvent => "event.invalid",
+            Self::IdentityMissing => "identity.missing",
+            Self::IdentityExists => "identity.exists",
+            Self::InvalidKey => "identity.invalid_key",
+            Self::InvalidKeyBackup => "identity.invalid_backup",
+            Self::RelayUnreachable => "relay.unreachable",
+            Self::PublishFailed => "relay.publish_failed",
+            Self::RelayRejected => "relay.rejected",
+            Self::InvalidRelayUrl => "relay.invalid_url",
+            Self::RelayBlacklisted => "relay.blacklisted",
+            Self::FetchFailed => "relay.fetch_failed",
+            Self::NoRelays => "relay.none",
+            Self::ProofOfWorkFailed => "relay.pow_failed",
+            Self::SyncNotRunning => "sync.not_running",
+            Self::Timeout => "network.timeout",
+            Self::NetworkError => "network.error",
+            Self::InsecureUrl => "network.insecure_url",
+            Self::ImageTooLarge => "image.too_large",
+            Self::ImageUnsupported => "image.unsupported",
+            Self::ImageInvalid => "image.invalid",
+            Self::NotificationSos => "notification.sos",
+            Self::NotificationMeetPin => "notification.meet_pin",
+            Self::NotificationCheckinRequest => "notification.checkin_request",
+            Self::NotificationPrecisionChanged => "notification.precision_changed",
+            Self::NotificationInvitation => "notification.invitation",
+            Self::NotificationMemberKeyChanged => "notification.member_key_changed",
+            Self::LocationCellArea => "location.cell_area",
+            Self::InvitationResendLimited => "invitation.resend_limited",
+            Self::StorageIncompatible => "storage.incompatible",
+            Self::RemoteSignerRefused => "signer.refused",
+            Self::RemoteSignerWrongIdentity => "signer.wrong_identity",
+            Self::RemoteSignerApproval => "signer.approval_required",
+        }
+    }
+
+    /// Built-in English text; `{name}` is replaced by the parameter `name`.
+    const fn english_template(self) -> &'static str {
+        match self {
+            Self::Internal => "Something went wrong.",
+            Self::InvalidInput => "The request was not valid.",
+            Self::Storage => "Could not access data on this device.",
+            Self::Unsupported => "This isn't supported yet.",
+            Self::CircleNotFound => "This circle no longer exists.",
+            Self::ContactNotFound => "Contact not found.",
+            Self::CircleAlreadyExists => "You are already in this circle.",
+            Self::MembershipConflict => "The circle's members changed. Try again.",
+            Self::CircleRemoved => "You are no longer in this circle.",
+            Self::LastMember => "You are the last member of this circle.",
+            Self::IllegalTransition => "A {from} circle cannot become {to}.",
+            Self::CircleIdCollision => "This circle clashes with another circle on this device.",
+            Self::AdminMustStepDown => "Hand over admin rights before leaving.",
+            Self::NotAdmin => "Only a circle admin can do that.",
+            Self::SharingPaused => "Location sharing is paused.",
+            Self::GroupError => "The circle could not be updated.",
+            Self::InvitationAlreadyProcessed => "This invitation was already handled.",
+            Self::InvitationNoRelays => "No relay can deliver this invitation.",
+            Self::EncryptionFailed => "Could not encrypt.",
+            Self::DecryptionFailed => "Could not decrypt.",
+            Self::InvalidSignature => "The signature is not valid.",
+            Self::MessageExpired => "This message has expired.",
+            Self::InvalidEvent => "Received data was not valid.",
+            Self::IdentityMissing => "Create or import an identity first.",
+            Self::IdentityExists => "An identity already exists on this device.",
+            Self::InvalidKey => "That key is not valid.",
+            Self::InvalidKeyBackup => "Wrong passphrase or damaged backup.",
+            Self::RelayUnreachable => "Could not reach {relay}.",
+            Self::PublishFailed => "No relay accepted the update.",
+            Self::RelayRejected => "{relay} refused the update: {reason}",
+            Self::InvalidRelayUrl => "{url} is not a valid secure relay address.",
+            Self::RelayBlacklisted => "{relay} is blocked.",
+            Self::FetchFailed => "Could not load updates from relays.",
+            Self::NoRelays => "No relays are available.",
+            Self::ProofOfWorkFailed => "A relay asked for more work than this device can do.",
+            Self::SyncNotRunning => "Sync is not running.",
+            Self::Timeout => "The network took too long to respond.",
+            Self::NetworkError => "A network error occurred.",
+            Self::InsecureUrl => "That address is not allowed.",
+            Self::ImageTooLarge => "The image is too large.",
+            Self::ImageUnsupported => "That image format is not supported.",
+            Self::ImageInvalid => "The image could not be read.",
+            Self::NotificationSos => "{sender_pubkey} needs help.",
+            Self::NotificationMeetPin => "{sender_pubkey} dropped a meeting point.",
+            Self::NotificationCheckinRequest => {
+                "{sender_pubkey} asked {target_pubkey} to check in."
+            }
+            Self::NotificationPrecisionChanged => {
+                "{sender_pubkey} is now sharing {observed} locations (was {baseline})."
+            }
+            Self::NotificationInvitation => "You were invited to a circle.",
+            Self::NotificationMemberKeyChanged => {
+                "{member_pubkey}'s security key changed. Verify it is really them."
+            }
+            Self::LocationCellArea => "~{width} m × {height} m area",
+            Self::InvitationResendLimited => "This invitation was resent recently.",
+            Self::StorageIncompatible => {
+                "This app version cannot open the circles on this device. Update the app."
+            }
+            Self::RemoteSignerRefused => "Your signer refused the request.",
+            Self::RemoteSignerWrongIdentity => "Your signer holds a different account.",
+            Self::RemoteSignerApproval => "Approve this device on your signer: {url}",
+        }
+    }
+}
+
+impl std::fmt::Display for MessageCode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// A message code with its parameters.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UserMessage {
+    /// What happened.
+    pub code: MessageCode,
+    /// Named values the text refers to, in insertion order.
+    pub params: Vec<(&'static str, String)>,
+}
+
+impl UserMessage {
+    /// A message without parameters.
+    #[must_use]
+    pub const fn new(code: MessageCode) -> Self {
+        Self {
+            code,
+            params: Vec::new(),
+        }
+    }
+
+    /// Adds a parameter.
+    #[must_use]
+    pub fn with(mut self, name: &'static str, value: impl Into<String>) -> Self {
+        self.params.push((name, value.into()));
+        self
+    }
+
+    /// The built-in English text with parameters filled in.
+    #[must_use]
+    pub fn english(&self) -> String {
+        self.params.iter().fold(
+            self.code.english_template().to_string(),
+            |text, (name, value)| text.replace(&format!("{{{name}}}"), value),
+        )
+    }
+}
+
+/// Types that have a user-facing [`UserMessage`].
+pub trait Localize {
+    /// The message to show the user (the `Display` text is for logs).
+    fn user_message(&self) -> UserMessage;
+}
+
+impl Localize for CircleError {
+    fn user_message(&self) -> UserMessage {
+        match self {
+            Self::Storage(_) | Self::Database(_) => UserMessage::new(MessageCode::Storage),
+            Self::NotFound(_) => UserMessage::new(MessageCode::CircleNotFound),
+            Self::ContactNotFound(_) => UserMessage::new(MessageCode::ContactNotFound),
+            Self::InvalidData(_) => UserMessage::new(MessageCode::InvalidInput),
+            Self::Mls(_) => UserMessage::new(MessageCode::GroupError),
+            Self::AlreadyExists(_) => UserMessage::new(MessageCode::CircleAlreadyExists),
+            Self::MembershipConflict(_) => UserMessage::new(MessageCode::MembershipConflict),
+            Self::OrphanedCircleRemoved => UserMessage::new(MessageCode::CircleRemoved),
+            Self::LastMemberAbandon => UserMessage::new(MessageCode::LastMember),
+            Self::AlreadyProcessed => UserMessage::new(MessageCode::InvitationAlreadyProcessed),
+            Self::MissingWelcomeRelays => UserMessage::new(MessageCode::InvitationNoRelays),
+            Self::NotAdmin => UserMessage::new(MessageCode::NotAdmin),
+            Self::SharingPaused => UserMessage::new(MessageCode::SharingPaused),
+            Self::NostrGroupIdCollision => UserMessage::new(MessageCode::CircleIdCollision),
+            Self::IllegalTransition { from, to } => {
+                UserMessage::new(MessageCode::IllegalTransition)
+                    .with("from", from.to_string())
+                    .with("to", to.to_string())
+            }
+            Self::ResendLimited { .. } => UserMessage::new(MessageCode::InvitationResendLimited),
+            Self::IncompatibleMlsStorage { .. } => {
+                UserMessage::new(MessageCode::StorageIncompatible)
+            }
+            Self::RejectedEvent(rejection) => UserMessage::new(match rejection {
+                GroupEventRejection::Expired => MessageCode::MessageExpired,
+                GroupEventRejection::InvalidSignature => MessageCode::InvalidSignature,
+                _ => MessageCode::InvalidEvent,
+            }),
+        }
+    }
+}
+
+impl Localize for RelayError {
+    fn user_message(&self) -> UserMessage {
+        match self {
+            Self::Connection { url, .. } => {
+                UserMessage::new(MessageCode::RelayUnreachable).with("relay", url.clone())
+            }
+            Self::Publish(_) | Self::AllRelaysFailed => {
+                UserMessage::new(MessageCode::PublishFailed)
+            }
+            Self::InvalidUrl(url) => {
+                UserMessage::new(MessageCode::InvalidRelayUrl).with("url", url.clone())
+            }
+            Self::InvalidPubkey => UserMessage::new(MessageCode::InvalidKey),
+            Self::Subscription(_) | Self::Fetch(_) | Self::NoEventsFound => {
+                UserMessage::new(MessageCode::FetchFailed)
+            }
+            Self::Rejected { relay, reason } => UserMessage::new(MessageCode::RelayRejected)
+                .with("relay", relay.clone())
+                .with("reason", reason.clone()),
+            Self::Timeout(_) => UserMessage::new(MessageCode::Timeout),
+            Self::NotInitialized | Self::Initialization(_) => {
+                UserMessage::new(MessageCode::SyncNotRunning)
+            }
+            Self::Blacklisted(relay) => {
+                UserMessage::new(MessageCode::RelayBlacklisted).with("relay", relay.clone())
+            }
+            Self::InvalidBlacklist(_) | Self::InvalidEvent(_) => {
+                UserMessage::new(MessageCode::InvalidEvent)
+            }
+            Self::ProofOfWork(_) => UserMessage::new(MessageCode::ProofOfWorkFailed),
+        }
+    }
+}
+
+impl Localize for NostrError {
+    fn user_message(&self) -> UserMessage {
+        match self {
+            Self::MlsGroup(_) | Self::MdkError(_) | Self::ExporterSecretUnavailable(_) => {
+                UserMessage::new(MessageCode::GroupError)
+            }
+            Self::Encryption(_) | Self::GiftWrap(_) => {
+                UserMessage::new(MessageCode::EncryptionFailed)
+            }
+            Self::Decryption(_) | Self::GiftUnwrap(_) | Self::InvalidWelcome(_) => {
+                UserMessage::new(MessageCode::DecryptionFailed)
+            }
+            Self::KeyDerivation(_) | Self::Signing(_) => UserMessage::new(MessageCode::Internal),
+            Self::Serialization(_) | Self::InvalidEvent(_) => {
+                UserMessage::new(MessageCode::InvalidEvent)
+            }
+            Self::Expired => UserMessage::new(MessageCode::MessageExpired),
+            Self::InvalidSignature => UserMessage::new(MessageCode::InvalidSignature),
+            Self::HexError(_) => UserMessage::new(MessageCode::InvalidInput),
+            Self::AdminSelfDemoteRequired => UserMessage::new(MessageCode::AdminMustStepDown),
+            Self::GroupNotFound(_) => UserMessage::new(MessageCode::CircleNotFound),
+            Self::StorageError(_) => UserMessage::new(MessageCode::Storage),
+            Self::IncompatibleMlsStorage { .. } => {
+                UserMessage::new(MessageCode::StorageIncompatible)
+            }
+        }
+    }
+}
+
+impl Localize for IdentityError {
+    fn user_message(&self) -> UserMessage {
+        match self {
+            Self::InvalidNsec(_) | Self::InvalidNpub(_) | Self::Bech32(_) => {
+                UserMessage::new(MessageCode::InvalidKey)
+            }
+            Self::InvalidNcryptsec(_) => UserMessage::new(MessageCode::InvalidKeyBackup),
+            Self::Encryption(_) => UserMessage::new(MessageCode::EncryptionFailed),
+            Self::NoIdentity => UserMessage::new(MessageCode::IdentityMissing),
+            Self::IdentityExists => UserMessage::new(MessageCode::IdentityExists),
+            Self::KeyDerivation(_) | Self::Signing(_) | Self::Lock(_) => {
+                UserMessage::new(MessageCode::Internal)
+            }
+            Self::Storage(_) => UserMessage::new(MessageCode::Storage),
+        }
+    }
+}
+
+impl Localize for Nip46Error {
+    fn user_message(&self) -> UserMessage {
+        match self {
+            Self::InvalidUri(_) => UserMessage::new(MessageCode::InvalidInput),
+            Self::Relay(_) => UserMessage::new(MessageCode::NetworkError),
+            Self::Timeout => UserMessage::new(MessageCode::Timeout),
+            Self::AuthRequired(url) => {
+                UserMessage::new(MessageCode::RemoteSignerApproval).with("url", url.clone())
+            }
+            Self::Rejected(_) => UserMessage::new(MessageCode::RemoteSignerRefused),
+            Self::IdentityMismatch => UserMessage::new(MessageCode::RemoteSignerWrongIdentity),
+            Self::InvalidResponse(_) => UserMessage::new(MessageCode::InvalidEvent),
+        }
+    }
+}
+
+impl Localize for PublisherError {
+    fn user_message(&self) -> UserMessage {
+        match self {
+            Self::Build(_) => UserMessage::new(MessageCode::Internal),
+        }
+    }
+}
+
+impl Localize for LiveSyncError {
+    fn user_message(&self) -> UserMessage {
+        match self {
+            Self::NoSession => UserMessage::new(MessageCode::SyncNotRunning),
+            Self::Relay(_) => UserMessage::new(MessageCode::FetchFailed),
+            Self::Mls(_) | Self::InvalidCompetitor => UserMessage::new(MessageCode::GroupError),
+            Self::Timeout => UserMessage::new(MessageCode::Timeout),
+        }
+    }
+}
+
+impl Localize for AvatarError {
+    fn user_message(&self) -> UserMessage {
+        match self {
+            Self::InputTooLarge | Self::TooLargeAfterEncode => {
+                UserMessage::new(MessageCode::ImageTooLarge)
+            }
+            Self::UnsupportedFormat => UserMessage::new(MessageCode::ImageUnsupported),
+            Self::Decode | Self::InvalidInput => UserMessage::new(MessageCode::ImageInvalid),
+            Self::Encode => UserMessage::new(MessageCode::Internal),
+            Self::Storage(_) => UserMessage::new(MessageCode::Storage),
+        }
+    }
+}
+
+impl Localize for ProfileError {
+    fn user_message(&self) -> UserMessage {
+        match self {
+            Self::Blossom(_) | Self::Http(_) => UserMessage::new(MessageCode::NetworkError),
+            Self::Timeout => UserMessage::new(MessageCode::Timeout),
+            Self::HashMismatch => UserMessage::new(MessageCode::InvalidEvent),
+            Self::InsecureUrl | Self::BadUrl => UserMessage::new(MessageCode::InsecureUrl),
+            Self::InvalidIdentifier => UserMessage::new(MessageCode::InvalidInput),
+            Self::Nip05NotFound => UserMessage::new(MessageCode::ContactNotFound),
+            Self::TooLarge => UserMessage::new(MessageCode::ImageTooLarge),
+            Self::Build(_) => UserMessage::new(MessageCode::Internal),
+            Self::Relay(_) => UserMessage::new(MessageCode::PublishFailed),
+            Self::NoRelays => UserMessage::new(MessageCode::NoRelays),
+            Self::Image(e) => e.user_message(),
+            Self::Sqlite(_) => UserMessage::new(MessageCode::Storage),
+        }
+    }
+}
+
+impl Localize for TileCacheError {
+    fn user_message(&self) -> UserMessage {
+        match self {
+            Self::InvalidKey => UserMessage::new(MessageCode::InvalidInput),
+            Self::Storage(_) | Self::DecryptFailed | Self::SchemaVersionMismatch | Self::Io(_) => {
+                UserMessage::new(MessageCode::Storage)
+            }
+        }
+    }
+}
+
+/// The notification to show for a live-sync event, if it warrants one.
+///
+/// Locations, roster updates and status changes update the UI silently and
+/// return `None`.
+#[must_use]
+pub fn notification_for(event: &LiveSyncEvent) -> Option<UserMessage> {
+    match event {
+        LiveSyncEvent::Sos { sender_pubkey, .. } => Some(
+            UserMessage::new(MessageCode::NotificationSos)
+                .with("sender_pubkey", sender_pubkey.clone()),
+        ),
+        LiveSyncEvent::MeetPin { sender_pubkey, .. } => Some(
+            UserMessage::new(MessageCode::NotificationMeetPin)
+                .with("sender_pubkey", sender_pubkey.clone()),
+        ),
+        LiveSyncEvent::CheckinRequest {
+            sender_pubkey,
+            target_pubkey,
+            ..
+        } => Some(
+            UserMessage::new(MessageCode::NotificationCheckinRequest)
+                .with("sender_pubkey", sender_pubkey.clone())
+                .with("target_pubkey", target_pubkey.clone()),
+        ),
+        LiveSyncEvent::PrecisionAnomaly {
+            sender_pubkey,
+            baseline,
+            observed,
+            ..
+        } => Some(
+            UserMessage::new(MessageCode::NotificationPrecisionChanged)
+                .with("sender_pubkey", sender_pubkey.clone())
+                .with("baseline", baseline.as_str())
+                .with("observed", observed.as_str()),
+        ),
+        LiveSyncEvent::Welcome { .. } => {
+            Some(UserMessage::new(MessageCode::NotificationInvitation))
+        }
+        LiveSyncEvent::Location { .. }
+        | LiveSyncEvent::GroupUpdate { .. }
+        | LiveSyncEvent::Status { .. } => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::location::PrecisionClass;
+
+    #[test]
+    fn english_fills_parameters() {
+        let message = RelayError::Rejected {
+            relay: "wss://relay.example.com".to_string(),
+            reason: "blocked: spam".to_string(),
+        }
+        .user_message();
+        assert_eq!(message.code.as_str(), "relay.rejected");
+        assert_eq!(
+            message.english(),
+            "wss://relay.example.com refused the update: blocked: spam"
+        );
+    }
+
+    #[test]
+    fn internal_detail_stays_out_of_the_user_message() {
+        let error = CircleError::Mls("engine detail deadbeef".to_string());
+        let message = error.user_message();
+        assert_eq!(message.code, MessageCode::GroupError);
+        assert!(message.params.is_empty());
+        assert!(!message.english().contains("deadbeef"));
+        // The log text keeps the detail.
+        assert!(error.to_string().contains("deadbeef"));
+    }
+
+    #[test]
+    fn nested_errors_use_the_inner_message() {
+        let error = ProfileError::Image(AvatarError::UnsupportedFormat);
+        assert_eq!(error.user_message().code, MessageCode::ImageUnsupported);
+    }
+
+    #[test]
+    fn notifications_only_for_attention_events() {
+        let anomaly = LiveSyncEvent::PrecisionAnomaly {
+            nostr_group_id: vec![1],
+            sender_pubkey: "abc".to_string(),
+            baseline: PrecisionClass::Coarse,
+            observed: PrecisionClass::Exact,
+            event_created_at_secs: 0,
+        };
+        let message = notification_for(&anomaly).expect("notifies");
+        assert_eq!(message.code.as_str(), "notification.precision_changed");
+        assert_eq!(
+            message.english(),
+            "abc is now sharing exact locations (was coarse)."
+        );
+
+        let location = LiveSyncEvent::Location {
+            nostr_group_id: vec![1],
+            sender_pubkey: "abc".to_string(),
+            content: String::new(),
+            event_created_at_secs: 0,
+        };
+        assert!(notification_for(&location).is_none());
+    }
+
+    #[test]
+    fn codes_are_unique() {
+        let codes = [
+            MessageCode::Internal,
+            MessageCode::InvalidInput,
+            MessageCode::Storage,
+            MessageCode::Unsupported,
+            MessageCode::CircleNotFound,
+            MessageCode::ContactNotFound,
+            MessageCode::CircleAlreadyExists,
+            MessageCode::MembershipConflict,
+            MessageCode::CircleRemoved,
+            MessageCode::LastMember,
+            MessageCode::IllegalTransition,
+            MessageCode::CircleIdCollision,
+            MessageCode::AdminMustStepDown,
+            MessageCode::NotAdmin,
+            MessageCode::SharingPaused,
+            MessageCode::GroupError,
+            MessageCode::InvitationAlreadyProcessed,
+            MessageCode::InvitationNoRelays,
+            MessageCode::EncryptionFailed,
+            MessageCode::DecryptionFailed,
+            MessageCode::InvalidSignature,
+            MessageCode::MessageExpired,
+            MessageCode::InvalidEvent,
+            MessageCode::IdentityMissing,
+            MessageCode::IdentityExists,
+            MessageCode::InvalidKey,
+            MessageCode::InvalidKeyBackup,
+            MessageCode::RelayUnreachable,
+            MessageCode::PublishFailed,
+            MessageCode::RelayRejected,
+            MessageCode::InvalidRelayUrl,
+            MessageCode::RelayBlacklisted,
+            MessageCode::FetchFailed,
+            MessageCode::NoRelays,
+            MessageCode::ProofOfWorkFailed,
+            MessageCode::SyncNotRunning,
+            MessageCode::Timeout,
+            MessageCode::NetworkError,
+            MessageCode::InsecureUrl,
+            MessageCode::ImageTooLarge,
+            MessageCode::ImageUnsupported,
+            MessageCode::ImageInvalid,
+            MessageCode::NotificationSos,
+            MessageCode::NotificationMeetPin,
+            MessageCode::NotificationCheckinRequest,
+            MessageCode::NotificationPrecisionChanged,
+            MessageCode::NotificationInvitation,
+            MessageCode::NotificationMemberKeyChanged,
+            MessageCode::LocationCellArea,
+            MessageCode::InvitationResendLimited,
+            MessageCode::StorageIncompatible,
+            MessageCode::RemoteSignerRefused,
+            MessageCode::RemoteSignerWrongIdentity,
+            MessageCode::RemoteSignerApproval,
+        ];
+        let unique: std::collections::HashSet<_> = codes.iter().map(|c| c.as_str()).collect();
+        assert_eq!(unique.len(), codes.len());
+    }
+}
@@ -73,6 +73,16 @@ pub enum NostrError {
     #[error("Storage error: {0}")]
     StorageError(String),
 
+    /// The MLS database was written in a storage format this build cannot
+    /// open (see [`crate::nostr::mls::storage::MLS_STORAGE_FORMAT`]).
+    #[error("MLS storage format {found} is not supported (this build reads up to {supported})")]
+    IncompatibleMlsStorage {
+        /// Format recorded for the database.
+        found: u32,
+        /// Newest format this build reads.
+        supported: u32,
+    },
+
     /// Gift-wrapping failed.
     #[error("Gift wrap failed: {0}")]
     GiftWrap(String),
@@ -3,11 +3,16 @@
 //! This module defines the event structures for encrypted location messages:
 //! - `UnsignedLocationEvent`: Inner event (kind 9) containing location data
 //! - `SignedLocationEvent`: Outer event (kind 445) ready for relay transmission
+//!
+//! [`validate_group_event`] is the gate every incoming kind 445 passes before
+//! it reaches MLS processing.
+
+use std::sync::Mutex;
 
 use chrono::{DateTime, TimeZone, Utc};
 use serde::{Deserialize, Serialize};
 use sha2::{Digest, Sha256};
-use subtle::ConstantTimeEq;
+use thiserror::Error;
 
 use crate::location::LocationMessage;
 use crate::nostr::error::{NostrError, Result};
@@ -23,6 +28,147 @@ pub const KIND_GROUP_MESSAGE: u16 = 445;
 /// A `["t", "location"]` tag distinguishes location messages from chat messages.
 pub const KIND_LOCATION_DATA: u16 = 9;
 
+/// Largest `content` accepted on an incoming group event, in bytes.
+///
+/// Location updates and commits for circles of Haven's size are a few
+/// kilobytes; anything near this is not worth handing to MLS.
+pub const MAX_GROUP_EVENT_CONTENT_BYTES: usize = 256 * 1024;
+
+/// Why [`validate_group_event`] refused an incoming event.
+#[derive(Error, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GroupEventRejection {
+    /// Not a kind 445 group message.
+    #[error("not a group message")]
+    WrongKind,
+
+    /// No `h` tag, more than one, or a value that is not a 32-byte hex id.
+    #[error("missing or malformed group tag")]
+    InvalidGroupTag,
+
+    /// NIP-40 expiration passed, beyond the receiver grace window.
+    #[error("event expired")]
+    Expired,
+
+    /// The id does not match the content or the signature does not verify.
+    #[error("invalid id or signature")]
+    InvalidSignature,
+
+    /// `content` is larger than [`MAX_GROUP_EVENT_CONTENT_BYTES`].
+    #[error("content too large")]
+    ContentTooLarge,
+}
+
+/// Incoming group events refused by [`validate_group_event`] since the
+/// process started, per reason.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct GroupEventRejects {
+    /// [`GroupEventRejection::WrongKind`] count.
+    pub wrong_kind: u64,
+    /// [`GroupEventRejection::InvalidGroupTag`] count.
+    pub invalid_group_tag: u64,
+    /// [`GroupEventRejection::Expired`] count.
+    pub expired: u64,
+    /// [`GroupEventRejection::InvalidSignature`] count.
+    pub invalid_signature: u64,
+    /// [`GroupEventRejection::ContentTooLarge`] count.
+    pub content_too_large: u64,
+}
+
+impl GroupEventRejects {
+    /// Total events rejected.
+    #[must_use]
+    pub const fn total(&self) -> u64 {
+        self.wrong_kind
+            .saturating_add(self.invalid_group_tag)
+            .saturating_add(self.expired)
+            .saturating_add(self.invalid_signature)
+            .saturating_add(self.content_too_large)
+    }
+
+    fn record(&mut self, rejection: GroupEventRejection) {
+        let counter = match rejection {
+            GroupEventRejection::WrongKind => &mut self.wrong_kind,
+            GroupEventRejection::InvalidGroupTag => &mut self.invalid_group_tag,
+            GroupEventRejection::Expired => &mut self.expired,
+            GroupEventRejection::InvalidSignature => &mut self.invalid_signature,
+            GroupEventRejection::ContentTooLarge => &mut self.content_too_large,
+        };
+        *counter = counter.saturating_add(1);
+    }
+}
+
+static REJECTS: Mutex<GroupEventRejects> = Mutex::new(GroupEventRejects {
+    wrong_kind: 0,
+    invalid_group_tag: 0,
+    expired: 0,
+    invalid_signature: 0,
+    content_too_large: 0,
+});
+
+/// Snapshot of the reject counters.
+#[must_use]
+pub fn group_event_rejects() -> GroupEventRejects {
+    REJECTS.lock().map(|r| *r).unwrap_or_default()
+}
+
+/// Checks an incoming kind 445 before it is handed to MLS.
+///
+/// Cheap structural checks run first — kind, content size, a single `h` tag
+/// holding a 64-character hex group id, and NIP-40 expiration (with the
+/// same [`RECEIVER_EXPIRATION_GRACE_SECS`] window the session applies) —
+/// then the id and signature, through the verified-event cache shared with
+/// the fetch path. Every rejection is counted in [`group_event_rejects`].
+///
+/// [`RECEIVER_EXPIRATION_GRACE_SECS`]: crate::location::ttl::RECEIVER_EXPIRATION_GRACE_SECS
+///
+/// # Errors
+///
+/// Returns the first check the event failed.
+pub fn validate_group_event(event: &nostr::Event) -> std::result::Result<(), GroupEventRejection> {
+    let outcome = check_group_event(event);
+    if let Err(rejection) = outcome {
+        if let Ok(mut rejects) = REJECTS.lock() {
+            rejects.record(rejection);
+        }
+    }
+    outcome
+}
+
+fn check_group_event(event: &nostr::Event) -> std::result::Result<(), GroupEventRejection> {
+    if event.kind != nostr::Kind::Custom(KIND_GROUP_MESSAGE) {
+        return Err(GroupEventRejection::WrongKind);
+    }
+    if event.content.len() > MAX_GROUP_EVENT_CONTENT_BYTES {
+        return Err(GroupEventRejection::ContentTooLarge);
+    }
+    let mut group_tags = event
+        .tags
+        .iter()
+        .map(nostr::Tag::as_slice)
+        .filter(|tag| tag.first().map(String::as_str) == Some("h"));
+    let group_id = group_tags.next().and_then(|tag| tag.get(1));
+    let well_formed =
+        group_id.is_some_and(|id| id.len() == 64 && id.bytes().all(|b| b.is_ascii_hexdigit()));
+    if !well_formed || group_tags.next().is_some() {
+        return Err(GroupEventRejection::InvalidGroupTag);
+    }
+    if let Some(expires_at) = event.tags.iter().find_map(|t| match t.as_standardized() {
+        Some(nostr::TagStandard::Expiration(ts)) => Some(*ts),
+        _ => None,
+    }) {
+        let deadline = expires_at
+            .as_secs()
+            .saturating_add(crate::location::ttl::RECEIVER_EXPIRATION_GRACE_SECS);
+        if nostr::Timestamp::now().as_secs() > deadline {
+            return Err(GroupEventRejection::Expired);
+        }
+    }
+    if !crate::relay::verify_event(event) {
+        return Err(GroupEventRejection::InvalidSignature);
+    }
+    Ok(())
+}
+
 /// An unsigned Nostr event containing location data.
 ///
 /// This is the inner event that gets encrypted before being wrapped
@@ -363,9 +509,7 @@ impl SignedLocationEvent {
             &self.content,
         )?;
 
-        if calculated_id.as_bytes().ct_eq(self.id.as_bytes()).into() {
-            // IDs match, continue with signature verification
-        } else {
+        if !crate::util::ct_eq(calculated_id.as_bytes(), self.id.as_bytes()) {
             return Err(NostrError::InvalidEvent("Event ID mismatch".to_string()));
         }
 
@@ -954,4 +1098,93 @@ mod tests {
             "alt tag must not contain 'family': got '{alt_value}'"
         );
     }
+
+    fn group_event(content: &str, tags: Vec<nostr::Tag>) -> nostr::Event {
+        nostr::EventBuilder::new(nostr::Kind::Custom(KIND_GROUP_MESSAGE), content)
+            .tags(tags)
+            .sign_with_keys(&nostr::Keys::generate())
+            .unwrap()
+    }
+
+    fn h_tag(value: &str) -> nostr::Tag {
+        nostr::Tag::parse(["h", value]).unwrap()
+    }
+
+    #[test]
+    fn validate_group_event_accepts_a_well_formed_event() {
+        let event = group_event(
+            "ciphertext",
+            vec![
+                h_tag(&"ab".repeat(32)),
+                nostr::Tag::expiration(nostr::Timestamp::from(
+                    nostr::Timestamp::now().as_secs() + 600,
+                )),
+            ],
+        );
+        assert_eq!(validate_group_event(&event), Ok(()));
+    }
+
+    #[test]
+    fn validate_group_event_rejects_each_failure_and_counts_it() {
+        let group = "ab".repeat(32);
+        let before = group_event_rejects();
+
+        let wrong_kind = nostr::EventBuilder::new(nostr::Kind::Custom(1), "x")
+            .tags([h_tag(&group)])
+            .sign_with_keys(&nostr::Keys::generate())
+            .unwrap();
+        assert_eq!(
+            validate_group_event(&wrong_kind),
+            Err(GroupEventRejection::WrongKind)
+        );
+        for tags in [
+            vec![],
+            vec![h_tag("not-hex")],
+            vec![h_tag(&group), h_tag(&"cd".repeat(32))],
+        ] {
+            assert_eq!(
+                validate_group_event(&group_event("x", tags)),
+                Err(GroupEventRejection::InvalidGroupTag)
+            );
+        }
+        let expired = group_event(
+            "x",
+            vec![
+                h_tag(&group),
+                nostr::Tag::expiration(nostr::Timestamp::from(
+                    nostr::Timestamp::now().as_secs()
+                        - crate::location::ttl::RECEIVER_EXPIRATION_GRACE_SECS
+                        - 60,
+                )),
+            ],
+        );
+        assert_eq!(
+            validate_group_event(&expired),
+            Err(GroupEventRejection::Expired)
+        );
+        let oversized = group_event(
+            &"a".repeat(MAX_GROUP_EVENT_CONTENT_BYTES + 1),
+            vec![h_tag(&group)],
+        );
+        assert_eq!(
+            validate_group_event(&oversized),
+            Err(GroupEventRejection::ContentTooLarge)
+        );
+        let mut forged = serde_json::to_value(group_event("x", vec![h_tag(&group)])).unwrap();
+        forged["content"] = serde_json::Value::String("tampered".to_string());
+        let forged = <nostr::Event as nostr::JsonUtil>::from_json(forged.to_string()).unwrap();
+        assert_eq!(
+            validate_group_event(&forged),
+            Err(GroupEventRejection::InvalidSignature)
+        );
+
+        // Other tests share the counters, so only a lower bound holds.
+        let after = group_event_rejects();
+        assert!(after.wrong_kind > before.wrong_kind);
+        assert!(after.invalid_group_tag >= before.invalid_group_tag + 3);
+        assert!(after.expired > before.expired);
+        assert!(after.content_too_large > before.content_too_large);
+        assert!(after.invalid_signature > before.invalid_signature);
+        assert!(after.total() >= before.total() + 7);
+    }
 }
@@ -30,17 +30,27 @@
 //! - **Unsigned rumor**: Kind 444 cannot be published even if leaked
 //! - **Ephemeral keys**: Fresh keypair per wrap, never stored
 //! - **Timestamp randomization**: ±48 hours to prevent timing correlation
+//!
+//! # Name hints
+//!
+//! An inviter may add a `["name_hint", "<name>"]` tag to the Welcome rumor —
+//! the name they would like to be saved as ("Dad"). It travels inside the
+//! seal, so only the recipient can read it. It is **unverified**: the inviter
+//! chooses it freely, so the recipient only ever shows it as a suggestion
+//! ([`UnwrappedWelcome::name_hint`]).
 
 use std::time::Duration;
 
 use nostr::nips::nip59::UnwrappedGift as NostrUnwrappedGift;
-use nostr::{Event, EventBuilder, EventId, Keys, Kind, PublicKey, Tag, Timestamp, UnsignedEvent};
+use nostr::{Event, EventId, Keys, Kind, PublicKey, Tag, TagKind, Timestamp, UnsignedEvent};
 
 /// Welcome events expire after 30 days. Recipients who haven't processed
 /// the invitation by then must be re-invited.
 const WELCOME_EXPIRATION_SECS: u64 = 30 * 24 * 60 * 60;
 
 use super::error::{NostrError, Result};
+use super::identity::{gift_wrap, Signer};
+use crate::privacy::PrivacySettings;
 
 /// Kind for Welcome events (MLS group invitation).
 pub const KIND_WELCOME: u16 = 444;
@@ -48,6 +58,12 @@ pub const KIND_WELCOME: u16 = 444;
 /// Kind for Gift Wrap (NIP-59).
 pub const KIND_GIFT_WRAP: u16 = 1059;
 
+/// Welcome-rumor tag carrying the inviter's self-chosen name hint.
+pub const NAME_HINT_TAG: &str = "name_hint";
+
+/// Maximum length of a name hint, in characters.
+pub const MAX_NAME_HINT_CHARS: usize = 64;
+
 /// Result of unwrapping a gift-wrapped Welcome event.
 #[derive(Clone)]
 pub struct UnwrappedWelcome {
@@ -61,6 +77,24 @@ pub struct UnwrappedWelcome {
     pub rumor: UnsignedEvent,
 }
 
+impl UnwrappedWelcome {
+    /// The inviter's self-chosen name hint, if the rumor carries one.
+    ///
+    /// Sanitized with [`sanitize_name_hint`]. Unverified — show it only as a
+    /// suggested contact name.
+    #[must_use]
+    pub fn name_hint(&self) -> Option<String> {
+        self.rumor.tags.iter().find_map(|tag| {
+            let v = tag.as_slice();
+            if v.len() >= 2 && v[0] == NAME_HINT_TAG {
+                sanitize_name_hint(&v[1])
+            } else {
+                None
+            }
+        })
+    }
+}
+
 impl std::fmt::Debug for UnwrappedWelcome {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("UnwrappedWelcome")
@@ -80,9 +114,11 @@ impl std::fmt::Debug for UnwrappedWelcome {
 ///
 /// # Arguments
 ///
-/// * `sender_keys` - The inviter's Nostr identity keys
+/// * `sender` - Signs as the inviter's Nostr identity
 /// * `recipient_pubkey` - The invitee's public key
 /// * `welcome_rumor` - The unsigned kind 444 event from MDK
+/// * `privacy` - Fuzzes the NIP-40 `expiration`, which would otherwise
+///   reveal the exact send time the randomized `created_at` hides
 ///
 /// # Returns
 ///
@@ -99,10 +135,11 @@ impl std::fmt::Debug for UnwrappedWelcome {
 /// - Uses a fresh ephemeral keypair for the outer layer
 /// - Randomizes timestamp by ±48 hours
 /// - Never stores or logs ephemeral keys
-pub async fn wrap_welcome(
-    sender_keys: &Keys,
+pub fn wrap_welcome(
+    sender: &dyn Signer,
     recipient_pubkey: &PublicKey,
     welcome_rumor: UnsignedEvent,
+    privacy: &PrivacySettings,
 ) -> Result<Event> {
     // Verify this is a kind 444 Welcome event
     if welcome_rumor.kind != Kind::Custom(KIND_WELCOME) {
@@ -112,24 +149,71 @@ pub async fn wrap_welcome(
         )));
     }
 
-    // Use nostr's built-in gift wrapping via EventBuilder
-    // This automatically:
-    // - Creates seal (kind 13) with sender's key
-    // - Generates ephemeral keypair for outer layer
-    // - Randomizes timestamp ±48 hours
-    // - NIP-44 encrypts both layers
-    let expiration = Timestamp::now() + Duration::from_secs(WELCOME_EXPIRATION_SECS);
-
-    let gift_wrap = EventBuilder::gift_wrap(
-        sender_keys,
+    // The seal (kind 13) is encrypted and signed through the sender's
+    // signer; the outer layer uses a fresh ephemeral keypair. Both
+    // timestamps are randomized ±48 hours.
+    let sent_at = privacy.fuzz_timestamp(Timestamp::now().as_secs(), u64::MAX);
+    let expiration = Timestamp::from(sent_at) + Duration::from_secs(WELCOME_EXPIRATION_SECS);
+
+    gift_wrap(
+        sender,
         recipient_pubkey,
         welcome_rumor,
         [Tag::expiration(expiration)],
     )
-    .await
-    .map_err(|e| NostrError::GiftWrap(e.to_string()))?;
+    .map_err(|e| NostrError::GiftWrap(e.to_string()))
+}
 
-    Ok(gift_wrap)
+/// Trims a name hint, strips control characters, and caps it at
+/// [`MAX_NAME_HINT_CHARS`]. Returns `None` if nothing is left.
+#[must_use]
+pub fn sanitize_name_hint(hint: &str) -> Option<String> {
+    let cleaned: String = hint
+        .trim()
+        .chars()
+        .filter(|c| !c.is_control())
+        .take(MAX_NAME_HINT_CHARS)
+        .collect();
+    let cleaned = cleaned.trim_end().to_string();
+    (!cleaned.is_empty()).then_some(cleaned)
+}
+
+/// Adds a `name_hint` tag to a kind 444 Welcome rumor.
+///
+/// A blank hint leaves the rumor unchanged. The rumor id is recomputed.
+///
+/// # Errors
+///
+/// Returns an error if the rumor is not kind 444.
+pub fn with_name_hint(welcome_rumor: UnsignedEvent, hint: &str) -> Result<UnsignedEvent> {
+    if welcome_rumor.kind != Kind::Custom(KIND_WELCOME) {
+        return Err(NostrError::GiftWrap(format!(
+            "Welcome rumor must be kind {KIND_WELCOME}, got {}",
+            welcome_rumor.kind.as_u16()
+        )));
+    }
+    let Some(hint) = sanitize_name_hint(hint) else {
+        return Ok(welcome_rumor);
+    };
+    let tags: Vec<Tag> = welcome_rumor
+        .tags
+        .iter()
+        .filter(|t| t.as_slice().first().map(String::as_str) != Some(NAME_HINT_TAG))
+        .cloned()
+        .chain(std::iter::once(Tag::custom(
+            TagKind::custom(NAME_HINT_TAG),
+            [hint],
+        )))
+        .collect();
+    let mut rumor = UnsignedEvent::new(
+        welcome_rumor.pubkey,
+        welcome_rumor.created_at,
+        welcome_rumor.kind,
+        tags,
+        welcome_rumor.content,
+    );
+    rumor.ensure_id();
+    Ok(rumor)
 }
 
 /// Unwraps a received gift-wrapped Welcome event.
@@ -191,6 +275,37 @@ pub async fn unwrap_welcome(
     })
 }
 
+/// Unwraps a batch of gift-wrapped Welcomes concurrently, one task per event.
+///
+/// The unwrap is pure crypto (two NIP-44 decryptions and a seal signature
+/// check) with no storage access, so on a multi-threaded runtime the events
+/// are spread across worker threads. Meant as the stage ahead of the
+/// serialized processing in
+/// [`crate::circle::CircleManager::process_gift_wrapped_invitations`], for a
+/// user returning to dozens of wraps after a long time offline.
+///
+/// Results are returned in input order, one per event.
+///
+/// Must be called from within a Tokio runtime.
+pub async fn unwrap_welcomes_parallel(
+    recipient_keys: &Keys,
+    gift_wrap_events: &[Event],
+) -> Vec<Result<UnwrappedWelcome>> {
+    let tasks = gift_wrap_events.iter().map(|event| {
+        let keys = recipient_keys.clone();
+        let event = event.clone();
+        tokio::spawn(async move { unwrap_welcome(&keys, &event).await })
+    });
+    futures::future::join_all(tasks)
+        .await
+        .into_iter()
+        .map(|joined| {
+            joined
+                .unwrap_or_else(|e| Err(NostrError::GiftUnwrap(format!("unwrap task failed: {e}"))))
+        })
+        .collect()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -216,31 +331,39 @@ mod tests {
         )
     }
 
-    #[tokio::test]
-    async fn wrap_welcome_creates_kind_1059() {
+    #[test]
+    fn wrap_welcome_creates_kind_1059() {
         let sender = Keys::generate();
         let recipient = Keys::generate();
         let rumor = create_test_welcome_rumor(&sender);
 
-        let wrapped = wrap_welcome(&sender, &recipient.public_key(), rumor)
-            .await
-            .unwrap();
+        let wrapped = wrap_welcome(
+            &sender,
+            &recipient.public_key(),
+            rumor,
+            &PrivacySettings::default(),
+        )
+        .unwrap();
 
         assert_eq!(wrapped.kind, Kind::GiftWrap);
         // Ephemeral pubkey should differ from sender
         assert_ne!(wrapped.pubkey, sender.public_key());
     }
 
-    #[tokio::test]
-    async fn wrap_welcome_sets_30_day_expiration() {
+    #[test]
+    fn wrap_welcome_sets_30_day_expiration() {
         let sender = Keys::generate();
         let recipient = Keys::generate();
         let before = Timestamp::now();
         let rumor = create_test_welcome_rumor(&sender);
 
-        let wrapped = wrap_welcome(&sender, &recipient.public_key(), rumor)
-            .await
-            .unwrap();
+        let wrapped = wrap_welcome(
+            &sender,
+            &recipient.public_key(),
+            rumor,
+            &PrivacySettings::default(),
+        )
+        .unwrap();
 
         // Find the expiration tag
         let expiration = wrapped
@@ -263,12 +386,64 @@ mod tests {
     }
 
     #[tokio::test]
-    async fn wrap_welcome_rejects_wrong_kind() {
+    async fn name_hint_survives_wrap_and_unwrap() {
+        let sender = Keys::generate();
+        let recipient = Keys::generate();
+        let rumor = with_name_hint(create_test_welcome_rumor(&sender), "  Dad\u{7} ").unwrap();
+
+        let wrapped = wrap_welcome(
+            &sender,
+            &recipient.public_key(),
+            rumor,
+            &PrivacySettings::default(),
+        )
+        .unwrap();
+        assert!(!wrapped.as_json().contains("Dad"), "hint is encrypted");
+
+        let unwrapped = unwrap_welcome(&recipient, &wrapped).await.unwrap();
+        assert_eq!(unwrapped.name_hint().as_deref(), Some("Dad"));
+    }
+
+    #[test]
+    fn with_name_hint_replaces_and_ignores_blank() {
+        let sender = Keys::generate();
+        let rumor = with_name_hint(create_test_welcome_rumor(&sender), "Mom").unwrap();
+        let rumor = with_name_hint(rumor, "Mum").unwrap();
+        let hints: Vec<_> = rumor
+            .tags
+            .iter()
+            .filter(|t| t.as_slice()[0] == NAME_HINT_TAG)
+            .collect();
+        assert_eq!(hints.len(), 1);
+        assert_eq!(hints[0].as_slice()[1], "Mum");
+
+        let untouched = with_name_hint(create_test_welcome_rumor(&sender), "   ").unwrap();
+        assert!(untouched.tags.is_empty());
+        assert!(with_name_hint(create_wrong_kind_rumor(&sender), "Dad").is_err());
+    }
+
+    #[test]
+    fn sanitize_name_hint_caps_length() {
+        let long = "x".repeat(MAX_NAME_HINT_CHARS + 10);
+        assert_eq!(
+            sanitize_name_hint(&long).unwrap().chars().count(),
+            MAX_NAME_HINT_CHARS
+        );
+        assert!(sanitize_name_hint("\n\t").is_none());
+    }
+
+    #[test]
+    fn wrap_welcome_rejects_wrong_kind() {
         let sender = Keys::generate();
         let recipient = Keys::generate();
         let wrong_rumor = create_wrong_kind_rumor(&sender);
 
-        let result = wrap_welcome(&sender, &recipient.public_key(), wrong_rumor).await;
+        let result = wrap_welcome(
+            &sender,
+            &recipient.public_key(),
+            wrong_rumor,
+            &PrivacySettings::default(),
+        );
 
         assert!(result.is_err());
         if let Err(NostrError::GiftWrap(msg)) = result {
@@ -285,9 +460,13 @@ mod tests {
         let rumor = create_test_welcome_rumor(&sender);
         let original_content = rumor.content.clone();
 
-        let wrapped = wrap_welcome(&sender, &recipient.public_key(), rumor)
-            .await
-            .unwrap();
+        let wrapped = wrap_welcome(
+            &sender,
+            &recipient.public_key(),
+            rumor,
+            &PrivacySettings::default(),
+        )
+        .unwrap();
 
         let unwrapped = unwrap_welcome(&recipient, &wrapped).await.unwrap();
 
@@ -304,29 +483,68 @@ mod tests {
         let wrong_recipient = Keys::generate();
         let rumor = create_test_welcome_rumor(&sender);
 
-        let wrapped = wrap_welcome(&sender, &intended_recipient.public_key(), rumor)
-            .await
-            .unwrap();
+        let wrapped = wrap_welcome(
+            &sender,
+            &intended_recipient.public_key(),
+            rumor,
+            &PrivacySettings::default(),
+        )
+        .unwrap();
 
         // Wrong recipient should fail to unwrap
         let result = unwrap_welcome(&wrong_recipient, &wrapped).await;
         assert!(result.is_err());
     }
 
-    #[tokio::test]
-    async fn ephemeral_keys_are_unique() {
+    #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+    async fn parallel_unwrap_keeps_input_order_and_per_event_errors() {
+        let sender = Keys::generate();
+        let recipient = Keys::generate();
+        let other = Keys::generate();
+        let mut events = Vec::new();
+        for to in [&recipient, &other, &recipient] {
+            let rumor = create_test_welcome_rumor(&sender);
+            events.push(
+                wrap_welcome(
+                    &sender,
+                    &to.public_key(),
+                    rumor,
+                    &PrivacySettings::default(),
+                )
+                .unwrap(),
+            );
+        }
+
+        let results = unwrap_welcomes_parallel(&recipient, &events).await;
+        assert_eq!(results.len(), 3);
+        assert_eq!(results[0].as_ref().unwrap().wrapper_event_id, events[0].id);
+        assert!(results[1].is_err());
+        assert_eq!(results[2].as_ref().unwrap().wrapper_event_id, events[2].id);
+        assert!(unwrap_welcomes_parallel(&recipient, &[]).await.is_empty());
+    }
+
+    #[test]
+    fn ephemeral_keys_are_unique() {
         let sender = Keys::generate();
         let recipient = Keys::generate();
 
         let rumor1 = create_test_welcome_rumor(&sender);
         let rumor2 = create_test_welcome_rumor(&sender);
 
-        let wrapped1 = wrap_welcome(&sender, &recipient.public_key(), rumor1)
-            .await
-            .unwrap();
-        let wrapped2 = wrap_welcome(&sender, &recipient.public_key(), rumor2)
-            .await
-            .unwrap();
+        let wrapped1 = wrap_welcome(
+            &sender,
+            &recipient.public_key(),
+            rumor1,
+            &PrivacySettings::default(),
+        )
+        .unwrap();
+        let wrapped2 = wrap_welcome(
+            &sender,
+            &recipient.public_key(),
+            rumor2,
+            &PrivacySettings::default(),
+        )
+        .unwrap();
 
         // Each wrap should use a different ephemeral key
         assert_ne!(wrapped1.pubkey, wrapped2.pubkey);
@@ -342,8 +560,8 @@ mod tests {
     /// Verifies the gift-wrap outer layer (kind 1059) does not contain
     /// readable MLS data or recipient identifying information in its
     /// serialized JSON form.
-    #[tokio::test]
-    async fn d8_gift_wrap_hides_mls_data_and_recipient_pubkey() {
+    #[test]
+    fn d8_gift_wrap_hides_mls_data_and_recipient_pubkey() {
         let sender = Keys::generate();
         let recipient = Keys::generate();
 
@@ -357,9 +575,13 @@ mod tests {
             mls_welcome_data.to_string(),
         );
 
-        let wrapped = wrap_welcome(&sender, &recipient.public_key(), rumor)
-            .await
-            .unwrap();
+        let wrapped = wrap_welcome(
+            &sender,
+            &recipient.public_key(),
+            rumor,
+            &PrivacySettings::default(),
+        )
+        .unwrap();
 
         // Serialize the outer event to JSON
         let json = wrapped.as_json();
@@ -384,4 +606,36 @@ mod tests {
             "Gift wrap JSON must not reveal inner event kind"
         );
     }
+
+    #[test]
+    fn fuzzed_welcome_expiration_stays_in_the_window() {
+        let sender = Keys::generate();
+        let recipient = Keys::generate();
+        let privacy = PrivacySettings {
+            timestamp_fuzz_minutes: 10,
+            ..PrivacySettings::default()
+        };
+        let before = Timestamp::now().as_secs();
+        let rumor = UnsignedEvent::new(
+            sender.public_key(),
+            Timestamp::now(),
+            Kind::Custom(KIND_WELCOME),
+            Vec::new(),
+            "welcome".to_string(),
+        );
+
+        let wrapped = wrap_welcome(&sender, &recipient.public_key(), rumor, &privacy).unwrap();
+
+        let expiration = wrapped
+            .tags
+            .iter()
+            .find_map(|t| match t.as_standardized() {
+                Some(nostr::TagStandard::Expiration(ts)) => Some(ts.as_secs()),
+                _ => None,
+            })
+            .expect("gift wrap carries an expiration");
+        let after = Timestamp::now().as_secs();
+        assert!(expiration >= before - 600 + WELCOME_EXPIRATION_SECS);
+        assert!(expiration <= after + 600 + WELCOME_EXPIRATION_SECS);
+    }
 }
@@ -0,0 +1,154 @@
+//! NIP-19 key parsing and validation.
+//!
+//! Text fields that take a key (add contact, import identity, paste an
+//! invite) should reject a bad key before anything acts on it, with the same
+//! rules the core applies later. These helpers are pure: [`validate_nsec`]
+//! parses a secret key without importing or storing it, and no error carries
+//! the input, so a mistyped secret never reaches a log.
+//!
+//! Public-key inputs accept an optional NIP-21 `nostr:` prefix, as produced by
+//! "share profile" links.
+
+use nostr::nips::nip19::{FromBech32, Nip19Profile, ToBech32};
+use nostr::{PublicKey, SecretKey};
+
+use super::IdentityError;
+
+const NIP21_SCHEME: &str = "nostr:";
+
+/// A NIP-19 `nprofile`: a public key with relay hints.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProfilePointer {
+    /// The profile's public key.
+    pub pubkey: PublicKey,
+    /// Relays the profile is said to publish to, in encoded order.
+    pub relays: Vec<String>,
+}
+
+fn strip_scheme(input: &str) -> &str {
+    let trimmed = input.trim();
+    trimmed
+        .get(..NIP21_SCHEME.len())
+        .filter(|scheme| scheme.eq_ignore_ascii_case(NIP21_SCHEME))
+        .map_or(trimmed, |_| &trimmed[NIP21_SCHEME.len()..])
+}
+
+/// Parses an `npub1...` string (optionally `nostr:`-prefixed).
+///
+/// # Errors
+///
+/// Returns [`IdentityError::InvalidNpub`] if the input is not a valid npub
+/// (hex keys and other NIP-19 entities are rejected).
+pub fn validate_npub(npub: &str) -> Result<PublicKey, IdentityError> {
+    PublicKey::from_bech32(strip_scheme(npub))
+        .map_err(|_| IdentityError::InvalidNpub("not a valid npub".to_string()))
+}
+
+/// Converts an npub to its 64-character hex public key.
+///
+/// # Errors
+///
+/// Returns [`IdentityError::InvalidNpub`] if the input is not a valid npub.
+pub fn npub_to_hex(npub: &str) -> Result<String, IdentityError> {
+    validate_npub(npub).map(|pk| pk.to_hex())
+}
+
+/// Converts a 64-character hex public key to its npub.
+///
+/// # Errors
+///
+/// Returns [`IdentityError::InvalidNpub`] if the input is not a valid hex
+/// public key, or [`IdentityError::Bech32`] if encoding fails.
+pub fn hex_to_npub(hex: &str) -> Result<String, IdentityError> {
+    let hex = hex.trim();
+    if hex.len() != 64 {
+        return Err(IdentityError::InvalidNpub(
+            "expected a 64-character hex public key".to_string(),
+        ));
+    }
+    PublicKey::from_hex(hex)
+        .map_err(|_| IdentityError::InvalidNpub("not a valid hex public key".to_string()))?
+        .to_bech32()
+        .map_err(|e| IdentityError::Bech32(e.to_string()))
+}
+
+/// Checks that `nsec` is a valid `nsec1...` secret key without importing it.
+///
+/// Returns the public key it belongs to, so a UI can show which identity an
+/// import would restore. The parsed secret is dropped before returning.
+///
+/// # Errors
+///
+/// Returns [`IdentityError::InvalidNsec`] if the input is not a valid nsec.
+/// The message never includes the input.
+pub fn validate_nsec(nsec: &str) -> Result<PublicKey, IdentityError> {
+    let secret = SecretKey::from_bech32(nsec.trim())
+        .map_err(|_| IdentityError::InvalidNsec("not a valid nsec".to_string()))?;
+    Ok(nostr::Keys::new(secret).public_key())
+}
+
+/// Decodes an `nprofile1...` string (optionally `nostr:`-prefixed).
+///
+/// # Errors
+///
+/// Returns [`IdentityError::InvalidNpub`] if the input is not a valid
+/// nprofile.
+pub fn decode_nprofile(nprofile: &str) -> Result<ProfilePointer, IdentityError> {
+    let profile = Nip19Profile::from_bech32(strip_scheme(nprofile))
+        .map_err(|_| IdentityError::InvalidNpub("not a valid nprofile".to_string()))?;
+    Ok(ProfilePointer {
+        pubkey: profile.public_key,
+        relays: profile.relays.iter().map(ToString::to_string).collect(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use nostr::{Keys, RelayUrl};
+
+    #[test]
+    fn npub_hex_roundtrip() {
+        let pk = Keys::generate().public_key();
+        let npub = hex_to_npub(&pk.to_hex()).unwrap();
+        assert!(npub.starts_with("npub1"));
+        assert_eq!(npub_to_hex(&npub).unwrap(), pk.to_hex());
+        assert_eq!(validate_npub(&format!("nostr:{npub}")).unwrap(), pk);
+
+        assert!(matches!(
+            validate_npub(&pk.to_hex()),
+            Err(IdentityError::InvalidNpub(_))
+        ));
+        assert!(hex_to_npub("abcd").is_err());
+        assert!(hex_to_npub(&"zz".repeat(32)).is_err());
+    }
+
+    #[test]
+    fn nsec_validates_without_echoing_input() {
+        let keys = Keys::generate();
+        let nsec = keys.secret_key().to_bech32().unwrap();
+        assert_eq!(validate_nsec(&nsec).unwrap(), keys.public_key());
+
+        let typo = format!("{}x", &nsec[..nsec.len() - 1]);
+        let err = validate_nsec(&typo).unwrap_err();
+        assert!(matches!(err, IdentityError::InvalidNsec(_)));
+        assert!(!err.to_string().contains(&typo[5..]));
+        // An npub is not a secret key.
+        let npub = keys.public_key().to_bech32().unwrap();
+        assert!(validate_nsec(&npub).is_err());
+    }
+
+    #[test]
+    fn nprofile_carries_relay_hints() {
+        let pk = Keys::generate().public_key();
+        let relays = vec![RelayUrl::parse("wss://relay.example.com").unwrap()];
+        let encoded = Nip19Profile::new(pk, relays).to_bech32().unwrap();
+
+        let decoded = decode_nprofile(&format!("nostr:{encoded}")).unwrap();
+        assert_eq!(decoded.pubkey, pk);
+        assert_eq!(decoded.relays, vec!["wss://relay.example.com".to_string()]);
+
+        let npub = pk.to_bech32().unwrap();
+        assert!(decode_nprofile(&npub).is_err());
+    }
+}
@@ -25,6 +25,7 @@ use nostr::secp256k1::{Keypair, Message, SecretKey as Secp256k1SecretKey};
 use nostr::SecretKey as NostrSecretKey;
 use zeroize::{Zeroize, ZeroizeOnDrop, Zeroizing};
 
+use super::ncryptsec::{self, ScryptParams};
 use super::IdentityError;
 use crate::nostr::keys::SECP;
 
@@ -195,6 +196,32 @@ impl IdentityKeypair {
         result
     }
 
+    /// Exports the secret key as a NIP-49 `ncryptsec`, encrypted under
+    /// `passphrase` with the given scrypt cost.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`IdentityError::Encryption`] if the passphrase is empty or
+    /// encryption fails.
+    pub fn export_encrypted(
+        &self,
+        passphrase: &str,
+        params: ScryptParams,
+    ) -> Result<String, IdentityError> {
+        ncryptsec::encrypt(&self.secret_bytes, passphrase, params)
+    }
+
+    /// Imports an identity from a NIP-49 `ncryptsec`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`IdentityError::InvalidNcryptsec`] if the string is malformed
+    /// or the passphrase is wrong.
+    pub fn from_ncryptsec(ncryptsec: &str, passphrase: &str) -> Result<Self, IdentityError> {
+        let secret_bytes = ncryptsec::decrypt(ncryptsec, passphrase)?;
+        Self::from_secret_bytes(*secret_bytes)
+    }
+
     /// Returns the public key as a 64-character hex string.
     ///
     /// This format is used in Nostr event `pubkey` fields and for MDK operations.
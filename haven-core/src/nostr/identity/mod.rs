@@ -50,7 +50,10 @@
 //! let pubkey_hex = manager.pubkey_hex()?;
 //! ```
 
+mod key_codec;
 mod keypair;
+mod ncryptsec;
+mod signer;
 mod storage;
 
 use std::sync::RwLock;
@@ -59,9 +62,16 @@ use chrono::{DateTime, Utc};
 use thiserror::Error;
 use zeroize::{Zeroize, Zeroizing};
 
+pub use key_codec::{
+    decode_nprofile, hex_to_npub, npub_to_hex, validate_npub, validate_nsec, ProfilePointer,
+};
 pub use keypair::IdentityKeypair;
+pub use ncryptsec::{ScryptParams, DEFAULT_SCRYPT_LOG_N, MAX_SCRYPT_LOG_N, MIN_SCRYPT_LOG_N};
+pub use signer::{gift_wrap, sign_event, sign_unsigned, Signer};
 pub use storage::{SecureKeyStorage, NOSTR_IDENTITY_KEY};
 
+use crate::device_link::LinkedIdentity;
+
 #[cfg(test)]
 pub use storage::tests::MockStorage;
 
@@ -72,6 +82,18 @@ pub enum IdentityError {
     #[error("Invalid nsec: {0}")]
     InvalidNsec(String),
 
+    /// Invalid npub, nprofile or hex public key.
+    #[error("Invalid public key: {0}")]
+    InvalidNpub(String),
+
+    /// Invalid NIP-49 `ncryptsec`, wrong passphrase, or corrupted backup.
+    #[error("Invalid ncryptsec: {0}")]
+    InvalidNcryptsec(String),
+
+    /// NIP-49 encryption failed (empty passphrase, invalid scrypt cost).
+    #[error("Encryption failed: {0}")]
+    Encryption(String),
+
     /// No identity has been created or imported.
     #[error("No identity found")]
     NoIdentity,
@@ -296,29 +318,7 @@ impl<S: SecureKeyStorage> IdentityManager<S> {
         }
 
         let keypair = IdentityKeypair::from_nsec(nsec)?;
-        let identity = PublicIdentity::from_keypair(&keypair)?;
-
-        // Store secret bytes (Zeroizing wrapper auto-clears on drop)
-        let secret = keypair.secret_bytes();
-        self.storage.store(NOSTR_IDENTITY_KEY, secret.as_ref())?;
-
-        // Cache the keypair and identity
-        {
-            let mut cache = self
-                .cached_keypair
-                .write()
-                .map_err(|e| IdentityError::Lock(e.to_string()))?;
-            *cache = Some(keypair);
-        }
-        {
-            let mut cache = self
-                .cached_identity
-                .write()
-                .map_err(|e| IdentityError::Lock(e.to_string()))?;
-            *cache = Some(identity.clone());
-        }
-
-        Ok(identity)
+        self.import_keypair(keypair)
     }
 
     /// Gets the public identity information.
@@ -453,6 +453,123 @@ impl<S: SecureKeyStorage> IdentityManager<S> {
             .export_nsec()
     }
 
+    /// Exports the identity as a NIP-49 `ncryptsec` for backup.
+    ///
+    /// Prefer this over [`Self::export_nsec`]: the result is encrypted under
+    /// `passphrase` and safe to store on paper or in cloud storage.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`IdentityError::NoIdentity`] if no identity exists.
+    /// Returns [`IdentityError::Encryption`] if the passphrase is empty.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// use haven_core::nostr::identity::{IdentityManager, MockStorage, ScryptParams};
+    ///
+    /// let manager = IdentityManager::new(MockStorage::new());
+    /// manager.create_identity().unwrap();
+    ///
+    /// let backup = manager
+    ///     .export_encrypted("correct horse battery", ScryptParams::default())
+    ///     .unwrap();
+    /// assert!(backup.starts_with("ncryptsec1"));
+    /// ```
+    pub fn export_encrypted(
+        &self,
+        passphrase: &str,
+        params: ScryptParams,
+    ) -> Result<String, IdentityError> {
+        self.load_keypair()?;
+
+        let cache = self
+            .cached_keypair
+            .read()
+            .map_err(|e| IdentityError::Lock(e.to_string()))?;
+
+        cache
+            .as_ref()
+            .ok_or(IdentityError::NoIdentity)?
+            .export_encrypted(passphrase, params)
+    }
+
+    /// Imports an identity from a NIP-49 `ncryptsec` backup.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`IdentityError::IdentityExists`] if an identity already exists.
+    /// Returns [`IdentityError::InvalidNcryptsec`] if the backup is malformed
+    /// or the passphrase is wrong.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// use haven_core::nostr::identity::{IdentityManager, MockStorage, ScryptParams};
+    ///
+    /// let manager1 = IdentityManager::new(MockStorage::new());
+    /// manager1.create_identity().unwrap();
+    /// let backup = manager1.export_encrypted("pw", ScryptParams::default()).unwrap();
+    ///
+    /// let manager2 = IdentityManager::new(MockStorage::new());
+    /// let identity = manager2.import_encrypted(&backup, "pw").unwrap();
+    /// assert_eq!(identity.pubkey_hex, manager1.pubkey_hex().unwrap());
+    /// ```
+    pub fn import_encrypted(
+        &self,
+        ncryptsec: &str,
+        passphrase: &str,
+    ) -> Result<PublicIdentity, IdentityError> {
+        if self.has_identity()? {
+            return Err(IdentityError::IdentityExists);
+        }
+
+        let keypair = IdentityKeypair::from_ncryptsec(ncryptsec, passphrase)?;
+        self.import_keypair(keypair)
+    }
+
+    /// Imports an identity received from another device over a device link.
+    ///
+    /// Show [`LinkedIdentity::pubkey_hex`] to the user before calling this.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`IdentityError::IdentityExists`] if an identity already exists.
+    pub fn import_linked(&self, linked: LinkedIdentity) -> Result<PublicIdentity, IdentityError> {
+        if self.has_identity()? {
+            return Err(IdentityError::IdentityExists);
+        }
+        let LinkedIdentity { keypair, .. } = linked;
+        self.import_keypair(keypair)
+    }
+
+    /// Stores and caches a new identity's keypair.
+    fn import_keypair(&self, keypair: IdentityKeypair) -> Result<PublicIdentity, IdentityError> {
+        let identity = PublicIdentity::from_keypair(&keypair)?;
+
+        // Store secret bytes (Zeroizing wrapper auto-clears on drop)
+        let secret = keypair.secret_bytes();
+        self.storage.store(NOSTR_IDENTITY_KEY, secret.as_ref())?;
+
+        // Cache the keypair and identity
+        {
+            let mut cache = self
+                .cached_keypair
+                .write()
+                .map_err(|e| IdentityError::Lock(e.to_string()))?;
+            *cache = Some(keypair);
+        }
+        {
+            let mut cache = self
+                .cached_identity
+                .write()
+                .map_err(|e| IdentityError::Lock(e.to_string()))?;
+            *cache = Some(identity.clone());
+        }
+
+        Ok(identity)
+    }
+
     /// Signs a 32-byte message hash using the identity.
     ///
     /// # Arguments
@@ -708,6 +825,21 @@ mod tests {
         assert!(matches!(result, Err(IdentityError::IdentityExists)));
     }
 
+    #[tokio::test]
+    async fn import_linked_takes_the_linked_identity() {
+        use crate::device_link::{wrap_device_link, DeviceLinkSession};
+
+        let old = nostr::Keys::generate();
+        let session = DeviceLinkSession::start(&["wss://r.example.com".to_string()]).unwrap();
+        let wrap = wrap_device_link(&old, session.offer(), &[]).unwrap();
+        let linked = session.open(&wrap).await.unwrap();
+
+        let manager = IdentityManager::new(MockStorage::new());
+        let identity = manager.import_linked(linked).unwrap();
+        assert_eq!(identity.pubkey_hex, old.public_key().to_hex());
+        assert_eq!(manager.pubkey_hex().unwrap(), old.public_key().to_hex());
+    }
+
     #[test]
     fn import_fails_with_invalid_nsec() {
         let manager = IdentityManager::new(MockStorage::new());
@@ -836,6 +968,42 @@ mod tests {
         assert!(nsec1 == nsec2, "exported nsec values should be consistent");
     }
 
+    #[test]
+    fn encrypted_export_import_roundtrip() {
+        let manager1 = IdentityManager::new(MockStorage::new());
+        let identity1 = manager1.create_identity().unwrap();
+        let backup = manager1
+            .export_encrypted("correct horse", ScryptParams::default())
+            .unwrap();
+        assert!(backup.starts_with("ncryptsec1"));
+
+        let manager2 = IdentityManager::new(MockStorage::new());
+        let identity2 = manager2.import_encrypted(&backup, "correct horse").unwrap();
+        assert_eq!(identity1.pubkey_hex, identity2.pubkey_hex);
+        assert!(manager2.has_identity().unwrap());
+    }
+
+    #[test]
+    fn import_encrypted_wrong_passphrase_stores_nothing() {
+        let manager1 = IdentityManager::new(MockStorage::new());
+        manager1.create_identity().unwrap();
+        let backup = manager1
+            .export_encrypted("correct horse", ScryptParams::default())
+            .unwrap();
+
+        let manager2 = IdentityManager::new(MockStorage::new());
+        let result = manager2.import_encrypted(&backup, "wrong");
+        assert!(matches!(result, Err(IdentityError::InvalidNcryptsec(_))));
+        assert!(!manager2.has_identity().unwrap());
+    }
+
+    #[test]
+    fn export_encrypted_fails_without_identity() {
+        let manager = IdentityManager::new(MockStorage::new());
+        let result = manager.export_encrypted("pw", ScryptParams::default());
+        assert!(matches!(result, Err(IdentityError::NoIdentity)));
+    }
+
     #[test]
     fn get_secret_bytes_returns_zeroizing() {
         let manager = IdentityManager::new(MockStorage::new());
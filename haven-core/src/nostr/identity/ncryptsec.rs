@@ -0,0 +1,197 @@
+//! NIP-49 passphrase-encrypted secret key backup (`ncryptsec`).
+//!
+//! An `ncryptsec1…` string is the identity secret encrypted with
+//! XChaCha20-Poly1305 under a key derived from the user's passphrase with
+//! scrypt. Unlike a raw nsec it is safe to write on paper or keep in cloud
+//! storage, provided the passphrase is strong.
+//!
+//! # Security
+//!
+//! - Error messages are fixed strings; they never echo the passphrase or the
+//!   `ncryptsec` input
+//! - Decrypted secret bytes are returned in [`Zeroizing`]
+//! - Passphrases are NFKC-normalized by the NIP-49 implementation, so the same
+//!   passphrase typed on different keyboards decrypts the same backup
+
+use nostr::nips::nip49::{EncryptedSecretKey, KeySecurity};
+use nostr::prelude::{FromBech32, ToBech32};
+use nostr::SecretKey as NostrSecretKey;
+use zeroize::{Zeroize, Zeroizing};
+
+use super::IdentityError;
+
+/// Default scrypt cost (`N = 2^16`, ~64 MiB, well under a second on phones).
+pub const DEFAULT_SCRYPT_LOG_N: u8 = 16;
+
+/// Lowest scrypt cost accepted for new backups.
+///
+/// Anything cheaper makes offline passphrase guessing against a leaked
+/// backup practical.
+pub const MIN_SCRYPT_LOG_N: u8 = 16;
+
+/// Highest scrypt cost accepted for new backups (`N = 2^22`, ~4 GiB).
+///
+/// Higher values exhaust memory on mobile devices.
+pub const MAX_SCRYPT_LOG_N: u8 = 22;
+
+/// scrypt parameters for [`encrypt`].
+///
+/// NIP-49 fixes `r = 8` and `p = 1`; only the cost exponent `log_n` is
+/// tunable. It is stored inside the `ncryptsec`, so decryption needs no
+/// parameters.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ScryptParams {
+    log_n: u8,
+}
+
+impl ScryptParams {
+    /// Creates parameters with the given cost exponent.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`IdentityError::Encryption`] if `log_n` is outside
+    /// [`MIN_SCRYPT_LOG_N`]..=[`MAX_SCRYPT_LOG_N`].
+    pub fn new(log_n: u8) -> Result<Self, IdentityError> {
+        if !(MIN_SCRYPT_LOG_N..=MAX_SCRYPT_LOG_N).contains(&log_n) {
+            return Err(IdentityError::Encryption(format!(
+                "scrypt log_n must be between {MIN_SCRYPT_LOG_N} and {MAX_SCRYPT_LOG_N}, got {log_n}"
+            )));
+        }
+        Ok(Self { log_n })
+    }
+
+    /// Returns the scrypt cost exponent.
+    #[must_use]
+    pub const fn log_n(self) -> u8 {
+        self.log_n
+    }
+}
+
+impl Default for ScryptParams {
+    fn default() -> Self {
+        Self {
+            log_n: DEFAULT_SCRYPT_LOG_N,
+        }
+    }
+}
+
+/// Encrypts `secret_bytes` into an `ncryptsec1…` string.
+///
+/// # Errors
+///
+/// Returns [`IdentityError::Encryption`] if the passphrase is empty or
+/// encryption fails, and [`IdentityError::Bech32`] if encoding fails.
+pub(super) fn encrypt(
+    secret_bytes: &[u8; 32],
+    passphrase: &str,
+    params: ScryptParams,
+) -> Result<String, IdentityError> {
+    if passphrase.is_empty() {
+        return Err(IdentityError::Encryption(
+            "Passphrase must not be empty".to_string(),
+        ));
+    }
+
+    let secret_key = NostrSecretKey::from_slice(secret_bytes)
+        .map_err(|e| IdentityError::KeyDerivation(e.to_string()))?;
+
+    // Haven has offered raw nsec export, so the key's history is unknown.
+    let encrypted =
+        EncryptedSecretKey::new(&secret_key, passphrase, params.log_n, KeySecurity::Unknown)
+            .map_err(|_| IdentityError::Encryption("Encryption failed".to_string()))?;
+
+    encrypted
+        .to_bech32()
+        .map_err(|e| IdentityError::Bech32(e.to_string()))
+}
+
+/// Decrypts an `ncryptsec1…` string into the raw secret bytes.
+///
+/// # Errors
+///
+/// Returns [`IdentityError::InvalidNcryptsec`] if the string is malformed,
+/// the passphrase is wrong, or the backup is corrupted. The cases are
+/// deliberately not distinguished.
+pub(super) fn decrypt(
+    ncryptsec: &str,
+    passphrase: &str,
+) -> Result<Zeroizing<[u8; 32]>, IdentityError> {
+    let encrypted = EncryptedSecretKey::from_bech32(ncryptsec.trim())
+        .map_err(|_| IdentityError::InvalidNcryptsec("Malformed ncryptsec".to_string()))?;
+
+    let secret_key = encrypted.decrypt(passphrase).map_err(|_| {
+        IdentityError::InvalidNcryptsec("Wrong passphrase or corrupted backup".to_string())
+    })?;
+
+    let mut bytes = secret_key.secret_bytes();
+    let out = Zeroizing::new(bytes);
+    bytes.zeroize();
+    Ok(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn scrypt_params_bounds() {
+        assert_eq!(ScryptParams::default().log_n(), DEFAULT_SCRYPT_LOG_N);
+        assert!(ScryptParams::new(MIN_SCRYPT_LOG_N).is_ok());
+        assert!(ScryptParams::new(MAX_SCRYPT_LOG_N).is_ok());
+        assert!(matches!(
+            ScryptParams::new(MIN_SCRYPT_LOG_N - 1),
+            Err(IdentityError::Encryption(_))
+        ));
+        assert!(matches!(
+            ScryptParams::new(MAX_SCRYPT_LOG_N + 1),
+            Err(IdentityError::Encryption(_))
+        ));
+    }
+
+    #[test]
+    fn encrypt_decrypt_roundtrip() {
+        let secret = [7u8; 32];
+        let ncryptsec = encrypt(&secret, "correct horse", ScryptParams::default()).unwrap();
+        assert!(ncryptsec.starts_with("ncryptsec1"));
+
+        let decrypted = decrypt(&ncryptsec, "correct horse").unwrap();
+        assert_eq!(*decrypted, secret);
+    }
+
+    #[test]
+    fn wrong_passphrase_fails_without_echo() {
+        let ncryptsec = encrypt(&[7u8; 32], "correct horse", ScryptParams::default()).unwrap();
+        let err = decrypt(&ncryptsec, "battery staple").unwrap_err();
+        assert!(matches!(err, IdentityError::InvalidNcryptsec(_)));
+        let msg = err.to_string();
+        assert!(!msg.contains("battery"));
+        assert!(!msg.contains(&ncryptsec[10..30]));
+    }
+
+    #[test]
+    fn decrypt_rejects_malformed_input() {
+        assert!(matches!(
+            decrypt("nsec1notanncryptsec", "pw"),
+            Err(IdentityError::InvalidNcryptsec(_))
+        ));
+    }
+
+    #[test]
+    fn decrypt_accepts_nip49_test_vector() {
+        // From the NIP-49 specification.
+        let ncryptsec = "ncryptsec1qgg9947rlpvqu76pj5ecreduf9jxhselq2nae2kghhvd5g7dgjtcxfqtd67p9m0w57lspw8gsq6yphnm8623nsl8xn9j4jdzz84zm3frztj3z7s35vpzmqf6ksu8r89qk5z2zxfmu5gv8th8wclt0h4p";
+        let decrypted = decrypt(ncryptsec, "nostr").unwrap();
+        assert_eq!(
+            hex::encode(*decrypted),
+            "3501454135014541350145413501453fefb02227e449e57cf4d3a3ce05378683"
+        );
+    }
+
+    #[test]
+    fn encrypt_rejects_empty_passphrase() {
+        assert!(matches!(
+            encrypt(&[7u8; 32], "", ScryptParams::default()),
+            Err(IdentityError::Encryption(_))
+        ));
+    }
+}
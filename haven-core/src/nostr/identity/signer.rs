@@ -0,0 +1,250 @@
+//! Signing abstraction over where the identity key lives.
+//!
+//! Event builders take a [`Signer`] rather than raw [`Keys`], so the identity
+//! key can sit somewhere other than this process: a platform keystore
+//! (Android Keystore, iOS Secure Enclave) or a remote signer, reached from the
+//! FFI through callbacks. [`Keys`] implements [`Signer`] for the in-memory
+//! identity, so existing callers pass `&keys` unchanged.
+//!
+//! A signer is asked for two things only:
+//!
+//! - a BIP-340 signature over a NIP-01 event id ([`Signer::sign_event_hash`]);
+//!   [`sign_event`] builds the event, computes the id itself and verifies the
+//!   returned signature before handing the event out, so a misbehaving
+//!   signer cannot produce an event that does not match what was built;
+//! - NIP-44 encryption to a recipient ([`Signer::nip44_encrypt`]), which a
+//!   NIP-59 seal needs ([`gift_wrap`]).
+//!
+//! A NIP-46 remote signer ([`crate::nostr::nip46`]) only signs whole events;
+//! it overrides [`Signer::sign_unsigned_event`] instead, and [`sign_unsigned`]
+//! checks the event it returns just the same.
+//!
+//! # Scope
+//!
+//! The MLS engine binds leaves to the identity with its own hardened proof
+//! signer ([`crate::nostr::mls::signer`]) and still needs the key in memory;
+//! so do NIP-44 decryption paths (unwrapping gift wraps). This trait covers
+//! the Nostr events Haven builds itself: key packages, relay lists,
+//! deletions, proof-of-work re-signing and gift wraps.
+
+use nostr::nips::nip44;
+use nostr::nips::nip59::RANGE_RANDOM_TIMESTAMP_TWEAK;
+use nostr::secp256k1::schnorr::Signature;
+use nostr::secp256k1::{Keypair, Message, SecretKey as Secp256k1SecretKey};
+use nostr::{Event, EventBuilder, JsonUtil, Keys, Kind, PublicKey, Tag, Timestamp, UnsignedEvent};
+use zeroize::Zeroizing;
+
+use super::IdentityError;
+use crate::nostr::keys::SECP;
+
+/// Something that signs and encrypts as the user's Nostr identity.
+pub trait Signer: Send + Sync {
+    /// The identity public key events are signed as.
+    fn public_key(&self) -> PublicKey;
+
+    /// Signs a 32-byte NIP-01 event id (BIP-340 Schnorr).
+    ///
+    /// # Errors
+    ///
+    /// Returns [`IdentityError::Signing`] if the signer refuses or fails.
+    fn sign_event_hash(&self, hash: &[u8; 32]) -> Result<Signature, IdentityError>;
+
+    /// NIP-44 (v2) encrypts `plaintext` to `recipient`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`IdentityError::Encryption`] if the signer refuses or fails.
+    fn nip44_encrypt(
+        &self,
+        recipient: &PublicKey,
+        plaintext: &str,
+    ) -> Result<String, IdentityError>;
+
+    /// Signs `unsigned`, whose id is already computed.
+    ///
+    /// The default signs the id with [`Self::sign_event_hash`]. Callers go
+    /// through [`sign_unsigned`], which verifies the result.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`IdentityError::Signing`] if the signer refuses or fails.
+    fn sign_unsigned_event(&self, unsigned: UnsignedEvent) -> Result<Event, IdentityError> {
+        let id = unsigned
+            .id
+            .ok_or_else(|| IdentityError::Signing("event id missing".to_string()))?;
+        let sig = self.sign_event_hash(id.as_bytes())?;
+        Ok(Event::new(
+            id,
+            unsigned.pubkey,
+            unsigned.created_at,
+            unsigned.kind,
+            unsigned.tags,
+            unsigned.content,
+            sig,
+        ))
+    }
+}
+
+impl Signer for Keys {
+    fn public_key(&self) -> PublicKey {
+        Self::public_key(self)
+    }
+
+    fn sign_event_hash(&self, hash: &[u8; 32]) -> Result<Signature, IdentityError> {
+        let secret = Zeroizing::new(self.secret_key().to_secret_bytes());
+        let secret_key = Secp256k1SecretKey::from_slice(secret.as_slice())
+            .map_err(|e| IdentityError::Signing(e.to_string()))?;
+        let keypair = Keypair::from_secret_key(&SECP, &secret_key);
+        Ok(SECP.sign_schnorr(&Message::from_digest(*hash), &keypair))
+    }
+
+    fn nip44_encrypt(
+        &self,
+        recipient: &PublicKey,
+        plaintext: &str,
+    ) -> Result<String, IdentityError> {
+        nip44::encrypt(self.secret_key(), recipient, plaintext, nip44::Version::V2)
+            .map_err(|e| IdentityError::Encryption(e.to_string()))
+    }
+}
+
+/// Builds `builder` as `signer` and signs it.
+///
+/// # Errors
+///
+/// Returns [`IdentityError::Signing`] if signing fails or the signature does
+/// not verify.
+pub fn sign_event(builder: EventBuilder, signer: &dyn Signer) -> Result<Event, IdentityError> {
+    sign_unsigned(builder.build(signer.public_key()), signer)
+}
+
+/// Signs an already built event. Its author must be `signer`.
+///
+/// # Errors
+///
+/// Returns [`IdentityError::Signing`] if the author is another key, signing
+/// fails, the signer hands back a different event or the signature does not
+/// verify.
+pub fn sign_unsigned(
+    mut unsigned: UnsignedEvent,
+    signer: &dyn Signer,
+) -> Result<Event, IdentityError> {
+    if unsigned.pubkey != signer.public_key() {
+        return Err(IdentityError::Signing(
+            "event author is not the signer".to_string(),
+        ));
+    }
+    // Recompute rather than trust a caller-set id.
+    unsigned.id = None;
+    unsigned.ensure_id();
+    let id = unsigned
+        .id
+        .ok_or_else(|| IdentityError::Signing("event id missing".to_string()))?;
+    let author = unsigned.pubkey;
+    let event = signer.sign_unsigned_event(unsigned)?;
+    if event.id != id || event.pubkey != author {
+        return Err(IdentityError::Signing(
+            "signer returned a different event".to_string(),
+        ));
+    }
+    event
+        .verify()
+        .map_err(|e| IdentityError::Signing(format!("signature does not verify: {e}")))?;
+    Ok(event)
+}
+
+/// NIP-59 gift-wraps `rumor` from `signer` to `recipient`.
+///
+/// The seal (kind 13) is encrypted and signed through `signer`, with a
+/// randomized timestamp; the outer wrap uses a fresh ephemeral key and
+/// carries `extra_tags`. Equivalent to `EventBuilder::gift_wrap`, which
+/// needs the secret key in memory.
+///
+/// # Errors
+///
+/// Returns an [`IdentityError`] if the rumor's author is not `signer`, or
+/// encryption or signing fails.
+pub fn gift_wrap<I>(
+    signer: &dyn Signer,
+    recipient: &PublicKey,
+    mut rumor: UnsignedEvent,
+    extra_tags: I,
+) -> Result<Event, IdentityError>
+where
+    I: IntoIterator<Item = Tag>,
+{
+    if rumor.pubkey != signer.public_key() {
+        return Err(IdentityError::Signing(
+            "rumor author is not the signer".to_string(),
+        ));
+    }
+    rumor.ensure_id();
+    let content = signer.nip44_encrypt(recipient, &rumor.as_json())?;
+    let seal = sign_event(
+        EventBuilder::new(Kind::Seal, content)
+            .custom_created_at(Timestamp::tweaked(RANGE_RANDOM_TIMESTAMP_TWEAK)),
+        signer,
+    )?;
+    EventBuilder::gift_wrap_from_seal(recipient, &seal, extra_tags)
+        .map_err(|e| IdentityError::Encryption(e.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use nostr::nips::nip59::UnwrappedGift;
+
+    /// A signer that returns another key's signatures.
+    struct WrongKey(Keys, Keys);
+
+    impl Signer for WrongKey {
+        fn public_key(&self) -> PublicKey {
+            self.0.public_key()
+        }
+
+        fn sign_event_hash(&self, hash: &[u8; 32]) -> Result<Signature, IdentityError> {
+            self.1.sign_event_hash(hash)
+        }
+
+        fn nip44_encrypt(
+            &self,
+            recipient: &PublicKey,
+            plaintext: &str,
+        ) -> Result<String, IdentityError> {
+            self.0.nip44_encrypt(recipient, plaintext)
+        }
+    }
+
+    #[test]
+    fn keys_sign_events_that_verify() {
+        let keys = Keys::generate();
+        let event = sign_event(EventBuilder::new(Kind::TextNote, "hi"), &keys).unwrap();
+        assert_eq!(event.pubkey, keys.public_key());
+        assert!(event.verify().is_ok());
+    }
+
+    #[test]
+    fn a_signature_from_another_key_is_rejected() {
+        let signer = WrongKey(Keys::generate(), Keys::generate());
+        assert!(matches!(
+            sign_event(EventBuilder::new(Kind::TextNote, "hi"), &signer),
+            Err(IdentityError::Signing(_))
+        ));
+
+        let other = EventBuilder::new(Kind::TextNote, "hi").build(Keys::generate().public_key());
+        assert!(sign_unsigned(other, &Keys::generate()).is_err());
+    }
+
+    #[tokio::test]
+    async fn gift_wraps_open_for_the_recipient_with_the_signer_as_sender() {
+        let (alice, bob) = (Keys::generate(), Keys::generate());
+        let rumor = EventBuilder::new(Kind::Custom(9), "hello").build(alice.public_key());
+        let wrap = gift_wrap(&alice, &bob.public_key(), rumor, []).unwrap();
+        assert_eq!(wrap.kind, Kind::GiftWrap);
+        assert_ne!(wrap.pubkey, alice.public_key());
+
+        let unwrapped = UnwrappedGift::from_gift_wrap(&bob, &wrap).await.unwrap();
+        assert_eq!(unwrapped.sender, alice.public_key());
+        assert_eq!(unwrapped.rumor.content, "hello");
+    }
+}
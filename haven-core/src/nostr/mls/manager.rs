@@ -26,8 +26,23 @@
 //! message(s) through Haven's own relay layer, then calls
 //! [`SessionManager::confirm_published`] on ≥1-relay ack, or
 //! [`SessionManager::publish_failed`] on failure. DM-3 wires this discipline.
+//!
+//! # Slow calls
+//!
+//! The engine's mutating calls — the ones that write its own `SQLite` store —
+//! are timed, including the wait for the session lock, and reported by
+//! method name to [`crate::diagnostics::slow_ops`].
+//!
+//! # Device transfer
+//!
+//! [`SessionManager::export_for_transfer`] moves the session's state to
+//! another device: it copies the database under the session lock, then
+//! retires this session. Every later mutating call fails, and the directory
+//! is never opened again (see [`super::storage`]); reads keep working so the
+//! app can show what was moved.
 
 use std::path::Path;
+use std::sync::atomic::{AtomicBool, AtomicU32, Ordering};
 use std::sync::Arc;
 
 use base64::engine::general_purpose::STANDARD as BASE64;
@@ -35,7 +50,8 @@ use base64::Engine as _;
 use nostr::{Event, JsonUtil, Keys, Kind, PublicKey, Tag, Timestamp, UnsignedEvent};
 use rand::rngs::OsRng;
 use rand::RngCore;
-use tokio::sync::Mutex;
+use tokio::sync::{Mutex, MutexGuard};
+use zeroize::Zeroizing;
 
 use cgka_engine::canonicalization::CanonicalizationPolicy;
 use cgka_engine::feature_registry::FeatureRegistry;
@@ -58,10 +74,12 @@ use storage_sqlite::SqlCipherKey;
 use transport_nostr_peeler::{NostrMlsPeeler, NostrTransportEvent};
 
 use super::signer::HavenIdentityProofSigner;
-use super::storage::{LiveSessionGuard, StorageConfig};
-use super::types::{LocationGroupConfig, LocationMessageResult};
+use super::storage::{LiveSessionGuard, SessionSnapshot, StorageConfig};
+use super::types::{EpochInfo, LocationGroupConfig, LocationMessageResult, MembershipDelta};
 use super::welcome::WelcomePreview;
+use crate::diagnostics::{slow_ops, SLOW_MLS_MODULE};
 use crate::nostr::error::{NostrError, Result};
+use crate::privacy::PrivacySettings;
 
 // `redact_hex_sequences` lives in the neutral `crate::util` module. Re-exported
 // here so every `crate::nostr::mls::redact_hex_sequences` caller (circle/error,
@@ -77,9 +95,30 @@ pub const DEFAULT_EXPORTER_LABEL: &str = "marmot/group-event";
 /// `peeler.rs:427-449`): the engine's internal `wrap_welcome_with_metadata`
 /// fail-closes above this count, so Haven validates before create/invite.
 const MAX_GROUP_WELCOME_RELAYS: usize = 16;
+/// How many past epochs the engine keeps secrets for, so a late application
+/// message from up to this many epochs back still decrypts (the engine's
+/// `app_message_past_epoch_limit` default, Rule 5).
+pub const APP_MESSAGE_PAST_EPOCH_LIMIT: u64 = 5;
+
 /// Bound on each group relay URL length in bytes (protocol W8).
 const MAX_GROUP_RELAY_URL_LEN: usize = 512;
 
+/// Error text once a session's state moved to another device.
+const SESSION_RETIRED: &str = "this device's MLS state was moved to another device";
+
+/// Builds the canonical inner location rumor: kind 9, a `["t","location"]`
+/// tag, the encoded [`crate::payload::HavenPayload::Location`] as content, and
+/// `sender` as `pubkey`.
+///
+/// [`SessionManager::send_location`] sends exactly this; [`crate::interop`]
+/// pins its shape for other clients.
+#[must_use]
+pub fn location_rumor(sender: PublicKey, content: String) -> UnsignedEvent {
+    nostr::EventBuilder::new(Kind::Custom(9), content)
+        .tags([Tag::hashtag("location")])
+        .build(sender)
+}
+
 /// Maps any engine/session/peeler error into Haven's redacted MLS-error bucket.
 ///
 /// #864 (open upstream): several `EngineError` validators embed full group-id
@@ -111,6 +150,15 @@ pub struct SessionManager {
     /// background isolate) fails closed instead of hydrating a divergent epoch
     /// state. Held for the session's lifetime; never read after construction.
     _live_guard: LiveSessionGuard,
+    /// Where the session's database lives.
+    storage: StorageConfig,
+    /// The database passphrase, kept for [`Self::export_for_transfer`].
+    passphrase: Zeroizing<String>,
+    /// Set once the state moved to another device; mutating calls then fail.
+    retired: AtomicBool,
+    /// [`PrivacySettings::timestamp_fuzz_minutes`] for outgoing app messages
+    /// (see [`Self::set_privacy_settings`]).
+    timestamp_fuzz_minutes: AtomicU32,
 }
 
 impl SessionManager {
@@ -130,8 +178,8 @@ impl SessionManager {
             NostrError::StorageError(format!("failed to create MLS data directory: {e}"))
         })?;
         let config = StorageConfig::new(data_dir);
-        let key = config.sqlcipher_key()?;
-        Self::open_session(config.database_path(), key, keys)
+        let passphrase = config.sqlcipher_passphrase()?;
+        Self::open_session(config, passphrase, keys)
     }
 
     /// Opens a session over a fixed-key encrypted temp database, bypassing the
@@ -151,14 +199,48 @@ impl SessionManager {
             NostrError::StorageError(format!("failed to create MLS data directory: {e}"))
         })?;
         let config = StorageConfig::new(data_dir);
-        let key = SqlCipherKey::new("haven-test-mls-passphrase")
-            .map_err(|e| NostrError::StorageError(format!("failed to build test key: {e}")))?;
-        Self::open_session(config.database_path(), key, keys)
+        let passphrase = Zeroizing::new("haven-test-mls-passphrase".to_string());
+        Self::open_session(config, passphrase, keys)
+    }
+
+    /// Opens a session over a throwaway database in `data_dir`, encrypted
+    /// under a random passphrase that is never stored.
+    ///
+    /// For the startup self-test ([`crate::self_test`]): it exercises the real
+    /// engine without touching the keyring, and the database is unreadable
+    /// once the session drops. The caller deletes `data_dir` afterwards.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the directory cannot be created or the session cannot
+    /// open/hydrate.
+    pub(crate) fn new_ephemeral(data_dir: &Path, keys: &Keys) -> Result<Self> {
+        std::fs::create_dir_all(data_dir).map_err(|e| {
+            NostrError::StorageError(format!("failed to create MLS data directory: {e}"))
+        })?;
+        let config = StorageConfig::new(data_dir);
+        let mut secret = zeroize::Zeroizing::new([0u8; 32]);
+        OsRng.fill_bytes(secret.as_mut());
+        let passphrase = Zeroizing::new(hex::encode(secret.as_ref()));
+        Self::open_session(config, passphrase, keys)
     }
 
     /// Shared open path: wires the peeler, the hardened proof signer, and the
     /// supported app-component set, then hydrates the session.
-    fn open_session(db_path: std::path::PathBuf, key: SqlCipherKey, keys: &Keys) -> Result<Self> {
+    fn open_session(
+        storage: StorageConfig,
+        passphrase: Zeroizing<String>,
+        keys: &Keys,
+    ) -> Result<Self> {
+        if storage.is_retired() {
+            return Err(NostrError::StorageError(SESSION_RETIRED.to_string()));
+        }
+        // Refuse a layout the engine cannot read (a downgrade, or a newer
+        // app's transfer) before the engine touches it.
+        storage.check_format()?;
+        let db_path = storage.database_path();
+        let key = SqlCipherKey::new(passphrase.as_str())
+            .map_err(|e| NostrError::StorageError(format!("Failed to build SQLCipher key: {e}")))?;
         // Rule 14 (runtime): fail closed if a live session already holds this DB
         // file. Acquired BEFORE the engine open so a rejected second open never
         // touches the on-disk state; if the engine open below fails, the guard
@@ -219,14 +301,86 @@ impl SessionManager {
             .feature_registry(self_remove_feature_registry());
 
         let session = AccountDeviceSession::open(config).map_err(map_mls_err)?;
+        storage.mark_format()?;
         Ok(Self {
             session: Mutex::new(session),
             identity_pubkey: keys.public_key(),
             preview_peeler,
             _live_guard: live_guard,
+            storage,
+            passphrase,
+            retired: AtomicBool::new(false),
+            timestamp_fuzz_minutes: AtomicU32::new(0),
         })
     }
 
+    /// Applies `settings` to messages sent from now on: each app message's
+    /// inner `created_at` — which the engine copies to the outer kind 445
+    /// and derives its `expiration` from — is fuzzed, keeping at least half
+    /// of [`LOCATION_MESSAGE_RETENTION_SECS`] of relay residency.
+    ///
+    /// [`LOCATION_MESSAGE_RETENTION_SECS`]: crate::location::ttl::LOCATION_MESSAGE_RETENTION_SECS
+    pub fn set_privacy_settings(&self, settings: &PrivacySettings) {
+        self.timestamp_fuzz_minutes
+            .store(settings.timestamp_fuzz_minutes, Ordering::Relaxed);
+    }
+
+    /// The settings last applied with [`Self::set_privacy_settings`], as far
+    /// as the session uses them; the rest read as their defaults.
+    #[must_use]
+    pub fn privacy_settings(&self) -> PrivacySettings {
+        PrivacySettings {
+            timestamp_fuzz_minutes: self.timestamp_fuzz_minutes.load(Ordering::Relaxed),
+            ..PrivacySettings::default()
+        }
+    }
+
+    /// Locks the session for a mutating call, failing once it was retired by
+    /// [`Self::export_for_transfer`]. The check runs under the lock, so no
+    /// call lands after the export's copy.
+    async fn live_session(&self) -> Result<MutexGuard<'_, AccountDeviceSession>> {
+        let session = self.session.lock().await;
+        if self.retired.load(Ordering::Acquire) {
+            return Err(NostrError::StorageError(SESSION_RETIRED.to_string()));
+        }
+        Ok(session)
+    }
+
+    /// The directory holding this session's database.
+    #[must_use]
+    pub fn data_dir(&self) -> &Path {
+        &self.storage.data_dir
+    }
+
+    /// Whether this session's state moved to another device.
+    #[must_use]
+    pub fn is_retired(&self) -> bool {
+        self.retired.load(Ordering::Acquire)
+    }
+
+    /// Copies this session's state for a device transfer, then retires the
+    /// session.
+    ///
+    /// Runs under the session lock, so the copy is consistent and no
+    /// mutating call slips in after it. Once this returns, every mutating
+    /// call fails and the directory never opens a session again: the new
+    /// device is now the only holder of the group state. Publish or roll
+    /// back staged commits before exporting; a pending commit moves with the
+    /// state, but its `PendingStateRef` does not.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`NostrError::StorageError`] if the session is already
+    /// retired or the database cannot be read or retired. Nothing is retired
+    /// on error.
+    pub async fn export_for_transfer(&self) -> Result<SessionSnapshot> {
+        let _session = self.live_session().await?;
+        let snapshot = self.storage.read_snapshot(&self.passphrase)?;
+        self.storage.retire()?;
+        self.retired.store(true, Ordering::Release);
+        Ok(snapshot)
+    }
+
     // ── Identity / conversions ───────────────────────────────────────────────
 
     /// The local identity public key.
@@ -344,12 +498,12 @@ impl SessionManager {
             initial_admins,
         };
 
-        self.session
-            .lock()
-            .await
-            .create_group(req)
+        slow_ops()
+            .time_async(SLOW_MLS_MODULE, "create_group", async {
+                let mut session = self.live_session().await?;
+                session.create_group(req).await.map_err(map_mls_err)
+            })
             .await
-            .map_err(map_mls_err)
     }
 
     /// Adds members to an existing group via their `KeyPackages`.
@@ -412,19 +566,20 @@ impl SessionManager {
     /// never carries the group id. Any other engine rejection (e.g. unknown
     /// group) maps to the redacted MLS-error bucket.
     pub async fn leave_group(&self, group_id: &GroupId) -> Result<SessionEffects> {
-        self.session
-            .lock()
-            .await
-            .send(SendIntent::Leave {
-                group_id: group_id.clone(),
+        let intent = SendIntent::Leave {
+            group_id: group_id.clone(),
+        };
+        slow_ops()
+            .time_async(SLOW_MLS_MODULE, "leave_group", async {
+                let mut session = self.live_session().await?;
+                session.send(intent).await.map_err(|e| match e {
+                    SessionError::Engine(EngineError::AdminCannotSelfRemove { .. }) => {
+                        NostrError::AdminSelfDemoteRequired
+                    }
+                    other => map_mls_err(other),
+                })
             })
             .await
-            .map_err(|e| match e {
-                SessionError::Engine(EngineError::AdminCannotSelfRemove { .. }) => {
-                    NostrError::AdminSelfDemoteRequired
-                }
-                other => map_mls_err(other),
-            })
     }
 
     /// Replaces the group's Nostr routing relay set (keeps the existing
@@ -461,12 +616,12 @@ impl SessionManager {
     ///
     /// Returns any engine error, redacted.
     pub async fn send(&self, intent: SendIntent) -> Result<SessionEffects> {
-        self.session
-            .lock()
-            .await
-            .send(intent)
+        slow_ops()
+            .time_async(SLOW_MLS_MODULE, "send", async {
+                let mut session = self.live_session().await?;
+                session.send(intent).await.map_err(map_mls_err)
+            })
             .await
-            .map_err(map_mls_err)
     }
 
     // ── Messaging ────────────────────────────────────────────────────────────
@@ -480,6 +635,9 @@ impl SessionManager {
     /// an `ApplicationMessage` transport message to publish (no pending ref —
     /// application messages do not advance the epoch).
     ///
+    /// The rumor's `created_at` is fuzzed first when timestamp fuzzing is on
+    /// (see [`Self::set_privacy_settings`]).
+    ///
     /// # Errors
     ///
     /// Returns an error if the rumor's pubkey is not the local identity (fail
@@ -487,13 +645,24 @@ impl SessionManager {
     pub async fn create_message(
         &self,
         group_id: &GroupId,
-        rumor: UnsignedEvent,
+        mut rumor: UnsignedEvent,
     ) -> Result<SessionEffects> {
         if rumor.pubkey != self.identity_pubkey {
             return Err(NostrError::InvalidEvent(
                 "inner app-message pubkey must equal the local sender identity".to_string(),
             ));
         }
+        let settings = self.privacy_settings();
+        if settings.timestamp_fuzz_minutes > 0 {
+            let fuzzed = settings.fuzz_timestamp(
+                rumor.created_at.as_secs(),
+                crate::location::ttl::LOCATION_MESSAGE_RETENTION_SECS / 2,
+            );
+            rumor.created_at = Timestamp::from(fuzzed);
+            // The id covers `created_at`: recompute it (W9 canonical id).
+            rumor.id = None;
+            rumor.ensure_id();
+        }
         let payload = rumor.as_json().into_bytes();
         self.send(SendIntent::AppMessage {
             group_id: group_id.clone(),
@@ -515,9 +684,89 @@ impl SessionManager {
         &self,
         group_id: &GroupId,
         content: String,
+    ) -> Result<SessionEffects> {
+        let rumor = location_rumor(self.identity_pubkey, content);
+        self.create_message(group_id, rumor).await
+    }
+
+    /// Builds an unsigned emergency rumor (inner kind-9 Marmot app event) and
+    /// sends it.
+    ///
+    /// Like [`Self::send_location`], but tagged `["t","sos"]` and carrying an
+    /// inner NIP-40 `expiration` at `expires_at`, so receivers can both route
+    /// it as an alert and stop surfacing it once it lapses.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the engine rejects the send.
+    pub async fn send_sos(
+        &self,
+        group_id: &GroupId,
+        content: String,
+        expires_at: Timestamp,
+    ) -> Result<SessionEffects> {
+        let rumor = nostr::EventBuilder::new(Kind::Custom(9), content)
+            .tags([
+                Tag::hashtag(crate::emergency::SOS_TAG),
+                Tag::expiration(expires_at),
+            ])
+            .build(self.identity_pubkey);
+        self.create_message(group_id, rumor).await
+    }
+
+    /// Builds an unsigned check-in request rumor (inner kind-9 Marmot app
+    /// event) asking `target` for their current location, and sends it.
+    ///
+    /// The rumor is tagged `["t","checkin_request"]`, names the target in a
+    /// `p` tag (read by receivers that predate [`crate::payload`]) and in a
+    /// [`HavenPayload::CheckinRequest`] content, and carries an inner NIP-40
+    /// `expiration` at `expires_at` (see [`crate::checkin`]).
+    ///
+    /// [`HavenPayload::CheckinRequest`]: crate::payload::HavenPayload::CheckinRequest
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the engine rejects the send.
+    pub async fn send_checkin_request(
+        &self,
+        group_id: &GroupId,
+        target: &PublicKey,
+        expires_at: Timestamp,
+    ) -> Result<SessionEffects> {
+        let content = crate::payload::HavenPayload::CheckinRequest {
+            target_pubkey: target.to_hex(),
+        }
+        .encode()?;
+        let rumor = nostr::EventBuilder::new(Kind::Custom(9), content)
+            .tags([
+                Tag::hashtag(crate::checkin::CHECKIN_REQUEST_TAG),
+                Tag::public_key(*target),
+                Tag::expiration(expires_at),
+            ])
+            .build(self.identity_pubkey);
+        self.create_message(group_id, rumor).await
+    }
+
+    /// Builds an unsigned meet-pin rumor (inner kind-9 Marmot app event) and
+    /// sends it.
+    ///
+    /// Tagged `["t","meet_pin"]` with an inner NIP-40 `expiration` at
+    /// `expires_at`; `content` is a [`crate::meet::MeetPin`] JSON payload.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the engine rejects the send.
+    pub async fn send_meet_pin(
+        &self,
+        group_id: &GroupId,
+        content: String,
+        expires_at: Timestamp,
     ) -> Result<SessionEffects> {
         let rumor = nostr::EventBuilder::new(Kind::Custom(9), content)
-            .tags([Tag::hashtag("location")])
+            .tags([
+                Tag::hashtag(crate::meet::MEET_PIN_TAG),
+                Tag::expiration(expires_at),
+            ])
             .build(self.identity_pubkey);
         self.create_message(group_id, rumor).await
     }
@@ -538,12 +787,12 @@ impl SessionManager {
         &self,
         msg: cgka_traits::transport::TransportMessage,
     ) -> Result<IngestEffects> {
-        self.session
-            .lock()
-            .await
-            .ingest(msg)
+        slow_ops()
+            .time_async(SLOW_MLS_MODULE, "ingest", async {
+                let mut session = self.live_session().await?;
+                session.ingest(msg).await.map_err(map_mls_err)
+            })
             .await
-            .map_err(map_mls_err)
     }
 
     /// Converts a signed Nostr event into a transport message and ingests it.
@@ -561,6 +810,46 @@ impl SessionManager {
     ///
     /// Returns an error if conversion or ingest fails hard.
     pub async fn process_event(&self, event: &Event) -> Result<IngestEffects> {
+        if let Some(dropped) = Self::expired_ingest(event) {
+            return Ok(dropped);
+        }
+        let msg = Self::event_to_transport_message(event)?;
+        self.ingest(msg).await
+    }
+
+    /// [`Self::process_event`] for several events under one session lock.
+    ///
+    /// Events are ingested in the order given — sort them by `created_at`
+    /// first — and one result is returned per event, so a failing event
+    /// never stops the rest.
+    pub async fn process_events(&self, events: &[&Event]) -> Vec<Result<IngestEffects>> {
+        slow_ops()
+            .time_async(SLOW_MLS_MODULE, "process_events", async {
+                let Ok(mut session) = self.live_session().await else {
+                    return events
+                        .iter()
+                        .map(|_| Err(NostrError::StorageError(SESSION_RETIRED.to_string())))
+                        .collect();
+                };
+                let mut out = Vec::with_capacity(events.len());
+                for event in events {
+                    if let Some(dropped) = Self::expired_ingest(event) {
+                        out.push(Ok(dropped));
+                        continue;
+                    }
+                    out.push(match Self::event_to_transport_message(event) {
+                        Ok(msg) => session.ingest(msg).await.map_err(map_mls_err),
+                        Err(e) => Err(e),
+                    });
+                }
+                out
+            })
+            .await
+    }
+
+    /// The effects [`Self::process_event`] reports for an event past its
+    /// NIP-40 expiration (plus grace), or `None` if it is still live.
+    fn expired_ingest(event: &Event) -> Option<IngestEffects> {
         if let Some(expires_at) = event.tags.iter().find_map(|t| match t.as_standardized() {
             Some(nostr::TagStandard::Expiration(ts)) => Some(*ts),
             _ => None,
@@ -575,7 +864,7 @@ impl SessionManager {
                 // effects so every caller advances its cursor past it (the
                 // same contract as the engine's own dedup outcomes) and
                 // nothing is surfaced to decrypt.
-                return Ok(IngestEffects {
+                return Some(IngestEffects {
                     outcome: super::types::IngestOutcome::Stale {
                         reason: super::types::StaleReason::AlreadySeen,
                     },
@@ -588,8 +877,7 @@ impl SessionManager {
                 });
             }
         }
-        let msg = Self::event_to_transport_message(event)?;
-        self.ingest(msg).await
+        None
     }
 
     /// Advances stored convergence for a group, releasing queued work and
@@ -599,12 +887,15 @@ impl SessionManager {
     ///
     /// Returns an error if the group is unknown or convergence fails.
     pub async fn advance_convergence(&self, group_id: &GroupId) -> Result<SessionEffects> {
-        self.session
-            .lock()
-            .await
-            .advance_convergence(group_id)
+        slow_ops()
+            .time_async(SLOW_MLS_MODULE, "advance_convergence", async {
+                let mut session = self.live_session().await?;
+                session
+                    .advance_convergence(group_id)
+                    .await
+                    .map_err(map_mls_err)
+            })
             .await
-            .map_err(map_mls_err)
     }
 
     // ── Publish-before-apply (Rule 13) ───────────────────────────────────────
@@ -618,12 +909,15 @@ impl SessionManager {
     /// Returns an error if the pending ref is unknown (already confirmed,
     /// rolled back, or never issued).
     pub async fn confirm_published(&self, pending: PendingStateRef) -> Result<SessionEffects> {
-        self.session
-            .lock()
-            .await
-            .confirm_published(pending)
+        slow_ops()
+            .time_async(SLOW_MLS_MODULE, "confirm_published", async {
+                let mut session = self.live_session().await?;
+                session
+                    .confirm_published(pending)
+                    .await
+                    .map_err(map_mls_err)
+            })
             .await
-            .map_err(map_mls_err)
     }
 
     /// Reports that a staged publish failed; the engine discards the staged
@@ -634,12 +928,12 @@ impl SessionManager {
     ///
     /// Returns an error if the pending ref is unknown.
     pub async fn publish_failed(&self, pending: PendingStateRef) -> Result<SessionEffects> {
-        self.session
-            .lock()
-            .await
-            .publish_failed(pending)
+        slow_ops()
+            .time_async(SLOW_MLS_MODULE, "publish_failed", async {
+                let mut session = self.live_session().await?;
+                session.publish_failed(pending).await.map_err(map_mls_err)
+            })
             .await
-            .map_err(map_mls_err)
     }
 
     // ── Welcomes (hold-before-ingest, F3) ────────────────────────────────────
@@ -666,7 +960,12 @@ impl SessionManager {
             .sender
             .map(|m| hex::encode(m.as_slice()))
             .unwrap_or_default();
-        Ok(WelcomePreview { inviter_pubkey })
+        // The name hint lives in the rumor tags, which the peeler does not
+        // surface; `CircleManager` fills it in from its own transient unwrap.
+        Ok(WelcomePreview {
+            inviter_pubkey,
+            suggested_name: None,
+        })
     }
 
     /// Accepts a held welcome by ingesting the still-encrypted 1059 into the
@@ -691,12 +990,12 @@ impl SessionManager {
     ///
     /// Returns an error if generation fails.
     pub async fn fresh_key_package(&self) -> Result<KeyPackage> {
-        self.session
-            .lock()
-            .await
-            .fresh_key_package()
+        slow_ops()
+            .time_async(SLOW_MLS_MODULE, "fresh_key_package", async {
+                let mut session = self.live_session().await?;
+                session.fresh_key_package().await.map_err(map_mls_err)
+            })
             .await
-            .map_err(map_mls_err)
     }
 
     /// Deletes a previously generated `KeyPackage` bundle from storage.
@@ -708,12 +1007,15 @@ impl SessionManager {
     ///
     /// Returns an error if deletion fails.
     pub async fn delete_key_package(&self, key_package: &KeyPackage) -> Result<()> {
-        self.session
-            .lock()
-            .await
-            .delete_key_package(key_package)
+        slow_ops()
+            .time_async(SLOW_MLS_MODULE, "delete_key_package", async {
+                let mut session = self.live_session().await?;
+                session
+                    .delete_key_package(key_package)
+                    .await
+                    .map_err(map_mls_err)
+            })
             .await
-            .map_err(map_mls_err)
     }
 
     // ── Inspection ───────────────────────────────────────────────────────────
@@ -810,6 +1112,22 @@ impl SessionManager {
             .map_err(map_mls_err)
     }
 
+    /// The group's epoch position: the current epoch, the oldest one whose
+    /// messages still decrypt, and the member count.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the group is unknown.
+    pub async fn epoch_info(&self, group_id: &GroupId) -> Result<EpochInfo> {
+        let epoch = self.epoch(group_id).await?;
+        let member_count = self.members(group_id).await?.len();
+        Ok(EpochInfo {
+            epoch,
+            oldest_readable_epoch: epoch.saturating_sub(APP_MESSAGE_PAST_EPOCH_LIMIT),
+            member_count,
+        })
+    }
+
     /// The group's Nostr routing: `(nostr_group_id, relays)` decoded from the
     /// `marmot.transport.nostr.routing.v1` component.
     ///
@@ -875,7 +1193,10 @@ impl SessionManager {
     /// meaning (group-created, fork-recovery bookkeeping, hydration events).
     ///
     /// - `MessageReceived` → `Location` (inner content extracted from the
-    ///   `MarmotAppEvent` payload).
+    ///   `MarmotAppEvent` payload), or `Sos` when the inner event carries a
+    ///   `["t","sos"]` tag, or `CheckinRequest` when it carries a
+    ///   `["t","checkin_request"]` tag (dropped if it names no valid target),
+    ///   or `MeetPin` when it carries a `["t","meet_pin"]` tag.
     /// - `GroupJoined` → `Joined`.
     /// - `GroupStateChanged` / `EpochChanged` → `GroupUpdate`.
     /// - `AppMessageInvalidated` / `GroupStateInvalidated` → `Invalidated`.
@@ -888,12 +1209,48 @@ impl SessionManager {
                 sender,
                 epoch,
                 payload,
-            } => Some(LocationMessageResult::Location {
-                sender_pubkey: hex::encode(sender.as_slice()),
-                content: inner_app_content(payload),
-                group_id: group_id.clone(),
-                epoch: epoch.0,
-            }),
+            } => {
+                let sender_pubkey = hex::encode(sender.as_slice());
+                if inner_app_has_hashtag(payload, crate::checkin::CHECKIN_REQUEST_TAG) {
+                    let target_pubkey = inner_app_tag_value(payload, "p")
+                        .and_then(|hex| PublicKey::from_hex(&hex).ok())?
+                        .to_hex();
+                    return Some(LocationMessageResult::CheckinRequest {
+                        sender_pubkey,
+                        target_pubkey,
+                        group_id: group_id.clone(),
+                        epoch: epoch.0,
+                    });
+                }
+                let content = inner_app_content(payload);
+                // Cover traffic ends here, unseen by anything downstream.
+                if crate::payload::HavenPayload::is_chaff(&content) {
+                    return None;
+                }
+                if inner_app_has_hashtag(payload, crate::meet::MEET_PIN_TAG) {
+                    return Some(LocationMessageResult::MeetPin {
+                        sender_pubkey,
+                        content,
+                        group_id: group_id.clone(),
+                        epoch: epoch.0,
+                    });
+                }
+                if inner_app_has_hashtag(payload, crate::emergency::SOS_TAG) {
+                    Some(LocationMessageResult::Sos {
+                        sender_pubkey,
+                        content,
+                        group_id: group_id.clone(),
+                        epoch: epoch.0,
+                    })
+                } else {
+                    Some(LocationMessageResult::Location {
+                        sender_pubkey,
+                        content,
+                        group_id: group_id.clone(),
+                        epoch: epoch.0,
+                    })
+                }
+            }
             GroupEvent::GroupJoined { group_id, .. } => Some(LocationMessageResult::Joined {
                 group_id: group_id.clone(),
             }),
@@ -917,6 +1274,7 @@ impl SessionManager {
             | GroupEvent::GroupHydrationRecovered { group_id, .. } => {
                 Some(LocationMessageResult::GroupUpdate {
                     group_id: group_id.clone(),
+                    membership: MembershipDelta::default(),
                 })
             }
             GroupEvent::AppMessageInvalidated { group_id, .. }
@@ -1016,6 +1374,34 @@ fn inner_app_content(payload: &[u8]) -> String {
         .unwrap_or_default()
 }
 
+/// Returns `true` if the inner app-event JSON carries a `["t", <topic>]` tag.
+fn inner_app_has_hashtag(payload: &[u8], topic: &str) -> bool {
+    serde_json::from_slice::<serde_json::Value>(payload)
+        .ok()
+        .and_then(|v| v.get("tags").and_then(|t| t.as_array().cloned()))
+        .is_some_and(|tags| {
+            tags.iter().any(|tag| {
+                tag.as_array().is_some_and(|parts| {
+                    parts.first().and_then(|p| p.as_str()) == Some("t")
+                        && parts.get(1).and_then(|p| p.as_str()) == Some(topic)
+                })
+            })
+        })
+}
+
+/// Returns the value of the first `[<name>, <value>, ...]` tag in the inner
+/// app-event JSON, if any.
+fn inner_app_tag_value(payload: &[u8], name: &str) -> Option<String> {
+    let value = serde_json::from_slice::<serde_json::Value>(payload).ok()?;
+    value.get("tags")?.as_array()?.iter().find_map(|tag| {
+        let parts = tag.as_array()?;
+        if parts.first()?.as_str()? != name {
+            return None;
+        }
+        parts.get(1)?.as_str().map(String::from)
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1097,6 +1483,35 @@ mod tests {
         let _ = std::fs::remove_dir_all(&other_dir);
     }
 
+    #[tokio::test]
+    async fn export_for_transfer_retires_the_session() {
+        let dir = temp_dir();
+        let keys = Keys::generate();
+        let manager = SessionManager::new_unencrypted(&dir, &keys).expect("open session");
+        assert!(!manager.is_retired());
+
+        let snapshot = manager.export_for_transfer().await.expect("export");
+        assert!(!snapshot.database.is_empty());
+        assert!(manager.is_retired());
+        assert!(manager.fresh_key_package().await.is_err());
+        assert!(manager.export_for_transfer().await.is_err());
+
+        // The directory never opens a session again, even after a drop.
+        drop(manager);
+        assert!(SessionManager::new_unencrypted(&dir, &keys).is_err());
+
+        // The copy opens on another directory.
+        let other = temp_dir();
+        StorageConfig::new(&other)
+            .write_snapshot(&snapshot)
+            .expect("install");
+        let moved = SessionManager::new_unencrypted(&other, &keys).expect("open moved state");
+        assert!(moved.fresh_key_package().await.is_ok());
+
+        let _ = std::fs::remove_dir_all(&dir);
+        let _ = std::fs::remove_dir_all(&other);
+    }
+
     #[tokio::test]
     async fn find_group_unknown_is_none() {
         let (manager, dir) = open_manager();
@@ -1244,6 +1659,98 @@ mod tests {
         );
     }
 
+    #[test]
+    fn location_result_from_sos_tagged_message_is_sos() {
+        let inner = nostr::EventBuilder::new(Kind::Custom(9), r#"{"lat":1.5}"#)
+            .tags([Tag::hashtag("sos")])
+            .build(Keys::generate().public_key());
+        let event = GroupEvent::MessageReceived {
+            group_id: GroupId::new(vec![7]),
+            sender: MemberId::new(vec![0xCD; 32]),
+            epoch: EpochId(2),
+            payload: inner.as_json().into_bytes(),
+        };
+        match SessionManager::location_result_from_event(&event) {
+            Some(LocationMessageResult::Sos { content, epoch, .. }) => {
+                assert!(content.contains("lat"));
+                assert_eq!(epoch, 2);
+            }
+            other => panic!("expected Sos, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn location_result_from_meet_pin_tagged_message_is_meet_pin() {
+        let inner = nostr::EventBuilder::new(Kind::Custom(9), r#"{"pin_id":"ab"}"#)
+            .tags([Tag::hashtag("meet_pin")])
+            .build(Keys::generate().public_key());
+        let event = GroupEvent::MessageReceived {
+            group_id: GroupId::new(vec![7]),
+            sender: MemberId::new(vec![0xCD; 32]),
+            epoch: EpochId(3),
+            payload: inner.as_json().into_bytes(),
+        };
+        match SessionManager::location_result_from_event(&event) {
+            Some(LocationMessageResult::MeetPin { content, epoch, .. }) => {
+                assert!(content.contains("pin_id"));
+                assert_eq!(epoch, 3);
+            }
+            other => panic!("expected MeetPin, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn location_result_from_checkin_request_names_target() {
+        let target = Keys::generate().public_key();
+        let inner = nostr::EventBuilder::new(Kind::Custom(9), "")
+            .tags([Tag::hashtag("checkin_request"), Tag::public_key(target)])
+            .build(Keys::generate().public_key());
+        let event = GroupEvent::MessageReceived {
+            group_id: GroupId::new(vec![7]),
+            sender: MemberId::new(vec![0xCD; 32]),
+            epoch: EpochId(4),
+            payload: inner.as_json().into_bytes(),
+        };
+        match SessionManager::location_result_from_event(&event) {
+            Some(LocationMessageResult::CheckinRequest {
+                sender_pubkey,
+                target_pubkey,
+                epoch,
+                ..
+            }) => {
+                assert_eq!(sender_pubkey, "cd".repeat(32));
+                assert_eq!(target_pubkey, target.to_hex());
+                assert_eq!(epoch, 4);
+            }
+            other => panic!("expected CheckinRequest, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn checkin_request_without_target_is_dropped() {
+        let event = GroupEvent::MessageReceived {
+            group_id: GroupId::new(vec![7]),
+            sender: MemberId::new(vec![0xCD; 32]),
+            epoch: EpochId(4),
+            payload: br#"{"content":"","tags":[["t","checkin_request"],["p","zz"]]}"#.to_vec(),
+        };
+        assert!(SessionManager::location_result_from_event(&event).is_none());
+    }
+
+    #[test]
+    fn inner_app_has_hashtag_matches_only_t_tags() {
+        assert!(inner_app_has_hashtag(
+            br#"{"tags":[["t","location"],["t","sos"]]}"#,
+            "sos"
+        ));
+        assert!(!inner_app_has_hashtag(br#"{"tags":[["p","sos"]]}"#, "sos"));
+        assert!(!inner_app_has_hashtag(
+            br#"{"tags":[["t","location"]]}"#,
+            "sos"
+        ));
+        assert!(!inner_app_has_hashtag(b"not json", "sos"));
+    }
+
     #[test]
     fn inner_app_content_is_empty_for_garbage() {
         assert_eq!(inner_app_content(b"not json"), "");
@@ -35,8 +35,13 @@ mod welcome;
 
 pub use context::MlsGroupContext;
 pub use manager::redact_hex_sequences;
-pub use manager::{SessionManager, DEFAULT_EXPORTER_LABEL};
+pub use manager::{
+    location_rumor, SessionManager, APP_MESSAGE_PAST_EPOCH_LIMIT, DEFAULT_EXPORTER_LABEL,
+};
 pub use signer::HavenIdentityProofSigner;
-pub use storage::StorageConfig;
-pub use types::{GroupIdExt, LocationGroupConfig, LocationGroupInfo, LocationMessageResult};
+pub use storage::{SessionSnapshot, StorageConfig};
+pub use types::{
+    EpochInfo, GroupIdExt, LocationGroupConfig, LocationGroupInfo, LocationMessageResult,
+    MembershipDelta,
+};
 pub use welcome::{PendingWelcome, PendingWelcomeStore, WelcomePreview};
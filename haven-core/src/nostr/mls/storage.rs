@@ -23,6 +23,34 @@
 //! wiped-and-recreated on the Dark Matter cutover, so there is no in-place
 //! re-key to reconcile.
 //!
+//! # Device transfer
+//!
+//! [`SessionManager::export_for_transfer`] copies `session.sqlite` (and its
+//! WAL) together with its passphrase into a [`SessionSnapshot`], and leaves
+//! a `session.retired` marker beside the database: a session never opens on
+//! a directory whose state moved to another device, since two devices
+//! running the same group state reuse ratchet generations.
+//! [`StorageConfig::install_transferred_session`] writes the snapshot on the
+//! new device and stores its passphrase in that device's keyring.
+//!
+//! [`SessionManager::export_for_transfer`]: super::SessionManager::export_for_transfer
+//!
+//! # Storage format
+//!
+//! `session.sqlite` is laid out by the pinned MDK `storage-sqlite`, which
+//! migrates older layouts forward when it opens them but cannot read a newer
+//! one. Haven numbers each layout it has shipped ([`MLS_STORAGE_FORMAT`]) and
+//! records the number in a `session.format` sidecar after every open. A
+//! session refuses to open a database outside
+//! [`OLDEST_MLS_STORAGE_FORMAT`]`..=`[`MLS_STORAGE_FORMAT`] with
+//! [`NostrError::IncompatibleMlsStorage`] — e.g. after a downgrade, or a
+//! transfer from a newer app — rather than letting the engine misread it.
+//!
+//! Bumping the MDK pin: if the upgrade changes the storage layout, increment
+//! [`MLS_STORAGE_FORMAT`] and generate a fixture for it (see
+//! `tests/mls_storage_compat_test.rs`); the fixtures of every older format
+//! still in range must keep opening and operating.
+//!
 //! # Legacy database (kept for the cutover wipe — security F6)
 //!
 //! The pre-Dark-Matter `haven_mdk.db` path and its `mdk.db.key.default` keyring
@@ -152,6 +180,40 @@ const MLS_DB_KEY_ID: &str = "mls.session.key.default";
 /// File name of the Dark Matter MLS database.
 const MLS_DB_FILENAME: &str = "session.sqlite";
 
+/// Marker left in the data directory once its MLS state moved to another
+/// device.
+const RETIRED_MARKER_FILENAME: &str = "session.retired";
+
+/// Sidecar recording the storage format of the database beside it.
+const FORMAT_MARKER_FILENAME: &str = "session.format";
+
+/// The `session.sqlite` layout the pinned MDK writes (MDK v0.9.4, rev
+/// `e391adc1`). Increment it whenever an MDK upgrade changes the layout.
+pub const MLS_STORAGE_FORMAT: u32 = 1;
+
+/// The oldest layout the pinned MDK still migrates forward at open.
+pub const OLDEST_MLS_STORAGE_FORMAT: u32 = 1;
+
+/// The format of a database (or transfer) from before the `session.format`
+/// sidecar existed: the first Dark Matter layout.
+pub const UNMARKED_MLS_STORAGE_FORMAT: u32 = 1;
+
+/// Checks that this build can open a database in storage format `found`.
+///
+/// # Errors
+///
+/// Returns [`NostrError::IncompatibleMlsStorage`] if `found` is outside
+/// [`OLDEST_MLS_STORAGE_FORMAT`]`..=`[`MLS_STORAGE_FORMAT`].
+pub const fn ensure_compatible_format(found: u32) -> Result<()> {
+    if found < OLDEST_MLS_STORAGE_FORMAT || found > MLS_STORAGE_FORMAT {
+        return Err(NostrError::IncompatibleMlsStorage {
+            found,
+            supported: MLS_STORAGE_FORMAT,
+        });
+    }
+    Ok(())
+}
+
 /// Pre-Dark-Matter MLS database file name. Retained only so the cutover wipe
 /// can find and delete it (plus its WAL/SHM/journal sidecars).
 const LEGACY_MLS_DB_FILENAME: &str = "haven_mdk.db";
@@ -160,6 +222,29 @@ const LEGACY_MLS_DB_FILENAME: &str = "haven_mdk.db";
 /// can destroy it (security F6).
 const LEGACY_MLS_DB_KEY_ID: &str = "mdk.db.key.default";
 
+/// A copy of `session.sqlite` taken for a device transfer.
+///
+/// Holds the database, its write-ahead log, and the passphrase it is
+/// encrypted under, all `Zeroizing`. Debug prints sizes only.
+pub struct SessionSnapshot {
+    pub(crate) database: Zeroizing<Vec<u8>>,
+    pub(crate) wal: Zeroizing<Vec<u8>>,
+    pub(crate) passphrase: Zeroizing<String>,
+    /// The database's storage format, checked before installing it.
+    pub(crate) format: u32,
+}
+
+impl std::fmt::Debug for SessionSnapshot {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SessionSnapshot")
+            .field("database_bytes", &self.database.len())
+            .field("wal_bytes", &self.wal.len())
+            .field("passphrase", &"<redacted>")
+            .field("format", &self.format)
+            .finish()
+    }
+}
+
 /// Configuration for the MLS session storage.
 ///
 /// Holds the data directory and derives the `session.sqlite` path plus the
@@ -192,6 +277,167 @@ impl StorageConfig {
         self.data_dir.join(MLS_DB_FILENAME)
     }
 
+    /// Path to the database's write-ahead log.
+    fn wal_path(&self) -> PathBuf {
+        self.data_dir.join(format!("{MLS_DB_FILENAME}-wal"))
+    }
+
+    /// Path to the marker left once this directory's MLS state moved to
+    /// another device.
+    #[must_use]
+    pub fn retired_marker_path(&self) -> PathBuf {
+        self.data_dir.join(RETIRED_MARKER_FILENAME)
+    }
+
+    /// Whether this directory's MLS state moved to another device. A session
+    /// refuses to open on a retired directory.
+    #[must_use]
+    pub fn is_retired(&self) -> bool {
+        self.retired_marker_path().exists()
+    }
+
+    /// Path to the sidecar recording the database's storage format.
+    #[must_use]
+    pub fn format_marker_path(&self) -> PathBuf {
+        self.data_dir.join(FORMAT_MARKER_FILENAME)
+    }
+
+    /// The storage format of the database here, or `None` if there is no
+    /// database yet. A database without a sidecar predates it and is in the
+    /// first Dark Matter format.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`NostrError::StorageError`] if the sidecar cannot be read or
+    /// does not hold a format number.
+    pub fn stored_format(&self) -> Result<Option<u32>> {
+        if !self.database_path().exists() {
+            return Ok(None);
+        }
+        match std::fs::read_to_string(self.format_marker_path()) {
+            Ok(text) => text.trim().parse().map(Some).map_err(|_| {
+                NostrError::StorageError("Unreadable MLS storage format marker".to_string())
+            }),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+                Ok(Some(UNMARKED_MLS_STORAGE_FORMAT))
+            }
+            Err(e) => Err(NostrError::StorageError(format!(
+                "Failed to read MLS storage format: {e}"
+            ))),
+        }
+    }
+
+    /// Checks that this build can open the database here, if there is one.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`NostrError::IncompatibleMlsStorage`] for a database in a
+    /// format this build cannot open, or [`NostrError::StorageError`] if the
+    /// format cannot be read.
+    pub fn check_format(&self) -> Result<()> {
+        match self.stored_format()? {
+            Some(found) => ensure_compatible_format(found),
+            None => Ok(()),
+        }
+    }
+
+    /// Records that the database is in [`MLS_STORAGE_FORMAT`]; called once
+    /// the engine opened it, which migrates older formats forward.
+    pub(crate) fn mark_format(&self) -> Result<()> {
+        std::fs::write(self.format_marker_path(), MLS_STORAGE_FORMAT.to_string()).map_err(|e| {
+            NostrError::StorageError(format!("Failed to record MLS storage format: {e}"))
+        })
+    }
+
+    /// Reads the database and its write-ahead log into a snapshot.
+    ///
+    /// The caller must keep every writer out (the session lock) so the two
+    /// files are consistent.
+    pub(crate) fn read_snapshot(&self, passphrase: &str) -> Result<SessionSnapshot> {
+        let read = |path: &Path| -> Result<Zeroizing<Vec<u8>>> {
+            match std::fs::read(path) {
+                Ok(bytes) => Ok(Zeroizing::new(bytes)),
+                Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+                    Ok(Zeroizing::new(Vec::new()))
+                }
+                Err(e) => Err(NostrError::StorageError(format!(
+                    "Failed to read MLS storage: {e}"
+                ))),
+            }
+        };
+        let database = read(&self.database_path())?;
+        if database.is_empty() {
+            return Err(NostrError::StorageError(
+                "No MLS storage to transfer".to_string(),
+            ));
+        }
+        Ok(SessionSnapshot {
+            database,
+            wal: read(&self.wal_path())?,
+            passphrase: Zeroizing::new(passphrase.to_string()),
+            format: self.stored_format()?.unwrap_or(MLS_STORAGE_FORMAT),
+        })
+    }
+
+    /// Leaves the retired marker, so no session opens here again.
+    pub(crate) fn retire(&self) -> Result<()> {
+        std::fs::write(self.retired_marker_path(), b"")
+            .map_err(|e| NostrError::StorageError(format!("Failed to retire MLS storage: {e}")))
+    }
+
+    /// Installs a transferred session: writes the snapshot's files and
+    /// stores its passphrase in the platform keyring, so
+    /// [`SessionManager::new`](super::SessionManager::new) opens it.
+    ///
+    /// Call before any session opens on this directory.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`NostrError::IncompatibleMlsStorage`] if the snapshot comes
+    /// from an app whose storage format this build cannot open, and
+    /// [`NostrError::StorageError`] if a session is live on the directory,
+    /// it already holds MLS state (or a retired marker), the files cannot be
+    /// written, or the keyring is unavailable.
+    pub fn install_transferred_session(&self, snapshot: &SessionSnapshot) -> Result<()> {
+        self.write_snapshot(snapshot)?;
+        store_passphrase(SERVICE_ID, MLS_DB_KEY_ID, &snapshot.passphrase)
+    }
+
+    /// Writes a snapshot's files, refusing to replace any existing state.
+    pub(crate) fn write_snapshot(&self, snapshot: &SessionSnapshot) -> Result<()> {
+        ensure_compatible_format(snapshot.format)?;
+        std::fs::create_dir_all(&self.data_dir).map_err(|e| {
+            NostrError::StorageError(format!(
+                "Failed to create data directory {}: {e}",
+                self.data_dir.display()
+            ))
+        })?;
+        // Held while writing, so no session opens on half-written files.
+        let _guard = LiveSessionGuard::acquire(&self.database_path())?;
+        if self.database_path().exists() || self.wal_path().exists() || self.is_retired() {
+            return Err(NostrError::StorageError(
+                "This device already holds MLS state; remove it before restoring a transfer"
+                    .to_string(),
+            ));
+        }
+        let write = |path: PathBuf, bytes: &[u8]| -> Result<()> {
+            use std::io::Write as _;
+            std::fs::OpenOptions::new()
+                .write(true)
+                .create_new(true)
+                .open(path)
+                .and_then(|mut file| file.write_all(bytes))
+                .map_err(|e| NostrError::StorageError(format!("Failed to write MLS storage: {e}")))
+        };
+        if !snapshot.wal.is_empty() {
+            write(self.wal_path(), &snapshot.wal)?;
+        }
+        write(self.database_path(), &snapshot.database)?;
+        std::fs::write(self.format_marker_path(), snapshot.format.to_string()).map_err(|e| {
+            NostrError::StorageError(format!("Failed to record MLS storage format: {e}"))
+        })
+    }
+
     /// Path to the pre-Dark-Matter MLS database file (`haven_mdk.db`).
     ///
     /// Present only so the cutover wipe can locate the abandoned database and
@@ -227,6 +473,16 @@ impl StorageConfig {
     /// Returns [`NostrError::StorageError`] if the keyring is unavailable or the
     /// key cannot be constructed.
     pub fn sqlcipher_key(&self) -> Result<SqlCipherKey> {
+        let passphrase = self.sqlcipher_passphrase()?;
+        // `as_str()` copies into a fresh String that `SqlCipherKey::new` moves
+        // into its own `Zeroizing<String>`; `passphrase` is zeroized on drop.
+        SqlCipherKey::new(passphrase.as_str())
+            .map_err(|e| NostrError::StorageError(format!("Failed to build SQLCipher key: {e}")))
+    }
+
+    /// Resolves the passphrase behind [`Self::sqlcipher_key`], provisioning
+    /// it on first use.
+    pub(crate) fn sqlcipher_passphrase(&self) -> Result<Zeroizing<String>> {
         let passphrase = get_or_create_passphrase(SERVICE_ID, MLS_DB_KEY_ID)?;
 
         // Migrate the freshly-created key's iOS access policy so a locked-device
@@ -238,11 +494,7 @@ impl StorageConfig {
         {
             log::warn!("MLS session DB key access-policy migration deferred: {e}");
         }
-
-        // `as_str()` copies into a fresh String that `SqlCipherKey::new` moves
-        // into its own `Zeroizing<String>`; `passphrase` is zeroized on drop.
-        SqlCipherKey::new(passphrase.as_str())
-            .map_err(|e| NostrError::StorageError(format!("Failed to build SQLCipher key: {e}")))
+        Ok(passphrase)
     }
 
     /// Opens (or creates) the encrypted `session.sqlite` backend.
@@ -328,6 +580,19 @@ fn get_or_create_passphrase(service: &str, key_id: &str) -> Result<Zeroizing<Str
     }
 }
 
+/// Stores a transferred MLS DB passphrase (lowercase hex) in the keyring,
+/// replacing any previous one.
+fn store_passphrase(service: &str, key_id: &str, passphrase: &str) -> Result<()> {
+    let bytes = Zeroizing::new(hex::decode(passphrase).map_err(|_| {
+        NostrError::StorageError("transferred MLS DB key is malformed".to_string())
+    })?);
+    let entry = keyring_core::Entry::new(service, key_id)
+        .map_err(|_| NostrError::StorageError("keyring unavailable".to_string()))?;
+    entry
+        .set_secret(bytes.as_slice())
+        .map_err(|_| NostrError::StorageError("failed to persist MLS DB key".to_string()))
+}
+
 /// Destroys the pre-Dark-Matter MLS DB keyring entry (`mdk.db.key.default`).
 ///
 /// Called on the Dark Matter cutover (DM-5). Because unlinking `haven_mdk.db` is
@@ -359,6 +624,31 @@ pub fn destroy_legacy_mls_key_material() -> Result<()> {
     }
 }
 
+/// Keyring key identifier written and deleted by [`probe_secure_storage`].
+const SELF_TEST_KEY_ID: &str = "selftest.probe";
+
+/// Checks that the platform keyring can store, return and delete a secret.
+///
+/// Writes a random 32-byte secret under a dedicated probe id, reads it back
+/// and deletes it. Used by [`crate::self_test`]; the error is a short failure
+/// slug, never keyring detail.
+pub(crate) fn probe_secure_storage() -> std::result::Result<(), &'static str> {
+    let entry =
+        keyring_core::Entry::new(SERVICE_ID, SELF_TEST_KEY_ID).map_err(|_| "unavailable")?;
+    let mut probe = Zeroizing::new([0u8; 32]);
+    OsRng.fill_bytes(probe.as_mut());
+    entry
+        .set_secret(probe.as_ref())
+        .map_err(|_| "write_failed")?;
+    let read = entry.get_secret().map(Zeroizing::new);
+    let deleted = entry.delete_credential();
+    let read = read.map_err(|_| "read_failed")?;
+    if !crate::util::ct_eq(read.as_slice(), probe.as_ref()) {
+        return Err("mismatch");
+    }
+    deleted.map_err(|_| "delete_failed")
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -487,6 +777,99 @@ mod tests {
         let _ = std::fs::remove_dir_all(&dir);
     }
 
+    #[test]
+    fn stored_passphrase_is_read_back() {
+        install_mock_store();
+        let key_id = unique_key_id("transfer");
+        let passphrase = "ab".repeat(32);
+        store_passphrase(SERVICE_ID, &key_id, &passphrase).unwrap();
+        assert_eq!(
+            get_or_create_passphrase(SERVICE_ID, &key_id)
+                .unwrap()
+                .as_str(),
+            passphrase
+        );
+        assert!(store_passphrase(SERVICE_ID, &key_id, "not hex").is_err());
+    }
+
+    #[test]
+    fn snapshots_never_replace_existing_state() {
+        let dir = unique_temp_dir();
+        let config = StorageConfig::new(&dir);
+        let snapshot = SessionSnapshot {
+            database: Zeroizing::new(vec![1, 2, 3]),
+            wal: Zeroizing::new(vec![4]),
+            passphrase: Zeroizing::new("ab".repeat(32)),
+            format: MLS_STORAGE_FORMAT,
+        };
+        config.write_snapshot(&snapshot).unwrap();
+        assert_eq!(std::fs::read(config.database_path()).unwrap(), [1, 2, 3]);
+
+        let copy = config.read_snapshot(&snapshot.passphrase).unwrap();
+        assert_eq!(copy.database.as_slice(), [1, 2, 3]);
+        assert_eq!(copy.wal.as_slice(), [4]);
+        assert_eq!(copy.format, MLS_STORAGE_FORMAT);
+        assert!(config.write_snapshot(&snapshot).is_err());
+
+        assert!(!config.is_retired());
+        config.retire().unwrap();
+        assert!(config.is_retired());
+        assert!(!format!("{copy:?}").contains(&"ab".repeat(32)));
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn storage_formats_outside_the_supported_range_are_refused() {
+        let dir = unique_temp_dir();
+        let config = StorageConfig::new(&dir);
+        assert_eq!(config.stored_format().unwrap(), None);
+        config.check_format().unwrap();
+
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(config.database_path(), b"db").unwrap();
+        assert_eq!(
+            config.stored_format().unwrap(),
+            Some(UNMARKED_MLS_STORAGE_FORMAT)
+        );
+        config.check_format().unwrap();
+
+        std::fs::write(
+            config.format_marker_path(),
+            (MLS_STORAGE_FORMAT + 1).to_string(),
+        )
+        .unwrap();
+        assert!(matches!(
+            config.check_format(),
+            Err(NostrError::IncompatibleMlsStorage { found, .. }) if found == MLS_STORAGE_FORMAT + 1
+        ));
+        std::fs::write(config.format_marker_path(), "garbage").unwrap();
+        assert!(matches!(
+            config.check_format(),
+            Err(NostrError::StorageError(_))
+        ));
+        config.mark_format().unwrap();
+        assert_eq!(config.stored_format().unwrap(), Some(MLS_STORAGE_FORMAT));
+
+        // A snapshot from a newer app is refused before anything is written.
+        let other = StorageConfig::new(unique_temp_dir());
+        let newer = SessionSnapshot {
+            database: Zeroizing::new(vec![1]),
+            wal: Zeroizing::new(Vec::new()),
+            passphrase: Zeroizing::new("ab".repeat(32)),
+            format: MLS_STORAGE_FORMAT + 1,
+        };
+        assert!(matches!(
+            other.write_snapshot(&newer),
+            Err(NostrError::IncompatibleMlsStorage { .. })
+        ));
+        assert!(!other.database_path().exists());
+        assert!(ensure_compatible_format(OLDEST_MLS_STORAGE_FORMAT - 1).is_err());
+
+        let _ = std::fs::remove_dir_all(&dir);
+        let _ = std::fs::remove_dir_all(&other.data_dir);
+    }
+
     #[test]
     fn destroy_legacy_key_is_idempotent() {
         install_mock_store();
@@ -116,6 +116,20 @@ impl LocationGroupConfig {
     }
 }
 
+/// A group's epoch position (see [`SessionManager::epoch_info`]).
+///
+/// [`SessionManager::epoch_info`]: super::SessionManager::epoch_info
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct EpochInfo {
+    /// The current MLS epoch.
+    pub epoch: u64,
+    /// The oldest epoch whose application messages can still be decrypted.
+    /// Messages from before it are unrecoverable on this device.
+    pub oldest_readable_epoch: u64,
+    /// Number of members at the current epoch.
+    pub member_count: usize,
+}
+
 /// Information about a joined or created group.
 ///
 /// A simplified, redaction-safe view of the group state suitable for the
@@ -148,6 +162,36 @@ impl std::fmt::Debug for LocationGroupInfo {
     }
 }
 
+/// Who joined or left a group in one update, as hex public keys.
+///
+/// The engine reports that a group's state changed, not how; Haven derives
+/// the delta by diffing the roster against the last one it stored (see
+/// `CircleManager::apply_group_update`).
+#[derive(Clone, Default, PartialEq, Eq)]
+pub struct MembershipDelta {
+    /// Members now in the group who were not before.
+    pub added: Vec<String>,
+    /// Members no longer in the group.
+    pub removed: Vec<String>,
+}
+
+impl MembershipDelta {
+    /// Whether nobody joined or left.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty()
+    }
+}
+
+impl std::fmt::Debug for MembershipDelta {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("MembershipDelta")
+            .field("added", &self.added.len())
+            .field("removed", &self.removed.len())
+            .finish()
+    }
+}
+
 /// Result of interpreting an ordered engine [`GroupEvent`] for the location
 /// sharing use case.
 ///
@@ -170,6 +214,63 @@ pub enum LocationMessageResult {
         /// The MLS epoch the message was authenticated at.
         epoch: u64,
     },
+    /// A decrypted emergency (SOS) broadcast: an application message whose
+    /// inner event carries a `["t","sos"]` tag. `content` is a
+    /// [`crate::emergency::SosMessage`] JSON payload, which also parses as a
+    /// plain location.
+    Sos {
+        /// The sender's public key (hex-encoded, from the MLS-authenticated
+        /// member id).
+        sender_pubkey: String,
+        /// The decrypted inner content (the SOS JSON payload).
+        content: String,
+        /// The MLS group ID this message belongs to.
+        group_id: GroupId,
+        /// The MLS epoch the message was authenticated at.
+        epoch: u64,
+    },
+    /// A decrypted check-in request: an application message whose inner event
+    /// carries a `["t","checkin_request"]` tag, asking `target_pubkey` to share
+    /// their current position. See [`crate::checkin`].
+    CheckinRequest {
+        /// The requester's public key (hex-encoded, from the MLS-authenticated
+        /// member id).
+        sender_pubkey: String,
+        /// The member being asked (hex-encoded, from the inner `p` tag).
+        target_pubkey: String,
+        /// The MLS group ID this message belongs to.
+        group_id: GroupId,
+        /// The MLS epoch the message was authenticated at.
+        epoch: u64,
+    },
+    /// A decrypted meet pin: an application message whose inner event carries
+    /// a `["t","meet_pin"]` tag. `content` is a [`crate::meet::MeetPin`] JSON
+    /// payload with exact coordinates; it is not the sender's own position.
+    MeetPin {
+        /// The sender's public key (hex-encoded, from the MLS-authenticated
+        /// member id).
+        sender_pubkey: String,
+        /// The decrypted inner content (the pin JSON payload).
+        content: String,
+        /// The MLS group ID this message belongs to.
+        group_id: GroupId,
+        /// The MLS epoch the message was authenticated at.
+        epoch: u64,
+    },
+    /// A sender's location was finer than the precision they had been
+    /// sharing at (see [`crate::location::precision`]). Emitted by
+    /// `CircleManager` right after the `Location` that triggered it, so the
+    /// recipient can check in with the sender.
+    PrecisionAnomaly {
+        /// The sender's public key (hex-encoded).
+        sender_pubkey: String,
+        /// The MLS group ID the location arrived in.
+        group_id: GroupId,
+        /// The precision the sender had established.
+        baseline: crate::location::PrecisionClass,
+        /// The finer precision just received.
+        observed: crate::location::PrecisionClass,
+    },
     /// The local client joined a group via an accepted welcome.
     Joined {
         /// The MLS group ID that was joined.
@@ -180,6 +281,9 @@ pub enum LocationMessageResult {
     GroupUpdate {
         /// The MLS group ID that was updated.
         group_id: GroupId,
+        /// Who joined or left. Empty as folded from the engine; filled in by
+        /// `CircleManager` once it has reconciled the stored roster.
+        membership: MembershipDelta,
     },
     /// A previously-surfaced application message or state change was withdrawn
     /// because branch selection superseded the commit that produced it. The
@@ -195,6 +299,37 @@ pub enum LocationMessageResult {
     },
 }
 
+impl LocationMessageResult {
+    /// Decodes the typed [`HavenPayload`] of a decrypted application message.
+    ///
+    /// Enveloped content is decoded by its own `type`; legacy content by the
+    /// inner topic tag this result was routed on. Returns `None` for results
+    /// that carry no message, or content that does not decode; a type this
+    /// version does not know is [`HavenPayload::Unknown`].
+    ///
+    /// [`HavenPayload`]: crate::payload::HavenPayload
+    /// [`HavenPayload::Unknown`]: crate::payload::HavenPayload::Unknown
+    #[must_use]
+    pub fn payload(&self) -> Option<crate::payload::HavenPayload> {
+        use crate::payload::{HavenPayload, LOCATION_TOPIC};
+
+        let (content, topic) = match self {
+            Self::Location { content, .. } => (content, LOCATION_TOPIC),
+            Self::Sos { content, .. } => (content, crate::emergency::SOS_TAG),
+            Self::MeetPin { content, .. } => (content, crate::meet::MEET_PIN_TAG),
+            Self::CheckinRequest { target_pubkey, .. } => {
+                return Some(HavenPayload::CheckinRequest {
+                    target_pubkey: target_pubkey.clone(),
+                });
+            }
+            _ => return None,
+        };
+        HavenPayload::decode(content, Some(topic))
+            .ok()
+            .map(|decoded| decoded.payload)
+    }
+}
+
 impl std::fmt::Debug for LocationMessageResult {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
@@ -205,13 +340,44 @@ impl std::fmt::Debug for LocationMessageResult {
                 .field("group_id", &"<redacted>")
                 .field("epoch", epoch)
                 .finish(),
+            Self::Sos { epoch, .. } => f
+                .debug_struct("Sos")
+                .field("sender_pubkey", &"<redacted>")
+                .field("content", &"<redacted>")
+                .field("group_id", &"<redacted>")
+                .field("epoch", epoch)
+                .finish(),
+            Self::MeetPin { epoch, .. } => f
+                .debug_struct("MeetPin")
+                .field("sender_pubkey", &"<redacted>")
+                .field("content", &"<redacted>")
+                .field("group_id", &"<redacted>")
+                .field("epoch", epoch)
+                .finish(),
+            Self::CheckinRequest { epoch, .. } => f
+                .debug_struct("CheckinRequest")
+                .field("sender_pubkey", &"<redacted>")
+                .field("target_pubkey", &"<redacted>")
+                .field("group_id", &"<redacted>")
+                .field("epoch", epoch)
+                .finish(),
+            Self::PrecisionAnomaly {
+                baseline, observed, ..
+            } => f
+                .debug_struct("PrecisionAnomaly")
+                .field("sender_pubkey", &"<redacted>")
+                .field("group_id", &"<redacted>")
+                .field("baseline", baseline)
+                .field("observed", observed)
+                .finish(),
             Self::Joined { .. } => f
                 .debug_struct("Joined")
                 .field("group_id", &"<redacted>")
                 .finish(),
-            Self::GroupUpdate { .. } => f
+            Self::GroupUpdate { membership, .. } => f
                 .debug_struct("GroupUpdate")
                 .field("group_id", &"<redacted>")
+                .field("membership", membership)
                 .finish(),
             Self::Invalidated { .. } => f
                 .debug_struct("Invalidated")
@@ -301,6 +467,10 @@ mod tests {
         for result in [
             LocationMessageResult::GroupUpdate {
                 group_id: GroupId::from_slice(&[1]),
+                membership: MembershipDelta {
+                    added: vec!["aa".repeat(32)],
+                    removed: vec!["bb".repeat(32)],
+                },
             },
             LocationMessageResult::Invalidated {
                 group_id: GroupId::from_slice(&[2]),
@@ -311,9 +481,34 @@ mod tests {
             LocationMessageResult::Unrecoverable {
                 group_id: GroupId::from_slice(&[4]),
             },
+            LocationMessageResult::Sos {
+                sender_pubkey: "pk".to_string(),
+                content: r#"{"lat":0}"#.to_string(),
+                group_id: GroupId::from_slice(&[5]),
+                epoch: 1,
+            },
+            LocationMessageResult::CheckinRequest {
+                sender_pubkey: "pk".to_string(),
+                target_pubkey: "target".to_string(),
+                group_id: GroupId::from_slice(&[6]),
+                epoch: 1,
+            },
+            LocationMessageResult::MeetPin {
+                sender_pubkey: "pk".to_string(),
+                content: r#"{"lat":0}"#.to_string(),
+                group_id: GroupId::from_slice(&[8]),
+                epoch: 1,
+            },
+            LocationMessageResult::PrecisionAnomaly {
+                sender_pubkey: "pk".to_string(),
+                group_id: GroupId::from_slice(&[7]),
+                baseline: crate::location::PrecisionClass::Coarse,
+                observed: crate::location::PrecisionClass::Exact,
+            },
         ] {
             let debug_str = format!("{result:?}");
             assert!(debug_str.contains("<redacted>"));
+            assert!(!debug_str.contains("aaaa") && !debug_str.contains("bbbb"));
         }
     }
 }
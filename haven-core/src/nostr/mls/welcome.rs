@@ -41,6 +41,10 @@ use nostr::{Event, EventId};
 pub struct WelcomePreview {
     /// The inviter's public key (hex-encoded), from the NIP-59 seal author.
     pub inviter_pubkey: String,
+    /// The inviter's self-chosen name hint from the Welcome rumor, if any.
+    /// **Unverified** — only ever a suggested contact name (see
+    /// [`crate::nostr::giftwrap::UnwrappedWelcome::name_hint`]).
+    pub suggested_name: Option<String>,
 }
 
 impl std::fmt::Debug for WelcomePreview {
@@ -49,6 +53,10 @@ impl std::fmt::Debug for WelcomePreview {
         // stray Debug line never correlates an invite to a specific inviter.
         f.debug_struct("WelcomePreview")
             .field("inviter_pubkey", &"<redacted>")
+            .field(
+                "suggested_name",
+                &self.suggested_name.as_ref().map(|_| "<redacted>"),
+            )
             .finish()
     }
 }
@@ -205,6 +213,7 @@ mod tests {
             fake_gift_wrap(tag),
             WelcomePreview {
                 inviter_pubkey: "deadbeef".to_string(),
+                suggested_name: None,
             },
         )
     }
@@ -53,10 +53,13 @@ pub mod encryption;
 pub mod giftwrap;
 pub mod identity;
 pub mod mls;
+pub mod nip46;
 
 pub use error::{NostrError, Result};
 pub use event::{
+    group_event_rejects, validate_group_event, GroupEventRejection, GroupEventRejects,
     SignedLocationEvent, UnsignedLocationEvent, KIND_GROUP_MESSAGE, KIND_LOCATION_DATA,
+    MAX_GROUP_EVENT_CONTENT_BYTES,
 };
 pub use identity::{
     IdentityError, IdentityKeypair, IdentityManager, PublicIdentity, SecureKeyStorage,
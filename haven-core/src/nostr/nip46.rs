@@ -0,0 +1,716 @@
+//! NIP-46 remote signing ("Nostr Connect"), client side.
+//!
+//! Lets the identity key live on a separate signer device (a "bunker", e.g.
+//! a parent's dedicated phone) instead of this one. Haven talks to it over the
+//! same [`RelayManager`] transport as everything else: each request is a
+//! kind-24133 event from a throwaway client key, NIP-44 encrypted to the
+//! bunker and tagged with its key; the answer comes back the same way.
+//!
+//! Pairing starts from a `bunker://<remote-signer-pubkey>?relay=wss://…&secret=…`
+//! URI shown by the signer ([`BunkerUri`]). [`pair`] sends `connect`, asks
+//! for the user's public key and returns a [`RemoteSignerConfig`] to persist
+//! and a [`Nip46Signer`] to sign with.
+//!
+//! [`Nip46Signer`] implements [`Signer`], so every event builder that takes
+//! a signer (key packages, relay lists, gift-wrap seals) can be routed
+//! through the bunker. It signs whole events ([`Signer::sign_unsigned_event`]),
+//! and [`crate::nostr::identity::sign_unsigned`] still verifies that what
+//! comes back is the event that was asked for, by the expected key.
+//!
+//! # Blocking
+//!
+//! [`Signer`] is synchronous while a round trip to the bunker is not: the
+//! signer blocks the calling thread with [`tokio::task::block_in_place`], so
+//! it must run on a multi-threaded Tokio runtime.
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use nostr::nips::nip44;
+use nostr::secp256k1::schnorr::Signature;
+use nostr::{
+    Event, EventBuilder, Filter, JsonUtil, Keys, Kind, PublicKey, SecretKey, Tag, UnsignedEvent,
+};
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+use zeroize::{Zeroize, ZeroizeOnDrop, Zeroizing};
+
+use super::identity::{IdentityError, Signer};
+use crate::relay::RelayManager;
+
+/// Event kind carrying NIP-46 requests and responses.
+pub const KIND_NOSTR_CONNECT: Kind = Kind::NostrConnect;
+
+/// How long to wait for the bunker to answer one request. Generous, since
+/// the user may have to approve it on the other device.
+pub const REQUEST_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// Errors talking to a remote signer.
+#[derive(Error, Debug)]
+pub enum Nip46Error {
+    /// The bunker URI or a stored configuration is malformed.
+    #[error("Invalid bunker URI: {0}")]
+    InvalidUri(String),
+
+    /// A request could not be sent or the subscription closed.
+    #[error("Relay error: {0}")]
+    Relay(String),
+
+    /// The bunker did not answer within [`REQUEST_TIMEOUT`].
+    #[error("Remote signer did not answer in time")]
+    Timeout,
+
+    /// The bunker wants the user to approve the client at this URL first.
+    #[error("Remote signer requires approval at {0}")]
+    AuthRequired(String),
+
+    /// The bunker answered with an error.
+    #[error("Remote signer refused: {0}")]
+    Rejected(String),
+
+    /// The bunker signs as another identity than this device's.
+    #[error("Remote signer holds a different identity")]
+    IdentityMismatch,
+
+    /// The bunker's answer could not be used.
+    #[error("Invalid remote signer response: {0}")]
+    InvalidResponse(String),
+}
+
+/// A parsed `bunker://` URI.
+#[derive(Clone, PartialEq, Eq, ZeroizeOnDrop)]
+pub struct BunkerUri {
+    /// The key the bunker answers requests with (not necessarily the user's).
+    #[zeroize(skip)]
+    pub remote_signer: PublicKey,
+    /// Relays the bunker listens on.
+    pub relays: Vec<String>,
+    /// One-time pairing secret, if the bunker issued one.
+    pub secret: Option<Zeroizing<String>>,
+}
+
+impl std::fmt::Debug for BunkerUri {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("BunkerUri")
+            .field("relays", &self.relays.len())
+            .field("secret", &self.secret.as_ref().map(|_| "<redacted>"))
+            .finish_non_exhaustive()
+    }
+}
+
+impl BunkerUri {
+    /// Parses `bunker://<hex pubkey>?relay=wss://…[&relay=…][&secret=…]`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Nip46Error::InvalidUri`] for another scheme, a bad public
+    /// key, or no relay.
+    pub fn parse(uri: &str) -> Result<Self, Nip46Error> {
+        let url = url::Url::parse(uri.trim()).map_err(|e| Nip46Error::InvalidUri(e.to_string()))?;
+        if url.scheme() != "bunker" {
+            return Err(Nip46Error::InvalidUri("not a bunker:// URI".to_string()));
+        }
+        let remote_signer = url
+            .host_str()
+            .ok_or_else(|| Nip46Error::InvalidUri("missing signer public key".to_string()))
+            .and_then(|host| {
+                PublicKey::from_hex(host).map_err(|e| Nip46Error::InvalidUri(e.to_string()))
+            })?;
+
+        let mut relays = Vec::new();
+        let mut secret = None;
+        for (key, value) in url.query_pairs() {
+            match key.as_ref() {
+                "relay" if !relays.iter().any(|r| *r == value) => relays.push(value.into_owned()),
+                "secret" if !value.is_empty() => {
+                    secret = Some(Zeroizing::new(value.into_owned()));
+                }
+                _ => {}
+            }
+        }
+        if relays.is_empty() {
+            return Err(Nip46Error::InvalidUri("no relay".to_string()));
+        }
+        Ok(Self {
+            remote_signer,
+            relays,
+            secret,
+        })
+    }
+}
+
+/// A paired remote signer, as persisted (see
+/// [`crate::circle::CircleStorage::set_remote_signer`]).
+#[derive(Clone, PartialEq, Eq, Serialize, Deserialize, Zeroize, ZeroizeOnDrop)]
+pub struct RemoteSignerConfig {
+    /// The bunker's own key (hex).
+    pub remote_signer: String,
+    /// Relays the bunker listens on.
+    pub relays: Vec<String>,
+    /// This device's client secret key (hex). Not the identity key: it only
+    /// authenticates requests to the bunker.
+    #[serde(with = "crate::util::zeroizing_string")]
+    pub client_secret: Zeroizing<String>,
+    /// The identity the bunker signs as (hex).
+    pub user_pubkey: String,
+    /// When the pairing was made (unix seconds).
+    pub connected_at: i64,
+}
+
+impl std::fmt::Debug for RemoteSignerConfig {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RemoteSignerConfig")
+            .field("relays", &self.relays.len())
+            .field("client_secret", &"<redacted>")
+            .field("connected_at", &self.connected_at)
+            .finish_non_exhaustive()
+    }
+}
+
+/// A NIP-46 JSON-RPC request.
+#[derive(Debug, Serialize, Deserialize)]
+struct Request {
+    id: String,
+    method: String,
+    params: Vec<String>,
+}
+
+/// A NIP-46 JSON-RPC response.
+#[derive(Debug, Serialize, Deserialize)]
+struct Response {
+    id: String,
+    #[serde(default)]
+    result: Option<String>,
+    #[serde(default)]
+    error: Option<String>,
+}
+
+/// Sends NIP-46 requests to one bunker.
+pub struct Nip46Client {
+    relay: RelayManager,
+    client_keys: Keys,
+    remote_signer: PublicKey,
+    relays: Vec<String>,
+    timeout: Duration,
+}
+
+impl std::fmt::Debug for Nip46Client {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Nip46Client")
+            .field("relays", &self.relays.len())
+            .finish_non_exhaustive()
+    }
+}
+
+impl Nip46Client {
+    /// A client talking to `remote_signer` on `relays` as `client_keys`.
+    #[must_use]
+    pub fn new(
+        relay: RelayManager,
+        client_keys: Keys,
+        remote_signer: PublicKey,
+        relays: Vec<String>,
+    ) -> Self {
+        Self {
+            relay,
+            client_keys,
+            remote_signer,
+            relays,
+            timeout: REQUEST_TIMEOUT,
+        }
+    }
+
+    /// The client for a stored pairing.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Nip46Error::InvalidUri`] if a stored key does not parse.
+    pub fn from_config(
+        relay: RelayManager,
+        config: &RemoteSignerConfig,
+    ) -> Result<Self, Nip46Error> {
+        let invalid = |e: nostr::key::Error| Nip46Error::InvalidUri(e.to_string());
+        let remote_signer = PublicKey::from_hex(&config.remote_signer).map_err(invalid)?;
+        let client_keys = Keys::new(SecretKey::from_hex(&config.client_secret).map_err(invalid)?);
+        Ok(Self::new(
+            relay,
+            client_keys,
+            remote_signer,
+            config.relays.clone(),
+        ))
+    }
+
+    /// Waits `timeout` instead of [`REQUEST_TIMEOUT`] for each answer.
+    #[must_use]
+    pub const fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Sends `connect`, with the pairing `secret` if the bunker issued one.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`Nip46Error`] if the bunker refuses or does not answer.
+    pub async fn connect(&self, secret: Option<&str>) -> Result<(), Nip46Error> {
+        let mut params = vec![self.remote_signer.to_hex()];
+        params.extend(secret.map(str::to_owned));
+        let result = self.request("connect", params).await?;
+        // "ack", or the secret echoed back.
+        if result == "ack" || secret == Some(result.as_str()) {
+            Ok(())
+        } else {
+            Err(Nip46Error::InvalidResponse(
+                "unexpected connect reply".to_string(),
+            ))
+        }
+    }
+
+    /// The identity the bunker signs as.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`Nip46Error`] if the bunker refuses, does not answer, or
+    /// answers with something other than a public key.
+    pub async fn get_public_key(&self) -> Result<PublicKey, Nip46Error> {
+        let result = self.request("get_public_key", Vec::new()).await?;
+        PublicKey::from_hex(result.trim()).map_err(|e| Nip46Error::InvalidResponse(e.to_string()))
+    }
+
+    /// Has the bunker sign `unsigned`. The returned event is not checked;
+    /// see [`crate::nostr::identity::sign_unsigned`].
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`Nip46Error`] if the bunker refuses, does not answer, or
+    /// answers with something other than an event.
+    pub async fn sign_event(&self, unsigned: &UnsignedEvent) -> Result<Event, Nip46Error> {
+        let template = serde_json::json!({
+            "kind": unsigned.kind.as_u16(),
+            "content": unsigned.content,
+            "tags": unsigned.tags,
+            "created_at": unsigned.created_at.as_secs(),
+        });
+        let result = self
+            .request("sign_event", vec![template.to_string()])
+            .await?;
+        Event::from_json(result).map_err(|e| Nip46Error::InvalidResponse(e.to_string()))
+    }
+
+    /// Has the bunker NIP-44 encrypt `plaintext` to `recipient`.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`Nip46Error`] if the bunker refuses or does not answer.
+    pub async fn nip44_encrypt(
+        &self,
+        recipient: &PublicKey,
+        plaintext: &str,
+    ) -> Result<String, Nip46Error> {
+        self.request(
+            "nip44_encrypt",
+            vec![recipient.to_hex(), plaintext.to_string()],
+        )
+        .await
+    }
+
+    /// Sends one request and waits for the answer with the same id.
+    async fn request(&self, method: &str, params: Vec<String>) -> Result<String, Nip46Error> {
+        let id = hex::encode(rand::random::<[u8; 16]>());
+        let body = serde_json::to_string(&Request {
+            id: id.clone(),
+            method: method.to_string(),
+            params,
+        })
+        .map_err(|e| Nip46Error::Relay(e.to_string()))?;
+        let content = nip44::encrypt(
+            self.client_keys.secret_key(),
+            &self.remote_signer,
+            body,
+            nip44::Version::V2,
+        )
+        .map_err(|e| Nip46Error::Relay(e.to_string()))?;
+        let event = EventBuilder::new(KIND_NOSTR_CONNECT, content)
+            .tag(Tag::public_key(self.remote_signer))
+            .sign_with_keys(&self.client_keys)
+            .map_err(|e| Nip46Error::Relay(e.to_string()))?;
+
+        // Subscribe before sending so a quick answer is not missed. The kind
+        // is ephemeral: relays forward it without storing it.
+        let filter = Filter::new()
+            .kind(KIND_NOSTR_CONNECT)
+            .author(self.remote_signer)
+            .pubkey(self.client_keys.public_key());
+        let mut answers = self
+            .relay
+            .subscribe(vec![filter], &self.relays)
+            .await
+            .map_err(|e| Nip46Error::Relay(e.to_string()))?;
+        self.relay
+            .publish_event(&event, &self.relays)
+            .await
+            .map_err(|e| Nip46Error::Relay(e.to_string()))?;
+
+        let wait = async {
+            while let Some(answer) = answers.recv().await {
+                if let Some(response) = self.open(&answer).filter(|r| r.id == id) {
+                    return Self::result(response);
+                }
+            }
+            Err(Nip46Error::Relay("subscription closed".to_string()))
+        };
+        tokio::time::timeout(self.timeout, wait)
+            .await
+            .map_err(|_| Nip46Error::Timeout)?
+    }
+
+    /// Decrypts an answer from the bunker; `None` for anything else.
+    fn open(&self, event: &Event) -> Option<Response> {
+        if event.kind != KIND_NOSTR_CONNECT
+            || event.pubkey != self.remote_signer
+            || event.verify().is_err()
+        {
+            return None;
+        }
+        let json = nip44::decrypt(
+            self.client_keys.secret_key(),
+            &self.remote_signer,
+            &event.content,
+        )
+        .ok()?;
+        serde_json::from_str(&json).ok()
+    }
+
+    fn result(response: Response) -> Result<String, Nip46Error> {
+        let error = response.error.filter(|e| !e.is_empty());
+        match (response.result, error) {
+            (Some(result), Some(url)) if result == "auth_url" => Err(Nip46Error::AuthRequired(url)),
+            (_, Some(error)) => Err(Nip46Error::Rejected(error)),
+            (Some(result), None) => Ok(result),
+            (None, None) => Err(Nip46Error::InvalidResponse("empty response".to_string())),
+        }
+    }
+}
+
+/// Pairs with the bunker at `uri` using a fresh client key.
+///
+/// Sends `connect` and asks for the user's public key, which must be
+/// `identity`: a bunker holding another key would sign relay lists and key
+/// packages as someone else. Returns the signer and the configuration to
+/// persist, stamped `now`.
+///
+/// # Errors
+///
+/// Returns [`Nip46Error::IdentityMismatch`] if the bunker signs as another
+/// identity, or another [`Nip46Error`] if it refuses or does not answer.
+pub async fn pair(
+    relay: RelayManager,
+    uri: &BunkerUri,
+    identity: &PublicKey,
+    now: i64,
+) -> Result<(Nip46Signer, RemoteSignerConfig), Nip46Error> {
+    let client_keys = Keys::generate();
+    let client_secret = Zeroizing::new(client_keys.secret_key().to_secret_hex());
+    let client = Nip46Client::new(relay, client_keys, uri.remote_signer, uri.relays.clone());
+    client
+        .connect(uri.secret.as_ref().map(|s| s.as_str()))
+        .await?;
+    let user = client.get_public_key().await?;
+    if user != *identity {
+        return Err(Nip46Error::IdentityMismatch);
+    }
+    let config = RemoteSignerConfig {
+        remote_signer: uri.remote_signer.to_hex(),
+        relays: uri.relays.clone(),
+        client_secret,
+        user_pubkey: user.to_hex(),
+        connected_at: now,
+    };
+    Ok((Nip46Signer::new(client, user), config))
+}
+
+/// A [`Signer`] backed by a bunker.
+///
+/// Blocks the calling thread on each round trip; see the module docs.
+#[derive(Clone, Debug)]
+pub struct Nip46Signer {
+    client: Arc<Nip46Client>,
+    user: PublicKey,
+}
+
+impl Nip46Signer {
+    /// A signer for `user`, whose key the bunker behind `client` holds.
+    #[must_use]
+    pub fn new(client: Nip46Client, user: PublicKey) -> Self {
+        Self {
+            client: Arc::new(client),
+            user,
+        }
+    }
+
+    /// The signer for a stored pairing.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Nip46Error::InvalidUri`] if a stored key does not parse.
+    pub fn from_config(
+        relay: RelayManager,
+        config: &RemoteSignerConfig,
+    ) -> Result<Self, Nip46Error> {
+        let user = PublicKey::from_hex(&config.user_pubkey)
+            .map_err(|e| Nip46Error::InvalidUri(e.to_string()))?;
+        Ok(Self::new(Nip46Client::from_config(relay, config)?, user))
+    }
+
+    /// The client this signer sends its requests through.
+    #[must_use]
+    pub fn client(&self) -> &Nip46Client {
+        &self.client
+    }
+
+    fn wait<T>(
+        future: impl std::future::Future<Output = Result<T, Nip46Error>>,
+    ) -> Result<T, Nip46Error> {
+        let handle = tokio::runtime::Handle::try_current()
+            .map_err(|_| Nip46Error::Relay("no async runtime".to_string()))?;
+        if handle.runtime_flavor() == tokio::runtime::RuntimeFlavor::CurrentThread {
+            return Err(Nip46Error::Relay(
+                "remote signing needs a multi-threaded runtime".to_string(),
+            ));
+        }
+        tokio::task::block_in_place(|| handle.block_on(future))
+    }
+}
+
+impl Signer for Nip46Signer {
+    fn public_key(&self) -> PublicKey {
+        self.user
+    }
+
+    fn sign_event_hash(&self, _hash: &[u8; 32]) -> Result<Signature, IdentityError> {
+        Err(IdentityError::Signing(
+            "a remote signer signs whole events only".to_string(),
+        ))
+    }
+
+    fn nip44_encrypt(
+        &self,
+        recipient: &PublicKey,
+        plaintext: &str,
+    ) -> Result<String, IdentityError> {
+        Self::wait(self.client.nip44_encrypt(recipient, plaintext))
+            .map_err(|e| IdentityError::Encryption(e.to_string()))
+    }
+
+    fn sign_unsigned_event(&self, unsigned: UnsignedEvent) -> Result<Event, IdentityError> {
+        Self::wait(self.client.sign_event(&unsigned))
+            .map_err(|e| IdentityError::Signing(e.to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::nostr::identity::{gift_wrap, sign_event};
+    use crate::relay::MemoryTransport;
+    use nostr::nips::nip59::UnwrappedGift;
+
+    const RELAY: &str = "wss://bunker.nip46.test";
+
+    /// Answers every request published to [`RELAY`] as a bunker holding
+    /// `user`, until the test ends.
+    fn run_bunker(transport: Arc<MemoryTransport>, bunker: Keys, user: Keys) {
+        tokio::spawn(async move {
+            let mut answered = 0;
+            loop {
+                let requests = transport.published(RELAY);
+                for request in requests.iter().skip(answered) {
+                    let json =
+                        nip44::decrypt(bunker.secret_key(), &request.pubkey, &request.content)
+                            .unwrap();
+                    let request_body: Request = serde_json::from_str(&json).unwrap();
+                    let result = match request_body.method.as_str() {
+                        "connect" => "ack".to_string(),
+                        "get_public_key" => user.public_key().to_hex(),
+                        "sign_event" => {
+                            let template: serde_json::Value =
+                                serde_json::from_str(&request_body.params[0]).unwrap();
+                            let kind = u16::try_from(template["kind"].as_u64().unwrap()).unwrap();
+                            let tags: Vec<Tag> =
+                                serde_json::from_value(template["tags"].clone()).unwrap();
+                            EventBuilder::new(
+                                Kind::from(kind),
+                                template["content"].as_str().unwrap(),
+                            )
+                            .tags(tags)
+                            .custom_created_at(nostr::Timestamp::from(
+                                template["created_at"].as_u64().unwrap(),
+                            ))
+                            .sign_with_keys(&user)
+                            .unwrap()
+                            .as_json()
+                        }
+                        "nip44_encrypt" => nip44::encrypt(
+                            user.secret_key(),
+                            &PublicKey::from_hex(&request_body.params[0]).unwrap(),
+                            &request_body.params[1],
+                            nip44::Version::V2,
+                        )
+                        .unwrap(),
+                        _ => String::new(),
+                    };
+                    let body = serde_json::to_string(&Response {
+                        id: request_body.id,
+                        result: Some(result),
+                        error: None,
+                    })
+                    .unwrap();
+                    let content = nip44::encrypt(
+                        bunker.secret_key(),
+                        &request.pubkey,
+                        body,
+                        nip44::Version::V2,
+                    )
+                    .unwrap();
+                    let answer = EventBuilder::new(KIND_NOSTR_CONNECT, content)
+                        .tag(Tag::public_key(request.pubkey))
+                        .sign_with_keys(&bunker)
+                        .unwrap();
+                    transport.deliver(RELAY, &answer);
+                }
+                answered = requests.len();
+                tokio::time::sleep(Duration::from_millis(5)).await;
+            }
+        });
+    }
+
+    #[test]
+    fn bunker_uris_parse() {
+        let signer = Keys::generate().public_key();
+        let uri = BunkerUri::parse(&format!(
+            "bunker://{}?relay=wss%3A%2F%2Fa.example&relay=wss://b.example&secret=s3cret",
+            signer.to_hex()
+        ))
+        .unwrap();
+        assert_eq!(uri.remote_signer, signer);
+        assert_eq!(uri.relays, ["wss://a.example", "wss://b.example"]);
+        assert_eq!(uri.secret.as_ref().map(|s| s.as_str()), Some("s3cret"));
+
+        for bad in [
+            format!("nostrconnect://{}?relay=wss://a.example", signer.to_hex()),
+            format!("bunker://{}", signer.to_hex()),
+            "bunker://nothex?relay=wss://a.example".to_string(),
+        ] {
+            assert!(
+                matches!(BunkerUri::parse(&bad), Err(Nip46Error::InvalidUri(_))),
+                "{bad}"
+            );
+        }
+    }
+
+    #[test]
+    fn config_debug_hides_the_client_secret() {
+        let config = RemoteSignerConfig {
+            remote_signer: Keys::generate().public_key().to_hex(),
+            relays: vec![RELAY.to_string()],
+            client_secret: Zeroizing::new(Keys::generate().secret_key().to_secret_hex()),
+            user_pubkey: Keys::generate().public_key().to_hex(),
+            connected_at: 1,
+        };
+        assert!(!format!("{config:?}").contains(config.client_secret.as_str()));
+    }
+
+    #[test]
+    fn error_responses_are_surfaced() {
+        let response = |result: Option<&str>, error: Option<&str>| Response {
+            id: "1".to_string(),
+            result: result.map(str::to_owned),
+            error: error.map(str::to_owned),
+        };
+        assert!(matches!(
+            Nip46Client::result(response(Some("auth_url"), Some("https://signer.example"))),
+            Err(Nip46Error::AuthRequired(url)) if url == "https://signer.example"
+        ));
+        assert!(matches!(
+            Nip46Client::result(response(None, Some("denied"))),
+            Err(Nip46Error::Rejected(_))
+        ));
+        assert_eq!(
+            Nip46Client::result(response(Some("ack"), Some(""))).unwrap(),
+            "ack"
+        );
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn pairs_and_signs_through_the_bunker() {
+        let transport = Arc::new(MemoryTransport::new());
+        let (bunker, user) = (Keys::generate(), Keys::generate());
+        run_bunker(transport.clone(), bunker.clone(), user.clone());
+
+        let uri = BunkerUri {
+            remote_signer: bunker.public_key(),
+            relays: vec![RELAY.to_string()],
+            secret: None,
+        };
+        assert!(matches!(
+            pair(
+                RelayManager::with_transport(transport.clone()),
+                &uri,
+                &Keys::generate().public_key(),
+                7
+            )
+            .await,
+            Err(Nip46Error::IdentityMismatch)
+        ));
+        let (signer, config) = pair(
+            RelayManager::with_transport(transport.clone()),
+            &uri,
+            &user.public_key(),
+            7,
+        )
+        .await
+        .unwrap();
+        assert_eq!(signer.public_key(), user.public_key());
+        assert_eq!(config.user_pubkey, user.public_key().to_hex());
+        assert_ne!(
+            config.client_secret.as_str(),
+            user.secret_key().to_secret_hex()
+        );
+
+        let signer =
+            Nip46Signer::from_config(RelayManager::with_transport(transport), &config).unwrap();
+        let recipient = Keys::generate();
+        let (note, wrap, recipient) = tokio::task::spawn_blocking(move || {
+            let note = sign_event(EventBuilder::new(Kind::TextNote, "hi"), &signer).unwrap();
+            let rumor = EventBuilder::new(Kind::Custom(9), "hello").build(signer.public_key());
+            let wrap = gift_wrap(&signer, &recipient.public_key(), rumor, []).unwrap();
+            (note, wrap, recipient)
+        })
+        .await
+        .unwrap();
+        assert_eq!(note.pubkey, user.public_key());
+        assert!(note.verify().is_ok());
+
+        let unwrapped = UnwrappedGift::from_gift_wrap(&recipient, &wrap)
+            .await
+            .unwrap();
+        assert_eq!(unwrapped.sender, user.public_key());
+        assert_eq!(unwrapped.rumor.content, "hello");
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn an_unanswered_request_times_out() {
+        let transport = Arc::new(MemoryTransport::new());
+        let client = Nip46Client::new(
+            RelayManager::with_transport(transport),
+            Keys::generate(),
+            Keys::generate().public_key(),
+            vec![RELAY.to_string()],
+        )
+        .with_timeout(Duration::from_millis(50));
+        assert!(matches!(
+            client.get_public_key().await,
+            Err(Nip46Error::Timeout)
+        ));
+    }
+}
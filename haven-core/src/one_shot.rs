@@ -0,0 +1,272 @@
+//! One-shot location sends to a single contact.
+//!
+//! "Send my location once" to someone outside any circle. Creating a
+//! transient two-person MLS group for it would consume the contact's
+//! `KeyPackage`, leave a group in the engine that the Dark Matter v0.9.4
+//! public API cannot delete, and put a circle in both users' lists. Instead
+//! the location travels as a NIP-17-style sealed message:
+//!
+//! ```text
+//! kind 1059 gift wrap (ephemeral key, NIP-40 expiration)
+//!   └─ kind 13 seal (sender's key, NIP-44)
+//!        └─ kind 9 rumor, ["t","one_shot_location"], NIP-40 expiration
+//!             content: LocationMessage JSON
+//! ```
+//!
+//! The wrap goes to the contact's inbox relays, where the
+//! [`InboxProcessor`](crate::relay::inbox::InboxProcessor) picks it up next
+//! to invitations and surfaces it as a [`OneShotLocation`]. Nothing is kept
+//! on either side: the sender stores no state, and the receiver records only
+//! the wrap id (in the gift-wrap dedup cache) so later polls skip it.
+//!
+//! # Expiry
+//!
+//! The sender picks a lifetime of [`MIN_ONE_SHOT_TTL_SECS`] to
+//! [`MAX_ONE_SHOT_TTL_SECS`]. It is carried three times: as the location's
+//! `share_expires_at`, in the rumor's `expiration` tag (authoritative for the
+//! receiver), and in the wrap's `expiration` tag so relays drop it. A one-shot
+//! read after it lapsed is discarded, never surfaced.
+//!
+//! # Privacy
+//!
+//! The coordinates are sent exactly as given. Unlike MLS messages, a sealed
+//! message has no forward secrecy: whoever later obtains the recipient's
+//! identity key can decrypt a wrap a relay kept despite its expiration.
+
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use nostr::nips::nip59::UnwrappedGift;
+use nostr::{Event, EventBuilder, EventId, Keys, Kind, PublicKey, Tag, Timestamp};
+
+use crate::location::LocationMessage;
+use crate::nostr::identity::{gift_wrap, Signer};
+use crate::nostr::{NostrError, Result, KIND_LOCATION_DATA};
+use crate::relay::{PublishResult, RelayError, RelayManager, RelayResult};
+
+/// Rumor hashtag marking a one-shot location.
+pub const ONE_SHOT_TAG: &str = "one_shot_location";
+
+/// Default lifetime of a one-shot location, in seconds (1 hour).
+pub const DEFAULT_ONE_SHOT_TTL_SECS: u64 = 60 * 60;
+
+/// Shortest lifetime a one-shot location can be given, in seconds (5 minutes).
+pub const MIN_ONE_SHOT_TTL_SECS: u64 = 5 * 60;
+
+/// Longest lifetime a one-shot location can be given, in seconds (24 hours).
+pub const MAX_ONE_SHOT_TTL_SECS: u64 = 24 * 60 * 60;
+
+/// A one-shot location received from a contact.
+#[derive(Clone)]
+pub struct OneShotLocation {
+    /// The sender's real public key (from the seal).
+    pub sender_pubkey: PublicKey,
+    /// The event id of the gift wrap it arrived in.
+    pub wrapper_event_id: EventId,
+    /// The shared location.
+    pub location: LocationMessage,
+    /// Unix timestamp after which it must no longer be shown.
+    pub expires_at: i64,
+}
+
+impl OneShotLocation {
+    /// Whether the location has lapsed at `now`.
+    #[must_use]
+    pub const fn is_expired(&self, now: i64) -> bool {
+        now >= self.expires_at
+    }
+}
+
+impl std::fmt::Debug for OneShotLocation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("OneShotLocation")
+            .field("sender_pubkey", &"<redacted>")
+            .field("wrapper_event_id", &self.wrapper_event_id)
+            .field("location", &self.location)
+            .field("expires_at", &self.expires_at)
+            .finish()
+    }
+}
+
+/// Gift-wraps `location` for `recipient`, living `ttl_secs` (clamped to
+/// [`MIN_ONE_SHOT_TTL_SECS`]..=[`MAX_ONE_SHOT_TTL_SECS`]).
+///
+/// Returns a kind 1059 event ready to publish to the recipient's inbox
+/// relays.
+///
+/// # Errors
+///
+/// Returns [`NostrError::GiftWrap`] if the location does not serialize or
+/// encryption fails.
+pub fn wrap_one_shot_location(
+    sender: &dyn Signer,
+    recipient: &PublicKey,
+    location: &LocationMessage,
+    ttl_secs: u64,
+) -> Result<Event> {
+    let ttl = ttl_secs.clamp(MIN_ONE_SHOT_TTL_SECS, MAX_ONE_SHOT_TTL_SECS);
+    let expiration = Timestamp::now() + Duration::from_secs(ttl);
+
+    let mut location = location.clone();
+    let expires_at =
+        DateTime::<Utc>::from_timestamp(i64::try_from(expiration.as_secs()).unwrap_or(i64::MAX), 0)
+            .unwrap_or(location.expires_at);
+    location.share_expires_at = Some(expires_at);
+    location.expires_at = location.expires_at.min(expires_at);
+    let content = location
+        .to_string()
+        .map_err(|e| NostrError::GiftWrap(format!("Failed to encode location: {e}")))?;
+
+    let rumor = EventBuilder::new(Kind::Custom(KIND_LOCATION_DATA), content)
+        .tags([Tag::hashtag(ONE_SHOT_TAG), Tag::expiration(expiration)])
+        .build(sender.public_key());
+
+    gift_wrap(sender, recipient, rumor, [Tag::expiration(expiration)])
+        .map_err(|e| NostrError::GiftWrap(e.to_string()))
+}
+
+/// Unwraps a gift wrap carrying a one-shot location.
+///
+/// The result may already be expired; check [`OneShotLocation::is_expired`]
+/// before showing it.
+///
+/// # Errors
+///
+/// Returns [`NostrError::GiftUnwrap`] if the event is not a gift wrap for
+/// `recipient_keys`, or does not carry a one-shot location.
+pub async fn unwrap_one_shot_location(
+    recipient_keys: &Keys,
+    gift_wrap_event: &Event,
+) -> Result<OneShotLocation> {
+    if gift_wrap_event.kind != Kind::GiftWrap {
+        return Err(NostrError::GiftUnwrap(format!(
+            "Event is not a gift wrap, got kind {}",
+            gift_wrap_event.kind.as_u16()
+        )));
+    }
+    let unwrapped = UnwrappedGift::from_gift_wrap(recipient_keys, gift_wrap_event)
+        .await
+        .map_err(|e| NostrError::GiftUnwrap(e.to_string()))?;
+    let rumor = unwrapped.rumor;
+    if rumor.kind != Kind::Custom(KIND_LOCATION_DATA) || rumor.pubkey != unwrapped.sender {
+        return Err(NostrError::GiftUnwrap(
+            "Gift wrap does not contain a one-shot location".to_string(),
+        ));
+    }
+    let tag_value = |name: &str| {
+        rumor.tags.iter().find_map(|tag| {
+            let v = tag.as_slice();
+            (v.len() >= 2 && v[0] == name).then(|| v[1].clone())
+        })
+    };
+    let tagged = rumor.tags.iter().any(|tag| {
+        let v = tag.as_slice();
+        v.len() >= 2 && v[0] == "t" && v[1] == ONE_SHOT_TAG
+    });
+    let expires_at = tag_value("expiration").and_then(|v| v.parse::<i64>().ok());
+    let (true, Some(expires_at)) = (tagged, expires_at) else {
+        return Err(NostrError::GiftUnwrap(
+            "Gift wrap does not contain a one-shot location".to_string(),
+        ));
+    };
+    let location = LocationMessage::from_string(&rumor.content)
+        .map_err(|e| NostrError::GiftUnwrap(format!("Invalid one-shot location: {e}")))?;
+
+    Ok(OneShotLocation {
+        sender_pubkey: unwrapped.sender,
+        wrapper_event_id: gift_wrap_event.id,
+        location,
+        expires_at,
+    })
+}
+
+/// Sends `location` once to `contact`, living `ttl_secs`.
+///
+/// Publishes to the contact's inbox relays (kind 10050), falling back to
+/// their NIP-65 read relays. Fails closed if they list neither: there is no
+/// other place the contact is known to read.
+///
+/// # Errors
+///
+/// Returns [`RelayError::Publish`] if the contact lists no relays,
+/// [`RelayError::InvalidEvent`] if wrapping fails, and otherwise the errors
+/// of [`RelayManager::publish_event`].
+pub async fn send_one_shot_location(
+    relays: &RelayManager,
+    sender: &dyn Signer,
+    contact: &PublicKey,
+    location: &LocationMessage,
+    ttl_secs: u64,
+) -> RelayResult<PublishResult> {
+    let contact_hex = contact.to_hex();
+    let mut targets = relays
+        .fetch_inbox_relays(&contact_hex)
+        .await
+        .unwrap_or_default();
+    if targets.is_empty() {
+        targets = relays
+            .fetch_nip65_relays(&contact_hex)
+            .await
+            .unwrap_or_default();
+    }
+    if targets.is_empty() {
+        return Err(RelayError::Publish(
+            "contact lists no inbox relays".to_string(),
+        ));
+    }
+
+    let wrap = wrap_one_shot_location(sender, contact, location, ttl_secs)
+        .map_err(|e| RelayError::InvalidEvent(e.to_string()))?;
+    relays.publish_event(&wrap, &targets).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn roundtrips_to_the_recipient_only() {
+        let (alice, bob) = (Keys::generate(), Keys::generate());
+        let location = LocationMessage::new(52.52, 13.405);
+        let wrap = wrap_one_shot_location(&alice, &bob.public_key(), &location, 600).unwrap();
+        assert_eq!(wrap.kind, Kind::GiftWrap);
+        assert!(wrap
+            .tags
+            .iter()
+            .any(|t| t.as_slice().first().map(String::as_str) == Some("expiration")));
+
+        let received = unwrap_one_shot_location(&bob, &wrap).await.unwrap();
+        assert_eq!(received.sender_pubkey, alice.public_key());
+        assert_eq!(received.wrapper_event_id, wrap.id);
+        assert!((received.location.latitude - 52.52).abs() < 1e-9);
+        let now = Utc::now().timestamp();
+        assert!(!received.is_expired(now));
+        assert!(received.is_expired(now + 601));
+
+        assert!(unwrap_one_shot_location(&Keys::generate(), &wrap)
+            .await
+            .is_err());
+    }
+
+    #[tokio::test]
+    async fn clamps_lifetime() {
+        let (alice, bob) = (Keys::generate(), Keys::generate());
+        let location = LocationMessage::new(1.0, 2.0);
+        let wrap = wrap_one_shot_location(&alice, &bob.public_key(), &location, 0).unwrap();
+        let received = unwrap_one_shot_location(&bob, &wrap).await.unwrap();
+        let lifetime = received.expires_at - Utc::now().timestamp();
+        let min = i64::try_from(MIN_ONE_SHOT_TTL_SECS).unwrap();
+        assert!((min - 5..=min).contains(&lifetime));
+    }
+
+    #[tokio::test]
+    async fn rejects_other_gift_wraps() {
+        let (alice, bob) = (Keys::generate(), Keys::generate());
+        let rumor =
+            EventBuilder::new(Kind::Custom(KIND_LOCATION_DATA), "{}").build(alice.public_key());
+        let wrap = EventBuilder::gift_wrap(&alice, &bob.public_key(), rumor, [])
+            .await
+            .unwrap();
+        assert!(unwrap_one_shot_location(&bob, &wrap).await.is_err());
+    }
+}
@@ -0,0 +1,320 @@
+//! Versioned, typed envelope for the content of inner application messages.
+//!
+//! Every kind-9 rumor Haven sends carries one [`HavenPayload`] as its JSON
+//! content, stamped with a schema version and a `type` discriminator:
+//!
+//! ```json
+//! {"v":1,"type":"location","latitude":57.64911,"longitude":10.40744,...}
+//! ```
+//!
+//! The payload's own fields stay at the top level, so a client that predates
+//! the envelope still parses the content as it did before (unknown keys are
+//! ignored) and still routes it by the rumor's `["t", ...]` tag, which senders
+//! keep emitting.
+//!
+//! # Decoding
+//!
+//! [`HavenPayload::decode`] dispatches on `type` when present. Content without
+//! one is a legacy payload (schema version `0`) and is decoded by the rumor's
+//! topic tag instead. Each variant goes through its own parser, so the
+//! sanitization those apply (SOS note, meet-pin label and bounds) is never
+//! skipped. A `type` this version does not know decodes to
+//! [`HavenPayload::Unknown`] rather than an error, so new message types can be
+//! added without breaking older receivers; a newer schema version is decoded
+//! on a best-effort basis for the same reason.
+
+use serde::{Deserialize, Serialize};
+
+use crate::emergency::SosMessage;
+use crate::location::{DeviceStatus, LocationMessage};
+use crate::meet::MeetPin;
+
+/// The schema version this build writes.
+pub const PAYLOAD_SCHEMA_VERSION: u32 = 1;
+
+/// Schema version reported for content without an envelope.
+pub const LEGACY_SCHEMA_VERSION: u32 = 0;
+
+/// Topic tag of a location rumor (`["t","location"]`).
+pub const LOCATION_TOPIC: &str = "location";
+
+/// The content of one inner application message.
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum HavenPayload {
+    /// A location update.
+    Location(LocationMessage),
+    /// An emergency broadcast.
+    Sos(SosMessage),
+    /// A request that `target_pubkey` share their location now.
+    CheckinRequest {
+        /// Hex public key of the member being asked.
+        target_pubkey: String,
+    },
+    /// A time-limited meeting point.
+    MeetPin(MeetPin),
+    /// Device status on its own, without a position.
+    Status(DeviceStatus),
+    /// Cover traffic (see [`crate::relay::chaff`]): random padding that
+    /// receivers drop unread.
+    Chaff {
+        /// Random characters sizing the message like a location update.
+        padding: String,
+    },
+    /// A type this version does not know (sent by a newer client).
+    #[serde(other)]
+    Unknown,
+}
+
+/// A decoded payload and the schema version it was written with.
+#[derive(Debug, Clone)]
+pub struct DecodedPayload {
+    /// Schema version of the content ([`LEGACY_SCHEMA_VERSION`] if it had no
+    /// envelope).
+    pub schema_version: u32,
+    /// The payload.
+    pub payload: HavenPayload,
+}
+
+#[derive(Serialize)]
+struct Envelope<'a> {
+    v: u32,
+    #[serde(flatten)]
+    payload: &'a HavenPayload,
+}
+
+impl HavenPayload {
+    /// The `type` discriminator written for this payload.
+    #[must_use]
+    pub const fn type_name(&self) -> &'static str {
+        match self {
+            Self::Location(_) => "location",
+            Self::Sos(_) => "sos",
+            Self::CheckinRequest { .. } => "checkin_request",
+            Self::MeetPin(_) => "meet_pin",
+            Self::Status(_) => "status",
+            Self::Chaff { .. } => "chaff",
+            Self::Unknown => "unknown",
+        }
+    }
+
+    /// Encodes the payload as rumor content, in an envelope stamped with
+    /// [`PAYLOAD_SCHEMA_VERSION`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if serialization fails (extremely rare).
+    pub fn encode(&self) -> Result<String, serde_json::Error> {
+        serde_json::to_string(&Envelope {
+            v: PAYLOAD_SCHEMA_VERSION,
+            payload: self,
+        })
+    }
+
+    /// Whether rumor content is a [`Self::Chaff`] payload. Reads only the
+    /// `type`, so chaff is dropped without decoding it.
+    #[must_use]
+    pub fn is_chaff(content: &str) -> bool {
+        #[derive(Deserialize)]
+        struct TypeOnly<'a> {
+            #[serde(rename = "type", borrow)]
+            type_name: Option<&'a str>,
+        }
+        serde_json::from_str::<TypeOnly<'_>>(content).is_ok_and(|t| t.type_name == Some("chaff"))
+    }
+
+    /// Decodes rumor content.
+    ///
+    /// `legacy_topic` is the rumor's `["t", ...]` topic, used only when the
+    /// content carries no `type` (a sender that predates the envelope).
+    /// Legacy check-in requests carry their target in a `p` tag rather than
+    /// the content, so the caller builds those itself.
+    ///
+    /// # Errors
+    ///
+    /// Returns a description of the problem if the content is not a JSON
+    /// object, or does not parse (or validate) as the type it names.
+    pub fn decode(content: &str, legacy_topic: Option<&str>) -> Result<DecodedPayload, String> {
+        let value: serde_json::Value =
+            serde_json::from_str(content).map_err(|_| "payload is not JSON".to_string())?;
+        let object = value
+            .as_object()
+            .ok_or_else(|| "payload is not a JSON object".to_string())?;
+        let schema_version = object
+            .get("v")
+            .and_then(serde_json::Value::as_u64)
+            .and_then(|v| u32::try_from(v).ok())
+            .unwrap_or(LEGACY_SCHEMA_VERSION);
+        let type_name = match object.get("type").and_then(serde_json::Value::as_str) {
+            Some(t) => t,
+            None => legacy_topic.unwrap_or(LOCATION_TOPIC),
+        };
+
+        let payload = match type_name {
+            "location" => Self::Location(
+                LocationMessage::from_string(content)
+                    .map_err(|_| "invalid location payload".to_string())?,
+            ),
+            "sos" => Self::Sos(
+                SosMessage::from_string(content).map_err(|_| "invalid SOS payload".to_string())?,
+            ),
+            "meet_pin" => Self::MeetPin(MeetPin::from_string(content)?),
+            "status" => {
+                let status: DeviceStatus = serde_json::from_str(content)
+                    .map_err(|_| "invalid status payload".to_string())?;
+                Self::Status(status.sanitized())
+            }
+            "chaff" => Self::Chaff {
+                padding: String::new(),
+            },
+            "checkin_request" => {
+                let target = object
+                    .get("target_pubkey")
+                    .and_then(serde_json::Value::as_str)
+                    .and_then(|hex| nostr::PublicKey::from_hex(hex).ok())
+                    .ok_or_else(|| "check-in request names no valid target".to_string())?;
+                Self::CheckinRequest {
+                    target_pubkey: target.to_hex(),
+                }
+            }
+            _ => Self::Unknown,
+        };
+        Ok(DecodedPayload {
+            schema_version,
+            payload,
+        })
+    }
+}
+
+impl std::fmt::Debug for HavenPayload {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Location(location) => f.debug_tuple("Location").field(location).finish(),
+            Self::Sos(sos) => f.debug_tuple("Sos").field(sos).finish(),
+            Self::CheckinRequest { .. } => f
+                .debug_struct("CheckinRequest")
+                .field("target_pubkey", &"<redacted>")
+                .finish(),
+            Self::MeetPin(pin) => f.debug_tuple("MeetPin").field(pin).finish(),
+            Self::Status(_) => f.debug_tuple("Status").field(&"<redacted>").finish(),
+            Self::Chaff { .. } => f.write_str("Chaff"),
+            Self::Unknown => f.write_str("Unknown"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn roundtrip(payload: &HavenPayload, topic: &str) -> DecodedPayload {
+        let json = payload.encode().unwrap();
+        assert!(json.starts_with(&format!(
+            r#"{{"v":{PAYLOAD_SCHEMA_VERSION},"type":"{}""#,
+            payload.type_name()
+        )));
+        // The topic hint must not matter once the content is enveloped.
+        HavenPayload::decode(&json, Some(topic)).unwrap()
+    }
+
+    #[test]
+    fn every_variant_roundtrips() {
+        let location = LocationMessage::new(57.649_11, 10.407_44);
+        let target = nostr::Keys::generate().public_key().to_hex();
+        let payloads = [
+            HavenPayload::Location(location.clone()),
+            HavenPayload::Sos(SosMessage::new(&location, Some("help"))),
+            HavenPayload::CheckinRequest {
+                target_pubkey: target,
+            },
+            HavenPayload::MeetPin(MeetPin::new(57.6, 10.4, Some("gate"), 3_600, 1_000).unwrap()),
+            HavenPayload::Status(DeviceStatus {
+                battery_percent: Some(12),
+                ..DeviceStatus::default()
+            }),
+            HavenPayload::Chaff {
+                padding: crate::relay::chaff::chaff_padding(),
+            },
+        ];
+        for payload in payloads {
+            let decoded = roundtrip(&payload, "unrelated");
+            assert_eq!(decoded.schema_version, PAYLOAD_SCHEMA_VERSION);
+            assert_eq!(decoded.payload.type_name(), payload.type_name());
+        }
+    }
+
+    #[test]
+    fn chaff_is_recognized_by_type_alone() {
+        let chaff = HavenPayload::Chaff {
+            padding: "x".repeat(200),
+        }
+        .encode()
+        .unwrap();
+        assert!(HavenPayload::is_chaff(&chaff));
+        let location = HavenPayload::Location(LocationMessage::new(1.5, 2.5))
+            .encode()
+            .unwrap();
+        assert!(!HavenPayload::is_chaff(&location));
+        assert!(!HavenPayload::is_chaff("not json"));
+    }
+
+    #[test]
+    fn enveloped_location_still_parses_for_legacy_readers() {
+        let json = HavenPayload::Location(LocationMessage::new(1.5, 2.5))
+            .encode()
+            .unwrap();
+        let legacy = LocationMessage::from_string(&json).unwrap();
+        assert!((legacy.latitude - 1.5).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn legacy_content_decodes_by_topic() {
+        let location = LocationMessage::new(1.5, 2.5);
+        let sos = SosMessage::new(&location, None).to_string().unwrap();
+        let decoded = HavenPayload::decode(&sos, Some(crate::emergency::SOS_TAG)).unwrap();
+        assert_eq!(decoded.schema_version, LEGACY_SCHEMA_VERSION);
+        assert!(matches!(decoded.payload, HavenPayload::Sos(_)));
+
+        let plain = location.to_string().unwrap();
+        let decoded = HavenPayload::decode(&plain, None).unwrap();
+        assert!(matches!(decoded.payload, HavenPayload::Location(_)));
+    }
+
+    #[test]
+    fn unknown_declared_precision_does_not_fail_the_message() {
+        let json = r#"{"v":2,"type":"location","latitude":1.5,"longitude":2.5,"geohash":"s00","timestamp":"2024-01-01T00:00:00Z","expires_at":"2024-01-01T00:15:00Z","precision":"neighborhood"}"#;
+        let decoded = HavenPayload::decode(json, None).unwrap();
+        let HavenPayload::Location(location) = decoded.payload else {
+            panic!("expected a location");
+        };
+        assert_eq!(
+            location.precision,
+            Some(crate::location::WirePrecision::Unknown(
+                "neighborhood".to_string()
+            ))
+        );
+
+        let numeric = json.replace(r#""neighborhood""#, "1");
+        let HavenPayload::Location(location) =
+            HavenPayload::decode(&numeric, None).unwrap().payload
+        else {
+            panic!("expected a location");
+        };
+        assert_eq!(
+            location.precision.and_then(|p| p.known()),
+            Some(crate::location::PrecisionClass::Approximate)
+        );
+    }
+
+    #[test]
+    fn unknown_types_and_garbage() {
+        let decoded = HavenPayload::decode(r#"{"v":7,"type":"hologram","x":1}"#, None).unwrap();
+        assert_eq!(decoded.schema_version, 7);
+        assert!(matches!(decoded.payload, HavenPayload::Unknown));
+
+        assert!(HavenPayload::decode("not json", None).is_err());
+        assert!(HavenPayload::decode("[]", None).is_err());
+        assert!(HavenPayload::decode(r#"{"v":1,"type":"checkin_request"}"#, None).is_err());
+        assert!(HavenPayload::decode(r#"{"v":1,"type":"location"}"#, None).is_err());
+    }
+}
@@ -0,0 +1,330 @@
+//! Machine-readable "privacy facts" derived from the live configuration.
+//!
+//! The privacy dashboard must describe what the code actually does, not what
+//! marketing copy says it does. Every value in [`PrivacyFacts`] is read from
+//! the constant or setting that enforces it, so changing a retention window or
+//! relay list changes the dashboard in the same commit.
+//!
+//! [`PrivacyFacts`] serializes to a stable JSON shape (versioned by
+//! [`PRIVACY_FACTS_VERSION`]) for the app and for exported reports.
+//!
+//! [`PrivacySettings`] holds the user's knobs for what published events
+//! reveal, applied centrally where the events are built.
+
+use rand::rngs::OsRng;
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+
+use crate::location::ttl::LOCATION_MESSAGE_RETENTION_SECS;
+use crate::location::{LOCATION_FRESHNESS_TTL_SECS, LOCATION_RETENTION_SECS};
+
+/// Schema version of the serialized [`PrivacyFacts`]. Bump on any breaking
+/// change to field names or meaning.
+pub const PRIVACY_FACTS_VERSION: u32 = 1;
+
+/// Geohash length stamped on every outgoing location (see
+/// [`crate::location::LocationMessage::new`]).
+pub const SHARED_GEOHASH_PRECISION: u8 = 8;
+
+/// Location metadata that is captured on-device but never serialized.
+pub const STRIPPED_LOCATION_FIELDS: &[&str] =
+    &["device_id", "raw_accuracy", "altitude", "speed", "heading"];
+
+/// Most a published `created_at` may be moved, in minutes (see
+/// [`PrivacySettings::timestamp_fuzz_minutes`]). Relays commonly refuse
+/// events dated more than 15 minutes ahead.
+pub const MAX_TIMESTAMP_FUZZ_MINUTES: u32 = 10;
+
+/// Most dummy events cover traffic may send per hour, across all circles
+/// (see [`PrivacySettings::cover_traffic_per_hour`]).
+pub const MAX_COVER_TRAFFIC_PER_HOUR: u32 = 12;
+
+/// What published events may reveal.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PrivacySettings {
+    /// Move the `created_at` of published events by a random amount of up
+    /// to this many minutes either way, so exact send times cannot tie a
+    /// user's events in different circles together. `0` (the default)
+    /// publishes the exact time. At most [`MAX_TIMESTAMP_FUZZ_MINUTES`].
+    ///
+    /// Applied to kind 445 application messages (locations, alerts, pins),
+    /// whose NIP-40 `expiration` follows `created_at`, and to the
+    /// `expiration` of gift-wrapped welcomes Haven wraps itself, whose
+    /// `created_at` NIP-59 already randomizes. Commits and proposals keep
+    /// their exact time: members order competing commits by it.
+    pub timestamp_fuzz_minutes: u32,
+    /// Send up to this many dummy kind 445 events per hour, spread over the
+    /// circles at random, so relays cannot read activity from the rhythm of
+    /// real updates (see [`crate::relay::chaff`]). `0` (the default) sends
+    /// none. At most [`MAX_COVER_TRAFFIC_PER_HOUR`].
+    pub cover_traffic_per_hour: u32,
+}
+
+impl PrivacySettings {
+    /// Checks every setting.
+    ///
+    /// # Errors
+    ///
+    /// Returns a description of the first invalid setting.
+    pub fn validate(&self) -> Result<(), String> {
+        if self.timestamp_fuzz_minutes > MAX_TIMESTAMP_FUZZ_MINUTES {
+            return Err(format!(
+                "Timestamp fuzzing must be at most {MAX_TIMESTAMP_FUZZ_MINUTES} minutes"
+            ));
+        }
+        if self.cover_traffic_per_hour > MAX_COVER_TRAFFIC_PER_HOUR {
+            return Err(format!(
+                "Cover traffic must be at most {MAX_COVER_TRAFFIC_PER_HOUR} events per hour"
+            ));
+        }
+        Ok(())
+    }
+
+    /// `now` (Unix seconds) moved by a uniformly random amount within the
+    /// fuzz window, but never more than `max_back_secs` into the past — an
+    /// event whose expiration follows its `created_at` must not arrive
+    /// already expired. Returns `now` when fuzzing is off.
+    ///
+    /// Uses `OsRng` for the same reason as [`crate::location::ttl`]: the
+    /// offset must be unpredictable to relay observers.
+    #[must_use]
+    pub fn fuzz_timestamp(&self, now: u64, max_back_secs: u64) -> u64 {
+        let window = u64::from(self.timestamp_fuzz_minutes.min(MAX_TIMESTAMP_FUZZ_MINUTES)) * 60;
+        if window == 0 {
+            return now;
+        }
+        let back = window.min(max_back_secs).min(now);
+        OsRng.gen_range(now - back..=now.saturating_add(window))
+    }
+}
+
+/// How precise the coordinates sent to circle members are.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SharedPrecision {
+    /// The exact GPS fix is sent (end-to-end encrypted to circle members).
+    ExactGps,
+}
+
+impl SharedPrecision {
+    /// Stable identifier, identical to the serialized form.
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::ExactGps => "exact_gps",
+        }
+    }
+}
+
+/// How relay connections are made.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum NetworkRouting {
+    /// Direct TLS connections: relays see the device's IP address.
+    Direct,
+}
+
+impl NetworkRouting {
+    /// Stable identifier, identical to the serialized form.
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Direct => "direct",
+        }
+    }
+}
+
+/// Retention windows, in seconds.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct RetentionFacts {
+    /// How long a received location is shown as "fresh".
+    pub location_freshness_secs: i64,
+    /// How long a received location is kept on this device before deletion.
+    pub location_local_retention_secs: u64,
+    /// The NIP-40 expiration requested from relays for location events.
+    pub relay_location_retention_secs: u64,
+    /// How long processed invitation ids are kept for de-duplication.
+    pub processed_invitation_retention_secs: i64,
+}
+
+/// A snapshot of Haven's effective privacy posture.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct PrivacyFacts {
+    /// Schema version ([`PRIVACY_FACTS_VERSION`]).
+    pub version: u32,
+    /// Retention windows.
+    pub retention: RetentionFacts,
+    /// Coordinate precision sent to circle members.
+    pub shared_precision: SharedPrecision,
+    /// Geohash length carried alongside the coordinates.
+    pub geohash_precision: u8,
+    /// Location metadata that never leaves the device.
+    pub stripped_location_fields: Vec<String>,
+    /// Whether location content is end-to-end encrypted (MLS).
+    pub end_to_end_encrypted: bool,
+    /// Whether the local circle database is encrypted at rest.
+    pub local_database_encrypted: bool,
+    /// How relay connections are routed.
+    pub network_routing: NetworkRouting,
+    /// Relays that receive this user's invitations (kind 10050).
+    pub inbox_relays: Vec<String>,
+    /// Relays that host this user's key packages (kind 10051).
+    pub key_package_relays: Vec<String>,
+    /// Distinct relays used by the user's circles.
+    pub circle_relays: Vec<String>,
+}
+
+impl PrivacyFacts {
+    /// Builds the facts from the live relay configuration; every other value
+    /// is read from the enforcing constant.
+    ///
+    /// Relay lists are sorted and de-duplicated so the output is stable.
+    #[must_use]
+    pub fn collect(
+        local_database_encrypted: bool,
+        inbox_relays: Vec<String>,
+        key_package_relays: Vec<String>,
+        circle_relays: Vec<String>,
+    ) -> Self {
+        Self {
+            version: PRIVACY_FACTS_VERSION,
+            retention: RetentionFacts {
+                location_freshness_secs: LOCATION_FRESHNESS_TTL_SECS,
+                location_local_retention_secs: LOCATION_RETENTION_SECS,
+                relay_location_retention_secs: LOCATION_MESSAGE_RETENTION_SECS,
+                processed_invitation_retention_secs:
+                    crate::circle::CircleStorage::PROCESSED_GIFT_WRAP_RETENTION_SECS,
+            },
+            shared_precision: SharedPrecision::ExactGps,
+            geohash_precision: SHARED_GEOHASH_PRECISION,
+            stripped_location_fields: STRIPPED_LOCATION_FIELDS
+                .iter()
+                .map(ToString::to_string)
+                .collect(),
+            end_to_end_encrypted: true,
+            local_database_encrypted,
+            network_routing: NetworkRouting::Direct,
+            inbox_relays: sorted_unique(inbox_relays),
+            key_package_relays: sorted_unique(key_package_relays),
+            circle_relays: sorted_unique(circle_relays),
+        }
+    }
+
+    /// Serializes the facts as JSON.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if serialization fails (extremely rare).
+    pub fn to_json(&self) -> Result<String, serde_json::Error> {
+        serde_json::to_string(self)
+    }
+}
+
+fn sorted_unique(mut relays: Vec<String>) -> Vec<String> {
+    relays.sort();
+    relays.dedup();
+    relays
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::location::LocationMessage;
+
+    fn facts() -> PrivacyFacts {
+        PrivacyFacts::collect(
+            true,
+            vec!["wss://b.example".into(), "wss://a.example".into()],
+            vec!["wss://kp.example".into()],
+            vec!["wss://a.example".into(), "wss://a.example".into()],
+        )
+    }
+
+    #[test]
+    fn retention_reflects_enforcing_constants() {
+        let f = facts();
+        assert_eq!(
+            f.retention.location_freshness_secs,
+            LOCATION_FRESHNESS_TTL_SECS
+        );
+        assert_eq!(
+            f.retention.location_local_retention_secs,
+            LOCATION_RETENTION_SECS
+        );
+        assert_eq!(
+            f.retention.relay_location_retention_secs,
+            LOCATION_MESSAGE_RETENTION_SECS
+        );
+    }
+
+    #[test]
+    fn geohash_precision_matches_outgoing_messages() {
+        let msg = LocationMessage::new(37.7749, -122.4194);
+        assert_eq!(
+            msg.geohash.len(),
+            usize::from(facts().geohash_precision),
+            "privacy facts must track the precision LocationMessage::new actually uses"
+        );
+    }
+
+    #[test]
+    fn stripped_fields_never_serialize() {
+        let mut msg = LocationMessage::new(1.0, 2.0);
+        msg.device_id = Some("dev".into());
+        msg.raw_accuracy = Some(1.0);
+        msg.altitude = Some(1.0);
+        msg.speed = Some(1.0);
+        msg.heading = Some(1.0);
+        let json = msg.to_string().unwrap();
+        for field in STRIPPED_LOCATION_FIELDS {
+            assert!(!json.contains(field), "{field} leaked into the wire form");
+        }
+    }
+
+    #[test]
+    fn relay_lists_are_sorted_and_deduplicated() {
+        let f = facts();
+        assert_eq!(f.inbox_relays, vec!["wss://a.example", "wss://b.example"]);
+        assert_eq!(f.circle_relays, vec!["wss://a.example"]);
+    }
+
+    #[test]
+    fn fuzzed_timestamps_stay_in_the_clamped_window() {
+        let now = 1_700_000_000;
+        assert_eq!(PrivacySettings::default().fuzz_timestamp(now, 600), now);
+
+        let settings = PrivacySettings {
+            timestamp_fuzz_minutes: 5,
+            ..PrivacySettings::default()
+        };
+        let samples: Vec<u64> = (0..500)
+            .map(|_| settings.fuzz_timestamp(now, 120))
+            .collect();
+        assert!(samples.iter().all(|t| (now - 120..=now + 300).contains(t)));
+        assert!(samples.iter().any(|&t| t != now));
+
+        let too_wide = PrivacySettings {
+            timestamp_fuzz_minutes: MAX_TIMESTAMP_FUZZ_MINUTES + 1,
+            ..PrivacySettings::default()
+        };
+        assert!(too_wide.validate().is_err());
+        let too_chatty = PrivacySettings {
+            cover_traffic_per_hour: MAX_COVER_TRAFFIC_PER_HOUR + 1,
+            ..PrivacySettings::default()
+        };
+        assert!(too_chatty.validate().is_err());
+        assert!(settings.validate().is_ok());
+    }
+
+    #[test]
+    fn json_shape_is_stable() {
+        let json = facts().to_json().unwrap();
+        let v: serde_json::Value = serde_json::from_str(&json).unwrap();
+        assert_eq!(v["version"], PRIVACY_FACTS_VERSION);
+        assert_eq!(v["shared_precision"], SharedPrecision::ExactGps.as_str());
+        assert_eq!(v["network_routing"], NetworkRouting::Direct.as_str());
+        assert_eq!(v["local_database_encrypted"], true);
+        assert!(v["retention"]["location_local_retention_secs"].is_u64());
+    }
+}
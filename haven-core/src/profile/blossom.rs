@@ -282,7 +282,7 @@ static DOWNLOAD_CLIENT: OnceLock<reqwest::Client> = OnceLock::new();
 ///
 /// Never panics on a build failure (maps to [`ProfileError::Http`]); the build
 /// is retried until it succeeds, then cached.
-fn download_client() -> Result<&'static reqwest::Client> {
+pub(crate) fn download_client() -> Result<&'static reqwest::Client> {
     if let Some(client) = DOWNLOAD_CLIENT.get() {
         return Ok(client);
     }
@@ -480,7 +480,7 @@ pub async fn download_profile_picture(url: &str) -> Result<ProfilePicture> {
 ///
 /// [`ProfileError::InsecureUrl`] if the host resolves to a forbidden address or
 /// does not resolve; [`ProfileError::Http`] on a resolver I/O error.
-async fn preflight_ssrf_check(url: &url::Url) -> Result<()> {
+pub(crate) async fn preflight_ssrf_check(url: &url::Url) -> Result<()> {
     let host = url.host_str().ok_or(ProfileError::BadUrl)?;
     let port = url.port_or_known_default().unwrap_or(443);
     let mut resolved = tokio::net::lookup_host((host, port))
@@ -528,7 +528,7 @@ async fn download_with_client(client: &reqwest::Client, url: &url::Url) -> Resul
     // sha256(raw) must match the URL's trailing 64-hex commitment when present.
     let raw_hash_hex = hex::encode(Sha256::digest(&raw));
     if let Some(expected) = extract_sha256_from_url(url) {
-        if raw_hash_hex != expected {
+        if !crate::util::ct_eq_str(&raw_hash_hex, &expected) {
             return Err(ProfileError::HashMismatch);
         }
     }
@@ -551,7 +551,7 @@ async fn download_with_client(client: &reqwest::Client, url: &url::Url) -> Resul
 ///
 /// [`ProfileError::TooLarge`] on overrun; [`ProfileError::Http`] on a stream
 /// read error.
-async fn read_body_capped(response: reqwest::Response, cap: u64) -> Result<Vec<u8>> {
+pub(crate) async fn read_body_capped(response: reqwest::Response, cap: u64) -> Result<Vec<u8>> {
     let cap_usize = usize::try_from(cap).unwrap_or(usize::MAX);
     let mut stream = response.bytes_stream();
     let mut buf: Vec<u8> = Vec::new();
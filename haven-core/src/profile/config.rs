@@ -27,6 +27,19 @@ pub const PROFILE_FETCH_TIMEOUT: Duration = Duration::from_secs(10);
 /// Bounded timeout for a Blossom upload/download HTTP round-trip.
 pub const BLOSSOM_TIMEOUT: Duration = Duration::from_secs(30);
 
+/// Bounded timeout for a NIP-05 `nostr.json` lookup.
+pub const NIP05_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Largest `nostr.json` body accepted (64 KiB).
+pub const NIP05_MAX_RESPONSE_BYTES: u64 = 64 * 1024;
+
+/// How long a resolved NIP-05 identifier is served from memory (1 hour, in
+/// seconds).
+pub const NIP05_CACHE_TTL_SECS: i64 = 3600;
+
+/// Most NIP-05 lookups cached at once (oldest evicted first).
+pub const NIP05_CACHE_CAPACITY: usize = 256;
+
 /// Lifetime, in seconds, of a Blossom kind-24242 authorization event's
 /// `expiration` tag (stamped `now + this`). Short-lived by design.
 pub const BLOSSOM_AUTH_EXPIRY_SECS: u64 = 60;
@@ -77,6 +77,15 @@ pub enum ProfileError {
     #[error("invalid url")]
     BadUrl,
 
+    /// A NIP-05 identifier (or the key a domain returned for it) was
+    /// malformed. Data-free: the identifier is never echoed.
+    #[error("invalid nip-05 identifier")]
+    InvalidIdentifier,
+
+    /// The domain does not list the NIP-05 name. Data-free.
+    #[error("nip-05 name not found")]
+    Nip05NotFound,
+
     /// The avatar image pipeline (decode / sanitize / re-encode) failed. Its
     /// own `Display` is already content-free.
     #[error(transparent)]
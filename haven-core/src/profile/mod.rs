@@ -38,6 +38,7 @@ pub mod consent;
 pub mod error;
 pub mod fetch;
 pub mod merge;
+pub mod nip05;
 pub mod parse;
 pub mod picture_cache;
 pub mod publish;
@@ -49,13 +50,15 @@ pub use blossom::{
 pub use config::{
     blossom_server, profile_read_relays, profile_write_relays, self_merge_base_relays,
     set_blossom_server_for_test, AVATAR_MIME, BLOSSOM_AUTH_EXPIRY_SECS, BLOSSOM_TIMEOUT,
-    DEFAULT_BLOSSOM_SERVER, PROFILE_FETCH_MAX_AUTHORS, PROFILE_FETCH_TIMEOUT,
+    DEFAULT_BLOSSOM_SERVER, NIP05_CACHE_CAPACITY, NIP05_CACHE_TTL_SECS, NIP05_MAX_RESPONSE_BYTES,
+    NIP05_TIMEOUT, PROFILE_FETCH_MAX_AUTHORS, PROFILE_FETCH_TIMEOUT,
     PROFILE_PICTURE_MAX_DOWNLOAD_BYTES, PROFILE_TTL_SECS,
 };
 pub use consent::has_published_profile;
 pub use error::{ProfileError, Result};
 pub use fetch::fetch_profiles;
 pub use merge::{enforce_name_rule, merge_edits};
+pub use nip05::{resolve_nip05, Nip05Identifier, Nip05Resolution};
 pub use parse::parse_newest_metadata;
 pub use picture_cache::{picture_is_current, picture_sync_action, PictureSyncAction};
 pub use publish::{
@@ -0,0 +1,317 @@
+//! NIP-05 identifier resolution (`name@domain` → public key + relay hints).
+//!
+//! Lets a user add a contact by a human-readable identifier instead of a raw
+//! npub. [`resolve_nip05`] fetches `/.well-known/nostr.json?name=<name>` from
+//! the identifier's domain over the shared anti-SSRF download client (see
+//! [`super::blossom`]): HTTPS only, redirects refused (NIP-05 requires
+//! ignoring them), non-public addresses blocked at connect time, and the
+//! response capped at [`NIP05_MAX_RESPONSE_BYTES`].
+//!
+//! # Privacy
+//!
+//! Haven ships no Tor client, so a lookup goes straight to the domain: its
+//! operator sees this device's IP address and which name was looked up.
+//! Every result carries [`Nip05Resolution::privacy_warning`], set when that
+//! request was made just now, so the UI can say so. Successful lookups are
+//! cached in memory for [`NIP05_CACHE_TTL_SECS`] to avoid repeating the
+//! exposure; failures are not cached. Identifiers are never logged.
+
+use std::collections::{HashMap, VecDeque};
+use std::sync::{LazyLock, Mutex};
+
+use nostr::{PublicKey, RelayUrl};
+
+use super::blossom::{download_client, preflight_ssrf_check, read_body_capped, require_https};
+use super::config::{
+    NIP05_CACHE_CAPACITY, NIP05_CACHE_TTL_SECS, NIP05_MAX_RESPONSE_BYTES, NIP05_TIMEOUT,
+};
+use super::error::{ProfileError, Result};
+
+/// Most relay hints kept from a response.
+pub const NIP05_MAX_RELAY_HINTS: usize = 5;
+
+/// A parsed `name@domain` identifier, lowercased.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Nip05Identifier {
+    /// The local part (`_` for a bare domain).
+    pub name: String,
+    /// The domain serving `/.well-known/nostr.json`.
+    pub domain: String,
+}
+
+impl Nip05Identifier {
+    /// Parses `name@domain`, or a bare `domain` (meaning `_@domain`).
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ProfileError::InvalidIdentifier`] if the name has characters
+    /// outside `a-z0-9-_.` or the domain is not a plain host name.
+    pub fn parse(input: &str) -> Result<Self> {
+        let input = input.trim().to_ascii_lowercase();
+        let (name, domain) = input.rsplit_once('@').unwrap_or(("_", input.as_str()));
+        let name_ok = !name.is_empty()
+            && name
+                .bytes()
+                .all(|b| b.is_ascii_alphanumeric() || matches!(b, b'-' | b'_' | b'.'));
+        let domain_ok = domain.contains('.')
+            && domain
+                .bytes()
+                .all(|b| b.is_ascii_alphanumeric() || matches!(b, b'-' | b'.'));
+        if !name_ok || !domain_ok {
+            return Err(ProfileError::InvalidIdentifier);
+        }
+        // Reject anything the URL parser reads as other than this exact host
+        // (IP literals, empty labels).
+        let parsed = url::Url::parse(&format!("https://{domain}/"))
+            .map_err(|_| ProfileError::InvalidIdentifier)?;
+        match parsed.host() {
+            Some(url::Host::Domain(host)) if host == domain => {}
+            _ => return Err(ProfileError::InvalidIdentifier),
+        }
+        Ok(Self {
+            name: name.to_string(),
+            domain: domain.to_string(),
+        })
+    }
+
+    /// The `/.well-known/nostr.json` lookup URL.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ProfileError::BadUrl`] if the URL cannot be built.
+    pub fn well_known_url(&self) -> Result<url::Url> {
+        let mut url = url::Url::parse(&format!("https://{}/.well-known/nostr.json", self.domain))
+            .map_err(|_| ProfileError::BadUrl)?;
+        url.query_pairs_mut().append_pair("name", &self.name);
+        Ok(url)
+    }
+}
+
+impl std::fmt::Display for Nip05Identifier {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}@{}", self.name, self.domain)
+    }
+}
+
+/// A resolved NIP-05 identifier.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Nip05Resolution {
+    /// The identifier, normalized (`name@domain`).
+    pub identifier: String,
+    /// The public key the domain vouches for.
+    pub pubkey: PublicKey,
+    /// `wss://` relay hints for the key (at most [`NIP05_MAX_RELAY_HINTS`]).
+    pub relays: Vec<String>,
+    /// `true` if this call contacted the domain, which then saw the device's
+    /// IP address and the name looked up; `false` for a cached answer.
+    pub privacy_warning: bool,
+}
+
+#[derive(serde::Deserialize)]
+struct NostrJson {
+    #[serde(default)]
+    names: HashMap<String, String>,
+    #[serde(default)]
+    relays: HashMap<String, Vec<String>>,
+}
+
+/// Extracts `name`'s public key and relay hints from a `nostr.json` body.
+///
+/// Relay hints that are not valid `wss://` URLs are dropped.
+///
+/// # Errors
+///
+/// Returns [`ProfileError::Http`] for a malformed document,
+/// [`ProfileError::Nip05NotFound`] if `name` is not listed, or
+/// [`ProfileError::InvalidIdentifier`] if its key is not a valid public key.
+pub fn parse_nip05_response(body: &[u8], name: &str) -> Result<(PublicKey, Vec<String>)> {
+    let doc: NostrJson =
+        serde_json::from_slice(body).map_err(|_| ProfileError::http("malformed nostr.json"))?;
+    let hex = doc
+        .names
+        .iter()
+        .find(|(listed, _)| listed.eq_ignore_ascii_case(name))
+        .map(|(_, hex)| hex)
+        .ok_or(ProfileError::Nip05NotFound)?;
+    let pubkey = PublicKey::from_hex(hex).map_err(|_| ProfileError::InvalidIdentifier)?;
+    let relays = doc
+        .relays
+        .iter()
+        .find(|(key, _)| key.eq_ignore_ascii_case(hex))
+        .map(|(_, urls)| {
+            urls.iter()
+                .filter(|u| u.starts_with("wss://") && RelayUrl::parse(u).is_ok())
+                .take(NIP05_MAX_RELAY_HINTS)
+                .cloned()
+                .collect()
+        })
+        .unwrap_or_default();
+    Ok((pubkey, relays))
+}
+
+#[derive(Debug, Clone)]
+struct CachedLookup {
+    pubkey: PublicKey,
+    relays: Vec<String>,
+    fetched_at: i64,
+}
+
+#[derive(Default)]
+struct Nip05Cache {
+    entries: HashMap<String, CachedLookup>,
+    order: VecDeque<String>,
+}
+
+impl Nip05Cache {
+    fn get(&self, key: &str, now: i64) -> Option<&CachedLookup> {
+        self.entries
+            .get(key)
+            .filter(|hit| now.saturating_sub(hit.fetched_at) < NIP05_CACHE_TTL_SECS)
+    }
+
+    fn insert(&mut self, key: String, lookup: CachedLookup) {
+        if self.entries.insert(key.clone(), lookup).is_none() {
+            self.order.push_back(key);
+        }
+        while self.order.len() > NIP05_CACHE_CAPACITY {
+            if let Some(oldest) = self.order.pop_front() {
+                self.entries.remove(&oldest);
+            }
+        }
+    }
+}
+
+static CACHE: LazyLock<Mutex<Nip05Cache>> = LazyLock::new(|| Mutex::new(Nip05Cache::default()));
+
+/// Resolves a NIP-05 identifier, from the cache when fresh.
+///
+/// `now` is the current Unix time in seconds.
+///
+/// # Errors
+///
+/// * [`ProfileError::InvalidIdentifier`] for a malformed identifier or key.
+/// * [`ProfileError::InsecureUrl`] if the domain resolves to a non-public
+///   address.
+/// * [`ProfileError::Nip05NotFound`] if the domain does not list the name.
+/// * [`ProfileError::TooLarge`] if the response exceeds the size cap.
+/// * [`ProfileError::Http`] / [`ProfileError::Timeout`] on transport failure
+///   (including a redirect, which NIP-05 says to ignore).
+pub async fn resolve_nip05(identifier: &str, now: i64) -> Result<Nip05Resolution> {
+    let id = Nip05Identifier::parse(identifier)?;
+    let key = id.to_string();
+    if let Some(hit) = CACHE.lock().ok().and_then(|c| c.get(&key, now).cloned()) {
+        return Ok(Nip05Resolution {
+            identifier: key,
+            pubkey: hit.pubkey,
+            relays: hit.relays,
+            privacy_warning: false,
+        });
+    }
+
+    let url = id.well_known_url()?;
+    require_https(&url)?;
+    preflight_ssrf_check(&url).await?;
+    let response = tokio::time::timeout(NIP05_TIMEOUT, download_client()?.get(url).send())
+        .await
+        .map_err(|_| ProfileError::Timeout)?
+        .map_err(ProfileError::http)?;
+    if !response.status().is_success() {
+        return Err(ProfileError::http(format!("status {}", response.status())));
+    }
+    let body = read_body_capped(response, NIP05_MAX_RESPONSE_BYTES).await?;
+    let (pubkey, relays) = parse_nip05_response(&body, &id.name)?;
+
+    if let Ok(mut cache) = CACHE.lock() {
+        cache.insert(
+            key.clone(),
+            CachedLookup {
+                pubkey,
+                relays: relays.clone(),
+                fetched_at: now,
+            },
+        );
+    }
+    Ok(Nip05Resolution {
+        identifier: key,
+        pubkey,
+        relays,
+        privacy_warning: true,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_identifiers() {
+        let id = Nip05Identifier::parse(" Alice@Example.COM ").unwrap();
+        assert_eq!(id.name, "alice");
+        assert_eq!(id.domain, "example.com");
+        assert_eq!(
+            id.well_known_url().unwrap().as_str(),
+            "https://example.com/.well-known/nostr.json?name=alice"
+        );
+        assert_eq!(
+            Nip05Identifier::parse("example.com").unwrap().to_string(),
+            "_@example.com"
+        );
+
+        for bad in [
+            "",
+            "alice@",
+            "@example.com",
+            "al ice@example.com",
+            "alice@localhost",
+            "alice@127.0.0.1",
+            "alice@example.com:8443",
+            "alice@example.com/path",
+            "alice@user@example.com",
+        ] {
+            assert!(Nip05Identifier::parse(bad).is_err(), "{bad}");
+        }
+    }
+
+    #[test]
+    fn reads_key_and_wss_relay_hints() {
+        let pk = nostr::Keys::generate().public_key();
+        let hex = pk.to_hex();
+        let body = serde_json::json!({
+            "names": { "alice": hex },
+            "relays": { hex.clone(): ["wss://relay.example.com", "ws://plain.example.com"] }
+        })
+        .to_string();
+        let (pubkey, relays) = parse_nip05_response(body.as_bytes(), "alice").unwrap();
+        assert_eq!(pubkey, pk);
+        assert_eq!(relays, vec!["wss://relay.example.com".to_string()]);
+
+        assert!(matches!(
+            parse_nip05_response(body.as_bytes(), "bob"),
+            Err(ProfileError::Nip05NotFound)
+        ));
+        assert!(parse_nip05_response(b"not json", "alice").is_err());
+        let bad_key = serde_json::json!({ "names": { "alice": "zz" } }).to_string();
+        assert!(parse_nip05_response(bad_key.as_bytes(), "alice").is_err());
+    }
+
+    #[test]
+    fn cache_expires_and_evicts() {
+        let lookup = |fetched_at| CachedLookup {
+            pubkey: nostr::Keys::generate().public_key(),
+            relays: Vec::new(),
+            fetched_at,
+        };
+        let mut cache = Nip05Cache::default();
+        cache.insert("a@example.com".to_string(), lookup(100));
+        assert!(cache.get("a@example.com", 100).is_some());
+        assert!(cache
+            .get("a@example.com", 100 + NIP05_CACHE_TTL_SECS)
+            .is_none());
+
+        for i in 0..NIP05_CACHE_CAPACITY {
+            cache.insert(format!("n{i}@example.com"), lookup(100));
+        }
+        assert!(cache.get("a@example.com", 100).is_none());
+        assert_eq!(cache.entries.len(), NIP05_CACHE_CAPACITY);
+    }
+}
@@ -0,0 +1,366 @@
+//! Relay blacklist: community-maintained malicious-relay lists plus local
+//! user overrides.
+//!
+//! # Model
+//!
+//! A [`RelayBlacklist`] merges three sources:
+//!
+//! 1. **Community list** — a NIP-51 "blocked relays" event (kind 10006)
+//!    published by a list maintainer. It is only honored when the user has
+//!    opted in, and only after [`verify_community_list`] has checked the
+//!    event id, the Schnorr signature, and that the author is
+//!    [`COMMUNITY_BLACKLIST_MAINTAINER`], the key compiled into this build.
+//!    Nothing at runtime can substitute another key, and an unsigned or
+//!    re-signed copy served by a hostile relay is rejected.
+//! 2. **Local blocks** — relays the user blocked by hand. Always honored.
+//! 3. **Local allows** — relays the user explicitly trusts. These override
+//!    both other sources, so a wrong or malicious community entry can never
+//!    lock a user out of their own relay.
+//!
+//! # Enforcement
+//!
+//! The active blacklist is process-wide (installed with
+//! [`install_relay_blacklist`]) because `RelayManager` instances are created
+//! ad hoc on many paths. Every `RelayManager` URL validation consults it, so a
+//! blacklisted relay is never added to the pool or connected to.
+//!
+//! # Fetching
+//!
+//! Community lists are fetched with
+//! [`RelayManager::fetch_community_blacklist`](super::RelayManager::fetch_community_blacklist)
+//! over the same direct WSS transport as every other relay read; the
+//! signature check, not the transport, is what makes the list trustworthy.
+
+use std::collections::HashSet;
+use std::sync::RwLock;
+
+use nostr::{Event, PublicKey, RelayUrl};
+
+use super::error::{RelayError, RelayResult};
+
+/// Event kind of a NIP-51 "blocked relays" list.
+pub const COMMUNITY_BLACKLIST_KIND: u16 = 10006;
+
+/// Hex public key of the community list maintainer, pinned at compile time
+/// so no caller can substitute its own.
+///
+/// `None` until the project publishes its list key: community lists are
+/// refused in a build that pins none.
+pub const COMMUNITY_BLACKLIST_MAINTAINER: Option<&str> = None;
+
+/// Maximum number of entries accepted from one community list.
+///
+/// Bounds memory and storage if a maintainer key is compromised and used to
+/// publish an oversized list.
+pub const MAX_COMMUNITY_BLACKLIST_ENTRIES: usize = 5_000;
+
+/// A user's explicit decision about one relay.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum LocalRelayOverride {
+    /// Always block this relay.
+    Block,
+    /// Never block this relay, even if a community list does.
+    Allow,
+}
+
+impl LocalRelayOverride {
+    /// Returns the canonical string slug used for storage and FFI.
+    #[must_use]
+    pub const fn as_str(&self) -> &'static str {
+        match self {
+            Self::Block => "block",
+            Self::Allow => "allow",
+        }
+    }
+
+    /// Parses a slug back into a [`LocalRelayOverride`].
+    #[must_use]
+    pub fn parse(s: &str) -> Option<Self> {
+        match s {
+            "block" => Some(Self::Block),
+            "allow" => Some(Self::Allow),
+            _ => None,
+        }
+    }
+}
+
+/// The merged relay blacklist.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RelayBlacklist {
+    community_enabled: bool,
+    community: HashSet<String>,
+    local_blocked: HashSet<String>,
+    local_allowed: HashSet<String>,
+}
+
+impl RelayBlacklist {
+    /// Creates a blacklist from its sources. URLs that do not parse as relay
+    /// URLs are dropped.
+    #[must_use]
+    pub fn new(
+        community_enabled: bool,
+        community: impl IntoIterator<Item = String>,
+        local: impl IntoIterator<Item = (String, LocalRelayOverride)>,
+    ) -> Self {
+        let mut local_blocked = HashSet::new();
+        let mut local_allowed = HashSet::new();
+        for (url, decision) in local {
+            let Some(url) = canonical_relay_url(&url) else {
+                continue;
+            };
+            match decision {
+                LocalRelayOverride::Block => local_blocked.insert(url),
+                LocalRelayOverride::Allow => local_allowed.insert(url),
+            };
+        }
+        Self {
+            community_enabled,
+            community: community
+                .into_iter()
+                .filter_map(|u| canonical_relay_url(&u))
+                .collect(),
+            local_blocked,
+            local_allowed,
+        }
+    }
+
+    /// Returns whether the community list is honored.
+    #[must_use]
+    pub const fn community_enabled(&self) -> bool {
+        self.community_enabled
+    }
+
+    /// Number of entries in the stored community list (honored or not).
+    #[must_use]
+    pub fn community_len(&self) -> usize {
+        self.community.len()
+    }
+
+    /// Returns `true` if connections to `url` must be refused.
+    ///
+    /// Local allows win over everything; local blocks always apply; the
+    /// community list applies only when opted in. Unparseable URLs are not
+    /// considered blacklisted here — URL validation rejects them separately.
+    #[must_use]
+    pub fn is_blocked(&self, url: &str) -> bool {
+        let Some(url) = canonical_relay_url(url) else {
+            return false;
+        };
+        if self.local_allowed.contains(&url) {
+            return false;
+        }
+        self.local_blocked.contains(&url)
+            || (self.community_enabled && self.community.contains(&url))
+    }
+}
+
+/// Canonical comparison form of a relay URL: parsed by [`RelayUrl`]
+/// (lowercased scheme and host) with trailing slashes removed.
+#[must_use]
+pub fn canonical_relay_url(url: &str) -> Option<String> {
+    let parsed = RelayUrl::parse(url.trim()).ok()?;
+    Some(parsed.as_str().trim_end_matches('/').to_string())
+}
+
+/// The pinned [`COMMUNITY_BLACKLIST_MAINTAINER`] key.
+///
+/// # Errors
+///
+/// Returns [`RelayError::InvalidBlacklist`] if this build pins no maintainer
+/// key or the pinned key does not parse.
+pub fn community_blacklist_maintainer() -> RelayResult<PublicKey> {
+    let hex = COMMUNITY_BLACKLIST_MAINTAINER.ok_or_else(|| {
+        RelayError::InvalidBlacklist("no community list maintainer is pinned".to_string())
+    })?;
+    PublicKey::from_hex(hex)
+        .map_err(|_| RelayError::InvalidBlacklist("pinned maintainer key is invalid".to_string()))
+}
+
+/// Verifies a community blacklist event and returns its relay URLs.
+///
+/// Checks, in order: the kind is [`COMMUNITY_BLACKLIST_KIND`], the author is
+/// `maintainer` (the [pinned key](community_blacklist_maintainer) outside
+/// tests), and the event id and signature are valid. Entries come from
+/// `["relay", <url>]` tags; malformed URLs are skipped and duplicates
+/// collapsed.
+///
+/// # Errors
+///
+/// Returns [`RelayError::InvalidBlacklist`] if any check fails or the list
+/// exceeds [`MAX_COMMUNITY_BLACKLIST_ENTRIES`].
+pub(crate) fn verify_community_list(
+    event: &Event,
+    maintainer: &PublicKey,
+) -> RelayResult<Vec<String>> {
+    if event.kind.as_u16() != COMMUNITY_BLACKLIST_KIND {
+        return Err(RelayError::InvalidBlacklist(format!(
+            "expected kind {COMMUNITY_BLACKLIST_KIND}, got {}",
+            event.kind.as_u16()
+        )));
+    }
+    if event.pubkey != *maintainer {
+        return Err(RelayError::InvalidBlacklist(
+            "list is not signed by the pinned maintainer key".to_string(),
+        ));
+    }
+    event
+        .verify()
+        .map_err(|_| RelayError::InvalidBlacklist("invalid id or signature".to_string()))?;
+
+    let mut seen = HashSet::new();
+    let mut urls = Vec::new();
+    for tag in event.tags.iter() {
+        let values = tag.as_slice();
+        if values.len() < 2 || values[0] != "relay" {
+            continue;
+        }
+        let Some(url) = canonical_relay_url(&values[1]) else {
+            continue;
+        };
+        if seen.insert(url.clone()) {
+            urls.push(url);
+        }
+        if urls.len() > MAX_COMMUNITY_BLACKLIST_ENTRIES {
+            return Err(RelayError::InvalidBlacklist(format!(
+                "list exceeds {MAX_COMMUNITY_BLACKLIST_ENTRIES} entries"
+            )));
+        }
+    }
+    Ok(urls)
+}
+
+/// Process-wide active blacklist consulted by every `RelayManager`.
+static ACTIVE_BLACKLIST: RwLock<Option<RelayBlacklist>> = RwLock::new(None);
+
+/// Installs `blacklist` as the process-wide active blacklist, replacing any
+/// previous one.
+pub fn install_relay_blacklist(blacklist: RelayBlacklist) {
+    // A poisoned lock only means a previous writer panicked mid-assignment;
+    // overwriting the value is still correct.
+    let mut guard = ACTIVE_BLACKLIST
+        .write()
+        .unwrap_or_else(std::sync::PoisonError::into_inner);
+    *guard = Some(blacklist);
+}
+
+/// Returns `true` if the active blacklist blocks `url`.
+#[must_use]
+pub fn is_relay_blacklisted(url: &str) -> bool {
+    ACTIVE_BLACKLIST
+        .read()
+        .unwrap_or_else(std::sync::PoisonError::into_inner)
+        .as_ref()
+        .is_some_and(|b| b.is_blocked(url))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use nostr::{EventBuilder, Keys, Kind, Tag};
+
+    fn list_event(keys: &Keys, kind: u16, relays: &[&str]) -> Event {
+        let tags = relays
+            .iter()
+            .map(|r| Tag::parse(["relay", *r]).expect("tag"));
+        EventBuilder::new(Kind::from(kind), "")
+            .tags(tags)
+            .sign_with_keys(keys)
+            .expect("sign")
+    }
+
+    #[test]
+    fn verify_accepts_pinned_maintainer() {
+        let maintainer = Keys::generate();
+        let event = list_event(
+            &maintainer,
+            COMMUNITY_BLACKLIST_KIND,
+            &["wss://evil.example/", "wss://EVIL.example", "not a url"],
+        );
+        let urls = verify_community_list(&event, &maintainer.public_key()).unwrap();
+        assert_eq!(urls, vec!["wss://evil.example".to_string()]);
+    }
+
+    #[test]
+    fn verify_rejects_other_author() {
+        let maintainer = Keys::generate();
+        let impostor = Keys::generate();
+        let event = list_event(&impostor, COMMUNITY_BLACKLIST_KIND, &["wss://a.example"]);
+        assert!(matches!(
+            verify_community_list(&event, &maintainer.public_key()),
+            Err(RelayError::InvalidBlacklist(_))
+        ));
+    }
+
+    #[test]
+    fn verify_rejects_wrong_kind() {
+        let maintainer = Keys::generate();
+        let event = list_event(&maintainer, 10_007, &["wss://a.example"]);
+        assert!(matches!(
+            verify_community_list(&event, &maintainer.public_key()),
+            Err(RelayError::InvalidBlacklist(_))
+        ));
+    }
+
+    #[test]
+    fn verify_rejects_tampered_event() {
+        let maintainer = Keys::generate();
+        let mut event = list_event(&maintainer, COMMUNITY_BLACKLIST_KIND, &["wss://a.example"]);
+        event.content = "tampered".to_string();
+        assert!(matches!(
+            verify_community_list(&event, &maintainer.public_key()),
+            Err(RelayError::InvalidBlacklist(_))
+        ));
+    }
+
+    #[test]
+    fn unpinned_build_refuses_community_lists() {
+        if COMMUNITY_BLACKLIST_MAINTAINER.is_none() {
+            assert!(matches!(
+                community_blacklist_maintainer(),
+                Err(RelayError::InvalidBlacklist(_))
+            ));
+        } else {
+            community_blacklist_maintainer().unwrap();
+        }
+    }
+
+    #[test]
+    fn community_entries_apply_only_when_opted_in() {
+        let community = vec!["wss://evil.example".to_string()];
+        let off = RelayBlacklist::new(false, community.clone(), []);
+        assert!(!off.is_blocked("wss://evil.example"));
+
+        let on = RelayBlacklist::new(true, community, []);
+        assert!(on.is_blocked("wss://evil.example/"));
+        assert!(!on.is_blocked("wss://good.example"));
+    }
+
+    #[test]
+    fn local_allow_overrides_community_and_local_block_always_applies() {
+        let bl = RelayBlacklist::new(
+            true,
+            vec!["wss://mine.example".to_string()],
+            [
+                ("wss://mine.example".to_string(), LocalRelayOverride::Allow),
+                ("wss://spam.example".to_string(), LocalRelayOverride::Block),
+            ],
+        );
+        assert!(!bl.is_blocked("wss://mine.example"));
+        assert!(bl.is_blocked("wss://spam.example"));
+
+        let off = RelayBlacklist::new(
+            false,
+            [],
+            [("wss://spam.example".to_string(), LocalRelayOverride::Block)],
+        );
+        assert!(off.is_blocked("wss://spam.example"));
+    }
+
+    #[test]
+    fn override_slug_round_trip() {
+        for o in [LocalRelayOverride::Block, LocalRelayOverride::Allow] {
+            assert_eq!(LocalRelayOverride::parse(o.as_str()), Some(o));
+        }
+        assert_eq!(LocalRelayOverride::parse("nope"), None);
+    }
+}
@@ -65,6 +65,12 @@ pub struct CatchupOutcome {
     pub deadline_hit: bool,
     /// Relay fetches that returned no response / errored (tallied, never fatal).
     pub relay_errors: usize,
+    /// Event signatures verified (see [`crate::relay::verify`]).
+    pub signatures_verified: usize,
+    /// Events accepted from the verified-event cache without re-verifying.
+    pub signatures_cached: usize,
+    /// Events dropped for a mismatched id or invalid signature.
+    pub signatures_rejected: usize,
 }
 
 /// Max events fetched per circle per sweep — a flood-guard (Rule 12) so a
@@ -85,6 +91,7 @@ use crate::relay::auto_commit::{CONVERGENCE_RETICK_DELAY, MAX_CONVERGENCE_RETICK
 use crate::relay::cursor::{since_for_stream, SubscribePhase};
 use crate::relay::live_sync::group_cursor_stream;
 use crate::relay::live_sync::planes::group::group_filter;
+use crate::relay::verify::verify_batch;
 use crate::relay::RelayManager;
 
 /// The cursor-advance target (ms) for a batch already sorted ascending by
@@ -117,7 +124,11 @@ async fn ingest_one(
         return ReceiveOnlyOutcome::Deferred;
     };
 
+    let sent_at = i64::try_from(ev.created_at.as_secs()).unwrap_or(i64::MAX);
     persist_locations(circle_mgr, &ingest.effects.events, ngid, own_hex);
+    circle_mgr
+        .reconcile_rosters(&ingest.effects.events, sent_at)
+        .await;
     resolve_publish_work(circle_mgr, relay_mgr, &ingest.effects.publish).await;
 
     // Release any queued convergence work + persist its locations, re-ticking a
@@ -134,6 +145,7 @@ async fn ingest_one(
         for gid in &pending {
             if let Ok(more) = circle_mgr.session().advance_convergence(gid).await {
                 persist_locations(circle_mgr, &more.events, ngid, own_hex);
+                circle_mgr.reconcile_rosters(&more.events, sent_at).await;
                 resolve_publish_work(circle_mgr, relay_mgr, &more.publish).await;
                 next.extend(more.pending_convergence);
             }
@@ -159,11 +171,27 @@ fn persist_locations(
     own_hex: &str,
 ) {
     for ge in events {
-        if let Some(LocationMessageResult::Location {
-            sender_pubkey,
-            content,
-            ..
-        }) = SessionManager::location_result_from_event(ge)
+        let result = SessionManager::location_result_from_event(ge);
+        // A meet pin is stored (or dropped if it lapsed while offline); it is
+        // never the sender's own position.
+        if let Some(pin @ LocationMessageResult::MeetPin { .. }) = &result {
+            let _ = circle_mgr.admit_meet_pin(pin);
+            continue;
+        }
+        // An SOS payload is a superset of a location, so it refreshes the
+        // sender's last-known position too.
+        if let Some(
+            LocationMessageResult::Location {
+                sender_pubkey,
+                content,
+                ..
+            }
+            | LocationMessageResult::Sos {
+                sender_pubkey,
+                content,
+                ..
+            },
+        ) = result
         {
             if sender_pubkey == own_hex {
                 continue;
@@ -178,7 +206,9 @@ fn persist_locations(
                     display_name: msg.display_name,
                     timestamp: msg.timestamp.timestamp(),
                     expires_at: msg.expires_at.timestamp(),
-                    purge_after: 0, // recomputed authoritatively by upsert
+                    // The sender's share expiration can only shorten the retention
+                    // window upsert derives; 0 means none was chosen.
+                    purge_after: msg.share_expires_at.map_or(0, |t| t.timestamp()),
                     updated_at: chrono::Utc::now().timestamp(),
                 };
                 let _ = circle_mgr.upsert_last_known_location(&row);
@@ -265,6 +295,14 @@ pub async fn run_catchup_all_circles(
                 }
             }
         }
+        // Verify signatures in parallel before any MLS processing; a forged
+        // event never reaches the engine (nor holds the cursor back).
+        let (valid, sigs) = verify_batch(&events).await;
+        out.signatures_verified += sigs.verified;
+        out.signatures_cached += sigs.cached;
+        out.signatures_rejected += sigs.rejected;
+        let mut valid = valid.into_iter();
+        events.retain(|_| valid.next().unwrap_or(false));
         // Ascending (created_at, id) so the contiguous-prefix cursor rule holds.
         events.sort_by(|a, b| a.created_at.cmp(&b.created_at).then(a.id.cmp(&b.id)));
 
@@ -0,0 +1,178 @@
+//! Cover traffic: dummy kind 445 events between real updates.
+//!
+//! Relays cannot read a circle's kind 445 events, but they see when each one
+//! arrives and how large it is — enough to tell when a member is out and
+//! about from the rhythm of their updates. With
+//! [`PrivacySettings::cover_traffic_per_hour`] set, the core mixes in chaff:
+//! application messages sent through the same MLS path as a location
+//! update, tagged the same, and padded to a location update's size, so only
+//! members can tell them apart after decryption. Receivers drop them there
+//! (a [`HavenPayload::Chaff`] payload); older receivers decode the type as
+//! unknown and drop it too.
+//!
+//! [`ChaffScheduler`] decides when. Each circle gets a randomized timer whose
+//! mean spreads the hourly budget across the circles, and a sliding one-hour
+//! window caps the total, so battery and bandwidth stay bounded however many
+//! circles there are. State is in memory; a restart draws new timers.
+//!
+//! [`PrivacySettings::cover_traffic_per_hour`]: crate::privacy::PrivacySettings::cover_traffic_per_hour
+//! [`HavenPayload::Chaff`]: crate::payload::HavenPayload::Chaff
+
+use std::collections::{HashMap, VecDeque};
+use std::sync::{Mutex, PoisonError};
+
+use rand::distributions::Alphanumeric;
+use rand::rngs::OsRng;
+use rand::Rng;
+
+use crate::privacy::MAX_COVER_TRAFFIC_PER_HOUR;
+
+/// Shortest chaff padding, in characters.
+pub const CHAFF_PADDING_MIN: usize = 160;
+
+/// Longest chaff padding, in characters. With the envelope around it, chaff
+/// content spans the sizes of an encoded location update.
+pub const CHAFF_PADDING_MAX: usize = 280;
+
+/// Length of the budget window (1 hour).
+const BUDGET_WINDOW_SECS: i64 = 60 * 60;
+
+#[derive(Debug, Default)]
+struct State {
+    next_at: HashMap<[u8; 32], i64>,
+    sent: VecDeque<i64>,
+}
+
+/// Per-circle chaff timers and the shared hourly budget, keyed by the
+/// circle's Nostr group ID.
+#[derive(Debug, Default)]
+pub struct ChaffScheduler {
+    state: Mutex<State>,
+}
+
+impl ChaffScheduler {
+    /// Creates a scheduler with no timers.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The `circles` due a dummy event at `now`, each rescheduled. At most
+    /// `per_hour` (capped at [`MAX_COVER_TRAFFIC_PER_HOUR`]) are handed out
+    /// in any hour across all circles; a circle left over stays due.
+    ///
+    /// A circle seen for the first time gets a timer instead of an event, and
+    /// `per_hour == 0` clears every timer.
+    pub fn take_due(&self, circles: &[[u8; 32]], per_hour: u32, now: i64) -> Vec<[u8; 32]> {
+        let per_hour = per_hour.min(MAX_COVER_TRAFFIC_PER_HOUR);
+        let mut state = self.state.lock().unwrap_or_else(PoisonError::into_inner);
+        if per_hour == 0 || circles.is_empty() {
+            state.next_at.clear();
+            return Vec::new();
+        }
+        state.next_at.retain(|id, _| circles.contains(id));
+        let window_start = now.saturating_sub(BUDGET_WINDOW_SECS);
+        while state.sent.front().is_some_and(|&at| at <= window_start) {
+            state.sent.pop_front();
+        }
+
+        let count = i64::try_from(circles.len()).unwrap_or(i64::MAX);
+        let mean = BUDGET_WINDOW_SECS.saturating_mul(count) / i64::from(per_hour);
+        let budget = usize::try_from(per_hour).unwrap_or(usize::MAX);
+        let mut due = Vec::new();
+        for circle in circles {
+            let next_at = *state
+                .next_at
+                .entry(*circle)
+                .or_insert_with(|| now.saturating_add(sample_interval(mean)));
+            if next_at > now {
+                continue;
+            }
+            if state.sent.len() >= budget {
+                break;
+            }
+            state.sent.push_back(now);
+            state
+                .next_at
+                .insert(*circle, now.saturating_add(sample_interval(mean)));
+            due.push(*circle);
+        }
+        due
+    }
+}
+
+/// A uniformly random interval in `[mean / 2, 3 * mean / 2]` seconds, at
+/// least one second.
+///
+/// Uses `OsRng` for the same reason as [`crate::location::ttl`]: relay
+/// observers must not be able to predict it.
+fn sample_interval(mean: i64) -> i64 {
+    let mean = mean.max(2);
+    OsRng.gen_range(mean / 2..=mean.saturating_add(mean / 2))
+}
+
+/// Random padding for a chaff payload, [`CHAFF_PADDING_MIN`] to
+/// [`CHAFF_PADDING_MAX`] characters long.
+#[must_use]
+pub fn chaff_padding() -> String {
+    let len = OsRng.gen_range(CHAFF_PADDING_MIN..=CHAFF_PADDING_MAX);
+    OsRng
+        .sample_iter(Alphanumeric)
+        .take(len)
+        .map(char::from)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn first_sight_schedules_and_timers_fire_within_the_spread() {
+        let scheduler = ChaffScheduler::new();
+        let circle = [1u8; 32];
+        assert!(scheduler.take_due(&[circle], 4, 0).is_empty());
+
+        // Mean 900 s for one circle at 4/hour: due between 450 and 1350 s.
+        assert!(scheduler.take_due(&[circle], 4, 449).is_empty());
+        assert_eq!(scheduler.take_due(&[circle], 4, 1_350), vec![circle]);
+        assert!(scheduler.take_due(&[circle], 4, 1_350).is_empty());
+    }
+
+    #[test]
+    fn budget_caps_every_hour_across_circles() {
+        let scheduler = ChaffScheduler::new();
+        let circles: Vec<[u8; 32]> = (0..10u8).map(|i| [i; 32]).collect();
+        scheduler.take_due(&circles, 3, 0);
+
+        let mut sent = Vec::new();
+        for minute in 1..=180 {
+            let now = minute * 60;
+            for _ in scheduler.take_due(&circles, 3, now) {
+                sent.push(now);
+            }
+        }
+        for (i, &at) in sent.iter().enumerate() {
+            let in_hour = sent[i..]
+                .iter()
+                .take_while(|&&t| t < at + BUDGET_WINDOW_SECS)
+                .count();
+            assert!(in_hour <= 3, "{in_hour} chaff events within an hour");
+        }
+        assert!(!sent.is_empty());
+    }
+
+    #[test]
+    fn zero_budget_sends_nothing_and_padding_stays_in_range() {
+        let scheduler = ChaffScheduler::new();
+        let circle = [7u8; 32];
+        scheduler.take_due(&[circle], 12, 0);
+        assert!(scheduler.take_due(&[circle], 0, 1_000_000).is_empty());
+
+        for _ in 0..20 {
+            let padding = chaff_padding();
+            assert!((CHAFF_PADDING_MIN..=CHAFF_PADDING_MAX).contains(&padding.len()));
+            assert!(padding.chars().all(|c| c.is_ascii_alphanumeric()));
+        }
+    }
+}
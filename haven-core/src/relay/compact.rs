@@ -0,0 +1,117 @@
+//! Compact, JSON-free representation of fetched events.
+//!
+//! Relay fetch results used to cross the FFI as one JSON string per event,
+//! which the app then handed back for decryption. For a large catch-up batch
+//! that holds every event twice (parsed + serialized) and pays JSON escaping
+//! on the base64 ciphertext. A [`CompactEvent`] carries the canonical NIP-01
+//! fields as raw bytes and plain strings instead; converting back
+//! ([`CompactEvent::into_event`]) re-verifies the id and signature, so a
+//! compact event is exactly as trustworthy as one parsed from JSON.
+
+use nostr::secp256k1::schnorr::Signature;
+use nostr::{Event, EventId, Kind, PublicKey, Tag, Timestamp};
+
+use super::error::{RelayError, RelayResult};
+
+/// An event's canonical fields, without JSON.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CompactEvent {
+    /// Event id (32 bytes).
+    pub id: [u8; 32],
+    /// Author public key (32-byte x-only).
+    pub pubkey: [u8; 32],
+    /// Unix timestamp (seconds).
+    pub created_at: u64,
+    /// Event kind.
+    pub kind: u16,
+    /// Tags, each a list of strings.
+    pub tags: Vec<Vec<String>>,
+    /// Event content.
+    pub content: String,
+    /// Schnorr signature (64 bytes).
+    pub sig: [u8; 64],
+}
+
+impl CompactEvent {
+    /// Moves `event`'s fields into compact form without re-serializing.
+    #[must_use]
+    pub fn from_event(event: Event) -> Self {
+        Self {
+            id: event.id.to_bytes(),
+            pubkey: event.pubkey.to_bytes(),
+            created_at: event.created_at.as_secs(),
+            kind: event.kind.as_u16(),
+            tags: event.tags.into_iter().map(Tag::to_vec).collect(),
+            content: event.content,
+            sig: event.sig.serialize(),
+        }
+    }
+
+    /// Rebuilds the event and verifies its id and signature.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`RelayError::InvalidEvent`] if a field is malformed or the id
+    /// or signature does not verify.
+    pub fn into_event(self) -> RelayResult<Event> {
+        let pubkey = PublicKey::from_slice(&self.pubkey)
+            .map_err(|_| RelayError::InvalidEvent("invalid pubkey".to_string()))?;
+        let sig = Signature::from_slice(&self.sig)
+            .map_err(|_| RelayError::InvalidEvent("invalid signature".to_string()))?;
+        let tags = self
+            .tags
+            .into_iter()
+            .map(|t| Tag::parse(t).map_err(|_| RelayError::InvalidEvent("invalid tag".to_string())))
+            .collect::<RelayResult<Vec<_>>>()?;
+
+        let event = Event::new(
+            EventId::from_byte_array(self.id),
+            pubkey,
+            Timestamp::from(self.created_at),
+            Kind::from(self.kind),
+            tags,
+            self.content,
+            sig,
+        );
+        event
+            .verify()
+            .map_err(|_| RelayError::InvalidEvent("invalid id or signature".to_string()))?;
+        Ok(event)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use nostr::{EventBuilder, Keys};
+
+    fn sample() -> Event {
+        let keys = Keys::generate();
+        EventBuilder::new(Kind::Custom(445), "ciphertext")
+            .tags([Tag::parse(["h", "abcd"]).unwrap()])
+            .sign_with_keys(&keys)
+            .unwrap()
+    }
+
+    #[test]
+    fn round_trip_preserves_event() {
+        let event = sample();
+        let compact = CompactEvent::from_event(event.clone());
+        assert_eq!(compact.kind, 445);
+        assert_eq!(
+            compact.tags,
+            vec![vec!["h".to_string(), "abcd".to_string()]]
+        );
+        assert_eq!(compact.into_event().unwrap(), event);
+    }
+
+    #[test]
+    fn tampered_content_is_rejected() {
+        let mut compact = CompactEvent::from_event(sample());
+        compact.content.push('!');
+        assert!(matches!(
+            compact.into_event(),
+            Err(RelayError::InvalidEvent(_))
+        ));
+    }
+}
@@ -74,6 +74,22 @@ pub enum RelayError {
     /// No events found.
     #[error("No events found for filter")]
     NoEventsFound,
+
+    /// Every requested relay is on the relay blacklist.
+    #[error("Relay is blacklisted: {0}")]
+    Blacklisted(String),
+
+    /// A community blacklist event failed verification.
+    #[error("Invalid relay blacklist: {0}")]
+    InvalidBlacklist(String),
+
+    /// A fetched event is malformed or fails id/signature verification.
+    #[error("Invalid event: {0}")]
+    InvalidEvent(String),
+
+    /// NIP-13 proof of work could not be added to an event.
+    #[error("Proof of work failed: {0}")]
+    ProofOfWork(String),
 }
 
 /// Result type for relay operations.
@@ -182,4 +198,13 @@ mod tests {
         let error = RelayError::NoEventsFound;
         assert_eq!(error.to_string(), "No events found for filter");
     }
+
+    #[test]
+    fn proof_of_work_error_display() {
+        let error = RelayError::ProofOfWork("difficulty 30 exceeds the cap of 24".to_string());
+        assert_eq!(
+            error.to_string(),
+            "Proof of work failed: difficulty 30 exceeds the cap of 24"
+        );
+    }
 }
@@ -0,0 +1,357 @@
+//! Gift-wrap inbox processor.
+//!
+//! One call that polls the user's inbox relays for `kind:1059` gift wraps,
+//! hands the new ones to [`CircleManager::process_gift_wrapped_invitations`]
+//! (parallel unwrap, serialized hold), and returns the invitations it
+//! surfaced. The app no longer orchestrates
+//! fetch → parse → process itself.
+//!
+//! Wraps that do not hold a Welcome are tried as
+//! [one-shot locations](crate::one_shot), then as
+//! [rejoin requests](crate::device_link::RejoinRequest); live ones are
+//! returned alongside the invitations and every one is recorded in the dedup
+//! cache, so each is surfaced at most once. A rejoin request is surfaced only
+//! if [`CircleManager::check_rejoin_request`] allows it.
+//!
+//! # Cursors
+//!
+//! Each relay keeps its own `since` cursor ([`inbox_cursor_stream`]), so a
+//! relay that was unreachable for a week is queried from where *it* left off
+//! rather than from where the healthiest relay did. The 7-day
+//! [`INBOX_GIFTWRAP_LOOKBACK_SECS`](super::cursor::INBOX_GIFTWRAP_LOOKBACK_SECS)
+//! is applied on every REQ, so a wrap that failed to process is fetched again
+//! on later polls until it ages out of that window.
+//!
+//! # Deduplication
+//!
+//! A wrap served by several relays is processed once per poll (by event id).
+//! Across polls, wraps already accepted or declined are skipped by the
+//! `processed_gift_wraps` table and wraps still awaiting a decision by the
+//! held-welcome store; both surface as [`CircleError::AlreadyProcessed`] and
+//! are counted as duplicates.
+
+use std::collections::HashMap;
+
+use nostr::{Event, EventId, Filter, Keys, Kind, Timestamp};
+
+use crate::circle::{CircleError, CircleManager, Invitation};
+use crate::device_link::{unwrap_rejoin_request, RejoinRequest};
+use crate::nostr::mls::types::GroupId;
+use crate::one_shot::{unwrap_one_shot_location, OneShotLocation};
+use crate::relay::cursor::{since_for_stream, SubscribePhase, STREAM_INBOX_1059};
+use crate::relay::{RelayFetchOutcome, RelayManager};
+
+/// Max gift wraps fetched per relay per poll — a flood-guard (Rule 12). A real
+/// inbox holds far fewer in the lookback window.
+const INBOX_MAX_WRAPS_PER_RELAY: usize = 1000;
+
+/// The per-relay inbox cursor stream key (`inbox_1059:<relay url>`).
+#[must_use]
+pub fn inbox_cursor_stream(relay_url: &str) -> String {
+    format!("{STREAM_INBOX_1059}:{relay_url}")
+}
+
+/// What one inbox poll did.
+#[derive(Clone, Default)]
+pub struct InboxSyncSummary {
+    /// Relays queried.
+    pub relays_polled: usize,
+    /// Relays that answered.
+    pub relays_responded: usize,
+    /// Distinct gift wraps fetched across all relays.
+    pub wraps_fetched: usize,
+    /// Wraps skipped because they were already processed or are already held.
+    pub duplicates: usize,
+    /// Wraps that could not be processed (not for us, malformed, engine error).
+    pub failed: usize,
+    /// Invitations surfaced by this poll, oldest wrap first.
+    pub new_invitations: Vec<Invitation>,
+    /// Unexpired one-shot locations received this poll, oldest wrap first.
+    pub one_shot_locations: Vec<OneShotLocation>,
+    /// Rejoin requests this device can grant, with the circle each is for,
+    /// oldest wrap first.
+    pub rejoin_requests: Vec<(GroupId, RejoinRequest)>,
+}
+
+impl std::fmt::Debug for InboxSyncSummary {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("InboxSyncSummary")
+            .field("relays_polled", &self.relays_polled)
+            .field("relays_responded", &self.relays_responded)
+            .field("wraps_fetched", &self.wraps_fetched)
+            .field("duplicates", &self.duplicates)
+            .field("failed", &self.failed)
+            .field("new_invitations", &self.new_invitations.len())
+            .field("one_shot_locations", &self.one_shot_locations.len())
+            .field("rejoin_requests", &self.rejoin_requests.len())
+            .finish()
+    }
+}
+
+/// A fetched wrap and the relays that served it.
+struct FetchedWrap {
+    event: Event,
+    relays: Vec<String>,
+}
+
+/// Merges per-relay fetches into distinct wraps (oldest first) and the list
+/// of relays that answered.
+fn merge_fetches(outcomes: Vec<RelayFetchOutcome>) -> (Vec<FetchedWrap>, Vec<String>) {
+    let mut responded = Vec::new();
+    let mut index: HashMap<EventId, usize> = HashMap::new();
+    let mut wraps: Vec<FetchedWrap> = Vec::new();
+    for outcome in outcomes {
+        if !outcome.responded {
+            continue;
+        }
+        for event in outcome.events {
+            if event.kind != Kind::GiftWrap {
+                continue;
+            }
+            if let Some(&i) = index.get(&event.id) {
+                wraps[i].relays.push(outcome.relay_url.clone());
+            } else {
+                index.insert(event.id, wraps.len());
+                wraps.push(FetchedWrap {
+                    event,
+                    relays: vec![outcome.relay_url.clone()],
+                });
+            }
+        }
+        responded.push(outcome.relay_url);
+    }
+    wraps.sort_by(|a, b| {
+        a.event
+            .created_at
+            .cmp(&b.event.created_at)
+            .then(a.event.id.cmp(&b.event.id))
+    });
+    (wraps, responded)
+}
+
+/// Raises each serving relay's cursor target to `wrap`'s `created_at` (ms).
+fn note_handled(targets: &mut HashMap<String, i64>, wrap: &FetchedWrap) {
+    let ms = i64::try_from(wrap.event.created_at.as_secs())
+        .unwrap_or(i64::MAX)
+        .saturating_mul(1000);
+    for relay in &wrap.relays {
+        let target = targets.entry(relay.clone()).or_insert(ms);
+        *target = (*target).max(ms);
+    }
+}
+
+/// Polls inbox relays for gift wraps and routes welcomes to a
+/// [`CircleManager`].
+///
+/// Must be given the foreground `CircleManager` (Rule 14 — one session per
+/// process), so a surfaced invitation lands in the same held-welcome store the
+/// accept/decline calls read.
+pub struct InboxProcessor<'a> {
+    circles: &'a CircleManager,
+    relays: &'a RelayManager,
+}
+
+impl<'a> InboxProcessor<'a> {
+    /// Creates a processor over the given managers.
+    #[must_use]
+    pub const fn new(circles: &'a CircleManager, relays: &'a RelayManager) -> Self {
+        Self { circles, relays }
+    }
+
+    /// Polls `inbox_relays` for gift wraps addressed to `keys`, processes new
+    /// ones, and advances each answering relay's cursor.
+    ///
+    /// Best-effort: unreachable relays and unprocessable wraps are counted in
+    /// the summary, never returned as an error. A cursor advances only past
+    /// wraps that were processed or recognised as duplicates.
+    pub async fn sync(&self, keys: &Keys, inbox_relays: &[String]) -> InboxSyncSummary {
+        let mut summary = InboxSyncSummary {
+            relays_polled: inbox_relays.len(),
+            ..InboxSyncSummary::default()
+        };
+        if inbox_relays.is_empty() {
+            return summary;
+        }
+
+        // Each relay gets its own `since`, so each is its own REQ.
+        let now_secs = chrono::Utc::now().timestamp();
+        let fetches = inbox_relays.iter().map(|relay| {
+            let cursor_ms = self
+                .circles
+                .read_sync_cursor(&inbox_cursor_stream(relay))
+                .ok()
+                .flatten()
+                .unwrap_or_else(|| now_secs.saturating_mul(1000));
+            let since = since_for_stream(
+                STREAM_INBOX_1059,
+                cursor_ms,
+                SubscribePhase::Resubscribe,
+                now_secs,
+            );
+            let filter = Filter::new()
+                .kind(Kind::GiftWrap)
+                .pubkey(keys.public_key())
+                .since(Timestamp::from(u64::try_from(since).unwrap_or(0)))
+                .limit(INBOX_MAX_WRAPS_PER_RELAY);
+            self.relays
+                .fetch_events_per_relay(filter, std::slice::from_ref(relay))
+        });
+        let outcomes: Vec<RelayFetchOutcome> = futures::future::join_all(fetches)
+            .await
+            .into_iter()
+            .filter_map(std::result::Result::ok)
+            .flatten()
+            .collect();
+
+        let (wraps, responded) = merge_fetches(outcomes);
+        summary.relays_responded = responded.len();
+        summary.wraps_fetched = wraps.len();
+
+        // Unwrapped in parallel, then held one at a time, in `wraps` order.
+        let events: Vec<Event> = wraps.iter().map(|w| w.event.clone()).collect();
+        let outcomes = self
+            .circles
+            .process_gift_wrapped_invitations(keys, &events)
+            .await;
+
+        let mut targets: HashMap<String, i64> = HashMap::new();
+        for (wrap, (_, outcome)) in wraps.iter().zip(outcomes) {
+            match outcome {
+                Ok(invitation) => {
+                    summary.new_invitations.push(invitation);
+                    note_handled(&mut targets, wrap);
+                }
+                Err(CircleError::AlreadyProcessed) => {
+                    summary.duplicates += 1;
+                    note_handled(&mut targets, wrap);
+                }
+                Err(e) => {
+                    if self
+                        .take_other_wrap(keys, &wrap.event, now_secs, &mut summary)
+                        .await
+                    {
+                        note_handled(&mut targets, wrap);
+                    } else {
+                        summary.failed += 1;
+                        log::debug!("[InboxProcessor] wrap not processed: {e}");
+                    }
+                }
+            }
+        }
+
+        for (relay, ms) in targets {
+            if let Err(e) = self
+                .circles
+                .advance_sync_cursor(&inbox_cursor_stream(&relay), ms)
+            {
+                log::warn!("[InboxProcessor] failed to advance inbox cursor: {e}");
+            }
+        }
+        summary
+    }
+
+    /// Handles a wrap that holds no Welcome: a one-shot location or a rejoin
+    /// request. Returns whether it was one.
+    async fn take_other_wrap(
+        &self,
+        keys: &Keys,
+        event: &Event,
+        now_secs: i64,
+        summary: &mut InboxSyncSummary,
+    ) -> bool {
+        if let Ok(location) = unwrap_one_shot_location(keys, event).await {
+            if let Err(e) = self.circles.record_one_shot_gift_wrap(&event.id) {
+                log::warn!("[InboxProcessor] failed to record one-shot wrap: {e}");
+            }
+            if !location.is_expired(now_secs) {
+                summary.one_shot_locations.push(location);
+            }
+            return true;
+        }
+        let Ok(request) = unwrap_rejoin_request(keys, event).await else {
+            return false;
+        };
+        if let Err(e) = self.circles.record_rejoin_gift_wrap(&event.id) {
+            log::warn!("[InboxProcessor] failed to record rejoin wrap: {e}");
+        }
+        match self.circles.check_rejoin_request(&request).await {
+            Ok(gid) => summary.rejoin_requests.push((gid, request)),
+            Err(e) => log::debug!("[InboxProcessor] rejoin request not granted here: {e}"),
+        }
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use nostr::EventBuilder;
+
+    fn wrap(created_at: u64) -> Event {
+        EventBuilder::new(Kind::GiftWrap, "sealed")
+            .custom_created_at(Timestamp::from(created_at))
+            .sign_with_keys(&Keys::generate())
+            .unwrap()
+    }
+
+    fn outcome(relay: &str, responded: bool, events: Vec<Event>) -> RelayFetchOutcome {
+        RelayFetchOutcome {
+            relay_url: relay.to_string(),
+            responded,
+            events,
+        }
+    }
+
+    #[test]
+    fn cursor_stream_is_per_relay() {
+        assert_eq!(
+            inbox_cursor_stream("wss://a.example.com"),
+            "inbox_1059:wss://a.example.com"
+        );
+        assert_ne!(
+            inbox_cursor_stream("wss://a.example.com"),
+            inbox_cursor_stream("wss://b.example.com")
+        );
+    }
+
+    #[test]
+    fn merge_dedups_across_relays_oldest_first() {
+        let old = wrap(100);
+        let new = wrap(200);
+        let (wraps, responded) = merge_fetches(vec![
+            outcome("wss://a", true, vec![new.clone(), old.clone()]),
+            outcome("wss://b", true, vec![new.clone()]),
+            outcome("wss://c", false, vec![]),
+        ]);
+        assert_eq!(responded, ["wss://a", "wss://b"]);
+        assert_eq!(wraps.len(), 2);
+        assert_eq!(wraps[0].event.id, old.id);
+        assert_eq!(wraps[0].relays, ["wss://a"]);
+        assert_eq!(wraps[1].event.id, new.id);
+        assert_eq!(wraps[1].relays, ["wss://a", "wss://b"]);
+    }
+
+    #[test]
+    fn merge_ignores_other_kinds() {
+        let note = EventBuilder::new(Kind::TextNote, "hi")
+            .sign_with_keys(&Keys::generate())
+            .unwrap();
+        let (wraps, responded) = merge_fetches(vec![outcome("wss://a", true, vec![note])]);
+        assert!(wraps.is_empty());
+        assert_eq!(responded.len(), 1);
+    }
+
+    #[test]
+    fn handled_wraps_raise_each_serving_relay_cursor() {
+        let (wraps, _) = merge_fetches(vec![
+            outcome("wss://a", true, vec![wrap(100), wrap(300)]),
+            outcome("wss://b", true, vec![]),
+        ]);
+        let mut targets = HashMap::new();
+        for w in &wraps {
+            note_handled(&mut targets, w);
+        }
+        assert_eq!(targets.get("wss://a"), Some(&300_000));
+        assert!(!targets.contains_key("wss://b"));
+    }
+}
@@ -13,15 +13,24 @@
 /// event); a blocked producer would stall the whole receive path.
 pub const BUS_CAP: usize = 8192;
 
-/// Capacity of the bounded receive→decrypt channel between the supervisor's
-/// notifications receiver and its decrypt worker.
-///
-/// Sized to absorb a burst while a slow `SQLCipher` decrypt runs; on overflow the
-/// receiver's `try_send` drops the event (never blocking the pool), and the
-/// dropped event is re-fetched via the cursor on the next subscribe — lossless,
-/// since the cursor advances only on applied events. Kept independent of
-/// [`BUS_CAP`] so the decouple buffer can be tuned separately (M11).
-pub const WORKER_QUEUE_CAP: usize = 8192;
+/// Events the receive→decrypt spool between the supervisor's notifications
+/// receiver and its decrypt worker holds in memory.
+///
+/// A burst beyond it spills to an encrypted file (see [`super::spool`]), so it
+/// bounds memory, not throughput. Kept independent of [`BUS_CAP`] so the
+/// decouple buffer can be tuned separately (M11).
+pub const WORKER_QUEUE_CAP: usize = 1024;
+
+/// Events the receive→decrypt spool may hold on disk beyond
+/// [`WORKER_QUEUE_CAP`].
+///
+/// On overflow the receiver drops the event (never blocking the pool), and it
+/// is re-fetched via the cursor on the next subscribe — lossless, since the
+/// cursor advances only on applied events.
+pub const SPOOL_MAX_SPILLED_EVENTS: usize = 65_536;
+
+/// Directory, under the data directory, that holds the spool's spill files.
+pub const SPOOL_DIR_NAME: &str = "live_sync_spool";
 
 /// Capacity of the nostr pool's notification broadcast channel.
 ///
@@ -59,6 +59,58 @@ pub enum LiveSyncEvent {
         /// The relay-public `created_at` of the source event (seconds).
         event_created_at_secs: i64,
     },
+    /// A decrypted emergency (SOS) broadcast for a circle. Same shape as
+    /// [`Self::Location`]; `content` is a `SosMessage` JSON payload.
+    Sos {
+        /// The circle's pseudonymous `nostr_group_id` (NOT the MLS group id).
+        nostr_group_id: Vec<u8>,
+        /// Sender's hex-encoded Nostr public key.
+        sender_pubkey: String,
+        /// Decrypted SOS content (JSON).
+        content: String,
+        /// The relay-public `created_at` of the source event (seconds).
+        event_created_at_secs: i64,
+    },
+    /// A decrypted, still-live meet pin for a circle. `content` is a
+    /// `MeetPin` JSON payload with exact coordinates; it is not the sender's
+    /// own position.
+    MeetPin {
+        /// The circle's pseudonymous `nostr_group_id` (NOT the MLS group id).
+        nostr_group_id: Vec<u8>,
+        /// Sender's hex-encoded Nostr public key.
+        sender_pubkey: String,
+        /// Decrypted pin content (JSON).
+        content: String,
+        /// The relay-public `created_at` of the source event (seconds).
+        event_created_at_secs: i64,
+    },
+    /// A decrypted check-in request: `sender_pubkey` asks `target_pubkey` to
+    /// share their current location.
+    CheckinRequest {
+        /// The circle's pseudonymous `nostr_group_id` (NOT the MLS group id).
+        nostr_group_id: Vec<u8>,
+        /// Requester's hex-encoded Nostr public key.
+        sender_pubkey: String,
+        /// Hex-encoded Nostr public key of the member being asked.
+        target_pubkey: String,
+        /// The relay-public `created_at` of the source event (seconds).
+        event_created_at_secs: i64,
+    },
+    /// A sender's location was finer than the precision they had been sharing
+    /// at in this circle (see `crate::location::precision`). Follows the
+    /// [`Self::Location`] that triggered it.
+    PrecisionAnomaly {
+        /// The circle's pseudonymous `nostr_group_id` (NOT the MLS group id).
+        nostr_group_id: Vec<u8>,
+        /// Sender's hex-encoded Nostr public key.
+        sender_pubkey: String,
+        /// The precision the sender had established.
+        baseline: crate::location::PrecisionClass,
+        /// The finer precision just received.
+        observed: crate::location::PrecisionClass,
+        /// The relay-public `created_at` of the source event (seconds).
+        event_created_at_secs: i64,
+    },
     /// A group membership / epoch update — the roster changed and the change is
     /// already applied locally. A UI-only signal: the consumer just refreshes; it
     /// owes NO publish/merge (since M6-2 the engine converges an auto-committed
@@ -99,6 +151,49 @@ impl std::fmt::Debug for LiveSyncEvent {
                 .field("content", &"<redacted>")
                 .field("event_created_at_secs", event_created_at_secs)
                 .finish(),
+            Self::Sos {
+                event_created_at_secs,
+                ..
+            } => f
+                .debug_struct("Sos")
+                .field("nostr_group_id", &"<redacted>")
+                .field("sender_pubkey", &"<redacted>")
+                .field("content", &"<redacted>")
+                .field("event_created_at_secs", event_created_at_secs)
+                .finish(),
+            Self::MeetPin {
+                event_created_at_secs,
+                ..
+            } => f
+                .debug_struct("MeetPin")
+                .field("nostr_group_id", &"<redacted>")
+                .field("sender_pubkey", &"<redacted>")
+                .field("content", &"<redacted>")
+                .field("event_created_at_secs", event_created_at_secs)
+                .finish(),
+            Self::CheckinRequest {
+                event_created_at_secs,
+                ..
+            } => f
+                .debug_struct("CheckinRequest")
+                .field("nostr_group_id", &"<redacted>")
+                .field("sender_pubkey", &"<redacted>")
+                .field("target_pubkey", &"<redacted>")
+                .field("event_created_at_secs", event_created_at_secs)
+                .finish(),
+            Self::PrecisionAnomaly {
+                baseline,
+                observed,
+                event_created_at_secs,
+                ..
+            } => f
+                .debug_struct("PrecisionAnomaly")
+                .field("nostr_group_id", &"<redacted>")
+                .field("sender_pubkey", &"<redacted>")
+                .field("baseline", baseline)
+                .field("observed", observed)
+                .field("event_created_at_secs", event_created_at_secs)
+                .finish(),
             Self::GroupUpdate {
                 evolution_event_json,
                 ..
@@ -140,6 +235,31 @@ mod tests {
             content: SECRET_CONTENT.to_string(),
             event_created_at_secs: 1234,
         };
+        let sos = LiveSyncEvent::Sos {
+            nostr_group_id: group_id.clone(),
+            sender_pubkey: SENDER_PK.to_string(),
+            content: SECRET_CONTENT.to_string(),
+            event_created_at_secs: 4321,
+        };
+        let meet_pin = LiveSyncEvent::MeetPin {
+            nostr_group_id: group_id.clone(),
+            sender_pubkey: SENDER_PK.to_string(),
+            content: SECRET_CONTENT.to_string(),
+            event_created_at_secs: 9753,
+        };
+        let checkin = LiveSyncEvent::CheckinRequest {
+            nostr_group_id: group_id.clone(),
+            sender_pubkey: SENDER_PK.to_string(),
+            target_pubkey: SENDER_PK.to_string(),
+            event_created_at_secs: 2468,
+        };
+        let anomaly = LiveSyncEvent::PrecisionAnomaly {
+            nostr_group_id: group_id.clone(),
+            sender_pubkey: SENDER_PK.to_string(),
+            baseline: crate::location::PrecisionClass::Coarse,
+            observed: crate::location::PrecisionClass::Exact,
+            event_created_at_secs: 1357,
+        };
         let group_update = LiveSyncEvent::GroupUpdate {
             nostr_group_id: group_id,
             evolution_event_json: Some(EVOLUTION_JSON.to_string()),
@@ -152,7 +272,16 @@ mod tests {
             reason: SyncStatusReason::Connected,
         };
 
-        for ev in [&location, &group_update, &welcome, &status] {
+        for ev in [
+            &location,
+            &sos,
+            &meet_pin,
+            &checkin,
+            &anomaly,
+            &group_update,
+            &welcome,
+            &status,
+        ] {
             let dbg = format!("{ev:?}");
             assert!(!dbg.contains(SECRET_CONTENT), "leaked content: {dbg}");
             assert!(!dbg.contains(SENDER_PK), "leaked sender pubkey: {dbg}");
@@ -167,6 +296,10 @@ mod tests {
 
         // Relay-public timestamps + the closed status enum may render.
         assert!(format!("{location:?}").contains("1234"));
+        assert!(format!("{sos:?}").contains("4321"));
+        assert!(format!("{meet_pin:?}").contains("9753"));
+        assert!(format!("{checkin:?}").contains("2468"));
+        assert!(format!("{anomaly:?}").contains("Exact"));
         assert!(format!("{welcome:?}").contains("5678"));
         assert!(format!("{group_update:?}").contains("has_evolution_event: true"));
         assert!(format!("{status:?}").contains("Connected"));
@@ -39,6 +39,7 @@ pub mod planes;
 pub mod processor;
 pub mod router;
 pub mod session;
+pub mod spool;
 pub mod supervisor;
 
 pub use config::COMMIT_SETTLE_WINDOW_SECS;
@@ -57,3 +58,4 @@ pub use planes::{
 pub use processor::{group_cursor_stream, EngineProcessor, GroupProcessOutcome};
 pub use router::{Router, SubCtx};
 pub use session::LiveSyncCore;
+pub use spool::{EventSpool, SpoolPush};
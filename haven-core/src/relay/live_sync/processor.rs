@@ -130,6 +130,9 @@ impl EngineProcessor {
         let created_at_secs = i64::try_from(event.created_at.as_secs()).unwrap_or(i64::MAX);
 
         let Ok(ingest) = self.circle.session().process_event(event).await else {
+            // Best-effort: feeds `CircleManager::group_health`; a write failure
+            // must not block the receive path.
+            let _ = self.circle.record_unprocessable(nostr_group_id);
             self.bus.send(LiveSyncEvent::Status {
                 reason: SyncStatusReason::Unprocessable,
             });
@@ -139,6 +142,9 @@ impl EngineProcessor {
         // Route the drained events, then release any stored convergence + route
         // those, resolving engine publish work as we go.
         self.route_events(&ingest.effects.events, nostr_group_id, created_at_secs);
+        self.circle
+            .reconcile_rosters(&ingest.effects.events, created_at_secs)
+            .await;
         self.resolve_publish_work(&ingest.effects.publish).await;
         self.drain_convergence(
             &ingest.effects.pending_convergence,
@@ -153,6 +159,10 @@ impl EngineProcessor {
         match ingest.outcome {
             IngestOutcome::Buffered { .. } => GroupProcessOutcome::Buffered,
             IngestOutcome::Processed | IngestOutcome::Stale { .. } => {
+                if matches!(ingest.outcome, IngestOutcome::Processed) {
+                    // Applied at the current epoch: the group is in step again.
+                    let _ = self.circle.clear_unprocessable(nostr_group_id);
+                }
                 let ms = created_at_secs.saturating_mul(1000);
                 // Best-effort: a cursor write failure must not drop the delivered
                 // event; the cursor re-advances on the next applied event.
@@ -191,6 +201,9 @@ impl EngineProcessor {
             for gid in &pending {
                 if let Ok(more) = self.circle.session().advance_convergence(gid).await {
                     self.route_events(&more.events, nostr_group_id, event_created_at_secs);
+                    self.circle
+                        .reconcile_rosters(&more.events, event_created_at_secs)
+                        .await;
                     self.resolve_publish_work(&more.publish).await;
                     next.extend(more.pending_convergence);
                 }
@@ -213,35 +226,93 @@ impl EngineProcessor {
             let Some(result) = SessionManager::location_result_from_event(group_event) else {
                 continue;
             };
-            match result {
-                LocationMessageResult::Location {
-                    sender_pubkey,
-                    content,
-                    ..
-                } => self.bus.send(LiveSyncEvent::Location {
+            if !self.circle.admit_meet_pin(&result) {
+                continue;
+            }
+            let anomaly = self.circle.observe_location_precision(&result);
+            for result in std::iter::once(result).chain(anomaly) {
+                self.route_result(result, nostr_group_id, event_created_at_secs);
+            }
+        }
+    }
+
+    /// Emits one folded result onto the fan-out bus.
+    fn route_result(
+        &self,
+        result: LocationMessageResult,
+        nostr_group_id: &[u8],
+        event_created_at_secs: i64,
+    ) {
+        match result {
+            LocationMessageResult::Location {
+                sender_pubkey,
+                content,
+                ..
+            } => self.bus.send(LiveSyncEvent::Location {
+                nostr_group_id: nostr_group_id.to_vec(),
+                sender_pubkey,
+                content,
+                event_created_at_secs,
+            }),
+            LocationMessageResult::Sos {
+                sender_pubkey,
+                content,
+                ..
+            } => self.bus.send(LiveSyncEvent::Sos {
+                nostr_group_id: nostr_group_id.to_vec(),
+                sender_pubkey,
+                content,
+                event_created_at_secs,
+            }),
+            LocationMessageResult::MeetPin {
+                sender_pubkey,
+                content,
+                ..
+            } => self.bus.send(LiveSyncEvent::MeetPin {
+                nostr_group_id: nostr_group_id.to_vec(),
+                sender_pubkey,
+                content,
+                event_created_at_secs,
+            }),
+            LocationMessageResult::PrecisionAnomaly {
+                sender_pubkey,
+                baseline,
+                observed,
+                ..
+            } => self.bus.send(LiveSyncEvent::PrecisionAnomaly {
+                nostr_group_id: nostr_group_id.to_vec(),
+                sender_pubkey,
+                baseline,
+                observed,
+                event_created_at_secs,
+            }),
+            LocationMessageResult::CheckinRequest {
+                sender_pubkey,
+                target_pubkey,
+                ..
+            } => self.bus.send(LiveSyncEvent::CheckinRequest {
+                nostr_group_id: nostr_group_id.to_vec(),
+                sender_pubkey,
+                target_pubkey,
+                event_created_at_secs,
+            }),
+            // A roster/epoch change, a join, or a superseded (invalidated)
+            // commit are all UI-only refresh signals now (the engine already
+            // applied / rolled back the change internally).
+            LocationMessageResult::GroupUpdate { .. }
+            | LocationMessageResult::Joined { .. }
+            | LocationMessageResult::Invalidated { .. } => {
+                self.bus.send(LiveSyncEvent::GroupUpdate {
                     nostr_group_id: nostr_group_id.to_vec(),
-                    sender_pubkey,
-                    content,
-                    event_created_at_secs,
-                }),
-                // A roster/epoch change, a join, or a superseded (invalidated)
-                // commit are all UI-only refresh signals now (the engine already
-                // applied / rolled back the change internally).
-                LocationMessageResult::GroupUpdate { .. }
-                | LocationMessageResult::Joined { .. }
-                | LocationMessageResult::Invalidated { .. } => {
-                    self.bus.send(LiveSyncEvent::GroupUpdate {
-                        nostr_group_id: nostr_group_id.to_vec(),
-                        evolution_event_json: None,
-                    });
-                }
-                // The group is unrecoverable: surface a blocked-state status so
-                // the UI can stop send/mutate (Rule 8).
-                LocationMessageResult::Unrecoverable { .. } => {
-                    self.bus.send(LiveSyncEvent::Status {
-                        reason: SyncStatusReason::Unprocessable,
-                    });
-                }
+                    evolution_event_json: None,
+                });
+            }
+            // The group is unrecoverable: surface a blocked-state status so
+            // the UI can stop send/mutate (Rule 8).
+            LocationMessageResult::Unrecoverable { .. } => {
+                self.bus.send(LiveSyncEvent::Status {
+                    reason: SyncStatusReason::Unprocessable,
+                });
             }
         }
     }
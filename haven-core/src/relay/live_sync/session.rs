@@ -19,15 +19,16 @@ use std::time::Duration;
 use nostr::{Filter, PublicKey, SubscriptionId};
 use nostr_sdk::pool::monitor::Monitor;
 use nostr_sdk::{Client, ClientOptions, RelayPoolNotification, RelayPoolOptions, RelayStatus};
-use tokio::sync::{broadcast, mpsc, Mutex as TokioMutex, RwLock};
+use tokio::sync::{broadcast, Mutex as TokioMutex, RwLock};
 use zeroize::Zeroizing;
 
 use crate::circle::CircleManager;
 use crate::relay::cursor::{since_for_stream, SubscribePhase, STREAM_INBOX_1059};
 
 use super::config::{
-    BUS_CAP, POOL_NOTIF_CAP, RELAY_LIFECYCLE_OP_TIMEOUT_SECS, SUBSCRIBE_CONNECT_WAIT_SECS,
-    SUBSCRIBE_MAX_ATTEMPTS, SUBSCRIBE_RETRY_WAIT_SECS, WORKER_QUEUE_CAP,
+    BUS_CAP, POOL_NOTIF_CAP, RELAY_LIFECYCLE_OP_TIMEOUT_SECS, SPOOL_DIR_NAME,
+    SPOOL_MAX_SPILLED_EVENTS, SUBSCRIBE_CONNECT_WAIT_SECS, SUBSCRIBE_MAX_ATTEMPTS,
+    SUBSCRIBE_RETRY_WAIT_SECS, WORKER_QUEUE_CAP,
 };
 use super::error::{LiveSyncError, LiveSyncResult};
 use super::event::{LiveSyncEvent, SyncStatusReason};
@@ -43,6 +44,7 @@ use super::planes::{
 };
 use super::processor::{group_cursor_stream, EngineProcessor};
 use super::router::{Router, SubCtx};
+use super::spool::EventSpool;
 use super::supervisor::{run_receiver, run_worker};
 
 /// Cold-start cursor seed: on first subscription a circle's cursor is seeded to
@@ -413,13 +415,21 @@ impl LiveSyncCore {
         // during the subscribe round-trip is missed (notifications() only yields
         // events seen after the receiver exists).
         let notifications: broadcast::Receiver<RelayPoolNotification> = self.client.notifications();
-        let (tx, rx) = mpsc::channel(WORKER_QUEUE_CAP);
+        let spool = Arc::new(EventSpool::new(
+            WORKER_QUEUE_CAP,
+            SPOOL_MAX_SPILLED_EVENTS,
+            Some(self.circle.data_dir().join(SPOOL_DIR_NAME)),
+        ));
         // The worker just drains events and feeds them to the engine (which owns
         // convergence + publish-before-apply internally); no per-circle gate /
         // settle buffer / converge task is needed anymore (plan §5.4).
-        tokio::spawn(run_receiver(notifications, tx, Arc::clone(&self.shutdown)));
+        tokio::spawn(run_receiver(
+            notifications,
+            Arc::clone(&spool),
+            Arc::clone(&self.shutdown),
+        ));
         tokio::spawn(run_worker(
-            rx,
+            spool,
             Arc::clone(&self.router),
             Arc::clone(&self.processor),
         ));
@@ -0,0 +1,448 @@
+//! The receive→decrypt queue: bounded in memory, spilling to disk.
+//!
+//! Starting live sync on several circles can deliver thousands of `kind:445`
+//! events faster than the decrypt worker ingests them. Holding them all in
+//! memory risks an OOM kill on a low-memory phone; dropping the overflow (the
+//! old bounded-channel behavior) is lossless thanks to cursor replay but
+//! re-fetches the whole burst on the next subscribe.
+//!
+//! [`EventSpool`] keeps at most [`WORKER_QUEUE_CAP`] events in memory. Once
+//! that is full, later events are appended to a spill file and read back, in
+//! order, as the worker drains the queue: it refills the in-memory part a
+//! batch at a time whenever it falls to half full. Events leave in the order
+//! they arrived, spilled or not. Beyond [`SPOOL_MAX_SPILLED_EVENTS`] the
+//! spool drops events as the channel did, and cursor replay fetches them
+//! again.
+//!
+//! # At rest
+//!
+//! Each spilled event is sealed with XChaCha20-Poly1305 under a key drawn
+//! from `OsRng` when the spool is created and never written anywhere, so the
+//! file is unreadable once the process exits. The file is removed when the
+//! spool drops, and the first spool of a process sweeps files a crashed one
+//! left behind.
+//!
+//! [`WORKER_QUEUE_CAP`]: super::config::WORKER_QUEUE_CAP
+//! [`SPOOL_MAX_SPILLED_EVENTS`]: super::config::SPOOL_MAX_SPILLED_EVENTS
+
+use std::collections::VecDeque;
+use std::fs::{File, OpenOptions};
+use std::io::{Read, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, Once, PoisonError};
+
+use chacha20poly1305::aead::{Aead, KeyInit};
+use chacha20poly1305::{Key, XChaCha20Poly1305, XNonce};
+use nostr::{Event, RelayUrl, SubscriptionId};
+use rand::rngs::OsRng;
+use rand::RngCore;
+use serde::{Deserialize, Serialize};
+use tokio::sync::Notify;
+use zeroize::Zeroizing;
+
+use super::supervisor::RawEvent;
+
+/// Prefix of spill file names.
+const SPILL_FILE_PREFIX: &str = "spool-";
+
+/// XChaCha20 nonce length.
+const NONCE_LEN: usize = 24;
+
+/// Guards the stale-file sweep: later spools of this process may still share
+/// the directory with a draining one.
+static SWEEP: Once = Once::new();
+
+/// What [`EventSpool::push`] did with an event.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SpoolPush {
+    /// Queued in memory.
+    Queued,
+    /// Written to the spill file.
+    Spilled,
+    /// Dropped: the spool is full or closed (cursor replay fetches it again).
+    Dropped,
+}
+
+/// A spilled [`RawEvent`].
+#[derive(Serialize, Deserialize)]
+struct SpilledEvent {
+    relay_url: String,
+    subscription_id: String,
+    event: Event,
+}
+
+/// The spill file and its cipher.
+struct SpillFile {
+    path: PathBuf,
+    file: File,
+    cipher: XChaCha20Poly1305,
+    /// Offset of the next record to read back.
+    read_at: u64,
+    /// Records written but not read back yet.
+    pending: usize,
+}
+
+impl SpillFile {
+    fn create(dir: &Path) -> std::io::Result<Self> {
+        std::fs::create_dir_all(dir)?;
+        let mut name = [0u8; 8];
+        OsRng.fill_bytes(&mut name);
+        let path = dir.join(format!("{SPILL_FILE_PREFIX}{}.bin", hex::encode(name)));
+        let file = OpenOptions::new()
+            .read(true)
+            .write(true)
+            .create_new(true)
+            .open(&path)?;
+        let key = Zeroizing::new({
+            let mut key = [0u8; 32];
+            OsRng.fill_bytes(&mut key);
+            key
+        });
+        Ok(Self {
+            path,
+            file,
+            cipher: XChaCha20Poly1305::new(Key::from_slice(key.as_ref())),
+            read_at: 0,
+            pending: 0,
+        })
+    }
+
+    /// Appends one sealed record: length, nonce, ciphertext.
+    fn append(&mut self, raw: &RawEvent) -> std::io::Result<()> {
+        let plain = Zeroizing::new(
+            serde_json::to_vec(&SpilledEvent {
+                relay_url: raw.relay_url.to_string(),
+                subscription_id: raw.subscription_id.to_string(),
+                event: raw.event.clone(),
+            })
+            .map_err(std::io::Error::other)?,
+        );
+        let mut nonce = [0u8; NONCE_LEN];
+        OsRng.fill_bytes(&mut nonce);
+        let sealed = self
+            .cipher
+            .encrypt(XNonce::from_slice(&nonce), plain.as_slice())
+            .map_err(|_| std::io::Error::other("spool seal failed"))?;
+        let len = u32::try_from(NONCE_LEN + sealed.len()).map_err(std::io::Error::other)?;
+        let mut record = Vec::with_capacity(4 + NONCE_LEN + sealed.len());
+        record.extend_from_slice(&len.to_le_bytes());
+        record.extend_from_slice(&nonce);
+        record.extend_from_slice(&sealed);
+        self.file.seek(SeekFrom::End(0))?;
+        self.file.write_all(&record)?;
+        self.pending += 1;
+        Ok(())
+    }
+
+    /// Reads the next record back.
+    fn next(&mut self) -> std::io::Result<RawEvent> {
+        self.file.seek(SeekFrom::Start(self.read_at))?;
+        let mut len = [0u8; 4];
+        self.file.read_exact(&mut len)?;
+        let len = usize::try_from(u32::from_le_bytes(len)).map_err(std::io::Error::other)?;
+        if len < NONCE_LEN {
+            return Err(std::io::Error::other("truncated spool record"));
+        }
+        let mut record = vec![0u8; len];
+        self.file.read_exact(&mut record)?;
+        self.read_at += 4 + u64::try_from(len).map_err(std::io::Error::other)?;
+        self.pending -= 1;
+
+        let (nonce, sealed) = record.split_at(NONCE_LEN);
+        let plain = Zeroizing::new(
+            self.cipher
+                .decrypt(XNonce::from_slice(nonce), sealed)
+                .map_err(|_| std::io::Error::other("spool record failed to open"))?,
+        );
+        let spilled: SpilledEvent =
+            serde_json::from_slice(&plain).map_err(std::io::Error::other)?;
+        Ok(RawEvent {
+            relay_url: RelayUrl::parse(&spilled.relay_url).map_err(std::io::Error::other)?,
+            subscription_id: SubscriptionId::new(spilled.subscription_id),
+            event: spilled.event,
+        })
+    }
+
+    /// Empties the file once everything was read back.
+    fn reset(&mut self) -> std::io::Result<()> {
+        self.pending = 0;
+        self.read_at = 0;
+        self.file.set_len(0)
+    }
+}
+
+impl Drop for SpillFile {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_file(&self.path);
+    }
+}
+
+#[derive(Default)]
+struct State {
+    memory: VecDeque<RawEvent>,
+    spill: Option<SpillFile>,
+    closed: bool,
+}
+
+impl State {
+    fn spilled(&self) -> usize {
+        self.spill.as_ref().map_or(0, |s| s.pending)
+    }
+
+    /// Moves spilled events back into memory, oldest first, until memory is
+    /// full. An unreadable record discards the rest of the file.
+    fn refill(&mut self, capacity: usize) {
+        let Some(spill) = self.spill.as_mut() else {
+            return;
+        };
+        while self.memory.len() < capacity && spill.pending > 0 {
+            match spill.next() {
+                Ok(raw) => self.memory.push_back(raw),
+                Err(e) => {
+                    log::warn!("[live_sync::spool] dropping spilled events: {e}");
+                    spill.pending = 0;
+                }
+            }
+        }
+        if spill.pending == 0 {
+            if let Err(e) = spill.reset() {
+                log::warn!("[live_sync::spool] failed to empty spill file: {e}");
+                self.spill = None;
+            }
+        }
+    }
+}
+
+/// A FIFO of relay events, bounded in memory, spilling the overflow to an
+/// encrypted file. One producer (the receiver) and one consumer (the worker).
+pub struct EventSpool {
+    state: Mutex<State>,
+    ready: Notify,
+    memory_capacity: usize,
+    max_spilled: usize,
+    spill_dir: Option<PathBuf>,
+}
+
+impl EventSpool {
+    /// Creates a spool holding up to `memory_capacity` events in memory and
+    /// up to `max_spilled` more in a file under `spill_dir` (`None` never
+    /// spills). The first spool of the process removes spill files a
+    /// previous process left in `spill_dir`.
+    #[must_use]
+    pub fn new(memory_capacity: usize, max_spilled: usize, spill_dir: Option<PathBuf>) -> Self {
+        if let Some(dir) = &spill_dir {
+            SWEEP.call_once(|| sweep_stale_spill_files(dir));
+        }
+        Self {
+            state: Mutex::new(State::default()),
+            ready: Notify::new(),
+            memory_capacity: memory_capacity.max(1),
+            max_spilled,
+            spill_dir,
+        }
+    }
+
+    /// Queues `raw` without blocking on the consumer.
+    pub fn push(&self, raw: RawEvent) -> SpoolPush {
+        let outcome = {
+            let mut state = self.state.lock().unwrap_or_else(PoisonError::into_inner);
+            if state.closed {
+                return SpoolPush::Dropped;
+            }
+            // Once anything is spilled, later events queue behind it on disk
+            // so order is kept.
+            if state.spilled() == 0 && state.memory.len() < self.memory_capacity {
+                state.memory.push_back(raw);
+                SpoolPush::Queued
+            } else if state.spilled() < self.max_spilled {
+                self.spill(&mut state, &raw)
+            } else {
+                SpoolPush::Dropped
+            }
+        };
+        if outcome != SpoolPush::Dropped {
+            self.ready.notify_one();
+        }
+        outcome
+    }
+
+    fn spill(&self, state: &mut State, raw: &RawEvent) -> SpoolPush {
+        let Some(dir) = &self.spill_dir else {
+            return SpoolPush::Dropped;
+        };
+        if state.spill.is_none() {
+            match SpillFile::create(dir) {
+                Ok(file) => state.spill = Some(file),
+                Err(e) => {
+                    log::warn!("[live_sync::spool] cannot create spill file: {e}");
+                    return SpoolPush::Dropped;
+                }
+            }
+        }
+        let Some(spill) = state.spill.as_mut() else {
+            return SpoolPush::Dropped;
+        };
+        match spill.append(raw) {
+            Ok(()) => SpoolPush::Spilled,
+            Err(e) => {
+                log::warn!("[live_sync::spool] spill write failed: {e}");
+                SpoolPush::Dropped
+            }
+        }
+    }
+
+    /// The next event, oldest first, waiting for one to arrive. `None` once
+    /// the spool is closed and drained.
+    pub async fn pop(&self) -> Option<RawEvent> {
+        loop {
+            {
+                let mut state = self.state.lock().unwrap_or_else(PoisonError::into_inner);
+                if state.memory.len() <= self.memory_capacity / 2 {
+                    state.refill(self.memory_capacity);
+                }
+                if let Some(raw) = state.memory.pop_front() {
+                    return Some(raw);
+                }
+                if state.closed {
+                    return None;
+                }
+            }
+            self.ready.notified().await;
+        }
+    }
+
+    /// Refuses further events, as dropping the sender of a channel does;
+    /// [`Self::pop`] still hands out what is queued, then returns `None`.
+    pub fn close(&self) {
+        self.state
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .closed = true;
+        self.ready.notify_one();
+    }
+
+    /// Events queued in memory and on disk.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        let state = self.state.lock().unwrap_or_else(PoisonError::into_inner);
+        state.memory.len() + state.spilled()
+    }
+
+    /// Whether no events are queued.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl std::fmt::Debug for EventSpool {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("EventSpool")
+            .field("queued", &self.len())
+            .field("memory_capacity", &self.memory_capacity)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Removes spill files a crashed process left behind; their key died with it.
+fn sweep_stale_spill_files(dir: &Path) {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return;
+    };
+    for entry in entries.flatten() {
+        if entry
+            .file_name()
+            .to_str()
+            .is_some_and(|name| name.starts_with(SPILL_FILE_PREFIX))
+        {
+            let _ = std::fs::remove_file(entry.path());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use nostr::{EventBuilder, Keys, Kind};
+    use tempfile::TempDir;
+
+    fn raw(content: &str) -> RawEvent {
+        RawEvent {
+            relay_url: RelayUrl::parse("wss://relay.example.com").unwrap(),
+            subscription_id: SubscriptionId::new("sub"),
+            event: EventBuilder::new(Kind::Custom(445), content)
+                .sign_with_keys(&Keys::generate())
+                .unwrap(),
+        }
+    }
+
+    fn spill_files(dir: &Path) -> Vec<PathBuf> {
+        std::fs::read_dir(dir)
+            .map(|entries| entries.flatten().map(|e| e.path()).collect())
+            .unwrap_or_default()
+    }
+
+    #[tokio::test]
+    async fn overflow_spills_encrypted_and_drains_in_order() {
+        let dir = TempDir::new().unwrap();
+        let spool = EventSpool::new(4, 100, Some(dir.path().to_path_buf()));
+        let outcomes: Vec<SpoolPush> = (0..20)
+            .map(|i| spool.push(raw(&format!("SPOOL_SENTINEL_{i:02}"))))
+            .collect();
+        assert_eq!(outcomes[..4], [SpoolPush::Queued; 4]);
+        assert!(outcomes[4..].iter().all(|o| *o == SpoolPush::Spilled));
+        assert_eq!(spool.len(), 20);
+
+        let files = spill_files(dir.path());
+        assert_eq!(files.len(), 1);
+        let bytes = std::fs::read(&files[0]).unwrap();
+        assert!(!bytes.windows(14).any(|w| w == b"SPOOL_SENTINEL"));
+
+        // A push while draining still lands behind the spilled events.
+        let first = spool.pop().await.unwrap();
+        assert_eq!(first.event.content, "SPOOL_SENTINEL_00");
+        assert_eq!(spool.push(raw("SPOOL_SENTINEL_20")), SpoolPush::Spilled);
+        for i in 1..=20 {
+            let next = spool.pop().await.unwrap();
+            assert_eq!(next.event.content, format!("SPOOL_SENTINEL_{i:02}"));
+            assert_eq!(next.relay_url.as_str(), "wss://relay.example.com");
+        }
+        assert!(spool.is_empty());
+
+        // Drained: new events go to memory again.
+        assert_eq!(spool.push(raw("after")), SpoolPush::Queued);
+    }
+
+    #[tokio::test]
+    async fn full_or_closed_spool_drops_and_cleans_up() {
+        let dir = TempDir::new().unwrap();
+        std::fs::write(dir.path().join("spool-stale.bin"), b"old").unwrap();
+        sweep_stale_spill_files(dir.path());
+        assert!(spill_files(dir.path()).is_empty(), "stale file swept");
+        let spool = EventSpool::new(1, 2, Some(dir.path().to_path_buf()));
+
+        let outcomes: Vec<SpoolPush> = (0..4).map(|i| spool.push(raw(&i.to_string()))).collect();
+        assert_eq!(
+            outcomes,
+            [
+                SpoolPush::Queued,
+                SpoolPush::Spilled,
+                SpoolPush::Spilled,
+                SpoolPush::Dropped
+            ]
+        );
+
+        spool.close();
+        assert_eq!(spool.push(raw("late")), SpoolPush::Dropped);
+        for expected in ["0", "1", "2"] {
+            assert_eq!(spool.pop().await.unwrap().event.content, expected);
+        }
+        assert!(spool.pop().await.is_none());
+        drop(spool);
+        assert!(spill_files(dir.path()).is_empty());
+
+        let memory_only = EventSpool::new(1, 100, None);
+        assert_eq!(memory_only.push(raw("a")), SpoolPush::Queued);
+        assert_eq!(memory_only.push(raw("b")), SpoolPush::Dropped);
+    }
+}
@@ -9,9 +9,10 @@
 //! kill the receive path forever. Instead [`run_receiver`] consumes
 //! `client.notifications()` directly, treats `Lagged` as `continue` (the cursor
 //! and catch-up replay anything skipped) and only `Closed`/`Shutdown` as a stop.
-//! It also **decouples** receive from ingest: it only `try_send`s onto a bounded
-//! channel so the notification consumer never blocks, while a separate
-//! [`run_worker`] drains that channel and awaits the engine ingest.
+//! It also **decouples** receive from ingest: it only pushes onto an
+//! [`EventSpool`] (bounded in memory, spilling to disk) so the notification
+//! consumer never blocks, while a separate [`run_worker`] drains the spool and
+//! awaits the engine ingest.
 //!
 //! # Write serialization (Rule 14)
 //!
@@ -26,12 +27,14 @@ use std::sync::Arc;
 use nostr::{Event, RelayUrl, SubscriptionId};
 use nostr_sdk::RelayPoolNotification;
 use tokio::sync::broadcast::error::RecvError;
-use tokio::sync::{broadcast, mpsc, RwLock};
+use tokio::sync::{broadcast, RwLock};
 
 use super::event::SyncStatusReason;
 use super::planes::PlaneKind;
 use super::processor::EngineProcessor;
 use super::router::Router;
+use super::spool::EventSpool;
+use crate::relay::metrics;
 
 /// One routed relay event handed from the receiver to the worker.
 #[derive(Debug, Clone)]
@@ -95,12 +98,13 @@ pub fn canonical_group_hex(nostr_group_id: &[u8]) -> String {
     hex::encode(nostr_group_id)
 }
 
-/// The RAW notifications receiver: forwards first-seen events onto `tx`, never
-/// blocking on decrypt, surviving `Lagged`, stopping only on `Closed`/`Shutdown`
-/// or the explicit `shutdown` flag.
+/// The RAW notifications receiver: forwards first-seen events onto `spool`,
+/// never blocking on decrypt, surviving `Lagged`, stopping only on
+/// `Closed`/`Shutdown` or the explicit `shutdown` flag. Closes the spool on
+/// exit, so the worker stops once it has drained it.
 pub async fn run_receiver(
     mut notifications: broadcast::Receiver<RelayPoolNotification>,
-    tx: mpsc::Sender<RawEvent>,
+    spool: Arc<EventSpool>,
     shutdown: Arc<AtomicBool>,
 ) {
     loop {
@@ -116,10 +120,11 @@ pub async fn run_receiver(
                         event,
                     } = n
                     {
-                        // try_send (never await) so the notification consumer
-                        // cannot lag the pool; a full channel drops to cursor
-                        // replay, never to a wedged receiver.
-                        let _ = tx.try_send(RawEvent {
+                        metrics::record_received(Some(relay_url.as_str()), &event);
+                        // Never awaits, so the notification consumer cannot
+                        // lag the pool; a full spool drops to cursor replay,
+                        // never to a wedged receiver.
+                        let _ = spool.push(RawEvent {
                             relay_url,
                             subscription_id,
                             event: *event,
@@ -136,10 +141,11 @@ pub async fn run_receiver(
             Err(RecvError::Closed) => break,
         }
     }
+    spool.close();
 }
 
-/// The ingest worker: drains `rx`, routes each event, and awaits the engine
-/// ingest.
+/// The ingest worker: drains `spool`, routes each event, and awaits the
+/// engine ingest.
 ///
 /// All MLS writes serialize through the one process-global session mutex
 /// (Rule 14), so no per-circle gate is needed. Panic isolation runs the ingest
@@ -147,11 +153,11 @@ pub async fn run_receiver(
 /// catch-up replay anything skipped), so one adversarial event can never blind
 /// the whole receive path.
 pub async fn run_worker(
-    mut rx: mpsc::Receiver<RawEvent>,
+    spool: Arc<EventSpool>,
     router: Arc<RwLock<Router>>,
     processor: Arc<EngineProcessor>,
 ) {
-    while let Some(raw) = rx.recv().await {
+    while let Some(raw) = spool.pop().await {
         // Resolve the subscription context (cloned so the router lock is not held
         // across the ingest).
         let ctx = {
@@ -292,9 +298,9 @@ mod supervisor_isolation_tests {
     };
     use nostr_sdk::RelayPoolNotification;
     use tempfile::TempDir;
-    use tokio::sync::{broadcast, mpsc, RwLock};
+    use tokio::sync::{broadcast, RwLock};
 
-    use super::{run_receiver, run_worker, RawEvent};
+    use super::{run_receiver, run_worker, EventSpool, RawEvent};
     use crate::circle::CircleManager;
     use crate::relay::live_sync::{
         EngineProcessor, EventBus, LiveSyncEvent, Router, SyncStatusReason,
@@ -338,8 +344,12 @@ mod supervisor_isolation_tests {
             &HashSet::from([group_hex.clone()]),
         );
 
-        let (tx, worker_rx) = mpsc::channel::<RawEvent>(16);
-        tokio::spawn(run_worker(worker_rx, Arc::clone(&router), processor));
+        let spool = Arc::new(EventSpool::new(16, 0, None));
+        tokio::spawn(run_worker(
+            Arc::clone(&spool),
+            Arc::clone(&router),
+            processor,
+        ));
 
         let raw = |content: &str| RawEvent {
             relay_url: RelayUrl::parse(&relay).unwrap(),
@@ -356,8 +366,8 @@ mod supervisor_isolation_tests {
         // `Ok(Stale)` — NOT an error — so an undecryptable event no longer emits a
         // Status; the panic seam is the stable, engine-independent proof of
         // continued draining. (Scary panic messages on stderr are expected.)
-        tx.send(raw("__panic_for_test__")).await.unwrap();
-        tx.send(raw("__panic_for_test__")).await.unwrap();
+        spool.push(raw("__panic_for_test__"));
+        spool.push(raw("__panic_for_test__"));
 
         let mut seen = 0usize;
         while let Ok(Ok(ev)) = tokio::time::timeout(Duration::from_secs(3), rx.recv()).await {
@@ -388,7 +398,7 @@ mod supervisor_isolation_tests {
     #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
     async fn run_receiver_survives_lagged_then_stops_on_closed() {
         let (btx, brx) = broadcast::channel::<RelayPoolNotification>(4);
-        let (mtx, mut mrx) = mpsc::channel::<RawEvent>(64);
+        let spool = Arc::new(EventSpool::new(64, 0, None));
         let shutdown = Arc::new(AtomicBool::new(false));
 
         let notif = |content: &str| {
@@ -409,7 +419,7 @@ mod supervisor_isolation_tests {
             let (_, n) = notif(&format!("junk{i}"));
             btx.send(n).unwrap();
         }
-        let handle = tokio::spawn(run_receiver(brx, mtx, Arc::clone(&shutdown)));
+        let handle = tokio::spawn(run_receiver(brx, Arc::clone(&spool), Arc::clone(&shutdown)));
 
         // A distinctive event AFTER the lag.
         let (marker_id, marker) = notif("MARKER");
@@ -417,7 +427,7 @@ mod supervisor_isolation_tests {
 
         // The receiver must swallow Lagged and still forward the post-lag marker.
         let mut forwarded = false;
-        while let Ok(Some(raw)) = tokio::time::timeout(Duration::from_secs(2), mrx.recv()).await {
+        while let Ok(Some(raw)) = tokio::time::timeout(Duration::from_secs(2), spool.pop()).await {
             if raw.event.id == marker_id {
                 forwarded = true;
                 break;
@@ -48,7 +48,7 @@
 //!
 //! [`RelayManager`]: crate::relay::RelayManager
 
-use nostr::{EventBuilder, Keys, Kind, Tag};
+use nostr::{EventBuilder, Kind, Tag};
 use rand::rngs::OsRng;
 use rand::RngCore;
 
@@ -57,6 +57,7 @@ use base64::Engine as _;
 
 use cgka_traits::engine::KeyPackage;
 
+use crate::nostr::identity::{sign_event, Signer};
 use crate::nostr::mls::SessionManager;
 use crate::relay::publishers::{build_unpublish_event, PublisherError, PublisherResult};
 
@@ -324,7 +325,11 @@ fn mint_d() -> String {
 /// base64 content; NO `encoding` tag; NO `relays` tag. Unlike the adapter's
 /// transport-agnostic unsigned event, Haven signs with the identity key (30443
 /// is identity-signed, W1).
-fn build_key_package_event(keys: &Keys, kp_bytes: &[u8], d: &str) -> PublisherResult<nostr::Event> {
+fn build_key_package_event(
+    signer: &dyn Signer,
+    kp_bytes: &[u8],
+    d: &str,
+) -> PublisherResult<nostr::Event> {
     if d.is_empty() {
         return Err(PublisherError::Build(
             "key package d must not be empty".into(),
@@ -346,12 +351,14 @@ fn build_key_package_event(keys: &Keys, kp_bytes: &[u8], d: &str) -> PublisherRe
         values_tag(APP_COMPONENTS_TAG, &APP_COMPONENTS)?,
     ];
 
-    EventBuilder::new(
-        Kind::Custom(KIND_MARMOT_KEY_PACKAGE),
-        BASE64.encode(kp_bytes),
+    sign_event(
+        EventBuilder::new(
+            Kind::Custom(KIND_MARMOT_KEY_PACKAGE),
+            BASE64.encode(kp_bytes),
+        )
+        .tags(tags),
+        signer,
     )
-    .tags(tags)
-    .sign_with_keys(keys)
     .map_err(|e| PublisherError::Build(format!("sign key package: {e}")))
 }
 
@@ -383,7 +390,7 @@ fn values_tag(name: &str, values: &[&str]) -> PublisherResult<Tag> {
 /// fails (inner detail redacted from `Display`).
 pub async fn build_kp_maintenance_events(
     session: &SessionManager,
-    keys: &Keys,
+    signer: &dyn Signer,
     own_kp_relays: &[String],
     existing_d: Option<&str>,
 ) -> PublisherResult<KpMaintenanceEvents> {
@@ -392,7 +399,7 @@ pub async fn build_kp_maintenance_events(
         .await
         .map_err(|e| PublisherError::Build(format!("mint key package: {e}")))?;
     let d_tag = existing_d.map_or_else(mint_d, str::to_owned);
-    let event = build_key_package_event(keys, key_package.bytes(), &d_tag)?;
+    let event = build_key_package_event(signer, key_package.bytes(), &d_tag)?;
     Ok(KpMaintenanceEvents {
         event,
         key_package,
@@ -414,12 +421,12 @@ pub async fn build_kp_maintenance_events(
 ///
 /// Returns [`PublisherError::Build`] if metadata derivation or signing fails.
 pub fn build_kp_maintenance_events_reusing(
-    keys: &Keys,
+    signer: &dyn Signer,
     cached_kp_bytes: &[u8],
     own_kp_relays: &[String],
     d: &str,
 ) -> PublisherResult<KpMaintenanceEvents> {
-    let event = build_key_package_event(keys, cached_kp_bytes, d)?;
+    let event = build_key_package_event(signer, cached_kp_bytes, d)?;
     Ok(KpMaintenanceEvents {
         event,
         key_package: KeyPackage::new(cached_kp_bytes.to_vec()),
@@ -434,7 +441,7 @@ pub fn build_kp_maintenance_events_reusing(
 ///
 /// The retired 443 is a NON-addressable regular event with no stable slot, so a
 /// stale twin must be scrubbed explicitly. This refuses unless the event author
-/// is the user themselves (`author == keys.public_key()`, the self-authorship
+/// is the user themselves (`author == signer.public_key()`, the self-authorship
 /// guard) — we never author a deletion of someone else's event.
 ///
 /// The deletion references the 443 **by event id only** (a single `e` tag) and
@@ -450,13 +457,13 @@ pub fn build_kp_maintenance_events_reusing(
 /// Returns [`PublisherError::Build`] if the self-authorship guard fails, the
 /// event id is malformed, or signing fails.
 pub fn build_legacy_key_package_retraction(
-    keys: &Keys,
+    signer: &dyn Signer,
     legacy_event_id_hex: &str,
     event_author_hex: &str,
 ) -> PublisherResult<nostr::Event> {
     // Self-authorship guard: never author a deletion of an event we did not
     // sign. Compared as lowercase hex to defeat case skew.
-    let own_hex = keys.public_key().to_hex();
+    let own_hex = signer.public_key().to_hex();
     if !event_author_hex.eq_ignore_ascii_case(&own_hex) {
         return Err(PublisherError::Build(
             "refusing to delete an event authored by another key".to_owned(),
@@ -469,8 +476,7 @@ pub fn build_legacy_key_package_retraction(
     // Id-only (`e`-tag) deletion — NO `a`-coordinate for the non-addressable
     // 443.
     let request = nostr::nips::nip09::EventDeletionRequest::new().ids(vec![event_id]);
-    EventBuilder::delete(request)
-        .sign_with_keys(keys)
+    sign_event(EventBuilder::delete(request), signer)
         .map_err(|e| PublisherError::Build(format!("sign deletion: {e}")))
 }
 
@@ -494,11 +500,11 @@ pub fn build_legacy_key_package_retraction(
 ///
 /// Returns [`PublisherError::Build`] if signing fails.
 pub fn build_key_package_relay_list_retraction(
-    keys: &Keys,
+    signer: &dyn Signer,
     last_published_at: Option<i64>,
 ) -> PublisherResult<nostr::Event> {
     build_unpublish_event(
-        keys,
+        signer,
         crate::circle::relay_prefs::RelayType::KeyPackage,
         last_published_at,
     )
@@ -559,6 +565,7 @@ impl KpMaintenanceOutcome {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use nostr::Keys;
 
     fn entry(d: &str, id: &str) -> RelayKpEntry {
         RelayKpEntry {
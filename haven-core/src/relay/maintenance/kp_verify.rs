@@ -0,0 +1,225 @@
+//! `KeyPackage` publish verification.
+//!
+//! A relay can OK-ack a kind-30443 write and still not serve it (write-only
+//! policies, async ingestion that later drops it, a paid relay that accepts
+//! but never indexes). When that happens nobody can invite the user and
+//! nothing in the app says why. This module fetches the tracked `KeyPackage`
+//! back from each of the user's `KeyPackage` relays a short while after
+//! publishing and records, per relay, whether it is actually served.
+//!
+//! The check is by event id: a relay serving a different (older or newer)
+//! package in the slot reports [`KpRelayStatus::Missing`] for the tracked one.
+//! Results are stored (the `key_package_verifications` table) so the latest
+//! verdict is readable without another network round-trip.
+
+use std::time::Duration;
+
+use nostr::{EventId, Filter};
+
+use crate::circle::{CircleManager, RelayType};
+use crate::relay::{dedup_relay_targets, RelayFetchOutcome, RelayManager};
+
+/// How long to wait after a publish before fetching it back. Long enough for
+/// a relay that ingests asynchronously to have indexed the event.
+pub const KP_VERIFY_DELAY_SECS: u64 = 10;
+
+/// Whether one relay serves the tracked `KeyPackage`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum KpRelayStatus {
+    /// The relay returned the tracked event.
+    Served,
+    /// The relay answered but did not return the tracked event.
+    Missing,
+    /// The relay did not answer.
+    Unreachable,
+}
+
+impl KpRelayStatus {
+    /// Storage representation.
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Served => "served",
+            Self::Missing => "missing",
+            Self::Unreachable => "unreachable",
+        }
+    }
+
+    /// Parses the storage representation.
+    #[must_use]
+    pub fn parse(s: &str) -> Option<Self> {
+        match s {
+            "served" => Some(Self::Served),
+            "missing" => Some(Self::Missing),
+            "unreachable" => Some(Self::Unreachable),
+            _ => None,
+        }
+    }
+}
+
+/// One relay's verdict.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct KpRelayVerification {
+    /// The relay URL.
+    pub relay_url: String,
+    /// Whether it serves the tracked `KeyPackage`.
+    pub status: KpRelayStatus,
+}
+
+/// The latest verification of the tracked `KeyPackage`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct KpVerification {
+    /// Hex id of the verified kind-30443 event.
+    pub event_id: String,
+    /// Unix timestamp of the check.
+    pub checked_at: i64,
+    /// Per-relay verdicts, in relay order.
+    pub relays: Vec<KpRelayVerification>,
+}
+
+impl KpVerification {
+    /// Number of relays serving the package.
+    #[must_use]
+    pub fn served_count(&self) -> usize {
+        self.relays
+            .iter()
+            .filter(|r| r.status == KpRelayStatus::Served)
+            .count()
+    }
+
+    /// Whether at least one relay serves the package, i.e. whether the user
+    /// can currently be invited.
+    #[must_use]
+    pub fn is_reachable(&self) -> bool {
+        self.served_count() > 0
+    }
+}
+
+/// Classifies per-relay fetches of `event_id`.
+#[must_use]
+pub fn classify_kp_fetch(
+    event_id: &EventId,
+    outcomes: &[RelayFetchOutcome],
+) -> Vec<KpRelayVerification> {
+    outcomes
+        .iter()
+        .map(|o| KpRelayVerification {
+            relay_url: o.relay_url.clone(),
+            status: if !o.responded {
+                KpRelayStatus::Unreachable
+            } else if o.events.iter().any(|e| e.id == *event_id) {
+                KpRelayStatus::Served
+            } else {
+                KpRelayStatus::Missing
+            },
+        })
+        .collect()
+}
+
+/// Waits `delay`, then fetches the tracked `KeyPackage` back from each of the
+/// user's `KeyPackage` relays and records the verdicts.
+///
+/// Returns `None` when there is nothing to verify: no package is tracked yet,
+/// the tracked row is a not-yet-minted seed, or no `KeyPackage` relays are
+/// configured.
+///
+/// # Errors
+///
+/// Returns an error if the tracking row, the relay list, or the result cannot
+/// be read or written. Relay failures are verdicts, not errors.
+pub async fn verify_key_package_publish(
+    circle_mgr: &CircleManager,
+    relay_mgr: &RelayManager,
+    delay: Duration,
+) -> crate::circle::Result<Option<KpVerification>> {
+    let Some(row) = circle_mgr.latest_published_key_package()? else {
+        return Ok(None);
+    };
+    let Ok(event_id) = EventId::from_hex(&row.event_id) else {
+        return Ok(None);
+    };
+    if row.key_package.is_empty() {
+        return Ok(None);
+    }
+    let relays = dedup_relay_targets(&circle_mgr.list_user_relays(RelayType::KeyPackage)?);
+    if relays.is_empty() {
+        return Ok(None);
+    }
+
+    tokio::time::sleep(delay).await;
+
+    let filter = Filter::new().id(event_id);
+    let outcomes = relay_mgr
+        .fetch_events_per_relay(filter, &relays)
+        .await
+        .unwrap_or_default();
+    let verification = KpVerification {
+        event_id: row.event_id,
+        checked_at: chrono::Utc::now().timestamp(),
+        relays: classify_kp_fetch(&event_id, &outcomes),
+    };
+    circle_mgr.record_key_package_verification(&verification)?;
+    Ok(Some(verification))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use nostr::{EventBuilder, Keys, Kind};
+
+    fn outcome(relay: &str, responded: bool, events: Vec<nostr::Event>) -> RelayFetchOutcome {
+        RelayFetchOutcome {
+            relay_url: relay.to_string(),
+            responded,
+            events,
+        }
+    }
+
+    #[test]
+    fn classifies_served_missing_and_unreachable() {
+        let keys = Keys::generate();
+        let tracked = EventBuilder::new(Kind::Custom(30443), "kp")
+            .sign_with_keys(&keys)
+            .unwrap();
+        let other = EventBuilder::new(Kind::Custom(30443), "older")
+            .sign_with_keys(&keys)
+            .unwrap();
+        let verdicts = classify_kp_fetch(
+            &tracked.id,
+            &[
+                outcome("wss://a", true, vec![tracked.clone()]),
+                outcome("wss://b", true, vec![other]),
+                outcome("wss://c", false, vec![]),
+            ],
+        );
+        let statuses: Vec<_> = verdicts.iter().map(|v| v.status).collect();
+        assert_eq!(
+            statuses,
+            [
+                KpRelayStatus::Served,
+                KpRelayStatus::Missing,
+                KpRelayStatus::Unreachable
+            ]
+        );
+
+        let verification = KpVerification {
+            event_id: tracked.id.to_hex(),
+            checked_at: 0,
+            relays: verdicts,
+        };
+        assert_eq!(verification.served_count(), 1);
+        assert!(verification.is_reachable());
+    }
+
+    #[test]
+    fn status_roundtrips_through_storage_form() {
+        for status in [
+            KpRelayStatus::Served,
+            KpRelayStatus::Missing,
+            KpRelayStatus::Unreachable,
+        ] {
+            assert_eq!(KpRelayStatus::parse(status.as_str()), Some(status));
+        }
+        assert_eq!(KpRelayStatus::parse("bogus"), None);
+    }
+}
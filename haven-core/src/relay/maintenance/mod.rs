@@ -15,12 +15,15 @@
 //!   unit-testable decision + event-building logic; the network probe / publish
 //!   orchestration is composed at the FFI boundary (which owns the identity
 //!   secret and the `RelayManager`).
+//! * [`kp_verify`] — fetch-back verification that each `KeyPackage` relay
+//!   actually serves the tracked package after a publish.
 //! * [`relay_list`] — B1 `RelayListMaintenance` (M8-1): republish-if-missing /
 //!   -drifted for the user's kind 10050 (inbox) + 10051 (`KeyPackage`) relay
 //!   lists. Pure decision only; the own-relays network probe + signed publish
 //!   are composed at the FFI boundary.
 
 pub mod key_package;
+pub mod kp_verify;
 pub mod relay_list;
 
 pub use key_package::{
@@ -29,6 +32,10 @@ pub use key_package::{
     decide_kp_maintenance, KpMaintenanceAction, KpMaintenanceDecision, KpMaintenanceEvents,
     KpMaintenanceOutcome, RelayKpEntry, RelayKpPerRelay, RelayKpSnapshot, KIND_MARMOT_KEY_PACKAGE,
 };
+pub use kp_verify::{
+    classify_kp_fetch, verify_key_package_publish, KpRelayStatus, KpRelayVerification,
+    KpVerification, KP_VERIFY_DELAY_SECS,
+};
 pub use relay_list::{
     decide_relay_list, list_relay_healthy, RelayListAction, RelayListCategoryOutcome,
     RelayListDecision, RelayListMaintenanceOutcome, RelayListPerRelay, RelayListSnapshot,
@@ -9,22 +9,39 @@
 
 #[cfg(debug_assertions)]
 use std::sync::OnceLock;
-use std::time::Duration;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
 
 #[cfg(debug_assertions)]
 use nostr::Url;
-use nostr::{Event, Filter, Kind, PublicKey, RelayUrl};
-use nostr_sdk::{Client, RelayPoolNotification};
+use nostr::{Event, EventId, Filter, Keys, Kind, PublicKey, RelayUrl, Timestamp};
+use nostr_sdk::Client;
 
+use super::blacklist::{is_relay_blacklisted, COMMUNITY_BLACKLIST_KIND};
 use super::discovery::discovery_relays;
 use super::error::{RelayError, RelayResult};
+use super::live_sync::planes::group::group_filter;
+use super::metrics;
+use super::paging::{page_from_outcomes, GroupMessagePage};
+use super::pool::{ConnectAction, ConnectionPool, PooledRelayHealth};
+use super::pow::{self, PowPolicy, MAX_POW_DIFFICULTY};
+use super::publish_queue::{active_publish_queue, OutboxFlush, OutboxPriority, PublishQueue};
+use super::relay_info::{self, RelayInfo};
+use super::relay_stats;
+use super::transport::{RelayTransport, SendOutcome};
 use super::types::{
-    PublishResult, RelayConnectionStatus, RelayEventCheck, RelayFetchOutcome, RelayStatus,
+    PublishQuorum, PublishResult, QuorumPublish, RelayConnectionStatus, RelayEventCheck,
+    RelayFetchOutcome, RelayStatus,
 };
 use crate::nostr::mls::redact_hex_sequences;
 
-/// Default timeout for relay operations.
-const DEFAULT_TIMEOUT: Duration = Duration::from_secs(10);
+/// Default timeout for relay operations, from the active
+/// [environment profile](crate::environment) (10 s in production).
+fn default_timeout() -> Duration {
+    crate::environment::active_environment()
+        .timing
+        .relay_timeout
+}
 
 /// Process-static opt-in for plaintext `ws://` URLs targeting loopback /
 /// emulator-host aliases. Set once via [`allow_ws_loopback_for_test`] in
@@ -67,6 +84,10 @@ const TEST_LOOPBACK_HOSTS: &[&str] = &["localhost", "127.0.0.1", "::1", "10.0.2.
 /// Timeout for waiting for relay WebSocket connections to establish.
 const CONNECTION_TIMEOUT: Duration = Duration::from_secs(5);
 
+/// How long past its own deadline a `REQ` may run before the manager gives
+/// up on the transport and reports a timeout.
+const REQ_DEADLINE_GRACE: Duration = Duration::from_secs(2);
+
 /// Maximum number of attempts (initial try + retries) for [`RelayManager::publish_event`].
 ///
 /// The first publish after a cold start — e.g. the app foregrounds and the
@@ -80,7 +101,7 @@ const CONNECTION_TIMEOUT: Duration = Duration::from_secs(5);
 /// now-established connection.
 ///
 /// Worst case (a genuinely unreachable relay): ~3 × (`CONNECTION_TIMEOUT` +
-/// `DEFAULT_TIMEOUT`) + 2 × `PUBLISH_RETRY_BACKOFF` ≈ 49 s before
+/// `default_timeout()`) + 2 × `PUBLISH_RETRY_BACKOFF` ≈ 49 s before
 /// `AllRelaysFailed` surfaces. That stays under the 72 s background publish
 /// cadence and is guarded against overlap by the caller (`_inFlightPublish` in
 /// the background isolate; `kLocationPublishOverlapGuard` in the foreground).
@@ -136,7 +157,8 @@ where
 /// Manager for Nostr relay connections.
 ///
 /// The `RelayManager` handles all communication with Nostr relays
-/// using direct WSS connections via nostr-sdk.
+/// using direct WSS connections via nostr-sdk, through a
+/// [`RelayTransport`] (see [`super::transport`]).
 ///
 /// # Example
 ///
@@ -149,16 +171,26 @@ where
 /// let result = manager.publish_event(&event, &relays).await?;
 /// ```
 pub struct RelayManager {
-    /// The nostr-sdk client.
-    client: Client,
+    /// The connection to the relays (the nostr-sdk client in production).
+    transport: Arc<dyn RelayTransport>,
+    /// Health and idle tracking for the client's relay connections.
+    pool: Arc<Mutex<ConnectionPool>>,
 }
 
 impl RelayManager {
     /// Creates a new relay manager.
     #[must_use]
     pub fn new() -> Self {
+        Self::with_transport(Arc::new(Client::builder().build()))
+    }
+
+    /// Creates a relay manager over `transport` instead of a nostr-sdk
+    /// client, such as the in-memory fake of [`super::transport`] in tests.
+    #[must_use]
+    pub fn with_transport(transport: Arc<dyn RelayTransport>) -> Self {
         Self {
-            client: Client::builder().build(),
+            transport,
+            pool: Arc::new(Mutex::new(ConnectionPool::default())),
         }
     }
 
@@ -168,51 +200,113 @@ impl RelayManager {
     /// previously-added relay in the pool, which would leak connection
     /// metadata to unrelated relay operators.
     ///
-    /// Takes the [`Client`] by reference (rather than `&self`) so the
-    /// publish retry path can drive it from a closure that owns a cheap
-    /// `Client` clone without borrowing the manager across `await` points.
-    async fn add_relays_and_connect(client: &Client, relay_urls: &[RelayUrl]) {
-        // Register relays sequentially (cheap metadata operation)
+    /// Connections are pooled: a relay whose socket is still up is reused
+    /// without a handshake, a relay backing off after failed connects is
+    /// skipped (the send then reports it as failed), and relays idle past
+    /// [`super::pool::POOL_IDLE_TIMEOUT`] are disconnected first. See
+    /// [`super::pool`].
+    ///
+    /// Takes the transport and pool by reference (rather than `&self`) so
+    /// the publish retry path can drive them from a closure that owns cheap
+    /// clones without borrowing the manager across `await` points.
+    async fn add_relays_and_connect(
+        transport: &dyn RelayTransport,
+        pool: &Mutex<ConnectionPool>,
+        relay_urls: &[RelayUrl],
+    ) {
+        Self::reap_idle(transport, pool).await;
+
+        // Register relays sequentially (cheap metadata operation; a no-op for
+        // relays already in the client)
         for url in relay_urls {
-            match client.add_relay(url.as_str()).await {
+            match transport.register(url).await {
                 Ok(newly_added) => {
                     log::debug!("[RelayManager] add_relay({url}): newly_added={newly_added}");
                 }
                 Err(e) => {
                     log::debug!(
                         "[RelayManager] add_relay({url}) failed: {}",
-                        redact_hex_sequences(&e.to_string())
+                        redact_hex_sequences(&e)
                     );
                 }
             }
         }
 
         // Connect to all relays in parallel (each has CONNECTION_TIMEOUT)
-        let connect_futures = relay_urls.iter().map(|url| async move {
-            match client
-                .try_connect_relay(url.as_str(), CONNECTION_TIMEOUT)
-                .await
-            {
+        let connect_futures = relay_urls
+            .iter()
+            .map(|url| Self::connect_pooled(transport, pool, url));
+
+        futures::future::join_all(connect_futures).await;
+    }
+
+    /// Brings one already-added relay's connection up through the pool.
+    ///
+    /// Returns whether the socket is connected afterwards.
+    async fn connect_pooled(
+        transport: &dyn RelayTransport,
+        pool: &Mutex<ConnectionPool>,
+        url: &RelayUrl,
+    ) -> bool {
+        let socket_connected = transport.is_connected(url).await;
+        let action = lock_pool(pool).plan(url.as_str(), socket_connected, Instant::now());
+        match action {
+            ConnectAction::Reuse => {
+                log::debug!("[RelayManager] reusing connection to {url}");
+                true
+            }
+            ConnectAction::BackOff => {
+                log::debug!("[RelayManager] {url} is backing off, not connecting");
+                false
+            }
+            ConnectAction::Connect => match transport.connect(url, CONNECTION_TIMEOUT).await {
                 Ok(()) => {
                     log::debug!("[RelayManager] connected to {url}");
+                    lock_pool(pool).record_connected(url.as_str(), Instant::now());
+                    true
                 }
                 Err(e) => {
                     log::debug!(
                         "[RelayManager] failed to connect to {url}: {}",
-                        redact_hex_sequences(&e.to_string())
+                        redact_hex_sequences(&e)
                     );
+                    lock_pool(pool).record_failure(url.as_str(), Instant::now());
+                    false
                 }
+            },
+        }
+    }
+
+    /// Disconnects relays the pool reports idle.
+    async fn reap_idle(transport: &dyn RelayTransport, pool: &Mutex<ConnectionPool>) {
+        let idle = lock_pool(pool).take_idle(Instant::now());
+        for url in idle {
+            let Ok(relay) = RelayUrl::parse(&url) else {
+                continue;
+            };
+            match transport.remove(&relay).await {
+                Ok(()) => log::debug!("[RelayManager] closed idle connection to {url}"),
+                Err(e) => log::debug!(
+                    "[RelayManager] closing idle {url} failed: {}",
+                    redact_hex_sequences(&e)
+                ),
             }
-        });
+        }
+    }
 
-        futures::future::join_all(connect_futures).await;
+    /// Health of the pooled relay connections (failures, backoff, idle time).
+    #[must_use]
+    pub fn pool_health(&self) -> Vec<PooledRelayHealth> {
+        lock_pool(&self.pool).health(Instant::now())
     }
 
     /// Publishes an event to the specified relays.
     ///
-    /// The event will be published to all specified relays.
-    /// Returns a [`PublishResult`] indicating which relays accepted or
-    /// rejected the event.
+    /// The event will be published to all specified relays, each under its
+    /// own deadline; a relay that misses it is listed as `failed` without
+    /// failing the publish. Returns a [`PublishResult`] indicating which
+    /// relays accepted or rejected the event. To return once some of the
+    /// relays accepted, see [`Self::publish_event_with_quorum`].
     ///
     /// # Arguments
     ///
@@ -226,9 +320,56 @@ impl RelayManager {
         &self,
         event: &Event,
         relays: &[String],
+    ) -> RelayResult<PublishResult> {
+        self.publish_event_with_pow(event, relays, None).await
+    }
+
+    /// Requests deletion (NIP-09, kind 5) of `event_ids` from `relays`.
+    ///
+    /// Builds an id-only deletion signed with `keys` (see
+    /// [`super::build_event_deletion`]) and publishes it. Relays only honor a
+    /// deletion signed by the key that authored the targets, and honoring it
+    /// at all is voluntary: a relay that acks may still serve the events, and
+    /// anyone who already fetched them keeps them.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`RelayError::InvalidEvent`] if `event_ids` is empty or signing
+    /// fails, and otherwise the errors of [`Self::publish_event`].
+    pub async fn delete_events(
+        &self,
+        keys: &Keys,
+        event_ids: &[EventId],
+        reason: Option<&str>,
+        relays: &[String],
+    ) -> RelayResult<PublishResult> {
+        let deletion = super::build_event_deletion(keys, event_ids, reason)
+            .map_err(|e| RelayError::InvalidEvent(e.to_string()))?;
+        self.publish_event(&deletion, relays).await
+    }
+
+    /// Publishes an event, adding NIP-13 proof of work under `pow`.
+    ///
+    /// With a [`PowPolicy`] the event is mined to its `target_difficulty`
+    /// before the first send, and re-mined (then re-sent to every relay) when
+    /// a relay rejects it with a `pow:` reason. The returned
+    /// [`PublishResult::event_id`] is the id of the event actually accepted,
+    /// which differs from `event.id` once work was added. A relay demanding
+    /// more than [`super::MAX_POW_DIFFICULTY`] keeps its rejection. Without a
+    /// policy this is exactly [`Self::publish_event`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if all relays reject the event, connection fails, or
+    /// up-front mining fails ([`RelayError::ProofOfWork`]).
+    pub async fn publish_event_with_pow(
+        &self,
+        event: &Event,
+        relays: &[String],
+        pow: Option<&PowPolicy>,
     ) -> RelayResult<PublishResult> {
         // Validate relay URLs (must be wss://)
-        let relay_urls = Self::validate_relay_urls(relays)?;
+        let relay_urls = Self::allowed_relay_urls(relays)?;
 
         log::debug!(
             "[RelayManager] publish_event: sending kind {} to {} relays",
@@ -236,95 +377,293 @@ impl RelayManager {
             relay_urls.len()
         );
 
+        let mut event = event.clone();
+        if let Some(policy) = pow {
+            if let Some(target) = policy.target_difficulty {
+                event = Self::mine_off_thread(event, target, policy.keys.clone()).await?;
+            }
+        }
+
         // Retry the connect+send a bounded number of times so the first
         // publish after a cold start (foreground resume / fresh process)
         // is not silently dropped when the WebSocket handshake loses the
         // race against the per-event OK ack. Each attempt owns cheap clones
-        // (the `Client` is internally `Arc`-backed) so the retry closure
+        // of the `Arc`-held transport and pool so the retry closure
         // does not borrow `self` across `await` points. Republishing the
-        // same event id is idempotent — relays dedupe by id.
-        let client = self.client.clone();
-        publish_with_retry(
+        // same event id is idempotent — relays dedupe by id. A mined event
+        // replaces `current` so later attempts do not mine again.
+        let transport = Arc::clone(&self.transport);
+        let connection_pool = Arc::clone(&self.pool);
+        let current = Arc::new(Mutex::new(event));
+        let policy = pow.cloned();
+        let result = publish_with_retry(
             MAX_PUBLISH_ATTEMPTS,
             PUBLISH_RETRY_BACKOFF,
             move |attempt| {
-                let client = client.clone();
+                let transport = Arc::clone(&transport);
+                let connection_pool = Arc::clone(&connection_pool);
                 let relay_urls = relay_urls.clone();
-                let event = event.clone();
+                let current = Arc::clone(&current);
+                let policy = policy.clone();
                 async move {
                     if attempt > 0 {
                         log::debug!("[RelayManager] publish_event: retry attempt {attempt}");
                     }
-                    Self::try_publish_once(&client, &relay_urls, &event).await
+                    let event = current
+                        .lock()
+                        .unwrap_or_else(std::sync::PoisonError::into_inner)
+                        .clone();
+                    let result =
+                        Self::try_publish_once(&transport, &connection_pool, &relay_urls, &event)
+                            .await?;
+                    let Some(policy) = policy else {
+                        return Ok(result);
+                    };
+                    let Some(required) = pow::demanded_difficulty(&result.rejected_by) else {
+                        return Ok(result);
+                    };
+                    if required <= pow::difficulty(&event.id) || required > MAX_POW_DIFFICULTY {
+                        log::debug!(
+                            "[RelayManager] publish_event: relay demands pow {required}, not mining"
+                        );
+                        return Ok(result);
+                    }
+                    log::debug!("[RelayManager] publish_event: mining pow {required}");
+                    let mined = Self::mine_off_thread(event, required, policy.keys).await?;
+                    *current
+                        .lock()
+                        .unwrap_or_else(std::sync::PoisonError::into_inner) = mined.clone();
+                    Self::try_publish_once(&transport, &connection_pool, &relay_urls, &mined).await
                 }
             },
         )
-        .await
+        .await;
+        Self::kick_outbox(&self.transport, &self.pool);
+        result
+    }
+
+    /// Publishes an event, keeping it in the offline outbox if no relay
+    /// accepts it.
+    ///
+    /// Same as [`Self::publish_event`], except that when every relay fails
+    /// (transport error, timeout, or no acknowledgement) and an outbox is
+    /// installed ([`super::install_publish_queue`]), the signed event is
+    /// persisted and re-sent later (see [`super::publish_queue`]). The error
+    /// is still returned: this send did not land.
+    ///
+    /// `priority` decides the retry order and how long the event is worth
+    /// delivering (see [`OutboxPriority`]).
+    ///
+    /// Only for events whose late arrival is harmless, such as location
+    /// updates. MLS commits and welcomes are rolled back when their publish
+    /// fails and must never be queued.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if all relays reject the event or connection fails.
+    pub async fn publish_event_queued(
+        &self,
+        event: &Event,
+        relays: &[String],
+        priority: OutboxPriority,
+    ) -> RelayResult<PublishResult> {
+        let result = self.publish_event(event, relays).await;
+        if let Err(
+            e @ (RelayError::AllRelaysFailed
+            | RelayError::Timeout(_)
+            | RelayError::Publish(_)
+            | RelayError::Connection { .. }),
+        ) = &result
+        {
+            if let Some(queue) = active_publish_queue() {
+                let now = chrono::Utc::now().timestamp();
+                match queue.enqueue(event, relays, priority, &e.to_string(), now) {
+                    Ok(queued) => {
+                        log::debug!("[RelayManager] publish_event_queued: queued={queued}");
+                    }
+                    Err(err) => log::warn!(
+                        "[RelayManager] publish_event_queued: could not queue: {}",
+                        redact_hex_sequences(&err.to_string())
+                    ),
+                }
+            }
+        }
+        result
+    }
+
+    /// Re-sends every pending outbox event now, without waiting for its
+    /// backoff. Use when the platform reports connectivity is back.
+    ///
+    /// Returns empty counts when no outbox is installed or a flush is
+    /// already running.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`RelayError::Publish`] if the outbox cannot be read or
+    /// updated.
+    pub async fn retry_outbox(&self) -> RelayResult<OutboxFlush> {
+        let Some(queue) = active_publish_queue() else {
+            return Ok(OutboxFlush::default());
+        };
+        lock_pool(&self.pool).take_reconnected();
+        Self::flush_outbox(&queue, &*self.transport, &self.pool, true)
+            .await
+            .map_err(|e| RelayError::Publish(redact_hex_sequences(&e.to_string())))
+    }
+
+    /// Drains the offline outbox in the background: every pending event when
+    /// a relay was freshly connected since the last drain (the network is
+    /// back), otherwise only events whose backoff has passed. A no-op without
+    /// an installed outbox or while a drain is running.
+    fn kick_outbox(transport: &Arc<dyn RelayTransport>, pool: &Arc<Mutex<ConnectionPool>>) {
+        let Some(queue) = active_publish_queue() else {
+            return;
+        };
+        if queue.is_flushing() {
+            return;
+        }
+        let reconnected = lock_pool(pool).take_reconnected();
+        let transport = Arc::clone(transport);
+        let pool = Arc::clone(pool);
+        tokio::spawn(async move {
+            match Self::flush_outbox(&queue, &transport, &pool, reconnected).await {
+                Ok(outcome) if outcome != OutboxFlush::default() => {
+                    log::debug!("[RelayManager] outbox drained: {outcome:?}");
+                }
+                Ok(_) => {}
+                Err(e) => log::warn!(
+                    "[RelayManager] outbox drain failed: {}",
+                    redact_hex_sequences(&e.to_string())
+                ),
+            }
+        });
+    }
+
+    /// Re-sends due outbox events, one connect-and-publish attempt each.
+    async fn flush_outbox(
+        queue: &PublishQueue,
+        transport: &Arc<dyn RelayTransport>,
+        pool: &Mutex<ConnectionPool>,
+        reconnected: bool,
+    ) -> crate::circle::Result<OutboxFlush> {
+        let now = chrono::Utc::now().timestamp();
+        queue
+            .flush(now, reconnected, |event, relays| async move {
+                let relay_urls = Self::allowed_relay_urls(&relays).map_err(|e| e.to_string())?;
+                let result = Self::try_publish_once(transport, pool, &relay_urls, &event)
+                    .await
+                    .map_err(|e| e.to_string())?;
+                if result.is_success() {
+                    Ok(())
+                } else {
+                    Err(RelayError::AllRelaysFailed.to_string())
+                }
+            })
+            .await
+    }
+
+    /// Runs [`pow::mine_event`] on the blocking pool.
+    async fn mine_off_thread(event: Event, difficulty: u8, keys: Keys) -> RelayResult<Event> {
+        tokio::task::spawn_blocking(move || pow::mine_event(&event, difficulty, &keys))
+            .await
+            .map_err(|_| RelayError::ProofOfWork("mining task failed".to_string()))?
     }
 
-    /// Performs a single connect-and-publish attempt.
+    /// Performs a single connect-and-publish attempt, waiting for every
+    /// relay to answer or miss its own deadline.
     ///
     /// Returns `Ok` with a [`PublishResult`] that may be unsuccessful
     /// (empty `accepted_by`) when the relays were reached but none
     /// acknowledged in time; the caller's retry loop treats that the same
-    /// as a transport error. Returns `Err` on a publish timeout or a
+    /// as a transport error. Returns `Err` when no relay answered at all:
+    /// [`RelayError::Timeout`] if any missed its deadline, otherwise the
     /// transport-level send error.
     async fn try_publish_once(
-        client: &Client,
+        transport: &Arc<dyn RelayTransport>,
+        pool: &Mutex<ConnectionPool>,
         relay_urls: &[RelayUrl],
         event: &Event,
     ) -> RelayResult<PublishResult> {
-        // Add relays, connect, and wait for WebSocket handshakes.
-        Self::add_relays_and_connect(client, relay_urls).await;
+        // Add relays, then reuse or (re)connect each pooled connection.
+        Self::add_relays_and_connect(&**transport, pool, relay_urls).await;
 
-        let send_result = tokio::time::timeout(
-            DEFAULT_TIMEOUT,
-            client.send_event_to(relay_urls.iter().map(RelayUrl::as_str), event),
-        )
-        .await
-        .map_err(|_| {
-            log::warn!(
-                "[RelayManager] publish_event: timed out after {}s",
-                DEFAULT_TIMEOUT.as_secs()
-            );
-            RelayError::Timeout("Event publish timed out".to_string())
-        })?
-        .map_err(|e| {
-            log::debug!(
-                "[RelayManager] publish_event: send_event error: {}",
-                redact_hex_sequences(&e.to_string())
-            );
-            RelayError::Publish(e.to_string())
-        })?;
+        let tally = PublishFanOut::start(transport, relay_urls, event)
+            .finish()
+            .await;
         log::debug!(
             "[RelayManager] publish_event: success={}, failed={}",
-            send_result.success.len(),
-            send_result.failed.len()
+            tally.accepted.len(),
+            tally.rejected.len() + tally.timed_out.len() + tally.errors.len()
         );
-        for (url, err) in &send_result.failed {
-            log::debug!(
-                "[RelayManager] publish_event: relay {url} failed: {}",
-                redact_hex_sequences(err)
-            );
+        if tally.accepted.is_empty() && tally.rejected.is_empty() {
+            if !tally.timed_out.is_empty() {
+                log::warn!(
+                    "[RelayManager] publish_event: timed out after {}s",
+                    default_timeout().as_secs()
+                );
+                return Err(RelayError::Timeout("Event publish timed out".to_string()));
+            }
+            if let Some((_, e)) = tally.errors.first() {
+                return Err(RelayError::Publish(e.clone()));
+            }
         }
+        Ok(tally.result())
+    }
 
-        // Build the result from Output<EventId>
-        let mut accepted_by = Vec::new();
-        let mut rejected_by = Vec::new();
+    /// Publishes an event and returns as soon as `quorum` relays accepted it.
+    ///
+    /// Each relay gets its own deadline ([`default_timeout`]), so one slow
+    /// relay neither holds up the others nor fails the publish. The sends to
+    /// relays that have not answered when the quorum is reached carry on in
+    /// the background; [`QuorumPublish::completion`] reports how they ended.
+    /// A quorum that cannot be reached returns once every relay answered,
+    /// with [`QuorumPublish::quorum_reached`] false.
+    ///
+    /// This is a single attempt: unlike [`Self::publish_event`] it neither
+    /// retries nor adds proof of work.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if relay URL validation fails, or
+    /// [`RelayError::AllRelaysFailed`] if every relay answered and none
+    /// accepted.
+    pub async fn publish_event_with_quorum(
+        &self,
+        event: &Event,
+        relays: &[String],
+        quorum: PublishQuorum,
+    ) -> RelayResult<QuorumPublish> {
+        let relay_urls = Self::allowed_relay_urls(relays)?;
+        Self::add_relays_and_connect(&*self.transport, &self.pool, &relay_urls).await;
+
+        let required = quorum.required(relay_urls.len());
+        let mut fan_out = PublishFanOut::start(&self.transport, &relay_urls, event);
+        fan_out.until_accepted(required).await;
+        let result = fan_out.tally.result();
+        let quorum_reached = result.accepted_by.len() >= required;
+        let pending = fan_out.pending;
+        log::debug!(
+            "[RelayManager] publish_event_with_quorum: {}/{required} accepted, {pending} pending",
+            result.accepted_by.len()
+        );
 
-        for url in &send_result.success {
-            accepted_by.push(url.to_string());
+        let (done, completion) = tokio::sync::oneshot::channel();
+        if pending == 0 {
+            let _ = done.send(result.clone());
+        } else {
+            tokio::spawn(async move {
+                let _ = done.send(fan_out.finish().await.result());
+            });
         }
-
-        for (url, error) in &send_result.failed {
-            rejected_by.push((url.to_string(), error.clone()));
+        Self::kick_outbox(&self.transport, &self.pool);
+        if !result.is_success() && pending == 0 {
+            return Err(RelayError::AllRelaysFailed);
         }
-
-        Ok(PublishResult {
-            event_id: event.id,
-            accepted_by,
-            rejected_by,
-            failed: Vec::new(),
+        Ok(QuorumPublish {
+            result,
+            quorum_reached,
+            pending,
+            completion,
         })
     }
 
@@ -338,45 +677,22 @@ impl RelayManager {
     ///
     /// Returns an error only if relay URL validation fails (before spawning).
     pub fn publish_event_background(&self, event: Event, relays: &[String]) -> RelayResult<()> {
-        let relay_urls = Self::validate_relay_urls(relays)?;
-        let client = self.client.clone();
+        let relay_urls = Self::allowed_relay_urls(relays)?;
+        let transport = Arc::clone(&self.transport);
+        let pool = Arc::clone(&self.pool);
 
         tokio::spawn(async move {
-            // Register and connect
-            for url in &relay_urls {
-                let _ = client.add_relay(url.as_str()).await;
-            }
-            let connect_futures = relay_urls.iter().map(|url| async {
-                let _ = client
-                    .try_connect_relay(url.as_str(), CONNECTION_TIMEOUT)
-                    .await;
-            });
-            futures::future::join_all(connect_futures).await;
+            Self::add_relays_and_connect(&*transport, &pool, &relay_urls).await;
 
-            // Publish with timeout
-            match tokio::time::timeout(
-                DEFAULT_TIMEOUT,
-                client.send_event_to(relay_urls.iter().map(RelayUrl::as_str), &event),
-            )
-            .await
-            {
-                Ok(Ok(result)) => {
-                    log::debug!(
-                        "[RelayManager] background publish: {} accepted, {} failed",
-                        result.success.len(),
-                        result.failed.len()
-                    );
-                }
-                Ok(Err(e)) => {
-                    log::debug!(
-                        "[RelayManager] background publish error: {}",
-                        redact_hex_sequences(&e.to_string())
-                    );
-                }
-                Err(_) => {
-                    log::debug!("[RelayManager] background publish timed out");
-                }
-            }
+            let tally = PublishFanOut::start(&transport, &relay_urls, &event)
+                .finish()
+                .await;
+            log::debug!(
+                "[RelayManager] background publish: {} accepted, {} failed",
+                tally.accepted.len(),
+                tally.rejected.len() + tally.timed_out.len() + tally.errors.len()
+            );
+            Self::kick_outbox(&transport, &pool);
         });
 
         Ok(())
@@ -399,56 +715,37 @@ impl RelayManager {
         filters: Vec<Filter>,
         relays: &[String],
     ) -> RelayResult<tokio::sync::mpsc::Receiver<Event>> {
-        let relay_urls = Self::validate_relay_urls(relays)?;
+        let relay_urls = Self::allowed_relay_urls(relays)?;
 
         // Add relays, connect, and wait for WebSocket handshakes
-        Self::add_relays_and_connect(&self.client, &relay_urls).await;
+        Self::add_relays_and_connect(&*self.transport, &self.pool, &relay_urls).await;
+        Self::kick_outbox(&self.transport, &self.pool);
+
+        // A live subscription keeps its connections open however long the
+        // relays go without a publish.
+        {
+            let mut pool = lock_pool(&self.pool);
+            let now = Instant::now();
+            for url in &relay_urls {
+                pool.pin(url.as_str(), now);
+            }
+        }
 
         // Create a channel for events
         let (tx, rx) = tokio::sync::mpsc::channel(100);
 
         // Subscribe to each filter individually
         for filter in filters {
-            let subscription_output = self
-                .client
-                .subscribe_to(relay_urls.iter().map(RelayUrl::as_str), filter, None)
+            self.transport
+                .subscribe(&relay_urls, filter, tx.clone())
                 .await
                 .map_err(|e| {
                     log::debug!(
                         "[RelayManager] subscribe_to error: {}",
-                        redact_hex_sequences(&e.to_string())
+                        redact_hex_sequences(&e)
                     );
-                    RelayError::Subscription(e.to_string())
+                    RelayError::Subscription(e)
                 })?;
-
-            // Spawn event handling task for this subscription
-            let client_clone = self.client.clone();
-            let tx_clone = tx.clone();
-            let subscription_id = subscription_output.val;
-
-            tokio::spawn(async move {
-                let _ = client_clone
-                    .handle_notifications(|notification| async {
-                        if let RelayPoolNotification::Event {
-                            subscription_id: sid,
-                            event,
-                            ..
-                        } = notification
-                        {
-                            if sid == subscription_id
-                                && tx_clone.send((*event).clone()).await.is_err()
-                            {
-                                // Receiver dropped — exit to trigger unsubscribe
-                                return Ok(true);
-                            }
-                        }
-                        Ok(false)
-                    })
-                    .await;
-
-                // Sends NIP-01 CLOSE to relays when receiver is dropped
-                client_clone.unsubscribe(&subscription_id).await;
-            });
         }
 
         Ok(rx)
@@ -456,24 +753,20 @@ impl RelayManager {
 
     /// Gets the relay connection status for all connected relays.
     pub async fn get_relay_status(&self) -> Vec<RelayConnectionStatus> {
-        let relays = self.client.relays().await;
-        let mut statuses = Vec::new();
-
-        for (url, relay) in relays {
-            let status = if relay.is_connected() {
-                RelayStatus::Connected
-            } else {
-                RelayStatus::Disconnected
-            };
-
-            statuses.push(RelayConnectionStatus {
+        self.transport
+            .relay_states()
+            .await
+            .into_iter()
+            .map(|(url, connected)| RelayConnectionStatus {
                 url: url.to_string(),
-                status,
+                status: if connected {
+                    RelayStatus::Connected
+                } else {
+                    RelayStatus::Disconnected
+                },
                 last_seen: None,
-            });
-        }
-
-        statuses
+            })
+            .collect()
     }
 
     /// Fetches events matching the given filter from relays.
@@ -496,31 +789,45 @@ impl RelayManager {
         relays: &[String],
         timeout: Option<Duration>,
     ) -> RelayResult<Vec<Event>> {
-        let relay_urls = Self::validate_relay_urls(relays)?;
+        let relay_urls = Self::allowed_relay_urls(relays)?;
 
         // Add relays, connect, and wait for WebSocket handshakes
-        Self::add_relays_and_connect(&self.client, &relay_urls).await;
+        Self::add_relays_and_connect(&*self.transport, &self.pool, &relay_urls).await;
+        Self::kick_outbox(&self.transport, &self.pool);
 
         // Fetch events with timeout
-        let timeout_duration = timeout.unwrap_or(DEFAULT_TIMEOUT);
-
-        let fetch_result = self
-            .client
-            .fetch_events_from(
-                relay_urls.iter().map(RelayUrl::as_str),
-                filter,
-                timeout_duration,
-            )
-            .await
-            .map_err(|e| {
-                log::debug!(
-                    "[RelayManager] fetch_events error: {}",
-                    redact_hex_sequences(&e.to_string())
-                );
-                RelayError::Fetch(e.to_string())
-            })?;
+        let timeout_duration = timeout.unwrap_or_else(default_timeout);
+        Self::send_req(&*self.transport, &relay_urls, filter, timeout_duration).await
+    }
 
-        Ok(fetch_result.into_iter().collect())
+    /// Runs one `REQ` on `relay_urls` and merges the answers: each event
+    /// once, newest first. Gives up with [`RelayError::Timeout`] if the
+    /// transport overruns `timeout` by more than [`REQ_DEADLINE_GRACE`].
+    async fn send_req(
+        transport: &dyn RelayTransport,
+        relay_urls: &[RelayUrl],
+        filter: Filter,
+        timeout: Duration,
+    ) -> RelayResult<Vec<Event>> {
+        let events = tokio::time::timeout(
+            timeout + REQ_DEADLINE_GRACE,
+            transport.send_req(relay_urls, filter, timeout),
+        )
+        .await
+        .map_err(|_| {
+            log::debug!("[RelayManager] fetch_events: timed out");
+            RelayError::Timeout("Event fetch timed out".to_string())
+        })?
+        .map_err(|e| {
+            log::debug!(
+                "[RelayManager] fetch_events error: {}",
+                redact_hex_sequences(&e)
+            );
+            RelayError::Fetch(e)
+        })?;
+        let events = merge_events(events);
+        meter_fetched(relay_urls, &events);
+        Ok(events)
     }
 
     /// Extracts `wss://` relay URLs from `"relay"` tags.
@@ -723,6 +1030,53 @@ impl RelayManager {
             .await
     }
 
+    /// Fetches a user's `KeyPackage` together with the relay lists their
+    /// Welcome is delivered through.
+    ///
+    /// Fetches kinds 10051, 10050 and 10002 concurrently, then runs
+    /// [`Self::fetch_keypackage_with_cascade`]. Each relay-list fetch is
+    /// tolerated independently: a transient failure on one list is treated
+    /// as an empty list so the cascades can still fall through to later
+    /// tiers.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the pubkey is invalid or the `KeyPackage` fetch
+    /// fails. `Ok(None)` if no tier has a `KeyPackage`.
+    pub async fn fetch_member_key_package(
+        &self,
+        pubkey: &str,
+    ) -> RelayResult<Option<crate::circle::MemberKeyPackage>> {
+        let (keypackage_result, inbox_result, nip65_result) = tokio::join!(
+            self.fetch_keypackage_relays(pubkey),
+            self.fetch_inbox_relays(pubkey),
+            self.fetch_nip65_relays(pubkey),
+        );
+        let tolerate = |result: RelayResult<Vec<String>>, kind: &str| {
+            result.unwrap_or_else(|e| {
+                log::debug!(
+                    "[fetch_member_key_package] kind {kind} fetch failed: {}",
+                    redact_hex_sequences(&e.to_string())
+                );
+                Vec::new()
+            })
+        };
+        let keypackage_relays = tolerate(keypackage_result, "10051");
+        let inbox_relays = tolerate(inbox_result, "10050");
+        let nip65_relays = tolerate(nip65_result, "10002");
+
+        let event = self
+            .fetch_keypackage_with_cascade(pubkey, &keypackage_relays, &nip65_relays)
+            .await?;
+        Ok(
+            event.map(|key_package_event| crate::circle::MemberKeyPackage {
+                key_package_event,
+                inbox_relays,
+                nip65_relays,
+            }),
+        )
+    }
+
     /// Runs the `KeyPackage` discovery cascade with pre-fetched relay lists.
     ///
     /// Tiers, in order: `keypackage_relays` (kind 10051) → `nip65_relays`
@@ -846,6 +1200,31 @@ impl RelayManager {
             .max_by_key(|e| e.created_at)
     }
 
+    /// Fetches the newest community relay blacklist published by the
+    /// [pinned maintainer](super::community_blacklist_maintainer).
+    ///
+    /// Every candidate is checked with
+    /// [`verify_community_list`](super::blacklist::verify_community_list)
+    /// before selection, so a relay cannot displace the genuine list with a
+    /// newer forged one. Returns `None` when no valid list is found.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if this build pins no maintainer key, the relay URLs
+    /// are invalid, or fetching fails.
+    pub async fn fetch_community_blacklist(&self, relays: &[String]) -> RelayResult<Option<Event>> {
+        let maintainer = super::community_blacklist_maintainer()?;
+        let filter = Filter::new()
+            .kind(Kind::Custom(COMMUNITY_BLACKLIST_KIND))
+            .author(maintainer)
+            .limit(5);
+        let events = self.fetch_events(filter, relays, None).await?;
+        Ok(events
+            .into_iter()
+            .filter(|e| super::blacklist::verify_community_list(e, &maintainer).is_ok())
+            .max_by_key(|e| e.created_at))
+    }
+
     /// Checks whether events matching a filter exist on a specific relay.
     ///
     /// Queries a single relay for events matching the given filter and returns
@@ -859,27 +1238,14 @@ impl RelayManager {
         relay_url: &str,
         filter: Filter,
     ) -> RelayResult<RelayEventCheck> {
-        let relay_urls = Self::validate_relay_urls(&[relay_url.to_string()])?;
+        let relay_urls = Self::allowed_relay_urls(&[relay_url.to_string()])?;
 
         // Add relay, connect, and wait for WebSocket handshake
-        Self::add_relays_and_connect(&self.client, &relay_urls).await;
+        Self::add_relays_and_connect(&*self.transport, &self.pool, &relay_urls).await;
 
         // Fetch events from this specific relay
-        let events = self
-            .client
-            .fetch_events_from(
-                relay_urls.iter().map(nostr::RelayUrl::as_str),
-                filter,
-                DEFAULT_TIMEOUT,
-            )
-            .await
-            .map_err(|e| {
-                log::debug!(
-                    "[RelayManager] check_event_on_relay error: {}",
-                    redact_hex_sequences(&e.to_string())
-                );
-                RelayError::Fetch(e.to_string())
-            })?;
+        let events =
+            Self::send_req(&*self.transport, &relay_urls, filter, default_timeout()).await?;
 
         let event_count = events.len();
         let newest_timestamp = events
@@ -900,7 +1266,7 @@ impl RelayManager {
     ///
     /// For every relay this attempts the WebSocket handshake (bounded by
     /// [`CONNECTION_TIMEOUT`]); on success it runs a one-shot fetch (bounded by
-    /// [`DEFAULT_TIMEOUT`]) and records the events, marking the relay
+    /// [`default_timeout`]) and records the events, marking the relay
     /// `responded`. A relay whose handshake fails is marked not responded and
     /// is not queried. Relays are processed concurrently.
     ///
@@ -933,7 +1299,9 @@ impl RelayManager {
         filter: Filter,
         relays: &[String],
     ) -> RelayResult<Vec<RelayFetchOutcome>> {
-        let client = &self.client;
+        let transport = &*self.transport;
+        let pool = &*self.pool;
+        Self::reap_idle(transport, pool).await;
 
         let fetch_futures = relays.iter().map(|relay| {
             let filter = filter.clone();
@@ -942,11 +1310,13 @@ impl RelayManager {
                 // single non-responder outcome, not a whole-probe abort. The
                 // raw string is echoed back verbatim so URL-equality matching
                 // in the caller is unaffected.
-                let Ok(url) = Self::validate_single_relay_url(relay) else {
+                let Ok(url) = Self::validate_single_relay_url(relay)
+                    .and_then(|url| Self::reject_blacklisted(url, relay))
+                else {
                     // Presence-only log: no URL string at debug/error level
                     // (it may be sensitive); only that one entry was invalid.
                     log::debug!(
-                        "[RelayManager] per-relay: skipping one invalid relay url (non-responder)"
+                        "[RelayManager] per-relay: skipping one invalid or blacklisted relay url (non-responder)"
                     );
                     return RelayFetchOutcome {
                         relay_url: relay.clone(),
@@ -956,15 +1326,12 @@ impl RelayManager {
                 };
                 let relay_url = url.as_str().to_string();
 
-                // Register the relay (cheap) then attempt a bounded handshake.
-                // `try_connect_relay` returns Ok if the socket is (or becomes)
-                // connected within CONNECTION_TIMEOUT — the transport-level
-                // equivalent of the relay answering our knock.
-                let _ = client.add_relay(url.as_str()).await;
-                let responded = client
-                    .try_connect_relay(url.as_str(), CONNECTION_TIMEOUT)
-                    .await
-                    .is_ok();
+                // Register the relay (cheap) then reuse or attempt a bounded
+                // handshake. A connected socket is the transport-level
+                // equivalent of the relay answering our knock; a relay still
+                // backing off from failed connects counts as not answering.
+                let _ = transport.register(&url).await;
+                let responded = Self::connect_pooled(transport, pool, &url).await;
 
                 if !responded {
                     // Presence-only: never log the own-relay URL (may be
@@ -980,21 +1347,22 @@ impl RelayManager {
                 // Connected: one-shot fetch from just this relay. A fetch error
                 // after a successful handshake still counts as responded (the
                 // relay answered); we simply record no events for it.
-                let events = match client
-                    .fetch_events_from(std::iter::once(url.as_str()), filter, DEFAULT_TIMEOUT)
-                    .await
-                {
-                    Ok(evs) => evs.into_iter().collect(),
-                    Err(e) => {
-                        // Presence-only: no own-relay URL at debug (may be
-                        // sensitive), matching the not-responded branch above.
-                        log::debug!(
-                            "[RelayManager] per-relay fetch error (one own relay): {}",
-                            redact_hex_sequences(&e.to_string())
-                        );
-                        Vec::new()
-                    }
-                };
+                let events = Self::send_req(
+                    transport,
+                    std::slice::from_ref(&url),
+                    filter,
+                    default_timeout(),
+                )
+                .await
+                .unwrap_or_else(|e| {
+                    // Presence-only: no own-relay URL at debug (may be
+                    // sensitive), matching the not-responded branch above.
+                    log::debug!(
+                        "[RelayManager] per-relay fetch error (one own relay): {}",
+                        redact_hex_sequences(&e.to_string())
+                    );
+                    Vec::new()
+                });
 
                 RelayFetchOutcome {
                     relay_url,
@@ -1007,6 +1375,40 @@ impl RelayManager {
         Ok(futures::future::join_all(fetch_futures).await)
     }
 
+    /// Fetches one page of a circle's group messages (kind 445), newest
+    /// first: up to `limit` events per relay created at or before `until`
+    /// (now if `None`) and at or after `since`.
+    ///
+    /// Pass the returned [`GroupMessagePage::next_until`] as the next
+    /// `until` to walk further back; see [`super::paging`] for how pages
+    /// across several relays are stitched. Unreachable relays are counted,
+    /// not raised.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`RelayError::InvalidEvent`] for a zero `limit`.
+    pub async fn fetch_group_messages_paged(
+        &self,
+        nostr_group_id: &[u8; 32],
+        relays: &[String],
+        until: Option<i64>,
+        since: Option<i64>,
+        limit: usize,
+    ) -> RelayResult<GroupMessagePage> {
+        if limit == 0 {
+            return Err(RelayError::InvalidEvent(
+                "page limit must be positive".to_string(),
+            ));
+        }
+        let mut filter =
+            group_filter(&[hex::encode(nostr_group_id)], since.unwrap_or(0)).limit(limit);
+        if let Some(until) = until {
+            filter = filter.until(Timestamp::from(u64::try_from(until).unwrap_or(0)));
+        }
+        let outcomes = self.fetch_events_per_relay(filter, relays).await?;
+        Ok(page_from_outcomes(outcomes, until, limit))
+    }
+
     /// Validates relay URLs and ensures they use wss://.
     ///
     /// Plaintext `ws://` is rejected unless the debug-only
@@ -1024,6 +1426,44 @@ impl RelayManager {
         Ok(urls)
     }
 
+    /// Validates relay URLs like [`validate_relay_urls`](Self::validate_relay_urls)
+    /// and drops every URL on the active relay blacklist (see
+    /// [`super::blacklist`]) before anything is added to the pool.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`RelayError::InvalidUrl`] for any invalid URL, and
+    /// [`RelayError::Blacklisted`] when a non-empty list is blacklisted in
+    /// its entirety (so callers never silently "succeed" against zero relays).
+    fn allowed_relay_urls(relays: &[String]) -> RelayResult<Vec<RelayUrl>> {
+        let urls = Self::validate_relay_urls(relays)?;
+        let total = urls.len();
+        let allowed: Vec<RelayUrl> = urls
+            .into_iter()
+            .filter(|url| !is_relay_blacklisted(url.as_str()))
+            .collect();
+        if allowed.len() < total {
+            log::debug!(
+                "[RelayManager] skipped {} blacklisted relay(s)",
+                total - allowed.len()
+            );
+        }
+        if total > 0 && allowed.is_empty() {
+            return Err(RelayError::Blacklisted(
+                "all requested relays are blacklisted".to_string(),
+            ));
+        }
+        Ok(allowed)
+    }
+
+    /// Per-relay blacklist gate for the fault-isolated paths.
+    fn reject_blacklisted(url: RelayUrl, raw: &str) -> RelayResult<RelayUrl> {
+        if is_relay_blacklisted(url.as_str()) {
+            return Err(RelayError::Blacklisted(raw.to_string()));
+        }
+        Ok(url)
+    }
+
     /// Validates ONE relay URL, enforcing the `wss://`-only policy (with the
     /// debug-only loopback opt-in).
     ///
@@ -1095,7 +1535,8 @@ impl RelayManager {
 
     /// Disconnects from all relays.
     pub async fn shutdown(&self) {
-        self.client.disconnect().await;
+        self.transport.disconnect_all().await;
+        lock_pool(&self.pool).clear();
     }
 
     /// Removes a relay from the connection pool and tears down its WebSocket.
@@ -1118,17 +1559,48 @@ impl RelayManager {
     pub async fn remove_relay(&self, url: &str) -> RelayResult<()> {
         // Defense in depth: validate before passing to nostr-sdk so that
         // operators reading logs cannot see surprising URL strings.
-        let _ = Self::validate_relay_urls(&[url.to_string()])?;
-        if let Err(e) = self.client.remove_relay(url).await {
-            log::debug!(
-                "[RelayManager] remove_relay({url}) failed: {}",
-                redact_hex_sequences(&e.to_string())
-            );
-        } else {
-            log::debug!("[RelayManager] remove_relay({url}) ok");
+        for relay_url in Self::validate_relay_urls(&[url.to_string()])? {
+            lock_pool(&self.pool).forget(relay_url.as_str());
+            if let Err(e) = self.transport.remove(&relay_url).await {
+                log::debug!(
+                    "[RelayManager] remove_relay({url}) failed: {}",
+                    redact_hex_sequences(&e)
+                );
+            } else {
+                log::debug!("[RelayManager] remove_relay({url}) ok");
+            }
         }
         Ok(())
     }
+
+    /// Per-relay health counters — publish outcomes, latency, last error and
+    /// bytes — accumulated by every `RelayManager` in the process, by URL.
+    ///
+    /// Persisted across restarts once a store is installed (see
+    /// [`super::relay_stats`]).
+    #[must_use]
+    pub fn relay_metrics() -> Vec<super::RelayStats> {
+        relay_stats::relay_metrics()
+    }
+
+    /// Fetches `url`'s NIP-11 information document.
+    ///
+    /// Goes over HTTPS, not the relay pool (see [`super::relay_info`]); the
+    /// caller persists the result with `CircleStorage::save_relay_info`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`RelayError::InvalidUrl`] for a non-`wss://` URL,
+    /// [`RelayError::Blacklisted`] for a blacklisted relay,
+    /// [`RelayError::Timeout`] if the relay does not answer in time, and
+    /// [`RelayError::Fetch`] for any other failure or a malformed document.
+    pub async fn fetch_relay_info(&self, url: &str) -> RelayResult<RelayInfo> {
+        let relay = Self::validate_single_relay_url(url)?;
+        if is_relay_blacklisted(relay.as_str()) {
+            return Err(RelayError::Blacklisted(relay.to_string()));
+        }
+        relay_info::fetch_relay_info(relay.as_str()).await
+    }
 }
 
 impl Default for RelayManager {
@@ -1137,6 +1609,193 @@ impl Default for RelayManager {
     }
 }
 
+/// One relay's answer to a fanned-out publish: its outcome, or `None` when it
+/// missed its deadline, and how long it took in milliseconds.
+type RelayAnswer = (RelayUrl, Option<Result<SendOutcome, String>>, u64);
+
+/// The answers a fanned-out publish has gathered so far, already metered
+/// and recorded in the relay stats.
+#[derive(Debug)]
+struct PublishTally {
+    event_id: EventId,
+    accepted: Vec<String>,
+    rejected: Vec<(String, String)>,
+    timed_out: Vec<String>,
+    errors: Vec<(String, String)>,
+}
+
+impl PublishTally {
+    fn add(&mut self, (url, answer, latency_ms): RelayAnswer, event: &Event) {
+        match answer {
+            Some(Ok(outcome)) => {
+                for (url, err) in &outcome.rejected {
+                    log::debug!(
+                        "[RelayManager] publish_event: relay {url} failed: {}",
+                        redact_hex_sequences(err)
+                    );
+                }
+                let (accepted, rejected) = record_outcome(&outcome, event, latency_ms);
+                self.accepted.extend(accepted);
+                self.rejected.extend(rejected);
+            }
+            Some(Err(e)) => {
+                log::debug!(
+                    "[RelayManager] publish_event: send_event error: {}",
+                    redact_hex_sequences(&e)
+                );
+                relay_stats::record_publish_error(&[url.to_string()], &e);
+                self.errors.push((url.to_string(), e));
+            }
+            None => {
+                relay_stats::record_publish_error(&[url.to_string()], "timed out");
+                self.timed_out.push(url.to_string());
+            }
+        }
+    }
+
+    /// The tally as a [`PublishResult`]; relays that timed out or hit a
+    /// transport error are `failed`.
+    fn result(&self) -> PublishResult {
+        PublishResult {
+            event_id: self.event_id,
+            accepted_by: self.accepted.clone(),
+            rejected_by: self.rejected.clone(),
+            failed: self
+                .timed_out
+                .iter()
+                .cloned()
+                .chain(self.errors.iter().map(|(url, _)| url.clone()))
+                .collect(),
+        }
+    }
+}
+
+/// A publish sent to each relay separately, each under its own deadline, so
+/// the answers can be read as they arrive.
+struct PublishFanOut {
+    event: Event,
+    answers: tokio::sync::mpsc::UnboundedReceiver<RelayAnswer>,
+    pending: usize,
+    tally: PublishTally,
+}
+
+impl PublishFanOut {
+    /// Spawns one send per relay.
+    fn start(transport: &Arc<dyn RelayTransport>, relay_urls: &[RelayUrl], event: &Event) -> Self {
+        let (tx, answers) = tokio::sync::mpsc::unbounded_channel();
+        let deadline = default_timeout();
+        for url in relay_urls {
+            let (transport, url, event, tx) = (
+                Arc::clone(transport),
+                url.clone(),
+                event.clone(),
+                tx.clone(),
+            );
+            tokio::spawn(async move {
+                let started = Instant::now();
+                let answer = tokio::time::timeout(
+                    deadline,
+                    transport.publish(std::slice::from_ref(&url), &event),
+                )
+                .await
+                .ok();
+                let latency_ms = u64::try_from(started.elapsed().as_millis()).unwrap_or(u64::MAX);
+                let _ = tx.send((url, answer, latency_ms));
+            });
+        }
+        Self {
+            event: event.clone(),
+            answers,
+            pending: relay_urls.len(),
+            tally: PublishTally {
+                event_id: event.id,
+                accepted: Vec::new(),
+                rejected: Vec::new(),
+                timed_out: Vec::new(),
+                errors: Vec::new(),
+            },
+        }
+    }
+
+    /// Reads answers until `required` relays accepted or none is pending.
+    async fn until_accepted(&mut self, required: usize) {
+        while self.pending > 0 && self.tally.accepted.len() < required {
+            let Some(answer) = self.answers.recv().await else {
+                break;
+            };
+            self.pending -= 1;
+            self.tally.add(answer, &self.event);
+        }
+    }
+
+    /// Reads every remaining answer.
+    async fn finish(mut self) -> PublishTally {
+        self.until_accepted(usize::MAX).await;
+        self.tally
+    }
+}
+
+/// Counts `event` as sent to each of `relays` (see [`super::metrics`]).
+fn meter_sent<'a>(relays: impl IntoIterator<Item = &'a RelayUrl>, event: &Event) {
+    for url in relays {
+        metrics::record_sent(url.as_str(), event);
+    }
+}
+
+/// Meters a publish's `outcome` and records it in the relay stats. Returns
+/// the accepting relays and the rejecting ones with their reasons.
+fn record_outcome(
+    outcome: &SendOutcome,
+    event: &Event,
+    latency_ms: u64,
+) -> (Vec<String>, Vec<(String, String)>) {
+    meter_sent(
+        outcome
+            .accepted
+            .iter()
+            .chain(outcome.rejected.iter().map(|(url, _)| url)),
+        event,
+    );
+    let accepted: Vec<String> = outcome.accepted.iter().map(ToString::to_string).collect();
+    let rejected: Vec<(String, String)> = outcome
+        .rejected
+        .iter()
+        .map(|(url, reason)| (url.to_string(), reason.clone()))
+        .collect();
+    relay_stats::record_publish(&accepted, &rejected, latency_ms);
+    (accepted, rejected)
+}
+
+/// Merges the answers of several relays: each event once, newest first.
+fn merge_events(events: Vec<Event>) -> Vec<Event> {
+    let mut seen = std::collections::HashSet::new();
+    let mut merged: Vec<Event> = events
+        .into_iter()
+        .filter(|event| seen.insert(event.id))
+        .collect();
+    merged.sort_by(|a, b| b.created_at.cmp(&a.created_at).then(a.id.cmp(&b.id)));
+    merged
+}
+
+/// Counts fetched `events` as received. A fetch from one relay is attributed
+/// to it; nostr-sdk merges a multi-relay fetch without reporting the source.
+fn meter_fetched(relay_urls: &[RelayUrl], events: &[Event]) {
+    let relay = match relay_urls {
+        [only] => Some(only.as_str()),
+        _ => None,
+    };
+    for event in events {
+        metrics::record_received(relay, event);
+    }
+}
+
+/// Locks the connection pool, recovering from poisoning (the pool is plain
+/// bookkeeping; a panicked holder cannot leave it unsafe to read).
+fn lock_pool(pool: &Mutex<ConnectionPool>) -> std::sync::MutexGuard<'_, ConnectionPool> {
+    pool.lock()
+        .unwrap_or_else(std::sync::PoisonError::into_inner)
+}
+
 /// Opt in to plaintext `ws://` URLs targeting loopback / emulator-host
 /// aliases for hermetic E2E tests.
 ///
@@ -1277,6 +1936,34 @@ mod tests {
         assert!(matches!(result, Err(RelayError::InvalidUrl(_))));
     }
 
+    #[test]
+    fn allowed_relay_urls_drops_blacklisted_relays() {
+        // The active blacklist is process-wide; these hostnames are used by
+        // no other test in the binary.
+        crate::relay::install_relay_blacklist(crate::relay::RelayBlacklist::new(
+            false,
+            [],
+            [(
+                "wss://blocked.blacklist-test.example".to_string(),
+                crate::relay::LocalRelayOverride::Block,
+            )],
+        ));
+
+        let mixed = vec![
+            "wss://blocked.blacklist-test.example".to_string(),
+            "wss://ok.blacklist-test.example".to_string(),
+        ];
+        let allowed = RelayManager::allowed_relay_urls(&mixed).unwrap();
+        assert_eq!(allowed.len(), 1);
+        assert!(allowed[0].as_str().contains("ok.blacklist-test"));
+
+        let only_blocked = vec!["wss://blocked.blacklist-test.example/".to_string()];
+        assert!(matches!(
+            RelayManager::allowed_relay_urls(&only_blocked),
+            Err(RelayError::Blacklisted(_))
+        ));
+    }
+
     // ----------------------------------------------------------------------
     // ws:// loopback test opt-in (debug-only)
     //
@@ -1484,6 +2171,28 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn unreachable_relay_backs_off_and_is_forgotten_on_remove() {
+        let manager = RelayManager::new();
+        assert!(manager.pool_health().is_empty());
+        let relays = vec!["wss://relay.invalid.example".to_string()];
+
+        let outcomes = manager
+            .fetch_events_per_relay(Filter::new().kind(Kind::GiftWrap).limit(1), &relays)
+            .await
+            .unwrap();
+        assert!(!outcomes[0].responded);
+
+        let health = manager.pool_health();
+        assert_eq!(health.len(), 1);
+        assert!(!health[0].connected);
+        assert_eq!(health[0].consecutive_failures, 1);
+        assert!(health[0].retry_in.is_some(), "a failed connect backs off");
+
+        manager.remove_relay(&relays[0]).await.unwrap();
+        assert!(manager.pool_health().is_empty());
+    }
+
     #[test]
     fn new_creates_manager() {
         let manager = RelayManager::new();
@@ -1602,6 +2311,286 @@ mod tests {
         assert_eq!(calls.get(), 1, "zero attempts clamps to a single try");
     }
 
+    // ----------------------------------------------------------------------
+    // RelayManager over the in-memory transport
+    //
+    // Paused time lets the retry backoff and the publish/REQ deadlines run
+    // instantly.
+    // ----------------------------------------------------------------------
+
+    const RELAY_A: &str = "wss://a.transport.test";
+    const RELAY_B: &str = "wss://b.transport.test";
+    const RELAY_C: &str = "wss://c.transport.test";
+
+    fn fake_manager() -> (Arc<crate::relay::MemoryTransport>, RelayManager) {
+        let transport = Arc::new(crate::relay::MemoryTransport::new());
+        let manager = RelayManager::with_transport(transport.clone());
+        (transport, manager)
+    }
+
+    fn note(keys: &Keys, created_at: u64) -> Event {
+        nostr::EventBuilder::text_note(format!("note {created_at}"))
+            .custom_created_at(Timestamp::from(created_at))
+            .sign_with_keys(keys)
+            .unwrap()
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn publish_retries_the_same_event_until_a_relay_acknowledges() {
+        let (transport, manager) = fake_manager();
+        transport.leave_unacknowledged(RELAY_A, 2);
+        let event = note(&Keys::generate(), 100);
+
+        let result = manager
+            .publish_event(&event, &[RELAY_A.to_string()])
+            .await
+            .unwrap();
+        assert!(result.is_success());
+        assert_eq!(result.event_id, event.id);
+        let sent = transport.published(RELAY_A);
+        assert_eq!(sent.len(), 3);
+        assert!(sent.iter().all(|e| e.id == event.id));
+        assert_eq!(transport.connects(RELAY_A), 1, "the connection is reused");
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn publish_gives_up_after_the_attempt_budget() {
+        let (transport, manager) = fake_manager();
+        transport.set_rejection(RELAY_A, Some("blocked: no thanks"));
+        let event = note(&Keys::generate(), 100);
+
+        let result = manager.publish_event(&event, &[RELAY_A.to_string()]).await;
+        assert!(matches!(result, Err(RelayError::AllRelaysFailed)));
+        assert_eq!(
+            u32::try_from(transport.published(RELAY_A).len()).unwrap(),
+            MAX_PUBLISH_ATTEMPTS
+        );
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn publish_succeeds_when_any_relay_acknowledges() {
+        let (transport, manager) = fake_manager();
+        transport.set_unreachable(RELAY_B, true);
+        let event = note(&Keys::generate(), 100);
+
+        let result = manager
+            .publish_event(&event, &[RELAY_A.to_string(), RELAY_B.to_string()])
+            .await
+            .unwrap();
+        assert_eq!(result.accepted_by.len(), 1);
+        assert_eq!(result.rejected_by.len(), 1);
+        assert!(transport.published(RELAY_B).is_empty());
+        let health = manager.pool_health();
+        assert!(health.iter().any(|h| h.consecutive_failures == 1));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn publish_to_a_stalled_relay_times_out() {
+        let (transport, manager) = fake_manager();
+        transport.set_stalled(RELAY_A, true);
+        let event = note(&Keys::generate(), 100);
+
+        let result = manager.publish_event(&event, &[RELAY_A.to_string()]).await;
+        assert!(matches!(result, Err(RelayError::Timeout(_))));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn a_stalled_relay_does_not_hold_up_the_others() {
+        let (transport, manager) = fake_manager();
+        transport.set_stalled(RELAY_B, true);
+        let event = note(&Keys::generate(), 100);
+
+        let result = manager
+            .publish_event(&event, &[RELAY_A.to_string(), RELAY_B.to_string()])
+            .await
+            .unwrap();
+        assert_eq!(result.accepted_by.len(), 1);
+        assert_eq!(
+            result.failed.len(),
+            1,
+            "the stalled relay missed its deadline"
+        );
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn quorum_publish_returns_early_and_reports_the_rest_later() {
+        let (transport, manager) = fake_manager();
+        transport.set_stalled(RELAY_B, true);
+        transport.set_rejection(RELAY_C, Some("blocked: no thanks"));
+        let relays = [
+            RELAY_A.to_string(),
+            RELAY_B.to_string(),
+            RELAY_C.to_string(),
+        ];
+        let event = note(&Keys::generate(), 100);
+
+        let started = tokio::time::Instant::now();
+        let publish = manager
+            .publish_event_with_quorum(&event, &relays, PublishQuorum::Any)
+            .await
+            .unwrap();
+        assert!(started.elapsed() < default_timeout(), "did not wait for B");
+        assert!(publish.quorum_reached);
+        assert_eq!(publish.result.accepted_by.len(), 1);
+        assert!(publish.pending >= 1);
+
+        let done = publish.completion.await.unwrap();
+        assert_eq!(done.accepted_by.len(), 1);
+        assert_eq!(done.rejected_by.len(), 1);
+        assert_eq!(done.failed.len(), 1);
+        assert_eq!(done.total_attempted(), 3);
+
+        let majority = manager
+            .publish_event_with_quorum(&event, &relays, PublishQuorum::Majority)
+            .await
+            .unwrap();
+        assert!(!majority.quorum_reached, "only one of three accepts");
+        assert_eq!(majority.pending, 0);
+
+        transport.set_rejection(RELAY_A, Some("blocked: no thanks"));
+        transport.set_stalled(RELAY_B, false);
+        transport.set_rejection(RELAY_B, Some("blocked: no thanks"));
+        assert!(matches!(
+            manager
+                .publish_event_with_quorum(&event, &relays, PublishQuorum::Any)
+                .await,
+            Err(RelayError::AllRelaysFailed)
+        ));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn fetch_merges_answers_from_several_relays() {
+        let (transport, manager) = fake_manager();
+        let keys = Keys::generate();
+        let (old, shared, new) = (note(&keys, 100), note(&keys, 200), note(&keys, 300));
+        transport.store(RELAY_A, old.clone());
+        transport.store(RELAY_A, shared.clone());
+        transport.store(RELAY_B, shared.clone());
+        transport.store(RELAY_B, new.clone());
+        transport.store(RELAY_B, note(&Keys::generate(), 250));
+
+        let events = manager
+            .fetch_events(
+                Filter::new().author(keys.public_key()).kind(Kind::TextNote),
+                &[RELAY_A.to_string(), RELAY_B.to_string()],
+                None,
+            )
+            .await
+            .unwrap();
+        let ids: Vec<EventId> = events.iter().map(|e| e.id).collect();
+        assert_eq!(ids, vec![new.id, shared.id, old.id]);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn fetch_returns_what_arrived_by_the_deadline() {
+        let (transport, manager) = fake_manager();
+        let keys = Keys::generate();
+        let event = note(&keys, 100);
+        transport.store(RELAY_A, event.clone());
+        transport.set_stalled(RELAY_B, true);
+
+        let started = tokio::time::Instant::now();
+        let events = manager
+            .fetch_events(
+                Filter::new().author(keys.public_key()),
+                &[RELAY_A.to_string(), RELAY_B.to_string()],
+                Some(Duration::from_secs(3)),
+            )
+            .await
+            .unwrap();
+        assert_eq!(events, vec![event]);
+        let waited = started.elapsed();
+        assert!(waited >= Duration::from_secs(3) && waited < Duration::from_secs(4));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn per_relay_fetch_reports_each_relay() {
+        let (transport, manager) = fake_manager();
+        let keys = Keys::generate();
+        transport.store(RELAY_A, note(&keys, 100));
+        transport.set_unreachable(RELAY_C, true);
+
+        let outcomes = manager
+            .fetch_events_per_relay(
+                Filter::new().author(keys.public_key()),
+                &[
+                    RELAY_A.to_string(),
+                    RELAY_B.to_string(),
+                    RELAY_C.to_string(),
+                ],
+            )
+            .await
+            .unwrap();
+        let summary: Vec<(bool, usize)> = outcomes
+            .iter()
+            .map(|o| (o.responded, o.events.len()))
+            .collect();
+        assert_eq!(summary, vec![(true, 1), (true, 0), (false, 0)]);
+
+        // The unreachable relay backs off instead of being dialled again.
+        let _ = manager
+            .fetch_events_per_relay(Filter::new(), &[RELAY_C.to_string()])
+            .await
+            .unwrap();
+        assert_eq!(transport.connects(RELAY_C), 1);
+        assert_eq!(
+            manager
+                .pool_health()
+                .iter()
+                .map(|h| h.consecutive_failures)
+                .max(),
+            Some(1)
+        );
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn dropped_connections_are_reopened() {
+        let (transport, manager) = fake_manager();
+        let relays = [RELAY_A.to_string()];
+        manager
+            .fetch_events(Filter::new(), &relays, None)
+            .await
+            .unwrap();
+        manager
+            .fetch_events(Filter::new(), &relays, None)
+            .await
+            .unwrap();
+        assert_eq!(transport.connects(RELAY_A), 1);
+
+        transport.drop_connection(RELAY_A);
+        manager
+            .fetch_events(Filter::new(), &relays, None)
+            .await
+            .unwrap();
+        assert_eq!(transport.connects(RELAY_A), 2);
+        assert_eq!(
+            manager.get_relay_status().await[0].status,
+            RelayStatus::Connected
+        );
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn subscriptions_forward_matching_events_until_dropped() {
+        let (transport, manager) = fake_manager();
+        let keys = Keys::generate();
+        let mut events = manager
+            .subscribe(
+                vec![Filter::new().author(keys.public_key())],
+                &[RELAY_A.to_string()],
+            )
+            .await
+            .unwrap();
+
+        let event = note(&keys, 100);
+        assert_eq!(transport.deliver(RELAY_A, &event), 1);
+        assert_eq!(transport.deliver(RELAY_A, &note(&Keys::generate(), 100)), 0);
+        assert_eq!(events.recv().await, Some(event.clone()));
+
+        drop(events);
+        assert_eq!(transport.deliver(RELAY_A, &event), 0);
+        assert_eq!(transport.subscriptions(RELAY_A), 0);
+    }
+
     // ------------------------------------------------------------------
     // NIP-65 relay tag parsing tests
     // ------------------------------------------------------------------
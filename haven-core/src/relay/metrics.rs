@@ -0,0 +1,299 @@
+//! Bandwidth accounting per relay and per circle.
+//!
+//! Users on metered connections (or paying the latency and capacity cost of
+//! routing through a system-wide Tor proxy) need to see what Haven spends.
+//! Every event a [`RelayManager`](super::RelayManager) or the live-sync
+//! receiver sends or receives is counted here, against the relay it went to or
+//! came from and against the circle it belongs to (its `h` tag).
+//!
+//! Sizes are the serialized event JSON, counted once per relay: the bytes an
+//! event occupies on each socket, minus the small NIP-01 envelope. An event
+//! fetched from several relays at once, where nostr-sdk does not report the
+//! source, is counted under no relay ([`BandwidthSummary::unattributed`]) but
+//! still against its circle.
+//!
+//! # Privacy
+//!
+//! Counters live in process memory only: they are never written to disk and
+//! never leave the device. They reset when the process restarts or on
+//! [`reset_bandwidth_usage`]. Per-relay byte totals are also fed to the
+//! persisted [relay health counters](super::relay_stats), which carry no
+//! circle attribution.
+
+use std::collections::HashMap;
+use std::sync::{LazyLock, Mutex};
+
+use nostr::{Event, JsonUtil};
+
+use super::live_sync::supervisor::extract_group_id_hex;
+
+/// Events and bytes in each direction.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Usage {
+    /// Events sent.
+    pub events_sent: u64,
+    /// Bytes sent.
+    pub bytes_sent: u64,
+    /// Events received.
+    pub events_received: u64,
+    /// Bytes received.
+    pub bytes_received: u64,
+}
+
+impl Usage {
+    fn add_sent(&mut self, bytes: u64) {
+        self.events_sent = self.events_sent.saturating_add(1);
+        self.bytes_sent = self.bytes_sent.saturating_add(bytes);
+    }
+
+    fn add_received(&mut self, bytes: u64) {
+        self.events_received = self.events_received.saturating_add(1);
+        self.bytes_received = self.bytes_received.saturating_add(bytes);
+    }
+
+    /// Bytes in both directions.
+    #[must_use]
+    pub const fn total_bytes(&self) -> u64 {
+        self.bytes_sent.saturating_add(self.bytes_received)
+    }
+}
+
+/// Usage of one relay.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RelayUsage {
+    /// Relay URL.
+    pub url: String,
+    /// Traffic with this relay.
+    pub usage: Usage,
+}
+
+/// Usage of one circle.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CircleUsage {
+    /// The circle's Nostr group id (hex, the `h` tag value).
+    pub nostr_group_id: String,
+    /// Traffic carrying this circle's events, across all relays.
+    pub usage: Usage,
+}
+
+/// A snapshot of the counters.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct BandwidthSummary {
+    /// Unix timestamp the counters started (process start or last reset).
+    pub since: i64,
+    /// All traffic.
+    pub total: Usage,
+    /// Traffic per relay, heaviest first.
+    pub relays: Vec<RelayUsage>,
+    /// Received traffic whose relay is unknown.
+    pub unattributed: Usage,
+    /// Traffic per circle, heaviest first. Events without an `h` tag (key
+    /// packages, gift wraps, profiles) count toward no circle.
+    pub circles: Vec<CircleUsage>,
+}
+
+/// Bandwidth counters.
+#[derive(Debug, Clone, Default)]
+pub struct BandwidthMeter {
+    since: i64,
+    total: Usage,
+    relays: HashMap<String, Usage>,
+    unattributed: Usage,
+    circles: HashMap<String, Usage>,
+}
+
+impl BandwidthMeter {
+    /// Creates empty counters starting at `since`.
+    #[must_use]
+    pub fn new(since: i64) -> Self {
+        Self {
+            since,
+            ..Self::default()
+        }
+    }
+
+    /// Counts `event` as sent to `relay`.
+    pub fn record_sent(&mut self, relay: &str, event: &Event) {
+        let bytes = event_bytes(event);
+        self.total.add_sent(bytes);
+        self.relays
+            .entry(relay.to_string())
+            .or_default()
+            .add_sent(bytes);
+        if let Some(circle) = circle_of(event) {
+            self.circles.entry(circle).or_default().add_sent(bytes);
+        }
+    }
+
+    /// Counts `event` as received from `relay`, if known.
+    pub fn record_received(&mut self, relay: Option<&str>, event: &Event) {
+        let bytes = event_bytes(event);
+        self.total.add_received(bytes);
+        match relay {
+            Some(relay) => self
+                .relays
+                .entry(relay.to_string())
+                .or_default()
+                .add_received(bytes),
+            None => self.unattributed.add_received(bytes),
+        }
+        if let Some(circle) = circle_of(event) {
+            self.circles.entry(circle).or_default().add_received(bytes);
+        }
+    }
+
+    /// Snapshot of the counters.
+    #[must_use]
+    pub fn summary(&self) -> BandwidthSummary {
+        let mut relays: Vec<RelayUsage> = self
+            .relays
+            .iter()
+            .map(|(url, usage)| RelayUsage {
+                url: url.clone(),
+                usage: *usage,
+            })
+            .collect();
+        relays.sort_by(|a, b| {
+            b.usage
+                .total_bytes()
+                .cmp(&a.usage.total_bytes())
+                .then_with(|| a.url.cmp(&b.url))
+        });
+        let mut circles: Vec<CircleUsage> = self
+            .circles
+            .iter()
+            .map(|(id, usage)| CircleUsage {
+                nostr_group_id: id.clone(),
+                usage: *usage,
+            })
+            .collect();
+        circles.sort_by(|a, b| {
+            b.usage
+                .total_bytes()
+                .cmp(&a.usage.total_bytes())
+                .then_with(|| a.nostr_group_id.cmp(&b.nostr_group_id))
+        });
+        BandwidthSummary {
+            since: self.since,
+            total: self.total,
+            relays,
+            unattributed: self.unattributed,
+            circles,
+        }
+    }
+}
+
+/// Serialized size of `event`, in bytes.
+fn event_bytes(event: &Event) -> u64 {
+    u64::try_from(event.as_json().len()).unwrap_or(u64::MAX)
+}
+
+/// The circle `event` belongs to: its `h` tag value, lowercased so a relay
+/// echoing an uppercase tag does not split the circle's counters.
+fn circle_of(event: &Event) -> Option<String> {
+    extract_group_id_hex(event).map(|id| id.to_ascii_lowercase())
+}
+
+/// Process-wide counters fed by every relay path.
+static METER: LazyLock<Mutex<BandwidthMeter>> =
+    LazyLock::new(|| Mutex::new(BandwidthMeter::new(chrono::Utc::now().timestamp())));
+
+fn meter() -> std::sync::MutexGuard<'static, BandwidthMeter> {
+    // Counters are plain integers; a poisoned lock leaves them consistent.
+    METER
+        .lock()
+        .unwrap_or_else(std::sync::PoisonError::into_inner)
+}
+
+/// Counts `event` as sent to `relay` in the process-wide counters.
+pub fn record_sent(relay: &str, event: &Event) {
+    meter().record_sent(relay, event);
+    super::relay_stats::record_bytes_sent(relay, event_bytes(event));
+}
+
+/// Counts `event` as received from `relay` (if known) in the process-wide
+/// counters.
+pub fn record_received(relay: Option<&str>, event: &Event) {
+    meter().record_received(relay, event);
+    if let Some(relay) = relay {
+        super::relay_stats::record_bytes_received(relay, event_bytes(event));
+    }
+}
+
+/// Snapshot of the process-wide counters.
+#[must_use]
+pub fn bandwidth_usage() -> BandwidthSummary {
+    meter().summary()
+}
+
+/// Zeroes the process-wide counters, starting a new period now.
+pub fn reset_bandwidth_usage() {
+    *meter() = BandwidthMeter::new(chrono::Utc::now().timestamp());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use nostr::{Alphabet, EventBuilder, Keys, Kind, SingleLetterTag, Tag, TagKind};
+
+    const GROUP: &str = "ab12cd";
+
+    fn group_event(content: &str) -> Event {
+        EventBuilder::new(Kind::Custom(445), content)
+            .tag(Tag::custom(
+                TagKind::SingleLetter(SingleLetterTag::lowercase(Alphabet::H)),
+                [GROUP.to_uppercase()],
+            ))
+            .sign_with_keys(&Keys::generate())
+            .unwrap()
+    }
+
+    #[test]
+    fn counts_per_relay_and_per_circle() {
+        let mut meter = BandwidthMeter::new(100);
+        let event = group_event("ciphertext");
+        let size = event_bytes(&event);
+        meter.record_sent("wss://a.example.com", &event);
+        meter.record_sent("wss://b.example.com", &event);
+        meter.record_received(Some("wss://a.example.com"), &event);
+
+        let summary = meter.summary();
+        assert_eq!(summary.since, 100);
+        assert_eq!(summary.total.events_sent, 2);
+        assert_eq!(summary.total.bytes_sent, 2 * size);
+        assert_eq!(summary.total.bytes_received, size);
+        assert_eq!(summary.relays[0].url, "wss://a.example.com");
+        assert_eq!(summary.relays[0].usage.total_bytes(), 2 * size);
+        assert_eq!(summary.relays[1].usage.events_received, 0);
+        assert_eq!(summary.circles.len(), 1);
+        assert_eq!(summary.circles[0].nostr_group_id, GROUP);
+        assert_eq!(summary.circles[0].usage.events_sent, 2);
+        assert_eq!(summary.circles[0].usage.events_received, 1);
+    }
+
+    #[test]
+    fn unknown_relay_and_untagged_events_are_kept_apart() {
+        let mut meter = BandwidthMeter::new(0);
+        let untagged = EventBuilder::new(Kind::TextNote, "untagged")
+            .sign_with_keys(&Keys::generate())
+            .unwrap();
+        meter.record_received(None, &untagged);
+        meter.record_received(None, &group_event("x"));
+
+        let summary = meter.summary();
+        assert!(summary.relays.is_empty());
+        assert_eq!(summary.unattributed.events_received, 2);
+        assert_eq!(summary.total, summary.unattributed);
+        assert_eq!(summary.circles.len(), 1);
+        assert_eq!(summary.circles[0].usage.events_received, 1);
+    }
+
+    #[test]
+    fn heaviest_entries_come_first() {
+        let mut meter = BandwidthMeter::new(0);
+        meter.record_sent("wss://light.example.com", &group_event("x"));
+        meter.record_sent("wss://heavy.example.com", &group_event(&"x".repeat(500)));
+        let urls: Vec<_> = meter.summary().relays.into_iter().map(|r| r.url).collect();
+        assert_eq!(urls, ["wss://heavy.example.com", "wss://light.example.com"]);
+    }
+}
@@ -6,7 +6,30 @@
 //! # Security Model
 //!
 //! - **WSS only**: Plaintext ws:// connections are rejected
-//! - **Direct connections**: Uses nostr-sdk Client for relay communication
+//! - **Direct connections**: Uses nostr-sdk Client for relay communication,
+//!   behind the [`RelayTransport`] trait (see [`transport`])
+//! - **Pooled connections**: Sockets are reused across operations and closed
+//!   when idle (see [`pool`])
+//! - **Offline outbox**: Location updates that no relay accepted are kept and
+//!   re-sent on reconnect (see [`publish_queue`])
+//! - **No embedded Tor**: relays see the device's IP (see the GAP below and
+//!   the *Network Threat Model* in `SECURITY.md`)
+//!
+//! # GAP (plan §5.2 #18)
+//!
+//! Tor bootstrap status and control is not implemented: there is no
+//! `TorController` (`bootstrap()`, a progress stream, `new_identity()`,
+//! `TorStatus`), and `publish_event` / `fetch_events` are not gated on a
+//! bootstrapped Tor client. The tree has no Tor client to control; embedding
+//! one (arti) means a new [`RelayTransport`] plus a dependency and
+//! threat-model review, not a control surface over the nostr-sdk client.
+//! Until then IP-level unlinkability comes from running the device behind a
+//! VPN or a system-wide Tor proxy.
+//!
+//! Per-operation circuit isolation (`CircuitPurpose`-keyed clients so
+//! key-package fetches, gift-wrap fetches and each group's kind-445 traffic
+//! use separate circuits) waits on the same transport: with no Tor client
+//! there are no circuits or SOCKS isolation parameters to select.
 //!
 //! # Architecture
 //!
@@ -17,27 +40,49 @@
 //! RelayManager
 //!     |
 //!     v
-//! nostr-sdk Client
+//! RelayTransport (nostr-sdk Client; in-memory fake in tests)
 //!     |
 //!     v
 //! Nostr Relays (WSS)
 //! ```
 
 pub mod auto_commit;
+pub mod blacklist;
 pub mod catchup;
+pub mod chaff;
+pub mod compact;
 pub mod cursor;
 pub mod discovery;
 mod error;
+pub mod inbox;
 pub mod live_sync;
 pub mod maintenance;
 mod manager;
+pub mod metrics;
+pub mod paging;
+pub mod pool;
+pub mod pow;
+pub mod publish_queue;
 pub mod publishers;
+pub mod relay_info;
+pub mod relay_list_publisher;
+pub mod relay_stats;
+pub mod sync;
+pub mod transport;
 mod types;
+pub mod verify;
+pub mod welcome_delivery;
 
 pub use auto_commit::{
     resolve_receive_publish_work, rollback_receive_publish_work, AutoCommitPublisher,
 };
+pub use blacklist::{
+    community_blacklist_maintainer, install_relay_blacklist, is_relay_blacklisted,
+    LocalRelayOverride, RelayBlacklist, COMMUNITY_BLACKLIST_KIND, COMMUNITY_BLACKLIST_MAINTAINER,
+};
 pub use catchup::{CatchupOutcome, ReceiveOnlyOutcome};
+pub use chaff::{chaff_padding, ChaffScheduler};
+pub use compact::CompactEvent;
 pub use cursor::{
     cap_timestamp_to_now, since_for_stream, SubscribePhase, GROUP_INITIAL_BUFFER_SECS,
     GROUP_RESUBSCRIBE_BUFFER_SECS, INBOX_GIFTWRAP_LOOKBACK_SECS, STREAM_GROUP_445,
@@ -45,12 +90,47 @@ pub use cursor::{
 };
 pub use discovery::{discovery_relays, set_discovery_relays_for_test, PRODUCTION_DISCOVERY_RELAYS};
 pub use error::{RelayError, RelayResult};
+pub use inbox::{inbox_cursor_stream, InboxProcessor, InboxSyncSummary};
 pub use manager::{allow_ws_loopback_for_test, ws_loopback_allowed_for_test, RelayManager};
+pub use metrics::{
+    bandwidth_usage, reset_bandwidth_usage, BandwidthSummary, CircleUsage, RelayUsage, Usage,
+};
+pub use paging::GroupMessagePage;
+pub use pool::{ConnectionPool, PooledRelayHealth};
+pub use pow::{PowPolicy, MAX_POW_DIFFICULTY};
+pub use publish_queue::{
+    active_publish_queue, install_publish_queue, OutboxEntry, OutboxFlush, OutboxPriority,
+    OutboxStatus, PublishQueue,
+};
 pub use publishers::{
-    build_nip09_deletion, build_nip65_relay_list_event, build_relay_list_event,
-    build_unpublish_event, dedup_relay_targets, superseding_created_at, PublisherError,
-    PublisherResult,
+    build_event_deletion, build_nip09_deletion, build_nip65_relay_list_event,
+    build_relay_list_event, build_unpublish_event, dedup_relay_targets, superseding_created_at,
+    PublisherError, PublisherResult,
 };
+pub use relay_info::{
+    exceeded_size_limit, is_size_rejection, parse_relay_info, rank_relays, score_relay,
+    PublishNeeds, RelayInfo, RELAY_INFO_TTL_SECS,
+};
+pub use relay_list_publisher::{
+    build_wire_relay_list_event, plan_relay_list_republish, relay_list_wire_kind,
+    RelayListDebouncer, RelayListRepublish, RELAY_LIST_DEBOUNCE_SECS, RELAY_LIST_MAX_DELAY_SECS,
+};
+pub use relay_stats::{
+    flush_relay_stats, install_relay_stats_store, relay_metrics, reset_relay_metrics, RelayStats,
+    RelayStatsStore, RELAY_STATS_PERSIST_INTERVAL_SECS,
+};
+pub use sync::{
+    BackfillDigest, CircleSyncDigest, SyncDigest, SyncManager, BACKFILL_MAX_PAGES,
+    BACKFILL_PAGE_SIZE,
+};
+#[cfg(any(test, feature = "test-utils"))]
+pub use transport::MemoryTransport;
+pub use transport::{RelayTransport, SendOutcome, TransportFuture};
 pub use types::{
-    PublishResult, RelayConnectionStatus, RelayEventCheck, RelayFetchOutcome, RelayStatus,
+    PublishQuorum, PublishResult, QuorumPublish, RelayConnectionStatus, RelayEventCheck,
+    RelayFetchOutcome, RelayStatus,
+};
+pub use verify::{verify_batch, verify_event, SignatureStats};
+pub use welcome_delivery::{
+    publish_welcome, WelcomeDeliveryReport, WelcomeRelayDelivery, WelcomeRelayOutcome,
 };
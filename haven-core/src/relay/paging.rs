@@ -0,0 +1,144 @@
+//! Backwards paging over a circle's group messages.
+//!
+//! Relays answer a filter with its newest `limit` events, so history is read
+//! newest-first: each page asks for events at or before an `until` cursor,
+//! and the next page continues from the oldest event of the last.
+//!
+//! Several relays are asked at once, each returning up to `limit` events.
+//! The next cursor is the latest of the oldest timestamps among relays that
+//! returned a full page: anything older than that from a relay with a short
+//! page is already in hand, and the full-page relays may hold more. The
+//! cursor is inclusive, so events sharing its second come back on the next
+//! page and are deduplicated by id; if a whole page shares one second the
+//! cursor steps one second back instead of repeating forever, which can miss
+//! events past `limit` in that second.
+
+use std::collections::HashMap;
+
+use nostr::{Event, EventId};
+
+use super::RelayFetchOutcome;
+
+/// One page of group messages.
+#[derive(Debug, Clone, Default)]
+pub struct GroupMessagePage {
+    /// Distinct events, oldest first.
+    pub events: Vec<Event>,
+    /// The `until` for the next (older) page, or `None` when every relay
+    /// returned its last event.
+    pub next_until: Option<i64>,
+    /// Relays queried.
+    pub relays_polled: usize,
+    /// Relays that answered.
+    pub relays_responded: usize,
+}
+
+/// Merges per-relay answers to a page request (`until`, `limit`) into a
+/// [`GroupMessagePage`].
+#[must_use]
+pub fn page_from_outcomes(
+    outcomes: Vec<RelayFetchOutcome>,
+    until: Option<i64>,
+    limit: usize,
+) -> GroupMessagePage {
+    let mut page = GroupMessagePage {
+        relays_polled: outcomes.len(),
+        ..GroupMessagePage::default()
+    };
+    let mut events: HashMap<EventId, Event> = HashMap::new();
+    let mut next_until: Option<i64> = None;
+    for outcome in outcomes {
+        if !outcome.responded {
+            continue;
+        }
+        page.relays_responded += 1;
+        if limit > 0 && outcome.events.len() >= limit {
+            let oldest = outcome
+                .events
+                .iter()
+                .map(|e| i64::try_from(e.created_at.as_secs()).unwrap_or(i64::MAX))
+                .min();
+            next_until = next_until.max(oldest);
+        }
+        for event in outcome.events {
+            events.entry(event.id).or_insert(event);
+        }
+    }
+
+    page.next_until = next_until.map(|next| match until {
+        Some(until) if next >= until => until.saturating_sub(1),
+        _ => next,
+    });
+    page.events = events.into_values().collect();
+    page.events
+        .sort_by(|a, b| a.created_at.cmp(&b.created_at).then(a.id.cmp(&b.id)));
+    page
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use nostr::{EventBuilder, Keys, Kind, Timestamp};
+
+    fn event(created_at: u64) -> Event {
+        EventBuilder::new(Kind::Custom(445), "ciphertext")
+            .custom_created_at(Timestamp::from(created_at))
+            .sign_with_keys(&Keys::generate())
+            .unwrap()
+    }
+
+    fn outcome(relay: &str, events: Vec<Event>) -> RelayFetchOutcome {
+        RelayFetchOutcome {
+            relay_url: relay.to_string(),
+            responded: true,
+            events,
+        }
+    }
+
+    #[test]
+    fn continues_from_the_latest_full_page() {
+        let shared = event(300);
+        let page = page_from_outcomes(
+            vec![
+                outcome("wss://a.example.com", vec![shared.clone(), event(200)]),
+                outcome("wss://b.example.com", vec![shared, event(250)]),
+                outcome("wss://c.example.com", vec![event(100)]),
+                RelayFetchOutcome {
+                    relay_url: "wss://d.example.com".to_string(),
+                    responded: false,
+                    events: Vec::new(),
+                },
+            ],
+            None,
+            2,
+        );
+        assert_eq!(page.relays_polled, 4);
+        assert_eq!(page.relays_responded, 3);
+        assert_eq!(page.events.len(), 4);
+        assert!(page
+            .events
+            .windows(2)
+            .all(|w| w[0].created_at <= w[1].created_at));
+        assert_eq!(page.next_until, Some(250));
+    }
+
+    #[test]
+    fn ends_when_no_relay_fills_a_page() {
+        let page = page_from_outcomes(
+            vec![outcome("wss://a.example.com", vec![event(10)])],
+            Some(50),
+            2,
+        );
+        assert_eq!(page.next_until, None);
+    }
+
+    #[test]
+    fn steps_past_a_second_that_fills_a_page() {
+        let page = page_from_outcomes(
+            vec![outcome("wss://a.example.com", vec![event(50), event(50)])],
+            Some(50),
+            2,
+        );
+        assert_eq!(page.next_until, Some(49));
+    }
+}
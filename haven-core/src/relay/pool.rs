@@ -0,0 +1,360 @@
+//! Connection pool bookkeeping for [`RelayManager`](super::RelayManager).
+//!
+//! The `nostr_sdk::Client` inside a `RelayManager` already keeps one WebSocket
+//! per relay URL. This module decides what to do with those sockets on each
+//! operation, so a location published every few minutes rides the existing
+//! connection instead of re-negotiating TLS each time:
+//!
+//! - **Reuse** — the socket is up: send on it, no handshake.
+//! - **Connect** — the socket is down and the relay is not backing off:
+//!   (re)connect now.
+//! - **Back off** — the last connects failed: skip the relay until its
+//!   exponential backoff ([`RECONNECT_BASE_BACKOFF`] doubling up to
+//!   [`RECONNECT_MAX_BACKOFF`]) expires. A success resets it.
+//!
+//! Every fresh connect is also noted for [`ConnectionPool::take_reconnected`],
+//! which tells the manager the network is back so it can drain the offline
+//! outbox (see [`super::publish_queue`]).
+//!
+//! Relays unused for [`POOL_IDLE_TIMEOUT`] are handed back by
+//! [`ConnectionPool::take_idle`] for the manager to disconnect, so the app does
+//! not hold sockets (and leak presence) to relays it no longer talks to.
+//! Relays carrying a subscription are pinned and never reaped.
+//!
+//! The pool holds no sockets itself; it is plain state, unit tested with an
+//! injected clock.
+
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+/// Idle time after which a pooled connection is closed in production.
+///
+/// [`ConnectionPool::default`] takes the active
+/// [environment profile](crate::environment)'s value, which is this unless a
+/// dev or staging profile shortens it. Longer than the slowest location cadence so periodic publishes keep their
+/// connection warm.
+pub const POOL_IDLE_TIMEOUT: Duration = Duration::from_secs(10 * 60);
+
+/// Backoff after the first failed connect to a relay.
+///
+/// Shorter than the publish retry backoff, so the retry loop inside one
+/// publish still gets a second connect attempt.
+pub const RECONNECT_BASE_BACKOFF: Duration = Duration::from_secs(1);
+
+/// Upper bound on the reconnect backoff.
+pub const RECONNECT_MAX_BACKOFF: Duration = Duration::from_secs(5 * 60);
+
+/// What to do with a relay's connection before using it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConnectAction {
+    /// The socket is connected; use it as-is.
+    Reuse,
+    /// The socket is down; connect now.
+    Connect,
+    /// Recent connects failed; skip the relay until the backoff expires.
+    BackOff,
+}
+
+/// Health of one pooled relay, for diagnostics.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PooledRelayHealth {
+    /// Relay URL.
+    pub url: String,
+    /// Whether the last connect succeeded and the socket was up when last used.
+    pub connected: bool,
+    /// Connect failures since the last success.
+    pub consecutive_failures: u32,
+    /// Time since the relay was last used.
+    pub idle_for: Duration,
+    /// Remaining backoff before the next connect attempt, if backing off.
+    pub retry_in: Option<Duration>,
+    /// Whether a subscription pins the connection open.
+    pub pinned: bool,
+}
+
+#[derive(Debug, Clone)]
+struct Entry {
+    connected: bool,
+    failures: u32,
+    last_used: Instant,
+    retry_at: Option<Instant>,
+    pinned: bool,
+}
+
+impl Entry {
+    const fn new(now: Instant) -> Self {
+        Self {
+            connected: false,
+            failures: 0,
+            last_used: now,
+            retry_at: None,
+            pinned: false,
+        }
+    }
+}
+
+/// Per-relay connection state, keyed by relay URL.
+#[derive(Debug, Clone)]
+pub struct ConnectionPool {
+    entries: HashMap<String, Entry>,
+    idle_timeout: Duration,
+    base_backoff: Duration,
+    max_backoff: Duration,
+    reconnected: bool,
+}
+
+impl Default for ConnectionPool {
+    fn default() -> Self {
+        Self::new(
+            crate::environment::active_environment()
+                .timing
+                .pool_idle_timeout,
+            RECONNECT_BASE_BACKOFF,
+            RECONNECT_MAX_BACKOFF,
+        )
+    }
+}
+
+impl ConnectionPool {
+    /// Creates an empty pool with the given timings.
+    #[must_use]
+    pub fn new(idle_timeout: Duration, base_backoff: Duration, max_backoff: Duration) -> Self {
+        Self {
+            entries: HashMap::new(),
+            idle_timeout,
+            base_backoff,
+            max_backoff,
+            reconnected: false,
+        }
+    }
+
+    /// Decides how to use `url`, given whether its socket is currently up,
+    /// and marks it used.
+    pub fn plan(&mut self, url: &str, socket_connected: bool, now: Instant) -> ConnectAction {
+        let entry = self
+            .entries
+            .entry(url.to_string())
+            .or_insert_with(|| Entry::new(now));
+        entry.last_used = now;
+        if socket_connected {
+            entry.connected = true;
+            return ConnectAction::Reuse;
+        }
+        entry.connected = false;
+        match entry.retry_at {
+            Some(at) if at > now => ConnectAction::BackOff,
+            _ => ConnectAction::Connect,
+        }
+    }
+
+    /// Records a successful connect, clearing any backoff.
+    pub fn record_connected(&mut self, url: &str, now: Instant) {
+        let entry = self
+            .entries
+            .entry(url.to_string())
+            .or_insert_with(|| Entry::new(now));
+        entry.connected = true;
+        entry.failures = 0;
+        entry.retry_at = None;
+        self.reconnected = true;
+    }
+
+    /// Returns whether any relay was (re)connected since the last call, and
+    /// clears the flag.
+    pub fn take_reconnected(&mut self) -> bool {
+        std::mem::take(&mut self.reconnected)
+    }
+
+    /// Records a failed connect and schedules the next attempt.
+    pub fn record_failure(&mut self, url: &str, now: Instant) {
+        let backoff = {
+            let entry = self
+                .entries
+                .entry(url.to_string())
+                .or_insert_with(|| Entry::new(now));
+            entry.connected = false;
+            entry.failures = entry.failures.saturating_add(1);
+            entry.failures
+        };
+        let delay = self.backoff(backoff);
+        if let Some(entry) = self.entries.get_mut(url) {
+            entry.retry_at = Some(now + delay);
+        }
+    }
+
+    /// Keeps `url` open regardless of idle time (it carries a subscription).
+    pub fn pin(&mut self, url: &str, now: Instant) {
+        self.entries
+            .entry(url.to_string())
+            .or_insert_with(|| Entry::new(now))
+            .pinned = true;
+    }
+
+    /// Drops all state for `url` (the relay was removed).
+    pub fn forget(&mut self, url: &str) {
+        self.entries.remove(url);
+    }
+
+    /// Drops all state (the manager disconnected everything).
+    pub fn clear(&mut self) {
+        self.entries.clear();
+    }
+
+    /// Removes and returns the unpinned relays idle for at least the idle
+    /// timeout. The caller disconnects them.
+    pub fn take_idle(&mut self, now: Instant) -> Vec<String> {
+        let idle_timeout = self.idle_timeout;
+        let idle: Vec<String> = self
+            .entries
+            .iter()
+            .filter(|(_, e)| {
+                !e.pinned && now.saturating_duration_since(e.last_used) >= idle_timeout
+            })
+            .map(|(url, _)| url.clone())
+            .collect();
+        for url in &idle {
+            self.entries.remove(url);
+        }
+        idle
+    }
+
+    /// Backoff after `failures` consecutive connect failures: the base
+    /// backoff doubled per failure, capped at the maximum.
+    #[must_use]
+    pub fn backoff(&self, failures: u32) -> Duration {
+        let exponent = failures.saturating_sub(1).min(31);
+        self.base_backoff
+            .saturating_mul(1u32 << exponent)
+            .min(self.max_backoff)
+    }
+
+    /// Health of every pooled relay, sorted by URL.
+    #[must_use]
+    pub fn health(&self, now: Instant) -> Vec<PooledRelayHealth> {
+        let mut health: Vec<PooledRelayHealth> = self
+            .entries
+            .iter()
+            .map(|(url, e)| PooledRelayHealth {
+                url: url.clone(),
+                connected: e.connected,
+                consecutive_failures: e.failures,
+                idle_for: now.saturating_duration_since(e.last_used),
+                retry_in: e
+                    .retry_at
+                    .filter(|at| *at > now)
+                    .map(|at| at.saturating_duration_since(now)),
+                pinned: e.pinned,
+            })
+            .collect();
+        health.sort_by(|a, b| a.url.cmp(&b.url));
+        health
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const URL: &str = "wss://relay.example.com";
+
+    fn pool() -> ConnectionPool {
+        ConnectionPool::new(
+            Duration::from_secs(60),
+            Duration::from_secs(1),
+            Duration::from_secs(8),
+        )
+    }
+
+    #[test]
+    fn connected_socket_is_reused() {
+        let mut pool = pool();
+        let now = Instant::now();
+        assert_eq!(pool.plan(URL, false, now), ConnectAction::Connect);
+        pool.record_connected(URL, now);
+        assert_eq!(pool.plan(URL, true, now), ConnectAction::Reuse);
+        assert!(pool.health(now)[0].connected);
+    }
+
+    #[test]
+    fn failures_back_off_exponentially_and_reset_on_success() {
+        let mut pool = pool();
+        let t0 = Instant::now();
+        assert_eq!(pool.backoff(1), Duration::from_secs(1));
+        assert_eq!(pool.backoff(2), Duration::from_secs(2));
+        assert_eq!(pool.backoff(3), Duration::from_secs(4));
+        assert_eq!(pool.backoff(10), Duration::from_secs(8), "capped");
+        assert_eq!(pool.backoff(u32::MAX), Duration::from_secs(8));
+
+        pool.record_failure(URL, t0);
+        pool.record_failure(URL, t0);
+        assert_eq!(pool.plan(URL, false, t0), ConnectAction::BackOff);
+        let health = &pool.health(t0)[0];
+        assert_eq!(health.consecutive_failures, 2);
+        assert_eq!(health.retry_in, Some(Duration::from_secs(2)));
+
+        let later = t0 + Duration::from_secs(2);
+        assert_eq!(pool.plan(URL, false, later), ConnectAction::Connect);
+        pool.record_connected(URL, later);
+        let health = &pool.health(later)[0];
+        assert_eq!(health.consecutive_failures, 0);
+        assert_eq!(health.retry_in, None);
+    }
+
+    #[test]
+    fn a_connected_socket_bypasses_backoff() {
+        // nostr-sdk may have reconnected on its own while we were backing off.
+        let mut pool = pool();
+        let now = Instant::now();
+        pool.record_failure(URL, now);
+        assert_eq!(pool.plan(URL, true, now), ConnectAction::Reuse);
+    }
+
+    #[test]
+    fn idle_relays_are_reaped_unless_pinned() {
+        let mut pool = pool();
+        let t0 = Instant::now();
+        pool.plan(URL, true, t0);
+        pool.plan("wss://sub.example.com", true, t0);
+        pool.pin("wss://sub.example.com", t0);
+        pool.plan(
+            "wss://fresh.example.com",
+            true,
+            t0 + Duration::from_secs(30),
+        );
+
+        let reaped = pool.take_idle(t0 + Duration::from_secs(60));
+        assert_eq!(reaped, vec![URL.to_string()]);
+        let remaining: Vec<_> = pool
+            .health(t0 + Duration::from_secs(60))
+            .into_iter()
+            .map(|h| h.url)
+            .collect();
+        assert_eq!(
+            remaining,
+            ["wss://fresh.example.com", "wss://sub.example.com"]
+        );
+    }
+
+    #[test]
+    fn a_fresh_connect_is_reported_once() {
+        let mut pool = pool();
+        let now = Instant::now();
+        assert!(!pool.take_reconnected());
+        pool.plan(URL, true, now);
+        assert!(!pool.take_reconnected(), "reuse is not a reconnect");
+        pool.record_failure(URL, now);
+        assert!(!pool.take_reconnected());
+        pool.record_connected(URL, now);
+        assert!(pool.take_reconnected());
+        assert!(!pool.take_reconnected());
+    }
+
+    #[test]
+    fn use_keeps_a_relay_warm() {
+        let mut pool = pool();
+        let t0 = Instant::now();
+        pool.plan(URL, true, t0);
+        pool.plan(URL, true, t0 + Duration::from_secs(50));
+        assert!(pool.take_idle(t0 + Duration::from_secs(100)).is_empty());
+    }
+}
@@ -0,0 +1,301 @@
+//! NIP-13 proof of work for relay publishing.
+//!
+//! Some public relays only accept events whose id starts with a minimum number
+//! of zero bits, and reject everything else with an `OK false` whose reason is
+//! prefixed `pow:` (NIP-01 machine-readable prefix). Work is added by trying
+//! `["nonce", <n>, <target>]` tags until the id is small enough.
+//!
+//! Mining changes the event id, so a mined event has to be re-signed. Proof of
+//! work is therefore only available for events signed with keys this device
+//! holds — the identity-signed relay lists, deletions and profile — via a
+//! [`PowPolicy`]. Group messages and gift wraps are signed with one-time keys
+//! (inside the engine / the NIP-59 wrapper) and are always published as-is.
+//!
+//! The miner spreads the nonce space over every available core
+//! ([`std::thread::available_parallelism`]) and is capped at
+//! [`MAX_POW_DIFFICULTY`] so a hostile relay cannot pin the phone's CPU.
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+
+use nostr::{Event, EventId, Keys, Tag, Tags, UnsignedEvent};
+
+use super::error::{RelayError, RelayResult};
+use crate::nostr::identity::{sign_unsigned, Signer};
+
+/// Highest difficulty Haven will mine.
+///
+/// 24 bits is ~16M hashes on average: a few seconds on a modern phone. A relay
+/// demanding more is treated like any other rejecting relay.
+pub const MAX_POW_DIFFICULTY: u8 = 24;
+
+/// Difficulty assumed when a relay's `pow:` rejection names no number.
+pub const DEFAULT_POW_DIFFICULTY: u8 = 16;
+
+/// Tag name NIP-13 uses for the nonce.
+const NONCE_TAG: &str = "nonce";
+
+/// How a publish may add proof of work.
+#[derive(Clone)]
+pub struct PowPolicy {
+    /// Keys that signed the event (the event is re-signed after mining).
+    pub keys: Keys,
+    /// Difficulty to mine before the first send. `None` sends the event as-is
+    /// and mines only if a relay rejects it with `pow:`.
+    pub target_difficulty: Option<u8>,
+}
+
+impl PowPolicy {
+    /// Mines only when a relay asks for it.
+    #[must_use]
+    pub const fn on_demand(keys: Keys) -> Self {
+        Self {
+            keys,
+            target_difficulty: None,
+        }
+    }
+
+    /// Mines to `difficulty` up front, and further if a relay asks for more.
+    #[must_use]
+    pub const fn with_target(keys: Keys, difficulty: u8) -> Self {
+        Self {
+            keys,
+            target_difficulty: Some(difficulty),
+        }
+    }
+}
+
+impl std::fmt::Debug for PowPolicy {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("PowPolicy")
+            .field("keys", &"<redacted>")
+            .field("target_difficulty", &self.target_difficulty)
+            .finish()
+    }
+}
+
+/// Number of leading zero bits of an event id.
+#[must_use]
+pub fn difficulty(id: &EventId) -> u8 {
+    let mut bits: u32 = 0;
+    for byte in id.as_bytes() {
+        bits += byte.leading_zeros();
+        if *byte != 0 {
+            break;
+        }
+    }
+    u8::try_from(bits).unwrap_or(u8::MAX)
+}
+
+/// Difficulty a relay rejection reason asks for, if it is a `pow:` rejection.
+///
+/// Relays phrase this differently (`pow: difficulty 8 is less than 20`,
+/// `pow: required 20`), so the largest number after the prefix is taken as the
+/// requirement, falling back to [`DEFAULT_POW_DIFFICULTY`] when there is none.
+#[must_use]
+pub fn required_difficulty(reason: &str) -> Option<u8> {
+    let lower = reason.to_ascii_lowercase();
+    let (_, rest) = lower.split_once("pow:")?;
+    let parsed = rest
+        .split(|c: char| !c.is_ascii_digit())
+        .filter_map(|n| n.parse::<u8>().ok())
+        .max();
+    Some(parsed.unwrap_or(DEFAULT_POW_DIFFICULTY))
+}
+
+/// Highest difficulty demanded across a publish's rejections, if any relay
+/// rejected for lack of work.
+#[must_use]
+pub fn demanded_difficulty(rejected_by: &[(String, String)]) -> Option<u8> {
+    rejected_by
+        .iter()
+        .filter_map(|(_, reason)| required_difficulty(reason))
+        .max()
+}
+
+/// Mines `unsigned` until its id has at least `difficulty_target` leading
+/// zero bits.
+///
+/// Any existing nonce tag is replaced. Blocks the calling thread (and every
+/// core) until a nonce is found — call from a blocking task.
+#[must_use]
+pub fn mine(unsigned: UnsignedEvent, difficulty_target: u8) -> UnsignedEvent {
+    let base: Vec<Tag> = unsigned
+        .tags
+        .iter()
+        .filter(|t| t.as_slice().first().map(String::as_str) != Some(NONCE_TAG))
+        .cloned()
+        .collect();
+    let workers = std::thread::available_parallelism()
+        .ok()
+        .and_then(|n| u64::try_from(n.get()).ok())
+        .unwrap_or(1);
+
+    let found = AtomicBool::new(false);
+    let winner: Mutex<Option<UnsignedEvent>> = Mutex::new(None);
+    std::thread::scope(|scope| {
+        for worker in 0..workers {
+            let (base, found, winner, template) = (&base, &found, &winner, &unsigned);
+            scope.spawn(move || {
+                let mut candidate = template.clone();
+                let mut nonce = worker;
+                while !found.load(Ordering::Relaxed) {
+                    let mut tags = base.clone();
+                    tags.push(Tag::pow(u128::from(nonce), difficulty_target));
+                    candidate.tags = Tags::from_list(tags);
+                    candidate.id = None;
+                    candidate.ensure_id();
+                    if candidate
+                        .id
+                        .is_some_and(|id| difficulty(&id) >= difficulty_target)
+                    {
+                        found.store(true, Ordering::Relaxed);
+                        let mut slot = winner
+                            .lock()
+                            .unwrap_or_else(std::sync::PoisonError::into_inner);
+                        if slot.is_none() {
+                            *slot = Some(candidate);
+                        }
+                        return;
+                    }
+                    nonce = nonce.wrapping_add(workers);
+                }
+            });
+        }
+    });
+
+    winner
+        .into_inner()
+        .unwrap_or_else(std::sync::PoisonError::into_inner)
+        .unwrap_or(unsigned)
+}
+
+/// Re-mines a signed `event` to `difficulty_target` and re-signs it with
+/// `signer`.
+///
+/// Returns the event unchanged if it already meets the target. Blocks like
+/// [`mine`].
+///
+/// # Errors
+///
+/// Returns [`RelayError::ProofOfWork`] if `difficulty_target` exceeds
+/// [`MAX_POW_DIFFICULTY`], `signer` did not sign `event`, or re-signing fails.
+pub fn mine_event(event: &Event, difficulty_target: u8, signer: &dyn Signer) -> RelayResult<Event> {
+    if difficulty_target > MAX_POW_DIFFICULTY {
+        return Err(RelayError::ProofOfWork(format!(
+            "difficulty {difficulty_target} exceeds the cap of {MAX_POW_DIFFICULTY}"
+        )));
+    }
+    if event.pubkey != signer.public_key() {
+        return Err(RelayError::ProofOfWork(
+            "event was not signed by the provided keys".to_string(),
+        ));
+    }
+    if difficulty(&event.id) >= difficulty_target {
+        return Ok(event.clone());
+    }
+    let unsigned = UnsignedEvent::new(
+        event.pubkey,
+        event.created_at,
+        event.kind,
+        event.tags.iter().cloned().collect::<Vec<_>>(),
+        event.content.clone(),
+    );
+    sign_unsigned(mine(unsigned, difficulty_target), signer)
+        .map_err(|e| RelayError::ProofOfWork(format!("failed to re-sign mined event: {e}")))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use nostr::{EventBuilder, Kind};
+
+    fn note(keys: &Keys) -> Event {
+        EventBuilder::new(Kind::TextNote, "hello")
+            .tags([Tag::parse(["t", "haven"]).unwrap()])
+            .sign_with_keys(keys)
+            .unwrap()
+    }
+
+    #[test]
+    fn difficulty_counts_leading_zero_bits() {
+        let mut bytes = [0xffu8; 32];
+        assert_eq!(difficulty(&EventId::from_byte_array(bytes)), 0);
+        bytes[0] = 0;
+        bytes[1] = 0x0f;
+        assert_eq!(difficulty(&EventId::from_byte_array(bytes)), 12);
+        assert_eq!(difficulty(&EventId::from_byte_array([0; 32])), u8::MAX);
+    }
+
+    #[test]
+    fn required_difficulty_parses_relay_reasons() {
+        assert_eq!(
+            required_difficulty("pow: difficulty 8 is less than 20"),
+            Some(20)
+        );
+        assert_eq!(required_difficulty("POW: required 18"), Some(18));
+        assert_eq!(
+            required_difficulty("pow: insufficient work"),
+            Some(DEFAULT_POW_DIFFICULTY)
+        );
+        assert_eq!(required_difficulty("blocked: not on whitelist"), None);
+        assert_eq!(
+            demanded_difficulty(&[
+                ("wss://a".to_string(), "rate-limited: slow down".to_string()),
+                ("wss://b".to_string(), "pow: 12".to_string()),
+                ("wss://c".to_string(), "pow: 10".to_string()),
+            ]),
+            Some(12)
+        );
+        assert_eq!(demanded_difficulty(&[]), None);
+    }
+
+    #[test]
+    fn mine_event_meets_target_and_keeps_content() {
+        let keys = Keys::generate();
+        let event = note(&keys);
+        let mined = mine_event(&event, 8, &keys).unwrap();
+
+        assert!(difficulty(&mined.id) >= 8);
+        mined.verify().unwrap();
+        assert_eq!(mined.content, event.content);
+        assert_eq!(mined.created_at, event.created_at);
+        let nonces: Vec<_> = mined
+            .tags
+            .iter()
+            .filter(|t| t.as_slice().first().map(String::as_str) == Some(NONCE_TAG))
+            .collect();
+        assert_eq!(nonces.len(), 1);
+        assert_eq!(nonces[0].as_slice()[2], "8");
+        assert!(mined
+            .tags
+            .iter()
+            .any(|t| t.as_slice() == ["t".to_string(), "haven".to_string()]));
+
+        // Re-mining replaces the nonce rather than stacking a second one.
+        let again = mine_event(&mined, 10, &keys).unwrap();
+        assert!(difficulty(&again.id) >= 10);
+        assert_eq!(
+            again
+                .tags
+                .iter()
+                .filter(|t| t.as_slice().first().map(String::as_str) == Some(NONCE_TAG))
+                .count(),
+            1
+        );
+    }
+
+    #[test]
+    fn mine_event_rejects_foreign_keys_and_excessive_difficulty() {
+        let keys = Keys::generate();
+        let event = note(&keys);
+        assert!(matches!(
+            mine_event(&event, 4, &Keys::generate()),
+            Err(RelayError::ProofOfWork(_))
+        ));
+        assert!(matches!(
+            mine_event(&event, MAX_POW_DIFFICULTY + 1, &keys),
+            Err(RelayError::ProofOfWork(_))
+        ));
+    }
+}
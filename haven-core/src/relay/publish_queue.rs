@@ -0,0 +1,884 @@
+//! Durable offline outbox for events that failed to publish.
+//!
+//! Mobile networks drop constantly. [`RelayManager::publish_event`] already
+//! retries a few times within one call, but when every relay stays
+//! unreachable the event used to be lost. [`RelayManager::publish_event_queued`]
+//! instead persists the signed event in the `outbox` table of `circles.db`
+//! (see [`CircleStorage`]) and the [`PublishQueue`] re-sends it later:
+//!
+//! - **On reconnect** — when a `RelayManager` freshly connects a relay (the
+//!   network is back), every pending event is retried at once.
+//! - **On schedule** — otherwise an event is retried once its backoff
+//!   ([`OUTBOX_BASE_BACKOFF_SECS`] doubling up to [`OUTBOX_MAX_BACKOFF_SECS`])
+//!   has passed, checked whenever the manager next talks to a relay.
+//!
+//! An event is never sent past its NIP-40 `expiration` nor past its
+//! [`OutboxPriority`]'s delivery deadline after it was queued (a location
+//! from an hour ago is noise, not an update), and is abandoned after
+//! [`OUTBOX_MAX_ATTEMPTS`] failed sends. Re-sending is idempotent: relays
+//! dedupe by event id.
+//!
+//! # Priorities
+//!
+//! Each entry carries an [`OutboxPriority`]. A flush sends higher priorities
+//! first, so after a long outage an SOS goes out before the backlog of
+//! location updates, and housekeeping goes last:
+//!
+//! | Priority | Deadline |
+//! |----------|----------|
+//! | [`OutboxPriority::Sos`] | [`OUTBOX_SOS_DEADLINE_SECS`] |
+//! | [`OutboxPriority::LiveShare`] | [`OUTBOX_LIVE_SHARE_DEADLINE_SECS`] |
+//! | [`OutboxPriority::Location`] | [`OUTBOX_LOCATION_DEADLINE_SECS`] |
+//! | [`OutboxPriority::Housekeeping`] | [`OUTBOX_MAX_AGE_SECS`] |
+//!
+//! An entry past its deadline is marked expired and never handed to a relay.
+//!
+//! # Paused sharing
+//!
+//! While the user has paused location sharing, globally or for the circle an
+//! event is routed to (see [`CircleStorage::is_location_paused`]), location
+//! and live-share events are not queued, and ones already queued are marked
+//! dropped at the next flush instead of being sent. Other priorities are
+//! unaffected.
+//!
+//! # What may be queued
+//!
+//! Only events whose late arrival is harmless — location updates and other
+//! application messages. MLS commits and welcomes follow publish-before-apply
+//! (Rule 13): a commit that failed to publish is rolled back, so re-sending it
+//! later would fork the group. Those go through
+//! [`RelayManager::publish_event`], never the outbox.
+//!
+//! Like the relay blacklist, the queue is installed process-wide
+//! ([`install_publish_queue`]) and consulted by every `RelayManager`.
+//!
+//! [`RelayManager::publish_event`]: super::RelayManager::publish_event
+//! [`RelayManager::publish_event_queued`]: super::RelayManager::publish_event_queued
+
+use std::future::Future;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, RwLock};
+
+use nostr::Event;
+
+use crate::circle::{CircleStorage, Result};
+use crate::nostr::mls::redact_hex_sequences;
+
+/// Delay before the first scheduled retry, in seconds.
+pub const OUTBOX_BASE_BACKOFF_SECS: i64 = 30;
+
+/// Upper bound on the retry backoff, in seconds (30 minutes).
+pub const OUTBOX_MAX_BACKOFF_SECS: i64 = 30 * 60;
+
+/// Failed sends (including the original publish) after which an event is
+/// abandoned.
+pub const OUTBOX_MAX_ATTEMPTS: u32 = 10;
+
+/// Longest an event stays queued, in seconds (24 hours). The delivery
+/// deadline of housekeeping events.
+pub const OUTBOX_MAX_AGE_SECS: i64 = 24 * 60 * 60;
+
+/// Delivery deadline of an SOS, in seconds (1 hour).
+pub const OUTBOX_SOS_DEADLINE_SECS: i64 = 60 * 60;
+
+/// Delivery deadline of a live-share update, in seconds (2 minutes): the
+/// next update supersedes it almost at once.
+pub const OUTBOX_LIVE_SHARE_DEADLINE_SECS: i64 = 2 * 60;
+
+/// Delivery deadline of a regular location update, in seconds (15 minutes).
+pub const OUTBOX_LOCATION_DEADLINE_SECS: i64 = 15 * 60;
+
+/// How long sent, expired, and abandoned entries stay visible in the status
+/// list, in seconds (1 hour).
+pub const OUTBOX_FINISHED_RETENTION_SECS: i64 = 60 * 60;
+
+/// Most events re-sent by one flush.
+const OUTBOX_FLUSH_BATCH: usize = 50;
+
+/// Delivery state of an outbox entry.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OutboxStatus {
+    /// Waiting for a retry.
+    Pending,
+    /// A relay accepted it.
+    Sent,
+    /// Its expiration passed before any relay accepted it.
+    Expired,
+    /// Gave up after [`OUTBOX_MAX_ATTEMPTS`] failed sends.
+    Abandoned,
+    /// Not sent because location sharing was paused.
+    Dropped,
+}
+
+impl OutboxStatus {
+    /// Stable slug stored in the `outbox.status` column.
+    #[must_use]
+    pub const fn as_str(&self) -> &'static str {
+        match self {
+            Self::Pending => "pending",
+            Self::Sent => "sent",
+            Self::Expired => "expired",
+            Self::Abandoned => "abandoned",
+            Self::Dropped => "dropped",
+        }
+    }
+
+    /// Parses a slug back into an [`OutboxStatus`].
+    #[must_use]
+    pub fn parse(s: &str) -> Option<Self> {
+        match s {
+            "pending" => Some(Self::Pending),
+            "sent" => Some(Self::Sent),
+            "expired" => Some(Self::Expired),
+            "abandoned" => Some(Self::Abandoned),
+            "dropped" => Some(Self::Dropped),
+            _ => None,
+        }
+    }
+}
+
+/// How urgently a queued event is delivered, and for how long it is worth
+/// delivering at all.
+///
+/// Ordered from least to most urgent, so `Sos > LiveShare > Location >
+/// Housekeeping`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum OutboxPriority {
+    /// Relay lists, key packages, and other upkeep.
+    Housekeeping,
+    /// A regular location update.
+    #[default]
+    Location,
+    /// An update of an ongoing live share.
+    LiveShare,
+    /// An emergency alert.
+    Sos,
+}
+
+impl OutboxPriority {
+    /// Stable slug used across the FFI.
+    #[must_use]
+    pub const fn as_str(&self) -> &'static str {
+        match self {
+            Self::Housekeeping => "housekeeping",
+            Self::Location => "location",
+            Self::LiveShare => "live_share",
+            Self::Sos => "sos",
+        }
+    }
+
+    /// Parses a slug back into an [`OutboxPriority`].
+    #[must_use]
+    pub fn parse(s: &str) -> Option<Self> {
+        match s {
+            "housekeeping" => Some(Self::Housekeeping),
+            "location" => Some(Self::Location),
+            "live_share" => Some(Self::LiveShare),
+            "sos" => Some(Self::Sos),
+            _ => None,
+        }
+    }
+
+    /// Rank stored in the `outbox.priority` column; higher is sent first.
+    #[must_use]
+    pub const fn rank(self) -> i64 {
+        match self {
+            Self::Housekeeping => 0,
+            Self::Location => 1,
+            Self::LiveShare => 2,
+            Self::Sos => 3,
+        }
+    }
+
+    /// Inverse of [`Self::rank`].
+    #[must_use]
+    pub const fn from_rank(rank: i64) -> Option<Self> {
+        match rank {
+            0 => Some(Self::Housekeeping),
+            1 => Some(Self::Location),
+            2 => Some(Self::LiveShare),
+            3 => Some(Self::Sos),
+            _ => None,
+        }
+    }
+
+    /// Seconds after queueing past which the event is dropped unsent.
+    #[must_use]
+    pub const fn deadline_secs(self) -> i64 {
+        match self {
+            Self::Housekeeping => OUTBOX_MAX_AGE_SECS,
+            Self::Location => OUTBOX_LOCATION_DEADLINE_SECS,
+            Self::LiveShare => OUTBOX_LIVE_SHARE_DEADLINE_SECS,
+            Self::Sos => OUTBOX_SOS_DEADLINE_SECS,
+        }
+    }
+}
+
+/// One outbox entry, as reported by [`PublishQueue::status`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OutboxEntry {
+    /// Row id.
+    pub id: i64,
+    /// Event id (64-char hex).
+    pub event_id: String,
+    /// Event kind.
+    pub kind: u16,
+    /// Relays the event is sent to.
+    pub relays: Vec<String>,
+    /// Delivery priority.
+    pub priority: OutboxPriority,
+    /// Delivery state.
+    pub status: OutboxStatus,
+    /// Failed sends so far, including the original publish.
+    pub attempts: u32,
+    /// Unix timestamp of the next scheduled retry (meaningful while pending).
+    pub next_attempt_at: i64,
+    /// Unix timestamp after which the event is no longer sent.
+    pub expires_at: i64,
+    /// Redacted reason of the last failure, if any.
+    pub last_error: Option<String>,
+    /// Unix timestamp the event was queued.
+    pub created_at: i64,
+    /// Unix timestamp of the last state change.
+    pub updated_at: i64,
+}
+
+/// A pending event loaded for re-sending.
+#[derive(Debug, Clone)]
+pub(crate) struct QueuedEvent {
+    pub id: i64,
+    pub event_json: String,
+    pub relays: Vec<String>,
+    pub attempts: u32,
+    pub expires_at: i64,
+    pub priority: OutboxPriority,
+}
+
+/// Counts from one [`PublishQueue::flush`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct OutboxFlush {
+    /// Events a relay accepted.
+    pub sent: usize,
+    /// Events that failed again and were rescheduled.
+    pub rescheduled: usize,
+    /// Events dropped because they expired.
+    pub expired: usize,
+    /// Events abandoned after too many failures.
+    pub abandoned: usize,
+    /// Location events dropped because sharing was paused.
+    pub dropped: usize,
+}
+
+/// Backoff before the retry following `attempts` failed sends: the base
+/// backoff doubled per failure, capped at the maximum.
+#[must_use]
+pub fn outbox_backoff_secs(attempts: u32) -> i64 {
+    let exponent = attempts.saturating_sub(1).min(30);
+    OUTBOX_BASE_BACKOFF_SECS
+        .saturating_mul(1i64 << exponent)
+        .min(OUTBOX_MAX_BACKOFF_SECS)
+}
+
+/// Deadline after which a queued `event` is no longer sent: its NIP-40
+/// expiration, capped at `priority`'s delivery deadline after `now`.
+#[must_use]
+pub fn outbox_expires_at(event: &Event, priority: OutboxPriority, now: i64) -> i64 {
+    let cap = now.saturating_add(priority.deadline_secs().min(OUTBOX_MAX_AGE_SECS));
+    event
+        .tags
+        .iter()
+        .find_map(|t| match t.as_standardized() {
+            Some(nostr::TagStandard::Expiration(ts)) => i64::try_from(ts.as_secs()).ok(),
+            _ => None,
+        })
+        .map_or(cap, |expiration| expiration.min(cap))
+}
+
+/// The persistent outbox over `circles.db`.
+pub struct PublishQueue {
+    storage: Arc<CircleStorage>,
+    /// Set while a flush runs, so overlapping triggers do not double-send.
+    flushing: AtomicBool,
+}
+
+impl std::fmt::Debug for PublishQueue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("PublishQueue")
+            .field("flushing", &self.flushing.load(Ordering::Relaxed))
+            .finish_non_exhaustive()
+    }
+}
+
+impl PublishQueue {
+    /// Creates a queue persisting into `storage`.
+    #[must_use]
+    pub const fn new(storage: Arc<CircleStorage>) -> Self {
+        Self {
+            storage,
+            flushing: AtomicBool::new(false),
+        }
+    }
+
+    /// Queues `event`, whose publish to `relays` just failed with `error`,
+    /// at `priority`.
+    ///
+    /// The failed publish counts as the first attempt. Returns `false` when
+    /// nothing was queued: the event has already expired, it is a location
+    /// update while sharing is paused, or it is queued already.
+    ///
+    /// # Errors
+    ///
+    /// Returns a database error on failure.
+    pub fn enqueue(
+        &self,
+        event: &Event,
+        relays: &[String],
+        priority: OutboxPriority,
+        error: &str,
+        now: i64,
+    ) -> Result<bool> {
+        if outbox_expires_at(event, priority, now) <= now || self.is_paused(event, priority)? {
+            return Ok(false);
+        }
+        self.storage.enqueue_outbox_event(
+            event,
+            relays,
+            priority,
+            &redact_hex_sequences(error),
+            now.saturating_add(outbox_backoff_secs(1)),
+            now,
+        )
+    }
+
+    /// Every entry still pending or recently finished, newest first.
+    ///
+    /// # Errors
+    ///
+    /// Returns a database error on failure.
+    pub fn status(&self) -> Result<Vec<OutboxEntry>> {
+        self.storage.list_outbox()
+    }
+
+    /// Whether a flush is running.
+    #[must_use]
+    pub fn is_flushing(&self) -> bool {
+        self.flushing.load(Ordering::Acquire)
+    }
+
+    /// Re-sends pending events through `publish`.
+    ///
+    /// With `reconnected` every pending event is retried; otherwise only
+    /// those whose backoff has passed at `now`. Higher priorities go first;
+    /// entries past their deadline are expired, and location updates while
+    /// sharing is paused dropped, without calling `publish`.
+    /// `publish` resolves to `Ok`
+    /// once at least one relay accepted the event, or to the failure reason.
+    /// A flush started while another runs returns empty counts at once.
+    ///
+    /// # Errors
+    ///
+    /// Returns a database error on failure.
+    pub async fn flush<F, Fut>(
+        &self,
+        now: i64,
+        reconnected: bool,
+        mut publish: F,
+    ) -> Result<OutboxFlush>
+    where
+        F: FnMut(Event, Vec<String>) -> Fut,
+        Fut: Future<Output = std::result::Result<(), String>>,
+    {
+        if self.flushing.swap(true, Ordering::AcqRel) {
+            return Ok(OutboxFlush::default());
+        }
+        let _guard = FlushGuard(&self.flushing);
+
+        self.storage
+            .prune_outbox(now.saturating_sub(OUTBOX_FINISHED_RETENTION_SECS))?;
+        let due_before = if reconnected { i64::MAX } else { now };
+        let mut outcome = OutboxFlush::default();
+        for queued in self
+            .storage
+            .due_outbox_events(due_before, OUTBOX_FLUSH_BATCH)?
+        {
+            if queued.expires_at <= now {
+                self.storage
+                    .finish_outbox_event(queued.id, OutboxStatus::Expired, None, now)?;
+                outcome.expired += 1;
+                continue;
+            }
+            let Ok(event) = serde_json::from_str::<Event>(&queued.event_json) else {
+                self.storage.finish_outbox_event(
+                    queued.id,
+                    OutboxStatus::Abandoned,
+                    Some("unreadable event"),
+                    now,
+                )?;
+                outcome.abandoned += 1;
+                continue;
+            };
+            if self.is_paused(&event, queued.priority)? {
+                self.storage
+                    .finish_outbox_event(queued.id, OutboxStatus::Dropped, None, now)?;
+                outcome.dropped += 1;
+                continue;
+            }
+            match publish(event, queued.relays).await {
+                Ok(()) => {
+                    self.storage
+                        .finish_outbox_event(queued.id, OutboxStatus::Sent, None, now)?;
+                    outcome.sent += 1;
+                }
+                Err(error) => {
+                    let error = redact_hex_sequences(&error);
+                    let attempts = queued.attempts.saturating_add(1);
+                    if attempts >= OUTBOX_MAX_ATTEMPTS {
+                        self.storage.finish_outbox_event(
+                            queued.id,
+                            OutboxStatus::Abandoned,
+                            Some(&error),
+                            now,
+                        )?;
+                        outcome.abandoned += 1;
+                    } else {
+                        self.storage.reschedule_outbox_event(
+                            queued.id,
+                            attempts,
+                            now.saturating_add(outbox_backoff_secs(attempts)),
+                            &error,
+                            now,
+                        )?;
+                        outcome.rescheduled += 1;
+                    }
+                }
+            }
+        }
+        Ok(outcome)
+    }
+
+    /// Whether `event`, queued at `priority`, is a location update to a
+    /// circle the user paused sharing to. An event without a readable `h`
+    /// tag only follows the global pause.
+    fn is_paused(&self, event: &Event, priority: OutboxPriority) -> Result<bool> {
+        if !matches!(
+            priority,
+            OutboxPriority::Location | OutboxPriority::LiveShare
+        ) {
+            return Ok(false);
+        }
+        let nostr_group_id = event
+            .tags
+            .iter()
+            .map(nostr::Tag::as_slice)
+            .find(|tag| tag.first().map(String::as_str) == Some("h"))
+            .and_then(|tag| tag.get(1))
+            .and_then(|id| hex::decode(id).ok());
+        match nostr_group_id {
+            Some(id) => self.storage.is_location_paused_for_nostr_group(&id),
+            None => Ok(self.storage.location_paused_at()?.is_some()),
+        }
+    }
+}
+
+/// Clears [`PublishQueue::flushing`] when a flush ends, even on error.
+struct FlushGuard<'a>(&'a AtomicBool);
+
+impl Drop for FlushGuard<'_> {
+    fn drop(&mut self) {
+        self.0.store(false, Ordering::Release);
+    }
+}
+
+/// Process-wide outbox consulted by every `RelayManager`.
+static ACTIVE_PUBLISH_QUEUE: RwLock<Option<Arc<PublishQueue>>> = RwLock::new(None);
+
+/// Installs `queue` as the process-wide outbox, replacing any previous one.
+pub fn install_publish_queue(queue: Arc<PublishQueue>) {
+    // A poisoned lock only means a previous writer panicked mid-assignment;
+    // overwriting the value is still correct.
+    let mut guard = ACTIVE_PUBLISH_QUEUE
+        .write()
+        .unwrap_or_else(std::sync::PoisonError::into_inner);
+    *guard = Some(queue);
+}
+
+/// Returns the installed outbox, if any.
+#[must_use]
+pub fn active_publish_queue() -> Option<Arc<PublishQueue>> {
+    ACTIVE_PUBLISH_QUEUE
+        .read()
+        .unwrap_or_else(std::sync::PoisonError::into_inner)
+        .clone()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use nostr::{EventBuilder, Keys, Kind, Tag, Timestamp};
+
+    const RELAY: &str = "wss://relay.example.com";
+
+    fn queue() -> PublishQueue {
+        PublishQueue::new(Arc::new(CircleStorage::in_memory().expect("in_memory")))
+    }
+
+    fn event(expiration: Option<u64>) -> Event {
+        let mut builder = EventBuilder::new(Kind::Custom(445), "ciphertext");
+        if let Some(secs) = expiration {
+            builder = builder.tag(Tag::expiration(Timestamp::from(secs)));
+        }
+        builder.sign_with_keys(&Keys::generate()).unwrap()
+    }
+
+    fn relays() -> Vec<String> {
+        vec![RELAY.to_string()]
+    }
+
+    #[test]
+    fn backoff_doubles_and_is_capped() {
+        assert_eq!(outbox_backoff_secs(1), 30);
+        assert_eq!(outbox_backoff_secs(2), 60);
+        assert_eq!(outbox_backoff_secs(3), 120);
+        assert_eq!(outbox_backoff_secs(20), OUTBOX_MAX_BACKOFF_SECS);
+        assert_eq!(outbox_backoff_secs(u32::MAX), OUTBOX_MAX_BACKOFF_SECS);
+    }
+
+    #[test]
+    fn expiry_is_the_event_expiration_capped_at_the_deadline() {
+        let housekeeping = OutboxPriority::Housekeeping;
+        assert_eq!(outbox_expires_at(&event(Some(500)), housekeeping, 100), 500);
+        assert_eq!(
+            outbox_expires_at(&event(None), housekeeping, 100),
+            100 + OUTBOX_MAX_AGE_SECS
+        );
+        assert_eq!(
+            outbox_expires_at(&event(Some(u64::MAX / 2)), housekeeping, 100),
+            100 + OUTBOX_MAX_AGE_SECS
+        );
+        assert_eq!(
+            outbox_expires_at(&event(None), OutboxPriority::LiveShare, 100),
+            100 + OUTBOX_LIVE_SHARE_DEADLINE_SECS
+        );
+        assert_eq!(
+            outbox_expires_at(&event(Some(150)), OutboxPriority::Sos, 100),
+            150
+        );
+    }
+
+    #[test]
+    fn expired_or_duplicate_events_are_not_queued() {
+        let queue = queue();
+        assert!(!queue
+            .enqueue(
+                &event(Some(100)),
+                &relays(),
+                OutboxPriority::Location,
+                "down",
+                100
+            )
+            .unwrap());
+        let ev = event(None);
+        assert!(queue
+            .enqueue(&ev, &relays(), OutboxPriority::Location, "down", 100)
+            .unwrap());
+        assert!(!queue
+            .enqueue(&ev, &relays(), OutboxPriority::Location, "down", 101)
+            .unwrap());
+
+        let status = queue.status().unwrap();
+        assert_eq!(status.len(), 1);
+        assert_eq!(status[0].event_id, ev.id.to_hex());
+        assert_eq!(status[0].status, OutboxStatus::Pending);
+        assert_eq!(status[0].attempts, 1);
+        assert_eq!(status[0].next_attempt_at, 100 + OUTBOX_BASE_BACKOFF_SECS);
+        assert_eq!(status[0].relays, relays());
+    }
+
+    #[tokio::test]
+    async fn flush_waits_for_backoff_unless_reconnected() {
+        let queue = queue();
+        let ev = event(None);
+        queue
+            .enqueue(&ev, &relays(), OutboxPriority::Location, "down", 100)
+            .unwrap();
+
+        let mut sent = Vec::new();
+        let outcome = queue
+            .flush(101, false, |event, _| {
+                sent.push(event.id);
+                async { Ok(()) }
+            })
+            .await
+            .unwrap();
+        assert_eq!(outcome, OutboxFlush::default());
+        assert!(sent.is_empty());
+
+        let outcome = queue
+            .flush(101, true, |event, relays| {
+                assert_eq!(relays, [RELAY]);
+                sent.push(event.id);
+                async { Ok(()) }
+            })
+            .await
+            .unwrap();
+        assert_eq!(outcome.sent, 1);
+        assert_eq!(sent, [ev.id]);
+        assert_eq!(queue.status().unwrap()[0].status, OutboxStatus::Sent);
+
+        // A sent entry is not re-sent and is pruned after the retention.
+        let outcome = queue
+            .flush(
+                101 + OUTBOX_FINISHED_RETENTION_SECS + 1,
+                true,
+                |_, _| async { Err("unexpected".to_string()) },
+            )
+            .await
+            .unwrap();
+        assert_eq!(outcome, OutboxFlush::default());
+        assert!(queue.status().unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn paused_sharing_drops_queued_locations() {
+        let storage = Arc::new(CircleStorage::in_memory().expect("in_memory"));
+        let queue = PublishQueue::new(Arc::clone(&storage));
+        let location = event(None);
+        let housekeeping = event(None);
+        queue
+            .enqueue(&location, &relays(), OutboxPriority::Location, "down", 100)
+            .unwrap();
+        queue
+            .enqueue(
+                &housekeeping,
+                &relays(),
+                OutboxPriority::Housekeeping,
+                "down",
+                100,
+            )
+            .unwrap();
+
+        storage.set_location_paused(Some(100)).unwrap();
+        assert!(!queue
+            .enqueue(
+                &event(None),
+                &relays(),
+                OutboxPriority::LiveShare,
+                "down",
+                100
+            )
+            .unwrap());
+
+        let mut sent = Vec::new();
+        let outcome = queue
+            .flush(101, true, |event, _| {
+                sent.push(event.id);
+                async { Ok(()) }
+            })
+            .await
+            .unwrap();
+        assert_eq!(outcome.dropped, 1);
+        assert_eq!(outcome.sent, 1);
+        assert_eq!(sent, [housekeeping.id]);
+        let status = queue.status().unwrap();
+        let dropped = status
+            .iter()
+            .find(|e| e.event_id == location.id.to_hex())
+            .unwrap();
+        assert_eq!(dropped.status, OutboxStatus::Dropped);
+    }
+
+    #[tokio::test]
+    async fn failures_reschedule_then_abandon() {
+        let queue = queue();
+        queue
+            .enqueue(
+                &event(None),
+                &relays(),
+                OutboxPriority::Housekeeping,
+                "down",
+                0,
+            )
+            .unwrap();
+
+        let mut now = 0;
+        for attempt in 2..OUTBOX_MAX_ATTEMPTS {
+            now += OUTBOX_MAX_BACKOFF_SECS;
+            let outcome = queue
+                .flush(now, false, |_, _| async { Err("still down".to_string()) })
+                .await
+                .unwrap();
+            assert_eq!(outcome.rescheduled, 1);
+            let entry = &queue.status().unwrap()[0];
+            assert_eq!(entry.attempts, attempt);
+            assert_eq!(entry.next_attempt_at, now + outbox_backoff_secs(attempt));
+            assert_eq!(entry.last_error.as_deref(), Some("still down"));
+        }
+
+        let outcome = queue
+            .flush(now + OUTBOX_MAX_BACKOFF_SECS, false, |_, _| async {
+                Err("still down".to_string())
+            })
+            .await
+            .unwrap();
+        assert_eq!(outcome.abandoned, 1);
+        assert_eq!(queue.status().unwrap()[0].status, OutboxStatus::Abandoned);
+    }
+
+    #[tokio::test]
+    async fn expired_entries_are_dropped_unsent() {
+        let queue = queue();
+        queue
+            .enqueue(
+                &event(Some(200)),
+                &relays(),
+                OutboxPriority::Location,
+                "down",
+                100,
+            )
+            .unwrap();
+        let outcome = queue
+            .flush(200, true, |_, _| async { Ok(()) })
+            .await
+            .unwrap();
+        assert_eq!(outcome.expired, 1);
+        assert_eq!(outcome.sent, 0);
+        assert_eq!(queue.status().unwrap()[0].status, OutboxStatus::Expired);
+    }
+
+    #[tokio::test]
+    async fn deadline_expired_entries_never_reach_relays() {
+        let queue = queue();
+        queue
+            .enqueue(
+                &event(None),
+                &relays(),
+                OutboxPriority::LiveShare,
+                "down",
+                0,
+            )
+            .unwrap();
+        queue
+            .enqueue(&event(None), &relays(), OutboxPriority::Location, "down", 0)
+            .unwrap();
+
+        let mut published = 0;
+        let outcome = queue
+            .flush(OUTBOX_LOCATION_DEADLINE_SECS, true, |_, _| {
+                published += 1;
+                async { Ok(()) }
+            })
+            .await
+            .unwrap();
+        assert_eq!(published, 0);
+        assert_eq!(outcome.expired, 2);
+        assert_eq!(outcome.sent, 0);
+        assert!(queue
+            .status()
+            .unwrap()
+            .iter()
+            .all(|e| e.status == OutboxStatus::Expired));
+
+        // Still expired, so a later flush has nothing to send either.
+        let outcome = queue
+            .flush(OUTBOX_LOCATION_DEADLINE_SECS + 1, true, |_, _| async {
+                Err("unexpected".to_string())
+            })
+            .await
+            .unwrap();
+        assert_eq!(outcome, OutboxFlush::default());
+    }
+
+    #[tokio::test]
+    async fn live_share_past_its_deadline_is_dropped_while_locations_are_sent() {
+        let queue = queue();
+        let live = event(None);
+        let location = event(None);
+        queue
+            .enqueue(&live, &relays(), OutboxPriority::LiveShare, "down", 0)
+            .unwrap();
+        queue
+            .enqueue(&location, &relays(), OutboxPriority::Location, "down", 0)
+            .unwrap();
+
+        let mut sent = Vec::new();
+        let outcome = queue
+            .flush(OUTBOX_LIVE_SHARE_DEADLINE_SECS, true, |event, _| {
+                sent.push(event.id);
+                async { Ok(()) }
+            })
+            .await
+            .unwrap();
+        assert_eq!(sent, [location.id]);
+        assert_eq!(outcome.expired, 1);
+        assert_eq!(outcome.sent, 1);
+    }
+
+    #[tokio::test]
+    async fn higher_priorities_are_sent_first() {
+        let queue = queue();
+        let housekeeping = event(None);
+        let location = event(None);
+        let sos = event(None);
+        let live = event(None);
+        for (ev, priority) in [
+            (&housekeeping, OutboxPriority::Housekeeping),
+            (&location, OutboxPriority::Location),
+            (&sos, OutboxPriority::Sos),
+            (&live, OutboxPriority::LiveShare),
+        ] {
+            queue.enqueue(ev, &relays(), priority, "down", 0).unwrap();
+        }
+
+        let mut sent = Vec::new();
+        queue
+            .flush(1, true, |event, _| {
+                sent.push(event.id);
+                async { Ok(()) }
+            })
+            .await
+            .unwrap();
+        assert_eq!(sent, [sos.id, live.id, location.id, housekeeping.id]);
+    }
+
+    #[test]
+    fn priorities_roundtrip_and_order() {
+        for priority in [
+            OutboxPriority::Housekeeping,
+            OutboxPriority::Location,
+            OutboxPriority::LiveShare,
+            OutboxPriority::Sos,
+        ] {
+            assert_eq!(OutboxPriority::parse(priority.as_str()), Some(priority));
+            assert_eq!(OutboxPriority::from_rank(priority.rank()), Some(priority));
+        }
+        assert_eq!(OutboxPriority::parse("bogus"), None);
+        assert!(OutboxPriority::Sos > OutboxPriority::LiveShare);
+        assert!(OutboxPriority::LiveShare > OutboxPriority::Location);
+        assert!(OutboxPriority::Location > OutboxPriority::Housekeeping);
+    }
+
+    #[test]
+    fn last_error_is_redacted() {
+        let queue = queue();
+        let error = format!("relay said {}", "ab".repeat(32));
+        queue
+            .enqueue(&event(None), &relays(), OutboxPriority::Location, &error, 0)
+            .unwrap();
+        let stored = queue.status().unwrap()[0].last_error.clone().unwrap();
+        assert!(!stored.contains(&"ab".repeat(32)));
+    }
+
+    #[test]
+    fn status_slugs_roundtrip() {
+        for status in [
+            OutboxStatus::Pending,
+            OutboxStatus::Sent,
+            OutboxStatus::Expired,
+            OutboxStatus::Abandoned,
+            OutboxStatus::Dropped,
+        ] {
+            assert_eq!(OutboxStatus::parse(status.as_str()), Some(status));
+        }
+        assert_eq!(OutboxStatus::parse("bogus"), None);
+    }
+}
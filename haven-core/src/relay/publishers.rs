@@ -36,9 +36,10 @@
 
 use chrono::Utc;
 use nostr::nips::nip01::Coordinate;
-use nostr::{nips::nip09::EventDeletionRequest, EventBuilder, EventId, Keys, Kind, Tag, Timestamp};
+use nostr::{nips::nip09::EventDeletionRequest, EventBuilder, EventId, Kind, Tag, Timestamp};
 
 use crate::circle::relay_prefs::RelayType;
+use crate::nostr::identity::{sign_event, Signer};
 
 /// Errors raised by event-building helpers.
 ///
@@ -130,7 +131,7 @@ pub(crate) fn dedup_key(url: &str) -> String {
 /// well-formed URLs cannot fail; we still propagate any error from
 /// `Tag::parse` defensively.
 pub fn build_relay_list_event(
-    keys: &Keys,
+    signer: &dyn Signer,
     relay_type: RelayType,
     urls: &[String],
     created_at: Option<i64>,
@@ -149,9 +150,7 @@ pub fn build_relay_list_event(
         let ts = u64::try_from(ts).unwrap_or(0);
         builder = builder.custom_created_at(Timestamp::from_secs(ts));
     }
-    builder
-        .sign_with_keys(keys)
-        .map_err(|e| PublisherError::Build(format!("sign: {e}")))
+    sign_event(builder, signer).map_err(|e| PublisherError::Build(format!("sign: {e}")))
 }
 
 /// Builds a signed NIP-65 relay-list event (kind 10002).
@@ -172,7 +171,7 @@ pub fn build_relay_list_event(
 ///
 /// Returns [`PublisherError::Build`] if a relay tag or signing fails.
 pub fn build_nip65_relay_list_event(
-    keys: &Keys,
+    signer: &dyn Signer,
     urls: &[String],
     created_at: Option<i64>,
 ) -> PublisherResult<nostr::Event> {
@@ -189,9 +188,7 @@ pub fn build_nip65_relay_list_event(
         let ts = u64::try_from(ts).unwrap_or(0);
         builder = builder.custom_created_at(Timestamp::from_secs(ts));
     }
-    builder
-        .sign_with_keys(keys)
-        .map_err(|e| PublisherError::Build(format!("sign: {e}")))
+    sign_event(builder, signer).map_err(|e| PublisherError::Build(format!("sign: {e}")))
 }
 
 /// Builds the "empty replacement" event used to unpublish a relay list.
@@ -209,16 +206,18 @@ pub fn build_nip65_relay_list_event(
 ///
 /// Returns [`PublisherError::Build`] if signing fails.
 pub fn build_unpublish_event(
-    keys: &Keys,
+    signer: &dyn Signer,
     relay_type: RelayType,
     last_published_at: Option<i64>,
 ) -> PublisherResult<nostr::Event> {
     let created_at_secs = superseding_created_at(last_published_at);
     let created_at_u = u64::try_from(created_at_secs).unwrap_or(0);
-    EventBuilder::new(relay_type.to_kind(), "")
-        .custom_created_at(Timestamp::from_secs(created_at_u))
-        .sign_with_keys(keys)
-        .map_err(|e| PublisherError::Build(format!("sign: {e}")))
+    sign_event(
+        EventBuilder::new(relay_type.to_kind(), "")
+            .custom_created_at(Timestamp::from_secs(created_at_u)),
+        signer,
+    )
+    .map_err(|e| PublisherError::Build(format!("sign: {e}")))
 }
 
 /// The `created_at` (Unix seconds) for a replaceable relay-list republish that
@@ -261,16 +260,41 @@ pub fn superseding_created_at(last_published_at: Option<i64>) -> i64 {
 ///
 /// Returns [`PublisherError::Build`] if signing fails.
 pub fn build_nip09_deletion(
-    keys: &Keys,
+    signer: &dyn Signer,
     event_id: EventId,
     kind: Kind,
 ) -> PublisherResult<nostr::Event> {
-    let coordinate = Coordinate::new(kind, keys.public_key());
+    let coordinate = Coordinate::new(kind, signer.public_key());
     let request = EventDeletionRequest::new()
         .ids(vec![event_id])
         .coordinate(coordinate);
-    EventBuilder::delete(request)
-        .sign_with_keys(keys)
+    sign_event(EventBuilder::delete(request), signer)
+        .map_err(|e| PublisherError::Build(format!("sign deletion: {e}")))
+}
+
+/// Builds a NIP-09 (kind 5) deletion request for regular events by id.
+///
+/// Id-only (`e` tags), with no coordinate: the targets are regular events, so
+/// a coordinate would widen the request to every event of that kind. `reason`
+/// becomes the deletion's `content`. Relays honor the request only when it is
+/// signed by the key that authored the targets, so `signer` must be that key.
+///
+/// # Errors
+///
+/// Returns [`PublisherError::Build`] if `event_ids` is empty or signing fails.
+pub fn build_event_deletion(
+    signer: &dyn Signer,
+    event_ids: &[EventId],
+    reason: Option<&str>,
+) -> PublisherResult<nostr::Event> {
+    if event_ids.is_empty() {
+        return Err(PublisherError::Build("no event ids to delete".to_owned()));
+    }
+    let mut request = EventDeletionRequest::new().ids(event_ids.iter().copied());
+    if let Some(reason) = reason.filter(|r| !r.is_empty()) {
+        request = request.reason(reason);
+    }
+    sign_event(EventBuilder::delete(request), signer)
         .map_err(|e| PublisherError::Build(format!("sign deletion: {e}")))
 }
 
@@ -278,7 +302,7 @@ pub fn build_nip09_deletion(
 mod tests {
     use super::*;
     use crate::circle::default_relays;
-    use nostr::Kind;
+    use nostr::{Keys, Kind};
 
     fn keys() -> Keys {
         Keys::generate()
@@ -515,4 +539,31 @@ mod tests {
             deletion.tags
         );
     }
+
+    #[test]
+    fn event_deletion_is_id_only_with_reason() {
+        let k = keys();
+        let a = nostr::EventBuilder::new(Kind::TextNote, "a")
+            .sign_with_keys(&k)
+            .unwrap();
+        let b = nostr::EventBuilder::new(Kind::TextNote, "b")
+            .sign_with_keys(&k)
+            .unwrap();
+        let deletion = build_event_deletion(&k, &[a.id, b.id], Some("shared by mistake")).unwrap();
+        assert_eq!(deletion.kind, Kind::EventDeletion);
+        assert_eq!(deletion.pubkey, k.public_key());
+        assert_eq!(deletion.content, "shared by mistake");
+        let e_tags: Vec<String> = deletion
+            .tags
+            .iter()
+            .filter(|t| t.as_slice().first().map(String::as_str) == Some("e"))
+            .map(|t| t.as_slice()[1].clone())
+            .collect();
+        assert_eq!(e_tags, [a.id.to_hex(), b.id.to_hex()]);
+        assert!(!deletion
+            .tags
+            .iter()
+            .any(|t| t.as_slice().first().map(String::as_str) == Some("a")));
+        assert!(build_event_deletion(&k, &[], None).is_err());
+    }
 }
@@ -0,0 +1,391 @@
+//! Relay capability probing (NIP-11) and publish-target scoring.
+//!
+//! A relay advertises what it accepts in a NIP-11 information document,
+//! served over HTTPS at the relay's own URL when asked with
+//! `Accept: application/nostr+json`. Publishing to a relay that requires
+//! payment or NIP-42 auth, or whose message limit is smaller than the event,
+//! only ever fails — and for a `KeyPackage` that silently leaves the user
+//! uninvitable there. [`RelayManager::fetch_relay_info`] fetches the document
+//! and [`rank_relays`] orders a publish's targets by [`score_relay`],
+//! dropping those that cannot take the event.
+//!
+//! Documents are persisted in `circles.db` (see
+//! `CircleStorage::save_relay_info`) and treated as fresh for
+//! [`RELAY_INFO_TTL_SECS`]. A relay with no fresh document is scored neutrally,
+//! never skipped: the limits are advisory, and probing is best effort.
+//!
+//! # Privacy
+//!
+//! The fetch goes over the same anti-SSRF HTTPS client as profile pictures
+//! (redirects refused, non-public addresses blocked). Haven ships no Tor
+//! client, so the operator sees this device's IP address — but only relays
+//! the user already publishes to are probed, and they see that address on
+//! every WebSocket connection anyway.
+//!
+//! [`RelayManager::fetch_relay_info`]: super::RelayManager::fetch_relay_info
+
+use std::collections::HashMap;
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+
+use super::blacklist::canonical_relay_url;
+use super::error::{RelayError, RelayResult};
+use super::pow::MAX_POW_DIFFICULTY;
+use crate::profile::blossom::{
+    download_client, preflight_ssrf_check, read_body_capped, require_https,
+};
+
+/// How long a fetched document is trusted before it is fetched again.
+pub const RELAY_INFO_TTL_SECS: i64 = 24 * 60 * 60;
+
+/// Largest information document accepted.
+pub const RELAY_INFO_MAX_BYTES: u64 = 64 * 1024;
+
+/// Timeout for one information-document fetch.
+pub const RELAY_INFO_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Bytes a serialized `["EVENT", …]` message adds around the event JSON.
+const EVENT_MESSAGE_OVERHEAD: usize = 16;
+
+/// What a relay advertises in its NIP-11 document.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RelayInfo {
+    /// The relay's self-declared name.
+    pub name: Option<String>,
+    /// NIPs the relay says it supports.
+    pub supported_nips: Vec<u16>,
+    /// Largest websocket message the relay accepts, in bytes.
+    pub max_message_length: Option<u64>,
+    /// Largest event `content` the relay accepts, in characters.
+    pub max_content_length: Option<u64>,
+    /// The relay requires NIP-42 authentication.
+    pub auth_required: bool,
+    /// The relay requires payment before it accepts events.
+    pub payment_required: bool,
+    /// The relay only accepts writes from some users.
+    pub restricted_writes: bool,
+    /// Minimum NIP-13 proof-of-work difficulty for accepted events.
+    pub min_pow_difficulty: Option<u8>,
+}
+
+impl RelayInfo {
+    /// Returns `true` if the relay says it supports `nip`.
+    #[must_use]
+    pub fn supports(&self, nip: u16) -> bool {
+        self.supported_nips.contains(&nip)
+    }
+}
+
+#[derive(Deserialize)]
+struct Nip11Document {
+    #[serde(default)]
+    name: Option<String>,
+    #[serde(default)]
+    supported_nips: Vec<serde_json::Value>,
+    #[serde(default)]
+    limitation: Nip11Limitation,
+}
+
+#[derive(Default, Deserialize)]
+struct Nip11Limitation {
+    #[serde(default)]
+    max_message_length: Option<u64>,
+    #[serde(default)]
+    max_content_length: Option<u64>,
+    #[serde(default)]
+    auth_required: bool,
+    #[serde(default)]
+    payment_required: bool,
+    #[serde(default)]
+    restricted_writes: bool,
+    #[serde(default)]
+    min_pow_difficulty: Option<u64>,
+}
+
+/// Parses a NIP-11 information document.
+///
+/// Unknown fields are ignored, as are `supported_nips` entries that are not
+/// small integers (some relays list strings).
+///
+/// # Errors
+///
+/// Returns [`RelayError::Fetch`] if the body is not a JSON object.
+pub fn parse_relay_info(body: &[u8]) -> RelayResult<RelayInfo> {
+    let doc: Nip11Document = serde_json::from_slice(body)
+        .map_err(|e| RelayError::Fetch(format!("malformed relay information document: {e}")))?;
+    let mut supported_nips: Vec<u16> = doc
+        .supported_nips
+        .iter()
+        .filter_map(|v| v.as_u64().and_then(|n| u16::try_from(n).ok()))
+        .collect();
+    supported_nips.sort_unstable();
+    supported_nips.dedup();
+    Ok(RelayInfo {
+        name: doc.name.map(|n| n.chars().take(64).collect()),
+        supported_nips,
+        max_message_length: doc.limitation.max_message_length.filter(|n| *n > 0),
+        max_content_length: doc.limitation.max_content_length.filter(|n| *n > 0),
+        auth_required: doc.limitation.auth_required,
+        payment_required: doc.limitation.payment_required,
+        restricted_writes: doc.limitation.restricted_writes,
+        min_pow_difficulty: doc
+            .limitation
+            .min_pow_difficulty
+            .filter(|d| *d > 0)
+            .map(|d| u8::try_from(d).unwrap_or(u8::MAX)),
+    })
+}
+
+/// The `https://` URL a relay serves its information document from.
+///
+/// # Errors
+///
+/// Returns [`RelayError::InvalidUrl`] if `relay` is not a `wss://` URL.
+pub fn relay_info_url(relay: &str) -> RelayResult<url::Url> {
+    let mut url =
+        url::Url::parse(relay.trim()).map_err(|_| RelayError::InvalidUrl(relay.to_string()))?;
+    if url.scheme() != "wss" || url.set_scheme("https").is_err() {
+        return Err(RelayError::InvalidUrl(relay.to_string()));
+    }
+    Ok(url)
+}
+
+/// Fetches and parses `relay`'s information document.
+pub(super) async fn fetch_relay_info(relay: &str) -> RelayResult<RelayInfo> {
+    let url = relay_info_url(relay)?;
+    let fetch_err = |e: crate::profile::ProfileError| RelayError::Fetch(e.to_string());
+    require_https(&url).map_err(fetch_err)?;
+    preflight_ssrf_check(&url).await.map_err(fetch_err)?;
+    let client = download_client().map_err(fetch_err)?;
+    let request = client
+        .get(url)
+        .header(reqwest::header::ACCEPT, "application/nostr+json");
+    let response = tokio::time::timeout(RELAY_INFO_TIMEOUT, request.send())
+        .await
+        .map_err(|_| RelayError::Timeout("relay information document".to_string()))?
+        .map_err(|e| RelayError::Fetch(e.to_string()))?;
+    if !response.status().is_success() {
+        return Err(RelayError::Fetch(format!("status {}", response.status())));
+    }
+    let body = read_body_capped(response, RELAY_INFO_MAX_BYTES)
+        .await
+        .map_err(fetch_err)?;
+    parse_relay_info(&body)
+}
+
+/// What a publish needs from a relay.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct PublishNeeds {
+    /// Serialized size of the event, in bytes.
+    pub event_bytes: usize,
+    /// Length of the event's `content`, in characters.
+    pub content_chars: usize,
+}
+
+impl PublishNeeds {
+    /// The needs of publishing `event`.
+    #[must_use]
+    pub fn of(event: &nostr::Event) -> Self {
+        Self {
+            event_bytes: nostr::JsonUtil::as_json(event).len(),
+            content_chars: event.content.chars().count(),
+        }
+    }
+}
+
+/// The size limit `info` advertises that an event with `needs` exceeds, if
+/// any.
+///
+/// Returns `Some(limit)` — the message limit in bytes, or the content limit
+/// in characters — when the relay says it cannot take an event that large.
+#[must_use]
+pub fn exceeded_size_limit(info: &RelayInfo, needs: PublishNeeds) -> Option<u64> {
+    let message_bytes = needs.event_bytes.saturating_add(EVENT_MESSAGE_OVERHEAD);
+    let too_small = |limit: Option<u64>, need: usize| {
+        limit.filter(|l| usize::try_from(*l).is_ok_and(|l| l < need))
+    };
+    too_small(info.max_message_length, message_bytes)
+        .or_else(|| too_small(info.max_content_length, needs.content_chars))
+}
+
+/// Returns `true` if a relay's rejection `reason` says the event was too
+/// large.
+///
+/// Relays word this freely (`"invalid: event too large"`, `"blocked: max
+/// event size exceeded"`), so this matches on the common phrasings.
+#[must_use]
+pub fn is_size_rejection(reason: &str) -> bool {
+    let reason = reason.to_ascii_lowercase();
+    [
+        "too large",
+        "too big",
+        "too long",
+        "size exceeded",
+        "exceeds",
+    ]
+    .iter()
+    .any(|phrase| reason.contains(phrase))
+}
+
+/// Scores a relay as a publish target for `needs`; higher is better.
+///
+/// Returns `None` if the relay cannot take the event: it requires payment or
+/// NIP-42 auth (Haven does neither), demands more proof of work than
+/// [`MAX_POW_DIFFICULTY`], or advertises a message or content limit below
+/// the event's size. A relay with no known document scores `0`.
+#[must_use]
+pub fn score_relay(info: Option<&RelayInfo>, needs: PublishNeeds) -> Option<i32> {
+    let Some(info) = info else {
+        return Some(0);
+    };
+    if info.payment_required
+        || info.auth_required
+        || info.min_pow_difficulty.unwrap_or(0) > MAX_POW_DIFFICULTY
+        || exceeded_size_limit(info, needs).is_some()
+    {
+        return None;
+    }
+
+    // A relay that answered is preferred over an unknown one; mining work and
+    // restricted writes make success less likely.
+    let mut score = 10;
+    if info.restricted_writes {
+        score -= 5;
+    }
+    score -= i32::from(info.min_pow_difficulty.unwrap_or(0));
+    if info.supports(1) {
+        score += 1;
+    }
+    Some(score)
+}
+
+/// Orders `relays` for a publish with `needs`, best first, dropping relays
+/// [`score_relay`] rules out.
+///
+/// `infos` is keyed by canonical relay URL (as returned by
+/// `CircleStorage::fresh_relay_infos`). Ties keep the input order. If every
+/// relay would be dropped, `relays` is returned unchanged: advertised limits
+/// are advisory, and publishing somewhere beats publishing nowhere.
+#[must_use]
+pub fn rank_relays<S: std::hash::BuildHasher>(
+    relays: &[String],
+    infos: &HashMap<String, RelayInfo, S>,
+    needs: PublishNeeds,
+) -> Vec<String> {
+    let mut scored: Vec<(i32, &String)> = relays
+        .iter()
+        .filter_map(|relay| {
+            let info = canonical_relay_url(relay).and_then(|key| infos.get(&key));
+            score_relay(info, needs).map(|score| (score, relay))
+        })
+        .collect();
+    if scored.is_empty() {
+        return relays.to_vec();
+    }
+    scored.sort_by_key(|(score, _)| std::cmp::Reverse(*score));
+    scored.into_iter().map(|(_, relay)| relay.clone()).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DOC: &str = r#"{
+        "name": "Example relay",
+        "supported_nips": [1, 11, "42", 11, 70000],
+        "limitation": {
+            "max_message_length": 16384,
+            "max_content_length": 8196,
+            "auth_required": false,
+            "payment_required": false,
+            "min_pow_difficulty": 0
+        },
+        "software": "strfry"
+    }"#;
+
+    #[test]
+    fn parses_documents_leniently() {
+        let info = parse_relay_info(DOC.as_bytes()).unwrap();
+        assert_eq!(info.name.as_deref(), Some("Example relay"));
+        assert_eq!(info.supported_nips, vec![1, 11]);
+        assert_eq!(info.max_message_length, Some(16_384));
+        assert_eq!(info.min_pow_difficulty, None);
+        assert!(info.supports(11));
+
+        assert_eq!(parse_relay_info(b"{}").unwrap(), RelayInfo::default());
+        assert!(parse_relay_info(b"<html>").is_err());
+    }
+
+    #[test]
+    fn info_url_uses_https() {
+        assert_eq!(
+            relay_info_url("wss://relay.example.com/sub")
+                .unwrap()
+                .as_str(),
+            "https://relay.example.com/sub"
+        );
+        assert!(relay_info_url("ws://relay.example.com").is_err());
+        assert!(relay_info_url("https://relay.example.com").is_err());
+    }
+
+    #[test]
+    fn scoring_skips_relays_that_cannot_take_the_event() {
+        let needs = PublishNeeds {
+            event_bytes: 20_000,
+            content_chars: 10_000,
+        };
+        assert_eq!(score_relay(None, needs), Some(0));
+
+        let roomy = RelayInfo::default();
+        assert!(score_relay(Some(&roomy), needs) > Some(0));
+        let small = parse_relay_info(DOC.as_bytes()).unwrap();
+        assert_eq!(score_relay(Some(&small), needs), None);
+        assert_eq!(exceeded_size_limit(&small, needs), Some(16_384));
+        assert_eq!(exceeded_size_limit(&roomy, needs), None);
+        let paid = RelayInfo {
+            payment_required: true,
+            ..RelayInfo::default()
+        };
+        assert_eq!(score_relay(Some(&paid), PublishNeeds::default()), None);
+        let auth = RelayInfo {
+            auth_required: true,
+            ..RelayInfo::default()
+        };
+        assert_eq!(score_relay(Some(&auth), PublishNeeds::default()), None);
+        let heavy_pow = RelayInfo {
+            min_pow_difficulty: Some(MAX_POW_DIFFICULTY + 1),
+            ..RelayInfo::default()
+        };
+        assert_eq!(score_relay(Some(&heavy_pow), PublishNeeds::default()), None);
+    }
+
+    #[test]
+    fn ranking_orders_and_drops() {
+        let relays: Vec<String> = [
+            "wss://unknown.example",
+            "wss://good.example",
+            "wss://paid.example",
+        ]
+        .iter()
+        .map(ToString::to_string)
+        .collect();
+        let mut infos = HashMap::new();
+        infos.insert("wss://good.example".to_string(), RelayInfo::default());
+        infos.insert(
+            "wss://paid.example".to_string(),
+            RelayInfo {
+                payment_required: true,
+                ..RelayInfo::default()
+            },
+        );
+        let needs = PublishNeeds::default();
+        assert_eq!(
+            rank_relays(&relays, &infos, needs),
+            vec!["wss://good.example", "wss://unknown.example"]
+        );
+
+        let only_paid = vec!["wss://paid.example".to_string()];
+        assert_eq!(rank_relays(&only_paid, &infos, needs), only_paid);
+    }
+}
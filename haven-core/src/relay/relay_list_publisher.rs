@@ -0,0 +1,299 @@
+//! Debounced republishing of the user's own relay lists.
+//!
+//! Every edit to a relay category (add, remove, restore defaults) changes the
+//! replaceable list the user advertises, but republishing on each edit would
+//! sign and fan out one replaceable event per tap while the user is still
+//! editing. Instead, edits are noted in a [`RelayListDebouncer`] and coalesced:
+//! a category becomes due once it has been quiet for
+//! [`RELAY_LIST_DEBOUNCE_SECS`], or [`RELAY_LIST_MAX_DELAY_SECS`] after its
+//! first unpublished edit, whichever comes first. The list is then signed
+//! once, from the relay set configured at that moment.
+//!
+//! # Targets: old ∪ new
+//!
+//! A relay the user just removed still serves the previous list, which keeps
+//! advertising it to peers. The republish therefore targets the union of the
+//! last published set and the current one ([`plan_relay_list_republish`]), so
+//! the removed relay is superseded too. It learns nothing new beyond the
+//! current set, which the list enumerates anyway once published.
+//!
+//! After a successful publish the caller records the published set (see
+//! `CircleStorage::record_published_relay_set`), which becomes the "old" side
+//! of the next union. Pending edits live in memory only: an edit lost to a
+//! process exit inside the debounce window is healed by the periodic
+//! relay-list maintenance (see [`crate::relay::maintenance::relay_list`]),
+//! which detects the drifted list on the user's relays.
+
+use std::collections::{HashMap, HashSet};
+use std::sync::Mutex;
+
+use nostr::Kind;
+
+use super::publishers::{
+    build_nip65_relay_list_event, build_relay_list_event, dedup_key, dedup_relay_targets,
+    superseding_created_at, PublisherResult,
+};
+use crate::circle::relay_prefs::RelayType;
+use crate::nostr::identity::Signer;
+
+/// How long a category must go without edits before it is republished.
+pub const RELAY_LIST_DEBOUNCE_SECS: i64 = 10;
+
+/// Longest a category's first unpublished edit may wait, so continuous
+/// editing still publishes eventually.
+pub const RELAY_LIST_MAX_DELAY_SECS: i64 = 60;
+
+/// The on-wire kind of a category's list under Dark Matter: 10050 for the
+/// inbox list, NIP-65 kind 10002 for the `KeyPackage`-discovery list (the
+/// kind-10051 list is retired).
+#[must_use]
+pub const fn relay_list_wire_kind(relay_type: RelayType) -> Kind {
+    match relay_type {
+        RelayType::Inbox => Kind::InboxRelays,
+        RelayType::KeyPackage => Kind::RelayList,
+    }
+}
+
+/// Builds the signed list for a category in its on-wire form (see
+/// [`relay_list_wire_kind`]).
+///
+/// # Errors
+///
+/// Returns [`PublisherError::Build`](super::PublisherError::Build) if a tag or
+/// signing fails.
+pub fn build_wire_relay_list_event(
+    signer: &dyn Signer,
+    relay_type: RelayType,
+    urls: &[String],
+    created_at: Option<i64>,
+) -> PublisherResult<nostr::Event> {
+    match relay_type {
+        RelayType::Inbox => build_relay_list_event(signer, RelayType::Inbox, urls, created_at),
+        RelayType::KeyPackage => build_nip65_relay_list_event(signer, urls, created_at),
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+struct PendingEdit {
+    first_at: i64,
+    last_at: i64,
+}
+
+/// Coalesces relay-list edits per category until they are due.
+#[derive(Debug, Default)]
+pub struct RelayListDebouncer {
+    pending: Mutex<HashMap<RelayType, PendingEdit>>,
+}
+
+impl RelayListDebouncer {
+    /// Creates an empty debouncer.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Notes an edit to `relay_type` at `now` (Unix seconds).
+    pub fn note_change(&self, relay_type: RelayType, now: i64) {
+        if let Ok(mut pending) = self.pending.lock() {
+            pending
+                .entry(relay_type)
+                .and_modify(|p| p.last_at = now)
+                .or_insert(PendingEdit {
+                    first_at: now,
+                    last_at: now,
+                });
+        }
+    }
+
+    /// Drops any pending edit for `relay_type` (e.g. publishing was turned off).
+    pub fn discard(&self, relay_type: RelayType) {
+        if let Ok(mut pending) = self.pending.lock() {
+            pending.remove(&relay_type);
+        }
+    }
+
+    /// When the earliest pending category becomes due, if any.
+    #[must_use]
+    pub fn next_due_at(&self) -> Option<i64> {
+        let pending = self.pending.lock().ok()?;
+        pending.values().map(|p| Self::due_at(*p)).min()
+    }
+
+    /// Removes and returns the categories due at `now`.
+    pub fn take_due(&self, now: i64) -> Vec<RelayType> {
+        let Ok(mut pending) = self.pending.lock() else {
+            return Vec::new();
+        };
+        let due: Vec<RelayType> = pending
+            .iter()
+            .filter(|(_, p)| Self::due_at(**p) <= now)
+            .map(|(t, _)| *t)
+            .collect();
+        for relay_type in &due {
+            pending.remove(relay_type);
+        }
+        due
+    }
+
+    const fn due_at(edit: PendingEdit) -> i64 {
+        let quiet = edit.last_at.saturating_add(RELAY_LIST_DEBOUNCE_SECS);
+        let cap = edit.first_at.saturating_add(RELAY_LIST_MAX_DELAY_SECS);
+        if quiet < cap {
+            quiet
+        } else {
+            cap
+        }
+    }
+}
+
+/// One coalesced republish, ready to sign.
+///
+/// Carries own-relay URLs, so it MUST NOT be logged.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RelayListRepublish {
+    /// The category being republished.
+    pub relay_type: RelayType,
+    /// The list content: the currently configured relays.
+    pub relays: Vec<String>,
+    /// Where to publish: the current relays plus any previously published
+    /// relay no longer configured.
+    pub targets: Vec<String>,
+}
+
+impl RelayListRepublish {
+    /// The on-wire kind of this list.
+    #[must_use]
+    pub const fn wire_kind(&self) -> Kind {
+        relay_list_wire_kind(self.relay_type)
+    }
+
+    /// Signs the list, superseding a previous publication at
+    /// `last_published_at` (see [`superseding_created_at`]).
+    ///
+    /// # Errors
+    ///
+    /// Returns [`PublisherError::Build`](super::PublisherError::Build) if
+    /// signing fails.
+    pub fn sign(
+        &self,
+        signer: &dyn Signer,
+        last_published_at: Option<i64>,
+    ) -> PublisherResult<nostr::Event> {
+        build_wire_relay_list_event(
+            signer,
+            self.relay_type,
+            &self.relays,
+            Some(superseding_created_at(last_published_at)),
+        )
+    }
+}
+
+/// Plans the republish of a category from its configured relays and the set
+/// last published (`None` if it never was).
+///
+/// Returns `None` when nothing configured, or when the configured set equals
+/// the published one (compared order-insensitively by scheme/host), e.g.
+/// after an edit that was undone within the debounce window.
+#[must_use]
+pub fn plan_relay_list_republish(
+    relay_type: RelayType,
+    configured: &[String],
+    published: Option<&[String]>,
+) -> Option<RelayListRepublish> {
+    let relays = dedup_relay_targets(configured);
+    if relays.is_empty() {
+        return None;
+    }
+    let published = published.unwrap_or_default();
+    let configured_keys: HashSet<String> = relays.iter().map(|u| dedup_key(u)).collect();
+    let published_keys: HashSet<String> = published.iter().map(|u| dedup_key(u)).collect();
+    if !published.is_empty() && configured_keys == published_keys {
+        return None;
+    }
+    let union: Vec<String> = relays.iter().chain(published).cloned().collect();
+    Some(RelayListRepublish {
+        relay_type,
+        targets: dedup_relay_targets(&union),
+        relays,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use nostr::Keys;
+
+    fn urls(list: &[&str]) -> Vec<String> {
+        list.iter().map(ToString::to_string).collect()
+    }
+
+    #[test]
+    fn edits_coalesce_until_quiet() {
+        let debouncer = RelayListDebouncer::new();
+        debouncer.note_change(RelayType::Inbox, 100);
+        debouncer.note_change(RelayType::Inbox, 105);
+        assert_eq!(
+            debouncer.next_due_at(),
+            Some(105 + RELAY_LIST_DEBOUNCE_SECS)
+        );
+        assert!(debouncer.take_due(110).is_empty());
+        assert_eq!(debouncer.take_due(115), vec![RelayType::Inbox]);
+        assert!(debouncer.take_due(1_000).is_empty());
+        assert_eq!(debouncer.next_due_at(), None);
+    }
+
+    #[test]
+    fn continuous_edits_are_capped() {
+        let debouncer = RelayListDebouncer::new();
+        for t in (0..=RELAY_LIST_MAX_DELAY_SECS).step_by(5) {
+            debouncer.note_change(RelayType::KeyPackage, t);
+        }
+        assert_eq!(debouncer.next_due_at(), Some(RELAY_LIST_MAX_DELAY_SECS));
+        assert_eq!(
+            debouncer.take_due(RELAY_LIST_MAX_DELAY_SECS),
+            vec![RelayType::KeyPackage]
+        );
+    }
+
+    #[test]
+    fn discard_drops_pending() {
+        let debouncer = RelayListDebouncer::new();
+        debouncer.note_change(RelayType::Inbox, 0);
+        debouncer.discard(RelayType::Inbox);
+        assert!(debouncer.take_due(1_000).is_empty());
+    }
+
+    #[test]
+    fn republish_targets_union_of_old_and_new() {
+        let old = urls(&["wss://a.example", "wss://b.example"]);
+        let new = urls(&["wss://B.example", "wss://c.example"]);
+        let plan = plan_relay_list_republish(RelayType::Inbox, &new, Some(&old)).unwrap();
+        assert_eq!(plan.relays, new);
+        assert_eq!(
+            plan.targets,
+            urls(&["wss://B.example", "wss://c.example", "wss://a.example"])
+        );
+        assert_eq!(plan.wire_kind(), Kind::InboxRelays);
+    }
+
+    #[test]
+    fn unchanged_or_empty_sets_need_no_republish() {
+        let set = urls(&["wss://a.example", "wss://b.example"]);
+        let reordered = urls(&["wss://b.example", "wss://a.example"]);
+        assert!(plan_relay_list_republish(RelayType::Inbox, &reordered, Some(&set)).is_none());
+        assert!(plan_relay_list_republish(RelayType::Inbox, &[], Some(&set)).is_none());
+        let first = plan_relay_list_republish(RelayType::KeyPackage, &set, None).unwrap();
+        assert_eq!(first.targets, set);
+    }
+
+    #[test]
+    fn signs_in_wire_form() {
+        let keys = Keys::generate();
+        let plan =
+            plan_relay_list_republish(RelayType::KeyPackage, &urls(&["wss://a.example"]), None)
+                .unwrap();
+        let event = plan.sign(&keys, None).unwrap();
+        assert_eq!(event.kind, Kind::RelayList);
+        assert_eq!(event.pubkey, keys.public_key());
+    }
+}
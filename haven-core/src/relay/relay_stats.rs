@@ -0,0 +1,415 @@
+//! Per-relay health counters for the relay dashboard.
+//!
+//! Families run their own relays and need to see which one is misbehaving.
+//! Every publish a [`RelayManager`](super::RelayManager) makes is counted
+//! here against each relay it went to: accepted or not, how long the relay
+//! took to answer, and the last error it gave. Bytes come from the same
+//! choke points as the [bandwidth meter](super::metrics).
+//!
+//! Latency is the time from sending an event until the publish call
+//! returned. A publish to several relays returns when all have answered (or
+//! timed out), so a fast relay published alongside a slow one is charged
+//! the slow one's time; the average still singles out a relay that is slow
+//! on its own.
+//!
+//! # Persistence
+//!
+//! Unlike the bandwidth meter, these counters survive restarts: once a
+//! [`RelayStatsStore`] is installed ([`install_relay_stats_store`]), they are
+//! written to the `relay_stats` table of `circles.db` at most every
+//! [`RELAY_STATS_PERSIST_INTERVAL_SECS`] and on [`flush_relay_stats`].
+//! Only relay URLs, counts, timestamps and redacted error reasons are
+//! stored — never events, ids or circles. Counters recorded before a store
+//! is installed are added to the persisted ones.
+
+use std::collections::HashMap;
+use std::sync::{Arc, LazyLock, Mutex, RwLock};
+
+use crate::circle::{CircleStorage, Result};
+use crate::nostr::mls::redact_hex_sequences;
+
+/// Shortest interval between two writes of the counters, in seconds.
+pub const RELAY_STATS_PERSIST_INTERVAL_SECS: i64 = 60;
+
+/// Longest stored error reason, in characters.
+pub const MAX_RELAY_ERROR_CHARS: usize = 200;
+
+/// Health counters of one relay.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RelayStats {
+    /// Relay URL.
+    pub url: String,
+    /// Publishes the relay accepted.
+    pub publish_ok: u64,
+    /// Publishes the relay rejected, failed or timed out on.
+    pub publish_failed: u64,
+    /// Sum of the measured publish latencies, in milliseconds.
+    pub latency_ms_total: u64,
+    /// Number of latencies summed in `latency_ms_total`.
+    pub latency_samples: u64,
+    /// Bytes of events sent to the relay.
+    pub bytes_sent: u64,
+    /// Bytes of events received from the relay.
+    pub bytes_received: u64,
+    /// Unix timestamp of the last accepted publish.
+    pub last_ok_at: Option<i64>,
+    /// Unix timestamp of the last failed publish.
+    pub last_error_at: Option<i64>,
+    /// Why the last failed publish failed (redacted, truncated).
+    pub last_error: Option<String>,
+}
+
+impl RelayStats {
+    fn new(url: &str) -> Self {
+        Self {
+            url: url.to_string(),
+            ..Self::default()
+        }
+    }
+
+    /// Average publish latency in milliseconds, if any was measured.
+    #[must_use]
+    pub const fn average_latency_ms(&self) -> Option<u64> {
+        if self.latency_samples == 0 {
+            None
+        } else {
+            Some(self.latency_ms_total / self.latency_samples)
+        }
+    }
+
+    /// Share of publishes that failed, from 0.0 to 1.0 (0.0 with none).
+    #[must_use]
+    #[allow(clippy::cast_precision_loss)]
+    pub fn failure_rate(&self) -> f64 {
+        let total = self.publish_ok.saturating_add(self.publish_failed);
+        if total == 0 {
+            0.0
+        } else {
+            self.publish_failed as f64 / total as f64
+        }
+    }
+
+    fn add(&mut self, other: &Self) {
+        self.publish_ok = self.publish_ok.saturating_add(other.publish_ok);
+        self.publish_failed = self.publish_failed.saturating_add(other.publish_failed);
+        self.latency_ms_total = self.latency_ms_total.saturating_add(other.latency_ms_total);
+        self.latency_samples = self.latency_samples.saturating_add(other.latency_samples);
+        self.bytes_sent = self.bytes_sent.saturating_add(other.bytes_sent);
+        self.bytes_received = self.bytes_received.saturating_add(other.bytes_received);
+        self.last_ok_at = self.last_ok_at.max(other.last_ok_at);
+        if other.last_error_at > self.last_error_at {
+            self.last_error_at = other.last_error_at;
+            self.last_error.clone_from(&other.last_error);
+        }
+    }
+}
+
+/// Counters for every relay seen.
+#[derive(Debug, Clone, Default)]
+pub struct RelayStatsBook {
+    relays: HashMap<String, RelayStats>,
+    dirty: bool,
+    persisted_at: i64,
+}
+
+impl RelayStatsBook {
+    fn entry(&mut self, url: &str) -> &mut RelayStats {
+        self.dirty = true;
+        self.relays
+            .entry(url.to_string())
+            .or_insert_with(|| RelayStats::new(url))
+    }
+
+    /// Counts an accepted publish to `url` that took `latency_ms`.
+    pub fn record_publish_ok(&mut self, url: &str, latency_ms: u64, now: i64) {
+        let stats = self.entry(url);
+        stats.publish_ok = stats.publish_ok.saturating_add(1);
+        stats.latency_ms_total = stats.latency_ms_total.saturating_add(latency_ms);
+        stats.latency_samples = stats.latency_samples.saturating_add(1);
+        stats.last_ok_at = Some(now);
+    }
+
+    /// Counts a failed publish to `url`. `latency_ms` is `None` when the
+    /// relay never answered.
+    pub fn record_publish_failed(
+        &mut self,
+        url: &str,
+        reason: &str,
+        latency_ms: Option<u64>,
+        now: i64,
+    ) {
+        let stats = self.entry(url);
+        stats.publish_failed = stats.publish_failed.saturating_add(1);
+        if let Some(ms) = latency_ms {
+            stats.latency_ms_total = stats.latency_ms_total.saturating_add(ms);
+            stats.latency_samples = stats.latency_samples.saturating_add(1);
+        }
+        stats.last_error_at = Some(now);
+        stats.last_error = Some(sanitize_reason(reason));
+    }
+
+    /// Counts `bytes` sent to `url`.
+    pub fn record_bytes_sent(&mut self, url: &str, bytes: u64) {
+        let stats = self.entry(url);
+        stats.bytes_sent = stats.bytes_sent.saturating_add(bytes);
+    }
+
+    /// Counts `bytes` received from `url`.
+    pub fn record_bytes_received(&mut self, url: &str, bytes: u64) {
+        let stats = self.entry(url);
+        stats.bytes_received = stats.bytes_received.saturating_add(bytes);
+    }
+
+    /// Adds `loaded` counters (e.g. from disk) to these.
+    pub fn merge(&mut self, loaded: &[RelayStats]) {
+        for stats in loaded {
+            self.relays
+                .entry(stats.url.clone())
+                .or_insert_with(|| RelayStats::new(&stats.url))
+                .add(stats);
+        }
+    }
+
+    /// Snapshot of the counters, by URL.
+    #[must_use]
+    pub fn snapshot(&self) -> Vec<RelayStats> {
+        let mut relays: Vec<RelayStats> = self.relays.values().cloned().collect();
+        relays.sort_by(|a, b| a.url.cmp(&b.url));
+        relays
+    }
+}
+
+/// Redacts hex runs, strips control characters and caps the length.
+fn sanitize_reason(reason: &str) -> String {
+    redact_hex_sequences(reason)
+        .chars()
+        .filter(|c| !c.is_control())
+        .take(MAX_RELAY_ERROR_CHARS)
+        .collect()
+}
+
+/// Persists the counters into `circles.db`.
+pub struct RelayStatsStore {
+    storage: Arc<CircleStorage>,
+}
+
+impl std::fmt::Debug for RelayStatsStore {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RelayStatsStore").finish_non_exhaustive()
+    }
+}
+
+impl RelayStatsStore {
+    /// Creates a store persisting into `storage`.
+    #[must_use]
+    pub const fn new(storage: Arc<CircleStorage>) -> Self {
+        Self { storage }
+    }
+
+    fn load(&self) -> Result<Vec<RelayStats>> {
+        self.storage.load_relay_stats()
+    }
+
+    fn save(&self, stats: &[RelayStats]) -> Result<()> {
+        self.storage.save_relay_stats(stats)
+    }
+}
+
+/// Process-wide counters fed by every relay path.
+static BOOK: LazyLock<Mutex<RelayStatsBook>> =
+    LazyLock::new(|| Mutex::new(RelayStatsBook::default()));
+
+static ACTIVE_STORE: RwLock<Option<Arc<RelayStatsStore>>> = RwLock::new(None);
+
+fn book() -> std::sync::MutexGuard<'static, RelayStatsBook> {
+    // Counters are plain integers; a poisoned lock leaves them consistent.
+    BOOK.lock()
+        .unwrap_or_else(std::sync::PoisonError::into_inner)
+}
+
+fn active_store() -> Option<Arc<RelayStatsStore>> {
+    ACTIVE_STORE
+        .read()
+        .unwrap_or_else(std::sync::PoisonError::into_inner)
+        .clone()
+}
+
+/// Installs `store` as the process-wide persistence, replacing any previous
+/// one, and loads its counters.
+///
+/// The first install adds the persisted counters to those recorded so far.
+/// A later install (another account) first flushes to the previous store and
+/// then starts over from the new store's counters.
+pub fn install_relay_stats_store(store: Arc<RelayStatsStore>) {
+    let loaded = store.load();
+    let previous = ACTIVE_STORE
+        .write()
+        .unwrap_or_else(std::sync::PoisonError::into_inner)
+        .replace(store);
+    if let Some(previous) = previous {
+        flush_to(&previous);
+        *book() = RelayStatsBook::default();
+    }
+    match loaded {
+        Ok(loaded) => book().merge(&loaded),
+        Err(e) => log::warn!("[RelayStats] failed to load relay stats: {e}"),
+    }
+    book().persisted_at = chrono::Utc::now().timestamp();
+}
+
+/// Writes the counters to the installed store now, if they changed.
+pub fn flush_relay_stats() {
+    if let Some(store) = active_store() {
+        flush_to(&store);
+    }
+}
+
+fn flush_to(store: &RelayStatsStore) {
+    let snapshot = {
+        let mut book = book();
+        if !book.dirty {
+            return;
+        }
+        book.dirty = false;
+        book.persisted_at = chrono::Utc::now().timestamp();
+        book.snapshot()
+    };
+    if let Err(e) = store.save(&snapshot) {
+        log::warn!("[RelayStats] failed to save relay stats: {e}");
+        book().dirty = true;
+    }
+}
+
+/// Writes the counters if the last write is older than
+/// [`RELAY_STATS_PERSIST_INTERVAL_SECS`].
+fn maybe_flush(now: i64) {
+    let due = {
+        let book = book();
+        book.dirty && now.saturating_sub(book.persisted_at) >= RELAY_STATS_PERSIST_INTERVAL_SECS
+    };
+    if due {
+        flush_relay_stats();
+    }
+}
+
+/// Records the outcome of one publish: `accepted` relays and `rejected`
+/// `(relay, reason)` pairs, after `latency_ms`.
+pub(crate) fn record_publish(accepted: &[String], rejected: &[(String, String)], latency_ms: u64) {
+    let now = chrono::Utc::now().timestamp();
+    {
+        let mut book = book();
+        for url in accepted {
+            book.record_publish_ok(url, latency_ms, now);
+        }
+        for (url, reason) in rejected {
+            book.record_publish_failed(url, reason, Some(latency_ms), now);
+        }
+    }
+    maybe_flush(now);
+}
+
+/// Records a publish to `relays` that failed as a whole (timeout, transport
+/// error) with `reason`.
+pub(crate) fn record_publish_error(relays: &[String], reason: &str) {
+    let now = chrono::Utc::now().timestamp();
+    {
+        let mut book = book();
+        for url in relays {
+            book.record_publish_failed(url, reason, None, now);
+        }
+    }
+    maybe_flush(now);
+}
+
+/// Counts `bytes` sent to `relay`.
+pub(crate) fn record_bytes_sent(relay: &str, bytes: u64) {
+    book().record_bytes_sent(relay, bytes);
+}
+
+/// Counts `bytes` received from `relay`.
+pub(crate) fn record_bytes_received(relay: &str, bytes: u64) {
+    book().record_bytes_received(relay, bytes);
+}
+
+/// Snapshot of the process-wide counters, by URL.
+#[must_use]
+pub fn relay_metrics() -> Vec<RelayStats> {
+    book().snapshot()
+}
+
+/// Zeroes the counters, in memory and in the installed store.
+///
+/// # Errors
+///
+/// Returns an error if the stored counters cannot be deleted.
+pub fn reset_relay_metrics() -> Result<()> {
+    {
+        let mut book = book();
+        *book = RelayStatsBook {
+            persisted_at: book.persisted_at,
+            ..RelayStatsBook::default()
+        };
+    }
+    match active_store() {
+        Some(store) => store.storage.wipe_relay_stats(),
+        None => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const RELAY: &str = "wss://relay.example.com";
+
+    #[test]
+    fn counts_outcomes_and_latency() {
+        let mut book = RelayStatsBook::default();
+        book.record_publish_ok(RELAY, 100, 10);
+        book.record_publish_ok(RELAY, 300, 20);
+        book.record_publish_failed(RELAY, "blocked: rate limited", Some(50), 30);
+        book.record_publish_failed(RELAY, "timed out", None, 40);
+        book.record_bytes_sent(RELAY, 512);
+        book.record_bytes_received(RELAY, 1024);
+
+        let stats = &book.snapshot()[0];
+        assert_eq!(stats.publish_ok, 2);
+        assert_eq!(stats.publish_failed, 2);
+        assert_eq!(stats.average_latency_ms(), Some(150));
+        assert!((stats.failure_rate() - 0.5).abs() < f64::EPSILON);
+        assert_eq!(stats.last_ok_at, Some(20));
+        assert_eq!(stats.last_error_at, Some(40));
+        assert_eq!(stats.last_error.as_deref(), Some("timed out"));
+        assert_eq!((stats.bytes_sent, stats.bytes_received), (512, 1024));
+        assert!(book.dirty);
+    }
+
+    #[test]
+    fn reasons_are_redacted_and_capped() {
+        let id = "ab".repeat(32);
+        let reason = sanitize_reason(&format!("duplicate: {id}\n{}", "x".repeat(500)));
+        assert!(!reason.contains(&id));
+        assert!(!reason.contains('\n'));
+        assert_eq!(reason.chars().count(), MAX_RELAY_ERROR_CHARS);
+    }
+
+    #[test]
+    fn merge_adds_counters_and_keeps_latest_error() {
+        let mut book = RelayStatsBook::default();
+        book.record_publish_failed(RELAY, "new", None, 200);
+        book.merge(&[RelayStats {
+            url: RELAY.to_string(),
+            publish_ok: 5,
+            publish_failed: 1,
+            last_ok_at: Some(150),
+            last_error_at: Some(100),
+            last_error: Some("old".to_string()),
+            ..RelayStats::default()
+        }]);
+        let stats = &book.snapshot()[0];
+        assert_eq!((stats.publish_ok, stats.publish_failed), (5, 2));
+        assert_eq!(stats.last_ok_at, Some(150));
+        assert_eq!(stats.last_error.as_deref(), Some("new"));
+        assert_eq!(RelayStats::default().average_latency_ms(), None);
+    }
+}
@@ -0,0 +1,645 @@
+//! Foreground group-message sync with per-relay cursors.
+//!
+//! One call that, for every accepted circle, fetches the `kind:445` events
+//! each of its relays holds past that relay's cursor, ingests them through
+//! [`CircleManager::decrypt_location_collecting_commits`], persists the
+//! decrypted locations, publishes any receive-side auto-commit, and returns a
+//! digest of what changed. The app no longer runs the per-circle
+//! fetch → decrypt → persist loop itself.
+//!
+//! # Cursors
+//!
+//! Each `(circle, relay)` pair keeps its own `(created_at, event id)` cursor
+//! (the `group_relay_cursors` table), so a relay that was down is caught up
+//! from where it stopped, not from where the healthiest relay is. The next
+//! fetch starts
+//! [`GROUP_RESUBSCRIBE_BUFFER_SECS`](super::cursor::GROUP_RESUBSCRIBE_BUFFER_SECS)
+//! before the cursor so a late concurrent commit is still fetched (the engine
+//! drops re-fetched duplicates as stale); the exact cursor event is skipped
+//! without ingest. A cursor advances only over the contiguous prefix of events
+//! that relay served which ingested without error, so a failed event is
+//! fetched again next time.
+//!
+//! # Backfill
+//!
+//! [`SyncManager::backfill_circle`] reads a circle's history backwards, page
+//! by page ([`RelayManager::fetch_group_messages_paged`]), down to a target
+//! time — so a newly joined member can fill in the last day of locations.
+//! Events older than every relay's cursor were already ingested by a sync
+//! and are skipped; the rest are ingested oldest first, as MLS requires.
+//! Backfill never moves a cursor. Events from before the member joined
+//! cannot be decrypted and are counted as failed.
+//!
+//! # Rule 14 (single session)
+//!
+//! As with [`crate::relay::catchup`], the caller must pass the foreground
+//! `CircleManager`; a second session on the same database would diverge.
+
+use std::collections::{HashMap, HashSet};
+
+use nostr::{Event, EventId};
+
+use crate::circle::{
+    CircleError, CircleManager, CommitToPublish, LastKnownLocation, MembershipStatus,
+};
+use crate::location::LocationMessage;
+use crate::nostr::mls::types::{GroupId, LocationMessageResult, MembershipDelta};
+use crate::nostr::validate_group_event;
+use crate::relay::auto_commit::AutoCommitPublisher;
+use crate::relay::cursor::{since_for_stream, SubscribePhase, STREAM_GROUP_445};
+use crate::relay::live_sync::planes::group::group_filter;
+use crate::relay::verify::verify_batch;
+use crate::relay::{RelayFetchOutcome, RelayManager};
+
+/// Max events fetched per relay per circle per sync — a flood-guard (Rule
+/// 12). A missed tail is fetched on the next sync.
+const SYNC_MAX_EVENTS_PER_RELAY: usize = 512;
+
+/// How far back an uncursored relay is fetched (seconds).
+const SYNC_INITIAL_LOOKBACK_SECS: i64 = 24 * 3600;
+
+/// Events requested per relay per backfill page.
+pub const BACKFILL_PAGE_SIZE: usize = 200;
+
+/// Most pages one backfill reads — a flood-guard (Rule 12).
+pub const BACKFILL_MAX_PAGES: usize = 20;
+
+/// What one backfill did.
+#[derive(Clone, Default)]
+pub struct BackfillDigest {
+    /// Pages fetched.
+    pub pages_fetched: usize,
+    /// Distinct events fetched across all pages.
+    pub events_fetched: usize,
+    /// Events skipped because a sync had already ingested them.
+    pub events_skipped: usize,
+    /// Events whose ingest failed (including events from before joining).
+    pub events_failed: usize,
+    /// Event signatures rejected.
+    pub signatures_rejected: usize,
+    /// Events dropped by [`validate_group_event`].
+    pub events_rejected: usize,
+    /// Last-known locations updated.
+    pub locations_updated: usize,
+    /// Whether the walk reached the target time (or the start of the
+    /// relays' history) rather than stopping at [`BACKFILL_MAX_PAGES`] or an
+    /// unreachable relay set.
+    pub complete: bool,
+    /// The decrypted results, oldest first.
+    pub results: Vec<LocationMessageResult>,
+}
+
+impl std::fmt::Debug for BackfillDigest {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("BackfillDigest")
+            .field("pages_fetched", &self.pages_fetched)
+            .field("events_fetched", &self.events_fetched)
+            .field("events_skipped", &self.events_skipped)
+            .field("events_failed", &self.events_failed)
+            .field("signatures_rejected", &self.signatures_rejected)
+            .field("events_rejected", &self.events_rejected)
+            .field("locations_updated", &self.locations_updated)
+            .field("complete", &self.complete)
+            .field("results", &self.results.len())
+            .finish()
+    }
+}
+
+/// What one sync changed in one circle.
+#[derive(Clone)]
+pub struct CircleSyncDigest {
+    /// The circle's MLS group id.
+    pub mls_group_id: GroupId,
+    /// The circle's Nostr group id.
+    pub nostr_group_id: [u8; 32],
+    /// Distinct events fetched across the circle's relays.
+    pub events_fetched: usize,
+    /// Events whose ingest failed (re-fetched next sync).
+    pub events_failed: usize,
+    /// Members whose last-known location was updated.
+    pub locations_updated: usize,
+    /// Whether group state (membership, name, relays) changed.
+    pub group_updated: bool,
+    /// Who joined or left, across every group update in the sync.
+    pub membership: MembershipDelta,
+    /// The decrypted results, in event order.
+    pub results: Vec<LocationMessageResult>,
+}
+
+impl std::fmt::Debug for CircleSyncDigest {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("CircleSyncDigest")
+            .field("mls_group_id", &"<redacted>")
+            .field("nostr_group_id", &"<redacted>")
+            .field("events_fetched", &self.events_fetched)
+            .field("events_failed", &self.events_failed)
+            .field("locations_updated", &self.locations_updated)
+            .field("group_updated", &self.group_updated)
+            .field("membership", &self.membership)
+            .field("results", &self.results.len())
+            .finish()
+    }
+}
+
+/// What one sync changed across all circles.
+#[derive(Debug, Clone, Default)]
+pub struct SyncDigest {
+    /// Accepted circles with at least one relay.
+    pub circles_synced: usize,
+    /// `(circle, relay)` fetches issued.
+    pub relays_polled: usize,
+    /// `(circle, relay)` fetches that answered.
+    pub relays_responded: usize,
+    /// Distinct events fetched.
+    pub events_fetched: usize,
+    /// Events whose ingest failed.
+    pub events_failed: usize,
+    /// Event signatures verified (see [`crate::relay::verify`]).
+    pub signatures_verified: usize,
+    /// Events accepted from the verified-event cache without re-verifying.
+    pub signatures_cached: usize,
+    /// Events dropped for a mismatched id or invalid signature.
+    pub signatures_rejected: usize,
+    /// Events dropped by [`validate_group_event`] (wrong kind, malformed
+    /// group tag, expired, oversized).
+    pub events_rejected: usize,
+    /// Last-known locations updated.
+    pub locations_updated: usize,
+    /// Receive-side auto-commits published and confirmed.
+    pub auto_commits_published: usize,
+    /// Per-circle detail, for circles that fetched at least one event.
+    pub circles: Vec<CircleSyncDigest>,
+}
+
+/// A fetched event and the relays that served it.
+struct FetchedEvent {
+    event: Event,
+    relays: Vec<String>,
+}
+
+/// Merges per-relay fetches into distinct events, ordered by
+/// `(created_at, id)`, and counts the relays that answered.
+fn merge_fetches(outcomes: Vec<RelayFetchOutcome>) -> (Vec<FetchedEvent>, usize) {
+    let mut responded = 0;
+    let mut index: HashMap<EventId, usize> = HashMap::new();
+    let mut events: Vec<FetchedEvent> = Vec::new();
+    for outcome in outcomes {
+        if !outcome.responded {
+            continue;
+        }
+        responded += 1;
+        for event in outcome.events {
+            if let Some(&i) = index.get(&event.id) {
+                events[i].relays.push(outcome.relay_url.clone());
+            } else {
+                index.insert(event.id, events.len());
+                events.push(FetchedEvent {
+                    event,
+                    relays: vec![outcome.relay_url.clone()],
+                });
+            }
+        }
+    }
+    events.sort_by(|a, b| {
+        a.event
+            .created_at
+            .cmp(&b.event.created_at)
+            .then(a.event.id.cmp(&b.event.id))
+    });
+    (events, responded)
+}
+
+/// For each relay, the last event in the contiguous prefix of events it
+/// served that ingested without error (`ok[i]` for `events[i]`).
+fn cursor_targets(events: &[FetchedEvent], ok: &[bool]) -> HashMap<String, (i64, EventId)> {
+    let mut targets = HashMap::new();
+    let mut stopped: HashSet<&str> = HashSet::new();
+    for (fetched, &ok) in events.iter().zip(ok) {
+        for relay in &fetched.relays {
+            if stopped.contains(relay.as_str()) {
+                continue;
+            }
+            if ok {
+                let secs = i64::try_from(fetched.event.created_at.as_secs()).unwrap_or(i64::MAX);
+                targets.insert(relay.clone(), (secs, fetched.event.id));
+            } else {
+                stopped.insert(relay);
+            }
+        }
+    }
+    targets
+}
+
+/// Syncs group messages for every accepted circle.
+pub struct SyncManager<'a> {
+    circles: &'a CircleManager,
+    relays: &'a RelayManager,
+}
+
+impl<'a> SyncManager<'a> {
+    /// Creates a sync manager over the given managers.
+    #[must_use]
+    pub const fn new(circles: &'a CircleManager, relays: &'a RelayManager) -> Self {
+        Self { circles, relays }
+    }
+
+    /// Fetches, ingests, and persists new group messages for every accepted
+    /// circle, skipping self-echoes from `own_pubkey_hex`.
+    ///
+    /// Best-effort: a relay or event failure is counted in the digest and
+    /// retried on the next sync.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error only if the circle list cannot be read.
+    pub async fn sync_all(&self, own_pubkey_hex: &str) -> Result<SyncDigest, CircleError> {
+        let mut digest = SyncDigest::default();
+        for cwm in self.circles.get_visible_circles().await? {
+            if cwm.membership.status != MembershipStatus::Accepted || cwm.circle.relays.is_empty() {
+                continue;
+            }
+            digest.circles_synced += 1;
+            let circle = self
+                .sync_circle(
+                    cwm.circle.mls_group_id,
+                    cwm.circle.nostr_group_id,
+                    &cwm.circle.relays,
+                    own_pubkey_hex,
+                    &mut digest,
+                )
+                .await;
+            if circle.events_fetched > 0 {
+                digest.events_fetched += circle.events_fetched;
+                digest.events_failed += circle.events_failed;
+                digest.locations_updated += circle.locations_updated;
+                digest.circles.push(circle);
+            }
+        }
+        Ok(digest)
+    }
+
+    async fn sync_circle(
+        &self,
+        mls_group_id: GroupId,
+        ngid: [u8; 32],
+        relays: &[String],
+        own_hex: &str,
+        digest: &mut SyncDigest,
+    ) -> CircleSyncDigest {
+        let hex_id = hex::encode(ngid);
+        let now_secs = chrono::Utc::now().timestamp();
+
+        let cursors: HashMap<&str, (i64, String)> = relays
+            .iter()
+            .filter_map(|relay| {
+                let cursor = self.circles.read_group_relay_cursor(&ngid, relay).ok()??;
+                Some((relay.as_str(), cursor))
+            })
+            .collect();
+        let fetches = relays.iter().map(|relay| {
+            let cursor_secs = cursors.get(relay.as_str()).map_or_else(
+                || now_secs.saturating_sub(SYNC_INITIAL_LOOKBACK_SECS),
+                |(secs, _)| *secs,
+            );
+            let since = since_for_stream(
+                STREAM_GROUP_445,
+                cursor_secs.saturating_mul(1000),
+                SubscribePhase::Resubscribe,
+                now_secs,
+            );
+            let filter =
+                group_filter(std::slice::from_ref(&hex_id), since).limit(SYNC_MAX_EVENTS_PER_RELAY);
+            self.relays
+                .fetch_events_per_relay(filter, std::slice::from_ref(relay))
+        });
+        let outcomes: Vec<RelayFetchOutcome> = futures::future::join_all(fetches)
+            .await
+            .into_iter()
+            .filter_map(std::result::Result::ok)
+            .flatten()
+            .collect();
+        digest.relays_polled += relays.len();
+
+        let (mut events, responded) = merge_fetches(outcomes);
+        digest.relays_responded += responded;
+
+        // Verify signatures in parallel before any MLS processing, then the
+        // rest of the group-event checks (the signature is cached by now). A
+        // rejected event is dropped outright, so it neither reaches the
+        // engine nor holds a relay's cursor back.
+        let (valid, sigs) = verify_batch(events.iter().map(|f| &f.event)).await;
+        digest.signatures_verified += sigs.verified;
+        digest.signatures_cached += sigs.cached;
+        digest.signatures_rejected += sigs.rejected;
+        let mut valid = valid.into_iter();
+        events.retain(|_| valid.next().unwrap_or(false));
+        let before = events.len();
+        events.retain(|f| validate_group_event(&f.event).is_ok());
+        digest.events_rejected += before - events.len();
+
+        let mut circle = CircleSyncDigest {
+            mls_group_id,
+            nostr_group_id: ngid,
+            events_fetched: events.len(),
+            events_failed: 0,
+            locations_updated: 0,
+            group_updated: false,
+            membership: MembershipDelta::default(),
+            results: Vec::new(),
+        };
+        let mut ok = Vec::with_capacity(events.len());
+        for fetched in &events {
+            // Already the cursor on every relay that served it: ingested.
+            let id_hex = fetched.event.id.to_hex();
+            if fetched.relays.iter().all(|r| {
+                cursors
+                    .get(r.as_str())
+                    .is_some_and(|(_, last)| *last == id_hex)
+            }) {
+                ok.push(true);
+                continue;
+            }
+            match self
+                .circles
+                .decrypt_location_collecting_commits(&fetched.event)
+                .await
+            {
+                Ok(ingest) => {
+                    for result in &ingest.results {
+                        if self.persist_location(&ngid, result, own_hex) {
+                            circle.locations_updated += 1;
+                        }
+                        if let LocationMessageResult::GroupUpdate { membership, .. } = result {
+                            circle.group_updated = true;
+                            circle.membership.added.extend_from_slice(&membership.added);
+                            circle
+                                .membership
+                                .removed
+                                .extend_from_slice(&membership.removed);
+                        }
+                    }
+                    digest.auto_commits_published +=
+                        self.publish_auto_commits(ingest.auto_commits).await;
+                    circle.results.extend(ingest.results);
+                    ok.push(true);
+                }
+                Err(e) => {
+                    log::debug!("[SyncManager] ingest failed: {e}");
+                    circle.events_failed += 1;
+                    ok.push(false);
+                }
+            }
+        }
+
+        for (relay, (secs, id)) in cursor_targets(&events, &ok) {
+            if let Err(e) =
+                self.circles
+                    .advance_group_relay_cursor(&ngid, &relay, secs, &id.to_hex())
+            {
+                log::warn!("[SyncManager] failed to advance group cursor: {e}");
+            }
+        }
+        circle
+    }
+
+    /// Reads `mls_group_id`'s history backwards down to `target_secs`,
+    /// ingesting what no sync has, oldest first, and persisting locations
+    /// (skipping self-echoes from `own_pubkey_hex`).
+    ///
+    /// A stored last-known location is only replaced by a newer one, so
+    /// backfilled history never overwrites a fresher position. Best-effort:
+    /// failures are counted in the digest.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`CircleError::NotFound`] if the circle does not exist, and an
+    /// error if it cannot be read.
+    pub async fn backfill_circle(
+        &self,
+        mls_group_id: &GroupId,
+        target_secs: i64,
+        own_pubkey_hex: &str,
+    ) -> Result<BackfillDigest, CircleError> {
+        let cwm = self
+            .circles
+            .get_circle(mls_group_id)
+            .await?
+            .ok_or_else(|| CircleError::NotFound("Circle not found: <redacted>".to_string()))?;
+        let ngid = cwm.circle.nostr_group_id;
+        let relays = cwm.circle.relays;
+        let mut digest = BackfillDigest::default();
+        if relays.is_empty() {
+            return Ok(digest);
+        }
+
+        // Everything before the oldest cursor was ingested on every relay.
+        let cursors: Vec<Option<(i64, String)>> = relays
+            .iter()
+            .map(|relay| {
+                self.circles
+                    .read_group_relay_cursor(&ngid, relay)
+                    .ok()
+                    .flatten()
+            })
+            .collect();
+        let synced_before = cursors
+            .iter()
+            .map(|c| c.as_ref().map(|(secs, _)| *secs))
+            .min()
+            .flatten();
+
+        let mut seen: HashSet<EventId> = HashSet::new();
+        let mut events: Vec<Event> = Vec::new();
+        let mut until = None;
+        while digest.pages_fetched < BACKFILL_MAX_PAGES {
+            let Ok(page) = self
+                .relays
+                .fetch_group_messages_paged(
+                    &ngid,
+                    &relays,
+                    until,
+                    Some(target_secs),
+                    BACKFILL_PAGE_SIZE,
+                )
+                .await
+            else {
+                break;
+            };
+            digest.pages_fetched += 1;
+            if page.relays_responded == 0 {
+                break;
+            }
+            events.extend(page.events.into_iter().filter(|e| seen.insert(e.id)));
+            match page.next_until {
+                Some(next) if next >= target_secs => until = Some(next),
+                _ => {
+                    digest.complete = true;
+                    break;
+                }
+            }
+        }
+        digest.events_fetched = events.len();
+
+        events.retain(|e| {
+            let secs = i64::try_from(e.created_at.as_secs()).unwrap_or(i64::MAX);
+            let synced = synced_before.is_some_and(|before| secs < before);
+            digest.events_skipped += usize::from(synced);
+            !synced
+        });
+        let (valid, sigs) = verify_batch(events.iter()).await;
+        digest.signatures_rejected = sigs.rejected;
+        let mut valid = valid.into_iter();
+        events.retain(|_| valid.next().unwrap_or(false));
+        let before = events.len();
+        events.retain(|e| validate_group_event(e).is_ok());
+        digest.events_rejected = before - events.len();
+        events.sort_by(|a, b| a.created_at.cmp(&b.created_at).then(a.id.cmp(&b.id)));
+
+        for event in &events {
+            match self
+                .circles
+                .decrypt_location_collecting_commits(event)
+                .await
+            {
+                Ok(ingest) => {
+                    for result in &ingest.results {
+                        if self.persist_location(&ngid, result, own_pubkey_hex) {
+                            digest.locations_updated += 1;
+                        }
+                    }
+                    self.publish_auto_commits(ingest.auto_commits).await;
+                    digest.results.extend(ingest.results);
+                }
+                Err(e) => {
+                    log::debug!("[SyncManager] backfill ingest failed: {e}");
+                    digest.events_failed += 1;
+                }
+            }
+        }
+        Ok(digest)
+    }
+
+    /// Stores a decrypted location (or SOS) as the sender's last-known
+    /// location. Returns whether a row was written.
+    fn persist_location(
+        &self,
+        ngid: &[u8; 32],
+        result: &LocationMessageResult,
+        own_hex: &str,
+    ) -> bool {
+        let (LocationMessageResult::Location {
+            sender_pubkey,
+            content,
+            ..
+        }
+        | LocationMessageResult::Sos {
+            sender_pubkey,
+            content,
+            ..
+        }) = result
+        else {
+            return false;
+        };
+        if sender_pubkey == own_hex {
+            return false;
+        }
+        let Ok(msg) = serde_json::from_str::<LocationMessage>(content) else {
+            return false;
+        };
+        let row = LastKnownLocation {
+            nostr_group_id: *ngid,
+            sender_pubkey: sender_pubkey.clone(),
+            latitude: msg.latitude,
+            longitude: msg.longitude,
+            geohash: msg.geohash,
+            display_name: msg.display_name,
+            timestamp: msg.timestamp.timestamp(),
+            expires_at: msg.expires_at.timestamp(),
+            // The sender's share expiration can only shorten the retention
+            // window upsert derives; 0 means none was chosen.
+            purge_after: msg.share_expires_at.map_or(0, |t| t.timestamp()),
+            updated_at: chrono::Utc::now().timestamp(),
+        };
+        self.circles.upsert_last_known_location(&row).is_ok()
+    }
+
+    /// Publishes each receive-side auto-commit to its circle's relays and
+    /// confirms it only on a relay ack, else rolls it back (Rule 13). Returns
+    /// the number confirmed.
+    async fn publish_auto_commits(&self, commits: Vec<CommitToPublish>) -> usize {
+        let mut confirmed = 0;
+        for commit in commits {
+            let relays = self
+                .circles
+                .relays_for_commit_event(&commit.commit_event)
+                .unwrap_or_default();
+            let acked = !relays.is_empty()
+                && self
+                    .relays
+                    .publish_auto_commit(&commit.commit_event, &relays)
+                    .await;
+            if acked && self.circles.confirm_published(commit.pending).await.is_ok() {
+                confirmed += 1;
+            } else if !acked {
+                let _ = self.circles.publish_failed(commit.pending).await;
+            }
+        }
+        confirmed
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use nostr::{EventBuilder, Keys, Kind, Timestamp};
+
+    fn event(created_at: u64) -> Event {
+        EventBuilder::new(Kind::Custom(445), "ciphertext")
+            .custom_created_at(Timestamp::from(created_at))
+            .sign_with_keys(&Keys::generate())
+            .unwrap()
+    }
+
+    fn outcome(relay: &str, responded: bool, events: Vec<Event>) -> RelayFetchOutcome {
+        RelayFetchOutcome {
+            relay_url: relay.to_string(),
+            responded,
+            events,
+        }
+    }
+
+    #[test]
+    fn merge_dedups_across_relays_in_order() {
+        let early = event(100);
+        let late = event(200);
+        let (events, responded) = merge_fetches(vec![
+            outcome("wss://a", true, vec![late.clone(), early.clone()]),
+            outcome("wss://b", true, vec![late.clone()]),
+            outcome("wss://c", false, vec![]),
+        ]);
+        assert_eq!(responded, 2);
+        assert_eq!(events.len(), 2);
+        assert_eq!(events[0].event.id, early.id);
+        assert_eq!(events[1].event.id, late.id);
+        assert_eq!(events[1].relays, ["wss://a", "wss://b"]);
+    }
+
+    #[test]
+    fn cursor_stops_at_each_relays_first_failure() {
+        let (a1, a2, b1) = (event(100), event(200), event(300));
+        let (events, _) = merge_fetches(vec![
+            outcome("wss://a", true, vec![a1.clone(), a2.clone()]),
+            outcome("wss://b", true, vec![a1.clone(), b1.clone()]),
+        ]);
+        // a1 ok, a2 failed, b1 ok: relay a stops at a1, relay b reaches b1.
+        let targets = cursor_targets(&events, &[true, false, true]);
+        assert_eq!(targets["wss://a"], (100, a1.id));
+        assert_eq!(targets["wss://b"], (300, b1.id));
+    }
+
+    #[test]
+    fn relay_whose_first_event_fails_is_not_advanced() {
+        let (events, _) = merge_fetches(vec![outcome("wss://a", true, vec![event(1), event(2)])]);
+        assert!(cursor_targets(&events, &[false, true]).is_empty());
+    }
+}
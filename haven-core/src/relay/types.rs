@@ -47,6 +47,54 @@ pub struct PublishResult {
     pub failed: Vec<String>,
 }
 
+/// How many relays must accept a publish before
+/// [`RelayManager::publish_event_with_quorum`](super::RelayManager::publish_event_with_quorum)
+/// returns.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum PublishQuorum {
+    /// Any one relay (1-of-N).
+    #[default]
+    Any,
+    /// More than half of the relays.
+    Majority,
+    /// Every relay.
+    All,
+    /// At least this many relays.
+    AtLeast(usize),
+}
+
+impl PublishQuorum {
+    /// Acceptances needed out of `relays`: at least one, at most `relays`.
+    #[must_use]
+    pub fn required(self, relays: usize) -> usize {
+        let required = match self {
+            Self::Any => 1,
+            Self::Majority => relays / 2 + 1,
+            Self::All => relays,
+            Self::AtLeast(n) => n,
+        };
+        required.clamp(1, relays.max(1))
+    }
+}
+
+/// A publish that returned once its quorum was settled.
+///
+/// `result` lists the relays that had answered by then; relays still pending
+/// are in none of its lists. Their sends carry on in the background, and
+/// `completion` resolves with the final result once every relay answered or
+/// hit its deadline. Dropping `completion` does not cancel them.
+#[derive(Debug)]
+pub struct QuorumPublish {
+    /// The answers received when the publish returned.
+    pub result: PublishResult,
+    /// Whether the quorum was met (otherwise every relay had answered).
+    pub quorum_reached: bool,
+    /// Relays whose answer was still outstanding.
+    pub pending: usize,
+    /// The final result across every relay.
+    pub completion: tokio::sync::oneshot::Receiver<PublishResult>,
+}
+
 /// Result of checking whether events exist on a specific relay.
 #[derive(Debug, Clone)]
 pub struct RelayEventCheck {
@@ -124,6 +172,17 @@ mod tests {
         }
     }
 
+    #[test]
+    fn publish_quorum_required_stays_within_the_relay_count() {
+        assert_eq!(PublishQuorum::Any.required(5), 1);
+        assert_eq!(PublishQuorum::Majority.required(5), 3);
+        assert_eq!(PublishQuorum::Majority.required(4), 3);
+        assert_eq!(PublishQuorum::All.required(5), 5);
+        assert_eq!(PublishQuorum::AtLeast(9).required(5), 5);
+        assert_eq!(PublishQuorum::AtLeast(0).required(5), 1);
+        assert_eq!(PublishQuorum::All.required(0), 1);
+    }
+
     #[test]
     fn publish_result_is_success_with_accepted() {
         let result = PublishResult {
@@ -0,0 +1,227 @@
+//! Batched signature verification for fetched events.
+//!
+//! A catch-up or sync fetch can return hundreds of kind-445 events at once.
+//! Verifying their Schnorr signatures one by one on the ingest path serializes
+//! the most expensive check in front of MLS processing, so [`verify_batch`]
+//! splits a batch into chunks of [`VERIFY_CHUNK_SIZE`] and verifies them on
+//! blocking-pool threads in parallel.
+//!
+//! # Verified-event cache
+//!
+//! The same event is commonly fetched again: from several relays, on the next
+//! sweep before the cursor moved past it, or by both the catch-up and the
+//! foreground sync. Each `(event id, signature)` pair that verified is
+//! remembered (up to [`VERIFIED_CACHE_CAPACITY`], oldest evicted first), and a
+//! later copy skips the Schnorr check. The id is still recomputed from the
+//! event's content every time — it is a cheap hash — so a forged event reusing
+//! a cached id and signature with different content is rejected.
+//!
+//! The cache lives in process memory only and holds no content, just ids and
+//! signatures that are public on the relays anyway.
+
+use std::collections::{HashSet, VecDeque};
+use std::sync::{LazyLock, Mutex};
+
+use nostr::{Event, EventId};
+
+/// Events verified per blocking task.
+pub const VERIFY_CHUNK_SIZE: usize = 64;
+
+/// Verified `(id, signature)` pairs remembered for reuse.
+pub const VERIFIED_CACHE_CAPACITY: usize = 8_192;
+
+type VerifiedKey = (EventId, [u8; 64]);
+
+#[derive(Default)]
+struct VerifiedCache {
+    keys: HashSet<VerifiedKey>,
+    order: VecDeque<VerifiedKey>,
+}
+
+impl VerifiedCache {
+    fn contains(&self, key: &VerifiedKey) -> bool {
+        self.keys.contains(key)
+    }
+
+    fn insert(&mut self, key: VerifiedKey) {
+        if !self.keys.insert(key) {
+            return;
+        }
+        self.order.push_back(key);
+        while self.order.len() > VERIFIED_CACHE_CAPACITY {
+            if let Some(oldest) = self.order.pop_front() {
+                self.keys.remove(&oldest);
+            }
+        }
+    }
+}
+
+static VERIFIED: LazyLock<Mutex<VerifiedCache>> =
+    LazyLock::new(|| Mutex::new(VerifiedCache::default()));
+
+fn key_of(event: &Event) -> VerifiedKey {
+    (event.id, event.sig.serialize())
+}
+
+/// Verifies one event's id and signature.
+///
+/// Shares the verified-event cache with [`verify_batch`], so an event that
+/// already passed on the fetch path skips the Schnorr check here.
+#[must_use]
+pub fn verify_event(event: &Event) -> bool {
+    if !event.verify_id() {
+        return false;
+    }
+    let key = key_of(event);
+    if VERIFIED.lock().ok().is_some_and(|c| c.contains(&key)) {
+        return true;
+    }
+    let valid = event.verify_signature();
+    if valid {
+        if let Ok(mut cache) = VERIFIED.lock() {
+            cache.insert(key);
+        }
+    }
+    valid
+}
+
+/// How a batch's signatures were checked.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SignatureStats {
+    /// Signatures verified in this batch.
+    pub verified: usize,
+    /// Events accepted from the verified-event cache without re-verifying.
+    pub cached: usize,
+    /// Events with a mismatched id or an invalid signature.
+    pub rejected: usize,
+}
+
+/// Verifies the ids and signatures of `events`, in parallel chunks.
+///
+/// Returns one flag per event, in order (`true` if valid), and the batch's
+/// [`SignatureStats`]. Fails closed: an event whose verification task did not
+/// complete counts as rejected.
+pub async fn verify_batch<'a, I>(events: I) -> (Vec<bool>, SignatureStats)
+where
+    I: IntoIterator<Item = &'a Event>,
+{
+    let events: Vec<&Event> = events.into_iter().collect();
+    let mut stats = SignatureStats::default();
+    let mut valid = vec![false; events.len()];
+    let mut pending: Vec<usize> = Vec::new();
+    {
+        let cache = VERIFIED.lock().ok();
+        for (i, event) in events.iter().enumerate() {
+            if !event.verify_id() {
+                stats.rejected += 1;
+            } else if cache.as_ref().is_some_and(|c| c.contains(&key_of(event))) {
+                valid[i] = true;
+                stats.cached += 1;
+            } else {
+                pending.push(i);
+            }
+        }
+    }
+
+    let chunks: Vec<Vec<usize>> = pending
+        .chunks(VERIFY_CHUNK_SIZE)
+        .map(<[usize]>::to_vec)
+        .collect();
+    let tasks = chunks.iter().map(|chunk| {
+        let batch: Vec<Event> = chunk.iter().map(|&i| events[i].clone()).collect();
+        tokio::task::spawn_blocking(move || {
+            batch
+                .iter()
+                .map(Event::verify_signature)
+                .collect::<Vec<bool>>()
+        })
+    });
+    let results = futures::future::join_all(tasks).await;
+
+    let mut cache = VERIFIED.lock().ok();
+    for (chunk, result) in chunks.iter().zip(results) {
+        let flags = result.unwrap_or_else(|_| vec![false; chunk.len()]);
+        for (&i, ok) in chunk.iter().zip(flags) {
+            if ok {
+                valid[i] = true;
+                stats.verified += 1;
+                if let Some(cache) = cache.as_mut() {
+                    cache.insert(key_of(events[i]));
+                }
+            } else {
+                stats.rejected += 1;
+            }
+        }
+    }
+    (valid, stats)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use nostr::{EventBuilder, JsonUtil, Keys, Kind};
+
+    fn signed(content: &str) -> Event {
+        EventBuilder::new(Kind::Custom(445), content)
+            .sign_with_keys(&Keys::generate())
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn verifies_in_chunks_and_rejects_forgeries() {
+        let mut events: Vec<Event> = (0..VERIFY_CHUNK_SIZE + 5)
+            .map(|i| signed(&format!("batch-{i}")))
+            .collect();
+        // Content swapped under a valid id + signature.
+        let mut forged = serde_json::to_value(&events[0]).unwrap();
+        forged["content"] = serde_json::Value::String("tampered".to_string());
+        events.push(Event::from_json(forged.to_string()).unwrap());
+
+        let (valid, stats) = verify_batch(&events).await;
+        assert_eq!(valid.len(), events.len());
+        assert!(valid[..events.len() - 1].iter().all(|v| *v));
+        assert!(!valid[events.len() - 1]);
+        assert_eq!(stats.rejected, 1);
+        assert_eq!(stats.verified + stats.cached, events.len() - 1);
+    }
+
+    #[tokio::test]
+    async fn already_verified_events_skip_the_signature_check() {
+        let events = vec![signed("first-seen"), signed("second-seen")];
+        let (_, first) = verify_batch(&events).await;
+        assert_eq!(first.verified, 2);
+        let (valid, again) = verify_batch(&events).await;
+        assert_eq!(valid, vec![true, true]);
+        assert_eq!(again.cached, 2);
+        assert_eq!(again.verified, 0);
+    }
+
+    #[tokio::test]
+    async fn single_event_check_shares_the_batch_cache() {
+        let event = signed("single");
+        assert!(verify_event(&event));
+        let (valid, stats) = verify_batch([&event]).await;
+        assert_eq!(valid, vec![true]);
+        assert_eq!(stats.cached, 1);
+
+        let mut forged = serde_json::to_value(&event).unwrap();
+        forged["content"] = serde_json::Value::String("tampered".to_string());
+        assert!(!verify_event(
+            &Event::from_json(forged.to_string()).unwrap()
+        ));
+    }
+
+    #[test]
+    fn cache_evicts_oldest() {
+        let mut cache = VerifiedCache::default();
+        let first = (EventId::all_zeros(), [0u8; 64]);
+        cache.insert(first);
+        for i in 0..VERIFIED_CACHE_CAPACITY {
+            let mut sig = [0u8; 64];
+            sig[..8].copy_from_slice(&(u64::try_from(i).unwrap() + 1).to_le_bytes());
+            cache.insert((EventId::all_zeros(), sig));
+        }
+        assert!(!cache.contains(&first));
+        assert_eq!(cache.order.len(), VERIFIED_CACHE_CAPACITY);
+    }
+}
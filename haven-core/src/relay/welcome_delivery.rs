@@ -0,0 +1,320 @@
+//! Size-aware Welcome delivery.
+//!
+//! A gift-wrapped Welcome carries the group's ratchet tree and grows with the
+//! circle, so it can be larger than some relays' NIP-11 `max_message_length`.
+//! Such a relay answers with a rejection, and if every relay a member reads
+//! does so the member is never invited — silently.
+//!
+//! [`publish_welcome`] walks the delivery cascade
+//! ([`GiftWrappedWelcome::recipient_relays`], then
+//! [`GiftWrappedWelcome::fallback_relays`]) one tier at a time:
+//!
+//! 1. Relays whose cached information document advertises a limit below the
+//!    Welcome's size are not sent to, and are reported
+//!    [`WelcomeRelayOutcome::TooLargeForRelay`].
+//! 2. The rest are published to at once. A rejection whose reason says the
+//!    event is too large is reported the same way.
+//! 3. If no relay in the tier accepted, the next tier is tried.
+//!
+//! A relay with no cached document is always tried: limits are advisory.
+
+use std::collections::HashMap;
+
+use super::blacklist::canonical_relay_url;
+use super::relay_info::{exceeded_size_limit, is_size_rejection, PublishNeeds, RelayInfo};
+use super::{PublishResult, RelayManager};
+use crate::circle::GiftWrappedWelcome;
+use crate::nostr::mls::redact_hex_sequences;
+
+/// What happened to a Welcome on one relay.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum WelcomeRelayOutcome {
+    /// The relay accepted the Welcome.
+    Accepted,
+    /// The relay cannot take an event this size. `limit` is the advertised
+    /// limit when the relay was skipped up front, `None` when it rejected
+    /// the Welcome as too large.
+    TooLargeForRelay {
+        /// The advertised limit exceeded, if known.
+        limit: Option<u64>,
+        /// The Welcome's serialized size, in bytes.
+        event_bytes: usize,
+    },
+    /// The relay rejected the Welcome for another reason.
+    Rejected {
+        /// The relay's reason.
+        reason: String,
+    },
+    /// The relay could not be reached.
+    Unreachable,
+}
+
+/// One relay's part in a Welcome delivery.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WelcomeRelayDelivery {
+    /// The relay URL.
+    pub relay_url: String,
+    /// What happened there.
+    pub outcome: WelcomeRelayOutcome,
+    /// Whether the relay came from the cascade's fallback tiers.
+    pub fallback: bool,
+}
+
+/// The result of delivering one Welcome.
+#[derive(Clone, Default)]
+pub struct WelcomeDeliveryReport {
+    /// The recipient's Nostr public key (hex).
+    pub recipient_pubkey: String,
+    /// Every relay considered, in the order they were tried.
+    pub deliveries: Vec<WelcomeRelayDelivery>,
+}
+
+impl WelcomeDeliveryReport {
+    /// Whether at least one relay accepted the Welcome.
+    #[must_use]
+    pub fn delivered(&self) -> bool {
+        self.deliveries
+            .iter()
+            .any(|d| d.outcome == WelcomeRelayOutcome::Accepted)
+    }
+
+    /// Relays that accepted the Welcome.
+    #[must_use]
+    pub fn accepted_by(&self) -> Vec<String> {
+        self.deliveries
+            .iter()
+            .filter(|d| d.outcome == WelcomeRelayOutcome::Accepted)
+            .map(|d| d.relay_url.clone())
+            .collect()
+    }
+}
+
+impl std::fmt::Debug for WelcomeDeliveryReport {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("WelcomeDeliveryReport")
+            .field("recipient_pubkey", &"<redacted>")
+            .field("deliveries", &self.deliveries)
+            .finish()
+    }
+}
+
+/// Splits `relays` into those to publish to and those whose advertised
+/// limits rule out an event with `needs`.
+///
+/// `infos` is keyed by canonical relay URL (as returned by
+/// `CircleStorage::fresh_relay_infos`).
+#[must_use]
+pub fn split_by_size<S: std::hash::BuildHasher>(
+    relays: &[String],
+    infos: &HashMap<String, RelayInfo, S>,
+    needs: PublishNeeds,
+) -> (Vec<String>, Vec<WelcomeRelayDelivery>) {
+    let mut eligible = Vec::new();
+    let mut too_large = Vec::new();
+    for relay in relays {
+        let limit = canonical_relay_url(relay)
+            .and_then(|key| infos.get(&key))
+            .and_then(|info| exceeded_size_limit(info, needs));
+        match limit {
+            Some(limit) => too_large.push(WelcomeRelayDelivery {
+                relay_url: relay.clone(),
+                outcome: WelcomeRelayOutcome::TooLargeForRelay {
+                    limit: Some(limit),
+                    event_bytes: needs.event_bytes,
+                },
+                fallback: false,
+            }),
+            None => eligible.push(relay.clone()),
+        }
+    }
+    (eligible, too_large)
+}
+
+/// Classifies each of `relays` by a publish `result` (`None` if the publish
+/// failed outright).
+#[must_use]
+pub fn classify_publish(
+    relays: &[String],
+    result: Option<&PublishResult>,
+    event_bytes: usize,
+) -> Vec<WelcomeRelayDelivery> {
+    let same = |a: &str, b: &str| canonical_relay_url(a) == canonical_relay_url(b);
+    relays
+        .iter()
+        .map(|relay| {
+            let outcome = result.map_or(WelcomeRelayOutcome::Unreachable, |result| {
+                if result.accepted_by.iter().any(|r| same(r, relay)) {
+                    return WelcomeRelayOutcome::Accepted;
+                }
+                match result.rejected_by.iter().find(|(r, _)| same(r, relay)) {
+                    Some((_, reason)) if is_size_rejection(reason) => {
+                        WelcomeRelayOutcome::TooLargeForRelay {
+                            limit: None,
+                            event_bytes,
+                        }
+                    }
+                    Some((_, reason)) => WelcomeRelayOutcome::Rejected {
+                        reason: reason.clone(),
+                    },
+                    None => WelcomeRelayOutcome::Unreachable,
+                }
+            });
+            WelcomeRelayDelivery {
+                relay_url: relay.clone(),
+                outcome,
+                fallback: false,
+            }
+        })
+        .collect()
+}
+
+/// Publishes `welcome` tier by tier, skipping relays too small for it.
+///
+/// Never fails: every relay's outcome is in the report, and
+/// [`WelcomeDeliveryReport::delivered`] says whether the Welcome landed. The
+/// caller confirms or rolls back the pending commit from that (Rule 13).
+pub async fn publish_welcome<S: std::hash::BuildHasher>(
+    relays: &RelayManager,
+    welcome: &GiftWrappedWelcome,
+    infos: &HashMap<String, RelayInfo, S>,
+) -> WelcomeDeliveryReport {
+    let needs = PublishNeeds::of(&welcome.event);
+    let mut report = WelcomeDeliveryReport {
+        recipient_pubkey: welcome.recipient_pubkey.clone(),
+        deliveries: Vec::new(),
+    };
+    let tiers = [
+        (welcome.recipient_relays.as_slice(), false),
+        (welcome.fallback_relays.as_slice(), true),
+    ];
+    for (tier, fallback) in tiers {
+        let (eligible, too_large) = split_by_size(tier, infos, needs);
+        let mut deliveries = too_large;
+        if !eligible.is_empty() {
+            let result = match relays.publish_event(&welcome.event, &eligible).await {
+                Ok(result) => Some(result),
+                Err(e) => {
+                    log::debug!(
+                        "[publish_welcome] publish failed: {}",
+                        redact_hex_sequences(&e.to_string())
+                    );
+                    None
+                }
+            };
+            deliveries.extend(classify_publish(
+                &eligible,
+                result.as_ref(),
+                needs.event_bytes,
+            ));
+        }
+        for delivery in &mut deliveries {
+            delivery.fallback = fallback;
+        }
+        report.deliveries.extend(deliveries);
+        if report.delivered() {
+            break;
+        }
+    }
+    report
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use nostr::EventId;
+
+    fn urls(relays: &[&str]) -> Vec<String> {
+        relays.iter().map(ToString::to_string).collect()
+    }
+
+    #[test]
+    fn skips_relays_that_advertise_a_smaller_limit() {
+        let mut infos = HashMap::new();
+        infos.insert(
+            "wss://small.example.com".to_string(),
+            RelayInfo {
+                max_message_length: Some(1024),
+                ..RelayInfo::default()
+            },
+        );
+        infos.insert("wss://roomy.example.com".to_string(), RelayInfo::default());
+        let needs = PublishNeeds {
+            event_bytes: 4096,
+            content_chars: 4000,
+        };
+        let (eligible, too_large) = split_by_size(
+            &urls(&[
+                "wss://small.example.com",
+                "wss://roomy.example.com",
+                "wss://unknown.example.com",
+            ]),
+            &infos,
+            needs,
+        );
+        assert_eq!(
+            eligible,
+            urls(&["wss://roomy.example.com", "wss://unknown.example.com"])
+        );
+        assert_eq!(too_large.len(), 1);
+        assert_eq!(
+            too_large[0].outcome,
+            WelcomeRelayOutcome::TooLargeForRelay {
+                limit: Some(1024),
+                event_bytes: 4096,
+            }
+        );
+    }
+
+    #[test]
+    fn classifies_each_relay() {
+        let relays = urls(&[
+            "wss://a.example.com",
+            "wss://b.example.com",
+            "wss://c.example.com",
+            "wss://d.example.com",
+        ]);
+        let result = PublishResult {
+            event_id: EventId::all_zeros(),
+            accepted_by: urls(&["wss://a.example.com/"]),
+            rejected_by: vec![
+                (
+                    "wss://b.example.com".to_string(),
+                    "invalid: event too large".to_string(),
+                ),
+                (
+                    "wss://c.example.com".to_string(),
+                    "blocked: spam".to_string(),
+                ),
+            ],
+            failed: Vec::new(),
+        };
+        let outcomes: Vec<WelcomeRelayOutcome> = classify_publish(&relays, Some(&result), 9000)
+            .into_iter()
+            .map(|d| d.outcome)
+            .collect();
+        assert_eq!(
+            outcomes,
+            vec![
+                WelcomeRelayOutcome::Accepted,
+                WelcomeRelayOutcome::TooLargeForRelay {
+                    limit: None,
+                    event_bytes: 9000,
+                },
+                WelcomeRelayOutcome::Rejected {
+                    reason: "blocked: spam".to_string(),
+                },
+                WelcomeRelayOutcome::Unreachable,
+            ]
+        );
+        assert!(classify_publish(&relays, None, 9000)
+            .iter()
+            .all(|d| d.outcome == WelcomeRelayOutcome::Unreachable));
+    }
+
+    #[test]
+    fn recognizes_size_rejections() {
+        assert!(is_size_rejection("invalid: event too large"));
+        assert!(is_size_rejection("blocked: Max event size exceeded"));
+        assert!(!is_size_rejection("pow: difficulty 20 required"));
+    }
+}
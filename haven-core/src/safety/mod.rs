@@ -0,0 +1,11 @@
+//! Safety features that watch for something *not* happening.
+//!
+//! - [`schedule`]: scheduled check-ins ("Emma shares by 22:00") and the
+//!   [`MissedCheckin`] alerts raised when a deadline passes in silence.
+
+pub mod schedule;
+
+pub use schedule::{
+    evaluate, CheckinRule, MissedCheckin, DEFAULT_CHECKIN_WINDOW_MINUTES, EVERY_DAY,
+    MAX_CHECKIN_WINDOW_MINUTES,
+};
@@ -0,0 +1,323 @@
+//! Scheduled check-ins ("Emma shares by 22:00").
+//!
+//! A [`CheckinRule`] is set up on *this* device for one member of one
+//! circle: a local deadline, the days it applies on, and a window before the
+//! deadline in which a location from the member counts as checking in. It is
+//! never sent to the circle; the member is not told they are being watched
+//! for, and nothing about the rule reaches a relay.
+//!
+//! The engine stamps each rule with the time of the member's latest location
+//! (or SOS) as it is decrypted (`last_seen_at`, the time the location was
+//! taken). [`evaluate`] then raises a [`MissedCheckin`] for every rule whose
+//! most recent deadline has passed without one since the window opened. A
+//! location that arrives after the deadline clears the alert — they were
+//! late, not missing — and so does acknowledging it
+//! ([`CheckinRule::acknowledged_deadline`]). Each deadline alerts on its own:
+//! tomorrow's is judged afresh.
+//!
+//! Evaluation is a pure function of the stored rules, the clock and the
+//! device's current UTC offset, so a background task can run it against the
+//! database without a live MLS session. Deadlines are wall-clock times in
+//! the offset passed to each evaluation, so they follow the device across
+//! time zones and daylight-saving changes.
+
+use rand::rngs::OsRng;
+use rand::RngCore;
+
+use crate::nostr::mls::types::GroupId;
+
+/// Window before the deadline suggested for a new rule, in minutes (2 hours).
+pub const DEFAULT_CHECKIN_WINDOW_MINUTES: u16 = 2 * 60;
+
+/// Longest window a rule can have, in minutes (12 hours).
+pub const MAX_CHECKIN_WINDOW_MINUTES: u16 = 12 * 60;
+
+/// Weekday mask for a rule that applies every day (bit 0 is Monday, bit 6
+/// Sunday).
+pub const EVERY_DAY: u8 = 0b0111_1111;
+
+/// Minutes in a day; a deadline is a minute of the day below this.
+const MINUTES_PER_DAY: u16 = 24 * 60;
+
+const SECS_PER_DAY: i64 = 24 * 60 * 60;
+
+/// Largest UTC offset honoured, either way (14 hours).
+const MAX_UTC_OFFSET_SECS: i32 = 14 * 60 * 60;
+
+/// An expected check-in by one member of one circle.
+#[derive(Clone, PartialEq, Eq)]
+pub struct CheckinRule {
+    /// Random id (hex), unique per rule.
+    pub rule_id: String,
+    /// The circle the member shares with.
+    pub mls_group_id: GroupId,
+    /// Hex pubkey of the member expected to check in.
+    pub member_pubkey: String,
+    /// Local deadline, in minutes after midnight (`0..1440`).
+    pub deadline_minute: u16,
+    /// How long before the deadline a location counts, in minutes.
+    pub window_minutes: u16,
+    /// Days the rule applies on (bit 0 is Monday, bit 6 Sunday).
+    pub weekdays: u8,
+    /// Unix timestamp the rule was set up; earlier deadlines never alert.
+    pub created_at: i64,
+    /// When the member's latest location was taken (Unix timestamp).
+    pub last_seen_at: Option<i64>,
+    /// The latest deadline whose alert was acknowledged (Unix timestamp).
+    pub acknowledged_deadline: Option<i64>,
+}
+
+impl CheckinRule {
+    /// Builds a rule with a fresh id, set up at `now`.
+    ///
+    /// # Errors
+    ///
+    /// Returns a description of the problem if the schedule is invalid.
+    pub fn new(
+        mls_group_id: GroupId,
+        member_pubkey: String,
+        deadline_minute: u16,
+        window_minutes: u16,
+        weekdays: u8,
+        now: i64,
+    ) -> Result<Self, String> {
+        let mut id = [0u8; 16];
+        OsRng.fill_bytes(&mut id);
+        let rule = Self {
+            rule_id: hex::encode(id),
+            mls_group_id,
+            member_pubkey,
+            deadline_minute,
+            window_minutes,
+            weekdays,
+            created_at: now,
+            last_seen_at: None,
+            acknowledged_deadline: None,
+        };
+        rule.validate()?;
+        Ok(rule)
+    }
+
+    /// Checks the schedule.
+    ///
+    /// # Errors
+    ///
+    /// Returns a description of the first invalid field.
+    pub fn validate(&self) -> Result<(), String> {
+        if self.deadline_minute >= MINUTES_PER_DAY {
+            return Err("Check-in deadline must be a minute of the day".to_string());
+        }
+        if !(1..=MAX_CHECKIN_WINDOW_MINUTES).contains(&self.window_minutes) {
+            return Err(format!(
+                "Check-in window must be between 1 and {MAX_CHECKIN_WINDOW_MINUTES} minutes"
+            ));
+        }
+        if self.weekdays == 0 || self.weekdays & !EVERY_DAY != 0 {
+            return Err("Check-in must apply on at least one weekday".to_string());
+        }
+        Ok(())
+    }
+
+    /// The most recent deadline at or before `now` (Unix timestamp), with
+    /// the device `utc_offset_secs` east of UTC.
+    #[must_use]
+    pub fn last_deadline(&self, now: i64, utc_offset_secs: i32) -> Option<i64> {
+        let offset = i64::from(utc_offset_secs.clamp(-MAX_UTC_OFFSET_SECS, MAX_UTC_OFFSET_SECS));
+        let local_now = now.saturating_add(offset);
+        let today = local_now.div_euclid(SECS_PER_DAY);
+        (0..=7)
+            .map(|back| today - back)
+            .filter(|&day| self.applies_on(day))
+            .map(|day| day * SECS_PER_DAY + i64::from(self.deadline_minute) * 60)
+            .find(|&deadline| deadline <= local_now)
+            .map(|deadline| deadline - offset)
+    }
+
+    /// The alert for the most recent deadline, if it was missed and not
+    /// acknowledged.
+    #[must_use]
+    pub fn missed(&self, now: i64, utc_offset_secs: i32) -> Option<MissedCheckin> {
+        let deadline_at = self.last_deadline(now, utc_offset_secs)?;
+        if deadline_at < self.created_at
+            || self
+                .acknowledged_deadline
+                .is_some_and(|acked| acked >= deadline_at)
+        {
+            return None;
+        }
+        let window_start = deadline_at - i64::from(self.window_minutes) * 60;
+        if self.last_seen_at.is_some_and(|seen| seen >= window_start) {
+            return None;
+        }
+        Some(MissedCheckin {
+            rule_id: self.rule_id.clone(),
+            mls_group_id: self.mls_group_id.clone(),
+            member_pubkey: self.member_pubkey.clone(),
+            deadline_at,
+            last_seen_at: self.last_seen_at,
+        })
+    }
+
+    /// Whether the rule applies on `day` (days since the epoch, local time).
+    fn applies_on(&self, day: i64) -> bool {
+        // 1970-01-01 was a Thursday: day 0 is weekday 3 counting from Monday.
+        let weekday = (day + 3).rem_euclid(7);
+        self.weekdays & (1 << weekday) != 0
+    }
+}
+
+impl std::fmt::Debug for CheckinRule {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("CheckinRule")
+            .field("rule_id", &self.rule_id)
+            .field("member_pubkey", &"<redacted>")
+            .field("deadline_minute", &self.deadline_minute)
+            .field("window_minutes", &self.window_minutes)
+            .field("weekdays", &self.weekdays)
+            .field("created_at", &self.created_at)
+            .field("last_seen_at", &self.last_seen_at)
+            .field("acknowledged_deadline", &self.acknowledged_deadline)
+            .finish_non_exhaustive()
+    }
+}
+
+/// A deadline that passed without the member checking in.
+#[derive(Clone, PartialEq, Eq)]
+pub struct MissedCheckin {
+    /// The rule that raised it.
+    pub rule_id: String,
+    /// The circle the member shares with.
+    pub mls_group_id: GroupId,
+    /// Hex pubkey of the member who did not check in.
+    pub member_pubkey: String,
+    /// The deadline that was missed (Unix timestamp); pass it back to
+    /// acknowledge the alert.
+    pub deadline_at: i64,
+    /// When the member's latest location was taken, if any was seen.
+    pub last_seen_at: Option<i64>,
+}
+
+impl std::fmt::Debug for MissedCheckin {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("MissedCheckin")
+            .field("rule_id", &self.rule_id)
+            .field("member_pubkey", &"<redacted>")
+            .field("deadline_at", &self.deadline_at)
+            .field("last_seen_at", &self.last_seen_at)
+            .finish_non_exhaustive()
+    }
+}
+
+/// The missed check-ins among `rules` at `now`, with the device
+/// `utc_offset_secs` east of UTC, earliest deadline first.
+#[must_use]
+pub fn evaluate(rules: &[CheckinRule], now: i64, utc_offset_secs: i32) -> Vec<MissedCheckin> {
+    let mut missed: Vec<MissedCheckin> = rules
+        .iter()
+        .filter_map(|rule| rule.missed(now, utc_offset_secs))
+        .collect();
+    missed.sort_by_key(|m| m.deadline_at);
+    missed
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::nostr::mls::types::GroupIdExt;
+
+    /// Monday 2024-01-01 00:00 UTC.
+    const MONDAY: i64 = 1_704_067_200;
+    const HOUR: i64 = 60 * 60;
+
+    fn rule(deadline_minute: u16, weekdays: u8) -> CheckinRule {
+        CheckinRule::new(
+            GroupId::from_slice(&[1; 32]),
+            "aa".repeat(32),
+            deadline_minute,
+            DEFAULT_CHECKIN_WINDOW_MINUTES,
+            weekdays,
+            MONDAY - 7 * SECS_PER_DAY,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn deadlines_follow_weekdays_and_the_utc_offset() {
+        let nightly = rule(22 * 60, EVERY_DAY);
+        assert_eq!(
+            nightly.last_deadline(MONDAY + 23 * HOUR, 0),
+            Some(MONDAY + 22 * HOUR)
+        );
+        assert_eq!(
+            nightly.last_deadline(MONDAY + 21 * HOUR, 0),
+            Some(MONDAY - 2 * HOUR)
+        );
+        // 22:00 at UTC+2 is 20:00 UTC.
+        assert_eq!(
+            nightly.last_deadline(MONDAY + 21 * HOUR, 2 * 3600),
+            Some(MONDAY + 20 * HOUR)
+        );
+
+        // Weekdays only: on Monday morning the last deadline was Friday's.
+        let weekdays = rule(22 * 60, 0b0001_1111);
+        assert_eq!(
+            weekdays.last_deadline(MONDAY + 8 * HOUR, 0),
+            Some(MONDAY - 2 * SECS_PER_DAY - 2 * HOUR)
+        );
+    }
+
+    #[test]
+    fn a_location_in_the_window_or_later_counts() {
+        let mut r = rule(22 * 60, EVERY_DAY);
+        let after = MONDAY + 22 * HOUR + 60;
+        assert!(r.missed(after, 0).is_some());
+
+        r.last_seen_at = Some(MONDAY + 19 * HOUR);
+        let missed = r.missed(after, 0).unwrap();
+        assert_eq!(missed.deadline_at, MONDAY + 22 * HOUR);
+        assert_eq!(missed.last_seen_at, Some(MONDAY + 19 * HOUR));
+
+        r.last_seen_at = Some(MONDAY + 20 * HOUR);
+        assert!(r.missed(after, 0).is_none());
+
+        // Late, but back: the alert clears.
+        r.last_seen_at = Some(MONDAY + 22 * HOUR + 30);
+        assert!(r.missed(after, 0).is_none());
+    }
+
+    #[test]
+    fn acknowledged_and_earlier_deadlines_do_not_alert() {
+        let mut r = rule(22 * 60, EVERY_DAY);
+        let after = MONDAY + 22 * HOUR + 60;
+        r.acknowledged_deadline = Some(MONDAY + 22 * HOUR);
+        assert!(r.missed(after, 0).is_none());
+        // The next night is judged afresh.
+        assert!(r.missed(after + SECS_PER_DAY, 0).is_some());
+
+        let mut fresh = rule(22 * 60, EVERY_DAY);
+        fresh.created_at = MONDAY + 22 * HOUR + 1;
+        assert!(fresh.missed(after, 0).is_none());
+    }
+
+    #[test]
+    fn evaluate_lists_missed_rules_earliest_first() {
+        let late = rule(22 * 60, EVERY_DAY);
+        let early = rule(8 * 60, EVERY_DAY);
+        let mut met = rule(12 * 60, EVERY_DAY);
+        met.last_seen_at = Some(MONDAY + 11 * HOUR);
+        let missed = evaluate(&[late.clone(), met, early.clone()], MONDAY + 23 * HOUR, 0);
+        let ids: Vec<&str> = missed.iter().map(|m| m.rule_id.as_str()).collect();
+        assert_eq!(ids, [early.rule_id.as_str(), late.rule_id.as_str()]);
+    }
+
+    #[test]
+    fn rejects_invalid_schedules() {
+        let gid = GroupId::from_slice(&[1; 32]);
+        let pk = "aa".repeat(32);
+        assert!(CheckinRule::new(gid.clone(), pk.clone(), 1440, 60, EVERY_DAY, 0).is_err());
+        assert!(CheckinRule::new(gid.clone(), pk.clone(), 600, 0, EVERY_DAY, 0).is_err());
+        assert!(CheckinRule::new(gid.clone(), pk.clone(), 600, 721, EVERY_DAY, 0).is_err());
+        assert!(CheckinRule::new(gid.clone(), pk.clone(), 600, 60, 0, 0).is_err());
+        assert!(CheckinRule::new(gid, pk, 600, 60, 0b1000_0000, 0).is_err());
+    }
+}
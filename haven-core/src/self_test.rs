@@ -0,0 +1,301 @@
+//! Startup self-test of the crypto and storage stack.
+//!
+//! When a user reports that "nothing works", the first question is whether
+//! the device can do the basics at all. [`run_self_test`] answers it without
+//! touching any real identity, circle or key:
+//!
+//! - **NIP-44**: two throwaway key pairs encrypt and decrypt a message.
+//! - **MLS**: two throwaway engine sessions form a group; one sends a
+//!   location, the other decrypts it.
+//! - **Database**: an encrypted `SQLCipher` file is written, closed, reopened
+//!   and read back.
+//! - **Secure storage**: the platform keyring stores, returns and deletes a
+//!   random probe secret.
+//!
+//! Everything is created under a fresh directory inside the caller's scratch
+//! directory and deleted afterwards. Each failure is reported as a short slug
+//! naming the step that failed (see [`crate::diagnostics::is_valid_slug`]),
+//! never as an error message, so a report is safe to share.
+
+use std::path::Path;
+use std::time::Instant;
+
+use nostr::nips::nip44;
+use nostr::Keys;
+use rand::rngs::OsRng;
+use rand::RngCore;
+use serde::Serialize;
+
+use crate::nostr::mls::types::{LocationGroupConfig, LocationMessageResult, PublishWork};
+use crate::nostr::mls::SessionManager;
+use crate::relay::maintenance::build_kp_maintenance_events;
+
+/// Plaintext carried by the NIP-44 and MLS round trips.
+const PROBE_PLAINTEXT: &str = r#"{"latitude":0.0,"longitude":0.0,"self_test":true}"#;
+
+/// Relay named in the throwaway group's routing; nothing is ever sent to it.
+const PROBE_RELAY: &str = "wss://self-test.invalid";
+
+/// One part of the self-test.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SelfTestCheck {
+    /// NIP-44 encrypt/decrypt between two key pairs.
+    Nip44,
+    /// MLS group creation, join and a location round trip.
+    Mls,
+    /// Encrypted `SQLCipher` write and read-back.
+    Database,
+    /// Platform keyring write, read and delete.
+    SecureStorage,
+}
+
+/// The outcome of one check.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct SelfTestResult {
+    /// Which check ran.
+    pub check: SelfTestCheck,
+    /// `true` if the check passed.
+    pub passed: bool,
+    /// The step that failed (e.g. `"decrypt"`), when it did not pass.
+    pub failure: Option<&'static str>,
+    /// How long the check took, in milliseconds.
+    pub duration_ms: u64,
+}
+
+/// The outcome of [`run_self_test`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct SelfTestReport {
+    /// The crate version that ran the test.
+    pub core_version: &'static str,
+    /// One result per check, in the order they ran.
+    pub results: Vec<SelfTestResult>,
+}
+
+impl SelfTestReport {
+    /// Returns `true` if every check passed.
+    #[must_use]
+    pub fn passed(&self) -> bool {
+        self.results.iter().all(|r| r.passed)
+    }
+
+    /// Serializes the report as JSON.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if serialization fails (extremely rare).
+    pub fn to_json(&self) -> Result<String, serde_json::Error> {
+        serde_json::to_string(self)
+    }
+}
+
+/// Runs every check, using a throwaway directory under `scratch_dir`.
+///
+/// Never fails: a check that cannot run is reported as failed. Takes a few
+/// hundred milliseconds, dominated by the MLS and `SQLCipher` key setup.
+pub async fn run_self_test(scratch_dir: &Path) -> SelfTestReport {
+    let mut suffix = [0u8; 8];
+    OsRng.fill_bytes(&mut suffix);
+    let dir = scratch_dir.join(format!("haven-self-test-{}", hex::encode(suffix)));
+
+    let mut results = Vec::with_capacity(4);
+    let started = Instant::now();
+    results.push(finish(SelfTestCheck::Nip44, started, check_nip44()));
+    let started = Instant::now();
+    results.push(finish(SelfTestCheck::Mls, started, check_mls(&dir).await));
+    let started = Instant::now();
+    results.push(finish(
+        SelfTestCheck::Database,
+        started,
+        check_database(&dir),
+    ));
+    let started = Instant::now();
+    results.push(finish(
+        SelfTestCheck::SecureStorage,
+        started,
+        crate::nostr::mls::storage::probe_secure_storage(),
+    ));
+
+    let _ = std::fs::remove_dir_all(&dir);
+    SelfTestReport {
+        core_version: env!("CARGO_PKG_VERSION"),
+        results,
+    }
+}
+
+fn finish(
+    check: SelfTestCheck,
+    started: Instant,
+    outcome: Result<(), &'static str>,
+) -> SelfTestResult {
+    SelfTestResult {
+        check,
+        passed: outcome.is_ok(),
+        failure: outcome.err(),
+        duration_ms: u64::try_from(started.elapsed().as_millis()).unwrap_or(u64::MAX),
+    }
+}
+
+fn check_nip44() -> Result<(), &'static str> {
+    let (alice, bob) = (Keys::generate(), Keys::generate());
+    let payload = nip44::encrypt(
+        alice.secret_key(),
+        &bob.public_key(),
+        PROBE_PLAINTEXT,
+        nip44::Version::V2,
+    )
+    .map_err(|_| "encrypt")?;
+    let plaintext =
+        nip44::decrypt(bob.secret_key(), &alice.public_key(), &payload).map_err(|_| "decrypt")?;
+    if plaintext != PROBE_PLAINTEXT {
+        return Err("mismatch");
+    }
+    // The wrong key must not decrypt it.
+    if nip44::decrypt(Keys::generate().secret_key(), &alice.public_key(), &payload).is_ok() {
+        return Err("wrong_key_accepted");
+    }
+    Ok(())
+}
+
+async fn check_mls(dir: &Path) -> Result<(), &'static str> {
+    let relays = vec![PROBE_RELAY.to_string()];
+    let (alice_keys, bob_keys) = (Keys::generate(), Keys::generate());
+    let alice = SessionManager::new_ephemeral(&dir.join("alice"), &alice_keys)
+        .map_err(|_| "open_session")?;
+    let bob =
+        SessionManager::new_ephemeral(&dir.join("bob"), &bob_keys).map_err(|_| "open_session")?;
+
+    let bob_kp_event = build_kp_maintenance_events(&bob, &bob_keys, &relays, None)
+        .await
+        .map_err(|_| "key_package")?
+        .event;
+    let bob_kp =
+        SessionManager::key_package_from_event(&bob_kp_event).map_err(|_| "key_package")?;
+
+    let config = LocationGroupConfig::new("Self-test")
+        .with_relay(PROBE_RELAY)
+        .with_admin(alice_keys.public_key().to_hex());
+    let created = alice
+        .create_group(vec![bob_kp], config)
+        .await
+        .map_err(|_| "create_group")?;
+    let (welcome, pending) = created
+        .effects
+        .publish
+        .iter()
+        .find_map(|work| match work {
+            PublishWork::GroupCreated { welcomes, pending } => {
+                welcomes.first().map(|w| (w.clone(), *pending))
+            }
+            _ => None,
+        })
+        .ok_or("create_group")?;
+    alice
+        .confirm_published(pending)
+        .await
+        .map_err(|_| "create_group")?;
+
+    let welcome = SessionManager::transport_message_to_event(&welcome).map_err(|_| "join")?;
+    bob.accept_welcome(&welcome).await.map_err(|_| "join")?;
+
+    let sent = alice
+        .send_location(&created.group_id, PROBE_PLAINTEXT.to_string())
+        .await
+        .map_err(|_| "encrypt")?;
+    let message = sent
+        .publish
+        .iter()
+        .find_map(|work| match work {
+            PublishWork::ApplicationMessage { msg } => Some(msg),
+            _ => None,
+        })
+        .ok_or("encrypt")?;
+    let event = SessionManager::transport_message_to_event(message).map_err(|_| "encrypt")?;
+
+    let ingest = bob.process_event(&event).await.map_err(|_| "decrypt")?;
+    let received = ingest
+        .effects
+        .events
+        .iter()
+        .find_map(SessionManager::location_result_from_event);
+    match received {
+        Some(LocationMessageResult::Location {
+            sender_pubkey,
+            content,
+            ..
+        }) if sender_pubkey == alice_keys.public_key().to_hex() && content == PROBE_PLAINTEXT => {
+            Ok(())
+        }
+        _ => Err("decrypt"),
+    }
+}
+
+fn check_database(dir: &Path) -> Result<(), &'static str> {
+    std::fs::create_dir_all(dir).map_err(|_| "create_dir")?;
+    let path = dir.join("probe.db");
+    let mut key = zeroize::Zeroizing::new([0u8; 32]);
+    OsRng.fill_bytes(key.as_mut());
+    let key_pragma =
+        zeroize::Zeroizing::new(format!("PRAGMA key = \"x'{}'\"", hex::encode(key.as_ref())));
+    let mut probe = [0u8; 16];
+    OsRng.fill_bytes(&mut probe);
+
+    let open = || -> Result<rusqlite::Connection, &'static str> {
+        let conn = rusqlite::Connection::open(&path).map_err(|_| "open")?;
+        conn.execute_batch(key_pragma.as_str()).map_err(|_| "key")?;
+        Ok(conn)
+    };
+
+    let conn = open()?;
+    let cipher: Option<String> = conn
+        .query_row("PRAGMA cipher_version", [], |r| r.get(0))
+        .ok();
+    if !cipher.is_some_and(|v| !v.is_empty()) {
+        return Err("not_encrypted");
+    }
+    conn.execute_batch("CREATE TABLE probe (value BLOB NOT NULL)")
+        .map_err(|_| "write")?;
+    conn.execute("INSERT INTO probe (value) VALUES (?1)", [probe.as_slice()])
+        .map_err(|_| "write")?;
+    drop(conn);
+
+    let conn = open()?;
+    let read: Vec<u8> = conn
+        .query_row("SELECT value FROM probe", [], |r| r.get(0))
+        .map_err(|_| "read")?;
+    if read != probe {
+        return Err("mismatch");
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn crypto_and_database_checks_pass() {
+        let scratch = std::env::temp_dir();
+        let report = run_self_test(&scratch).await;
+        assert_eq!(report.results.len(), 4);
+        for result in &report.results {
+            // The keyring depends on the host; everything else must pass.
+            if result.check != SelfTestCheck::SecureStorage {
+                assert!(
+                    result.passed,
+                    "{:?} failed: {:?}",
+                    result.check, result.failure
+                );
+            }
+        }
+        let json = report.to_json().unwrap();
+        assert!(json.contains("\"secure_storage\""));
+    }
+
+    #[test]
+    fn failures_are_slugs() {
+        let result = finish(SelfTestCheck::Nip44, Instant::now(), Err("decrypt"));
+        assert!(!result.passed);
+        assert!(crate::diagnostics::is_valid_slug(result.failure.unwrap()));
+    }
+}
@@ -171,9 +171,8 @@ impl TileCacheStorage {
     /// * `cipher_memory_security` is intentionally **NOT** set: this DB holds
     ///   public map imagery, not secret bytes. Wiping page buffers buys nothing
     ///   here and costs throughput on the pan-read hot path.
-    /// * `journal_mode = WAL` is intentionally set (circles.db deliberately
-    ///   avoids touching `journal_mode`). WAL lets foreground reads run
-    ///   concurrently with prefetch writes. `SQLCipher` encrypts the
+    /// * `journal_mode = WAL` (no longer a divergence: circles.db is WAL too).
+    ///   WAL lets foreground reads run concurrently with prefetch writes. `SQLCipher` encrypts the
     ///   `-wal`/`-shm` sidecar pages, so the on-disk privacy guarantee holds —
     ///   but those sidecars MUST be deleted alongside `tiles.db` on wipe.
     ///
@@ -1,9 +1,58 @@
-//! Small, dependency-free utility helpers shared across modules.
+//! Small utility helpers shared across modules.
 //!
 //! This module deliberately imports nothing from `crate::circle`,
 //! `crate::nostr::mls`, or the MLS/MDK layer: it holds pure functions that
 //! several subsystems (MLS error surfacing, live-sync, the public-profile
 //! module) need without creating a dependency edge into any of those modules.
+//!
+//! # Constant-time comparison
+//!
+//! Secrets and authenticators (secret key bytes, NIP-44 conversation keys,
+//! event ids and content hashes being verified, invitation confirmation
+//! codes) are compared with [`ct_eq`] and friends, never `==`. `==` on slices
+//! returns at the first differing byte, so its timing tells an attacker who
+//! can submit guesses how many leading bytes were right. Only the contents
+//! are protected: lengths are compared up front and are assumed public.
+
+use subtle::ConstantTimeEq;
+
+/// Constant-time equality of two byte strings.
+///
+/// Returns `false` immediately when the lengths differ (lengths are not
+/// secret); otherwise runs in time independent of where the inputs differ.
+#[must_use]
+pub fn ct_eq(a: &[u8], b: &[u8]) -> bool {
+    a.ct_eq(b).into()
+}
+
+/// Constant-time equality of two 32-byte keys (secret keys, conversation
+/// keys, digests).
+#[must_use]
+pub fn ct_eq_32(a: &[u8; 32], b: &[u8; 32]) -> bool {
+    a.ct_eq(b).into()
+}
+
+/// Constant-time equality of two strings (e.g. a typed confirmation code
+/// against the expected one).
+#[must_use]
+pub fn ct_eq_str(a: &str, b: &str) -> bool {
+    ct_eq(a.as_bytes(), b.as_bytes())
+}
+
+/// Constant-time, ASCII-case-insensitive string equality, for hex digests
+/// and codes users may type in either case.
+///
+/// Both inputs are folded to lowercase before comparing; the folding runs
+/// over every byte, so its time depends only on the lengths.
+#[must_use]
+pub fn ct_eq_ignore_ascii_case(a: &str, b: &str) -> bool {
+    if a.len() != b.len() {
+        return false;
+    }
+    let a = zeroize::Zeroizing::new(a.to_ascii_lowercase());
+    let b = zeroize::Zeroizing::new(b.to_ascii_lowercase());
+    ct_eq(a.as_bytes(), b.as_bytes())
+}
 
 /// Redacts long hex sequences from error/log messages to prevent leakage of
 /// MLS group IDs, key material, sha256 digests, or full-length pubkeys.
@@ -38,9 +87,49 @@ pub fn redact_hex_sequences(msg: &str) -> String {
     result
 }
 
+/// Serde for a [`Zeroizing`](zeroize::Zeroizing) string field, used as
+/// `#[serde(with = "crate::util::zeroizing_string")]` so a secret never sits
+/// in a plain `String` on its way in or out of JSON.
+pub(crate) mod zeroizing_string {
+    use serde::{Deserialize, Deserializer, Serializer};
+    use zeroize::Zeroizing;
+
+    pub fn serialize<S: Serializer>(
+        value: &Zeroizing<String>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(value)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Zeroizing<String>, D::Error> {
+        String::deserialize(deserializer).map(Zeroizing::new)
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use super::redact_hex_sequences;
+    use super::{ct_eq, ct_eq_32, ct_eq_ignore_ascii_case, ct_eq_str, redact_hex_sequences};
+
+    #[test]
+    fn ct_eq_matches_plain_equality() {
+        assert!(ct_eq(b"secret", b"secret"));
+        assert!(!ct_eq(b"secret", b"secreT"));
+        assert!(!ct_eq(b"secret", b"secret!"));
+        assert!(ct_eq(b"", b""));
+        assert!(ct_eq_32(&[7; 32], &[7; 32]));
+        assert!(!ct_eq_32(&[7; 32], &[8; 32]));
+    }
+
+    #[test]
+    fn ct_eq_str_variants() {
+        assert!(ct_eq_str("482-913", "482-913"));
+        assert!(!ct_eq_str("482-913", "482-914"));
+        assert!(ct_eq_ignore_ascii_case("ABCdef01", "abcDEF01"));
+        assert!(!ct_eq_ignore_ascii_case("abcdef01", "abcdef02"));
+        assert!(!ct_eq_ignore_ascii_case("abc", "abcd"));
+    }
 
     #[test]
     fn redact_hex_sequences_preserves_short_hex() {
@@ -81,6 +81,8 @@ fn create_test_contact(id: u8) -> Contact {
         notes: Some(format!("Notes for contact {id}")),
         created_at: 1_000_000,
         updated_at: 2_000_000,
+        verified_at: None,
+        avatar_id: None,
     }
 }
 
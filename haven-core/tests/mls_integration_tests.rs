@@ -21,7 +21,9 @@ use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::Arc;
 
 use haven_core::nostr::mls::storage::StorageConfig;
-use haven_core::nostr::mls::types::{GroupId, LocationGroupConfig, LocationMessageResult};
+use haven_core::nostr::mls::types::{
+    GroupId, LocationGroupConfig, LocationMessageResult, MembershipDelta,
+};
 use haven_core::nostr::mls::{GroupIdExt as _, MlsGroupContext, SessionManager};
 
 // Atomic counter for unique test directories
@@ -333,6 +335,7 @@ mod location_message_result_tests {
             (
                 LocationMessageResult::GroupUpdate {
                     group_id: g.clone(),
+                    membership: MembershipDelta::default(),
                 },
                 "GroupUpdate",
             ),
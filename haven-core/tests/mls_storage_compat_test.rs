@@ -0,0 +1,248 @@
+//! Compatibility of Haven's MLS database across MDK upgrades.
+//!
+//! `session.sqlite` is laid out by the pinned MDK `storage-sqlite` crate, so a
+//! dependency bump can silently change what is on every user's device. These
+//! tests pin that down:
+//!
+//! - Every fixture under `tests/fixtures/mls_storage/format-<N>/` is a real
+//!   two-party group written by the app that shipped storage format `N`. It
+//!   must still open under the current pin, keep its group, members and
+//!   epoch, and carry a location from Alice to Bob.
+//! - A database in a format this build cannot read fails with
+//!   `IncompatibleMlsStorage` instead of reaching the engine.
+//!
+//! # Adding a fixture
+//!
+//! Before bumping the MDK pin, and whenever `MLS_STORAGE_FORMAT` changes, run
+//! the ignored generator on the build that ships the format:
+//!
+//! ```text
+//! cargo test -p haven-core --test mls_storage_compat_test -- --ignored
+//! ```
+//!
+//! It writes `format-<MLS_STORAGE_FORMAT>/` with both parties' databases and a
+//! `manifest.json` naming their test keys and the group. Commit the result;
+//! never regenerate a fixture for a format that already shipped. The
+//! compatibility test fails while the current format has no fixture.
+
+mod helpers;
+
+use std::path::{Path, PathBuf};
+
+use haven_core::circle::{CircleError, CircleManager};
+use haven_core::nostr::mls::storage::{StorageConfig, MLS_STORAGE_FORMAT};
+use haven_core::nostr::mls::types::{GroupId, PublishWork};
+use haven_core::nostr::mls::{GroupIdExt as _, SessionManager};
+use haven_core::nostr::NostrError;
+use helpers::{cleanup_dir, location_senders, setup_two_party_group, unique_temp_dir};
+use nostr::Keys;
+use serde::{Deserialize, Serialize};
+
+/// What a fixture holds, besides the two databases.
+#[derive(Serialize, Deserialize)]
+struct Manifest {
+    format: u32,
+    alice_secret_hex: String,
+    bob_secret_hex: String,
+    mls_group_id_hex: String,
+    nostr_group_id_hex: String,
+    epoch: u64,
+}
+
+fn fixtures_root() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/mls_storage")
+}
+
+fn copy_dir(from: &Path, to: &Path) {
+    std::fs::create_dir_all(to).expect("create copy");
+    for entry in std::fs::read_dir(from).expect("read fixture") {
+        let entry = entry.expect("fixture entry");
+        std::fs::copy(entry.path(), to.join(entry.file_name())).expect("copy fixture file");
+    }
+}
+
+#[tokio::test]
+async fn shipped_storage_formats_open_and_operate() {
+    let root = fixtures_root();
+    let fixtures: Vec<PathBuf> = std::fs::read_dir(&root)
+        .unwrap_or_else(|e| {
+            panic!(
+                "{}: no MLS storage fixtures ({e}); see the module docs to generate one",
+                root.display()
+            )
+        })
+        .map(|entry| entry.expect("fixture dir").path())
+        .collect();
+    assert!(
+        !fixtures.is_empty(),
+        "{}: no MLS storage fixtures; see the module docs to generate one",
+        root.display()
+    );
+    assert!(
+        fixtures
+            .iter()
+            .any(|f| f.ends_with(format!("format-{MLS_STORAGE_FORMAT}"))),
+        "no fixture for the current storage format {MLS_STORAGE_FORMAT}"
+    );
+    for fixture in fixtures {
+        let manifest: Manifest = serde_json::from_slice(
+            &std::fs::read(fixture.join("manifest.json")).expect("read manifest"),
+        )
+        .expect("parse manifest");
+        let name = fixture.display().to_string();
+        assert!(
+            name.ends_with(&format!("format-{}", manifest.format)),
+            "{name}: manifest names format {}",
+            manifest.format
+        );
+
+        // Work on copies: opening migrates the databases in place.
+        let work = unique_temp_dir("mls_compat");
+        let (alice_dir, bob_dir) = (work.join("alice"), work.join("bob"));
+        copy_dir(&fixture.join("alice"), &alice_dir);
+        copy_dir(&fixture.join("bob"), &bob_dir);
+
+        let alice_keys = Keys::parse(&manifest.alice_secret_hex).expect("alice key");
+        let bob_keys = Keys::parse(&manifest.bob_secret_hex).expect("bob key");
+        let alice = SessionManager::new_unencrypted(&alice_dir, &alice_keys)
+            .unwrap_or_else(|e| panic!("{name}: alice does not open: {e}"));
+        let bob = SessionManager::new_unencrypted(&bob_dir, &bob_keys)
+            .unwrap_or_else(|e| panic!("{name}: bob does not open: {e}"));
+
+        let group_id = GroupId::from_slice(&hex::decode(&manifest.mls_group_id_hex).unwrap());
+        let (nostr_group_id, _) = alice.group_routing(&group_id).await.expect("routing");
+        assert_eq!(
+            hex::encode(nostr_group_id),
+            manifest.nostr_group_id_hex,
+            "{name}"
+        );
+        assert_eq!(
+            alice.epoch(&group_id).await.unwrap(),
+            manifest.epoch,
+            "{name}"
+        );
+        assert_eq!(
+            bob.epoch(&group_id).await.unwrap(),
+            manifest.epoch,
+            "{name}"
+        );
+        let mut members = alice.member_pubkeys(&group_id).await.unwrap();
+        members.sort();
+        let mut expected = vec![
+            alice_keys.public_key().to_hex(),
+            bob_keys.public_key().to_hex(),
+        ];
+        expected.sort();
+        assert_eq!(members, expected, "{name}");
+
+        let location = haven_core::location::LocationMessage::new(51.5, -0.12)
+            .to_string()
+            .unwrap();
+        let effects = alice
+            .send_location(&group_id, location)
+            .await
+            .unwrap_or_else(|e| panic!("{name}: alice cannot send: {e}"));
+        let event = effects
+            .publish
+            .iter()
+            .find_map(|work| match work {
+                PublishWork::ApplicationMessage { msg } => {
+                    Some(SessionManager::transport_message_to_event(msg).unwrap())
+                }
+                _ => None,
+            })
+            .expect("app message");
+        let ingest = bob
+            .process_event(&event)
+            .await
+            .unwrap_or_else(|e| panic!("{name}: bob cannot read: {e}"));
+        assert_eq!(
+            location_senders(&ingest.effects.events),
+            vec![alice_keys.public_key().to_hex()],
+            "{name}"
+        );
+
+        // Opening moved both databases to the current format.
+        for dir in [&alice_dir, &bob_dir] {
+            assert_eq!(
+                StorageConfig::new(dir).stored_format().unwrap(),
+                Some(MLS_STORAGE_FORMAT),
+                "{name}"
+            );
+        }
+        drop((alice, bob));
+        cleanup_dir(&work);
+    }
+}
+
+#[tokio::test]
+async fn newer_storage_format_is_refused_before_the_engine_opens_it() {
+    let dir = unique_temp_dir("mls_compat_newer");
+    let keys = Keys::generate();
+    drop(SessionManager::new_unencrypted(&dir, &keys).expect("first open"));
+    let config = StorageConfig::new(&dir);
+    assert_eq!(config.stored_format().unwrap(), Some(MLS_STORAGE_FORMAT));
+
+    std::fs::write(
+        config.format_marker_path(),
+        (MLS_STORAGE_FORMAT + 1).to_string(),
+    )
+    .unwrap();
+    let before = std::fs::read(config.database_path()).unwrap();
+    match SessionManager::new_unencrypted(&dir, &keys) {
+        Err(NostrError::IncompatibleMlsStorage { found, supported }) => {
+            assert_eq!(found, MLS_STORAGE_FORMAT + 1);
+            assert_eq!(supported, MLS_STORAGE_FORMAT);
+        }
+        Err(e) => panic!("expected IncompatibleMlsStorage, got {e}"),
+        Ok(_) => panic!("a newer storage format must not open"),
+    }
+    assert_eq!(std::fs::read(config.database_path()).unwrap(), before);
+    assert!(matches!(
+        CircleManager::new_unencrypted(&dir, &keys),
+        Err(CircleError::IncompatibleMlsStorage { .. })
+    ));
+
+    // A database from before the marker existed opens and gets marked.
+    std::fs::remove_file(config.format_marker_path()).unwrap();
+    drop(SessionManager::new_unencrypted(&dir, &keys).expect("unmarked database opens"));
+    assert_eq!(config.stored_format().unwrap(), Some(MLS_STORAGE_FORMAT));
+
+    cleanup_dir(&dir);
+}
+
+/// Writes the fixture for the current storage format. See the module docs.
+#[tokio::test]
+#[ignore = "writes a fixture into the source tree"]
+async fn generate_current_format_fixture() {
+    let group = setup_two_party_group("mls_compat_fixture").await;
+    let epoch = group.alice.epoch(&group.group_id).await.unwrap();
+    let manifest = Manifest {
+        format: MLS_STORAGE_FORMAT,
+        alice_secret_hex: group.alice_keys.secret_key().to_secret_hex(),
+        bob_secret_hex: group.bob_keys.secret_key().to_secret_hex(),
+        mls_group_id_hex: hex::encode(group.group_id.as_slice()),
+        nostr_group_id_hex: hex::encode(group.nostr_group_id),
+        epoch,
+    };
+    let (alice_dir, bob_dir) = (group.alice_dir.clone(), group.bob_dir.clone());
+    // Close both sessions so everything is checkpointed into the files.
+    drop(group.alice);
+    drop(group.bob);
+
+    let out = fixtures_root().join(format!("format-{MLS_STORAGE_FORMAT}"));
+    assert!(
+        !out.exists(),
+        "{} exists; shipped fixtures are never regenerated",
+        out.display()
+    );
+    copy_dir(&alice_dir, &out.join("alice"));
+    copy_dir(&bob_dir, &out.join("bob"));
+    std::fs::write(
+        out.join("manifest.json"),
+        serde_json::to_vec_pretty(&manifest).unwrap(),
+    )
+    .unwrap();
+    cleanup_dir(&alice_dir);
+    cleanup_dir(&bob_dir);
+}
@@ -387,14 +387,14 @@ class _AddMemberPageState extends ConsumerState<AddMemberPage> {
       final creatorFallbackRelays = await _fetchCreatorFallbackRelays(ref);
 
       // The identity secret is fetched FRESH inside addMember for each staging
-      // attempt and scrubbed immediately after (Rule 9), so pass the notifier's
-      // fetcher rather than holding raw bytes across the converge loop. The
+      // attempt and disposed immediately after (Rule 9), so pass the notifier's
+      // fetcher rather than holding one handle across the converge loop. The
       // notifier is a non-autoDispose singleton, so the tear-off stays valid.
       final identityNotifier = ref.read(identityNotifierProvider.notifier);
       final result = await ref
           .read(circleServiceProvider)
           .addMember(
-            secretProvider: identityNotifier.getSecretBytes,
+            secretProvider: identityNotifier.getSecretHandle,
             mlsGroupId: widget.circle.mlsGroupId,
             memberKeyPackages: keyPackages,
             creatorFallbackRelays: creatorFallbackRelays,
@@ -45,6 +45,7 @@ import 'package:haven/src/services/nostr_relay_service.dart';
 import 'package:haven/src/services/pending_leave_service.dart';
 import 'package:haven/src/services/subscription_service.dart';
 import 'package:haven/src/theme/theme.dart';
+import 'package:haven/src/utils/ffi_error.dart';
 import 'package:haven/src/utils/profile_refresh_trigger.dart';
 import 'package:haven/src/widgets/circles/circles_bottom_sheet.dart';
 import 'package:haven/src/widgets/common/dim_overlay.dart';
@@ -333,7 +334,9 @@ class _MapShellState extends ConsumerState<MapShell>
     } on Object catch (e) {
       debugPrint('[MapShell] pruneExpiredLastKnown failed: ${e.runtimeType}');
       if (kDebugMode) {
-        debugPrint('[MapShell] pruneExpiredLastKnown error: $e');
+        debugPrint(
+          '[MapShell] pruneExpiredLastKnown error: ${ffiErrorDetail(e)}',
+        );
       }
     }
     // The widget may have been disposed while the first FFI call was in
@@ -346,7 +349,9 @@ class _MapShellState extends ConsumerState<MapShell>
     } on Object catch (e) {
       debugPrint('[MapShell] pruneProcessedGiftWraps failed: ${e.runtimeType}');
       if (kDebugMode) {
-        debugPrint('[MapShell] pruneProcessedGiftWraps error: $e');
+        debugPrint(
+          '[MapShell] pruneProcessedGiftWraps error: ${ffiErrorDetail(e)}',
+        );
       }
     }
   }
@@ -393,12 +398,12 @@ class _MapShellState extends ConsumerState<MapShell>
       );
     } on Object catch (e) {
       debugPrint('[MapShell] live-sync start failed: ${e.runtimeType}');
-      // The FFI error is a Rust `Result<_, String>` already sanitized by
-      // `redact_hex_sequences`; surface its (redacted) detail in debug/e2e
-      // builds so a start failure is diagnosable instead of an opaque
-      // "String" runtimeType. Release builds keep only the runtimeType.
+      // The FFI error is a HavenErrorFfi whose detail is already sanitized by
+      // `redact_hex_sequences`; surface it in debug/e2e builds so a start
+      // failure is diagnosable instead of an opaque type name. Release
+      // builds keep only the runtimeType.
       if (kDebugMode) {
-        debugPrint('[MapShell] live-sync start error: $e');
+        debugPrint('[MapShell] live-sync start error: ${ffiErrorDetail(e)}');
       }
     }
   }
@@ -12,6 +12,7 @@ import 'package:haven/src/providers/live_sync_provider.dart';
 import 'package:haven/src/providers/maintenance_scheduler_provider.dart';
 import 'package:haven/src/providers/service_providers.dart';
 import 'package:haven/src/providers/tile_prefetch_provider.dart';
+import 'package:haven/src/rust/api.dart' show SecretHandle;
 import 'package:haven/src/services/background_location_manager.dart';
 import 'package:haven/src/services/geolocator_location_service.dart';
 import 'package:haven/src/services/identity_service.dart';
@@ -391,11 +392,11 @@ class IdentityNotifier extends AsyncNotifier<Identity?> {
     return service.exportNsec();
   }
 
-  /// Gets the secret bytes for FFI operations.
+  /// Gets a handle to the secret key for FFI operations.
   ///
-  /// Returns 32 bytes of the secret key.
-  Future<List<int>> getSecretBytes() async {
+  /// The key never leaves Rust memory; dispose the handle once done.
+  Future<SecretHandle> getSecretHandle() async {
     final service = ref.read(identityServiceProvider);
-    return service.getSecretBytes();
+    return service.getSecretHandle();
   }
 }
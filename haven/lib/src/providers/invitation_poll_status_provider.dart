@@ -265,19 +265,17 @@ class InvitationPollStatusNotifier extends Notifier<InvitationPollStatus> {
     }
 
     if (uniqueEvents.isNotEmpty) {
-      // Fetch secret bytes once for the batch and only when there is work,
-      // minimising secret exposure. Dart has no zeroize, so copy into a buffer
-      // we control and scrub it in `finally` (Rule #9).
-      final secretBytes = Uint8List.fromList(
-        await identityNotifier.getSecretBytes(),
-      );
+      // Fetch a secret handle once for the batch and only when there is work,
+      // minimising secret exposure; dispose it in `finally` so the Rust-side
+      // key copy is zeroized straight away (Rule #9).
+      final secret = await identityNotifier.getSecretHandle();
       try {
         final results = await Future.wait(
           uniqueEvents.map((eventJson) async {
             try {
               final invitation = await circleService
                   .processGiftWrappedInvitation(
-                    identitySecretBytes: secretBytes,
+                    secret: secret,
                     giftWrapEventJson: eventJson,
                   );
               return invitation == null ? 0 : 1;
@@ -291,7 +289,7 @@ class InvitationPollStatusNotifier extends Notifier<InvitationPollStatus> {
         );
         newCount = results.fold(0, (sum, v) => sum + v);
       } finally {
-        secretBytes.fillRange(0, secretBytes.length, 0);
+        secret.dispose();
       }
     }
 
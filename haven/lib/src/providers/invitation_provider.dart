@@ -15,6 +15,7 @@ import 'package:haven/src/providers/identity_provider.dart';
 import 'package:haven/src/providers/relay_preferences_provider.dart';
 import 'package:haven/src/providers/service_providers.dart';
 import 'package:haven/src/services/circle_service.dart';
+import 'package:haven/src/services/fresh_secret.dart';
 
 /// Provider for the list of pending invitations.
 ///
@@ -116,45 +117,51 @@ final invitationPollerProvider = FutureProvider<int>((ref) async {
       '[InvitationPoller] fetched ${giftWraps.length} gift-wrap events',
     );
 
-    // Fetch secret bytes once for the batch — each gift wrap creates
-    // an independent MLS group, so parallel processing is safe.
-    final secretBytes = await identityNotifier.getSecretBytes();
-
+    // Fetch a secret handle once for the batch — each gift wrap creates
+    // an independent MLS group, so parallel processing is safe. Disposed as
+    // soon as the batch settles (Rule #9).
+    //
     // Process all gift wraps in parallel. Each result records whether the
     // wrap was newly accepted (for the count) and the wrapper `created_at`
     // (seconds) when it was HANDLED WITHOUT ERROR (new or dedup) — eligible to
     // advance the inbox cursor. A wrap that threw yields `wrapSecs == null` so
     // the cursor never advances past an un-handled wrap (it retries next poll).
-    final results = await Future.wait(
-      giftWraps.map((eventJson) async {
-        try {
-          final invitation = await circleService.processGiftWrappedInvitation(
-            identitySecretBytes: secretBytes,
-            giftWrapEventJson: eventJson,
-          );
-          // `null` → already-processed gift wrap (handled by Rust dedup).
-          // Silent no-op for the count, but still a handled wrap.
-          return (
-            isNew: invitation != null,
-            wrapSecs: _giftWrapCreatedAtSecs(eventJson),
-          );
-        } on CircleServiceException catch (e) {
-          // Real failure from the service layer (malformed event, MDK
-          // error, storage failure). The underlying Rust error has already
-          // been logged with sanitized detail by `nostr_circle_service.dart`.
-          debugPrint('[InvitationPoller] skipped gift-wrap: ${e.runtimeType}');
-          return (isNew: false, wrapSecs: null);
-        } on Object catch (e) {
-          // FFI Error path. Log only the runtime type — error messages from
-          // non-Mls CircleError variants (NotFound, ContactNotFound, etc.)
-          // can embed pubkeys or group IDs in their Display output.
-          debugPrint(
-            '[InvitationPoller] skipped gift-wrap (processing error): '
-            '${e.runtimeType}',
-          );
-          return (isNew: false, wrapSecs: null);
-        }
-      }),
+    final results = await withFreshSecret(
+      identityNotifier.getSecretHandle,
+      (secret) => Future.wait(
+        giftWraps.map((eventJson) async {
+          try {
+            final invitation = await circleService
+                .processGiftWrappedInvitation(
+                  secret: secret,
+                  giftWrapEventJson: eventJson,
+                );
+            // `null` → already-processed gift wrap (handled by Rust dedup).
+            // Silent no-op for the count, but still a handled wrap.
+            return (
+              isNew: invitation != null,
+              wrapSecs: _giftWrapCreatedAtSecs(eventJson),
+            );
+          } on CircleServiceException catch (e) {
+            // Real failure from the service layer (malformed event, MDK
+            // error, storage failure). The underlying Rust error has already
+            // been logged with sanitized detail by `nostr_circle_service.dart`.
+            debugPrint(
+              '[InvitationPoller] skipped gift-wrap: ${e.runtimeType}',
+            );
+            return (isNew: false, wrapSecs: null);
+          } on Object catch (e) {
+            // FFI Error path. Log only the runtime type — error messages from
+            // non-Mls CircleError variants (NotFound, ContactNotFound, etc.)
+            // can embed pubkeys or group IDs in their Display output.
+            debugPrint(
+              '[InvitationPoller] skipped gift-wrap (processing error): '
+              '${e.runtimeType}',
+            );
+            return (isNew: false, wrapSecs: null);
+          }
+        }),
+      ),
     );
     final newCount = results.where((r) => r.isNew).length;
 
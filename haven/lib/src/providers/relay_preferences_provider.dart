@@ -11,8 +11,6 @@
 /// read.
 library;
 
-import 'dart:typed_data';
-
 import 'package:flutter/foundation.dart';
 import 'package:flutter_riverpod/flutter_riverpod.dart';
 import 'package:haven/src/constants/relays.dart';
@@ -24,6 +22,7 @@ import 'package:haven/src/constants/relays.dart';
 import 'package:haven/src/providers/identity_provider.dart';
 import 'package:haven/src/providers/key_package_provider.dart';
 import 'package:haven/src/providers/service_providers.dart';
+import 'package:haven/src/rust/api.dart' show SecretHandle;
 import 'package:haven/src/services/nostr_circle_service.dart';
 import 'package:haven/src/services/nostr_relay_preferences_service.dart';
 import 'package:haven/src/services/relay_preferences_service.dart';
@@ -96,16 +95,15 @@ Future<void> _scrubDroppedRelay(
   RelayCategory category,
   String url,
 ) async {
-  // Zeroed on every exit path (success, early return, catch) — mirrors the
-  // secret-lifetime convention in key_package_provider / background_location_task
-  // (Security Rule #9: minimize the lifetime of the Dart heap copy).
-  Uint8List? secretBuffer;
+  // Disposed on every exit path (success, early return, catch) so the
+  // Rust-side key copy is zeroized straight away (Security Rule #9).
+  SecretHandle? secret;
   try {
     final service = await ref.read(relayPreferencesServiceProvider.future);
     final identityNotifier = ref.read(identityNotifierProvider.notifier);
-    secretBuffer = Uint8List.fromList(await identityNotifier.getSecretBytes());
+    secret = await identityNotifier.getSecretHandle();
     final scrub = await service.buildRelayRemovalScrub(
-      identitySecretBytes: secretBuffer,
+      secret: secret,
       category: category,
       droppedRelays: [url],
     );
@@ -119,7 +117,7 @@ Future<void> _scrubDroppedRelay(
   } on Object catch (e) {
     debugPrint('Relay removal scrub failed (best-effort): ${e.runtimeType}');
   } finally {
-    secretBuffer?.fillRange(0, secretBuffer.length, 0);
+    secret?.dispose();
   }
 }
 
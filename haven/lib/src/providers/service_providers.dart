@@ -85,12 +85,12 @@ final circleServiceProvider = Provider<CircleService>((ref) {
     // fetch.
     enableLeaverBackstop: true,
     // Dark Matter (DM-4): `CircleManagerFfi.newInstance` hard-requires the
-    // device identity's secret bytes at construction time (it binds the
+    // device identity's secret at construction time (it binds the
     // account identity, the NIP-59 welcome signer, and the
     // account-identity-proof signer). Re-fetched fresh on every
     // `initialize()` call rather than held (Security Rule 9).
-    identitySecretBytesProvider: () =>
-        ref.read(identityNotifierProvider.notifier).getSecretBytes(),
+    identitySecretProvider: () =>
+        ref.read(identityNotifierProvider.notifier).getSecretHandle(),
   );
 });
 
@@ -152,8 +152,8 @@ final subscriptionServiceProvider = Provider<SubscriptionService>((ref) {
   final router = LiveEventRouter(
     circleService: ref.read(circleServiceProvider),
     circlesSnapshot: () => ref.read(circlesProvider.future),
-    secretBytes: () =>
-        ref.read(identityNotifierProvider.notifier).getSecretBytes(),
+    secretProvider: () =>
+        ref.read(identityNotifierProvider.notifier).getSecretHandle(),
     parseLocation: (content, sender) async {
       // Reuse the Rust serde schema (no Dart duplication); null = not a
       // parseable LocationMessage — e.g. a legacy `haven-avatar-*` chunk
@@ -273,7 +273,7 @@ final maintenanceServiceProvider = Provider<MaintenanceService>((ref) {
     // is disposed by a concurrent logout when a tick fires, this `ref.read`
     // may throw — that is caught by `MaintenanceService._withSecret`, which
     // fails closed (returns an empty outcome, no publish).
-    identitySecretBytes: () =>
-        ref.read(identityNotifierProvider.notifier).getSecretBytes(),
+    identitySecret: () =>
+        ref.read(identityNotifierProvider.notifier).getSecretHandle(),
   );
 });
@@ -6,11 +6,87 @@
 import 'frb_generated.dart';
 import 'package:flutter_rust_bridge/flutter_rust_bridge_for_generated.dart';
 
-// These functions are ignored because they are not marked as `pub`: `build_relay_list_event_for`, `build_relay_list_unpublish_for`, `commit_event_to_json`, `convert_commit_to_publish`, `convert_location_result`, `current_cache`, `delete_circles_db_files`, `delete_db_files`, `delete_legacy_mls_db_files`, `delete_mls_session_db_files`, `delete_tile_db_files`, `event_secs_to_cursor_ms`, `from_cached`, `get_or_create_circle_db_key`, `get_or_create_tiles_db_key`, `hex_to_npub`, `keys_from_secret_bytes`, `kp_event_d_tag`, `live_event_to_ffi`, `live_session_core`, `maintain_relay_list_category`, `nip65_relay_list_urls`, `now_ms`, `platform_init_keyring`, `profile_now_secs`, `redact_profile_err`, `relay_list_urls_for`, `relay_list_urls`, `relay_list_wire_kind`, `remove_circles_db_key`, `remove_file_strict`, `remove_keyring_key`, `remove_mls_session_db_key`, `remove_tiles_db_key`, `republish_key_package`, `run_blocking`, `sync_reason_to_ffi`, `tile_err_to_string`, `unknown`
-// These types are ignored because they are neither used by any `pub` functions nor (for structs and enums) marked `#[frb(unignore)]`: `InMemoryStorage`
-// These function are ignored because they are on traits that is not defined in current crate (put an empty `#[frb]` on it to unignore): `assert_receiver_is_total_eq`, `assert_receiver_is_total_eq`, `assert_receiver_is_total_eq`, `assert_receiver_is_total_eq`, `assert_receiver_is_total_eq`, `assert_receiver_is_total_eq`, `assert_receiver_is_total_eq`, `assert_receiver_is_total_eq`, `assert_receiver_is_total_eq`, `assert_receiver_is_total_eq`, `clone`, `clone`, `clone`, `clone`, `clone`, `clone`, `clone`, `clone`, `clone`, `clone`, `clone`, `clone`, `clone`, `clone`, `clone`, `clone`, `clone`, `clone`, `clone`, `clone`, `clone`, `clone`, `clone`, `clone`, `clone`, `clone`, `clone`, `clone`, `clone`, `clone`, `clone`, `clone`, `clone`, `clone`, `clone`, `clone`, `clone`, `clone`, `clone`, `clone`, `clone`, `clone`, `delete`, `eq`, `eq`, `eq`, `eq`, `eq`, `eq`, `eq`, `eq`, `eq`, `eq`, `exists`, `fmt`, `fmt`, `fmt`, `fmt`, `fmt`, `fmt`, `fmt`, `fmt`, `fmt`, `fmt`, `fmt`, `fmt`, `fmt`, `fmt`, `fmt`, `fmt`, `fmt`, `fmt`, `fmt`, `fmt`, `fmt`, `fmt`, `fmt`, `fmt`, `fmt`, `fmt`, `fmt`, `fmt`, `fmt`, `fmt`, `fmt`, `fmt`, `fmt`, `fmt`, `fmt`, `fmt`, `fmt`, `fmt`, `fmt`, `fmt`, `fmt`, `fmt`, `fmt`, `fmt`, `fmt`, `fmt`, `fmt`, `fmt`, `fmt`, `fmt`, `fmt`, `from`, `from`, `from`, `from`, `from`, `from`, `from`, `from`, `from`, `from`, `from`, `from`, `from`, `from`, `from`, `from`, `from`, `from`, `from`, `from`, `from`, `from`, `from`, `from`, `from`, `from`, `retrieve`, `store`
+// These functions are ignored because they are not marked as `pub`: `add_members_result_to_ffi`, `build_relay_list_event_for`, `build_relay_list_publish_signed`, `build_relay_list_unpublish_for`, `commit_event_to_json`, `convert_commit_to_publish`, `convert_ingest`, `convert_location_result`, `creation_result_to_ffi`, `current_cache`, `decode_engine_location`, `delete_circles_db_files`, `delete_db_files`, `delete_legacy_mls_db_files`, `delete_mls_session_db_files`, `delete_tile_db_files`, `event_secs_to_cursor_ms`, `fetch_group_message_events`, `from_cached`, `from_core`, `from_decoded`, `from_location`, `from_slice`, `get_or_create_circle_db_key`, `get_or_create_tiles_db_key`, `ingest_collecting_commits`, `install_remote_signer`, `internal`, `invalid_input`, `invalid_key`, `keys`, `kp_event_d_tag`, `live_event_to_ffi`, `live_session_core`, `location_update_for`, `maintain_key_package_signed`, `maintain_relay_list_category`, `member_key_package_from_ffi`, `new`, `nip65_relay_list_urls`, `now_ms`, `npub_or_hex`, `open`, `outgoing_location`, `platform_init_keyring`, `pow_policy`, `profile_now_secs`, `proximity_target_from_ffi`, `redact_hex`, `redact_profile_err`, `relay_list_urls_for`, `relay_list_urls`, `relay_list_wire_kind`, `remove_circles_db_key`, `remove_file_strict`, `remove_keyring_key`, `remove_mls_session_db_key`, `remove_tiles_db_key`, `republish_key_package`, `resolve`, `run_blocking`, `send_one_shot_location_signed`, `sign_deletion`, `storage`, `sync_not_running`, `sync_reason_to_ffi`, `tile_err_to_ffi`, `unknown`, `unsupported`, `wait`, `welcome_to_ffi`, `with_code`, `with_pow`
+// These types are ignored because they are neither used by any `pub` functions nor (for structs and enums) marked `#[frb(unignore)]`: `CallbackSigner`, `IdentitySigner`, `InMemoryStorage`
+// These function are ignored because they are on traits that is not defined in current crate (put an empty `#[frb]` on it to unignore): `assert_receiver_is_total_eq`, `assert_receiver_is_total_eq`, `assert_receiver_is_total_eq`, `assert_receiver_is_total_eq`, `assert_receiver_is_total_eq`, `assert_receiver_is_total_eq`, `assert_receiver_is_total_eq`, `assert_receiver_is_total_eq`, `assert_receiver_is_total_eq`, `assert_receiver_is_total_eq`, `assert_receiver_is_total_eq`, `assert_receiver_is_total_eq`, `assert_receiver_is_total_eq`, `assert_receiver_is_total_eq`, `assert_receiver_is_total_eq`, `assert_receiver_is_total_eq`, `assert_receiver_is_total_eq`, `assert_receiver_is_total_eq`, `assert_receiver_is_total_eq`, `assert_receiver_is_total_eq`, `assert_receiver_is_total_eq`, `assert_receiver_is_total_eq`, `assert_receiver_is_total_eq`, `assert_receiver_is_total_eq`, `assert_receiver_is_total_eq`, `assert_receiver_is_total_eq`, `assert_receiver_is_total_eq`, `assert_receiver_is_total_eq`, `assert_receiver_is_total_eq`, `assert_receiver_is_total_eq`, `assert_receiver_is_total_eq`, `assert_receiver_is_total_eq`, `assert_receiver_is_total_eq`, `assert_receiver_is_total_eq`, `assert_receiver_is_total_eq`, `assert_receiver_is_total_eq`, `assert_receiver_is_total_eq`, `assert_receiver_is_total_eq`, `assert_receiver_is_total_eq`, `assert_receiver_is_total_eq`, `assert_receiver_is_total_eq`, `clone`, `clone`, `clone`, `clone`, `clone`, `clone`, `clone`, `clone`, `clone`, `clone`, `clone`, `clone`, `clone`, `clone`, `clone`, `clone`, `clone`, `clone`, `clone`, `clone`, `clone`, `clone`, `clone`, `clone`, `clone`, `clone`, `clone`, `clone`, `clone`, `clone`, `clone`, `clone`, `clone`, `clone`, `clone`, `clone`, `clone`, `clone`, `clone`, `clone`, `clone`, `clone`, `clone`, `clone`, `clone`, `clone`, `clone`, `clone`, `clone`, `clone`, `clone`, `clone`, `clone`, `clone`, `clone`, `clone`, `clone`, `clone`, `clone`, `clone`, `clone`, `clone`, `clone`, `clone`, `clone`, `clone`, `clone`, `clone`, `clone`, `clone`, `clone`, `clone`, `clone`, `clone`, `clone`, `clone`, `clone`, `clone`, `clone`, `clone`, `clone`, `clone`, `clone`, `clone`, `clone`, `clone`, `clone`, `clone`, `clone`, `clone`, `clone`, `clone`, `clone`, `clone`, `clone`, `clone`, `clone`, `clone`, `clone`, `clone`, `clone`, `clone`, `clone`, `clone`, `clone`, `clone`, `clone`, `clone`, `clone`, `clone`, `clone`, `clone`, `clone`, `clone`, `clone`, `clone`, `clone`, `clone`, `clone`, `clone`, `clone`, `clone`, `clone`, `clone`, `clone`, `clone`, `clone`, `clone`, `clone`, `clone`, `clone`, `clone`, `clone`, `clone`, `clone`, `delete`, `eq`, `eq`, `eq`, `eq`, `eq`, `eq`, `eq`, `eq`, `eq`, `eq`, `eq`, `eq`, `eq`, `eq`, `eq`, `eq`, `eq`, `eq`, `eq`, `eq`, `eq`, `eq`, `eq`, `eq`, `eq`, `eq`, `eq`, `eq`, `eq`, `eq`, `eq`, `eq`, `eq`, `eq`, `eq`, `eq`, `eq`, `eq`, `eq`, `eq`, `eq`, `eq`, `eq`, `exists`, `fmt`, `fmt`, `fmt`, `fmt`, `fmt`, `fmt`, `fmt`, `fmt`, `fmt`, `fmt`, `fmt`, `fmt`, `fmt`, `fmt`, `fmt`, `fmt`, `fmt`, `fmt`, `fmt`, `fmt`, `fmt`, `fmt`, `fmt`, `fmt`, `fmt`, `fmt`, `fmt`, `fmt`, `fmt`, `fmt`, `fmt`, `fmt`, `fmt`, `fmt`, `fmt`, `fmt`, `fmt`, `fmt`, `fmt`, `fmt`, `fmt`, `fmt`, `fmt`, `fmt`, `fmt`, `fmt`, `fmt`, `fmt`, `fmt`, `fmt`, `fmt`, `fmt`, `fmt`, `fmt`, `fmt`, `fmt`, `fmt`, `fmt`, `fmt`, `fmt`, `fmt`, `fmt`, `fmt`, `fmt`, `fmt`, `fmt`, `fmt`, `fmt`, `fmt`, `fmt`, `fmt`, `fmt`, `fmt`, `fmt`, `fmt`, `fmt`, `fmt`, `fmt`, `fmt`, `fmt`, `fmt`, `fmt`, `fmt`, `fmt`, `fmt`, `fmt`, `fmt`, `fmt`, `fmt`, `fmt`, `fmt`, `fmt`, `fmt`, `fmt`, `fmt`, `fmt`, `fmt`, `fmt`, `fmt`, `fmt`, `fmt`, `fmt`, `fmt`, `fmt`, `fmt`, `fmt`, `fmt`, `fmt`, `fmt`, `fmt`, `fmt`, `fmt`, `fmt`, `fmt`, `fmt`, `fmt`, `fmt`, `fmt`, `fmt`, `fmt`, `fmt`, `fmt`, `fmt`, `fmt`, `fmt`, `fmt`, `fmt`, `fmt`, `fmt`, `fmt`, `fmt`, `fmt`, `fmt`, `fmt`, `fmt`, `fmt`, `fmt`, `fmt`, `fmt`, `fmt`, `fmt`, `fmt`, `fmt`, `fmt`, `fmt`, `fmt`, `fmt`, `fmt`, `fmt`, `fmt`, `fmt`, `from`, `from`, `from`, `from`, `from`, `from`, `from`, `from`, `from`, `from`, `from`, `from`, `from`, `from`, `from`, `from`, `from`, `from`, `from`, `from`, `from`, `from`, `from`, `from`, `from`, `from`, `from`, `from`, `from`, `from`, `from`, `from`, `from`, `from`, `from`, `from`, `from`, `from`, `from`, `from`, `from`, `from`, `from`, `from`, `from`, `from`, `from`, `from`, `from`, `from`, `from`, `from`, `from`, `from`, `from`, `from`, `from`, `from`, `from`, `from`, `from`, `from`, `from`, `from`, `from`, `from`, `from`, `from`, `from`, `from`, `from`, `from`, `from`, `from`, `from`, `from`, `from`, `from`, `from`, `from`, `from`, `from`, `from`, `from`, `from`, `from`, `from`, `from`, `from`, `from`, `from`, `from`, `from`, `from`, `from`, `from`, `from`, `from`, `from`, `from`, `from`, `from`, `from`, `from`, `from`, `nip44_encrypt`, `nip44_encrypt`, `public_key`, `public_key`, `retrieve`, `sign_event_hash`, `sign_event_hash`, `sign_unsigned_event`, `store`, `try_from`, `try_from`, `try_from`, `try_from`
 // These functions are ignored (category: IgnoreBecauseOwnerTyShouldIgnore): `default`
 
+/// The ground size of a geohash cell of `geohash_len` characters at
+/// `latitude` (length clamped to 1..=12).
+CellSizeFfi geohashCellSize({
+  required int geohashLen,
+  required double latitude,
+}) => RustLib.instance.api.crateApiGeohashCellSize(
+  geohashLen: geohashLen,
+  latitude: latitude,
+);
+
+/// The ground size of the cell a circle sees at `precision`, computed at
+/// `latitude` so settings screens can explain it accurately.
+CellSizeFfi precisionCellSize({
+  required LocationPrecisionFfi precision,
+  required double latitude,
+}) => RustLib.instance.api.crateApiPrecisionCellSize(
+  precision: precision,
+  latitude: latitude,
+);
+
+/// Returns `true` if `npub` is a valid `npub1...` (optionally `nostr:`-prefixed).
+bool validateNpub({required String npub}) =>
+    RustLib.instance.api.crateApiValidateNpub(npub: npub);
+
+/// Converts an npub to its 64-character hex public key.
+///
+/// # Errors
+///
+/// Returns an error if `npub` is not a valid npub.
+String npubToHex({required String npub}) =>
+    RustLib.instance.api.crateApiNpubToHex(npub: npub);
+
+/// Converts a 64-character hex public key to its npub.
+///
+/// # Errors
+///
+/// Returns an error if `hex` is not a valid public key.
+String hexToNpub({required String hex}) =>
+    RustLib.instance.api.crateApiHexToNpub(hex: hex);
+
+/// Checks an `nsec1...` secret key without importing it, returning the npub
+/// it belongs to.
+///
+/// # Errors
+///
+/// Returns an error if `nsec` is not a valid nsec; the error never contains
+/// the input.
+String validateNsec({required String nsec}) =>
+    RustLib.instance.api.crateApiValidateNsec(nsec: nsec);
+
+/// Decodes an `nprofile1...` (optionally `nostr:`-prefixed) into its key and
+/// relay hints.
+///
+/// # Errors
+///
+/// Returns an error if `nprofile` is not a valid nprofile.
+NprofileFfi decodeNprofile({required String nprofile}) =>
+    RustLib.instance.api.crateApiDecodeNprofile(nprofile: nprofile);
+
+/// Starts linking this new device to the identity on another device, which
+/// will send it through up to three of `relays`.
+///
+/// # Errors
+///
+/// Returns an error if `relays` is empty.
+DeviceLinkSessionFfi startDeviceLink({required List<String> relays}) =>
+    RustLib.instance.api.crateApiStartDeviceLink(relays: relays);
+
+/// Parses a device transfer bundle.
+///
+/// # Errors
+///
+/// Returns an error if `bytes` is not a transfer bundle.
+TransferBundleFfi parseTransferBundle({required List<int> bytes}) =>
+    RustLib.instance.api.crateApiParseTransferBundle(bytes: bytes);
+
 /// Initializes the platform-specific keyring credential store.
 ///
 /// Must be called **once** before any `CircleManagerFfi` operations. The keyring
@@ -275,6 +351,58 @@ Future<DecryptedLocationFfi> parseEngineLocation({
   senderPubkey: senderPubkey,
 );
 
+/// Decodes decrypted inner `content` into a typed [`HavenPayloadFfi`] (see
+/// `haven_core::payload`).
+///
+/// `topic` is the inner rumor's `["t", ...]` topic, only consulted for
+/// legacy content without a `type`; `sender_pubkey` is stamped on a decoded
+/// location or meet pin. A type this build does not know decodes to
+/// [`HavenPayloadKindFfi::Unknown`] rather than an error.
+///
+/// # Errors
+///
+/// Returns an error if the content is not a valid payload.
+HavenPayloadFfi decodeHavenPayload({
+  required String contentJson,
+  String? topic,
+  required String senderPubkey,
+}) => RustLib.instance.api.crateApiDecodeHavenPayload(
+  contentJson: contentJson,
+  topic: topic,
+  senderPubkey: senderPubkey,
+);
+
+/// Encodes a `haven:` invite payload for a link or QR code.
+///
+/// `pubkey` is hex or `npub1...`; `circle_id`, if given, is a circle's
+/// 32-byte Nostr group id. At most three `wss://` relay hints fit.
+String generateInvitePayload({
+  required String pubkey,
+  required List<String> relays,
+  List<int>? circleId,
+}) => RustLib.instance.api.crateApiGenerateInvitePayload(
+  pubkey: pubkey,
+  relays: relays,
+  circleId: circleId,
+);
+
+/// Parses a scanned or pasted invite payload (with or without `haven:`).
+InvitePayloadFfi parseInvitePayload({required String payload}) =>
+    RustLib.instance.api.crateApiParseInvitePayload(payload: payload);
+
+/// Resolves a NIP-05 identifier (`name@domain`) to a public key and relay
+/// hints, so a contact can be added without pasting an npub.
+///
+/// Goes directly to the domain (Haven has no Tor client) unless a fresh
+/// answer is cached; see [`Nip05ResolutionFfi::privacy_warning`].
+///
+/// # Errors
+///
+/// Returns an error if the identifier is malformed, the domain does not list
+/// it, or the lookup fails.
+Future<Nip05ResolutionFfi> resolveNip05({required String identifier}) =>
+    RustLib.instance.api.crateApiResolveNip05(identifier: identifier);
+
 /// Returns the canonical default relay list shared by Rust and Dart.
 ///
 /// Single source of truth for [`haven_core::circle::default_relays`].
@@ -400,6 +528,34 @@ void allowPrivateBlossomForTest() =>
 void setBlossomServerForTest({required String url}) =>
     RustLib.instance.api.crateApiSetBlossomServerForTest(url: url);
 
+/// Returns the bytes sent to and received from relays, per relay and per
+/// circle, since the process started or [`reset_bandwidth_usage`].
+///
+/// Counted in memory only; nothing is persisted or sent anywhere.
+BandwidthUsageFfi getBandwidthUsage() =>
+    RustLib.instance.api.crateApiGetBandwidthUsage();
+
+/// Zeroes the bandwidth counters (e.g. at the start of a billing period).
+void resetBandwidthUsage() =>
+    RustLib.instance.api.crateApiResetBandwidthUsage();
+
+/// Returns every relay's health counters, by URL, accumulated since they
+/// were last reset (across restarts once a circle manager is open).
+List<RelayMetricsFfi> getRelayMetrics() =>
+    RustLib.instance.api.crateApiGetRelayMetrics();
+
+/// Writes the relay health counters to disk now. Call when the app is
+/// backgrounded so the last minute of counts is not lost.
+void flushRelayMetrics() => RustLib.instance.api.crateApiFlushRelayMetrics();
+
+/// Zeroes the relay health counters, in memory and on disk.
+void resetRelayMetrics() => RustLib.instance.api.crateApiResetRelayMetrics();
+
+/// Recommended delay (seconds) between publishing a `KeyPackage` and
+/// [`RelayManagerFfi::verify_key_package`], from the active environment
+/// profile.
+BigInt kpVerifyDelaySecs() => RustLib.instance.api.crateApiKpVerifyDelaySecs();
+
 /// M8-4 subscription-health maintenance tick (Dart-timer-driven, no secret).
 ///
 /// Reads the `SESSION` global: with no live engine session it returns the inert
@@ -438,6 +594,28 @@ abstract class CircleManagerFfi implements RustOpaqueInterface {
     required List<int> giftWrapId,
   });
 
+  /// Marks a member key change as reviewed.
+  Future<void> acknowledgeMemberKeyChange({required PlatformInt64 id});
+
+  /// Dismisses a missed check-in until the rule's next deadline after
+  /// `deadline_at`.
+  Future<void> acknowledgeMissedCheckin({
+    required String ruleId,
+    required PlatformInt64 deadlineAt,
+  });
+
+  /// Sets up a scheduled check-in: `member_pubkey` (hex) is expected to
+  /// share a location in the circle within `window_minutes` before
+  /// `deadline_minute` (local minutes after midnight) on `weekdays`
+  /// (bit 0 is Monday, `0x7f` every day). Stays on this device.
+  Future<CheckinRuleFfi> addCheckinRule({
+    required List<int> mlsGroupId,
+    required String memberPubkey,
+    required int deadlineMinute,
+    required int windowMinutes,
+    required int weekdays,
+  });
+
   /// Adds members to an existing circle and gift-wraps their Welcomes.
   ///
   /// The add-time counterpart to [`create_circle`]: stages an MLS Add commit
@@ -452,7 +630,7 @@ abstract class CircleManagerFfi implements RustOpaqueInterface {
   ///
   /// # Arguments
   ///
-  /// * `identity_secret_bytes` - The admin's 32-byte Nostr secret key.
+  /// * `secret` - The admin's identity.
   /// * `mls_group_id` - The circle's MLS group ID.
   /// * `members` - Key packages and inbox/NIP-65 relays for the new members.
   /// * `creator_fallback_relays` - The admin's own inbox relays (kind 10050),
@@ -464,20 +642,28 @@ abstract class CircleManagerFfi implements RustOpaqueInterface {
   /// # Security
   ///
   /// The Welcome events are gift-wrapped per NIP-59, hiding the sender's
-  /// identity behind a fresh ephemeral key. The secret bytes are zeroized.
+  /// identity behind a fresh ephemeral key.
   ///
   /// # Errors
   ///
-  /// Returns an error if the secret bytes are invalid, a key package fails to
-  /// parse, the caller is not an admin, or a member has no reachable Welcome
-  /// relay (fail-closed).
+  /// Returns an error if a key package fails to parse, the caller is not an
+  /// admin, or a member has no reachable Welcome relay (fail-closed).
   Future<AddMembersResultFfi> addMembersToCircle({
-    required List<int> identitySecretBytes,
+    required SecretHandle secret,
     required List<int> mlsGroupId,
     required List<MemberKeyPackageFfi> members,
     required List<String> creatorFallbackRelays,
   });
 
+  /// Sets up a proximity alert: raised when `member_pubkey` (hex) comes
+  /// within `range_m` meters of `target`. Stays on this device.
+  Future<ProximityRuleFfi> addProximityRule({
+    required List<int> mlsGroupId,
+    required String memberPubkey,
+    required ProximityTargetFfi target,
+    required double rangeM,
+  });
+
   /// Adds a relay to one category (idempotent).
   ///
   /// The URL is normalized via `nostr::RelayUrl::parse`; duplicates are
@@ -487,6 +673,41 @@ abstract class CircleManagerFfi implements RustOpaqueInterface {
     required RelayTypeFfi relayType,
   });
 
+  /// Adopts an MLS group this device already belongs to through another
+  /// Marmot client as a circle. `circle_type` is `"location_sharing"` or
+  /// `"direct_share"`; a blank `display_name` uses the group's own name.
+  Future<CircleWithMembersFfi> adoptGroup({
+    required List<int> mlsGroupId,
+    required String displayName,
+    required String circleType,
+  });
+
+  /// Restores the circles of an opened transfer bundle. Call after
+  /// [`TransferContentsFfi::install_session`].
+  ///
+  /// # Errors
+  ///
+  /// Returns an error if the bundle belongs to another identity.
+  Future<TransferRestoreFfi> adoptTransferred({
+    required TransferContentsFfi contents,
+  });
+
+  /// Applies a JSON merge patch to the current settings (`null` resets a
+  /// setting to its default) and returns the resulting config JSON.
+  Future<String> applyConfigPatch({required String patchJson});
+
+  /// Reconciles the circle's stored roster with MLS and returns who joined
+  /// or left since the last reconcile.
+  ///
+  /// Decrypt and sync already do this for every `GroupUpdate` (and fill in
+  /// its `membership`); call it to refresh a circle on demand. A departed
+  /// member's last-known location is deleted; if it is this device, the
+  /// circle becomes `Removed`.
+  Future<MembershipDeltaFfi> applyGroupUpdate({required List<int> mlsGroupId});
+
+  /// Hides an active circle without leaving it.
+  Future<void> archiveCircle({required List<int> mlsGroupId});
+
   /// Atomically gates on the toggle, signs a kind 10050 / 10051 event,
   /// and resolves the publish targets.
   ///
@@ -500,9 +721,25 @@ abstract class CircleManagerFfi implements RustOpaqueInterface {
   /// returned `targets`, Dart should call
   /// [`Self::record_published_relay_list`] so the unpublish path can
   /// later issue a NIP-09 deletion referencing the event id.
+  ///
+  /// `pow_difficulty` mines NIP-13 work into the event before returning it
+  /// (capped at [`haven_core::relay::MAX_POW_DIFFICULTY`]); `None` for
+  /// relays that do not require it.
+  ///
+  /// With a remote signer paired ([`Self::configure_remote_signer`]), the
+  /// event is signed by it and `secret` is not used.
   Future<BuiltRelayListEventFfi> buildRelayListPublish({
-    required List<int> identitySecretBytes,
+    required SecretHandle secret,
+    required RelayTypeFfi relayType,
+    int? powDifficulty,
+  });
+
+  /// [`Self::build_relay_list_publish`], signed by a key held outside this
+  /// process (see [`ExternalSignerFfi`]).
+  Future<BuiltRelayListEventFfi> buildRelayListPublishWithSigner({
+    required ExternalSignerFfi signer,
     required RelayTypeFfi relayType,
+    int? powDifficulty,
   });
 
   /// Builds a best-effort NIP-09 deletion to scrub a removed relay's stale
@@ -526,10 +763,14 @@ abstract class CircleManagerFfi implements RustOpaqueInterface {
   /// last-published event is the stale one being scrubbed. Returns
   /// `suppressed=true` with no event when nothing was ever published for
   /// this kind (the dropped relays never received our list).
+  ///
+  /// `pow_difficulty` mines NIP-13 work into the deletion, as for
+  /// [`Self::build_relay_list_publish`].
   Future<BuiltUnpublishFfi> buildRelayRemovalScrub({
-    required List<int> identitySecretBytes,
+    required SecretHandle secret,
     required RelayTypeFfi relayType,
     required List<String> droppedRelays,
+    int? powDifficulty,
   });
 
   /// Builds the events needed to unpublish a relay list category.
@@ -542,15 +783,58 @@ abstract class CircleManagerFfi implements RustOpaqueInterface {
   /// Dart should publish both events to the returned `targets` and then
   /// also flip the toggle off via `set_publish_relay_list`. This method
   /// itself does not change the toggle.
+  ///
+  /// `pow_difficulty` mines NIP-13 work into both events, and a paired
+  /// remote signer signs them, as for [`Self::build_relay_list_publish`].
   Future<BuiltUnpublishFfi> buildUnpublishRelayList({
-    required List<int> identitySecretBytes,
+    required SecretHandle secret,
     required RelayTypeFfi relayType,
+    int? powDifficulty,
+  });
+
+  /// Folds `circles.db`'s write-ahead log back into the database file.
+  ///
+  /// Call at quiet moments (app backgrounded, after a large sync). Returns
+  /// `false` if part of the log was still in use; the rest is folded next
+  /// time.
+  Future<bool> checkpointStorage();
+
+  /// When sharing was paused for this circle alone (Unix timestamp), or
+  /// `None` if it is not. See [`Self::is_location_paused`] for whether
+  /// anything is sent to it.
+  Future<PlatformInt64?> circleLocationPausedAt({
+    required List<int> mlsGroupId,
   });
 
+  /// Deletes every stored breadcrumb.
+  Future<void> clearBreadcrumbs();
+
+  /// Removes a contact's picture.
+  Future<ContactFfi> clearContactAvatar({required String pubkey});
+
+  /// Clears `pubkey`'s verification.
+  Future<ContactFfi> clearContactVerification({required String pubkey});
+
+  /// Forgets the remote signer; signing goes back to the local key.
+  /// Returns `false` if none was paired.
+  Future<bool> clearRemoteSigner();
+
+  /// Summary of a frozen circle, or `None` if it is not in cold storage.
+  Future<ColdCircleInfoFfi?> coldCircleInfo({required List<int> mlsGroupId});
+
   /// Removes the local circle row after a successful leave sequence, or
   /// for the `OrphanLocalOnly` plan. (Storage-only; sync in the core.)
   Future<void> completeLeave({required List<int> mlsGroupId});
 
+  /// Pairs with the NIP-46 remote signer ("bunker") at `bunker_uri` and
+  /// routes key package, relay list and one-shot gift-wrap signing through
+  /// it from now on, replacing any previous pairing.
+  ///
+  /// The signer device may ask the user to approve the pairing; each
+  /// request waits up to a minute. Fails closed, storing nothing, if the
+  /// bunker signs as another identity than this device's.
+  Future<RemoteSignerFfi> configureRemoteSigner({required String bunkerUri});
+
   /// Confirms a staged commit was published (≥1-relay OK-ack) so the engine
   /// applies it and advances the epoch.
   ///
@@ -567,8 +851,8 @@ abstract class CircleManagerFfi implements RustOpaqueInterface {
   ///
   /// # Arguments
   ///
-  /// * `identity_secret_bytes` - The creator's identity secret bytes (32 bytes,
-  ///   from `NostrIdentityManager.get_secret_bytes()`)
+  /// * `secret` - The creator's identity (from
+  ///   `NostrIdentityManager.secret_handle()`)
   /// * `members` - Key packages and inbox relays for each member
   /// * `name` - Circle name
   /// * `description` - Optional circle description
@@ -586,7 +870,7 @@ abstract class CircleManagerFfi implements RustOpaqueInterface {
   /// identity behind an ephemeral key. Each Welcome uses a fresh ephemeral
   /// keypair and randomized timestamp.
   Future<CircleCreationResultFfi> createCircle({
-    required List<int> identitySecretBytes,
+    required SecretHandle secret,
     required List<MemberKeyPackageFfi> members,
     required String name,
     String? description,
@@ -595,6 +879,23 @@ abstract class CircleManagerFfi implements RustOpaqueInterface {
     required List<String> creatorFallbackRelays,
   });
 
+  /// Creates a circle with every contact labeled `label`, fetching each
+  /// one's key package through `relay_manager`.
+  ///
+  /// Contacts without a usable key package are skipped and listed in
+  /// `without_key_package`; publish and confirm the result exactly like
+  /// [`Self::create_circle`].
+  Future<LabeledCircleCreationFfi> createCircleFromLabel({
+    required RelayManagerFfi relayManager,
+    required SecretHandle secret,
+    required String label,
+    required String name,
+    String? description,
+    required String circleType,
+    required List<String> relays,
+    required List<String> creatorFallbackRelays,
+  });
+
   /// Advances `stream`'s cursor to `ms` (monotonic max; never backward).
   ///
   /// `ms` is a millisecond timestamp. Prefer the seconds-taking semantic
@@ -688,14 +989,17 @@ abstract class CircleManagerFfi implements RustOpaqueInterface {
   Future<void> declineInvitation({required List<int> giftWrapId});
 
   /// Decrypts / ingests a received `kind:445` event, returning the folded
-  /// engine results (Dark Matter five-variant taxonomy).
+  /// engine results (Dark Matter six-variant taxonomy).
   ///
   /// A single ingest can yield SEVERAL [`LocationMessageResultFfi`] — the
   /// engine's `advance_convergence` may release buffered inbound after the
   /// outer event — so this returns a `Vec`. The engine owns stale / duplicate
   /// / out-of-order handling internally (a future-epoch event is durably
   /// buffered and re-surfaced once the gap fills), so there is no
-  /// `Unprocessable` / `PreviouslyFailed` outcome anymore.
+  /// `Unprocessable` / `PreviouslyFailed` outcome anymore. An event that was
+  /// already ingested (e.g. re-fetched after a restart) returns its cached
+  /// locations, SOS, meet pins and group updates without touching MLS
+  /// state, or the error it failed with.
   ///
   /// # Peer `SelfRemove` eviction (auto-commit) — Rule 13
   ///
@@ -756,6 +1060,33 @@ abstract class CircleManagerFfi implements RustOpaqueInterface {
     required String eventJson,
   });
 
+  /// [`decrypt_location_collecting_commits`](Self::decrypt_location_collecting_commits)
+  /// for an event from `RelayManagerFfi::fetch_group_messages_compact`.
+  ///
+  /// The id and signature are re-verified before ingest; the publish /
+  /// confirm contract for `auto_commits` is unchanged.
+  ///
+  /// # Errors
+  ///
+  /// Returns an error if a field is malformed or fails verification, or a
+  /// redacted error string if the engine ingest fails hard.
+  Future<DecryptLocationOutcomeFfi> decryptLocationCompact({
+    required CompactEventFfi event,
+  });
+
+  /// Decrypts / ingests a batch of fetched `kind:445` events in one call,
+  /// e.g. a cold-start sync of hundreds of them.
+  ///
+  /// The events are processed in `created_at` order under a single session
+  /// lock, so a backlog costs far less than calling
+  /// [`decrypt_location_collecting_commits`](Self::decrypt_location_collecting_commits)
+  /// per event. Returns one item per input, in processing order; one bad
+  /// event never fails the batch. Each item's `auto_commits` carry the same
+  /// publish / confirm contract. Only unparseable JSON fails the whole call.
+  Future<List<DecryptBatchItemFfi>> decryptLocationsBatch({
+    required List<String> eventsJson,
+  });
+
   /// Deletes a contact.
   Future<void> deleteContact({required String pubkey});
 
@@ -770,7 +1101,7 @@ abstract class CircleManagerFfi implements RustOpaqueInterface {
   /// # Errors
   ///
   /// Returns a redacted error string on relay or database failure.
-  Future<void> deleteMyPublicProfile({required List<int> identitySecretBytes});
+  Future<void> deleteMyPublicProfile({required SecretHandle secret});
 
   /// Reconciles a member's cached profile-picture bytes with their current
   /// kind-0 `picture` URL (authoritative; never driven by Dart).
@@ -827,12 +1158,74 @@ abstract class CircleManagerFfi implements RustOpaqueInterface {
   ///   plus a network-propagation buffer — see `location.dart`. The
   ///   absolute expiration timestamp is sampled uniformly from
   ///   `[interval, 2 * interval]` seconds in the future.
+  /// * `share_expiration_secs` - How long members keep this location
+  ///   (e.g. 3600 / 28800 / 86400, or any value clamped to
+  ///   `[3600, 86400]`). `None` keeps the 1-day default. Members can only
+  ///   be asked to drop it sooner, never to keep it longer.
+  /// * `device_status` - Battery / charging / motion to send along. Pass it
+  ///   only while [`LocationSettings::share_device_status`] is on; `None`
+  ///   sends no status.
+  /// * `vertical_position` - Altitude and floor level to send along. Pass
+  ///   it only while [`LocationSettings::share_vertical_position`] is on;
+  ///   `None` sends neither. Dropped when the circle shares coarsely.
+  ///
+  /// The circle's own settings (see [`Self::set_circle_location_settings`])
+  /// are applied on top: the location may be rounded and its expiration
+  /// shortened further, never the reverse.
+  ///
+  /// Fails without producing an event while sharing to the circle is
+  /// paused (see [`Self::set_location_paused`]).
   Future<EncryptedLocationFfi> encryptLocation({
     required List<int> mlsGroupId,
     required String senderPubkeyHex,
     required double latitude,
     required double longitude,
     required BigInt updateIntervalSecs,
+    BigInt? shareExpirationSecs,
+    DeviceStatusFfi? deviceStatus,
+    VerticalPositionFfi? verticalPosition,
+  });
+
+  /// The circle's epoch position: current epoch, the oldest one whose
+  /// messages still decrypt, and the member count.
+  Future<EpochInfoFfi> epochInfo({required List<int> mlsGroupId});
+
+  /// The proximity alerts raised by now: members who came within a rule's
+  /// range since the last call, earliest sighting first.
+  ///
+  /// Reads only this device's database; safe to call from a background
+  /// task.
+  Future<List<ProximityAlertFfi>> evaluateProximityRules();
+
+  /// The scheduled check-ins missed by now, earliest deadline first.
+  /// `utc_offset_secs` is the device's current offset east of UTC, which
+  /// the deadlines are read in.
+  ///
+  /// Reads only this device's database; safe to call from a background
+  /// task.
+  Future<List<MissedCheckinFfi>> evaluateSafetyRules({
+    required int utcOffsetSecs,
+  });
+
+  /// Returns the health snapshot (privacy facts + breadcrumbs) as JSON for
+  /// the user to share. Nothing is sent anywhere by this call.
+  Future<String> exportHealthSnapshot();
+
+  /// Seals this device's circles into a transfer bundle for a new device,
+  /// with `identity_ncryptsec` (from `export_encrypted`) as its key.
+  ///
+  /// With `carry_state` the MLS state moves into the bundle and this
+  /// device stops sending or reading circle traffic for good; without it
+  /// the new device rejoins each circle through an admin.
+  ///
+  /// # Errors
+  ///
+  /// Returns an error if a circle change is still in flight, the backup is
+  /// not an `ncryptsec` of this identity, or sealing fails.
+  Future<Uint8List> exportTransferBundle({
+    required SecretHandle secret,
+    required String identityNcryptsec,
+    required bool carryState,
   });
 
   /// Resolves public profiles for the given member pubkeys, fetching stale or
@@ -876,9 +1269,25 @@ abstract class CircleManagerFfi implements RustOpaqueInterface {
     required List<int> mlsGroupId,
   });
 
+  /// Moves an archived circle's local history into compressed, sealed
+  /// cold storage. Returns the existing summary if already frozen.
+  Future<ColdCircleInfoFfi> freezeCircle({required List<int> mlsGroupId});
+
+  /// Lists running precise-sharing sessions, soonest to end first.
+  Future<List<SharingSessionFfi>> getActiveSessions();
+
+  /// The circle's admins, as hex public keys.
+  Future<List<String>> getAdmins({required List<int> mlsGroupId});
+
   /// Gets all contacts.
   Future<List<ContactFfi>> getAllContacts();
 
+  /// Circles kept for their history but no longer in use: archived, left
+  /// with [`leave_and_archive_circle`](Self::leave_and_archive_circle), or
+  /// removed by an admin. See
+  /// [`get_circle_lifecycle`](Self::get_circle_lifecycle) to tell them apart.
+  Future<List<CircleWithMembersFfi>> getArchivedCircles();
+
   /// Returns the locally cached profile for a pubkey, or `None`.
   ///
   /// Pure cache read (no network) — the synchronous hot path for member
@@ -889,6 +1298,9 @@ abstract class CircleManagerFfi implements RustOpaqueInterface {
   /// Returns a redacted error string on database failure.
   ProfileMetadataFfi? getCachedProfile({required String pubkeyHex});
 
+  /// Scheduled check-ins of one circle, or of all circles with `None`.
+  Future<List<CheckinRuleFfi>> getCheckinRules({List<int>? mlsGroupId});
+
   /// Gets a circle by its MLS group ID.
   ///
   /// Async: resolving the roster reads the Dark Matter session (which is
@@ -896,12 +1308,91 @@ abstract class CircleManagerFfi implements RustOpaqueInterface {
   /// the current worker rather than dispatching to the blocking pool.
   Future<CircleWithMembersFfi?> getCircle({required List<int> mlsGroupId});
 
+  /// Returns the circle's lifecycle state (`"invited"`, `"pending"`,
+  /// `"active"`, `"archived"`, `"left"`, `"removed"` or `"declined"`).
+  Future<String> getCircleLifecycle({required List<int> mlsGroupId});
+
+  /// Returns a circle's location sharing overrides, or `None` if it follows
+  /// the global settings.
+  Future<CircleLocationSettingsFfi?> getCircleLocationSettings({
+    required List<int> mlsGroupId,
+  });
+
+  /// Summarizes where the circle's members are relative to `geofences`.
+  ///
+  /// Async: reads the roster from the Dark Matter session. Returns counts
+  /// only, so home-screen widgets never receive member coordinates.
+  Future<CircleStatusFfi> getCircleStatus({
+    required List<int> mlsGroupId,
+    required List<GeofenceFfi> geofences,
+    required PlatformInt64 nowUnixSecs,
+  });
+
   /// Gets all circles.
   Future<List<CircleWithMembersFfi>> getCircles();
 
+  /// Returns whether the community relay blacklist is enabled (default
+  /// `false`).
+  Future<bool> getCommunityBlacklistEnabled();
+
   /// Gets a contact by pubkey.
   Future<ContactFfi?> getContact({required String pubkey});
 
+  /// A contact's picture as JPEG bytes, or `None` if it has none.
+  Future<Uint8List?> getContactAvatar({required String pubkey});
+
+  /// Labels on the contact `pubkey`, alphabetically.
+  Future<List<String>> getContactLabels({required String pubkey});
+
+  /// Contacts carrying `label`.
+  Future<List<ContactFfi>> getContactsByLabel({required String label});
+
+  /// Everyone who would receive the next location update, across all
+  /// circles, with the user's name for them and the precision each circle
+  /// gets. The answer to "who can see me right now".
+  Future<List<AudienceMemberFfi>> getCurrentAudience();
+
+  /// Builds the minimal snapshot for watch complications and home-screen
+  /// widgets across every visible circle.
+  ///
+  /// `own_latitude` / `own_longitude` (both or neither) only bucket
+  /// distances and are not carried in the result.
+  Future<GlanceableSnapshotFfi> getGlanceableSnapshot({
+    double? ownLatitude,
+    double? ownLongitude,
+    required PlatformInt64 nowUnixSecs,
+  });
+
+  /// Returns the current settings as one `HavenConfig` JSON document, for
+  /// export or display.
+  Future<String> getHavenConfig();
+
+  /// Returns whether the member key audit is enabled (default `false`).
+  Future<bool> getKeyAuditEnabled();
+
+  /// Returns the latest fetch-back verification of the published
+  /// `KeyPackage` (see [`RelayManagerFfi::verify_key_package`]), or `None`
+  /// if none has run since the package was last published.
+  Future<KpVerificationFfi?> getKeyPackageVerification();
+
+  /// Returns each member's newest location with its
+  /// `effective_display_location`, which is `None` once older than the
+  /// circle's display threshold. UIs draw the display location so stale
+  /// markers blank out everywhere alike.
+  Future<List<MemberLocationFfi>> getMemberLocations({
+    required List<int> mlsGroupId,
+    required PlatformInt64 nowUnixSecs,
+  });
+
+  /// When each member of the circle other than the local user was last
+  /// heard from, in roster order. Use this instead of inferring liveness
+  /// from location history: group messages that carry no location count
+  /// too.
+  Future<List<MemberPresenceFfi>> getMemberPresence({
+    required List<int> mlsGroupId,
+    required PlatformInt64 nowUnixSecs,
+  });
+
   /// Gets members of a circle with resolved contact info.
   ///
   /// Async: reads the roster from the Dark Matter session (awaits directly).
@@ -919,6 +1410,30 @@ abstract class CircleManagerFfi implements RustOpaqueInterface {
   /// [`decline_invitation`](Self::decline_invitation).
   Future<List<InvitationFfi>> getPendingInvitations();
 
+  /// Returns stored locations inside the map viewport captured between
+  /// `from` and `to` (Unix seconds, inclusive), newest first and at most
+  /// `limit` (capped at 1000). Covers every circle unless `mls_group_id`
+  /// is given. `min_lon > max_lon` means the viewport crosses the
+  /// antimeridian.
+  Future<List<HistoryPointFfi>> getPointsInViewport({
+    List<int>? mlsGroupId,
+    required double minLat,
+    required double minLon,
+    required double maxLat,
+    required double maxLon,
+    required PlatformInt64 from,
+    required PlatformInt64 to,
+    required int limit,
+    required PlatformInt64 nowUnixSecs,
+  });
+
+  /// Returns the privacy facts derived from the live configuration
+  /// (retention windows, precision, relay sets, network routing).
+  Future<PrivacyFactsFfi> getPrivacyFacts();
+
+  /// Returns the privacy settings for published events.
+  Future<PrivacySettingsFfi> getPrivacySettings();
+
   /// Returns a member's cached full-resolution profile-picture bytes, or `None`.
   ///
   /// # Errors
@@ -933,10 +1448,20 @@ abstract class CircleManagerFfi implements RustOpaqueInterface {
   /// Returns a redacted error string on database failure.
   Future<Uint8List?> getProfileThumbnail({required String pubkeyHex});
 
+  /// Proximity alerts of one circle, or of all circles with `None`.
+  Future<List<ProximityRuleFfi>> getProximityRules({List<int>? mlsGroupId});
+
   /// Returns whether this user wants to publish their relay list for the
   /// given category. Defaults to `true` when never set.
   Future<bool> getPublishRelayList({required RelayTypeFfi relayType});
 
+  /// The paired remote signer, or `None` if signing uses the local key.
+  Future<RemoteSignerFfi?> getRemoteSigner();
+
+  /// The safety number shared with `pubkey` (hex or npub), as 5-digit
+  /// groups. Both people see the same number; compare it in person.
+  String getSafetyNumber({required String pubkey});
+
   /// Gets visible circles (excludes declined invitations).
   Future<List<CircleWithMembersFfi>> getVisibleCircles();
 
@@ -955,9 +1480,73 @@ abstract class CircleManagerFfi implements RustOpaqueInterface {
   /// Returns an error if the group does not exist or the MDK query fails.
   Future<BigInt> groupEpochForTest({required List<int> mlsGroupId});
 
+  /// The circle's receive-side health. Show a "circle needs repair" state
+  /// while `needs_repair` is set, offering [`Self::repair_group`].
+  Future<GroupHealthFfi> groupHealth({required List<int> mlsGroupId});
+
+  /// Lists the circles that still depend on the local identity. Show this
+  /// before offering identity deletion.
+  Future<IdentityDeletionReportFfi> identityDeletionReport();
+
+  /// Whether sharing to a circle is paused, globally or for the circle.
+  Future<bool> isLocationPaused({required List<int> mlsGroupId});
+
+  /// Leaves every circle the user can leave, at once (panic button).
+  ///
+  /// Leave proposals are queued in the offline outbox and returned so they
+  /// can be published immediately; left circles are removed locally.
+  /// Admin circles are kept and listed in `not_left`.
+  Future<LeaveAllReportFfi> leaveAllCircles();
+
+  /// Leaves a circle but keeps its local history, read-only, until
+  /// [`purge_circle`](Self::purge_circle).
+  ///
+  /// The `SelfRemove` proposal is queued in the offline outbox and returned
+  /// so it can be published right away; `None` for a sole-member or
+  /// orphaned circle, which needs nothing published.
+  Future<LeaveEventFfi?> leaveAndArchiveCircle({required List<int> mlsGroupId});
+
+  /// Returns stored breadcrumbs, oldest first.
+  Future<List<BreadcrumbFfi>> listBreadcrumbs();
+
+  /// Every label in use with its contact count, for the label picker.
+  Future<List<ContactLabelFfi>> listContactLabels();
+
+  /// Lists a circle's live meet pins (own and received), oldest first.
+  /// Expired pins are removed, never returned.
+  Future<List<MeetPinFfi>> listMeetPins({required List<int> mlsGroupId});
+
+  /// Lists member key changes, newest first. With
+  /// `include_acknowledged == false` only changes awaiting review.
+  Future<List<MemberKeyChangeFfi>> listMemberKeyChanges({
+    required bool includeAcknowledged,
+  });
+
+  /// Lists the user's per-relay block/allow decisions.
+  Future<List<RelayOverrideEntryFfi>> listRelayOverrides();
+
   /// Returns the user's relays for one category, ordered by insertion time.
   Future<List<String>> listUserRelays({required RelayTypeFfi relayType});
 
+  /// When sharing was paused for every circle (Unix timestamp), or `None`
+  /// if it is not.
+  Future<PlatformInt64?> locationPausedAt();
+
+  /// Marks `pubkey` as verified after the safety numbers matched in person.
+  Future<ContactFfi> markContactVerified({required String pubkey});
+
+  /// Records that the local user was removed from the circle by an admin.
+  /// The row is kept (hidden) until `purge_circle` deletes it.
+  Future<void> markRemoved({required List<int> mlsGroupId});
+
+  /// Current distance and rough ETA from `member_pubkey` (hex) to
+  /// `target`, or `None` while either position is missing or stale.
+  Future<ProximityEstimateFfi?> memberProximity({
+    required List<int> mlsGroupId,
+    required String memberPubkey,
+    required ProximityTargetFfi target,
+  });
+
   // HINT: Make it `#[frb(sync)]` to let it become the default constructor of Dart class.
   /// Creates a new circle manager bound to the device identity.
   ///
@@ -971,21 +1560,24 @@ abstract class CircleManagerFfi implements RustOpaqueInterface {
   /// its account identity, its NIP-59 welcome signer, AND its hardened
   /// account-identity-proof signer (Rule 1), so the identity secret is now a
   /// HARD construction requirement — the engine cannot open without it.
-  /// `identity_secret_bytes` (32 bytes, from
-  /// `NostrIdentityManager.get_secret_bytes()`) MUST be present; a wrong
-  /// length or missing identity fails closed with a clear error, matching the
-  /// existing identity-gating call sites. The bytes are `Zeroizing`-wrapped
-  /// and dropped before this returns.
+  /// `secret` (from `NostrIdentityManager.secret_handle()`) MUST be present;
+  /// a missing identity fails closed with a clear error, matching the
+  /// existing identity-gating call sites.
   ///
   /// [`SessionManager`]: haven_core::nostr::mls::SessionManager
   static Future<CircleManagerFfi> newInstance({
     required String dataDir,
-    required List<int> identitySecretBytes,
+    required SecretHandle secret,
   }) => RustLib.instance.api.crateApiCircleManagerFfiNew(
     dataDir: dataDir,
-    identitySecretBytes: identitySecretBytes,
+    secret: secret,
   );
 
+  /// When the next debounced relay-list republish is due (Unix seconds),
+  /// or `None` if no relay-preference edit is pending. Dart schedules
+  /// [`RelayManagerFfi::flush_relay_list_republishes`] for then.
+  PlatformInt64? nextRelayListPublishAt();
+
   /// Classifies the leave operation — see [`LeavePlanFfi`] for the
   /// Flutter-side state machine.
   Future<LeavePlanFfi> planLeave({
@@ -993,6 +1585,27 @@ abstract class CircleManagerFfi implements RustOpaqueInterface {
     required String selfPubkeyHex,
   });
 
+  /// Wipes the identity-scoped circle state ahead of identity deletion,
+  /// optionally leaving every circle first. Fails while circles remain
+  /// unless `force` is set. Prefer
+  /// [`NostrIdentityManager::delete_identity_guarded`], which also deletes
+  /// the key.
+  Future<IdentityTeardownFfi> prepareIdentityDeletion({
+    required bool force,
+    required bool leaveFirst,
+  });
+
+  /// Computes exactly what [`Self::encrypt_location`] would publish to the
+  /// circle for a raw position, under its current settings, so the preview
+  /// map matches reality. Pass the same `share_expiration_secs` the send
+  /// would use. Nothing is sent.
+  Future<SharePreviewFfi> previewShare({
+    required List<int> mlsGroupId,
+    required double rawLat,
+    required double rawLon,
+    BigInt? shareExpirationSecs,
+  });
+
   /// Processes a gift-wrapped Welcome event (kind 1059).
   ///
   /// This is the high-level API for processing incoming invitations.
@@ -1002,7 +1615,7 @@ abstract class CircleManagerFfi implements RustOpaqueInterface {
   ///
   /// # Arguments
   ///
-  /// * `identity_secret_bytes` - The recipient's identity secret bytes (32 bytes)
+  /// * `secret` - The recipient's identity
   /// * `gift_wrap_event_json` - The kind 1059 gift-wrapped event JSON
   ///
   /// # Returns
@@ -1016,10 +1629,23 @@ abstract class CircleManagerFfi implements RustOpaqueInterface {
   /// * `Err(msg)` — a real failure (malformed event, MDK error, etc.).
   ///   The message is already sanitized via `redact_hex_sequences`.
   Future<InvitationFfi?> processGiftWrappedInvitation({
-    required List<int> identitySecretBytes,
+    required SecretHandle secret,
     required String giftWrapEventJson,
   });
 
+  /// Processes a batch of gift-wrapped Welcome events (kind 1059), e.g. the
+  /// inbox backlog after a long time offline.
+  ///
+  /// The wraps are unwrapped in parallel and then held one at a time, so a
+  /// backlog costs far less than calling
+  /// [`Self::process_gift_wrapped_invitation`] per event. Returns one
+  /// outcome per input, in input order; one bad wrap never fails the batch.
+  /// Only unparseable JSON fails the whole call.
+  Future<List<GiftWrapOutcomeFfi>> processGiftWrappedInvitations({
+    required SecretHandle secret,
+    required List<String> giftWrapEventsJson,
+  });
+
   /// Step 1 of admin handoff: propose promoting `successor_hex` to admin.
   ///
   /// # GAP (plan §5.2 #18)
@@ -1054,6 +1680,9 @@ abstract class CircleManagerFfi implements RustOpaqueInterface {
   /// Returns the number of rows removed.
   Future<int> pruneExpiredLastKnown({required PlatformInt64 nowUnixSecs});
 
+  /// Deletes every expired meet pin. Returns how many were removed.
+  Future<int> pruneExpiredMeetPins();
+
   /// Prunes the gift-wrap dedup cache (`processed_gift_wraps`): drops rows
   /// past the retention window, then enforces the row cap. Returns the number
   /// of rows removed. Best-effort maintenance, safe to call on every poll
@@ -1068,6 +1697,15 @@ abstract class CircleManagerFfi implements RustOpaqueInterface {
   /// pass the same `pending` token.
   Future<void> publishFailed({required PendingStateRefFfi pending});
 
+  /// Returns the jittered delay before the next publish to a circle, in
+  /// seconds. Like [`HavenCore::jittered_publish_interval_secs`], but a
+  /// running trip mode's shorter interval replaces `nominal_secs`. The
+  /// publisher should rearm each circle's timer with this value.
+  Future<BigInt> publishIntervalSecs({
+    required List<int> mlsGroupId,
+    required BigInt nominalSecs,
+  });
+
   /// Publishes the local user's OWN public profile (fetch → merge → publish).
   ///
   /// Publishing is **unconditional** (public-by-default, owner-directed
@@ -1082,11 +1720,22 @@ abstract class CircleManagerFfi implements RustOpaqueInterface {
   ///
   /// Returns a redacted error string on relay or database failure.
   Future<ProfileMetadataFfi> publishMyProfile({
-    required List<int> identitySecretBytes,
+    required SecretHandle secret,
     String? displayName,
     String? about,
   });
 
+  /// Deletes a circle the user is no longer a member of, with its local
+  /// history. (Storage-only; sync in the core.)
+  Future<void> purgeCircle({required List<int> mlsGroupId});
+
+  /// Records a local error breadcrumb. Both arguments must be short
+  /// lowercase slugs — never pass an error message.
+  Future<void> recordBreadcrumb({
+    required String module,
+    required String errorKind,
+  });
+
   /// Records a successful publication so the unpublish path can issue a
   /// NIP-09 deletion later. Pass the `event_id_hex`, `kind`, and
   /// `published_at_secs` returned in
@@ -1103,6 +1752,26 @@ abstract class CircleManagerFfi implements RustOpaqueInterface {
     required PlatformInt64 publishedAtSecs,
   });
 
+  /// Fetches the newest community relay blacklist signed by the maintainer
+  /// key pinned in haven-core from `relays`, verifies it, stores it if
+  /// newer, and re-applies the merged blacklist.
+  ///
+  /// Returns `true` if a newer list was stored. Fails when the user has not
+  /// opted in, so nothing is fetched without consent.
+  Future<bool> refreshCommunityBlacklist({required List<String> relays});
+
+  /// Invites an unjoined member again with their current `KeyPackage`,
+  /// when [`resend_invite`](Self::resend_invite) returned `None`.
+  ///
+  /// Publish and confirm like
+  /// [`add_members_to_circle`](Self::add_members_to_circle).
+  Future<AddMembersResultFfi> reissueInvite({
+    required SecretHandle secret,
+    required List<int> mlsGroupId,
+    required MemberKeyPackageFfi member,
+    required List<String> creatorFallbackRelays,
+  });
+
   /// Returns the deduplicated publish targets for `relay_type` — the
   /// user's own configured relays and nothing else.
   ///
@@ -1113,6 +1782,9 @@ abstract class CircleManagerFfi implements RustOpaqueInterface {
   /// targets in Dart — call [`Self::build_relay_list_publish`] instead.
   Future<List<String>> relayPublishTargets({required RelayTypeFfi relayType});
 
+  /// Removes a scheduled check-in.
+  Future<void> removeCheckinRule({required String ruleId});
+
   /// Removes every last-known location row for a circle.
   ///
   /// Called when the user leaves or deletes a circle.
@@ -1147,9 +1819,12 @@ abstract class CircleManagerFfi implements RustOpaqueInterface {
   ///
   /// Returns a redacted error string on relay or database failure.
   Future<ProfileMetadataFfi> removeMyProfilePicture({
-    required List<int> identitySecretBytes,
+    required SecretHandle secret,
   });
 
+  /// Removes a proximity alert.
+  Future<void> removeProximityRule({required String ruleId});
+
   /// Removes a relay from one category.
   ///
   /// Returns `true` when a row was removed, `false` when the URL was not
@@ -1160,11 +1835,53 @@ abstract class CircleManagerFfi implements RustOpaqueInterface {
     required RelayTypeFfi relayType,
   });
 
+  /// Attempts to bring a circle flagged by [`Self::group_health`] back in
+  /// step. Route `drained.results` like any decrypt, and publish then
+  /// confirm each `drained.auto_commits` entry exactly as for
+  /// [`Self::decrypt_location_collecting_commits`].
+  Future<GroupRepairFfi> repairGroup({required List<int> mlsGroupId});
+
+  /// Asks `target_pubkey_hex` to share their current location now.
+  ///
+  /// Publish the returned event to its relays exactly like
+  /// [`Self::encrypt_location`]'s result. Receivers see a
+  /// [`LocationMessageResultKindFfi::CheckinRequest`].
+  Future<EncryptedLocationFfi> requestCheckin({
+    required List<int> mlsGroupId,
+    required String targetPubkeyHex,
+  });
+
+  /// Resends an unjoined member's invitation.
+  ///
+  /// Returns the original Welcome to publish again to its relays, or
+  /// `None` when the circle has changed since: then fetch the member's
+  /// current `KeyPackage` and call [`reissue_invite`](Self::reissue_invite).
+  ///
+  /// # Errors
+  ///
+  /// Returns an error if there is no invitation to the member, they have
+  /// joined or left, or the invitation was resent too recently or too
+  /// often.
+  Future<GiftWrappedWelcomeFfi?> resendInvite({
+    required List<int> mlsGroupId,
+    required String memberPubkey,
+  });
+
   /// Resets ALL sync cursors (bulk) for the wipe-on-logout path, so a
   /// returning identity re-seeds cleanly instead of resuming at a stale
   /// floor. Errors are redacted.
   Future<void> resetAllSyncCursors();
 
+  /// Answers a check-in request with the current location.
+  ///
+  /// The result is an ordinary location update; publish it like
+  /// [`Self::encrypt_location`]'s result.
+  Future<EncryptedLocationFfi> respondToCheckin({
+    required List<int> mlsGroupId,
+    required double latitude,
+    required double longitude,
+  });
+
   /// Restores defaults for a category **non-destructively**.
   ///
   /// Adds any missing default relays via `INSERT OR IGNORE`. Existing
@@ -1172,6 +1889,20 @@ abstract class CircleManagerFfi implements RustOpaqueInterface {
   /// [`Self::wipe_and_reset_defaults_for`] for the destructive variant.
   Future<void> restoreDefaultsFor({required RelayTypeFfi relayType});
 
+  /// Builds a NIP-09 deletion pulling back a location this device published.
+  ///
+  /// Publish the returned event to its relays with
+  /// [`RelayManagerFfi::publish_event`]. Deletion is a request: relays may
+  /// ignore it and members who already fetched the location keep it.
+  ///
+  /// Currently always fails for an existing circle: the engine does not
+  /// expose the ephemeral key that signed the location, and only that key
+  /// can delete it.
+  Future<LocationRetractionFfi> retractLocation({
+    required List<int> mlsGroupId,
+    required String eventIdHex,
+  });
+
   /// Seeds the user's relay lists with the default relay list returned by
   /// [`haven_core::circle::default_relays`] on first launch.
   ///
@@ -1186,25 +1917,117 @@ abstract class CircleManagerFfi implements RustOpaqueInterface {
   /// already set.
   Future<bool> seedRelayDefaultsIfUnseeded();
 
-  /// Sets or updates a contact.
+  /// Drops a time-limited meet pin at an exact point for the circle.
   ///
-  /// Contact information is stored locally only and never synced to relays.
-  Future<ContactFfi> setContact({
-    required String pubkey,
-    String? displayName,
-    String? notes,
+  /// `exact_location_consent` must be `true`: only pass it after the user
+  /// confirmed sharing the exact spot. `ttl_secs` is clamped to 5 minutes
+  /// ..= 24 hours (2 hours is the suggested default). Publish the returned
+  /// event like [`Self::encrypt_location`]'s result. Receivers see a
+  /// [`LocationMessageResultKindFfi::MeetPin`].
+  Future<EncryptedLocationFfi> sendMeetPin({
+    required List<int> mlsGroupId,
+    required double latitude,
+    required double longitude,
+    String? label,
+    required PlatformInt64 ttlSecs,
+    required bool exactLocationConsent,
   });
 
-  /// Sets or clears a member's local petname (contact `display_name`).
+  /// Broadcasts an emergency (SOS) alert to several circles at once.
   ///
-  /// A purely local override (plan D6): `Some(name)` sets it, `None` clears it.
-  /// Existing contact `notes` are preserved. Never leaves the device.
+  /// Always sends the exact GPS fix with a short expiration and an optional
+  /// note. Publish each returned delivery to its relays exactly like
+  /// [`Self::encrypt_location`]'s result; circles that could not be encrypted
+  /// for are listed in `failed_group_ids` and do not abort the others.
+  Future<SosFanoutFfi> sendSos({
+    required List<Uint8List> mlsGroupIds,
+    required double latitude,
+    required double longitude,
+    String? message,
+  });
+
+  /// Replaces the circle's admin set with `admin_pubkeys` (hex), promoting
+  /// and demoting members in one commit. Admin-only; every entry must be a
+  /// current member.
   ///
-  /// # Errors
+  /// # GAP (plan §5.2 #18)
   ///
-  /// Returns a redacted error string on database failure.
-  void setLocalNickname({required String pubkeyHex, String? nickname});
-
+  /// Same admin-policy codec gap as
+  /// [`propose_admin_handoff`](Self::propose_admin_handoff): after
+  /// validation and the admin check, the core method returns a documented
+  /// error.
+  Future<CommitToPublishFfi> setAdmins({
+    required List<int> mlsGroupId,
+    required List<String> adminPubkeys,
+  });
+
+  /// Pauses or resumes location sharing to one circle, as
+  /// [`Self::set_location_paused`] does for all of them.
+  Future<void> setCircleLocationPaused({
+    required List<int> mlsGroupId,
+    required bool paused,
+  });
+
+  /// Sets a circle's location sharing overrides, applied to every location
+  /// sent to it by [`Self::encrypt_location`].
+  Future<void> setCircleLocationSettings({
+    required List<int> mlsGroupId,
+    required CircleLocationSettingsFfi settings,
+  });
+
+  /// Opts in to (or out of) the community relay blacklist and re-applies
+  /// the merged blacklist immediately.
+  Future<void> setCommunityBlacklistEnabled({required bool enabled});
+
+  /// Sets or updates a contact.
+  ///
+  /// Contact information is stored locally only and never synced to relays.
+  Future<ContactFfi> setContact({
+    required String pubkey,
+    String? displayName,
+    String? notes,
+  });
+
+  /// Sets a contact's picture from raw image bytes (JPEG, PNG or WebP).
+  ///
+  /// The core downscales the picture, strips its EXIF/GPS metadata and
+  /// stores it encrypted on the device; the app never manages the file.
+  Future<ContactFfi> setContactAvatar({
+    required String pubkey,
+    required List<int> imageBytes,
+  });
+
+  /// Imports a `HavenConfig` JSON document, replacing every setting it
+  /// covers. Missing fields take their defaults.
+  Future<void> setHavenConfig({required String configJson});
+
+  /// Enables or disables the member key audit: recording the MLS signature
+  /// key of every member this device adds and flagging changes.
+  Future<void> setKeyAuditEnabled({required bool enabled});
+
+  /// Sets or clears a member's local petname (contact `display_name`).
+  ///
+  /// A purely local override (plan D6): `Some(name)` sets it, `None` clears it.
+  /// Existing contact `notes` are preserved. Never leaves the device.
+  ///
+  /// # Errors
+  ///
+  /// Returns a redacted error string on database failure.
+  void setLocalNickname({required String pubkeyHex, String? nickname});
+
+  /// Pauses or resumes location sharing to every circle.
+  ///
+  /// Enforced in the core: while paused, [`Self::encrypt_location`] and
+  /// [`Self::share_location_if_due`] fail with a "sharing paused" error and
+  /// queued location updates are dropped rather than sent, whatever the UI
+  /// shows. Persists across restarts.
+  Future<void> setLocationPaused({required bool paused});
+
+  /// Saves the privacy settings; they apply to events published from now
+  /// on. Fails for a fuzz window above 10 minutes or more than 12 dummy
+  /// events an hour.
+  Future<void> setPrivacySettings({required PrivacySettingsFfi settings});
+
   /// Sets whether this user wants to publish their relay list for the
   /// given category.
   Future<void> setPublishRelayList({
@@ -1212,13 +2035,46 @@ abstract class CircleManagerFfi implements RustOpaqueInterface {
     required bool value,
   });
 
+  /// Blocks, allows, or (with `None`) clears the user's decision for one
+  /// relay, then re-applies the merged blacklist. A local allow overrides
+  /// the community list.
+  Future<void> setRelayOverride({
+    required String url,
+    RelayOverrideFfi? decision,
+  });
+
+  /// Records storage and MLS calls taking at least `threshold_ms` as
+  /// breadcrumbs (statement or method name only); `None` turns it off.
+  void setSlowOpThresholdMs({BigInt? thresholdMs});
+
+  /// Encrypts a scheduled location update for a circle if one is due;
+  /// `None` when it is not. Call from the app's location timer instead of
+  /// [`Self::encrypt_location`].
+  ///
+  /// An update is due once `update_interval_minutes` (the user's
+  /// [`LocationSettings::update_interval_minutes`], less the publish jitter
+  /// spread) has passed since the circle's last one, or sooner when the
+  /// position moved far enough. A misfiring timer therefore cannot flood
+  /// the circle's relays. The other arguments are as for
+  /// [`Self::encrypt_location`], and it fails the same way while sharing is
+  /// paused.
+  Future<EncryptedLocationFfi?> shareLocationIfDue({
+    required List<int> mlsGroupId,
+    required double latitude,
+    required double longitude,
+    required int updateIntervalMinutes,
+    BigInt? shareExpirationSecs,
+    DeviceStatusFfi? deviceStatus,
+    VerticalPositionFfi? verticalPosition,
+  });
+
   /// Signs a NIP-09 event deletion event.
   ///
   /// Creates a kind 5 deletion event referencing the given event IDs,
   /// signed with the provided identity key. Used to delete consumed
   /// `KeyPackage` events from relays after rotation.
   String signDeletionEvent({
-    required List<int> identitySecretBytes,
+    required SecretHandle secret,
     required List<String> eventIds,
   });
 
@@ -1228,6 +2084,23 @@ abstract class CircleManagerFfi implements RustOpaqueInterface {
     required PlatformInt64 nowUnixSecs,
   });
 
+  /// Shares exact location with a circle for `duration_secs` (at most one
+  /// day), ignoring its precision setting until the session ends by itself.
+  Future<SharingSessionFfi> startPreciseSession({
+    required List<int> mlsGroupId,
+    required BigInt durationSecs,
+  });
+
+  /// Starts trip mode for a circle for `duration_secs` (at most one day):
+  /// locations use `share_expiration_secs` and the publish schedule uses
+  /// `update_interval_secs` when shorter. Reverts by itself when it ends.
+  Future<TripModeFfi> startTripMode({
+    required List<int> mlsGroupId,
+    required BigInt durationSecs,
+    required BigInt updateIntervalSecs,
+    required BigInt shareExpirationSecs,
+  });
+
   /// Returns whether `pubkey_hex` is still in the circle's current MLS
   /// roster — the REV-1 leaver-backstop liveness predicate.
   ///
@@ -1244,6 +2117,73 @@ abstract class CircleManagerFfi implements RustOpaqueInterface {
     required String pubkeyHex,
   });
 
+  /// Ends a circle's precise-sharing session early. Returns whether one was
+  /// running.
+  Future<bool> stopPreciseSession({required List<int> mlsGroupId});
+
+  /// Ends a circle's trip mode early. Returns whether one was running.
+  Future<bool> stopTripMode({required List<int> mlsGroupId});
+
+  /// Summarizes `member_pubkey`'s (hex) trips on `date` (`YYYY-MM-DD`),
+  /// the local day at `utc_offset_secs` east of UTC, from the locations
+  /// they shared in every circle. Computed on this device.
+  Future<DaySummaryFfi> summarizeDay({
+    required String memberPubkey,
+    required String date,
+    required int utcOffsetSecs,
+    required PlatformInt64 nowUnixSecs,
+  });
+
+  /// Adds `label` (e.g. "family") to the contact `pubkey`. Returns `false`
+  /// if the contact already had it.
+  Future<bool> tagContact({required String pubkey, required String label});
+
+  /// Encrypts the cover-traffic events due now, to publish like location
+  /// updates. Empty unless [`PrivacySettingsFfi::cover_traffic_per_hour`]
+  /// is set; the app's timer can call this as often as it likes.
+  Future<List<EncryptedLocationFfi>> takeDueChaff();
+
+  /// Restores a frozen circle's history without unarchiving it. Returns
+  /// `false` if it was not frozen.
+  Future<bool> thawCircle({required List<int> mlsGroupId});
+
+  /// Returns the circle's running trip mode, if any.
+  Future<TripModeFfi?> tripMode({required List<int> mlsGroupId});
+
+  /// Restores an archived circle, thawing it first if it was frozen.
+  Future<void> unarchiveCircle({required List<int> mlsGroupId});
+
+  /// Members invited more than `grace_secs` ago (default two days) who
+  /// have not sent anything to the circle yet.
+  Future<List<UnjoinedMemberFfi>> unjoinedMembers({
+    required List<int> mlsGroupId,
+    PlatformInt64? graceSecs,
+  });
+
+  /// Removes `label` from the contact `pubkey`. Returns `false` if the
+  /// contact did not have it.
+  Future<bool> untagContact({required String pubkey, required String label});
+
+  /// Admin: update a circle's name, description and/or group relay list.
+  /// `None` leaves a field unchanged; at least one must be set.
+  ///
+  /// Publish and finalize exactly as for
+  /// [`update_circle_relays`](Self::update_circle_relays) (publish to the
+  /// union of current and new relays, then
+  /// [`finalize_relay_update`](Self::finalize_relay_update)).
+  ///
+  /// # GAP (plan §5.2 #18)
+  ///
+  /// Name/description changes need the group-profile component codec, which
+  /// the engine does not expose yet; the core currently returns a documented
+  /// error for them. Relay-only updates work.
+  Future<CommitToPublishFfi> updateCircle({
+    required List<int> mlsGroupId,
+    String? newName,
+    String? newDescription,
+    List<String>? newRelays,
+  });
+
   /// Admin: replace this circle's group relay list (MIP-01) via an
   /// `UpdateAppComponents(nostr-routing.v1)` commit.
   ///
@@ -1273,7 +2213,7 @@ abstract class CircleManagerFfi implements RustOpaqueInterface {
   ///
   /// Returns a redacted error string on upload, relay, or database failure.
   Future<ProfilePictureRefFfi> uploadMyProfilePicture({
-    required List<int> identitySecretBytes,
+    required SecretHandle secret,
     required List<int> raw,
   });
 
@@ -1302,17 +2242,61 @@ abstract class CircleManagerFfi implements RustOpaqueInterface {
   Future<void> wipeAndResetDefaultsFor({required RelayTypeFfi relayType});
 }
 
+// Rust type: RustOpaqueMoi<flutter_rust_bridge::for_generated::RustAutoOpaqueInner<DeviceLinkSessionFfi>>
+abstract class DeviceLinkSessionFfi implements RustOpaqueInterface {
+  /// Unix timestamp after which the link is void.
+  PlatformInt64 expiresAt();
+
+  /// The `haven-link:` payload to show as a QR code.
+  String payload();
+}
+
+// Rust type: RustOpaqueMoi<flutter_rust_bridge::for_generated::RustAutoOpaqueInner<ExternalSignerFfi>>
+abstract class ExternalSignerFfi implements RustOpaqueInterface {
+  /// Creates a signer for the identity `pubkey_hex`.
+  ///
+  /// # Errors
+  ///
+  /// Returns an error if `pubkey_hex` is not a valid public key.
+  factory ExternalSignerFfi({
+    required String pubkeyHex,
+    required FutureOr<String> Function(Uint8List) signEventHash,
+    required FutureOr<String> Function(String, String) nip44Encrypt,
+  }) => RustLib.instance.api.crateApiExternalSignerFfiNew(
+    pubkeyHex: pubkeyHex,
+    signEventHash: signEventHash,
+    nip44Encrypt: nip44Encrypt,
+  );
+
+  /// The public key events are signed as, as 64-character hex.
+  String pubkeyHex();
+}
+
 // Rust type: RustOpaqueMoi<flutter_rust_bridge::for_generated::RustAutoOpaqueInner<HavenCore>>
 abstract class HavenCore implements RustOpaqueInterface {
   static Future<HavenCore> default_() =>
       RustLib.instance.api.crateApiHavenCoreDefault();
 
+  /// Returns the active deployment environment.
+  EnvironmentFfi environment();
+
   /// Gets the current location settings.
   LocationSettings getLocationSettings();
 
   /// Initializes the core.
   Future<void> initialize();
 
+  /// Initializes the core for a deployment environment.
+  ///
+  /// `relays` overrides the default relays for dev and staging (empty keeps
+  /// the profile's own: the local mock relay for dev); production ignores
+  /// it. Installs the profile's log level, relay timing, and mock-relay
+  /// opt-in process-wide. Fails for a dev profile in a release build.
+  Future<void> initializeWithEnvironment({
+    required EnvironmentFfi environment,
+    required List<String> relays,
+  });
+
   /// Returns whether the core has been initialized.
   bool isInitialized();
 
@@ -1321,17 +2305,32 @@ abstract class HavenCore implements RustOpaqueInterface {
   static Future<HavenCore> newInstance() =>
       RustLib.instance.api.crateApiHavenCoreNew();
 
+  /// Runs the crypto and storage self-test, for the "nothing works"
+  /// support screen. `scratch_dir` is a writable directory (the app's
+  /// cache dir); nothing is left behind in it.
+  Future<SelfTestReportFfi> runSelfTest({required String scratchDir});
+
   /// Updates the location settings.
   void setLocationSettings({required LocationSettings settings});
 
   /// Processes raw location data and returns a `LocationMessage` with
-  /// exact GPS coordinates.
+  /// exact GPS coordinates, rounded down while the device stays in one spot
+  /// if adaptive precision is on.
   LocationMessage updateLocation({
     required double latitude,
     required double longitude,
   });
 }
 
+// Rust type: RustOpaqueMoi<flutter_rust_bridge::for_generated::RustAutoOpaqueInner<LinkedIdentityFfi>>
+abstract class LinkedIdentityFfi implements RustOpaqueInterface {
+  /// The old device's circles, to ask to rejoin.
+  List<LinkedCircleFfi> circles();
+
+  /// The linked identity's public key (hex), for the user to confirm.
+  String pubkeyHex();
+}
+
 // Rust type: RustOpaqueMoi<flutter_rust_bridge::for_generated::RustAutoOpaqueInner<LiveSyncFfi>>
 abstract class LiveSyncFfi implements RustOpaqueInterface {
   /// Whether a live session is currently running.
@@ -1350,6 +2349,23 @@ abstract class LiveSyncFfi implements RustOpaqueInterface {
   /// Returns an error if there is no active session or the lock is poisoned.
   Stream<FfiRelayEvent> liveEvents();
 
+  /// Streams the locations the live session decrypts for ONE circle, already
+  /// parsed, so a map widget can listen instead of polling `decrypt_location`
+  /// per fetched event. Everything else on the bus (SOS, status, other
+  /// circles, content that is not a location) is skipped; use
+  /// [`Self::live_events`] for those.
+  ///
+  /// Same lifetime as [`Self::live_events`]: it ends when Dart closes the sink
+  /// or the session stops, and a lag is skipped (catch-up replays it).
+  ///
+  /// # Errors
+  ///
+  /// Returns an error if `nostr_group_id` is malformed, there is no active
+  /// session, or the lock is poisoned.
+  Stream<DecryptedLocationFfi> locationUpdatesStream({
+    required List<int> nostrGroupId,
+  });
+
   /// Builds a handle over `circle`'s MLS state for `own_pubkey_hex`.
   ///
   /// # Errors
@@ -1473,6 +2489,12 @@ abstract class LocationMessage implements RustOpaqueInterface {
 
 // Rust type: RustOpaqueMoi<flutter_rust_bridge::for_generated::RustAutoOpaqueInner<LocationSettings>>
 abstract class LocationSettings implements RustOpaqueInterface {
+  /// Whether precision coarsens while the device stays in one spot.
+  bool adaptivePrecision();
+
+  /// Whether rounded locations are also shifted by a seeded random amount.
+  bool coordinateJitter();
+
   // HINT: Make it `#[frb(sync)]` to let it become the default constructor of Dart class.
   /// Creates new location settings.
   static Future<LocationSettings> newInstance({
@@ -1481,6 +2503,37 @@ abstract class LocationSettings implements RustOpaqueInterface {
     updateIntervalMinutes: updateIntervalMinutes,
   );
 
+  /// Gets the precision shared with circles that have no override.
+  LocationPrecisionFfi precision();
+
+  /// Turns adaptive precision on or off.
+  void setAdaptivePrecision({required bool enabled});
+
+  /// Turns coordinate jitter on or off.
+  void setCoordinateJitter({required bool enabled});
+
+  /// Sets the precision shared with circles that have no override.
+  void setPrecision({required LocationPrecisionFfi precision});
+
+  /// Turns sharing battery, charging and motion state on or off.
+  void setShareDeviceStatus({required bool enabled});
+
+  /// Sets how long members keep each shared location, in seconds (1-hour,
+  /// 8-hour and 1-day presets, or a custom value clamped to 1 h..1 day).
+  void setShareExpirationSecs({required BigInt secs});
+
+  /// Turns sharing altitude and floor level on or off.
+  void setShareVerticalPosition({required bool enabled});
+
+  /// Whether battery, charging and motion state are shared with locations.
+  bool shareDeviceStatus();
+
+  /// Gets how long members keep each shared location, in seconds.
+  BigInt shareExpirationSecs();
+
+  /// Whether altitude and floor level are shared with locations.
+  bool shareVerticalPosition();
+
   /// Gets the update interval in minutes.
   int updateIntervalMinutes();
 }
@@ -1500,9 +2553,31 @@ abstract class NostrIdentityManager implements RustOpaqueInterface {
   static Future<NostrIdentityManager> default_() =>
       RustLib.instance.api.crateApiNostrIdentityManagerDefault();
 
-  /// Deletes the identity.
+  /// Deletes the identity key only, without checking for circles that
+  /// depend on it. Prefer [`Self::delete_identity_guarded`].
   Future<void> deleteIdentity();
 
+  /// Deletes the identity after tearing down the circle state bound to it.
+  ///
+  /// Fails, changing nothing, while the identity is still a member of a
+  /// circle unless `force` is set (see
+  /// [`CircleManagerFfi::identity_deletion_report`]). With `leave_first`,
+  /// each circle that can be left gets a signed `SelfRemove` in the result.
+  /// Afterwards, publish those events (best effort), drop the circle
+  /// manager and call [`wipe_all_mls_state`] to remove the session database.
+  Future<IdentityTeardownFfi> deleteIdentityGuarded({
+    required CircleManagerFfi circle,
+    required bool force,
+    required bool leaveFirst,
+  });
+
+  /// Exports the identity as a NIP-49 `ncryptsec` encrypted under
+  /// `passphrase`, safe for paper or cloud backup.
+  ///
+  /// `scrypt_log_n` defaults to 16 when `None`; values outside 16..=22 are
+  /// rejected.
+  Future<String> exportEncrypted({required String passphrase, int? scryptLogN});
+
   /// Exports the identity as nsec for backup.
   ///
   /// # Security Warning
@@ -1525,11 +2600,30 @@ abstract class NostrIdentityManager implements RustOpaqueInterface {
   /// Checks if an identity is loaded.
   bool hasIdentity();
 
+  /// Imports an identity from a NIP-49 `ncryptsec` backup.
+  ///
+  /// After calling this, use `get_secret_bytes()` to persist the secret.
+  Future<PublicIdentity> importEncrypted({
+    required String ncryptsec,
+    required String passphrase,
+  });
+
   /// Imports an identity from an nsec string.
   ///
   /// After calling this, use `get_secret_bytes()` to persist the secret.
   Future<PublicIdentity> importFromNsec({required String nsec});
 
+  /// Imports the identity received over a device link. Show
+  /// [`LinkedIdentityFfi::pubkey_hex`] to the user first.
+  ///
+  /// After calling this, use `get_secret_bytes()` to persist the secret.
+  ///
+  /// # Errors
+  ///
+  /// Returns an error if an identity already exists or `linked` was
+  /// already imported.
+  Future<PublicIdentity> importLinked({required LinkedIdentityFfi linked});
+
   /// Loads an identity from raw secret bytes (retrieved from Flutter secure storage).
   ///
   /// Call this on app startup if you have persisted secret bytes.
@@ -1543,6 +2637,13 @@ abstract class NostrIdentityManager implements RustOpaqueInterface {
   /// Gets the public key as hex string (for MDK operations).
   String pubkeyHex();
 
+  /// Returns a [`SecretHandle`] to the loaded identity.
+  ///
+  /// Every operation that signs as the identity takes this handle rather
+  /// than the bytes from [`Self::get_secret_bytes`], so the secret stays in
+  /// Rust memory.
+  Future<SecretHandle> secretHandle();
+
   /// Signs a 32-byte message hash.
   ///
   /// Returns the signature as a 128-character hex string.
@@ -1551,6 +2652,31 @@ abstract class NostrIdentityManager implements RustOpaqueInterface {
 
 // Rust type: RustOpaqueMoi<flutter_rust_bridge::for_generated::RustAutoOpaqueInner<RelayManagerFfi>>
 abstract class RelayManagerFfi implements RustOpaqueInterface {
+  /// Backfills one circle's history down to `target_secs`.
+  ///
+  /// Walks backwards page by page, skips what a sync already ingested,
+  /// ingests the rest oldest first and persists locations. Meant for a
+  /// newly joined member: pass now minus a day to fill in the last day of
+  /// locations. Sync cursors are not moved.
+  ///
+  /// # Arguments
+  ///
+  /// * `circle` - The foreground circle manager (Rule 14)
+  /// * `mls_group_id` - The circle's MLS group id
+  /// * `target_secs` - Unix timestamp (seconds) to walk back to
+  /// * `own_pubkey_hex` - The local identity's public key, to skip self-echoes
+  ///
+  /// # Errors
+  ///
+  /// Returns an error if the pubkey is invalid or the circle does not
+  /// exist.
+  Future<BackfillDigestFfi> backfillCircle({
+    required CircleManagerFfi circle,
+    required List<int> mlsGroupId,
+    required PlatformInt64 targetSecs,
+    required String ownPubkeyHex,
+  });
+
   /// Checks whether events of a given kind by an author exist on a relay.
   ///
   /// Queries a single relay for events matching the given kind and author.
@@ -1562,6 +2688,49 @@ abstract class RelayManagerFfi implements RustOpaqueInterface {
     required int eventKind,
   });
 
+  /// Checks the link's relays for the identity sent by the old device.
+  ///
+  /// Returns `None` until it arrives; poll until
+  /// [`DeviceLinkSessionFfi::expires_at`].
+  ///
+  /// # Errors
+  ///
+  /// Returns an error once the link expired, or if the fetch fails.
+  Future<LinkedIdentityFfi?> completeDeviceLink({
+    required DeviceLinkSessionFfi session,
+  });
+
+  /// Retires a `KeyPackage` this device published once it was used to add
+  /// the user to a group, and publishes a replacement.
+  ///
+  /// Publishes a NIP-09 deletion of `event_id_hex` and a fresh package for
+  /// the same slot to the user's own `KeyPackage` relays, records the
+  /// replacement on success, and deletes its material on failure (mdk#160).
+  /// The consumed package's material is deleted either way.
+  ///
+  /// # Errors
+  ///
+  /// Returns an error if the secret or event id is invalid, no `KeyPackage`
+  /// relays are configured, or the event is not a tracked package.
+  Future<ConsumedKeyPackageFfi> consumeKeyPackage({
+    required CircleManagerFfi circle,
+    required SecretHandle secret,
+    required String eventIdHex,
+  });
+
+  /// Requests deletion (NIP-09, kind 5) of identity-signed events.
+  ///
+  /// Signs an id-only deletion of `event_ids` with the identity key and
+  /// publishes it to `relays`. Relays only honor deletions from the events'
+  /// author, so this cannot delete a location (see
+  /// [`CircleManagerFfi::retract_location`]).
+  Future<PublishResultFfi> deleteEvents({
+    required SecretHandle secret,
+    required List<String> eventIds,
+    String? reason,
+    required List<String> relays,
+  });
+
   /// Removes a single relay from the persistent connection pool by URL
   /// and tears down its WebSocket.
   ///
@@ -1641,6 +2810,36 @@ abstract class RelayManagerFfi implements RustOpaqueInterface {
     int? limit,
   });
 
+  /// Same query as [`fetch_group_messages`](Self::fetch_group_messages), but
+  /// returns [`CompactEventFfi`]s instead of JSON strings.
+  ///
+  /// Each event's fields are moved (not re-serialized) into the result, so a
+  /// large catch-up batch is held once rather than twice. Pass the results to
+  /// `CircleManagerFfi::decrypt_location_compact`.
+  Future<List<CompactEventFfi>> fetchGroupMessagesCompact({
+    required List<int> nostrGroupId,
+    required List<String> relays,
+    PlatformInt64? since,
+    int? limit,
+  });
+
+  /// Fetches one page of group messages (kind 445), walking backwards.
+  ///
+  /// Returns up to `limit` events per relay created at or before `until`
+  /// (now if `None`) and at or after `since`. Pass the returned
+  /// `next_until` as the next `until` until it is `None`.
+  ///
+  /// # Errors
+  ///
+  /// Returns an error if the group id is not 32 bytes or `limit` is zero.
+  Future<GroupMessagePageFfi> fetchGroupMessagesPaged({
+    required List<int> nostrGroupId,
+    required List<String> relays,
+    PlatformInt64? until,
+    PlatformInt64? since,
+    required int limit,
+  });
+
   /// Fetches a user's key package (kind 30443; legacy 443 is detected but
   /// never returned).
   ///
@@ -1711,6 +2910,36 @@ abstract class RelayManagerFfi implements RustOpaqueInterface {
   /// List of relay URLs from "r" tags, or empty if no relay list is published.
   Future<List<String>> fetchNip65Relays({required String pubkey});
 
+  /// Returns `url`'s NIP-11 capabilities, from `circles.db` when fresh.
+  ///
+  /// Fetches the document over HTTPS (and persists it) when none was
+  /// fetched within the TTL, or always with `refresh`. Publish paths read
+  /// the persisted documents to order and skip relays.
+  Future<RelayInfoFfi> fetchRelayInfo({
+    required CircleManagerFfi circle,
+    required String url,
+    required bool refresh,
+  });
+
+  /// Publishes the relay lists whose edits have settled (debounced; see
+  /// [`haven_core::relay::relay_list_publisher`]).
+  ///
+  /// Relay-preference edits made through [`CircleManagerFfi`] are coalesced
+  /// in core; Dart calls this at
+  /// [`CircleManagerFfi::next_relay_list_publish_at`] (or on any later
+  /// tick). Each due category is signed once and published to the union of
+  /// the relays it was last published to and the current ones, then
+  /// recorded. A failed sign or publish requeues the category. Secret
+  /// bytes are zeroized after use; a paired remote signer signs instead.
+  Future<RelayListFlushFfi> flushRelayListRepublishes({
+    required CircleManagerFfi circle,
+    required SecretHandle secret,
+  });
+
+  /// Lists the offline outbox: events still waiting for a retry and those
+  /// sent, expired, or abandoned within the last hour. Newest first.
+  Future<List<OutboxEntryFfi>> getOutboxStatus();
+
   /// Gets the connection status of all relays.
   Future<List<RelayConnectionStatusFfi>> getRelayStatus();
 
@@ -1719,12 +2948,14 @@ abstract class RelayManagerFfi implements RustOpaqueInterface {
   /// FIRST-publish path (onboarding / login): a responding relay serving
   /// nothing + no tracked slot mints a fresh package.
   ///
-  /// Dart-timer-driven (the identity secret lives only in Dart, Security Rule
-  /// 9): the secret bytes are consumed per-call and zeroized. Fail-soft and
-  /// idempotent — one tick of the periodic maintenance loop.
+  /// Dart-timer-driven; the identity comes in as a [`SecretHandle`] and the
+  /// derived keys live for this call only. Fail-soft and idempotent — one
+  /// tick of the periodic maintenance loop.
   ///
   /// Steps:
-  /// 1. Derive `Keys`/pubkey from the secret bytes (zeroized after).
+  /// 1. Derive `Keys`/pubkey from `secret`, or use
+  ///    the paired remote signer ([`CircleManagerFfi::configure_remote_signer`]),
+  ///    which then signs the key package event.
   /// 2. Probe the user's OWN NIP-65 relays (dedup'd, own-relays-only — never a
   ///    default union) for kind-30443 events authored by self.
   /// 3. Build the presence snapshot (`(d, event_id)` per responder) — under
@@ -1741,15 +2972,22 @@ abstract class RelayManagerFfi implements RustOpaqueInterface {
   /// [`decide_kp_maintenance`]: haven_core::relay::maintenance::decide_kp_maintenance
   Future<KpMaintenanceOutcomeFfi> maintainKeyPackage({
     required CircleManagerFfi circle,
-    required List<int> identitySecretBytes,
+    required SecretHandle secret,
+  });
+
+  /// [`Self::maintain_key_package`], with the key package event signed by a
+  /// key held outside this process (see [`ExternalSignerFfi`]).
+  Future<KpMaintenanceOutcomeFfi> maintainKeyPackageWithSigner({
+    required CircleManagerFfi circle,
+    required ExternalSignerFfi signer,
   });
 
   /// M8-1 relay-list maintenance — republish-if-missing/drifted for the
   /// user's kind 10050 (inbox) + 10051 (`KeyPackage`) relay lists, honoring
   /// the per-category privacy toggle.
   ///
-  /// Dart-timer-driven; the secret bytes are consumed per-call and zeroized
-  /// (Security Rule 9). Fail-soft + idempotent.
+  /// Dart-timer-driven; the identity comes in as a [`SecretHandle`] and the
+  /// derived keys live for this call only. Fail-soft + idempotent.
   ///
   /// Per category: reads the user's OWN configured relays, NETWORK-PROBES the
   /// user's OWN relays for a currently-reachable list (never a local-timestamp
@@ -1765,7 +3003,7 @@ abstract class RelayManagerFfi implements RustOpaqueInterface {
   /// [`dedup_relay_targets`]: haven_core::relay::dedup_relay_targets
   Future<RelayListMaintenanceOutcomeFfi> maintainRelayList({
     required CircleManagerFfi circle,
-    required List<int> identitySecretBytes,
+    required SecretHandle secret,
   });
 
   /// Creates a new relay manager.
@@ -1792,6 +3030,96 @@ abstract class RelayManagerFfi implements RustOpaqueInterface {
     required List<String> relays,
   });
 
+  /// Publishes a location update, keeping it in the offline outbox when no
+  /// relay accepts it.
+  ///
+  /// On failure the error is still returned, but the signed event is
+  /// re-sent automatically once a relay reconnects (see
+  /// [`Self::get_outbox_status`]). Never use this for commits or welcomes:
+  /// those are rolled back when their publish fails.
+  ///
+  /// Queued as a regular location update; see
+  /// [`Self::publish_event_queued_with_priority`].
+  Future<PublishResultFfi> publishEventQueued({
+    required String eventJson,
+    required List<String> relays,
+  });
+
+  /// Same as [`Self::publish_event_queued`], queued at `priority`: "sos",
+  /// "live_share", "location", or "housekeeping".
+  ///
+  /// Higher priorities are retried first, and each is dropped unsent once
+  /// its delivery deadline passes (SOS 1 hour, live share 2 minutes,
+  /// location 15 minutes, housekeeping 24 hours).
+  Future<PublishResultFfi> publishEventQueuedWithPriority({
+    required String eventJson,
+    required List<String> relays,
+    required String priority,
+  });
+
+  /// Publishes an identity-signed event, adding NIP-13 proof of work.
+  ///
+  /// Mines to `target_difficulty` before sending (if set), and re-mines
+  /// and re-sends when a relay rejects with a `pow:` reason. The event is
+  /// re-signed with the identity key, so `event_json` must be signed by it.
+  /// The returned `event_id` is the id relays accepted — record that one,
+  /// not the id of `event_json`.
+  Future<PublishResultFfi> publishEventWithPow({
+    required String eventJson,
+    required List<String> relays,
+    required SecretHandle secret,
+    int? targetDifficulty,
+  });
+
+  /// Publishes a signed event and reports back as soon as `quorum` relays
+  /// accepted it, instead of waiting on every relay.
+  ///
+  /// The stream yields the early report (`is_final == false`) once the
+  /// quorum is met, then the final one (`is_final == true`) when every
+  /// relay answered or missed its own deadline. When nothing was pending
+  /// at the early report, only the final one is sent. A single attempt:
+  /// use [`Self::publish_event`] for retries.
+  ///
+  /// # Errors
+  ///
+  /// Returns an error if the event JSON or a relay URL is invalid, or if
+  /// every relay answered and none accepted.
+  Stream<QuorumPublishUpdateFfi> publishEventWithQuorum({
+    required String eventJson,
+    required List<String> relays,
+    required PublishQuorumFfi quorum,
+  });
+
+  /// Publishes a gift-wrapped Welcome, skipping relays too small for it.
+  ///
+  /// Relays whose cached NIP-11 document advertises a limit below the
+  /// Welcome's size are not sent to; if no relay in `recipient_relays`
+  /// takes it, `fallback_relays` are tried. Each relay's outcome, including
+  /// `TooLargeForRelay`, is in the report. Confirm the pending commit only
+  /// if `delivered` (Rule 13).
+  ///
+  /// # Errors
+  ///
+  /// Returns an error if `welcome.event_json` is not a valid event.
+  Future<WelcomeDeliveryReportFfi> publishWelcome({
+    required CircleManagerFfi circle,
+    required GiftWrappedWelcomeFfi welcome,
+  });
+
+  /// Asks the admins of each of `circles` to re-add this device with its
+  /// freshly published `KeyPackage` (`key_package_json`).
+  ///
+  /// Returns how many requests were delivered.
+  ///
+  /// # Errors
+  ///
+  /// Returns an error if the secret or `KeyPackage` JSON is invalid.
+  Future<int> requestRejoin({
+    required SecretHandle secret,
+    required List<LinkedCircleFfi> circles,
+    required String keyPackageJson,
+  });
+
   /// Once-only legacy relay hygiene (Dark Matter §6 step 5 / F10a): retracts
   /// this account's stale pre-migration KeyPackage advertisements so an
   /// old-stack client cannot mint a Welcome the new stack can't process.
@@ -1816,9 +3144,13 @@ abstract class RelayManagerFfi implements RustOpaqueInterface {
   /// [`build_key_package_relay_list_retraction`]: haven_core::relay::maintenance::build_key_package_relay_list_retraction
   Future<LegacyRetractionOutcomeFfi> retractLegacyKeyMaterial({
     required CircleManagerFfi circle,
-    required List<int> identitySecretBytes,
+    required SecretHandle secret,
   });
 
+  /// Re-sends every pending outbox event now. Call when the platform
+  /// reports that connectivity is back.
+  Future<OutboxFlushFfi> retryOutbox();
+
   /// Runs an M7 receive-only catch-up sweep over every visible circle.
   ///
   /// Best-effort + deadline-bounded; NEVER authors/merges/converges a commit.
@@ -1832,13 +3164,163 @@ abstract class RelayManagerFfi implements RustOpaqueInterface {
     required BigInt maxDurationSecs,
   });
 
+  /// Sends this identity and its circles to the new device that shows
+  /// `payload` (its `haven-link:` QR code). See [`haven_core::device_link`].
+  ///
+  /// # Errors
+  ///
+  /// Returns an error if the payload is not a live device link or the
+  /// publish fails.
+  Future<PublishResultFfi> sendDeviceLink({
+    required CircleManagerFfi circle,
+    required SecretHandle secret,
+    required String payload,
+  });
+
+  /// Sends the user's location once to a contact, outside any circle.
+  ///
+  /// The location is gift-wrapped to the contact's inbox relays and expires
+  /// after `ttl_secs` (default one hour, clamped to 5 minutes..24 hours).
+  /// No circle or MLS state is created on either side; the contact receives
+  /// it through [`Self::sync_inbox`]. See [`haven_core::one_shot`].
+  ///
+  /// With a remote signer paired
+  /// ([`CircleManagerFfi::configure_remote_signer`]), the gift wrap's seal
+  /// is encrypted and signed by it instead.
+  ///
+  /// # Arguments
+  ///
+  /// * `secret` - The sender's identity
+  /// * `contact_pubkey_hex` - The contact's public key (hex)
+  /// * `latitude`, `longitude` - The coordinates to send, exactly as given
+  /// * `ttl_secs` - Lifetime in seconds, or `None` for the default
+  Future<PublishResultFfi> sendOneShotLocation({
+    required SecretHandle secret,
+    required String contactPubkeyHex,
+    required double latitude,
+    required double longitude,
+    BigInt? ttlSecs,
+  });
+
+  /// [`Self::send_one_shot_location`], with the gift wrap's seal encrypted
+  /// and signed by a key held outside this process (see
+  /// [`ExternalSignerFfi`]).
+  Future<PublishResultFfi> sendOneShotLocationWithSigner({
+    required ExternalSignerFfi signer,
+    required String contactPubkeyHex,
+    required double latitude,
+    required double longitude,
+    BigInt? ttlSecs,
+  });
+
   /// Disconnects from all relays.
   Future<void> shutdown();
-}
 
-/// Result of adding members to an existing circle (FFI-friendly).
-///
-/// Publish-before-apply (Rule 13): publish `commit_event_json`, confirm
+  /// Syncs group messages for every accepted circle.
+  ///
+  /// Replaces the Dart-side per-circle `fetch_group_messages` →
+  /// `decrypt_location_collecting_commits` → upsert loop: each circle's
+  /// relays are queried from their own cursors, events are ingested through
+  /// the foreground session, locations are persisted, receive-side
+  /// auto-commits are published and confirmed here (Rule 13), and a digest
+  /// of the changes is returned.
+  ///
+  /// # Arguments
+  ///
+  /// * `circle` - The foreground circle manager (Rule 14)
+  /// * `own_pubkey_hex` - The local identity's public key, to skip self-echoes
+  ///
+  /// # Errors
+  ///
+  /// Returns an error if the pubkey is invalid or the circle list cannot be
+  /// read.
+  Future<SyncDigestFfi> syncGroupMessages({
+    required CircleManagerFfi circle,
+    required String ownPubkeyHex,
+  });
+
+  /// Polls the inbox relays for gift-wrapped invitations and processes them.
+  ///
+  /// Replaces the Dart-side `fetch_gift_wraps_per_relay` →
+  /// `process_gift_wrapped_invitation` → `cursor_advance_inbox_to_wrap` loop:
+  /// each relay is queried from its own cursor, wraps are deduplicated by id,
+  /// welcomes are held in `circle`'s invitation store, and the new
+  /// invitations are returned. Best-effort — relay and per-wrap failures are
+  /// counted, not raised.
+  ///
+  /// # Arguments
+  ///
+  /// * `circle` - The foreground circle manager (Rule 14)
+  /// * `secret` - The recipient's identity
+  /// * `relays` - The user's inbox relay URLs
+  Future<InboxSyncSummaryFfi> syncInbox({
+    required CircleManagerFfi circle,
+    required SecretHandle secret,
+    required List<String> relays,
+  });
+
+  /// Verifies that the published `KeyPackage` is actually retrievable.
+  ///
+  /// Waits `delay_secs` (use [`kp_verify_delay_secs`] after a publish), then
+  /// fetches the tracked kind-30443 event back from each of the user's
+  /// `KeyPackage` relays by id and records per relay whether it is served,
+  /// missing, or unreachable. A relay that acks a write but never serves it
+  /// leaves the user uninvitable there; this makes that visible.
+  ///
+  /// Returns `None` if no package has been published yet or no
+  /// `KeyPackage` relays are configured.
+  Future<KpVerificationFfi?> verifyKeyPackage({
+    required CircleManagerFfi circle,
+    required BigInt delaySecs,
+  });
+}
+
+// Rust type: RustOpaqueMoi<flutter_rust_bridge::for_generated::RustAutoOpaqueInner<SecretHandle>>
+abstract class SecretHandle implements RustOpaqueInterface {
+  /// The public key as 64-character hex.
+  String pubkeyHex();
+}
+
+// Rust type: RustOpaqueMoi<flutter_rust_bridge::for_generated::RustAutoOpaqueInner<TransferBundleFfi>>
+abstract class TransferBundleFfi implements RustOpaqueInterface {
+  /// The identity backup (NIP-49 `ncryptsec`) to restore first with
+  /// [`NostrIdentityManager::import_encrypted`].
+  String identityBackup();
+
+  /// Opens the bundle with the restored identity.
+  ///
+  /// # Errors
+  ///
+  /// Returns an error if the bundle was sealed for another identity or has
+  /// been tampered with.
+  Future<TransferContentsFfi> open({required SecretHandle secret});
+}
+
+// Rust type: RustOpaqueMoi<flutter_rust_bridge::for_generated::RustAutoOpaqueInner<TransferContentsFfi>>
+abstract class TransferContentsFfi implements RustOpaqueInterface {
+  /// Whether the bundle carries the MLS state. Without it every circle
+  /// needs a rejoin.
+  bool carriesState();
+
+  /// The old device's circles.
+  List<LinkedCircleFfi> circles();
+
+  /// Installs the carried MLS state into `data_dir`. Returns `false` when
+  /// the bundle carries none.
+  ///
+  /// # Errors
+  ///
+  /// Returns an error if `data_dir` already holds MLS state or the state
+  /// cannot be stored.
+  Future<bool> installSession({required String dataDir});
+
+  /// The transferred identity's public key (hex).
+  String pubkeyHex();
+}
+
+/// Result of adding members to an existing circle (FFI-friendly).
+///
+/// Publish-before-apply (Rule 13): publish `commit_event_json`, confirm
 /// `pending`, THEN publish `welcome_events` (a welcome for a losing/unconfirmed
 /// commit references an epoch that never applied).
 class AddMembersResultFfi {
@@ -1875,6 +3357,225 @@ class AddMembersResultFfi {
           pending == other.pending;
 }
 
+/// A circle through which an [`AudienceMemberFfi`] receives the next update
+/// (mirrors `haven_core::circle::AudienceCircle`).
+class AudienceCircleFfi {
+  /// MLS group ID of the circle.
+  final Uint8List mlsGroupId;
+
+  /// The circle's local name.
+  final String name;
+
+  /// Precision the circle receives.
+  final LocationPrecisionFfi precision;
+
+  /// End of a running precise session (Unix seconds).
+  final PlatformInt64? preciseUntil;
+
+  const AudienceCircleFfi({
+    required this.mlsGroupId,
+    required this.name,
+    required this.precision,
+    this.preciseUntil,
+  });
+
+  @override
+  int get hashCode =>
+      mlsGroupId.hashCode ^
+      name.hashCode ^
+      precision.hashCode ^
+      preciseUntil.hashCode;
+
+  @override
+  bool operator ==(Object other) =>
+      identical(this, other) ||
+      other is AudienceCircleFfi &&
+          runtimeType == other.runtimeType &&
+          mlsGroupId == other.mlsGroupId &&
+          name == other.name &&
+          precision == other.precision &&
+          preciseUntil == other.preciseUntil;
+}
+
+/// Someone who would receive the next location update (mirrors
+/// `haven_core::circle::AudienceMember`).
+class AudienceMemberFfi {
+  /// The member's public key (hex).
+  final String pubkey;
+
+  /// The user's local name for them, if any.
+  final String? displayName;
+
+  /// The finest precision they receive through any circle.
+  final LocationPrecisionFfi precision;
+
+  /// Every circle they receive it through.
+  final List<AudienceCircleFfi> circles;
+
+  const AudienceMemberFfi({
+    required this.pubkey,
+    this.displayName,
+    required this.precision,
+    required this.circles,
+  });
+
+  @override
+  int get hashCode =>
+      pubkey.hashCode ^
+      displayName.hashCode ^
+      precision.hashCode ^
+      circles.hashCode;
+
+  @override
+  bool operator ==(Object other) =>
+      identical(this, other) ||
+      other is AudienceMemberFfi &&
+          runtimeType == other.runtimeType &&
+          pubkey == other.pubkey &&
+          displayName == other.displayName &&
+          precision == other.precision &&
+          circles == other.circles;
+}
+
+/// What a backfill did (FFI mirror of
+/// [`haven_core::relay::BackfillDigest`]).
+class BackfillDigestFfi {
+  /// Pages fetched.
+  final int pagesFetched;
+
+  /// Distinct events fetched across all pages.
+  final int eventsFetched;
+
+  /// Events skipped because a sync had already ingested them.
+  final int eventsSkipped;
+
+  /// Events whose ingest failed (including events from before joining).
+  final int eventsFailed;
+
+  /// Events dropped for a mismatched id or invalid signature.
+  final int signaturesRejected;
+
+  /// Last-known locations updated.
+  final int locationsUpdated;
+
+  /// Whether the walk reached the target time.
+  final bool complete;
+
+  /// The decrypted results, oldest first.
+  final List<LocationMessageResultFfi> results;
+
+  const BackfillDigestFfi({
+    required this.pagesFetched,
+    required this.eventsFetched,
+    required this.eventsSkipped,
+    required this.eventsFailed,
+    required this.signaturesRejected,
+    required this.locationsUpdated,
+    required this.complete,
+    required this.results,
+  });
+
+  @override
+  int get hashCode =>
+      pagesFetched.hashCode ^
+      eventsFetched.hashCode ^
+      eventsSkipped.hashCode ^
+      eventsFailed.hashCode ^
+      signaturesRejected.hashCode ^
+      locationsUpdated.hashCode ^
+      complete.hashCode ^
+      results.hashCode;
+
+  @override
+  bool operator ==(Object other) =>
+      identical(this, other) ||
+      other is BackfillDigestFfi &&
+          runtimeType == other.runtimeType &&
+          pagesFetched == other.pagesFetched &&
+          eventsFetched == other.eventsFetched &&
+          eventsSkipped == other.eventsSkipped &&
+          eventsFailed == other.eventsFailed &&
+          signaturesRejected == other.signaturesRejected &&
+          locationsUpdated == other.locationsUpdated &&
+          complete == other.complete &&
+          results == other.results;
+}
+
+/// Bandwidth used since process start or the last reset (FFI-friendly).
+class BandwidthUsageFfi {
+  /// Unix timestamp the counters started.
+  final PlatformInt64 since;
+
+  /// All traffic.
+  final UsageFfi total;
+
+  /// Per relay, heaviest first.
+  final List<RelayUsageFfi> relays;
+
+  /// Received traffic whose relay is unknown (multi-relay fetches).
+  final UsageFfi unattributed;
+
+  /// Per circle, heaviest first.
+  final List<CircleUsageFfi> circles;
+
+  const BandwidthUsageFfi({
+    required this.since,
+    required this.total,
+    required this.relays,
+    required this.unattributed,
+    required this.circles,
+  });
+
+  @override
+  int get hashCode =>
+      since.hashCode ^
+      total.hashCode ^
+      relays.hashCode ^
+      unattributed.hashCode ^
+      circles.hashCode;
+
+  @override
+  bool operator ==(Object other) =>
+      identical(this, other) ||
+      other is BandwidthUsageFfi &&
+          runtimeType == other.runtimeType &&
+          since == other.since &&
+          total == other.total &&
+          relays == other.relays &&
+          unattributed == other.unattributed &&
+          circles == other.circles;
+}
+
+/// Mirrors `haven_core::diagnostics::Breadcrumb`.
+class BreadcrumbFfi {
+  /// Module slug, e.g. `"circle.manager"`.
+  final String module;
+
+  /// Error-kind slug, e.g. `"mls"`.
+  final String errorKind;
+
+  /// Unix timestamp (seconds) when the error was recorded.
+  final PlatformInt64 timestamp;
+
+  const BreadcrumbFfi({
+    required this.module,
+    required this.errorKind,
+    required this.timestamp,
+  });
+
+  @override
+  int get hashCode => module.hashCode ^ errorKind.hashCode ^ timestamp.hashCode;
+
+  @override
+  bool operator ==(Object other) =>
+      identical(this, other) ||
+      other is BreadcrumbFfi &&
+          runtimeType == other.runtimeType &&
+          module == other.module &&
+          errorKind == other.errorKind &&
+          timestamp == other.timestamp;
+}
+
 /// Outcome of a [`CircleManagerFfi::build_relay_list_publish`] call.
 ///
 /// The FFI builds the signed event AND resolves the publish targets
@@ -2015,6 +3716,15 @@ class CatchupResultFfi {
   /// Relay fetches that returned no response / errored (never fatal).
   final int relayErrors;
 
+  /// Event signatures verified.
+  final int signaturesVerified;
+
+  /// Events accepted from the verified-event cache without re-verifying.
+  final int signaturesCached;
+
+  /// Events dropped for a mismatched id or invalid signature.
+  final int signaturesRejected;
+
   const CatchupResultFfi({
     required this.circlesSwept,
     required this.eventsApplied,
@@ -2022,6 +3732,9 @@ class CatchupResultFfi {
     required this.cursorsAdvanced,
     required this.deadlineHit,
     required this.relayErrors,
+    required this.signaturesVerified,
+    required this.signaturesCached,
+    required this.signaturesRejected,
   });
 
   @override
@@ -2031,7 +3744,10 @@ class CatchupResultFfi {
       eventsDeferred.hashCode ^
       cursorsAdvanced.hashCode ^
       deadlineHit.hashCode ^
-      relayErrors.hashCode;
+      relayErrors.hashCode ^
+      signaturesVerified.hashCode ^
+      signaturesCached.hashCode ^
+      signaturesRejected.hashCode;
 
   @override
   bool operator ==(Object other) =>
@@ -2043,7 +3759,112 @@ class CatchupResultFfi {
           eventsDeferred == other.eventsDeferred &&
           cursorsAdvanced == other.cursorsAdvanced &&
           deadlineHit == other.deadlineHit &&
-          relayErrors == other.relayErrors;
+          relayErrors == other.relayErrors &&
+          signaturesVerified == other.signaturesVerified &&
+          signaturesCached == other.signaturesCached &&
+          signaturesRejected == other.signaturesRejected;
+}
+
+/// The ground size of a shared location cell (FFI).
+class CellSizeFfi {
+  /// East-west extent in meters, at the latitude asked about.
+  final double widthM;
+
+  /// North-south extent in meters.
+  final double heightM;
+
+  /// A `location.cell_area` message whose `width` and `height` are plain
+  /// meter counts ("2400"), for the app to format with the user's locale
+  /// and units.
+  final UserMessageFfi description;
+
+  const CellSizeFfi({
+    required this.widthM,
+    required this.heightM,
+    required this.description,
+  });
+
+  @override
+  int get hashCode => widthM.hashCode ^ heightM.hashCode ^ description.hashCode;
+
+  @override
+  bool operator ==(Object other) =>
+      identical(this, other) ||
+      other is CellSizeFfi &&
+          runtimeType == other.runtimeType &&
+          widthM == other.widthM &&
+          heightM == other.heightM &&
+          description == other.description;
+}
+
+/// A scheduled check-in (FFI mirror of
+/// `haven_core::safety::CheckinRule`).
+class CheckinRuleFfi {
+  /// Rule id (hex).
+  final String ruleId;
+
+  /// The circle's MLS group ID.
+  final Uint8List mlsGroupId;
+
+  /// The member expected to check in (hex).
+  final String memberPubkey;
+
+  /// Local deadline, in minutes after midnight.
+  final int deadlineMinute;
+
+  /// How long before the deadline a location counts, in minutes.
+  final int windowMinutes;
+
+  /// Days the rule applies on (bit 0 is Monday, bit 6 Sunday).
+  final int weekdays;
+
+  /// When the rule was set up (Unix timestamp).
+  final PlatformInt64 createdAt;
+
+  /// When the member's latest location was taken (Unix timestamp).
+  final PlatformInt64? lastSeenAt;
+
+  /// The latest deadline whose alert was dismissed (Unix timestamp).
+  final PlatformInt64? acknowledgedDeadline;
+
+  const CheckinRuleFfi({
+    required this.ruleId,
+    required this.mlsGroupId,
+    required this.memberPubkey,
+    required this.deadlineMinute,
+    required this.windowMinutes,
+    required this.weekdays,
+    required this.createdAt,
+    this.lastSeenAt,
+    this.acknowledgedDeadline,
+  });
+
+  @override
+  int get hashCode =>
+      ruleId.hashCode ^
+      mlsGroupId.hashCode ^
+      memberPubkey.hashCode ^
+      deadlineMinute.hashCode ^
+      windowMinutes.hashCode ^
+      weekdays.hashCode ^
+      createdAt.hashCode ^
+      lastSeenAt.hashCode ^
+      acknowledgedDeadline.hashCode;
+
+  @override
+  bool operator ==(Object other) =>
+      identical(this, other) ||
+      other is CheckinRuleFfi &&
+          runtimeType == other.runtimeType &&
+          ruleId == other.ruleId &&
+          mlsGroupId == other.mlsGroupId &&
+          memberPubkey == other.memberPubkey &&
+          deadlineMinute == other.deadlineMinute &&
+          windowMinutes == other.windowMinutes &&
+          weekdays == other.weekdays &&
+          createdAt == other.createdAt &&
+          lastSeenAt == other.lastSeenAt &&
+          acknowledgedDeadline == other.acknowledgedDeadline;
 }
 
 /// Result of circle creation (FFI-friendly).
@@ -2141,6 +3962,40 @@ class CircleFfi {
           updatedAt == other.updatedAt;
 }
 
+/// A circle's location sharing overrides (FFI).
+class CircleLocationSettingsFfi {
+  /// Precision shared with the circle.
+  final LocationPrecisionFfi precision;
+
+  /// How long the circle's members keep each location, in seconds.
+  final BigInt shareExpirationSecs;
+
+  /// How old a member's location may be and still be shown, in seconds;
+  /// `None` for the default (1 hour).
+  final BigInt? displayMaxAgeSecs;
+
+  const CircleLocationSettingsFfi({
+    required this.precision,
+    required this.shareExpirationSecs,
+    this.displayMaxAgeSecs,
+  });
+
+  @override
+  int get hashCode =>
+      precision.hashCode ^
+      shareExpirationSecs.hashCode ^
+      displayMaxAgeSecs.hashCode;
+
+  @override
+  bool operator ==(Object other) =>
+      identical(this, other) ||
+      other is CircleLocationSettingsFfi &&
+          runtimeType == other.runtimeType &&
+          precision == other.precision &&
+          shareExpirationSecs == other.shareExpirationSecs &&
+          displayMaxAgeSecs == other.displayMaxAgeSecs;
+}
+
 /// Circle member with resolved local contact info (FFI-friendly).
 class CircleMemberFfi {
   /// Nostr public key (hex) - always available.
@@ -2180,6 +4035,128 @@ class CircleMemberFfi {
           isAdmin == other.isAdmin;
 }
 
+/// Compact "where is everyone" summary for widgets and watch complications.
+///
+/// Mirrors `haven_core::circle::CircleStatus`. Carries counts only — no
+/// coordinates and no pubkeys.
+class CircleStatusFfi {
+  /// Members counted (the roster minus the local user).
+  final int totalMembers;
+
+  /// Per-geofence counts, in the order the geofences were supplied.
+  final List<GeofenceOccupancyFfi> geofences;
+
+  /// Members with a fresh location outside every geofence.
+  final int elsewhere;
+
+  /// Members with no fresh location.
+  final int unknown;
+
+  const CircleStatusFfi({
+    required this.totalMembers,
+    required this.geofences,
+    required this.elsewhere,
+    required this.unknown,
+  });
+
+  @override
+  int get hashCode =>
+      totalMembers.hashCode ^
+      geofences.hashCode ^
+      elsewhere.hashCode ^
+      unknown.hashCode;
+
+  @override
+  bool operator ==(Object other) =>
+      identical(this, other) ||
+      other is CircleStatusFfi &&
+          runtimeType == other.runtimeType &&
+          totalMembers == other.totalMembers &&
+          geofences == other.geofences &&
+          elsewhere == other.elsewhere &&
+          unknown == other.unknown;
+}
+
+/// What a group sync changed in one circle (FFI mirror of
+/// [`haven_core::relay::CircleSyncDigest`]).
+class CircleSyncDigestFfi {
+  /// The circle's MLS group id (raw bytes).
+  final Uint8List mlsGroupId;
+
+  /// Distinct events fetched across the circle's relays.
+  final int eventsFetched;
+
+  /// Events whose ingest failed (re-fetched next sync).
+  final int eventsFailed;
+
+  /// Members whose last-known location was updated.
+  final int locationsUpdated;
+
+  /// Whether group state (membership, name, relays) changed.
+  final bool groupUpdated;
+
+  /// Who joined or left, across every group update in the sync.
+  final MembershipDeltaFfi membership;
+
+  /// The decrypted results, in event order.
+  final List<LocationMessageResultFfi> results;
+
+  const CircleSyncDigestFfi({
+    required this.mlsGroupId,
+    required this.eventsFetched,
+    required this.eventsFailed,
+    required this.locationsUpdated,
+    required this.groupUpdated,
+    required this.membership,
+    required this.results,
+  });
+
+  @override
+  int get hashCode =>
+      mlsGroupId.hashCode ^
+      eventsFetched.hashCode ^
+      eventsFailed.hashCode ^
+      locationsUpdated.hashCode ^
+      groupUpdated.hashCode ^
+      membership.hashCode ^
+      results.hashCode;
+
+  @override
+  bool operator ==(Object other) =>
+      identical(this, other) ||
+      other is CircleSyncDigestFfi &&
+          runtimeType == other.runtimeType &&
+          mlsGroupId == other.mlsGroupId &&
+          eventsFetched == other.eventsFetched &&
+          eventsFailed == other.eventsFailed &&
+          locationsUpdated == other.locationsUpdated &&
+          groupUpdated == other.groupUpdated &&
+          membership == other.membership &&
+          results == other.results;
+}
+
+/// Traffic carrying one circle's events (FFI-friendly).
+class CircleUsageFfi {
+  /// The circle's Nostr group id (hex).
+  final String nostrGroupId;
+
+  /// Traffic across all relays.
+  final UsageFfi usage;
+
+  const CircleUsageFfi({required this.nostrGroupId, required this.usage});
+
+  @override
+  int get hashCode => nostrGroupId.hashCode ^ usage.hashCode;
+
+  @override
+  bool operator ==(Object other) =>
+      identical(this, other) ||
+      other is CircleUsageFfi &&
+          runtimeType == other.runtimeType &&
+          nostrGroupId == other.nostrGroupId &&
+          usage == other.usage;
+}
+
 /// Circle with its membership and member list (FFI-friendly).
 class CircleWithMembersFfi {
   /// The circle.
@@ -2219,9 +4196,49 @@ class CircleWithMembersFfi {
           members == other.members;
 }
 
-/// A group-evolving commit awaiting publish + confirm (remove / relay update /
-/// admin change) — FFI mirror of `haven_core::circle::CommitToPublish`.
-///
+/// Summary of a circle in cold storage (see
+/// `haven_core::circle::cold_storage`).
+class ColdCircleInfoFfi {
+  /// Number of rows held in the blob.
+  final BigInt rowCount;
+
+  /// Size before compression and sealing, in bytes.
+  final BigInt rawBytes;
+
+  /// Size of the sealed blob, in bytes.
+  final BigInt sealedBytes;
+
+  /// Unix timestamp the circle was frozen.
+  final PlatformInt64 frozenAt;
+
+  const ColdCircleInfoFfi({
+    required this.rowCount,
+    required this.rawBytes,
+    required this.sealedBytes,
+    required this.frozenAt,
+  });
+
+  @override
+  int get hashCode =>
+      rowCount.hashCode ^
+      rawBytes.hashCode ^
+      sealedBytes.hashCode ^
+      frozenAt.hashCode;
+
+  @override
+  bool operator ==(Object other) =>
+      identical(this, other) ||
+      other is ColdCircleInfoFfi &&
+          runtimeType == other.runtimeType &&
+          rowCount == other.rowCount &&
+          rawBytes == other.rawBytes &&
+          sealedBytes == other.sealedBytes &&
+          frozenAt == other.frozenAt;
+}
+
+/// A group-evolving commit awaiting publish + confirm (remove / relay update /
+/// admin change) — FFI mirror of `haven_core::circle::CommitToPublish`.
+///
 /// Publish-before-apply (Rule 13): publish `commit_event_json` to the circle's
 /// relays, then confirm `pending` via [`CircleManagerFfi::confirm_published`]
 /// on ≥1-relay ACK (or roll back via [`CircleManagerFfi::publish_failed`]).
@@ -2249,6 +4266,93 @@ class CommitToPublishFfi {
           pending == other.pending;
 }
 
+/// A fetched event's canonical NIP-01 fields, without JSON.
+///
+/// Mirrors `haven_core::relay::CompactEvent`; byte fields are fixed-length
+/// (`id`/`pubkey` 32, `sig` 64) and re-verified when handed back to the core.
+class CompactEventFfi {
+  /// Event id (32 bytes).
+  final Uint8List id;
+
+  /// Author public key (32 bytes).
+  final Uint8List pubkey;
+
+  /// Unix timestamp (seconds).
+  final BigInt createdAt;
+
+  /// Event kind.
+  final int kind;
+
+  /// Tags, each a list of strings.
+  final List<List<String>> tags;
+
+  /// Event content.
+  final String content;
+
+  /// Schnorr signature (64 bytes).
+  final Uint8List sig;
+
+  const CompactEventFfi({
+    required this.id,
+    required this.pubkey,
+    required this.createdAt,
+    required this.kind,
+    required this.tags,
+    required this.content,
+    required this.sig,
+  });
+
+  @override
+  int get hashCode =>
+      id.hashCode ^
+      pubkey.hashCode ^
+      createdAt.hashCode ^
+      kind.hashCode ^
+      tags.hashCode ^
+      content.hashCode ^
+      sig.hashCode;
+
+  @override
+  bool operator ==(Object other) =>
+      identical(this, other) ||
+      other is CompactEventFfi &&
+          runtimeType == other.runtimeType &&
+          id == other.id &&
+          pubkey == other.pubkey &&
+          createdAt == other.createdAt &&
+          kind == other.kind &&
+          tags == other.tags &&
+          content == other.content &&
+          sig == other.sig;
+}
+
+/// Result of [`RelayManagerFfi::consume_key_package`].
+class ConsumedKeyPackageFfi {
+  /// Whether at least one relay accepted the deletion of the consumed
+  /// package.
+  final bool deletionPublished;
+
+  /// Event id (hex) of the published replacement, or `None` if no relay
+  /// accepted it (the next maintenance tick mints one).
+  final String? replacementEventId;
+
+  const ConsumedKeyPackageFfi({
+    required this.deletionPublished,
+    this.replacementEventId,
+  });
+
+  @override
+  int get hashCode => deletionPublished.hashCode ^ replacementEventId.hashCode;
+
+  @override
+  bool operator ==(Object other) =>
+      identical(this, other) ||
+      other is ConsumedKeyPackageFfi &&
+          runtimeType == other.runtimeType &&
+          deletionPublished == other.deletionPublished &&
+          replacementEventId == other.replacementEventId;
+}
+
 /// Local contact information (FFI-friendly).
 ///
 /// **Privacy Note**: This data is stored only on the user's device,
@@ -2270,12 +4374,20 @@ class ContactFfi {
   /// When this contact was last updated (Unix timestamp).
   final PlatformInt64 updatedAt;
 
+  /// Whether the user verified this contact's safety number in person.
+  final bool verified;
+
+  /// When the contact was verified (Unix timestamp).
+  final PlatformInt64? verifiedAt;
+
   const ContactFfi({
     required this.pubkey,
     this.displayName,
     this.notes,
     required this.createdAt,
     required this.updatedAt,
+    required this.verified,
+    this.verifiedAt,
   });
 
   @override
@@ -2284,7 +4396,9 @@ class ContactFfi {
       displayName.hashCode ^
       notes.hashCode ^
       createdAt.hashCode ^
-      updatedAt.hashCode;
+      updatedAt.hashCode ^
+      verified.hashCode ^
+      verifiedAt.hashCode;
 
   @override
   bool operator ==(Object other) =>
@@ -2295,7 +4409,112 @@ class ContactFfi {
           displayName == other.displayName &&
           notes == other.notes &&
           createdAt == other.createdAt &&
-          updatedAt == other.updatedAt;
+          updatedAt == other.updatedAt &&
+          verified == other.verified &&
+          verifiedAt == other.verifiedAt;
+}
+
+/// A contact label with how many contacts carry it (FFI-friendly).
+class ContactLabelFfi {
+  /// Normalized label (trimmed, lowercase).
+  final String label;
+
+  /// Number of contacts with this label.
+  final int contactCount;
+
+  const ContactLabelFfi({required this.label, required this.contactCount});
+
+  @override
+  int get hashCode => label.hashCode ^ contactCount.hashCode;
+
+  @override
+  bool operator ==(Object other) =>
+      identical(this, other) ||
+      other is ContactLabelFfi &&
+          runtimeType == other.runtimeType &&
+          label == other.label &&
+          contactCount == other.contactCount;
+}
+
+/// A member's trips over one local day (FFI mirror of
+/// `haven_core::location::DaySummary`). Times and distances only, no
+/// coordinates. Returned from `CircleManagerFfi::summarize_day`.
+class DaySummaryFfi {
+  /// The day summarized (`YYYY-MM-DD`).
+  final String date;
+
+  /// The day's first position (Unix timestamp), if any.
+  final PlatformInt64? firstSeenAt;
+
+  /// The day's last position (Unix timestamp), if any.
+  final PlatformInt64? lastSeenAt;
+
+  /// The day's trips, earliest first.
+  final List<TripFfi> trips;
+
+  /// Distance covered by all trips, in meters.
+  final double distanceM;
+
+  /// Time spent on trips, in seconds.
+  final PlatformInt64 movingSecs;
+
+  const DaySummaryFfi({
+    required this.date,
+    this.firstSeenAt,
+    this.lastSeenAt,
+    required this.trips,
+    required this.distanceM,
+    required this.movingSecs,
+  });
+
+  @override
+  int get hashCode =>
+      date.hashCode ^
+      firstSeenAt.hashCode ^
+      lastSeenAt.hashCode ^
+      trips.hashCode ^
+      distanceM.hashCode ^
+      movingSecs.hashCode;
+
+  @override
+  bool operator ==(Object other) =>
+      identical(this, other) ||
+      other is DaySummaryFfi &&
+          runtimeType == other.runtimeType &&
+          date == other.date &&
+          firstSeenAt == other.firstSeenAt &&
+          lastSeenAt == other.lastSeenAt &&
+          trips == other.trips &&
+          distanceM == other.distanceM &&
+          movingSecs == other.movingSecs;
+}
+
+/// Outcome of one event in [`CircleManagerFfi::decrypt_locations_batch`].
+///
+/// Exactly one of `outcome` and `error` is set.
+class DecryptBatchItemFfi {
+  /// Hex id of the event.
+  final String eventId;
+
+  /// What the event decrypted to, with its auto-commits to publish.
+  final DecryptLocationOutcomeFfi? outcome;
+
+  /// Why the event could not be ingested.
+  final HavenErrorFfi? error;
+
+  const DecryptBatchItemFfi({required this.eventId, this.outcome, this.error});
+
+  @override
+  int get hashCode => eventId.hashCode ^ outcome.hashCode ^ error.hashCode;
+
+  @override
+  bool operator ==(Object other) =>
+      identical(this, other) ||
+      other is DecryptBatchItemFfi &&
+          runtimeType == other.runtimeType &&
+          eventId == other.eventId &&
+          outcome == other.outcome &&
+          error == other.error;
 }
 
 /// The folded outcome of ingesting one received `kind:445` — FFI mirror of
@@ -2360,6 +4579,22 @@ class DecryptedLocationFfi {
   /// When this location expires (Unix seconds).
   final PlatformInt64 expiresAt;
 
+  /// Battery, charging and motion state, if the sender shares them.
+  final DeviceStatusFfi? deviceStatus;
+
+  /// Altitude and floor level, if the sender shares them.
+  final VerticalPositionFfi? verticalPosition;
+
+  /// Precision of the coordinates as received (`"coarse"`,
+  /// `"approximate"` or `"exact"`), classified from the coordinates
+  /// themselves.
+  final String precision;
+
+  /// The precision the sender declared, by its stable name. A value from a
+  /// newer sender is passed through verbatim; treat unknown names as
+  /// informational.
+  final String? declaredPrecision;
+
   const DecryptedLocationFfi({
     required this.senderPubkey,
     required this.latitude,
@@ -2367,6 +4602,10 @@ class DecryptedLocationFfi {
     required this.geohash,
     required this.timestamp,
     required this.expiresAt,
+    this.deviceStatus,
+    this.verticalPosition,
+    required this.precision,
+    this.declaredPrecision,
   });
 
   @override
@@ -2376,7 +4615,11 @@ class DecryptedLocationFfi {
       longitude.hashCode ^
       geohash.hashCode ^
       timestamp.hashCode ^
-      expiresAt.hashCode;
+      expiresAt.hashCode ^
+      deviceStatus.hashCode ^
+      verticalPosition.hashCode ^
+      precision.hashCode ^
+      declaredPrecision.hashCode;
 
   @override
   bool operator ==(Object other) =>
@@ -2388,13 +4631,99 @@ class DecryptedLocationFfi {
           longitude == other.longitude &&
           geohash == other.geohash &&
           timestamp == other.timestamp &&
-          expiresAt == other.expiresAt;
+          expiresAt == other.expiresAt &&
+          deviceStatus == other.deviceStatus &&
+          verticalPosition == other.verticalPosition &&
+          precision == other.precision &&
+          declaredPrecision == other.declaredPrecision;
+}
+
+/// A circle that still depends on the identity — see
+/// [`CircleManagerFfi::identity_deletion_report`].
+class DependentCircleFfi {
+  /// MLS group ID bytes.
+  final Uint8List mlsGroupId;
+
+  /// The circle's local display name.
+  final String name;
+
+  /// How the identity would leave it.
+  final LeavePlanFfi plan;
+
+  const DependentCircleFfi({
+    required this.mlsGroupId,
+    required this.name,
+    required this.plan,
+  });
+
+  @override
+  int get hashCode => mlsGroupId.hashCode ^ name.hashCode ^ plan.hashCode;
+
+  @override
+  bool operator ==(Object other) =>
+      identical(this, other) ||
+      other is DependentCircleFfi &&
+          runtimeType == other.runtimeType &&
+          mlsGroupId == other.mlsGroupId &&
+          name == other.name &&
+          plan == other.plan;
+}
+
+/// Device status shared alongside a location (FFI mirror of
+/// [`haven_core::location::DeviceStatus`]).
+class DeviceStatusFfi {
+  /// Battery level, 0–100.
+  final int? batteryPercent;
+
+  /// Whether the device is charging.
+  final bool? charging;
+
+  /// What the device is doing.
+  final MotionStateFfi? motion;
+
+  const DeviceStatusFfi({this.batteryPercent, this.charging, this.motion});
+
+  @override
+  int get hashCode =>
+      batteryPercent.hashCode ^ charging.hashCode ^ motion.hashCode;
+
+  @override
+  bool operator ==(Object other) =>
+      identical(this, other) ||
+      other is DeviceStatusFfi &&
+          runtimeType == other.runtimeType &&
+          batteryPercent == other.batteryPercent &&
+          charging == other.charging &&
+          motion == other.motion;
+}
+
+/// How far a member is from the local user (mirrors
+/// `haven_core::circle::DistanceBucket`).
+enum DistanceBucketFfi {
+  /// Under 1 km.
+  nearby,
+
+  /// 1–10 km.
+  local,
+
+  /// 10–100 km.
+  regional,
+
+  /// 100 km or more.
+  far,
+
+  /// No own position, or no location for the member.
+  unknown,
 }
 
 /// Encrypted location event ready for relay publishing (FFI-friendly).
 ///
-/// Contains the signed kind 445 event and routing metadata.
+/// Contains the signed kind 445 event and routing metadata. `event_id` is
+/// the id the publish result, the outbox and a later retraction refer to.
 class EncryptedLocationFfi {
+  /// The event's id (64-char lowercase hex).
+  final String eventId;
+
   /// JSON-serialized signed Nostr event (kind 445).
   final String eventJson;
 
@@ -2405,6 +4734,7 @@ class EncryptedLocationFfi {
   final List<String> relays;
 
   const EncryptedLocationFfi({
+    required this.eventId,
     required this.eventJson,
     required this.nostrGroupId,
     required this.relays,
@@ -2412,18 +4742,65 @@ class EncryptedLocationFfi {
 
   @override
   int get hashCode =>
-      eventJson.hashCode ^ nostrGroupId.hashCode ^ relays.hashCode;
+      eventId.hashCode ^
+      eventJson.hashCode ^
+      nostrGroupId.hashCode ^
+      relays.hashCode;
 
   @override
   bool operator ==(Object other) =>
       identical(this, other) ||
       other is EncryptedLocationFfi &&
           runtimeType == other.runtimeType &&
+          eventId == other.eventId &&
           eventJson == other.eventJson &&
           nostrGroupId == other.nostrGroupId &&
           relays == other.relays;
 }
 
+/// Deployment environment (FFI mirror of [`haven_core::Environment`]).
+enum EnvironmentFfi {
+  /// Developer build against a local mock relay.
+  dev,
+
+  /// QA build against staging relays.
+  staging,
+
+  /// The shipped app.
+  production,
+}
+
+/// A circle's epoch position (mirrors `haven_core::nostr::mls::EpochInfo`).
+class EpochInfoFfi {
+  /// The current MLS epoch.
+  final BigInt epoch;
+
+  /// The oldest epoch whose messages can still be decrypted.
+  final BigInt oldestReadableEpoch;
+
+  /// Number of members at the current epoch.
+  final int memberCount;
+
+  const EpochInfoFfi({
+    required this.epoch,
+    required this.oldestReadableEpoch,
+    required this.memberCount,
+  });
+
+  @override
+  int get hashCode =>
+      epoch.hashCode ^ oldestReadableEpoch.hashCode ^ memberCount.hashCode;
+
+  @override
+  bool operator ==(Object other) =>
+      identical(this, other) ||
+      other is EpochInfoFfi &&
+          runtimeType == other.runtimeType &&
+          epoch == other.epoch &&
+          oldestReadableEpoch == other.oldestReadableEpoch &&
+          memberCount == other.memberCount;
+}
+
 /// A circle to subscribe the live-sync engine to. `nostr_group_id` is the raw
 /// 32-byte pseudonymous id (NEVER the MLS group id); the engine hex-encodes it
 /// for the `#h` filter.
@@ -2462,6 +4839,12 @@ class FfiRelayEvent {
   /// Sender's hex Nostr public key (Location).
   final String? senderPubkey;
 
+  /// Hex Nostr public key of the member asked to check in (CheckinRequest).
+  final String? targetPubkey;
+
+  /// Baseline and observed precision (PrecisionAnomaly).
+  final PrecisionAnomalyFfi? precisionAnomaly;
+
   /// Decrypted location content JSON (Location).
   final String? content;
 
@@ -2481,16 +4864,23 @@ class FfiRelayEvent {
   /// Closed status reason (Status).
   final FfiSyncStatusReason? statusReason;
 
+  /// Localizable notification text, for events that warrant one (Sos,
+  /// MeetPin, CheckinRequest, PrecisionAnomaly, Welcome).
+  final UserMessageFfi? notification;
+
   const FfiRelayEvent({
     required this.kind,
     this.nostrGroupId,
     this.senderPubkey,
+    this.targetPubkey,
+    this.precisionAnomaly,
     this.content,
     this.eventCreatedAtSecs,
     this.evolutionEventJson,
     this.giftWrapJson,
     this.wrapCreatedAtSecs,
     this.statusReason,
+    this.notification,
   });
 
   @override
@@ -2498,12 +4888,15 @@ class FfiRelayEvent {
       kind.hashCode ^
       nostrGroupId.hashCode ^
       senderPubkey.hashCode ^
+      targetPubkey.hashCode ^
+      precisionAnomaly.hashCode ^
       content.hashCode ^
       eventCreatedAtSecs.hashCode ^
       evolutionEventJson.hashCode ^
       giftWrapJson.hashCode ^
       wrapCreatedAtSecs.hashCode ^
-      statusReason.hashCode;
+      statusReason.hashCode ^
+      notification.hashCode;
 
   @override
   bool operator ==(Object other) =>
@@ -2513,12 +4906,15 @@ class FfiRelayEvent {
           kind == other.kind &&
           nostrGroupId == other.nostrGroupId &&
           senderPubkey == other.senderPubkey &&
+          targetPubkey == other.targetPubkey &&
+          precisionAnomaly == other.precisionAnomaly &&
           content == other.content &&
           eventCreatedAtSecs == other.eventCreatedAtSecs &&
           evolutionEventJson == other.evolutionEventJson &&
           giftWrapJson == other.giftWrapJson &&
           wrapCreatedAtSecs == other.wrapCreatedAtSecs &&
-          statusReason == other.statusReason;
+          statusReason == other.statusReason &&
+          notification == other.notification;
 }
 
 /// Discriminator for [`FfiRelayEvent`] (struct-of-discriminant, like
@@ -2527,6 +4923,19 @@ enum FfiRelayEventKind {
   /// A decrypted location.
   location,
 
+  /// A decrypted emergency (SOS) broadcast; `content` is `SosMessage` JSON.
+  sos,
+
+  /// A decrypted check-in request from `sender_pubkey` to `target_pubkey`.
+  checkinRequest,
+
+  /// A decrypted, still-live meet pin; `content` is `MeetPin` JSON.
+  meetPin,
+
+  /// `sender_pubkey` started sharing finer locations; see
+  /// `precision_anomaly`.
+  precisionAnomaly,
+
   /// A group membership/epoch update.
   groupUpdate,
 
@@ -2572,161 +4981,467 @@ enum FfiSyncStatusReason {
   backgroundResumed,
 }
 
-/// A gift-wrapped Welcome ready for publishing (FFI-friendly).
+/// How current a member's location is (mirrors
+/// `haven_core::circle::FreshnessBucket`).
+enum FreshnessBucketFfi {
+  /// Within the sender's freshness window.
+  live,
+
+  /// Expired, but captured within the last hour.
+  recent,
+
+  /// Older than that, but still cached.
+  stale,
+
+  /// No location cached.
+  unknown,
+}
+
+/// A local-only circular geofence (FFI-friendly).
 ///
-/// Contains the kind 1059 gift-wrapped event along with recipient
-/// information needed for relay publishing.
-class GiftWrappedWelcomeFfi {
-  /// The recipient's Nostr public key (hex).
-  final String recipientPubkey;
+/// Mirrors `haven_core::location::Geofence`. Geofences never leave the device;
+/// they are passed in per call to [`CircleManagerFfi::get_circle_status`].
+class GeofenceFfi {
+  /// User-facing label (e.g. "Home").
+  final String label;
 
-  /// Relay URLs to publish this Welcome to (recipient's inbox relays).
-  final List<String> recipientRelays;
+  /// Center latitude in degrees.
+  final double latitude;
 
-  /// The gift-wrapped event JSON (kind 1059), ready to publish.
-  final String eventJson;
+  /// Center longitude in degrees.
+  final double longitude;
 
-  const GiftWrappedWelcomeFfi({
-    required this.recipientPubkey,
-    required this.recipientRelays,
-    required this.eventJson,
+  /// Radius in meters.
+  final double radiusM;
+
+  const GeofenceFfi({
+    required this.label,
+    required this.latitude,
+    required this.longitude,
+    required this.radiusM,
   });
 
   @override
   int get hashCode =>
-      recipientPubkey.hashCode ^ recipientRelays.hashCode ^ eventJson.hashCode;
+      label.hashCode ^
+      latitude.hashCode ^
+      longitude.hashCode ^
+      radiusM.hashCode;
 
   @override
   bool operator ==(Object other) =>
       identical(this, other) ||
-      other is GiftWrappedWelcomeFfi &&
+      other is GeofenceFfi &&
           runtimeType == other.runtimeType &&
-          recipientPubkey == other.recipientPubkey &&
-          recipientRelays == other.recipientRelays &&
-          eventJson == other.eventJson;
+          label == other.label &&
+          latitude == other.latitude &&
+          longitude == other.longitude &&
+          radiusM == other.radiusM;
 }
 
-/// Pending invitation to join a circle (FFI-friendly).
-class InvitationFfi {
-  /// MLS group ID.
-  final Uint8List mlsGroupId;
-
-  /// Circle name.
-  final String circleName;
-
-  /// Public key (hex) of who invited us.
-  final String inviterPubkey;
+/// Member count inside one geofence (FFI-friendly).
+class GeofenceOccupancyFfi {
+  /// The geofence label, as supplied by the caller.
+  final String label;
 
-  /// Number of members in the circle.
+  /// Number of members whose fresh location is inside the geofence.
   final int memberCount;
 
-  /// When we were invited (Unix timestamp).
-  final PlatformInt64 invitedAt;
+  const GeofenceOccupancyFfi({required this.label, required this.memberCount});
 
-  const InvitationFfi({
-    required this.mlsGroupId,
-    required this.circleName,
-    required this.inviterPubkey,
-    required this.memberCount,
-    required this.invitedAt,
-  });
+  @override
+  int get hashCode => label.hashCode ^ memberCount.hashCode;
 
   @override
-  int get hashCode =>
-      mlsGroupId.hashCode ^
-      circleName.hashCode ^
-      inviterPubkey.hashCode ^
-      memberCount.hashCode ^
-      invitedAt.hashCode;
+  bool operator ==(Object other) =>
+      identical(this, other) ||
+      other is GeofenceOccupancyFfi &&
+          runtimeType == other.runtimeType &&
+          label == other.label &&
+          memberCount == other.memberCount;
+}
+
+/// Outcome of one gift wrap in a batch (FFI-friendly).
+///
+/// Exactly one of: `invitation` is set (a new pending invitation),
+/// `already_processed` is true (a repeat; skip silently), or `error` is set.
+class GiftWrapOutcomeFfi {
+  /// Hex id of the gift-wrap event.
+  final String eventId;
+
+  /// The new pending invitation, if one was surfaced.
+  final InvitationFfi? invitation;
+
+  /// Whether the wrap had already been processed or is already held.
+  final bool alreadyProcessed;
+
+  /// Why the wrap could not be processed.
+  final HavenErrorFfi? error;
+
+  const GiftWrapOutcomeFfi({
+    required this.eventId,
+    this.invitation,
+    required this.alreadyProcessed,
+    this.error,
+  });
+
+  @override
+  int get hashCode =>
+      eventId.hashCode ^
+      invitation.hashCode ^
+      alreadyProcessed.hashCode ^
+      error.hashCode;
 
   @override
   bool operator ==(Object other) =>
       identical(this, other) ||
-      other is InvitationFfi &&
+      other is GiftWrapOutcomeFfi &&
           runtimeType == other.runtimeType &&
-          mlsGroupId == other.mlsGroupId &&
-          circleName == other.circleName &&
-          inviterPubkey == other.inviterPubkey &&
-          memberCount == other.memberCount &&
-          invitedAt == other.invitedAt;
+          eventId == other.eventId &&
+          invitation == other.invitation &&
+          alreadyProcessed == other.alreadyProcessed &&
+          error == other.error;
 }
 
-/// What an M8-2 `KeyPackage` maintenance tick did (FFI mirror of
-/// [`haven_core::relay::maintenance::KpMaintenanceAction`]).
+/// A gift-wrapped Welcome ready for publishing (FFI-friendly).
 ///
-/// Fieldless / payload-free — no `d`, url, hex, or group id — so it is
-/// leak-free by construction (Security Rule 4/6).
-enum KpMaintenanceActionFfi {
-  /// A live-material canonical `KeyPackage` was already reachable — no change.
-  alreadyHealthy,
+/// Contains the kind 1059 gift-wrapped event along with recipient
+/// information needed for relay publishing.
+class GiftWrappedWelcomeFfi {
+  /// The recipient's Nostr public key (hex).
+  final String recipientPubkey;
 
-  /// A stable `d` was seeded from an on-relay canonical this tick; no publish.
-  seededD,
+  /// Relay URLs to publish this Welcome to (recipient's inbox relays).
+  final List<String> recipientRelays;
 
-  /// A `KeyPackage` was (re)published into a reused, tracked/seeded stable `d`.
-  republishedStableD,
+  /// Later delivery tiers, tried only if no relay in `recipient_relays`
+  /// takes the Welcome.
+  final List<String> fallbackRelays;
 
-  /// A `KeyPackage` was published into a freshly-minted `d` (first-ever slot).
-  republishedFreshD,
+  /// The gift-wrapped event JSON (kind 1059), ready to publish.
+  final String eventJson;
+
+  const GiftWrappedWelcomeFfi({
+    required this.recipientPubkey,
+    required this.recipientRelays,
+    required this.fallbackRelays,
+    required this.eventJson,
+  });
+
+  @override
+  int get hashCode =>
+      recipientPubkey.hashCode ^
+      recipientRelays.hashCode ^
+      fallbackRelays.hashCode ^
+      eventJson.hashCode;
+
+  @override
+  bool operator ==(Object other) =>
+      identical(this, other) ||
+      other is GiftWrappedWelcomeFfi &&
+          runtimeType == other.runtimeType &&
+          recipientPubkey == other.recipientPubkey &&
+          recipientRelays == other.recipientRelays &&
+          fallbackRelays == other.fallbackRelays &&
+          eventJson == other.eventJson;
 }
 
-/// Presence-only result of an M8-2 `KeyPackage` maintenance tick.
+/// One circle in a [`GlanceableSnapshotFfi`].
+class GlanceCircleFfi {
+  /// The circle's local name, shortened.
+  final String name;
+
+  /// Every member except the local user.
+  final List<GlanceMemberFfi> members;
+
+  const GlanceCircleFfi({required this.name, required this.members});
+
+  @override
+  int get hashCode => name.hashCode ^ members.hashCode;
+
+  @override
+  bool operator ==(Object other) =>
+      identical(this, other) ||
+      other is GlanceCircleFfi &&
+          runtimeType == other.runtimeType &&
+          name == other.name &&
+          members == other.members;
+}
+
+/// One member in a [`GlanceCircleFfi`].
+class GlanceMemberFfi {
+  /// Up to two uppercase initials, or `?`.
+  final String initials;
+
+  /// How current the member's location is.
+  final FreshnessBucketFfi freshness;
+
+  /// How far the member is from the local user.
+  final DistanceBucketFfi distance;
+
+  const GlanceMemberFfi({
+    required this.initials,
+    required this.freshness,
+    required this.distance,
+  });
+
+  @override
+  int get hashCode =>
+      initials.hashCode ^ freshness.hashCode ^ distance.hashCode;
+
+  @override
+  bool operator ==(Object other) =>
+      identical(this, other) ||
+      other is GlanceMemberFfi &&
+          runtimeType == other.runtimeType &&
+          initials == other.initials &&
+          freshness == other.freshness &&
+          distance == other.distance;
+}
+
+/// Minimal snapshot for watch complications and home-screen widgets.
 ///
-/// Counters + an action enum only — never a relay url, `d`, hex, or group id —
-/// so it is leak-free (Security Rule 4/6). This is the shape the Dart
-/// `MaintenanceScheduler` folds ticks into.
-class KpMaintenanceOutcomeFfi {
-  /// What the tick did.
-  final KpMaintenanceActionFfi action;
+/// Mirrors `haven_core::circle::GlanceableSnapshot`. Carries no coordinates,
+/// pubkeys or exact timestamps, so it is safe to hand to a widget extension
+/// or a paired watch that stores it outside the app's protected storage.
+class GlanceableSnapshotFfi {
+  /// When the snapshot was assembled (Unix seconds).
+  final PlatformInt64 generatedAt;
+
+  /// Visible circles.
+  final List<GlanceCircleFfi> circles;
+
+  const GlanceableSnapshotFfi({
+    required this.generatedAt,
+    required this.circles,
+  });
 
-  /// Own-relay canonical (kind 30443) events the probe observed (summed
-  /// across responders).
-  final int canonicalOnRelays;
+  @override
+  int get hashCode => generatedAt.hashCode ^ circles.hashCode;
 
-  /// Responding own relays the probe reached this tick (non-responders
-  /// excluded).
-  final int respondersProbed;
+  @override
+  bool operator ==(Object other) =>
+      identical(this, other) ||
+      other is GlanceableSnapshotFfi &&
+          runtimeType == other.runtimeType &&
+          generatedAt == other.generatedAt &&
+          circles == other.circles;
+}
 
-  /// Responding + non-live relays this tick republished to.
-  final int relaysHealed;
+/// A circle's receive-side health (mirrors `haven_core::circle::GroupHealth`).
+class GroupHealthFfi {
+  /// Current MLS epoch, or `None` if the engine no longer holds the group.
+  final BigInt? epoch;
 
-  /// Relay probes/publishes that errored (tallied, never fatal).
-  final int relayErrors;
+  /// Commits staged for the circle and not yet confirmed or rolled back.
+  final int pendingCommits;
 
-  const KpMaintenanceOutcomeFfi({
-    required this.action,
-    required this.canonicalOnRelays,
-    required this.respondersProbed,
-    required this.relaysHealed,
-    required this.relayErrors,
+  /// Messages that failed to ingest since the last one that applied.
+  final int unprocessableCount;
+
+  /// Whether to show the circle as needing repair.
+  final bool needsRepair;
+
+  const GroupHealthFfi({
+    this.epoch,
+    required this.pendingCommits,
+    required this.unprocessableCount,
+    required this.needsRepair,
   });
 
   @override
   int get hashCode =>
-      action.hashCode ^
-      canonicalOnRelays.hashCode ^
-      respondersProbed.hashCode ^
-      relaysHealed.hashCode ^
-      relayErrors.hashCode;
+      epoch.hashCode ^
+      pendingCommits.hashCode ^
+      unprocessableCount.hashCode ^
+      needsRepair.hashCode;
 
   @override
   bool operator ==(Object other) =>
       identical(this, other) ||
-      other is KpMaintenanceOutcomeFfi &&
+      other is GroupHealthFfi &&
           runtimeType == other.runtimeType &&
-          action == other.action &&
-          canonicalOnRelays == other.canonicalOnRelays &&
-          respondersProbed == other.respondersProbed &&
-          relaysHealed == other.relaysHealed &&
-          relayErrors == other.relayErrors;
+          epoch == other.epoch &&
+          pendingCommits == other.pendingCommits &&
+          unprocessableCount == other.unprocessableCount &&
+          needsRepair == other.needsRepair;
 }
 
-/// A persisted last-known location for a circle member (FFI-friendly).
+/// One page of group messages (FFI mirror of
+/// [`haven_core::relay::GroupMessagePage`]).
+class GroupMessagePageFfi {
+  /// Distinct events as JSON, oldest first.
+  final List<String> eventsJson;
+
+  /// The `until` for the next (older) page; `None` when history is
+  /// exhausted.
+  final PlatformInt64? nextUntil;
+
+  /// Relays queried.
+  final int relaysPolled;
+
+  /// Relays that answered.
+  final int relaysResponded;
+
+  const GroupMessagePageFfi({
+    required this.eventsJson,
+    this.nextUntil,
+    required this.relaysPolled,
+    required this.relaysResponded,
+  });
+
+  @override
+  int get hashCode =>
+      eventsJson.hashCode ^
+      nextUntil.hashCode ^
+      relaysPolled.hashCode ^
+      relaysResponded.hashCode;
+
+  @override
+  bool operator ==(Object other) =>
+      identical(this, other) ||
+      other is GroupMessagePageFfi &&
+          runtimeType == other.runtimeType &&
+          eventsJson == other.eventsJson &&
+          nextUntil == other.nextUntil &&
+          relaysPolled == other.relaysPolled &&
+          relaysResponded == other.relaysResponded;
+}
+
+/// Result of [`CircleManagerFfi::repair_group`].
+class GroupRepairFfi {
+  /// What the repair did.
+  final RepairOutcomeFfi outcome;
+
+  /// Results and auto-commits drained while re-driving the group.
+  final DecryptLocationOutcomeFfi drained;
+
+  const GroupRepairFfi({required this.outcome, required this.drained});
+
+  @override
+  int get hashCode => outcome.hashCode ^ drained.hashCode;
+
+  @override
+  bool operator ==(Object other) =>
+      identical(this, other) ||
+      other is GroupRepairFfi &&
+          runtimeType == other.runtimeType &&
+          outcome == other.outcome &&
+          drained == other.drained;
+}
+
+/// Error returned by every fallible FFI call.
 ///
-/// Mirrors `haven_core::circle::LastKnownLocation`. Returned from
-/// `CircleManagerFfi::snapshot_last_known_for_circle` so the Flutter
-/// layer can hydrate its in-memory cache on app start.
-class LastKnownLocationFfi {
+/// `message` is what the user sees (localized by Flutter); `detail` is the
+/// detailed English description for logs and bug reports. `Display` prints
+/// `detail`, so existing `log::` call sites keep their text.
+class HavenErrorFfi implements FrbException {
+  /// User-facing, localizable message.
+  final UserMessageFfi message;
+
+  /// Detailed English description, for logs only.
+  final String detail;
+
+  const HavenErrorFfi({required this.message, required this.detail});
+
+  @override
+  int get hashCode => message.hashCode ^ detail.hashCode;
+
+  @override
+  bool operator ==(Object other) =>
+      identical(this, other) ||
+      other is HavenErrorFfi &&
+          runtimeType == other.runtimeType &&
+          message == other.message &&
+          detail == other.detail;
+}
+
+/// A decoded inner message payload (FFI-friendly tagged form of
+/// `haven_core::payload::HavenPayload`): `kind` says which of the optional
+/// fields is set, following the [`LocationMessageResultFfi`] convention.
+class HavenPayloadFfi {
+  /// Which payload this is.
+  final HavenPayloadKindFfi kind;
+
+  /// Schema version the sender wrote (`0` for content without an envelope).
+  final int schemaVersion;
+
+  /// The position — `Some` for `kind == Location` and `kind == Sos`.
+  final DecryptedLocationFfi? location;
+
+  /// The optional SOS note — only for `kind == Sos`.
+  final String? sosMessage;
+
+  /// Lowercase hex pubkey of the member asked — only for
+  /// `kind == CheckinRequest`.
+  final String? checkinTargetPubkey;
+
+  /// The pin — only for `kind == MeetPin`.
+  final MeetPinFfi? meetPin;
+
+  /// Device status — only for `kind == Status`.
+  final DeviceStatusFfi? deviceStatus;
+
+  const HavenPayloadFfi({
+    required this.kind,
+    required this.schemaVersion,
+    this.location,
+    this.sosMessage,
+    this.checkinTargetPubkey,
+    this.meetPin,
+    this.deviceStatus,
+  });
+
+  @override
+  int get hashCode =>
+      kind.hashCode ^
+      schemaVersion.hashCode ^
+      location.hashCode ^
+      sosMessage.hashCode ^
+      checkinTargetPubkey.hashCode ^
+      meetPin.hashCode ^
+      deviceStatus.hashCode;
+
+  @override
+  bool operator ==(Object other) =>
+      identical(this, other) ||
+      other is HavenPayloadFfi &&
+          runtimeType == other.runtimeType &&
+          kind == other.kind &&
+          schemaVersion == other.schemaVersion &&
+          location == other.location &&
+          sosMessage == other.sosMessage &&
+          checkinTargetPubkey == other.checkinTargetPubkey &&
+          meetPin == other.meetPin &&
+          deviceStatus == other.deviceStatus;
+}
+
+/// Which [`HavenPayloadFfi`] variant a payload is (mirror of
+/// `haven_core::payload::HavenPayload`).
+enum HavenPayloadKindFfi {
+  /// A location update; details in `location`.
+  location,
+
+  /// An emergency broadcast; `location` plus the optional `sos_message`.
+  sos,
+
+  /// A check-in request for `checkin_target_pubkey`.
+  checkinRequest,
+
+  /// A meet pin; details in `meet_pin`.
+  meetPin,
+
+  /// Device status without a position; details in `device_status`.
+  status,
+
+  /// A type this build does not know (sent by a newer client).
+  unknown,
+}
+
+/// A location from the history (FFI mirror of
+/// `haven_core::circle::HistoryPoint`). Returned from
+/// `CircleManagerFfi::get_points_in_viewport`.
+class HistoryPointFfi {
   /// Nostr group ID (32 bytes) of the circle.
   final Uint8List nostrGroupId;
 
@@ -2739,35 +5454,15 @@ class LastKnownLocationFfi {
   /// Longitude (exact GPS reading).
   final double longitude;
 
-  /// Geohash of the location.
-  final String geohash;
-
-  /// Display name carried in the encrypted location message, if any.
-  final String? displayName;
-
   /// When the location was captured (Unix seconds).
   final PlatformInt64 timestamp;
 
-  /// When the inner freshness window expires (Unix seconds).
-  final PlatformInt64 expiresAt;
-
-  /// Row must be deleted after this Unix-seconds moment.
-  final PlatformInt64 purgeAfter;
-
-  /// When this row was last written (Unix seconds, receiver clock).
-  final PlatformInt64 updatedAt;
-
-  const LastKnownLocationFfi({
+  const HistoryPointFfi({
     required this.nostrGroupId,
     required this.senderPubkey,
     required this.latitude,
     required this.longitude,
-    required this.geohash,
-    this.displayName,
     required this.timestamp,
-    required this.expiresAt,
-    required this.purgeAfter,
-    required this.updatedAt,
   });
 
   @override
@@ -2776,271 +5471,1678 @@ class LastKnownLocationFfi {
       senderPubkey.hashCode ^
       latitude.hashCode ^
       longitude.hashCode ^
-      geohash.hashCode ^
-      displayName.hashCode ^
-      timestamp.hashCode ^
-      expiresAt.hashCode ^
-      purgeAfter.hashCode ^
-      updatedAt.hashCode;
+      timestamp.hashCode;
 
   @override
   bool operator ==(Object other) =>
       identical(this, other) ||
-      other is LastKnownLocationFfi &&
+      other is HistoryPointFfi &&
           runtimeType == other.runtimeType &&
           nostrGroupId == other.nostrGroupId &&
           senderPubkey == other.senderPubkey &&
           latitude == other.latitude &&
           longitude == other.longitude &&
-          geohash == other.geohash &&
-          displayName == other.displayName &&
-          timestamp == other.timestamp &&
-          expiresAt == other.expiresAt &&
-          purgeAfter == other.purgeAfter &&
-          updatedAt == other.updatedAt;
+          timestamp == other.timestamp;
 }
 
-/// FFI mirror of [`haven_core::circle::LeavePlan`].
-///
-/// Struct shape (rather than tagged enum) avoids pulling in Dart `freezed`
-/// for sealed classes while preserving all the information.
-class LeavePlanFfi {
-  /// Which branch of the state machine to execute.
-  final LeavePlanKindFfi kind;
+/// FFI mirror of [`haven_core::circle::IdentityDeletionReport`].
+class IdentityDeletionReportFfi {
+  /// Circles the identity is a member of; non-empty means deletion needs
+  /// `force`.
+  final List<DependentCircleFfi> circles;
 
-  /// Hex-encoded successor pubkey when `kind == AdminHandoff`, else `None`.
-  final String? successorHex;
+  /// Unanswered invitations (discarded on deletion).
+  final int pendingInvitations;
 
-  const LeavePlanFfi({required this.kind, this.successorHex});
+  const IdentityDeletionReportFfi({
+    required this.circles,
+    required this.pendingInvitations,
+  });
 
   @override
-  int get hashCode => kind.hashCode ^ successorHex.hashCode;
+  int get hashCode => circles.hashCode ^ pendingInvitations.hashCode;
 
   @override
   bool operator ==(Object other) =>
       identical(this, other) ||
-      other is LeavePlanFfi &&
+      other is IdentityDeletionReportFfi &&
           runtimeType == other.runtimeType &&
-          kind == other.kind &&
-          successorHex == other.successorHex;
+          circles == other.circles &&
+          pendingInvitations == other.pendingInvitations;
 }
 
-/// Discriminator for [`LeavePlanFfi`].
+/// FFI mirror of [`haven_core::circle::IdentityTeardown`].
+class IdentityTeardownFfi {
+  /// Leave proposals to publish (best effort; already signed, so the key
+  /// is not needed to publish them).
+  final List<LeaveEventFfi> leaveEvents;
+
+  /// Circles dropped with nothing to publish.
+  final List<Uint8List> abandoned;
+
+  /// Circles that could not be left (e.g. admin circles).
+  final List<Uint8List> notLeft;
+
+  /// Circle rows removed from local storage.
+  final int circlesWiped;
+
+  const IdentityTeardownFfi({
+    required this.leaveEvents,
+    required this.abandoned,
+    required this.notLeft,
+    required this.circlesWiped,
+  });
+
+  @override
+  int get hashCode =>
+      leaveEvents.hashCode ^
+      abandoned.hashCode ^
+      notLeft.hashCode ^
+      circlesWiped.hashCode;
+
+  @override
+  bool operator ==(Object other) =>
+      identical(this, other) ||
+      other is IdentityTeardownFfi &&
+          runtimeType == other.runtimeType &&
+          leaveEvents == other.leaveEvents &&
+          abandoned == other.abandoned &&
+          notLeft == other.notLeft &&
+          circlesWiped == other.circlesWiped;
+}
+
+/// Result of an inbox poll (FFI mirror of
+/// [`haven_core::relay::InboxSyncSummary`]).
+class InboxSyncSummaryFfi {
+  /// Relays queried.
+  final int relaysPolled;
+
+  /// Relays that answered.
+  final int relaysResponded;
+
+  /// Distinct gift wraps fetched across all relays.
+  final int wrapsFetched;
+
+  /// Wraps skipped as already processed or already pending.
+  final int duplicates;
+
+  /// Wraps that could not be processed.
+  final int failed;
+
+  /// Invitations surfaced by this poll, oldest first.
+  final List<InvitationFfi> newInvitations;
+
+  /// Unexpired one-shot locations received this poll, oldest first.
+  final List<OneShotLocationFfi> oneShotLocations;
+
+  /// Rejoin requests this device can grant, oldest first.
+  final List<RejoinRequestFfi> rejoinRequests;
+
+  const InboxSyncSummaryFfi({
+    required this.relaysPolled,
+    required this.relaysResponded,
+    required this.wrapsFetched,
+    required this.duplicates,
+    required this.failed,
+    required this.newInvitations,
+    required this.oneShotLocations,
+    required this.rejoinRequests,
+  });
+
+  @override
+  int get hashCode =>
+      relaysPolled.hashCode ^
+      relaysResponded.hashCode ^
+      wrapsFetched.hashCode ^
+      duplicates.hashCode ^
+      failed.hashCode ^
+      newInvitations.hashCode ^
+      oneShotLocations.hashCode ^
+      rejoinRequests.hashCode;
+
+  @override
+  bool operator ==(Object other) =>
+      identical(this, other) ||
+      other is InboxSyncSummaryFfi &&
+          runtimeType == other.runtimeType &&
+          relaysPolled == other.relaysPolled &&
+          relaysResponded == other.relaysResponded &&
+          wrapsFetched == other.wrapsFetched &&
+          duplicates == other.duplicates &&
+          failed == other.failed &&
+          newInvitations == other.newInvitations &&
+          oneShotLocations == other.oneShotLocations &&
+          rejoinRequests == other.rejoinRequests;
+}
+
+/// Pending invitation to join a circle (FFI-friendly).
+class InvitationFfi {
+  /// MLS group ID.
+  final Uint8List mlsGroupId;
+
+  /// Circle name.
+  final String circleName;
+
+  /// Public key (hex) of who invited us.
+  final String inviterPubkey;
+
+  /// Number of members in the circle.
+  final int memberCount;
+
+  /// When we were invited (Unix timestamp).
+  final PlatformInt64 invitedAt;
+
+  /// The inviter's self-chosen name from the Welcome, if any. Unverified —
+  /// show it only as a suggested contact name.
+  final String? suggestedName;
+
+  const InvitationFfi({
+    required this.mlsGroupId,
+    required this.circleName,
+    required this.inviterPubkey,
+    required this.memberCount,
+    required this.invitedAt,
+    this.suggestedName,
+  });
+
+  @override
+  int get hashCode =>
+      mlsGroupId.hashCode ^
+      circleName.hashCode ^
+      inviterPubkey.hashCode ^
+      memberCount.hashCode ^
+      invitedAt.hashCode ^
+      suggestedName.hashCode;
+
+  @override
+  bool operator ==(Object other) =>
+      identical(this, other) ||
+      other is InvitationFfi &&
+          runtimeType == other.runtimeType &&
+          mlsGroupId == other.mlsGroupId &&
+          circleName == other.circleName &&
+          inviterPubkey == other.inviterPubkey &&
+          memberCount == other.memberCount &&
+          invitedAt == other.invitedAt &&
+          suggestedName == other.suggestedName;
+}
+
+/// A decoded invite link / QR payload — see
+/// [`haven_core::circle::invite_link`].
+class InvitePayloadFfi {
+  /// The key to invite, hex-encoded.
+  final String pubkeyHex;
+
+  /// The same key in NIP-19 bech32 (`npub1...`).
+  final String npub;
+
+  /// Relays the key's `KeyPackage` can be fetched from.
+  final List<String> relays;
+
+  /// The circle the invitation is about (its Nostr group id), if any.
+  final Uint8List? circleId;
+
+  const InvitePayloadFfi({
+    required this.pubkeyHex,
+    required this.npub,
+    required this.relays,
+    this.circleId,
+  });
+
+  @override
+  int get hashCode =>
+      pubkeyHex.hashCode ^ npub.hashCode ^ relays.hashCode ^ circleId.hashCode;
+
+  @override
+  bool operator ==(Object other) =>
+      identical(this, other) ||
+      other is InvitePayloadFfi &&
+          runtimeType == other.runtimeType &&
+          pubkeyHex == other.pubkeyHex &&
+          npub == other.npub &&
+          relays == other.relays &&
+          circleId == other.circleId;
+}
+
+/// What an M8-2 `KeyPackage` maintenance tick did (FFI mirror of
+/// [`haven_core::relay::maintenance::KpMaintenanceAction`]).
 ///
-/// Drives the Flutter-side leave state machine. See the core
-/// `LeavePlan` docs for the full flow per variant.
-enum LeavePlanKindFfi {
-  /// Caller is a non-admin — skip handoff/demotion, go to `propose_leave`.
-  nonAdmin,
+/// Fieldless / payload-free — no `d`, url, hex, or group id — so it is
+/// leak-free by construction (Security Rule 4/6).
+enum KpMaintenanceActionFfi {
+  /// A live-material canonical `KeyPackage` was already reachable — no change.
+  alreadyHealthy,
 
-  /// Caller is the sole admin — must promote `successor_hex` before
-  /// self-demoting and leaving.
-  adminHandoff,
+  /// A stable `d` was seeded from an on-relay canonical this tick; no publish.
+  seededD,
 
-  /// Caller is one of multiple admins — skip promotion and go directly
-  /// to `propose_self_demote` + `propose_leave`.
-  adminDemote,
+  /// A `KeyPackage` was (re)published into a reused, tracked/seeded stable `d`.
+  republishedStableD,
 
-  /// Caller is the sole remaining member — call
-  /// `abandon_circle_local_only` to wipe the local row.
-  abandon,
+  /// A `KeyPackage` was published into a freshly-minted `d` (first-ever slot).
+  republishedFreshD,
+}
 
-  /// MDK has no record of the group — call `complete_leave` to wipe the
-  /// orphaned local row.
-  orphanLocalOnly,
+/// Presence-only result of an M8-2 `KeyPackage` maintenance tick.
+///
+/// Counters + an action enum only — never a relay url, `d`, hex, or group id —
+/// so it is leak-free (Security Rule 4/6). This is the shape the Dart
+/// `MaintenanceScheduler` folds ticks into.
+class KpMaintenanceOutcomeFfi {
+  /// What the tick did.
+  final KpMaintenanceActionFfi action;
+
+  /// Own-relay canonical (kind 30443) events the probe observed (summed
+  /// across responders).
+  final int canonicalOnRelays;
+
+  /// Responding own relays the probe reached this tick (non-responders
+  /// excluded).
+  final int respondersProbed;
+
+  /// Responding + non-live relays this tick republished to.
+  final int relaysHealed;
+
+  /// Relay probes/publishes that errored (tallied, never fatal).
+  final int relayErrors;
+
+  const KpMaintenanceOutcomeFfi({
+    required this.action,
+    required this.canonicalOnRelays,
+    required this.respondersProbed,
+    required this.relaysHealed,
+    required this.relayErrors,
+  });
+
+  @override
+  int get hashCode =>
+      action.hashCode ^
+      canonicalOnRelays.hashCode ^
+      respondersProbed.hashCode ^
+      relaysHealed.hashCode ^
+      relayErrors.hashCode;
+
+  @override
+  bool operator ==(Object other) =>
+      identical(this, other) ||
+      other is KpMaintenanceOutcomeFfi &&
+          runtimeType == other.runtimeType &&
+          action == other.action &&
+          canonicalOnRelays == other.canonicalOnRelays &&
+          respondersProbed == other.respondersProbed &&
+          relaysHealed == other.relaysHealed &&
+          relayErrors == other.relayErrors;
 }
 
-/// Presence-only result of the one-time legacy KeyPackage retraction (F10a).
+/// Whether one relay serves the published `KeyPackage` (FFI mirror of
+/// [`haven_core::relay::maintenance::KpRelayStatus`]).
+enum KpRelayStatusFfi {
+  /// The relay returned the package.
+  served,
+
+  /// The relay answered but did not return the package.
+  missing,
+
+  /// The relay did not answer.
+  unreachable,
+}
+
+/// One relay's `KeyPackage` verification verdict.
+class KpRelayVerificationFfi {
+  /// The relay URL.
+  final String relayUrl;
+
+  /// Whether it serves the package.
+  final KpRelayStatusFfi status;
+
+  const KpRelayVerificationFfi({required this.relayUrl, required this.status});
+
+  @override
+  int get hashCode => relayUrl.hashCode ^ status.hashCode;
+
+  @override
+  bool operator ==(Object other) =>
+      identical(this, other) ||
+      other is KpRelayVerificationFfi &&
+          runtimeType == other.runtimeType &&
+          relayUrl == other.relayUrl &&
+          status == other.status;
+}
+
+/// The latest fetch-back verification of the published `KeyPackage`.
+class KpVerificationFfi {
+  /// Hex id of the verified kind-30443 event.
+  final String eventId;
+
+  /// Unix timestamp of the check.
+  final PlatformInt64 checkedAt;
+
+  /// Number of relays serving the package.
+  final int servedCount;
+
+  /// Whether at least one relay serves it (the user can be invited).
+  final bool reachable;
+
+  /// Per-relay verdicts.
+  final List<KpRelayVerificationFfi> relays;
+
+  const KpVerificationFfi({
+    required this.eventId,
+    required this.checkedAt,
+    required this.servedCount,
+    required this.reachable,
+    required this.relays,
+  });
+
+  @override
+  int get hashCode =>
+      eventId.hashCode ^
+      checkedAt.hashCode ^
+      servedCount.hashCode ^
+      reachable.hashCode ^
+      relays.hashCode;
+
+  @override
+  bool operator ==(Object other) =>
+      identical(this, other) ||
+      other is KpVerificationFfi &&
+          runtimeType == other.runtimeType &&
+          eventId == other.eventId &&
+          checkedAt == other.checkedAt &&
+          servedCount == other.servedCount &&
+          reachable == other.reachable &&
+          relays == other.relays;
+}
+
+/// Result of creating a circle from a contact label (FFI-friendly).
+class LabeledCircleCreationFfi {
+  /// The staged circle; publish and confirm as for `create_circle`.
+  final CircleCreationResultFfi creation;
+
+  /// Labeled contacts left out because no key package was found (hex).
+  final List<String> withoutKeyPackage;
+
+  const LabeledCircleCreationFfi({
+    required this.creation,
+    required this.withoutKeyPackage,
+  });
+
+  @override
+  int get hashCode => creation.hashCode ^ withoutKeyPackage.hashCode;
+
+  @override
+  bool operator ==(Object other) =>
+      identical(this, other) ||
+      other is LabeledCircleCreationFfi &&
+          runtimeType == other.runtimeType &&
+          creation == other.creation &&
+          withoutKeyPackage == other.withoutKeyPackage;
+}
+
+/// A persisted last-known location for a circle member (FFI-friendly).
 ///
-/// Counters only — no relay urls, event ids, or `d` values — so a derived
-/// `Debug` is leak-free (Security Rule 4/6).
-class LegacyRetractionOutcomeFfi {
-  /// `true` when the sentinel was already set (no work done this call).
-  final bool alreadyDone;
+/// Mirrors `haven_core::circle::LastKnownLocation`. Returned from
+/// `CircleManagerFfi::snapshot_last_known_for_circle` so the Flutter
+/// layer can hydrate its in-memory cache on app start.
+class LastKnownLocationFfi {
+  /// Nostr group ID (32 bytes) of the circle.
+  final Uint8List nostrGroupId;
+
+  /// Sender's Nostr public key (hex-encoded).
+  final String senderPubkey;
+
+  /// Latitude (exact GPS reading).
+  final double latitude;
+
+  /// Longitude (exact GPS reading).
+  final double longitude;
+
+  /// Geohash of the location.
+  final String geohash;
+
+  /// Display name carried in the encrypted location message, if any.
+  final String? displayName;
+
+  /// When the location was captured (Unix seconds).
+  final PlatformInt64 timestamp;
+
+  /// When the inner freshness window expires (Unix seconds).
+  final PlatformInt64 expiresAt;
+
+  /// Row must be deleted after this Unix-seconds moment.
+  final PlatformInt64 purgeAfter;
+
+  /// When this row was last written (Unix seconds, receiver clock).
+  final PlatformInt64 updatedAt;
+
+  const LastKnownLocationFfi({
+    required this.nostrGroupId,
+    required this.senderPubkey,
+    required this.latitude,
+    required this.longitude,
+    required this.geohash,
+    this.displayName,
+    required this.timestamp,
+    required this.expiresAt,
+    required this.purgeAfter,
+    required this.updatedAt,
+  });
+
+  @override
+  int get hashCode =>
+      nostrGroupId.hashCode ^
+      senderPubkey.hashCode ^
+      latitude.hashCode ^
+      longitude.hashCode ^
+      geohash.hashCode ^
+      displayName.hashCode ^
+      timestamp.hashCode ^
+      expiresAt.hashCode ^
+      purgeAfter.hashCode ^
+      updatedAt.hashCode;
+
+  @override
+  bool operator ==(Object other) =>
+      identical(this, other) ||
+      other is LastKnownLocationFfi &&
+          runtimeType == other.runtimeType &&
+          nostrGroupId == other.nostrGroupId &&
+          senderPubkey == other.senderPubkey &&
+          latitude == other.latitude &&
+          longitude == other.longitude &&
+          geohash == other.geohash &&
+          displayName == other.displayName &&
+          timestamp == other.timestamp &&
+          expiresAt == other.expiresAt &&
+          purgeAfter == other.purgeAfter &&
+          updatedAt == other.updatedAt;
+}
+
+/// FFI mirror of [`haven_core::circle::LeaveAllReport`].
+class LeaveAllReportFfi {
+  /// Circles left, with the leave proposal to publish now (it is also
+  /// queued in the outbox).
+  final List<LeaveEventFfi> left;
+
+  /// Circles dropped with nothing to publish.
+  final List<Uint8List> abandoned;
+
+  /// Circles that could not be left (e.g. admin circles); kept.
+  final List<Uint8List> notLeft;
+
+  /// Leave proposals newly queued in the offline outbox.
+  final int queued;
+
+  const LeaveAllReportFfi({
+    required this.left,
+    required this.abandoned,
+    required this.notLeft,
+    required this.queued,
+  });
+
+  @override
+  int get hashCode =>
+      left.hashCode ^ abandoned.hashCode ^ notLeft.hashCode ^ queued.hashCode;
+
+  @override
+  bool operator ==(Object other) =>
+      identical(this, other) ||
+      other is LeaveAllReportFfi &&
+          runtimeType == other.runtimeType &&
+          left == other.left &&
+          abandoned == other.abandoned &&
+          notLeft == other.notLeft &&
+          queued == other.queued;
+}
+
+/// A `SelfRemove` proposal to publish before the identity is gone.
+class LeaveEventFfi {
+  /// MLS group ID bytes of the circle being left.
+  final Uint8List mlsGroupId;
+
+  /// The signed kind:445 event JSON.
+  final String eventJson;
+
+  /// The circle's relays, to publish to.
+  final List<String> relays;
+
+  const LeaveEventFfi({
+    required this.mlsGroupId,
+    required this.eventJson,
+    required this.relays,
+  });
+
+  @override
+  int get hashCode =>
+      mlsGroupId.hashCode ^ eventJson.hashCode ^ relays.hashCode;
+
+  @override
+  bool operator ==(Object other) =>
+      identical(this, other) ||
+      other is LeaveEventFfi &&
+          runtimeType == other.runtimeType &&
+          mlsGroupId == other.mlsGroupId &&
+          eventJson == other.eventJson &&
+          relays == other.relays;
+}
+
+/// FFI mirror of [`haven_core::circle::LeavePlan`].
+///
+/// Struct shape (rather than tagged enum) avoids pulling in Dart `freezed`
+/// for sealed classes while preserving all the information.
+class LeavePlanFfi {
+  /// Which branch of the state machine to execute.
+  final LeavePlanKindFfi kind;
+
+  /// Hex-encoded successor pubkey when `kind == AdminHandoff`, else `None`.
+  final String? successorHex;
+
+  const LeavePlanFfi({required this.kind, this.successorHex});
+
+  @override
+  int get hashCode => kind.hashCode ^ successorHex.hashCode;
+
+  @override
+  bool operator ==(Object other) =>
+      identical(this, other) ||
+      other is LeavePlanFfi &&
+          runtimeType == other.runtimeType &&
+          kind == other.kind &&
+          successorHex == other.successorHex;
+}
+
+/// Discriminator for [`LeavePlanFfi`].
+///
+/// Drives the Flutter-side leave state machine. See the core
+/// `LeavePlan` docs for the full flow per variant.
+enum LeavePlanKindFfi {
+  /// Caller is a non-admin — skip handoff/demotion, go to `propose_leave`.
+  nonAdmin,
+
+  /// Caller is the sole admin — must promote `successor_hex` before
+  /// self-demoting and leaving.
+  adminHandoff,
+
+  /// Caller is one of multiple admins — skip promotion and go directly
+  /// to `propose_self_demote` + `propose_leave`.
+  adminDemote,
+
+  /// Caller is the sole remaining member — call
+  /// `abandon_circle_local_only` to wipe the local row.
+  abandon,
+
+  /// MDK has no record of the group — call `complete_leave` to wipe the
+  /// orphaned local row.
+  orphanLocalOnly,
+}
+
+/// Presence-only result of the one-time legacy KeyPackage retraction (F10a).
+///
+/// Counters only — no relay urls, event ids, or `d` values — so a derived
+/// `Debug` is leak-free (Security Rule 4/6).
+class LegacyRetractionOutcomeFfi {
+  /// `true` when the sentinel was already set (no work done this call).
+  final bool alreadyDone;
+
+  /// Stale legacy kind-443 KeyPackage twins scrubbed (kind-5 deletions ACKed).
+  final int legacy443Scrubbed;
+
+  /// `true` when the kind-10051 KeyPackage-relay list was retracted (≥1 ACK).
+  final bool relayListRetracted;
+
+  /// Relay probes / publishes that errored (tallied, never fatal).
+  final int relayErrors;
+
+  const LegacyRetractionOutcomeFfi({
+    required this.alreadyDone,
+    required this.legacy443Scrubbed,
+    required this.relayListRetracted,
+    required this.relayErrors,
+  });
+
+  static Future<LegacyRetractionOutcomeFfi> default_() =>
+      RustLib.instance.api.crateApiLegacyRetractionOutcomeFfiDefault();
+
+  @override
+  int get hashCode =>
+      alreadyDone.hashCode ^
+      legacy443Scrubbed.hashCode ^
+      relayListRetracted.hashCode ^
+      relayErrors.hashCode;
+
+  @override
+  bool operator ==(Object other) =>
+      identical(this, other) ||
+      other is LegacyRetractionOutcomeFfi &&
+          runtimeType == other.runtimeType &&
+          alreadyDone == other.alreadyDone &&
+          legacy443Scrubbed == other.legacy443Scrubbed &&
+          relayListRetracted == other.relayListRetracted &&
+          relayErrors == other.relayErrors;
+}
+
+/// One circle of a linked identity (FFI mirror of
+/// [`haven_core::device_link::LinkedCircle`]).
+class LinkedCircleFfi {
+  /// The circle's Nostr group ID (hex).
+  final String nostrGroupId;
+
+  /// The circle's display name on the old device.
+  final String displayName;
+
+  /// The circle's relays.
+  final List<String> relays;
+
+  /// Admin public keys (hex), who can re-add this device.
+  final List<String> admins;
+
+  const LinkedCircleFfi({
+    required this.nostrGroupId,
+    required this.displayName,
+    required this.relays,
+    required this.admins,
+  });
+
+  @override
+  int get hashCode =>
+      nostrGroupId.hashCode ^
+      displayName.hashCode ^
+      relays.hashCode ^
+      admins.hashCode;
+
+  @override
+  bool operator ==(Object other) =>
+      identical(this, other) ||
+      other is LinkedCircleFfi &&
+          runtimeType == other.runtimeType &&
+          nostrGroupId == other.nostrGroupId &&
+          displayName == other.displayName &&
+          relays == other.relays &&
+          admins == other.admins;
+}
+
+/// One folded engine [`haven_core::nostr::mls::types::LocationMessageResult`],
+/// FFI-friendly. A single `kind:445` ingest can yield SEVERAL of these (an
+/// engine `advance_convergence` may release buffered inbound after the outer
+/// event), so [`CircleManagerFfi::decrypt_location`] returns a `Vec` of them.
+///
+/// Cursor contract (DM-4b): the engine now owns out-of-order buffering, so the
+/// caller advances its relay sync cursor on the OUTER event's `created_at`
+/// (which Dart already holds — it passed `event_json` in), NOT per result.
+/// A buffered future-epoch event is re-surfaced by the engine once the gap
+/// fills; the caller never needs to re-fetch it.
+class LocationMessageResultFfi {
+  /// Which of the six outcomes this result is.
+  final LocationMessageResultKindFfi kind;
+
+  /// The decrypted location — `Some` only when `kind == Location` AND the
+  /// inner content parsed as a `LocationMessage`. A successfully-decrypted
+  /// but unparseable inner (e.g. a forward-incompatible payload) still yields
+  /// `kind == Location` with `location == None` (decrypt succeeded).
+  final DecryptedLocationFfi? location;
+
+  /// The MLS group id (raw bytes) this result belongs to — the LOCAL circle
+  /// handle (never published; Rule 4 keeps it off the wire). Present for
+  /// every variant so the caller can refresh / join / block / withdraw the
+  /// right circle.
+  final Uint8List mlsGroupId;
+
+  /// The MLS epoch the message was authenticated at — meaningful only for
+  /// `kind == Location` / `kind == Sos` (0 otherwise).
+  final BigInt epoch;
+
+  /// The sender's optional SOS note — `Some` only for `kind == Sos`.
+  final String? sosMessage;
+
+  /// Lowercase hex pubkey of the member asking — `Some` only for
+  /// `kind == CheckinRequest`.
+  final String? checkinRequesterPubkey;
+
+  /// Lowercase hex pubkey of the member being asked — `Some` only for
+  /// `kind == CheckinRequest`. Compare with the own pubkey to decide
+  /// whether to prompt.
+  final String? checkinTargetPubkey;
+
+  /// The precision change — `Some` only for `kind == PrecisionAnomaly`.
+  final PrecisionAnomalyFfi? precisionAnomaly;
+
+  /// The pin — `Some` only for `kind == MeetPin` when the payload parsed.
+  final MeetPinFfi? meetPin;
+
+  /// Who joined or left — `Some` only for `kind == GroupUpdate`.
+  final MembershipDeltaFfi? membership;
+
+  const LocationMessageResultFfi({
+    required this.kind,
+    this.location,
+    required this.mlsGroupId,
+    required this.epoch,
+    this.sosMessage,
+    this.checkinRequesterPubkey,
+    this.checkinTargetPubkey,
+    this.precisionAnomaly,
+    this.meetPin,
+    this.membership,
+  });
+
+  @override
+  int get hashCode =>
+      kind.hashCode ^
+      location.hashCode ^
+      mlsGroupId.hashCode ^
+      epoch.hashCode ^
+      sosMessage.hashCode ^
+      checkinRequesterPubkey.hashCode ^
+      checkinTargetPubkey.hashCode ^
+      precisionAnomaly.hashCode ^
+      meetPin.hashCode ^
+      membership.hashCode;
+
+  @override
+  bool operator ==(Object other) =>
+      identical(this, other) ||
+      other is LocationMessageResultFfi &&
+          runtimeType == other.runtimeType &&
+          kind == other.kind &&
+          location == other.location &&
+          mlsGroupId == other.mlsGroupId &&
+          epoch == other.epoch &&
+          sosMessage == other.sosMessage &&
+          checkinRequesterPubkey == other.checkinRequesterPubkey &&
+          checkinTargetPubkey == other.checkinTargetPubkey &&
+          precisionAnomaly == other.precisionAnomaly &&
+          meetPin == other.meetPin &&
+          membership == other.membership;
+}
+
+/// Discriminator for [`LocationMessageResultFfi`].
+///
+/// Mirrors the six
+/// [`haven_core::nostr::mls::types::LocationMessageResult`] variants 1:1
+/// (Dark Matter taxonomy). Unlike the pre-migration outcome, stale / duplicate
+/// / out-of-order handling is entirely engine-internal (the engine durably
+/// buffers a future-epoch event and re-surfaces it once the gap fills), so
+/// there is NO `Unprocessable` / `PreviouslyFailed` here. A struct-with-
+/// discriminant shape (rather than a tagged Rust enum) follows the existing
+/// [`LeavePlanFfi`] convention and avoids pulling Dart `freezed` into the
+/// generated bindings.
+enum LocationMessageResultKindFfi {
+  /// A decrypted application (location) message.
+  location,
+
+  /// The local client joined a group via an accepted welcome.
+  joined,
+
+  /// A durable, MLS-authenticated change to group state (membership, admin,
+  /// rename, retention) or an epoch advance the receiver should react to by
+  /// refreshing the circle's roster.
+  groupUpdate,
+
+  /// A previously-surfaced result was withdrawn because branch selection
+  /// superseded the commit that produced it — the caller must treat the
+  /// earlier change as if it never happened.
+  invalidated,
+
+  /// The group entered the unrecoverable state; the UI MUST block
+  /// send/mutate for it (Rule 8, blocked-group state).
+  unrecoverable,
+
+  /// A decrypted emergency (SOS) broadcast. `location` carries the sender's
+  /// exact position and `sos_message` the optional note.
+  sos,
+
+  /// A decrypted check-in request: `checkin_requester_pubkey` asks
+  /// `checkin_target_pubkey` to share their location. The target's app
+  /// answers with [`CircleManagerFfi::respond_to_checkin`].
+  checkinRequest,
+
+  /// A sender's location was finer than the precision they had been
+  /// sharing at; details in `precision_anomaly`. Always follows the
+  /// `Location` that triggered it.
+  precisionAnomaly,
+
+  /// A still-live meet pin (an exact meeting point, not the sender's
+  /// position); details in `meet_pin`. Already stored; list with
+  /// [`CircleManagerFfi::list_meet_pins`].
+  meetPin,
+}
+
+/// Location sharing precision (FFI mirror of
+/// [`haven_core::location::LocationPrecision`]).
+enum LocationPrecisionFfi {
+  /// Rounded to ~1 km.
+  coarse,
+
+  /// Rounded to ~10 m.
+  approximate,
+
+  /// The raw GPS fix.
+  exact,
+}
+
+/// Signed NIP-09 deletion of a published location (FFI-friendly).
+class LocationRetractionFfi {
+  /// The deletion event's id (64-char lowercase hex).
+  final String eventId;
+
+  /// The id of the location it deletes (64-char lowercase hex).
+  final String retractedEventId;
+
+  /// JSON-serialized signed Nostr event (kind 5).
+  final String eventJson;
+
+  /// Relay URLs to publish to (the circle's relays).
+  final List<String> relays;
+
+  const LocationRetractionFfi({
+    required this.eventId,
+    required this.retractedEventId,
+    required this.eventJson,
+    required this.relays,
+  });
+
+  @override
+  int get hashCode =>
+      eventId.hashCode ^
+      retractedEventId.hashCode ^
+      eventJson.hashCode ^
+      relays.hashCode;
+
+  @override
+  bool operator ==(Object other) =>
+      identical(this, other) ||
+      other is LocationRetractionFfi &&
+          runtimeType == other.runtimeType &&
+          eventId == other.eventId &&
+          retractedEventId == other.retractedEventId &&
+          eventJson == other.eventJson &&
+          relays == other.relays;
+}
+
+/// A time-limited meet pin (see `haven_core::meet`): an exact meeting point
+/// dropped by `sender_pubkey`, gone once `expires_at` passes.
+class MeetPinFfi {
+  /// Random pin id (hex).
+  final String pinId;
+
+  /// Lowercase hex pubkey of the member who dropped the pin.
+  final String senderPubkey;
+
+  /// Exact latitude of the meeting point.
+  final double latitude;
+
+  /// Exact longitude of the meeting point.
+  final double longitude;
+
+  /// Optional short label.
+  final String? label;
+
+  /// Unix timestamp the pin was dropped.
+  final PlatformInt64 createdAt;
+
+  /// Unix timestamp the pin lapses.
+  final PlatformInt64 expiresAt;
+
+  const MeetPinFfi({
+    required this.pinId,
+    required this.senderPubkey,
+    required this.latitude,
+    required this.longitude,
+    this.label,
+    required this.createdAt,
+    required this.expiresAt,
+  });
+
+  @override
+  int get hashCode =>
+      pinId.hashCode ^
+      senderPubkey.hashCode ^
+      latitude.hashCode ^
+      longitude.hashCode ^
+      label.hashCode ^
+      createdAt.hashCode ^
+      expiresAt.hashCode;
+
+  @override
+  bool operator ==(Object other) =>
+      identical(this, other) ||
+      other is MeetPinFfi &&
+          runtimeType == other.runtimeType &&
+          pinId == other.pinId &&
+          senderPubkey == other.senderPubkey &&
+          latitude == other.latitude &&
+          longitude == other.longitude &&
+          label == other.label &&
+          createdAt == other.createdAt &&
+          expiresAt == other.expiresAt;
+}
+
+/// A member whose MLS signature key changed (see
+/// `haven_core::circle::key_audit`), for the user to review.
+class MemberKeyChangeFfi {
+  /// Id to pass to [`CircleManagerFfi::acknowledge_member_key_change`].
+  final PlatformInt64 id;
+
+  /// Lowercase hex pubkey of the member.
+  final String memberPubkey;
+
+  /// Circle the new key was observed in, if any.
+  final Uint8List? mlsGroupId;
+
+  /// Fingerprint (hex SHA-256) of the previously recorded key.
+  final String previousFingerprint;
+
+  /// Fingerprint (hex SHA-256) of the newly observed key.
+  final String observedFingerprint;
+
+  /// Unix timestamp of the observation.
+  final PlatformInt64 observedAt;
+
+  /// Unix timestamp the user acknowledged the change, if they have.
+  final PlatformInt64? acknowledgedAt;
+
+  /// Notification text for the change.
+  final UserMessageFfi notification;
+
+  const MemberKeyChangeFfi({
+    required this.id,
+    required this.memberPubkey,
+    this.mlsGroupId,
+    required this.previousFingerprint,
+    required this.observedFingerprint,
+    required this.observedAt,
+    this.acknowledgedAt,
+    required this.notification,
+  });
+
+  @override
+  int get hashCode =>
+      id.hashCode ^
+      memberPubkey.hashCode ^
+      mlsGroupId.hashCode ^
+      previousFingerprint.hashCode ^
+      observedFingerprint.hashCode ^
+      observedAt.hashCode ^
+      acknowledgedAt.hashCode ^
+      notification.hashCode;
+
+  @override
+  bool operator ==(Object other) =>
+      identical(this, other) ||
+      other is MemberKeyChangeFfi &&
+          runtimeType == other.runtimeType &&
+          id == other.id &&
+          memberPubkey == other.memberPubkey &&
+          mlsGroupId == other.mlsGroupId &&
+          previousFingerprint == other.previousFingerprint &&
+          observedFingerprint == other.observedFingerprint &&
+          observedAt == other.observedAt &&
+          acknowledgedAt == other.acknowledgedAt &&
+          notification == other.notification;
+}
+
+/// Key package bundle for publishing (FFI-friendly).
+///
+/// A member's key package with their inbox relay list (FFI-friendly).
+///
+/// Used when adding members to a circle. Relay resolution follows a
+/// cascading fallback: inbox (kind 10050) → NIP-65 (kind 10002) → defaults.
+class MemberKeyPackageFfi {
+  /// The key package event JSON (kind 30443 or legacy kind 443).
+  final String keyPackageJson;
+
+  /// Relay URLs from the member's inbox relay list (kind 10050).
+  final List<String> inboxRelays;
+
+  /// Fallback relay URLs from NIP-65 relay list (kind 10002).
+  final List<String> nip65Relays;
+
+  const MemberKeyPackageFfi({
+    required this.keyPackageJson,
+    required this.inboxRelays,
+    required this.nip65Relays,
+  });
+
+  @override
+  int get hashCode =>
+      keyPackageJson.hashCode ^ inboxRelays.hashCode ^ nip65Relays.hashCode;
+
+  @override
+  bool operator ==(Object other) =>
+      identical(this, other) ||
+      other is MemberKeyPackageFfi &&
+          runtimeType == other.runtimeType &&
+          keyPackageJson == other.keyPackageJson &&
+          inboxRelays == other.inboxRelays &&
+          nip65Relays == other.nip65Relays;
+}
+
+/// A member's newest location and what to show for it (FFI mirror of
+/// `haven_core::circle::MemberLocation`).
+class MemberLocationFfi {
+  /// The newest stored location, however old.
+  final LastKnownLocationFfi latest;
+
+  /// `latest` while within the circle's display threshold, `None` once it
+  /// is older. Draw this one.
+  final LastKnownLocationFfi? effectiveDisplayLocation;
+
+  const MemberLocationFfi({
+    required this.latest,
+    this.effectiveDisplayLocation,
+  });
+
+  @override
+  int get hashCode => latest.hashCode ^ effectiveDisplayLocation.hashCode;
+
+  @override
+  bool operator ==(Object other) =>
+      identical(this, other) ||
+      other is MemberLocationFfi &&
+          runtimeType == other.runtimeType &&
+          latest == other.latest &&
+          effectiveDisplayLocation == other.effectiveDisplayLocation;
+}
+
+/// When a circle member was last heard from (mirrors
+/// `haven_core::circle::MemberPresence`). Any decrypted message counts, not
+/// only locations; commits do not.
+class MemberPresenceFfi {
+  /// The member's public key (hex).
+  final String pubkey;
+
+  /// Send time of the member's latest message (Unix seconds).
+  final PlatformInt64? lastSeen;
+
+  /// Send time of the member's latest location or SOS (Unix seconds).
+  final PlatformInt64? lastLocationAt;
+
+  /// `last_seen` classified at the time of the query.
+  final PresenceStatusFfi status;
+
+  const MemberPresenceFfi({
+    required this.pubkey,
+    this.lastSeen,
+    this.lastLocationAt,
+    required this.status,
+  });
+
+  @override
+  int get hashCode =>
+      pubkey.hashCode ^
+      lastSeen.hashCode ^
+      lastLocationAt.hashCode ^
+      status.hashCode;
+
+  @override
+  bool operator ==(Object other) =>
+      identical(this, other) ||
+      other is MemberPresenceFfi &&
+          runtimeType == other.runtimeType &&
+          pubkey == other.pubkey &&
+          lastSeen == other.lastSeen &&
+          lastLocationAt == other.lastLocationAt &&
+          status == other.status;
+}
+
+/// Who joined or left a circle (FFI mirror of
+/// [`haven_core::nostr::mls::MembershipDelta`]).
+class MembershipDeltaFfi {
+  /// Lowercase hex pubkeys of members who joined.
+  final List<String> added;
+
+  /// Lowercase hex pubkeys of members who left.
+  final List<String> removed;
+
+  const MembershipDeltaFfi({required this.added, required this.removed});
+
+  static Future<MembershipDeltaFfi> default_() =>
+      RustLib.instance.api.crateApiMembershipDeltaFfiDefault();
+
+  @override
+  int get hashCode => added.hashCode ^ removed.hashCode;
+
+  @override
+  bool operator ==(Object other) =>
+      identical(this, other) ||
+      other is MembershipDeltaFfi &&
+          runtimeType == other.runtimeType &&
+          added == other.added &&
+          removed == other.removed;
+}
+
+/// One named parameter of a [`UserMessageFfi`].
+class MessageParamFfi {
+  /// Placeholder name (`{name}` in the English template).
+  final String name;
+
+  /// Value to substitute.
+  final String value;
+
+  const MessageParamFfi({required this.name, required this.value});
+
+  @override
+  int get hashCode => name.hashCode ^ value.hashCode;
+
+  @override
+  bool operator ==(Object other) =>
+      identical(this, other) ||
+      other is MessageParamFfi &&
+          runtimeType == other.runtimeType &&
+          name == other.name &&
+          value == other.value;
+}
+
+/// A deadline that passed without the member checking in (FFI mirror of
+/// `haven_core::safety::MissedCheckin`).
+class MissedCheckinFfi {
+  /// The rule that raised it.
+  final String ruleId;
+
+  /// The circle's MLS group ID.
+  final Uint8List mlsGroupId;
+
+  /// The member who did not check in (hex).
+  final String memberPubkey;
+
+  /// The missed deadline (Unix timestamp); pass it back to dismiss.
+  final PlatformInt64 deadlineAt;
+
+  /// When the member's latest location was taken, if any was seen.
+  final PlatformInt64? lastSeenAt;
+
+  const MissedCheckinFfi({
+    required this.ruleId,
+    required this.mlsGroupId,
+    required this.memberPubkey,
+    required this.deadlineAt,
+    this.lastSeenAt,
+  });
+
+  @override
+  int get hashCode =>
+      ruleId.hashCode ^
+      mlsGroupId.hashCode ^
+      memberPubkey.hashCode ^
+      deadlineAt.hashCode ^
+      lastSeenAt.hashCode;
+
+  @override
+  bool operator ==(Object other) =>
+      identical(this, other) ||
+      other is MissedCheckinFfi &&
+          runtimeType == other.runtimeType &&
+          ruleId == other.ruleId &&
+          mlsGroupId == other.mlsGroupId &&
+          memberPubkey == other.memberPubkey &&
+          deadlineAt == other.deadlineAt &&
+          lastSeenAt == other.lastSeenAt;
+}
+
+/// Sender's device motion state (FFI mirror of
+/// [`haven_core::location::MotionState`]).
+enum MotionStateFfi {
+  /// Not moving.
+  stationary,
+
+  /// Walking.
+  walking,
+
+  /// Running.
+  running,
+
+  /// Cycling.
+  cycling,
+
+  /// In a vehicle.
+  driving,
+
+  /// A state this version does not know.
+  unknown,
+}
+
+/// A resolved NIP-05 identifier (FFI mirror of
+/// [`haven_core::profile::Nip05Resolution`]).
+class Nip05ResolutionFfi {
+  /// The identifier, normalized (`name@domain`).
+  final String identifier;
+
+  /// Public key as 64-character hex string.
+  final String pubkeyHex;
+
+  /// Public key in NIP-19 bech32 format (npub1...).
+  final String npub;
+
+  /// `wss://` relay hints for the key.
+  final List<String> relays;
+
+  /// `true` if the domain was contacted for this lookup and so saw the
+  /// device's IP address; show the user a notice.
+  final bool privacyWarning;
+
+  const Nip05ResolutionFfi({
+    required this.identifier,
+    required this.pubkeyHex,
+    required this.npub,
+    required this.relays,
+    required this.privacyWarning,
+  });
+
+  @override
+  int get hashCode =>
+      identifier.hashCode ^
+      pubkeyHex.hashCode ^
+      npub.hashCode ^
+      relays.hashCode ^
+      privacyWarning.hashCode;
+
+  @override
+  bool operator ==(Object other) =>
+      identical(this, other) ||
+      other is Nip05ResolutionFfi &&
+          runtimeType == other.runtimeType &&
+          identifier == other.identifier &&
+          pubkeyHex == other.pubkeyHex &&
+          npub == other.npub &&
+          relays == other.relays &&
+          privacyWarning == other.privacyWarning;
+}
+
+/// A decoded NIP-19 `nprofile` (FFI-friendly).
+class NprofileFfi {
+  /// Public key as 64-character hex string.
+  final String pubkeyHex;
+
+  /// Public key in NIP-19 bech32 format (npub1...).
+  final String npub;
+
+  /// Relay hints carried by the nprofile.
+  final List<String> relays;
+
+  const NprofileFfi({
+    required this.pubkeyHex,
+    required this.npub,
+    required this.relays,
+  });
+
+  @override
+  int get hashCode => pubkeyHex.hashCode ^ npub.hashCode ^ relays.hashCode;
+
+  @override
+  bool operator ==(Object other) =>
+      identical(this, other) ||
+      other is NprofileFfi &&
+          runtimeType == other.runtimeType &&
+          pubkeyHex == other.pubkeyHex &&
+          npub == other.npub &&
+          relays == other.relays;
+}
+
+/// A location a contact sent once, outside any circle (FFI mirror of
+/// [`haven_core::one_shot::OneShotLocation`]).
+class OneShotLocationFfi {
+  /// The location and its sender.
+  final DecryptedLocationFfi location;
+
+  /// Hex id of the gift wrap it arrived in.
+  final String wrapperEventId;
+
+  /// Unix timestamp after which it must no longer be shown.
+  final PlatformInt64 expiresAt;
+
+  const OneShotLocationFfi({
+    required this.location,
+    required this.wrapperEventId,
+    required this.expiresAt,
+  });
+
+  @override
+  int get hashCode =>
+      location.hashCode ^ wrapperEventId.hashCode ^ expiresAt.hashCode;
+
+  @override
+  bool operator ==(Object other) =>
+      identical(this, other) ||
+      other is OneShotLocationFfi &&
+          runtimeType == other.runtimeType &&
+          location == other.location &&
+          wrapperEventId == other.wrapperEventId &&
+          expiresAt == other.expiresAt;
+}
+
+/// One offline-outbox entry (FFI-friendly).
+class OutboxEntryFfi {
+  /// The queued event's ID (64-char hex).
+  final String eventId;
+
+  /// Event kind.
+  final int kind;
+
+  /// Relays the event is sent to.
+  final List<String> relays;
+
+  /// Delivery priority: "sos", "live_share", "location", or
+  /// "housekeeping".
+  final String priority;
+
+  /// Delivery state: "pending", "sent", "expired", "abandoned", or
+  /// "dropped" (location sharing was paused).
+  final String status;
+
+  /// Failed sends so far, including the original publish.
+  final int attempts;
+
+  /// Next scheduled retry (Unix timestamp), meaningful while pending.
+  final PlatformInt64 nextAttemptAt;
+
+  /// Unix timestamp after which the event is no longer sent.
+  final PlatformInt64 expiresAt;
+
+  /// Redacted reason of the last failure, if any.
+  final String? lastError;
+
+  /// Unix timestamp the event was queued.
+  final PlatformInt64 createdAt;
+
+  const OutboxEntryFfi({
+    required this.eventId,
+    required this.kind,
+    required this.relays,
+    required this.priority,
+    required this.status,
+    required this.attempts,
+    required this.nextAttemptAt,
+    required this.expiresAt,
+    this.lastError,
+    required this.createdAt,
+  });
+
+  @override
+  int get hashCode =>
+      eventId.hashCode ^
+      kind.hashCode ^
+      relays.hashCode ^
+      priority.hashCode ^
+      status.hashCode ^
+      attempts.hashCode ^
+      nextAttemptAt.hashCode ^
+      expiresAt.hashCode ^
+      lastError.hashCode ^
+      createdAt.hashCode;
+
+  @override
+  bool operator ==(Object other) =>
+      identical(this, other) ||
+      other is OutboxEntryFfi &&
+          runtimeType == other.runtimeType &&
+          eventId == other.eventId &&
+          kind == other.kind &&
+          relays == other.relays &&
+          priority == other.priority &&
+          status == other.status &&
+          attempts == other.attempts &&
+          nextAttemptAt == other.nextAttemptAt &&
+          expiresAt == other.expiresAt &&
+          lastError == other.lastError &&
+          createdAt == other.createdAt;
+}
 
-  /// Stale legacy kind-443 KeyPackage twins scrubbed (kind-5 deletions ACKed).
-  final int legacy443Scrubbed;
+/// Counts from one outbox retry pass (FFI-friendly).
+class OutboxFlushFfi {
+  /// Events a relay accepted.
+  final int sent;
 
-  /// `true` when the kind-10051 KeyPackage-relay list was retracted (≥1 ACK).
-  final bool relayListRetracted;
+  /// Events that failed again and were rescheduled.
+  final int rescheduled;
 
-  /// Relay probes / publishes that errored (tallied, never fatal).
-  final int relayErrors;
+  /// Events dropped because they expired.
+  final int expired;
 
-  const LegacyRetractionOutcomeFfi({
-    required this.alreadyDone,
-    required this.legacy443Scrubbed,
-    required this.relayListRetracted,
-    required this.relayErrors,
+  /// Events abandoned after too many failures.
+  final int abandoned;
+
+  const OutboxFlushFfi({
+    required this.sent,
+    required this.rescheduled,
+    required this.expired,
+    required this.abandoned,
   });
 
-  static Future<LegacyRetractionOutcomeFfi> default_() =>
-      RustLib.instance.api.crateApiLegacyRetractionOutcomeFfiDefault();
+  static Future<OutboxFlushFfi> default_() =>
+      RustLib.instance.api.crateApiOutboxFlushFfiDefault();
 
   @override
   int get hashCode =>
-      alreadyDone.hashCode ^
-      legacy443Scrubbed.hashCode ^
-      relayListRetracted.hashCode ^
-      relayErrors.hashCode;
+      sent.hashCode ^
+      rescheduled.hashCode ^
+      expired.hashCode ^
+      abandoned.hashCode;
 
   @override
   bool operator ==(Object other) =>
       identical(this, other) ||
-      other is LegacyRetractionOutcomeFfi &&
+      other is OutboxFlushFfi &&
           runtimeType == other.runtimeType &&
-          alreadyDone == other.alreadyDone &&
-          legacy443Scrubbed == other.legacy443Scrubbed &&
-          relayListRetracted == other.relayListRetracted &&
-          relayErrors == other.relayErrors;
+          sent == other.sent &&
+          rescheduled == other.rescheduled &&
+          expired == other.expired &&
+          abandoned == other.abandoned;
 }
 
-/// One folded engine [`haven_core::nostr::mls::types::LocationMessageResult`],
-/// FFI-friendly. A single `kind:445` ingest can yield SEVERAL of these (an
-/// engine `advance_convergence` may release buffered inbound after the outer
-/// event), so [`CircleManagerFfi::decrypt_location`] returns a `Vec` of them.
+/// A publish-before-apply token (FFI mirror of `PendingStateRef`).
 ///
-/// Cursor contract (DM-4b): the engine now owns out-of-order buffering, so the
-/// caller advances its relay sync cursor on the OUTER event's `created_at`
-/// (which Dart already holds — it passed `event_json` in), NOT per result.
-/// A buffered future-epoch event is re-surfaced by the engine once the gap
-/// fills; the caller never needs to re-fetch it.
-class LocationMessageResultFfi {
-  /// Which of the five outcomes this result is.
-  final LocationMessageResultKindFfi kind;
+/// The Dark Matter engine stages a group-evolving commit and returns an opaque
+/// `PendingStateRef` (a `u64` newtype). Haven publishes the commit (and, for an
+/// Add, the welcomes), and ONLY after ≥1 relay returns OK confirms the token via
+/// [`CircleManagerFfi::confirm_published`] so the engine applies the commit and
+/// advances the epoch (Rule 13, publish-before-apply). On publish FAILURE the
+/// token is rolled back via [`CircleManagerFfi::publish_failed`]. The `token` is
+/// an in-memory session handle — meaningless across a process restart, never
+/// persisted, never published.
+class PendingStateRefFfi {
+  /// The opaque engine token (a `PendingStateRef` `u64`).
+  final BigInt token;
 
-  /// The decrypted location — `Some` only when `kind == Location` AND the
-  /// inner content parsed as a `LocationMessage`. A successfully-decrypted
-  /// but unparseable inner (e.g. a forward-incompatible payload) still yields
-  /// `kind == Location` with `location == None` (decrypt succeeded).
-  final DecryptedLocationFfi? location;
+  const PendingStateRefFfi({required this.token});
 
-  /// The MLS group id (raw bytes) this result belongs to — the LOCAL circle
-  /// handle (never published; Rule 4 keeps it off the wire). Present for
-  /// every variant so the caller can refresh / join / block / withdraw the
-  /// right circle.
-  final Uint8List mlsGroupId;
+  @override
+  int get hashCode => token.hashCode;
 
-  /// The MLS epoch the message was authenticated at — meaningful only for
-  /// `kind == Location` (0 otherwise).
-  final BigInt epoch;
+  @override
+  bool operator ==(Object other) =>
+      identical(this, other) ||
+      other is PendingStateRefFfi &&
+          runtimeType == other.runtimeType &&
+          token == other.token;
+}
 
-  const LocationMessageResultFfi({
-    required this.kind,
-    this.location,
-    required this.mlsGroupId,
-    required this.epoch,
+/// A sender started sharing finer locations than their baseline in a circle.
+class PrecisionAnomalyFfi {
+  /// Lowercase hex pubkey of the sender.
+  final String senderPubkey;
+
+  /// The precision the sender had established.
+  final PrecisionClassFfi baseline;
+
+  /// The finer precision just received.
+  final PrecisionClassFfi observed;
+
+  const PrecisionAnomalyFfi({
+    required this.senderPubkey,
+    required this.baseline,
+    required this.observed,
   });
 
   @override
   int get hashCode =>
-      kind.hashCode ^ location.hashCode ^ mlsGroupId.hashCode ^ epoch.hashCode;
+      senderPubkey.hashCode ^ baseline.hashCode ^ observed.hashCode;
 
   @override
   bool operator ==(Object other) =>
       identical(this, other) ||
-      other is LocationMessageResultFfi &&
+      other is PrecisionAnomalyFfi &&
           runtimeType == other.runtimeType &&
-          kind == other.kind &&
-          location == other.location &&
-          mlsGroupId == other.mlsGroupId &&
-          epoch == other.epoch;
+          senderPubkey == other.senderPubkey &&
+          baseline == other.baseline &&
+          observed == other.observed;
 }
 
-/// Discriminator for [`LocationMessageResultFfi`].
-///
-/// Mirrors the five
-/// [`haven_core::nostr::mls::types::LocationMessageResult`] variants 1:1
-/// (Dark Matter taxonomy). Unlike the pre-migration outcome, stale / duplicate
-/// / out-of-order handling is entirely engine-internal (the engine durably
-/// buffers a future-epoch event and re-surfaces it once the gap fills), so
-/// there is NO `Unprocessable` / `PreviouslyFailed` here. A struct-with-
-/// discriminant shape (rather than a tagged Rust enum) follows the existing
-/// [`LeavePlanFfi`] convention and avoids pulling Dart `freezed` into the
-/// generated bindings.
-enum LocationMessageResultKindFfi {
-  /// A decrypted application (location) message.
-  location,
+/// Mirrors `haven_core::location::PrecisionClass`, coarsest first.
+enum PrecisionClassFfi {
+  /// At most 2 decimal places (~1 km or coarser).
+  coarse,
 
-  /// The local client joined a group via an accepted welcome.
-  joined,
+  /// 3–4 decimal places (~10–100 m).
+  approximate,
 
-  /// A durable, MLS-authenticated change to group state (membership, admin,
-  /// rename, retention) or an epoch advance the receiver should react to by
-  /// refreshing the circle's roster.
-  groupUpdate,
+  /// 5 or more decimal places: a raw GPS fix.
+  exact,
+}
 
-  /// A previously-surfaced result was withdrawn because branch selection
-  /// superseded the commit that produced it — the caller must treat the
-  /// earlier change as if it never happened.
-  invalidated,
+/// How recently a member was heard from (mirrors
+/// `haven_core::circle::PresenceStatus`).
+enum PresenceStatusFfi {
+  /// A message within the last 30 minutes.
+  active,
 
-  /// The group entered the unrecoverable state; the UI MUST block
-  /// send/mutate for it (Rule 8, blocked-group state).
-  unrecoverable,
+  /// Quiet for longer, but heard from within 6 hours.
+  stale,
+
+  /// Quiet for longer than that.
+  offline,
+
+  /// Never heard from on this device.
+  unknown,
 }
 
-/// Key package bundle for publishing (FFI-friendly).
-///
-/// A member's key package with their inbox relay list (FFI-friendly).
+/// Mirrors `haven_core::privacy::PrivacyFacts`, flattened for FFI.
 ///
-/// Used when adding members to a circle. Relay resolution follows a
-/// cascading fallback: inbox (kind 10050) → NIP-65 (kind 10002) → defaults.
-class MemberKeyPackageFfi {
-  /// The key package event JSON (kind 30443 or legacy kind 443).
-  final String keyPackageJson;
+/// Every value is read from the enforcing configuration at call time; the
+/// privacy dashboard renders this instead of hardcoded copy.
+class PrivacyFactsFfi {
+  /// Schema version of the facts.
+  final int version;
 
-  /// Relay URLs from the member's inbox relay list (kind 10050).
-  final List<String> inboxRelays;
+  /// How long a received location is shown as "fresh", in seconds.
+  final PlatformInt64 locationFreshnessSecs;
 
-  /// Fallback relay URLs from NIP-65 relay list (kind 10002).
-  final List<String> nip65Relays;
+  /// How long a received location is kept on this device, in seconds.
+  final BigInt locationLocalRetentionSecs;
 
-  const MemberKeyPackageFfi({
-    required this.keyPackageJson,
+  /// NIP-40 expiration requested from relays for location events, in seconds.
+  final BigInt relayLocationRetentionSecs;
+
+  /// How long processed invitation ids are kept, in seconds.
+  final PlatformInt64 processedInvitationRetentionSecs;
+
+  /// Coordinate precision sent to members (e.g. `"exact_gps"`).
+  final String sharedPrecision;
+
+  /// Geohash length carried alongside the coordinates.
+  final int geohashPrecision;
+
+  /// Location metadata that never leaves the device.
+  final List<String> strippedLocationFields;
+
+  /// Whether location content is end-to-end encrypted.
+  final bool endToEndEncrypted;
+
+  /// Whether the local circle database is encrypted at rest.
+  final bool localDatabaseEncrypted;
+
+  /// How relay connections are routed (e.g. `"direct"`).
+  final String networkRouting;
+
+  /// Inbox relays (kind 10050).
+  final List<String> inboxRelays;
+
+  /// Key-package relays (kind 10051).
+  final List<String> keyPackageRelays;
+
+  /// Distinct relays used by the user's circles.
+  final List<String> circleRelays;
+
+  const PrivacyFactsFfi({
+    required this.version,
+    required this.locationFreshnessSecs,
+    required this.locationLocalRetentionSecs,
+    required this.relayLocationRetentionSecs,
+    required this.processedInvitationRetentionSecs,
+    required this.sharedPrecision,
+    required this.geohashPrecision,
+    required this.strippedLocationFields,
+    required this.endToEndEncrypted,
+    required this.localDatabaseEncrypted,
+    required this.networkRouting,
     required this.inboxRelays,
-    required this.nip65Relays,
+    required this.keyPackageRelays,
+    required this.circleRelays,
   });
 
   @override
   int get hashCode =>
-      keyPackageJson.hashCode ^ inboxRelays.hashCode ^ nip65Relays.hashCode;
+      version.hashCode ^
+      locationFreshnessSecs.hashCode ^
+      locationLocalRetentionSecs.hashCode ^
+      relayLocationRetentionSecs.hashCode ^
+      processedInvitationRetentionSecs.hashCode ^
+      sharedPrecision.hashCode ^
+      geohashPrecision.hashCode ^
+      strippedLocationFields.hashCode ^
+      endToEndEncrypted.hashCode ^
+      localDatabaseEncrypted.hashCode ^
+      networkRouting.hashCode ^
+      inboxRelays.hashCode ^
+      keyPackageRelays.hashCode ^
+      circleRelays.hashCode;
 
   @override
   bool operator ==(Object other) =>
       identical(this, other) ||
-      other is MemberKeyPackageFfi &&
+      other is PrivacyFactsFfi &&
           runtimeType == other.runtimeType &&
-          keyPackageJson == other.keyPackageJson &&
+          version == other.version &&
+          locationFreshnessSecs == other.locationFreshnessSecs &&
+          locationLocalRetentionSecs == other.locationLocalRetentionSecs &&
+          relayLocationRetentionSecs == other.relayLocationRetentionSecs &&
+          processedInvitationRetentionSecs == other.processedInvitationRetentionSecs &&
+          sharedPrecision == other.sharedPrecision &&
+          geohashPrecision == other.geohashPrecision &&
+          strippedLocationFields == other.strippedLocationFields &&
+          endToEndEncrypted == other.endToEndEncrypted &&
+          localDatabaseEncrypted == other.localDatabaseEncrypted &&
+          networkRouting == other.networkRouting &&
           inboxRelays == other.inboxRelays &&
-          nip65Relays == other.nip65Relays;
+          keyPackageRelays == other.keyPackageRelays &&
+          circleRelays == other.circleRelays;
 }
 
-/// A publish-before-apply token (FFI mirror of `PendingStateRef`).
-///
-/// The Dark Matter engine stages a group-evolving commit and returns an opaque
-/// `PendingStateRef` (a `u64` newtype). Haven publishes the commit (and, for an
-/// Add, the welcomes), and ONLY after ≥1 relay returns OK confirms the token via
-/// [`CircleManagerFfi::confirm_published`] so the engine applies the commit and
-/// advances the epoch (Rule 13, publish-before-apply). On publish FAILURE the
-/// token is rolled back via [`CircleManagerFfi::publish_failed`]. The `token` is
-/// an in-memory session handle — meaningless across a process restart, never
-/// persisted, never published.
-class PendingStateRefFfi {
-  /// The opaque engine token (a `PendingStateRef` `u64`).
-  final BigInt token;
+/// What published events may reveal (FFI mirror of
+/// [`haven_core::privacy::PrivacySettings`]).
+class PrivacySettingsFfi {
+  /// Randomly move the time on published events by up to this many
+  /// minutes either way (0–10, `0` = off).
+  final int timestampFuzzMinutes;
 
-  const PendingStateRefFfi({required this.token});
+  /// Dummy events per hour mixed in across all circles (0–12, `0` = off).
+  final int coverTrafficPerHour;
+
+  const PrivacySettingsFfi({
+    required this.timestampFuzzMinutes,
+    required this.coverTrafficPerHour,
+  });
 
   @override
-  int get hashCode => token.hashCode;
+  int get hashCode =>
+      timestampFuzzMinutes.hashCode ^ coverTrafficPerHour.hashCode;
 
   @override
   bool operator ==(Object other) =>
       identical(this, other) ||
-      other is PendingStateRefFfi &&
+      other is PrivacySettingsFfi &&
           runtimeType == other.runtimeType &&
-          token == other.token;
+          timestampFuzzMinutes == other.timestampFuzzMinutes &&
+          coverTrafficPerHour == other.coverTrafficPerHour;
 }
 
 /// A member's public Nostr profile (kind-0 metadata), FFI-friendly.
@@ -3142,6 +7244,189 @@ class ProfilePictureRefFfi {
           sha256Hex == other.sha256Hex;
 }
 
+/// A member who just came within a proximity rule's range (FFI mirror of
+/// `haven_core::location::ProximityAlert`).
+class ProximityAlertFfi {
+  /// The rule that raised it.
+  final String ruleId;
+
+  /// The circle's MLS group ID.
+  final Uint8List mlsGroupId;
+
+  /// The member who came near (hex).
+  final String memberPubkey;
+
+  /// What they came near.
+  final ProximityTargetFfi target;
+
+  /// Their distance and ETA at the time.
+  final ProximityEstimateFfi estimate;
+
+  /// When the member's position was taken (Unix timestamp).
+  final PlatformInt64 seenAt;
+
+  const ProximityAlertFfi({
+    required this.ruleId,
+    required this.mlsGroupId,
+    required this.memberPubkey,
+    required this.target,
+    required this.estimate,
+    required this.seenAt,
+  });
+
+  @override
+  int get hashCode =>
+      ruleId.hashCode ^
+      mlsGroupId.hashCode ^
+      memberPubkey.hashCode ^
+      target.hashCode ^
+      estimate.hashCode ^
+      seenAt.hashCode;
+
+  @override
+  bool operator ==(Object other) =>
+      identical(this, other) ||
+      other is ProximityAlertFfi &&
+          runtimeType == other.runtimeType &&
+          ruleId == other.ruleId &&
+          mlsGroupId == other.mlsGroupId &&
+          memberPubkey == other.memberPubkey &&
+          target == other.target &&
+          estimate == other.estimate &&
+          seenAt == other.seenAt;
+}
+
+/// Distance and rough ETA between a member and a target (FFI mirror of
+/// `haven_core::location::ProximityEstimate`).
+class ProximityEstimateFfi {
+  /// Distance between the two shared positions, in meters.
+  final double distanceM;
+
+  /// How far the true distance may be from `distance_m`, in meters.
+  final double errorM;
+
+  /// Seconds until arrival at the recent approach speed; `None` while the
+  /// member is not approaching.
+  final BigInt? etaSecs;
+
+  const ProximityEstimateFfi({
+    required this.distanceM,
+    required this.errorM,
+    this.etaSecs,
+  });
+
+  @override
+  int get hashCode => distanceM.hashCode ^ errorM.hashCode ^ etaSecs.hashCode;
+
+  @override
+  bool operator ==(Object other) =>
+      identical(this, other) ||
+      other is ProximityEstimateFfi &&
+          runtimeType == other.runtimeType &&
+          distanceM == other.distanceM &&
+          errorM == other.errorM &&
+          etaSecs == other.etaSecs;
+}
+
+/// A proximity alert rule (FFI mirror of
+/// `haven_core::location::ProximityRule`).
+class ProximityRuleFfi {
+  /// Rule id (hex).
+  final String ruleId;
+
+  /// The circle's MLS group ID.
+  final Uint8List mlsGroupId;
+
+  /// The member watched (hex).
+  final String memberPubkey;
+
+  /// What the member's distance is measured to.
+  final ProximityTargetFfi target;
+
+  /// Alert when the member comes within this many meters.
+  final double rangeM;
+
+  /// When the rule was set up (Unix timestamp).
+  final PlatformInt64 createdAt;
+
+  /// Whether the member was within range at the last evaluation.
+  final bool within;
+
+  const ProximityRuleFfi({
+    required this.ruleId,
+    required this.mlsGroupId,
+    required this.memberPubkey,
+    required this.target,
+    required this.rangeM,
+    required this.createdAt,
+    required this.within,
+  });
+
+  @override
+  int get hashCode =>
+      ruleId.hashCode ^
+      mlsGroupId.hashCode ^
+      memberPubkey.hashCode ^
+      target.hashCode ^
+      rangeM.hashCode ^
+      createdAt.hashCode ^
+      within.hashCode;
+
+  @override
+  bool operator ==(Object other) =>
+      identical(this, other) ||
+      other is ProximityRuleFfi &&
+          runtimeType == other.runtimeType &&
+          ruleId == other.ruleId &&
+          mlsGroupId == other.mlsGroupId &&
+          memberPubkey == other.memberPubkey &&
+          target == other.target &&
+          rangeM == other.rangeM &&
+          createdAt == other.createdAt &&
+          within == other.within;
+}
+
+/// What a proximity alert measures the member's distance to (FFI mirror of
+/// `haven_core::location::ProximityTarget`). Set `target_pubkey` for another
+/// member, or the three `place_*` fields for a place.
+class ProximityTargetFfi {
+  /// The member to measure to (hex).
+  final String? targetPubkey;
+
+  /// The place's user-facing label.
+  final String? placeLabel;
+
+  /// The place's latitude in degrees.
+  final double? placeLatitude;
+
+  /// The place's longitude in degrees.
+  final double? placeLongitude;
+
+  const ProximityTargetFfi({
+    this.targetPubkey,
+    this.placeLabel,
+    this.placeLatitude,
+    this.placeLongitude,
+  });
+
+  @override
+  int get hashCode =>
+      targetPubkey.hashCode ^
+      placeLabel.hashCode ^
+      placeLatitude.hashCode ^
+      placeLongitude.hashCode;
+
+  @override
+  bool operator ==(Object other) =>
+      identical(this, other) ||
+      other is ProximityTargetFfi &&
+          runtimeType == other.runtimeType &&
+          targetPubkey == other.targetPubkey &&
+          placeLabel == other.placeLabel &&
+          placeLatitude == other.placeLatitude &&
+          placeLongitude == other.placeLongitude;
+}
+
 /// Public identity information (FFI-friendly).
 ///
 /// Contains only public data that can be safely stored and shared.
@@ -3174,6 +7459,47 @@ class PublicIdentity {
           createdAt == other.createdAt;
 }
 
+/// How many relays must accept a quorum publish (mirrors
+/// `haven_core::relay::PublishQuorum`).
+///
+/// Struct shape (rather than tagged enum) avoids pulling in Dart `freezed`
+/// for sealed classes.
+class PublishQuorumFfi {
+  /// Which quorum rule applies.
+  final PublishQuorumKindFfi kind;
+
+  /// The relay count when `kind == AtLeast`; ignored otherwise.
+  final int atLeast;
+
+  const PublishQuorumFfi({required this.kind, required this.atLeast});
+
+  @override
+  int get hashCode => kind.hashCode ^ atLeast.hashCode;
+
+  @override
+  bool operator ==(Object other) =>
+      identical(this, other) ||
+      other is PublishQuorumFfi &&
+          runtimeType == other.runtimeType &&
+          kind == other.kind &&
+          atLeast == other.atLeast;
+}
+
+/// Discriminator for [`PublishQuorumFfi`].
+enum PublishQuorumKindFfi {
+  /// Any one relay.
+  any,
+
+  /// More than half of the relays.
+  majority,
+
+  /// Every relay.
+  all,
+
+  /// At least `at_least` relays.
+  atLeast,
+}
+
 /// Result of publishing an event (FFI-friendly).
 class PublishResultFfi {
   /// The event ID that was published (64-char hex).
@@ -3219,6 +7545,79 @@ class PublishResultFfi {
           isSuccess == other.isSuccess;
 }
 
+/// One report from [`RelayManagerFfi::publish_event_with_quorum`].
+class QuorumPublishUpdateFfi {
+  /// The relays' answers so far.
+  final PublishResultFfi result;
+
+  /// Whether the quorum was met.
+  final bool quorumReached;
+
+  /// `false` for the early report, `true` once every relay answered.
+  final bool isFinal;
+
+  const QuorumPublishUpdateFfi({
+    required this.result,
+    required this.quorumReached,
+    required this.isFinal,
+  });
+
+  @override
+  int get hashCode =>
+      result.hashCode ^ quorumReached.hashCode ^ isFinal.hashCode;
+
+  @override
+  bool operator ==(Object other) =>
+      identical(this, other) ||
+      other is QuorumPublishUpdateFfi &&
+          runtimeType == other.runtimeType &&
+          result == other.result &&
+          quorumReached == other.quorumReached &&
+          isFinal == other.isFinal;
+}
+
+/// A request from a member's new device to be re-added to a circle (FFI
+/// mirror of [`haven_core::device_link::RejoinRequest`]).
+///
+/// Grant it by adding `key_package_json` to the circle as a new member.
+class RejoinRequestFfi {
+  /// The circle's MLS group ID.
+  final Uint8List mlsGroupId;
+
+  /// The requesting member's public key (hex).
+  final String requesterPubkey;
+
+  /// The new device's `KeyPackage` event (JSON).
+  final String keyPackageJson;
+
+  /// Hex id of the gift wrap it arrived in.
+  final String wrapperEventId;
+
+  const RejoinRequestFfi({
+    required this.mlsGroupId,
+    required this.requesterPubkey,
+    required this.keyPackageJson,
+    required this.wrapperEventId,
+  });
+
+  @override
+  int get hashCode =>
+      mlsGroupId.hashCode ^
+      requesterPubkey.hashCode ^
+      keyPackageJson.hashCode ^
+      wrapperEventId.hashCode;
+
+  @override
+  bool operator ==(Object other) =>
+      identical(this, other) ||
+      other is RejoinRequestFfi &&
+          runtimeType == other.runtimeType &&
+          mlsGroupId == other.mlsGroupId &&
+          requesterPubkey == other.requesterPubkey &&
+          keyPackageJson == other.keyPackageJson &&
+          wrapperEventId == other.wrapperEventId;
+}
+
 /// Relay connection status (FFI-friendly).
 class RelayConnectionStatusFfi {
   /// The relay URL.
@@ -3324,6 +7723,76 @@ class RelayGiftWrapFetchFfi {
           events == other.events;
 }
 
+/// A relay's advertised NIP-11 capabilities (FFI).
+class RelayInfoFfi {
+  /// The relay's self-declared name.
+  final String? name;
+
+  /// NIPs the relay says it supports.
+  final Uint16List supportedNips;
+
+  /// Largest message the relay accepts, in bytes.
+  final BigInt? maxMessageLength;
+
+  /// Largest event content the relay accepts, in characters.
+  final BigInt? maxContentLength;
+
+  /// The relay requires NIP-42 authentication.
+  final bool authRequired;
+
+  /// The relay requires payment.
+  final bool paymentRequired;
+
+  /// The relay only accepts writes from some users.
+  final bool restrictedWrites;
+
+  /// Minimum NIP-13 difficulty the relay demands.
+  final int? minPowDifficulty;
+
+  /// `false` if Haven skips this relay when publishing a typical
+  /// `KeyPackage` (payment, auth, or limits too small).
+  final bool usableForKeyPackages;
+
+  const RelayInfoFfi({
+    this.name,
+    required this.supportedNips,
+    this.maxMessageLength,
+    this.maxContentLength,
+    required this.authRequired,
+    required this.paymentRequired,
+    required this.restrictedWrites,
+    this.minPowDifficulty,
+    required this.usableForKeyPackages,
+  });
+
+  @override
+  int get hashCode =>
+      name.hashCode ^
+      supportedNips.hashCode ^
+      maxMessageLength.hashCode ^
+      maxContentLength.hashCode ^
+      authRequired.hashCode ^
+      paymentRequired.hashCode ^
+      restrictedWrites.hashCode ^
+      minPowDifficulty.hashCode ^
+      usableForKeyPackages.hashCode;
+
+  @override
+  bool operator ==(Object other) =>
+      identical(this, other) ||
+      other is RelayInfoFfi &&
+          runtimeType == other.runtimeType &&
+          name == other.name &&
+          supportedNips == other.supportedNips &&
+          maxMessageLength == other.maxMessageLength &&
+          maxContentLength == other.maxContentLength &&
+          authRequired == other.authRequired &&
+          paymentRequired == other.paymentRequired &&
+          restrictedWrites == other.restrictedWrites &&
+          minPowDifficulty == other.minPowDifficulty &&
+          usableForKeyPackages == other.usableForKeyPackages;
+}
+
 /// What an M8-1 relay-list maintenance tick did for one category (FFI mirror of
 /// [`haven_core::relay::maintenance::RelayListAction`]).
 ///
@@ -3371,77 +7840,489 @@ class RelayListCategoryOutcomeFfi {
   @override
   bool operator ==(Object other) =>
       identical(this, other) ||
-      other is RelayListCategoryOutcomeFfi &&
+      other is RelayListCategoryOutcomeFfi &&
+          runtimeType == other.runtimeType &&
+          action == other.action &&
+          respondersProbed == other.respondersProbed &&
+          relaysHealed == other.relaysHealed &&
+          relayErrors == other.relayErrors;
+}
+
+/// Presence-only result of a debounced relay-list republish
+/// ([`RelayManagerFfi::flush_relay_list_republishes`]).
+class RelayListFlushFfi {
+  /// Lists signed and accepted by at least one relay.
+  final int published;
+
+  /// Lists whose sign or publish failed; they are requeued.
+  final int failed;
+
+  /// When the next pending republish becomes due (Unix seconds), if any.
+  final PlatformInt64? nextDueAt;
+
+  const RelayListFlushFfi({
+    required this.published,
+    required this.failed,
+    this.nextDueAt,
+  });
+
+  @override
+  int get hashCode => published.hashCode ^ failed.hashCode ^ nextDueAt.hashCode;
+
+  @override
+  bool operator ==(Object other) =>
+      identical(this, other) ||
+      other is RelayListFlushFfi &&
+          runtimeType == other.runtimeType &&
+          published == other.published &&
+          failed == other.failed &&
+          nextDueAt == other.nextDueAt;
+}
+
+/// Presence-only result of an M8-1 relay-list maintenance tick (both
+/// categories). Counters + action enums only — leak-free (Security Rule 4/6).
+class RelayListMaintenanceOutcomeFfi {
+  /// The inbox (kind 10050) category outcome.
+  final RelayListCategoryOutcomeFfi inbox;
+
+  /// The `KeyPackage` (kind 10051) category outcome.
+  final RelayListCategoryOutcomeFfi keyPackage;
+
+  const RelayListMaintenanceOutcomeFfi({
+    required this.inbox,
+    required this.keyPackage,
+  });
+
+  @override
+  int get hashCode => inbox.hashCode ^ keyPackage.hashCode;
+
+  @override
+  bool operator ==(Object other) =>
+      identical(this, other) ||
+      other is RelayListMaintenanceOutcomeFfi &&
+          runtimeType == other.runtimeType &&
+          inbox == other.inbox &&
+          keyPackage == other.keyPackage;
+}
+
+/// Health counters of one relay (FFI mirror of
+/// [`haven_core::relay::RelayStats`]).
+class RelayMetricsFfi {
+  /// Relay URL.
+  final String url;
+
+  /// Publishes the relay accepted.
+  final BigInt publishOk;
+
+  /// Publishes the relay rejected, failed or timed out on.
+  final BigInt publishFailed;
+
+  /// Share of publishes that failed, from 0.0 to 1.0.
+  final double failureRate;
+
+  /// Average publish latency in milliseconds, if any was measured.
+  final BigInt? averageLatencyMs;
+
+  /// Bytes of events sent to the relay.
+  final BigInt bytesSent;
+
+  /// Bytes of events received from the relay.
+  final BigInt bytesReceived;
+
+  /// Unix timestamp of the last accepted publish.
+  final PlatformInt64? lastOkAt;
+
+  /// Unix timestamp of the last failed publish.
+  final PlatformInt64? lastErrorAt;
+
+  /// Why the last failed publish failed (redacted).
+  final String? lastError;
+
+  const RelayMetricsFfi({
+    required this.url,
+    required this.publishOk,
+    required this.publishFailed,
+    required this.failureRate,
+    this.averageLatencyMs,
+    required this.bytesSent,
+    required this.bytesReceived,
+    this.lastOkAt,
+    this.lastErrorAt,
+    this.lastError,
+  });
+
+  @override
+  int get hashCode =>
+      url.hashCode ^
+      publishOk.hashCode ^
+      publishFailed.hashCode ^
+      failureRate.hashCode ^
+      averageLatencyMs.hashCode ^
+      bytesSent.hashCode ^
+      bytesReceived.hashCode ^
+      lastOkAt.hashCode ^
+      lastErrorAt.hashCode ^
+      lastError.hashCode;
+
+  @override
+  bool operator ==(Object other) =>
+      identical(this, other) ||
+      other is RelayMetricsFfi &&
+          runtimeType == other.runtimeType &&
+          url == other.url &&
+          publishOk == other.publishOk &&
+          publishFailed == other.publishFailed &&
+          failureRate == other.failureRate &&
+          averageLatencyMs == other.averageLatencyMs &&
+          bytesSent == other.bytesSent &&
+          bytesReceived == other.bytesReceived &&
+          lastOkAt == other.lastOkAt &&
+          lastErrorAt == other.lastErrorAt &&
+          lastError == other.lastError;
+}
+
+/// One stored per-relay decision.
+class RelayOverrideEntryFfi {
+  /// Canonical relay URL.
+  final String url;
+
+  /// The user's decision.
+  final RelayOverrideFfi decision;
+
+  const RelayOverrideEntryFfi({required this.url, required this.decision});
+
+  @override
+  int get hashCode => url.hashCode ^ decision.hashCode;
+
+  @override
+  bool operator ==(Object other) =>
+      identical(this, other) ||
+      other is RelayOverrideEntryFfi &&
+          runtimeType == other.runtimeType &&
+          url == other.url &&
+          decision == other.decision;
+}
+
+/// A user's decision about one relay (mirrors
+/// `haven_core::relay::LocalRelayOverride`).
+enum RelayOverrideFfi {
+  /// Always block this relay.
+  block,
+
+  /// Never block this relay, even if the community list does.
+  allow,
+}
+
+/// Relay rejection info (FFI-friendly).
+class RelayRejectionFfi {
+  /// Relay URL that rejected.
+  final String url;
+
+  /// Rejection reason.
+  final String reason;
+
+  const RelayRejectionFfi({required this.url, required this.reason});
+
+  @override
+  int get hashCode => url.hashCode ^ reason.hashCode;
+
+  @override
+  bool operator ==(Object other) =>
+      identical(this, other) ||
+      other is RelayRejectionFfi &&
+          runtimeType == other.runtimeType &&
+          url == other.url &&
+          reason == other.reason;
+}
+
+/// Category of relay preference managed per user.
+///
+/// Mirrors [`haven_core::circle::RelayType`] with the Dark Matter W2 rename:
+/// - [`RelayTypeFfi::Inbox`] → kind 10050 (NIP-17 welcome delivery).
+/// - [`RelayTypeFfi::Nip65`] → kind 10002 (NIP-65; KeyPackage discovery under
+///   Dark Matter). Persisted under the same slot the retired kind-10051
+///   `KeyPackage` list used, so no relay-preference data migrates.
+enum RelayTypeFfi {
+  /// Inbox relays (kind 10050, NIP-17).
+  inbox,
+
+  /// NIP-65 relays (kind 10002) — where this account's KeyPackage is
+  /// discoverable under Dark Matter (W2, replacing the retired kind-10051
+  /// list). Stored under the `RelayType::KeyPackage` slot.
+  nip65,
+}
+
+/// Traffic with one relay (FFI-friendly).
+class RelayUsageFfi {
+  /// Relay URL.
+  final String url;
+
+  /// Traffic with this relay.
+  final UsageFfi usage;
+
+  const RelayUsageFfi({required this.url, required this.usage});
+
+  @override
+  int get hashCode => url.hashCode ^ usage.hashCode;
+
+  @override
+  bool operator ==(Object other) =>
+      identical(this, other) ||
+      other is RelayUsageFfi &&
+          runtimeType == other.runtimeType &&
+          url == other.url &&
+          usage == other.usage;
+}
+
+/// A paired NIP-46 remote signer (FFI).
+class RemoteSignerFfi {
+  /// The identity the bunker signs as (hex).
+  final String userPubkeyHex;
+
+  /// The bunker's own key (hex).
+  final String remoteSignerPubkeyHex;
+
+  /// Relays the bunker listens on.
+  final List<String> relays;
+
+  /// When the pairing was made (unix seconds).
+  final PlatformInt64 connectedAt;
+
+  const RemoteSignerFfi({
+    required this.userPubkeyHex,
+    required this.remoteSignerPubkeyHex,
+    required this.relays,
+    required this.connectedAt,
+  });
+
+  @override
+  int get hashCode =>
+      userPubkeyHex.hashCode ^
+      remoteSignerPubkeyHex.hashCode ^
+      relays.hashCode ^
+      connectedAt.hashCode;
+
+  @override
+  bool operator ==(Object other) =>
+      identical(this, other) ||
+      other is RemoteSignerFfi &&
+          runtimeType == other.runtimeType &&
+          userPubkeyHex == other.userPubkeyHex &&
+          remoteSignerPubkeyHex == other.remoteSignerPubkeyHex &&
+          relays == other.relays &&
+          connectedAt == other.connectedAt;
+}
+
+/// What [`CircleManagerFfi::repair_group`] did (mirrors
+/// `haven_core::circle::RepairOutcome`).
+enum RepairOutcomeFfi {
+  /// Nothing needed repair.
+  healthy,
+
+  /// The group was re-driven; the next sync shows whether it caught up.
+  resynced,
+
+  /// This device is no longer in the group: ask an admin to re-invite it.
+  needsRejoin,
+}
+
+/// One part of the self-test (FFI mirror of
+/// [`haven_core::self_test::SelfTestCheck`]).
+enum SelfTestCheckFfi {
+  /// NIP-44 encrypt/decrypt.
+  nip44,
+
+  /// MLS group creation, join and a location round trip.
+  mls,
+
+  /// Encrypted database write and read-back.
+  database,
+
+  /// Platform keyring write, read and delete.
+  secureStorage,
+}
+
+/// The outcome of [`HavenCore::run_self_test`] (FFI).
+class SelfTestReportFfi {
+  /// `true` if every check passed.
+  final bool passed;
+
+  /// One result per check, in the order they ran.
+  final List<SelfTestResultFfi> results;
+
+  /// The report as JSON, for attaching to a support request.
+  final String json;
+
+  const SelfTestReportFfi({
+    required this.passed,
+    required this.results,
+    required this.json,
+  });
+
+  @override
+  int get hashCode => passed.hashCode ^ results.hashCode ^ json.hashCode;
+
+  @override
+  bool operator ==(Object other) =>
+      identical(this, other) ||
+      other is SelfTestReportFfi &&
+          runtimeType == other.runtimeType &&
+          passed == other.passed &&
+          results == other.results &&
+          json == other.json;
+}
+
+/// The outcome of one self-test check (FFI).
+class SelfTestResultFfi {
+  /// Which check ran.
+  final SelfTestCheckFfi check;
+
+  /// `true` if the check passed.
+  final bool passed;
+
+  /// The step that failed (a short slug such as `decrypt`), if any.
+  final String? failure;
+
+  /// How long the check took, in milliseconds.
+  final BigInt durationMs;
+
+  const SelfTestResultFfi({
+    required this.check,
+    required this.passed,
+    this.failure,
+    required this.durationMs,
+  });
+
+  @override
+  int get hashCode =>
+      check.hashCode ^ passed.hashCode ^ failure.hashCode ^ durationMs.hashCode;
+
+  @override
+  bool operator ==(Object other) =>
+      identical(this, other) ||
+      other is SelfTestResultFfi &&
           runtimeType == other.runtimeType &&
-          action == other.action &&
-          respondersProbed == other.respondersProbed &&
-          relaysHealed == other.relaysHealed &&
-          relayErrors == other.relayErrors;
+          check == other.check &&
+          passed == other.passed &&
+          failure == other.failure &&
+          durationMs == other.durationMs;
 }
 
-/// Presence-only result of an M8-1 relay-list maintenance tick (both
-/// categories). Counters + action enums only — leak-free (Security Rule 4/6).
-class RelayListMaintenanceOutcomeFfi {
-  /// The inbox (kind 10050) category outcome.
-  final RelayListCategoryOutcomeFfi inbox;
+/// Exactly what a circle would receive for a location (FFI mirror of
+/// [`haven_core::circle::SharePreview`]). The cell is `None` only for an
+/// invalid position.
+class SharePreviewFfi {
+  /// Latitude as published.
+  final double latitude;
 
-  /// The `KeyPackage` (kind 10051) category outcome.
-  final RelayListCategoryOutcomeFfi keyPackage;
+  /// Longitude as published.
+  final double longitude;
 
-  const RelayListMaintenanceOutcomeFfi({
-    required this.inbox,
-    required this.keyPackage,
+  /// Geohash as published.
+  final String geohash;
+
+  /// Southern edge of the geohash cell.
+  final double? cellMinLat;
+
+  /// Western edge of the geohash cell.
+  final double? cellMinLon;
+
+  /// Northern edge of the geohash cell.
+  final double? cellMaxLat;
+
+  /// Eastern edge of the geohash cell.
+  final double? cellMaxLon;
+
+  /// Precision applied for this circle.
+  final LocationPrecisionFfi precision;
+
+  /// When members show the location as stale (Unix seconds).
+  final PlatformInt64 expiresAt;
+
+  /// When members drop the location (Unix seconds), if sooner than 1 day.
+  final PlatformInt64? shareExpiresAt;
+
+  const SharePreviewFfi({
+    required this.latitude,
+    required this.longitude,
+    required this.geohash,
+    this.cellMinLat,
+    this.cellMinLon,
+    this.cellMaxLat,
+    this.cellMaxLon,
+    required this.precision,
+    required this.expiresAt,
+    this.shareExpiresAt,
   });
 
   @override
-  int get hashCode => inbox.hashCode ^ keyPackage.hashCode;
+  int get hashCode =>
+      latitude.hashCode ^
+      longitude.hashCode ^
+      geohash.hashCode ^
+      cellMinLat.hashCode ^
+      cellMinLon.hashCode ^
+      cellMaxLat.hashCode ^
+      cellMaxLon.hashCode ^
+      precision.hashCode ^
+      expiresAt.hashCode ^
+      shareExpiresAt.hashCode;
 
   @override
   bool operator ==(Object other) =>
       identical(this, other) ||
-      other is RelayListMaintenanceOutcomeFfi &&
+      other is SharePreviewFfi &&
           runtimeType == other.runtimeType &&
-          inbox == other.inbox &&
-          keyPackage == other.keyPackage;
+          latitude == other.latitude &&
+          longitude == other.longitude &&
+          geohash == other.geohash &&
+          cellMinLat == other.cellMinLat &&
+          cellMinLon == other.cellMinLon &&
+          cellMaxLat == other.cellMaxLat &&
+          cellMaxLon == other.cellMaxLon &&
+          precision == other.precision &&
+          expiresAt == other.expiresAt &&
+          shareExpiresAt == other.shareExpiresAt;
 }
 
-/// Relay rejection info (FFI-friendly).
-class RelayRejectionFfi {
-  /// Relay URL that rejected.
-  final String url;
+/// A running precise-sharing session (FFI mirror of
+/// [`haven_core::circle::SharingSession`]).
+class SharingSessionFfi {
+  /// MLS group ID of the circle.
+  final Uint8List mlsGroupId;
 
-  /// Rejection reason.
-  final String reason;
+  /// Unix timestamp the session started.
+  final PlatformInt64 startedAt;
 
-  const RelayRejectionFfi({required this.url, required this.reason});
+  /// Unix timestamp the session ends by itself.
+  final PlatformInt64 endsAt;
+
+  /// Seconds left when this was read, for a countdown.
+  final PlatformInt64 remainingSecs;
+
+  const SharingSessionFfi({
+    required this.mlsGroupId,
+    required this.startedAt,
+    required this.endsAt,
+    required this.remainingSecs,
+  });
 
   @override
-  int get hashCode => url.hashCode ^ reason.hashCode;
+  int get hashCode =>
+      mlsGroupId.hashCode ^
+      startedAt.hashCode ^
+      endsAt.hashCode ^
+      remainingSecs.hashCode;
 
   @override
   bool operator ==(Object other) =>
       identical(this, other) ||
-      other is RelayRejectionFfi &&
+      other is SharingSessionFfi &&
           runtimeType == other.runtimeType &&
-          url == other.url &&
-          reason == other.reason;
-}
-
-/// Category of relay preference managed per user.
-///
-/// Mirrors [`haven_core::circle::RelayType`] with the Dark Matter W2 rename:
-/// - [`RelayTypeFfi::Inbox`] → kind 10050 (NIP-17 welcome delivery).
-/// - [`RelayTypeFfi::Nip65`] → kind 10002 (NIP-65; KeyPackage discovery under
-///   Dark Matter). Persisted under the same slot the retired kind-10051
-///   `KeyPackage` list used, so no relay-preference data migrates.
-enum RelayTypeFfi {
-  /// Inbox relays (kind 10050, NIP-17).
-  inbox,
-
-  /// NIP-65 relays (kind 10002) — where this account's KeyPackage is
-  /// discoverable under Dark Matter (W2, replacing the retired kind-10051
-  /// list). Stored under the `RelayType::KeyPackage` slot.
-  nip65,
+          mlsGroupId == other.mlsGroupId &&
+          startedAt == other.startedAt &&
+          endsAt == other.endsAt &&
+          remainingSecs == other.remainingSecs;
 }
 
 /// Signed location event (FFI wrapper for outer event kind 445).
@@ -3504,6 +8385,32 @@ class SignedLocationEventFfi {
           sig == other.sig;
 }
 
+/// Result of fanning an SOS out to several circles (FFI-friendly).
+///
+/// Mirrors `haven_core::emergency::SosFanout`. Each delivery is published
+/// exactly like an [`EncryptedLocationFfi`]; `failed_group_ids` lists the
+/// circles the alert could not be encrypted for.
+class SosFanoutFfi {
+  /// One ready-to-publish event per reachable circle.
+  final List<EncryptedLocationFfi> deliveries;
+
+  /// MLS group ids (raw bytes) of circles that were skipped.
+  final List<Uint8List> failedGroupIds;
+
+  const SosFanoutFfi({required this.deliveries, required this.failedGroupIds});
+
+  @override
+  int get hashCode => deliveries.hashCode ^ failedGroupIds.hashCode;
+
+  @override
+  bool operator ==(Object other) =>
+      identical(this, other) ||
+      other is SosFanoutFfi &&
+          runtimeType == other.runtimeType &&
+          deliveries == other.deliveries &&
+          failedGroupIds == other.failedGroupIds;
+}
+
 /// What an M8-4 subscription-health tick did (FFI mirror of
 /// [`haven_core::relay::live_sync::HealthAction`]).
 ///
@@ -3567,6 +8474,88 @@ class SubscriptionHealthOutcomeFfi {
           relaysDisconnected == other.relaysDisconnected;
 }
 
+/// What a group sync changed across all circles (FFI mirror of
+/// [`haven_core::relay::SyncDigest`]).
+class SyncDigestFfi {
+  /// Accepted circles with at least one relay.
+  final int circlesSynced;
+
+  /// `(circle, relay)` fetches issued.
+  final int relaysPolled;
+
+  /// `(circle, relay)` fetches that answered.
+  final int relaysResponded;
+
+  /// Distinct events fetched.
+  final int eventsFetched;
+
+  /// Events whose ingest failed.
+  final int eventsFailed;
+
+  /// Event signatures verified.
+  final int signaturesVerified;
+
+  /// Events accepted from the verified-event cache without re-verifying.
+  final int signaturesCached;
+
+  /// Events dropped for a mismatched id or invalid signature.
+  final int signaturesRejected;
+
+  /// Last-known locations updated.
+  final int locationsUpdated;
+
+  /// Receive-side auto-commits published and confirmed.
+  final int autoCommitsPublished;
+
+  /// Per-circle detail, for circles that fetched at least one event.
+  final List<CircleSyncDigestFfi> circles;
+
+  const SyncDigestFfi({
+    required this.circlesSynced,
+    required this.relaysPolled,
+    required this.relaysResponded,
+    required this.eventsFetched,
+    required this.eventsFailed,
+    required this.signaturesVerified,
+    required this.signaturesCached,
+    required this.signaturesRejected,
+    required this.locationsUpdated,
+    required this.autoCommitsPublished,
+    required this.circles,
+  });
+
+  @override
+  int get hashCode =>
+      circlesSynced.hashCode ^
+      relaysPolled.hashCode ^
+      relaysResponded.hashCode ^
+      eventsFetched.hashCode ^
+      eventsFailed.hashCode ^
+      signaturesVerified.hashCode ^
+      signaturesCached.hashCode ^
+      signaturesRejected.hashCode ^
+      locationsUpdated.hashCode ^
+      autoCommitsPublished.hashCode ^
+      circles.hashCode;
+
+  @override
+  bool operator ==(Object other) =>
+      identical(this, other) ||
+      other is SyncDigestFfi &&
+          runtimeType == other.runtimeType &&
+          circlesSynced == other.circlesSynced &&
+          relaysPolled == other.relaysPolled &&
+          relaysResponded == other.relaysResponded &&
+          eventsFetched == other.eventsFetched &&
+          eventsFailed == other.eventsFailed &&
+          signaturesVerified == other.signaturesVerified &&
+          signaturesCached == other.signaturesCached &&
+          signaturesRejected == other.signaturesRejected &&
+          locationsUpdated == other.locationsUpdated &&
+          autoCommitsPublished == other.autoCommitsPublished &&
+          circles == other.circles;
+}
+
 /// A cached tile and its conditional-revalidation metadata, for Dart.
 ///
 /// `bytes` is public map imagery (encrypted only at rest), so it is surfaced
@@ -3610,6 +8599,136 @@ class TileCacheEntryFfi {
           etag == other.etag;
 }
 
+/// The outcome of restoring a device transfer (FFI mirror of
+/// [`haven_core::device_transfer::TransferRestoreReport`]).
+class TransferRestoreFfi {
+  /// Circles restored with their state.
+  final List<CircleWithMembersFfi> adopted;
+
+  /// Circles to ask an admin to rejoin.
+  final List<LinkedCircleFfi> needsRejoin;
+
+  const TransferRestoreFfi({required this.adopted, required this.needsRejoin});
+
+  @override
+  int get hashCode => adopted.hashCode ^ needsRejoin.hashCode;
+
+  @override
+  bool operator ==(Object other) =>
+      identical(this, other) ||
+      other is TransferRestoreFfi &&
+          runtimeType == other.runtimeType &&
+          adopted == other.adopted &&
+          needsRejoin == other.needsRejoin;
+}
+
+/// One journey between two stays (FFI mirror of
+/// `haven_core::location::Trip`).
+class TripFfi {
+  /// When the member set off (Unix timestamp).
+  final PlatformInt64 startedAt;
+
+  /// When they arrived (Unix timestamp).
+  final PlatformInt64 endedAt;
+
+  /// Distance along the shared positions, in meters.
+  final double distanceM;
+
+  const TripFfi({
+    required this.startedAt,
+    required this.endedAt,
+    required this.distanceM,
+  });
+
+  @override
+  int get hashCode =>
+      startedAt.hashCode ^ endedAt.hashCode ^ distanceM.hashCode;
+
+  @override
+  bool operator ==(Object other) =>
+      identical(this, other) ||
+      other is TripFfi &&
+          runtimeType == other.runtimeType &&
+          startedAt == other.startedAt &&
+          endedAt == other.endedAt &&
+          distanceM == other.distanceM;
+}
+
+/// A circle's running trip mode (FFI mirror of
+/// [`haven_core::circle::TripMode`]).
+class TripModeFfi {
+  /// Nominal publish interval during the trip, in seconds.
+  final BigInt updateIntervalSecs;
+
+  /// How long members keep each location shared during the trip, in seconds.
+  final BigInt shareExpirationSecs;
+
+  /// Unix timestamp at which the trip mode ends by itself.
+  final PlatformInt64 endsAt;
+
+  const TripModeFfi({
+    required this.updateIntervalSecs,
+    required this.shareExpirationSecs,
+    required this.endsAt,
+  });
+
+  @override
+  int get hashCode =>
+      updateIntervalSecs.hashCode ^
+      shareExpirationSecs.hashCode ^
+      endsAt.hashCode;
+
+  @override
+  bool operator ==(Object other) =>
+      identical(this, other) ||
+      other is TripModeFfi &&
+          runtimeType == other.runtimeType &&
+          updateIntervalSecs == other.updateIntervalSecs &&
+          shareExpirationSecs == other.shareExpirationSecs &&
+          endsAt == other.endsAt;
+}
+
+/// A member who was invited but has not joined yet (FFI mirror of
+/// `haven_core::circle::UnjoinedMember`).
+class UnjoinedMemberFfi {
+  /// The member's public key (hex).
+  final String memberPubkey;
+
+  /// When they were first invited (Unix timestamp).
+  final PlatformInt64 invitedAt;
+
+  /// How often the invitation was resent.
+  final int resendCount;
+
+  /// Earliest the invitation can be resent again, or `None` once the
+  /// resends are used up.
+  final PlatformInt64? nextResendAt;
+
+  const UnjoinedMemberFfi({
+    required this.memberPubkey,
+    required this.invitedAt,
+    required this.resendCount,
+    this.nextResendAt,
+  });
+
+  @override
+  int get hashCode =>
+      memberPubkey.hashCode ^
+      invitedAt.hashCode ^
+      resendCount.hashCode ^
+      nextResendAt.hashCode;
+
+  @override
+  bool operator ==(Object other) =>
+      identical(this, other) ||
+      other is UnjoinedMemberFfi &&
+          runtimeType == other.runtimeType &&
+          memberPubkey == other.memberPubkey &&
+          invitedAt == other.invitedAt &&
+          resendCount == other.resendCount &&
+          nextResendAt == other.nextResendAt;
+}
+
 /// Unsigned location event (FFI wrapper for inner event kind 9).
 ///
 /// This is the inner event containing location data before encryption.
@@ -3648,3 +8767,201 @@ class UnsignedLocationEventFfi {
           tags == other.tags &&
           createdAt == other.createdAt;
 }
+
+/// Events and bytes in each direction (FFI-friendly).
+class UsageFfi {
+  /// Events sent.
+  final BigInt eventsSent;
+
+  /// Bytes sent.
+  final BigInt bytesSent;
+
+  /// Events received.
+  final BigInt eventsReceived;
+
+  /// Bytes received.
+  final BigInt bytesReceived;
+
+  const UsageFfi({
+    required this.eventsSent,
+    required this.bytesSent,
+    required this.eventsReceived,
+    required this.bytesReceived,
+  });
+
+  static Future<UsageFfi> default_() =>
+      RustLib.instance.api.crateApiUsageFfiDefault();
+
+  @override
+  int get hashCode =>
+      eventsSent.hashCode ^
+      bytesSent.hashCode ^
+      eventsReceived.hashCode ^
+      bytesReceived.hashCode;
+
+  @override
+  bool operator ==(Object other) =>
+      identical(this, other) ||
+      other is UsageFfi &&
+          runtimeType == other.runtimeType &&
+          eventsSent == other.eventsSent &&
+          bytesSent == other.bytesSent &&
+          eventsReceived == other.eventsReceived &&
+          bytesReceived == other.bytesReceived;
+}
+
+/// A localizable message: a stable code plus parameters (FFI).
+///
+/// Flutter looks `code` up in its translation catalog and substitutes
+/// `params`; `english` is the built-in fallback for codes the app does not
+/// translate yet.
+class UserMessageFfi {
+  /// Stable message code, e.g. `relay.rejected`.
+  final String code;
+
+  /// Named values the message refers to.
+  final List<MessageParamFfi> params;
+
+  /// English rendering of the message.
+  final String english;
+
+  const UserMessageFfi({
+    required this.code,
+    required this.params,
+    required this.english,
+  });
+
+  @override
+  int get hashCode => code.hashCode ^ params.hashCode ^ english.hashCode;
+
+  @override
+  bool operator ==(Object other) =>
+      identical(this, other) ||
+      other is UserMessageFfi &&
+          runtimeType == other.runtimeType &&
+          code == other.code &&
+          params == other.params &&
+          english == other.english;
+}
+
+/// Altitude and floor level shared alongside a location (FFI mirror of
+/// [`haven_core::location::VerticalPosition`]).
+class VerticalPositionFfi {
+  /// Altitude above sea level, in whole meters.
+  final double? altitudeM;
+
+  /// Floor level, `0` for the ground floor and negative below it.
+  final int? floor;
+
+  const VerticalPositionFfi({this.altitudeM, this.floor});
+
+  @override
+  int get hashCode => altitudeM.hashCode ^ floor.hashCode;
+
+  @override
+  bool operator ==(Object other) =>
+      identical(this, other) ||
+      other is VerticalPositionFfi &&
+          runtimeType == other.runtimeType &&
+          altitudeM == other.altitudeM &&
+          floor == other.floor;
+}
+
+/// The result of delivering one Welcome (FFI mirror of
+/// [`haven_core::relay::WelcomeDeliveryReport`]).
+class WelcomeDeliveryReportFfi {
+  /// The recipient's Nostr public key (hex).
+  final String recipientPubkey;
+
+  /// Every relay considered, in the order they were tried.
+  final List<WelcomeRelayDeliveryFfi> deliveries;
+
+  /// Whether at least one relay accepted the Welcome.
+  final bool delivered;
+
+  const WelcomeDeliveryReportFfi({
+    required this.recipientPubkey,
+    required this.deliveries,
+    required this.delivered,
+  });
+
+  @override
+  int get hashCode =>
+      recipientPubkey.hashCode ^ deliveries.hashCode ^ delivered.hashCode;
+
+  @override
+  bool operator ==(Object other) =>
+      identical(this, other) ||
+      other is WelcomeDeliveryReportFfi &&
+          runtimeType == other.runtimeType &&
+          recipientPubkey == other.recipientPubkey &&
+          deliveries == other.deliveries &&
+          delivered == other.delivered;
+}
+
+/// One relay's part in a Welcome delivery (FFI-friendly).
+class WelcomeRelayDeliveryFfi {
+  /// The relay URL.
+  final String relayUrl;
+
+  /// What happened there.
+  final WelcomeRelayOutcomeFfi outcome;
+
+  /// For `TooLargeForRelay`: the advertised limit exceeded, if known.
+  final BigInt? limit;
+
+  /// For `TooLargeForRelay`: the Welcome's serialized size, in bytes.
+  final BigInt? eventBytes;
+
+  /// For `Rejected`: the relay's reason.
+  final String? reason;
+
+  /// Whether the relay came from the fallback tiers.
+  final bool fallback;
+
+  const WelcomeRelayDeliveryFfi({
+    required this.relayUrl,
+    required this.outcome,
+    this.limit,
+    this.eventBytes,
+    this.reason,
+    required this.fallback,
+  });
+
+  @override
+  int get hashCode =>
+      relayUrl.hashCode ^
+      outcome.hashCode ^
+      limit.hashCode ^
+      eventBytes.hashCode ^
+      reason.hashCode ^
+      fallback.hashCode;
+
+  @override
+  bool operator ==(Object other) =>
+      identical(this, other) ||
+      other is WelcomeRelayDeliveryFfi &&
+          runtimeType == other.runtimeType &&
+          relayUrl == other.relayUrl &&
+          outcome == other.outcome &&
+          limit == other.limit &&
+          eventBytes == other.eventBytes &&
+          reason == other.reason &&
+          fallback == other.fallback;
+}
+
+/// What happened to a Welcome on one relay (FFI mirror of
+/// [`haven_core::relay::WelcomeRelayOutcome`]).
+enum WelcomeRelayOutcomeFfi {
+  /// The relay accepted the Welcome.
+  accepted,
+
+  /// The relay cannot take an event this size.
+  tooLargeForRelay,
+
+  /// The relay rejected the Welcome for another reason.
+  rejected,
+
+  /// The relay could not be reached.
+  unreachable,
+}
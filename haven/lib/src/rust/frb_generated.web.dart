@@ -24,10 +24,22 @@ abstract class RustLibApiImplPlatform extends BaseApiImpl<RustLibWire> {
   get rust_arc_decrement_strong_count_CircleManagerFfiPtr => wire
       .rust_arc_decrement_strong_count_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerCircleManagerFfi;
 
+  CrossPlatformFinalizerArg
+  get rust_arc_decrement_strong_count_DeviceLinkSessionFfiPtr => wire
+      .rust_arc_decrement_strong_count_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerDeviceLinkSessionFfi;
+
+  CrossPlatformFinalizerArg
+  get rust_arc_decrement_strong_count_ExternalSignerFfiPtr => wire
+      .rust_arc_decrement_strong_count_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerExternalSignerFfi;
+
   CrossPlatformFinalizerArg
   get rust_arc_decrement_strong_count_HavenCorePtr => wire
       .rust_arc_decrement_strong_count_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerHavenCore;
 
+  CrossPlatformFinalizerArg
+  get rust_arc_decrement_strong_count_LinkedIdentityFfiPtr => wire
+      .rust_arc_decrement_strong_count_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerLinkedIdentityFfi;
+
   CrossPlatformFinalizerArg
   get rust_arc_decrement_strong_count_LiveSyncFfiPtr => wire
       .rust_arc_decrement_strong_count_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerLiveSyncFfi;
@@ -52,6 +64,18 @@ abstract class RustLibApiImplPlatform extends BaseApiImpl<RustLibWire> {
   get rust_arc_decrement_strong_count_RelayManagerFfiPtr => wire
       .rust_arc_decrement_strong_count_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerRelayManagerFfi;
 
+  CrossPlatformFinalizerArg
+  get rust_arc_decrement_strong_count_SecretHandlePtr => wire
+      .rust_arc_decrement_strong_count_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerSecretHandle;
+
+  CrossPlatformFinalizerArg
+  get rust_arc_decrement_strong_count_TransferBundleFfiPtr => wire
+      .rust_arc_decrement_strong_count_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerTransferBundleFfi;
+
+  CrossPlatformFinalizerArg
+  get rust_arc_decrement_strong_count_TransferContentsFfiPtr => wire
+      .rust_arc_decrement_strong_count_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerTransferContentsFfi;
+
   @protected
   AnyhowException dco_decode_AnyhowException(dynamic raw);
 
@@ -61,12 +85,30 @@ abstract class RustLibApiImplPlatform extends BaseApiImpl<RustLibWire> {
     dynamic raw,
   );
 
+  @protected
+  DeviceLinkSessionFfi
+  dco_decode_Auto_Owned_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerDeviceLinkSessionFfi(
+    dynamic raw,
+  );
+
+  @protected
+  ExternalSignerFfi
+  dco_decode_Auto_Owned_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerExternalSignerFfi(
+    dynamic raw,
+  );
+
   @protected
   HavenCore
   dco_decode_Auto_Owned_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerHavenCore(
     dynamic raw,
   );
 
+  @protected
+  LinkedIdentityFfi
+  dco_decode_Auto_Owned_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerLinkedIdentityFfi(
+    dynamic raw,
+  );
+
   @protected
   LiveSyncFfi
   dco_decode_Auto_Owned_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerLiveSyncFfi(
@@ -103,24 +145,66 @@ abstract class RustLibApiImplPlatform extends BaseApiImpl<RustLibWire> {
     dynamic raw,
   );
 
+  @protected
+  SecretHandle
+  dco_decode_Auto_Owned_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerSecretHandle(
+    dynamic raw,
+  );
+
+  @protected
+  TransferBundleFfi
+  dco_decode_Auto_Owned_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerTransferBundleFfi(
+    dynamic raw,
+  );
+
+  @protected
+  TransferContentsFfi
+  dco_decode_Auto_Owned_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerTransferContentsFfi(
+    dynamic raw,
+  );
+
   @protected
   HavenCore
   dco_decode_Auto_RefMut_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerHavenCore(
     dynamic raw,
   );
 
+  @protected
+  LocationSettings
+  dco_decode_Auto_RefMut_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerLocationSettings(
+    dynamic raw,
+  );
+
   @protected
   CircleManagerFfi
   dco_decode_Auto_Ref_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerCircleManagerFfi(
     dynamic raw,
   );
 
+  @protected
+  DeviceLinkSessionFfi
+  dco_decode_Auto_Ref_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerDeviceLinkSessionFfi(
+    dynamic raw,
+  );
+
+  @protected
+  ExternalSignerFfi
+  dco_decode_Auto_Ref_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerExternalSignerFfi(
+    dynamic raw,
+  );
+
   @protected
   HavenCore
   dco_decode_Auto_Ref_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerHavenCore(
     dynamic raw,
   );
 
+  @protected
+  LinkedIdentityFfi
+  dco_decode_Auto_Ref_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerLinkedIdentityFfi(
+    dynamic raw,
+  );
+
   @protected
   LiveSyncFfi
   dco_decode_Auto_Ref_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerLiveSyncFfi(
@@ -157,18 +241,69 @@ abstract class RustLibApiImplPlatform extends BaseApiImpl<RustLibWire> {
     dynamic raw,
   );
 
+  @protected
+  SecretHandle
+  dco_decode_Auto_Ref_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerSecretHandle(
+    dynamic raw,
+  );
+
+  @protected
+  TransferBundleFfi
+  dco_decode_Auto_Ref_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerTransferBundleFfi(
+    dynamic raw,
+  );
+
+  @protected
+  TransferContentsFfi
+  dco_decode_Auto_Ref_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerTransferContentsFfi(
+    dynamic raw,
+  );
+
+  @protected
+  FutureOr<String> Function(String, String)
+  dco_decode_DartFn_Inputs_String_String_Output_String_AnyhowException(
+    dynamic raw,
+  );
+
+  @protected
+  FutureOr<String> Function(Uint8List)
+  dco_decode_DartFn_Inputs_list_prim_u_8_strict_Output_String_AnyhowException(
+    dynamic raw,
+  );
+
+  @protected
+  Object dco_decode_DartOpaque(dynamic raw);
+
   @protected
   CircleManagerFfi
   dco_decode_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerCircleManagerFfi(
     dynamic raw,
   );
 
+  @protected
+  DeviceLinkSessionFfi
+  dco_decode_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerDeviceLinkSessionFfi(
+    dynamic raw,
+  );
+
+  @protected
+  ExternalSignerFfi
+  dco_decode_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerExternalSignerFfi(
+    dynamic raw,
+  );
+
   @protected
   HavenCore
   dco_decode_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerHavenCore(
     dynamic raw,
   );
 
+  @protected
+  LinkedIdentityFfi
+  dco_decode_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerLinkedIdentityFfi(
+    dynamic raw,
+  );
+
   @protected
   LiveSyncFfi
   dco_decode_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerLiveSyncFfi(
@@ -206,1677 +341,4552 @@ abstract class RustLibApiImplPlatform extends BaseApiImpl<RustLibWire> {
   );
 
   @protected
-  RustStreamSink<FfiRelayEvent> dco_decode_StreamSink_ffi_relay_event_Sse(
+  SecretHandle
+  dco_decode_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerSecretHandle(
     dynamic raw,
   );
 
   @protected
-  String dco_decode_String(dynamic raw);
+  TransferBundleFfi
+  dco_decode_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerTransferBundleFfi(
+    dynamic raw,
+  );
 
   @protected
-  AddMembersResultFfi dco_decode_add_members_result_ffi(dynamic raw);
+  TransferContentsFfi
+  dco_decode_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerTransferContentsFfi(
+    dynamic raw,
+  );
 
   @protected
-  bool dco_decode_bool(dynamic raw);
+  RustStreamSink<DecryptedLocationFfi>
+  dco_decode_StreamSink_decrypted_location_ffi_Sse(dynamic raw);
 
   @protected
-  CircleWithMembersFfi dco_decode_box_autoadd_circle_with_members_ffi(
+  RustStreamSink<FfiRelayEvent> dco_decode_StreamSink_ffi_relay_event_Sse(
     dynamic raw,
   );
 
   @protected
-  ContactFfi dco_decode_box_autoadd_contact_ffi(dynamic raw);
+  RustStreamSink<QuorumPublishUpdateFfi>
+  dco_decode_StreamSink_quorum_publish_update_ffi_Sse(dynamic raw);
 
   @protected
-  DecryptedLocationFfi dco_decode_box_autoadd_decrypted_location_ffi(
-    dynamic raw,
-  );
+  String dco_decode_String(dynamic raw);
 
   @protected
-  FfiGroupSpec dco_decode_box_autoadd_ffi_group_spec(dynamic raw);
+  AddMembersResultFfi dco_decode_add_members_result_ffi(dynamic raw);
 
   @protected
-  FfiSyncStatusReason dco_decode_box_autoadd_ffi_sync_status_reason(
-    dynamic raw,
-  );
+  AudienceCircleFfi dco_decode_audience_circle_ffi(dynamic raw);
 
   @protected
-  PlatformInt64 dco_decode_box_autoadd_i_64(dynamic raw);
+  AudienceMemberFfi dco_decode_audience_member_ffi(dynamic raw);
 
   @protected
-  InvitationFfi dco_decode_box_autoadd_invitation_ffi(dynamic raw);
+  BackfillDigestFfi dco_decode_backfill_digest_ffi(dynamic raw);
 
   @protected
-  LastKnownLocationFfi dco_decode_box_autoadd_last_known_location_ffi(
+  BandwidthUsageFfi dco_decode_bandwidth_usage_ffi(dynamic raw);
+
+  @protected
+  bool dco_decode_bool(dynamic raw);
+
+  @protected
+  bool dco_decode_box_autoadd_bool(dynamic raw);
+
+  @protected
+  CircleLocationSettingsFfi dco_decode_box_autoadd_circle_location_settings_ffi(
     dynamic raw,
   );
 
   @protected
-  MemberKeyPackageFfi dco_decode_box_autoadd_member_key_package_ffi(
+  CircleWithMembersFfi dco_decode_box_autoadd_circle_with_members_ffi(
     dynamic raw,
   );
 
   @protected
-  PendingStateRefFfi dco_decode_box_autoadd_pending_state_ref_ffi(dynamic raw);
+  ColdCircleInfoFfi dco_decode_box_autoadd_cold_circle_info_ffi(dynamic raw);
 
   @protected
-  ProfileMetadataFfi dco_decode_box_autoadd_profile_metadata_ffi(dynamic raw);
+  CompactEventFfi dco_decode_box_autoadd_compact_event_ffi(dynamic raw);
 
   @protected
-  PublicIdentity dco_decode_box_autoadd_public_identity(dynamic raw);
+  ContactFfi dco_decode_box_autoadd_contact_ffi(dynamic raw);
 
   @protected
-  SignedLocationEventFfi dco_decode_box_autoadd_signed_location_event_ffi(
+  DecryptLocationOutcomeFfi dco_decode_box_autoadd_decrypt_location_outcome_ffi(
     dynamic raw,
   );
 
   @protected
-  TileCacheEntryFfi dco_decode_box_autoadd_tile_cache_entry_ffi(dynamic raw);
+  DecryptedLocationFfi dco_decode_box_autoadd_decrypted_location_ffi(
+    dynamic raw,
+  );
 
   @protected
-  int dco_decode_box_autoadd_u_16(dynamic raw);
+  DeviceStatusFfi dco_decode_box_autoadd_device_status_ffi(dynamic raw);
 
   @protected
-  int dco_decode_box_autoadd_u_32(dynamic raw);
+  EncryptedLocationFfi dco_decode_box_autoadd_encrypted_location_ffi(
+    dynamic raw,
+  );
 
   @protected
-  BuiltRelayListEventFfi dco_decode_built_relay_list_event_ffi(dynamic raw);
+  double dco_decode_box_autoadd_f_64(dynamic raw);
 
   @protected
-  BuiltUnpublishFfi dco_decode_built_unpublish_ffi(dynamic raw);
+  FfiGroupSpec dco_decode_box_autoadd_ffi_group_spec(dynamic raw);
 
   @protected
-  CatchupResultFfi dco_decode_catchup_result_ffi(dynamic raw);
+  FfiSyncStatusReason dco_decode_box_autoadd_ffi_sync_status_reason(
+    dynamic raw,
+  );
 
   @protected
-  CircleCreationResultFfi dco_decode_circle_creation_result_ffi(dynamic raw);
+  GiftWrappedWelcomeFfi dco_decode_box_autoadd_gift_wrapped_welcome_ffi(
+    dynamic raw,
+  );
 
   @protected
-  CircleFfi dco_decode_circle_ffi(dynamic raw);
+  HavenErrorFfi dco_decode_box_autoadd_haven_error_ffi(dynamic raw);
 
   @protected
-  CircleMemberFfi dco_decode_circle_member_ffi(dynamic raw);
+  int dco_decode_box_autoadd_i_16(dynamic raw);
 
   @protected
-  CircleWithMembersFfi dco_decode_circle_with_members_ffi(dynamic raw);
+  PlatformInt64 dco_decode_box_autoadd_i_64(dynamic raw);
 
   @protected
-  CommitToPublishFfi dco_decode_commit_to_publish_ffi(dynamic raw);
+  InvitationFfi dco_decode_box_autoadd_invitation_ffi(dynamic raw);
 
   @protected
-  ContactFfi dco_decode_contact_ffi(dynamic raw);
+  KpVerificationFfi dco_decode_box_autoadd_kp_verification_ffi(dynamic raw);
 
   @protected
-  DecryptLocationOutcomeFfi dco_decode_decrypt_location_outcome_ffi(
+  LastKnownLocationFfi dco_decode_box_autoadd_last_known_location_ffi(
     dynamic raw,
   );
 
   @protected
-  DecryptedLocationFfi dco_decode_decrypted_location_ffi(dynamic raw);
-
-  @protected
-  EncryptedLocationFfi dco_decode_encrypted_location_ffi(dynamic raw);
+  LeaveEventFfi dco_decode_box_autoadd_leave_event_ffi(dynamic raw);
 
   @protected
-  double dco_decode_f_64(dynamic raw);
+  MeetPinFfi dco_decode_box_autoadd_meet_pin_ffi(dynamic raw);
 
   @protected
-  FfiGroupSpec dco_decode_ffi_group_spec(dynamic raw);
+  MemberKeyPackageFfi dco_decode_box_autoadd_member_key_package_ffi(
+    dynamic raw,
+  );
 
   @protected
-  FfiRelayEvent dco_decode_ffi_relay_event(dynamic raw);
+  MembershipDeltaFfi dco_decode_box_autoadd_membership_delta_ffi(dynamic raw);
 
   @protected
-  FfiRelayEventKind dco_decode_ffi_relay_event_kind(dynamic raw);
+  MotionStateFfi dco_decode_box_autoadd_motion_state_ffi(dynamic raw);
 
   @protected
-  FfiSyncStatusReason dco_decode_ffi_sync_status_reason(dynamic raw);
+  PendingStateRefFfi dco_decode_box_autoadd_pending_state_ref_ffi(dynamic raw);
 
   @protected
-  GiftWrappedWelcomeFfi dco_decode_gift_wrapped_welcome_ffi(dynamic raw);
+  PrecisionAnomalyFfi dco_decode_box_autoadd_precision_anomaly_ffi(dynamic raw);
 
   @protected
-  int dco_decode_i_32(dynamic raw);
+  PrivacySettingsFfi dco_decode_box_autoadd_privacy_settings_ffi(dynamic raw);
 
   @protected
-  PlatformInt64 dco_decode_i_64(dynamic raw);
+  ProfileMetadataFfi dco_decode_box_autoadd_profile_metadata_ffi(dynamic raw);
 
   @protected
-  InvitationFfi dco_decode_invitation_ffi(dynamic raw);
+  ProximityEstimateFfi dco_decode_box_autoadd_proximity_estimate_ffi(
+    dynamic raw,
+  );
 
   @protected
-  KpMaintenanceActionFfi dco_decode_kp_maintenance_action_ffi(dynamic raw);
+  ProximityTargetFfi dco_decode_box_autoadd_proximity_target_ffi(dynamic raw);
 
   @protected
-  KpMaintenanceOutcomeFfi dco_decode_kp_maintenance_outcome_ffi(dynamic raw);
+  PublicIdentity dco_decode_box_autoadd_public_identity(dynamic raw);
 
   @protected
-  LastKnownLocationFfi dco_decode_last_known_location_ffi(dynamic raw);
+  PublishQuorumFfi dco_decode_box_autoadd_publish_quorum_ffi(dynamic raw);
 
   @protected
-  LeavePlanFfi dco_decode_leave_plan_ffi(dynamic raw);
+  RelayOverrideFfi dco_decode_box_autoadd_relay_override_ffi(dynamic raw);
 
   @protected
-  LeavePlanKindFfi dco_decode_leave_plan_kind_ffi(dynamic raw);
+  RemoteSignerFfi dco_decode_box_autoadd_remote_signer_ffi(dynamic raw);
 
   @protected
-  LegacyRetractionOutcomeFfi dco_decode_legacy_retraction_outcome_ffi(
+  SignedLocationEventFfi dco_decode_box_autoadd_signed_location_event_ffi(
     dynamic raw,
   );
 
   @protected
-  List<String> dco_decode_list_String(dynamic raw);
+  TileCacheEntryFfi dco_decode_box_autoadd_tile_cache_entry_ffi(dynamic raw);
 
   @protected
-  List<CircleMemberFfi> dco_decode_list_circle_member_ffi(dynamic raw);
+  TripModeFfi dco_decode_box_autoadd_trip_mode_ffi(dynamic raw);
 
   @protected
-  List<CircleWithMembersFfi> dco_decode_list_circle_with_members_ffi(
-    dynamic raw,
-  );
+  int dco_decode_box_autoadd_u_16(dynamic raw);
 
   @protected
-  List<CommitToPublishFfi> dco_decode_list_commit_to_publish_ffi(dynamic raw);
+  int dco_decode_box_autoadd_u_32(dynamic raw);
 
   @protected
-  List<ContactFfi> dco_decode_list_contact_ffi(dynamic raw);
+  BigInt dco_decode_box_autoadd_u_64(dynamic raw);
 
   @protected
-  List<FfiGroupSpec> dco_decode_list_ffi_group_spec(dynamic raw);
+  int dco_decode_box_autoadd_u_8(dynamic raw);
 
   @protected
-  List<GiftWrappedWelcomeFfi> dco_decode_list_gift_wrapped_welcome_ffi(
-    dynamic raw,
-  );
+  UserMessageFfi dco_decode_box_autoadd_user_message_ffi(dynamic raw);
 
   @protected
-  List<InvitationFfi> dco_decode_list_invitation_ffi(dynamic raw);
+  VerticalPositionFfi dco_decode_box_autoadd_vertical_position_ffi(dynamic raw);
 
   @protected
-  List<LastKnownLocationFfi> dco_decode_list_last_known_location_ffi(
-    dynamic raw,
-  );
+  BreadcrumbFfi dco_decode_breadcrumb_ffi(dynamic raw);
 
   @protected
-  List<List<String>> dco_decode_list_list_String(dynamic raw);
+  BuiltRelayListEventFfi dco_decode_built_relay_list_event_ffi(dynamic raw);
 
   @protected
-  List<LocationMessageResultFfi> dco_decode_list_location_message_result_ffi(
-    dynamic raw,
-  );
+  BuiltUnpublishFfi dco_decode_built_unpublish_ffi(dynamic raw);
 
   @protected
-  List<MemberKeyPackageFfi> dco_decode_list_member_key_package_ffi(dynamic raw);
+  CatchupResultFfi dco_decode_catchup_result_ffi(dynamic raw);
 
   @protected
-  List<int> dco_decode_list_prim_u_8_loose(dynamic raw);
+  CellSizeFfi dco_decode_cell_size_ffi(dynamic raw);
 
   @protected
-  Uint8List dco_decode_list_prim_u_8_strict(dynamic raw);
+  CheckinRuleFfi dco_decode_checkin_rule_ffi(dynamic raw);
 
   @protected
-  List<ProfileMetadataFfi> dco_decode_list_profile_metadata_ffi(dynamic raw);
+  CircleCreationResultFfi dco_decode_circle_creation_result_ffi(dynamic raw);
 
   @protected
-  List<RelayConnectionStatusFfi> dco_decode_list_relay_connection_status_ffi(
-    dynamic raw,
-  );
+  CircleFfi dco_decode_circle_ffi(dynamic raw);
 
   @protected
-  List<RelayGiftWrapFetchFfi> dco_decode_list_relay_gift_wrap_fetch_ffi(
+  CircleLocationSettingsFfi dco_decode_circle_location_settings_ffi(
     dynamic raw,
   );
 
   @protected
-  List<RelayRejectionFfi> dco_decode_list_relay_rejection_ffi(dynamic raw);
-
-  @protected
-  LocationMessageResultFfi dco_decode_location_message_result_ffi(dynamic raw);
+  CircleMemberFfi dco_decode_circle_member_ffi(dynamic raw);
 
   @protected
-  LocationMessageResultKindFfi dco_decode_location_message_result_kind_ffi(
-    dynamic raw,
-  );
+  CircleStatusFfi dco_decode_circle_status_ffi(dynamic raw);
 
   @protected
-  MemberKeyPackageFfi dco_decode_member_key_package_ffi(dynamic raw);
+  CircleSyncDigestFfi dco_decode_circle_sync_digest_ffi(dynamic raw);
 
   @protected
-  String? dco_decode_opt_String(dynamic raw);
+  CircleUsageFfi dco_decode_circle_usage_ffi(dynamic raw);
 
   @protected
-  CircleWithMembersFfi? dco_decode_opt_box_autoadd_circle_with_members_ffi(
-    dynamic raw,
-  );
+  CircleWithMembersFfi dco_decode_circle_with_members_ffi(dynamic raw);
 
   @protected
-  ContactFfi? dco_decode_opt_box_autoadd_contact_ffi(dynamic raw);
+  ColdCircleInfoFfi dco_decode_cold_circle_info_ffi(dynamic raw);
 
   @protected
-  DecryptedLocationFfi? dco_decode_opt_box_autoadd_decrypted_location_ffi(
-    dynamic raw,
-  );
+  CommitToPublishFfi dco_decode_commit_to_publish_ffi(dynamic raw);
 
   @protected
-  FfiSyncStatusReason? dco_decode_opt_box_autoadd_ffi_sync_status_reason(
-    dynamic raw,
-  );
+  CompactEventFfi dco_decode_compact_event_ffi(dynamic raw);
 
   @protected
-  PlatformInt64? dco_decode_opt_box_autoadd_i_64(dynamic raw);
+  ConsumedKeyPackageFfi dco_decode_consumed_key_package_ffi(dynamic raw);
 
   @protected
-  InvitationFfi? dco_decode_opt_box_autoadd_invitation_ffi(dynamic raw);
+  ContactFfi dco_decode_contact_ffi(dynamic raw);
 
   @protected
-  MemberKeyPackageFfi? dco_decode_opt_box_autoadd_member_key_package_ffi(
-    dynamic raw,
-  );
+  ContactLabelFfi dco_decode_contact_label_ffi(dynamic raw);
 
   @protected
-  ProfileMetadataFfi? dco_decode_opt_box_autoadd_profile_metadata_ffi(
-    dynamic raw,
-  );
+  DaySummaryFfi dco_decode_day_summary_ffi(dynamic raw);
 
   @protected
-  PublicIdentity? dco_decode_opt_box_autoadd_public_identity(dynamic raw);
+  DecryptBatchItemFfi dco_decode_decrypt_batch_item_ffi(dynamic raw);
 
   @protected
-  TileCacheEntryFfi? dco_decode_opt_box_autoadd_tile_cache_entry_ffi(
+  DecryptLocationOutcomeFfi dco_decode_decrypt_location_outcome_ffi(
     dynamic raw,
   );
 
   @protected
-  int? dco_decode_opt_box_autoadd_u_16(dynamic raw);
+  DecryptedLocationFfi dco_decode_decrypted_location_ffi(dynamic raw);
 
   @protected
-  int? dco_decode_opt_box_autoadd_u_32(dynamic raw);
+  DependentCircleFfi dco_decode_dependent_circle_ffi(dynamic raw);
 
   @protected
-  Uint8List? dco_decode_opt_list_prim_u_8_strict(dynamic raw);
+  DeviceStatusFfi dco_decode_device_status_ffi(dynamic raw);
 
   @protected
-  PendingStateRefFfi dco_decode_pending_state_ref_ffi(dynamic raw);
+  DistanceBucketFfi dco_decode_distance_bucket_ffi(dynamic raw);
 
   @protected
-  ProfileMetadataFfi dco_decode_profile_metadata_ffi(dynamic raw);
+  EncryptedLocationFfi dco_decode_encrypted_location_ffi(dynamic raw);
 
   @protected
-  ProfilePictureRefFfi dco_decode_profile_picture_ref_ffi(dynamic raw);
+  EnvironmentFfi dco_decode_environment_ffi(dynamic raw);
 
   @protected
-  PublicIdentity dco_decode_public_identity(dynamic raw);
+  EpochInfoFfi dco_decode_epoch_info_ffi(dynamic raw);
 
   @protected
-  PublishResultFfi dco_decode_publish_result_ffi(dynamic raw);
+  double dco_decode_f_64(dynamic raw);
 
   @protected
-  RelayConnectionStatusFfi dco_decode_relay_connection_status_ffi(dynamic raw);
+  FfiGroupSpec dco_decode_ffi_group_spec(dynamic raw);
 
   @protected
-  RelayEventCheckFfi dco_decode_relay_event_check_ffi(dynamic raw);
+  FfiRelayEvent dco_decode_ffi_relay_event(dynamic raw);
 
   @protected
-  RelayGiftWrapFetchFfi dco_decode_relay_gift_wrap_fetch_ffi(dynamic raw);
+  FfiRelayEventKind dco_decode_ffi_relay_event_kind(dynamic raw);
 
   @protected
-  RelayListActionFfi dco_decode_relay_list_action_ffi(dynamic raw);
+  FfiSyncStatusReason dco_decode_ffi_sync_status_reason(dynamic raw);
 
   @protected
-  RelayListCategoryOutcomeFfi dco_decode_relay_list_category_outcome_ffi(
-    dynamic raw,
-  );
+  FreshnessBucketFfi dco_decode_freshness_bucket_ffi(dynamic raw);
 
   @protected
-  RelayListMaintenanceOutcomeFfi dco_decode_relay_list_maintenance_outcome_ffi(
-    dynamic raw,
-  );
+  GeofenceFfi dco_decode_geofence_ffi(dynamic raw);
 
   @protected
-  RelayRejectionFfi dco_decode_relay_rejection_ffi(dynamic raw);
+  GeofenceOccupancyFfi dco_decode_geofence_occupancy_ffi(dynamic raw);
 
   @protected
-  RelayTypeFfi dco_decode_relay_type_ffi(dynamic raw);
+  GiftWrapOutcomeFfi dco_decode_gift_wrap_outcome_ffi(dynamic raw);
 
   @protected
-  SignedLocationEventFfi dco_decode_signed_location_event_ffi(dynamic raw);
+  GiftWrappedWelcomeFfi dco_decode_gift_wrapped_welcome_ffi(dynamic raw);
 
   @protected
-  SubscriptionHealthActionFfi dco_decode_subscription_health_action_ffi(
-    dynamic raw,
-  );
+  GlanceCircleFfi dco_decode_glance_circle_ffi(dynamic raw);
 
   @protected
-  SubscriptionHealthOutcomeFfi dco_decode_subscription_health_outcome_ffi(
-    dynamic raw,
-  );
+  GlanceMemberFfi dco_decode_glance_member_ffi(dynamic raw);
 
   @protected
-  TileCacheEntryFfi dco_decode_tile_cache_entry_ffi(dynamic raw);
+  GlanceableSnapshotFfi dco_decode_glanceable_snapshot_ffi(dynamic raw);
 
   @protected
-  int dco_decode_u_16(dynamic raw);
+  GroupHealthFfi dco_decode_group_health_ffi(dynamic raw);
 
   @protected
-  int dco_decode_u_32(dynamic raw);
+  GroupMessagePageFfi dco_decode_group_message_page_ffi(dynamic raw);
 
   @protected
-  BigInt dco_decode_u_64(dynamic raw);
+  GroupRepairFfi dco_decode_group_repair_ffi(dynamic raw);
 
   @protected
-  int dco_decode_u_8(dynamic raw);
+  HavenErrorFfi dco_decode_haven_error_ffi(dynamic raw);
 
   @protected
-  void dco_decode_unit(dynamic raw);
+  HavenPayloadFfi dco_decode_haven_payload_ffi(dynamic raw);
 
   @protected
-  UnsignedLocationEventFfi dco_decode_unsigned_location_event_ffi(dynamic raw);
+  HavenPayloadKindFfi dco_decode_haven_payload_kind_ffi(dynamic raw);
 
   @protected
-  BigInt dco_decode_usize(dynamic raw);
+  HistoryPointFfi dco_decode_history_point_ffi(dynamic raw);
 
   @protected
-  AnyhowException sse_decode_AnyhowException(SseDeserializer deserializer);
+  int dco_decode_i_16(dynamic raw);
 
   @protected
-  CircleManagerFfi
-  sse_decode_Auto_Owned_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerCircleManagerFfi(
-    SseDeserializer deserializer,
-  );
+  int dco_decode_i_32(dynamic raw);
 
   @protected
-  HavenCore
-  sse_decode_Auto_Owned_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerHavenCore(
-    SseDeserializer deserializer,
-  );
+  PlatformInt64 dco_decode_i_64(dynamic raw);
 
   @protected
-  LiveSyncFfi
-  sse_decode_Auto_Owned_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerLiveSyncFfi(
-    SseDeserializer deserializer,
+  IdentityDeletionReportFfi dco_decode_identity_deletion_report_ffi(
+    dynamic raw,
   );
 
   @protected
-  LocationEventService
-  sse_decode_Auto_Owned_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerLocationEventService(
-    SseDeserializer deserializer,
-  );
+  IdentityTeardownFfi dco_decode_identity_teardown_ffi(dynamic raw);
 
   @protected
-  LocationMessage
-  sse_decode_Auto_Owned_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerLocationMessage(
-    SseDeserializer deserializer,
-  );
+  InboxSyncSummaryFfi dco_decode_inbox_sync_summary_ffi(dynamic raw);
 
   @protected
-  LocationSettings
-  sse_decode_Auto_Owned_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerLocationSettings(
-    SseDeserializer deserializer,
-  );
+  InvitationFfi dco_decode_invitation_ffi(dynamic raw);
 
   @protected
-  NostrIdentityManager
-  sse_decode_Auto_Owned_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerNostrIdentityManager(
-    SseDeserializer deserializer,
-  );
+  InvitePayloadFfi dco_decode_invite_payload_ffi(dynamic raw);
 
   @protected
-  RelayManagerFfi
-  sse_decode_Auto_Owned_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerRelayManagerFfi(
-    SseDeserializer deserializer,
-  );
+  KpMaintenanceActionFfi dco_decode_kp_maintenance_action_ffi(dynamic raw);
 
   @protected
-  HavenCore
-  sse_decode_Auto_RefMut_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerHavenCore(
-    SseDeserializer deserializer,
-  );
+  KpMaintenanceOutcomeFfi dco_decode_kp_maintenance_outcome_ffi(dynamic raw);
 
   @protected
-  CircleManagerFfi
-  sse_decode_Auto_Ref_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerCircleManagerFfi(
-    SseDeserializer deserializer,
-  );
+  KpRelayStatusFfi dco_decode_kp_relay_status_ffi(dynamic raw);
 
   @protected
-  HavenCore
-  sse_decode_Auto_Ref_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerHavenCore(
-    SseDeserializer deserializer,
-  );
+  KpRelayVerificationFfi dco_decode_kp_relay_verification_ffi(dynamic raw);
 
   @protected
-  LiveSyncFfi
-  sse_decode_Auto_Ref_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerLiveSyncFfi(
-    SseDeserializer deserializer,
-  );
+  KpVerificationFfi dco_decode_kp_verification_ffi(dynamic raw);
 
   @protected
-  LocationEventService
-  sse_decode_Auto_Ref_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerLocationEventService(
-    SseDeserializer deserializer,
-  );
+  LabeledCircleCreationFfi dco_decode_labeled_circle_creation_ffi(dynamic raw);
 
   @protected
-  LocationMessage
-  sse_decode_Auto_Ref_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerLocationMessage(
-    SseDeserializer deserializer,
-  );
+  LastKnownLocationFfi dco_decode_last_known_location_ffi(dynamic raw);
 
   @protected
-  LocationSettings
-  sse_decode_Auto_Ref_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerLocationSettings(
-    SseDeserializer deserializer,
-  );
+  LeaveAllReportFfi dco_decode_leave_all_report_ffi(dynamic raw);
 
   @protected
-  NostrIdentityManager
-  sse_decode_Auto_Ref_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerNostrIdentityManager(
-    SseDeserializer deserializer,
-  );
+  LeaveEventFfi dco_decode_leave_event_ffi(dynamic raw);
 
   @protected
-  RelayManagerFfi
-  sse_decode_Auto_Ref_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerRelayManagerFfi(
-    SseDeserializer deserializer,
-  );
+  LeavePlanFfi dco_decode_leave_plan_ffi(dynamic raw);
 
   @protected
-  CircleManagerFfi
-  sse_decode_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerCircleManagerFfi(
-    SseDeserializer deserializer,
-  );
+  LeavePlanKindFfi dco_decode_leave_plan_kind_ffi(dynamic raw);
 
   @protected
-  HavenCore
-  sse_decode_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerHavenCore(
-    SseDeserializer deserializer,
+  LegacyRetractionOutcomeFfi dco_decode_legacy_retraction_outcome_ffi(
+    dynamic raw,
   );
 
   @protected
-  LiveSyncFfi
-  sse_decode_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerLiveSyncFfi(
-    SseDeserializer deserializer,
-  );
+  LinkedCircleFfi dco_decode_linked_circle_ffi(dynamic raw);
 
   @protected
-  LocationEventService
-  sse_decode_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerLocationEventService(
-    SseDeserializer deserializer,
-  );
+  List<String> dco_decode_list_String(dynamic raw);
 
   @protected
-  LocationMessage
-  sse_decode_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerLocationMessage(
-    SseDeserializer deserializer,
-  );
+  List<AudienceCircleFfi> dco_decode_list_audience_circle_ffi(dynamic raw);
 
   @protected
-  LocationSettings
-  sse_decode_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerLocationSettings(
-    SseDeserializer deserializer,
-  );
+  List<AudienceMemberFfi> dco_decode_list_audience_member_ffi(dynamic raw);
 
   @protected
-  NostrIdentityManager
-  sse_decode_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerNostrIdentityManager(
-    SseDeserializer deserializer,
-  );
+  List<BreadcrumbFfi> dco_decode_list_breadcrumb_ffi(dynamic raw);
 
   @protected
-  RelayManagerFfi
-  sse_decode_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerRelayManagerFfi(
-    SseDeserializer deserializer,
-  );
+  List<CheckinRuleFfi> dco_decode_list_checkin_rule_ffi(dynamic raw);
 
   @protected
-  RustStreamSink<FfiRelayEvent> sse_decode_StreamSink_ffi_relay_event_Sse(
-    SseDeserializer deserializer,
-  );
+  List<CircleMemberFfi> dco_decode_list_circle_member_ffi(dynamic raw);
 
   @protected
-  String sse_decode_String(SseDeserializer deserializer);
+  List<CircleSyncDigestFfi> dco_decode_list_circle_sync_digest_ffi(dynamic raw);
 
   @protected
-  AddMembersResultFfi sse_decode_add_members_result_ffi(
-    SseDeserializer deserializer,
+  List<CircleUsageFfi> dco_decode_list_circle_usage_ffi(dynamic raw);
+
+  @protected
+  List<CircleWithMembersFfi> dco_decode_list_circle_with_members_ffi(
+    dynamic raw,
   );
 
   @protected
-  bool sse_decode_bool(SseDeserializer deserializer);
+  List<CommitToPublishFfi> dco_decode_list_commit_to_publish_ffi(dynamic raw);
 
   @protected
-  CircleWithMembersFfi sse_decode_box_autoadd_circle_with_members_ffi(
-    SseDeserializer deserializer,
-  );
+  List<CompactEventFfi> dco_decode_list_compact_event_ffi(dynamic raw);
 
   @protected
-  ContactFfi sse_decode_box_autoadd_contact_ffi(SseDeserializer deserializer);
+  List<ContactFfi> dco_decode_list_contact_ffi(dynamic raw);
 
   @protected
-  DecryptedLocationFfi sse_decode_box_autoadd_decrypted_location_ffi(
-    SseDeserializer deserializer,
-  );
+  List<ContactLabelFfi> dco_decode_list_contact_label_ffi(dynamic raw);
 
   @protected
-  FfiGroupSpec sse_decode_box_autoadd_ffi_group_spec(
-    SseDeserializer deserializer,
-  );
+  List<DecryptBatchItemFfi> dco_decode_list_decrypt_batch_item_ffi(dynamic raw);
 
   @protected
-  FfiSyncStatusReason sse_decode_box_autoadd_ffi_sync_status_reason(
-    SseDeserializer deserializer,
+  List<DependentCircleFfi> dco_decode_list_dependent_circle_ffi(dynamic raw);
+
+  @protected
+  List<EncryptedLocationFfi> dco_decode_list_encrypted_location_ffi(
+    dynamic raw,
   );
 
   @protected
-  PlatformInt64 sse_decode_box_autoadd_i_64(SseDeserializer deserializer);
+  List<FfiGroupSpec> dco_decode_list_ffi_group_spec(dynamic raw);
 
   @protected
-  InvitationFfi sse_decode_box_autoadd_invitation_ffi(
-    SseDeserializer deserializer,
-  );
+  List<GeofenceFfi> dco_decode_list_geofence_ffi(dynamic raw);
 
   @protected
-  LastKnownLocationFfi sse_decode_box_autoadd_last_known_location_ffi(
-    SseDeserializer deserializer,
+  List<GeofenceOccupancyFfi> dco_decode_list_geofence_occupancy_ffi(
+    dynamic raw,
   );
 
   @protected
-  MemberKeyPackageFfi sse_decode_box_autoadd_member_key_package_ffi(
-    SseDeserializer deserializer,
-  );
+  List<GiftWrapOutcomeFfi> dco_decode_list_gift_wrap_outcome_ffi(dynamic raw);
 
   @protected
-  PendingStateRefFfi sse_decode_box_autoadd_pending_state_ref_ffi(
-    SseDeserializer deserializer,
+  List<GiftWrappedWelcomeFfi> dco_decode_list_gift_wrapped_welcome_ffi(
+    dynamic raw,
   );
 
   @protected
-  ProfileMetadataFfi sse_decode_box_autoadd_profile_metadata_ffi(
-    SseDeserializer deserializer,
-  );
+  List<GlanceCircleFfi> dco_decode_list_glance_circle_ffi(dynamic raw);
 
   @protected
-  PublicIdentity sse_decode_box_autoadd_public_identity(
-    SseDeserializer deserializer,
-  );
+  List<GlanceMemberFfi> dco_decode_list_glance_member_ffi(dynamic raw);
 
   @protected
-  SignedLocationEventFfi sse_decode_box_autoadd_signed_location_event_ffi(
-    SseDeserializer deserializer,
+  List<HistoryPointFfi> dco_decode_list_history_point_ffi(dynamic raw);
+
+  @protected
+  List<InvitationFfi> dco_decode_list_invitation_ffi(dynamic raw);
+
+  @protected
+  List<KpRelayVerificationFfi> dco_decode_list_kp_relay_verification_ffi(
+    dynamic raw,
   );
 
   @protected
-  TileCacheEntryFfi sse_decode_box_autoadd_tile_cache_entry_ffi(
-    SseDeserializer deserializer,
+  List<LastKnownLocationFfi> dco_decode_list_last_known_location_ffi(
+    dynamic raw,
   );
 
   @protected
-  int sse_decode_box_autoadd_u_16(SseDeserializer deserializer);
+  List<LeaveEventFfi> dco_decode_list_leave_event_ffi(dynamic raw);
 
   @protected
-  int sse_decode_box_autoadd_u_32(SseDeserializer deserializer);
+  List<LinkedCircleFfi> dco_decode_list_linked_circle_ffi(dynamic raw);
 
   @protected
-  BuiltRelayListEventFfi sse_decode_built_relay_list_event_ffi(
-    SseDeserializer deserializer,
-  );
+  List<List<String>> dco_decode_list_list_String(dynamic raw);
 
   @protected
-  BuiltUnpublishFfi sse_decode_built_unpublish_ffi(
-    SseDeserializer deserializer,
+  List<Uint8List> dco_decode_list_list_prim_u_8_strict(dynamic raw);
+
+  @protected
+  List<LocationMessageResultFfi> dco_decode_list_location_message_result_ffi(
+    dynamic raw,
   );
 
   @protected
-  CatchupResultFfi sse_decode_catchup_result_ffi(SseDeserializer deserializer);
+  List<MeetPinFfi> dco_decode_list_meet_pin_ffi(dynamic raw);
 
   @protected
-  CircleCreationResultFfi sse_decode_circle_creation_result_ffi(
-    SseDeserializer deserializer,
-  );
+  List<MemberKeyChangeFfi> dco_decode_list_member_key_change_ffi(dynamic raw);
 
   @protected
-  CircleFfi sse_decode_circle_ffi(SseDeserializer deserializer);
+  List<MemberKeyPackageFfi> dco_decode_list_member_key_package_ffi(dynamic raw);
 
   @protected
-  CircleMemberFfi sse_decode_circle_member_ffi(SseDeserializer deserializer);
+  List<MemberLocationFfi> dco_decode_list_member_location_ffi(dynamic raw);
 
   @protected
-  CircleWithMembersFfi sse_decode_circle_with_members_ffi(
-    SseDeserializer deserializer,
-  );
+  List<MemberPresenceFfi> dco_decode_list_member_presence_ffi(dynamic raw);
 
   @protected
-  CommitToPublishFfi sse_decode_commit_to_publish_ffi(
-    SseDeserializer deserializer,
-  );
+  List<MessageParamFfi> dco_decode_list_message_param_ffi(dynamic raw);
 
   @protected
-  ContactFfi sse_decode_contact_ffi(SseDeserializer deserializer);
+  List<MissedCheckinFfi> dco_decode_list_missed_checkin_ffi(dynamic raw);
 
   @protected
-  DecryptLocationOutcomeFfi sse_decode_decrypt_location_outcome_ffi(
-    SseDeserializer deserializer,
-  );
+  List<OneShotLocationFfi> dco_decode_list_one_shot_location_ffi(dynamic raw);
 
   @protected
-  DecryptedLocationFfi sse_decode_decrypted_location_ffi(
-    SseDeserializer deserializer,
-  );
+  List<OutboxEntryFfi> dco_decode_list_outbox_entry_ffi(dynamic raw);
 
   @protected
-  EncryptedLocationFfi sse_decode_encrypted_location_ffi(
-    SseDeserializer deserializer,
-  );
+  Uint16List dco_decode_list_prim_u_16_strict(dynamic raw);
 
   @protected
-  double sse_decode_f_64(SseDeserializer deserializer);
+  List<int> dco_decode_list_prim_u_8_loose(dynamic raw);
 
   @protected
-  FfiGroupSpec sse_decode_ffi_group_spec(SseDeserializer deserializer);
+  Uint8List dco_decode_list_prim_u_8_strict(dynamic raw);
 
   @protected
-  FfiRelayEvent sse_decode_ffi_relay_event(SseDeserializer deserializer);
+  List<ProfileMetadataFfi> dco_decode_list_profile_metadata_ffi(dynamic raw);
 
   @protected
-  FfiRelayEventKind sse_decode_ffi_relay_event_kind(
-    SseDeserializer deserializer,
-  );
+  List<ProximityAlertFfi> dco_decode_list_proximity_alert_ffi(dynamic raw);
 
   @protected
-  FfiSyncStatusReason sse_decode_ffi_sync_status_reason(
-    SseDeserializer deserializer,
-  );
+  List<ProximityRuleFfi> dco_decode_list_proximity_rule_ffi(dynamic raw);
 
   @protected
-  GiftWrappedWelcomeFfi sse_decode_gift_wrapped_welcome_ffi(
+  List<RejoinRequestFfi> dco_decode_list_rejoin_request_ffi(dynamic raw);
+
+  @protected
+  List<RelayConnectionStatusFfi> dco_decode_list_relay_connection_status_ffi(
+    dynamic raw,
+  );
+
+  @protected
+  List<RelayGiftWrapFetchFfi> dco_decode_list_relay_gift_wrap_fetch_ffi(
+    dynamic raw,
+  );
+
+  @protected
+  List<RelayMetricsFfi> dco_decode_list_relay_metrics_ffi(dynamic raw);
+
+  @protected
+  List<RelayOverrideEntryFfi> dco_decode_list_relay_override_entry_ffi(
+    dynamic raw,
+  );
+
+  @protected
+  List<RelayRejectionFfi> dco_decode_list_relay_rejection_ffi(dynamic raw);
+
+  @protected
+  List<RelayUsageFfi> dco_decode_list_relay_usage_ffi(dynamic raw);
+
+  @protected
+  List<SelfTestResultFfi> dco_decode_list_self_test_result_ffi(dynamic raw);
+
+  @protected
+  List<SharingSessionFfi> dco_decode_list_sharing_session_ffi(dynamic raw);
+
+  @protected
+  List<TripFfi> dco_decode_list_trip_ffi(dynamic raw);
+
+  @protected
+  List<UnjoinedMemberFfi> dco_decode_list_unjoined_member_ffi(dynamic raw);
+
+  @protected
+  List<WelcomeRelayDeliveryFfi> dco_decode_list_welcome_relay_delivery_ffi(
+    dynamic raw,
+  );
+
+  @protected
+  LocationMessageResultFfi dco_decode_location_message_result_ffi(dynamic raw);
+
+  @protected
+  LocationMessageResultKindFfi dco_decode_location_message_result_kind_ffi(
+    dynamic raw,
+  );
+
+  @protected
+  LocationPrecisionFfi dco_decode_location_precision_ffi(dynamic raw);
+
+  @protected
+  LocationRetractionFfi dco_decode_location_retraction_ffi(dynamic raw);
+
+  @protected
+  MeetPinFfi dco_decode_meet_pin_ffi(dynamic raw);
+
+  @protected
+  MemberKeyChangeFfi dco_decode_member_key_change_ffi(dynamic raw);
+
+  @protected
+  MemberKeyPackageFfi dco_decode_member_key_package_ffi(dynamic raw);
+
+  @protected
+  MemberLocationFfi dco_decode_member_location_ffi(dynamic raw);
+
+  @protected
+  MemberPresenceFfi dco_decode_member_presence_ffi(dynamic raw);
+
+  @protected
+  MembershipDeltaFfi dco_decode_membership_delta_ffi(dynamic raw);
+
+  @protected
+  MessageParamFfi dco_decode_message_param_ffi(dynamic raw);
+
+  @protected
+  MissedCheckinFfi dco_decode_missed_checkin_ffi(dynamic raw);
+
+  @protected
+  MotionStateFfi dco_decode_motion_state_ffi(dynamic raw);
+
+  @protected
+  Nip05ResolutionFfi dco_decode_nip05_resolution_ffi(dynamic raw);
+
+  @protected
+  NprofileFfi dco_decode_nprofile_ffi(dynamic raw);
+
+  @protected
+  OneShotLocationFfi dco_decode_one_shot_location_ffi(dynamic raw);
+
+  @protected
+  LinkedIdentityFfi?
+  dco_decode_opt_Auto_Owned_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerLinkedIdentityFfi(
+    dynamic raw,
+  );
+
+  @protected
+  String? dco_decode_opt_String(dynamic raw);
+
+  @protected
+  bool? dco_decode_opt_box_autoadd_bool(dynamic raw);
+
+  @protected
+  CircleLocationSettingsFfi?
+  dco_decode_opt_box_autoadd_circle_location_settings_ffi(dynamic raw);
+
+  @protected
+  CircleWithMembersFfi? dco_decode_opt_box_autoadd_circle_with_members_ffi(
+    dynamic raw,
+  );
+
+  @protected
+  ColdCircleInfoFfi? dco_decode_opt_box_autoadd_cold_circle_info_ffi(
+    dynamic raw,
+  );
+
+  @protected
+  ContactFfi? dco_decode_opt_box_autoadd_contact_ffi(dynamic raw);
+
+  @protected
+  DecryptLocationOutcomeFfi?
+  dco_decode_opt_box_autoadd_decrypt_location_outcome_ffi(dynamic raw);
+
+  @protected
+  DecryptedLocationFfi? dco_decode_opt_box_autoadd_decrypted_location_ffi(
+    dynamic raw,
+  );
+
+  @protected
+  DeviceStatusFfi? dco_decode_opt_box_autoadd_device_status_ffi(dynamic raw);
+
+  @protected
+  EncryptedLocationFfi? dco_decode_opt_box_autoadd_encrypted_location_ffi(
+    dynamic raw,
+  );
+
+  @protected
+  double? dco_decode_opt_box_autoadd_f_64(dynamic raw);
+
+  @protected
+  FfiSyncStatusReason? dco_decode_opt_box_autoadd_ffi_sync_status_reason(
+    dynamic raw,
+  );
+
+  @protected
+  GiftWrappedWelcomeFfi? dco_decode_opt_box_autoadd_gift_wrapped_welcome_ffi(
+    dynamic raw,
+  );
+
+  @protected
+  HavenErrorFfi? dco_decode_opt_box_autoadd_haven_error_ffi(dynamic raw);
+
+  @protected
+  int? dco_decode_opt_box_autoadd_i_16(dynamic raw);
+
+  @protected
+  PlatformInt64? dco_decode_opt_box_autoadd_i_64(dynamic raw);
+
+  @protected
+  InvitationFfi? dco_decode_opt_box_autoadd_invitation_ffi(dynamic raw);
+
+  @protected
+  KpVerificationFfi? dco_decode_opt_box_autoadd_kp_verification_ffi(
+    dynamic raw,
+  );
+
+  @protected
+  LastKnownLocationFfi? dco_decode_opt_box_autoadd_last_known_location_ffi(
+    dynamic raw,
+  );
+
+  @protected
+  LeaveEventFfi? dco_decode_opt_box_autoadd_leave_event_ffi(dynamic raw);
+
+  @protected
+  MeetPinFfi? dco_decode_opt_box_autoadd_meet_pin_ffi(dynamic raw);
+
+  @protected
+  MemberKeyPackageFfi? dco_decode_opt_box_autoadd_member_key_package_ffi(
+    dynamic raw,
+  );
+
+  @protected
+  MembershipDeltaFfi? dco_decode_opt_box_autoadd_membership_delta_ffi(
+    dynamic raw,
+  );
+
+  @protected
+  MotionStateFfi? dco_decode_opt_box_autoadd_motion_state_ffi(dynamic raw);
+
+  @protected
+  PrecisionAnomalyFfi? dco_decode_opt_box_autoadd_precision_anomaly_ffi(
+    dynamic raw,
+  );
+
+  @protected
+  ProfileMetadataFfi? dco_decode_opt_box_autoadd_profile_metadata_ffi(
+    dynamic raw,
+  );
+
+  @protected
+  ProximityEstimateFfi? dco_decode_opt_box_autoadd_proximity_estimate_ffi(
+    dynamic raw,
+  );
+
+  @protected
+  PublicIdentity? dco_decode_opt_box_autoadd_public_identity(dynamic raw);
+
+  @protected
+  RelayOverrideFfi? dco_decode_opt_box_autoadd_relay_override_ffi(dynamic raw);
+
+  @protected
+  RemoteSignerFfi? dco_decode_opt_box_autoadd_remote_signer_ffi(dynamic raw);
+
+  @protected
+  TileCacheEntryFfi? dco_decode_opt_box_autoadd_tile_cache_entry_ffi(
+    dynamic raw,
+  );
+
+  @protected
+  TripModeFfi? dco_decode_opt_box_autoadd_trip_mode_ffi(dynamic raw);
+
+  @protected
+  int? dco_decode_opt_box_autoadd_u_16(dynamic raw);
+
+  @protected
+  int? dco_decode_opt_box_autoadd_u_32(dynamic raw);
+
+  @protected
+  BigInt? dco_decode_opt_box_autoadd_u_64(dynamic raw);
+
+  @protected
+  int? dco_decode_opt_box_autoadd_u_8(dynamic raw);
+
+  @protected
+  UserMessageFfi? dco_decode_opt_box_autoadd_user_message_ffi(dynamic raw);
+
+  @protected
+  VerticalPositionFfi? dco_decode_opt_box_autoadd_vertical_position_ffi(
+    dynamic raw,
+  );
+
+  @protected
+  List<String>? dco_decode_opt_list_String(dynamic raw);
+
+  @protected
+  List<int>? dco_decode_opt_list_prim_u_8_loose(dynamic raw);
+
+  @protected
+  Uint8List? dco_decode_opt_list_prim_u_8_strict(dynamic raw);
+
+  @protected
+  OutboxEntryFfi dco_decode_outbox_entry_ffi(dynamic raw);
+
+  @protected
+  OutboxFlushFfi dco_decode_outbox_flush_ffi(dynamic raw);
+
+  @protected
+  PendingStateRefFfi dco_decode_pending_state_ref_ffi(dynamic raw);
+
+  @protected
+  PrecisionAnomalyFfi dco_decode_precision_anomaly_ffi(dynamic raw);
+
+  @protected
+  PrecisionClassFfi dco_decode_precision_class_ffi(dynamic raw);
+
+  @protected
+  PresenceStatusFfi dco_decode_presence_status_ffi(dynamic raw);
+
+  @protected
+  PrivacyFactsFfi dco_decode_privacy_facts_ffi(dynamic raw);
+
+  @protected
+  PrivacySettingsFfi dco_decode_privacy_settings_ffi(dynamic raw);
+
+  @protected
+  ProfileMetadataFfi dco_decode_profile_metadata_ffi(dynamic raw);
+
+  @protected
+  ProfilePictureRefFfi dco_decode_profile_picture_ref_ffi(dynamic raw);
+
+  @protected
+  ProximityAlertFfi dco_decode_proximity_alert_ffi(dynamic raw);
+
+  @protected
+  ProximityEstimateFfi dco_decode_proximity_estimate_ffi(dynamic raw);
+
+  @protected
+  ProximityRuleFfi dco_decode_proximity_rule_ffi(dynamic raw);
+
+  @protected
+  ProximityTargetFfi dco_decode_proximity_target_ffi(dynamic raw);
+
+  @protected
+  PublicIdentity dco_decode_public_identity(dynamic raw);
+
+  @protected
+  PublishQuorumFfi dco_decode_publish_quorum_ffi(dynamic raw);
+
+  @protected
+  PublishQuorumKindFfi dco_decode_publish_quorum_kind_ffi(dynamic raw);
+
+  @protected
+  PublishResultFfi dco_decode_publish_result_ffi(dynamic raw);
+
+  @protected
+  QuorumPublishUpdateFfi dco_decode_quorum_publish_update_ffi(dynamic raw);
+
+  @protected
+  RejoinRequestFfi dco_decode_rejoin_request_ffi(dynamic raw);
+
+  @protected
+  RelayConnectionStatusFfi dco_decode_relay_connection_status_ffi(dynamic raw);
+
+  @protected
+  RelayEventCheckFfi dco_decode_relay_event_check_ffi(dynamic raw);
+
+  @protected
+  RelayGiftWrapFetchFfi dco_decode_relay_gift_wrap_fetch_ffi(dynamic raw);
+
+  @protected
+  RelayInfoFfi dco_decode_relay_info_ffi(dynamic raw);
+
+  @protected
+  RelayListActionFfi dco_decode_relay_list_action_ffi(dynamic raw);
+
+  @protected
+  RelayListCategoryOutcomeFfi dco_decode_relay_list_category_outcome_ffi(
+    dynamic raw,
+  );
+
+  @protected
+  RelayListFlushFfi dco_decode_relay_list_flush_ffi(dynamic raw);
+
+  @protected
+  RelayListMaintenanceOutcomeFfi dco_decode_relay_list_maintenance_outcome_ffi(
+    dynamic raw,
+  );
+
+  @protected
+  RelayMetricsFfi dco_decode_relay_metrics_ffi(dynamic raw);
+
+  @protected
+  RelayOverrideEntryFfi dco_decode_relay_override_entry_ffi(dynamic raw);
+
+  @protected
+  RelayOverrideFfi dco_decode_relay_override_ffi(dynamic raw);
+
+  @protected
+  RelayRejectionFfi dco_decode_relay_rejection_ffi(dynamic raw);
+
+  @protected
+  RelayTypeFfi dco_decode_relay_type_ffi(dynamic raw);
+
+  @protected
+  RelayUsageFfi dco_decode_relay_usage_ffi(dynamic raw);
+
+  @protected
+  RemoteSignerFfi dco_decode_remote_signer_ffi(dynamic raw);
+
+  @protected
+  RepairOutcomeFfi dco_decode_repair_outcome_ffi(dynamic raw);
+
+  @protected
+  SelfTestCheckFfi dco_decode_self_test_check_ffi(dynamic raw);
+
+  @protected
+  SelfTestReportFfi dco_decode_self_test_report_ffi(dynamic raw);
+
+  @protected
+  SelfTestResultFfi dco_decode_self_test_result_ffi(dynamic raw);
+
+  @protected
+  SharePreviewFfi dco_decode_share_preview_ffi(dynamic raw);
+
+  @protected
+  SharingSessionFfi dco_decode_sharing_session_ffi(dynamic raw);
+
+  @protected
+  SignedLocationEventFfi dco_decode_signed_location_event_ffi(dynamic raw);
+
+  @protected
+  SosFanoutFfi dco_decode_sos_fanout_ffi(dynamic raw);
+
+  @protected
+  SubscriptionHealthActionFfi dco_decode_subscription_health_action_ffi(
+    dynamic raw,
+  );
+
+  @protected
+  SubscriptionHealthOutcomeFfi dco_decode_subscription_health_outcome_ffi(
+    dynamic raw,
+  );
+
+  @protected
+  SyncDigestFfi dco_decode_sync_digest_ffi(dynamic raw);
+
+  @protected
+  TileCacheEntryFfi dco_decode_tile_cache_entry_ffi(dynamic raw);
+
+  @protected
+  TransferRestoreFfi dco_decode_transfer_restore_ffi(dynamic raw);
+
+  @protected
+  TripFfi dco_decode_trip_ffi(dynamic raw);
+
+  @protected
+  TripModeFfi dco_decode_trip_mode_ffi(dynamic raw);
+
+  @protected
+  int dco_decode_u_16(dynamic raw);
+
+  @protected
+  int dco_decode_u_32(dynamic raw);
+
+  @protected
+  BigInt dco_decode_u_64(dynamic raw);
+
+  @protected
+  int dco_decode_u_8(dynamic raw);
+
+  @protected
+  void dco_decode_unit(dynamic raw);
+
+  @protected
+  UnjoinedMemberFfi dco_decode_unjoined_member_ffi(dynamic raw);
+
+  @protected
+  UnsignedLocationEventFfi dco_decode_unsigned_location_event_ffi(dynamic raw);
+
+  @protected
+  UsageFfi dco_decode_usage_ffi(dynamic raw);
+
+  @protected
+  UserMessageFfi dco_decode_user_message_ffi(dynamic raw);
+
+  @protected
+  BigInt dco_decode_usize(dynamic raw);
+
+  @protected
+  VerticalPositionFfi dco_decode_vertical_position_ffi(dynamic raw);
+
+  @protected
+  WelcomeDeliveryReportFfi dco_decode_welcome_delivery_report_ffi(dynamic raw);
+
+  @protected
+  WelcomeRelayDeliveryFfi dco_decode_welcome_relay_delivery_ffi(dynamic raw);
+
+  @protected
+  WelcomeRelayOutcomeFfi dco_decode_welcome_relay_outcome_ffi(dynamic raw);
+
+  @protected
+  AnyhowException sse_decode_AnyhowException(SseDeserializer deserializer);
+
+  @protected
+  CircleManagerFfi
+  sse_decode_Auto_Owned_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerCircleManagerFfi(
+    SseDeserializer deserializer,
+  );
+
+  @protected
+  DeviceLinkSessionFfi
+  sse_decode_Auto_Owned_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerDeviceLinkSessionFfi(
+    SseDeserializer deserializer,
+  );
+
+  @protected
+  ExternalSignerFfi
+  sse_decode_Auto_Owned_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerExternalSignerFfi(
+    SseDeserializer deserializer,
+  );
+
+  @protected
+  HavenCore
+  sse_decode_Auto_Owned_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerHavenCore(
+    SseDeserializer deserializer,
+  );
+
+  @protected
+  LinkedIdentityFfi
+  sse_decode_Auto_Owned_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerLinkedIdentityFfi(
+    SseDeserializer deserializer,
+  );
+
+  @protected
+  LiveSyncFfi
+  sse_decode_Auto_Owned_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerLiveSyncFfi(
+    SseDeserializer deserializer,
+  );
+
+  @protected
+  LocationEventService
+  sse_decode_Auto_Owned_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerLocationEventService(
+    SseDeserializer deserializer,
+  );
+
+  @protected
+  LocationMessage
+  sse_decode_Auto_Owned_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerLocationMessage(
+    SseDeserializer deserializer,
+  );
+
+  @protected
+  LocationSettings
+  sse_decode_Auto_Owned_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerLocationSettings(
+    SseDeserializer deserializer,
+  );
+
+  @protected
+  NostrIdentityManager
+  sse_decode_Auto_Owned_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerNostrIdentityManager(
+    SseDeserializer deserializer,
+  );
+
+  @protected
+  RelayManagerFfi
+  sse_decode_Auto_Owned_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerRelayManagerFfi(
+    SseDeserializer deserializer,
+  );
+
+  @protected
+  SecretHandle
+  sse_decode_Auto_Owned_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerSecretHandle(
+    SseDeserializer deserializer,
+  );
+
+  @protected
+  TransferBundleFfi
+  sse_decode_Auto_Owned_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerTransferBundleFfi(
+    SseDeserializer deserializer,
+  );
+
+  @protected
+  TransferContentsFfi
+  sse_decode_Auto_Owned_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerTransferContentsFfi(
+    SseDeserializer deserializer,
+  );
+
+  @protected
+  HavenCore
+  sse_decode_Auto_RefMut_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerHavenCore(
+    SseDeserializer deserializer,
+  );
+
+  @protected
+  LocationSettings
+  sse_decode_Auto_RefMut_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerLocationSettings(
+    SseDeserializer deserializer,
+  );
+
+  @protected
+  CircleManagerFfi
+  sse_decode_Auto_Ref_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerCircleManagerFfi(
+    SseDeserializer deserializer,
+  );
+
+  @protected
+  DeviceLinkSessionFfi
+  sse_decode_Auto_Ref_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerDeviceLinkSessionFfi(
+    SseDeserializer deserializer,
+  );
+
+  @protected
+  ExternalSignerFfi
+  sse_decode_Auto_Ref_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerExternalSignerFfi(
+    SseDeserializer deserializer,
+  );
+
+  @protected
+  HavenCore
+  sse_decode_Auto_Ref_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerHavenCore(
+    SseDeserializer deserializer,
+  );
+
+  @protected
+  LinkedIdentityFfi
+  sse_decode_Auto_Ref_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerLinkedIdentityFfi(
+    SseDeserializer deserializer,
+  );
+
+  @protected
+  LiveSyncFfi
+  sse_decode_Auto_Ref_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerLiveSyncFfi(
+    SseDeserializer deserializer,
+  );
+
+  @protected
+  LocationEventService
+  sse_decode_Auto_Ref_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerLocationEventService(
+    SseDeserializer deserializer,
+  );
+
+  @protected
+  LocationMessage
+  sse_decode_Auto_Ref_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerLocationMessage(
+    SseDeserializer deserializer,
+  );
+
+  @protected
+  LocationSettings
+  sse_decode_Auto_Ref_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerLocationSettings(
+    SseDeserializer deserializer,
+  );
+
+  @protected
+  NostrIdentityManager
+  sse_decode_Auto_Ref_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerNostrIdentityManager(
+    SseDeserializer deserializer,
+  );
+
+  @protected
+  RelayManagerFfi
+  sse_decode_Auto_Ref_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerRelayManagerFfi(
+    SseDeserializer deserializer,
+  );
+
+  @protected
+  SecretHandle
+  sse_decode_Auto_Ref_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerSecretHandle(
+    SseDeserializer deserializer,
+  );
+
+  @protected
+  TransferBundleFfi
+  sse_decode_Auto_Ref_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerTransferBundleFfi(
+    SseDeserializer deserializer,
+  );
+
+  @protected
+  TransferContentsFfi
+  sse_decode_Auto_Ref_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerTransferContentsFfi(
+    SseDeserializer deserializer,
+  );
+
+  @protected
+  FutureOr<String> Function(String, String)
+  sse_decode_DartFn_Inputs_String_String_Output_String_AnyhowException(
+    SseDeserializer deserializer,
+  );
+
+  @protected
+  FutureOr<String> Function(Uint8List)
+  sse_decode_DartFn_Inputs_list_prim_u_8_strict_Output_String_AnyhowException(
+    SseDeserializer deserializer,
+  );
+
+  @protected
+  Object sse_decode_DartOpaque(SseDeserializer deserializer);
+
+  @protected
+  CircleManagerFfi
+  sse_decode_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerCircleManagerFfi(
+    SseDeserializer deserializer,
+  );
+
+  @protected
+  DeviceLinkSessionFfi
+  sse_decode_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerDeviceLinkSessionFfi(
+    SseDeserializer deserializer,
+  );
+
+  @protected
+  ExternalSignerFfi
+  sse_decode_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerExternalSignerFfi(
+    SseDeserializer deserializer,
+  );
+
+  @protected
+  HavenCore
+  sse_decode_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerHavenCore(
+    SseDeserializer deserializer,
+  );
+
+  @protected
+  LinkedIdentityFfi
+  sse_decode_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerLinkedIdentityFfi(
+    SseDeserializer deserializer,
+  );
+
+  @protected
+  LiveSyncFfi
+  sse_decode_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerLiveSyncFfi(
+    SseDeserializer deserializer,
+  );
+
+  @protected
+  LocationEventService
+  sse_decode_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerLocationEventService(
+    SseDeserializer deserializer,
+  );
+
+  @protected
+  LocationMessage
+  sse_decode_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerLocationMessage(
+    SseDeserializer deserializer,
+  );
+
+  @protected
+  LocationSettings
+  sse_decode_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerLocationSettings(
+    SseDeserializer deserializer,
+  );
+
+  @protected
+  NostrIdentityManager
+  sse_decode_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerNostrIdentityManager(
+    SseDeserializer deserializer,
+  );
+
+  @protected
+  RelayManagerFfi
+  sse_decode_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerRelayManagerFfi(
+    SseDeserializer deserializer,
+  );
+
+  @protected
+  SecretHandle
+  sse_decode_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerSecretHandle(
+    SseDeserializer deserializer,
+  );
+
+  @protected
+  TransferBundleFfi
+  sse_decode_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerTransferBundleFfi(
+    SseDeserializer deserializer,
+  );
+
+  @protected
+  TransferContentsFfi
+  sse_decode_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerTransferContentsFfi(
+    SseDeserializer deserializer,
+  );
+
+  @protected
+  RustStreamSink<DecryptedLocationFfi>
+  sse_decode_StreamSink_decrypted_location_ffi_Sse(
+    SseDeserializer deserializer,
+  );
+
+  @protected
+  RustStreamSink<FfiRelayEvent> sse_decode_StreamSink_ffi_relay_event_Sse(
+    SseDeserializer deserializer,
+  );
+
+  @protected
+  RustStreamSink<QuorumPublishUpdateFfi>
+  sse_decode_StreamSink_quorum_publish_update_ffi_Sse(
+    SseDeserializer deserializer,
+  );
+
+  @protected
+  String sse_decode_String(SseDeserializer deserializer);
+
+  @protected
+  AddMembersResultFfi sse_decode_add_members_result_ffi(
+    SseDeserializer deserializer,
+  );
+
+  @protected
+  AudienceCircleFfi sse_decode_audience_circle_ffi(
+    SseDeserializer deserializer,
+  );
+
+  @protected
+  AudienceMemberFfi sse_decode_audience_member_ffi(
+    SseDeserializer deserializer,
+  );
+
+  @protected
+  BackfillDigestFfi sse_decode_backfill_digest_ffi(
+    SseDeserializer deserializer,
+  );
+
+  @protected
+  BandwidthUsageFfi sse_decode_bandwidth_usage_ffi(
+    SseDeserializer deserializer,
+  );
+
+  @protected
+  bool sse_decode_bool(SseDeserializer deserializer);
+
+  @protected
+  bool sse_decode_box_autoadd_bool(SseDeserializer deserializer);
+
+  @protected
+  CircleLocationSettingsFfi sse_decode_box_autoadd_circle_location_settings_ffi(
+    SseDeserializer deserializer,
+  );
+
+  @protected
+  CircleWithMembersFfi sse_decode_box_autoadd_circle_with_members_ffi(
+    SseDeserializer deserializer,
+  );
+
+  @protected
+  ColdCircleInfoFfi sse_decode_box_autoadd_cold_circle_info_ffi(
+    SseDeserializer deserializer,
+  );
+
+  @protected
+  CompactEventFfi sse_decode_box_autoadd_compact_event_ffi(
+    SseDeserializer deserializer,
+  );
+
+  @protected
+  ContactFfi sse_decode_box_autoadd_contact_ffi(SseDeserializer deserializer);
+
+  @protected
+  DecryptLocationOutcomeFfi sse_decode_box_autoadd_decrypt_location_outcome_ffi(
+    SseDeserializer deserializer,
+  );
+
+  @protected
+  DecryptedLocationFfi sse_decode_box_autoadd_decrypted_location_ffi(
+    SseDeserializer deserializer,
+  );
+
+  @protected
+  DeviceStatusFfi sse_decode_box_autoadd_device_status_ffi(
+    SseDeserializer deserializer,
+  );
+
+  @protected
+  EncryptedLocationFfi sse_decode_box_autoadd_encrypted_location_ffi(
+    SseDeserializer deserializer,
+  );
+
+  @protected
+  double sse_decode_box_autoadd_f_64(SseDeserializer deserializer);
+
+  @protected
+  FfiGroupSpec sse_decode_box_autoadd_ffi_group_spec(
+    SseDeserializer deserializer,
+  );
+
+  @protected
+  FfiSyncStatusReason sse_decode_box_autoadd_ffi_sync_status_reason(
+    SseDeserializer deserializer,
+  );
+
+  @protected
+  GiftWrappedWelcomeFfi sse_decode_box_autoadd_gift_wrapped_welcome_ffi(
+    SseDeserializer deserializer,
+  );
+
+  @protected
+  HavenErrorFfi sse_decode_box_autoadd_haven_error_ffi(
+    SseDeserializer deserializer,
+  );
+
+  @protected
+  int sse_decode_box_autoadd_i_16(SseDeserializer deserializer);
+
+  @protected
+  PlatformInt64 sse_decode_box_autoadd_i_64(SseDeserializer deserializer);
+
+  @protected
+  InvitationFfi sse_decode_box_autoadd_invitation_ffi(
+    SseDeserializer deserializer,
+  );
+
+  @protected
+  KpVerificationFfi sse_decode_box_autoadd_kp_verification_ffi(
+    SseDeserializer deserializer,
+  );
+
+  @protected
+  LastKnownLocationFfi sse_decode_box_autoadd_last_known_location_ffi(
+    SseDeserializer deserializer,
+  );
+
+  @protected
+  LeaveEventFfi sse_decode_box_autoadd_leave_event_ffi(
+    SseDeserializer deserializer,
+  );
+
+  @protected
+  MeetPinFfi sse_decode_box_autoadd_meet_pin_ffi(SseDeserializer deserializer);
+
+  @protected
+  MemberKeyPackageFfi sse_decode_box_autoadd_member_key_package_ffi(
+    SseDeserializer deserializer,
+  );
+
+  @protected
+  MembershipDeltaFfi sse_decode_box_autoadd_membership_delta_ffi(
+    SseDeserializer deserializer,
+  );
+
+  @protected
+  MotionStateFfi sse_decode_box_autoadd_motion_state_ffi(
+    SseDeserializer deserializer,
+  );
+
+  @protected
+  PendingStateRefFfi sse_decode_box_autoadd_pending_state_ref_ffi(
+    SseDeserializer deserializer,
+  );
+
+  @protected
+  PrecisionAnomalyFfi sse_decode_box_autoadd_precision_anomaly_ffi(
+    SseDeserializer deserializer,
+  );
+
+  @protected
+  PrivacySettingsFfi sse_decode_box_autoadd_privacy_settings_ffi(
+    SseDeserializer deserializer,
+  );
+
+  @protected
+  ProfileMetadataFfi sse_decode_box_autoadd_profile_metadata_ffi(
+    SseDeserializer deserializer,
+  );
+
+  @protected
+  ProximityEstimateFfi sse_decode_box_autoadd_proximity_estimate_ffi(
+    SseDeserializer deserializer,
+  );
+
+  @protected
+  ProximityTargetFfi sse_decode_box_autoadd_proximity_target_ffi(
+    SseDeserializer deserializer,
+  );
+
+  @protected
+  PublicIdentity sse_decode_box_autoadd_public_identity(
+    SseDeserializer deserializer,
+  );
+
+  @protected
+  PublishQuorumFfi sse_decode_box_autoadd_publish_quorum_ffi(
+    SseDeserializer deserializer,
+  );
+
+  @protected
+  RelayOverrideFfi sse_decode_box_autoadd_relay_override_ffi(
+    SseDeserializer deserializer,
+  );
+
+  @protected
+  RemoteSignerFfi sse_decode_box_autoadd_remote_signer_ffi(
+    SseDeserializer deserializer,
+  );
+
+  @protected
+  SignedLocationEventFfi sse_decode_box_autoadd_signed_location_event_ffi(
+    SseDeserializer deserializer,
+  );
+
+  @protected
+  TileCacheEntryFfi sse_decode_box_autoadd_tile_cache_entry_ffi(
+    SseDeserializer deserializer,
+  );
+
+  @protected
+  TripModeFfi sse_decode_box_autoadd_trip_mode_ffi(
+    SseDeserializer deserializer,
+  );
+
+  @protected
+  int sse_decode_box_autoadd_u_16(SseDeserializer deserializer);
+
+  @protected
+  int sse_decode_box_autoadd_u_32(SseDeserializer deserializer);
+
+  @protected
+  BigInt sse_decode_box_autoadd_u_64(SseDeserializer deserializer);
+
+  @protected
+  int sse_decode_box_autoadd_u_8(SseDeserializer deserializer);
+
+  @protected
+  UserMessageFfi sse_decode_box_autoadd_user_message_ffi(
+    SseDeserializer deserializer,
+  );
+
+  @protected
+  VerticalPositionFfi sse_decode_box_autoadd_vertical_position_ffi(
+    SseDeserializer deserializer,
+  );
+
+  @protected
+  BreadcrumbFfi sse_decode_breadcrumb_ffi(SseDeserializer deserializer);
+
+  @protected
+  BuiltRelayListEventFfi sse_decode_built_relay_list_event_ffi(
+    SseDeserializer deserializer,
+  );
+
+  @protected
+  BuiltUnpublishFfi sse_decode_built_unpublish_ffi(
+    SseDeserializer deserializer,
+  );
+
+  @protected
+  CatchupResultFfi sse_decode_catchup_result_ffi(SseDeserializer deserializer);
+
+  @protected
+  CellSizeFfi sse_decode_cell_size_ffi(SseDeserializer deserializer);
+
+  @protected
+  CheckinRuleFfi sse_decode_checkin_rule_ffi(SseDeserializer deserializer);
+
+  @protected
+  CircleCreationResultFfi sse_decode_circle_creation_result_ffi(
+    SseDeserializer deserializer,
+  );
+
+  @protected
+  CircleFfi sse_decode_circle_ffi(SseDeserializer deserializer);
+
+  @protected
+  CircleLocationSettingsFfi sse_decode_circle_location_settings_ffi(
+    SseDeserializer deserializer,
+  );
+
+  @protected
+  CircleMemberFfi sse_decode_circle_member_ffi(SseDeserializer deserializer);
+
+  @protected
+  CircleStatusFfi sse_decode_circle_status_ffi(SseDeserializer deserializer);
+
+  @protected
+  CircleSyncDigestFfi sse_decode_circle_sync_digest_ffi(
+    SseDeserializer deserializer,
+  );
+
+  @protected
+  CircleUsageFfi sse_decode_circle_usage_ffi(SseDeserializer deserializer);
+
+  @protected
+  CircleWithMembersFfi sse_decode_circle_with_members_ffi(
+    SseDeserializer deserializer,
+  );
+
+  @protected
+  ColdCircleInfoFfi sse_decode_cold_circle_info_ffi(
+    SseDeserializer deserializer,
+  );
+
+  @protected
+  CommitToPublishFfi sse_decode_commit_to_publish_ffi(
+    SseDeserializer deserializer,
+  );
+
+  @protected
+  CompactEventFfi sse_decode_compact_event_ffi(SseDeserializer deserializer);
+
+  @protected
+  ConsumedKeyPackageFfi sse_decode_consumed_key_package_ffi(
+    SseDeserializer deserializer,
+  );
+
+  @protected
+  ContactFfi sse_decode_contact_ffi(SseDeserializer deserializer);
+
+  @protected
+  ContactLabelFfi sse_decode_contact_label_ffi(SseDeserializer deserializer);
+
+  @protected
+  DaySummaryFfi sse_decode_day_summary_ffi(SseDeserializer deserializer);
+
+  @protected
+  DecryptBatchItemFfi sse_decode_decrypt_batch_item_ffi(
+    SseDeserializer deserializer,
+  );
+
+  @protected
+  DecryptLocationOutcomeFfi sse_decode_decrypt_location_outcome_ffi(
+    SseDeserializer deserializer,
+  );
+
+  @protected
+  DecryptedLocationFfi sse_decode_decrypted_location_ffi(
+    SseDeserializer deserializer,
+  );
+
+  @protected
+  DependentCircleFfi sse_decode_dependent_circle_ffi(
+    SseDeserializer deserializer,
+  );
+
+  @protected
+  DeviceStatusFfi sse_decode_device_status_ffi(SseDeserializer deserializer);
+
+  @protected
+  DistanceBucketFfi sse_decode_distance_bucket_ffi(
+    SseDeserializer deserializer,
+  );
+
+  @protected
+  EncryptedLocationFfi sse_decode_encrypted_location_ffi(
+    SseDeserializer deserializer,
+  );
+
+  @protected
+  EnvironmentFfi sse_decode_environment_ffi(SseDeserializer deserializer);
+
+  @protected
+  EpochInfoFfi sse_decode_epoch_info_ffi(SseDeserializer deserializer);
+
+  @protected
+  double sse_decode_f_64(SseDeserializer deserializer);
+
+  @protected
+  FfiGroupSpec sse_decode_ffi_group_spec(SseDeserializer deserializer);
+
+  @protected
+  FfiRelayEvent sse_decode_ffi_relay_event(SseDeserializer deserializer);
+
+  @protected
+  FfiRelayEventKind sse_decode_ffi_relay_event_kind(
+    SseDeserializer deserializer,
+  );
+
+  @protected
+  FfiSyncStatusReason sse_decode_ffi_sync_status_reason(
+    SseDeserializer deserializer,
+  );
+
+  @protected
+  FreshnessBucketFfi sse_decode_freshness_bucket_ffi(
+    SseDeserializer deserializer,
+  );
+
+  @protected
+  GeofenceFfi sse_decode_geofence_ffi(SseDeserializer deserializer);
+
+  @protected
+  GeofenceOccupancyFfi sse_decode_geofence_occupancy_ffi(
+    SseDeserializer deserializer,
+  );
+
+  @protected
+  GiftWrapOutcomeFfi sse_decode_gift_wrap_outcome_ffi(
+    SseDeserializer deserializer,
+  );
+
+  @protected
+  GiftWrappedWelcomeFfi sse_decode_gift_wrapped_welcome_ffi(
+    SseDeserializer deserializer,
+  );
+
+  @protected
+  GlanceCircleFfi sse_decode_glance_circle_ffi(SseDeserializer deserializer);
+
+  @protected
+  GlanceMemberFfi sse_decode_glance_member_ffi(SseDeserializer deserializer);
+
+  @protected
+  GlanceableSnapshotFfi sse_decode_glanceable_snapshot_ffi(
+    SseDeserializer deserializer,
+  );
+
+  @protected
+  GroupHealthFfi sse_decode_group_health_ffi(SseDeserializer deserializer);
+
+  @protected
+  GroupMessagePageFfi sse_decode_group_message_page_ffi(
+    SseDeserializer deserializer,
+  );
+
+  @protected
+  GroupRepairFfi sse_decode_group_repair_ffi(SseDeserializer deserializer);
+
+  @protected
+  HavenErrorFfi sse_decode_haven_error_ffi(SseDeserializer deserializer);
+
+  @protected
+  HavenPayloadFfi sse_decode_haven_payload_ffi(SseDeserializer deserializer);
+
+  @protected
+  HavenPayloadKindFfi sse_decode_haven_payload_kind_ffi(
+    SseDeserializer deserializer,
+  );
+
+  @protected
+  HistoryPointFfi sse_decode_history_point_ffi(SseDeserializer deserializer);
+
+  @protected
+  int sse_decode_i_16(SseDeserializer deserializer);
+
+  @protected
+  int sse_decode_i_32(SseDeserializer deserializer);
+
+  @protected
+  PlatformInt64 sse_decode_i_64(SseDeserializer deserializer);
+
+  @protected
+  IdentityDeletionReportFfi sse_decode_identity_deletion_report_ffi(
+    SseDeserializer deserializer,
+  );
+
+  @protected
+  IdentityTeardownFfi sse_decode_identity_teardown_ffi(
+    SseDeserializer deserializer,
+  );
+
+  @protected
+  InboxSyncSummaryFfi sse_decode_inbox_sync_summary_ffi(
+    SseDeserializer deserializer,
+  );
+
+  @protected
+  InvitationFfi sse_decode_invitation_ffi(SseDeserializer deserializer);
+
+  @protected
+  InvitePayloadFfi sse_decode_invite_payload_ffi(SseDeserializer deserializer);
+
+  @protected
+  KpMaintenanceActionFfi sse_decode_kp_maintenance_action_ffi(
+    SseDeserializer deserializer,
+  );
+
+  @protected
+  KpMaintenanceOutcomeFfi sse_decode_kp_maintenance_outcome_ffi(
+    SseDeserializer deserializer,
+  );
+
+  @protected
+  KpRelayStatusFfi sse_decode_kp_relay_status_ffi(SseDeserializer deserializer);
+
+  @protected
+  KpRelayVerificationFfi sse_decode_kp_relay_verification_ffi(
+    SseDeserializer deserializer,
+  );
+
+  @protected
+  KpVerificationFfi sse_decode_kp_verification_ffi(
+    SseDeserializer deserializer,
+  );
+
+  @protected
+  LabeledCircleCreationFfi sse_decode_labeled_circle_creation_ffi(
+    SseDeserializer deserializer,
+  );
+
+  @protected
+  LastKnownLocationFfi sse_decode_last_known_location_ffi(
+    SseDeserializer deserializer,
+  );
+
+  @protected
+  LeaveAllReportFfi sse_decode_leave_all_report_ffi(
+    SseDeserializer deserializer,
+  );
+
+  @protected
+  LeaveEventFfi sse_decode_leave_event_ffi(SseDeserializer deserializer);
+
+  @protected
+  LeavePlanFfi sse_decode_leave_plan_ffi(SseDeserializer deserializer);
+
+  @protected
+  LeavePlanKindFfi sse_decode_leave_plan_kind_ffi(SseDeserializer deserializer);
+
+  @protected
+  LegacyRetractionOutcomeFfi sse_decode_legacy_retraction_outcome_ffi(
+    SseDeserializer deserializer,
+  );
+
+  @protected
+  LinkedCircleFfi sse_decode_linked_circle_ffi(SseDeserializer deserializer);
+
+  @protected
+  List<String> sse_decode_list_String(SseDeserializer deserializer);
+
+  @protected
+  List<AudienceCircleFfi> sse_decode_list_audience_circle_ffi(
+    SseDeserializer deserializer,
+  );
+
+  @protected
+  List<AudienceMemberFfi> sse_decode_list_audience_member_ffi(
+    SseDeserializer deserializer,
+  );
+
+  @protected
+  List<BreadcrumbFfi> sse_decode_list_breadcrumb_ffi(
+    SseDeserializer deserializer,
+  );
+
+  @protected
+  List<CheckinRuleFfi> sse_decode_list_checkin_rule_ffi(
+    SseDeserializer deserializer,
+  );
+
+  @protected
+  List<CircleMemberFfi> sse_decode_list_circle_member_ffi(
+    SseDeserializer deserializer,
+  );
+
+  @protected
+  List<CircleSyncDigestFfi> sse_decode_list_circle_sync_digest_ffi(
+    SseDeserializer deserializer,
+  );
+
+  @protected
+  List<CircleUsageFfi> sse_decode_list_circle_usage_ffi(
+    SseDeserializer deserializer,
+  );
+
+  @protected
+  List<CircleWithMembersFfi> sse_decode_list_circle_with_members_ffi(
+    SseDeserializer deserializer,
+  );
+
+  @protected
+  List<CommitToPublishFfi> sse_decode_list_commit_to_publish_ffi(
+    SseDeserializer deserializer,
+  );
+
+  @protected
+  List<CompactEventFfi> sse_decode_list_compact_event_ffi(
+    SseDeserializer deserializer,
+  );
+
+  @protected
+  List<ContactFfi> sse_decode_list_contact_ffi(SseDeserializer deserializer);
+
+  @protected
+  List<ContactLabelFfi> sse_decode_list_contact_label_ffi(
+    SseDeserializer deserializer,
+  );
+
+  @protected
+  List<DecryptBatchItemFfi> sse_decode_list_decrypt_batch_item_ffi(
+    SseDeserializer deserializer,
+  );
+
+  @protected
+  List<DependentCircleFfi> sse_decode_list_dependent_circle_ffi(
+    SseDeserializer deserializer,
+  );
+
+  @protected
+  List<EncryptedLocationFfi> sse_decode_list_encrypted_location_ffi(
+    SseDeserializer deserializer,
+  );
+
+  @protected
+  List<FfiGroupSpec> sse_decode_list_ffi_group_spec(
+    SseDeserializer deserializer,
+  );
+
+  @protected
+  List<GeofenceFfi> sse_decode_list_geofence_ffi(SseDeserializer deserializer);
+
+  @protected
+  List<GeofenceOccupancyFfi> sse_decode_list_geofence_occupancy_ffi(
+    SseDeserializer deserializer,
+  );
+
+  @protected
+  List<GiftWrapOutcomeFfi> sse_decode_list_gift_wrap_outcome_ffi(
+    SseDeserializer deserializer,
+  );
+
+  @protected
+  List<GiftWrappedWelcomeFfi> sse_decode_list_gift_wrapped_welcome_ffi(
+    SseDeserializer deserializer,
+  );
+
+  @protected
+  List<GlanceCircleFfi> sse_decode_list_glance_circle_ffi(
+    SseDeserializer deserializer,
+  );
+
+  @protected
+  List<GlanceMemberFfi> sse_decode_list_glance_member_ffi(
+    SseDeserializer deserializer,
+  );
+
+  @protected
+  List<HistoryPointFfi> sse_decode_list_history_point_ffi(
+    SseDeserializer deserializer,
+  );
+
+  @protected
+  List<InvitationFfi> sse_decode_list_invitation_ffi(
+    SseDeserializer deserializer,
+  );
+
+  @protected
+  List<KpRelayVerificationFfi> sse_decode_list_kp_relay_verification_ffi(
+    SseDeserializer deserializer,
+  );
+
+  @protected
+  List<LastKnownLocationFfi> sse_decode_list_last_known_location_ffi(
+    SseDeserializer deserializer,
+  );
+
+  @protected
+  List<LeaveEventFfi> sse_decode_list_leave_event_ffi(
+    SseDeserializer deserializer,
+  );
+
+  @protected
+  List<LinkedCircleFfi> sse_decode_list_linked_circle_ffi(
+    SseDeserializer deserializer,
+  );
+
+  @protected
+  List<List<String>> sse_decode_list_list_String(SseDeserializer deserializer);
+
+  @protected
+  List<Uint8List> sse_decode_list_list_prim_u_8_strict(
+    SseDeserializer deserializer,
+  );
+
+  @protected
+  List<LocationMessageResultFfi> sse_decode_list_location_message_result_ffi(
+    SseDeserializer deserializer,
+  );
+
+  @protected
+  List<MeetPinFfi> sse_decode_list_meet_pin_ffi(SseDeserializer deserializer);
+
+  @protected
+  List<MemberKeyChangeFfi> sse_decode_list_member_key_change_ffi(
+    SseDeserializer deserializer,
+  );
+
+  @protected
+  List<MemberKeyPackageFfi> sse_decode_list_member_key_package_ffi(
+    SseDeserializer deserializer,
+  );
+
+  @protected
+  List<MemberLocationFfi> sse_decode_list_member_location_ffi(
+    SseDeserializer deserializer,
+  );
+
+  @protected
+  List<MemberPresenceFfi> sse_decode_list_member_presence_ffi(
+    SseDeserializer deserializer,
+  );
+
+  @protected
+  List<MessageParamFfi> sse_decode_list_message_param_ffi(
+    SseDeserializer deserializer,
+  );
+
+  @protected
+  List<MissedCheckinFfi> sse_decode_list_missed_checkin_ffi(
+    SseDeserializer deserializer,
+  );
+
+  @protected
+  List<OneShotLocationFfi> sse_decode_list_one_shot_location_ffi(
+    SseDeserializer deserializer,
+  );
+
+  @protected
+  List<OutboxEntryFfi> sse_decode_list_outbox_entry_ffi(
+    SseDeserializer deserializer,
+  );
+
+  @protected
+  Uint16List sse_decode_list_prim_u_16_strict(SseDeserializer deserializer);
+
+  @protected
+  List<int> sse_decode_list_prim_u_8_loose(SseDeserializer deserializer);
+
+  @protected
+  Uint8List sse_decode_list_prim_u_8_strict(SseDeserializer deserializer);
+
+  @protected
+  List<ProfileMetadataFfi> sse_decode_list_profile_metadata_ffi(
+    SseDeserializer deserializer,
+  );
+
+  @protected
+  List<ProximityAlertFfi> sse_decode_list_proximity_alert_ffi(
+    SseDeserializer deserializer,
+  );
+
+  @protected
+  List<ProximityRuleFfi> sse_decode_list_proximity_rule_ffi(
+    SseDeserializer deserializer,
+  );
+
+  @protected
+  List<RejoinRequestFfi> sse_decode_list_rejoin_request_ffi(
+    SseDeserializer deserializer,
+  );
+
+  @protected
+  List<RelayConnectionStatusFfi> sse_decode_list_relay_connection_status_ffi(
+    SseDeserializer deserializer,
+  );
+
+  @protected
+  List<RelayGiftWrapFetchFfi> sse_decode_list_relay_gift_wrap_fetch_ffi(
+    SseDeserializer deserializer,
+  );
+
+  @protected
+  List<RelayMetricsFfi> sse_decode_list_relay_metrics_ffi(
+    SseDeserializer deserializer,
+  );
+
+  @protected
+  List<RelayOverrideEntryFfi> sse_decode_list_relay_override_entry_ffi(
+    SseDeserializer deserializer,
+  );
+
+  @protected
+  List<RelayRejectionFfi> sse_decode_list_relay_rejection_ffi(
+    SseDeserializer deserializer,
+  );
+
+  @protected
+  List<RelayUsageFfi> sse_decode_list_relay_usage_ffi(
+    SseDeserializer deserializer,
+  );
+
+  @protected
+  List<SelfTestResultFfi> sse_decode_list_self_test_result_ffi(
+    SseDeserializer deserializer,
+  );
+
+  @protected
+  List<SharingSessionFfi> sse_decode_list_sharing_session_ffi(
+    SseDeserializer deserializer,
+  );
+
+  @protected
+  List<TripFfi> sse_decode_list_trip_ffi(SseDeserializer deserializer);
+
+  @protected
+  List<UnjoinedMemberFfi> sse_decode_list_unjoined_member_ffi(
+    SseDeserializer deserializer,
+  );
+
+  @protected
+  List<WelcomeRelayDeliveryFfi> sse_decode_list_welcome_relay_delivery_ffi(
+    SseDeserializer deserializer,
+  );
+
+  @protected
+  LocationMessageResultFfi sse_decode_location_message_result_ffi(
+    SseDeserializer deserializer,
+  );
+
+  @protected
+  LocationMessageResultKindFfi sse_decode_location_message_result_kind_ffi(
+    SseDeserializer deserializer,
+  );
+
+  @protected
+  LocationPrecisionFfi sse_decode_location_precision_ffi(
+    SseDeserializer deserializer,
+  );
+
+  @protected
+  LocationRetractionFfi sse_decode_location_retraction_ffi(
+    SseDeserializer deserializer,
+  );
+
+  @protected
+  MeetPinFfi sse_decode_meet_pin_ffi(SseDeserializer deserializer);
+
+  @protected
+  MemberKeyChangeFfi sse_decode_member_key_change_ffi(
+    SseDeserializer deserializer,
+  );
+
+  @protected
+  MemberKeyPackageFfi sse_decode_member_key_package_ffi(
+    SseDeserializer deserializer,
+  );
+
+  @protected
+  MemberLocationFfi sse_decode_member_location_ffi(
+    SseDeserializer deserializer,
+  );
+
+  @protected
+  MemberPresenceFfi sse_decode_member_presence_ffi(
+    SseDeserializer deserializer,
+  );
+
+  @protected
+  MembershipDeltaFfi sse_decode_membership_delta_ffi(
+    SseDeserializer deserializer,
+  );
+
+  @protected
+  MessageParamFfi sse_decode_message_param_ffi(SseDeserializer deserializer);
+
+  @protected
+  MissedCheckinFfi sse_decode_missed_checkin_ffi(SseDeserializer deserializer);
+
+  @protected
+  MotionStateFfi sse_decode_motion_state_ffi(SseDeserializer deserializer);
+
+  @protected
+  Nip05ResolutionFfi sse_decode_nip05_resolution_ffi(
+    SseDeserializer deserializer,
+  );
+
+  @protected
+  NprofileFfi sse_decode_nprofile_ffi(SseDeserializer deserializer);
+
+  @protected
+  OneShotLocationFfi sse_decode_one_shot_location_ffi(
+    SseDeserializer deserializer,
+  );
+
+  @protected
+  LinkedIdentityFfi?
+  sse_decode_opt_Auto_Owned_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerLinkedIdentityFfi(
+    SseDeserializer deserializer,
+  );
+
+  @protected
+  String? sse_decode_opt_String(SseDeserializer deserializer);
+
+  @protected
+  bool? sse_decode_opt_box_autoadd_bool(SseDeserializer deserializer);
+
+  @protected
+  CircleLocationSettingsFfi?
+  sse_decode_opt_box_autoadd_circle_location_settings_ffi(
+    SseDeserializer deserializer,
+  );
+
+  @protected
+  CircleWithMembersFfi? sse_decode_opt_box_autoadd_circle_with_members_ffi(
+    SseDeserializer deserializer,
+  );
+
+  @protected
+  ColdCircleInfoFfi? sse_decode_opt_box_autoadd_cold_circle_info_ffi(
+    SseDeserializer deserializer,
+  );
+
+  @protected
+  ContactFfi? sse_decode_opt_box_autoadd_contact_ffi(
+    SseDeserializer deserializer,
+  );
+
+  @protected
+  DecryptLocationOutcomeFfi?
+  sse_decode_opt_box_autoadd_decrypt_location_outcome_ffi(
+    SseDeserializer deserializer,
+  );
+
+  @protected
+  DecryptedLocationFfi? sse_decode_opt_box_autoadd_decrypted_location_ffi(
+    SseDeserializer deserializer,
+  );
+
+  @protected
+  DeviceStatusFfi? sse_decode_opt_box_autoadd_device_status_ffi(
+    SseDeserializer deserializer,
+  );
+
+  @protected
+  EncryptedLocationFfi? sse_decode_opt_box_autoadd_encrypted_location_ffi(
+    SseDeserializer deserializer,
+  );
+
+  @protected
+  double? sse_decode_opt_box_autoadd_f_64(SseDeserializer deserializer);
+
+  @protected
+  FfiSyncStatusReason? sse_decode_opt_box_autoadd_ffi_sync_status_reason(
+    SseDeserializer deserializer,
+  );
+
+  @protected
+  GiftWrappedWelcomeFfi? sse_decode_opt_box_autoadd_gift_wrapped_welcome_ffi(
+    SseDeserializer deserializer,
+  );
+
+  @protected
+  HavenErrorFfi? sse_decode_opt_box_autoadd_haven_error_ffi(
+    SseDeserializer deserializer,
+  );
+
+  @protected
+  int? sse_decode_opt_box_autoadd_i_16(SseDeserializer deserializer);
+
+  @protected
+  PlatformInt64? sse_decode_opt_box_autoadd_i_64(SseDeserializer deserializer);
+
+  @protected
+  InvitationFfi? sse_decode_opt_box_autoadd_invitation_ffi(
+    SseDeserializer deserializer,
+  );
+
+  @protected
+  KpVerificationFfi? sse_decode_opt_box_autoadd_kp_verification_ffi(
+    SseDeserializer deserializer,
+  );
+
+  @protected
+  LastKnownLocationFfi? sse_decode_opt_box_autoadd_last_known_location_ffi(
+    SseDeserializer deserializer,
+  );
+
+  @protected
+  LeaveEventFfi? sse_decode_opt_box_autoadd_leave_event_ffi(
+    SseDeserializer deserializer,
+  );
+
+  @protected
+  MeetPinFfi? sse_decode_opt_box_autoadd_meet_pin_ffi(
+    SseDeserializer deserializer,
+  );
+
+  @protected
+  MemberKeyPackageFfi? sse_decode_opt_box_autoadd_member_key_package_ffi(
+    SseDeserializer deserializer,
+  );
+
+  @protected
+  MembershipDeltaFfi? sse_decode_opt_box_autoadd_membership_delta_ffi(
+    SseDeserializer deserializer,
+  );
+
+  @protected
+  MotionStateFfi? sse_decode_opt_box_autoadd_motion_state_ffi(
+    SseDeserializer deserializer,
+  );
+
+  @protected
+  PrecisionAnomalyFfi? sse_decode_opt_box_autoadd_precision_anomaly_ffi(
+    SseDeserializer deserializer,
+  );
+
+  @protected
+  ProfileMetadataFfi? sse_decode_opt_box_autoadd_profile_metadata_ffi(
+    SseDeserializer deserializer,
+  );
+
+  @protected
+  ProximityEstimateFfi? sse_decode_opt_box_autoadd_proximity_estimate_ffi(
+    SseDeserializer deserializer,
+  );
+
+  @protected
+  PublicIdentity? sse_decode_opt_box_autoadd_public_identity(
+    SseDeserializer deserializer,
+  );
+
+  @protected
+  RelayOverrideFfi? sse_decode_opt_box_autoadd_relay_override_ffi(
+    SseDeserializer deserializer,
+  );
+
+  @protected
+  RemoteSignerFfi? sse_decode_opt_box_autoadd_remote_signer_ffi(
+    SseDeserializer deserializer,
+  );
+
+  @protected
+  TileCacheEntryFfi? sse_decode_opt_box_autoadd_tile_cache_entry_ffi(
+    SseDeserializer deserializer,
+  );
+
+  @protected
+  TripModeFfi? sse_decode_opt_box_autoadd_trip_mode_ffi(
+    SseDeserializer deserializer,
+  );
+
+  @protected
+  int? sse_decode_opt_box_autoadd_u_16(SseDeserializer deserializer);
+
+  @protected
+  int? sse_decode_opt_box_autoadd_u_32(SseDeserializer deserializer);
+
+  @protected
+  BigInt? sse_decode_opt_box_autoadd_u_64(SseDeserializer deserializer);
+
+  @protected
+  int? sse_decode_opt_box_autoadd_u_8(SseDeserializer deserializer);
+
+  @protected
+  UserMessageFfi? sse_decode_opt_box_autoadd_user_message_ffi(
+    SseDeserializer deserializer,
+  );
+
+  @protected
+  VerticalPositionFfi? sse_decode_opt_box_autoadd_vertical_position_ffi(
+    SseDeserializer deserializer,
+  );
+
+  @protected
+  List<String>? sse_decode_opt_list_String(SseDeserializer deserializer);
+
+  @protected
+  List<int>? sse_decode_opt_list_prim_u_8_loose(SseDeserializer deserializer);
+
+  @protected
+  Uint8List? sse_decode_opt_list_prim_u_8_strict(SseDeserializer deserializer);
+
+  @protected
+  OutboxEntryFfi sse_decode_outbox_entry_ffi(SseDeserializer deserializer);
+
+  @protected
+  OutboxFlushFfi sse_decode_outbox_flush_ffi(SseDeserializer deserializer);
+
+  @protected
+  PendingStateRefFfi sse_decode_pending_state_ref_ffi(
+    SseDeserializer deserializer,
+  );
+
+  @protected
+  PrecisionAnomalyFfi sse_decode_precision_anomaly_ffi(
+    SseDeserializer deserializer,
+  );
+
+  @protected
+  PrecisionClassFfi sse_decode_precision_class_ffi(
+    SseDeserializer deserializer,
+  );
+
+  @protected
+  PresenceStatusFfi sse_decode_presence_status_ffi(
+    SseDeserializer deserializer,
+  );
+
+  @protected
+  PrivacyFactsFfi sse_decode_privacy_facts_ffi(SseDeserializer deserializer);
+
+  @protected
+  PrivacySettingsFfi sse_decode_privacy_settings_ffi(
+    SseDeserializer deserializer,
+  );
+
+  @protected
+  ProfileMetadataFfi sse_decode_profile_metadata_ffi(
+    SseDeserializer deserializer,
+  );
+
+  @protected
+  ProfilePictureRefFfi sse_decode_profile_picture_ref_ffi(
+    SseDeserializer deserializer,
+  );
+
+  @protected
+  ProximityAlertFfi sse_decode_proximity_alert_ffi(
+    SseDeserializer deserializer,
+  );
+
+  @protected
+  ProximityEstimateFfi sse_decode_proximity_estimate_ffi(
+    SseDeserializer deserializer,
+  );
+
+  @protected
+  ProximityRuleFfi sse_decode_proximity_rule_ffi(SseDeserializer deserializer);
+
+  @protected
+  ProximityTargetFfi sse_decode_proximity_target_ffi(
+    SseDeserializer deserializer,
+  );
+
+  @protected
+  PublicIdentity sse_decode_public_identity(SseDeserializer deserializer);
+
+  @protected
+  PublishQuorumFfi sse_decode_publish_quorum_ffi(SseDeserializer deserializer);
+
+  @protected
+  PublishQuorumKindFfi sse_decode_publish_quorum_kind_ffi(
+    SseDeserializer deserializer,
+  );
+
+  @protected
+  PublishResultFfi sse_decode_publish_result_ffi(SseDeserializer deserializer);
+
+  @protected
+  QuorumPublishUpdateFfi sse_decode_quorum_publish_update_ffi(
+    SseDeserializer deserializer,
+  );
+
+  @protected
+  RejoinRequestFfi sse_decode_rejoin_request_ffi(SseDeserializer deserializer);
+
+  @protected
+  RelayConnectionStatusFfi sse_decode_relay_connection_status_ffi(
+    SseDeserializer deserializer,
+  );
+
+  @protected
+  RelayEventCheckFfi sse_decode_relay_event_check_ffi(
+    SseDeserializer deserializer,
+  );
+
+  @protected
+  RelayGiftWrapFetchFfi sse_decode_relay_gift_wrap_fetch_ffi(
+    SseDeserializer deserializer,
+  );
+
+  @protected
+  RelayInfoFfi sse_decode_relay_info_ffi(SseDeserializer deserializer);
+
+  @protected
+  RelayListActionFfi sse_decode_relay_list_action_ffi(
+    SseDeserializer deserializer,
+  );
+
+  @protected
+  RelayListCategoryOutcomeFfi sse_decode_relay_list_category_outcome_ffi(
+    SseDeserializer deserializer,
+  );
+
+  @protected
+  RelayListFlushFfi sse_decode_relay_list_flush_ffi(
+    SseDeserializer deserializer,
+  );
+
+  @protected
+  RelayListMaintenanceOutcomeFfi sse_decode_relay_list_maintenance_outcome_ffi(
+    SseDeserializer deserializer,
+  );
+
+  @protected
+  RelayMetricsFfi sse_decode_relay_metrics_ffi(SseDeserializer deserializer);
+
+  @protected
+  RelayOverrideEntryFfi sse_decode_relay_override_entry_ffi(
+    SseDeserializer deserializer,
+  );
+
+  @protected
+  RelayOverrideFfi sse_decode_relay_override_ffi(SseDeserializer deserializer);
+
+  @protected
+  RelayRejectionFfi sse_decode_relay_rejection_ffi(
+    SseDeserializer deserializer,
+  );
+
+  @protected
+  RelayTypeFfi sse_decode_relay_type_ffi(SseDeserializer deserializer);
+
+  @protected
+  RelayUsageFfi sse_decode_relay_usage_ffi(SseDeserializer deserializer);
+
+  @protected
+  RemoteSignerFfi sse_decode_remote_signer_ffi(SseDeserializer deserializer);
+
+  @protected
+  RepairOutcomeFfi sse_decode_repair_outcome_ffi(SseDeserializer deserializer);
+
+  @protected
+  SelfTestCheckFfi sse_decode_self_test_check_ffi(SseDeserializer deserializer);
+
+  @protected
+  SelfTestReportFfi sse_decode_self_test_report_ffi(
+    SseDeserializer deserializer,
+  );
+
+  @protected
+  SelfTestResultFfi sse_decode_self_test_result_ffi(
+    SseDeserializer deserializer,
+  );
+
+  @protected
+  SharePreviewFfi sse_decode_share_preview_ffi(SseDeserializer deserializer);
+
+  @protected
+  SharingSessionFfi sse_decode_sharing_session_ffi(
+    SseDeserializer deserializer,
+  );
+
+  @protected
+  SignedLocationEventFfi sse_decode_signed_location_event_ffi(
+    SseDeserializer deserializer,
+  );
+
+  @protected
+  SosFanoutFfi sse_decode_sos_fanout_ffi(SseDeserializer deserializer);
+
+  @protected
+  SubscriptionHealthActionFfi sse_decode_subscription_health_action_ffi(
+    SseDeserializer deserializer,
+  );
+
+  @protected
+  SubscriptionHealthOutcomeFfi sse_decode_subscription_health_outcome_ffi(
+    SseDeserializer deserializer,
+  );
+
+  @protected
+  SyncDigestFfi sse_decode_sync_digest_ffi(SseDeserializer deserializer);
+
+  @protected
+  TileCacheEntryFfi sse_decode_tile_cache_entry_ffi(
+    SseDeserializer deserializer,
+  );
+
+  @protected
+  TransferRestoreFfi sse_decode_transfer_restore_ffi(
     SseDeserializer deserializer,
   );
 
   @protected
-  int sse_decode_i_32(SseDeserializer deserializer);
+  TripFfi sse_decode_trip_ffi(SseDeserializer deserializer);
+
+  @protected
+  TripModeFfi sse_decode_trip_mode_ffi(SseDeserializer deserializer);
+
+  @protected
+  int sse_decode_u_16(SseDeserializer deserializer);
+
+  @protected
+  int sse_decode_u_32(SseDeserializer deserializer);
+
+  @protected
+  BigInt sse_decode_u_64(SseDeserializer deserializer);
+
+  @protected
+  int sse_decode_u_8(SseDeserializer deserializer);
+
+  @protected
+  void sse_decode_unit(SseDeserializer deserializer);
+
+  @protected
+  UnjoinedMemberFfi sse_decode_unjoined_member_ffi(
+    SseDeserializer deserializer,
+  );
+
+  @protected
+  UnsignedLocationEventFfi sse_decode_unsigned_location_event_ffi(
+    SseDeserializer deserializer,
+  );
+
+  @protected
+  UsageFfi sse_decode_usage_ffi(SseDeserializer deserializer);
+
+  @protected
+  UserMessageFfi sse_decode_user_message_ffi(SseDeserializer deserializer);
+
+  @protected
+  BigInt sse_decode_usize(SseDeserializer deserializer);
+
+  @protected
+  VerticalPositionFfi sse_decode_vertical_position_ffi(
+    SseDeserializer deserializer,
+  );
+
+  @protected
+  WelcomeDeliveryReportFfi sse_decode_welcome_delivery_report_ffi(
+    SseDeserializer deserializer,
+  );
+
+  @protected
+  WelcomeRelayDeliveryFfi sse_decode_welcome_relay_delivery_ffi(
+    SseDeserializer deserializer,
+  );
+
+  @protected
+  WelcomeRelayOutcomeFfi sse_decode_welcome_relay_outcome_ffi(
+    SseDeserializer deserializer,
+  );
+
+  @protected
+  void sse_encode_AnyhowException(
+    AnyhowException self,
+    SseSerializer serializer,
+  );
+
+  @protected
+  void
+  sse_encode_Auto_Owned_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerCircleManagerFfi(
+    CircleManagerFfi self,
+    SseSerializer serializer,
+  );
+
+  @protected
+  void
+  sse_encode_Auto_Owned_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerDeviceLinkSessionFfi(
+    DeviceLinkSessionFfi self,
+    SseSerializer serializer,
+  );
+
+  @protected
+  void
+  sse_encode_Auto_Owned_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerExternalSignerFfi(
+    ExternalSignerFfi self,
+    SseSerializer serializer,
+  );
+
+  @protected
+  void
+  sse_encode_Auto_Owned_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerHavenCore(
+    HavenCore self,
+    SseSerializer serializer,
+  );
+
+  @protected
+  void
+  sse_encode_Auto_Owned_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerLinkedIdentityFfi(
+    LinkedIdentityFfi self,
+    SseSerializer serializer,
+  );
+
+  @protected
+  void
+  sse_encode_Auto_Owned_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerLiveSyncFfi(
+    LiveSyncFfi self,
+    SseSerializer serializer,
+  );
+
+  @protected
+  void
+  sse_encode_Auto_Owned_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerLocationEventService(
+    LocationEventService self,
+    SseSerializer serializer,
+  );
+
+  @protected
+  void
+  sse_encode_Auto_Owned_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerLocationMessage(
+    LocationMessage self,
+    SseSerializer serializer,
+  );
+
+  @protected
+  void
+  sse_encode_Auto_Owned_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerLocationSettings(
+    LocationSettings self,
+    SseSerializer serializer,
+  );
+
+  @protected
+  void
+  sse_encode_Auto_Owned_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerNostrIdentityManager(
+    NostrIdentityManager self,
+    SseSerializer serializer,
+  );
+
+  @protected
+  void
+  sse_encode_Auto_Owned_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerRelayManagerFfi(
+    RelayManagerFfi self,
+    SseSerializer serializer,
+  );
+
+  @protected
+  void
+  sse_encode_Auto_Owned_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerSecretHandle(
+    SecretHandle self,
+    SseSerializer serializer,
+  );
+
+  @protected
+  void
+  sse_encode_Auto_Owned_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerTransferBundleFfi(
+    TransferBundleFfi self,
+    SseSerializer serializer,
+  );
+
+  @protected
+  void
+  sse_encode_Auto_Owned_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerTransferContentsFfi(
+    TransferContentsFfi self,
+    SseSerializer serializer,
+  );
+
+  @protected
+  void
+  sse_encode_Auto_RefMut_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerHavenCore(
+    HavenCore self,
+    SseSerializer serializer,
+  );
+
+  @protected
+  void
+  sse_encode_Auto_RefMut_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerLocationSettings(
+    LocationSettings self,
+    SseSerializer serializer,
+  );
+
+  @protected
+  void
+  sse_encode_Auto_Ref_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerCircleManagerFfi(
+    CircleManagerFfi self,
+    SseSerializer serializer,
+  );
+
+  @protected
+  void
+  sse_encode_Auto_Ref_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerDeviceLinkSessionFfi(
+    DeviceLinkSessionFfi self,
+    SseSerializer serializer,
+  );
+
+  @protected
+  void
+  sse_encode_Auto_Ref_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerExternalSignerFfi(
+    ExternalSignerFfi self,
+    SseSerializer serializer,
+  );
+
+  @protected
+  void
+  sse_encode_Auto_Ref_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerHavenCore(
+    HavenCore self,
+    SseSerializer serializer,
+  );
+
+  @protected
+  void
+  sse_encode_Auto_Ref_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerLinkedIdentityFfi(
+    LinkedIdentityFfi self,
+    SseSerializer serializer,
+  );
+
+  @protected
+  void
+  sse_encode_Auto_Ref_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerLiveSyncFfi(
+    LiveSyncFfi self,
+    SseSerializer serializer,
+  );
+
+  @protected
+  void
+  sse_encode_Auto_Ref_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerLocationEventService(
+    LocationEventService self,
+    SseSerializer serializer,
+  );
+
+  @protected
+  void
+  sse_encode_Auto_Ref_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerLocationMessage(
+    LocationMessage self,
+    SseSerializer serializer,
+  );
+
+  @protected
+  void
+  sse_encode_Auto_Ref_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerLocationSettings(
+    LocationSettings self,
+    SseSerializer serializer,
+  );
+
+  @protected
+  void
+  sse_encode_Auto_Ref_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerNostrIdentityManager(
+    NostrIdentityManager self,
+    SseSerializer serializer,
+  );
+
+  @protected
+  void
+  sse_encode_Auto_Ref_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerRelayManagerFfi(
+    RelayManagerFfi self,
+    SseSerializer serializer,
+  );
+
+  @protected
+  void
+  sse_encode_Auto_Ref_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerSecretHandle(
+    SecretHandle self,
+    SseSerializer serializer,
+  );
+
+  @protected
+  void
+  sse_encode_Auto_Ref_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerTransferBundleFfi(
+    TransferBundleFfi self,
+    SseSerializer serializer,
+  );
+
+  @protected
+  void
+  sse_encode_Auto_Ref_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerTransferContentsFfi(
+    TransferContentsFfi self,
+    SseSerializer serializer,
+  );
+
+  @protected
+  void sse_encode_DartFn_Inputs_String_String_Output_String_AnyhowException(
+    FutureOr<String> Function(String, String) self,
+    SseSerializer serializer,
+  );
+
+  @protected
+  void
+  sse_encode_DartFn_Inputs_list_prim_u_8_strict_Output_String_AnyhowException(
+    FutureOr<String> Function(Uint8List) self,
+    SseSerializer serializer,
+  );
+
+  @protected
+  void sse_encode_DartOpaque(Object self, SseSerializer serializer);
+
+  @protected
+  void
+  sse_encode_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerCircleManagerFfi(
+    CircleManagerFfi self,
+    SseSerializer serializer,
+  );
+
+  @protected
+  void
+  sse_encode_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerDeviceLinkSessionFfi(
+    DeviceLinkSessionFfi self,
+    SseSerializer serializer,
+  );
+
+  @protected
+  void
+  sse_encode_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerExternalSignerFfi(
+    ExternalSignerFfi self,
+    SseSerializer serializer,
+  );
+
+  @protected
+  void
+  sse_encode_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerHavenCore(
+    HavenCore self,
+    SseSerializer serializer,
+  );
+
+  @protected
+  void
+  sse_encode_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerLinkedIdentityFfi(
+    LinkedIdentityFfi self,
+    SseSerializer serializer,
+  );
+
+  @protected
+  void
+  sse_encode_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerLiveSyncFfi(
+    LiveSyncFfi self,
+    SseSerializer serializer,
+  );
+
+  @protected
+  void
+  sse_encode_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerLocationEventService(
+    LocationEventService self,
+    SseSerializer serializer,
+  );
+
+  @protected
+  void
+  sse_encode_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerLocationMessage(
+    LocationMessage self,
+    SseSerializer serializer,
+  );
+
+  @protected
+  void
+  sse_encode_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerLocationSettings(
+    LocationSettings self,
+    SseSerializer serializer,
+  );
+
+  @protected
+  void
+  sse_encode_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerNostrIdentityManager(
+    NostrIdentityManager self,
+    SseSerializer serializer,
+  );
+
+  @protected
+  void
+  sse_encode_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerRelayManagerFfi(
+    RelayManagerFfi self,
+    SseSerializer serializer,
+  );
+
+  @protected
+  void
+  sse_encode_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerSecretHandle(
+    SecretHandle self,
+    SseSerializer serializer,
+  );
+
+  @protected
+  void
+  sse_encode_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerTransferBundleFfi(
+    TransferBundleFfi self,
+    SseSerializer serializer,
+  );
+
+  @protected
+  void
+  sse_encode_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerTransferContentsFfi(
+    TransferContentsFfi self,
+    SseSerializer serializer,
+  );
+
+  @protected
+  void sse_encode_StreamSink_decrypted_location_ffi_Sse(
+    RustStreamSink<DecryptedLocationFfi> self,
+    SseSerializer serializer,
+  );
+
+  @protected
+  void sse_encode_StreamSink_ffi_relay_event_Sse(
+    RustStreamSink<FfiRelayEvent> self,
+    SseSerializer serializer,
+  );
+
+  @protected
+  void sse_encode_StreamSink_quorum_publish_update_ffi_Sse(
+    RustStreamSink<QuorumPublishUpdateFfi> self,
+    SseSerializer serializer,
+  );
+
+  @protected
+  void sse_encode_String(String self, SseSerializer serializer);
+
+  @protected
+  void sse_encode_add_members_result_ffi(
+    AddMembersResultFfi self,
+    SseSerializer serializer,
+  );
+
+  @protected
+  void sse_encode_audience_circle_ffi(
+    AudienceCircleFfi self,
+    SseSerializer serializer,
+  );
+
+  @protected
+  void sse_encode_audience_member_ffi(
+    AudienceMemberFfi self,
+    SseSerializer serializer,
+  );
+
+  @protected
+  void sse_encode_backfill_digest_ffi(
+    BackfillDigestFfi self,
+    SseSerializer serializer,
+  );
+
+  @protected
+  void sse_encode_bandwidth_usage_ffi(
+    BandwidthUsageFfi self,
+    SseSerializer serializer,
+  );
+
+  @protected
+  void sse_encode_bool(bool self, SseSerializer serializer);
+
+  @protected
+  void sse_encode_box_autoadd_bool(bool self, SseSerializer serializer);
+
+  @protected
+  void sse_encode_box_autoadd_circle_location_settings_ffi(
+    CircleLocationSettingsFfi self,
+    SseSerializer serializer,
+  );
+
+  @protected
+  void sse_encode_box_autoadd_circle_with_members_ffi(
+    CircleWithMembersFfi self,
+    SseSerializer serializer,
+  );
+
+  @protected
+  void sse_encode_box_autoadd_cold_circle_info_ffi(
+    ColdCircleInfoFfi self,
+    SseSerializer serializer,
+  );
+
+  @protected
+  void sse_encode_box_autoadd_compact_event_ffi(
+    CompactEventFfi self,
+    SseSerializer serializer,
+  );
+
+  @protected
+  void sse_encode_box_autoadd_contact_ffi(
+    ContactFfi self,
+    SseSerializer serializer,
+  );
+
+  @protected
+  void sse_encode_box_autoadd_decrypt_location_outcome_ffi(
+    DecryptLocationOutcomeFfi self,
+    SseSerializer serializer,
+  );
+
+  @protected
+  void sse_encode_box_autoadd_decrypted_location_ffi(
+    DecryptedLocationFfi self,
+    SseSerializer serializer,
+  );
+
+  @protected
+  void sse_encode_box_autoadd_device_status_ffi(
+    DeviceStatusFfi self,
+    SseSerializer serializer,
+  );
+
+  @protected
+  void sse_encode_box_autoadd_encrypted_location_ffi(
+    EncryptedLocationFfi self,
+    SseSerializer serializer,
+  );
+
+  @protected
+  void sse_encode_box_autoadd_f_64(double self, SseSerializer serializer);
+
+  @protected
+  void sse_encode_box_autoadd_ffi_group_spec(
+    FfiGroupSpec self,
+    SseSerializer serializer,
+  );
+
+  @protected
+  void sse_encode_box_autoadd_ffi_sync_status_reason(
+    FfiSyncStatusReason self,
+    SseSerializer serializer,
+  );
+
+  @protected
+  void sse_encode_box_autoadd_gift_wrapped_welcome_ffi(
+    GiftWrappedWelcomeFfi self,
+    SseSerializer serializer,
+  );
+
+  @protected
+  void sse_encode_box_autoadd_haven_error_ffi(
+    HavenErrorFfi self,
+    SseSerializer serializer,
+  );
+
+  @protected
+  void sse_encode_box_autoadd_i_16(int self, SseSerializer serializer);
+
+  @protected
+  void sse_encode_box_autoadd_i_64(
+    PlatformInt64 self,
+    SseSerializer serializer,
+  );
+
+  @protected
+  void sse_encode_box_autoadd_invitation_ffi(
+    InvitationFfi self,
+    SseSerializer serializer,
+  );
+
+  @protected
+  void sse_encode_box_autoadd_kp_verification_ffi(
+    KpVerificationFfi self,
+    SseSerializer serializer,
+  );
+
+  @protected
+  void sse_encode_box_autoadd_last_known_location_ffi(
+    LastKnownLocationFfi self,
+    SseSerializer serializer,
+  );
+
+  @protected
+  void sse_encode_box_autoadd_leave_event_ffi(
+    LeaveEventFfi self,
+    SseSerializer serializer,
+  );
+
+  @protected
+  void sse_encode_box_autoadd_meet_pin_ffi(
+    MeetPinFfi self,
+    SseSerializer serializer,
+  );
+
+  @protected
+  void sse_encode_box_autoadd_member_key_package_ffi(
+    MemberKeyPackageFfi self,
+    SseSerializer serializer,
+  );
+
+  @protected
+  void sse_encode_box_autoadd_membership_delta_ffi(
+    MembershipDeltaFfi self,
+    SseSerializer serializer,
+  );
+
+  @protected
+  void sse_encode_box_autoadd_motion_state_ffi(
+    MotionStateFfi self,
+    SseSerializer serializer,
+  );
+
+  @protected
+  void sse_encode_box_autoadd_pending_state_ref_ffi(
+    PendingStateRefFfi self,
+    SseSerializer serializer,
+  );
+
+  @protected
+  void sse_encode_box_autoadd_precision_anomaly_ffi(
+    PrecisionAnomalyFfi self,
+    SseSerializer serializer,
+  );
+
+  @protected
+  void sse_encode_box_autoadd_privacy_settings_ffi(
+    PrivacySettingsFfi self,
+    SseSerializer serializer,
+  );
+
+  @protected
+  void sse_encode_box_autoadd_profile_metadata_ffi(
+    ProfileMetadataFfi self,
+    SseSerializer serializer,
+  );
+
+  @protected
+  void sse_encode_box_autoadd_proximity_estimate_ffi(
+    ProximityEstimateFfi self,
+    SseSerializer serializer,
+  );
+
+  @protected
+  void sse_encode_box_autoadd_proximity_target_ffi(
+    ProximityTargetFfi self,
+    SseSerializer serializer,
+  );
+
+  @protected
+  void sse_encode_box_autoadd_public_identity(
+    PublicIdentity self,
+    SseSerializer serializer,
+  );
+
+  @protected
+  void sse_encode_box_autoadd_publish_quorum_ffi(
+    PublishQuorumFfi self,
+    SseSerializer serializer,
+  );
+
+  @protected
+  void sse_encode_box_autoadd_relay_override_ffi(
+    RelayOverrideFfi self,
+    SseSerializer serializer,
+  );
+
+  @protected
+  void sse_encode_box_autoadd_remote_signer_ffi(
+    RemoteSignerFfi self,
+    SseSerializer serializer,
+  );
+
+  @protected
+  void sse_encode_box_autoadd_signed_location_event_ffi(
+    SignedLocationEventFfi self,
+    SseSerializer serializer,
+  );
+
+  @protected
+  void sse_encode_box_autoadd_tile_cache_entry_ffi(
+    TileCacheEntryFfi self,
+    SseSerializer serializer,
+  );
+
+  @protected
+  void sse_encode_box_autoadd_trip_mode_ffi(
+    TripModeFfi self,
+    SseSerializer serializer,
+  );
+
+  @protected
+  void sse_encode_box_autoadd_u_16(int self, SseSerializer serializer);
+
+  @protected
+  void sse_encode_box_autoadd_u_32(int self, SseSerializer serializer);
+
+  @protected
+  void sse_encode_box_autoadd_u_64(BigInt self, SseSerializer serializer);
+
+  @protected
+  void sse_encode_box_autoadd_u_8(int self, SseSerializer serializer);
+
+  @protected
+  void sse_encode_box_autoadd_user_message_ffi(
+    UserMessageFfi self,
+    SseSerializer serializer,
+  );
+
+  @protected
+  void sse_encode_box_autoadd_vertical_position_ffi(
+    VerticalPositionFfi self,
+    SseSerializer serializer,
+  );
+
+  @protected
+  void sse_encode_breadcrumb_ffi(BreadcrumbFfi self, SseSerializer serializer);
+
+  @protected
+  void sse_encode_built_relay_list_event_ffi(
+    BuiltRelayListEventFfi self,
+    SseSerializer serializer,
+  );
+
+  @protected
+  void sse_encode_built_unpublish_ffi(
+    BuiltUnpublishFfi self,
+    SseSerializer serializer,
+  );
+
+  @protected
+  void sse_encode_catchup_result_ffi(
+    CatchupResultFfi self,
+    SseSerializer serializer,
+  );
+
+  @protected
+  void sse_encode_cell_size_ffi(CellSizeFfi self, SseSerializer serializer);
+
+  @protected
+  void sse_encode_checkin_rule_ffi(
+    CheckinRuleFfi self,
+    SseSerializer serializer,
+  );
+
+  @protected
+  void sse_encode_circle_creation_result_ffi(
+    CircleCreationResultFfi self,
+    SseSerializer serializer,
+  );
+
+  @protected
+  void sse_encode_circle_ffi(CircleFfi self, SseSerializer serializer);
+
+  @protected
+  void sse_encode_circle_location_settings_ffi(
+    CircleLocationSettingsFfi self,
+    SseSerializer serializer,
+  );
+
+  @protected
+  void sse_encode_circle_member_ffi(
+    CircleMemberFfi self,
+    SseSerializer serializer,
+  );
+
+  @protected
+  void sse_encode_circle_status_ffi(
+    CircleStatusFfi self,
+    SseSerializer serializer,
+  );
+
+  @protected
+  void sse_encode_circle_sync_digest_ffi(
+    CircleSyncDigestFfi self,
+    SseSerializer serializer,
+  );
+
+  @protected
+  void sse_encode_circle_usage_ffi(
+    CircleUsageFfi self,
+    SseSerializer serializer,
+  );
+
+  @protected
+  void sse_encode_circle_with_members_ffi(
+    CircleWithMembersFfi self,
+    SseSerializer serializer,
+  );
+
+  @protected
+  void sse_encode_cold_circle_info_ffi(
+    ColdCircleInfoFfi self,
+    SseSerializer serializer,
+  );
+
+  @protected
+  void sse_encode_commit_to_publish_ffi(
+    CommitToPublishFfi self,
+    SseSerializer serializer,
+  );
+
+  @protected
+  void sse_encode_compact_event_ffi(
+    CompactEventFfi self,
+    SseSerializer serializer,
+  );
+
+  @protected
+  void sse_encode_consumed_key_package_ffi(
+    ConsumedKeyPackageFfi self,
+    SseSerializer serializer,
+  );
+
+  @protected
+  void sse_encode_contact_ffi(ContactFfi self, SseSerializer serializer);
+
+  @protected
+  void sse_encode_contact_label_ffi(
+    ContactLabelFfi self,
+    SseSerializer serializer,
+  );
+
+  @protected
+  void sse_encode_day_summary_ffi(DaySummaryFfi self, SseSerializer serializer);
+
+  @protected
+  void sse_encode_decrypt_batch_item_ffi(
+    DecryptBatchItemFfi self,
+    SseSerializer serializer,
+  );
+
+  @protected
+  void sse_encode_decrypt_location_outcome_ffi(
+    DecryptLocationOutcomeFfi self,
+    SseSerializer serializer,
+  );
+
+  @protected
+  void sse_encode_decrypted_location_ffi(
+    DecryptedLocationFfi self,
+    SseSerializer serializer,
+  );
+
+  @protected
+  void sse_encode_dependent_circle_ffi(
+    DependentCircleFfi self,
+    SseSerializer serializer,
+  );
+
+  @protected
+  void sse_encode_device_status_ffi(
+    DeviceStatusFfi self,
+    SseSerializer serializer,
+  );
+
+  @protected
+  void sse_encode_distance_bucket_ffi(
+    DistanceBucketFfi self,
+    SseSerializer serializer,
+  );
+
+  @protected
+  void sse_encode_encrypted_location_ffi(
+    EncryptedLocationFfi self,
+    SseSerializer serializer,
+  );
+
+  @protected
+  void sse_encode_environment_ffi(
+    EnvironmentFfi self,
+    SseSerializer serializer,
+  );
+
+  @protected
+  void sse_encode_epoch_info_ffi(EpochInfoFfi self, SseSerializer serializer);
 
   @protected
-  PlatformInt64 sse_decode_i_64(SseDeserializer deserializer);
+  void sse_encode_f_64(double self, SseSerializer serializer);
 
   @protected
-  InvitationFfi sse_decode_invitation_ffi(SseDeserializer deserializer);
+  void sse_encode_ffi_group_spec(FfiGroupSpec self, SseSerializer serializer);
 
   @protected
-  KpMaintenanceActionFfi sse_decode_kp_maintenance_action_ffi(
-    SseDeserializer deserializer,
-  );
+  void sse_encode_ffi_relay_event(FfiRelayEvent self, SseSerializer serializer);
 
   @protected
-  KpMaintenanceOutcomeFfi sse_decode_kp_maintenance_outcome_ffi(
-    SseDeserializer deserializer,
+  void sse_encode_ffi_relay_event_kind(
+    FfiRelayEventKind self,
+    SseSerializer serializer,
   );
 
   @protected
-  LastKnownLocationFfi sse_decode_last_known_location_ffi(
-    SseDeserializer deserializer,
+  void sse_encode_ffi_sync_status_reason(
+    FfiSyncStatusReason self,
+    SseSerializer serializer,
   );
 
   @protected
-  LeavePlanFfi sse_decode_leave_plan_ffi(SseDeserializer deserializer);
+  void sse_encode_freshness_bucket_ffi(
+    FreshnessBucketFfi self,
+    SseSerializer serializer,
+  );
 
   @protected
-  LeavePlanKindFfi sse_decode_leave_plan_kind_ffi(SseDeserializer deserializer);
+  void sse_encode_geofence_ffi(GeofenceFfi self, SseSerializer serializer);
 
   @protected
-  LegacyRetractionOutcomeFfi sse_decode_legacy_retraction_outcome_ffi(
-    SseDeserializer deserializer,
+  void sse_encode_geofence_occupancy_ffi(
+    GeofenceOccupancyFfi self,
+    SseSerializer serializer,
   );
 
   @protected
-  List<String> sse_decode_list_String(SseDeserializer deserializer);
+  void sse_encode_gift_wrap_outcome_ffi(
+    GiftWrapOutcomeFfi self,
+    SseSerializer serializer,
+  );
 
   @protected
-  List<CircleMemberFfi> sse_decode_list_circle_member_ffi(
-    SseDeserializer deserializer,
+  void sse_encode_gift_wrapped_welcome_ffi(
+    GiftWrappedWelcomeFfi self,
+    SseSerializer serializer,
   );
 
   @protected
-  List<CircleWithMembersFfi> sse_decode_list_circle_with_members_ffi(
-    SseDeserializer deserializer,
+  void sse_encode_glance_circle_ffi(
+    GlanceCircleFfi self,
+    SseSerializer serializer,
   );
 
   @protected
-  List<CommitToPublishFfi> sse_decode_list_commit_to_publish_ffi(
-    SseDeserializer deserializer,
+  void sse_encode_glance_member_ffi(
+    GlanceMemberFfi self,
+    SseSerializer serializer,
   );
 
   @protected
-  List<ContactFfi> sse_decode_list_contact_ffi(SseDeserializer deserializer);
+  void sse_encode_glanceable_snapshot_ffi(
+    GlanceableSnapshotFfi self,
+    SseSerializer serializer,
+  );
 
   @protected
-  List<FfiGroupSpec> sse_decode_list_ffi_group_spec(
-    SseDeserializer deserializer,
+  void sse_encode_group_health_ffi(
+    GroupHealthFfi self,
+    SseSerializer serializer,
   );
 
   @protected
-  List<GiftWrappedWelcomeFfi> sse_decode_list_gift_wrapped_welcome_ffi(
-    SseDeserializer deserializer,
+  void sse_encode_group_message_page_ffi(
+    GroupMessagePageFfi self,
+    SseSerializer serializer,
   );
 
   @protected
-  List<InvitationFfi> sse_decode_list_invitation_ffi(
-    SseDeserializer deserializer,
+  void sse_encode_group_repair_ffi(
+    GroupRepairFfi self,
+    SseSerializer serializer,
   );
 
   @protected
-  List<LastKnownLocationFfi> sse_decode_list_last_known_location_ffi(
-    SseDeserializer deserializer,
-  );
+  void sse_encode_haven_error_ffi(HavenErrorFfi self, SseSerializer serializer);
 
   @protected
-  List<List<String>> sse_decode_list_list_String(SseDeserializer deserializer);
+  void sse_encode_haven_payload_ffi(
+    HavenPayloadFfi self,
+    SseSerializer serializer,
+  );
 
   @protected
-  List<LocationMessageResultFfi> sse_decode_list_location_message_result_ffi(
-    SseDeserializer deserializer,
+  void sse_encode_haven_payload_kind_ffi(
+    HavenPayloadKindFfi self,
+    SseSerializer serializer,
   );
 
   @protected
-  List<MemberKeyPackageFfi> sse_decode_list_member_key_package_ffi(
-    SseDeserializer deserializer,
+  void sse_encode_history_point_ffi(
+    HistoryPointFfi self,
+    SseSerializer serializer,
   );
 
   @protected
-  List<int> sse_decode_list_prim_u_8_loose(SseDeserializer deserializer);
+  void sse_encode_i_16(int self, SseSerializer serializer);
 
   @protected
-  Uint8List sse_decode_list_prim_u_8_strict(SseDeserializer deserializer);
+  void sse_encode_i_32(int self, SseSerializer serializer);
 
   @protected
-  List<ProfileMetadataFfi> sse_decode_list_profile_metadata_ffi(
-    SseDeserializer deserializer,
-  );
+  void sse_encode_i_64(PlatformInt64 self, SseSerializer serializer);
 
   @protected
-  List<RelayConnectionStatusFfi> sse_decode_list_relay_connection_status_ffi(
-    SseDeserializer deserializer,
+  void sse_encode_identity_deletion_report_ffi(
+    IdentityDeletionReportFfi self,
+    SseSerializer serializer,
   );
 
   @protected
-  List<RelayGiftWrapFetchFfi> sse_decode_list_relay_gift_wrap_fetch_ffi(
-    SseDeserializer deserializer,
+  void sse_encode_identity_teardown_ffi(
+    IdentityTeardownFfi self,
+    SseSerializer serializer,
   );
 
   @protected
-  List<RelayRejectionFfi> sse_decode_list_relay_rejection_ffi(
-    SseDeserializer deserializer,
+  void sse_encode_inbox_sync_summary_ffi(
+    InboxSyncSummaryFfi self,
+    SseSerializer serializer,
   );
 
   @protected
-  LocationMessageResultFfi sse_decode_location_message_result_ffi(
-    SseDeserializer deserializer,
-  );
+  void sse_encode_invitation_ffi(InvitationFfi self, SseSerializer serializer);
 
   @protected
-  LocationMessageResultKindFfi sse_decode_location_message_result_kind_ffi(
-    SseDeserializer deserializer,
+  void sse_encode_invite_payload_ffi(
+    InvitePayloadFfi self,
+    SseSerializer serializer,
   );
 
   @protected
-  MemberKeyPackageFfi sse_decode_member_key_package_ffi(
-    SseDeserializer deserializer,
+  void sse_encode_kp_maintenance_action_ffi(
+    KpMaintenanceActionFfi self,
+    SseSerializer serializer,
   );
 
   @protected
-  String? sse_decode_opt_String(SseDeserializer deserializer);
+  void sse_encode_kp_maintenance_outcome_ffi(
+    KpMaintenanceOutcomeFfi self,
+    SseSerializer serializer,
+  );
 
   @protected
-  CircleWithMembersFfi? sse_decode_opt_box_autoadd_circle_with_members_ffi(
-    SseDeserializer deserializer,
+  void sse_encode_kp_relay_status_ffi(
+    KpRelayStatusFfi self,
+    SseSerializer serializer,
   );
 
   @protected
-  ContactFfi? sse_decode_opt_box_autoadd_contact_ffi(
-    SseDeserializer deserializer,
+  void sse_encode_kp_relay_verification_ffi(
+    KpRelayVerificationFfi self,
+    SseSerializer serializer,
   );
 
   @protected
-  DecryptedLocationFfi? sse_decode_opt_box_autoadd_decrypted_location_ffi(
-    SseDeserializer deserializer,
+  void sse_encode_kp_verification_ffi(
+    KpVerificationFfi self,
+    SseSerializer serializer,
   );
 
   @protected
-  FfiSyncStatusReason? sse_decode_opt_box_autoadd_ffi_sync_status_reason(
-    SseDeserializer deserializer,
+  void sse_encode_labeled_circle_creation_ffi(
+    LabeledCircleCreationFfi self,
+    SseSerializer serializer,
   );
 
   @protected
-  PlatformInt64? sse_decode_opt_box_autoadd_i_64(SseDeserializer deserializer);
+  void sse_encode_last_known_location_ffi(
+    LastKnownLocationFfi self,
+    SseSerializer serializer,
+  );
 
   @protected
-  InvitationFfi? sse_decode_opt_box_autoadd_invitation_ffi(
-    SseDeserializer deserializer,
+  void sse_encode_leave_all_report_ffi(
+    LeaveAllReportFfi self,
+    SseSerializer serializer,
   );
 
   @protected
-  MemberKeyPackageFfi? sse_decode_opt_box_autoadd_member_key_package_ffi(
-    SseDeserializer deserializer,
-  );
+  void sse_encode_leave_event_ffi(LeaveEventFfi self, SseSerializer serializer);
 
   @protected
-  ProfileMetadataFfi? sse_decode_opt_box_autoadd_profile_metadata_ffi(
-    SseDeserializer deserializer,
+  void sse_encode_leave_plan_ffi(LeavePlanFfi self, SseSerializer serializer);
+
+  @protected
+  void sse_encode_leave_plan_kind_ffi(
+    LeavePlanKindFfi self,
+    SseSerializer serializer,
   );
 
   @protected
-  PublicIdentity? sse_decode_opt_box_autoadd_public_identity(
-    SseDeserializer deserializer,
+  void sse_encode_legacy_retraction_outcome_ffi(
+    LegacyRetractionOutcomeFfi self,
+    SseSerializer serializer,
   );
 
   @protected
-  TileCacheEntryFfi? sse_decode_opt_box_autoadd_tile_cache_entry_ffi(
-    SseDeserializer deserializer,
+  void sse_encode_linked_circle_ffi(
+    LinkedCircleFfi self,
+    SseSerializer serializer,
   );
 
   @protected
-  int? sse_decode_opt_box_autoadd_u_16(SseDeserializer deserializer);
+  void sse_encode_list_String(List<String> self, SseSerializer serializer);
 
   @protected
-  int? sse_decode_opt_box_autoadd_u_32(SseDeserializer deserializer);
+  void sse_encode_list_audience_circle_ffi(
+    List<AudienceCircleFfi> self,
+    SseSerializer serializer,
+  );
 
   @protected
-  Uint8List? sse_decode_opt_list_prim_u_8_strict(SseDeserializer deserializer);
+  void sse_encode_list_audience_member_ffi(
+    List<AudienceMemberFfi> self,
+    SseSerializer serializer,
+  );
 
   @protected
-  PendingStateRefFfi sse_decode_pending_state_ref_ffi(
-    SseDeserializer deserializer,
+  void sse_encode_list_breadcrumb_ffi(
+    List<BreadcrumbFfi> self,
+    SseSerializer serializer,
   );
 
   @protected
-  ProfileMetadataFfi sse_decode_profile_metadata_ffi(
-    SseDeserializer deserializer,
+  void sse_encode_list_checkin_rule_ffi(
+    List<CheckinRuleFfi> self,
+    SseSerializer serializer,
   );
 
   @protected
-  ProfilePictureRefFfi sse_decode_profile_picture_ref_ffi(
-    SseDeserializer deserializer,
+  void sse_encode_list_circle_member_ffi(
+    List<CircleMemberFfi> self,
+    SseSerializer serializer,
   );
 
   @protected
-  PublicIdentity sse_decode_public_identity(SseDeserializer deserializer);
+  void sse_encode_list_circle_sync_digest_ffi(
+    List<CircleSyncDigestFfi> self,
+    SseSerializer serializer,
+  );
 
   @protected
-  PublishResultFfi sse_decode_publish_result_ffi(SseDeserializer deserializer);
+  void sse_encode_list_circle_usage_ffi(
+    List<CircleUsageFfi> self,
+    SseSerializer serializer,
+  );
 
   @protected
-  RelayConnectionStatusFfi sse_decode_relay_connection_status_ffi(
-    SseDeserializer deserializer,
+  void sse_encode_list_circle_with_members_ffi(
+    List<CircleWithMembersFfi> self,
+    SseSerializer serializer,
   );
 
   @protected
-  RelayEventCheckFfi sse_decode_relay_event_check_ffi(
-    SseDeserializer deserializer,
+  void sse_encode_list_commit_to_publish_ffi(
+    List<CommitToPublishFfi> self,
+    SseSerializer serializer,
   );
 
   @protected
-  RelayGiftWrapFetchFfi sse_decode_relay_gift_wrap_fetch_ffi(
-    SseDeserializer deserializer,
+  void sse_encode_list_compact_event_ffi(
+    List<CompactEventFfi> self,
+    SseSerializer serializer,
   );
 
   @protected
-  RelayListActionFfi sse_decode_relay_list_action_ffi(
-    SseDeserializer deserializer,
+  void sse_encode_list_contact_ffi(
+    List<ContactFfi> self,
+    SseSerializer serializer,
   );
 
   @protected
-  RelayListCategoryOutcomeFfi sse_decode_relay_list_category_outcome_ffi(
-    SseDeserializer deserializer,
+  void sse_encode_list_contact_label_ffi(
+    List<ContactLabelFfi> self,
+    SseSerializer serializer,
   );
 
   @protected
-  RelayListMaintenanceOutcomeFfi sse_decode_relay_list_maintenance_outcome_ffi(
-    SseDeserializer deserializer,
+  void sse_encode_list_decrypt_batch_item_ffi(
+    List<DecryptBatchItemFfi> self,
+    SseSerializer serializer,
   );
 
   @protected
-  RelayRejectionFfi sse_decode_relay_rejection_ffi(
-    SseDeserializer deserializer,
+  void sse_encode_list_dependent_circle_ffi(
+    List<DependentCircleFfi> self,
+    SseSerializer serializer,
   );
 
   @protected
-  RelayTypeFfi sse_decode_relay_type_ffi(SseDeserializer deserializer);
+  void sse_encode_list_encrypted_location_ffi(
+    List<EncryptedLocationFfi> self,
+    SseSerializer serializer,
+  );
 
   @protected
-  SignedLocationEventFfi sse_decode_signed_location_event_ffi(
-    SseDeserializer deserializer,
+  void sse_encode_list_ffi_group_spec(
+    List<FfiGroupSpec> self,
+    SseSerializer serializer,
   );
 
   @protected
-  SubscriptionHealthActionFfi sse_decode_subscription_health_action_ffi(
-    SseDeserializer deserializer,
+  void sse_encode_list_geofence_ffi(
+    List<GeofenceFfi> self,
+    SseSerializer serializer,
   );
 
   @protected
-  SubscriptionHealthOutcomeFfi sse_decode_subscription_health_outcome_ffi(
-    SseDeserializer deserializer,
+  void sse_encode_list_geofence_occupancy_ffi(
+    List<GeofenceOccupancyFfi> self,
+    SseSerializer serializer,
   );
 
   @protected
-  TileCacheEntryFfi sse_decode_tile_cache_entry_ffi(
-    SseDeserializer deserializer,
+  void sse_encode_list_gift_wrap_outcome_ffi(
+    List<GiftWrapOutcomeFfi> self,
+    SseSerializer serializer,
   );
 
   @protected
-  int sse_decode_u_16(SseDeserializer deserializer);
+  void sse_encode_list_gift_wrapped_welcome_ffi(
+    List<GiftWrappedWelcomeFfi> self,
+    SseSerializer serializer,
+  );
 
   @protected
-  int sse_decode_u_32(SseDeserializer deserializer);
+  void sse_encode_list_glance_circle_ffi(
+    List<GlanceCircleFfi> self,
+    SseSerializer serializer,
+  );
 
   @protected
-  BigInt sse_decode_u_64(SseDeserializer deserializer);
+  void sse_encode_list_glance_member_ffi(
+    List<GlanceMemberFfi> self,
+    SseSerializer serializer,
+  );
 
   @protected
-  int sse_decode_u_8(SseDeserializer deserializer);
+  void sse_encode_list_history_point_ffi(
+    List<HistoryPointFfi> self,
+    SseSerializer serializer,
+  );
 
   @protected
-  void sse_decode_unit(SseDeserializer deserializer);
+  void sse_encode_list_invitation_ffi(
+    List<InvitationFfi> self,
+    SseSerializer serializer,
+  );
 
   @protected
-  UnsignedLocationEventFfi sse_decode_unsigned_location_event_ffi(
-    SseDeserializer deserializer,
+  void sse_encode_list_kp_relay_verification_ffi(
+    List<KpRelayVerificationFfi> self,
+    SseSerializer serializer,
   );
 
   @protected
-  BigInt sse_decode_usize(SseDeserializer deserializer);
+  void sse_encode_list_last_known_location_ffi(
+    List<LastKnownLocationFfi> self,
+    SseSerializer serializer,
+  );
 
   @protected
-  void sse_encode_AnyhowException(
-    AnyhowException self,
+  void sse_encode_list_leave_event_ffi(
+    List<LeaveEventFfi> self,
     SseSerializer serializer,
   );
 
   @protected
-  void
-  sse_encode_Auto_Owned_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerCircleManagerFfi(
-    CircleManagerFfi self,
+  void sse_encode_list_linked_circle_ffi(
+    List<LinkedCircleFfi> self,
     SseSerializer serializer,
   );
 
   @protected
-  void
-  sse_encode_Auto_Owned_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerHavenCore(
-    HavenCore self,
+  void sse_encode_list_list_String(
+    List<List<String>> self,
     SseSerializer serializer,
   );
 
   @protected
-  void
-  sse_encode_Auto_Owned_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerLiveSyncFfi(
-    LiveSyncFfi self,
+  void sse_encode_list_list_prim_u_8_strict(
+    List<Uint8List> self,
     SseSerializer serializer,
   );
 
   @protected
-  void
-  sse_encode_Auto_Owned_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerLocationEventService(
-    LocationEventService self,
+  void sse_encode_list_location_message_result_ffi(
+    List<LocationMessageResultFfi> self,
     SseSerializer serializer,
   );
 
   @protected
-  void
-  sse_encode_Auto_Owned_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerLocationMessage(
-    LocationMessage self,
+  void sse_encode_list_meet_pin_ffi(
+    List<MeetPinFfi> self,
+    SseSerializer serializer,
+  );
+
+  @protected
+  void sse_encode_list_member_key_change_ffi(
+    List<MemberKeyChangeFfi> self,
     SseSerializer serializer,
   );
 
   @protected
-  void
-  sse_encode_Auto_Owned_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerLocationSettings(
-    LocationSettings self,
+  void sse_encode_list_member_key_package_ffi(
+    List<MemberKeyPackageFfi> self,
     SseSerializer serializer,
   );
 
   @protected
-  void
-  sse_encode_Auto_Owned_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerNostrIdentityManager(
-    NostrIdentityManager self,
+  void sse_encode_list_member_location_ffi(
+    List<MemberLocationFfi> self,
     SseSerializer serializer,
   );
 
   @protected
-  void
-  sse_encode_Auto_Owned_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerRelayManagerFfi(
-    RelayManagerFfi self,
+  void sse_encode_list_member_presence_ffi(
+    List<MemberPresenceFfi> self,
     SseSerializer serializer,
   );
 
   @protected
-  void
-  sse_encode_Auto_RefMut_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerHavenCore(
-    HavenCore self,
+  void sse_encode_list_message_param_ffi(
+    List<MessageParamFfi> self,
     SseSerializer serializer,
   );
 
   @protected
-  void
-  sse_encode_Auto_Ref_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerCircleManagerFfi(
-    CircleManagerFfi self,
+  void sse_encode_list_missed_checkin_ffi(
+    List<MissedCheckinFfi> self,
     SseSerializer serializer,
   );
 
   @protected
-  void
-  sse_encode_Auto_Ref_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerHavenCore(
-    HavenCore self,
+  void sse_encode_list_one_shot_location_ffi(
+    List<OneShotLocationFfi> self,
     SseSerializer serializer,
   );
 
   @protected
-  void
-  sse_encode_Auto_Ref_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerLiveSyncFfi(
-    LiveSyncFfi self,
+  void sse_encode_list_outbox_entry_ffi(
+    List<OutboxEntryFfi> self,
     SseSerializer serializer,
   );
 
   @protected
-  void
-  sse_encode_Auto_Ref_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerLocationEventService(
-    LocationEventService self,
+  void sse_encode_list_prim_u_16_strict(
+    Uint16List self,
     SseSerializer serializer,
   );
 
   @protected
-  void
-  sse_encode_Auto_Ref_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerLocationMessage(
-    LocationMessage self,
+  void sse_encode_list_prim_u_8_loose(List<int> self, SseSerializer serializer);
+
+  @protected
+  void sse_encode_list_prim_u_8_strict(
+    Uint8List self,
     SseSerializer serializer,
   );
 
   @protected
-  void
-  sse_encode_Auto_Ref_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerLocationSettings(
-    LocationSettings self,
+  void sse_encode_list_profile_metadata_ffi(
+    List<ProfileMetadataFfi> self,
     SseSerializer serializer,
   );
 
   @protected
-  void
-  sse_encode_Auto_Ref_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerNostrIdentityManager(
-    NostrIdentityManager self,
+  void sse_encode_list_proximity_alert_ffi(
+    List<ProximityAlertFfi> self,
     SseSerializer serializer,
   );
 
   @protected
-  void
-  sse_encode_Auto_Ref_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerRelayManagerFfi(
-    RelayManagerFfi self,
+  void sse_encode_list_proximity_rule_ffi(
+    List<ProximityRuleFfi> self,
     SseSerializer serializer,
   );
 
   @protected
-  void
-  sse_encode_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerCircleManagerFfi(
-    CircleManagerFfi self,
+  void sse_encode_list_rejoin_request_ffi(
+    List<RejoinRequestFfi> self,
     SseSerializer serializer,
   );
 
   @protected
-  void
-  sse_encode_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerHavenCore(
-    HavenCore self,
+  void sse_encode_list_relay_connection_status_ffi(
+    List<RelayConnectionStatusFfi> self,
     SseSerializer serializer,
   );
 
   @protected
-  void
-  sse_encode_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerLiveSyncFfi(
-    LiveSyncFfi self,
+  void sse_encode_list_relay_gift_wrap_fetch_ffi(
+    List<RelayGiftWrapFetchFfi> self,
     SseSerializer serializer,
   );
 
   @protected
-  void
-  sse_encode_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerLocationEventService(
-    LocationEventService self,
+  void sse_encode_list_relay_metrics_ffi(
+    List<RelayMetricsFfi> self,
     SseSerializer serializer,
   );
 
   @protected
-  void
-  sse_encode_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerLocationMessage(
-    LocationMessage self,
+  void sse_encode_list_relay_override_entry_ffi(
+    List<RelayOverrideEntryFfi> self,
     SseSerializer serializer,
   );
 
   @protected
-  void
-  sse_encode_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerLocationSettings(
-    LocationSettings self,
+  void sse_encode_list_relay_rejection_ffi(
+    List<RelayRejectionFfi> self,
     SseSerializer serializer,
   );
 
   @protected
-  void
-  sse_encode_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerNostrIdentityManager(
-    NostrIdentityManager self,
+  void sse_encode_list_relay_usage_ffi(
+    List<RelayUsageFfi> self,
     SseSerializer serializer,
   );
 
   @protected
-  void
-  sse_encode_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerRelayManagerFfi(
-    RelayManagerFfi self,
+  void sse_encode_list_self_test_result_ffi(
+    List<SelfTestResultFfi> self,
     SseSerializer serializer,
   );
 
   @protected
-  void sse_encode_StreamSink_ffi_relay_event_Sse(
-    RustStreamSink<FfiRelayEvent> self,
+  void sse_encode_list_sharing_session_ffi(
+    List<SharingSessionFfi> self,
     SseSerializer serializer,
   );
 
   @protected
-  void sse_encode_String(String self, SseSerializer serializer);
+  void sse_encode_list_trip_ffi(List<TripFfi> self, SseSerializer serializer);
 
   @protected
-  void sse_encode_add_members_result_ffi(
-    AddMembersResultFfi self,
+  void sse_encode_list_unjoined_member_ffi(
+    List<UnjoinedMemberFfi> self,
     SseSerializer serializer,
   );
 
   @protected
-  void sse_encode_bool(bool self, SseSerializer serializer);
+  void sse_encode_list_welcome_relay_delivery_ffi(
+    List<WelcomeRelayDeliveryFfi> self,
+    SseSerializer serializer,
+  );
 
   @protected
-  void sse_encode_box_autoadd_circle_with_members_ffi(
-    CircleWithMembersFfi self,
+  void sse_encode_location_message_result_ffi(
+    LocationMessageResultFfi self,
     SseSerializer serializer,
   );
 
   @protected
-  void sse_encode_box_autoadd_contact_ffi(
-    ContactFfi self,
+  void sse_encode_location_message_result_kind_ffi(
+    LocationMessageResultKindFfi self,
     SseSerializer serializer,
   );
 
   @protected
-  void sse_encode_box_autoadd_decrypted_location_ffi(
-    DecryptedLocationFfi self,
+  void sse_encode_location_precision_ffi(
+    LocationPrecisionFfi self,
     SseSerializer serializer,
   );
 
   @protected
-  void sse_encode_box_autoadd_ffi_group_spec(
-    FfiGroupSpec self,
+  void sse_encode_location_retraction_ffi(
+    LocationRetractionFfi self,
     SseSerializer serializer,
   );
 
   @protected
-  void sse_encode_box_autoadd_ffi_sync_status_reason(
-    FfiSyncStatusReason self,
+  void sse_encode_meet_pin_ffi(MeetPinFfi self, SseSerializer serializer);
+
+  @protected
+  void sse_encode_member_key_change_ffi(
+    MemberKeyChangeFfi self,
     SseSerializer serializer,
   );
 
   @protected
-  void sse_encode_box_autoadd_i_64(
-    PlatformInt64 self,
+  void sse_encode_member_key_package_ffi(
+    MemberKeyPackageFfi self,
     SseSerializer serializer,
   );
 
   @protected
-  void sse_encode_box_autoadd_invitation_ffi(
-    InvitationFfi self,
+  void sse_encode_member_location_ffi(
+    MemberLocationFfi self,
     SseSerializer serializer,
   );
 
   @protected
-  void sse_encode_box_autoadd_last_known_location_ffi(
-    LastKnownLocationFfi self,
+  void sse_encode_member_presence_ffi(
+    MemberPresenceFfi self,
     SseSerializer serializer,
   );
 
   @protected
-  void sse_encode_box_autoadd_member_key_package_ffi(
-    MemberKeyPackageFfi self,
+  void sse_encode_membership_delta_ffi(
+    MembershipDeltaFfi self,
     SseSerializer serializer,
   );
 
   @protected
-  void sse_encode_box_autoadd_pending_state_ref_ffi(
-    PendingStateRefFfi self,
+  void sse_encode_message_param_ffi(
+    MessageParamFfi self,
     SseSerializer serializer,
   );
 
   @protected
-  void sse_encode_box_autoadd_profile_metadata_ffi(
-    ProfileMetadataFfi self,
+  void sse_encode_missed_checkin_ffi(
+    MissedCheckinFfi self,
     SseSerializer serializer,
   );
 
   @protected
-  void sse_encode_box_autoadd_public_identity(
-    PublicIdentity self,
+  void sse_encode_motion_state_ffi(
+    MotionStateFfi self,
     SseSerializer serializer,
   );
 
   @protected
-  void sse_encode_box_autoadd_signed_location_event_ffi(
-    SignedLocationEventFfi self,
+  void sse_encode_nip05_resolution_ffi(
+    Nip05ResolutionFfi self,
     SseSerializer serializer,
   );
 
   @protected
-  void sse_encode_box_autoadd_tile_cache_entry_ffi(
-    TileCacheEntryFfi self,
+  void sse_encode_nprofile_ffi(NprofileFfi self, SseSerializer serializer);
+
+  @protected
+  void sse_encode_one_shot_location_ffi(
+    OneShotLocationFfi self,
     SseSerializer serializer,
   );
 
   @protected
-  void sse_encode_box_autoadd_u_16(int self, SseSerializer serializer);
+  void
+  sse_encode_opt_Auto_Owned_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerLinkedIdentityFfi(
+    LinkedIdentityFfi? self,
+    SseSerializer serializer,
+  );
 
   @protected
-  void sse_encode_box_autoadd_u_32(int self, SseSerializer serializer);
+  void sse_encode_opt_String(String? self, SseSerializer serializer);
 
   @protected
-  void sse_encode_built_relay_list_event_ffi(
-    BuiltRelayListEventFfi self,
+  void sse_encode_opt_box_autoadd_bool(bool? self, SseSerializer serializer);
+
+  @protected
+  void sse_encode_opt_box_autoadd_circle_location_settings_ffi(
+    CircleLocationSettingsFfi? self,
     SseSerializer serializer,
   );
 
   @protected
-  void sse_encode_built_unpublish_ffi(
-    BuiltUnpublishFfi self,
+  void sse_encode_opt_box_autoadd_circle_with_members_ffi(
+    CircleWithMembersFfi? self,
     SseSerializer serializer,
   );
 
   @protected
-  void sse_encode_catchup_result_ffi(
-    CatchupResultFfi self,
+  void sse_encode_opt_box_autoadd_cold_circle_info_ffi(
+    ColdCircleInfoFfi? self,
     SseSerializer serializer,
   );
 
   @protected
-  void sse_encode_circle_creation_result_ffi(
-    CircleCreationResultFfi self,
+  void sse_encode_opt_box_autoadd_contact_ffi(
+    ContactFfi? self,
     SseSerializer serializer,
   );
 
   @protected
-  void sse_encode_circle_ffi(CircleFfi self, SseSerializer serializer);
+  void sse_encode_opt_box_autoadd_decrypt_location_outcome_ffi(
+    DecryptLocationOutcomeFfi? self,
+    SseSerializer serializer,
+  );
 
   @protected
-  void sse_encode_circle_member_ffi(
-    CircleMemberFfi self,
+  void sse_encode_opt_box_autoadd_decrypted_location_ffi(
+    DecryptedLocationFfi? self,
     SseSerializer serializer,
   );
 
   @protected
-  void sse_encode_circle_with_members_ffi(
-    CircleWithMembersFfi self,
+  void sse_encode_opt_box_autoadd_device_status_ffi(
+    DeviceStatusFfi? self,
     SseSerializer serializer,
   );
 
   @protected
-  void sse_encode_commit_to_publish_ffi(
-    CommitToPublishFfi self,
+  void sse_encode_opt_box_autoadd_encrypted_location_ffi(
+    EncryptedLocationFfi? self,
     SseSerializer serializer,
   );
 
   @protected
-  void sse_encode_contact_ffi(ContactFfi self, SseSerializer serializer);
+  void sse_encode_opt_box_autoadd_f_64(double? self, SseSerializer serializer);
 
   @protected
-  void sse_encode_decrypt_location_outcome_ffi(
-    DecryptLocationOutcomeFfi self,
+  void sse_encode_opt_box_autoadd_ffi_sync_status_reason(
+    FfiSyncStatusReason? self,
     SseSerializer serializer,
   );
 
   @protected
-  void sse_encode_decrypted_location_ffi(
-    DecryptedLocationFfi self,
+  void sse_encode_opt_box_autoadd_gift_wrapped_welcome_ffi(
+    GiftWrappedWelcomeFfi? self,
     SseSerializer serializer,
   );
 
   @protected
-  void sse_encode_encrypted_location_ffi(
-    EncryptedLocationFfi self,
+  void sse_encode_opt_box_autoadd_haven_error_ffi(
+    HavenErrorFfi? self,
     SseSerializer serializer,
   );
 
   @protected
-  void sse_encode_f_64(double self, SseSerializer serializer);
-
-  @protected
-  void sse_encode_ffi_group_spec(FfiGroupSpec self, SseSerializer serializer);
-
-  @protected
-  void sse_encode_ffi_relay_event(FfiRelayEvent self, SseSerializer serializer);
+  void sse_encode_opt_box_autoadd_i_16(int? self, SseSerializer serializer);
 
   @protected
-  void sse_encode_ffi_relay_event_kind(
-    FfiRelayEventKind self,
+  void sse_encode_opt_box_autoadd_i_64(
+    PlatformInt64? self,
     SseSerializer serializer,
   );
 
   @protected
-  void sse_encode_ffi_sync_status_reason(
-    FfiSyncStatusReason self,
+  void sse_encode_opt_box_autoadd_invitation_ffi(
+    InvitationFfi? self,
     SseSerializer serializer,
   );
 
   @protected
-  void sse_encode_gift_wrapped_welcome_ffi(
-    GiftWrappedWelcomeFfi self,
+  void sse_encode_opt_box_autoadd_kp_verification_ffi(
+    KpVerificationFfi? self,
     SseSerializer serializer,
   );
 
   @protected
-  void sse_encode_i_32(int self, SseSerializer serializer);
+  void sse_encode_opt_box_autoadd_last_known_location_ffi(
+    LastKnownLocationFfi? self,
+    SseSerializer serializer,
+  );
 
   @protected
-  void sse_encode_i_64(PlatformInt64 self, SseSerializer serializer);
+  void sse_encode_opt_box_autoadd_leave_event_ffi(
+    LeaveEventFfi? self,
+    SseSerializer serializer,
+  );
 
   @protected
-  void sse_encode_invitation_ffi(InvitationFfi self, SseSerializer serializer);
+  void sse_encode_opt_box_autoadd_meet_pin_ffi(
+    MeetPinFfi? self,
+    SseSerializer serializer,
+  );
 
   @protected
-  void sse_encode_kp_maintenance_action_ffi(
-    KpMaintenanceActionFfi self,
+  void sse_encode_opt_box_autoadd_member_key_package_ffi(
+    MemberKeyPackageFfi? self,
     SseSerializer serializer,
   );
 
   @protected
-  void sse_encode_kp_maintenance_outcome_ffi(
-    KpMaintenanceOutcomeFfi self,
+  void sse_encode_opt_box_autoadd_membership_delta_ffi(
+    MembershipDeltaFfi? self,
     SseSerializer serializer,
   );
 
   @protected
-  void sse_encode_last_known_location_ffi(
-    LastKnownLocationFfi self,
+  void sse_encode_opt_box_autoadd_motion_state_ffi(
+    MotionStateFfi? self,
     SseSerializer serializer,
   );
 
   @protected
-  void sse_encode_leave_plan_ffi(LeavePlanFfi self, SseSerializer serializer);
+  void sse_encode_opt_box_autoadd_precision_anomaly_ffi(
+    PrecisionAnomalyFfi? self,
+    SseSerializer serializer,
+  );
 
   @protected
-  void sse_encode_leave_plan_kind_ffi(
-    LeavePlanKindFfi self,
+  void sse_encode_opt_box_autoadd_profile_metadata_ffi(
+    ProfileMetadataFfi? self,
     SseSerializer serializer,
   );
 
   @protected
-  void sse_encode_legacy_retraction_outcome_ffi(
-    LegacyRetractionOutcomeFfi self,
+  void sse_encode_opt_box_autoadd_proximity_estimate_ffi(
+    ProximityEstimateFfi? self,
     SseSerializer serializer,
   );
 
   @protected
-  void sse_encode_list_String(List<String> self, SseSerializer serializer);
+  void sse_encode_opt_box_autoadd_public_identity(
+    PublicIdentity? self,
+    SseSerializer serializer,
+  );
 
   @protected
-  void sse_encode_list_circle_member_ffi(
-    List<CircleMemberFfi> self,
+  void sse_encode_opt_box_autoadd_relay_override_ffi(
+    RelayOverrideFfi? self,
     SseSerializer serializer,
   );
 
   @protected
-  void sse_encode_list_circle_with_members_ffi(
-    List<CircleWithMembersFfi> self,
+  void sse_encode_opt_box_autoadd_remote_signer_ffi(
+    RemoteSignerFfi? self,
     SseSerializer serializer,
   );
 
   @protected
-  void sse_encode_list_commit_to_publish_ffi(
-    List<CommitToPublishFfi> self,
+  void sse_encode_opt_box_autoadd_tile_cache_entry_ffi(
+    TileCacheEntryFfi? self,
     SseSerializer serializer,
   );
 
   @protected
-  void sse_encode_list_contact_ffi(
-    List<ContactFfi> self,
+  void sse_encode_opt_box_autoadd_trip_mode_ffi(
+    TripModeFfi? self,
     SseSerializer serializer,
   );
 
   @protected
-  void sse_encode_list_ffi_group_spec(
-    List<FfiGroupSpec> self,
+  void sse_encode_opt_box_autoadd_u_16(int? self, SseSerializer serializer);
+
+  @protected
+  void sse_encode_opt_box_autoadd_u_32(int? self, SseSerializer serializer);
+
+  @protected
+  void sse_encode_opt_box_autoadd_u_64(BigInt? self, SseSerializer serializer);
+
+  @protected
+  void sse_encode_opt_box_autoadd_u_8(int? self, SseSerializer serializer);
+
+  @protected
+  void sse_encode_opt_box_autoadd_user_message_ffi(
+    UserMessageFfi? self,
     SseSerializer serializer,
   );
 
   @protected
-  void sse_encode_list_gift_wrapped_welcome_ffi(
-    List<GiftWrappedWelcomeFfi> self,
+  void sse_encode_opt_box_autoadd_vertical_position_ffi(
+    VerticalPositionFfi? self,
     SseSerializer serializer,
   );
 
   @protected
-  void sse_encode_list_invitation_ffi(
-    List<InvitationFfi> self,
+  void sse_encode_opt_list_String(List<String>? self, SseSerializer serializer);
+
+  @protected
+  void sse_encode_opt_list_prim_u_8_loose(
+    List<int>? self,
     SseSerializer serializer,
   );
 
   @protected
-  void sse_encode_list_last_known_location_ffi(
-    List<LastKnownLocationFfi> self,
+  void sse_encode_opt_list_prim_u_8_strict(
+    Uint8List? self,
     SseSerializer serializer,
   );
 
   @protected
-  void sse_encode_list_list_String(
-    List<List<String>> self,
+  void sse_encode_outbox_entry_ffi(
+    OutboxEntryFfi self,
     SseSerializer serializer,
   );
 
   @protected
-  void sse_encode_list_location_message_result_ffi(
-    List<LocationMessageResultFfi> self,
+  void sse_encode_outbox_flush_ffi(
+    OutboxFlushFfi self,
     SseSerializer serializer,
   );
 
   @protected
-  void sse_encode_list_member_key_package_ffi(
-    List<MemberKeyPackageFfi> self,
+  void sse_encode_pending_state_ref_ffi(
+    PendingStateRefFfi self,
     SseSerializer serializer,
   );
 
   @protected
-  void sse_encode_list_prim_u_8_loose(List<int> self, SseSerializer serializer);
+  void sse_encode_precision_anomaly_ffi(
+    PrecisionAnomalyFfi self,
+    SseSerializer serializer,
+  );
 
-  @protected
-  void sse_encode_list_prim_u_8_strict(
-    Uint8List self,
+  @protected
+  void sse_encode_precision_class_ffi(
+    PrecisionClassFfi self,
     SseSerializer serializer,
   );
 
   @protected
-  void sse_encode_list_profile_metadata_ffi(
-    List<ProfileMetadataFfi> self,
+  void sse_encode_presence_status_ffi(
+    PresenceStatusFfi self,
     SseSerializer serializer,
   );
 
   @protected
-  void sse_encode_list_relay_connection_status_ffi(
-    List<RelayConnectionStatusFfi> self,
+  void sse_encode_privacy_facts_ffi(
+    PrivacyFactsFfi self,
     SseSerializer serializer,
   );
 
   @protected
-  void sse_encode_list_relay_gift_wrap_fetch_ffi(
-    List<RelayGiftWrapFetchFfi> self,
+  void sse_encode_privacy_settings_ffi(
+    PrivacySettingsFfi self,
     SseSerializer serializer,
   );
 
   @protected
-  void sse_encode_list_relay_rejection_ffi(
-    List<RelayRejectionFfi> self,
+  void sse_encode_profile_metadata_ffi(
+    ProfileMetadataFfi self,
     SseSerializer serializer,
   );
 
   @protected
-  void sse_encode_location_message_result_ffi(
-    LocationMessageResultFfi self,
+  void sse_encode_profile_picture_ref_ffi(
+    ProfilePictureRefFfi self,
     SseSerializer serializer,
   );
 
   @protected
-  void sse_encode_location_message_result_kind_ffi(
-    LocationMessageResultKindFfi self,
+  void sse_encode_proximity_alert_ffi(
+    ProximityAlertFfi self,
     SseSerializer serializer,
   );
 
   @protected
-  void sse_encode_member_key_package_ffi(
-    MemberKeyPackageFfi self,
+  void sse_encode_proximity_estimate_ffi(
+    ProximityEstimateFfi self,
     SseSerializer serializer,
   );
 
   @protected
-  void sse_encode_opt_String(String? self, SseSerializer serializer);
+  void sse_encode_proximity_rule_ffi(
+    ProximityRuleFfi self,
+    SseSerializer serializer,
+  );
 
   @protected
-  void sse_encode_opt_box_autoadd_circle_with_members_ffi(
-    CircleWithMembersFfi? self,
+  void sse_encode_proximity_target_ffi(
+    ProximityTargetFfi self,
     SseSerializer serializer,
   );
 
   @protected
-  void sse_encode_opt_box_autoadd_contact_ffi(
-    ContactFfi? self,
+  void sse_encode_public_identity(
+    PublicIdentity self,
     SseSerializer serializer,
   );
 
   @protected
-  void sse_encode_opt_box_autoadd_decrypted_location_ffi(
-    DecryptedLocationFfi? self,
+  void sse_encode_publish_quorum_ffi(
+    PublishQuorumFfi self,
     SseSerializer serializer,
   );
 
   @protected
-  void sse_encode_opt_box_autoadd_ffi_sync_status_reason(
-    FfiSyncStatusReason? self,
+  void sse_encode_publish_quorum_kind_ffi(
+    PublishQuorumKindFfi self,
     SseSerializer serializer,
   );
 
   @protected
-  void sse_encode_opt_box_autoadd_i_64(
-    PlatformInt64? self,
+  void sse_encode_publish_result_ffi(
+    PublishResultFfi self,
     SseSerializer serializer,
   );
 
   @protected
-  void sse_encode_opt_box_autoadd_invitation_ffi(
-    InvitationFfi? self,
+  void sse_encode_quorum_publish_update_ffi(
+    QuorumPublishUpdateFfi self,
     SseSerializer serializer,
   );
 
   @protected
-  void sse_encode_opt_box_autoadd_member_key_package_ffi(
-    MemberKeyPackageFfi? self,
+  void sse_encode_rejoin_request_ffi(
+    RejoinRequestFfi self,
     SseSerializer serializer,
   );
 
   @protected
-  void sse_encode_opt_box_autoadd_profile_metadata_ffi(
-    ProfileMetadataFfi? self,
+  void sse_encode_relay_connection_status_ffi(
+    RelayConnectionStatusFfi self,
     SseSerializer serializer,
   );
 
   @protected
-  void sse_encode_opt_box_autoadd_public_identity(
-    PublicIdentity? self,
+  void sse_encode_relay_event_check_ffi(
+    RelayEventCheckFfi self,
     SseSerializer serializer,
   );
 
   @protected
-  void sse_encode_opt_box_autoadd_tile_cache_entry_ffi(
-    TileCacheEntryFfi? self,
+  void sse_encode_relay_gift_wrap_fetch_ffi(
+    RelayGiftWrapFetchFfi self,
     SseSerializer serializer,
   );
 
   @protected
-  void sse_encode_opt_box_autoadd_u_16(int? self, SseSerializer serializer);
+  void sse_encode_relay_info_ffi(RelayInfoFfi self, SseSerializer serializer);
 
   @protected
-  void sse_encode_opt_box_autoadd_u_32(int? self, SseSerializer serializer);
+  void sse_encode_relay_list_action_ffi(
+    RelayListActionFfi self,
+    SseSerializer serializer,
+  );
 
   @protected
-  void sse_encode_opt_list_prim_u_8_strict(
-    Uint8List? self,
+  void sse_encode_relay_list_category_outcome_ffi(
+    RelayListCategoryOutcomeFfi self,
     SseSerializer serializer,
   );
 
   @protected
-  void sse_encode_pending_state_ref_ffi(
-    PendingStateRefFfi self,
+  void sse_encode_relay_list_flush_ffi(
+    RelayListFlushFfi self,
     SseSerializer serializer,
   );
 
   @protected
-  void sse_encode_profile_metadata_ffi(
-    ProfileMetadataFfi self,
+  void sse_encode_relay_list_maintenance_outcome_ffi(
+    RelayListMaintenanceOutcomeFfi self,
     SseSerializer serializer,
   );
 
   @protected
-  void sse_encode_profile_picture_ref_ffi(
-    ProfilePictureRefFfi self,
+  void sse_encode_relay_metrics_ffi(
+    RelayMetricsFfi self,
     SseSerializer serializer,
   );
 
   @protected
-  void sse_encode_public_identity(
-    PublicIdentity self,
+  void sse_encode_relay_override_entry_ffi(
+    RelayOverrideEntryFfi self,
     SseSerializer serializer,
   );
 
   @protected
-  void sse_encode_publish_result_ffi(
-    PublishResultFfi self,
+  void sse_encode_relay_override_ffi(
+    RelayOverrideFfi self,
     SseSerializer serializer,
   );
 
   @protected
-  void sse_encode_relay_connection_status_ffi(
-    RelayConnectionStatusFfi self,
+  void sse_encode_relay_rejection_ffi(
+    RelayRejectionFfi self,
     SseSerializer serializer,
   );
 
   @protected
-  void sse_encode_relay_event_check_ffi(
-    RelayEventCheckFfi self,
+  void sse_encode_relay_type_ffi(RelayTypeFfi self, SseSerializer serializer);
+
+  @protected
+  void sse_encode_relay_usage_ffi(RelayUsageFfi self, SseSerializer serializer);
+
+  @protected
+  void sse_encode_remote_signer_ffi(
+    RemoteSignerFfi self,
     SseSerializer serializer,
   );
 
   @protected
-  void sse_encode_relay_gift_wrap_fetch_ffi(
-    RelayGiftWrapFetchFfi self,
+  void sse_encode_repair_outcome_ffi(
+    RepairOutcomeFfi self,
     SseSerializer serializer,
   );
 
   @protected
-  void sse_encode_relay_list_action_ffi(
-    RelayListActionFfi self,
+  void sse_encode_self_test_check_ffi(
+    SelfTestCheckFfi self,
     SseSerializer serializer,
   );
 
   @protected
-  void sse_encode_relay_list_category_outcome_ffi(
-    RelayListCategoryOutcomeFfi self,
+  void sse_encode_self_test_report_ffi(
+    SelfTestReportFfi self,
     SseSerializer serializer,
   );
 
   @protected
-  void sse_encode_relay_list_maintenance_outcome_ffi(
-    RelayListMaintenanceOutcomeFfi self,
+  void sse_encode_self_test_result_ffi(
+    SelfTestResultFfi self,
     SseSerializer serializer,
   );
 
   @protected
-  void sse_encode_relay_rejection_ffi(
-    RelayRejectionFfi self,
+  void sse_encode_share_preview_ffi(
+    SharePreviewFfi self,
     SseSerializer serializer,
   );
 
   @protected
-  void sse_encode_relay_type_ffi(RelayTypeFfi self, SseSerializer serializer);
+  void sse_encode_sharing_session_ffi(
+    SharingSessionFfi self,
+    SseSerializer serializer,
+  );
 
   @protected
   void sse_encode_signed_location_event_ffi(
@@ -1884,6 +4894,9 @@ abstract class RustLibApiImplPlatform extends BaseApiImpl<RustLibWire> {
     SseSerializer serializer,
   );
 
+  @protected
+  void sse_encode_sos_fanout_ffi(SosFanoutFfi self, SseSerializer serializer);
+
   @protected
   void sse_encode_subscription_health_action_ffi(
     SubscriptionHealthActionFfi self,
@@ -1896,12 +4909,27 @@ abstract class RustLibApiImplPlatform extends BaseApiImpl<RustLibWire> {
     SseSerializer serializer,
   );
 
+  @protected
+  void sse_encode_sync_digest_ffi(SyncDigestFfi self, SseSerializer serializer);
+
   @protected
   void sse_encode_tile_cache_entry_ffi(
     TileCacheEntryFfi self,
     SseSerializer serializer,
   );
 
+  @protected
+  void sse_encode_transfer_restore_ffi(
+    TransferRestoreFfi self,
+    SseSerializer serializer,
+  );
+
+  @protected
+  void sse_encode_trip_ffi(TripFfi self, SseSerializer serializer);
+
+  @protected
+  void sse_encode_trip_mode_ffi(TripModeFfi self, SseSerializer serializer);
+
   @protected
   void sse_encode_u_16(int self, SseSerializer serializer);
 
@@ -1917,14 +4945,53 @@ abstract class RustLibApiImplPlatform extends BaseApiImpl<RustLibWire> {
   @protected
   void sse_encode_unit(void self, SseSerializer serializer);
 
+  @protected
+  void sse_encode_unjoined_member_ffi(
+    UnjoinedMemberFfi self,
+    SseSerializer serializer,
+  );
+
   @protected
   void sse_encode_unsigned_location_event_ffi(
     UnsignedLocationEventFfi self,
     SseSerializer serializer,
   );
 
+  @protected
+  void sse_encode_usage_ffi(UsageFfi self, SseSerializer serializer);
+
+  @protected
+  void sse_encode_user_message_ffi(
+    UserMessageFfi self,
+    SseSerializer serializer,
+  );
+
   @protected
   void sse_encode_usize(BigInt self, SseSerializer serializer);
+
+  @protected
+  void sse_encode_vertical_position_ffi(
+    VerticalPositionFfi self,
+    SseSerializer serializer,
+  );
+
+  @protected
+  void sse_encode_welcome_delivery_report_ffi(
+    WelcomeDeliveryReportFfi self,
+    SseSerializer serializer,
+  );
+
+  @protected
+  void sse_encode_welcome_relay_delivery_ffi(
+    WelcomeRelayDeliveryFfi self,
+    SseSerializer serializer,
+  );
+
+  @protected
+  void sse_encode_welcome_relay_outcome_ffi(
+    WelcomeRelayOutcomeFfi self,
+    SseSerializer serializer,
+  );
 }
 
 // Section: wire_class
@@ -1948,6 +5015,38 @@ class RustLibWire implements BaseWire {
         ptr,
       );
 
+  void
+  rust_arc_increment_strong_count_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerDeviceLinkSessionFfi(
+    int ptr,
+  ) => wasmModule
+      .rust_arc_increment_strong_count_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerDeviceLinkSessionFfi(
+        ptr,
+      );
+
+  void
+  rust_arc_decrement_strong_count_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerDeviceLinkSessionFfi(
+    int ptr,
+  ) => wasmModule
+      .rust_arc_decrement_strong_count_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerDeviceLinkSessionFfi(
+        ptr,
+      );
+
+  void
+  rust_arc_increment_strong_count_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerExternalSignerFfi(
+    int ptr,
+  ) => wasmModule
+      .rust_arc_increment_strong_count_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerExternalSignerFfi(
+        ptr,
+      );
+
+  void
+  rust_arc_decrement_strong_count_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerExternalSignerFfi(
+    int ptr,
+  ) => wasmModule
+      .rust_arc_decrement_strong_count_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerExternalSignerFfi(
+        ptr,
+      );
+
   void
   rust_arc_increment_strong_count_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerHavenCore(
     int ptr,
@@ -1964,6 +5063,22 @@ class RustLibWire implements BaseWire {
         ptr,
       );
 
+  void
+  rust_arc_increment_strong_count_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerLinkedIdentityFfi(
+    int ptr,
+  ) => wasmModule
+      .rust_arc_increment_strong_count_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerLinkedIdentityFfi(
+        ptr,
+      );
+
+  void
+  rust_arc_decrement_strong_count_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerLinkedIdentityFfi(
+    int ptr,
+  ) => wasmModule
+      .rust_arc_decrement_strong_count_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerLinkedIdentityFfi(
+        ptr,
+      );
+
   void
   rust_arc_increment_strong_count_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerLiveSyncFfi(
     int ptr,
@@ -2059,6 +5174,54 @@ class RustLibWire implements BaseWire {
       .rust_arc_decrement_strong_count_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerRelayManagerFfi(
         ptr,
       );
+
+  void
+  rust_arc_increment_strong_count_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerSecretHandle(
+    int ptr,
+  ) => wasmModule
+      .rust_arc_increment_strong_count_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerSecretHandle(
+        ptr,
+      );
+
+  void
+  rust_arc_decrement_strong_count_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerSecretHandle(
+    int ptr,
+  ) => wasmModule
+      .rust_arc_decrement_strong_count_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerSecretHandle(
+        ptr,
+      );
+
+  void
+  rust_arc_increment_strong_count_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerTransferBundleFfi(
+    int ptr,
+  ) => wasmModule
+      .rust_arc_increment_strong_count_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerTransferBundleFfi(
+        ptr,
+      );
+
+  void
+  rust_arc_decrement_strong_count_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerTransferBundleFfi(
+    int ptr,
+  ) => wasmModule
+      .rust_arc_decrement_strong_count_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerTransferBundleFfi(
+        ptr,
+      );
+
+  void
+  rust_arc_increment_strong_count_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerTransferContentsFfi(
+    int ptr,
+  ) => wasmModule
+      .rust_arc_increment_strong_count_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerTransferContentsFfi(
+        ptr,
+      );
+
+  void
+  rust_arc_decrement_strong_count_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerTransferContentsFfi(
+    int ptr,
+  ) => wasmModule
+      .rust_arc_decrement_strong_count_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerTransferContentsFfi(
+        ptr,
+      );
 }
 
 @JS('wasm_bindgen')
@@ -2077,6 +5240,26 @@ extension type RustLibWasmModule._(JSObject _) implements JSObject {
     int ptr,
   );
 
+  external void
+  rust_arc_increment_strong_count_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerDeviceLinkSessionFfi(
+    int ptr,
+  );
+
+  external void
+  rust_arc_decrement_strong_count_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerDeviceLinkSessionFfi(
+    int ptr,
+  );
+
+  external void
+  rust_arc_increment_strong_count_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerExternalSignerFfi(
+    int ptr,
+  );
+
+  external void
+  rust_arc_decrement_strong_count_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerExternalSignerFfi(
+    int ptr,
+  );
+
   external void
   rust_arc_increment_strong_count_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerHavenCore(
     int ptr,
@@ -2087,6 +5270,16 @@ extension type RustLibWasmModule._(JSObject _) implements JSObject {
     int ptr,
   );
 
+  external void
+  rust_arc_increment_strong_count_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerLinkedIdentityFfi(
+    int ptr,
+  );
+
+  external void
+  rust_arc_decrement_strong_count_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerLinkedIdentityFfi(
+    int ptr,
+  );
+
   external void
   rust_arc_increment_strong_count_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerLiveSyncFfi(
     int ptr,
@@ -2146,4 +5339,34 @@ extension type RustLibWasmModule._(JSObject _) implements JSObject {
   rust_arc_decrement_strong_count_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerRelayManagerFfi(
     int ptr,
   );
+
+  external void
+  rust_arc_increment_strong_count_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerSecretHandle(
+    int ptr,
+  );
+
+  external void
+  rust_arc_decrement_strong_count_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerSecretHandle(
+    int ptr,
+  );
+
+  external void
+  rust_arc_increment_strong_count_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerTransferBundleFfi(
+    int ptr,
+  );
+
+  external void
+  rust_arc_decrement_strong_count_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerTransferBundleFfi(
+    int ptr,
+  );
+
+  external void
+  rust_arc_increment_strong_count_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerTransferContentsFfi(
+    int ptr,
+  );
+
+  external void
+  rust_arc_decrement_strong_count_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerTransferContentsFfi(
+    int ptr,
+  );
 }
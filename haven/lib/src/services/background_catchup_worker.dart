@@ -82,8 +82,10 @@ import 'package:haven/src/rust/api.dart';
 import 'package:haven/src/rust/frb_generated.dart';
 import 'package:haven/src/services/background_location_manager.dart';
 import 'package:haven/src/services/catchup_service.dart';
+import 'package:haven/src/services/fresh_secret.dart';
 import 'package:haven/src/services/nostr_relay_service.dart';
 import 'package:haven/src/services/pending_mls_wipe_service.dart';
+import 'package:haven/src/utils/ffi_error.dart';
 import 'package:shared_preferences/shared_preferences.dart';
 import 'package:workmanager/workmanager.dart';
 
@@ -315,11 +317,12 @@ Future<bool> runBackgroundCatchupTask({
     // (and this isolate also replicates main()'s release debugPrint silencer),
     // so this never reaches a production log. It surfaces the reason so a
     // cold-worker BOOTSTRAP failure is debuggable — e.g. the e2e-background-catchup
-    // CI lane, where a reused worker process double-inits RustLib. FFI Result
-    // errors are hex-redacted on the Rust side, and a PanicException carries a
-    // Rust panic location (bug info), not key material.
+    // CI lane, where a reused worker process double-inits RustLib. A
+    // HavenErrorFfi detail is hex-redacted on the Rust side, and a
+    // PanicException carries a Rust panic location (bug info), not key
+    // material.
     assert(() {
-      debugPrint('[CatchupWorker] sweep failed detail: $e');
+      debugPrint('[CatchupWorker] sweep failed detail: ${ffiErrorDetail(e)}');
       return true;
     }(), 'debug-only cold-worker failure diagnostic');
     return false;
@@ -503,10 +506,9 @@ Future<void> _runCatchupViaWorkerBootstrap() async {
   //    (account identity + NIP-59 welcome signer + account-identity-proof
   //    signer); re-fetched fresh here rather than reusing `bytes` above
   //    (already zeroized) — Security Rule 9.
-  final identitySecretBytes = await identityManager.getSecretBytes();
-  final circleManager = await CircleManagerFfi.newInstance(
-    dataDir: dataDir,
-    identitySecretBytes: identitySecretBytes,
+  final circleManager = await withFreshSecret(
+    identityManager.secretHandle,
+    (secret) => CircleManagerFfi.newInstance(dataDir: dataDir, secret: secret),
   );
 
   // 6. Relay service.
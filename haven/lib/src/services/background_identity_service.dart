@@ -83,8 +83,8 @@ class BackgroundIdentityService implements IdentityService {
   );
 
   @override
-  Future<List<int>> getSecretBytes() => throw UnimplementedError(
-    'BackgroundIdentityService does not support getSecretBytes',
+  Future<SecretHandle> getSecretHandle() => throw UnimplementedError(
+    'BackgroundIdentityService does not support getSecretHandle',
   );
 
   @override
@@ -34,6 +34,7 @@ import 'package:haven/src/rust/frb_generated.dart';
 import 'package:haven/src/services/background_identity_service.dart';
 import 'package:haven/src/services/background_location_manager.dart';
 import 'package:haven/src/services/circle_service.dart';
+import 'package:haven/src/services/fresh_secret.dart';
 import 'package:haven/src/services/geolocator_location_service.dart';
 import 'package:haven/src/services/location_sharing_service.dart';
 import 'package:haven/src/services/nostr_circle_service.dart';
@@ -183,10 +184,10 @@ class BackgroundLocationTaskHandler extends TaskHandler {
       } else if (_identityManager!.hasIdentity()) {
         // Re-fetched fresh rather than reusing `bytes` above (already
         // zeroized) — Security Rule 9.
-        final identitySecretBytes = await _identityManager!.getSecretBytes();
-        _circleManager = await CircleManagerFfi.newInstance(
-          dataDir: dataDir,
-          identitySecretBytes: identitySecretBytes,
+        _circleManager = await withFreshSecret(
+          _identityManager!.secretHandle,
+          (secret) =>
+              CircleManagerFfi.newInstance(dataDir: dataDir, secret: secret),
         );
       }
 
@@ -416,6 +417,12 @@ class BackgroundLocationTaskHandler extends TaskHandler {
             updateIntervalSecs: BigInt.from(
               kLocationPublishMaxInterval.inSeconds + kTtlNetworkBufferSeconds,
             ),
+            // Members keep the location for the 1-day default.
+            shareExpirationSecs: null,
+            // Battery and motion status are opt-in and not collected here.
+            deviceStatus: null,
+            // Altitude and floor level are opt-in and not collected here.
+            verticalPosition: null,
           );
 
           await _relayService!.publishEvent(
@@ -9,6 +9,7 @@
 library;
 
 import 'package:flutter/foundation.dart';
+import 'package:haven/src/rust/api.dart' show SecretHandle;
 
 /// Exception thrown when circle operations fail.
 class CircleServiceException implements Exception {
@@ -464,8 +465,8 @@ class DecryptLocationOutcome {
 abstract class CircleService {
   /// Creates a new circle with the given members.
   ///
-  /// The [identitySecretBytes] are the creator's identity secret bytes
-  /// (32 bytes). The [memberKeyPackages] are the fetched KeyPackage data
+  /// The [secret] is a handle to the creator's identity key. The
+  /// [memberKeyPackages] are the fetched KeyPackage data
   /// for each member. The [name] is the user-facing display name.
   ///
   /// Publishes the gift-wrapped Welcome events and confirms (or rolls back)
@@ -481,7 +482,7 @@ abstract class CircleService {
   ///
   /// Throws [CircleServiceException] if creation fails.
   Future<CircleCreationResult> createCircle({
-    required List<int> identitySecretBytes,
+    required SecretHandle secret,
     required List<KeyPackageData> memberKeyPackages,
     required String name,
     required CircleType circleType,
@@ -598,16 +599,16 @@ abstract class CircleService {
   /// [creatorFallbackRelays] are the adder's own inbox relays (kind 10050),
   /// the third tier of the Welcome-delivery cascade.
   ///
-  /// [secretProvider] yields the caller's 32-byte identity secret; it is
-  /// invoked FRESH for each staging attempt (and scrubbed straight after) so
-  /// the plaintext is never held across a settle-window wait (Rule 9). Under
-  /// live-sync convergence an add re-stages up to three times, so passing raw
-  /// bytes would keep the secret resident for ~24s.
+  /// [secretProvider] yields a handle to the caller's identity key; it is
+  /// invoked FRESH for each staging attempt (and disposed straight after) so
+  /// no key copy is held across a settle-window wait (Rule 9). Under
+  /// live-sync convergence an add re-stages up to three times, so passing one
+  /// handle would keep the secret resident for ~24s.
   ///
   /// Throws [CircleServiceException] if not admin, relays unavailable,
   /// staging/publish/finalize fails, or delivery fails closed.
   Future<AddMemberResult> addMember({
-    required Future<List<int>> Function() secretProvider,
+    required Future<SecretHandle> Function() secretProvider,
     required List<int> mlsGroupId,
     required List<KeyPackageData> memberKeyPackages,
     List<String> creatorFallbackRelays = const [],
@@ -630,7 +631,7 @@ abstract class CircleService {
   /// Throws [CircleServiceException] for real failures (malformed event,
   /// MDK error, storage failure).
   Future<Invitation?> processGiftWrappedInvitation({
-    required List<int> identitySecretBytes,
+    required SecretHandle secret,
     required String giftWrapEventJson,
   });
 
@@ -767,7 +768,7 @@ abstract class CircleService {
   ///
   /// Throws [CircleServiceException] if signing fails.
   Future<String> signDeletionEvent({
-    required List<int> identitySecretBytes,
+    required SecretHandle secret,
     required List<String> eventIds,
   });
 
@@ -10,6 +10,7 @@
 library;
 
 import 'package:flutter/foundation.dart';
+import 'package:haven/src/rust/api.dart' show SecretHandle;
 
 /// Exception thrown when identity operations fail.
 class IdentityServiceException implements Exception {
@@ -131,16 +132,15 @@ abstract class IdentityService {
   /// Throws [IdentityServiceException] if no identity exists.
   Future<String> getPubkeyHex();
 
-  /// Gets the secret key as raw bytes.
+  /// Gets an opaque handle to the secret key.
   ///
-  /// Returns 32 bytes of the secret key for operations requiring
-  /// the identity (e.g., gift-wrapping welcome events).
-  ///
-  /// **Security Warning**: Handle these bytes carefully. They should only be
-  /// passed to FFI operations and never logged or stored unencrypted.
+  /// The key stays in Rust memory; the handle is what every FFI operation
+  /// that signs as the identity (e.g., gift-wrapping welcome events) takes.
+  /// Call [SecretHandle.dispose] once done so the key is zeroized promptly
+  /// rather than at garbage collection.
   ///
   /// Throws [IdentityServiceException] if no identity exists.
-  Future<List<int>> getSecretBytes();
+  Future<SecretHandle> getSecretHandle();
 
   /// Deletes the identity from secure storage.
   ///
@@ -5,20 +5,20 @@
 /// Used by the [`maintenanceSchedulerProvider`] timers. Best-effort — it never
 /// throws into its caller. The heavy lifting (relay probe, live-material gate,
 /// stable-`d` seeding, sign + publish) happens in Rust; this only resolves the
-/// FFI handle + the identity secret, forwards to [RelayService], and scrubs the
-/// secret buffer afterwards.
+/// FFI handle + the identity secret, forwards to [RelayService], and disposes
+/// the secret handle afterwards.
 ///
 /// ## Secret lifetime (Security Rule 9)
 ///
-/// Each task **re-fetches** the identity secret per call, copies it into a
-/// [Uint8List] buffer this service owns, passes it to the FFI (which consumes
-/// and zeroizes it Rust-side), and scrubs the Dart-side buffer in a `finally`.
-/// The secret is never held across ticks.
+/// Each task **re-fetches** a [SecretHandle] per call, passes it to the FFI,
+/// and disposes it in a `finally` so the Rust-side key copy is zeroized. The
+/// key never reaches the Dart heap and no handle is held across ticks.
 library;
 
 import 'package:flutter/foundation.dart';
 
 import 'package:haven/src/rust/api.dart';
+import 'package:haven/src/services/fresh_secret.dart';
 import 'package:haven/src/services/relay_service.dart';
 
 /// Runs the scheduled M8 maintenance tasks.
@@ -27,14 +27,14 @@ class MaintenanceService {
   /// unit-testable without the FFI bridge).
   MaintenanceService({
     required Future<CircleManagerFfi> Function() circleManagerFactory,
-    required Future<List<int>> Function() identitySecretBytes,
+    required Future<SecretHandle> Function() identitySecret,
     required RelayService relayService,
   }) : _circleManagerFactory = circleManagerFactory,
-       _identitySecretBytes = identitySecretBytes,
+       _identitySecret = identitySecret,
        _relayService = relayService;
 
   final Future<CircleManagerFfi> Function() _circleManagerFactory;
-  final Future<List<int>> Function() _identitySecretBytes;
+  final Future<SecretHandle> Function() _identitySecret;
   final RelayService _relayService;
 
   /// Runs a `KeyPackage` maintenance tick (kinds 30443 + 443).
@@ -45,7 +45,7 @@ class MaintenanceService {
     return _withSecret(
       (circle, secret) => _relayService.maintainKeyPackage(
         circle: circle,
-        identitySecretBytes: secret,
+        secret: secret,
       ),
       onFailure: const KeyPackageMaintenanceResult.empty(),
       label: 'KeyPackage',
@@ -60,7 +60,7 @@ class MaintenanceService {
     return _withSecret(
       (circle, secret) => _relayService.maintainRelayList(
         circle: circle,
-        identitySecretBytes: secret,
+        secret: secret,
       ),
       onFailure: const RelayListMaintenanceResult.empty(),
       label: 'relay-list',
@@ -81,7 +81,7 @@ class MaintenanceService {
     return _withSecret(
       (circle, secret) => _relayService.retractLegacyKeyMaterial(
         circle: circle,
-        identitySecretBytes: secret,
+        secret: secret,
       ),
       onFailure: const LegacyRetractionResult.empty(),
       label: 'legacy-retraction',
@@ -103,26 +103,24 @@ class MaintenanceService {
     }
   }
 
-  /// Resolves the circle handle + a scrubbed-in-`finally` secret buffer, runs
+  /// Resolves the circle handle + a disposed-in-`finally` secret handle, runs
   /// [op], and returns [onFailure] if any step throws (fail-soft).
   Future<T> _withSecret<T>(
-    Future<T> Function(CircleManagerFfi circle, Uint8List secret) op, {
+    Future<T> Function(CircleManagerFfi circle, SecretHandle secret) op, {
     required T onFailure,
     required String label,
   }) async {
-    // Hold the secret-bytes copy in a typed buffer so we can `fillRange` it on
-    // exit, minimising the window the secret sits in Dart's managed heap after
-    // the FFI has consumed it. Mirrors `key_package_provider.dart`.
-    Uint8List? secretBuffer;
     try {
       final circle = await _circleManagerFactory();
-      secretBuffer = Uint8List.fromList(await _identitySecretBytes());
-      return await op(circle, secretBuffer);
+      // Disposed as soon as the FFI returns so the Rust-side key copy is
+      // zeroized now rather than at garbage collection (Rule 9).
+      return await withFreshSecret(
+        _identitySecret,
+        (secret) => op(circle, secret),
+      );
     } on Object catch (e) {
       debugPrint('[Maintenance] $label orchestration failed: ${e.runtimeType}');
       return onFailure;
-    } finally {
-      secretBuffer?.fillRange(0, secretBuffer.length, 0);
     }
   }
 }
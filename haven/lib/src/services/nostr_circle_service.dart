@@ -65,13 +65,13 @@ class NostrCircleService implements CircleService {
     DataDirectoryProvider? dataDirectoryProvider,
     KeyringInitializer? keyringInitializer,
     bool enableLeaverBackstop = false,
-    Future<List<int>> Function()? identitySecretBytesProvider,
+    Future<SecretHandle> Function()? identitySecretProvider,
   }) : _relayService = relayService,
        _dataDirectoryProvider =
            dataDirectoryProvider ?? const PathProviderDataDirectory(),
        _keyringInitializer = keyringInitializer ?? initKeyringStore,
        _enableLeaverBackstop = enableLeaverBackstop,
-       _identitySecretBytesProvider = identitySecretBytesProvider;
+       _identitySecretProvider = identitySecretProvider;
 
   /// Creates a [NostrCircleService] backed by a pre-built [CircleManagerFfi].
   ///
@@ -93,7 +93,7 @@ class NostrCircleService implements CircleService {
        _dataDirectoryProvider = const PathProviderDataDirectory(),
        _keyringInitializer = initKeyringStore,
        _enableLeaverBackstop = false,
-       _identitySecretBytesProvider = null,
+       _identitySecretProvider = null,
        _manager = injectedManager,
        _initialized = true;
 
@@ -101,7 +101,7 @@ class NostrCircleService implements CircleService {
   final DataDirectoryProvider _dataDirectoryProvider;
   final KeyringInitializer _keyringInitializer;
 
-  /// Supplies the device identity's 32-byte Nostr secret for
+  /// Supplies a handle to the device identity's Nostr secret for
   /// [CircleManagerFfi.newInstance], which (Dark Matter) hard-requires the
   /// identity at construction time (it binds the account identity, the NIP-59
   /// welcome signer, AND the account-identity-proof signer). Re-fetched on
@@ -109,7 +109,7 @@ class NostrCircleService implements CircleService {
   ///
   /// `null` for the background-isolate / injected-manager constructor, which
   /// never calls [initialize] (the manager is already open).
-  final Future<List<int>> Function()? _identitySecretBytesProvider;
+  final Future<SecretHandle> Function()? _identitySecretProvider;
 
   /// Whether this instance runs the REV-1 leaver backstop (driver 2) after a
   /// `propose_leave`.
@@ -193,18 +193,18 @@ class NostrCircleService implements CircleService {
           'Failed to initialize secure storage',
         );
       }
-      final secretBytesProvider = _identitySecretBytesProvider;
-      if (secretBytesProvider == null) {
+      final secretProvider = _identitySecretProvider;
+      if (secretProvider == null) {
         throw const CircleServiceException(
           'No identity available to open the circle manager',
         );
       }
       final dataDir = await _dataDirectoryProvider.getDataDirectory();
       // Re-fetched fresh (never held) — Security Rule 9.
-      final identitySecretBytes = await secretBytesProvider();
-      final manager = await CircleManagerFfi.newInstance(
-        dataDir: dataDir,
-        identitySecretBytes: identitySecretBytes,
+      final manager = await withFreshSecret(
+        secretProvider,
+        (secret) =>
+            CircleManagerFfi.newInstance(dataDir: dataDir, secret: secret),
       );
       // M10 (H1 in-flight race): the service may have been wiped/latched while
       // this init was suspended at the awaited open above. If so, do NOT adopt
@@ -326,7 +326,7 @@ class NostrCircleService implements CircleService {
 
   @override
   Future<CircleCreationResult> createCircle({
-    required List<int> identitySecretBytes,
+    required SecretHandle secret,
     required List<KeyPackageData> memberKeyPackages,
     required String name,
     required CircleType circleType,
@@ -337,14 +337,6 @@ class NostrCircleService implements CircleService {
     final manager = await _ensureInitialized();
 
     try {
-      // Validate identity secret bytes length
-      if (identitySecretBytes.length != 32) {
-        throw CircleServiceException(
-          'Invalid identity secret bytes length: '
-          'expected 32, got ${identitySecretBytes.length}',
-        );
-      }
-
       // Convert KeyPackageData to MemberKeyPackageFfi
       final members = memberKeyPackages
           .map(
@@ -376,7 +368,7 @@ class NostrCircleService implements CircleService {
           : memberRelays; // empty → Rust substitutes user Inbox → defaults
 
       final result = await manager.createCircle(
-        identitySecretBytes: Uint8List.fromList(identitySecretBytes),
+        secret: secret,
         members: members,
         name: name,
         description: description,
@@ -546,14 +538,14 @@ class NostrCircleService implements CircleService {
 
   @override
   Future<Invitation?> processGiftWrappedInvitation({
-    required List<int> identitySecretBytes,
+    required SecretHandle secret,
     required String giftWrapEventJson,
   }) async {
     final manager = await _ensureInitialized();
 
     try {
       final ffiInvitation = await manager.processGiftWrappedInvitation(
-        identitySecretBytes: Uint8List.fromList(identitySecretBytes),
+        secret: secret,
         giftWrapEventJson: giftWrapEventJson,
       );
       // `null` signals the Rust side detected a duplicate gift wrap (the
@@ -912,7 +904,7 @@ class NostrCircleService implements CircleService {
 
   @override
   Future<AddMemberResult> addMember({
-    required Future<List<int>> Function() secretProvider,
+    required Future<SecretHandle> Function() secretProvider,
     required List<int> mlsGroupId,
     required List<KeyPackageData> memberKeyPackages,
     List<String> creatorFallbackRelays = const [],
@@ -954,7 +946,7 @@ class NostrCircleService implements CircleService {
             throw const CircleServiceException('Failed to add member');
           }
           return manager.addMembersToCircle(
-            identitySecretBytes: secret,
+            secret: secret,
             mlsGroupId: groupId,
             members: ffiMembers,
             creatorFallbackRelays: creatorFallbackRelays,
@@ -1220,6 +1212,12 @@ class NostrCircleService implements CircleService {
         latitude: latitude,
         longitude: longitude,
         updateIntervalSecs: BigInt.from(updateIntervalSecs),
+        // Members keep the location for the 1-day default.
+        shareExpirationSecs: null,
+        // Battery and motion status are opt-in and not collected here.
+        deviceStatus: null,
+        // Altitude and floor level are opt-in and not collected here.
+        verticalPosition: null,
       );
 
       return EncryptedLocation(
@@ -1403,14 +1401,14 @@ class NostrCircleService implements CircleService {
 
   @override
   Future<String> signDeletionEvent({
-    required List<int> identitySecretBytes,
+    required SecretHandle secret,
     required List<String> eventIds,
   }) async {
     final manager = await _ensureInitialized();
 
     try {
       return manager.signDeletionEvent(
-        identitySecretBytes: Uint8List.fromList(identitySecretBytes),
+        secret: secret,
         eventIds: eventIds,
       );
     } on Object catch (e) {
@@ -229,14 +229,14 @@ class NostrIdentityService implements IdentityService {
   }
 
   @override
-  Future<List<int>> getSecretBytes() async {
+  Future<SecretHandle> getSecretHandle() async {
     final manager = await _ensureInitialized();
 
     try {
-      return manager.getSecretBytes();
-    } on Exception catch (_) {
-      debugPrint('[Identity] Secret bytes retrieval failed');
-      throw const IdentityServiceException('Failed to get secret bytes');
+      return await manager.secretHandle();
+    } on Object catch (_) {
+      debugPrint('[Identity] Secret handle retrieval failed');
+      throw const IdentityServiceException('Failed to get secret handle');
     }
   }
 
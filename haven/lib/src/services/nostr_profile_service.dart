@@ -6,8 +6,8 @@
 ///   [`CatchupService`]/[`MaintenanceService`], which take the same
 ///   `Future<CircleManagerFfi> Function()` factory shape for the same reason.
 /// - Resolves the caller's own pubkey/secret via the injected
-///   [IdentityService], re-fetching secret bytes per call and scrubbing
-///   them in a `finally` (Security Rule 9 — mirrors
+///   [IdentityService], re-fetching a secret handle per call and disposing
+///   it in a `finally` (Security Rule 9 — mirrors
 ///   `MaintenanceService._withSecret`).
 /// - Publishes unconditionally: `updateOwnProfile`/`setOwnAvatar` carry no
 ///   consent gate (public-by-default, owner-directed 2026-07-16, matching the
@@ -87,14 +87,12 @@ class NostrProfileService implements ProfileService {
     required String displayName,
     String? about,
   }) async {
-    Uint8List? secretBuffer;
+    SecretHandle? secret;
     try {
       final manager = await _circleManagerFactory();
-      secretBuffer = Uint8List.fromList(
-        await _identityService.getSecretBytes(),
-      );
+      secret = await _identityService.getSecretHandle();
       final ffi = await manager.publishMyProfile(
-        identitySecretBytes: secretBuffer,
+        secret: secret,
         displayName: displayName,
         about: about,
       );
@@ -103,20 +101,18 @@ class NostrProfileService implements ProfileService {
       debugPrint('[Profile] updateOwnProfile: ${e.runtimeType}');
       throw const ProfileServiceException('Failed to update profile');
     } finally {
-      secretBuffer?.fillRange(0, secretBuffer.length, 0);
+      secret?.dispose();
     }
   }
 
   @override
   Future<Profile> setOwnAvatar(Uint8List raw) async {
-    Uint8List? secretBuffer;
+    SecretHandle? secret;
     try {
       final manager = await _circleManagerFactory();
-      secretBuffer = Uint8List.fromList(
-        await _identityService.getSecretBytes(),
-      );
+      secret = await _identityService.getSecretHandle();
       final ref = await manager.uploadMyProfilePicture(
-        identitySecretBytes: secretBuffer,
+        secret: secret,
         raw: raw,
       );
       // `uploadMyProfilePicture` upserts both the picture bytes and the
@@ -148,28 +144,26 @@ class NostrProfileService implements ProfileService {
       debugPrint('[Profile] setOwnAvatar: ${e.runtimeType}');
       throw const ProfileServiceException('Failed to set profile picture');
     } finally {
-      secretBuffer?.fillRange(0, secretBuffer.length, 0);
+      secret?.dispose();
     }
   }
 
   @override
   Future<Profile> removeOwnAvatar() async {
     // Retraction is always allowed — see class doc.
-    Uint8List? secretBuffer;
+    SecretHandle? secret;
     try {
       final manager = await _circleManagerFactory();
-      secretBuffer = Uint8List.fromList(
-        await _identityService.getSecretBytes(),
-      );
+      secret = await _identityService.getSecretHandle();
       final ffi = await manager.removeMyProfilePicture(
-        identitySecretBytes: secretBuffer,
+        secret: secret,
       );
       return await _toProfile(manager, ffi, fullResolution: true);
     } on Object catch (e) {
       debugPrint('[Profile] removeOwnAvatar: ${e.runtimeType}');
       throw const ProfileServiceException('Failed to remove profile picture');
     } finally {
-      secretBuffer?.fillRange(0, secretBuffer.length, 0);
+      secret?.dispose();
     }
   }
 
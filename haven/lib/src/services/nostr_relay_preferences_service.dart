@@ -9,6 +9,7 @@ library;
 import 'package:flutter/foundation.dart';
 import 'package:haven/src/rust/api.dart';
 import 'package:haven/src/services/relay_preferences_service.dart';
+import 'package:haven/src/utils/ffi_error.dart';
 
 /// Production implementation backed by `CircleManagerFfi`.
 class NostrRelayPreferencesService implements RelayPreferencesService {
@@ -126,12 +127,12 @@ class NostrRelayPreferencesService implements RelayPreferencesService {
 
   @override
   Future<BuiltRelayListPublish> buildRelayListPublish({
-    required Uint8List identitySecretBytes,
+    required SecretHandle secret,
     required RelayCategory category,
   }) async {
     try {
       final ffi = await _manager.buildRelayListPublish(
-        identitySecretBytes: identitySecretBytes,
+        secret: secret,
         relayType: _toFfi(category),
       );
       return BuiltRelayListPublish(
@@ -172,12 +173,12 @@ class NostrRelayPreferencesService implements RelayPreferencesService {
 
   @override
   Future<BuiltUnpublish> buildUnpublishRelayList({
-    required Uint8List identitySecretBytes,
+    required SecretHandle secret,
     required RelayCategory category,
   }) async {
     try {
       final ffi = await _manager.buildUnpublishRelayList(
-        identitySecretBytes: identitySecretBytes,
+        secret: secret,
         relayType: _toFfi(category),
       );
       return BuiltUnpublish(
@@ -196,13 +197,13 @@ class NostrRelayPreferencesService implements RelayPreferencesService {
 
   @override
   Future<BuiltUnpublish> buildRelayRemovalScrub({
-    required Uint8List identitySecretBytes,
+    required SecretHandle secret,
     required RelayCategory category,
     required List<String> droppedRelays,
   }) async {
     try {
       final ffi = await _manager.buildRelayRemovalScrub(
-        identitySecretBytes: identitySecretBytes,
+        secret: secret,
         relayType: _toFfi(category),
         droppedRelays: droppedRelays,
       );
@@ -224,12 +225,17 @@ class NostrRelayPreferencesService implements RelayPreferencesService {
 
   /// Maps an FFI error into the appropriate Dart exception type.
   ///
-  /// FFI errors arrive as `String`; we inspect a small set of known
-  /// substrings so the caller can distinguish "user typed a bad URL"
-  /// from "the database lock failed". Unknown errors fall through to
-  /// the generic [`RelayPreferencesException`].
+  /// FFI errors arrive as [HavenErrorFfi]. Every URL check the Rust side
+  /// raises shares the `invalid_input` code, so within that code we inspect
+  /// a small set of known substrings of the English `detail` to tell "user
+  /// typed a bad URL" apart from "that was the last relay". Any other code
+  /// (e.g. `storage` when the database lock failed) falls through to the
+  /// generic [`RelayPreferencesException`].
   Exception _mapStorageError(Object e) {
-    final raw = e.toString().toLowerCase();
+    if (e is! HavenErrorFfi || ffiErrorCode(e) != 'invalid_input') {
+      return const RelayPreferencesException('Relay update failed.');
+    }
+    final raw = e.detail.toLowerCase();
     // Validation messages we explicitly raise from the Rust side. Keep
     // the matched substrings short and language-agnostic.
     if (raw.contains('use wss://')) {
@@ -272,15 +272,15 @@ class NostrRelayService implements RelayService {
   @override
   Future<KeyPackageMaintenanceResult> maintainKeyPackage({
     required CircleManagerFfi circle,
-    required List<int> identitySecretBytes,
+    required SecretHandle secret,
   }) async {
     // Best-effort: a scheduled maintenance tick must never throw into its
-    // caller. The secret bytes are consumed + zeroized Rust-side.
+    // caller.
     try {
       final manager = await _ensureInitialized();
       final r = await manager.maintainKeyPackage(
         circle: circle,
-        identitySecretBytes: identitySecretBytes,
+        secret: secret,
       );
       return KeyPackageMaintenanceResult(
         action: _mapKpAction(r.action),
@@ -296,14 +296,14 @@ class NostrRelayService implements RelayService {
   @override
   Future<RelayListMaintenanceResult> maintainRelayList({
     required CircleManagerFfi circle,
-    required List<int> identitySecretBytes,
+    required SecretHandle secret,
   }) async {
-    // Best-effort: never throws. Secret bytes consumed + zeroized Rust-side.
+    // Best-effort: never throws.
     try {
       final manager = await _ensureInitialized();
       final r = await manager.maintainRelayList(
         circle: circle,
-        identitySecretBytes: identitySecretBytes,
+        secret: secret,
       );
       return RelayListMaintenanceResult(
         inbox: _mapRelayListCategory(r.inbox),
@@ -318,15 +318,15 @@ class NostrRelayService implements RelayService {
   @override
   Future<LegacyRetractionResult> retractLegacyKeyMaterial({
     required CircleManagerFfi circle,
-    required List<int> identitySecretBytes,
+    required SecretHandle secret,
   }) async {
     // Best-effort: a scheduled/cutover tick must never throw into its
-    // caller. The secret bytes are consumed + zeroized Rust-side.
+    // caller.
     try {
       final manager = await _ensureInitialized();
       final r = await manager.retractLegacyKeyMaterial(
         circle: circle,
-        identitySecretBytes: identitySecretBytes,
+        secret: secret,
       );
       return LegacyRetractionResult(
         alreadyDone: r.alreadyDone,
@@ -4,6 +4,7 @@ import 'package:flutter/foundation.dart';
 
 import 'package:haven/src/rust/api.dart';
 import 'package:haven/src/services/subscription_service.dart';
+import 'package:haven/src/utils/ffi_error.dart';
 
 /// The Rust-backed [SubscriptionService]: builds a [LiveSyncFfi] engine, starts
 /// the session, and feeds `liveEvents()` to a [LiveEventRouter] for the session
@@ -64,12 +65,12 @@ class NostrSubscriptionService implements SubscriptionService {
       );
     } on Object catch (e) {
       debugPrint('[Subscription] start failed: ${e.runtimeType}');
-      // The underlying FFI error is a Rust `Result` string already sanitized by
-      // `redact_hex_sequences`; surface its (redacted) detail in debug/e2e builds
-      // so an engine-start failure is diagnosable, not an opaque "String" type
-      // (the wrapper thrown below otherwise hides it from MapShell).
+      // The underlying FFI error is a HavenErrorFfi whose detail is already
+      // sanitized by `redact_hex_sequences`; surface it in debug/e2e builds so
+      // an engine-start failure is diagnosable, not an opaque type name (the
+      // wrapper thrown below otherwise hides it from MapShell).
       if (kDebugMode) {
-        debugPrint('[Subscription] start error detail: $e');
+        debugPrint('[Subscription] start error detail: ${ffiErrorDetail(e)}');
       }
       await stop();
       throw const SubscriptionServiceException('failed to start live session');
@@ -13,6 +13,7 @@
 library;
 
 import 'package:flutter/foundation.dart';
+import 'package:haven/src/rust/api.dart' show SecretHandle;
 
 /// Category of relay preference managed per user.
 ///
@@ -192,7 +193,7 @@ abstract class RelayPreferencesService {
   /// event, and resolves the publish targets. The only path through
   /// which the UI may publish a relay list event.
   Future<BuiltRelayListPublish> buildRelayListPublish({
-    required Uint8List identitySecretBytes,
+    required SecretHandle secret,
     required RelayCategory category,
   });
 
@@ -212,7 +213,7 @@ abstract class RelayPreferencesService {
 
   /// Builds the events needed to unpublish a relay list category.
   Future<BuiltUnpublish> buildUnpublishRelayList({
-    required Uint8List identitySecretBytes,
+    required SecretHandle secret,
     required RelayCategory category,
   });
 
@@ -230,7 +231,7 @@ abstract class RelayPreferencesService {
   /// result with empty targets when nothing was ever published for this
   /// category (the dropped relays never received the list).
   Future<BuiltUnpublish> buildRelayRemovalScrub({
-    required Uint8List identitySecretBytes,
+    required SecretHandle secret,
     required RelayCategory category,
     required List<String> droppedRelays,
   });
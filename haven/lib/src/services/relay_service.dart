@@ -446,14 +446,14 @@ abstract class RelayService {
   /// and republishes into a stable NIP-33 `d` slot only when none is
   /// reachable — the Rust core owns the whole decision (live-material gate +
   /// stable-`d` seeding). `circle` is the circle-manager FFI handle (from
-  /// [CircleService.getCircleManagerFfi]); the secret bytes are consumed by
-  /// the FFI and zeroized Rust-side.
+  /// [CircleService.getCircleManagerFfi]); `secret` is the identity's
+  /// [SecretHandle], so the key never reaches the Dart heap.
   ///
   /// Best-effort — returns a [KeyPackageMaintenanceResult.empty] on failure
   /// rather than throwing (a background/timer tick must never throw).
   Future<KeyPackageMaintenanceResult> maintainKeyPackage({
     required CircleManagerFfi circle,
-    required List<int> identitySecretBytes,
+    required SecretHandle secret,
   });
 
   /// Runs an M8 relay-list maintenance tick (kind 10050 inbox + 10051
@@ -462,13 +462,13 @@ abstract class RelayService {
   /// Network-probes the user's OWN relays for each list and republishes to
   /// own relays only when missing/drifted, honoring the per-category privacy
   /// toggle. Never NIP-65/kind-10002. `circle` is the circle-manager FFI
-  /// handle; the secret bytes are consumed by the FFI and zeroized Rust-side.
+  /// handle; `secret` is the identity's [SecretHandle].
   ///
   /// Best-effort — returns a [RelayListMaintenanceResult.empty] on failure
   /// rather than throwing.
   Future<RelayListMaintenanceResult> maintainRelayList({
     required CircleManagerFfi circle,
-    required List<int> identitySecretBytes,
+    required SecretHandle secret,
   });
 
   /// Once-only Dark Matter cutover cleanup (plan §6 step 5 / security F10a):
@@ -479,14 +479,14 @@ abstract class RelayService {
   /// Self-gates on a persisted sentinel (`legacy_kp_retraction_done`) so it
   /// fires at most once; a repeat call after the sentinel is set returns
   /// [LegacyRetractionResult.alreadyDone] `== true` with no relay traffic.
-  /// `circle` is the circle-manager FFI handle; the secret bytes are
-  /// consumed by the FFI and zeroized Rust-side.
+  /// `circle` is the circle-manager FFI handle; `secret` is the identity's
+  /// [SecretHandle].
   ///
   /// Best-effort — returns a [LegacyRetractionResult.empty] on failure rather
   /// than throwing.
   Future<LegacyRetractionResult> retractLegacyKeyMaterial({
     required CircleManagerFfi circle,
-    required List<int> identitySecretBytes,
+    required SecretHandle secret,
   });
 
   /// Runs an M8 subscription-health maintenance tick (engine-coupled).
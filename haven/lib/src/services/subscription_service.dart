@@ -2,6 +2,7 @@ import 'package:flutter/foundation.dart';
 
 import 'package:haven/src/rust/api.dart';
 import 'package:haven/src/services/circle_service.dart';
+import 'package:haven/src/services/fresh_secret.dart';
 
 /// Thrown by the live-sync subscription service for setup/teardown failures.
 ///
@@ -67,7 +68,7 @@ class LiveEventRouter {
   LiveEventRouter({
     required this.circleService,
     required this.circlesSnapshot,
-    required this.secretBytes,
+    required this.secretProvider,
     required this.parseLocation,
     required this.ingestLocation,
     required this.reconcileRoster,
@@ -83,9 +84,10 @@ class LiveEventRouter {
   /// Snapshot of the user's joined circles (to resolve a `nostr_group_id`).
   final Future<List<Circle>> Function() circlesSnapshot;
 
-  /// Provides the identity secret bytes for invitation unwrapping (copied into
-  /// a `Uint8List` and zeroized after use by this router — Security Rule 9).
-  final Future<List<int>> Function() secretBytes;
+  /// Provides a handle to the identity secret for invitation unwrapping
+  /// (fetched per event and disposed after use by this router — Security
+  /// Rule 9).
+  final Future<SecretHandle> Function() secretProvider;
 
   /// Parses an engine Location `content` + sender into a [DecryptedLocation],
   /// or returns `null` if the content is not a parseable `LocationMessage`
@@ -234,12 +236,13 @@ class LiveEventRouter {
     if (giftWrapJson == null) return;
     final wrapSecs = event.wrapCreatedAtSecs;
 
-    Uint8List? secret;
     try {
-      secret = Uint8List.fromList(await secretBytes());
-      final invitation = await circleService.processGiftWrappedInvitation(
-        identitySecretBytes: secret,
-        giftWrapEventJson: giftWrapJson,
+      final invitation = await withFreshSecret(
+        secretProvider,
+        (secret) => circleService.processGiftWrappedInvitation(
+          secret: secret,
+          giftWrapEventJson: giftWrapJson,
+        ),
       );
       if (wrapSecs != null) {
         await circleService.advanceInboxCursorToWrapSecs(wrapSecs);
@@ -250,11 +253,6 @@ class LiveEventRouter {
       }
     } on Object catch (e) {
       debugPrint('[Subscription] welcome processing failed: ${e.runtimeType}');
-    } finally {
-      // Rule 9: minimize secret lifetime — zeroize the bytes after use.
-      if (secret != null) {
-        secret.fillRange(0, secret.length, 0);
-      }
     }
   }
 
@@ -0,0 +1,22 @@
+/// Utilities for reading errors thrown across the Rust FFI boundary.
+///
+/// Every fallible FFI call throws a [HavenErrorFfi]: a localizable
+/// [UserMessageFfi] keyed by a stable `code`, plus an English `detail` for
+/// logs. The generated class has no `toString`, so interpolating the error
+/// itself prints only its type.
+library;
+
+import 'package:haven/src/rust/api.dart';
+
+/// The stable message code of an FFI error (e.g. `relay.invalid_url`), or
+/// `null` when [error] did not come from the FFI.
+String? ffiErrorCode(Object error) =>
+    error is HavenErrorFfi ? error.message.code : null;
+
+/// The English description of [error], for debug logs only.
+///
+/// The Rust side redacts hex sequences from it, but it is still detail a
+/// release log must not carry — keep callers behind `kDebugMode` or an
+/// `assert`.
+String ffiErrorDetail(Object error) =>
+    error is HavenErrorFfi ? error.detail : error.toString();
@@ -3,7 +3,7 @@
 use std::collections::HashMap;
 use std::sync::{Arc, RwLock};
 
-use flutter_rust_bridge::frb;
+use flutter_rust_bridge::{frb, DartFnFuture};
 
 /// Initializes the Rust runtime (logging, panic hooks).
 ///
@@ -68,8 +68,9 @@ pub fn init_app() {
 }
 use haven_core::nostr::identity::{
     IdentityError, IdentityManager, PublicIdentity as CorePublicIdentity,
-    SecureKeyStorage as CoreSecureKeyStorage,
+    SecureKeyStorage as CoreSecureKeyStorage, Signer,
 };
+use haven_core::nostr::nip46::{Nip46Signer, RemoteSignerConfig};
 
 /// Core interface for Haven functionality (wrapper around haven-core).
 #[derive(Debug, Default)]
@@ -95,12 +96,37 @@ impl HavenCore {
     }
 
     /// Initializes the core.
-    pub fn initialize(&mut self) -> Result<(), String> {
-        self.inner.initialize()
+    pub fn initialize(&mut self) -> Result<(), HavenErrorFfi> {
+        self.inner.initialize().map_err(HavenErrorFfi::internal)
+    }
+
+    /// Initializes the core for a deployment environment.
+    ///
+    /// `relays` overrides the default relays for dev and staging (empty keeps
+    /// the profile's own: the local mock relay for dev); production ignores
+    /// it. Installs the profile's log level, relay timing, and mock-relay
+    /// opt-in process-wide. Fails for a dev profile in a release build.
+    pub fn initialize_with_environment(
+        &mut self,
+        environment: EnvironmentFfi,
+        relays: Vec<String>,
+    ) -> Result<(), HavenErrorFfi> {
+        let profile = haven_core::EnvironmentProfile::for_environment(environment.into(), relays);
+        self.inner
+            .initialize_with_environment(profile)
+            .map_err(HavenErrorFfi::internal)
+    }
+
+    /// Returns the active deployment environment.
+    #[must_use]
+    #[frb(sync)]
+    pub fn environment(&self) -> EnvironmentFfi {
+        haven_core::HavenCore::environment().into()
     }
 
     /// Processes raw location data and returns a `LocationMessage` with
-    /// exact GPS coordinates.
+    /// exact GPS coordinates, rounded down while the device stays in one spot
+    /// if adaptive precision is on.
     #[frb(sync)]
     pub fn update_location(&self, latitude: f64, longitude: f64) -> LocationMessage {
         let msg = self.inner.update_location(latitude, longitude);
@@ -119,6 +145,322 @@ impl HavenCore {
     pub fn set_location_settings(&mut self, settings: LocationSettings) {
         self.inner.set_location_settings(settings.inner);
     }
+
+    /// Runs the crypto and storage self-test, for the "nothing works"
+    /// support screen. `scratch_dir` is a writable directory (the app's
+    /// cache dir); nothing is left behind in it.
+    pub async fn run_self_test(&self, scratch_dir: String) -> SelfTestReportFfi {
+        haven_core::HavenCore::run_self_test(std::path::Path::new(&scratch_dir))
+            .await
+            .into()
+    }
+}
+
+/// One part of the self-test (FFI mirror of
+/// [`haven_core::self_test::SelfTestCheck`]).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SelfTestCheckFfi {
+    /// NIP-44 encrypt/decrypt.
+    Nip44,
+    /// MLS group creation, join and a location round trip.
+    Mls,
+    /// Encrypted database write and read-back.
+    Database,
+    /// Platform keyring write, read and delete.
+    SecureStorage,
+}
+
+impl From<haven_core::self_test::SelfTestCheck> for SelfTestCheckFfi {
+    fn from(c: haven_core::self_test::SelfTestCheck) -> Self {
+        match c {
+            haven_core::self_test::SelfTestCheck::Nip44 => Self::Nip44,
+            haven_core::self_test::SelfTestCheck::Mls => Self::Mls,
+            haven_core::self_test::SelfTestCheck::Database => Self::Database,
+            haven_core::self_test::SelfTestCheck::SecureStorage => Self::SecureStorage,
+        }
+    }
+}
+
+/// The outcome of one self-test check (FFI).
+#[derive(Debug, Clone)]
+pub struct SelfTestResultFfi {
+    /// Which check ran.
+    pub check: SelfTestCheckFfi,
+    /// `true` if the check passed.
+    pub passed: bool,
+    /// The step that failed (a short slug such as `decrypt`), if any.
+    pub failure: Option<String>,
+    /// How long the check took, in milliseconds.
+    pub duration_ms: u64,
+}
+
+/// The outcome of [`HavenCore::run_self_test`] (FFI).
+#[derive(Debug, Clone)]
+pub struct SelfTestReportFfi {
+    /// `true` if every check passed.
+    pub passed: bool,
+    /// One result per check, in the order they ran.
+    pub results: Vec<SelfTestResultFfi>,
+    /// The report as JSON, for attaching to a support request.
+    pub json: String,
+}
+
+impl From<haven_core::self_test::SelfTestReport> for SelfTestReportFfi {
+    fn from(report: haven_core::self_test::SelfTestReport) -> Self {
+        Self {
+            passed: report.passed(),
+            json: report.to_json().unwrap_or_default(),
+            results: report
+                .results
+                .into_iter()
+                .map(|r| SelfTestResultFfi {
+                    check: r.check.into(),
+                    passed: r.passed,
+                    failure: r.failure.map(str::to_string),
+                    duration_ms: r.duration_ms,
+                })
+                .collect(),
+        }
+    }
+}
+
+/// Deployment environment (FFI mirror of [`haven_core::Environment`]).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EnvironmentFfi {
+    /// Developer build against a local mock relay.
+    Dev,
+    /// QA build against staging relays.
+    Staging,
+    /// The shipped app.
+    Production,
+}
+
+impl From<haven_core::Environment> for EnvironmentFfi {
+    fn from(e: haven_core::Environment) -> Self {
+        match e {
+            haven_core::Environment::Dev => Self::Dev,
+            haven_core::Environment::Staging => Self::Staging,
+            haven_core::Environment::Production => Self::Production,
+        }
+    }
+}
+
+impl From<EnvironmentFfi> for haven_core::Environment {
+    fn from(e: EnvironmentFfi) -> Self {
+        match e {
+            EnvironmentFfi::Dev => Self::Dev,
+            EnvironmentFfi::Staging => Self::Staging,
+            EnvironmentFfi::Production => Self::Production,
+        }
+    }
+}
+
+/// Location sharing precision (FFI mirror of
+/// [`haven_core::location::LocationPrecision`]).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LocationPrecisionFfi {
+    /// Rounded to ~1 km.
+    Coarse,
+    /// Rounded to ~10 m.
+    Approximate,
+    /// The raw GPS fix.
+    Exact,
+}
+
+impl From<haven_core::location::LocationPrecision> for LocationPrecisionFfi {
+    fn from(p: haven_core::location::LocationPrecision) -> Self {
+        match p {
+            haven_core::location::LocationPrecision::Coarse => Self::Coarse,
+            haven_core::location::LocationPrecision::Approximate => Self::Approximate,
+            haven_core::location::LocationPrecision::Exact => Self::Exact,
+        }
+    }
+}
+
+impl From<LocationPrecisionFfi> for haven_core::location::LocationPrecision {
+    fn from(p: LocationPrecisionFfi) -> Self {
+        match p {
+            LocationPrecisionFfi::Coarse => Self::Coarse,
+            LocationPrecisionFfi::Approximate => Self::Approximate,
+            LocationPrecisionFfi::Exact => Self::Exact,
+        }
+    }
+}
+
+/// The ground size of a shared location cell (FFI).
+#[derive(Debug, Clone)]
+pub struct CellSizeFfi {
+    /// East-west extent in meters, at the latitude asked about.
+    pub width_m: f64,
+    /// North-south extent in meters.
+    pub height_m: f64,
+    /// A `location.cell_area` message whose `width` and `height` are plain
+    /// meter counts ("2400"), for the app to format with the user's locale
+    /// and units.
+    pub description: UserMessageFfi,
+}
+
+impl From<haven_core::location::CellSize> for CellSizeFfi {
+    fn from(size: haven_core::location::CellSize) -> Self {
+        Self {
+            width_m: size.width_m,
+            height_m: size.height_m,
+            description: size.user_message().into(),
+        }
+    }
+}
+
+/// The ground size of a geohash cell of `geohash_len` characters at
+/// `latitude` (length clamped to 1..=12).
+#[frb(sync)]
+#[must_use]
+pub fn geohash_cell_size(geohash_len: u8, latitude: f64) -> CellSizeFfi {
+    haven_core::location::geohash_cell_size(geohash_len, latitude).into()
+}
+
+/// The ground size of the cell a circle sees at `precision`, computed at
+/// `latitude` so settings screens can explain it accurately.
+#[frb(sync)]
+#[must_use]
+pub fn precision_cell_size(precision: LocationPrecisionFfi, latitude: f64) -> CellSizeFfi {
+    haven_core::location::LocationPrecision::from(precision)
+        .cell_size_at(latitude)
+        .into()
+}
+
+/// A circle's location sharing overrides (FFI).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CircleLocationSettingsFfi {
+    /// Precision shared with the circle.
+    pub precision: LocationPrecisionFfi,
+    /// How long the circle's members keep each location, in seconds.
+    pub share_expiration_secs: u64,
+    /// How old a member's location may be and still be shown, in seconds;
+    /// `None` for the default (1 hour).
+    pub display_max_age_secs: Option<u64>,
+}
+
+impl From<haven_core::circle::CircleLocationSettings> for CircleLocationSettingsFfi {
+    fn from(s: haven_core::circle::CircleLocationSettings) -> Self {
+        Self {
+            precision: s.precision.into(),
+            share_expiration_secs: s.share_expiration.as_secs(),
+            display_max_age_secs: s.display_max_age_secs,
+        }
+    }
+}
+
+impl From<CircleLocationSettingsFfi> for haven_core::circle::CircleLocationSettings {
+    fn from(s: CircleLocationSettingsFfi) -> Self {
+        Self {
+            precision: s.precision.into(),
+            share_expiration: haven_core::location::ShareExpiration::from_secs(
+                s.share_expiration_secs,
+            ),
+            display_max_age_secs: s.display_max_age_secs,
+        }
+    }
+}
+
+/// Exactly what a circle would receive for a location (FFI mirror of
+/// [`haven_core::circle::SharePreview`]). The cell is `None` only for an
+/// invalid position.
+#[derive(Clone, PartialEq)]
+pub struct SharePreviewFfi {
+    /// Latitude as published.
+    pub latitude: f64,
+    /// Longitude as published.
+    pub longitude: f64,
+    /// Geohash as published.
+    pub geohash: String,
+    /// Southern edge of the geohash cell.
+    pub cell_min_lat: Option<f64>,
+    /// Western edge of the geohash cell.
+    pub cell_min_lon: Option<f64>,
+    /// Northern edge of the geohash cell.
+    pub cell_max_lat: Option<f64>,
+    /// Eastern edge of the geohash cell.
+    pub cell_max_lon: Option<f64>,
+    /// Precision applied for this circle.
+    pub precision: LocationPrecisionFfi,
+    /// When members show the location as stale (Unix seconds).
+    pub expires_at: i64,
+    /// When members drop the location (Unix seconds), if sooner than 1 day.
+    pub share_expires_at: Option<i64>,
+}
+
+impl std::fmt::Debug for SharePreviewFfi {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SharePreviewFfi")
+            .field("precision", &self.precision)
+            .field("expires_at", &self.expires_at)
+            .field("share_expires_at", &self.share_expires_at)
+            .finish_non_exhaustive()
+    }
+}
+
+impl From<haven_core::circle::SharePreview> for SharePreviewFfi {
+    fn from(p: haven_core::circle::SharePreview) -> Self {
+        Self {
+            latitude: p.latitude,
+            longitude: p.longitude,
+            geohash: p.geohash,
+            cell_min_lat: p.cell.map(|c| c.min_lat),
+            cell_min_lon: p.cell.map(|c| c.min_lon),
+            cell_max_lat: p.cell.map(|c| c.max_lat),
+            cell_max_lon: p.cell.map(|c| c.max_lon),
+            precision: p.precision.into(),
+            expires_at: p.expires_at,
+            share_expires_at: p.share_expires_at,
+        }
+    }
+}
+
+/// A circle's running trip mode (FFI mirror of
+/// [`haven_core::circle::TripMode`]).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TripModeFfi {
+    /// Nominal publish interval during the trip, in seconds.
+    pub update_interval_secs: u64,
+    /// How long members keep each location shared during the trip, in seconds.
+    pub share_expiration_secs: u64,
+    /// Unix timestamp at which the trip mode ends by itself.
+    pub ends_at: i64,
+}
+
+impl From<haven_core::circle::TripMode> for TripModeFfi {
+    fn from(t: haven_core::circle::TripMode) -> Self {
+        Self {
+            update_interval_secs: t.update_interval_secs,
+            share_expiration_secs: t.share_expiration.as_secs(),
+            ends_at: t.ends_at,
+        }
+    }
+}
+
+/// A running precise-sharing session (FFI mirror of
+/// [`haven_core::circle::SharingSession`]).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SharingSessionFfi {
+    /// MLS group ID of the circle.
+    pub mls_group_id: Vec<u8>,
+    /// Unix timestamp the session started.
+    pub started_at: i64,
+    /// Unix timestamp the session ends by itself.
+    pub ends_at: i64,
+    /// Seconds left when this was read, for a countdown.
+    pub remaining_secs: i64,
+}
+
+impl SharingSessionFfi {
+    fn from_core(s: &haven_core::circle::SharingSession, now: i64) -> Self {
+        Self {
+            mls_group_id: s.mls_group_id.as_slice().to_vec(),
+            started_at: s.started_at,
+            ends_at: s.ends_at,
+            remaining_secs: s.remaining_secs(now),
+        }
+    }
 }
 
 /// Location message with exact GPS coordinates (FFI wrapper).
@@ -194,6 +536,7 @@ impl LocationSettings {
         Self {
             inner: haven_core::location::LocationSettings {
                 update_interval_minutes,
+                ..Default::default()
             },
         }
     }
@@ -204,6 +547,262 @@ impl LocationSettings {
     pub fn update_interval_minutes(&self) -> u32 {
         self.inner.update_interval_minutes
     }
+
+    /// Whether precision coarsens while the device stays in one spot.
+    #[frb(sync)]
+    #[must_use]
+    pub fn adaptive_precision(&self) -> bool {
+        self.inner.adaptive_precision
+    }
+
+    /// Turns adaptive precision on or off.
+    #[frb(sync)]
+    pub fn set_adaptive_precision(&mut self, enabled: bool) {
+        self.inner.adaptive_precision = enabled;
+    }
+
+    /// Whether rounded locations are also shifted by a seeded random amount.
+    #[frb(sync)]
+    #[must_use]
+    pub fn coordinate_jitter(&self) -> bool {
+        self.inner.coordinate_jitter
+    }
+
+    /// Turns coordinate jitter on or off.
+    #[frb(sync)]
+    pub fn set_coordinate_jitter(&mut self, enabled: bool) {
+        self.inner.coordinate_jitter = enabled;
+    }
+
+    /// Gets how long members keep each shared location, in seconds.
+    #[frb(sync)]
+    #[must_use]
+    pub fn share_expiration_secs(&self) -> u64 {
+        self.inner.share_expiration.as_secs()
+    }
+
+    /// Sets how long members keep each shared location, in seconds (1-hour,
+    /// 8-hour and 1-day presets, or a custom value clamped to 1 h..1 day).
+    #[frb(sync)]
+    pub fn set_share_expiration_secs(&mut self, secs: u64) {
+        self.inner.share_expiration = haven_core::location::ShareExpiration::from_secs(secs);
+    }
+
+    /// Gets the precision shared with circles that have no override.
+    #[frb(sync)]
+    #[must_use]
+    pub fn precision(&self) -> LocationPrecisionFfi {
+        self.inner.precision.into()
+    }
+
+    /// Sets the precision shared with circles that have no override.
+    #[frb(sync)]
+    pub fn set_precision(&mut self, precision: LocationPrecisionFfi) {
+        self.inner.precision = precision.into();
+    }
+
+    /// Whether battery, charging and motion state are shared with locations.
+    #[frb(sync)]
+    #[must_use]
+    pub fn share_device_status(&self) -> bool {
+        self.inner.share_device_status
+    }
+
+    /// Turns sharing battery, charging and motion state on or off.
+    #[frb(sync)]
+    pub fn set_share_device_status(&mut self, enabled: bool) {
+        self.inner.share_device_status = enabled;
+    }
+
+    /// Whether altitude and floor level are shared with locations.
+    #[frb(sync)]
+    #[must_use]
+    pub fn share_vertical_position(&self) -> bool {
+        self.inner.share_vertical_position
+    }
+
+    /// Turns sharing altitude and floor level on or off.
+    #[frb(sync)]
+    pub fn set_share_vertical_position(&mut self, enabled: bool) {
+        self.inner.share_vertical_position = enabled;
+    }
+}
+
+// ============================================================================
+// Errors and User-Facing Messages
+// ============================================================================
+
+use haven_core::messages::{Localize, MessageCode, UserMessage};
+
+/// One named parameter of a [`UserMessageFfi`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MessageParamFfi {
+    /// Placeholder name (`{name}` in the English template).
+    pub name: String,
+    /// Value to substitute.
+    pub value: String,
+}
+
+/// A localizable message: a stable code plus parameters (FFI).
+///
+/// Flutter looks `code` up in its translation catalog and substitutes
+/// `params`; `english` is the built-in fallback for codes the app does not
+/// translate yet.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UserMessageFfi {
+    /// Stable message code, e.g. `relay.rejected`.
+    pub code: String,
+    /// Named values the message refers to.
+    pub params: Vec<MessageParamFfi>,
+    /// English rendering of the message.
+    pub english: String,
+}
+
+impl From<UserMessage> for UserMessageFfi {
+    fn from(m: UserMessage) -> Self {
+        Self {
+            code: m.code.as_str().to_string(),
+            english: m.english(),
+            params: m
+                .params
+                .into_iter()
+                .map(|(name, value)| MessageParamFfi {
+                    name: name.to_string(),
+                    value,
+                })
+                .collect(),
+        }
+    }
+}
+
+/// Error returned by every fallible FFI call.
+///
+/// `message` is what the user sees (localized by Flutter); `detail` is the
+/// detailed English description for logs and bug reports. `Display` prints
+/// `detail`, so existing `log::` call sites keep their text.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HavenErrorFfi {
+    /// User-facing, localizable message.
+    pub message: UserMessageFfi,
+    /// Detailed English description, for logs only.
+    pub detail: String,
+}
+
+impl HavenErrorFfi {
+    fn with_code(code: MessageCode, detail: impl Into<String>) -> Self {
+        Self {
+            message: UserMessage::new(code).into(),
+            detail: detail.into(),
+        }
+    }
+
+    /// An unexpected internal failure (lock poisoning, serialization, ...).
+    pub(crate) fn internal(detail: impl Into<String>) -> Self {
+        Self::with_code(MessageCode::Internal, detail)
+    }
+
+    /// A malformed argument from the caller.
+    pub(crate) fn invalid_input(detail: impl Into<String>) -> Self {
+        Self::with_code(MessageCode::InvalidInput, detail)
+    }
+
+    /// A malformed secret key or public key argument.
+    pub(crate) fn invalid_key(detail: impl Into<String>) -> Self {
+        Self::with_code(MessageCode::InvalidKey, detail)
+    }
+
+    /// A keyring, file-system or database failure outside the core stores.
+    pub(crate) fn storage(detail: impl Into<String>) -> Self {
+        Self::with_code(MessageCode::Storage, detail)
+    }
+
+    /// A test-only entry point called in a release build.
+    pub(crate) fn unsupported(detail: impl Into<String>) -> Self {
+        Self::with_code(MessageCode::Unsupported, detail)
+    }
+
+    /// No live-sync session is running.
+    pub(crate) fn sync_not_running() -> Self {
+        Self::with_code(MessageCode::SyncNotRunning, "no active live-sync session")
+    }
+
+    /// Scrubs hex sequences (keys, ids, hashes) from the detail and params.
+    fn redact_hex(mut self) -> Self {
+        self.detail = haven_core::util::redact_hex_sequences(&self.detail);
+        for param in &mut self.message.params {
+            param.value = haven_core::util::redact_hex_sequences(&param.value);
+        }
+        self.message.english = haven_core::util::redact_hex_sequences(&self.message.english);
+        self
+    }
+}
+
+impl std::fmt::Display for HavenErrorFfi {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.detail)
+    }
+}
+
+/// Converts core errors: the message comes from [`Localize`], the detail from
+/// `Display`.
+macro_rules! localized_error {
+    ($($ty:ty),+ $(,)?) => {
+        $(
+            impl From<$ty> for HavenErrorFfi {
+                fn from(e: $ty) -> Self {
+                    Self {
+                        message: e.user_message().into(),
+                        detail: e.to_string(),
+                    }
+                }
+            }
+        )+
+    };
+}
+
+localized_error!(
+    haven_core::circle::CircleError,
+    haven_core::relay::RelayError,
+    haven_core::relay::PublisherError,
+    haven_core::relay::live_sync::LiveSyncError,
+    haven_core::nostr::NostrError,
+    haven_core::nostr::identity::IdentityError,
+    haven_core::nostr::nip46::Nip46Error,
+    haven_core::profile::ProfileError,
+    haven_core::avatar::AvatarError,
+    haven_core::tiles::TileCacheError,
+);
+
+impl From<nostr::key::Error> for HavenErrorFfi {
+    fn from(e: nostr::key::Error) -> Self {
+        Self::invalid_key(e.to_string())
+    }
+}
+
+impl From<hex::FromHexError> for HavenErrorFfi {
+    fn from(e: hex::FromHexError) -> Self {
+        Self::invalid_input(e.to_string())
+    }
+}
+
+impl From<serde_json::Error> for HavenErrorFfi {
+    fn from(e: serde_json::Error) -> Self {
+        Self::internal(e.to_string())
+    }
+}
+
+/// Helpers that still report plain strings (the `haven_core::validation`
+/// checks, FFI struct conversions) only ever reject caller input.
+impl From<String> for HavenErrorFfi {
+    fn from(detail: String) -> Self {
+        Self::invalid_input(detail)
+    }
+}
+
+impl From<&str> for HavenErrorFfi {
+    fn from(detail: &str) -> Self {
+        Self::invalid_input(detail)
+    }
 }
 
 // ============================================================================
@@ -308,6 +907,7 @@ impl From<CorePublicIdentity> for PublicIdentity {
 /// 3. Call `load_from_bytes()` to restore the identity
 /// 4. Or call `create_identity()` to generate a new one
 /// 5. Before app exits, get secret bytes with `get_secret_bytes()` and persist in Flutter
+/// 6. For routine operations, pass a `secret_handle()` instead of the bytes
 #[frb(opaque)]
 pub struct NostrIdentityManager {
     inner: IdentityManager<InMemoryStorage>,
@@ -325,18 +925,18 @@ impl NostrIdentityManager {
     /// Loads an identity from raw secret bytes (retrieved from Flutter secure storage).
     ///
     /// Call this on app startup if you have persisted secret bytes.
-    pub fn load_from_bytes(&self, secret_bytes: Vec<u8>) -> Result<PublicIdentity, String> {
+    pub fn load_from_bytes(&self, secret_bytes: Vec<u8>) -> Result<PublicIdentity, HavenErrorFfi> {
         let secret_bytes = zeroize::Zeroizing::new(secret_bytes);
         // Store and validate the secret bytes
         self.inner
             .store_secret_bytes(&secret_bytes)
-            .map_err(|e| e.to_string())?;
+            .map_err(HavenErrorFfi::from)?;
 
         // Get the identity
         self.inner
             .get_identity()
-            .map_err(|e| e.to_string())?
-            .ok_or_else(|| "Failed to load identity".to_string())
+            .map_err(HavenErrorFfi::from)?
+            .ok_or_else(|| HavenErrorFfi::internal("Failed to load identity"))
             .map(Into::into)
     }
 
@@ -350,36 +950,61 @@ impl NostrIdentityManager {
     /// Creates a new random identity.
     ///
     /// After calling this, use `get_secret_bytes()` to persist the secret.
-    pub fn create_identity(&self) -> Result<PublicIdentity, String> {
+    pub fn create_identity(&self) -> Result<PublicIdentity, HavenErrorFfi> {
         self.inner
             .create_identity()
             .map(Into::into)
-            .map_err(|e| e.to_string())
+            .map_err(HavenErrorFfi::from)
     }
 
     /// Imports an identity from an nsec string.
     ///
     /// After calling this, use `get_secret_bytes()` to persist the secret.
-    pub fn import_from_nsec(&self, nsec: String) -> Result<PublicIdentity, String> {
+    pub fn import_from_nsec(&self, nsec: String) -> Result<PublicIdentity, HavenErrorFfi> {
         self.inner
             .import_from_nsec(&nsec)
             .map(Into::into)
-            .map_err(|e| e.to_string())
+            .map_err(HavenErrorFfi::from)
+    }
+
+    /// Imports the identity received over a device link. Show
+    /// [`LinkedIdentityFfi::pubkey_hex`] to the user first.
+    ///
+    /// After calling this, use `get_secret_bytes()` to persist the secret.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if an identity already exists or `linked` was
+    /// already imported.
+    pub fn import_linked(
+        &self,
+        linked: &LinkedIdentityFfi,
+    ) -> Result<PublicIdentity, HavenErrorFfi> {
+        let identity = linked
+            .inner
+            .lock()
+            .map_err(|_| HavenErrorFfi::internal("Linked identity lock poisoned"))?
+            .take()
+            .ok_or_else(|| HavenErrorFfi::invalid_input("Linked identity already imported"))?;
+        self.inner
+            .import_linked(identity)
+            .map(Into::into)
+            .map_err(HavenErrorFfi::from)
     }
 
     /// Gets the current public identity.
     #[frb(sync)]
-    pub fn get_identity(&self) -> Result<Option<PublicIdentity>, String> {
+    pub fn get_identity(&self) -> Result<Option<PublicIdentity>, HavenErrorFfi> {
         self.inner
             .get_identity()
             .map(|opt| opt.map(Into::into))
-            .map_err(|e| e.to_string())
+            .map_err(HavenErrorFfi::from)
     }
 
     /// Gets the public key as hex string (for MDK operations).
     #[frb(sync)]
-    pub fn pubkey_hex(&self) -> Result<String, String> {
-        self.inner.pubkey_hex().map_err(|e| e.to_string())
+    pub fn pubkey_hex(&self) -> Result<String, HavenErrorFfi> {
+        self.inner.pubkey_hex().map_err(HavenErrorFfi::from)
     }
 
     /// Exports the identity as nsec for backup.
@@ -387,25 +1012,61 @@ impl NostrIdentityManager {
     /// # Security Warning
     ///
     /// This exposes the secret key. Only use for user-initiated backup.
-    pub fn export_nsec(&self) -> Result<String, String> {
-        self.inner.export_nsec().map_err(|e| e.to_string())
+    pub fn export_nsec(&self) -> Result<String, HavenErrorFfi> {
+        self.inner.export_nsec().map_err(HavenErrorFfi::from)
+    }
+
+    /// Exports the identity as a NIP-49 `ncryptsec` encrypted under
+    /// `passphrase`, safe for paper or cloud backup.
+    ///
+    /// `scrypt_log_n` defaults to 16 when `None`; values outside 16..=22 are
+    /// rejected.
+    pub fn export_encrypted(
+        &self,
+        passphrase: String,
+        scrypt_log_n: Option<u8>,
+    ) -> Result<String, HavenErrorFfi> {
+        let passphrase = zeroize::Zeroizing::new(passphrase);
+        let params = scrypt_log_n
+            .map(haven_core::nostr::identity::ScryptParams::new)
+            .transpose()
+            .map_err(HavenErrorFfi::from)?
+            .unwrap_or_default();
+        self.inner
+            .export_encrypted(&passphrase, params)
+            .map_err(HavenErrorFfi::from)
+    }
+
+    /// Imports an identity from a NIP-49 `ncryptsec` backup.
+    ///
+    /// After calling this, use `get_secret_bytes()` to persist the secret.
+    pub fn import_encrypted(
+        &self,
+        ncryptsec: String,
+        passphrase: String,
+    ) -> Result<PublicIdentity, HavenErrorFfi> {
+        let passphrase = zeroize::Zeroizing::new(passphrase);
+        self.inner
+            .import_encrypted(&ncryptsec, &passphrase)
+            .map(Into::into)
+            .map_err(HavenErrorFfi::from)
     }
 
     /// Signs a 32-byte message hash.
     ///
     /// Returns the signature as a 128-character hex string.
-    pub fn sign(&self, message_hash: Vec<u8>) -> Result<String, String> {
+    pub fn sign(&self, message_hash: Vec<u8>) -> Result<String, HavenErrorFfi> {
         if message_hash.len() != 32 {
-            return Err(format!(
+            return Err(HavenErrorFfi::invalid_input(format!(
                 "Invalid message hash length: expected 32, got {}",
                 message_hash.len()
-            ));
+            )));
         }
 
         let mut hash = [0u8; 32];
         hash.copy_from_slice(&message_hash);
 
-        self.inner.sign(&hash).map_err(|e| e.to_string())
+        self.inner.sign(&hash).map_err(HavenErrorFfi::from)
     }
 
     /// Gets the secret bytes for persistence in Flutter secure storage.
@@ -415,18 +1076,48 @@ impl NostrIdentityManager {
     /// Handle these bytes with extreme care. They should only be stored
     /// in platform secure storage (iOS Keychain, Android Keystore, etc.).
     /// The bytes are automatically zeroized in Rust memory after this call.
-    pub fn get_secret_bytes(&self) -> Result<Vec<u8>, String> {
+    pub fn get_secret_bytes(&self) -> Result<Vec<u8>, HavenErrorFfi> {
         // The inner method returns Zeroizing<Vec<u8>>, we extract the bytes
         // for FFI. Flutter must handle these securely.
         self.inner
             .get_secret_bytes()
             .map(|z| z.to_vec())
-            .map_err(|e| e.to_string())
+            .map_err(HavenErrorFfi::from)
     }
 
-    /// Deletes the identity.
-    pub fn delete_identity(&self) -> Result<(), String> {
-        self.inner.delete_identity().map_err(|e| e.to_string())
+    /// Returns a [`SecretHandle`] to the loaded identity.
+    ///
+    /// Every operation that signs as the identity takes this handle rather
+    /// than the bytes from [`Self::get_secret_bytes`], so the secret stays in
+    /// Rust memory.
+    pub fn secret_handle(&self) -> Result<SecretHandle, HavenErrorFfi> {
+        let bytes = self.inner.get_secret_bytes().map_err(HavenErrorFfi::from)?;
+        SecretHandle::from_slice(&bytes)
+    }
+
+    /// Deletes the identity key only, without checking for circles that
+    /// depend on it. Prefer [`Self::delete_identity_guarded`].
+    pub fn delete_identity(&self) -> Result<(), HavenErrorFfi> {
+        self.inner.delete_identity().map_err(HavenErrorFfi::from)
+    }
+
+    /// Deletes the identity after tearing down the circle state bound to it.
+    ///
+    /// Fails, changing nothing, while the identity is still a member of a
+    /// circle unless `force` is set (see
+    /// [`CircleManagerFfi::identity_deletion_report`]). With `leave_first`,
+    /// each circle that can be left gets a signed `SelfRemove` in the result.
+    /// Afterwards, publish those events (best effort), drop the circle
+    /// manager and call [`wipe_all_mls_state`] to remove the session database.
+    pub async fn delete_identity_guarded(
+        &self,
+        circle: &CircleManagerFfi,
+        force: bool,
+        leave_first: bool,
+    ) -> Result<IdentityTeardownFfi, HavenErrorFfi> {
+        let teardown = circle.prepare_identity_deletion(force, leave_first).await?;
+        self.inner.delete_identity().map_err(HavenErrorFfi::from)?;
+        Ok(teardown)
     }
 
     /// Clears the in-memory cache.
@@ -452,3434 +1143,7993 @@ impl std::fmt::Debug for NostrIdentityManager {
     }
 }
 
-// ============================================================================
-// Encrypted Event Types (FFI wrappers for Nostr event generation)
-// ============================================================================
-
-/// Unsigned location event (FFI wrapper for inner event kind 9).
+/// Opaque handle to the identity secret key.
 ///
-/// This is the inner event containing location data before encryption.
-/// It is wrapped in a kind 445 group message for transmission.
-#[derive(Clone)]
-pub struct UnsignedLocationEventFfi {
-    /// Event kind (9 for location data per MIP-03).
-    pub kind: u16,
-    /// JSON-serialized location data.
-    pub content: String,
-    /// Event tags (typically empty for inner events).
-    pub tags: Vec<Vec<String>>,
-    /// Unix timestamp when the event was created.
-    pub created_at: i64,
+/// The 32 secret bytes live in Rust memory only and are zeroized when the
+/// handle is dropped. Dart holds a pointer, never the key, and every FFI
+/// operation that signs as the identity takes a handle, so the nsec is never
+/// copied onto the Dart heap for it. Get one from
+/// [`NostrIdentityManager::secret_handle`]; `get_secret_bytes()` remains
+/// only for persisting to platform secure storage.
+#[frb(opaque)]
+pub struct SecretHandle {
+    secret: zeroize::Zeroizing<[u8; 32]>,
+}
+
+impl SecretHandle {
+    /// Copies and validates a 32-byte secret key.
+    fn from_slice(bytes: &[u8]) -> Result<Self, HavenErrorFfi> {
+        let secret: [u8; 32] = bytes
+            .try_into()
+            .map_err(|_| HavenErrorFfi::invalid_key("Invalid secret bytes length"))?;
+        let handle = Self {
+            secret: zeroize::Zeroizing::new(secret),
+        };
+        handle.keys()?;
+        Ok(handle)
+    }
+
+    /// The public key as 64-character hex.
+    #[frb(sync)]
+    pub fn pubkey_hex(&self) -> Result<String, HavenErrorFfi> {
+        Ok(self.keys()?.public_key().to_hex())
+    }
+
+    /// Derives the identity [`Keys`] for one operation.
+    ///
+    /// [`Keys`]: nostr::Keys
+    fn keys(&self) -> Result<nostr::Keys, HavenErrorFfi> {
+        let secret_key = nostr::SecretKey::from_slice(self.secret.as_slice())
+            .map_err(|e| HavenErrorFfi::invalid_key(format!("Invalid secret key: {e}")))?;
+        Ok(nostr::Keys::new(secret_key))
+    }
 }
 
-impl std::fmt::Debug for UnsignedLocationEventFfi {
+impl std::fmt::Debug for SecretHandle {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        f.debug_struct("UnsignedLocationEventFfi")
-            .field("kind", &self.kind)
-            .field("content", &"<redacted>")
-            .field("tag_count", &self.tags.len())
-            .field("created_at", &self.created_at)
+        f.debug_struct("SecretHandle")
+            .field("secret", &"<redacted>")
             .finish()
     }
 }
 
-impl From<haven_core::nostr::UnsignedLocationEvent> for UnsignedLocationEventFfi {
-    fn from(e: haven_core::nostr::UnsignedLocationEvent) -> Self {
-        Self {
-            kind: e.kind,
-            content: e.content,
-            tags: e.tags,
-            created_at: e.created_at,
-        }
+/// A signer whose key never enters this process: Android Keystore, the iOS
+/// Secure Enclave or a remote signer, reached through Dart callbacks.
+///
+/// - `sign_event_hash` receives a 32-byte NIP-01 event id and returns the
+///   64-byte BIP-340 signature as hex;
+/// - `nip44_encrypt` receives a recipient pubkey (hex) and a plaintext and
+///   returns the NIP-44 v2 payload (needed to seal gift wraps).
+///
+/// Either callback refuses by returning an empty string. Every signature is
+/// verified against the event Rust built before it is used, so a callback
+/// cannot substitute another event.
+#[frb(opaque)]
+pub struct ExternalSignerFfi {
+    inner: CallbackSigner,
+}
+
+impl ExternalSignerFfi {
+    /// Creates a signer for the identity `pubkey_hex`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `pubkey_hex` is not a valid public key.
+    #[frb(sync)]
+    pub fn new(
+        pubkey_hex: String,
+        sign_event_hash: impl Fn(Vec<u8>) -> DartFnFuture<String> + Send + Sync + 'static,
+        nip44_encrypt: impl Fn(String, String) -> DartFnFuture<String> + Send + Sync + 'static,
+    ) -> Result<Self, HavenErrorFfi> {
+        let public_key = nostr::PublicKey::from_hex(&pubkey_hex)
+            .map_err(|e| HavenErrorFfi::invalid_input(format!("Invalid public key: {e}")))?;
+        Ok(Self {
+            inner: CallbackSigner {
+                public_key,
+                sign_event_hash: Arc::new(sign_event_hash),
+                nip44_encrypt: Arc::new(nip44_encrypt),
+            },
+        })
+    }
+
+    /// The public key events are signed as, as 64-character hex.
+    #[frb(sync)]
+    #[must_use]
+    pub fn pubkey_hex(&self) -> String {
+        self.inner.public_key.to_hex()
     }
 }
 
-/// Signed location event (FFI wrapper for outer event kind 445).
-///
-/// This is the outer event ready for relay transmission.
-/// Contains encrypted content signed with an ephemeral keypair.
-#[derive(Debug, Clone)]
-pub struct SignedLocationEventFfi {
-    /// Event ID (SHA256 hash, 64 hex chars).
-    pub id: String,
-    /// Ephemeral public key (64 hex chars).
-    pub pubkey: String,
-    /// Unix timestamp when the event was created.
-    pub created_at: i64,
-    /// Event kind (445 for Marmot group messages).
-    pub kind: u16,
-    /// Event tags: `[["h", group_id], ["expiration", ts], ...]`.
-    pub tags: Vec<Vec<String>>,
-    /// NIP-44 encrypted content (base64).
-    pub content: String,
-    /// Schnorr signature (128 hex chars).
-    pub sig: String,
+impl std::fmt::Debug for ExternalSignerFfi {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ExternalSignerFfi").finish_non_exhaustive()
+    }
 }
 
-impl From<haven_core::nostr::SignedLocationEvent> for SignedLocationEventFfi {
-    fn from(e: haven_core::nostr::SignedLocationEvent) -> Self {
-        Self {
-            id: e.id,
-            pubkey: e.pubkey,
-            created_at: e.created_at,
-            kind: e.kind,
-            tags: e.tags,
-            content: e.content,
-            sig: e.sig,
+/// The core [`Signer`] behind [`ExternalSignerFfi`].
+///
+/// The trait is synchronous, so each call parks its thread on the Dart
+/// callback the way [`Nip46Signer`] waits on the bunker: safe from the
+/// blocking pool and from an async task on the multi-threaded runtime.
+#[derive(Clone)]
+struct CallbackSigner {
+    public_key: nostr::PublicKey,
+    sign_event_hash: Arc<dyn Fn(Vec<u8>) -> DartFnFuture<String> + Send + Sync>,
+    nip44_encrypt: Arc<dyn Fn(String, String) -> DartFnFuture<String> + Send + Sync>,
+}
+
+impl CallbackSigner {
+    /// The callback's answer, or why there is none (an empty answer is a
+    /// refusal).
+    fn wait(future: DartFnFuture<String>) -> Result<String, String> {
+        let handle =
+            tokio::runtime::Handle::try_current().map_err(|_| "no async runtime".to_string())?;
+        // `block_in_place` panics on a current-thread runtime.
+        if handle.runtime_flavor() == tokio::runtime::RuntimeFlavor::CurrentThread {
+            return Err("external signing needs a multi-threaded runtime".to_string());
         }
+        let out = tokio::task::block_in_place(|| handle.block_on(future));
+        if out.is_empty() {
+            return Err("External signer refused".to_string());
+        }
+        Ok(out)
     }
 }
 
-// ============================================================================
-// Location Event Service
-// ============================================================================
+impl Signer for CallbackSigner {
+    fn public_key(&self) -> nostr::PublicKey {
+        self.public_key
+    }
 
-/// Service for creating and verifying Nostr location events.
+    fn sign_event_hash(
+        &self,
+        hash: &[u8; 32],
+    ) -> Result<nostr::secp256k1::schnorr::Signature, IdentityError> {
+        let sig_hex =
+            Self::wait((self.sign_event_hash)(hash.to_vec())).map_err(IdentityError::Signing)?;
+        let bytes = hex::decode(sig_hex.trim())
+            .map_err(|_| IdentityError::Signing("Malformed external signature".to_string()))?;
+        nostr::secp256k1::schnorr::Signature::from_slice(&bytes)
+            .map_err(|_| IdentityError::Signing("Malformed external signature".to_string()))
+    }
+
+    fn nip44_encrypt(
+        &self,
+        recipient: &nostr::PublicKey,
+        plaintext: &str,
+    ) -> Result<String, IdentityError> {
+        Self::wait((self.nip44_encrypt)(
+            recipient.to_hex(),
+            plaintext.to_string(),
+        ))
+        .map_err(IdentityError::Encryption)
+    }
+}
+
+// ==================== Remote signer (NIP-46) ====================
+
+/// The paired NIP-46 remote signer, if any (see [`haven_core::nostr::nip46`]).
 ///
-/// Provides methods for creating unsigned Nostr events from location data
-/// and verifying signatures on signed events.
-#[derive(Debug, Default)]
-#[frb(opaque)]
-pub struct LocationEventService {
-    _private: (),
+/// Loaded from `circles.db` when the [`CircleManagerFfi`] opens and updated
+/// by [`CircleManagerFfi::configure_remote_signer`] and
+/// [`CircleManagerFfi::clear_remote_signer`]. While set, key package, relay
+/// list and one-shot gift-wrap signing go to the bunker instead of the local
+/// key.
+static REMOTE_SIGNER: RwLock<Option<Nip46Signer>> = RwLock::new(None);
+
+fn install_remote_signer(signer: Option<Nip46Signer>) {
+    *REMOTE_SIGNER
+        .write()
+        .unwrap_or_else(std::sync::PoisonError::into_inner) = signer;
 }
 
-impl LocationEventService {
-    /// Creates a new `LocationEventService`.
-    #[frb(sync)]
-    #[must_use]
-    pub fn new() -> Self {
-        Self { _private: () }
+/// The identity signer for one routed call.
+#[derive(Clone)]
+enum IdentitySigner {
+    /// The identity key, in memory.
+    Local(nostr::Keys),
+    /// The paired bunker.
+    Remote(Nip46Signer),
+    /// A key outside this process, reached through Dart
+    /// ([`ExternalSignerFfi`]).
+    External(CallbackSigner),
+}
+
+impl IdentitySigner {
+    /// The paired remote signer if there is one, otherwise the key `local`
+    /// returns. `local` is not called while a remote signer is paired.
+    fn resolve(
+        local: impl FnOnce() -> Result<nostr::Keys, HavenErrorFfi>,
+    ) -> Result<Self, HavenErrorFfi> {
+        let remote = REMOTE_SIGNER
+            .read()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+            .clone();
+        match remote {
+            Some(signer) => Ok(Self::Remote(signer)),
+            None => local().map(Self::Local),
+        }
     }
 
-    /// Creates an unsigned location event (kind 9 per MIP-03).
-    ///
-    /// This is the inner event that gets encrypted before being wrapped
-    /// in a kind 445 group message.
-    #[frb(sync)]
-    pub fn create_unsigned_event(
+    /// How to answer a relay's proof-of-work demand. Mining re-signs the
+    /// event, so only the local key does it; a remote- or externally-signed
+    /// event goes out as-is.
+    fn pow_policy(&self) -> Option<haven_core::relay::PowPolicy> {
+        match self {
+            Self::Local(keys) => Some(haven_core::relay::PowPolicy::on_demand(keys.clone())),
+            Self::Remote(_) | Self::External(_) => None,
+        }
+    }
+}
+
+impl Signer for IdentitySigner {
+    fn public_key(&self) -> nostr::PublicKey {
+        match self {
+            Self::Local(keys) => Signer::public_key(keys),
+            Self::Remote(signer) => signer.public_key(),
+            Self::External(signer) => signer.public_key(),
+        }
+    }
+
+    fn sign_event_hash(
         &self,
-        location: &LocationMessage,
-    ) -> Result<UnsignedLocationEventFfi, String> {
-        haven_core::nostr::UnsignedLocationEvent::from_location(&location.inner)
-            .map(Into::into)
-            .map_err(|e| e.to_string())
+        hash: &[u8; 32],
+    ) -> Result<nostr::secp256k1::schnorr::Signature, IdentityError> {
+        match self {
+            Self::Local(keys) => Signer::sign_event_hash(keys, hash),
+            Self::Remote(signer) => signer.sign_event_hash(hash),
+            Self::External(signer) => signer.sign_event_hash(hash),
+        }
     }
 
-    /// Verifies the signature of a signed event.
-    ///
-    /// Returns `true` if the signature is valid, `false` otherwise.
-    #[frb(sync)]
-    pub fn verify_signature(&self, event: &SignedLocationEventFfi) -> Result<bool, String> {
-        let core_event = haven_core::nostr::SignedLocationEvent {
-            id: event.id.clone(),
-            pubkey: event.pubkey.clone(),
-            created_at: event.created_at,
-            kind: event.kind,
-            tags: event.tags.clone(),
-            content: event.content.clone(),
-            sig: event.sig.clone(),
-        };
+    fn nip44_encrypt(
+        &self,
+        recipient: &nostr::PublicKey,
+        plaintext: &str,
+    ) -> Result<String, IdentityError> {
+        match self {
+            Self::Local(keys) => Signer::nip44_encrypt(keys, recipient, plaintext),
+            Self::Remote(signer) => signer.nip44_encrypt(recipient, plaintext),
+            Self::External(signer) => signer.nip44_encrypt(recipient, plaintext),
+        }
+    }
 
-        match core_event.verify_signature() {
-            Ok(()) => Ok(true),
-            Err(_) => Ok(false),
+    fn sign_unsigned_event(
+        &self,
+        unsigned: nostr::UnsignedEvent,
+    ) -> Result<nostr::Event, IdentityError> {
+        match self {
+            Self::Local(keys) => Signer::sign_unsigned_event(keys, unsigned),
+            Self::Remote(signer) => signer.sign_unsigned_event(unsigned),
+            Self::External(signer) => signer.sign_unsigned_event(unsigned),
         }
     }
+}
 
-    /// Returns a jittered publish interval in seconds using the Rust-side
-    /// CSPRNG (`OsRng`), sampled uniformly in
-    /// `[nominal_secs * 0.6, nominal_secs * 1.4]` (40% spread).
-    ///
-    /// Callers pass the nominal interval (typically 120 s) and rearm their
-    /// timer with the returned value. This is NOT the TTL window — the
-    /// `expiration` tag sampled inside `encrypt_location` is independent
-    /// and intentionally decoupled (see `haven-core/SECURITY.md`).
-    ///
-    /// On `nominal_secs == 0` the call falls back to `nominal_secs` rather
-    /// than panicking, so a zero at the boundary does not silently
-    /// reschedule at 0 s. Dart callers should pass a positive nominal.
-    #[frb(sync)]
-    #[must_use]
-    pub fn jittered_publish_interval_secs(&self, nominal_secs: u64) -> u64 {
-        haven_core::location::compute_jittered_publish_interval_secs(
-            nominal_secs,
-            haven_core::location::PUBLISH_INTERVAL_JITTER_FRACTION_BP,
-        )
-        .unwrap_or(nominal_secs)
+/// A paired NIP-46 remote signer (FFI).
+#[derive(Clone)]
+pub struct RemoteSignerFfi {
+    /// The identity the bunker signs as (hex).
+    pub user_pubkey_hex: String,
+    /// The bunker's own key (hex).
+    pub remote_signer_pubkey_hex: String,
+    /// Relays the bunker listens on.
+    pub relays: Vec<String>,
+    /// When the pairing was made (unix seconds).
+    pub connected_at: i64,
+}
+
+impl std::fmt::Debug for RemoteSignerFfi {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RemoteSignerFfi")
+            .field("user_pubkey_hex", &"<redacted>")
+            .field("relays", &self.relays.len())
+            .field("connected_at", &self.connected_at)
+            .finish_non_exhaustive()
     }
 }
 
-// ============================================================================
-// Platform Initialization
-// ============================================================================
+impl From<&RemoteSignerConfig> for RemoteSignerFfi {
+    fn from(config: &RemoteSignerConfig) -> Self {
+        Self {
+            user_pubkey_hex: config.user_pubkey.clone(),
+            remote_signer_pubkey_hex: config.remote_signer.clone(),
+            relays: config.relays.clone(),
+            connected_at: config.connected_at,
+        }
+    }
+}
 
-use std::sync::Mutex;
+// ==================== Key codec (NIP-19) ====================
 
-/// Guard for keyring store initialization. Only caches success so that transient
-/// failures (e.g., iOS data protection not yet unlocked after reboot) can be
-/// retried on the next call.
-static KEYRING_INIT: Mutex<Option<()>> = Mutex::new(None);
+/// A decoded NIP-19 `nprofile` (FFI-friendly).
+#[derive(Clone)]
+pub struct NprofileFfi {
+    /// Public key as 64-character hex string.
+    pub pubkey_hex: String,
+    /// Public key in NIP-19 bech32 format (npub1...).
+    pub npub: String,
+    /// Relay hints carried by the nprofile.
+    pub relays: Vec<String>,
+}
 
-/// Initializes the platform-specific keyring credential store.
+impl std::fmt::Debug for NprofileFfi {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("NprofileFfi")
+            .field("pubkey_hex", &"<redacted>")
+            .field("relays", &self.relays.len())
+            .finish_non_exhaustive()
+    }
+}
+
+/// Returns `true` if `npub` is a valid `npub1...` (optionally `nostr:`-prefixed).
+#[frb(sync)]
+#[must_use]
+pub fn validate_npub(npub: String) -> bool {
+    haven_core::nostr::identity::validate_npub(&npub).is_ok()
+}
+
+/// Converts an npub to its 64-character hex public key.
 ///
-/// Must be called **once** before any `CircleManagerFfi` operations. The keyring
-/// store is used by MDK to securely store the `SQLCipher` database encryption key
-/// in the platform's native credential store (Keychain, Keystore, etc.).
+/// # Errors
 ///
-/// This function is idempotent: once initialization succeeds, subsequent calls
-/// return `Ok(())` immediately. If initialization fails, the next call will
-/// retry rather than returning a cached error.
+/// Returns an error if `npub` is not a valid npub.
+#[frb(sync)]
+pub fn npub_to_hex(npub: String) -> Result<String, HavenErrorFfi> {
+    haven_core::nostr::identity::npub_to_hex(&npub).map_err(HavenErrorFfi::from)
+}
+
+/// Converts a 64-character hex public key to its npub.
 ///
 /// # Errors
 ///
-/// Returns an error string if the platform keyring store cannot be initialized
-/// (e.g., on Android when the JNI context has not been provided).
-pub fn init_keyring_store() -> Result<(), String> {
-    let mut guard = KEYRING_INIT
-        .lock()
-        .map_err(|e| format!("Keyring lock poisoned: {e}"))?;
-    if guard.is_some() {
-        return Ok(());
-    }
-    platform_init_keyring().map_err(|e| format!("Keyring initialization failed: {e}"))?;
-    *guard = Some(());
-    Ok(())
+/// Returns an error if `hex` is not a valid public key.
+#[frb(sync)]
+pub fn hex_to_npub(hex: String) -> Result<String, HavenErrorFfi> {
+    haven_core::nostr::identity::hex_to_npub(&hex).map_err(HavenErrorFfi::from)
 }
 
-/// Installs an in-memory keyring backend for E2E tests.
-///
-/// Intended exclusively for hermetic test harnesses on CI runners that lack
-/// a platform credential store (e.g., Linux runners with no D-Bus Secret
-/// Service). The backing storage is process-local and dropped when the
-/// process exits — secrets never touch disk.
+/// Checks an `nsec1...` secret key without importing it, returning the npub
+/// it belongs to.
 ///
-/// Must be called **before** [`init_keyring_store`]. If a platform backend
-/// has already been installed (`KEYRING_INIT.is_some()`), this call is a
-/// no-op so test setup can be defensive about ordering.
+/// # Errors
 ///
-/// Idempotent: calling twice returns `Ok(())` on the second call.
+/// Returns an error if `nsec` is not a valid nsec; the error never contains
+/// the input.
+#[frb(sync)]
+pub fn validate_nsec(nsec: String) -> Result<String, HavenErrorFfi> {
+    use nostr::prelude::ToBech32 as _;
+    let nsec = zeroize::Zeroizing::new(nsec);
+    haven_core::nostr::identity::validate_nsec(&nsec)
+        .map_err(HavenErrorFfi::from)?
+        .to_bech32()
+        .map_err(|e| HavenErrorFfi::internal(e.to_string()))
+}
+
+/// Decodes an `nprofile1...` (optionally `nostr:`-prefixed) into its key and
+/// relay hints.
 ///
 /// # Errors
 ///
-/// * Returns an error if the keyring init mutex is poisoned.
-/// * Returns an error if the in-memory store cannot be constructed
-///   (the upstream `keyring_core::mock::Store::new()` does not fail in
-///   practice, but the boundary is preserved for forward compatibility).
-/// * In release builds this function is unreachable; the sibling stub
-///   always returns an error.
-#[cfg(debug_assertions)]
-pub fn use_in_memory_keyring_for_test() -> Result<(), String> {
-    let mut guard = KEYRING_INIT
-        .lock()
-        .map_err(|e| format!("Keyring lock poisoned: {e}"))?;
-    if guard.is_some() {
-        // A backend (platform or in-memory) is already installed. Treat this
-        // as idempotent success so test setup that races init paths does
-        // not surface as a flaky failure.
-        return Ok(());
+/// Returns an error if `nprofile` is not a valid nprofile.
+#[frb(sync)]
+pub fn decode_nprofile(nprofile: String) -> Result<NprofileFfi, HavenErrorFfi> {
+    let profile =
+        haven_core::nostr::identity::decode_nprofile(&nprofile).map_err(HavenErrorFfi::from)?;
+    let pubkey_hex = profile.pubkey.to_hex();
+    Ok(NprofileFfi {
+        npub: npub_or_hex(&pubkey_hex),
+        pubkey_hex,
+        relays: profile.relays,
+    })
+}
+
+// ============================================================================
+// Device Linking
+// ============================================================================
+
+/// The new device's side of a device link (see [`haven_core::device_link`]).
+#[frb(opaque)]
+pub struct DeviceLinkSessionFfi {
+    inner: haven_core::device_link::DeviceLinkSession,
+}
+
+impl DeviceLinkSessionFfi {
+    /// The `haven-link:` payload to show as a QR code.
+    #[frb(sync)]
+    pub fn payload(&self) -> Result<String, HavenErrorFfi> {
+        self.inner.offer().to_payload().map_err(HavenErrorFfi::from)
+    }
+
+    /// Unix timestamp after which the link is void.
+    #[frb(sync)]
+    #[must_use]
+    pub fn expires_at(&self) -> i64 {
+        self.inner.offer().expires_at
     }
-    let store = crate::test_keyring::build_in_memory_store()?;
-    keyring_core::set_default_store(store);
-    *guard = Some(());
-    Ok(())
 }
 
-/// Release-build stub for [`use_in_memory_keyring_for_test`].
-///
-/// Returns an error so release callers fail closed — the in-memory backend
-/// is physically unreachable in release builds (the `test_keyring` module is
-/// gated on `#[cfg(debug_assertions)]`).
+/// Starts linking this new device to the identity on another device, which
+/// will send it through up to three of `relays`.
 ///
 /// # Errors
 ///
-/// Always returns an error.
-#[cfg(not(debug_assertions))]
-pub fn use_in_memory_keyring_for_test() -> Result<(), String> {
-    Err("use_in_memory_keyring_for_test is disabled in release builds".to_string())
+/// Returns an error if `relays` is empty.
+#[frb(sync)]
+pub fn start_device_link(relays: Vec<String>) -> Result<DeviceLinkSessionFfi, HavenErrorFfi> {
+    haven_core::device_link::DeviceLinkSession::start(&relays)
+        .map(|inner| DeviceLinkSessionFfi { inner })
+        .map_err(HavenErrorFfi::from)
 }
 
-#[cfg(not(any(
-    target_os = "macos",
-    target_os = "ios",
-    target_os = "linux",
-    target_os = "windows",
-    target_os = "android",
-)))]
-compile_error!("No keyring store implementation for this target OS");
+/// One circle of a linked identity (FFI mirror of
+/// [`haven_core::device_link::LinkedCircle`]).
+#[derive(Debug, Clone)]
+pub struct LinkedCircleFfi {
+    /// The circle's Nostr group ID (hex).
+    pub nostr_group_id: String,
+    /// The circle's display name on the old device.
+    pub display_name: String,
+    /// The circle's relays.
+    pub relays: Vec<String>,
+    /// Admin public keys (hex), who can re-add this device.
+    pub admins: Vec<String>,
+}
 
-/// Platform-specific keyring store initialization.
-//
-// `return` statements are needed inside cfg-gated branches because each
-// branch is mutually exclusive — without them clippy fires `needless_return`
-// only on whichever target is currently being compiled, which then varies
-// across CI runners. Allow the lint at the function level to keep the
-// branches symmetrical and platform-portable.
-#[allow(clippy::needless_return)]
-fn platform_init_keyring() -> Result<(), String> {
-    #[cfg(target_os = "macos")]
-    {
-        let store = apple_native_keyring_store::keychain::Store::new()
-            .map_err(|e| format!("macOS keychain store: {e}"))?;
-        keyring_core::set_default_store(store);
-        return Ok(());
+impl From<haven_core::device_link::LinkedCircle> for LinkedCircleFfi {
+    fn from(c: haven_core::device_link::LinkedCircle) -> Self {
+        Self {
+            nostr_group_id: c.nostr_group_id,
+            display_name: c.display_name,
+            relays: c.relays,
+            admins: c.admins.iter().map(|a| normalize_pubkey_hex(a)).collect(),
+        }
     }
+}
 
-    #[cfg(target_os = "ios")]
-    {
-        let store = apple_native_keyring_store::protected::Store::new()
-            .map_err(|e| format!("iOS protected store: {e}"))?;
-        keyring_core::set_default_store(store);
-        return Ok(());
+impl From<LinkedCircleFfi> for haven_core::device_link::LinkedCircle {
+    fn from(c: LinkedCircleFfi) -> Self {
+        Self {
+            nostr_group_id: c.nostr_group_id,
+            display_name: c.display_name,
+            relays: c.relays,
+            admins: c.admins,
+        }
     }
+}
 
-    #[cfg(target_os = "linux")]
-    {
-        let store = zbus_secret_service_keyring_store::Store::new()
-            .map_err(|e| format!("Linux secret service store: {e}"))?;
-        keyring_core::set_default_store(store);
-        return Ok(());
-    }
+/// An identity received over a device link, until
+/// [`NostrIdentityManager::import_linked`] takes it.
+#[frb(opaque)]
+pub struct LinkedIdentityFfi {
+    pubkey_hex: String,
+    circles: Vec<LinkedCircleFfi>,
+    inner: std::sync::Mutex<Option<haven_core::device_link::LinkedIdentity>>,
+}
 
-    #[cfg(target_os = "windows")]
-    {
-        let store = windows_native_keyring_store::Store::new()
-            .map_err(|e| format!("Windows keyring store: {e}"))?;
-        keyring_core::set_default_store(store);
-        return Ok(());
+impl LinkedIdentityFfi {
+    /// The linked identity's public key (hex), for the user to confirm.
+    #[frb(sync)]
+    #[must_use]
+    pub fn pubkey_hex(&self) -> String {
+        self.pubkey_hex.clone()
     }
 
-    #[cfg(target_os = "android")]
-    {
-        let store = android_native_keyring_store::Store::from_ndk_context()
-            .map_err(|e| format!("Android keyring store: {e}"))?;
-        keyring_core::set_default_store(store);
-        return Ok(());
+    /// The old device's circles, to ask to rejoin.
+    #[frb(sync)]
+    #[must_use]
+    pub fn circles(&self) -> Vec<LinkedCircleFfi> {
+        self.circles.clone()
     }
 }
 
-// ============================================================================
-// Circles DB Encryption Key Management
-// ============================================================================
+impl From<haven_core::device_link::LinkedIdentity> for LinkedIdentityFfi {
+    fn from(linked: haven_core::device_link::LinkedIdentity) -> Self {
+        Self {
+            pubkey_hex: normalize_pubkey_hex(&linked.pubkey_hex),
+            circles: linked
+                .circles
+                .iter()
+                .cloned()
+                .map(LinkedCircleFfi::from)
+                .collect(),
+            inner: std::sync::Mutex::new(Some(linked)),
+        }
+    }
+}
 
-/// Keyring service identifier for circles.db encryption.
-const CIRCLES_DB_SERVICE: &str = "com.oblivioustech.haven";
+/// A request from a member's new device to be re-added to a circle (FFI
+/// mirror of [`haven_core::device_link::RejoinRequest`]).
+///
+/// Grant it by adding `key_package_json` to the circle as a new member.
+#[derive(Debug, Clone)]
+pub struct RejoinRequestFfi {
+    /// The circle's MLS group ID.
+    pub mls_group_id: Vec<u8>,
+    /// The requesting member's public key (hex).
+    pub requester_pubkey: String,
+    /// The new device's `KeyPackage` event (JSON).
+    pub key_package_json: String,
+    /// Hex id of the gift wrap it arrived in.
+    pub wrapper_event_id: String,
+}
 
-/// Keyring key identifier for the circles.db encryption key.
-const CIRCLES_DB_KEY_ID: &str = "circles.db.key";
+impl From<(GroupId, haven_core::device_link::RejoinRequest)> for RejoinRequestFfi {
+    fn from((gid, r): (GroupId, haven_core::device_link::RejoinRequest)) -> Self {
+        Self {
+            mls_group_id: gid.as_slice().to_vec(),
+            requester_pubkey: normalize_pubkey_hex(&r.requester_pubkey.to_hex()),
+            key_package_json: nostr::JsonUtil::as_json(&r.key_package_event),
+            wrapper_event_id: r.wrapper_event_id.to_hex(),
+        }
+    }
+}
 
-/// On-disk filename for the encrypted circle-metadata DB (rollback-journal
-/// mode → `-journal` sidecar).
-const CIRCLES_DB_FILENAME: &str = "circles.db";
+// ============================================================================
+// Device Transfer
+// ============================================================================
 
-/// On-disk filename for the Dark Matter MLS-state DB (WAL mode →
-/// `-wal`/`-shm` sidecars). Mirrors
-/// [`haven_core::nostr::mls::StorageConfig::database_path`] (`session.sqlite`).
-const MLS_SESSION_DB_FILENAME: &str = "session.sqlite";
+/// A device transfer bundle read from a file, before the user's passphrase
+/// opens it (see [`haven_core::device_transfer`]).
+#[frb(opaque)]
+pub struct TransferBundleFfi {
+    inner: haven_core::device_transfer::TransferBundle,
+}
 
-/// Keyring key identifier for the Dark Matter MLS session passphrase. The
-/// service identifier is the shared [`CIRCLES_DB_SERVICE`]; only the key id
-/// differs. Mirrors the `MLS_DB_KEY_ID` constant in
-/// `haven_core::nostr::mls::storage`.
-const MLS_SESSION_DB_KEY_ID: &str = "mls.session.key.default";
+impl TransferBundleFfi {
+    /// The identity backup (NIP-49 `ncryptsec`) to restore first with
+    /// [`NostrIdentityManager::import_encrypted`].
+    #[frb(sync)]
+    #[must_use]
+    pub fn identity_backup(&self) -> String {
+        self.inner.identity_backup().to_string()
+    }
 
-/// On-disk filename for the PRE-Dark-Matter MLS-state DB (retained only for the
-/// one-time first-launch cutover cleanup; see [`destroy_legacy_mls_state`]).
-const LEGACY_MLS_DB_FILENAME: &str = "haven_mdk.db";
+    /// Opens the bundle with the restored identity.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the bundle was sealed for another identity or has
+    /// been tampered with.
+    pub fn open(&self, secret: &SecretHandle) -> Result<TransferContentsFfi, HavenErrorFfi> {
+        let keys = secret.keys()?;
+        self.inner
+            .open(&keys)
+            .map(|inner| TransferContentsFfi { inner })
+            .map_err(HavenErrorFfi::from)
+    }
+}
 
-/// Retrieves or creates the circles.db encryption key from the system keyring.
-///
-/// On first call, generates a cryptographically random 256-bit key using `OsRng`,
-/// stores the raw bytes in the platform keyring, and returns the hex-encoded key.
-/// Subsequent calls retrieve and hex-encode the stored key.
+/// Parses a device transfer bundle.
 ///
 /// # Errors
 ///
-/// Returns an error string if the keyring cannot be accessed or if key
-/// generation fails.
-fn get_or_create_circle_db_key() -> Result<zeroize::Zeroizing<String>, String> {
-    use rand::RngCore;
+/// Returns an error if `bytes` is not a transfer bundle.
+#[frb(sync)]
+pub fn parse_transfer_bundle(bytes: Vec<u8>) -> Result<TransferBundleFfi, HavenErrorFfi> {
+    haven_core::device_transfer::TransferBundle::parse(&bytes)
+        .map(|inner| TransferBundleFfi { inner })
+        .map_err(HavenErrorFfi::from)
+}
 
-    let entry = keyring_core::Entry::new(CIRCLES_DB_SERVICE, CIRCLES_DB_KEY_ID)
-        .map_err(|e| format!("Failed to create keyring entry for circles.db: {e}"))?;
+/// The opened contents of a device transfer bundle.
+///
+/// Call [`Self::install_session`] before creating the `CircleManagerFfi`,
+/// then [`CircleManagerFfi::adopt_transferred`].
+#[frb(opaque)]
+pub struct TransferContentsFfi {
+    inner: haven_core::device_transfer::TransferContents,
+}
 
-    let key = match entry.get_secret() {
-        Ok(secret_bytes) => {
-            // Key exists — wrap in Zeroizing to ensure zeroing on drop, then hex-encode
-            let secret_bytes = zeroize::Zeroizing::new(secret_bytes);
-            zeroize::Zeroizing::new(hex::encode(&*secret_bytes))
-        }
-        Err(keyring_core::Error::NoEntry) => {
-            // Key doesn't exist — generate and store a new one
-            let mut key_bytes = zeroize::Zeroizing::new([0u8; 32]);
-            rand::rngs::OsRng.fill_bytes(key_bytes.as_mut());
+impl TransferContentsFfi {
+    /// The transferred identity's public key (hex).
+    #[frb(sync)]
+    #[must_use]
+    pub fn pubkey_hex(&self) -> String {
+        normalize_pubkey_hex(&self.inner.pubkey_hex)
+    }
 
-            entry
-                .set_secret(key_bytes.as_ref())
-                .map_err(|e| format!("Failed to store circles.db key in keyring: {e}"))?;
+    /// Whether the bundle carries the MLS state. Without it every circle
+    /// needs a rejoin.
+    #[frb(sync)]
+    #[must_use]
+    pub fn carries_state(&self) -> bool {
+        self.inner.carries_state()
+    }
 
-            zeroize::Zeroizing::new(hex::encode(key_bytes.as_ref()))
-        }
-        Err(keyring_core::Error::NoStorageAccess(err)) => {
-            return Err(format!("Keyring not accessible for circles.db key: {err}"));
-        }
-        Err(e) => return Err(format!("Failed to retrieve circles.db key: {e}")),
-    };
+    /// The old device's circles.
+    #[frb(sync)]
+    #[must_use]
+    pub fn circles(&self) -> Vec<LinkedCircleFfi> {
+        self.inner
+            .circles
+            .iter()
+            .map(|c| LinkedCircleFfi::from(c.circle.clone()))
+            .collect()
+    }
 
-    // On iOS, migrate the circles.db key (born `WhenUnlocked`) to
-    // `AfterFirstUnlockThisDeviceOnly` so a locked-device background wake can
-    // open the database. No-op on every other target. Non-fatal: the migration
-    // restores the key on any failure, so we log a redacted warning and return
-    // the (unchanged) key.
-    if let Err(e) = haven_core::keyring_policy::ensure_db_key_after_first_unlock(
-        CIRCLES_DB_SERVICE,
-        CIRCLES_DB_KEY_ID,
-    ) {
-        log::warn!("circles.db key access-policy migration deferred: {e}");
+    /// Installs the carried MLS state into `data_dir`. Returns `false` when
+    /// the bundle carries none.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `data_dir` already holds MLS state or the state
+    /// cannot be stored.
+    pub fn install_session(&self, data_dir: String) -> Result<bool, HavenErrorFfi> {
+        self.inner
+            .install_session(std::path::Path::new(&data_dir))
+            .map_err(HavenErrorFfi::from)
     }
+}
 
-    Ok(key)
+/// The outcome of restoring a device transfer (FFI mirror of
+/// [`haven_core::device_transfer::TransferRestoreReport`]).
+#[derive(Debug, Clone)]
+pub struct TransferRestoreFfi {
+    /// Circles restored with their state.
+    pub adopted: Vec<CircleWithMembersFfi>,
+    /// Circles to ask an admin to rejoin.
+    pub needs_rejoin: Vec<LinkedCircleFfi>,
 }
 
-/// Removes one keyring entry, distinguishing "already gone" from a genuine
-/// failure.
+// ============================================================================
+// Encrypted Event Types (FFI wrappers for Nostr event generation)
+// ============================================================================
+
+/// Unsigned location event (FFI wrapper for inner event kind 9).
 ///
-/// A missing entry (`NoEntry`) — or no store installed at all
-/// (`NoDefaultStore`) — is treated as success: there is no key left at rest, so
-/// the wipe is idempotent. Any OTHER error (a locked / unavailable Secret
-/// Service, a platform failure) is a GENUINE failure: the encrypted DB key may
-/// still live at rest, so it is propagated to the caller. The returned message
-/// is generic/opaque — it never carries the service, key id, or the raw backend
-/// error (Security: no secret/path leak).
-fn remove_keyring_key(service: &str, key_id: &str) -> Result<(), String> {
-    let entry = match keyring_core::Entry::new(service, key_id) {
-        Ok(entry) => entry,
-        // No store installed / no matching entry ⇒ nothing persisted for us to
-        // leave at rest ⇒ already-clean slate (idempotent success).
-        Err(keyring_core::Error::NoDefaultStore | keyring_core::Error::NoEntry) => return Ok(()),
-        Err(_) => return Err("failed to remove a keyring key".to_string()),
-    };
-    match entry.delete_credential() {
-        // Deleted, or already absent — both leave nothing at rest.
-        Ok(()) | Err(keyring_core::Error::NoEntry) => Ok(()),
-        // A locked / unavailable Secret Service (PlatformFailure /
-        // NoStorageAccess / …) leaves the encrypted DB key at rest: surface it
-        // so the M10.1 logout retry keeps its durable marker and re-attempts.
-        Err(_) => Err("failed to remove a keyring key".to_string()),
+/// This is the inner event containing location data before encryption.
+/// It is wrapped in a kind 445 group message for transmission.
+#[derive(Clone)]
+pub struct UnsignedLocationEventFfi {
+    /// Event kind (9 for location data per MIP-03).
+    pub kind: u16,
+    /// JSON-serialized location data.
+    pub content: String,
+    /// Event tags (typically empty for inner events).
+    pub tags: Vec<Vec<String>>,
+    /// Unix timestamp when the event was created.
+    pub created_at: i64,
+}
+
+impl std::fmt::Debug for UnsignedLocationEventFfi {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("UnsignedLocationEventFfi")
+            .field("kind", &self.kind)
+            .field("content", &"<redacted>")
+            .field("tag_count", &self.tags.len())
+            .field("created_at", &self.created_at)
+            .finish()
     }
 }
 
-/// Removes the circles.db keyring entry. See [`remove_keyring_key`].
-///
-/// Used by the logout MLS-state wipe so a returning identity mints a fresh key
-/// rather than inheriting the prior one.
-fn remove_circles_db_key() -> Result<(), String> {
-    remove_keyring_key(CIRCLES_DB_SERVICE, CIRCLES_DB_KEY_ID)
+impl From<haven_core::nostr::UnsignedLocationEvent> for UnsignedLocationEventFfi {
+    fn from(e: haven_core::nostr::UnsignedLocationEvent) -> Self {
+        Self {
+            kind: e.kind,
+            content: e.content,
+            tags: e.tags,
+            created_at: e.created_at,
+        }
+    }
 }
 
-/// Removes the Dark Matter MLS session's DB keyring entry. See [`remove_keyring_key`].
+/// Signed location event (FFI wrapper for outer event kind 445).
 ///
-/// The core `haven_core::nostr::mls::storage` provisions this key; we never
-/// touch the core, only delete the keyring entry so the next identity
-/// re-provisions a fresh `session.sqlite` passphrase (wipe-on-logout).
-fn remove_mls_session_db_key() -> Result<(), String> {
-    remove_keyring_key(CIRCLES_DB_SERVICE, MLS_SESSION_DB_KEY_ID)
+/// This is the outer event ready for relay transmission.
+/// Contains encrypted content signed with an ephemeral keypair.
+#[derive(Debug, Clone)]
+pub struct SignedLocationEventFfi {
+    /// Event ID (SHA256 hash, 64 hex chars).
+    pub id: String,
+    /// Ephemeral public key (64 hex chars).
+    pub pubkey: String,
+    /// Unix timestamp when the event was created.
+    pub created_at: i64,
+    /// Event kind (445 for Marmot group messages).
+    pub kind: u16,
+    /// Event tags: `[["h", group_id], ["expiration", ts], ...]`.
+    pub tags: Vec<Vec<String>>,
+    /// NIP-44 encrypted content (base64).
+    pub content: String,
+    /// Schnorr signature (128 hex chars).
+    pub sig: String,
 }
 
-/// Deletes one file, distinguishing "already gone" from a genuine failure.
-///
-/// A missing file (`NotFound`) is success (idempotent wipe). Any OTHER error
-/// (file locked, permission denied, path is a directory) is a GENUINE failure:
-/// an encrypted DB may still be readable at rest, so it is propagated. The
-/// message is generic/opaque — never the path or the raw OS error (Security).
-fn remove_file_strict(path: &std::path::Path) -> Result<(), String> {
-    match std::fs::remove_file(path) {
-        Ok(()) => Ok(()),
-        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(()),
-        Err(_) => Err("failed to delete a database file".to_string()),
+impl From<haven_core::nostr::SignedLocationEvent> for SignedLocationEventFfi {
+    fn from(e: haven_core::nostr::SignedLocationEvent) -> Self {
+        Self {
+            id: e.id,
+            pubkey: e.pubkey,
+            created_at: e.created_at,
+            kind: e.kind,
+            tags: e.tags,
+            content: e.content,
+            sig: e.sig,
+        }
     }
 }
 
-/// Best-effort deletion of a `SQLCipher` DB file and its WAL/SHM/journal
-/// sidecars under `data_dir`.
-///
-/// The `-wal`/`-shm` sidecars hold `SQLCipher`-encrypted pages not yet
-/// checkpointed into the main file, and `-journal` a transient rollback
-/// journal; all MUST be deleted alongside the base file so no MLS state or
-/// circle metadata lingers at rest. Missing files are not an error.
+// ============================================================================
+// Location Event Service
+// ============================================================================
+
+/// Service for creating and verifying Nostr location events.
 ///
-/// EVERY sidecar is attempted even if an earlier one fails, so a single locked
-/// file never strands the others; a genuine (non-`NotFound`) failure on ANY of
-/// them is reported so the caller can surface it (M10.1 retries the wipe).
-fn delete_db_files(data_dir: &str, filename: &str) -> Result<(), String> {
-    let base = std::path::Path::new(data_dir).join(filename);
-    let mut failed = false;
-    for suffix in ["", "-wal", "-shm", "-journal"] {
-        let path = if suffix.is_empty() {
-            base.clone()
-        } else {
-            let mut s = base.clone().into_os_string();
-            s.push(suffix);
-            std::path::PathBuf::from(s)
+/// Provides methods for creating unsigned Nostr events from location data
+/// and verifying signatures on signed events.
+#[derive(Debug, Default)]
+#[frb(opaque)]
+pub struct LocationEventService {
+    _private: (),
+}
+
+impl LocationEventService {
+    /// Creates a new `LocationEventService`.
+    #[frb(sync)]
+    #[must_use]
+    pub fn new() -> Self {
+        Self { _private: () }
+    }
+
+    /// Creates an unsigned location event (kind 9 per MIP-03).
+    ///
+    /// This is the inner event that gets encrypted before being wrapped
+    /// in a kind 445 group message.
+    #[frb(sync)]
+    pub fn create_unsigned_event(
+        &self,
+        location: &LocationMessage,
+    ) -> Result<UnsignedLocationEventFfi, HavenErrorFfi> {
+        haven_core::nostr::UnsignedLocationEvent::from_location(&location.inner)
+            .map(Into::into)
+            .map_err(HavenErrorFfi::from)
+    }
+
+    /// Verifies the signature of a signed event.
+    ///
+    /// Returns `true` if the signature is valid, `false` otherwise.
+    #[frb(sync)]
+    pub fn verify_signature(&self, event: &SignedLocationEventFfi) -> Result<bool, HavenErrorFfi> {
+        let core_event = haven_core::nostr::SignedLocationEvent {
+            id: event.id.clone(),
+            pubkey: event.pubkey.clone(),
+            created_at: event.created_at,
+            kind: event.kind,
+            tags: event.tags.clone(),
+            content: event.content.clone(),
+            sig: event.sig.clone(),
         };
-        if remove_file_strict(&path).is_err() {
-            failed = true;
+
+        match core_event.verify_signature() {
+            Ok(()) => Ok(true),
+            Err(_) => Ok(false),
         }
     }
-    if failed {
-        Err("failed to delete a database file".to_string())
-    } else {
-        Ok(())
+
+    /// Returns a jittered publish interval in seconds using the Rust-side
+    /// CSPRNG (`OsRng`), sampled uniformly in
+    /// `[nominal_secs * 0.6, nominal_secs * 1.4]` (40% spread).
+    ///
+    /// Callers pass the nominal interval (typically 120 s) and rearm their
+    /// timer with the returned value. This is NOT the TTL window — the
+    /// `expiration` tag sampled inside `encrypt_location` is independent
+    /// and intentionally decoupled (see `haven-core/SECURITY.md`).
+    ///
+    /// On `nominal_secs == 0` the call falls back to `nominal_secs` rather
+    /// than panicking, so a zero at the boundary does not silently
+    /// reschedule at 0 s. Dart callers should pass a positive nominal.
+    #[frb(sync)]
+    #[must_use]
+    pub fn jittered_publish_interval_secs(&self, nominal_secs: u64) -> u64 {
+        haven_core::location::compute_jittered_publish_interval_secs(
+            nominal_secs,
+            haven_core::location::PUBLISH_INTERVAL_JITTER_FRACTION_BP,
+        )
+        .unwrap_or(nominal_secs)
     }
 }
 
-/// Deletes `circles.db` (+ sidecars) under `data_dir`. See [`delete_db_files`].
-fn delete_circles_db_files(data_dir: &str) -> Result<(), String> {
-    delete_db_files(data_dir, CIRCLES_DB_FILENAME)
-}
+// ============================================================================
+// Platform Initialization
+// ============================================================================
 
-/// Deletes `session.sqlite` (+ sidecars) under `data_dir`. See [`delete_db_files`].
-fn delete_mls_session_db_files(data_dir: &str) -> Result<(), String> {
-    delete_db_files(data_dir, MLS_SESSION_DB_FILENAME)
-}
+use std::sync::Mutex;
 
-/// Deletes the PRE-Dark-Matter `haven_mdk.db` (+ sidecars) under `data_dir` —
-/// the first-launch cutover cleanup. See [`delete_db_files`].
-fn delete_legacy_mls_db_files(data_dir: &str) -> Result<(), String> {
-    delete_db_files(data_dir, LEGACY_MLS_DB_FILENAME)
-}
+/// Guard for keyring store initialization. Only caches success so that transient
+/// failures (e.g., iOS data protection not yet unlocked after reboot) can be
+/// retried on the next call.
+static KEYRING_INIT: Mutex<Option<()>> = Mutex::new(None);
 
-/// Wipes ALL local NEW-STACK MLS state on logout: deletes both encrypted
-/// databases' files (`circles.db` and the Dark Matter `session.sqlite`, plus
-/// every WAL/SHM/journal sidecar) and then removes both keyring keys.
-///
-/// This is the highest-severity teardown in the logout path: it guarantees a
-/// returning (or different) identity never inherits the prior identity's MLS
-/// group state, circle metadata, dedup cache, sync cursors, or DB keys. It
-/// targets the NEW Dark Matter stack (`session.sqlite` +
-/// `mls.session.key.default`); the PRE-Dark-Matter legacy DB/key are handled
-/// separately by the one-time first-launch cutover ([`destroy_legacy_mls_state`]).
-///
-/// # Ordering and safety
+/// Initializes the platform-specific keyring credential store.
 ///
-/// The Dart caller MUST have dropped its `CircleManagerFfi` handle (so the last
-/// `Arc<CoreCircleManager>` is gone and both SQLite connections have closed)
-/// and stopped any live subscriptions BEFORE calling this — that is the Flutter
-/// layer's responsibility. No Rust global holds the manager, so there is
-/// nothing for this function to `.take()`. (Rule 14: at most one live session
-/// per DB file — the handle drop closes it.)
+/// Must be called **once** before any `CircleManagerFfi` operations. The keyring
+/// store is used by MDK to securely store the `SQLCipher` database encryption key
+/// in the platform's native credential store (Keychain, Keystore, etc.).
 ///
-/// This function is **idempotent**: deleting an already-gone file or key is not
-/// an error, so a partial prior wipe or a double-call both converge to "nothing
-/// left" and return `Ok(())` — the M10.1 launch-retry relies on this to avoid an
-/// infinite loop. It relies on POSIX unlink semantics rather than GC timing —
-/// should a file descriptor still be briefly open (e.g. an in-flight blocking
-/// call that cloned the `Arc` just before the handle drop), `remove_file`
-/// unlinks the path immediately and the kernel reclaims the inode once the last
-/// descriptor closes; no new at-rest data is written to a file we are unlinking
-/// under a key we are removing.
+/// This function is idempotent: once initialization succeeds, subsequent calls
+/// return `Ok(())` immediately. If initialization fails, the next call will
+/// retry rather than returning a cached error.
 ///
 /// # Errors
 ///
-/// Returns `Err` if ANY teardown step hit a GENUINE failure (a file locked /
-/// permission-denied / not-a-file, or a locked / unavailable keyring) — as
-/// opposed to "already gone", which is success. Surfacing a genuine failure is
-/// load-bearing: it tells the Dart M10.1 logout to KEEP its durable retry
-/// marker and re-attempt on the next launch, instead of clearing it and leaving
-/// a decryptable `circles.db` / `session.sqlite` + keyring key at rest. The
-/// error string is generic/opaque — no path, key id, or backend detail (Security).
-pub async fn wipe_all_mls_state(data_dir: String) -> Result<(), String> {
-    run_blocking(move || {
-        // Delete DB files first (POSIX-safe even if a descriptor briefly
-        // outlives the handle drop), THEN remove the keyring keys so a fresh
-        // open after this mints new keys against a clean on-disk slate.
-        //
-        // Attempt EVERY step even if an earlier one fails, so a single locked
-        // file / unavailable keyring never strands the rest; surface a genuine
-        // failure only (an "already gone" step returns Ok and does not set the
-        // flag, preserving idempotency for the launch-retry).
-        let mut failed = false;
-        failed |= delete_circles_db_files(&data_dir).is_err();
-        failed |= delete_mls_session_db_files(&data_dir).is_err();
-        failed |= remove_circles_db_key().is_err();
-        failed |= remove_mls_session_db_key().is_err();
-        if failed {
-            Err("failed to fully wipe local MLS state".to_string())
-        } else {
-            Ok(())
-        }
-    })
-    .await
+/// Returns an error string if the platform keyring store cannot be initialized
+/// (e.g., on Android when the JNI context has not been provided).
+pub fn init_keyring_store() -> Result<(), HavenErrorFfi> {
+    let mut guard = KEYRING_INIT
+        .lock()
+        .map_err(|e| HavenErrorFfi::internal(format!("Keyring lock poisoned: {e}")))?;
+    if guard.is_some() {
+        return Ok(());
+    }
+    platform_init_keyring()
+        .map_err(|e| HavenErrorFfi::storage(format!("Keyring initialization failed: {e}")))?;
+    *guard = Some(());
+    Ok(())
 }
 
-/// First-launch cutover hook (Dark Matter §6 step 2 / security F6): destroys ALL
-/// PRE-Dark-Matter MLS state so the new stack starts on a clean slate and the
-/// old key material is gone.
+/// Installs an in-memory keyring backend for E2E tests.
 ///
-/// Deletes the legacy `haven_mdk.db` (+ WAL/SHM/journal sidecars) AND destroys
-/// the legacy keyring entry `mdk.db.key.default` (via the core helper). The old
-/// DB was NOT written with `secure_delete` and flash wear-leveling can leave
-/// residual ciphertext, so key destruction is the practical secure-erase for
-/// the abandoned SQLCipher DB (F6). Distinct from [`wipe_all_mls_state`]: THIS
-/// destroys LEGACY state (a one-time migration), that wipes NEW-stack state on
-/// logout.
+/// Intended exclusively for hermetic test harnesses on CI runners that lack
+/// a platform credential store (e.g., Linux runners with no D-Bus Secret
+/// Service). The backing storage is process-local and dropped when the
+/// process exits — secrets never touch disk.
 ///
-/// Idempotent and fail-soft on "already gone"; the Dart cutover guard should
-/// call this ONCE on the first launch of the Dark Matter build (before opening
-/// the new `CircleManagerFfi`), keyed off a persisted one-time flag.
+/// Must be called **before** [`init_keyring_store`]. If a platform backend
+/// has already been installed (`KEYRING_INIT.is_some()`), this call is a
+/// no-op so test setup can be defensive about ordering.
+///
+/// Idempotent: calling twice returns `Ok(())` on the second call.
 ///
 /// # Errors
 ///
-/// Returns `Err` (generic/opaque) if deleting a legacy DB file or destroying the
-/// legacy keyring key hit a GENUINE failure (locked file / unavailable keyring),
-/// so the Dart guard KEEPS its "cutover pending" flag and retries next launch.
-pub async fn destroy_legacy_mls_state(data_dir: String) -> Result<(), String> {
-    run_blocking(move || {
-        let mut failed = false;
-        failed |= delete_legacy_mls_db_files(&data_dir).is_err();
-        // Key destruction is the practical secure-erase for the abandoned DB.
-        failed |= haven_core::nostr::mls::storage::destroy_legacy_mls_key_material().is_err();
-        if failed {
-            Err("failed to destroy legacy MLS state".to_string())
-        } else {
-            Ok(())
-        }
-    })
-    .await
+/// * Returns an error if the keyring init mutex is poisoned.
+/// * Returns an error if the in-memory store cannot be constructed
+///   (the upstream `keyring_core::mock::Store::new()` does not fail in
+///   practice, but the boundary is preserved for forward compatibility).
+/// * In release builds this function is unreachable; the sibling stub
+///   always returns an error.
+#[cfg(debug_assertions)]
+pub fn use_in_memory_keyring_for_test() -> Result<(), HavenErrorFfi> {
+    let mut guard = KEYRING_INIT
+        .lock()
+        .map_err(|e| HavenErrorFfi::internal(format!("Keyring lock poisoned: {e}")))?;
+    if guard.is_some() {
+        // A backend (platform or in-memory) is already installed. Treat this
+        // as idempotent success so test setup that races init paths does
+        // not surface as a flaky failure.
+        return Ok(());
+    }
+    let store = crate::test_keyring::build_in_memory_store().map_err(HavenErrorFfi::storage)?;
+    keyring_core::set_default_store(store);
+    *guard = Some(());
+    Ok(())
 }
 
-/// Process-wide serialization for EVERY test that creates a `CircleManagerFfi`
-/// or otherwise touches the shared, fixed keyring key-ids (`circles.db.key` /
-/// `mdk.db.key.default`) — the M10 wipe end-to-end tests AND the
-/// `maintenance_real_ffi_tests` (tc1/tc2/tc3).
+/// Release-build stub for [`use_in_memory_keyring_for_test`].
 ///
-/// `CircleManagerFfi::new` provisions those fixed global key-ids, so a `tc*`
-/// create running concurrently with an M10 wipe could re-provision a key
-/// between the wipe and its "key absent" assertion, flaking it. A single shared
-/// lock across both test modules keeps their create→assert→wipe sequences from
-/// interleaving.
+/// Returns an error so release callers fail closed — the in-memory backend
+/// is physically unreachable in release builds (the `test_keyring` module is
+/// gated on `#[cfg(debug_assertions)]`).
 ///
-/// It is a `tokio::sync::Mutex` (not `std::sync`) so the async `tc*` tests can
-/// hold the guard across `.await` points without making their futures non-Send;
-/// the synchronous M10 tests take it via `blocking_lock` (they run under no
-/// ambient runtime, so that cannot panic).
-#[cfg(test)]
-static SHARED_KEYRING_TEST_LOCK: tokio::sync::Mutex<()> = tokio::sync::Mutex::const_new(());
+/// # Errors
+///
+/// Always returns an error.
+#[cfg(not(debug_assertions))]
+pub fn use_in_memory_keyring_for_test() -> Result<(), HavenErrorFfi> {
+    Err(HavenErrorFfi::unsupported(
+        "use_in_memory_keyring_for_test is disabled in release builds",
+    ))
+}
 
-// ============================================================================
-// Tile Cache (tiles.db) — Encrypted Map-Tile Cache (FFI)
-// ============================================================================
+#[cfg(not(any(
+    target_os = "macos",
+    target_os = "ios",
+    target_os = "linux",
+    target_os = "windows",
+    target_os = "android",
+)))]
+compile_error!("No keyring store implementation for this target OS");
 
-use haven_core::tiles::{TileCacheError, TileCacheStorage, TileEntry};
+/// Platform-specific keyring store initialization.
+//
+// `return` statements are needed inside cfg-gated branches because each
+// branch is mutually exclusive — without them clippy fires `needless_return`
+// only on whichever target is currently being compiled, which then varies
+// across CI runners. Allow the lint at the function level to keep the
+// branches symmetrical and platform-portable.
+#[allow(clippy::needless_return)]
+fn platform_init_keyring() -> Result<(), HavenErrorFfi> {
+    #[cfg(target_os = "macos")]
+    {
+        let store = apple_native_keyring_store::keychain::Store::new()
+            .map_err(|e| HavenErrorFfi::storage(format!("macOS keychain store: {e}")))?;
+        keyring_core::set_default_store(store);
+        return Ok(());
+    }
 
-/// Keyring service identifier for the tiles.db encryption key.
-const TILES_DB_SERVICE: &str = "com.oblivioustech.haven";
+    #[cfg(target_os = "ios")]
+    {
+        let store = apple_native_keyring_store::protected::Store::new()
+            .map_err(|e| HavenErrorFfi::storage(format!("iOS protected store: {e}")))?;
+        keyring_core::set_default_store(store);
+        return Ok(());
+    }
 
-/// Keyring key identifier for the tiles.db encryption key.
-const TILES_DB_KEY_ID: &str = "tiles.db.key";
+    #[cfg(target_os = "linux")]
+    {
+        let store = zbus_secret_service_keyring_store::Store::new()
+            .map_err(|e| HavenErrorFfi::storage(format!("Linux secret service store: {e}")))?;
+        keyring_core::set_default_store(store);
+        return Ok(());
+    }
 
-/// On-disk filename for the encrypted tile cache (with `-wal`/`-shm` sidecars).
-const TILES_DB_FILENAME: &str = "tiles.db";
+    #[cfg(target_os = "windows")]
+    {
+        let store = windows_native_keyring_store::Store::new()
+            .map_err(|e| HavenErrorFfi::storage(format!("Windows keyring store: {e}")))?;
+        keyring_core::set_default_store(store);
+        return Ok(());
+    }
 
-/// The live encrypted tile cache, shared across FFI calls.
-///
-/// `RwLock<Option<Arc<...>>>`: cloning the `Arc` under a read lock lets every
-/// tile call run concurrently against the same storage (which has its own
-/// internal read/write connection split). Set to `None` by [`tile_cache_wipe`]
-/// so the last `Arc` drop closes the connections.
-static TILE_CACHE: RwLock<Option<Arc<TileCacheStorage>>> = RwLock::new(None);
+    #[cfg(target_os = "android")]
+    {
+        let store = android_native_keyring_store::Store::from_ndk_context()
+            .map_err(|e| HavenErrorFfi::storage(format!("Android keyring store: {e}")))?;
+        keyring_core::set_default_store(store);
+        return Ok(());
+    }
+}
 
-/// Remembers the data directory passed to [`tile_cache_init`] so the wipe path
-/// can delete `tiles.db` + its `-wal`/`-shm` sidecars by absolute path.
-static TILE_CACHE_DIR: Mutex<Option<String>> = Mutex::new(None);
+// ============================================================================
+// Circles DB Encryption Key Management
+// ============================================================================
 
-/// Returns the current unix time in **milliseconds**.
-///
-/// Clamped to `i64`; a clock before the unix epoch yields a negative value,
-/// which is harmless for the cache's relative-age arithmetic.
-fn now_ms() -> i64 {
-    use std::time::{SystemTime, UNIX_EPOCH};
-    SystemTime::now()
-        .duration_since(UNIX_EPOCH)
-        .map_or(0, |d| i64::try_from(d.as_millis()).unwrap_or(i64::MAX))
-}
+/// Keyring service identifier for circles.db encryption.
+const CIRCLES_DB_SERVICE: &str = "com.oblivioustech.haven";
 
-/// Maps a [`TileCacheError`] to a generic boundary string.
-///
-/// The error types are already redaction-safe (their `Display` never carries
-/// coordinates, bytes, or key material), but this keeps the FFI surface uniform
-/// and ensures no tile `(z, x, y)` can ever reach Dart.
-fn tile_err_to_string(err: &TileCacheError) -> String {
-    err.to_string()
-}
+/// Keyring key identifier for the circles.db encryption key.
+const CIRCLES_DB_KEY_ID: &str = "circles.db.key";
 
-/// Retrieves or creates the tiles.db encryption key from the system keyring.
+/// On-disk filename for the encrypted circle-metadata DB (WAL mode →
+/// `-wal`/`-shm` sidecars; older installs may still leave a `-journal`).
+const CIRCLES_DB_FILENAME: &str = "circles.db";
+
+/// On-disk filename for the Dark Matter MLS-state DB (WAL mode →
+/// `-wal`/`-shm` sidecars). Mirrors
+/// [`haven_core::nostr::mls::StorageConfig::database_path`] (`session.sqlite`).
+const MLS_SESSION_DB_FILENAME: &str = "session.sqlite";
+
+/// Keyring key identifier for the Dark Matter MLS session passphrase. The
+/// service identifier is the shared [`CIRCLES_DB_SERVICE`]; only the key id
+/// differs. Mirrors the `MLS_DB_KEY_ID` constant in
+/// `haven_core::nostr::mls::storage`.
+const MLS_SESSION_DB_KEY_ID: &str = "mls.session.key.default";
+
+/// On-disk filename for the PRE-Dark-Matter MLS-state DB (retained only for the
+/// one-time first-launch cutover cleanup; see [`destroy_legacy_mls_state`]).
+const LEGACY_MLS_DB_FILENAME: &str = "haven_mdk.db";
+
+/// Retrieves or creates the circles.db encryption key from the system keyring.
 ///
-/// Near-copy of [`get_or_create_circle_db_key`]: on first call it generates a
-/// 256-bit `OsRng` key, stores the raw bytes in the platform keyring, and
-/// returns the hex-encoded key; subsequent calls retrieve and hex-encode it.
+/// On first call, generates a cryptographically random 256-bit key using `OsRng`,
+/// stores the raw bytes in the platform keyring, and returns the hex-encoded key.
+/// Subsequent calls retrieve and hex-encode the stored key.
 ///
 /// # Errors
 ///
-/// Returns an error string if the keyring cannot be accessed or key generation
-/// fails.
-fn get_or_create_tiles_db_key() -> Result<zeroize::Zeroizing<String>, String> {
+/// Returns an error string if the keyring cannot be accessed or if key
+/// generation fails.
+fn get_or_create_circle_db_key() -> Result<zeroize::Zeroizing<String>, HavenErrorFfi> {
     use rand::RngCore;
 
-    let entry = keyring_core::Entry::new(TILES_DB_SERVICE, TILES_DB_KEY_ID)
-        .map_err(|e| format!("Failed to create keyring entry for tiles.db: {e}"))?;
+    let entry = keyring_core::Entry::new(CIRCLES_DB_SERVICE, CIRCLES_DB_KEY_ID).map_err(|e| {
+        HavenErrorFfi::storage(format!(
+            "Failed to create keyring entry for circles.db: {e}"
+        ))
+    })?;
 
     let key = match entry.get_secret() {
         Ok(secret_bytes) => {
+            // Key exists — wrap in Zeroizing to ensure zeroing on drop, then hex-encode
             let secret_bytes = zeroize::Zeroizing::new(secret_bytes);
             zeroize::Zeroizing::new(hex::encode(&*secret_bytes))
         }
         Err(keyring_core::Error::NoEntry) => {
+            // Key doesn't exist — generate and store a new one
             let mut key_bytes = zeroize::Zeroizing::new([0u8; 32]);
             rand::rngs::OsRng.fill_bytes(key_bytes.as_mut());
 
-            entry
-                .set_secret(key_bytes.as_ref())
-                .map_err(|e| format!("Failed to store tiles.db key in keyring: {e}"))?;
+            entry.set_secret(key_bytes.as_ref()).map_err(|e| {
+                HavenErrorFfi::storage(format!("Failed to store circles.db key in keyring: {e}"))
+            })?;
 
             zeroize::Zeroizing::new(hex::encode(key_bytes.as_ref()))
         }
         Err(keyring_core::Error::NoStorageAccess(err)) => {
-            return Err(format!("Keyring not accessible for tiles.db key: {err}"));
+            return Err(HavenErrorFfi::storage(format!(
+                "Keyring not accessible for circles.db key: {err}"
+            )));
+        }
+        Err(e) => {
+            return Err(HavenErrorFfi::storage(format!(
+                "Failed to retrieve circles.db key: {e}"
+            )))
         }
-        Err(e) => return Err(format!("Failed to retrieve tiles.db key: {e}")),
     };
 
-    // On iOS, migrate the tiles.db key (born `WhenUnlocked`) to
+    // On iOS, migrate the circles.db key (born `WhenUnlocked`) to
     // `AfterFirstUnlockThisDeviceOnly` so a locked-device background wake can
-    // open the cache. No-op on every other target. Non-fatal: the migration
+    // open the database. No-op on every other target. Non-fatal: the migration
     // restores the key on any failure, so we log a redacted warning and return
     // the (unchanged) key.
-    if haven_core::keyring_policy::ensure_db_key_after_first_unlock(
-        TILES_DB_SERVICE,
-        TILES_DB_KEY_ID,
-    )
-    .is_err()
-    {
-        // Static message (no interpolation): a tile-layer log line must never
-        // embed a value (check_no_tile_cache_secrets). Non-fatal — the migration
-        // restored the key, so the cache stays fully functional.
-        log::warn!("tiles.db key access-policy migration deferred (non-fatal)");
+    if let Err(e) = haven_core::keyring_policy::ensure_db_key_after_first_unlock(
+        CIRCLES_DB_SERVICE,
+        CIRCLES_DB_KEY_ID,
+    ) {
+        log::warn!("circles.db key access-policy migration deferred: {e}");
     }
 
     Ok(key)
 }
 
-/// Best-effort removal of the tiles.db keyring entry.
+/// Removes one keyring entry, distinguishing "already gone" from a genuine
+/// failure.
 ///
-/// Ignores `NoEntry` (already gone). Used by the disposable-cache recovery and
-/// the logout wipe.
-fn remove_tiles_db_key() {
-    if let Ok(entry) = keyring_core::Entry::new(TILES_DB_SERVICE, TILES_DB_KEY_ID) {
-        let _ = entry.delete_credential();
+/// A missing entry (`NoEntry`) — or no store installed at all
+/// (`NoDefaultStore`) — is treated as success: there is no key left at rest, so
+/// the wipe is idempotent. Any OTHER error (a locked / unavailable Secret
+/// Service, a platform failure) is a GENUINE failure: the encrypted DB key may
+/// still live at rest, so it is propagated to the caller. The returned message
+/// is generic/opaque — it never carries the service, key id, or the raw backend
+/// error (Security: no secret/path leak).
+fn remove_keyring_key(service: &str, key_id: &str) -> Result<(), HavenErrorFfi> {
+    let entry = match keyring_core::Entry::new(service, key_id) {
+        Ok(entry) => entry,
+        // No store installed / no matching entry ⇒ nothing persisted for us to
+        // leave at rest ⇒ already-clean slate (idempotent success).
+        Err(keyring_core::Error::NoDefaultStore | keyring_core::Error::NoEntry) => return Ok(()),
+        Err(_) => return Err(HavenErrorFfi::storage("failed to remove a keyring key")),
+    };
+    match entry.delete_credential() {
+        // Deleted, or already absent — both leave nothing at rest.
+        Ok(()) | Err(keyring_core::Error::NoEntry) => Ok(()),
+        // A locked / unavailable Secret Service (PlatformFailure /
+        // NoStorageAccess / …) leaves the encrypted DB key at rest: surface it
+        // so the M10.1 logout retry keeps its durable marker and re-attempts.
+        Err(_) => Err(HavenErrorFfi::storage("failed to remove a keyring key")),
     }
 }
 
-/// Best-effort deletion of `tiles.db` and its WAL/SHM/journal sidecars under
-/// `data_dir`.
+/// Removes the circles.db keyring entry. See [`remove_keyring_key`].
 ///
-/// The `-wal`/`-shm` sidecars hold `SQLCipher`-encrypted pages that have not yet
-/// been checkpointed into the main file, so they MUST be deleted alongside it on
-/// wipe — otherwise cached map areas could linger at rest. `-journal` is included
-/// for defense-in-depth: although WAL mode normally precludes a rollback journal,
-/// SQLite can transiently fall back to one (e.g. during VACUUM/checkpoint), so we
-/// delete it too. Missing files are not an error.
-fn delete_tile_db_files(data_dir: &str) {
-    let base = std::path::Path::new(data_dir).join(TILES_DB_FILENAME);
-    for suffix in ["", "-wal", "-shm", "-journal"] {
-        let path = if suffix.is_empty() {
-            base.clone()
-        } else {
-            let mut s = base.clone().into_os_string();
-            s.push(suffix);
-            std::path::PathBuf::from(s)
-        };
-        let _ = std::fs::remove_file(&path);
-    }
+/// Used by the logout MLS-state wipe so a returning identity mints a fresh key
+/// rather than inheriting the prior one.
+fn remove_circles_db_key() -> Result<(), HavenErrorFfi> {
+    remove_keyring_key(CIRCLES_DB_SERVICE, CIRCLES_DB_KEY_ID)
 }
 
-/// A cached tile and its conditional-revalidation metadata, for Dart.
+/// Removes the Dark Matter MLS session's DB keyring entry. See [`remove_keyring_key`].
 ///
-/// `bytes` is public map imagery (encrypted only at rest), so it is surfaced
-/// directly. No coordinates are carried, and there is intentionally no `Debug`
-/// impl that prints `bytes`.
-pub struct TileCacheEntryFfi {
-    /// Raw tile bytes (PNG).
-    pub bytes: Vec<u8>,
-    /// HTTP freshness deadline in unix milliseconds.
-    pub stale_at_ms: i64,
-    /// `Last-Modified` as unix milliseconds, if present.
-    pub last_modified_ms: Option<i64>,
-    /// `ETag` value, if present.
-    pub etag: Option<String>,
+/// The core `haven_core::nostr::mls::storage` provisions this key; we never
+/// touch the core, only delete the keyring entry so the next identity
+/// re-provisions a fresh `session.sqlite` passphrase (wipe-on-logout).
+fn remove_mls_session_db_key() -> Result<(), HavenErrorFfi> {
+    remove_keyring_key(CIRCLES_DB_SERVICE, MLS_SESSION_DB_KEY_ID)
 }
 
-impl From<TileEntry> for TileCacheEntryFfi {
-    fn from(e: TileEntry) -> Self {
-        Self {
-            bytes: e.bytes,
-            stale_at_ms: e.stale_at_ms,
-            last_modified_ms: e.last_modified_ms,
-            etag: e.etag,
-        }
+/// Deletes one file, distinguishing "already gone" from a genuine failure.
+///
+/// A missing file (`NotFound`) is success (idempotent wipe). Any OTHER error
+/// (file locked, permission denied, path is a directory) is a GENUINE failure:
+/// an encrypted DB may still be readable at rest, so it is propagated. The
+/// message is generic/opaque — never the path or the raw OS error (Security).
+fn remove_file_strict(path: &std::path::Path) -> Result<(), HavenErrorFfi> {
+    match std::fs::remove_file(path) {
+        Ok(()) => Ok(()),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(()),
+        Err(_) => Err(HavenErrorFfi::storage("failed to delete a database file")),
     }
 }
 
-/// Clones the live tile-cache `Arc` under a read lock.
+/// Best-effort deletion of a `SQLCipher` DB file and its WAL/SHM/journal
+/// sidecars under `data_dir`.
 ///
-/// # Errors
+/// The `-wal`/`-shm` sidecars hold `SQLCipher`-encrypted pages not yet
+/// checkpointed into the main file, and `-journal` a transient rollback
+/// journal; all MUST be deleted alongside the base file so no MLS state or
+/// circle metadata lingers at rest. Missing files are not an error.
 ///
-/// Returns an error string if the cache is not initialized or the lock is
-/// poisoned.
-fn current_cache() -> Result<Arc<TileCacheStorage>, String> {
-    let guard = TILE_CACHE
-        .read()
-        .map_err(|_| "tile cache lock poisoned".to_string())?;
-    guard
-        .as_ref()
-        .cloned()
-        .ok_or_else(|| "tile cache not initialized".to_string())
+/// EVERY sidecar is attempted even if an earlier one fails, so a single locked
+/// file never strands the others; a genuine (non-`NotFound`) failure on ANY of
+/// them is reported so the caller can surface it (M10.1 retries the wipe).
+fn delete_db_files(data_dir: &str, filename: &str) -> Result<(), HavenErrorFfi> {
+    let base = std::path::Path::new(data_dir).join(filename);
+    let mut failed = false;
+    for suffix in ["", "-wal", "-shm", "-journal"] {
+        let path = if suffix.is_empty() {
+            base.clone()
+        } else {
+            let mut s = base.clone().into_os_string();
+            s.push(suffix);
+            std::path::PathBuf::from(s)
+        };
+        if remove_file_strict(&path).is_err() {
+            failed = true;
+        }
+    }
+    if failed {
+        Err(HavenErrorFfi::storage("failed to delete a database file"))
+    } else {
+        Ok(())
+    }
 }
 
-/// Initializes the encrypted tile cache at `data_dir`/`tiles.db`.
-///
-/// Ensures the platform keyring is initialized, fetches/creates the tiles.db
-/// key, and opens the cache. On a decrypt failure or schema-version mismatch the
-/// cache is **disposable**: it is dropped and recreated (delete the DB files,
-/// remove the keyring entry, mint a fresh key, reopen) so the map is never
-/// blocked. Other errors are returned (Dart treats the cache as unavailable and
-/// falls back to live tiles).
-///
-/// This is a plain `pub fn`; `flutter_rust_bridge` dispatches it on a worker, so
-/// the one-time `SQLCipher` open at startup does not block the UI isolate.
-///
-/// # Errors
-///
-/// Returns an error string if the keyring is unavailable or the cache cannot be
-/// opened even after disposable recovery.
-pub fn tile_cache_init(data_dir: String) -> Result<(), String> {
-    init_keyring_store()?;
-
-    // Ensure the data directory exists. `tile_cache_init` runs from `main()`
-    // before the lazily-constructed `CircleManagerFfi` (which is the usual
-    // creator of this directory via `create_dir_all`), so on a fresh install
-    // the directory would not yet exist and `Connection::open` — which creates
-    // the DB file but NOT its parent dirs — would fail, silently disabling the
-    // cache for the whole first session. Create it here so init is self-
-    // sufficient regardless of call order.
-    std::fs::create_dir_all(&data_dir)
-        .map_err(|e| format!("Failed to create tile cache data dir: {e}"))?;
-
-    let path = std::path::Path::new(&data_dir).join(TILES_DB_FILENAME);
+/// Deletes `circles.db` (+ sidecars) under `data_dir`. See [`delete_db_files`].
+fn delete_circles_db_files(data_dir: &str) -> Result<(), HavenErrorFfi> {
+    delete_db_files(data_dir, CIRCLES_DB_FILENAME)
+}
 
-    let key = get_or_create_tiles_db_key()?;
-    let storage = match TileCacheStorage::open(&path, &key) {
-        Ok(s) => s,
-        Err(TileCacheError::DecryptFailed | TileCacheError::SchemaVersionMismatch) => {
-            // Disposable-cache recovery: the cache holds only re-fetchable public
-            // imagery, so on a corrupt/undecryptable/stale-schema DB we drop and
-            // recreate it rather than blocking the map.
-            log::warn!("tiles.db unreadable; recreating disposable tile cache");
-            drop(key);
-            delete_tile_db_files(&data_dir);
-            remove_tiles_db_key();
-            let fresh_key = get_or_create_tiles_db_key()?;
-            TileCacheStorage::open(&path, &fresh_key).map_err(|e| tile_err_to_string(&e))?
-        }
-        Err(e) => return Err(tile_err_to_string(&e)),
-    };
+/// Deletes `session.sqlite` (+ sidecars) under `data_dir`. See [`delete_db_files`].
+fn delete_mls_session_db_files(data_dir: &str) -> Result<(), HavenErrorFfi> {
+    delete_db_files(data_dir, MLS_SESSION_DB_FILENAME)
+}
 
-    {
-        let mut guard = TILE_CACHE
-            .write()
-            .map_err(|_| "tile cache lock poisoned".to_string())?;
-        *guard = Some(Arc::new(storage));
-    }
-    {
-        let mut dir = TILE_CACHE_DIR
-            .lock()
-            .map_err(|_| "tile cache dir lock poisoned".to_string())?;
-        *dir = Some(data_dir);
-    }
-    Ok(())
+/// Deletes the PRE-Dark-Matter `haven_mdk.db` (+ sidecars) under `data_dir` —
+/// the first-launch cutover cleanup. See [`delete_db_files`].
+fn delete_legacy_mls_db_files(data_dir: &str) -> Result<(), HavenErrorFfi> {
+    delete_db_files(data_dir, LEGACY_MLS_DB_FILENAME)
 }
 
-/// Returns the cached tile for `(style, z, x, y, retina)`, or `None`.
+/// Wipes ALL local NEW-STACK MLS state on logout: deletes both encrypted
+/// databases' files (`circles.db` and the Dark Matter `session.sqlite`, plus
+/// every WAL/SHM/journal sidecar) and then removes both keyring keys.
+///
+/// This is the highest-severity teardown in the logout path: it guarantees a
+/// returning (or different) identity never inherits the prior identity's MLS
+/// group state, circle metadata, dedup cache, sync cursors, or DB keys. It
+/// targets the NEW Dark Matter stack (`session.sqlite` +
+/// `mls.session.key.default`); the PRE-Dark-Matter legacy DB/key are handled
+/// separately by the one-time first-launch cutover ([`destroy_legacy_mls_state`]).
+///
+/// # Ordering and safety
+///
+/// The Dart caller MUST have dropped its `CircleManagerFfi` handle (so the last
+/// `Arc<CoreCircleManager>` is gone and both SQLite connections have closed)
+/// and stopped any live subscriptions BEFORE calling this — that is the Flutter
+/// layer's responsibility. No Rust global holds the manager, so there is
+/// nothing for this function to `.take()`. (Rule 14: at most one live session
+/// per DB file — the handle drop closes it.)
+///
+/// This function is **idempotent**: deleting an already-gone file or key is not
+/// an error, so a partial prior wipe or a double-call both converge to "nothing
+/// left" and return `Ok(())` — the M10.1 launch-retry relies on this to avoid an
+/// infinite loop. It relies on POSIX unlink semantics rather than GC timing —
+/// should a file descriptor still be briefly open (e.g. an in-flight blocking
+/// call that cloned the `Arc` just before the handle drop), `remove_file`
+/// unlinks the path immediately and the kernel reclaims the inode once the last
+/// descriptor closes; no new at-rest data is written to a file we are unlinking
+/// under a key we are removing.
 ///
 /// # Errors
 ///
-/// Returns an error string if the cache is uninitialized or the read fails.
-/// Coordinates are never included in the error.
-pub async fn tile_cache_get(
-    style: String,
-    z: i64,
-    x: i64,
-    y: i64,
-    retina: bool,
-) -> Result<Option<TileCacheEntryFfi>, String> {
-    let cache = current_cache()?;
+/// Returns `Err` if ANY teardown step hit a GENUINE failure (a file locked /
+/// permission-denied / not-a-file, or a locked / unavailable keyring) — as
+/// opposed to "already gone", which is success. Surfacing a genuine failure is
+/// load-bearing: it tells the Dart M10.1 logout to KEEP its durable retry
+/// marker and re-attempt on the next launch, instead of clearing it and leaving
+/// a decryptable `circles.db` / `session.sqlite` + keyring key at rest. The
+/// error string is generic/opaque — no path, key id, or backend detail (Security).
+pub async fn wipe_all_mls_state(data_dir: String) -> Result<(), HavenErrorFfi> {
     run_blocking(move || {
-        cache
-            .get(&style, z, x, y, retina, now_ms())
-            .map(|opt| opt.map(TileCacheEntryFfi::from))
-            .map_err(|e| tile_err_to_string(&e))
+        // Delete DB files first (POSIX-safe even if a descriptor briefly
+        // outlives the handle drop), THEN remove the keyring keys so a fresh
+        // open after this mints new keys against a clean on-disk slate.
+        //
+        // Attempt EVERY step even if an earlier one fails, so a single locked
+        // file / unavailable keyring never strands the rest; surface a genuine
+        // failure only (an "already gone" step returns Ok and does not set the
+        // flag, preserving idempotency for the launch-retry).
+        let mut failed = false;
+        failed |= delete_circles_db_files(&data_dir).is_err();
+        failed |= delete_mls_session_db_files(&data_dir).is_err();
+        failed |= remove_circles_db_key().is_err();
+        failed |= remove_mls_session_db_key().is_err();
+        if failed {
+            Err(HavenErrorFfi::storage(
+                "failed to fully wipe local MLS state",
+            ))
+        } else {
+            Ok(())
+        }
     })
     .await
 }
 
-/// Inserts or replaces a tile's bytes and metadata (a bytes-write).
+/// First-launch cutover hook (Dark Matter §6 step 2 / security F6): destroys ALL
+/// PRE-Dark-Matter MLS state so the new stack starts on a clean slate and the
+/// old key material is gone.
+///
+/// Deletes the legacy `haven_mdk.db` (+ WAL/SHM/journal sidecars) AND destroys
+/// the legacy keyring entry `mdk.db.key.default` (via the core helper). The old
+/// DB was NOT written with `secure_delete` and flash wear-leveling can leave
+/// residual ciphertext, so key destruction is the practical secure-erase for
+/// the abandoned SQLCipher DB (F6). Distinct from [`wipe_all_mls_state`]: THIS
+/// destroys LEGACY state (a one-time migration), that wipes NEW-stack state on
+/// logout.
+///
+/// Idempotent and fail-soft on "already gone"; the Dart cutover guard should
+/// call this ONCE on the first launch of the Dark Matter build (before opening
+/// the new `CircleManagerFfi`), keyed off a persisted one-time flag.
 ///
 /// # Errors
 ///
-/// Returns an error string if the cache is uninitialized or the write fails.
-#[allow(
-    clippy::too_many_arguments,
-    reason = "the tile key + payload is inherently wide; grouping would change the wire contract"
-)]
-pub async fn tile_cache_put(
-    style: String,
-    z: i64,
-    x: i64,
-    y: i64,
-    retina: bool,
-    bytes: Vec<u8>,
-    stale_at_ms: i64,
-    last_modified_ms: Option<i64>,
-    etag: Option<String>,
-) -> Result<(), String> {
-    let cache = current_cache()?;
+/// Returns `Err` (generic/opaque) if deleting a legacy DB file or destroying the
+/// legacy keyring key hit a GENUINE failure (locked file / unavailable keyring),
+/// so the Dart guard KEEPS its "cutover pending" flag and retries next launch.
+pub async fn destroy_legacy_mls_state(data_dir: String) -> Result<(), HavenErrorFfi> {
     run_blocking(move || {
-        cache
-            .put(
-                &style,
-                z,
-                x,
-                y,
-                retina,
-                &bytes,
-                stale_at_ms,
-                last_modified_ms,
-                etag.as_deref(),
-                now_ms(),
-            )
-            .map_err(|e| tile_err_to_string(&e))
+        let mut failed = false;
+        failed |= delete_legacy_mls_db_files(&data_dir).is_err();
+        // Key destruction is the practical secure-erase for the abandoned DB.
+        failed |= haven_core::nostr::mls::storage::destroy_legacy_mls_key_material().is_err();
+        if failed {
+            Err(HavenErrorFfi::storage("failed to destroy legacy MLS state"))
+        } else {
+            Ok(())
+        }
     })
     .await
 }
 
-/// Refreshes only a tile's conditional-revalidation metadata (the HTTP-304
-/// path); never touches the bytes or the `fetched_at` anchor.
+/// Process-wide serialization for EVERY test that creates a `CircleManagerFfi`
+/// or otherwise touches the shared, fixed keyring key-ids (`circles.db.key` /
+/// `mdk.db.key.default`) — the M10 wipe end-to-end tests AND the
+/// `maintenance_real_ffi_tests` (tc1/tc2/tc3).
 ///
-/// # Errors
+/// `CircleManagerFfi::new` provisions those fixed global key-ids, so a `tc*`
+/// create running concurrently with an M10 wipe could re-provision a key
+/// between the wipe and its "key absent" assertion, flaking it. A single shared
+/// lock across both test modules keeps their create→assert→wipe sequences from
+/// interleaving.
 ///
-/// Returns an error string if the cache is uninitialized or the update fails.
-#[allow(
-    clippy::too_many_arguments,
-    reason = "mirrors the tile key + metadata shape of `tile_cache_put`"
-)]
-pub async fn tile_cache_put_metadata(
-    style: String,
-    z: i64,
-    x: i64,
-    y: i64,
-    retina: bool,
-    stale_at_ms: i64,
-    last_modified_ms: Option<i64>,
-    etag: Option<String>,
-) -> Result<(), String> {
-    let cache = current_cache()?;
-    run_blocking(move || {
-        cache
-            .put_metadata(
-                &style,
-                z,
-                x,
-                y,
-                retina,
-                stale_at_ms,
-                last_modified_ms,
-                etag.as_deref(),
-                now_ms(),
-            )
-            .map_err(|e| tile_err_to_string(&e))
-    })
-    .await
-}
+/// It is a `tokio::sync::Mutex` (not `std::sync`) so the async `tc*` tests can
+/// hold the guard across `.await` points without making their futures non-Send;
+/// the synchronous M10 tests take it via `blocking_lock` (they run under no
+/// ambient runtime, so that cannot panic).
+#[cfg(test)]
+static SHARED_KEYRING_TEST_LOCK: tokio::sync::Mutex<()> = tokio::sync::Mutex::const_new(());
 
-/// Evicts stale, over-retention, and over-budget tiles in one transaction.
-///
-/// `idle_age_secs` and `max_retention_secs` are converted to milliseconds to
-/// match the storage layer's unix-millisecond clocks.
+// ============================================================================
+// Tile Cache (tiles.db) — Encrypted Map-Tile Cache (FFI)
+// ============================================================================
+
+use haven_core::tiles::{TileCacheError, TileCacheStorage, TileEntry};
+
+/// Keyring service identifier for the tiles.db encryption key.
+const TILES_DB_SERVICE: &str = "com.oblivioustech.haven";
+
+/// Keyring key identifier for the tiles.db encryption key.
+const TILES_DB_KEY_ID: &str = "tiles.db.key";
+
+/// On-disk filename for the encrypted tile cache (with `-wal`/`-shm` sidecars).
+const TILES_DB_FILENAME: &str = "tiles.db";
+
+/// The live encrypted tile cache, shared across FFI calls.
 ///
-/// Returns the number of rows deleted.
+/// `RwLock<Option<Arc<...>>>`: cloning the `Arc` under a read lock lets every
+/// tile call run concurrently against the same storage (which has its own
+/// internal read/write connection split). Set to `None` by [`tile_cache_wipe`]
+/// so the last `Arc` drop closes the connections.
+static TILE_CACHE: RwLock<Option<Arc<TileCacheStorage>>> = RwLock::new(None);
+
+/// Remembers the data directory passed to [`tile_cache_init`] so the wipe path
+/// can delete `tiles.db` + its `-wal`/`-shm` sidecars by absolute path.
+static TILE_CACHE_DIR: Mutex<Option<String>> = Mutex::new(None);
+
+/// Returns the current unix time in **milliseconds**.
 ///
-/// # Errors
+/// Clamped to `i64`; a clock before the unix epoch yields a negative value,
+/// which is harmless for the cache's relative-age arithmetic.
+fn now_ms() -> i64 {
+    use std::time::{SystemTime, UNIX_EPOCH};
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |d| i64::try_from(d.as_millis()).unwrap_or(i64::MAX))
+}
+
+/// Maps a [`TileCacheError`] to the FFI error.
 ///
-/// Returns an error string if the cache is uninitialized or the eviction fails.
-pub async fn tile_cache_evict(
-    max_bytes: i64,
-    idle_age_secs: i64,
-    max_retention_secs: i64,
-) -> Result<u64, String> {
-    let cache = current_cache()?;
-    let idle_age_ms = idle_age_secs.saturating_mul(1000);
-    let max_retention_ms = max_retention_secs.saturating_mul(1000);
-    run_blocking(move || {
-        cache
-            .evict(max_bytes, idle_age_ms, max_retention_ms, now_ms())
-            .map_err(|e| tile_err_to_string(&e))
-    })
-    .await
+/// The error types are already redaction-safe (their `Display` never carries
+/// coordinates, bytes, or key material), but this keeps the FFI surface uniform
+/// and ensures no tile `(z, x, y)` can ever reach Dart.
+fn tile_err_to_ffi(err: TileCacheError) -> HavenErrorFfi {
+    err.into()
 }
 
-/// Wipes the encrypted tile cache (logout path).
+/// Retrieves or creates the tiles.db encryption key from the system keyring.
 ///
-/// Best-effort clears the content, drops the live `Arc` (closing the
-/// connections once the last reference is gone), deletes `tiles.db` + its
-/// `-wal`/`-shm` sidecars, and removes the tiles keyring entry. Already-gone
-/// files are not an error: a new identity must never inherit the prior
-/// identity's cached map areas.
+/// Near-copy of [`get_or_create_circle_db_key`]: on first call it generates a
+/// 256-bit `OsRng` key, stores the raw bytes in the platform keyring, and
+/// returns the hex-encoded key; subsequent calls retrieve and hex-encode it.
 ///
 /// # Errors
 ///
-/// Returns an error string only if an internal lock is poisoned.
-pub async fn tile_cache_wipe() -> Result<(), String> {
-    // Best-effort content clear while the cache is still live.
-    if let Ok(cache) = current_cache() {
-        let _ = run_blocking(move || cache.clear().map_err(|e| tile_err_to_string(&e))).await;
-    }
-
-    // Drop the live Arc so the connections close once the last ref is gone.
-    //
-    // Race note (SF-2): a `tile_cache_get/put` that called `current_cache()`
-    // just before this `take` holds its own Arc clone, so its connection
-    // briefly outlives the wipe. This is POSIX-safe — `remove_file` unlinks but
-    // defers reclamation until the last fd closes, and that in-flight call
-    // writes no *new* at-rest data (a `get` is read-only; a racing `put` lands
-    // in a file we are unlinking, under a key we are about to remove). The M-C
-    // logout path must still cancel prefetch/tile traffic before calling this
-    // so no such race is in flight in practice.
-    {
-        let mut guard = TILE_CACHE
-            .write()
-            .map_err(|_| "tile cache lock poisoned".to_string())?;
-        *guard = None;
-    }
+/// Returns an error string if the keyring cannot be accessed or key generation
+/// fails.
+fn get_or_create_tiles_db_key() -> Result<zeroize::Zeroizing<String>, HavenErrorFfi> {
+    use rand::RngCore;
 
-    // Delete the DB files (+ sidecars) using the remembered data dir.
-    let data_dir = {
-        let dir = TILE_CACHE_DIR
-            .lock()
-            .map_err(|_| "tile cache dir lock poisoned".to_string())?;
-        dir.clone()
-    };
-    if let Some(dir) = data_dir {
-        delete_tile_db_files(&dir);
-    }
+    let entry = keyring_core::Entry::new(TILES_DB_SERVICE, TILES_DB_KEY_ID).map_err(|e| {
+        HavenErrorFfi::storage(format!("Failed to create keyring entry for tiles.db: {e}"))
+    })?;
 
-    // Remove the keyring entry so a fresh cache mints a fresh key.
-    remove_tiles_db_key();
-    Ok(())
-}
+    let key = match entry.get_secret() {
+        Ok(secret_bytes) => {
+            let secret_bytes = zeroize::Zeroizing::new(secret_bytes);
+            zeroize::Zeroizing::new(hex::encode(&*secret_bytes))
+        }
+        Err(keyring_core::Error::NoEntry) => {
+            let mut key_bytes = zeroize::Zeroizing::new([0u8; 32]);
+            rand::rngs::OsRng.fill_bytes(key_bytes.as_mut());
 
-// ============================================================================
-// Circle Management (FFI)
-// ============================================================================
+            entry.set_secret(key_bytes.as_ref()).map_err(|e| {
+                HavenErrorFfi::storage(format!("Failed to store tiles.db key in keyring: {e}"))
+            })?;
 
-use std::path::Path;
+            zeroize::Zeroizing::new(hex::encode(key_bytes.as_ref()))
+        }
+        Err(keyring_core::Error::NoStorageAccess(err)) => {
+            return Err(HavenErrorFfi::storage(format!(
+                "Keyring not accessible for tiles.db key: {err}"
+            )));
+        }
+        Err(e) => {
+            return Err(HavenErrorFfi::storage(format!(
+                "Failed to retrieve tiles.db key: {e}"
+            )))
+        }
+    };
 
-use haven_core::circle::{
-    Circle as CoreCircle, CircleConfig as CoreCircleConfig, CircleManager as CoreCircleManager,
-    CircleMember as CoreCircleMember, CircleType as CoreCircleType,
-    CircleWithMembers as CoreCircleWithMembers, Contact as CoreContact,
-    Invitation as CoreInvitation,
-};
-use haven_core::nostr::mls::types::{GroupId, GroupIdExt, PendingStateRef};
+    // On iOS, migrate the tiles.db key (born `WhenUnlocked`) to
+    // `AfterFirstUnlockThisDeviceOnly` so a locked-device background wake can
+    // open the cache. No-op on every other target. Non-fatal: the migration
+    // restores the key on any failure, so we log a redacted warning and return
+    // the (unchanged) key.
+    if haven_core::keyring_policy::ensure_db_key_after_first_unlock(
+        TILES_DB_SERVICE,
+        TILES_DB_KEY_ID,
+    )
+    .is_err()
+    {
+        // Static message (no interpolation): a tile-layer log line must never
+        // embed a value (check_no_tile_cache_secrets). Non-fatal — the migration
+        // restored the key, so the cache stays fully functional.
+        log::warn!("tiles.db key access-policy migration deferred (non-fatal)");
+    }
 
-/// Circle information (FFI-friendly).
-///
-/// Represents a location sharing circle (group of people).
-#[derive(Clone)]
-pub struct CircleFfi {
-    /// MLS group ID (opaque bytes, used for API calls).
-    pub mls_group_id: Vec<u8>,
-    /// Nostr group ID (32 bytes, used in h-tags for relay routing).
-    pub nostr_group_id: Vec<u8>,
-    /// User-facing display name (local only).
-    pub display_name: String,
-    /// Circle type: "location_sharing" or "direct_share".
-    pub circle_type: String,
-    /// Relay URLs for this circle's messages.
-    pub relays: Vec<String>,
-    /// When the circle was created (Unix timestamp).
-    pub created_at: i64,
-    /// When the circle was last updated (Unix timestamp).
-    pub updated_at: i64,
+    Ok(key)
 }
 
-impl std::fmt::Debug for CircleFfi {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        f.debug_struct("CircleFfi")
-            .field("mls_group_id", &"<redacted>")
-            .field(
-                "nostr_group_id",
-                &self
-                    .nostr_group_id
-                    .iter()
-                    .map(|b| format!("{b:02x}"))
-                    .collect::<String>(),
-            )
-            .field("display_name", &self.display_name)
-            .field("circle_type", &self.circle_type)
-            .field("relays", &self.relays)
-            .field("created_at", &self.created_at)
-            .field("updated_at", &self.updated_at)
-            .finish()
+/// Best-effort removal of the tiles.db keyring entry.
+///
+/// Ignores `NoEntry` (already gone). Used by the disposable-cache recovery and
+/// the logout wipe.
+fn remove_tiles_db_key() {
+    if let Ok(entry) = keyring_core::Entry::new(TILES_DB_SERVICE, TILES_DB_KEY_ID) {
+        let _ = entry.delete_credential();
     }
 }
 
-impl From<&CoreCircle> for CircleFfi {
-    fn from(c: &CoreCircle) -> Self {
-        Self {
-            mls_group_id: c.mls_group_id.as_slice().to_vec(),
-            nostr_group_id: c.nostr_group_id.to_vec(),
-            display_name: c.display_name.clone(),
-            circle_type: c.circle_type.as_str().to_string(),
-            relays: c.relays.clone(),
-            created_at: c.created_at,
-            updated_at: c.updated_at,
-        }
+/// Best-effort deletion of `tiles.db` and its WAL/SHM/journal sidecars under
+/// `data_dir`.
+///
+/// The `-wal`/`-shm` sidecars hold `SQLCipher`-encrypted pages that have not yet
+/// been checkpointed into the main file, so they MUST be deleted alongside it on
+/// wipe — otherwise cached map areas could linger at rest. `-journal` is included
+/// for defense-in-depth: although WAL mode normally precludes a rollback journal,
+/// SQLite can transiently fall back to one (e.g. during VACUUM/checkpoint), so we
+/// delete it too. Missing files are not an error.
+fn delete_tile_db_files(data_dir: &str) {
+    let base = std::path::Path::new(data_dir).join(TILES_DB_FILENAME);
+    for suffix in ["", "-wal", "-shm", "-journal"] {
+        let path = if suffix.is_empty() {
+            base.clone()
+        } else {
+            let mut s = base.clone().into_os_string();
+            s.push(suffix);
+            std::path::PathBuf::from(s)
+        };
+        let _ = std::fs::remove_file(&path);
     }
 }
 
-/// Local contact information (FFI-friendly).
+/// A cached tile and its conditional-revalidation metadata, for Dart.
 ///
-/// **Privacy Note**: This data is stored only on the user's device,
-/// never synced to Nostr relays. Each user assigns their own names
-/// and avatars to contacts (like phone contacts).
-#[derive(Clone)]
-pub struct ContactFfi {
-    /// Nostr public key (hex) - the ONLY identifier visible to relays.
-    pub pubkey: String,
-    /// Locally assigned display name.
-    pub display_name: Option<String>,
-    /// Optional notes about this contact.
-    pub notes: Option<String>,
-    /// When this contact was created (Unix timestamp).
-    pub created_at: i64,
-    /// When this contact was last updated (Unix timestamp).
-    pub updated_at: i64,
-}
-
-impl std::fmt::Debug for ContactFfi {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        f.debug_struct("ContactFfi")
-            .field(
-                "pubkey",
-                &format_args!("{}...", &self.pubkey[..16.min(self.pubkey.len())]),
-            )
-            .field("display_name", &"<redacted>")
-            .field("notes", &"<redacted>")
-            .field("created_at", &self.created_at)
-            .field("updated_at", &self.updated_at)
-            .finish()
-    }
+/// `bytes` is public map imagery (encrypted only at rest), so it is surfaced
+/// directly. No coordinates are carried, and there is intentionally no `Debug`
+/// impl that prints `bytes`.
+pub struct TileCacheEntryFfi {
+    /// Raw tile bytes (PNG).
+    pub bytes: Vec<u8>,
+    /// HTTP freshness deadline in unix milliseconds.
+    pub stale_at_ms: i64,
+    /// `Last-Modified` as unix milliseconds, if present.
+    pub last_modified_ms: Option<i64>,
+    /// `ETag` value, if present.
+    pub etag: Option<String>,
 }
 
-impl From<&CoreContact> for ContactFfi {
-    fn from(c: &CoreContact) -> Self {
+impl From<TileEntry> for TileCacheEntryFfi {
+    fn from(e: TileEntry) -> Self {
         Self {
-            pubkey: c.pubkey.clone(),
-            display_name: c.display_name.clone(),
-            notes: c.notes.clone(),
-            created_at: c.created_at,
-            updated_at: c.updated_at,
+            bytes: e.bytes,
+            stale_at_ms: e.stale_at_ms,
+            last_modified_ms: e.last_modified_ms,
+            etag: e.etag,
         }
     }
 }
 
-impl From<CoreContact> for ContactFfi {
-    fn from(c: CoreContact) -> Self {
-        Self::from(&c)
-    }
-}
-
-/// Circle member with resolved local contact info (FFI-friendly).
-#[derive(Clone)]
-pub struct CircleMemberFfi {
-    /// Nostr public key (hex) - always available.
-    pub pubkey: String,
-    /// Nostr public key in NIP-19 bech32 format (`npub1...`).
-    ///
-    /// A derived, display/copy-friendly encoding of [`Self::pubkey`]. An npub is
-    /// a PUBLIC key, so it is safe to compute and expose.
-    pub npub: String,
-    /// Display name from local Contact, if set.
-    pub display_name: Option<String>,
-    /// Whether this member is a group admin.
-    pub is_admin: bool,
-}
-
-/// Redacting `Debug` that mirrors the core [`CoreCircleMember`] impl
-/// (see `haven-core/src/circle/types.rs`): public keys are truncated to a short
-/// prefix and the local `display_name` is elided. Even though `pubkey`/`npub`
-/// are PUBLIC keys, we never print them in full (Security Rule 6: no key
-/// material in logs) so accidental `{:?}` formatting cannot leak identifiers.
-impl std::fmt::Debug for CircleMemberFfi {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        f.debug_struct("CircleMemberFfi")
-            .field(
-                "pubkey",
-                &format_args!("{}...", &self.pubkey[..16.min(self.pubkey.len())]),
-            )
-            .field(
-                "npub",
-                &format_args!("{}...", &self.npub[..16.min(self.npub.len())]),
-            )
-            .field("display_name", &"<redacted>")
-            .field("is_admin", &self.is_admin)
-            .finish()
-    }
-}
-
-impl From<&CoreCircleMember> for CircleMemberFfi {
-    fn from(m: &CoreCircleMember) -> Self {
-        Self {
-            npub: hex_to_npub(&m.pubkey),
-            pubkey: m.pubkey.clone(),
-            display_name: m.display_name.clone(),
-            is_admin: m.is_admin,
-        }
-    }
+/// Clones the live tile-cache `Arc` under a read lock.
+///
+/// # Errors
+///
+/// Returns an error string if the cache is not initialized or the lock is
+/// poisoned.
+fn current_cache() -> Result<Arc<TileCacheStorage>, HavenErrorFfi> {
+    let guard = TILE_CACHE
+        .read()
+        .map_err(|_| HavenErrorFfi::internal("tile cache lock poisoned"))?;
+    guard
+        .as_ref()
+        .cloned()
+        .ok_or_else(|| HavenErrorFfi::internal("tile cache not initialized"))
 }
 
-/// Bech32-encodes a hex Nostr public key as an `npub1...` string (NIP-19).
+/// Initializes the encrypted tile cache at `data_dir`/`tiles.db`.
 ///
-/// An npub is a PUBLIC key, so it is safe to compute and expose across FFI.
-/// On the (in practice impossible) parse/encode failure of a well-formed hex
-/// member pubkey, falls back to the original hex so the UI never renders an
-/// empty identifier.
-fn hex_to_npub(hex: &str) -> String {
-    use nostr::prelude::ToBech32 as _;
-    nostr::PublicKey::parse(hex)
-        .ok()
-        .and_then(|pk| pk.to_bech32().ok())
-        .unwrap_or_else(|| hex.to_string())
-}
+/// Ensures the platform keyring is initialized, fetches/creates the tiles.db
+/// key, and opens the cache. On a decrypt failure or schema-version mismatch the
+/// cache is **disposable**: it is dropped and recreated (delete the DB files,
+/// remove the keyring entry, mint a fresh key, reopen) so the map is never
+/// blocked. Other errors are returned (Dart treats the cache as unavailable and
+/// falls back to live tiles).
+///
+/// This is a plain `pub fn`; `flutter_rust_bridge` dispatches it on a worker, so
+/// the one-time `SQLCipher` open at startup does not block the UI isolate.
+///
+/// # Errors
+///
+/// Returns an error string if the keyring is unavailable or the cache cannot be
+/// opened even after disposable recovery.
+pub fn tile_cache_init(data_dir: String) -> Result<(), HavenErrorFfi> {
+    init_keyring_store()?;
 
-/// Circle with its membership and member list (FFI-friendly).
-#[derive(Debug, Clone)]
-pub struct CircleWithMembersFfi {
-    /// The circle.
-    pub circle: CircleFfi,
-    /// User's membership status: "pending", "accepted", or "declined".
-    pub membership_status: String,
-    /// Public key of who invited us (if known).
-    pub inviter_pubkey: Option<String>,
-    /// Members with resolved contact info.
-    pub members: Vec<CircleMemberFfi>,
-}
+    // Ensure the data directory exists. `tile_cache_init` runs from `main()`
+    // before the lazily-constructed `CircleManagerFfi` (which is the usual
+    // creator of this directory via `create_dir_all`), so on a fresh install
+    // the directory would not yet exist and `Connection::open` — which creates
+    // the DB file but NOT its parent dirs — would fail, silently disabling the
+    // cache for the whole first session. Create it here so init is self-
+    // sufficient regardless of call order.
+    std::fs::create_dir_all(&data_dir).map_err(|e| {
+        HavenErrorFfi::storage(format!("Failed to create tile cache data dir: {e}"))
+    })?;
 
-impl From<&CoreCircleWithMembers> for CircleWithMembersFfi {
-    fn from(c: &CoreCircleWithMembers) -> Self {
-        Self {
-            circle: CircleFfi::from(&c.circle),
-            membership_status: c.membership.status.as_str().to_string(),
-            inviter_pubkey: c.membership.inviter_pubkey.clone(),
-            members: c.members.iter().map(CircleMemberFfi::from).collect(),
-        }
-    }
-}
+    let path = std::path::Path::new(&data_dir).join(TILES_DB_FILENAME);
 
-/// Pending invitation to join a circle (FFI-friendly).
-#[derive(Clone)]
-pub struct InvitationFfi {
-    /// MLS group ID.
-    pub mls_group_id: Vec<u8>,
-    /// Circle name.
-    pub circle_name: String,
-    /// Public key (hex) of who invited us.
-    pub inviter_pubkey: String,
-    /// Number of members in the circle.
-    pub member_count: u32,
-    /// When we were invited (Unix timestamp).
-    pub invited_at: i64,
-}
+    let key = get_or_create_tiles_db_key()?;
+    let storage = match TileCacheStorage::open(&path, &key) {
+        Ok(s) => s,
+        Err(TileCacheError::DecryptFailed | TileCacheError::SchemaVersionMismatch) => {
+            // Disposable-cache recovery: the cache holds only re-fetchable public
+            // imagery, so on a corrupt/undecryptable/stale-schema DB we drop and
+            // recreate it rather than blocking the map.
+            log::warn!("tiles.db unreadable; recreating disposable tile cache");
+            drop(key);
+            delete_tile_db_files(&data_dir);
+            remove_tiles_db_key();
+            let fresh_key = get_or_create_tiles_db_key()?;
+            TileCacheStorage::open(&path, &fresh_key).map_err(tile_err_to_ffi)?
+        }
+        Err(e) => return Err(tile_err_to_ffi(e)),
+    };
 
-impl std::fmt::Debug for InvitationFfi {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        f.debug_struct("InvitationFfi")
-            .field("mls_group_id", &"<redacted>")
-            .field("circle_name", &self.circle_name)
-            .field("inviter_pubkey", &self.inviter_pubkey)
-            .field("member_count", &self.member_count)
-            .field("invited_at", &self.invited_at)
-            .finish()
+    {
+        let mut guard = TILE_CACHE
+            .write()
+            .map_err(|_| HavenErrorFfi::internal("tile cache lock poisoned"))?;
+        *guard = Some(Arc::new(storage));
     }
-}
-
-impl From<&CoreInvitation> for InvitationFfi {
-    fn from(i: &CoreInvitation) -> Self {
-        Self {
-            mls_group_id: i.mls_group_id.as_slice().to_vec(),
-            circle_name: i.circle_name.clone(),
-            inviter_pubkey: i.inviter_pubkey.clone(),
-            member_count: i.member_count as u32,
-            invited_at: i.invited_at,
-        }
+    {
+        let mut dir = TILE_CACHE_DIR
+            .lock()
+            .map_err(|_| HavenErrorFfi::internal("tile cache dir lock poisoned"))?;
+        *dir = Some(data_dir);
     }
+    Ok(())
 }
 
-impl From<CoreInvitation> for InvitationFfi {
-    fn from(i: CoreInvitation) -> Self {
-        Self::from(&i)
-    }
+/// Returns the cached tile for `(style, z, x, y, retina)`, or `None`.
+///
+/// # Errors
+///
+/// Returns an error string if the cache is uninitialized or the read fails.
+/// Coordinates are never included in the error.
+pub async fn tile_cache_get(
+    style: String,
+    z: i64,
+    x: i64,
+    y: i64,
+    retina: bool,
+) -> Result<Option<TileCacheEntryFfi>, HavenErrorFfi> {
+    let cache = current_cache()?;
+    run_blocking(move || {
+        cache
+            .get(&style, z, x, y, retina, now_ms())
+            .map(|opt| opt.map(TileCacheEntryFfi::from))
+            .map_err(tile_err_to_ffi)
+    })
+    .await
 }
 
-/// Key package bundle for publishing (FFI-friendly).
+/// Inserts or replaces a tile's bytes and metadata (a bytes-write).
 ///
-/// A member's key package with their inbox relay list (FFI-friendly).
+/// # Errors
 ///
-/// Used when adding members to a circle. Relay resolution follows a
-/// cascading fallback: inbox (kind 10050) → NIP-65 (kind 10002) → defaults.
-#[derive(Clone)]
-pub struct MemberKeyPackageFfi {
-    /// The key package event JSON (kind 30443 or legacy kind 443).
-    pub key_package_json: String,
-    /// Relay URLs from the member's inbox relay list (kind 10050).
-    pub inbox_relays: Vec<String>,
-    /// Fallback relay URLs from NIP-65 relay list (kind 10002).
-    pub nip65_relays: Vec<String>,
-}
-
-impl std::fmt::Debug for MemberKeyPackageFfi {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        f.debug_struct("MemberKeyPackageFfi")
-            .field("key_package_json", &"<redacted>")
-            .field("inbox_relays_count", &self.inbox_relays.len())
-            .field("nip65_relays_count", &self.nip65_relays.len())
-            .finish()
-    }
+/// Returns an error string if the cache is uninitialized or the write fails.
+#[allow(
+    clippy::too_many_arguments,
+    reason = "the tile key + payload is inherently wide; grouping would change the wire contract"
+)]
+pub async fn tile_cache_put(
+    style: String,
+    z: i64,
+    x: i64,
+    y: i64,
+    retina: bool,
+    bytes: Vec<u8>,
+    stale_at_ms: i64,
+    last_modified_ms: Option<i64>,
+    etag: Option<String>,
+) -> Result<(), HavenErrorFfi> {
+    let cache = current_cache()?;
+    run_blocking(move || {
+        cache
+            .put(
+                &style,
+                z,
+                x,
+                y,
+                retina,
+                &bytes,
+                stale_at_ms,
+                last_modified_ms,
+                etag.as_deref(),
+                now_ms(),
+            )
+            .map_err(tile_err_to_ffi)
+    })
+    .await
 }
 
-/// A gift-wrapped Welcome ready for publishing (FFI-friendly).
+/// Refreshes only a tile's conditional-revalidation metadata (the HTTP-304
+/// path); never touches the bytes or the `fetched_at` anchor.
 ///
-/// Contains the kind 1059 gift-wrapped event along with recipient
-/// information needed for relay publishing.
-#[derive(Clone)]
-pub struct GiftWrappedWelcomeFfi {
-    /// The recipient's Nostr public key (hex).
-    pub recipient_pubkey: String,
-    /// Relay URLs to publish this Welcome to (recipient's inbox relays).
-    pub recipient_relays: Vec<String>,
-    /// The gift-wrapped event JSON (kind 1059), ready to publish.
-    pub event_json: String,
+/// # Errors
+///
+/// Returns an error string if the cache is uninitialized or the update fails.
+#[allow(
+    clippy::too_many_arguments,
+    reason = "mirrors the tile key + metadata shape of `tile_cache_put`"
+)]
+pub async fn tile_cache_put_metadata(
+    style: String,
+    z: i64,
+    x: i64,
+    y: i64,
+    retina: bool,
+    stale_at_ms: i64,
+    last_modified_ms: Option<i64>,
+    etag: Option<String>,
+) -> Result<(), HavenErrorFfi> {
+    let cache = current_cache()?;
+    run_blocking(move || {
+        cache
+            .put_metadata(
+                &style,
+                z,
+                x,
+                y,
+                retina,
+                stale_at_ms,
+                last_modified_ms,
+                etag.as_deref(),
+                now_ms(),
+            )
+            .map_err(tile_err_to_ffi)
+    })
+    .await
 }
 
-impl std::fmt::Debug for GiftWrappedWelcomeFfi {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        f.debug_struct("GiftWrappedWelcomeFfi")
-            .field("recipient_pubkey", &"<redacted>")
-            .field("recipient_relays_count", &self.recipient_relays.len())
-            .field("event_json", &"<redacted>")
-            .finish()
-    }
+/// Evicts stale, over-retention, and over-budget tiles in one transaction.
+///
+/// `idle_age_secs` and `max_retention_secs` are converted to milliseconds to
+/// match the storage layer's unix-millisecond clocks.
+///
+/// Returns the number of rows deleted.
+///
+/// # Errors
+///
+/// Returns an error string if the cache is uninitialized or the eviction fails.
+pub async fn tile_cache_evict(
+    max_bytes: i64,
+    idle_age_secs: i64,
+    max_retention_secs: i64,
+) -> Result<u64, HavenErrorFfi> {
+    let cache = current_cache()?;
+    let idle_age_ms = idle_age_secs.saturating_mul(1000);
+    let max_retention_ms = max_retention_secs.saturating_mul(1000);
+    run_blocking(move || {
+        cache
+            .evict(max_bytes, idle_age_ms, max_retention_ms, now_ms())
+            .map_err(tile_err_to_ffi)
+    })
+    .await
 }
 
-/// A publish-before-apply token (FFI mirror of `PendingStateRef`).
+/// Wipes the encrypted tile cache (logout path).
 ///
-/// The Dark Matter engine stages a group-evolving commit and returns an opaque
-/// `PendingStateRef` (a `u64` newtype). Haven publishes the commit (and, for an
-/// Add, the welcomes), and ONLY after ≥1 relay returns OK confirms the token via
-/// [`CircleManagerFfi::confirm_published`] so the engine applies the commit and
-/// advances the epoch (Rule 13, publish-before-apply). On publish FAILURE the
-/// token is rolled back via [`CircleManagerFfi::publish_failed`]. The `token` is
-/// an in-memory session handle — meaningless across a process restart, never
-/// persisted, never published.
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
-pub struct PendingStateRefFfi {
-    /// The opaque engine token (a `PendingStateRef` `u64`).
-    pub token: u64,
-}
+/// Best-effort clears the content, drops the live `Arc` (closing the
+/// connections once the last reference is gone), deletes `tiles.db` + its
+/// `-wal`/`-shm` sidecars, and removes the tiles keyring entry. Already-gone
+/// files are not an error: a new identity must never inherit the prior
+/// identity's cached map areas.
+///
+/// # Errors
+///
+/// Returns an error string only if an internal lock is poisoned.
+pub async fn tile_cache_wipe() -> Result<(), HavenErrorFfi> {
+    // Best-effort content clear while the cache is still live.
+    if let Ok(cache) = current_cache() {
+        let _ = run_blocking(move || cache.clear().map_err(tile_err_to_ffi)).await;
+    }
 
-impl From<PendingStateRef> for PendingStateRefFfi {
-    fn from(p: PendingStateRef) -> Self {
-        Self { token: p.as_u64() }
+    // Drop the live Arc so the connections close once the last ref is gone.
+    //
+    // Race note (SF-2): a `tile_cache_get/put` that called `current_cache()`
+    // just before this `take` holds its own Arc clone, so its connection
+    // briefly outlives the wipe. This is POSIX-safe — `remove_file` unlinks but
+    // defers reclamation until the last fd closes, and that in-flight call
+    // writes no *new* at-rest data (a `get` is read-only; a racing `put` lands
+    // in a file we are unlinking, under a key we are about to remove). The M-C
+    // logout path must still cancel prefetch/tile traffic before calling this
+    // so no such race is in flight in practice.
+    {
+        let mut guard = TILE_CACHE
+            .write()
+            .map_err(|_| HavenErrorFfi::internal("tile cache lock poisoned"))?;
+        *guard = None;
     }
-}
 
-impl From<PendingStateRefFfi> for PendingStateRef {
-    fn from(p: PendingStateRefFfi) -> Self {
-        Self::new(p.token)
+    // Delete the DB files (+ sidecars) using the remembered data dir.
+    let data_dir = {
+        let dir = TILE_CACHE_DIR
+            .lock()
+            .map_err(|_| HavenErrorFfi::internal("tile cache dir lock poisoned"))?;
+        dir.clone()
+    };
+    if let Some(dir) = data_dir {
+        delete_tile_db_files(&dir);
     }
-}
 
-/// Result of circle creation (FFI-friendly).
-///
-/// Publish-before-apply (Rule 13): publish `welcome_events`, then confirm
-/// `pending` via [`CircleManagerFfi::confirm_published`] once ≥1 relay ACKs a
-/// welcome (or roll back via [`CircleManagerFfi::publish_failed`]).
-#[derive(Debug, Clone)]
-pub struct CircleCreationResultFfi {
-    /// The created circle.
-    pub circle: CircleFfi,
-    /// Gift-wrapped Welcome events ready to publish to recipients.
-    /// Each is a kind 1059 event containing an encrypted kind 444 Welcome.
-    pub welcome_events: Vec<GiftWrappedWelcomeFfi>,
-    /// The pending group-creation state to confirm after ≥1-relay welcome ACK.
-    pub pending: PendingStateRefFfi,
+    // Remove the keyring entry so a fresh cache mints a fresh key.
+    remove_tiles_db_key();
+    Ok(())
 }
 
-/// Result of adding members to an existing circle (FFI-friendly).
+// ============================================================================
+// Circle Management (FFI)
+// ============================================================================
+
+use std::path::Path;
+
+use haven_core::circle::{
+    Circle as CoreCircle, CircleConfig as CoreCircleConfig, CircleManager as CoreCircleManager,
+    CircleMember as CoreCircleMember, CircleType as CoreCircleType,
+    CircleWithMembers as CoreCircleWithMembers, Contact as CoreContact,
+    Invitation as CoreInvitation,
+};
+use haven_core::nostr::mls::types::{GroupId, GroupIdExt, PendingStateRef};
+
+/// Circle information (FFI-friendly).
 ///
-/// Publish-before-apply (Rule 13): publish `commit_event_json`, confirm
-/// `pending`, THEN publish `welcome_events` (a welcome for a losing/unconfirmed
-/// commit references an epoch that never applied).
+/// Represents a location sharing circle (group of people).
 #[derive(Clone)]
-pub struct AddMembersResultFfi {
-    /// JSON-serialized kind 445 evolution (Add commit) event, to publish to
-    /// the circle's relays before confirming `pending`.
-    pub commit_event_json: String,
-    /// Gift-wrapped Welcome events for the newly added members.
-    /// Each is a kind 1059 event containing an encrypted kind 444 Welcome.
-    /// Publish these only after `commit_event_json` is published and `pending`
-    /// is confirmed.
-    pub welcome_events: Vec<GiftWrappedWelcomeFfi>,
-    /// The pending commit to confirm after ≥1-relay commit ACK.
-    pub pending: PendingStateRefFfi,
+pub struct CircleFfi {
+    /// MLS group ID (opaque bytes, used for API calls).
+    pub mls_group_id: Vec<u8>,
+    /// Nostr group ID (32 bytes, used in h-tags for relay routing).
+    pub nostr_group_id: Vec<u8>,
+    /// User-facing display name (local only).
+    pub display_name: String,
+    /// Circle type: "location_sharing" or "direct_share".
+    pub circle_type: String,
+    /// Relay URLs for this circle's messages.
+    pub relays: Vec<String>,
+    /// When the circle was created (Unix timestamp).
+    pub created_at: i64,
+    /// When the circle was last updated (Unix timestamp).
+    pub updated_at: i64,
 }
 
-impl std::fmt::Debug for AddMembersResultFfi {
+impl std::fmt::Debug for CircleFfi {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        // The commit-event JSON can embed the MLS group ID in its tags;
-        // redact it. Welcome events redact themselves via their own Debug impl.
-        f.debug_struct("AddMembersResultFfi")
-            .field("commit_event_json", &"<redacted>")
-            .field("welcome_events_count", &self.welcome_events.len())
-            .field("pending", &self.pending)
-            .finish()
+        f.debug_struct("CircleFfi")
+            .field("mls_group_id", &"<redacted>")
+            .field(
+                "nostr_group_id",
+                &self
+                    .nostr_group_id
+                    .iter()
+                    .map(|b| format!("{b:02x}"))
+                    .collect::<String>(),
+            )
+            .field("display_name", &self.display_name)
+            .field("circle_type", &self.circle_type)
+            .field("relays", &self.relays)
+            .field("created_at", &self.created_at)
+            .field("updated_at", &self.updated_at)
+            .finish()
     }
 }
 
-/// A group-evolving commit awaiting publish + confirm (remove / relay update /
-/// admin change) — FFI mirror of `haven_core::circle::CommitToPublish`.
+impl From<&CoreCircle> for CircleFfi {
+    fn from(c: &CoreCircle) -> Self {
+        Self {
+            mls_group_id: c.mls_group_id.as_slice().to_vec(),
+            nostr_group_id: c.nostr_group_id.to_vec(),
+            display_name: c.display_name.clone(),
+            circle_type: c.circle_type.as_str().to_string(),
+            relays: c.relays.clone(),
+            created_at: c.created_at,
+            updated_at: c.updated_at,
+        }
+    }
+}
+
+/// Local contact information (FFI-friendly).
 ///
-/// Publish-before-apply (Rule 13): publish `commit_event_json` to the circle's
-/// relays, then confirm `pending` via [`CircleManagerFfi::confirm_published`]
-/// on ≥1-relay ACK (or roll back via [`CircleManagerFfi::publish_failed`]).
+/// **Privacy Note**: This data is stored only on the user's device,
+/// never synced to Nostr relays. Each user assigns their own names
+/// and avatars to contacts (like phone contacts).
 #[derive(Clone)]
-pub struct CommitToPublishFfi {
-    /// JSON-serialized kind 445 commit event to publish to the circle's relays.
-    pub commit_event_json: String,
-    /// The pending commit to confirm after ≥1-relay ACK.
-    pub pending: PendingStateRefFfi,
+pub struct ContactFfi {
+    /// Nostr public key (hex) - the ONLY identifier visible to relays.
+    pub pubkey: String,
+    /// Locally assigned display name.
+    pub display_name: Option<String>,
+    /// Optional notes about this contact.
+    pub notes: Option<String>,
+    /// When this contact was created (Unix timestamp).
+    pub created_at: i64,
+    /// When this contact was last updated (Unix timestamp).
+    pub updated_at: i64,
+    /// Whether the user verified this contact's safety number in person.
+    pub verified: bool,
+    /// When the contact was verified (Unix timestamp).
+    pub verified_at: Option<i64>,
 }
 
-impl std::fmt::Debug for CommitToPublishFfi {
+impl std::fmt::Debug for ContactFfi {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        // The commit-event JSON's `h` tag carries the nostr_group_id; redact.
-        f.debug_struct("CommitToPublishFfi")
-            .field("commit_event_json", &"<redacted>")
-            .field("pending", &self.pending)
+        f.debug_struct("ContactFfi")
+            .field(
+                "pubkey",
+                &format_args!("{}...", &self.pubkey[..16.min(self.pubkey.len())]),
+            )
+            .field("display_name", &"<redacted>")
+            .field("notes", &"<redacted>")
+            .field("created_at", &self.created_at)
+            .field("updated_at", &self.updated_at)
+            .field("verified", &self.verified)
             .finish()
     }
 }
 
-/// Encrypted location event ready for relay publishing (FFI-friendly).
-///
-/// Contains the signed kind 445 event and routing metadata.
-#[derive(Debug, Clone)]
-pub struct EncryptedLocationFfi {
-    /// JSON-serialized signed Nostr event (kind 445).
-    pub event_json: String,
-    /// Nostr group ID (32 bytes, for h-tag relay routing).
-    pub nostr_group_id: Vec<u8>,
-    /// Relay URLs to publish to.
-    pub relays: Vec<String>,
-}
-
-/// Decrypted location from a peer (FFI-friendly).
-///
-/// Contains the sender identity and location data.
-#[derive(Clone)]
-pub struct DecryptedLocationFfi {
-    /// Sender's Nostr public key (hex-encoded).
-    pub sender_pubkey: String,
-    /// Latitude (exact GPS reading).
-    pub latitude: f64,
-    /// Longitude (exact GPS reading).
-    pub longitude: f64,
-    /// Geohash of the location.
-    pub geohash: String,
-    /// When the location was recorded (Unix seconds).
-    pub timestamp: i64,
-    /// When this location expires (Unix seconds).
-    pub expires_at: i64,
+impl From<&CoreContact> for ContactFfi {
+    fn from(c: &CoreContact) -> Self {
+        Self {
+            pubkey: c.pubkey.clone(),
+            display_name: c.display_name.clone(),
+            notes: c.notes.clone(),
+            created_at: c.created_at,
+            updated_at: c.updated_at,
+            verified: c.is_verified(),
+            verified_at: c.verified_at,
+        }
+    }
 }
 
-impl std::fmt::Debug for DecryptedLocationFfi {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        f.debug_struct("DecryptedLocationFfi")
-            .field("sender_pubkey", &"<redacted>")
-            .field("latitude", &"<redacted>")
-            .field("longitude", &"<redacted>")
-            .field("geohash", &"<redacted>")
-            .field("timestamp", &self.timestamp)
-            .field("expires_at", &self.expires_at)
-            .finish()
+impl From<CoreContact> for ContactFfi {
+    fn from(c: CoreContact) -> Self {
+        Self::from(&c)
     }
 }
 
-/// A persisted last-known location for a circle member (FFI-friendly).
-///
-/// Mirrors `haven_core::circle::LastKnownLocation`. Returned from
-/// `CircleManagerFfi::snapshot_last_known_for_circle` so the Flutter
-/// layer can hydrate its in-memory cache on app start.
+/// Circle member with resolved local contact info (FFI-friendly).
 #[derive(Clone)]
-pub struct LastKnownLocationFfi {
-    /// Nostr group ID (32 bytes) of the circle.
-    pub nostr_group_id: Vec<u8>,
-    /// Sender's Nostr public key (hex-encoded).
-    pub sender_pubkey: String,
-    /// Latitude (exact GPS reading).
-    pub latitude: f64,
-    /// Longitude (exact GPS reading).
-    pub longitude: f64,
-    /// Geohash of the location.
-    pub geohash: String,
-    /// Display name carried in the encrypted location message, if any.
+pub struct CircleMemberFfi {
+    /// Nostr public key (hex) - always available.
+    pub pubkey: String,
+    /// Nostr public key in NIP-19 bech32 format (`npub1...`).
+    ///
+    /// A derived, display/copy-friendly encoding of [`Self::pubkey`]. An npub is
+    /// a PUBLIC key, so it is safe to compute and expose.
+    pub npub: String,
+    /// Display name from local Contact, if set.
     pub display_name: Option<String>,
-    /// When the location was captured (Unix seconds).
-    pub timestamp: i64,
-    /// When the inner freshness window expires (Unix seconds).
-    pub expires_at: i64,
-    /// Row must be deleted after this Unix-seconds moment.
-    pub purge_after: i64,
-    /// When this row was last written (Unix seconds, receiver clock).
-    pub updated_at: i64,
+    /// Whether this member is a group admin.
+    pub is_admin: bool,
 }
 
-impl std::fmt::Debug for LastKnownLocationFfi {
+/// Redacting `Debug` that mirrors the core [`CoreCircleMember`] impl
+/// (see `haven-core/src/circle/types.rs`): public keys are truncated to a short
+/// prefix and the local `display_name` is elided. Even though `pubkey`/`npub`
+/// are PUBLIC keys, we never print them in full (Security Rule 6: no key
+/// material in logs) so accidental `{:?}` formatting cannot leak identifiers.
+impl std::fmt::Debug for CircleMemberFfi {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        f.debug_struct("LastKnownLocationFfi")
-            .field("sender_pubkey", &"<redacted>")
-            .field("latitude", &"<redacted>")
-            .field("longitude", &"<redacted>")
-            .field("geohash", &"<redacted>")
+        f.debug_struct("CircleMemberFfi")
+            .field(
+                "pubkey",
+                &format_args!("{}...", &self.pubkey[..16.min(self.pubkey.len())]),
+            )
+            .field(
+                "npub",
+                &format_args!("{}...", &self.npub[..16.min(self.npub.len())]),
+            )
             .field("display_name", &"<redacted>")
-            .field("timestamp", &self.timestamp)
-            .field("expires_at", &self.expires_at)
-            .field("purge_after", &self.purge_after)
-            .field("updated_at", &self.updated_at)
+            .field("is_admin", &self.is_admin)
             .finish()
     }
 }
 
-/// Discriminator for [`LocationMessageResultFfi`].
-///
-/// Mirrors the five
-/// [`haven_core::nostr::mls::types::LocationMessageResult`] variants 1:1
-/// (Dark Matter taxonomy). Unlike the pre-migration outcome, stale / duplicate
-/// / out-of-order handling is entirely engine-internal (the engine durably
-/// buffers a future-epoch event and re-surfaces it once the gap fills), so
-/// there is NO `Unprocessable` / `PreviouslyFailed` here. A struct-with-
-/// discriminant shape (rather than a tagged Rust enum) follows the existing
-/// [`LeavePlanFfi`] convention and avoids pulling Dart `freezed` into the
-/// generated bindings.
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
-pub enum LocationMessageResultKindFfi {
-    /// A decrypted application (location) message.
-    Location,
-    /// The local client joined a group via an accepted welcome.
-    Joined,
-    /// A durable, MLS-authenticated change to group state (membership, admin,
-    /// rename, retention) or an epoch advance the receiver should react to by
-    /// refreshing the circle's roster.
-    GroupUpdate,
-    /// A previously-surfaced result was withdrawn because branch selection
-    /// superseded the commit that produced it — the caller must treat the
-    /// earlier change as if it never happened.
-    Invalidated,
-    /// The group entered the unrecoverable state; the UI MUST block
-    /// send/mutate for it (Rule 8, blocked-group state).
-    Unrecoverable,
+impl From<&CoreCircleMember> for CircleMemberFfi {
+    fn from(m: &CoreCircleMember) -> Self {
+        Self {
+            npub: npub_or_hex(&m.pubkey),
+            pubkey: m.pubkey.clone(),
+            display_name: m.display_name.clone(),
+            is_admin: m.is_admin,
+        }
+    }
 }
 
-/// One folded engine [`haven_core::nostr::mls::types::LocationMessageResult`],
-/// FFI-friendly. A single `kind:445` ingest can yield SEVERAL of these (an
-/// engine `advance_convergence` may release buffered inbound after the outer
-/// event), so [`CircleManagerFfi::decrypt_location`] returns a `Vec` of them.
+/// Bech32-encodes a hex Nostr public key as an `npub1...` string (NIP-19).
 ///
-/// Cursor contract (DM-4b): the engine now owns out-of-order buffering, so the
-/// caller advances its relay sync cursor on the OUTER event's `created_at`
-/// (which Dart already holds — it passed `event_json` in), NOT per result.
-/// A buffered future-epoch event is re-surfaced by the engine once the gap
-/// fills; the caller never needs to re-fetch it.
-pub struct LocationMessageResultFfi {
-    /// Which of the five outcomes this result is.
-    pub kind: LocationMessageResultKindFfi,
-    /// The decrypted location — `Some` only when `kind == Location` AND the
-    /// inner content parsed as a `LocationMessage`. A successfully-decrypted
-    /// but unparseable inner (e.g. a forward-incompatible payload) still yields
-    /// `kind == Location` with `location == None` (decrypt succeeded).
-    pub location: Option<DecryptedLocationFfi>,
-    /// The MLS group id (raw bytes) this result belongs to — the LOCAL circle
-    /// handle (never published; Rule 4 keeps it off the wire). Present for
-    /// every variant so the caller can refresh / join / block / withdraw the
-    /// right circle.
-    pub mls_group_id: Vec<u8>,
-    /// The MLS epoch the message was authenticated at — meaningful only for
-    /// `kind == Location` (0 otherwise).
-    pub epoch: u64,
+/// An npub is a PUBLIC key, so it is safe to compute and expose across FFI.
+/// On the (in practice impossible) parse/encode failure of a well-formed hex
+/// member pubkey, falls back to the original hex so the UI never renders an
+/// empty identifier.
+fn npub_or_hex(hex: &str) -> String {
+    use nostr::prelude::ToBech32 as _;
+    nostr::PublicKey::parse(hex)
+        .ok()
+        .and_then(|pk| pk.to_bech32().ok())
+        .unwrap_or_else(|| hex.to_string())
 }
 
-impl std::fmt::Debug for LocationMessageResultFfi {
-    /// Redacts payloads (location, raw group id) and exposes only presence;
-    /// `epoch` is a non-secret counter.
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        f.debug_struct("LocationMessageResultFfi")
-            .field("kind", &self.kind)
-            .field("has_location", &self.location.is_some())
-            .field("mls_group_id", &"<redacted>")
-            .field("epoch", &self.epoch)
-            .finish()
+/// Circle with its membership and member list (FFI-friendly).
+#[derive(Debug, Clone)]
+pub struct CircleWithMembersFfi {
+    /// The circle.
+    pub circle: CircleFfi,
+    /// User's membership status: "pending", "accepted", or "declined".
+    pub membership_status: String,
+    /// Public key of who invited us (if known).
+    pub inviter_pubkey: Option<String>,
+    /// Members with resolved contact info.
+    pub members: Vec<CircleMemberFfi>,
+}
+
+impl From<&CoreCircleWithMembers> for CircleWithMembersFfi {
+    fn from(c: &CoreCircleWithMembers) -> Self {
+        Self {
+            circle: CircleFfi::from(&c.circle),
+            membership_status: c.membership.status.as_str().to_string(),
+            inviter_pubkey: c.membership.inviter_pubkey.clone(),
+            members: c.members.iter().map(CircleMemberFfi::from).collect(),
+        }
     }
 }
 
-/// Converts a core [`LocationMessageResult`] into the FFI
-/// [`DecryptOutcomeFfi`], surfacing all four variants.
-///
-/// Pure and synchronous (no MDK / no FFI / no async) so the M1 safety
-/// contract — `Unprocessable` and `PreviouslyFailed` are SURFACED, not
-/// flattened away — is unit-testable without a live `CircleManager`.
-/// `event_created_at_secs` is the outer event timestamp, threaded through
-/// onto every outcome.
-///
-/// [`LocationMessageResult`]: haven_core::nostr::mls::types::LocationMessageResult
-///
-/// # Errors
-///
-/// Returns a redacted error string if a `Location` payload cannot be parsed
-/// or a `GroupUpdate` evolution event cannot be serialized.
-/// Parses an engine-delivered Location `content` (the decrypted `LocationMessage`
-/// JSON carried on `FfiRelayEvent.content`) into a structured
-/// [`DecryptedLocationFfi`], reusing the Rust `serde` schema so the Dart stream
-/// consumer (M6-3) never duplicates the content schema or the `DateTime` format.
-/// The `sender_pubkey` comes from the stream event, not the content.
-///
-/// # Errors
-///
-/// Returns an error if `content_json` is not a valid `LocationMessage`.
-pub fn parse_engine_location(
-    content_json: String,
-    sender_pubkey: String,
-) -> Result<DecryptedLocationFfi, String> {
-    // Fixed message — never interpolate the serde error, which could echo a
-    // fragment of the decrypted location content (defense-in-depth; the Dart
-    // consumer already discards this and logs only the runtime type).
-    let location: haven_core::location::LocationMessage =
-        serde_json::from_str(&content_json).map_err(|_| "invalid location content".to_string())?;
-    Ok(DecryptedLocationFfi {
-        // Normalize to lowercase so the Dart self-compare against the cached own
-        // pubkey is case-insensitive by construction (mirrors convert_location_result).
-        sender_pubkey: normalize_pubkey_hex(&sender_pubkey),
-        latitude: location.latitude,
-        longitude: location.longitude,
-        geohash: location.geohash,
-        timestamp: location.timestamp.timestamp(),
-        expires_at: location.expires_at.timestamp(),
-    })
+/// Pending invitation to join a circle (FFI-friendly).
+#[derive(Clone)]
+pub struct InvitationFfi {
+    /// MLS group ID.
+    pub mls_group_id: Vec<u8>,
+    /// Circle name.
+    pub circle_name: String,
+    /// Public key (hex) of who invited us.
+    pub inviter_pubkey: String,
+    /// Number of members in the circle.
+    pub member_count: u32,
+    /// When we were invited (Unix timestamp).
+    pub invited_at: i64,
+    /// The inviter's self-chosen name from the Welcome, if any. Unverified —
+    /// show it only as a suggested contact name.
+    pub suggested_name: Option<String>,
 }
 
-/// Converts a core [`LocationMessageResult`] into the FFI
-/// [`LocationMessageResultFfi`] (Dark Matter five-variant taxonomy).
+/// Outcome of one gift wrap in a batch (FFI-friendly).
 ///
-/// Pure and synchronous (no engine / no FFI / no async) so the fold is
-/// unit-testable without a live `CircleManager`.
-///
-/// [`LocationMessageResult`]: haven_core::nostr::mls::types::LocationMessageResult
-fn convert_location_result(
-    result: haven_core::nostr::mls::types::LocationMessageResult,
-) -> LocationMessageResultFfi {
-    use haven_core::nostr::mls::types::LocationMessageResult as R;
-    match result {
-        R::Location {
-            sender_pubkey,
-            content,
-            group_id,
-            epoch,
-        } => {
-            // Decrypt succeeded — a location result regardless of whether the
-            // inner content parses. A forward-incompatible inner (from a peer
-            // on a newer content schema) decrypts fine but yields `None`; the
-            // caller advances past it exactly like a `GroupUpdate`.
-            let location = serde_json::from_str::<haven_core::location::LocationMessage>(&content)
-                .ok()
-                .map(|location| DecryptedLocationFfi {
-                    // Normalize to lowercase so the Dart self-compare against
-                    // the cached own pubkey is case-insensitive by construction.
-                    sender_pubkey: normalize_pubkey_hex(&sender_pubkey),
-                    latitude: location.latitude,
-                    longitude: location.longitude,
-                    geohash: location.geohash,
-                    timestamp: location.timestamp.timestamp(),
-                    expires_at: location.expires_at.timestamp(),
-                });
-            LocationMessageResultFfi {
-                kind: LocationMessageResultKindFfi::Location,
-                location,
-                mls_group_id: group_id.as_slice().to_vec(),
-                epoch,
-            }
+/// Exactly one of: `invitation` is set (a new pending invitation),
+/// `already_processed` is true (a repeat; skip silently), or `error` is set.
+#[derive(Debug, Clone)]
+pub struct GiftWrapOutcomeFfi {
+    /// Hex id of the gift-wrap event.
+    pub event_id: String,
+    /// The new pending invitation, if one was surfaced.
+    pub invitation: Option<InvitationFfi>,
+    /// Whether the wrap had already been processed or is already held.
+    pub already_processed: bool,
+    /// Why the wrap could not be processed.
+    pub error: Option<HavenErrorFfi>,
+}
+
+impl GiftWrapOutcomeFfi {
+    fn new(
+        event_id: String,
+        outcome: Result<haven_core::circle::Invitation, haven_core::circle::CircleError>,
+    ) -> Self {
+        let (invitation, already_processed, error) = match outcome {
+            Ok(invitation) => (Some(InvitationFfi::from(invitation)), false, None),
+            Err(haven_core::circle::CircleError::AlreadyProcessed) => (None, true, None),
+            Err(e) => (None, false, Some(HavenErrorFfi::from(e))),
+        };
+        Self {
+            event_id,
+            invitation,
+            already_processed,
+            error,
         }
-        R::Joined { group_id } => LocationMessageResultFfi {
-            kind: LocationMessageResultKindFfi::Joined,
-            location: None,
-            mls_group_id: group_id.as_slice().to_vec(),
-            epoch: 0,
-        },
-        R::GroupUpdate { group_id } => LocationMessageResultFfi {
-            kind: LocationMessageResultKindFfi::GroupUpdate,
-            location: None,
-            mls_group_id: group_id.as_slice().to_vec(),
-            epoch: 0,
-        },
-        R::Invalidated { group_id } => LocationMessageResultFfi {
-            kind: LocationMessageResultKindFfi::Invalidated,
-            location: None,
-            mls_group_id: group_id.as_slice().to_vec(),
-            epoch: 0,
-        },
-        R::Unrecoverable { group_id } => LocationMessageResultFfi {
-            kind: LocationMessageResultKindFfi::Unrecoverable,
-            location: None,
-            mls_group_id: group_id.as_slice().to_vec(),
-            epoch: 0,
-        },
     }
 }
 
-/// The folded outcome of ingesting one received `kind:445` — FFI mirror of
-/// `haven_core::circle::DecryptedIngest`.
-///
-/// Carries the folded location results AND any receive-side auto-commit the
-/// engine staged (a peer `SelfRemove` eviction). Publish-before-apply (Rule 13 /
-/// security F13): for EACH [`Self::auto_commits`] entry, publish
-/// `commit_event_json` to the circle's relays, then
-/// [`CircleManagerFfi::confirm_published`] on a ≥1-relay ACK (or
-/// [`CircleManagerFfi::publish_failed`] on failure) — exactly like the
-/// [`CommitToPublishFfi`] returned by remove / relay-update. NEVER confirm before
-/// a relay ACKs, and NEVER drop an entry silently (that re-forks the group the
-/// leaver departed).
-///
-/// Both fields carry redacting `Debug` impls (`LocationMessageResultFfi` /
-/// `CommitToPublishFfi`), so the derived `Debug` here cannot leak group ids or
-/// coordinates.
-#[derive(Debug)]
-pub struct DecryptLocationOutcomeFfi {
-    /// The folded location-facing results (locations, joins, updates, …).
-    pub results: Vec<LocationMessageResultFfi>,
-    /// Receive-side auto-commits the caller MUST publish then confirm/fail.
-    pub auto_commits: Vec<CommitToPublishFfi>,
+impl std::fmt::Debug for InvitationFfi {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("InvitationFfi")
+            .field("mls_group_id", &"<redacted>")
+            .field("circle_name", &self.circle_name)
+            .field("inviter_pubkey", &self.inviter_pubkey)
+            .field("member_count", &self.member_count)
+            .field("invited_at", &self.invited_at)
+            .field("has_suggested_name", &self.suggested_name.is_some())
+            .finish()
+    }
 }
 
-/// Converts a Nostr event `created_at` (Unix seconds) to the millisecond unit
-/// the sync cursor stores.
-///
-/// Saturating, so a pathological future timestamp cannot overflow `i64`.
-/// Centralizing the seconds→milliseconds conversion here (rather than in Dart)
-/// removes the ms/s drift footgun at the FFI boundary.
-const fn event_secs_to_cursor_ms(secs: i64) -> i64 {
-    secs.saturating_mul(1000)
+impl From<&CoreInvitation> for InvitationFfi {
+    fn from(i: &CoreInvitation) -> Self {
+        Self {
+            mls_group_id: i.mls_group_id.as_slice().to_vec(),
+            circle_name: i.circle_name.clone(),
+            inviter_pubkey: i.inviter_pubkey.clone(),
+            member_count: i.member_count as u32,
+            invited_at: i.invited_at,
+            suggested_name: i.suggested_name.clone(),
+        }
+    }
 }
 
-/// Discriminator for [`LeavePlanFfi`].
-///
-/// Drives the Flutter-side leave state machine. See the core
-/// `LeavePlan` docs for the full flow per variant.
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
-pub enum LeavePlanKindFfi {
-    /// Caller is a non-admin — skip handoff/demotion, go to `propose_leave`.
-    NonAdmin,
-    /// Caller is the sole admin — must promote `successor_hex` before
-    /// self-demoting and leaving.
-    AdminHandoff,
-    /// Caller is one of multiple admins — skip promotion and go directly
-    /// to `propose_self_demote` + `propose_leave`.
-    AdminDemote,
-    /// Caller is the sole remaining member — call
-    /// `abandon_circle_local_only` to wipe the local row.
-    Abandon,
-    /// MDK has no record of the group — call `complete_leave` to wipe the
-    /// orphaned local row.
-    OrphanLocalOnly,
+impl From<CoreInvitation> for InvitationFfi {
+    fn from(i: CoreInvitation) -> Self {
+        Self::from(&i)
+    }
 }
 
-/// FFI mirror of [`haven_core::circle::LeavePlan`].
+/// Key package bundle for publishing (FFI-friendly).
 ///
-/// Struct shape (rather than tagged enum) avoids pulling in Dart `freezed`
-/// for sealed classes while preserving all the information.
+/// A member's key package with their inbox relay list (FFI-friendly).
+///
+/// Used when adding members to a circle. Relay resolution follows a
+/// cascading fallback: inbox (kind 10050) → NIP-65 (kind 10002) → defaults.
 #[derive(Clone)]
-pub struct LeavePlanFfi {
-    /// Which branch of the state machine to execute.
-    pub kind: LeavePlanKindFfi,
-    /// Hex-encoded successor pubkey when `kind == AdminHandoff`, else `None`.
-    pub successor_hex: Option<String>,
+pub struct MemberKeyPackageFfi {
+    /// The key package event JSON (kind 30443 or legacy kind 443).
+    pub key_package_json: String,
+    /// Relay URLs from the member's inbox relay list (kind 10050).
+    pub inbox_relays: Vec<String>,
+    /// Fallback relay URLs from NIP-65 relay list (kind 10002).
+    pub nip65_relays: Vec<String>,
 }
 
-impl std::fmt::Debug for LeavePlanFfi {
-    /// Redacts `successor_hex` to an 8-char prefix so log lines cannot be
-    /// used to correlate a user to a specific handoff.
+impl std::fmt::Debug for MemberKeyPackageFfi {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        let redacted = self
-            .successor_hex
-            .as_deref()
-            .map(|h| h.chars().take(8).collect::<String>() + "…");
-        f.debug_struct("LeavePlanFfi")
-            .field("kind", &self.kind)
-            .field("successor_hex", &redacted)
+        f.debug_struct("MemberKeyPackageFfi")
+            .field("key_package_json", &"<redacted>")
+            .field("inbox_relays_count", &self.inbox_relays.len())
+            .field("nip65_relays_count", &self.nip65_relays.len())
             .finish()
     }
 }
 
-// ==================== Relay preferences (kind 10050 / 10002) ====================
-//
-// FFI mirror of `haven_core::circle::RelayType`. Compile-time exhaustive on
-// the Dart side so we never round-trip a stringly-typed slug across the
-// boundary. Conversions live next to the type so they are easy to audit.
-//
-// RelayTypeFfi decision (Dark Matter W2): the pre-migration `KeyPackage`
-// variant (kind 10051) is RETIRED in favor of `Nip65` (kind 10002). Under Dark
-// Matter, KeyPackages are discovered on the account's NIP-65 kind-10002 relays
-// (the kind-10051 list is abolished + retracted at cutover). To avoid a
-// storage/schema churn, `Nip65` maps onto the SAME underlying
-// `RelayType::KeyPackage` storage slot + publish toggle (`publish_kp_relay_list`)
-// — only the ON-WIRE publish kind changes from 10051 to 10002. So a user's
-// "where my KeyPackage is discoverable" relay list is stored exactly as before;
-// the publish/maintenance paths just emit kind 10002 (`r` tags) instead of
-// 10051 (`relay` tags). The core `RelayType::KeyPackage` (10051) survives only
-// for the one-time cutover RETRACTION of the abolished 10051 list.
-
-/// Category of relay preference managed per user.
+/// A gift-wrapped Welcome ready for publishing (FFI-friendly).
 ///
-/// Mirrors [`haven_core::circle::RelayType`] with the Dark Matter W2 rename:
-/// - [`RelayTypeFfi::Inbox`] → kind 10050 (NIP-17 welcome delivery).
-/// - [`RelayTypeFfi::Nip65`] → kind 10002 (NIP-65; KeyPackage discovery under
-///   Dark Matter). Persisted under the same slot the retired kind-10051
-///   `KeyPackage` list used, so no relay-preference data migrates.
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
-pub enum RelayTypeFfi {
-    /// Inbox relays (kind 10050, NIP-17).
-    Inbox,
-    /// NIP-65 relays (kind 10002) — where this account's KeyPackage is
-    /// discoverable under Dark Matter (W2, replacing the retired kind-10051
-    /// list). Stored under the `RelayType::KeyPackage` slot.
-    Nip65,
-}
-
-impl From<RelayTypeFfi> for haven_core::circle::RelayType {
-    fn from(t: RelayTypeFfi) -> Self {
-        match t {
-            RelayTypeFfi::Inbox => Self::Inbox,
-            // `Nip65` shares the persisted `KeyPackage` slot (see banner).
-            RelayTypeFfi::Nip65 => Self::KeyPackage,
-        }
-    }
+/// Contains the kind 1059 gift-wrapped event along with recipient
+/// information needed for relay publishing.
+#[derive(Clone)]
+pub struct GiftWrappedWelcomeFfi {
+    /// The recipient's Nostr public key (hex).
+    pub recipient_pubkey: String,
+    /// Relay URLs to publish this Welcome to (recipient's inbox relays).
+    pub recipient_relays: Vec<String>,
+    /// Later delivery tiers, tried only if no relay in `recipient_relays`
+    /// takes the Welcome.
+    pub fallback_relays: Vec<String>,
+    /// The gift-wrapped event JSON (kind 1059), ready to publish.
+    pub event_json: String,
 }
 
-impl From<haven_core::circle::RelayType> for RelayTypeFfi {
-    fn from(t: haven_core::circle::RelayType) -> Self {
-        match t {
-            haven_core::circle::RelayType::Inbox => Self::Inbox,
-            haven_core::circle::RelayType::KeyPackage => Self::Nip65,
-        }
+impl std::fmt::Debug for GiftWrappedWelcomeFfi {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("GiftWrappedWelcomeFfi")
+            .field("recipient_pubkey", &"<redacted>")
+            .field("recipient_relays_count", &self.recipient_relays.len())
+            .field("fallback_relays_count", &self.fallback_relays.len())
+            .field("event_json", &"<redacted>")
+            .finish()
     }
 }
 
-/// The on-wire replaceable-list kind published for a relay-preference category
-/// under Dark Matter: 10050 for Inbox, **10002 (NIP-65)** for the KeyPackage-
-/// discovery list (W2; the kind-10051 list is retired).
-fn relay_list_wire_kind(relay_type: RelayTypeFfi) -> nostr::Kind {
-    match relay_type {
-        RelayTypeFfi::Inbox => nostr::Kind::InboxRelays,
-        RelayTypeFfi::Nip65 => nostr::Kind::RelayList,
-    }
+/// A publish-before-apply token (FFI mirror of `PendingStateRef`).
+///
+/// The Dark Matter engine stages a group-evolving commit and returns an opaque
+/// `PendingStateRef` (a `u64` newtype). Haven publishes the commit (and, for an
+/// Add, the welcomes), and ONLY after ≥1 relay returns OK confirms the token via
+/// [`CircleManagerFfi::confirm_published`] so the engine applies the commit and
+/// advances the epoch (Rule 13, publish-before-apply). On publish FAILURE the
+/// token is rolled back via [`CircleManagerFfi::publish_failed`]. The `token` is
+/// an in-memory session handle — meaningless across a process restart, never
+/// persisted, never published.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PendingStateRefFfi {
+    /// The opaque engine token (a `PendingStateRef` `u64`).
+    pub token: u64,
 }
 
-/// Builds the signed replaceable relay-list event for `relay_type`, choosing
-/// the correct Dark Matter wire form: kind-10050 `relay` tags for Inbox,
-/// kind-10002 `r` tags for the NIP-65 KeyPackage-discovery list (W2).
-fn build_relay_list_event_for(
-    relay_type: RelayTypeFfi,
-    keys: &nostr::Keys,
-    urls: &[String],
-    created_at: Option<i64>,
-) -> Result<nostr::Event, String> {
-    match relay_type {
-        RelayTypeFfi::Inbox => haven_core::relay::build_relay_list_event(
-            keys,
-            haven_core::circle::RelayType::Inbox,
-            urls,
-            created_at,
-        ),
-        RelayTypeFfi::Nip65 => {
-            haven_core::relay::build_nip65_relay_list_event(keys, urls, created_at)
-        }
+impl From<PendingStateRef> for PendingStateRefFfi {
+    fn from(p: PendingStateRef) -> Self {
+        Self { token: p.as_u64() }
     }
-    .map_err(|e| format!("Failed to build relay list event: {e}"))
 }
 
-/// Builds the "empty replacement" event used to unpublish a relay-list category,
-/// in the correct Dark Matter wire form: an empty kind-10050 replacement for
-/// Inbox, an empty kind-10002 (NIP-65) replacement for the KeyPackage-discovery
-/// list (W2). `last_published_at` floors the `created_at` so the replacement
-/// strictly supersedes the previous list across clock skew.
-fn build_relay_list_unpublish_for(
-    relay_type: RelayTypeFfi,
-    keys: &nostr::Keys,
-    last_published_at: Option<i64>,
-) -> Result<nostr::Event, String> {
-    match relay_type {
-        RelayTypeFfi::Inbox => haven_core::relay::build_unpublish_event(
-            keys,
-            haven_core::circle::RelayType::Inbox,
-            last_published_at,
-        ),
-        RelayTypeFfi::Nip65 => haven_core::relay::build_nip65_relay_list_event(
-            keys,
-            &[],
-            Some(haven_core::relay::superseding_created_at(last_published_at)),
-        ),
+impl From<PendingStateRefFfi> for PendingStateRef {
+    fn from(p: PendingStateRefFfi) -> Self {
+        Self::new(p.token)
     }
-    .map_err(|e| format!("Failed to build replacement: {e}"))
 }
 
-/// Outcome of a [`CircleManagerFfi::build_relay_list_publish`] call.
+/// Result of circle creation (FFI-friendly).
 ///
-/// The FFI builds the signed event AND resolves the publish targets
-/// atomically with the toggle check, then hands them to Dart. Dart must
-/// publish using the returned `targets` exactly — it must NOT widen them
-/// or fall back to a Dart-side default. The toggle integrity guarantee
-/// rests on the fact that Dart cannot get an event without first calling
-/// this method, which short-circuits to `suppressed=true` when the toggle
-/// is off.
+/// Publish-before-apply (Rule 13): publish `welcome_events`, then confirm
+/// `pending` via [`CircleManagerFfi::confirm_published`] once ≥1 relay ACKs a
+/// welcome (or roll back via [`CircleManagerFfi::publish_failed`]).
 #[derive(Debug, Clone)]
-pub struct BuiltRelayListEventFfi {
-    /// Signed event JSON, ready for `RelayManagerFfi::publish_event`.
-    /// `None` when `suppressed` is `true`.
-    pub event_json: Option<String>,
-    /// Hex-encoded event id; `None` when suppressed.
-    pub event_id_hex: Option<String>,
-    /// Resolved publish targets — the user's own configured relays for
-    /// `relay_type`, deduplicated and nothing else. Two-plane model: the
-    /// public default set is NEVER force-unioned in, so a private relay is
-    /// never published to a public relay. Empty when suppressed.
-    pub targets: Vec<String>,
-    /// Numeric Nostr kind (10050 or 10051). `None` when suppressed.
-    pub kind: Option<u16>,
-    /// Unix-seconds `created_at` from the signed event. Pass this back
-    /// to [`CircleManagerFfi::record_published_relay_list`] so the
-    /// recorded `published_at` matches the timestamp other relays will
-    /// see — this is what
-    /// [`haven_core::relay::publishers::build_unpublish_event`] reads to
-    /// defeat clock skew on the next replacement.
-    pub created_at_secs: Option<i64>,
-    /// `true` when the user's privacy toggle is OFF for this category;
-    /// the caller must NOT publish anything in this case.
-    pub suppressed: bool,
+pub struct CircleCreationResultFfi {
+    /// The created circle.
+    pub circle: CircleFfi,
+    /// Gift-wrapped Welcome events ready to publish to recipients.
+    /// Each is a kind 1059 event containing an encrypted kind 444 Welcome.
+    pub welcome_events: Vec<GiftWrappedWelcomeFfi>,
+    /// The pending group-creation state to confirm after ≥1-relay welcome ACK.
+    pub pending: PendingStateRefFfi,
 }
 
-/// Outcome of [`CircleManagerFfi::build_unpublish_relay_list`].
-///
-/// Two events: the empty-replacement (always populated when not suppressed)
-/// and the optional NIP-09 deletion (populated only when a previously
-/// published event is on record).
+/// A contact label with how many contacts carry it (FFI-friendly).
 #[derive(Debug, Clone)]
-pub struct BuiltUnpublishFfi {
-    /// Empty-replacement event JSON. Always populated when `suppressed`
-    /// is false.
-    pub replacement_event_json: Option<String>,
-    /// Best-effort NIP-09 deletion JSON. Populated only when a prior
-    /// publication was recorded.
-    pub deletion_event_json: Option<String>,
-    /// Resolved targets. For `build_unpublish_relay_list` (full opt-out)
-    /// these are the user's own configured relays (no default union). For
-    /// `build_relay_removal_scrub` these are the specific dropped relays.
-    pub targets: Vec<String>,
-    /// `true` when nothing should be published (no prior record AND
-    /// toggle was already off — there's nothing to unpublish).
-    pub suppressed: bool,
+pub struct ContactLabelFfi {
+    /// Normalized label (trimmed, lowercase).
+    pub label: String,
+    /// Number of contacts with this label.
+    pub contact_count: u32,
 }
 
-/// Serializes a group-evolving commit event to JSON for the Dart publish path.
-fn commit_event_to_json(event: &nostr::Event) -> Result<String, String> {
-    serde_json::to_string(event).map_err(|e| format!("Failed to serialize commit event: {e}"))
+/// Result of creating a circle from a contact label (FFI-friendly).
+#[derive(Clone)]
+pub struct LabeledCircleCreationFfi {
+    /// The staged circle; publish and confirm as for `create_circle`.
+    pub creation: CircleCreationResultFfi,
+    /// Labeled contacts left out because no key package was found (hex).
+    pub without_key_package: Vec<String>,
 }
 
-/// Converts a core [`haven_core::circle::CommitToPublish`] into its FFI mirror.
-fn convert_commit_to_publish(
-    commit: haven_core::circle::CommitToPublish,
-) -> Result<CommitToPublishFfi, String> {
-    Ok(CommitToPublishFfi {
-        commit_event_json: commit_event_to_json(&commit.commit_event)?,
-        pending: commit.pending.into(),
-    })
+impl std::fmt::Debug for LabeledCircleCreationFfi {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("LabeledCircleCreationFfi")
+            .field("creation", &self.creation)
+            .field("without_key_package", &self.without_key_package.len())
+            .finish()
+    }
 }
 
-// ==================== FFI input validation helpers ====================
-//
-// The actual validators live in `haven_core::validation` so they can be
-// unit-tested from `cargo test -p haven-core` without the Flutter bridge.
-use haven_core::validation::{normalize_pubkey_hex, parse_nostr_group_id, validate_pubkey_hex};
-
-/// Circle manager (FFI wrapper).
-///
-/// High-level API for managing circles (location sharing groups).
-/// Combines MLS operations with local storage for circle metadata
-/// and contact information.
-///
-/// # Privacy Model
-///
-/// Haven uses a privacy-first approach:
-/// - User profiles (kind 0) are never published to relays
-/// - Contact info (display names, avatars) is stored locally only
-/// - Relays only see pubkeys, never usernames
-///
-/// # Thread Safety
+/// Result of adding members to an existing circle (FFI-friendly).
 ///
-/// This type is `Send + Sync` via `Arc<CoreCircleManager>`. The underlying
-/// `CircleManager` protects its SQLite connection with its own fine-grained
-/// internal `Mutex`, so wrapping it in an outer mutex here would only
-/// serialise every FFI call through a single global lock — a significant
-/// throughput bottleneck under concurrent Dart→Rust traffic. Blocking
-/// I/O work (SQLite, MDK) is dispatched to `tokio::task::spawn_blocking`
-/// via [`run_blocking`] so it does not monopolise the async runtime's
-/// worker threads. Pure-CPU `#[frb(sync)]` methods (e.g. constant
-/// getters, relay list signing) bypass `run_blocking` and execute inline.
-#[frb(opaque)]
-pub struct CircleManagerFfi {
-    inner: Arc<CoreCircleManager>,
+/// Publish-before-apply (Rule 13): publish `commit_event_json`, confirm
+/// `pending`, THEN publish `welcome_events` (a welcome for a losing/unconfirmed
+/// commit references an epoch that never applied).
+#[derive(Clone)]
+pub struct AddMembersResultFfi {
+    /// JSON-serialized kind 445 evolution (Add commit) event, to publish to
+    /// the circle's relays before confirming `pending`.
+    pub commit_event_json: String,
+    /// Gift-wrapped Welcome events for the newly added members.
+    /// Each is a kind 1059 event containing an encrypted kind 444 Welcome.
+    /// Publish these only after `commit_event_json` is published and `pending`
+    /// is confirmed.
+    pub welcome_events: Vec<GiftWrappedWelcomeFfi>,
+    /// The pending commit to confirm after ≥1-relay commit ACK.
+    pub pending: PendingStateRefFfi,
 }
 
-// Compile-time assertion: the refactor above is only sound if the core
-// manager is actually `Send + Sync`. If this ever stops compiling, the
-// outer mutex must be reinstated (or the root cause fixed upstream).
-const _: fn() = || {
-    fn assert_send_sync<T: Send + Sync>() {}
-    assert_send_sync::<CoreCircleManager>();
-};
+impl std::fmt::Debug for AddMembersResultFfi {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        // The commit-event JSON can embed the MLS group ID in its tags;
+        // redact it. Welcome events redact themselves via their own Debug impl.
+        f.debug_struct("AddMembersResultFfi")
+            .field("commit_event_json", &"<redacted>")
+            .field("welcome_events_count", &self.welcome_events.len())
+            .field("pending", &self.pending)
+            .finish()
+    }
+}
 
-/// Extracts the NIP-33 `d` tag value from a probed kind-30443 `KeyPackage`
-/// event, if present. Used by [`RelayManagerFfi::maintain_key_package`] to
-/// build the on-relay snapshot. Never logged.
-#[inline]
-fn kp_event_d_tag(event: &nostr::Event) -> Option<String> {
-    event.tags.iter().find_map(|t| {
-        let s = t.as_slice();
-        (s.len() >= 2 && s[0] == "d").then(|| s[1].clone())
-    })
+/// A member who was invited but has not joined yet (FFI mirror of
+/// `haven_core::circle::UnjoinedMember`).
+#[derive(Debug, Clone)]
+pub struct UnjoinedMemberFfi {
+    /// The member's public key (hex).
+    pub member_pubkey: String,
+    /// When they were first invited (Unix timestamp).
+    pub invited_at: i64,
+    /// How often the invitation was resent.
+    pub resend_count: u32,
+    /// Earliest the invitation can be resent again, or `None` once the
+    /// resends are used up.
+    pub next_resend_at: Option<i64>,
 }
 
-/// Extracts the `["relay", <url>]` URLs from a probed kind-10050 (Inbox)
-/// relay-list event, for drift detection in
-/// [`RelayManagerFfi::maintain_relay_list_category`]. Never logged.
-#[inline]
-fn relay_list_urls(event: &nostr::Event) -> Vec<String> {
-    event
+impl From<&haven_core::circle::UnjoinedMember> for UnjoinedMemberFfi {
+    fn from(m: &haven_core::circle::UnjoinedMember) -> Self {
+        Self {
+            member_pubkey: normalize_pubkey_hex(&m.member_pubkey),
+            invited_at: m.invited_at,
+            resend_count: m.resend_count,
+            next_resend_at: m.next_resend_at,
+        }
+    }
+}
+
+/// A scheduled check-in (FFI mirror of
+/// `haven_core::safety::CheckinRule`).
+#[derive(Debug, Clone)]
+pub struct CheckinRuleFfi {
+    /// Rule id (hex).
+    pub rule_id: String,
+    /// The circle's MLS group ID.
+    pub mls_group_id: Vec<u8>,
+    /// The member expected to check in (hex).
+    pub member_pubkey: String,
+    /// Local deadline, in minutes after midnight.
+    pub deadline_minute: u16,
+    /// How long before the deadline a location counts, in minutes.
+    pub window_minutes: u16,
+    /// Days the rule applies on (bit 0 is Monday, bit 6 Sunday).
+    pub weekdays: u8,
+    /// When the rule was set up (Unix timestamp).
+    pub created_at: i64,
+    /// When the member's latest location was taken (Unix timestamp).
+    pub last_seen_at: Option<i64>,
+    /// The latest deadline whose alert was dismissed (Unix timestamp).
+    pub acknowledged_deadline: Option<i64>,
+}
+
+impl From<&haven_core::safety::CheckinRule> for CheckinRuleFfi {
+    fn from(r: &haven_core::safety::CheckinRule) -> Self {
+        Self {
+            rule_id: r.rule_id.clone(),
+            mls_group_id: r.mls_group_id.as_slice().to_vec(),
+            member_pubkey: normalize_pubkey_hex(&r.member_pubkey),
+            deadline_minute: r.deadline_minute,
+            window_minutes: r.window_minutes,
+            weekdays: r.weekdays,
+            created_at: r.created_at,
+            last_seen_at: r.last_seen_at,
+            acknowledged_deadline: r.acknowledged_deadline,
+        }
+    }
+}
+
+/// A deadline that passed without the member checking in (FFI mirror of
+/// `haven_core::safety::MissedCheckin`).
+#[derive(Debug, Clone)]
+pub struct MissedCheckinFfi {
+    /// The rule that raised it.
+    pub rule_id: String,
+    /// The circle's MLS group ID.
+    pub mls_group_id: Vec<u8>,
+    /// The member who did not check in (hex).
+    pub member_pubkey: String,
+    /// The missed deadline (Unix timestamp); pass it back to dismiss.
+    pub deadline_at: i64,
+    /// When the member's latest location was taken, if any was seen.
+    pub last_seen_at: Option<i64>,
+}
+
+impl From<&haven_core::safety::MissedCheckin> for MissedCheckinFfi {
+    fn from(m: &haven_core::safety::MissedCheckin) -> Self {
+        Self {
+            rule_id: m.rule_id.clone(),
+            mls_group_id: m.mls_group_id.as_slice().to_vec(),
+            member_pubkey: normalize_pubkey_hex(&m.member_pubkey),
+            deadline_at: m.deadline_at,
+            last_seen_at: m.last_seen_at,
+        }
+    }
+}
+
+/// What a proximity alert measures the member's distance to (FFI mirror of
+/// `haven_core::location::ProximityTarget`). Set `target_pubkey` for another
+/// member, or the three `place_*` fields for a place.
+#[derive(Clone)]
+pub struct ProximityTargetFfi {
+    /// The member to measure to (hex).
+    pub target_pubkey: Option<String>,
+    /// The place's user-facing label.
+    pub place_label: Option<String>,
+    /// The place's latitude in degrees.
+    pub place_latitude: Option<f64>,
+    /// The place's longitude in degrees.
+    pub place_longitude: Option<f64>,
+}
+
+impl std::fmt::Debug for ProximityTargetFfi {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ProximityTargetFfi")
+            .field("has_target_pubkey", &self.target_pubkey.is_some())
+            .field("place_label", &self.place_label)
+            .field("place_latitude", &"<redacted>")
+            .field("place_longitude", &"<redacted>")
+            .finish()
+    }
+}
+
+impl From<&haven_core::location::ProximityTarget> for ProximityTargetFfi {
+    fn from(t: &haven_core::location::ProximityTarget) -> Self {
+        use haven_core::location::ProximityTarget as T;
+        match t {
+            T::Member(pubkey) => Self {
+                target_pubkey: Some(normalize_pubkey_hex(pubkey)),
+                place_label: None,
+                place_latitude: None,
+                place_longitude: None,
+            },
+            T::Place {
+                label,
+                latitude,
+                longitude,
+            } => Self {
+                target_pubkey: None,
+                place_label: Some(label.clone()),
+                place_latitude: Some(*latitude),
+                place_longitude: Some(*longitude),
+            },
+        }
+    }
+}
+
+fn proximity_target_from_ffi(
+    t: ProximityTargetFfi,
+) -> Result<haven_core::location::ProximityTarget, HavenErrorFfi> {
+    use haven_core::location::ProximityTarget as T;
+    match (
+        t.target_pubkey,
+        t.place_label,
+        t.place_latitude,
+        t.place_longitude,
+    ) {
+        (Some(pubkey), None, None, None) => Ok(T::Member(pubkey)),
+        (None, Some(label), Some(latitude), Some(longitude)) => Ok(T::Place {
+            label,
+            latitude,
+            longitude,
+        }),
+        _ => Err(HavenErrorFfi::invalid_input(
+            "Proximity target must be a member or a place",
+        )),
+    }
+}
+
+/// A proximity alert rule (FFI mirror of
+/// `haven_core::location::ProximityRule`).
+#[derive(Debug, Clone)]
+pub struct ProximityRuleFfi {
+    /// Rule id (hex).
+    pub rule_id: String,
+    /// The circle's MLS group ID.
+    pub mls_group_id: Vec<u8>,
+    /// The member watched (hex).
+    pub member_pubkey: String,
+    /// What the member's distance is measured to.
+    pub target: ProximityTargetFfi,
+    /// Alert when the member comes within this many meters.
+    pub range_m: f64,
+    /// When the rule was set up (Unix timestamp).
+    pub created_at: i64,
+    /// Whether the member was within range at the last evaluation.
+    pub within: bool,
+}
+
+impl From<&haven_core::location::ProximityRule> for ProximityRuleFfi {
+    fn from(r: &haven_core::location::ProximityRule) -> Self {
+        Self {
+            rule_id: r.rule_id.clone(),
+            mls_group_id: r.mls_group_id.as_slice().to_vec(),
+            member_pubkey: normalize_pubkey_hex(&r.member_pubkey),
+            target: ProximityTargetFfi::from(&r.target),
+            range_m: r.range_m,
+            created_at: r.created_at,
+            within: r.within,
+        }
+    }
+}
+
+/// Distance and rough ETA between a member and a target (FFI mirror of
+/// `haven_core::location::ProximityEstimate`).
+#[derive(Debug, Clone, Copy)]
+pub struct ProximityEstimateFfi {
+    /// Distance between the two shared positions, in meters.
+    pub distance_m: f64,
+    /// How far the true distance may be from `distance_m`, in meters.
+    pub error_m: f64,
+    /// Seconds until arrival at the recent approach speed; `None` while the
+    /// member is not approaching.
+    pub eta_secs: Option<u64>,
+}
+
+impl From<&haven_core::location::ProximityEstimate> for ProximityEstimateFfi {
+    fn from(e: &haven_core::location::ProximityEstimate) -> Self {
+        Self {
+            distance_m: e.distance_m,
+            error_m: e.error_m,
+            eta_secs: e.eta_secs,
+        }
+    }
+}
+
+/// A member who just came within a proximity rule's range (FFI mirror of
+/// `haven_core::location::ProximityAlert`).
+#[derive(Debug, Clone)]
+pub struct ProximityAlertFfi {
+    /// The rule that raised it.
+    pub rule_id: String,
+    /// The circle's MLS group ID.
+    pub mls_group_id: Vec<u8>,
+    /// The member who came near (hex).
+    pub member_pubkey: String,
+    /// What they came near.
+    pub target: ProximityTargetFfi,
+    /// Their distance and ETA at the time.
+    pub estimate: ProximityEstimateFfi,
+    /// When the member's position was taken (Unix timestamp).
+    pub seen_at: i64,
+}
+
+impl From<&haven_core::location::ProximityAlert> for ProximityAlertFfi {
+    fn from(a: &haven_core::location::ProximityAlert) -> Self {
+        Self {
+            rule_id: a.rule_id.clone(),
+            mls_group_id: a.mls_group_id.as_slice().to_vec(),
+            member_pubkey: normalize_pubkey_hex(&a.member_pubkey),
+            target: ProximityTargetFfi::from(&a.target),
+            estimate: ProximityEstimateFfi::from(&a.estimate),
+            seen_at: a.seen_at,
+        }
+    }
+}
+
+/// A group-evolving commit awaiting publish + confirm (remove / relay update /
+/// admin change) — FFI mirror of `haven_core::circle::CommitToPublish`.
+///
+/// Publish-before-apply (Rule 13): publish `commit_event_json` to the circle's
+/// relays, then confirm `pending` via [`CircleManagerFfi::confirm_published`]
+/// on ≥1-relay ACK (or roll back via [`CircleManagerFfi::publish_failed`]).
+#[derive(Clone)]
+pub struct CommitToPublishFfi {
+    /// JSON-serialized kind 445 commit event to publish to the circle's relays.
+    pub commit_event_json: String,
+    /// The pending commit to confirm after ≥1-relay ACK.
+    pub pending: PendingStateRefFfi,
+}
+
+impl std::fmt::Debug for CommitToPublishFfi {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        // The commit-event JSON's `h` tag carries the nostr_group_id; redact.
+        f.debug_struct("CommitToPublishFfi")
+            .field("commit_event_json", &"<redacted>")
+            .field("pending", &self.pending)
+            .finish()
+    }
+}
+
+/// Encrypted location event ready for relay publishing (FFI-friendly).
+///
+/// Contains the signed kind 445 event and routing metadata. `event_id` is
+/// the id the publish result, the outbox and a later retraction refer to.
+#[derive(Debug, Clone)]
+pub struct EncryptedLocationFfi {
+    /// The event's id (64-char lowercase hex).
+    pub event_id: String,
+    /// JSON-serialized signed Nostr event (kind 445).
+    pub event_json: String,
+    /// Nostr group ID (32 bytes, for h-tag relay routing).
+    pub nostr_group_id: Vec<u8>,
+    /// Relay URLs to publish to.
+    pub relays: Vec<String>,
+}
+
+impl EncryptedLocationFfi {
+    fn new(
+        event: &nostr::Event,
+        nostr_group_id: &[u8; 32],
+        relays: Vec<String>,
+    ) -> Result<Self, HavenErrorFfi> {
+        Ok(Self {
+            event_id: event.id.to_hex(),
+            event_json: serde_json::to_string(event)
+                .map_err(|e| HavenErrorFfi::internal(format!("Failed to serialize event: {e}")))?,
+            nostr_group_id: nostr_group_id.to_vec(),
+            relays,
+        })
+    }
+}
+
+/// Signed NIP-09 deletion of a published location (FFI-friendly).
+#[derive(Debug, Clone)]
+pub struct LocationRetractionFfi {
+    /// The deletion event's id (64-char lowercase hex).
+    pub event_id: String,
+    /// The id of the location it deletes (64-char lowercase hex).
+    pub retracted_event_id: String,
+    /// JSON-serialized signed Nostr event (kind 5).
+    pub event_json: String,
+    /// Relay URLs to publish to (the circle's relays).
+    pub relays: Vec<String>,
+}
+
+/// Result of fanning an SOS out to several circles (FFI-friendly).
+///
+/// Mirrors `haven_core::emergency::SosFanout`. Each delivery is published
+/// exactly like an [`EncryptedLocationFfi`]; `failed_group_ids` lists the
+/// circles the alert could not be encrypted for.
+#[derive(Clone)]
+pub struct SosFanoutFfi {
+    /// One ready-to-publish event per reachable circle.
+    pub deliveries: Vec<EncryptedLocationFfi>,
+    /// MLS group ids (raw bytes) of circles that were skipped.
+    pub failed_group_ids: Vec<Vec<u8>>,
+}
+
+impl std::fmt::Debug for SosFanoutFfi {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SosFanoutFfi")
+            .field("deliveries", &self.deliveries.len())
+            .field("failed_group_ids", &self.failed_group_ids.len())
+            .finish()
+    }
+}
+
+/// Sender's device motion state (FFI mirror of
+/// [`haven_core::location::MotionState`]).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MotionStateFfi {
+    /// Not moving.
+    Stationary,
+    /// Walking.
+    Walking,
+    /// Running.
+    Running,
+    /// Cycling.
+    Cycling,
+    /// In a vehicle.
+    Driving,
+    /// A state this version does not know.
+    Unknown,
+}
+
+impl From<haven_core::location::MotionState> for MotionStateFfi {
+    fn from(m: haven_core::location::MotionState) -> Self {
+        use haven_core::location::MotionState as M;
+        match m {
+            M::Stationary => Self::Stationary,
+            M::Walking => Self::Walking,
+            M::Running => Self::Running,
+            M::Cycling => Self::Cycling,
+            M::Driving => Self::Driving,
+            M::Unknown => Self::Unknown,
+        }
+    }
+}
+
+impl From<MotionStateFfi> for haven_core::location::MotionState {
+    fn from(m: MotionStateFfi) -> Self {
+        match m {
+            MotionStateFfi::Stationary => Self::Stationary,
+            MotionStateFfi::Walking => Self::Walking,
+            MotionStateFfi::Running => Self::Running,
+            MotionStateFfi::Cycling => Self::Cycling,
+            MotionStateFfi::Driving => Self::Driving,
+            MotionStateFfi::Unknown => Self::Unknown,
+        }
+    }
+}
+
+/// Device status shared alongside a location (FFI mirror of
+/// [`haven_core::location::DeviceStatus`]).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DeviceStatusFfi {
+    /// Battery level, 0–100.
+    pub battery_percent: Option<u8>,
+    /// Whether the device is charging.
+    pub charging: Option<bool>,
+    /// What the device is doing.
+    pub motion: Option<MotionStateFfi>,
+}
+
+impl From<haven_core::location::DeviceStatus> for DeviceStatusFfi {
+    fn from(d: haven_core::location::DeviceStatus) -> Self {
+        let d = d.sanitized();
+        Self {
+            battery_percent: d.battery_percent,
+            charging: d.charging,
+            motion: d.motion.map(Into::into),
+        }
+    }
+}
+
+impl From<DeviceStatusFfi> for haven_core::location::DeviceStatus {
+    fn from(d: DeviceStatusFfi) -> Self {
+        Self {
+            battery_percent: d.battery_percent,
+            charging: d.charging,
+            motion: d.motion.map(Into::into),
+        }
+    }
+}
+
+/// Altitude and floor level shared alongside a location (FFI mirror of
+/// [`haven_core::location::VerticalPosition`]).
+#[derive(Clone, Copy, PartialEq)]
+pub struct VerticalPositionFfi {
+    /// Altitude above sea level, in whole meters.
+    pub altitude_m: Option<f64>,
+    /// Floor level, `0` for the ground floor and negative below it.
+    pub floor: Option<i16>,
+}
+
+impl std::fmt::Debug for VerticalPositionFfi {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("VerticalPositionFfi")
+            .field("altitude_m", &self.altitude_m.map(|_| "<redacted>"))
+            .field("floor", &self.floor.map(|_| "<redacted>"))
+            .finish()
+    }
+}
+
+impl From<haven_core::location::VerticalPosition> for VerticalPositionFfi {
+    fn from(v: haven_core::location::VerticalPosition) -> Self {
+        let v = v.sanitized();
+        Self {
+            altitude_m: v.altitude_m,
+            floor: v.floor,
+        }
+    }
+}
+
+impl From<VerticalPositionFfi> for haven_core::location::VerticalPosition {
+    fn from(v: VerticalPositionFfi) -> Self {
+        Self {
+            altitude_m: v.altitude_m,
+            floor: v.floor,
+        }
+    }
+}
+
+/// What published events may reveal (FFI mirror of
+/// [`haven_core::privacy::PrivacySettings`]).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PrivacySettingsFfi {
+    /// Randomly move the time on published events by up to this many
+    /// minutes either way (0–10, `0` = off).
+    pub timestamp_fuzz_minutes: u32,
+    /// Dummy events per hour mixed in across all circles (0–12, `0` = off).
+    pub cover_traffic_per_hour: u32,
+}
+
+impl From<haven_core::privacy::PrivacySettings> for PrivacySettingsFfi {
+    fn from(s: haven_core::privacy::PrivacySettings) -> Self {
+        Self {
+            timestamp_fuzz_minutes: s.timestamp_fuzz_minutes,
+            cover_traffic_per_hour: s.cover_traffic_per_hour,
+        }
+    }
+}
+
+impl From<PrivacySettingsFfi> for haven_core::privacy::PrivacySettings {
+    fn from(s: PrivacySettingsFfi) -> Self {
+        Self {
+            timestamp_fuzz_minutes: s.timestamp_fuzz_minutes,
+            cover_traffic_per_hour: s.cover_traffic_per_hour,
+        }
+    }
+}
+
+/// Decrypted location from a peer (FFI-friendly).
+///
+/// Contains the sender identity and location data.
+#[derive(Clone)]
+pub struct DecryptedLocationFfi {
+    /// Sender's Nostr public key (hex-encoded).
+    pub sender_pubkey: String,
+    /// Latitude (exact GPS reading).
+    pub latitude: f64,
+    /// Longitude (exact GPS reading).
+    pub longitude: f64,
+    /// Geohash of the location.
+    pub geohash: String,
+    /// When the location was recorded (Unix seconds).
+    pub timestamp: i64,
+    /// When this location expires (Unix seconds).
+    pub expires_at: i64,
+    /// Battery, charging and motion state, if the sender shares them.
+    pub device_status: Option<DeviceStatusFfi>,
+    /// Altitude and floor level, if the sender shares them.
+    pub vertical_position: Option<VerticalPositionFfi>,
+    /// Precision of the coordinates as received (`"coarse"`,
+    /// `"approximate"` or `"exact"`), classified from the coordinates
+    /// themselves.
+    pub precision: String,
+    /// The precision the sender declared, by its stable name. A value from a
+    /// newer sender is passed through verbatim; treat unknown names as
+    /// informational.
+    pub declared_precision: Option<String>,
+}
+
+impl DecryptedLocationFfi {
+    /// Builds from a parsed location. `sender_pubkey` is normalized to
+    /// lowercase so the Dart self-compare against the cached own pubkey is
+    /// case-insensitive by construction.
+    fn from_location(sender_pubkey: &str, location: haven_core::location::LocationMessage) -> Self {
+        let precision = haven_core::location::precision::classify(&location)
+            .as_str()
+            .to_string();
+        Self {
+            precision,
+            declared_precision: location.precision.as_ref().map(|p| p.as_str().to_string()),
+            sender_pubkey: normalize_pubkey_hex(sender_pubkey),
+            latitude: location.latitude,
+            longitude: location.longitude,
+            geohash: location.geohash,
+            timestamp: location.timestamp.timestamp(),
+            expires_at: location.expires_at.timestamp(),
+            device_status: location.device_status.map(Into::into),
+            vertical_position: location.vertical_position.map(Into::into),
+        }
+    }
+}
+
+impl std::fmt::Debug for DecryptedLocationFfi {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("DecryptedLocationFfi")
+            .field("sender_pubkey", &"<redacted>")
+            .field("latitude", &"<redacted>")
+            .field("longitude", &"<redacted>")
+            .field("geohash", &"<redacted>")
+            .field("timestamp", &self.timestamp)
+            .field("expires_at", &self.expires_at)
+            .field("device_status", &self.device_status.map(|_| "<redacted>"))
+            .field(
+                "vertical_position",
+                &self.vertical_position.map(|_| "<redacted>"),
+            )
+            .field("precision", &self.precision)
+            .field("declared_precision", &self.declared_precision)
+            .finish()
+    }
+}
+
+/// A persisted last-known location for a circle member (FFI-friendly).
+///
+/// Mirrors `haven_core::circle::LastKnownLocation`. Returned from
+/// `CircleManagerFfi::snapshot_last_known_for_circle` so the Flutter
+/// layer can hydrate its in-memory cache on app start.
+#[derive(Clone)]
+pub struct LastKnownLocationFfi {
+    /// Nostr group ID (32 bytes) of the circle.
+    pub nostr_group_id: Vec<u8>,
+    /// Sender's Nostr public key (hex-encoded).
+    pub sender_pubkey: String,
+    /// Latitude (exact GPS reading).
+    pub latitude: f64,
+    /// Longitude (exact GPS reading).
+    pub longitude: f64,
+    /// Geohash of the location.
+    pub geohash: String,
+    /// Display name carried in the encrypted location message, if any.
+    pub display_name: Option<String>,
+    /// When the location was captured (Unix seconds).
+    pub timestamp: i64,
+    /// When the inner freshness window expires (Unix seconds).
+    pub expires_at: i64,
+    /// Row must be deleted after this Unix-seconds moment.
+    pub purge_after: i64,
+    /// When this row was last written (Unix seconds, receiver clock).
+    pub updated_at: i64,
+}
+
+impl std::fmt::Debug for LastKnownLocationFfi {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("LastKnownLocationFfi")
+            .field("sender_pubkey", &"<redacted>")
+            .field("latitude", &"<redacted>")
+            .field("longitude", &"<redacted>")
+            .field("geohash", &"<redacted>")
+            .field("display_name", &"<redacted>")
+            .field("timestamp", &self.timestamp)
+            .field("expires_at", &self.expires_at)
+            .field("purge_after", &self.purge_after)
+            .field("updated_at", &self.updated_at)
+            .finish()
+    }
+}
+
+impl From<haven_core::circle::LastKnownLocation> for LastKnownLocationFfi {
+    fn from(loc: haven_core::circle::LastKnownLocation) -> Self {
+        Self {
+            nostr_group_id: loc.nostr_group_id.to_vec(),
+            sender_pubkey: loc.sender_pubkey,
+            latitude: loc.latitude,
+            longitude: loc.longitude,
+            geohash: loc.geohash,
+            display_name: loc.display_name,
+            timestamp: loc.timestamp,
+            expires_at: loc.expires_at,
+            purge_after: loc.purge_after,
+            updated_at: loc.updated_at,
+        }
+    }
+}
+
+/// A location from the history (FFI mirror of
+/// `haven_core::circle::HistoryPoint`). Returned from
+/// `CircleManagerFfi::get_points_in_viewport`.
+#[derive(Clone)]
+pub struct HistoryPointFfi {
+    /// Nostr group ID (32 bytes) of the circle.
+    pub nostr_group_id: Vec<u8>,
+    /// Sender's Nostr public key (hex-encoded).
+    pub sender_pubkey: String,
+    /// Latitude (exact GPS reading).
+    pub latitude: f64,
+    /// Longitude (exact GPS reading).
+    pub longitude: f64,
+    /// When the location was captured (Unix seconds).
+    pub timestamp: i64,
+}
+
+impl std::fmt::Debug for HistoryPointFfi {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("HistoryPointFfi")
+            .field("sender_pubkey", &"<redacted>")
+            .field("latitude", &"<redacted>")
+            .field("longitude", &"<redacted>")
+            .field("timestamp", &self.timestamp)
+            .finish()
+    }
+}
+
+impl From<haven_core::circle::HistoryPoint> for HistoryPointFfi {
+    fn from(point: haven_core::circle::HistoryPoint) -> Self {
+        Self {
+            nostr_group_id: point.nostr_group_id.to_vec(),
+            sender_pubkey: point.sender_pubkey,
+            latitude: point.latitude,
+            longitude: point.longitude,
+            timestamp: point.timestamp,
+        }
+    }
+}
+
+/// One journey between two stays (FFI mirror of
+/// `haven_core::location::Trip`).
+#[derive(Debug, Clone, Copy)]
+pub struct TripFfi {
+    /// When the member set off (Unix timestamp).
+    pub started_at: i64,
+    /// When they arrived (Unix timestamp).
+    pub ended_at: i64,
+    /// Distance along the shared positions, in meters.
+    pub distance_m: f64,
+}
+
+impl From<&haven_core::location::Trip> for TripFfi {
+    fn from(trip: &haven_core::location::Trip) -> Self {
+        Self {
+            started_at: trip.started_at,
+            ended_at: trip.ended_at,
+            distance_m: trip.distance_m,
+        }
+    }
+}
+
+/// A member's trips over one local day (FFI mirror of
+/// `haven_core::location::DaySummary`). Times and distances only, no
+/// coordinates. Returned from `CircleManagerFfi::summarize_day`.
+#[derive(Debug, Clone)]
+pub struct DaySummaryFfi {
+    /// The day summarized (`YYYY-MM-DD`).
+    pub date: String,
+    /// The day's first position (Unix timestamp), if any.
+    pub first_seen_at: Option<i64>,
+    /// The day's last position (Unix timestamp), if any.
+    pub last_seen_at: Option<i64>,
+    /// The day's trips, earliest first.
+    pub trips: Vec<TripFfi>,
+    /// Distance covered by all trips, in meters.
+    pub distance_m: f64,
+    /// Time spent on trips, in seconds.
+    pub moving_secs: i64,
+}
+
+impl From<&haven_core::location::DaySummary> for DaySummaryFfi {
+    fn from(summary: &haven_core::location::DaySummary) -> Self {
+        Self {
+            date: summary.date.to_string(),
+            first_seen_at: summary.first_seen_at,
+            last_seen_at: summary.last_seen_at,
+            trips: summary.trips.iter().map(TripFfi::from).collect(),
+            distance_m: summary.distance_m(),
+            moving_secs: summary.moving_secs(),
+        }
+    }
+}
+
+/// A member's newest location and what to show for it (FFI mirror of
+/// `haven_core::circle::MemberLocation`).
+#[derive(Clone, Debug)]
+pub struct MemberLocationFfi {
+    /// The newest stored location, however old.
+    pub latest: LastKnownLocationFfi,
+    /// `latest` while within the circle's display threshold, `None` once it
+    /// is older. Draw this one.
+    pub effective_display_location: Option<LastKnownLocationFfi>,
+}
+
+impl From<haven_core::circle::MemberLocation> for MemberLocationFfi {
+    fn from(m: haven_core::circle::MemberLocation) -> Self {
+        Self {
+            latest: m.latest.into(),
+            effective_display_location: m.effective_display_location.map(Into::into),
+        }
+    }
+}
+
+/// A local-only circular geofence (FFI-friendly).
+///
+/// Mirrors `haven_core::location::Geofence`. Geofences never leave the device;
+/// they are passed in per call to [`CircleManagerFfi::get_circle_status`].
+#[derive(Clone)]
+pub struct GeofenceFfi {
+    /// User-facing label (e.g. "Home").
+    pub label: String,
+    /// Center latitude in degrees.
+    pub latitude: f64,
+    /// Center longitude in degrees.
+    pub longitude: f64,
+    /// Radius in meters.
+    pub radius_m: f64,
+}
+
+impl std::fmt::Debug for GeofenceFfi {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("GeofenceFfi")
+            .field("label", &self.label)
+            .field("latitude", &"<redacted>")
+            .field("longitude", &"<redacted>")
+            .field("radius_m", &self.radius_m)
+            .finish()
+    }
+}
+
+impl From<GeofenceFfi> for haven_core::location::Geofence {
+    fn from(g: GeofenceFfi) -> Self {
+        Self::new(g.label, g.latitude, g.longitude, g.radius_m)
+    }
+}
+
+/// Member count inside one geofence (FFI-friendly).
+#[derive(Debug, Clone)]
+pub struct GeofenceOccupancyFfi {
+    /// The geofence label, as supplied by the caller.
+    pub label: String,
+    /// Number of members whose fresh location is inside the geofence.
+    pub member_count: u32,
+}
+
+/// Compact "where is everyone" summary for widgets and watch complications.
+///
+/// Mirrors `haven_core::circle::CircleStatus`. Carries counts only — no
+/// coordinates and no pubkeys.
+#[derive(Debug, Clone)]
+pub struct CircleStatusFfi {
+    /// Members counted (the roster minus the local user).
+    pub total_members: u32,
+    /// Per-geofence counts, in the order the geofences were supplied.
+    pub geofences: Vec<GeofenceOccupancyFfi>,
+    /// Members with a fresh location outside every geofence.
+    pub elsewhere: u32,
+    /// Members with no fresh location.
+    pub unknown: u32,
+}
+
+impl From<haven_core::circle::CircleStatus> for CircleStatusFfi {
+    fn from(s: haven_core::circle::CircleStatus) -> Self {
+        Self {
+            total_members: s.total_members,
+            geofences: s
+                .geofences
+                .into_iter()
+                .map(|g| GeofenceOccupancyFfi {
+                    label: g.label,
+                    member_count: g.member_count,
+                })
+                .collect(),
+            elsewhere: s.elsewhere,
+            unknown: s.unknown,
+        }
+    }
+}
+
+/// How current a member's location is (mirrors
+/// `haven_core::circle::FreshnessBucket`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FreshnessBucketFfi {
+    /// Within the sender's freshness window.
+    Live,
+    /// Expired, but captured within the last hour.
+    Recent,
+    /// Older than that, but still cached.
+    Stale,
+    /// No location cached.
+    Unknown,
+}
+
+impl From<haven_core::circle::FreshnessBucket> for FreshnessBucketFfi {
+    fn from(b: haven_core::circle::FreshnessBucket) -> Self {
+        use haven_core::circle::FreshnessBucket as B;
+        match b {
+            B::Live => Self::Live,
+            B::Recent => Self::Recent,
+            B::Stale => Self::Stale,
+            B::Unknown => Self::Unknown,
+        }
+    }
+}
+
+/// How far a member is from the local user (mirrors
+/// `haven_core::circle::DistanceBucket`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DistanceBucketFfi {
+    /// Under 1 km.
+    Nearby,
+    /// 1–10 km.
+    Local,
+    /// 10–100 km.
+    Regional,
+    /// 100 km or more.
+    Far,
+    /// No own position, or no location for the member.
+    Unknown,
+}
+
+impl From<haven_core::circle::DistanceBucket> for DistanceBucketFfi {
+    fn from(b: haven_core::circle::DistanceBucket) -> Self {
+        use haven_core::circle::DistanceBucket as B;
+        match b {
+            B::Nearby => Self::Nearby,
+            B::Local => Self::Local,
+            B::Regional => Self::Regional,
+            B::Far => Self::Far,
+            B::Unknown => Self::Unknown,
+        }
+    }
+}
+
+/// One member in a [`GlanceCircleFfi`].
+#[derive(Debug, Clone)]
+pub struct GlanceMemberFfi {
+    /// Up to two uppercase initials, or `?`.
+    pub initials: String,
+    /// How current the member's location is.
+    pub freshness: FreshnessBucketFfi,
+    /// How far the member is from the local user.
+    pub distance: DistanceBucketFfi,
+}
+
+/// One circle in a [`GlanceableSnapshotFfi`].
+#[derive(Debug, Clone)]
+pub struct GlanceCircleFfi {
+    /// The circle's local name, shortened.
+    pub name: String,
+    /// Every member except the local user.
+    pub members: Vec<GlanceMemberFfi>,
+}
+
+/// Minimal snapshot for watch complications and home-screen widgets.
+///
+/// Mirrors `haven_core::circle::GlanceableSnapshot`. Carries no coordinates,
+/// pubkeys or exact timestamps, so it is safe to hand to a widget extension
+/// or a paired watch that stores it outside the app's protected storage.
+#[derive(Debug, Clone)]
+pub struct GlanceableSnapshotFfi {
+    /// When the snapshot was assembled (Unix seconds).
+    pub generated_at: i64,
+    /// Visible circles.
+    pub circles: Vec<GlanceCircleFfi>,
+}
+
+impl From<haven_core::circle::GlanceableSnapshot> for GlanceableSnapshotFfi {
+    fn from(s: haven_core::circle::GlanceableSnapshot) -> Self {
+        Self {
+            generated_at: s.generated_at,
+            circles: s
+                .circles
+                .into_iter()
+                .map(|c| GlanceCircleFfi {
+                    name: c.name,
+                    members: c
+                        .members
+                        .into_iter()
+                        .map(|m| GlanceMemberFfi {
+                            initials: m.initials,
+                            freshness: m.freshness.into(),
+                            distance: m.distance.into(),
+                        })
+                        .collect(),
+                })
+                .collect(),
+        }
+    }
+}
+
+/// A user's decision about one relay (mirrors
+/// `haven_core::relay::LocalRelayOverride`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RelayOverrideFfi {
+    /// Always block this relay.
+    Block,
+    /// Never block this relay, even if the community list does.
+    Allow,
+}
+
+impl From<RelayOverrideFfi> for haven_core::relay::LocalRelayOverride {
+    fn from(d: RelayOverrideFfi) -> Self {
+        match d {
+            RelayOverrideFfi::Block => Self::Block,
+            RelayOverrideFfi::Allow => Self::Allow,
+        }
+    }
+}
+
+impl From<haven_core::relay::LocalRelayOverride> for RelayOverrideFfi {
+    fn from(d: haven_core::relay::LocalRelayOverride) -> Self {
+        match d {
+            haven_core::relay::LocalRelayOverride::Block => Self::Block,
+            haven_core::relay::LocalRelayOverride::Allow => Self::Allow,
+        }
+    }
+}
+
+/// One stored per-relay decision.
+#[derive(Debug, Clone)]
+pub struct RelayOverrideEntryFfi {
+    /// Canonical relay URL.
+    pub url: String,
+    /// The user's decision.
+    pub decision: RelayOverrideFfi,
+}
+
+/// Mirrors `haven_core::privacy::PrivacyFacts`, flattened for FFI.
+///
+/// Every value is read from the enforcing configuration at call time; the
+/// privacy dashboard renders this instead of hardcoded copy.
+#[derive(Debug, Clone)]
+pub struct PrivacyFactsFfi {
+    /// Schema version of the facts.
+    pub version: u32,
+    /// How long a received location is shown as "fresh", in seconds.
+    pub location_freshness_secs: i64,
+    /// How long a received location is kept on this device, in seconds.
+    pub location_local_retention_secs: u64,
+    /// NIP-40 expiration requested from relays for location events, in seconds.
+    pub relay_location_retention_secs: u64,
+    /// How long processed invitation ids are kept, in seconds.
+    pub processed_invitation_retention_secs: i64,
+    /// Coordinate precision sent to members (e.g. `"exact_gps"`).
+    pub shared_precision: String,
+    /// Geohash length carried alongside the coordinates.
+    pub geohash_precision: u8,
+    /// Location metadata that never leaves the device.
+    pub stripped_location_fields: Vec<String>,
+    /// Whether location content is end-to-end encrypted.
+    pub end_to_end_encrypted: bool,
+    /// Whether the local circle database is encrypted at rest.
+    pub local_database_encrypted: bool,
+    /// How relay connections are routed (e.g. `"direct"`).
+    pub network_routing: String,
+    /// Inbox relays (kind 10050).
+    pub inbox_relays: Vec<String>,
+    /// Key-package relays (kind 10051).
+    pub key_package_relays: Vec<String>,
+    /// Distinct relays used by the user's circles.
+    pub circle_relays: Vec<String>,
+}
+
+impl From<haven_core::privacy::PrivacyFacts> for PrivacyFactsFfi {
+    fn from(f: haven_core::privacy::PrivacyFacts) -> Self {
+        Self {
+            version: f.version,
+            location_freshness_secs: f.retention.location_freshness_secs,
+            location_local_retention_secs: f.retention.location_local_retention_secs,
+            relay_location_retention_secs: f.retention.relay_location_retention_secs,
+            processed_invitation_retention_secs: f.retention.processed_invitation_retention_secs,
+            shared_precision: f.shared_precision.as_str().to_string(),
+            geohash_precision: f.geohash_precision,
+            stripped_location_fields: f.stripped_location_fields,
+            end_to_end_encrypted: f.end_to_end_encrypted,
+            local_database_encrypted: f.local_database_encrypted,
+            network_routing: f.network_routing.as_str().to_string(),
+            inbox_relays: f.inbox_relays,
+            key_package_relays: f.key_package_relays,
+            circle_relays: f.circle_relays,
+        }
+    }
+}
+
+/// A fetched event's canonical NIP-01 fields, without JSON.
+///
+/// Mirrors `haven_core::relay::CompactEvent`; byte fields are fixed-length
+/// (`id`/`pubkey` 32, `sig` 64) and re-verified when handed back to the core.
+#[derive(Debug, Clone)]
+pub struct CompactEventFfi {
+    /// Event id (32 bytes).
+    pub id: Vec<u8>,
+    /// Author public key (32 bytes).
+    pub pubkey: Vec<u8>,
+    /// Unix timestamp (seconds).
+    pub created_at: u64,
+    /// Event kind.
+    pub kind: u16,
+    /// Tags, each a list of strings.
+    pub tags: Vec<Vec<String>>,
+    /// Event content.
+    pub content: String,
+    /// Schnorr signature (64 bytes).
+    pub sig: Vec<u8>,
+}
+
+impl From<haven_core::relay::CompactEvent> for CompactEventFfi {
+    fn from(e: haven_core::relay::CompactEvent) -> Self {
+        Self {
+            id: e.id.to_vec(),
+            pubkey: e.pubkey.to_vec(),
+            created_at: e.created_at,
+            kind: e.kind,
+            tags: e.tags,
+            content: e.content,
+            sig: e.sig.to_vec(),
+        }
+    }
+}
+
+impl TryFrom<CompactEventFfi> for haven_core::relay::CompactEvent {
+    type Error = HavenErrorFfi;
+
+    fn try_from(e: CompactEventFfi) -> Result<Self, HavenErrorFfi> {
+        Ok(Self {
+            id: e.id.try_into().map_err(|_| "Invalid event id length")?,
+            pubkey: e.pubkey.try_into().map_err(|_| "Invalid pubkey length")?,
+            created_at: e.created_at,
+            kind: e.kind,
+            tags: e.tags,
+            content: e.content,
+            sig: e.sig.try_into().map_err(|_| "Invalid signature length")?,
+        })
+    }
+}
+
+/// Mirrors `haven_core::diagnostics::Breadcrumb`.
+#[derive(Debug, Clone)]
+pub struct BreadcrumbFfi {
+    /// Module slug, e.g. `"circle.manager"`.
+    pub module: String,
+    /// Error-kind slug, e.g. `"mls"`.
+    pub error_kind: String,
+    /// Unix timestamp (seconds) when the error was recorded.
+    pub timestamp: i64,
+}
+
+impl From<haven_core::diagnostics::Breadcrumb> for BreadcrumbFfi {
+    fn from(b: haven_core::diagnostics::Breadcrumb) -> Self {
+        Self {
+            module: b.module,
+            error_kind: b.error_kind,
+            timestamp: b.timestamp,
+        }
+    }
+}
+
+/// Discriminator for [`LocationMessageResultFfi`].
+///
+/// Mirrors the six
+/// [`haven_core::nostr::mls::types::LocationMessageResult`] variants 1:1
+/// (Dark Matter taxonomy). Unlike the pre-migration outcome, stale / duplicate
+/// / out-of-order handling is entirely engine-internal (the engine durably
+/// buffers a future-epoch event and re-surfaces it once the gap fills), so
+/// there is NO `Unprocessable` / `PreviouslyFailed` here. A struct-with-
+/// discriminant shape (rather than a tagged Rust enum) follows the existing
+/// [`LeavePlanFfi`] convention and avoids pulling Dart `freezed` into the
+/// generated bindings.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LocationMessageResultKindFfi {
+    /// A decrypted application (location) message.
+    Location,
+    /// The local client joined a group via an accepted welcome.
+    Joined,
+    /// A durable, MLS-authenticated change to group state (membership, admin,
+    /// rename, retention) or an epoch advance the receiver should react to by
+    /// refreshing the circle's roster.
+    GroupUpdate,
+    /// A previously-surfaced result was withdrawn because branch selection
+    /// superseded the commit that produced it — the caller must treat the
+    /// earlier change as if it never happened.
+    Invalidated,
+    /// The group entered the unrecoverable state; the UI MUST block
+    /// send/mutate for it (Rule 8, blocked-group state).
+    Unrecoverable,
+    /// A decrypted emergency (SOS) broadcast. `location` carries the sender's
+    /// exact position and `sos_message` the optional note.
+    Sos,
+    /// A decrypted check-in request: `checkin_requester_pubkey` asks
+    /// `checkin_target_pubkey` to share their location. The target's app
+    /// answers with [`CircleManagerFfi::respond_to_checkin`].
+    CheckinRequest,
+    /// A sender's location was finer than the precision they had been
+    /// sharing at; details in `precision_anomaly`. Always follows the
+    /// `Location` that triggered it.
+    PrecisionAnomaly,
+    /// A still-live meet pin (an exact meeting point, not the sender's
+    /// position); details in `meet_pin`. Already stored; list with
+    /// [`CircleManagerFfi::list_meet_pins`].
+    MeetPin,
+}
+
+/// Which [`HavenPayloadFfi`] variant a payload is (mirror of
+/// `haven_core::payload::HavenPayload`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HavenPayloadKindFfi {
+    /// A location update; details in `location`.
+    Location,
+    /// An emergency broadcast; `location` plus the optional `sos_message`.
+    Sos,
+    /// A check-in request for `checkin_target_pubkey`.
+    CheckinRequest,
+    /// A meet pin; details in `meet_pin`.
+    MeetPin,
+    /// Device status without a position; details in `device_status`.
+    Status,
+    /// A type this build does not know (sent by a newer client).
+    Unknown,
+}
+
+/// A decoded inner message payload (FFI-friendly tagged form of
+/// `haven_core::payload::HavenPayload`): `kind` says which of the optional
+/// fields is set, following the [`LocationMessageResultFfi`] convention.
+pub struct HavenPayloadFfi {
+    /// Which payload this is.
+    pub kind: HavenPayloadKindFfi,
+    /// Schema version the sender wrote (`0` for content without an envelope).
+    pub schema_version: u32,
+    /// The position — `Some` for `kind == Location` and `kind == Sos`.
+    pub location: Option<DecryptedLocationFfi>,
+    /// The optional SOS note — only for `kind == Sos`.
+    pub sos_message: Option<String>,
+    /// Lowercase hex pubkey of the member asked — only for
+    /// `kind == CheckinRequest`.
+    pub checkin_target_pubkey: Option<String>,
+    /// The pin — only for `kind == MeetPin`.
+    pub meet_pin: Option<MeetPinFfi>,
+    /// Device status — only for `kind == Status`.
+    pub device_status: Option<DeviceStatusFfi>,
+}
+
+impl HavenPayloadFfi {
+    fn from_decoded(sender_pubkey: &str, decoded: haven_core::payload::DecodedPayload) -> Self {
+        use haven_core::payload::HavenPayload as P;
+        let mut out = Self {
+            kind: HavenPayloadKindFfi::Unknown,
+            schema_version: decoded.schema_version,
+            location: None,
+            sos_message: None,
+            checkin_target_pubkey: None,
+            meet_pin: None,
+            device_status: None,
+        };
+        match decoded.payload {
+            P::Location(location) => {
+                out.kind = HavenPayloadKindFfi::Location;
+                out.location = Some(DecryptedLocationFfi::from_location(sender_pubkey, location));
+            }
+            P::Sos(sos) => {
+                out.kind = HavenPayloadKindFfi::Sos;
+                out.sos_message = sos.message;
+                out.location = Some(DecryptedLocationFfi::from_location(
+                    sender_pubkey,
+                    sos.location,
+                ));
+            }
+            P::CheckinRequest { target_pubkey } => {
+                out.kind = HavenPayloadKindFfi::CheckinRequest;
+                out.checkin_target_pubkey = Some(normalize_pubkey_hex(&target_pubkey));
+            }
+            P::MeetPin(pin) => {
+                out.kind = HavenPayloadKindFfi::MeetPin;
+                out.meet_pin = Some(MeetPinFfi::from(haven_core::meet::StoredMeetPin {
+                    sender_pubkey: sender_pubkey.to_string(),
+                    pin,
+                }));
+            }
+            P::Status(status) => {
+                out.kind = HavenPayloadKindFfi::Status;
+                out.device_status = Some(status.into());
+            }
+            P::Unknown => {}
+        }
+        out
+    }
+}
+
+impl std::fmt::Debug for HavenPayloadFfi {
+    /// Redacts payload contents and exposes only presence.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("HavenPayloadFfi")
+            .field("kind", &self.kind)
+            .field("schema_version", &self.schema_version)
+            .field("has_location", &self.location.is_some())
+            .field("has_sos_message", &self.sos_message.is_some())
+            .field("has_meet_pin", &self.meet_pin.is_some())
+            .finish_non_exhaustive()
+    }
+}
+
+/// Mirrors `haven_core::location::PrecisionClass`, coarsest first.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PrecisionClassFfi {
+    /// At most 2 decimal places (~1 km or coarser).
+    Coarse,
+    /// 3–4 decimal places (~10–100 m).
+    Approximate,
+    /// 5 or more decimal places: a raw GPS fix.
+    Exact,
+}
+
+impl From<haven_core::location::PrecisionClass> for PrecisionClassFfi {
+    fn from(class: haven_core::location::PrecisionClass) -> Self {
+        use haven_core::location::PrecisionClass as C;
+        match class {
+            C::Coarse => Self::Coarse,
+            C::Approximate => Self::Approximate,
+            C::Exact => Self::Exact,
+        }
+    }
+}
+
+/// A sender started sharing finer locations than their baseline in a circle.
+pub struct PrecisionAnomalyFfi {
+    /// Lowercase hex pubkey of the sender.
+    pub sender_pubkey: String,
+    /// The precision the sender had established.
+    pub baseline: PrecisionClassFfi,
+    /// The finer precision just received.
+    pub observed: PrecisionClassFfi,
+}
+
+impl std::fmt::Debug for PrecisionAnomalyFfi {
+    /// Redacts the sender; the precision classes are not secret.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("PrecisionAnomalyFfi")
+            .field("sender_pubkey", &"<redacted>")
+            .field("baseline", &self.baseline)
+            .field("observed", &self.observed)
+            .finish()
+    }
+}
+
+/// Summary of a circle in cold storage (see
+/// `haven_core::circle::cold_storage`).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ColdCircleInfoFfi {
+    /// Number of rows held in the blob.
+    pub row_count: u64,
+    /// Size before compression and sealing, in bytes.
+    pub raw_bytes: u64,
+    /// Size of the sealed blob, in bytes.
+    pub sealed_bytes: u64,
+    /// Unix timestamp the circle was frozen.
+    pub frozen_at: i64,
+}
+
+impl From<haven_core::circle::ColdCircleInfo> for ColdCircleInfoFfi {
+    fn from(info: haven_core::circle::ColdCircleInfo) -> Self {
+        Self {
+            row_count: info.row_count,
+            raw_bytes: info.raw_bytes,
+            sealed_bytes: info.sealed_bytes,
+            frozen_at: info.frozen_at,
+        }
+    }
+}
+
+/// A time-limited meet pin (see `haven_core::meet`): an exact meeting point
+/// dropped by `sender_pubkey`, gone once `expires_at` passes.
+pub struct MeetPinFfi {
+    /// Random pin id (hex).
+    pub pin_id: String,
+    /// Lowercase hex pubkey of the member who dropped the pin.
+    pub sender_pubkey: String,
+    /// Exact latitude of the meeting point.
+    pub latitude: f64,
+    /// Exact longitude of the meeting point.
+    pub longitude: f64,
+    /// Optional short label.
+    pub label: Option<String>,
+    /// Unix timestamp the pin was dropped.
+    pub created_at: i64,
+    /// Unix timestamp the pin lapses.
+    pub expires_at: i64,
+}
+
+impl From<haven_core::meet::StoredMeetPin> for MeetPinFfi {
+    fn from(stored: haven_core::meet::StoredMeetPin) -> Self {
+        Self {
+            pin_id: stored.pin.pin_id,
+            sender_pubkey: normalize_pubkey_hex(&stored.sender_pubkey),
+            latitude: stored.pin.lat,
+            longitude: stored.pin.lon,
+            label: stored.pin.label,
+            created_at: stored.pin.created_at,
+            expires_at: stored.pin.expires_at,
+        }
+    }
+}
+
+impl std::fmt::Debug for MeetPinFfi {
+    /// Redacts the sender, coordinates and label.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("MeetPinFfi")
+            .field("pin_id", &self.pin_id)
+            .field("sender_pubkey", &"<redacted>")
+            .field("created_at", &self.created_at)
+            .field("expires_at", &self.expires_at)
+            .finish_non_exhaustive()
+    }
+}
+
+/// A member whose MLS signature key changed (see
+/// `haven_core::circle::key_audit`), for the user to review.
+pub struct MemberKeyChangeFfi {
+    /// Id to pass to [`CircleManagerFfi::acknowledge_member_key_change`].
+    pub id: i64,
+    /// Lowercase hex pubkey of the member.
+    pub member_pubkey: String,
+    /// Circle the new key was observed in, if any.
+    pub mls_group_id: Option<Vec<u8>>,
+    /// Fingerprint (hex SHA-256) of the previously recorded key.
+    pub previous_fingerprint: String,
+    /// Fingerprint (hex SHA-256) of the newly observed key.
+    pub observed_fingerprint: String,
+    /// Unix timestamp of the observation.
+    pub observed_at: i64,
+    /// Unix timestamp the user acknowledged the change, if they have.
+    pub acknowledged_at: Option<i64>,
+    /// Notification text for the change.
+    pub notification: UserMessageFfi,
+}
+
+impl From<haven_core::circle::MemberKeyChange> for MemberKeyChangeFfi {
+    fn from(c: haven_core::circle::MemberKeyChange) -> Self {
+        Self {
+            notification: c.notification().into(),
+            id: c.id,
+            member_pubkey: c.member_pubkey,
+            mls_group_id: c.mls_group_id.map(|g| g.as_slice().to_vec()),
+            previous_fingerprint: c.previous_fingerprint,
+            observed_fingerprint: c.observed_fingerprint,
+            observed_at: c.observed_at,
+            acknowledged_at: c.acknowledged_at,
+        }
+    }
+}
+
+impl std::fmt::Debug for MemberKeyChangeFfi {
+    /// Redacts the member, circle and fingerprints.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("MemberKeyChangeFfi")
+            .field("id", &self.id)
+            .field("member_pubkey", &"<redacted>")
+            .field("observed_at", &self.observed_at)
+            .field("acknowledged_at", &self.acknowledged_at)
+            .finish_non_exhaustive()
+    }
+}
+
+/// One folded engine [`haven_core::nostr::mls::types::LocationMessageResult`],
+/// FFI-friendly. A single `kind:445` ingest can yield SEVERAL of these (an
+/// engine `advance_convergence` may release buffered inbound after the outer
+/// event), so [`CircleManagerFfi::decrypt_location`] returns a `Vec` of them.
+///
+/// Cursor contract (DM-4b): the engine now owns out-of-order buffering, so the
+/// caller advances its relay sync cursor on the OUTER event's `created_at`
+/// (which Dart already holds — it passed `event_json` in), NOT per result.
+/// A buffered future-epoch event is re-surfaced by the engine once the gap
+/// fills; the caller never needs to re-fetch it.
+pub struct LocationMessageResultFfi {
+    /// Which of the six outcomes this result is.
+    pub kind: LocationMessageResultKindFfi,
+    /// The decrypted location — `Some` only when `kind == Location` AND the
+    /// inner content parsed as a `LocationMessage`. A successfully-decrypted
+    /// but unparseable inner (e.g. a forward-incompatible payload) still yields
+    /// `kind == Location` with `location == None` (decrypt succeeded).
+    pub location: Option<DecryptedLocationFfi>,
+    /// The MLS group id (raw bytes) this result belongs to — the LOCAL circle
+    /// handle (never published; Rule 4 keeps it off the wire). Present for
+    /// every variant so the caller can refresh / join / block / withdraw the
+    /// right circle.
+    pub mls_group_id: Vec<u8>,
+    /// The MLS epoch the message was authenticated at — meaningful only for
+    /// `kind == Location` / `kind == Sos` (0 otherwise).
+    pub epoch: u64,
+    /// The sender's optional SOS note — `Some` only for `kind == Sos`.
+    pub sos_message: Option<String>,
+    /// Lowercase hex pubkey of the member asking — `Some` only for
+    /// `kind == CheckinRequest`.
+    pub checkin_requester_pubkey: Option<String>,
+    /// Lowercase hex pubkey of the member being asked — `Some` only for
+    /// `kind == CheckinRequest`. Compare with the own pubkey to decide
+    /// whether to prompt.
+    pub checkin_target_pubkey: Option<String>,
+    /// The precision change — `Some` only for `kind == PrecisionAnomaly`.
+    pub precision_anomaly: Option<PrecisionAnomalyFfi>,
+    /// The pin — `Some` only for `kind == MeetPin` when the payload parsed.
+    pub meet_pin: Option<MeetPinFfi>,
+    /// Who joined or left — `Some` only for `kind == GroupUpdate`.
+    pub membership: Option<MembershipDeltaFfi>,
+}
+
+impl std::fmt::Debug for LocationMessageResultFfi {
+    /// Redacts payloads (location, raw group id) and exposes only presence;
+    /// `epoch` is a non-secret counter.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("LocationMessageResultFfi")
+            .field("kind", &self.kind)
+            .field("has_location", &self.location.is_some())
+            .field("mls_group_id", &"<redacted>")
+            .field("epoch", &self.epoch)
+            .field("has_sos_message", &self.sos_message.is_some())
+            .field("has_checkin_target", &self.checkin_target_pubkey.is_some())
+            .field("precision_anomaly", &self.precision_anomaly)
+            .field("meet_pin", &self.meet_pin)
+            .field("membership", &self.membership)
+            .finish()
+    }
+}
+
+/// Converts a core [`LocationMessageResult`] into the FFI
+/// [`DecryptOutcomeFfi`], surfacing all four variants.
+///
+/// Pure and synchronous (no MDK / no FFI / no async) so the M1 safety
+/// contract — `Unprocessable` and `PreviouslyFailed` are SURFACED, not
+/// flattened away — is unit-testable without a live `CircleManager`.
+/// `event_created_at_secs` is the outer event timestamp, threaded through
+/// onto every outcome.
+///
+/// [`LocationMessageResult`]: haven_core::nostr::mls::types::LocationMessageResult
+///
+/// # Errors
+///
+/// Returns a redacted error string if a `Location` payload cannot be parsed
+/// or a `GroupUpdate` evolution event cannot be serialized.
+/// Parses an engine-delivered Location `content` (the decrypted `LocationMessage`
+/// JSON carried on `FfiRelayEvent.content`) into a structured
+/// [`DecryptedLocationFfi`], reusing the Rust `serde` schema so the Dart stream
+/// consumer (M6-3) never duplicates the content schema or the `DateTime` format.
+/// The `sender_pubkey` comes from the stream event, not the content.
+///
+/// # Errors
+///
+/// Returns an error if `content_json` is not a valid `LocationMessage`.
+pub fn parse_engine_location(
+    content_json: String,
+    sender_pubkey: String,
+) -> Result<DecryptedLocationFfi, HavenErrorFfi> {
+    // Fixed message — never interpolate the decode error, which could echo a
+    // fragment of the decrypted location content (defense-in-depth; the Dart
+    // consumer already discards this and logs only the runtime type).
+    decode_engine_location(&content_json, &sender_pubkey)
+        .ok_or_else(|| HavenErrorFfi::invalid_input("invalid location content"))
+}
+
+/// Shared decode behind [`parse_engine_location`] and
+/// [`LiveSyncFfi::location_updates_stream`]; `None` if `content_json` is not a
+/// location.
+fn decode_engine_location(content_json: &str, sender_pubkey: &str) -> Option<DecryptedLocationFfi> {
+    use haven_core::payload::{HavenPayload, LOCATION_TOPIC};
+    match HavenPayload::decode(content_json, Some(LOCATION_TOPIC)).map(|d| d.payload) {
+        Ok(HavenPayload::Location(location)) => {
+            Some(DecryptedLocationFfi::from_location(sender_pubkey, location))
+        }
+        _ => None,
+    }
+}
+
+/// Decodes decrypted inner `content` into a typed [`HavenPayloadFfi`] (see
+/// `haven_core::payload`).
+///
+/// `topic` is the inner rumor's `["t", ...]` topic, only consulted for
+/// legacy content without a `type`; `sender_pubkey` is stamped on a decoded
+/// location or meet pin. A type this build does not know decodes to
+/// [`HavenPayloadKindFfi::Unknown`] rather than an error.
+///
+/// # Errors
+///
+/// Returns an error if the content is not a valid payload.
+#[frb(sync)]
+pub fn decode_haven_payload(
+    content_json: String,
+    topic: Option<String>,
+    sender_pubkey: String,
+) -> Result<HavenPayloadFfi, HavenErrorFfi> {
+    // Fixed message, as in `parse_engine_location`.
+    let decoded = haven_core::payload::HavenPayload::decode(&content_json, topic.as_deref())
+        .map_err(|_| HavenErrorFfi::invalid_input("invalid payload content"))?;
+    Ok(HavenPayloadFfi::from_decoded(&sender_pubkey, decoded))
+}
+
+/// Converts a core [`LocationMessageResult`] into the FFI
+/// [`LocationMessageResultFfi`] (Dark Matter six-variant taxonomy).
+///
+/// Pure and synchronous (no engine / no FFI / no async) so the fold is
+/// unit-testable without a live `CircleManager`.
+///
+/// [`LocationMessageResult`]: haven_core::nostr::mls::types::LocationMessageResult
+fn convert_location_result(
+    result: haven_core::nostr::mls::types::LocationMessageResult,
+) -> LocationMessageResultFfi {
+    use haven_core::nostr::mls::types::LocationMessageResult as R;
+    use haven_core::payload::HavenPayload;
+    let payload = result.payload();
+    match result {
+        R::Location {
+            sender_pubkey,
+            group_id,
+            epoch,
+            ..
+        } => {
+            // Decrypt succeeded — a location result regardless of whether the
+            // inner content parses. A forward-incompatible inner (from a peer
+            // on a newer content schema, or a payload type this build does not
+            // know) decrypts fine but yields `None`; the caller advances past
+            // it exactly like a `GroupUpdate`.
+            let location = match payload {
+                Some(HavenPayload::Location(location)) => Some(
+                    DecryptedLocationFfi::from_location(&sender_pubkey, location),
+                ),
+                _ => None,
+            };
+            LocationMessageResultFfi {
+                kind: LocationMessageResultKindFfi::Location,
+                location,
+                mls_group_id: group_id.as_slice().to_vec(),
+                epoch,
+                sos_message: None,
+                checkin_requester_pubkey: None,
+                checkin_target_pubkey: None,
+                precision_anomaly: None,
+                meet_pin: None,
+                membership: None,
+            }
+        }
+        R::Sos {
+            sender_pubkey,
+            group_id,
+            epoch,
+            ..
+        } => {
+            let sos = match payload {
+                Some(HavenPayload::Sos(sos)) => Some(sos),
+                _ => None,
+            };
+            let sos_message = sos.as_ref().and_then(|s| s.message.clone());
+            let location =
+                sos.map(|s| DecryptedLocationFfi::from_location(&sender_pubkey, s.location));
+            LocationMessageResultFfi {
+                kind: LocationMessageResultKindFfi::Sos,
+                location,
+                mls_group_id: group_id.as_slice().to_vec(),
+                epoch,
+                sos_message,
+                checkin_requester_pubkey: None,
+                checkin_target_pubkey: None,
+                precision_anomaly: None,
+                meet_pin: None,
+                membership: None,
+            }
+        }
+        R::CheckinRequest {
+            sender_pubkey,
+            target_pubkey,
+            group_id,
+            epoch,
+        } => LocationMessageResultFfi {
+            kind: LocationMessageResultKindFfi::CheckinRequest,
+            location: None,
+            mls_group_id: group_id.as_slice().to_vec(),
+            epoch,
+            sos_message: None,
+            checkin_requester_pubkey: Some(normalize_pubkey_hex(&sender_pubkey)),
+            checkin_target_pubkey: Some(normalize_pubkey_hex(&target_pubkey)),
+            precision_anomaly: None,
+            meet_pin: None,
+            membership: None,
+        },
+        R::MeetPin {
+            sender_pubkey,
+            group_id,
+            epoch,
+            ..
+        } => {
+            let meet_pin = match payload {
+                Some(HavenPayload::MeetPin(pin)) => {
+                    Some(MeetPinFfi::from(haven_core::meet::StoredMeetPin {
+                        sender_pubkey,
+                        pin,
+                    }))
+                }
+                _ => None,
+            };
+            LocationMessageResultFfi {
+                kind: LocationMessageResultKindFfi::MeetPin,
+                location: None,
+                mls_group_id: group_id.as_slice().to_vec(),
+                epoch,
+                sos_message: None,
+                checkin_requester_pubkey: None,
+                checkin_target_pubkey: None,
+                precision_anomaly: None,
+                meet_pin,
+                membership: None,
+            }
+        }
+        R::PrecisionAnomaly {
+            sender_pubkey,
+            group_id,
+            baseline,
+            observed,
+        } => LocationMessageResultFfi {
+            kind: LocationMessageResultKindFfi::PrecisionAnomaly,
+            location: None,
+            mls_group_id: group_id.as_slice().to_vec(),
+            epoch: 0,
+            sos_message: None,
+            checkin_requester_pubkey: None,
+            checkin_target_pubkey: None,
+            precision_anomaly: Some(PrecisionAnomalyFfi {
+                sender_pubkey: normalize_pubkey_hex(&sender_pubkey),
+                baseline: baseline.into(),
+                observed: observed.into(),
+            }),
+            meet_pin: None,
+            membership: None,
+        },
+        R::Joined { group_id } => LocationMessageResultFfi {
+            kind: LocationMessageResultKindFfi::Joined,
+            location: None,
+            mls_group_id: group_id.as_slice().to_vec(),
+            epoch: 0,
+            sos_message: None,
+            checkin_requester_pubkey: None,
+            checkin_target_pubkey: None,
+            precision_anomaly: None,
+            meet_pin: None,
+            membership: None,
+        },
+        R::GroupUpdate {
+            group_id,
+            membership,
+        } => LocationMessageResultFfi {
+            kind: LocationMessageResultKindFfi::GroupUpdate,
+            location: None,
+            mls_group_id: group_id.as_slice().to_vec(),
+            epoch: 0,
+            sos_message: None,
+            checkin_requester_pubkey: None,
+            checkin_target_pubkey: None,
+            precision_anomaly: None,
+            meet_pin: None,
+            membership: Some(membership.into()),
+        },
+        R::Invalidated { group_id } => LocationMessageResultFfi {
+            kind: LocationMessageResultKindFfi::Invalidated,
+            location: None,
+            mls_group_id: group_id.as_slice().to_vec(),
+            epoch: 0,
+            sos_message: None,
+            checkin_requester_pubkey: None,
+            checkin_target_pubkey: None,
+            precision_anomaly: None,
+            meet_pin: None,
+            membership: None,
+        },
+        R::Unrecoverable { group_id } => LocationMessageResultFfi {
+            kind: LocationMessageResultKindFfi::Unrecoverable,
+            location: None,
+            mls_group_id: group_id.as_slice().to_vec(),
+            epoch: 0,
+            sos_message: None,
+            checkin_requester_pubkey: None,
+            checkin_target_pubkey: None,
+            precision_anomaly: None,
+            meet_pin: None,
+            membership: None,
+        },
+    }
+}
+
+/// The folded outcome of ingesting one received `kind:445` — FFI mirror of
+/// `haven_core::circle::DecryptedIngest`.
+///
+/// Carries the folded location results AND any receive-side auto-commit the
+/// engine staged (a peer `SelfRemove` eviction). Publish-before-apply (Rule 13 /
+/// security F13): for EACH [`Self::auto_commits`] entry, publish
+/// `commit_event_json` to the circle's relays, then
+/// [`CircleManagerFfi::confirm_published`] on a ≥1-relay ACK (or
+/// [`CircleManagerFfi::publish_failed`] on failure) — exactly like the
+/// [`CommitToPublishFfi`] returned by remove / relay-update. NEVER confirm before
+/// a relay ACKs, and NEVER drop an entry silently (that re-forks the group the
+/// leaver departed).
+///
+/// Both fields carry redacting `Debug` impls (`LocationMessageResultFfi` /
+/// `CommitToPublishFfi`), so the derived `Debug` here cannot leak group ids or
+/// coordinates.
+#[derive(Debug)]
+pub struct DecryptLocationOutcomeFfi {
+    /// The folded location-facing results (locations, joins, updates, …).
+    pub results: Vec<LocationMessageResultFfi>,
+    /// Receive-side auto-commits the caller MUST publish then confirm/fail.
+    pub auto_commits: Vec<CommitToPublishFfi>,
+}
+
+/// Outcome of one event in [`CircleManagerFfi::decrypt_locations_batch`].
+///
+/// Exactly one of `outcome` and `error` is set.
+#[derive(Debug)]
+pub struct DecryptBatchItemFfi {
+    /// Hex id of the event.
+    pub event_id: String,
+    /// What the event decrypted to, with its auto-commits to publish.
+    pub outcome: Option<DecryptLocationOutcomeFfi>,
+    /// Why the event could not be ingested.
+    pub error: Option<HavenErrorFfi>,
+}
+
+/// A circle's epoch position (mirrors `haven_core::nostr::mls::EpochInfo`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct EpochInfoFfi {
+    /// The current MLS epoch.
+    pub epoch: u64,
+    /// The oldest epoch whose messages can still be decrypted.
+    pub oldest_readable_epoch: u64,
+    /// Number of members at the current epoch.
+    pub member_count: u32,
+}
+
+impl From<haven_core::nostr::mls::EpochInfo> for EpochInfoFfi {
+    fn from(info: haven_core::nostr::mls::EpochInfo) -> Self {
+        Self {
+            epoch: info.epoch,
+            oldest_readable_epoch: info.oldest_readable_epoch,
+            member_count: u32::try_from(info.member_count).unwrap_or(u32::MAX),
+        }
+    }
+}
+
+/// A circle's receive-side health (mirrors `haven_core::circle::GroupHealth`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct GroupHealthFfi {
+    /// Current MLS epoch, or `None` if the engine no longer holds the group.
+    pub epoch: Option<u64>,
+    /// Commits staged for the circle and not yet confirmed or rolled back.
+    pub pending_commits: u32,
+    /// Messages that failed to ingest since the last one that applied.
+    pub unprocessable_count: u32,
+    /// Whether to show the circle as needing repair.
+    pub needs_repair: bool,
+}
+
+impl From<haven_core::circle::GroupHealth> for GroupHealthFfi {
+    fn from(h: haven_core::circle::GroupHealth) -> Self {
+        Self {
+            epoch: h.epoch,
+            pending_commits: h.pending_commits,
+            unprocessable_count: h.unprocessable_count,
+            needs_repair: h.needs_repair,
+        }
+    }
+}
+
+/// How recently a member was heard from (mirrors
+/// `haven_core::circle::PresenceStatus`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PresenceStatusFfi {
+    /// A message within the last 30 minutes.
+    Active,
+    /// Quiet for longer, but heard from within 6 hours.
+    Stale,
+    /// Quiet for longer than that.
+    Offline,
+    /// Never heard from on this device.
+    Unknown,
+}
+
+impl From<haven_core::circle::PresenceStatus> for PresenceStatusFfi {
+    fn from(s: haven_core::circle::PresenceStatus) -> Self {
+        use haven_core::circle::PresenceStatus as S;
+        match s {
+            S::Active => Self::Active,
+            S::Stale => Self::Stale,
+            S::Offline => Self::Offline,
+            S::Unknown => Self::Unknown,
+        }
+    }
+}
+
+/// When a circle member was last heard from (mirrors
+/// `haven_core::circle::MemberPresence`). Any decrypted message counts, not
+/// only locations; commits do not.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MemberPresenceFfi {
+    /// The member's public key (hex).
+    pub pubkey: String,
+    /// Send time of the member's latest message (Unix seconds).
+    pub last_seen: Option<i64>,
+    /// Send time of the member's latest location or SOS (Unix seconds).
+    pub last_location_at: Option<i64>,
+    /// `last_seen` classified at the time of the query.
+    pub status: PresenceStatusFfi,
+}
+
+impl From<haven_core::circle::MemberPresence> for MemberPresenceFfi {
+    fn from(p: haven_core::circle::MemberPresence) -> Self {
+        Self {
+            pubkey: p.pubkey,
+            last_seen: p.last_seen,
+            last_location_at: p.last_location_at,
+            status: p.status.into(),
+        }
+    }
+}
+
+/// A circle through which an [`AudienceMemberFfi`] receives the next update
+/// (mirrors `haven_core::circle::AudienceCircle`).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AudienceCircleFfi {
+    /// MLS group ID of the circle.
+    pub mls_group_id: Vec<u8>,
+    /// The circle's local name.
+    pub name: String,
+    /// Precision the circle receives.
+    pub precision: LocationPrecisionFfi,
+    /// End of a running precise session (Unix seconds).
+    pub precise_until: Option<i64>,
+}
+
+impl From<haven_core::circle::AudienceCircle> for AudienceCircleFfi {
+    fn from(c: haven_core::circle::AudienceCircle) -> Self {
+        Self {
+            mls_group_id: c.mls_group_id.as_slice().to_vec(),
+            name: c.name,
+            precision: c.precision.into(),
+            precise_until: c.precise_until,
+        }
+    }
+}
+
+/// Someone who would receive the next location update (mirrors
+/// `haven_core::circle::AudienceMember`).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AudienceMemberFfi {
+    /// The member's public key (hex).
+    pub pubkey: String,
+    /// The user's local name for them, if any.
+    pub display_name: Option<String>,
+    /// The finest precision they receive through any circle.
+    pub precision: LocationPrecisionFfi,
+    /// Every circle they receive it through.
+    pub circles: Vec<AudienceCircleFfi>,
+}
+
+impl From<haven_core::circle::AudienceMember> for AudienceMemberFfi {
+    fn from(m: haven_core::circle::AudienceMember) -> Self {
+        Self {
+            pubkey: m.pubkey,
+            display_name: m.display_name,
+            precision: m.precision.into(),
+            circles: m.circles.into_iter().map(AudienceCircleFfi::from).collect(),
+        }
+    }
+}
+
+/// What [`CircleManagerFfi::repair_group`] did (mirrors
+/// `haven_core::circle::RepairOutcome`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RepairOutcomeFfi {
+    /// Nothing needed repair.
+    Healthy,
+    /// The group was re-driven; the next sync shows whether it caught up.
+    Resynced,
+    /// This device is no longer in the group: ask an admin to re-invite it.
+    NeedsRejoin,
+}
+
+impl From<haven_core::circle::RepairOutcome> for RepairOutcomeFfi {
+    fn from(o: haven_core::circle::RepairOutcome) -> Self {
+        use haven_core::circle::RepairOutcome as O;
+        match o {
+            O::Healthy => Self::Healthy,
+            O::Resynced => Self::Resynced,
+            O::NeedsRejoin => Self::NeedsRejoin,
+        }
+    }
+}
+
+/// Result of [`CircleManagerFfi::repair_group`].
+#[derive(Debug)]
+pub struct GroupRepairFfi {
+    /// What the repair did.
+    pub outcome: RepairOutcomeFfi,
+    /// Results and auto-commits drained while re-driving the group.
+    pub drained: DecryptLocationOutcomeFfi,
+}
+
+/// Converts a Nostr event `created_at` (Unix seconds) to the millisecond unit
+/// the sync cursor stores.
+///
+/// Saturating, so a pathological future timestamp cannot overflow `i64`.
+/// Centralizing the seconds→milliseconds conversion here (rather than in Dart)
+/// removes the ms/s drift footgun at the FFI boundary.
+const fn event_secs_to_cursor_ms(secs: i64) -> i64 {
+    secs.saturating_mul(1000)
+}
+
+/// Discriminator for [`LeavePlanFfi`].
+///
+/// Drives the Flutter-side leave state machine. See the core
+/// `LeavePlan` docs for the full flow per variant.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LeavePlanKindFfi {
+    /// Caller is a non-admin — skip handoff/demotion, go to `propose_leave`.
+    NonAdmin,
+    /// Caller is the sole admin — must promote `successor_hex` before
+    /// self-demoting and leaving.
+    AdminHandoff,
+    /// Caller is one of multiple admins — skip promotion and go directly
+    /// to `propose_self_demote` + `propose_leave`.
+    AdminDemote,
+    /// Caller is the sole remaining member — call
+    /// `abandon_circle_local_only` to wipe the local row.
+    Abandon,
+    /// MDK has no record of the group — call `complete_leave` to wipe the
+    /// orphaned local row.
+    OrphanLocalOnly,
+}
+
+/// FFI mirror of [`haven_core::circle::LeavePlan`].
+///
+/// Struct shape (rather than tagged enum) avoids pulling in Dart `freezed`
+/// for sealed classes while preserving all the information.
+#[derive(Clone)]
+pub struct LeavePlanFfi {
+    /// Which branch of the state machine to execute.
+    pub kind: LeavePlanKindFfi,
+    /// Hex-encoded successor pubkey when `kind == AdminHandoff`, else `None`.
+    pub successor_hex: Option<String>,
+}
+
+impl std::fmt::Debug for LeavePlanFfi {
+    /// Redacts `successor_hex` to an 8-char prefix so log lines cannot be
+    /// used to correlate a user to a specific handoff.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let redacted = self
+            .successor_hex
+            .as_deref()
+            .map(|h| h.chars().take(8).collect::<String>() + "…");
+        f.debug_struct("LeavePlanFfi")
+            .field("kind", &self.kind)
+            .field("successor_hex", &redacted)
+            .finish()
+    }
+}
+
+impl From<haven_core::circle::LeavePlan> for LeavePlanFfi {
+    fn from(plan: haven_core::circle::LeavePlan) -> Self {
+        match plan {
+            haven_core::circle::LeavePlan::NonAdmin => Self {
+                kind: LeavePlanKindFfi::NonAdmin,
+                successor_hex: None,
+            },
+            haven_core::circle::LeavePlan::AdminHandoff { successor } => Self {
+                kind: LeavePlanKindFfi::AdminHandoff,
+                successor_hex: Some(successor.to_hex()),
+            },
+            haven_core::circle::LeavePlan::AdminDemote => Self {
+                kind: LeavePlanKindFfi::AdminDemote,
+                successor_hex: None,
+            },
+            haven_core::circle::LeavePlan::Abandon => Self {
+                kind: LeavePlanKindFfi::Abandon,
+                successor_hex: None,
+            },
+            haven_core::circle::LeavePlan::OrphanLocalOnly => Self {
+                kind: LeavePlanKindFfi::OrphanLocalOnly,
+                successor_hex: None,
+            },
+        }
+    }
+}
+
+/// A circle that still depends on the identity — see
+/// [`CircleManagerFfi::identity_deletion_report`].
+#[derive(Debug, Clone)]
+pub struct DependentCircleFfi {
+    /// MLS group ID bytes.
+    pub mls_group_id: Vec<u8>,
+    /// The circle's local display name.
+    pub name: String,
+    /// How the identity would leave it.
+    pub plan: LeavePlanFfi,
+}
+
+/// FFI mirror of [`haven_core::circle::IdentityDeletionReport`].
+#[derive(Debug, Clone)]
+pub struct IdentityDeletionReportFfi {
+    /// Circles the identity is a member of; non-empty means deletion needs
+    /// `force`.
+    pub circles: Vec<DependentCircleFfi>,
+    /// Unanswered invitations (discarded on deletion).
+    pub pending_invitations: u32,
+}
+
+/// A `SelfRemove` proposal to publish before the identity is gone.
+#[derive(Clone)]
+pub struct LeaveEventFfi {
+    /// MLS group ID bytes of the circle being left.
+    pub mls_group_id: Vec<u8>,
+    /// The signed kind:445 event JSON.
+    pub event_json: String,
+    /// The circle's relays, to publish to.
+    pub relays: Vec<String>,
+}
+
+impl std::fmt::Debug for LeaveEventFfi {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("LeaveEventFfi")
+            .field("mls_group_id", &"<redacted>")
+            .field("relays", &self.relays.len())
+            .finish_non_exhaustive()
+    }
+}
+
+impl TryFrom<haven_core::circle::LeaveEvent> for LeaveEventFfi {
+    type Error = HavenErrorFfi;
+
+    fn try_from(e: haven_core::circle::LeaveEvent) -> Result<Self, Self::Error> {
+        Ok(Self {
+            mls_group_id: e.mls_group_id.as_slice().to_vec(),
+            event_json: commit_event_to_json(&e.event)?,
+            relays: e.relays,
+        })
+    }
+}
+
+/// FFI mirror of [`haven_core::circle::IdentityTeardown`].
+#[derive(Debug, Clone)]
+pub struct IdentityTeardownFfi {
+    /// Leave proposals to publish (best effort; already signed, so the key
+    /// is not needed to publish them).
+    pub leave_events: Vec<LeaveEventFfi>,
+    /// Circles dropped with nothing to publish.
+    pub abandoned: Vec<Vec<u8>>,
+    /// Circles that could not be left (e.g. admin circles).
+    pub not_left: Vec<Vec<u8>>,
+    /// Circle rows removed from local storage.
+    pub circles_wiped: u32,
+}
+
+impl TryFrom<haven_core::circle::IdentityTeardown> for IdentityTeardownFfi {
+    type Error = HavenErrorFfi;
+
+    fn try_from(t: haven_core::circle::IdentityTeardown) -> Result<Self, Self::Error> {
+        let leave_events = t
+            .leave_events
+            .into_iter()
+            .map(LeaveEventFfi::try_from)
+            .collect::<Result<Vec<_>, HavenErrorFfi>>()?;
+        Ok(Self {
+            leave_events,
+            abandoned: t.abandoned.iter().map(|g| g.as_slice().to_vec()).collect(),
+            not_left: t.not_left.iter().map(|g| g.as_slice().to_vec()).collect(),
+            circles_wiped: u32::try_from(t.circles_wiped).unwrap_or(u32::MAX),
+        })
+    }
+}
+
+/// FFI mirror of [`haven_core::circle::LeaveAllReport`].
+#[derive(Debug, Clone)]
+pub struct LeaveAllReportFfi {
+    /// Circles left, with the leave proposal to publish now (it is also
+    /// queued in the outbox).
+    pub left: Vec<LeaveEventFfi>,
+    /// Circles dropped with nothing to publish.
+    pub abandoned: Vec<Vec<u8>>,
+    /// Circles that could not be left (e.g. admin circles); kept.
+    pub not_left: Vec<Vec<u8>>,
+    /// Leave proposals newly queued in the offline outbox.
+    pub queued: u32,
+}
+
+impl TryFrom<haven_core::circle::LeaveAllReport> for LeaveAllReportFfi {
+    type Error = HavenErrorFfi;
+
+    fn try_from(r: haven_core::circle::LeaveAllReport) -> Result<Self, Self::Error> {
+        let left = r
+            .left
+            .into_iter()
+            .map(LeaveEventFfi::try_from)
+            .collect::<Result<Vec<_>, HavenErrorFfi>>()?;
+        Ok(Self {
+            left,
+            abandoned: r.abandoned.iter().map(|g| g.as_slice().to_vec()).collect(),
+            not_left: r.not_left.iter().map(|g| g.as_slice().to_vec()).collect(),
+            queued: u32::try_from(r.queued).unwrap_or(u32::MAX),
+        })
+    }
+}
+
+// ==================== Invite links ====================
+
+/// A decoded invite link / QR payload — see
+/// [`haven_core::circle::invite_link`].
+#[derive(Debug, Clone)]
+pub struct InvitePayloadFfi {
+    /// The key to invite, hex-encoded.
+    pub pubkey_hex: String,
+    /// The same key in NIP-19 bech32 (`npub1...`).
+    pub npub: String,
+    /// Relays the key's `KeyPackage` can be fetched from.
+    pub relays: Vec<String>,
+    /// The circle the invitation is about (its Nostr group id), if any.
+    pub circle_id: Option<Vec<u8>>,
+}
+
+/// Encodes a `haven:` invite payload for a link or QR code.
+///
+/// `pubkey` is hex or `npub1...`; `circle_id`, if given, is a circle's
+/// 32-byte Nostr group id. At most three `wss://` relay hints fit.
+#[frb(sync)]
+pub fn generate_invite_payload(
+    pubkey: String,
+    relays: Vec<String>,
+    circle_id: Option<Vec<u8>>,
+) -> Result<String, HavenErrorFfi> {
+    let pubkey = nostr::PublicKey::parse(&pubkey)
+        .map_err(|_| HavenErrorFfi::invalid_key("Invalid pubkey"))?;
+    let circle_id = circle_id
+        .map(|id| <[u8; 32]>::try_from(id.as_slice()))
+        .transpose()
+        .map_err(|_| HavenErrorFfi::invalid_input("circle_id must be 32 bytes"))?;
+    haven_core::circle::InvitePayload::new(pubkey, relays, circle_id)
+        .and_then(|payload| payload.to_uri())
+        .map_err(HavenErrorFfi::from)
+}
+
+/// Parses a scanned or pasted invite payload (with or without `haven:`).
+#[frb(sync)]
+pub fn parse_invite_payload(payload: String) -> Result<InvitePayloadFfi, HavenErrorFfi> {
+    let parsed = haven_core::circle::InvitePayload::parse(&payload).map_err(HavenErrorFfi::from)?;
+    let pubkey_hex = parsed.pubkey.to_hex();
+    Ok(InvitePayloadFfi {
+        npub: npub_or_hex(&pubkey_hex),
+        pubkey_hex,
+        relays: parsed.relays,
+        circle_id: parsed.circle_id.map(|id| id.to_vec()),
+    })
+}
+
+// ==================== Relay preferences (kind 10050 / 10002) ====================
+//
+// FFI mirror of `haven_core::circle::RelayType`. Compile-time exhaustive on
+// the Dart side so we never round-trip a stringly-typed slug across the
+// boundary. Conversions live next to the type so they are easy to audit.
+//
+// RelayTypeFfi decision (Dark Matter W2): the pre-migration `KeyPackage`
+// variant (kind 10051) is RETIRED in favor of `Nip65` (kind 10002). Under Dark
+// Matter, KeyPackages are discovered on the account's NIP-65 kind-10002 relays
+// (the kind-10051 list is abolished + retracted at cutover). To avoid a
+// storage/schema churn, `Nip65` maps onto the SAME underlying
+// `RelayType::KeyPackage` storage slot + publish toggle (`publish_kp_relay_list`)
+// — only the ON-WIRE publish kind changes from 10051 to 10002. So a user's
+// "where my KeyPackage is discoverable" relay list is stored exactly as before;
+// the publish/maintenance paths just emit kind 10002 (`r` tags) instead of
+// 10051 (`relay` tags). The core `RelayType::KeyPackage` (10051) survives only
+// for the one-time cutover RETRACTION of the abolished 10051 list.
+
+/// Category of relay preference managed per user.
+///
+/// Mirrors [`haven_core::circle::RelayType`] with the Dark Matter W2 rename:
+/// - [`RelayTypeFfi::Inbox`] → kind 10050 (NIP-17 welcome delivery).
+/// - [`RelayTypeFfi::Nip65`] → kind 10002 (NIP-65; KeyPackage discovery under
+///   Dark Matter). Persisted under the same slot the retired kind-10051
+///   `KeyPackage` list used, so no relay-preference data migrates.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RelayTypeFfi {
+    /// Inbox relays (kind 10050, NIP-17).
+    Inbox,
+    /// NIP-65 relays (kind 10002) — where this account's KeyPackage is
+    /// discoverable under Dark Matter (W2, replacing the retired kind-10051
+    /// list). Stored under the `RelayType::KeyPackage` slot.
+    Nip65,
+}
+
+impl From<RelayTypeFfi> for haven_core::circle::RelayType {
+    fn from(t: RelayTypeFfi) -> Self {
+        match t {
+            RelayTypeFfi::Inbox => Self::Inbox,
+            // `Nip65` shares the persisted `KeyPackage` slot (see banner).
+            RelayTypeFfi::Nip65 => Self::KeyPackage,
+        }
+    }
+}
+
+impl From<haven_core::circle::RelayType> for RelayTypeFfi {
+    fn from(t: haven_core::circle::RelayType) -> Self {
+        match t {
+            haven_core::circle::RelayType::Inbox => Self::Inbox,
+            haven_core::circle::RelayType::KeyPackage => Self::Nip65,
+        }
+    }
+}
+
+/// The on-wire replaceable-list kind published for a relay-preference category
+/// under Dark Matter: 10050 for Inbox, **10002 (NIP-65)** for the KeyPackage-
+/// discovery list (W2; the kind-10051 list is retired).
+fn relay_list_wire_kind(relay_type: RelayTypeFfi) -> nostr::Kind {
+    haven_core::relay::relay_list_wire_kind(relay_type.into())
+}
+
+/// Builds the signed replaceable relay-list event for `relay_type`, choosing
+/// the correct Dark Matter wire form: kind-10050 `relay` tags for Inbox,
+/// kind-10002 `r` tags for the NIP-65 KeyPackage-discovery list (W2).
+fn build_relay_list_event_for(
+    relay_type: RelayTypeFfi,
+    signer: &dyn Signer,
+    urls: &[String],
+    created_at: Option<i64>,
+) -> Result<nostr::Event, HavenErrorFfi> {
+    haven_core::relay::build_wire_relay_list_event(signer, relay_type.into(), urls, created_at)
+        .map_err(|e| HavenErrorFfi::internal(format!("Failed to build relay list event: {e}")))
+}
+
+/// Builds the "empty replacement" event used to unpublish a relay-list category,
+/// in the correct Dark Matter wire form: an empty kind-10050 replacement for
+/// Inbox, an empty kind-10002 (NIP-65) replacement for the KeyPackage-discovery
+/// list (W2). `last_published_at` floors the `created_at` so the replacement
+/// strictly supersedes the previous list across clock skew.
+fn build_relay_list_unpublish_for(
+    relay_type: RelayTypeFfi,
+    signer: &dyn Signer,
+    last_published_at: Option<i64>,
+) -> Result<nostr::Event, HavenErrorFfi> {
+    match relay_type {
+        RelayTypeFfi::Inbox => haven_core::relay::build_unpublish_event(
+            signer,
+            haven_core::circle::RelayType::Inbox,
+            last_published_at,
+        ),
+        RelayTypeFfi::Nip65 => haven_core::relay::build_nip65_relay_list_event(
+            signer,
+            &[],
+            Some(haven_core::relay::superseding_created_at(last_published_at)),
+        ),
+    }
+    .map_err(|e| HavenErrorFfi::internal(format!("Failed to build replacement: {e}")))
+}
+
+/// Outcome of a [`CircleManagerFfi::build_relay_list_publish`] call.
+///
+/// The FFI builds the signed event AND resolves the publish targets
+/// atomically with the toggle check, then hands them to Dart. Dart must
+/// publish using the returned `targets` exactly — it must NOT widen them
+/// or fall back to a Dart-side default. The toggle integrity guarantee
+/// rests on the fact that Dart cannot get an event without first calling
+/// this method, which short-circuits to `suppressed=true` when the toggle
+/// is off.
+#[derive(Debug, Clone)]
+pub struct BuiltRelayListEventFfi {
+    /// Signed event JSON, ready for `RelayManagerFfi::publish_event`.
+    /// `None` when `suppressed` is `true`.
+    pub event_json: Option<String>,
+    /// Hex-encoded event id; `None` when suppressed.
+    pub event_id_hex: Option<String>,
+    /// Resolved publish targets — the user's own configured relays for
+    /// `relay_type`, deduplicated and nothing else. Two-plane model: the
+    /// public default set is NEVER force-unioned in, so a private relay is
+    /// never published to a public relay. Empty when suppressed.
+    pub targets: Vec<String>,
+    /// Numeric Nostr kind (10050 or 10051). `None` when suppressed.
+    pub kind: Option<u16>,
+    /// Unix-seconds `created_at` from the signed event. Pass this back
+    /// to [`CircleManagerFfi::record_published_relay_list`] so the
+    /// recorded `published_at` matches the timestamp other relays will
+    /// see — this is what
+    /// [`haven_core::relay::publishers::build_unpublish_event`] reads to
+    /// defeat clock skew on the next replacement.
+    pub created_at_secs: Option<i64>,
+    /// `true` when the user's privacy toggle is OFF for this category;
+    /// the caller must NOT publish anything in this case.
+    pub suppressed: bool,
+}
+
+/// Outcome of [`CircleManagerFfi::build_unpublish_relay_list`].
+///
+/// Two events: the empty-replacement (always populated when not suppressed)
+/// and the optional NIP-09 deletion (populated only when a previously
+/// published event is on record).
+#[derive(Debug, Clone)]
+pub struct BuiltUnpublishFfi {
+    /// Empty-replacement event JSON. Always populated when `suppressed`
+    /// is false.
+    pub replacement_event_json: Option<String>,
+    /// Best-effort NIP-09 deletion JSON. Populated only when a prior
+    /// publication was recorded.
+    pub deletion_event_json: Option<String>,
+    /// Resolved targets. For `build_unpublish_relay_list` (full opt-out)
+    /// these are the user's own configured relays (no default union). For
+    /// `build_relay_removal_scrub` these are the specific dropped relays.
+    pub targets: Vec<String>,
+    /// `true` when nothing should be published (no prior record AND
+    /// toggle was already off — there's nothing to unpublish).
+    pub suppressed: bool,
+}
+
+/// Serializes a group-evolving commit event to JSON for the Dart publish path.
+fn commit_event_to_json(event: &nostr::Event) -> Result<String, HavenErrorFfi> {
+    serde_json::to_string(event)
+        .map_err(|e| HavenErrorFfi::internal(format!("Failed to serialize commit event: {e}")))
+}
+
+/// Converts a core [`haven_core::circle::CommitToPublish`] into its FFI mirror.
+fn convert_commit_to_publish(
+    commit: haven_core::circle::CommitToPublish,
+) -> Result<CommitToPublishFfi, HavenErrorFfi> {
+    Ok(CommitToPublishFfi {
+        commit_event_json: commit_event_to_json(&commit.commit_event)?,
+        pending: commit.pending.into(),
+    })
+}
+
+// ==================== FFI input validation helpers ====================
+//
+// The actual validators live in `haven_core::validation` so they can be
+// unit-tested from `cargo test -p haven-core` without the Flutter bridge.
+use haven_core::validation::{normalize_pubkey_hex, parse_nostr_group_id, validate_pubkey_hex};
+
+/// Circle manager (FFI wrapper).
+///
+/// High-level API for managing circles (location sharing groups).
+/// Combines MLS operations with local storage for circle metadata
+/// and contact information.
+///
+/// # Privacy Model
+///
+/// Haven uses a privacy-first approach:
+/// - User profiles (kind 0) are never published to relays
+/// - Contact info (display names, avatars) is stored locally only
+/// - Relays only see pubkeys, never usernames
+///
+/// # Thread Safety
+///
+/// This type is `Send + Sync` via `Arc<CoreCircleManager>`. The underlying
+/// `CircleManager` protects its SQLite connection with its own fine-grained
+/// internal `Mutex`, so wrapping it in an outer mutex here would only
+/// serialise every FFI call through a single global lock — a significant
+/// throughput bottleneck under concurrent Dart→Rust traffic. Blocking
+/// I/O work (SQLite, MDK) is dispatched to `tokio::task::spawn_blocking`
+/// via [`run_blocking`] so it does not monopolise the async runtime's
+/// worker threads. Pure-CPU `#[frb(sync)]` methods (e.g. constant
+/// getters, relay list signing) bypass `run_blocking` and execute inline.
+#[frb(opaque)]
+pub struct CircleManagerFfi {
+    inner: Arc<CoreCircleManager>,
+}
+
+// Compile-time assertion: the refactor above is only sound if the core
+// manager is actually `Send + Sync`. If this ever stops compiling, the
+// outer mutex must be reinstated (or the root cause fixed upstream).
+const _: fn() = || {
+    fn assert_send_sync<T: Send + Sync>() {}
+    assert_send_sync::<CoreCircleManager>();
+};
+
+/// Extracts the NIP-33 `d` tag value from a probed kind-30443 `KeyPackage`
+/// event, if present. Used by [`RelayManagerFfi::maintain_key_package`] to
+/// build the on-relay snapshot. Never logged.
+#[inline]
+fn kp_event_d_tag(event: &nostr::Event) -> Option<String> {
+    event.tags.iter().find_map(|t| {
+        let s = t.as_slice();
+        (s.len() >= 2 && s[0] == "d").then(|| s[1].clone())
+    })
+}
+
+/// Extracts the `["relay", <url>]` URLs from a probed kind-10050 (Inbox)
+/// relay-list event, for drift detection in
+/// [`RelayManagerFfi::maintain_relay_list_category`]. Never logged.
+#[inline]
+fn relay_list_urls(event: &nostr::Event) -> Vec<String> {
+    event
         .tags
         .iter()
         .filter_map(|t| {
             let s = t.as_slice();
             (s.len() >= 2 && s[0] == "relay").then(|| s[1].clone())
         })
-        .collect()
-}
+        .collect()
+}
+
+/// Extracts the `["r", <url>]` URLs from a probed kind-10002 (NIP-65)
+/// relay-list event, for drift detection on the KeyPackage-discovery list under
+/// Dark Matter (W2; NIP-65 tags relays as `r`, NOT the `relay` form 10050 uses).
+#[inline]
+fn nip65_relay_list_urls(event: &nostr::Event) -> Vec<String> {
+    event
+        .tags
+        .iter()
+        .filter_map(|t| {
+            let s = t.as_slice();
+            (s.len() >= 2 && s[0] == "r").then(|| s[1].clone())
+        })
+        .collect()
+}
+
+/// The on-relay URL extractor for a relay-list category's wire form: `relay`
+/// tags for Inbox (10050), `r` tags for the NIP-65 KeyPackage list (10002).
+#[inline]
+fn relay_list_urls_for(relay_type: RelayTypeFfi, event: &nostr::Event) -> Vec<String> {
+    match relay_type {
+        RelayTypeFfi::Inbox => relay_list_urls(event),
+        RelayTypeFfi::Nip65 => nip65_relay_list_urls(event),
+    }
+}
+
+#[inline]
+async fn run_blocking<F, T>(f: F) -> Result<T, HavenErrorFfi>
+where
+    F: FnOnce() -> Result<T, HavenErrorFfi> + Send + 'static,
+    T: Send + 'static,
+{
+    tokio::task::spawn_blocking(f).await.map_err(|e| {
+        // Log only the failure category, never the panic payload — it may
+        // contain key material or MLS group IDs via Debug representations.
+        if e.is_panic() {
+            log::error!("CircleManagerFfi blocking task panicked");
+        } else {
+            log::error!("CircleManagerFfi blocking task cancelled");
+        }
+        HavenErrorFfi::internal("Internal task failure")
+    })?
+}
+
+/// Adds NIP-13 proof of work to an identity-signed event on the blocking pool
+/// when `pow_difficulty` is set (see [`haven_core::relay::pow`]); returns the
+/// event unchanged otherwise.
+async fn with_pow<S: Signer + Clone + 'static>(
+    event: nostr::Event,
+    signer: &S,
+    pow_difficulty: Option<u8>,
+) -> Result<nostr::Event, HavenErrorFfi> {
+    let Some(difficulty) = pow_difficulty else {
+        return Ok(event);
+    };
+    let signer = signer.clone();
+    run_blocking(move || {
+        haven_core::relay::pow::mine_event(&event, difficulty, &signer).map_err(HavenErrorFfi::from)
+    })
+    .await
+}
+
+/// Signs a NIP-09 deletion of `event_ids` and returns it as JSON.
+fn sign_deletion(keys: &nostr::Keys, event_ids: &[String]) -> Result<String, HavenErrorFfi> {
+    if event_ids.is_empty() {
+        return Err(HavenErrorFfi::invalid_input(
+            "No event IDs provided for deletion",
+        ));
+    }
+    let ids: Vec<nostr::EventId> = event_ids
+        .iter()
+        .map(|id| {
+            nostr::EventId::from_hex(id)
+                .map_err(|e| HavenErrorFfi::invalid_input(format!("Invalid event ID '{id}': {e}")))
+        })
+        .collect::<Result<Vec<_>, HavenErrorFfi>>()?;
+
+    let deletion = nostr::nips::nip09::EventDeletionRequest::new().ids(ids);
+    let event = nostr::EventBuilder::delete(deletion)
+        .sign_with_keys(keys)
+        .map_err(|e| HavenErrorFfi::internal(format!("Failed to sign deletion event: {e}")))?;
+
+    serde_json::to_string(&event)
+        .map_err(|e| HavenErrorFfi::internal(format!("Failed to serialize deletion event: {e}")))
+}
+
+/// Parses a member's key package bundle.
+fn member_key_package_from_ffi(
+    m: MemberKeyPackageFfi,
+) -> Result<haven_core::circle::MemberKeyPackage, HavenErrorFfi> {
+    let key_package_event: nostr::Event = serde_json::from_str(&m.key_package_json)
+        .map_err(|e| HavenErrorFfi::invalid_input(format!("Invalid key package JSON: {e}")))?;
+    Ok(haven_core::circle::MemberKeyPackage {
+        key_package_event,
+        inbox_relays: m.inbox_relays,
+        nip65_relays: m.nip65_relays,
+    })
+}
+
+/// Converts a gift-wrapped Welcome for the FFI.
+fn welcome_to_ffi(
+    w: haven_core::circle::GiftWrappedWelcome,
+) -> Result<GiftWrappedWelcomeFfi, HavenErrorFfi> {
+    let event_json = serde_json::to_string(&w.event)
+        .map_err(|e| HavenErrorFfi::internal(format!("Failed to serialize welcome event: {e}")))?;
+    Ok(GiftWrappedWelcomeFfi {
+        recipient_pubkey: w.recipient_pubkey,
+        recipient_relays: w.recipient_relays,
+        fallback_relays: w.fallback_relays,
+        event_json,
+    })
+}
+
+/// Converts the result of adding members for the FFI.
+fn add_members_result_to_ffi(
+    result: haven_core::circle::AddMembersResult,
+) -> Result<AddMembersResultFfi, HavenErrorFfi> {
+    Ok(AddMembersResultFfi {
+        commit_event_json: commit_event_to_json(&result.commit_event)?,
+        welcome_events: result
+            .welcome_events
+            .into_iter()
+            .map(welcome_to_ffi)
+            .collect::<Result<Vec<_>, HavenErrorFfi>>()?,
+        pending: result.pending.into(),
+    })
+}
+
+/// Builds an outgoing location from the FFI send arguments.
+///
+/// Location messages no longer carry a display name: names moved to public
+/// kind-0 profiles at the public-profile migration.
+fn outgoing_location(
+    latitude: f64,
+    longitude: f64,
+    share_expiration_secs: Option<u64>,
+    device_status: Option<DeviceStatusFfi>,
+    vertical_position: Option<VerticalPositionFfi>,
+) -> haven_core::location::LocationMessage {
+    let mut location = share_expiration_secs.map_or_else(
+        || haven_core::location::LocationMessage::new(latitude, longitude),
+        |secs| {
+            haven_core::location::LocationMessage::with_expiration(
+                latitude,
+                longitude,
+                haven_core::location::ShareExpiration::from_secs(secs),
+            )
+        },
+    );
+    location.device_status = device_status
+        .map(|d| haven_core::location::DeviceStatus::from(d).sanitized())
+        .filter(|d| !d.is_empty());
+    location.vertical_position = vertical_position
+        .map(|v| haven_core::location::VerticalPosition::from(v).sanitized())
+        .filter(|v| !v.is_empty());
+    location
+}
+
+impl CircleManagerFfi {
+    /// Creates a new circle manager bound to the device identity.
+    ///
+    /// Initializes both the Dark Matter MLS session (`session.sqlite`) and the
+    /// circle metadata database (`circles.db`) at the given data directory.
+    /// Ensures the platform keyring store is initialized first (idempotent).
+    ///
+    /// # Dark Matter identity gating (DM-4)
+    ///
+    /// The Dark Matter [`SessionManager`] binds the device's Nostr identity as
+    /// its account identity, its NIP-59 welcome signer, AND its hardened
+    /// account-identity-proof signer (Rule 1), so the identity secret is now a
+    /// HARD construction requirement — the engine cannot open without it.
+    /// `secret` (from `NostrIdentityManager.secret_handle()`) MUST be present;
+    /// a missing identity fails closed with a clear error, matching the
+    /// existing identity-gating call sites.
+    ///
+    /// [`SessionManager`]: haven_core::nostr::mls::SessionManager
+    pub fn new(data_dir: String, secret: &SecretHandle) -> Result<Self, HavenErrorFfi> {
+        Self::open(&data_dir, &secret.keys()?)
+    }
+
+    fn open(data_dir: &str, keys: &nostr::Keys) -> Result<Self, HavenErrorFfi> {
+        init_keyring_store()?;
+        let circle_db_key = get_or_create_circle_db_key()?;
+        let path = Path::new(data_dir);
+        let inner = CoreCircleManager::new(path, keys, Some(&circle_db_key))
+            .map_err(HavenErrorFfi::from)?;
+        // Every RelayManager in the process consults the persisted blacklist,
+        // queues undeliverable location updates in the offline outbox and
+        // persists its relay health counters.
+        haven_core::relay::install_relay_blacklist(
+            inner.relay_blacklist().map_err(HavenErrorFfi::from)?,
+        );
+        haven_core::relay::install_publish_queue(Arc::new(inner.publish_queue()));
+        haven_core::relay::install_relay_stats_store(Arc::new(inner.relay_stats_store()));
+        // Event signing follows the stored NIP-46 pairing, if any.
+        install_remote_signer(
+            inner
+                .get_remote_signer()
+                .map_err(HavenErrorFfi::from)?
+                .map(|config| Nip46Signer::from_config(CoreRelayManager::new(), &config))
+                .transpose()?,
+        );
+        Ok(Self {
+            inner: Arc::new(inner),
+        })
+    }
+
+    // ==================== Circle Lifecycle ====================
+
+    /// Creates a new circle with gift-wrapped Welcome events.
+    ///
+    /// Returns the created circle and gift-wrapped Welcome events ready
+    /// to publish to the invited members' inbox relays.
+    ///
+    /// # Arguments
+    ///
+    /// * `secret` - The creator's identity (from
+    ///   `NostrIdentityManager.secret_handle()`)
+    /// * `members` - Key packages and inbox relays for each member
+    /// * `name` - Circle name
+    /// * `description` - Optional circle description
+    /// * `circle_type` - Circle type: "location_sharing" or "direct_share"
+    /// * `relays` - Relay URLs for the circle's messages
+    /// * `creator_fallback_relays` - The creator's own inbox relays (kind
+    ///   10050), used as the third tier in the Welcome delivery cascade
+    ///   (member 10050 → member 10002 → creator inbox → FAIL CLOSED). Pass an
+    ///   empty list if the creator has no inbox relays; delivery then fails
+    ///   closed (no public-default fallback) when tiers 1–2 are also empty.
+    ///
+    /// # Security
+    ///
+    /// The Welcome events are gift-wrapped per NIP-59, hiding the sender's
+    /// identity behind an ephemeral key. Each Welcome uses a fresh ephemeral
+    /// keypair and randomized timestamp.
+    //
+    // The FFI signature is dictated by the Flutter-Rust contract (one Dart
+    // argument per Rust parameter); grouping these into a struct would
+    // change the generated bindings. The arity is reviewed at the
+    // architectural level, not by clippy.
+    #[allow(clippy::too_many_arguments)]
+    pub async fn create_circle(
+        &self,
+        secret: &SecretHandle,
+        members: Vec<MemberKeyPackageFfi>,
+        name: String,
+        description: Option<String>,
+        circle_type: String,
+        relays: Vec<String>,
+        creator_fallback_relays: Vec<String>,
+    ) -> Result<CircleCreationResultFfi, HavenErrorFfi> {
+        let keys = secret.keys()?;
+        // Parse member key packages
+        let member_key_packages: Vec<haven_core::circle::MemberKeyPackage> = members
+            .into_iter()
+            .map(|m| {
+                let key_package_event: nostr::Event = serde_json::from_str(&m.key_package_json)
+                    .map_err(|e| {
+                        HavenErrorFfi::invalid_input(format!("Invalid key package JSON: {e}"))
+                    })?;
+                Ok(haven_core::circle::MemberKeyPackage {
+                    key_package_event,
+                    inbox_relays: m.inbox_relays,
+                    nip65_relays: m.nip65_relays,
+                })
+            })
+            .collect::<Result<Vec<_>, HavenErrorFfi>>()?;
+
+        // Parse circle type
+        let ct = CoreCircleType::parse(&circle_type).ok_or_else(|| {
+            HavenErrorFfi::invalid_input(format!("Invalid circle type: {circle_type}"))
+        })?;
+
+        let config = CoreCircleConfig::new(&name)
+            .with_type(ct)
+            .with_relays(relays);
+
+        let config = if let Some(desc) = description {
+            config.with_description(desc)
+        } else {
+            config
+        };
+
+        // `CircleManager::create_circle` is genuinely async (giftwrap
+        // construction awaits), so it stays on the current tokio worker.
+        // The inner SQLite writes are still protected by the core's own
+        // fine-grained mutex.
+        let result = self
+            .inner
+            .create_circle(
+                &keys,
+                member_key_packages,
+                &config,
+                &creator_fallback_relays,
+            )
+            .await
+            .map_err(HavenErrorFfi::from)?;
+
+        self.creation_result_to_ffi(result).await
+    }
+
+    /// Converts a staged creation to FFI. F3: if serialization fails
+    /// after the create was staged, roll the pending back BEFORE returning
+    /// (the core `publish_failed` also deletes the just-saved circle rows), so
+    /// neither a leaked `PendingStateRef` nor a ghost circle row survives.
+    async fn creation_result_to_ffi(
+        &self,
+        result: haven_core::circle::CircleCreationResult,
+    ) -> Result<CircleCreationResultFfi, HavenErrorFfi> {
+        let pending_ref = result.pending;
+        let welcome_events: Vec<GiftWrappedWelcomeFfi> = match result
+            .welcome_events
+            .into_iter()
+            .map(|w| {
+                let event_json = serde_json::to_string(&w.event).map_err(|e| {
+                    HavenErrorFfi::internal(format!("Failed to serialize welcome event: {e}"))
+                })?;
+                Ok(GiftWrappedWelcomeFfi {
+                    recipient_pubkey: w.recipient_pubkey,
+                    recipient_relays: w.recipient_relays,
+                    fallback_relays: w.fallback_relays,
+                    event_json,
+                })
+            })
+            .collect::<Result<Vec<_>, HavenErrorFfi>>()
+        {
+            Ok(events) => events,
+            Err(e) => {
+                let _ = self.inner.publish_failed(pending_ref).await;
+                return Err(e);
+            }
+        };
+
+        let pending = pending_ref.into();
+        Ok(CircleCreationResultFfi {
+            circle: CircleFfi::from(&result.circle),
+            welcome_events,
+            pending,
+        })
+    }
+
+    /// Gets a circle by its MLS group ID.
+    ///
+    /// Async: resolving the roster reads the Dark Matter session (which is
+    /// `&mut`-serialized behind a `tokio` mutex), so this awaits directly on
+    /// the current worker rather than dispatching to the blocking pool.
+    pub async fn get_circle(
+        &self,
+        mls_group_id: Vec<u8>,
+    ) -> Result<Option<CircleWithMembersFfi>, HavenErrorFfi> {
+        let group_id = GroupId::from_slice(&mls_group_id);
+        self.inner
+            .get_circle(&group_id)
+            .await
+            .map(|opt| opt.map(|c| CircleWithMembersFfi::from(&c)))
+            .map_err(HavenErrorFfi::from)
+    }
+
+    /// Gets all circles.
+    pub async fn get_circles(&self) -> Result<Vec<CircleWithMembersFfi>, HavenErrorFfi> {
+        self.inner
+            .get_circles()
+            .await
+            .map(|circles| circles.iter().map(CircleWithMembersFfi::from).collect())
+            .map_err(HavenErrorFfi::from)
+    }
+
+    /// Gets visible circles (excludes declined invitations).
+    pub async fn get_visible_circles(&self) -> Result<Vec<CircleWithMembersFfi>, HavenErrorFfi> {
+        self.inner
+            .get_visible_circles()
+            .await
+            .map(|circles| circles.iter().map(CircleWithMembersFfi::from).collect())
+            .map_err(HavenErrorFfi::from)
+    }
+
+    /// Classifies the leave operation — see [`LeavePlanFfi`] for the
+    /// Flutter-side state machine.
+    pub async fn plan_leave(
+        &self,
+        mls_group_id: Vec<u8>,
+        self_pubkey_hex: String,
+    ) -> Result<LeavePlanFfi, HavenErrorFfi> {
+        let group_id = GroupId::from_slice(&mls_group_id);
+        let self_pk = nostr::PublicKey::from_hex(&self_pubkey_hex)
+            .map_err(|_| HavenErrorFfi::invalid_key("Invalid self_pubkey_hex"))?;
+        let plan = self
+            .inner
+            .plan_leave(&group_id, &self_pk)
+            .await
+            .map_err(HavenErrorFfi::from)?;
+        Ok(LeavePlanFfi::from(plan))
+    }
+
+    /// Lists the circles that still depend on the local identity. Show this
+    /// before offering identity deletion.
+    pub async fn identity_deletion_report(
+        &self,
+    ) -> Result<IdentityDeletionReportFfi, HavenErrorFfi> {
+        let report = self
+            .inner
+            .identity_deletion_report()
+            .await
+            .map_err(HavenErrorFfi::from)?;
+        Ok(IdentityDeletionReportFfi {
+            circles: report
+                .circles
+                .into_iter()
+                .map(|c| DependentCircleFfi {
+                    mls_group_id: c.mls_group_id.as_slice().to_vec(),
+                    name: c.name,
+                    plan: LeavePlanFfi::from(c.plan),
+                })
+                .collect(),
+            pending_invitations: u32::try_from(report.pending_invitations).unwrap_or(u32::MAX),
+        })
+    }
+
+    /// Wipes the identity-scoped circle state ahead of identity deletion,
+    /// optionally leaving every circle first. Fails while circles remain
+    /// unless `force` is set. Prefer
+    /// [`NostrIdentityManager::delete_identity_guarded`], which also deletes
+    /// the key.
+    pub async fn prepare_identity_deletion(
+        &self,
+        force: bool,
+        leave_first: bool,
+    ) -> Result<IdentityTeardownFfi, HavenErrorFfi> {
+        let teardown = self
+            .inner
+            .prepare_identity_deletion(force, leave_first)
+            .await
+            .map_err(HavenErrorFfi::from)?;
+        IdentityTeardownFfi::try_from(teardown)
+    }
+
+    /// The circle's admins, as hex public keys.
+    pub async fn get_admins(&self, mls_group_id: Vec<u8>) -> Result<Vec<String>, HavenErrorFfi> {
+        let group_id = GroupId::from_slice(&mls_group_id);
+        self.inner
+            .admins(&group_id)
+            .await
+            .map_err(HavenErrorFfi::from)
+    }
+
+    /// Replaces the circle's admin set with `admin_pubkeys` (hex), promoting
+    /// and demoting members in one commit. Admin-only; every entry must be a
+    /// current member.
+    ///
+    /// # GAP (plan §5.2 #18)
+    ///
+    /// Same admin-policy codec gap as
+    /// [`propose_admin_handoff`](Self::propose_admin_handoff): after
+    /// validation and the admin check, the core method returns a documented
+    /// error.
+    pub async fn set_admins(
+        &self,
+        mls_group_id: Vec<u8>,
+        admin_pubkeys: Vec<String>,
+    ) -> Result<CommitToPublishFfi, HavenErrorFfi> {
+        let group_id = GroupId::from_slice(&mls_group_id);
+        let commit = self
+            .inner
+            .set_admins(&group_id, &admin_pubkeys)
+            .await
+            .map_err(HavenErrorFfi::from)?;
+        convert_commit_to_publish(commit)
+    }
+
+    /// Step 1 of admin handoff: propose promoting `successor_hex` to admin.
+    ///
+    /// # GAP (plan §5.2 #18)
+    ///
+    /// The Dark Matter v0.9.4 public API exposes no admin-policy component
+    /// codec, so this currently returns a documented error (the core method
+    /// fails closed). Kept so the Dart leave state machine keeps its shape.
+    pub async fn propose_admin_handoff(
+        &self,
+        mls_group_id: Vec<u8>,
+        successor_hex: String,
+    ) -> Result<CommitToPublishFfi, HavenErrorFfi> {
+        let group_id = GroupId::from_slice(&mls_group_id);
+        let successor = nostr::PublicKey::from_hex(&successor_hex)
+            .map_err(|_| HavenErrorFfi::invalid_key("Invalid successor_hex"))?;
+        let commit = self
+            .inner
+            .propose_admin_handoff(&group_id, &successor)
+            .await
+            .map_err(HavenErrorFfi::from)?;
+        convert_commit_to_publish(commit)
+    }
+
+    /// Admin: replace this circle's group relay list (MIP-01) via an
+    /// `UpdateAppComponents(nostr-routing.v1)` commit.
+    ///
+    /// Returns a [`CommitToPublishFfi`]. Publish `commit_event_json` to the
+    /// **union of the circle's current relays and `new_relays`** (so a member
+    /// only listening on a relay being removed still receives the commit), then
+    /// call [`finalize_relay_update`](Self::finalize_relay_update) on a ≥1-relay
+    /// ACK or [`publish_failed`](Self::publish_failed) on failure. `new_relays`
+    /// MUST be non-empty, `wss://` (or the debug loopback test seam),
+    /// credential-free, and at most 20 entries; admin authorization is enforced
+    /// by the engine against live MLS state.
+    pub async fn update_circle_relays(
+        &self,
+        mls_group_id: Vec<u8>,
+        new_relays: Vec<String>,
+    ) -> Result<CommitToPublishFfi, HavenErrorFfi> {
+        let group_id = GroupId::from_slice(&mls_group_id);
+        let commit = self
+            .inner
+            .update_circle_relays(&group_id, &new_relays)
+            .await
+            .map_err(HavenErrorFfi::from)?;
+        convert_commit_to_publish(commit)
+    }
+
+    /// Admin: update a circle's name, description and/or group relay list.
+    /// `None` leaves a field unchanged; at least one must be set.
+    ///
+    /// Publish and finalize exactly as for
+    /// [`update_circle_relays`](Self::update_circle_relays) (publish to the
+    /// union of current and new relays, then
+    /// [`finalize_relay_update`](Self::finalize_relay_update)).
+    ///
+    /// # GAP (plan §5.2 #18)
+    ///
+    /// Name/description changes need the group-profile component codec, which
+    /// the engine does not expose yet; the core currently returns a documented
+    /// error for them. Relay-only updates work.
+    pub async fn update_circle(
+        &self,
+        mls_group_id: Vec<u8>,
+        new_name: Option<String>,
+        new_description: Option<String>,
+        new_relays: Option<Vec<String>>,
+    ) -> Result<CommitToPublishFfi, HavenErrorFfi> {
+        let group_id = GroupId::from_slice(&mls_group_id);
+        let commit = self
+            .inner
+            .update_circle_metadata(
+                &group_id,
+                new_name.as_deref(),
+                new_description.as_deref(),
+                new_relays.as_deref(),
+            )
+            .await
+            .map_err(HavenErrorFfi::from)?;
+        convert_commit_to_publish(commit)
+    }
+
+    /// Step 2 of admin handoff (or step 1 of `Abandon`): demote self from admin.
+    ///
+    /// # GAP (plan §5.2 #18)
+    ///
+    /// Same admin-policy-codec gap as [`propose_admin_handoff`](Self::propose_admin_handoff);
+    /// the core method currently returns a documented error.
+    pub async fn propose_self_demote(
+        &self,
+        mls_group_id: Vec<u8>,
+    ) -> Result<CommitToPublishFfi, HavenErrorFfi> {
+        let group_id = GroupId::from_slice(&mls_group_id);
+        let commit = self
+            .inner
+            .propose_self_demote(&group_id)
+            .await
+            .map_err(HavenErrorFfi::from)?;
+        convert_commit_to_publish(commit)
+    }
+
+    /// Returns a `SelfRemove` proposal event JSON. Publish it, then call
+    /// [`complete_leave`](Self::complete_leave).
+    ///
+    /// A bare `SelfRemove` proposal has NO `PendingStateRef` — a remaining
+    /// member commits it later (RFC 9420 §12.1.2), so there is nothing to
+    /// confirm or roll back. The returned string is the signed kind:445 event
+    /// JSON to publish to the circle's relays.
+    pub async fn propose_leave(&self, mls_group_id: Vec<u8>) -> Result<String, HavenErrorFfi> {
+        let group_id = GroupId::from_slice(&mls_group_id);
+        let event = self
+            .inner
+            .propose_leave(&group_id)
+            .await
+            .map_err(HavenErrorFfi::from)?;
+        commit_event_to_json(&event)
+    }
+
+    /// Removes the local circle row after a successful leave sequence, or
+    /// for the `OrphanLocalOnly` plan. (Storage-only; sync in the core.)
+    pub async fn complete_leave(&self, mls_group_id: Vec<u8>) -> Result<(), HavenErrorFfi> {
+        let inner = self.inner.clone();
+        run_blocking(move || {
+            let group_id = GroupId::from_slice(&mls_group_id);
+            inner.complete_leave(&group_id).map_err(HavenErrorFfi::from)
+        })
+        .await
+    }
+
+    /// Leaves a circle but keeps its local history, read-only, until
+    /// [`purge_circle`](Self::purge_circle).
+    ///
+    /// The `SelfRemove` proposal is queued in the offline outbox and returned
+    /// so it can be published right away; `None` for a sole-member or
+    /// orphaned circle, which needs nothing published.
+    pub async fn leave_and_archive_circle(
+        &self,
+        mls_group_id: Vec<u8>,
+    ) -> Result<Option<LeaveEventFfi>, HavenErrorFfi> {
+        let group_id = GroupId::from_slice(&mls_group_id);
+        self.inner
+            .leave_and_archive_circle(&group_id)
+            .await
+            .map_err(HavenErrorFfi::from)?
+            .map(LeaveEventFfi::try_from)
+            .transpose()
+    }
+
+    /// Circles kept for their history but no longer in use: archived, left
+    /// with [`leave_and_archive_circle`](Self::leave_and_archive_circle), or
+    /// removed by an admin. See
+    /// [`get_circle_lifecycle`](Self::get_circle_lifecycle) to tell them apart.
+    pub async fn get_archived_circles(&self) -> Result<Vec<CircleWithMembersFfi>, HavenErrorFfi> {
+        self.inner
+            .get_archived_circles()
+            .await
+            .map(|circles| circles.iter().map(CircleWithMembersFfi::from).collect())
+            .map_err(HavenErrorFfi::from)
+    }
+
+    /// Deletes a circle the user is no longer a member of, with its local
+    /// history. (Storage-only; sync in the core.)
+    pub async fn purge_circle(&self, mls_group_id: Vec<u8>) -> Result<(), HavenErrorFfi> {
+        let inner = self.inner.clone();
+        run_blocking(move || {
+            let group_id = GroupId::from_slice(&mls_group_id);
+            inner.purge_circle(&group_id).map_err(HavenErrorFfi::from)
+        })
+        .await
+    }
+
+    /// Leaves every circle the user can leave, at once (panic button).
+    ///
+    /// Leave proposals are queued in the offline outbox and returned so they
+    /// can be published immediately; left circles are removed locally.
+    /// Admin circles are kept and listed in `not_left`.
+    pub async fn leave_all_circles(&self) -> Result<LeaveAllReportFfi, HavenErrorFfi> {
+        let report = self
+            .inner
+            .leave_all_circles()
+            .await
+            .map_err(HavenErrorFfi::from)?;
+        LeaveAllReportFfi::try_from(report)
+    }
+
+    /// Wipes local state for the `Abandon` plan — sole-member cleanup with
+    /// no MLS commit and no relay publish. (Storage-only; sync in the core.)
+    pub async fn abandon_circle_local_only(
+        &self,
+        mls_group_id: Vec<u8>,
+    ) -> Result<(), HavenErrorFfi> {
+        let inner = self.inner.clone();
+        run_blocking(move || {
+            let group_id = GroupId::from_slice(&mls_group_id);
+            inner
+                .abandon_circle_local_only(&group_id)
+                .map_err(HavenErrorFfi::from)
+        })
+        .await
+    }
+
+    /// Returns the circle's lifecycle state (`"invited"`, `"pending"`,
+    /// `"active"`, `"archived"`, `"left"`, `"removed"` or `"declined"`).
+    pub async fn get_circle_lifecycle(
+        &self,
+        mls_group_id: Vec<u8>,
+    ) -> Result<String, HavenErrorFfi> {
+        let inner = self.inner.clone();
+        run_blocking(move || {
+            let group_id = GroupId::from_slice(&mls_group_id);
+            inner
+                .circle_lifecycle(&group_id)
+                .map(|state| state.as_str().to_string())
+                .map_err(HavenErrorFfi::from)
+        })
+        .await
+    }
+
+    /// Hides an active circle without leaving it.
+    pub async fn archive_circle(&self, mls_group_id: Vec<u8>) -> Result<(), HavenErrorFfi> {
+        let inner = self.inner.clone();
+        run_blocking(move || {
+            let group_id = GroupId::from_slice(&mls_group_id);
+            inner.archive_circle(&group_id).map_err(HavenErrorFfi::from)
+        })
+        .await
+    }
+
+    /// Restores an archived circle, thawing it first if it was frozen.
+    pub async fn unarchive_circle(&self, mls_group_id: Vec<u8>) -> Result<(), HavenErrorFfi> {
+        let inner = self.inner.clone();
+        run_blocking(move || {
+            let group_id = GroupId::from_slice(&mls_group_id);
+            inner
+                .unarchive_circle(&group_id)
+                .map_err(HavenErrorFfi::from)
+        })
+        .await
+    }
+
+    /// Moves an archived circle's local history into compressed, sealed
+    /// cold storage. Returns the existing summary if already frozen.
+    pub async fn freeze_circle(
+        &self,
+        mls_group_id: Vec<u8>,
+    ) -> Result<ColdCircleInfoFfi, HavenErrorFfi> {
+        let inner = self.inner.clone();
+        run_blocking(move || {
+            inner
+                .freeze_circle(&GroupId::from_slice(&mls_group_id))
+                .map(Into::into)
+                .map_err(HavenErrorFfi::from)
+        })
+        .await
+    }
+
+    /// Restores a frozen circle's history without unarchiving it. Returns
+    /// `false` if it was not frozen.
+    pub async fn thaw_circle(&self, mls_group_id: Vec<u8>) -> Result<bool, HavenErrorFfi> {
+        let inner = self.inner.clone();
+        run_blocking(move || {
+            inner
+                .thaw_circle(&GroupId::from_slice(&mls_group_id))
+                .map_err(HavenErrorFfi::from)
+        })
+        .await
+    }
+
+    /// Summary of a frozen circle, or `None` if it is not in cold storage.
+    pub async fn cold_circle_info(
+        &self,
+        mls_group_id: Vec<u8>,
+    ) -> Result<Option<ColdCircleInfoFfi>, HavenErrorFfi> {
+        let inner = self.inner.clone();
+        run_blocking(move || {
+            inner
+                .cold_circle_info(&GroupId::from_slice(&mls_group_id))
+                .map(|info| info.map(Into::into))
+                .map_err(HavenErrorFfi::from)
+        })
+        .await
+    }
+
+    /// Records that the local user was removed from the circle by an admin.
+    /// The row is kept (hidden) until `purge_circle` deletes it.
+    pub async fn mark_removed(&self, mls_group_id: Vec<u8>) -> Result<(), HavenErrorFfi> {
+        let inner = self.inner.clone();
+        run_blocking(move || {
+            let group_id = GroupId::from_slice(&mls_group_id);
+            inner.mark_removed(&group_id).map_err(HavenErrorFfi::from)
+        })
+        .await
+    }
+
+    // ==================== Publish-before-apply (Rule 13) ====================
+
+    /// Confirms a staged commit was published (≥1-relay OK-ack) so the engine
+    /// applies it and advances the epoch.
+    ///
+    /// "Acked" MUST mean a relay returned OK — never merely "sent" — to avoid
+    /// optimistic-merge forks (Rule 13, security F13). Pass the `pending` token
+    /// carried in a [`CircleCreationResultFfi`] / [`AddMembersResultFfi`] /
+    /// [`CommitToPublishFfi`].
+    pub async fn confirm_published(
+        &self,
+        pending: PendingStateRefFfi,
+    ) -> Result<(), HavenErrorFfi> {
+        self.inner
+            .confirm_published(pending.into())
+            .await
+            .map_err(HavenErrorFfi::from)
+    }
+
+    /// Reports that a staged publish FAILED; the engine discards the staged
+    /// commit and returns the group to `Stable` at the prior epoch.
+    ///
+    /// The publish-failure counterpart to [`confirm_published`](Self::confirm_published);
+    /// pass the same `pending` token.
+    pub async fn publish_failed(&self, pending: PendingStateRefFfi) -> Result<(), HavenErrorFfi> {
+        self.inner
+            .publish_failed(pending.into())
+            .await
+            .map_err(HavenErrorFfi::from)
+    }
+
+    // ==================== Member Management ====================
+
+    /// Adds members to an existing circle and gift-wraps their Welcomes.
+    ///
+    /// The add-time counterpart to [`create_circle`]: stages an MLS Add commit
+    /// (kind 445, advances existing members on finalize) and gift-wraps the
+    /// resulting per-member Welcome rumors (kind 444) for delivery, resolving
+    /// each recipient's relays through the same fail-closed cascade.
+    ///
+    /// The caller owns the publish/finalize cycle: publish
+    /// `evolution_event_json` to the circle's relays, finalize the pending
+    /// commit on success (or clear it on failure), then publish each
+    /// gift-wrapped Welcome only after a successful finalize.
+    ///
+    /// # Arguments
+    ///
+    /// * `secret` - The admin's identity.
+    /// * `mls_group_id` - The circle's MLS group ID.
+    /// * `members` - Key packages and inbox/NIP-65 relays for the new members.
+    /// * `creator_fallback_relays` - The admin's own inbox relays (kind 10050),
+    ///   used as the third tier in the Welcome delivery cascade (member 10050 →
+    ///   member 10002 → admin inbox → FAIL CLOSED). Pass an empty list if the
+    ///   admin has no inbox relays; delivery then fails closed (no
+    ///   public-default fallback) when tiers 1–2 are also empty.
+    ///
+    /// # Security
+    ///
+    /// The Welcome events are gift-wrapped per NIP-59, hiding the sender's
+    /// identity behind a fresh ephemeral key.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a key package fails to parse, the caller is not an
+    /// admin, or a member has no reachable Welcome relay (fail-closed).
+    pub async fn add_members_to_circle(
+        &self,
+        secret: &SecretHandle,
+        mls_group_id: Vec<u8>,
+        members: Vec<MemberKeyPackageFfi>,
+        creator_fallback_relays: Vec<String>,
+    ) -> Result<AddMembersResultFfi, HavenErrorFfi> {
+        let keys = secret.keys()?;
+
+        let member_key_packages: Vec<haven_core::circle::MemberKeyPackage> = members
+            .into_iter()
+            .map(member_key_package_from_ffi)
+            .collect::<Result<Vec<_>, HavenErrorFfi>>()?;
+
+        let group_id = GroupId::from_slice(&mls_group_id);
+
+        // `add_members_with_welcomes` is genuinely async (engine send +
+        // giftwrap construction await), so it stays on the current tokio worker.
+        let result = self
+            .inner
+            .add_members_with_welcomes(
+                &keys,
+                &group_id,
+                member_key_packages,
+                &creator_fallback_relays,
+            )
+            .await
+            .map_err(HavenErrorFfi::from)?;
+        add_members_result_to_ffi(result)
+    }
+
+    /// Members invited more than `grace_secs` ago (default two days) who
+    /// have not sent anything to the circle yet.
+    pub async fn unjoined_members(
+        &self,
+        mls_group_id: Vec<u8>,
+        grace_secs: Option<i64>,
+    ) -> Result<Vec<UnjoinedMemberFfi>, HavenErrorFfi> {
+        let group_id = GroupId::from_slice(&mls_group_id);
+        let grace_secs = grace_secs.unwrap_or(haven_core::circle::INVITE_JOIN_GRACE_SECS);
+        let inner = self.inner.clone();
+        run_blocking(move || {
+            inner
+                .unjoined_members(&group_id, grace_secs, now_ms() / 1000)
+                .map(|members| members.iter().map(UnjoinedMemberFfi::from).collect())
+                .map_err(HavenErrorFfi::from)
+        })
+        .await
+    }
+
+    /// Resends an unjoined member's invitation.
+    ///
+    /// Returns the original Welcome to publish again to its relays, or
+    /// `None` when the circle has changed since: then fetch the member's
+    /// current `KeyPackage` and call [`reissue_invite`](Self::reissue_invite).
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if there is no invitation to the member, they have
+    /// joined or left, or the invitation was resent too recently or too
+    /// often.
+    pub async fn resend_invite(
+        &self,
+        mls_group_id: Vec<u8>,
+        member_pubkey: String,
+    ) -> Result<Option<GiftWrappedWelcomeFfi>, HavenErrorFfi> {
+        let group_id = GroupId::from_slice(&mls_group_id);
+        match self
+            .inner
+            .resend_invite(&group_id, &member_pubkey)
+            .await
+            .map_err(HavenErrorFfi::from)?
+        {
+            haven_core::circle::InviteResend::Republish(welcome) => {
+                welcome_to_ffi(welcome).map(Some)
+            }
+            haven_core::circle::InviteResend::NeedsKeyPackage => Ok(None),
+        }
+    }
+
+    /// Invites an unjoined member again with their current `KeyPackage`,
+    /// when [`resend_invite`](Self::resend_invite) returned `None`.
+    ///
+    /// Publish and confirm like
+    /// [`add_members_to_circle`](Self::add_members_to_circle).
+    pub async fn reissue_invite(
+        &self,
+        secret: &SecretHandle,
+        mls_group_id: Vec<u8>,
+        member: MemberKeyPackageFfi,
+        creator_fallback_relays: Vec<String>,
+    ) -> Result<AddMembersResultFfi, HavenErrorFfi> {
+        let keys = secret.keys()?;
+        let member = member_key_package_from_ffi(member)?;
+        let group_id = GroupId::from_slice(&mls_group_id);
+        let result = self
+            .inner
+            .reissue_invite(&keys, &group_id, member, &creator_fallback_relays)
+            .await
+            .map_err(HavenErrorFfi::from)?;
+        add_members_result_to_ffi(result)
+    }
+
+    /// Sets up a scheduled check-in: `member_pubkey` (hex) is expected to
+    /// share a location in the circle within `window_minutes` before
+    /// `deadline_minute` (local minutes after midnight) on `weekdays`
+    /// (bit 0 is Monday, `0x7f` every day). Stays on this device.
+    pub async fn add_checkin_rule(
+        &self,
+        mls_group_id: Vec<u8>,
+        member_pubkey: String,
+        deadline_minute: u16,
+        window_minutes: u16,
+        weekdays: u8,
+    ) -> Result<CheckinRuleFfi, HavenErrorFfi> {
+        let group_id = GroupId::from_slice(&mls_group_id);
+        self.inner
+            .add_checkin_rule(
+                &group_id,
+                &member_pubkey,
+                deadline_minute,
+                window_minutes,
+                weekdays,
+            )
+            .await
+            .map(|r| CheckinRuleFfi::from(&r))
+            .map_err(HavenErrorFfi::from)
+    }
+
+    /// Scheduled check-ins of one circle, or of all circles with `None`.
+    pub async fn get_checkin_rules(
+        &self,
+        mls_group_id: Option<Vec<u8>>,
+    ) -> Result<Vec<CheckinRuleFfi>, HavenErrorFfi> {
+        let inner = self.inner.clone();
+        run_blocking(move || {
+            let group_id = mls_group_id.map(|g| GroupId::from_slice(&g));
+            inner
+                .checkin_rules(group_id.as_ref())
+                .map(|rules| rules.iter().map(CheckinRuleFfi::from).collect())
+                .map_err(HavenErrorFfi::from)
+        })
+        .await
+    }
+
+    /// Removes a scheduled check-in.
+    pub async fn remove_checkin_rule(&self, rule_id: String) -> Result<(), HavenErrorFfi> {
+        let inner = self.inner.clone();
+        run_blocking(move || {
+            inner
+                .remove_checkin_rule(&rule_id)
+                .map_err(HavenErrorFfi::from)
+        })
+        .await
+    }
+
+    /// The scheduled check-ins missed by now, earliest deadline first.
+    /// `utc_offset_secs` is the device's current offset east of UTC, which
+    /// the deadlines are read in.
+    ///
+    /// Reads only this device's database; safe to call from a background
+    /// task.
+    pub async fn evaluate_safety_rules(
+        &self,
+        utc_offset_secs: i32,
+    ) -> Result<Vec<MissedCheckinFfi>, HavenErrorFfi> {
+        let inner = self.inner.clone();
+        run_blocking(move || {
+            inner
+                .evaluate_safety_rules(now_ms() / 1000, utc_offset_secs)
+                .map(|missed| missed.iter().map(MissedCheckinFfi::from).collect())
+                .map_err(HavenErrorFfi::from)
+        })
+        .await
+    }
+
+    /// Dismisses a missed check-in until the rule's next deadline after
+    /// `deadline_at`.
+    pub async fn acknowledge_missed_checkin(
+        &self,
+        rule_id: String,
+        deadline_at: i64,
+    ) -> Result<(), HavenErrorFfi> {
+        let inner = self.inner.clone();
+        run_blocking(move || {
+            inner
+                .acknowledge_missed_checkin(&rule_id, deadline_at)
+                .map_err(HavenErrorFfi::from)
+        })
+        .await
+    }
+
+    /// Sets up a proximity alert: raised when `member_pubkey` (hex) comes
+    /// within `range_m` meters of `target`. Stays on this device.
+    pub async fn add_proximity_rule(
+        &self,
+        mls_group_id: Vec<u8>,
+        member_pubkey: String,
+        target: ProximityTargetFfi,
+        range_m: f64,
+    ) -> Result<ProximityRuleFfi, HavenErrorFfi> {
+        let group_id = GroupId::from_slice(&mls_group_id);
+        let target = proximity_target_from_ffi(target)?;
+        self.inner
+            .add_proximity_rule(&group_id, &member_pubkey, target, range_m)
+            .await
+            .map(|r| ProximityRuleFfi::from(&r))
+            .map_err(HavenErrorFfi::from)
+    }
+
+    /// Proximity alerts of one circle, or of all circles with `None`.
+    pub async fn get_proximity_rules(
+        &self,
+        mls_group_id: Option<Vec<u8>>,
+    ) -> Result<Vec<ProximityRuleFfi>, HavenErrorFfi> {
+        let inner = self.inner.clone();
+        run_blocking(move || {
+            let group_id = mls_group_id.map(|g| GroupId::from_slice(&g));
+            inner
+                .proximity_rules(group_id.as_ref())
+                .map(|rules| rules.iter().map(ProximityRuleFfi::from).collect())
+                .map_err(HavenErrorFfi::from)
+        })
+        .await
+    }
+
+    /// Removes a proximity alert.
+    pub async fn remove_proximity_rule(&self, rule_id: String) -> Result<(), HavenErrorFfi> {
+        let inner = self.inner.clone();
+        run_blocking(move || {
+            inner
+                .remove_proximity_rule(&rule_id)
+                .map_err(HavenErrorFfi::from)
+        })
+        .await
+    }
+
+    /// Current distance and rough ETA from `member_pubkey` (hex) to
+    /// `target`, or `None` while either position is missing or stale.
+    pub async fn member_proximity(
+        &self,
+        mls_group_id: Vec<u8>,
+        member_pubkey: String,
+        target: ProximityTargetFfi,
+    ) -> Result<Option<ProximityEstimateFfi>, HavenErrorFfi> {
+        let target = proximity_target_from_ffi(target)?;
+        let inner = self.inner.clone();
+        run_blocking(move || {
+            let group_id = GroupId::from_slice(&mls_group_id);
+            inner
+                .member_proximity(&group_id, &member_pubkey, &target, now_ms() / 1000)
+                .map(|e| e.as_ref().map(ProximityEstimateFfi::from))
+                .map_err(HavenErrorFfi::from)
+        })
+        .await
+    }
+
+    /// The proximity alerts raised by now: members who came within a rule's
+    /// range since the last call, earliest sighting first.
+    ///
+    /// Reads only this device's database; safe to call from a background
+    /// task.
+    pub async fn evaluate_proximity_rules(&self) -> Result<Vec<ProximityAlertFfi>, HavenErrorFfi> {
+        let inner = self.inner.clone();
+        run_blocking(move || {
+            inner
+                .evaluate_proximity_rules(now_ms() / 1000)
+                .map(|alerts| alerts.iter().map(ProximityAlertFfi::from).collect())
+                .map_err(HavenErrorFfi::from)
+        })
+        .await
+    }
+
+    /// Removes members from a circle.
+    ///
+    /// Returns a [`CommitToPublishFfi`] (publish-before-apply, Rule 13):
+    /// publish `commit_event_json`, then [`confirm_published`](Self::confirm_published)
+    /// on a ≥1-relay ACK or [`publish_failed`](Self::publish_failed) on failure.
+    pub async fn remove_members(
+        &self,
+        mls_group_id: Vec<u8>,
+        member_pubkeys: Vec<String>,
+    ) -> Result<CommitToPublishFfi, HavenErrorFfi> {
+        let group_id = GroupId::from_slice(&mls_group_id);
+        let commit = self
+            .inner
+            .remove_members(&group_id, &member_pubkeys)
+            .await
+            .map_err(HavenErrorFfi::from)?;
+        convert_commit_to_publish(commit)
+    }
+
+    /// Gets members of a circle with resolved contact info.
+    ///
+    /// Async: reads the roster from the Dark Matter session (awaits directly).
+    pub async fn get_members(
+        &self,
+        mls_group_id: Vec<u8>,
+    ) -> Result<Vec<CircleMemberFfi>, HavenErrorFfi> {
+        let group_id = GroupId::from_slice(&mls_group_id);
+        self.inner
+            .get_members(&group_id)
+            .await
+            .map(|members| members.iter().map(CircleMemberFfi::from).collect())
+            .map_err(HavenErrorFfi::from)
+    }
+
+    /// Reconciles the circle's stored roster with MLS and returns who joined
+    /// or left since the last reconcile.
+    ///
+    /// Decrypt and sync already do this for every `GroupUpdate` (and fill in
+    /// its `membership`); call it to refresh a circle on demand. A departed
+    /// member's last-known location is deleted; if it is this device, the
+    /// circle becomes `Removed`.
+    pub async fn apply_group_update(
+        &self,
+        mls_group_id: Vec<u8>,
+    ) -> Result<MembershipDeltaFfi, HavenErrorFfi> {
+        let group_id = GroupId::from_slice(&mls_group_id);
+        self.inner
+            .apply_group_update(&group_id)
+            .await
+            .map(MembershipDeltaFfi::from)
+            .map_err(HavenErrorFfi::from)
+    }
+
+    /// Returns whether `pubkey_hex` is still in the circle's current MLS
+    /// roster — the REV-1 leaver-backstop liveness predicate.
+    ///
+    /// The Dart leave flow polls this with the leaver's OWN pubkey after
+    /// publishing a `SelfRemove` (see [`propose_leave`](Self::propose_leave)):
+    /// while it returns `true` the leaver re-issues a fresh `propose_leave` on
+    /// each epoch advance; once it returns `false` the eviction has landed and
+    /// [`complete_leave`](Self::complete_leave) can wipe local state. Fails
+    /// SAFE to `false` when the group is gone or the caller has been evicted,
+    /// so a removed leaver stops re-issuing. Error strings are hex-redacted by
+    /// the core method (Security Rule 4/8).
+    pub async fn still_a_member(
+        &self,
+        mls_group_id: Vec<u8>,
+        pubkey_hex: String,
+    ) -> Result<bool, HavenErrorFfi> {
+        let group_id = GroupId::from_slice(&mls_group_id);
+        self.inner
+            .still_a_member(&group_id, &pubkey_hex)
+            .await
+            .map_err(HavenErrorFfi::from)
+    }
+
+    // ==================== Contact Management ====================
+
+    /// Sets or updates a contact.
+    ///
+    /// Contact information is stored locally only and never synced to relays.
+    pub async fn set_contact(
+        &self,
+        pubkey: String,
+        display_name: Option<String>,
+        notes: Option<String>,
+    ) -> Result<ContactFfi, HavenErrorFfi> {
+        let inner = self.inner.clone();
+        run_blocking(move || {
+            inner
+                .set_contact(&pubkey, display_name.as_deref(), notes.as_deref())
+                .map(ContactFfi::from)
+                .map_err(HavenErrorFfi::from)
+        })
+        .await
+    }
+
+    /// Gets a contact by pubkey.
+    pub async fn get_contact(&self, pubkey: String) -> Result<Option<ContactFfi>, HavenErrorFfi> {
+        let inner = self.inner.clone();
+        run_blocking(move || {
+            inner
+                .get_contact(&pubkey)
+                .map(|opt| opt.map(ContactFfi::from))
+                .map_err(HavenErrorFfi::from)
+        })
+        .await
+    }
+
+    /// Gets all contacts.
+    pub async fn get_all_contacts(&self) -> Result<Vec<ContactFfi>, HavenErrorFfi> {
+        let inner = self.inner.clone();
+        run_blocking(move || {
+            inner
+                .get_all_contacts()
+                .map(|contacts| contacts.into_iter().map(ContactFfi::from).collect())
+                .map_err(HavenErrorFfi::from)
+        })
+        .await
+    }
+
+    /// Deletes a contact.
+    pub async fn delete_contact(&self, pubkey: String) -> Result<(), HavenErrorFfi> {
+        let inner = self.inner.clone();
+        run_blocking(move || inner.delete_contact(&pubkey).map_err(HavenErrorFfi::from)).await
+    }
+
+    /// Sets a contact's picture from raw image bytes (JPEG, PNG or WebP).
+    ///
+    /// The core downscales the picture, strips its EXIF/GPS metadata and
+    /// stores it encrypted on the device; the app never manages the file.
+    pub async fn set_contact_avatar(
+        &self,
+        pubkey: String,
+        image_bytes: Vec<u8>,
+    ) -> Result<ContactFfi, HavenErrorFfi> {
+        let inner = self.inner.clone();
+        run_blocking(move || {
+            inner
+                .set_contact_avatar(&pubkey, &image_bytes)
+                .map(ContactFfi::from)
+                .map_err(HavenErrorFfi::from)
+        })
+        .await
+    }
+
+    /// Removes a contact's picture.
+    pub async fn clear_contact_avatar(&self, pubkey: String) -> Result<ContactFfi, HavenErrorFfi> {
+        let inner = self.inner.clone();
+        run_blocking(move || {
+            inner
+                .clear_contact_avatar(&pubkey)
+                .map(ContactFfi::from)
+                .map_err(HavenErrorFfi::from)
+        })
+        .await
+    }
+
+    /// A contact's picture as JPEG bytes, or `None` if it has none.
+    pub async fn get_contact_avatar(
+        &self,
+        pubkey: String,
+    ) -> Result<Option<Vec<u8>>, HavenErrorFfi> {
+        let inner = self.inner.clone();
+        run_blocking(move || {
+            inner
+                .get_contact_avatar(&pubkey)
+                .map(|jpeg| jpeg.map(|bytes| bytes.to_vec()))
+                .map_err(HavenErrorFfi::from)
+        })
+        .await
+    }
+
+    // ==================== Contact Labels ====================
+
+    /// Adds `label` (e.g. "family") to the contact `pubkey`. Returns `false`
+    /// if the contact already had it.
+    pub async fn tag_contact(&self, pubkey: String, label: String) -> Result<bool, HavenErrorFfi> {
+        let inner = self.inner.clone();
+        run_blocking(move || {
+            inner
+                .tag_contact(&pubkey, &label)
+                .map_err(HavenErrorFfi::from)
+        })
+        .await
+    }
+
+    /// Removes `label` from the contact `pubkey`. Returns `false` if the
+    /// contact did not have it.
+    pub async fn untag_contact(
+        &self,
+        pubkey: String,
+        label: String,
+    ) -> Result<bool, HavenErrorFfi> {
+        let inner = self.inner.clone();
+        run_blocking(move || {
+            inner
+                .untag_contact(&pubkey, &label)
+                .map_err(HavenErrorFfi::from)
+        })
+        .await
+    }
+
+    /// Labels on the contact `pubkey`, alphabetically.
+    pub async fn get_contact_labels(&self, pubkey: String) -> Result<Vec<String>, HavenErrorFfi> {
+        let inner = self.inner.clone();
+        run_blocking(move || {
+            inner
+                .get_contact_labels(&pubkey)
+                .map_err(HavenErrorFfi::from)
+        })
+        .await
+    }
+
+    /// Contacts carrying `label`.
+    pub async fn get_contacts_by_label(
+        &self,
+        label: String,
+    ) -> Result<Vec<ContactFfi>, HavenErrorFfi> {
+        let inner = self.inner.clone();
+        run_blocking(move || {
+            inner
+                .get_contacts_by_label(&label)
+                .map(|contacts| contacts.into_iter().map(ContactFfi::from).collect())
+                .map_err(HavenErrorFfi::from)
+        })
+        .await
+    }
+
+    /// Every label in use with its contact count, for the label picker.
+    pub async fn list_contact_labels(&self) -> Result<Vec<ContactLabelFfi>, HavenErrorFfi> {
+        let inner = self.inner.clone();
+        run_blocking(move || {
+            inner
+                .list_contact_labels()
+                .map(|labels| {
+                    labels
+                        .into_iter()
+                        .map(|(label, count)| ContactLabelFfi {
+                            label,
+                            contact_count: u32::try_from(count).unwrap_or(u32::MAX),
+                        })
+                        .collect()
+                })
+                .map_err(HavenErrorFfi::from)
+        })
+        .await
+    }
+
+    /// Creates a circle with every contact labeled `label`, fetching each
+    /// one's key package through `relay_manager`.
+    ///
+    /// Contacts without a usable key package are skipped and listed in
+    /// `without_key_package`; publish and confirm the result exactly like
+    /// [`Self::create_circle`].
+    //
+    // One Dart argument per Rust parameter, as for `create_circle`.
+    #[allow(clippy::too_many_arguments)]
+    pub async fn create_circle_from_label(
+        &self,
+        relay_manager: &RelayManagerFfi,
+        secret: &SecretHandle,
+        label: String,
+        name: String,
+        description: Option<String>,
+        circle_type: String,
+        relays: Vec<String>,
+        creator_fallback_relays: Vec<String>,
+    ) -> Result<LabeledCircleCreationFfi, HavenErrorFfi> {
+        let keys = secret.keys()?;
+        let ct = CoreCircleType::parse(&circle_type).ok_or_else(|| {
+            HavenErrorFfi::invalid_input(format!("Invalid circle type: {circle_type}"))
+        })?;
+        let config = CoreCircleConfig::new(&name)
+            .with_type(ct)
+            .with_relays(relays);
+        let config = if let Some(desc) = description {
+            config.with_description(desc)
+        } else {
+            config
+        };
+
+        let labeled = self
+            .inner
+            .create_circle_from_label(
+                &relay_manager.inner,
+                &keys,
+                &label,
+                &config,
+                &creator_fallback_relays,
+            )
+            .await
+            .map_err(HavenErrorFfi::from)?;
+        Ok(LabeledCircleCreationFfi {
+            creation: self.creation_result_to_ffi(labeled.creation).await?,
+            without_key_package: labeled.without_key_package,
+        })
+    }
+
+    /// The safety number shared with `pubkey` (hex or npub), as 5-digit
+    /// groups. Both people see the same number; compare it in person.
+    #[frb(sync)]
+    pub fn get_safety_number(&self, pubkey: String) -> Result<String, HavenErrorFfi> {
+        self.inner
+            .get_safety_number(&pubkey)
+            .map_err(HavenErrorFfi::from)
+    }
+
+    /// Marks `pubkey` as verified after the safety numbers matched in person.
+    pub async fn mark_contact_verified(&self, pubkey: String) -> Result<ContactFfi, HavenErrorFfi> {
+        let inner = self.inner.clone();
+        run_blocking(move || {
+            inner
+                .mark_contact_verified(&pubkey)
+                .map(ContactFfi::from)
+                .map_err(HavenErrorFfi::from)
+        })
+        .await
+    }
+
+    /// Clears `pubkey`'s verification.
+    pub async fn clear_contact_verification(
+        &self,
+        pubkey: String,
+    ) -> Result<ContactFfi, HavenErrorFfi> {
+        let inner = self.inner.clone();
+        run_blocking(move || {
+            inner
+                .clear_contact_verification(&pubkey)
+                .map(ContactFfi::from)
+                .map_err(HavenErrorFfi::from)
+        })
+        .await
+    }
+
+    // ==================== Invitation Handling ====================
+
+    /// Processes a gift-wrapped Welcome event (kind 1059).
+    ///
+    /// This is the high-level API for processing incoming invitations.
+    /// It unwraps the gift-wrapped event, extracts the sender info,
+    /// and processes the invitation. Circle name and relays are
+    /// extracted from the Welcome's embedded group data.
+    ///
+    /// # Arguments
+    ///
+    /// * `secret` - The recipient's identity
+    /// * `gift_wrap_event_json` - The kind 1059 gift-wrapped event JSON
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Some(invitation))` — a new pending invitation the caller must
+    ///   accept or decline.
+    /// * `Ok(None)` — the gift wrap has already been processed on a prior
+    ///   poll cycle and should be silently skipped. This is the expected
+    ///   outcome for NIP-59's 2-day lookback window repeatedly surfacing
+    ///   the same wrapper events.
+    /// * `Err(msg)` — a real failure (malformed event, MDK error, etc.).
+    ///   The message is already sanitized via `redact_hex_sequences`.
+    pub async fn process_gift_wrapped_invitation(
+        &self,
+        secret: &SecretHandle,
+        gift_wrap_event_json: String,
+    ) -> Result<Option<InvitationFfi>, HavenErrorFfi> {
+        let keys = secret.keys()?;
+
+        // Parse the gift-wrapped event
+        let gift_wrap_event: nostr::Event =
+            serde_json::from_str(&gift_wrap_event_json).map_err(|e| {
+                HavenErrorFfi::invalid_input(format!("Invalid gift wrap event JSON: {e}"))
+            })?;
+
+        // Genuinely async — the Dark Matter peeler previews the welcome (peel
+        // WITHOUT ingest, F3 hold-before-ingest) internally. The returned
+        // `InvitationFfi.mlsGroupId` is a STAND-IN (the gift-wrap event id)
+        // until Accept ingests and the engine yields the real MLS group id;
+        // Dart passes that stand-in id back to `accept_invitation` /
+        // `decline_invitation` (they key on the gift-wrap id).
+        match self
+            .inner
+            .process_gift_wrapped_invitation(&keys, &gift_wrap_event)
+            .await
+        {
+            Ok(invitation) => Ok(Some(InvitationFfi::from(invitation))),
+            // Idempotent skip: the poller has already processed this wrapper.
+            // Flatten to `Ok(None)` so the Dart side never surfaces it as
+            // a failure.
+            Err(haven_core::circle::CircleError::AlreadyProcessed) => Ok(None),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    /// Processes a batch of gift-wrapped Welcome events (kind 1059), e.g. the
+    /// inbox backlog after a long time offline.
+    ///
+    /// The wraps are unwrapped in parallel and then held one at a time, so a
+    /// backlog costs far less than calling
+    /// [`Self::process_gift_wrapped_invitation`] per event. Returns one
+    /// outcome per input, in input order; one bad wrap never fails the batch.
+    /// Only unparseable JSON fails the whole call.
+    pub async fn process_gift_wrapped_invitations(
+        &self,
+        secret: &SecretHandle,
+        gift_wrap_events_json: Vec<String>,
+    ) -> Result<Vec<GiftWrapOutcomeFfi>, HavenErrorFfi> {
+        let keys = secret.keys()?;
+        let events = gift_wrap_events_json
+            .iter()
+            .map(|json| {
+                serde_json::from_str::<nostr::Event>(json).map_err(|e| {
+                    HavenErrorFfi::invalid_input(format!("Invalid gift wrap event JSON: {e}"))
+                })
+            })
+            .collect::<Result<Vec<_>, HavenErrorFfi>>()?;
+
+        let outcomes = self
+            .inner
+            .process_gift_wrapped_invitations(&keys, &events)
+            .await;
+        Ok(outcomes
+            .into_iter()
+            .map(|(id, outcome)| GiftWrapOutcomeFfi::new(id.to_hex(), outcome))
+            .collect())
+    }
+
+    /// Gets all pending invitations from the in-memory held-welcome store.
+    ///
+    /// Each [`InvitationFfi`] carries pre-join STAND-IN fields (the gift-wrap
+    /// event id as `mlsGroupId`, `"New Circle"` as the name) because the real
+    /// MLS group state lives inside the still-encrypted 1059 held until Accept
+    /// (F3). `memberCount` reports the provably-known members pre-join — the
+    /// NIP-59-seal-authenticated inviter, i.e. 1 — never the full roster. The
+    /// gift-wrap id is the key the caller passes to
+    /// [`accept_invitation`](Self::accept_invitation) /
+    /// [`decline_invitation`](Self::decline_invitation).
+    pub async fn get_pending_invitations(&self) -> Result<Vec<InvitationFfi>, HavenErrorFfi> {
+        let inner = self.inner.clone();
+        run_blocking(move || {
+            inner
+                .get_pending_invitations()
+                .map(|invitations| invitations.into_iter().map(InvitationFfi::from).collect())
+                .map_err(HavenErrorFfi::from)
+        })
+        .await
+    }
+
+    /// Accepts an invitation to join a circle, keyed by the gift-wrap event id.
+    ///
+    /// Feeds the still-encrypted 1059 held for `gift_wrap_id` (the stand-in
+    /// `mlsGroupId` bytes from [`process_gift_wrapped_invitation`]) to the
+    /// engine, which peels + joins and yields the real circle.
+    pub async fn accept_invitation(
+        &self,
+        gift_wrap_id: Vec<u8>,
+    ) -> Result<CircleWithMembersFfi, HavenErrorFfi> {
+        let event_id = nostr::EventId::from_slice(&gift_wrap_id)
+            .map_err(|e| HavenErrorFfi::invalid_input(format!("Invalid gift-wrap id: {e}")))?;
+        self.inner
+            .accept_invitation(&event_id)
+            .await
+            .map(|c| CircleWithMembersFfi::from(&c))
+            .map_err(HavenErrorFfi::from)
+    }
+
+    /// Adopts an MLS group this device already belongs to through another
+    /// Marmot client as a circle. `circle_type` is `"location_sharing"` or
+    /// `"direct_share"`; a blank `display_name` uses the group's own name.
+    pub async fn adopt_group(
+        &self,
+        mls_group_id: Vec<u8>,
+        display_name: String,
+        circle_type: String,
+    ) -> Result<CircleWithMembersFfi, HavenErrorFfi> {
+        let ct = CoreCircleType::parse(&circle_type).ok_or_else(|| {
+            HavenErrorFfi::invalid_input(format!("Invalid circle type: {circle_type}"))
+        })?;
+        let group_id = GroupId::from_slice(&mls_group_id);
+        self.inner
+            .adopt_group(&group_id, &display_name, ct)
+            .await
+            .map(|c| CircleWithMembersFfi::from(&c))
+            .map_err(HavenErrorFfi::from)
+    }
+
+    /// Seals this device's circles into a transfer bundle for a new device,
+    /// with `identity_ncryptsec` (from `export_encrypted`) as its key.
+    ///
+    /// With `carry_state` the MLS state moves into the bundle and this
+    /// device stops sending or reading circle traffic for good; without it
+    /// the new device rejoins each circle through an admin.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a circle change is still in flight, the backup is
+    /// not an `ncryptsec` of this identity, or sealing fails.
+    pub async fn export_transfer_bundle(
+        &self,
+        secret: &SecretHandle,
+        identity_ncryptsec: String,
+        carry_state: bool,
+    ) -> Result<Vec<u8>, HavenErrorFfi> {
+        let keys = secret.keys()?;
+        let contents = self
+            .inner
+            .export_for_transfer(carry_state)
+            .await
+            .map_err(HavenErrorFfi::from)?;
+        haven_core::device_transfer::seal_transfer_bundle(&keys, &identity_ncryptsec, &contents)
+            .map_err(HavenErrorFfi::from)
+    }
+
+    /// Restores the circles of an opened transfer bundle. Call after
+    /// [`TransferContentsFfi::install_session`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the bundle belongs to another identity.
+    pub async fn adopt_transferred(
+        &self,
+        contents: &TransferContentsFfi,
+    ) -> Result<TransferRestoreFfi, HavenErrorFfi> {
+        let report = self
+            .inner
+            .adopt_transferred(&contents.inner)
+            .await
+            .map_err(HavenErrorFfi::from)?;
+        Ok(TransferRestoreFfi {
+            adopted: report
+                .adopted
+                .iter()
+                .map(CircleWithMembersFfi::from)
+                .collect(),
+            needs_rejoin: report
+                .needs_rejoin
+                .into_iter()
+                .map(LinkedCircleFfi::from)
+                .collect(),
+        })
+    }
 
-/// Extracts the `["r", <url>]` URLs from a probed kind-10002 (NIP-65)
-/// relay-list event, for drift detection on the KeyPackage-discovery list under
-/// Dark Matter (W2; NIP-65 tags relays as `r`, NOT the `relay` form 10050 uses).
-#[inline]
-fn nip65_relay_list_urls(event: &nostr::Event) -> Vec<String> {
-    event
-        .tags
-        .iter()
-        .filter_map(|t| {
-            let s = t.as_slice();
-            (s.len() >= 2 && s[0] == "r").then(|| s[1].clone())
+    /// Declines an invitation, keyed by the gift-wrap event id.
+    ///
+    /// Drops the held 1059 locally (never ingested → nothing on the wire,
+    /// Rule 10) and marks the gift wrap resolved so a re-poll never re-surfaces
+    /// it. (Storage-only; sync in the core.)
+    pub async fn decline_invitation(&self, gift_wrap_id: Vec<u8>) -> Result<(), HavenErrorFfi> {
+        let inner = self.inner.clone();
+        run_blocking(move || {
+            let event_id = nostr::EventId::from_slice(&gift_wrap_id)
+                .map_err(|e| HavenErrorFfi::invalid_input(format!("Invalid gift-wrap id: {e}")))?;
+            inner
+                .decline_invitation(&event_id)
+                .map_err(HavenErrorFfi::from)
         })
-        .collect()
-}
-
-/// The on-relay URL extractor for a relay-list category's wire form: `relay`
-/// tags for Inbox (10050), `r` tags for the NIP-65 KeyPackage list (10002).
-#[inline]
-fn relay_list_urls_for(relay_type: RelayTypeFfi, event: &nostr::Event) -> Vec<String> {
-    match relay_type {
-        RelayTypeFfi::Inbox => relay_list_urls(event),
-        RelayTypeFfi::Nip65 => nip65_relay_list_urls(event),
+        .await
     }
-}
 
-#[inline]
-async fn run_blocking<F, T>(f: F) -> Result<T, String>
-where
-    F: FnOnce() -> Result<T, String> + Send + 'static,
-    T: Send + 'static,
-{
-    tokio::task::spawn_blocking(f).await.map_err(|e| {
-        // Log only the failure category, never the panic payload — it may
-        // contain key material or MLS group IDs via Debug representations.
-        if e.is_panic() {
-            log::error!("CircleManagerFfi blocking task panicked");
-        } else {
-            log::error!("CircleManagerFfi blocking task cancelled");
-        }
-        "Internal task failure".to_string()
-    })?
-}
+    // ==================== Key Packages ====================
 
-/// Derives identity [`Keys`] from 32-byte secret bytes, failing closed on a
-/// wrong length or malformed key. Wraps the input in `Zeroizing` so an
-/// early-return path never leaks the secret (Security Rule 7/9).
-///
-/// [`Keys`]: nostr::Keys
-fn keys_from_secret_bytes(identity_secret_bytes: Vec<u8>) -> Result<nostr::Keys, String> {
-    let identity_secret_bytes = zeroize::Zeroizing::new(identity_secret_bytes);
-    if identity_secret_bytes.len() != 32 {
-        return Err("Invalid secret bytes length".to_string());
+    // NOTE (Dark Matter): the old `create_key_package` / `sign_key_package_event`
+    // / `record_published_key_packages` trio is RETIRED. It built the kind
+    // 30443+443 TWIN from a `KeyPackageBundle`, which no longer exists — the
+    // Dark Matter engine mints a single last-resort KeyPackage (`fresh_key_package`)
+    // and the 443 twin is dropped (W1). The ONE onboarding/login/heal KeyPackage
+    // publish path is now [`RelayManagerFfi::maintain_key_package`], which mints
+    // via the engine, builds+signs the single kind-30443 event, publishes it to
+    // the user's own NIP-65 relays, records the tracking row, and deletes the
+    // minted material on a failed publish (mdk#160) — all under one idempotent,
+    // fail-soft tick that also serves the first-ever publish (a responding relay
+    // that serves nothing + no tracked slot ⇒ mint-fresh Republish). DM-4b
+    // re-points onboarding/login to call `maintainKeyPackage`.
+
+    /// Returns the latest fetch-back verification of the published
+    /// `KeyPackage` (see [`RelayManagerFfi::verify_key_package`]), or `None`
+    /// if none has run since the package was last published.
+    pub async fn get_key_package_verification(
+        &self,
+    ) -> Result<Option<KpVerificationFfi>, HavenErrorFfi> {
+        let inner = self.inner.clone();
+        let verification = run_blocking(move || {
+            inner
+                .latest_key_package_verification()
+                .map_err(HavenErrorFfi::from)
+        })
+        .await?;
+        Ok(verification.map(KpVerificationFfi::from))
     }
-    let secret_key = nostr::SecretKey::from_slice(&identity_secret_bytes)
-        .map_err(|e| format!("Invalid secret key: {e}"))?;
-    Ok(nostr::Keys::new(secret_key))
-}
 
-impl CircleManagerFfi {
-    /// Creates a new circle manager bound to the device identity.
-    ///
-    /// Initializes both the Dark Matter MLS session (`session.sqlite`) and the
-    /// circle metadata database (`circles.db`) at the given data directory.
-    /// Ensures the platform keyring store is initialized first (idempotent).
-    ///
-    /// # Dark Matter identity gating (DM-4)
+    /// Signs a NIP-09 event deletion event.
     ///
-    /// The Dark Matter [`SessionManager`] binds the device's Nostr identity as
-    /// its account identity, its NIP-59 welcome signer, AND its hardened
-    /// account-identity-proof signer (Rule 1), so the identity secret is now a
-    /// HARD construction requirement — the engine cannot open without it.
-    /// `identity_secret_bytes` (32 bytes, from
-    /// `NostrIdentityManager.get_secret_bytes()`) MUST be present; a wrong
-    /// length or missing identity fails closed with a clear error, matching the
-    /// existing identity-gating call sites. The bytes are `Zeroizing`-wrapped
-    /// and dropped before this returns.
+    /// Creates a kind 5 deletion event referencing the given event IDs,
+    /// signed with the provided identity key. Used to delete consumed
+    /// `KeyPackage` events from relays after rotation.
+    #[frb(sync)]
+    pub fn sign_deletion_event(
+        &self,
+        secret: &SecretHandle,
+        event_ids: Vec<String>,
+    ) -> Result<String, HavenErrorFfi> {
+        sign_deletion(&secret.keys()?, &event_ids)
+    }
+
+    // NOTE (Dark Matter): `self_update` and `groups_needing_self_update` are
+    // RETIRED. The Dark Matter engine internalizes leaf-key rotation and
+    // convergence (there is no app-driven periodic self-update; Haven already
+    // shipped `enablePeriodicSelfUpdate = false`). The publish-before-apply
+    // pending-commit lifecycle that `finalize_pending_commit` /
+    // `clear_pending_commit` used to drive is now
+    // [`confirm_published`](Self::confirm_published) /
+    // [`publish_failed`](Self::publish_failed), keyed by the typed
+    // `PendingStateRefFfi` token carried in each result — not by group id.
+    //
+    // # GAP (plan §5.2 #18): on-demand leaf rotation is declined, not wired.
+    // The Dark Matter v0.9.4 public API has no self-update `SendIntent`, and
+    // SECURITY.md keeps periodic self-update off because it forked epochs, so
+    // there is no `rotate_my_keys` / rotation scheduler surface here.
+
+    /// Finalizes an admin relay update: confirms the pending commit, then
+    /// re-syncs the admin's own `circle.relays` from the engine's routing
+    /// component so the admin converges on the new set immediately.
     ///
-    /// [`SessionManager`]: haven_core::nostr::mls::SessionManager
-    pub fn new(data_dir: String, identity_secret_bytes: Vec<u8>) -> Result<Self, String> {
-        let keys = keys_from_secret_bytes(identity_secret_bytes)?;
-        init_keyring_store()?;
-        let circle_db_key = get_or_create_circle_db_key()?;
-        let path = Path::new(&data_dir);
-        CoreCircleManager::new(path, &keys, Some(&circle_db_key))
-            .map(|inner| Self {
-                inner: Arc::new(inner),
-            })
-            .map_err(|e| e.to_string())
+    /// Use this instead of a bare [`confirm_published`](Self::confirm_published)
+    /// for the [`update_circle_relays`](Self::update_circle_relays) flow
+    /// (members converge via the receive path). Pass the `pending` token from
+    /// the [`CommitToPublishFfi`] and the circle's `mls_group_id`.
+    pub async fn finalize_relay_update(
+        &self,
+        pending: PendingStateRefFfi,
+        mls_group_id: Vec<u8>,
+    ) -> Result<(), HavenErrorFfi> {
+        let group_id = GroupId::from_slice(&mls_group_id);
+        self.inner
+            .finalize_relay_update(pending.into(), &group_id)
+            .await
+            .map_err(HavenErrorFfi::from)
     }
 
-    // ==================== Circle Lifecycle ====================
+    // ==================== Location Sharing ====================
 
-    /// Creates a new circle with gift-wrapped Welcome events.
+    /// Encrypts a location for a circle.
     ///
-    /// Returns the created circle and gift-wrapped Welcome events ready
-    /// to publish to the invited members' inbox relays.
+    /// Creates an MLS-encrypted kind 445 event containing the location data.
+    /// The returned event is ready to publish to the circle's relays.
     ///
-    /// # Arguments
+    /// # Concurrency
     ///
-    /// * `identity_secret_bytes` - The creator's identity secret bytes (32 bytes,
-    ///   from `NostrIdentityManager.get_secret_bytes()`)
-    /// * `members` - Key packages and inbox relays for each member
-    /// * `name` - Circle name
-    /// * `description` - Optional circle description
-    /// * `circle_type` - Circle type: "location_sharing" or "direct_share"
-    /// * `relays` - Relay URLs for the circle's messages
-    /// * `creator_fallback_relays` - The creator's own inbox relays (kind
-    ///   10050), used as the third tier in the Welcome delivery cascade
-    ///   (member 10050 → member 10002 → creator inbox → FAIL CLOSED). Pass an
-    ///   empty list if the creator has no inbox relays; delivery then fails
-    ///   closed (no public-default fallback) when tiers 1–2 are also empty.
+    /// MDK's `create_message` performs a non-atomic read-modify-write on the
+    /// MLS group state. Two concurrent calls for the **same** group can race
+    /// on the epoch counter, causing one message to be rejected by all
+    /// recipients. Callers **must not** invoke this method concurrently for
+    /// the same `mls_group_id`. The Dart-side `locationPublisherProvider`
+    /// satisfies this constraint by publishing one group at a time per
+    /// publish cycle. If this ever changes, add a per-group `Mutex` here.
     ///
-    /// # Security
+    /// # Arguments
     ///
-    /// The Welcome events are gift-wrapped per NIP-59, hiding the sender's
-    /// identity behind an ephemeral key. Each Welcome uses a fresh ephemeral
-    /// keypair and randomized timestamp.
-    //
-    // The FFI signature is dictated by the Flutter-Rust contract (one Dart
-    // argument per Rust parameter); grouping these into a struct would
-    // change the generated bindings. The arity is reviewed at the
-    // architectural level, not by clippy.
+    /// * `mls_group_id` - The circle's MLS group ID
+    /// * `sender_pubkey_hex` - The sender's Nostr public key (hex)
+    /// * `latitude` - GPS latitude (exact)
+    /// * `longitude` - GPS longitude (exact)
+    /// * `update_interval_secs` - Publish-cadence hint used to compute the
+    ///   jittered NIP-40 `expiration` tag on the outer kind:445 wrapper.
+    ///   Must be in `[60, 3600]`. The Dart call site normally passes
+    ///   `kLocationPublishMaxInterval.inSeconds + 30` so the minimum
+    ///   sampled TTL comfortably exceeds the maximum jittered publish delay
+    ///   plus a network-propagation buffer — see `location.dart`. The
+    ///   absolute expiration timestamp is sampled uniformly from
+    ///   `[interval, 2 * interval]` seconds in the future.
+    /// * `share_expiration_secs` - How long members keep this location
+    ///   (e.g. 3600 / 28800 / 86400, or any value clamped to
+    ///   `[3600, 86400]`). `None` keeps the 1-day default. Members can only
+    ///   be asked to drop it sooner, never to keep it longer.
+    /// * `device_status` - Battery / charging / motion to send along. Pass it
+    ///   only while [`LocationSettings::share_device_status`] is on; `None`
+    ///   sends no status.
+    /// * `vertical_position` - Altitude and floor level to send along. Pass
+    ///   it only while [`LocationSettings::share_vertical_position`] is on;
+    ///   `None` sends neither. Dropped when the circle shares coarsely.
+    ///
+    /// The circle's own settings (see [`Self::set_circle_location_settings`])
+    /// are applied on top: the location may be rounded and its expiration
+    /// shortened further, never the reverse.
+    ///
+    /// Fails without producing an event while sharing to the circle is
+    /// paused (see [`Self::set_location_paused`]).
     #[allow(clippy::too_many_arguments)]
-    pub async fn create_circle(
+    pub async fn encrypt_location(
         &self,
-        identity_secret_bytes: Vec<u8>,
-        members: Vec<MemberKeyPackageFfi>,
-        name: String,
-        description: Option<String>,
-        circle_type: String,
-        relays: Vec<String>,
-        creator_fallback_relays: Vec<String>,
-    ) -> Result<CircleCreationResultFfi, String> {
-        let keys = keys_from_secret_bytes(identity_secret_bytes)?;
-
-        // Parse member key packages
-        let member_key_packages: Vec<haven_core::circle::MemberKeyPackage> = members
-            .into_iter()
-            .map(|m| {
-                let key_package_event: nostr::Event = serde_json::from_str(&m.key_package_json)
-                    .map_err(|e| format!("Invalid key package JSON: {e}"))?;
-                Ok(haven_core::circle::MemberKeyPackage {
-                    key_package_event,
-                    inbox_relays: m.inbox_relays,
-                    nip65_relays: m.nip65_relays,
-                })
-            })
-            .collect::<Result<Vec<_>, String>>()?;
-
-        // Parse circle type
-        let ct = CoreCircleType::parse(&circle_type)
-            .ok_or_else(|| format!("Invalid circle type: {circle_type}"))?;
-
-        let config = CoreCircleConfig::new(&name)
-            .with_type(ct)
-            .with_relays(relays);
-
-        let config = if let Some(desc) = description {
-            config.with_description(desc)
-        } else {
-            config
-        };
+        mls_group_id: Vec<u8>,
+        sender_pubkey_hex: String,
+        latitude: f64,
+        longitude: f64,
+        update_interval_secs: u64,
+        share_expiration_secs: Option<u64>,
+        device_status: Option<DeviceStatusFfi>,
+        vertical_position: Option<VerticalPositionFfi>,
+    ) -> Result<EncryptedLocationFfi, HavenErrorFfi> {
+        // Validate at the FFI boundary so a buggy Dart caller cannot produce
+        // already-expired (0) or multi-day TTLs. The range mirrors
+        // `haven_core::location::ttl::{MIN,MAX}_UPDATE_INTERVAL_SECS`
+        // (60..=3600) so callers get an explicit error instead of a silent
+        // clamp-up inside the core.
+        if !(60..=3600).contains(&update_interval_secs) {
+            return Err(HavenErrorFfi::invalid_input(format!(
+                "update_interval_secs out of range [60, 3600]: {update_interval_secs}"
+            )));
+        }
+        let sender_pubkey = nostr::PublicKey::parse(&sender_pubkey_hex)
+            .map_err(|e| HavenErrorFfi::invalid_key(format!("Invalid sender pubkey: {e}")))?;
+        let location = outgoing_location(
+            latitude,
+            longitude,
+            share_expiration_secs,
+            device_status,
+            vertical_position,
+        );
 
-        // `CircleManager::create_circle` is genuinely async (giftwrap
-        // construction awaits), so it stays on the current tokio worker.
-        // The inner SQLite writes are still protected by the core's own
-        // fine-grained mutex.
-        let result = self
+        // `encrypt_location` sends via the Dark Matter engine (async), so it
+        // awaits directly on the current worker.
+        let group_id = GroupId::from_slice(&mls_group_id);
+        let (event, nostr_group_id, relays) = self
             .inner
-            .create_circle(
-                &keys,
-                member_key_packages,
-                &config,
-                &creator_fallback_relays,
-            )
+            .encrypt_location(&group_id, &sender_pubkey, &location, update_interval_secs)
             .await
-            .map_err(|e| e.to_string())?;
+            .map_err(HavenErrorFfi::from)?;
 
-        // Convert gift-wrapped welcome events to FFI. F3: if serialization fails
-        // after the create was staged, roll the pending back BEFORE returning
-        // (the core `publish_failed` also deletes the just-saved circle rows), so
-        // neither a leaked `PendingStateRef` nor a ghost circle row survives.
-        let pending_ref = result.pending;
-        let welcome_events: Vec<GiftWrappedWelcomeFfi> = match result
-            .welcome_events
-            .into_iter()
-            .map(|w| {
-                let event_json = serde_json::to_string(&w.event)
-                    .map_err(|e| format!("Failed to serialize welcome event: {e}"))?;
-                Ok(GiftWrappedWelcomeFfi {
-                    recipient_pubkey: w.recipient_pubkey,
-                    recipient_relays: w.recipient_relays,
-                    event_json,
-                })
-            })
-            .collect::<Result<Vec<_>, String>>()
-        {
-            Ok(events) => events,
-            Err(e) => {
-                let _ = self.inner.publish_failed(pending_ref).await;
-                return Err(e);
-            }
-        };
+        // Event id prefix for correlating publish → fetch → decrypt across
+        // the two devices. Public on relays, so no privacy cost.
+        let evt_prefix: String = event.id.to_hex().chars().take(8).collect();
+        log::debug!(
+            "[FFI encrypt] evt={evt_prefix} → kind:445 ready (relays={})",
+            relays.len()
+        );
 
-        let pending = pending_ref.into();
-        Ok(CircleCreationResultFfi {
-            circle: CircleFfi::from(&result.circle),
-            welcome_events,
-            pending,
-        })
+        EncryptedLocationFfi::new(&event, &nostr_group_id, relays)
     }
 
-    /// Gets a circle by its MLS group ID.
+    /// Encrypts a scheduled location update for a circle if one is due;
+    /// `None` when it is not. Call from the app's location timer instead of
+    /// [`Self::encrypt_location`].
     ///
-    /// Async: resolving the roster reads the Dark Matter session (which is
-    /// `&mut`-serialized behind a `tokio` mutex), so this awaits directly on
-    /// the current worker rather than dispatching to the blocking pool.
-    pub async fn get_circle(
+    /// An update is due once `update_interval_minutes` (the user's
+    /// [`LocationSettings::update_interval_minutes`], less the publish jitter
+    /// spread) has passed since the circle's last one, or sooner when the
+    /// position moved far enough. A misfiring timer therefore cannot flood
+    /// the circle's relays. The other arguments are as for
+    /// [`Self::encrypt_location`], and it fails the same way while sharing is
+    /// paused.
+    #[allow(clippy::too_many_arguments)]
+    pub async fn share_location_if_due(
         &self,
         mls_group_id: Vec<u8>,
-    ) -> Result<Option<CircleWithMembersFfi>, String> {
+        latitude: f64,
+        longitude: f64,
+        update_interval_minutes: u32,
+        share_expiration_secs: Option<u64>,
+        device_status: Option<DeviceStatusFfi>,
+        vertical_position: Option<VerticalPositionFfi>,
+    ) -> Result<Option<EncryptedLocationFfi>, HavenErrorFfi> {
+        let location = outgoing_location(
+            latitude,
+            longitude,
+            share_expiration_secs,
+            device_status,
+            vertical_position,
+        );
         let group_id = GroupId::from_slice(&mls_group_id);
-        self.inner
-            .get_circle(&group_id)
+        let Some((event, nostr_group_id, relays)) = self
+            .inner
+            .share_location_if_due(&group_id, &location, update_interval_minutes)
             .await
-            .map(|opt| opt.map(|c| CircleWithMembersFfi::from(&c)))
-            .map_err(|e| e.to_string())
+            .map_err(HavenErrorFfi::from)?
+        else {
+            return Ok(None);
+        };
+        EncryptedLocationFfi::new(&event, &nostr_group_id, relays).map(Some)
     }
 
-    /// Gets all circles.
-    pub async fn get_circles(&self) -> Result<Vec<CircleWithMembersFfi>, String> {
+    /// Encrypts the cover-traffic events due now, to publish like location
+    /// updates. Empty unless [`PrivacySettingsFfi::cover_traffic_per_hour`]
+    /// is set; the app's timer can call this as often as it likes.
+    pub async fn take_due_chaff(&self) -> Result<Vec<EncryptedLocationFfi>, HavenErrorFfi> {
         self.inner
-            .get_circles()
+            .take_due_chaff()
             .await
-            .map(|circles| circles.iter().map(CircleWithMembersFfi::from).collect())
-            .map_err(|e| e.to_string())
+            .map_err(HavenErrorFfi::from)?
+            .into_iter()
+            .map(|(event, nostr_group_id, relays)| {
+                EncryptedLocationFfi::new(&event, &nostr_group_id, relays)
+            })
+            .collect()
     }
 
-    /// Gets visible circles (excludes declined invitations).
-    pub async fn get_visible_circles(&self) -> Result<Vec<CircleWithMembersFfi>, String> {
-        self.inner
-            .get_visible_circles()
-            .await
-            .map(|circles| circles.iter().map(CircleWithMembersFfi::from).collect())
-            .map_err(|e| e.to_string())
+    /// Builds a NIP-09 deletion pulling back a location this device published.
+    ///
+    /// Publish the returned event to its relays with
+    /// [`RelayManagerFfi::publish_event`]. Deletion is a request: relays may
+    /// ignore it and members who already fetched the location keep it.
+    ///
+    /// Currently always fails for an existing circle: the engine does not
+    /// expose the ephemeral key that signed the location, and only that key
+    /// can delete it.
+    pub async fn retract_location(
+        &self,
+        mls_group_id: Vec<u8>,
+        event_id_hex: String,
+    ) -> Result<LocationRetractionFfi, HavenErrorFfi> {
+        let event_id = nostr::EventId::from_hex(&event_id_hex)
+            .map_err(|e| HavenErrorFfi::invalid_input(format!("Invalid event ID: {e}")))?;
+        let inner = self.inner.clone();
+        let (event, relays) = run_blocking(move || {
+            inner
+                .retract_location(&GroupId::from_slice(&mls_group_id), &event_id)
+                .map_err(HavenErrorFfi::from)
+        })
+        .await?;
+        let event_json = serde_json::to_string(&event)
+            .map_err(|e| HavenErrorFfi::internal(format!("Failed to serialize event: {e}")))?;
+        Ok(LocationRetractionFfi {
+            event_id: event.id.to_hex(),
+            retracted_event_id: event_id.to_hex(),
+            event_json,
+            relays,
+        })
     }
 
-    /// Classifies the leave operation — see [`LeavePlanFfi`] for the
-    /// Flutter-side state machine.
-    pub async fn plan_leave(
+    /// Sets a circle's location sharing overrides, applied to every location
+    /// sent to it by [`Self::encrypt_location`].
+    pub async fn set_circle_location_settings(
         &self,
         mls_group_id: Vec<u8>,
-        self_pubkey_hex: String,
-    ) -> Result<LeavePlanFfi, String> {
-        let group_id = GroupId::from_slice(&mls_group_id);
-        let self_pk = nostr::PublicKey::from_hex(&self_pubkey_hex)
-            .map_err(|_| "Invalid self_pubkey_hex".to_string())?;
-        let plan = self
-            .inner
-            .plan_leave(&group_id, &self_pk)
-            .await
-            .map_err(|e| e.to_string())?;
-        Ok(match plan {
-            haven_core::circle::LeavePlan::NonAdmin => LeavePlanFfi {
-                kind: LeavePlanKindFfi::NonAdmin,
-                successor_hex: None,
-            },
-            haven_core::circle::LeavePlan::AdminHandoff { successor } => LeavePlanFfi {
-                kind: LeavePlanKindFfi::AdminHandoff,
-                successor_hex: Some(successor.to_hex()),
-            },
-            haven_core::circle::LeavePlan::AdminDemote => LeavePlanFfi {
-                kind: LeavePlanKindFfi::AdminDemote,
-                successor_hex: None,
-            },
-            haven_core::circle::LeavePlan::Abandon => LeavePlanFfi {
-                kind: LeavePlanKindFfi::Abandon,
-                successor_hex: None,
-            },
-            haven_core::circle::LeavePlan::OrphanLocalOnly => LeavePlanFfi {
-                kind: LeavePlanKindFfi::OrphanLocalOnly,
-                successor_hex: None,
-            },
+        settings: CircleLocationSettingsFfi,
+    ) -> Result<(), HavenErrorFfi> {
+        let inner = self.inner.clone();
+        run_blocking(move || {
+            inner
+                .set_circle_location_settings(&GroupId::from_slice(&mls_group_id), settings.into())
+                .map_err(HavenErrorFfi::from)
         })
+        .await
     }
 
-    /// Step 1 of admin handoff: propose promoting `successor_hex` to admin.
-    ///
-    /// # GAP (plan §5.2 #18)
-    ///
-    /// The Dark Matter v0.9.4 public API exposes no admin-policy component
-    /// codec, so this currently returns a documented error (the core method
-    /// fails closed). Kept so the Dart leave state machine keeps its shape.
-    pub async fn propose_admin_handoff(
+    /// Computes exactly what [`Self::encrypt_location`] would publish to the
+    /// circle for a raw position, under its current settings, so the preview
+    /// map matches reality. Pass the same `share_expiration_secs` the send
+    /// would use. Nothing is sent.
+    pub async fn preview_share(
         &self,
         mls_group_id: Vec<u8>,
-        successor_hex: String,
-    ) -> Result<CommitToPublishFfi, String> {
-        let group_id = GroupId::from_slice(&mls_group_id);
-        let successor = nostr::PublicKey::from_hex(&successor_hex)
-            .map_err(|_| "Invalid successor_hex".to_string())?;
-        let commit = self
-            .inner
-            .propose_admin_handoff(&group_id, &successor)
-            .await
-            .map_err(|e| e.to_string())?;
-        convert_commit_to_publish(commit)
+        raw_lat: f64,
+        raw_lon: f64,
+        share_expiration_secs: Option<u64>,
+    ) -> Result<SharePreviewFfi, HavenErrorFfi> {
+        let inner = self.inner.clone();
+        run_blocking(move || {
+            inner
+                .preview_share(
+                    &GroupId::from_slice(&mls_group_id),
+                    raw_lat,
+                    raw_lon,
+                    share_expiration_secs.map(haven_core::location::ShareExpiration::from_secs),
+                )
+                .map(Into::into)
+                .map_err(HavenErrorFfi::from)
+        })
+        .await
     }
 
-    /// Admin: replace this circle's group relay list (MIP-01) via an
-    /// `UpdateAppComponents(nostr-routing.v1)` commit.
+    /// Returns a circle's location sharing overrides, or `None` if it follows
+    /// the global settings.
+    pub async fn get_circle_location_settings(
+        &self,
+        mls_group_id: Vec<u8>,
+    ) -> Result<Option<CircleLocationSettingsFfi>, HavenErrorFfi> {
+        let inner = self.inner.clone();
+        run_blocking(move || {
+            inner
+                .get_circle_location_settings(&GroupId::from_slice(&mls_group_id))
+                .map(|s| s.map(Into::into))
+                .map_err(HavenErrorFfi::from)
+        })
+        .await
+    }
+
+    /// Pauses or resumes location sharing to every circle.
     ///
-    /// Returns a [`CommitToPublishFfi`]. Publish `commit_event_json` to the
-    /// **union of the circle's current relays and `new_relays`** (so a member
-    /// only listening on a relay being removed still receives the commit), then
-    /// call [`finalize_relay_update`](Self::finalize_relay_update) on a ≥1-relay
-    /// ACK or [`publish_failed`](Self::publish_failed) on failure. `new_relays`
-    /// MUST be non-empty, `wss://` (or the debug loopback test seam),
-    /// credential-free, and at most 20 entries; admin authorization is enforced
-    /// by the engine against live MLS state.
-    pub async fn update_circle_relays(
+    /// Enforced in the core: while paused, [`Self::encrypt_location`] and
+    /// [`Self::share_location_if_due`] fail with a "sharing paused" error and
+    /// queued location updates are dropped rather than sent, whatever the UI
+    /// shows. Persists across restarts.
+    pub async fn set_location_paused(&self, paused: bool) -> Result<(), HavenErrorFfi> {
+        let inner = self.inner.clone();
+        run_blocking(move || {
+            inner
+                .set_location_paused(paused)
+                .map_err(HavenErrorFfi::from)
+        })
+        .await
+    }
+
+    /// When sharing was paused for every circle (Unix timestamp), or `None`
+    /// if it is not.
+    pub async fn location_paused_at(&self) -> Result<Option<i64>, HavenErrorFfi> {
+        let inner = self.inner.clone();
+        run_blocking(move || inner.location_paused_at().map_err(HavenErrorFfi::from)).await
+    }
+
+    /// Pauses or resumes location sharing to one circle, as
+    /// [`Self::set_location_paused`] does for all of them.
+    pub async fn set_circle_location_paused(
         &self,
         mls_group_id: Vec<u8>,
-        new_relays: Vec<String>,
-    ) -> Result<CommitToPublishFfi, String> {
-        let group_id = GroupId::from_slice(&mls_group_id);
-        let commit = self
+        paused: bool,
+    ) -> Result<(), HavenErrorFfi> {
+        let inner = self.inner.clone();
+        run_blocking(move || {
+            inner
+                .set_circle_location_paused(&GroupId::from_slice(&mls_group_id), paused)
+                .map_err(HavenErrorFfi::from)
+        })
+        .await
+    }
+
+    /// When sharing was paused for this circle alone (Unix timestamp), or
+    /// `None` if it is not. See [`Self::is_location_paused`] for whether
+    /// anything is sent to it.
+    pub async fn circle_location_paused_at(
+        &self,
+        mls_group_id: Vec<u8>,
+    ) -> Result<Option<i64>, HavenErrorFfi> {
+        let inner = self.inner.clone();
+        run_blocking(move || {
+            inner
+                .circle_location_paused_at(&GroupId::from_slice(&mls_group_id))
+                .map_err(HavenErrorFfi::from)
+        })
+        .await
+    }
+
+    /// Whether sharing to a circle is paused, globally or for the circle.
+    pub async fn is_location_paused(&self, mls_group_id: Vec<u8>) -> Result<bool, HavenErrorFfi> {
+        let inner = self.inner.clone();
+        run_blocking(move || {
+            inner
+                .is_location_paused(&GroupId::from_slice(&mls_group_id))
+                .map_err(HavenErrorFfi::from)
+        })
+        .await
+    }
+
+    /// Starts trip mode for a circle for `duration_secs` (at most one day):
+    /// locations use `share_expiration_secs` and the publish schedule uses
+    /// `update_interval_secs` when shorter. Reverts by itself when it ends.
+    pub async fn start_trip_mode(
+        &self,
+        mls_group_id: Vec<u8>,
+        duration_secs: u64,
+        update_interval_secs: u64,
+        share_expiration_secs: u64,
+    ) -> Result<TripModeFfi, HavenErrorFfi> {
+        let inner = self.inner.clone();
+        run_blocking(move || {
+            inner
+                .start_trip_mode(
+                    &GroupId::from_slice(&mls_group_id),
+                    duration_secs,
+                    update_interval_secs,
+                    haven_core::location::ShareExpiration::from_secs(share_expiration_secs),
+                )
+                .map(Into::into)
+                .map_err(HavenErrorFfi::from)
+        })
+        .await
+    }
+
+    /// Ends a circle's trip mode early. Returns whether one was running.
+    pub async fn stop_trip_mode(&self, mls_group_id: Vec<u8>) -> Result<bool, HavenErrorFfi> {
+        let inner = self.inner.clone();
+        run_blocking(move || {
+            inner
+                .stop_trip_mode(&GroupId::from_slice(&mls_group_id))
+                .map_err(HavenErrorFfi::from)
+        })
+        .await
+    }
+
+    /// Returns the circle's running trip mode, if any.
+    pub async fn trip_mode(
+        &self,
+        mls_group_id: Vec<u8>,
+    ) -> Result<Option<TripModeFfi>, HavenErrorFfi> {
+        let inner = self.inner.clone();
+        run_blocking(move || {
+            inner
+                .trip_mode(&GroupId::from_slice(&mls_group_id))
+                .map(|t| t.map(Into::into))
+                .map_err(HavenErrorFfi::from)
+        })
+        .await
+    }
+
+    /// Shares exact location with a circle for `duration_secs` (at most one
+    /// day), ignoring its precision setting until the session ends by itself.
+    pub async fn start_precise_session(
+        &self,
+        mls_group_id: Vec<u8>,
+        duration_secs: u64,
+    ) -> Result<SharingSessionFfi, HavenErrorFfi> {
+        let inner = self.inner.clone();
+        run_blocking(move || {
+            inner
+                .start_precise_session(&GroupId::from_slice(&mls_group_id), duration_secs)
+                .map(|s| SharingSessionFfi::from_core(&s, s.started_at))
+                .map_err(HavenErrorFfi::from)
+        })
+        .await
+    }
+
+    /// Ends a circle's precise-sharing session early. Returns whether one was
+    /// running.
+    pub async fn stop_precise_session(&self, mls_group_id: Vec<u8>) -> Result<bool, HavenErrorFfi> {
+        let inner = self.inner.clone();
+        run_blocking(move || {
+            inner
+                .stop_precise_session(&GroupId::from_slice(&mls_group_id))
+                .map_err(HavenErrorFfi::from)
+        })
+        .await
+    }
+
+    /// Lists running precise-sharing sessions, soonest to end first.
+    pub async fn get_active_sessions(&self) -> Result<Vec<SharingSessionFfi>, HavenErrorFfi> {
+        let inner = self.inner.clone();
+        run_blocking(move || {
+            let now = i64::try_from(nostr::Timestamp::now().as_secs()).unwrap_or(0);
+            inner
+                .get_active_sessions()
+                .map(|sessions| {
+                    sessions
+                        .iter()
+                        .map(|s| SharingSessionFfi::from_core(s, now))
+                        .collect()
+                })
+                .map_err(HavenErrorFfi::from)
+        })
+        .await
+    }
+
+    /// Returns the jittered delay before the next publish to a circle, in
+    /// seconds. Like [`HavenCore::jittered_publish_interval_secs`], but a
+    /// running trip mode's shorter interval replaces `nominal_secs`. The
+    /// publisher should rearm each circle's timer with this value.
+    pub async fn publish_interval_secs(
+        &self,
+        mls_group_id: Vec<u8>,
+        nominal_secs: u64,
+    ) -> Result<u64, HavenErrorFfi> {
+        let inner = self.inner.clone();
+        run_blocking(move || {
+            inner
+                .publish_interval_secs(&GroupId::from_slice(&mls_group_id), nominal_secs)
+                .map_err(HavenErrorFfi::from)
+        })
+        .await
+    }
+
+    /// Broadcasts an emergency (SOS) alert to several circles at once.
+    ///
+    /// Always sends the exact GPS fix with a short expiration and an optional
+    /// note. Publish each returned delivery to its relays exactly like
+    /// [`Self::encrypt_location`]'s result; circles that could not be encrypted
+    /// for are listed in `failed_group_ids` and do not abort the others.
+    pub async fn send_sos(
+        &self,
+        mls_group_ids: Vec<Vec<u8>>,
+        latitude: f64,
+        longitude: f64,
+        message: Option<String>,
+    ) -> Result<SosFanoutFfi, HavenErrorFfi> {
+        let group_ids: Vec<GroupId> = mls_group_ids
+            .iter()
+            .map(|id| GroupId::from_slice(id))
+            .collect();
+        let location = haven_core::location::LocationMessage::new(latitude, longitude);
+        let fanout = self
             .inner
-            .update_circle_relays(&group_id, &new_relays)
+            .send_sos(&group_ids, &location, message.as_deref())
             .await
-            .map_err(|e| e.to_string())?;
-        convert_commit_to_publish(commit)
+            .map_err(HavenErrorFfi::from)?;
+
+        let mut deliveries = Vec::with_capacity(fanout.deliveries.len());
+        for d in fanout.deliveries {
+            deliveries.push(EncryptedLocationFfi::new(
+                &d.event,
+                &d.nostr_group_id,
+                d.relays,
+            )?);
+        }
+        Ok(SosFanoutFfi {
+            deliveries,
+            failed_group_ids: fanout
+                .failed
+                .iter()
+                .map(|g| g.as_slice().to_vec())
+                .collect(),
+        })
     }
 
-    /// Step 2 of admin handoff (or step 1 of `Abandon`): demote self from admin.
-    ///
-    /// # GAP (plan §5.2 #18)
+    /// Asks `target_pubkey_hex` to share their current location now.
     ///
-    /// Same admin-policy-codec gap as [`propose_admin_handoff`](Self::propose_admin_handoff);
-    /// the core method currently returns a documented error.
-    pub async fn propose_self_demote(
+    /// Publish the returned event to its relays exactly like
+    /// [`Self::encrypt_location`]'s result. Receivers see a
+    /// [`LocationMessageResultKindFfi::CheckinRequest`].
+    pub async fn request_checkin(
         &self,
         mls_group_id: Vec<u8>,
-    ) -> Result<CommitToPublishFfi, String> {
+        target_pubkey_hex: String,
+    ) -> Result<EncryptedLocationFfi, HavenErrorFfi> {
+        let target = nostr::PublicKey::parse(&target_pubkey_hex)
+            .map_err(|e| HavenErrorFfi::invalid_key(format!("Invalid target pubkey: {e}")))?;
         let group_id = GroupId::from_slice(&mls_group_id);
-        let commit = self
+        let (event, nostr_group_id, relays) = self
             .inner
-            .propose_self_demote(&group_id)
+            .request_checkin(&group_id, &target)
             .await
-            .map_err(|e| e.to_string())?;
-        convert_commit_to_publish(commit)
+            .map_err(HavenErrorFfi::from)?;
+        EncryptedLocationFfi::new(&event, &nostr_group_id, relays)
     }
 
-    /// Returns a `SelfRemove` proposal event JSON. Publish it, then call
-    /// [`complete_leave`](Self::complete_leave).
+    /// Answers a check-in request with the current location.
     ///
-    /// A bare `SelfRemove` proposal has NO `PendingStateRef` — a remaining
-    /// member commits it later (RFC 9420 §12.1.2), so there is nothing to
-    /// confirm or roll back. The returned string is the signed kind:445 event
-    /// JSON to publish to the circle's relays.
-    pub async fn propose_leave(&self, mls_group_id: Vec<u8>) -> Result<String, String> {
+    /// The result is an ordinary location update; publish it like
+    /// [`Self::encrypt_location`]'s result.
+    pub async fn respond_to_checkin(
+        &self,
+        mls_group_id: Vec<u8>,
+        latitude: f64,
+        longitude: f64,
+    ) -> Result<EncryptedLocationFfi, HavenErrorFfi> {
+        let location = haven_core::location::LocationMessage::new(latitude, longitude);
         let group_id = GroupId::from_slice(&mls_group_id);
-        let event = self
+        let (event, nostr_group_id, relays) = self
             .inner
-            .propose_leave(&group_id)
+            .respond_to_checkin(&group_id, &location)
             .await
-            .map_err(|e| e.to_string())?;
-        commit_event_to_json(&event)
+            .map_err(HavenErrorFfi::from)?;
+        EncryptedLocationFfi::new(&event, &nostr_group_id, relays)
     }
 
-    /// Removes the local circle row after a successful leave sequence, or
-    /// for the `OrphanLocalOnly` plan. (Storage-only; sync in the core.)
-    pub async fn complete_leave(&self, mls_group_id: Vec<u8>) -> Result<(), String> {
-        let inner = self.inner.clone();
-        run_blocking(move || {
-            let group_id = GroupId::from_slice(&mls_group_id);
-            inner.complete_leave(&group_id).map_err(|e| e.to_string())
-        })
-        .await
+    /// Drops a time-limited meet pin at an exact point for the circle.
+    ///
+    /// `exact_location_consent` must be `true`: only pass it after the user
+    /// confirmed sharing the exact spot. `ttl_secs` is clamped to 5 minutes
+    /// ..= 24 hours (2 hours is the suggested default). Publish the returned
+    /// event like [`Self::encrypt_location`]'s result. Receivers see a
+    /// [`LocationMessageResultKindFfi::MeetPin`].
+    pub async fn send_meet_pin(
+        &self,
+        mls_group_id: Vec<u8>,
+        latitude: f64,
+        longitude: f64,
+        label: Option<String>,
+        ttl_secs: i64,
+        exact_location_consent: bool,
+    ) -> Result<EncryptedLocationFfi, HavenErrorFfi> {
+        let group_id = GroupId::from_slice(&mls_group_id);
+        let (event, nostr_group_id, relays) = self
+            .inner
+            .send_meet_pin(
+                &group_id,
+                latitude,
+                longitude,
+                label.as_deref(),
+                ttl_secs,
+                exact_location_consent,
+            )
+            .await
+            .map_err(HavenErrorFfi::from)?;
+        EncryptedLocationFfi::new(&event, &nostr_group_id, relays)
     }
 
-    /// Wipes local state for the `Abandon` plan — sole-member cleanup with
-    /// no MLS commit and no relay publish. (Storage-only; sync in the core.)
-    pub async fn abandon_circle_local_only(&self, mls_group_id: Vec<u8>) -> Result<(), String> {
+    /// Lists a circle's live meet pins (own and received), oldest first.
+    /// Expired pins are removed, never returned.
+    pub async fn list_meet_pins(
+        &self,
+        mls_group_id: Vec<u8>,
+    ) -> Result<Vec<MeetPinFfi>, HavenErrorFfi> {
         let inner = self.inner.clone();
         run_blocking(move || {
-            let group_id = GroupId::from_slice(&mls_group_id);
             inner
-                .abandon_circle_local_only(&group_id)
-                .map_err(|e| e.to_string())
+                .list_meet_pins(&GroupId::from_slice(&mls_group_id))
+                .map(|pins| pins.into_iter().map(Into::into).collect())
+                .map_err(HavenErrorFfi::from)
         })
         .await
     }
 
-    // ==================== Publish-before-apply (Rule 13) ====================
-
-    /// Confirms a staged commit was published (≥1-relay OK-ack) so the engine
-    /// applies it and advances the epoch.
-    ///
-    /// "Acked" MUST mean a relay returned OK — never merely "sent" — to avoid
-    /// optimistic-merge forks (Rule 13, security F13). Pass the `pending` token
-    /// carried in a [`CircleCreationResultFfi`] / [`AddMembersResultFfi`] /
-    /// [`CommitToPublishFfi`].
-    pub async fn confirm_published(&self, pending: PendingStateRefFfi) -> Result<(), String> {
-        self.inner
-            .confirm_published(pending.into())
-            .await
-            .map_err(|e| e.to_string())
+    /// Deletes every expired meet pin. Returns how many were removed.
+    pub async fn prune_expired_meet_pins(&self) -> Result<u32, HavenErrorFfi> {
+        let inner = self.inner.clone();
+        let removed =
+            run_blocking(move || inner.prune_expired_meet_pins().map_err(HavenErrorFfi::from))
+                .await?;
+        Ok(u32::try_from(removed).unwrap_or(u32::MAX))
     }
 
-    /// Reports that a staged publish FAILED; the engine discards the staged
-    /// commit and returns the group to `Stable` at the prior epoch.
+    /// Decrypts / ingests a received `kind:445` event, returning the folded
+    /// engine results (Dark Matter six-variant taxonomy).
     ///
-    /// The publish-failure counterpart to [`confirm_published`](Self::confirm_published);
-    /// pass the same `pending` token.
-    pub async fn publish_failed(&self, pending: PendingStateRefFfi) -> Result<(), String> {
-        self.inner
-            .publish_failed(pending.into())
-            .await
-            .map_err(|e| e.to_string())
-    }
-
-    // ==================== Member Management ====================
-
-    /// Adds members to an existing circle and gift-wraps their Welcomes.
+    /// A single ingest can yield SEVERAL [`LocationMessageResultFfi`] — the
+    /// engine's `advance_convergence` may release buffered inbound after the
+    /// outer event — so this returns a `Vec`. The engine owns stale / duplicate
+    /// / out-of-order handling internally (a future-epoch event is durably
+    /// buffered and re-surfaced once the gap fills), so there is no
+    /// `Unprocessable` / `PreviouslyFailed` outcome anymore. An event that was
+    /// already ingested (e.g. re-fetched after a restart) returns its cached
+    /// locations, SOS, meet pins and group updates without touching MLS
+    /// state, or the error it failed with.
     ///
-    /// The add-time counterpart to [`create_circle`]: stages an MLS Add commit
-    /// (kind 445, advances existing members on finalize) and gift-wraps the
-    /// resulting per-member Welcome rumors (kind 444) for delivery, resolving
-    /// each recipient's relays through the same fail-closed cascade.
+    /// # Peer `SelfRemove` eviction (auto-commit) — Rule 13
     ///
-    /// The caller owns the publish/finalize cycle: publish
-    /// `evolution_event_json` to the circle's relays, finalize the pending
-    /// commit on success (or clear it on failure), then publish each
-    /// gift-wrapped Welcome only after a successful finalize.
+    /// This variant does NOT surface receive-side auto-commits (a peer
+    /// `SelfRemove` eviction the engine staged): to stay Rule-13-safe the core
+    /// rolls back any that surfaced rather than confirm an unpublished commit.
+    /// The eviction still propagates via the background catch-up sweep (which
+    /// publishes it). Callers that own relay publishing in poll mode SHOULD
+    /// migrate to [`Self::decrypt_location_collecting_commits`], which surfaces the
+    /// auto-commit so the caller can publish-then-confirm it in the foreground.
     ///
-    /// # Arguments
+    /// # Cursor contract (DM-4b)
     ///
-    /// * `identity_secret_bytes` - The admin's 32-byte Nostr secret key.
-    /// * `mls_group_id` - The circle's MLS group ID.
-    /// * `members` - Key packages and inbox/NIP-65 relays for the new members.
-    /// * `creator_fallback_relays` - The admin's own inbox relays (kind 10050),
-    ///   used as the third tier in the Welcome delivery cascade (member 10050 →
-    ///   member 10002 → admin inbox → FAIL CLOSED). Pass an empty list if the
-    ///   admin has no inbox relays; delivery then fails closed (no
-    ///   public-default fallback) when tiers 1–2 are also empty.
+    /// The engine owns retry/buffering, so the caller advances its relay sync
+    /// cursor on the OUTER event's `created_at` (which the Dart caller already
+    /// holds — it passed `event_json` in). A buffered event is re-delivered by
+    /// the engine when the epoch gap fills; the caller never re-fetches it.
     ///
-    /// # Security
+    /// # Concurrency
     ///
-    /// The Welcome events are gift-wrapped per NIP-59, hiding the sender's
-    /// identity behind a fresh ephemeral key. The secret bytes are zeroized.
+    /// The session is `&mut`-serialized behind a `tokio` mutex, so concurrent
+    /// calls are serialized by the engine; this awaits directly.
+    ///
+    /// # Arguments
+    ///
+    /// * `event_json` - JSON-serialized `kind:445` event.
     ///
     /// # Errors
     ///
-    /// Returns an error if the secret bytes are invalid, a key package fails to
-    /// parse, the caller is not an admin, or a member has no reachable Welcome
-    /// relay (fail-closed).
-    pub async fn add_members_to_circle(
+    /// Returns a redacted error string if the event JSON is invalid or the
+    /// engine ingest fails hard.
+    pub async fn decrypt_location(
         &self,
-        identity_secret_bytes: Vec<u8>,
-        mls_group_id: Vec<u8>,
-        members: Vec<MemberKeyPackageFfi>,
-        creator_fallback_relays: Vec<String>,
-    ) -> Result<AddMembersResultFfi, String> {
-        let keys = keys_from_secret_bytes(identity_secret_bytes)?;
-
-        // Parse member key packages.
-        let member_key_packages: Vec<haven_core::circle::MemberKeyPackage> = members
-            .into_iter()
-            .map(|m| {
-                let key_package_event: nostr::Event = serde_json::from_str(&m.key_package_json)
-                    .map_err(|e| format!("Invalid key package JSON: {e}"))?;
-                Ok(haven_core::circle::MemberKeyPackage {
-                    key_package_event,
-                    inbox_relays: m.inbox_relays,
-                    nip65_relays: m.nip65_relays,
-                })
-            })
-            .collect::<Result<Vec<_>, String>>()?;
+        event_json: String,
+    ) -> Result<Vec<LocationMessageResultFfi>, HavenErrorFfi> {
+        let event: nostr::Event = serde_json::from_str(&event_json)
+            .map_err(|e| HavenErrorFfi::invalid_input(format!("Invalid event JSON: {e}")))?;
 
-        let group_id = GroupId::from_slice(&mls_group_id);
+        // Event id prefix for correlating diagnostic logs across publish /
+        // fetch / decrypt. Nostr event ids are public on relays, so no cost.
+        let evt_prefix: String = event.id.to_hex().chars().take(8).collect();
 
-        // `add_members_with_welcomes` is genuinely async (engine send +
-        // giftwrap construction await), so it stays on the current tokio worker.
-        let result = self
+        // Defense-in-depth: the core `decrypt_location` already redacts its
+        // error strings; re-redact at the boundary so the invariant is local.
+        let results = self
             .inner
-            .add_members_with_welcomes(
-                &keys,
-                &group_id,
-                member_key_packages,
-                &creator_fallback_relays,
-            )
+            .decrypt_location(&event)
             .await
-            .map_err(|e| e.to_string())?;
+            .map_err(|e| haven_core::nostr::mls::redact_hex_sequences(&e.to_string()))?;
 
-        let commit_event_json = commit_event_to_json(&result.commit_event)?;
-        let pending = result.pending.into();
+        let out: Vec<LocationMessageResultFfi> =
+            results.into_iter().map(convert_location_result).collect();
 
-        // Convert gift-wrapped welcome events to FFI.
-        let welcome_events: Vec<GiftWrappedWelcomeFfi> = result
-            .welcome_events
-            .into_iter()
-            .map(|w| {
-                let event_json = serde_json::to_string(&w.event)
-                    .map_err(|e| format!("Failed to serialize welcome event: {e}"))?;
-                Ok(GiftWrappedWelcomeFfi {
-                    recipient_pubkey: w.recipient_pubkey,
-                    recipient_relays: w.recipient_relays,
-                    event_json,
-                })
-            })
-            .collect::<Result<Vec<_>, String>>()?;
+        log::debug!("[FFI decrypt] evt={evt_prefix} → {} result(s)", out.len());
 
-        Ok(AddMembersResultFfi {
-            commit_event_json,
-            welcome_events,
-            pending,
-        })
+        Ok(out)
     }
 
-    /// Removes members from a circle.
+    /// Decrypts / ingests a received `kind:445`, returning the folded results AND
+    /// any receive-side auto-commit the engine staged (publish-before-apply).
     ///
-    /// Returns a [`CommitToPublishFfi`] (publish-before-apply, Rule 13):
-    /// publish `commit_event_json`, then [`confirm_published`](Self::confirm_published)
-    /// on a ≥1-relay ACK or [`publish_failed`](Self::publish_failed) on failure.
-    pub async fn remove_members(
+    /// Identical ingest to [`Self::decrypt_location`], but surfaces a peer
+    /// `SelfRemove` eviction the engine auto-committed instead of dropping it.
+    /// Rule 13 / security F13: for EACH
+    /// [`DecryptLocationOutcomeFfi::auto_commits`] entry, publish
+    /// `commit_event_json` to the circle's relays, then
+    /// [`confirm_published`](Self::confirm_published) on a ≥1-relay ACK (or
+    /// [`publish_failed`](Self::publish_failed) on failure) — exactly like the
+    /// [`CommitToPublishFfi`] from remove / relay-update. Confirming before an
+    /// ACK, or dropping an entry, re-forks the group the leaver departed. The
+    /// foreground poll receive path SHOULD call this in place of
+    /// [`Self::decrypt_location`].
+    ///
+    /// The cursor contract of [`Self::decrypt_location`] applies unchanged.
+    ///
+    /// # Errors
+    ///
+    /// Returns a redacted error string if the event JSON is invalid or the
+    /// engine ingest fails hard.
+    pub async fn decrypt_location_collecting_commits(
         &self,
-        mls_group_id: Vec<u8>,
-        member_pubkeys: Vec<String>,
-    ) -> Result<CommitToPublishFfi, String> {
-        let group_id = GroupId::from_slice(&mls_group_id);
-        let commit = self
-            .inner
-            .remove_members(&group_id, &member_pubkeys)
-            .await
-            .map_err(|e| e.to_string())?;
-        convert_commit_to_publish(commit)
+        event_json: String,
+    ) -> Result<DecryptLocationOutcomeFfi, HavenErrorFfi> {
+        let event: nostr::Event = serde_json::from_str(&event_json)
+            .map_err(|e| HavenErrorFfi::invalid_input(format!("Invalid event JSON: {e}")))?;
+        self.ingest_collecting_commits(event).await
     }
 
-    /// Gets members of a circle with resolved contact info.
+    /// [`decrypt_location_collecting_commits`](Self::decrypt_location_collecting_commits)
+    /// for an event from `RelayManagerFfi::fetch_group_messages_compact`.
     ///
-    /// Async: reads the roster from the Dark Matter session (awaits directly).
-    pub async fn get_members(&self, mls_group_id: Vec<u8>) -> Result<Vec<CircleMemberFfi>, String> {
-        let group_id = GroupId::from_slice(&mls_group_id);
-        self.inner
-            .get_members(&group_id)
-            .await
-            .map(|members| members.iter().map(CircleMemberFfi::from).collect())
-            .map_err(|e| e.to_string())
+    /// The id and signature are re-verified before ingest; the publish /
+    /// confirm contract for `auto_commits` is unchanged.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a field is malformed or fails verification, or a
+    /// redacted error string if the engine ingest fails hard.
+    pub async fn decrypt_location_compact(
+        &self,
+        event: CompactEventFfi,
+    ) -> Result<DecryptLocationOutcomeFfi, HavenErrorFfi> {
+        let event = haven_core::relay::CompactEvent::try_from(event)?
+            .into_event()
+            .map_err(HavenErrorFfi::from)?;
+        self.ingest_collecting_commits(event).await
+    }
+
+    /// Decrypts / ingests a batch of fetched `kind:445` events in one call,
+    /// e.g. a cold-start sync of hundreds of them.
+    ///
+    /// The events are processed in `created_at` order under a single session
+    /// lock, so a backlog costs far less than calling
+    /// [`decrypt_location_collecting_commits`](Self::decrypt_location_collecting_commits)
+    /// per event. Returns one item per input, in processing order; one bad
+    /// event never fails the batch. Each item's `auto_commits` carry the same
+    /// publish / confirm contract. Only unparseable JSON fails the whole call.
+    pub async fn decrypt_locations_batch(
+        &self,
+        events_json: Vec<String>,
+    ) -> Result<Vec<DecryptBatchItemFfi>, HavenErrorFfi> {
+        let events = events_json
+            .iter()
+            .map(|json| {
+                serde_json::from_str::<nostr::Event>(json)
+                    .map_err(|e| HavenErrorFfi::invalid_input(format!("Invalid event JSON: {e}")))
+            })
+            .collect::<Result<Vec<_>, HavenErrorFfi>>()?;
+
+        let outcomes = self.inner.decrypt_locations_batch(&events).await;
+        let mut items = Vec::with_capacity(outcomes.len());
+        for (id, outcome) in outcomes {
+            let converted = match outcome {
+                Ok(ingest) => self.convert_ingest(ingest).await,
+                Err(e) => Err(HavenErrorFfi::from(e)),
+            };
+            let (outcome, error) = match converted {
+                Ok(outcome) => (Some(outcome), None),
+                Err(e) => (None, Some(e)),
+            };
+            items.push(DecryptBatchItemFfi {
+                event_id: id.to_hex(),
+                outcome,
+                error,
+            });
+        }
+        log::debug!("[FFI decrypt] batch of {} event(s)", items.len());
+        Ok(items)
     }
 
-    /// Returns whether `pubkey_hex` is still in the circle's current MLS
-    /// roster — the REV-1 leaver-backstop liveness predicate.
-    ///
-    /// The Dart leave flow polls this with the leaver's OWN pubkey after
-    /// publishing a `SelfRemove` (see [`propose_leave`](Self::propose_leave)):
-    /// while it returns `true` the leaver re-issues a fresh `propose_leave` on
-    /// each epoch advance; once it returns `false` the eviction has landed and
-    /// [`complete_leave`](Self::complete_leave) can wipe local state. Fails
-    /// SAFE to `false` when the group is gone or the caller has been evicted,
-    /// so a removed leaver stops re-issuing. Error strings are hex-redacted by
-    /// the core method (Security Rule 4/8).
-    pub async fn still_a_member(
+    async fn ingest_collecting_commits(
         &self,
-        mls_group_id: Vec<u8>,
-        pubkey_hex: String,
-    ) -> Result<bool, String> {
-        let group_id = GroupId::from_slice(&mls_group_id);
-        self.inner
-            .still_a_member(&group_id, &pubkey_hex)
+        event: nostr::Event,
+    ) -> Result<DecryptLocationOutcomeFfi, HavenErrorFfi> {
+        let evt_prefix: String = event.id.to_hex().chars().take(8).collect();
+
+        let ingest = self
+            .inner
+            .decrypt_location_collecting_commits(&event)
             .await
-            .map_err(|e| e.to_string())
+            .map_err(|e| haven_core::nostr::mls::redact_hex_sequences(&e.to_string()))?;
+        let outcome = self.convert_ingest(ingest).await?;
+
+        log::debug!(
+            "[FFI decrypt] evt={evt_prefix} → {} result(s), {} auto-commit(s)",
+            outcome.results.len(),
+            outcome.auto_commits.len()
+        );
+        Ok(outcome)
+    }
+
+    /// Converts a core ingest into its FFI mirror, rolling back every staged
+    /// auto-commit if any of them cannot be converted.
+    async fn convert_ingest(
+        &self,
+        ingest: haven_core::circle::DecryptedIngest,
+    ) -> Result<DecryptLocationOutcomeFfi, HavenErrorFfi> {
+        let results: Vec<LocationMessageResultFfi> = ingest
+            .results
+            .into_iter()
+            .map(convert_location_result)
+            .collect();
+        // F3: a convert failure MUST NOT drop the OTHER surfaced auto-commit
+        // pendings on the floor. Collect every pending up-front so a mid-stream
+        // failure rolls each staged receive-side auto-commit back item-by-item
+        // (`publish_failed`) instead of leaking them — otherwise a group the
+        // leaver departed silently re-forks (Rule 13 / security F13).
+        let all_pendings: Vec<PendingStateRef> =
+            ingest.auto_commits.iter().map(|c| c.pending).collect();
+        let mut auto_commits: Vec<CommitToPublishFfi> =
+            Vec::with_capacity(ingest.auto_commits.len());
+        let mut convert_err: Option<HavenErrorFfi> = None;
+        for commit in ingest.auto_commits {
+            match convert_commit_to_publish(commit) {
+                Ok(ffi) => auto_commits.push(ffi),
+                Err(e) => {
+                    convert_err = Some(e);
+                    break;
+                }
+            }
+        }
+        if let Some(e) = convert_err {
+            for pending in all_pendings {
+                let _ = self.inner.publish_failed(pending).await;
+            }
+            return Err(e);
+        }
+
+        Ok(DecryptLocationOutcomeFfi {
+            results,
+            auto_commits,
+        })
     }
 
-    // ==================== Contact Management ====================
+    // ==================== Sync Cursors ====================
 
-    /// Sets or updates a contact.
+    /// Reads the persisted relay sync cursor (raw ms) for `stream`.
     ///
-    /// Contact information is stored locally only and never synced to relays.
-    pub async fn set_contact(
-        &self,
-        pubkey: String,
-        display_name: Option<String>,
-        notes: Option<String>,
-    ) -> Result<ContactFfi, String> {
+    /// Returns `None` when the stream has never been seeded — callers MUST
+    /// seed a floor before opening a subscription. See
+    /// [`haven_core::relay::cursor`] for stream keys and semantics.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error string if the storage read fails.
+    ///
+    /// Storage errors here can only reference the constant `stream` key, the
+    /// `sync_cursors` table, or an `i64`, never secret material — but they are
+    /// still re-redacted via `redact_hex_sequences` so every error crossing
+    /// this FFI boundary honors the same no-raw-hex invariant uniformly.
+    pub async fn cursor_get(&self, stream: String) -> Result<Option<i64>, HavenErrorFfi> {
         let inner = self.inner.clone();
         run_blocking(move || {
             inner
-                .set_contact(&pubkey, display_name.as_deref(), notes.as_deref())
-                .map(ContactFfi::from)
-                .map_err(|e| e.to_string())
+                .read_sync_cursor(&stream)
+                .map_err(|e| haven_core::nostr::mls::redact_hex_sequences(&e.to_string()))
         })
         .await
     }
 
-    /// Gets a contact by pubkey.
-    pub async fn get_contact(&self, pubkey: String) -> Result<Option<ContactFfi>, String> {
+    /// Seeds `stream`'s cursor to `ms` only if it is currently unseeded.
+    ///
+    /// Idempotent: an already-seeded cursor is never moved.
+    ///
+    /// # Errors
+    ///
+    /// Returns a redacted error string if the storage write fails.
+    pub async fn cursor_seed_if_unset(&self, stream: String, ms: i64) -> Result<(), HavenErrorFfi> {
         let inner = self.inner.clone();
         run_blocking(move || {
             inner
-                .get_contact(&pubkey)
-                .map(|opt| opt.map(ContactFfi::from))
-                .map_err(|e| e.to_string())
+                .seed_sync_cursor_if_unset(&stream, ms)
+                .map_err(|e| haven_core::nostr::mls::redact_hex_sequences(&e.to_string()))
         })
         .await
     }
 
-    /// Gets all contacts.
-    pub async fn get_all_contacts(&self) -> Result<Vec<ContactFfi>, String> {
+    /// Advances `stream`'s cursor to `ms` (monotonic max; never backward).
+    ///
+    /// `ms` is a millisecond timestamp. Prefer the seconds-taking semantic
+    /// wrappers [`Self::cursor_advance_group_to_event`] /
+    /// [`Self::cursor_advance_inbox_to_wrap`], which own the stream key and the
+    /// seconds→milliseconds conversion; reach for this generic form only when
+    /// the caller already holds a millisecond value. There is intentionally no
+    /// unconditional setter: the cursor only moves forward, and only for a
+    /// successfully-processed event.
+    ///
+    /// # Errors
+    ///
+    /// Returns a redacted error string if the storage write fails.
+    pub async fn cursor_advance(&self, stream: String, ms: i64) -> Result<(), HavenErrorFfi> {
         let inner = self.inner.clone();
         run_blocking(move || {
             inner
-                .get_all_contacts()
-                .map(|contacts| contacts.into_iter().map(ContactFfi::from).collect())
-                .map_err(|e| e.to_string())
+                .advance_sync_cursor(&stream, ms)
+                .map_err(|e| haven_core::nostr::mls::redact_hex_sequences(&e.to_string()))
         })
         .await
     }
 
-    /// Deletes a contact.
-    pub async fn delete_contact(&self, pubkey: String) -> Result<(), String> {
+    /// Resets `stream`'s cursor to the unseeded state (wipe-on-logout).
+    ///
+    /// # Errors
+    ///
+    /// Returns a redacted error string if the storage write fails.
+    pub async fn cursor_reset(&self, stream: String) -> Result<(), HavenErrorFfi> {
         let inner = self.inner.clone();
-        run_blocking(move || inner.delete_contact(&pubkey).map_err(|e| e.to_string())).await
+        run_blocking(move || {
+            inner
+                .reset_sync_cursor(&stream)
+                .map_err(|e| haven_core::nostr::mls::redact_hex_sequences(&e.to_string()))
+        })
+        .await
     }
 
-    // ==================== Invitation Handling ====================
-
-    /// Processes a gift-wrapped Welcome event (kind 1059).
-    ///
-    /// This is the high-level API for processing incoming invitations.
-    /// It unwraps the gift-wrapped event, extracts the sender info,
-    /// and processes the invitation. Circle name and relays are
-    /// extracted from the Welcome's embedded group data.
-    ///
-    /// # Arguments
+    /// Advances the `group_445` cursor to a fully-processed `kind:445` event's
+    /// `created_at` (Unix **seconds**).
     ///
-    /// * `identity_secret_bytes` - The recipient's identity secret bytes (32 bytes)
-    /// * `gift_wrap_event_json` - The kind 1059 gift-wrapped event JSON
+    /// Convenience over [`Self::cursor_advance`] that owns BOTH the stream key
+    /// ([`haven_core::relay::STREAM_GROUP_445`]) and the seconds→milliseconds
+    /// conversion in one place, so the Dart caller passes a plain event
+    /// timestamp and cannot drift the key or the unit. Monotonic-max: only ever
+    /// moves the cursor forward.
     ///
-    /// # Returns
+    /// # Errors
     ///
-    /// * `Ok(Some(invitation))` — a new pending invitation the caller must
-    ///   accept or decline.
-    /// * `Ok(None)` — the gift wrap has already been processed on a prior
-    ///   poll cycle and should be silently skipped. This is the expected
-    ///   outcome for NIP-59's 2-day lookback window repeatedly surfacing
-    ///   the same wrapper events.
-    /// * `Err(msg)` — a real failure (malformed event, MDK error, etc.).
-    ///   The message is already sanitized via `redact_hex_sequences`.
-    pub async fn process_gift_wrapped_invitation(
+    /// Returns a redacted error string if the storage write fails.
+    pub async fn cursor_advance_group_to_event(
         &self,
-        identity_secret_bytes: Vec<u8>,
-        gift_wrap_event_json: String,
-    ) -> Result<Option<InvitationFfi>, String> {
-        let keys = keys_from_secret_bytes(identity_secret_bytes)?;
-
-        // Parse the gift-wrapped event
-        let gift_wrap_event: nostr::Event = serde_json::from_str(&gift_wrap_event_json)
-            .map_err(|e| format!("Invalid gift wrap event JSON: {e}"))?;
-
-        // Genuinely async — the Dark Matter peeler previews the welcome (peel
-        // WITHOUT ingest, F3 hold-before-ingest) internally. The returned
-        // `InvitationFfi.mlsGroupId` is a STAND-IN (the gift-wrap event id)
-        // until Accept ingests and the engine yields the real MLS group id;
-        // Dart passes that stand-in id back to `accept_invitation` /
-        // `decline_invitation` (they key on the gift-wrap id).
-        match self
-            .inner
-            .process_gift_wrapped_invitation(&keys, &gift_wrap_event)
-            .await
-        {
-            Ok(invitation) => Ok(Some(InvitationFfi::from(invitation))),
-            // Idempotent skip: the poller has already processed this wrapper.
-            // Flatten to `Ok(None)` so the Dart side never surfaces it as
-            // a failure.
-            Err(haven_core::circle::CircleError::AlreadyProcessed) => Ok(None),
-            Err(e) => Err(e.to_string()),
-        }
+        event_created_at_secs: i64,
+    ) -> Result<(), HavenErrorFfi> {
+        let ms = event_secs_to_cursor_ms(event_created_at_secs);
+        let inner = self.inner.clone();
+        run_blocking(move || {
+            inner
+                .advance_sync_cursor(haven_core::relay::STREAM_GROUP_445, ms)
+                .map_err(|e| haven_core::nostr::mls::redact_hex_sequences(&e.to_string()))
+        })
+        .await
     }
 
-    /// Gets all pending invitations from the in-memory held-welcome store.
+    /// Advances the `inbox_1059` cursor to a processed gift-wrap's `created_at`
+    /// (Unix **seconds**).
     ///
-    /// Each [`InvitationFfi`] carries pre-join STAND-IN fields (the gift-wrap
-    /// event id as `mlsGroupId`, `"New Circle"` as the name) because the real
-    /// MLS group state lives inside the still-encrypted 1059 held until Accept
-    /// (F3). `memberCount` reports the provably-known members pre-join — the
-    /// NIP-59-seal-authenticated inviter, i.e. 1 — never the full roster. The
-    /// gift-wrap id is the key the caller passes to
-    /// [`accept_invitation`](Self::accept_invitation) /
-    /// [`decline_invitation`](Self::decline_invitation).
-    pub async fn get_pending_invitations(&self) -> Result<Vec<InvitationFfi>, String> {
+    /// As [`Self::cursor_advance_group_to_event`], but for the gift-wrap inbox
+    /// stream ([`haven_core::relay::STREAM_INBOX_1059`]). The 7-day inbox
+    /// lookback applied at REQ time (see [`haven_core::relay::cursor`]) absorbs
+    /// NIP-59's wrapper backdating, so advancing on the outer wrapper timestamp
+    /// is safe.
+    ///
+    /// # Errors
+    ///
+    /// Returns a redacted error string if the storage write fails.
+    pub async fn cursor_advance_inbox_to_wrap(
+        &self,
+        wrap_created_at_secs: i64,
+    ) -> Result<(), HavenErrorFfi> {
+        let ms = event_secs_to_cursor_ms(wrap_created_at_secs);
         let inner = self.inner.clone();
         run_blocking(move || {
             inner
-                .get_pending_invitations()
-                .map(|invitations| invitations.into_iter().map(InvitationFfi::from).collect())
-                .map_err(|e| e.to_string())
+                .advance_sync_cursor(haven_core::relay::STREAM_INBOX_1059, ms)
+                .map_err(|e| haven_core::nostr::mls::redact_hex_sequences(&e.to_string()))
         })
         .await
     }
 
-    /// Accepts an invitation to join a circle, keyed by the gift-wrap event id.
+    // ==================== Last-Known Location Cache ====================
+
+    /// Persists a last-known location row.
     ///
-    /// Feeds the still-encrypted 1059 held for `gift_wrap_id` (the stand-in
-    /// `mlsGroupId` bytes from [`process_gift_wrapped_invitation`]) to the
-    /// engine, which peels + joins and yields the real circle.
-    pub async fn accept_invitation(
+    /// Input is validated at the FFI boundary; the core manager is the
+    /// authoritative enforcement point for `purge_after` derivation. The
+    /// `purge_after` value supplied by the caller is advisory only — the
+    /// core recomputes it as `timestamp + LOCATION_RETENTION_SECS`.
+    pub async fn upsert_last_known_location(
         &self,
-        gift_wrap_id: Vec<u8>,
-    ) -> Result<CircleWithMembersFfi, String> {
-        let event_id = nostr::EventId::from_slice(&gift_wrap_id)
-            .map_err(|e| format!("Invalid gift-wrap id: {e}"))?;
-        self.inner
-            .accept_invitation(&event_id)
-            .await
-            .map(|c| CircleWithMembersFfi::from(&c))
-            .map_err(|e| e.to_string())
-    }
+        location: LastKnownLocationFfi,
+    ) -> Result<(), HavenErrorFfi> {
+        let ngid = parse_nostr_group_id(&location.nostr_group_id)?;
+        validate_pubkey_hex(&location.sender_pubkey, "sender_pubkey")?;
+        let sender_pubkey = normalize_pubkey_hex(&location.sender_pubkey);
+
+        let core = haven_core::circle::LastKnownLocation {
+            nostr_group_id: ngid,
+            sender_pubkey,
+            latitude: location.latitude,
+            longitude: location.longitude,
+            geohash: location.geohash,
+            display_name: location.display_name,
+            timestamp: location.timestamp,
+            expires_at: location.expires_at,
+            // Core re-derives this from timestamp + LOCATION_RETENTION_SECS;
+            // a caller value can only shorten that window.
+            purge_after: location.purge_after,
+            updated_at: location.updated_at,
+        };
 
-    /// Declines an invitation, keyed by the gift-wrap event id.
-    ///
-    /// Drops the held 1059 locally (never ingested → nothing on the wire,
-    /// Rule 10) and marks the gift wrap resolved so a re-poll never re-surfaces
-    /// it. (Storage-only; sync in the core.)
-    pub async fn decline_invitation(&self, gift_wrap_id: Vec<u8>) -> Result<(), String> {
         let inner = self.inner.clone();
         run_blocking(move || {
-            let event_id = nostr::EventId::from_slice(&gift_wrap_id)
-                .map_err(|e| format!("Invalid gift-wrap id: {e}"))?;
             inner
-                .decline_invitation(&event_id)
-                .map_err(|e| e.to_string())
+                .upsert_last_known_location(&core)
+                .map_err(|e| haven_core::nostr::mls::redact_hex_sequences(&e.to_string()))
         })
         .await
     }
 
-    // ==================== Key Packages ====================
-
-    // NOTE (Dark Matter): the old `create_key_package` / `sign_key_package_event`
-    // / `record_published_key_packages` trio is RETIRED. It built the kind
-    // 30443+443 TWIN from a `KeyPackageBundle`, which no longer exists — the
-    // Dark Matter engine mints a single last-resort KeyPackage (`fresh_key_package`)
-    // and the 443 twin is dropped (W1). The ONE onboarding/login/heal KeyPackage
-    // publish path is now [`RelayManagerFfi::maintain_key_package`], which mints
-    // via the engine, builds+signs the single kind-30443 event, publishes it to
-    // the user's own NIP-65 relays, records the tracking row, and deletes the
-    // minted material on a failed publish (mdk#160) — all under one idempotent,
-    // fail-soft tick that also serves the first-ever publish (a responding relay
-    // that serves nothing + no tracked slot ⇒ mint-fresh Republish). DM-4b
-    // re-points onboarding/login to call `maintainKeyPackage`.
-
-    /// Signs a NIP-09 event deletion event.
-    ///
-    /// Creates a kind 5 deletion event referencing the given event IDs,
-    /// signed with the provided identity key. Used to delete consumed
-    /// `KeyPackage` events from relays after rotation.
-    #[frb(sync)]
-    pub fn sign_deletion_event(
+    /// Returns all non-purged last-known locations for a circle.
+    pub async fn snapshot_last_known_for_circle(
         &self,
-        identity_secret_bytes: Vec<u8>,
-        event_ids: Vec<String>,
-    ) -> Result<String, String> {
-        let identity_secret_bytes = zeroize::Zeroizing::new(identity_secret_bytes);
-        if identity_secret_bytes.len() != 32 {
-            return Err("Invalid secret bytes length".to_string());
-        }
-        if event_ids.is_empty() {
-            return Err("No event IDs provided for deletion".to_string());
-        }
-        let secret_key = nostr::SecretKey::from_slice(&identity_secret_bytes)
-            .map_err(|e| format!("Invalid secret key: {e}"))?;
-        let keys = nostr::Keys::new(secret_key);
+        nostr_group_id: Vec<u8>,
+        now_unix_secs: i64,
+    ) -> Result<Vec<LastKnownLocationFfi>, HavenErrorFfi> {
+        let ngid = parse_nostr_group_id(&nostr_group_id)?;
 
-        let ids: Vec<nostr::EventId> = event_ids
-            .iter()
-            .map(|id| {
-                nostr::EventId::from_hex(id).map_err(|e| format!("Invalid event ID '{id}': {e}"))
-            })
-            .collect::<Result<Vec<_>, String>>()?;
+        let inner = self.inner.clone();
+        let rows = run_blocking(move || {
+            inner
+                .snapshot_last_known_for_circle(&ngid, now_unix_secs)
+                .map_err(HavenErrorFfi::from)
+        })
+        .await?;
+
+        Ok(rows.into_iter().map(LastKnownLocationFfi::from).collect())
+    }
 
-        let deletion = nostr::nips::nip09::EventDeletionRequest::new().ids(ids);
-        let event = nostr::EventBuilder::delete(deletion)
-            .sign_with_keys(&keys)
-            .map_err(|e| format!("Failed to sign deletion event: {e}"))?;
+    /// Returns each member's newest location with its
+    /// `effective_display_location`, which is `None` once older than the
+    /// circle's display threshold. UIs draw the display location so stale
+    /// markers blank out everywhere alike.
+    pub async fn get_member_locations(
+        &self,
+        mls_group_id: Vec<u8>,
+        now_unix_secs: i64,
+    ) -> Result<Vec<MemberLocationFfi>, HavenErrorFfi> {
+        let inner = self.inner.clone();
+        let rows = run_blocking(move || {
+            inner
+                .get_member_locations(&GroupId::from_slice(&mls_group_id), now_unix_secs)
+                .map_err(HavenErrorFfi::from)
+        })
+        .await?;
+        Ok(rows.into_iter().map(MemberLocationFfi::from).collect())
+    }
 
-        serde_json::to_string(&event)
-            .map_err(|e| format!("Failed to serialize deletion event: {e}"))
+    /// Returns stored locations inside the map viewport captured between
+    /// `from` and `to` (Unix seconds, inclusive), newest first and at most
+    /// `limit` (capped at 1000). Covers every circle unless `mls_group_id`
+    /// is given. `min_lon > max_lon` means the viewport crosses the
+    /// antimeridian.
+    #[allow(clippy::too_many_arguments)]
+    pub async fn get_points_in_viewport(
+        &self,
+        mls_group_id: Option<Vec<u8>>,
+        min_lat: f64,
+        min_lon: f64,
+        max_lat: f64,
+        max_lon: f64,
+        from: i64,
+        to: i64,
+        limit: u32,
+        now_unix_secs: i64,
+    ) -> Result<Vec<HistoryPointFfi>, HavenErrorFfi> {
+        let bounds = haven_core::location::GeohashBounds {
+            min_lat,
+            min_lon,
+            max_lat,
+            max_lon,
+        };
+        let inner = self.inner.clone();
+        let rows = run_blocking(move || {
+            let group_id = mls_group_id.map(|id| GroupId::from_slice(&id));
+            inner
+                .get_points_in_viewport(group_id.as_ref(), &bounds, from, to, limit, now_unix_secs)
+                .map_err(HavenErrorFfi::from)
+        })
+        .await?;
+        Ok(rows.into_iter().map(HistoryPointFfi::from).collect())
     }
 
-    // NOTE (Dark Matter): `self_update` and `groups_needing_self_update` are
-    // RETIRED. The Dark Matter engine internalizes leaf-key rotation and
-    // convergence (there is no app-driven periodic self-update; Haven already
-    // shipped `enablePeriodicSelfUpdate = false`). The publish-before-apply
-    // pending-commit lifecycle that `finalize_pending_commit` /
-    // `clear_pending_commit` used to drive is now
-    // [`confirm_published`](Self::confirm_published) /
-    // [`publish_failed`](Self::publish_failed), keyed by the typed
-    // `PendingStateRefFfi` token carried in each result — not by group id.
+    /// Summarizes `member_pubkey`'s (hex) trips on `date` (`YYYY-MM-DD`),
+    /// the local day at `utc_offset_secs` east of UTC, from the locations
+    /// they shared in every circle. Computed on this device.
+    pub async fn summarize_day(
+        &self,
+        member_pubkey: String,
+        date: String,
+        utc_offset_secs: i32,
+        now_unix_secs: i64,
+    ) -> Result<DaySummaryFfi, HavenErrorFfi> {
+        let date = haven_core::location::trips::parse_date(&date)
+            .ok_or_else(|| HavenErrorFfi::invalid_input("Date must be YYYY-MM-DD"))?;
+        let inner = self.inner.clone();
+        run_blocking(move || {
+            inner
+                .summarize_day(&member_pubkey, date, utc_offset_secs, now_unix_secs)
+                .map(|summary| DaySummaryFfi::from(&summary))
+                .map_err(HavenErrorFfi::from)
+        })
+        .await
+    }
 
-    /// Finalizes an admin relay update: confirms the pending commit, then
-    /// re-syncs the admin's own `circle.relays` from the engine's routing
-    /// component so the admin converges on the new set immediately.
-    ///
-    /// Use this instead of a bare [`confirm_published`](Self::confirm_published)
-    /// for the [`update_circle_relays`](Self::update_circle_relays) flow
-    /// (members converge via the receive path). Pass the `pending` token from
-    /// the [`CommitToPublishFfi`] and the circle's `mls_group_id`.
-    pub async fn finalize_relay_update(
+    /// The circle's epoch position: current epoch, the oldest one whose
+    /// messages still decrypt, and the member count.
+    pub async fn epoch_info(&self, mls_group_id: Vec<u8>) -> Result<EpochInfoFfi, HavenErrorFfi> {
+        let group_id = GroupId::from_slice(&mls_group_id);
+        self.inner
+            .epoch_info(&group_id)
+            .await
+            .map(EpochInfoFfi::from)
+            .map_err(HavenErrorFfi::from)
+    }
+
+    /// The circle's receive-side health. Show a "circle needs repair" state
+    /// while `needs_repair` is set, offering [`Self::repair_group`].
+    pub async fn group_health(
         &self,
-        pending: PendingStateRefFfi,
         mls_group_id: Vec<u8>,
-    ) -> Result<(), String> {
+    ) -> Result<GroupHealthFfi, HavenErrorFfi> {
         let group_id = GroupId::from_slice(&mls_group_id);
         self.inner
-            .finalize_relay_update(pending.into(), &group_id)
+            .group_health(&group_id)
             .await
-            .map_err(|e| e.to_string())
+            .map(GroupHealthFfi::from)
+            .map_err(HavenErrorFfi::from)
     }
 
-    // ==================== Location Sharing ====================
-
-    /// Encrypts a location for a circle.
-    ///
-    /// Creates an MLS-encrypted kind 445 event containing the location data.
-    /// The returned event is ready to publish to the circle's relays.
-    ///
-    /// # Concurrency
-    ///
-    /// MDK's `create_message` performs a non-atomic read-modify-write on the
-    /// MLS group state. Two concurrent calls for the **same** group can race
-    /// on the epoch counter, causing one message to be rejected by all
-    /// recipients. Callers **must not** invoke this method concurrently for
-    /// the same `mls_group_id`. The Dart-side `locationPublisherProvider`
-    /// satisfies this constraint by publishing one group at a time per
-    /// publish cycle. If this ever changes, add a per-group `Mutex` here.
-    ///
-    /// # Arguments
-    ///
-    /// * `mls_group_id` - The circle's MLS group ID
-    /// * `sender_pubkey_hex` - The sender's Nostr public key (hex)
-    /// * `latitude` - GPS latitude (exact)
-    /// * `longitude` - GPS longitude (exact)
-    /// * `update_interval_secs` - Publish-cadence hint used to compute the
-    ///   jittered NIP-40 `expiration` tag on the outer kind:445 wrapper.
-    ///   Must be in `[60, 3600]`. The Dart call site normally passes
-    ///   `kLocationPublishMaxInterval.inSeconds + 30` so the minimum
-    ///   sampled TTL comfortably exceeds the maximum jittered publish delay
-    ///   plus a network-propagation buffer — see `location.dart`. The
-    ///   absolute expiration timestamp is sampled uniformly from
-    ///   `[interval, 2 * interval]` seconds in the future.
-    pub async fn encrypt_location(
+    /// When each member of the circle other than the local user was last
+    /// heard from, in roster order. Use this instead of inferring liveness
+    /// from location history: group messages that carry no location count
+    /// too.
+    pub async fn get_member_presence(
         &self,
         mls_group_id: Vec<u8>,
-        sender_pubkey_hex: String,
-        latitude: f64,
-        longitude: f64,
-        update_interval_secs: u64,
-    ) -> Result<EncryptedLocationFfi, String> {
-        // Validate at the FFI boundary so a buggy Dart caller cannot produce
-        // already-expired (0) or multi-day TTLs. The range mirrors
-        // `haven_core::location::ttl::{MIN,MAX}_UPDATE_INTERVAL_SECS`
-        // (60..=3600) so callers get an explicit error instead of a silent
-        // clamp-up inside the core.
-        if !(60..=3600).contains(&update_interval_secs) {
-            return Err(format!(
-                "update_interval_secs out of range [60, 3600]: {update_interval_secs}"
-            ));
-        }
-        let sender_pubkey = nostr::PublicKey::parse(&sender_pubkey_hex)
-            .map_err(|e| format!("Invalid sender pubkey: {e}"))?;
-        // Location messages no longer carry a display name: names moved to
-        // public kind-0 profiles at the public-profile migration.
-        let location = haven_core::location::LocationMessage::new(latitude, longitude);
-
-        // `encrypt_location` sends via the Dark Matter engine (async), so it
-        // awaits directly on the current worker.
+        now_unix_secs: i64,
+    ) -> Result<Vec<MemberPresenceFfi>, HavenErrorFfi> {
         let group_id = GroupId::from_slice(&mls_group_id);
-        let (event, nostr_group_id, relays) = self
+        let presence = self
             .inner
-            .encrypt_location(&group_id, &sender_pubkey, &location, update_interval_secs)
+            .get_member_presence(&group_id, now_unix_secs)
             .await
-            .map_err(|e| e.to_string())?;
-
-        let event_json =
-            serde_json::to_string(&event).map_err(|e| format!("Failed to serialize event: {e}"))?;
+            .map_err(HavenErrorFfi::from)?;
+        Ok(presence.into_iter().map(MemberPresenceFfi::from).collect())
+    }
 
-        // Event id prefix for correlating publish → fetch → decrypt across
-        // the two devices. Public on relays, so no privacy cost.
-        let evt_prefix: String = event.id.to_hex().chars().take(8).collect();
-        log::debug!(
-            "[FFI encrypt] evt={evt_prefix} → kind:445 ready (relays={})",
-            relays.len()
-        );
+    /// Everyone who would receive the next location update, across all
+    /// circles, with the user's name for them and the precision each circle
+    /// gets. The answer to "who can see me right now".
+    pub async fn get_current_audience(&self) -> Result<Vec<AudienceMemberFfi>, HavenErrorFfi> {
+        let audience = self
+            .inner
+            .get_current_audience()
+            .await
+            .map_err(HavenErrorFfi::from)?;
+        Ok(audience.into_iter().map(AudienceMemberFfi::from).collect())
+    }
 
-        Ok(EncryptedLocationFfi {
-            event_json,
-            nostr_group_id: nostr_group_id.to_vec(),
-            relays,
+    /// Attempts to bring a circle flagged by [`Self::group_health`] back in
+    /// step. Route `drained.results` like any decrypt, and publish then
+    /// confirm each `drained.auto_commits` entry exactly as for
+    /// [`Self::decrypt_location_collecting_commits`].
+    pub async fn repair_group(
+        &self,
+        mls_group_id: Vec<u8>,
+    ) -> Result<GroupRepairFfi, HavenErrorFfi> {
+        let group_id = GroupId::from_slice(&mls_group_id);
+        let (outcome, ingest) = self
+            .inner
+            .repair_group(&group_id)
+            .await
+            .map_err(HavenErrorFfi::from)?;
+        Ok(GroupRepairFfi {
+            outcome: outcome.into(),
+            drained: self.convert_ingest(ingest).await?,
         })
     }
 
-    /// Decrypts / ingests a received `kind:445` event, returning the folded
-    /// engine results (Dark Matter five-variant taxonomy).
-    ///
-    /// A single ingest can yield SEVERAL [`LocationMessageResultFfi`] — the
-    /// engine's `advance_convergence` may release buffered inbound after the
-    /// outer event — so this returns a `Vec`. The engine owns stale / duplicate
-    /// / out-of-order handling internally (a future-epoch event is durably
-    /// buffered and re-surfaced once the gap fills), so there is no
-    /// `Unprocessable` / `PreviouslyFailed` outcome anymore.
-    ///
-    /// # Peer `SelfRemove` eviction (auto-commit) — Rule 13
-    ///
-    /// This variant does NOT surface receive-side auto-commits (a peer
-    /// `SelfRemove` eviction the engine staged): to stay Rule-13-safe the core
-    /// rolls back any that surfaced rather than confirm an unpublished commit.
-    /// The eviction still propagates via the background catch-up sweep (which
-    /// publishes it). Callers that own relay publishing in poll mode SHOULD
-    /// migrate to [`Self::decrypt_location_collecting_commits`], which surfaces the
-    /// auto-commit so the caller can publish-then-confirm it in the foreground.
-    ///
-    /// # Cursor contract (DM-4b)
-    ///
-    /// The engine owns retry/buffering, so the caller advances its relay sync
-    /// cursor on the OUTER event's `created_at` (which the Dart caller already
-    /// holds — it passed `event_json` in). A buffered event is re-delivered by
-    /// the engine when the epoch gap fills; the caller never re-fetches it.
-    ///
-    /// # Concurrency
-    ///
-    /// The session is `&mut`-serialized behind a `tokio` mutex, so concurrent
-    /// calls are serialized by the engine; this awaits directly.
-    ///
-    /// # Arguments
-    ///
-    /// * `event_json` - JSON-serialized `kind:445` event.
-    ///
-    /// # Errors
+    /// Summarizes where the circle's members are relative to `geofences`.
     ///
-    /// Returns a redacted error string if the event JSON is invalid or the
-    /// engine ingest fails hard.
-    pub async fn decrypt_location(
+    /// Async: reads the roster from the Dark Matter session. Returns counts
+    /// only, so home-screen widgets never receive member coordinates.
+    pub async fn get_circle_status(
         &self,
-        event_json: String,
-    ) -> Result<Vec<LocationMessageResultFfi>, String> {
-        let event: nostr::Event =
-            serde_json::from_str(&event_json).map_err(|e| format!("Invalid event JSON: {e}"))?;
-
-        // Event id prefix for correlating diagnostic logs across publish /
-        // fetch / decrypt. Nostr event ids are public on relays, so no cost.
-        let evt_prefix: String = event.id.to_hex().chars().take(8).collect();
-
-        // Defense-in-depth: the core `decrypt_location` already redacts its
-        // error strings; re-redact at the boundary so the invariant is local.
-        let results = self
-            .inner
-            .decrypt_location(&event)
+        mls_group_id: Vec<u8>,
+        geofences: Vec<GeofenceFfi>,
+        now_unix_secs: i64,
+    ) -> Result<CircleStatusFfi, HavenErrorFfi> {
+        let group_id = GroupId::from_slice(&mls_group_id);
+        let fences: Vec<haven_core::location::Geofence> =
+            geofences.into_iter().map(Into::into).collect();
+        self.inner
+            .get_circle_status(&group_id, &fences, now_unix_secs)
             .await
-            .map_err(|e| haven_core::nostr::mls::redact_hex_sequences(&e.to_string()))?;
-
-        let out: Vec<LocationMessageResultFfi> =
-            results.into_iter().map(convert_location_result).collect();
-
-        log::debug!("[FFI decrypt] evt={evt_prefix} → {} result(s)", out.len());
-
-        Ok(out)
+            .map(CircleStatusFfi::from)
+            .map_err(HavenErrorFfi::from)
     }
 
-    /// Decrypts / ingests a received `kind:445`, returning the folded results AND
-    /// any receive-side auto-commit the engine staged (publish-before-apply).
-    ///
-    /// Identical ingest to [`Self::decrypt_location`], but surfaces a peer
-    /// `SelfRemove` eviction the engine auto-committed instead of dropping it.
-    /// Rule 13 / security F13: for EACH
-    /// [`DecryptLocationOutcomeFfi::auto_commits`] entry, publish
-    /// `commit_event_json` to the circle's relays, then
-    /// [`confirm_published`](Self::confirm_published) on a ≥1-relay ACK (or
-    /// [`publish_failed`](Self::publish_failed) on failure) — exactly like the
-    /// [`CommitToPublishFfi`] from remove / relay-update. Confirming before an
-    /// ACK, or dropping an entry, re-forks the group the leaver departed. The
-    /// foreground poll receive path SHOULD call this in place of
-    /// [`Self::decrypt_location`].
-    ///
-    /// The cursor contract of [`Self::decrypt_location`] applies unchanged.
-    ///
-    /// # Errors
+    /// Builds the minimal snapshot for watch complications and home-screen
+    /// widgets across every visible circle.
     ///
-    /// Returns a redacted error string if the event JSON is invalid or the
-    /// engine ingest fails hard.
-    pub async fn decrypt_location_collecting_commits(
+    /// `own_latitude` / `own_longitude` (both or neither) only bucket
+    /// distances and are not carried in the result.
+    pub async fn get_glanceable_snapshot(
         &self,
-        event_json: String,
-    ) -> Result<DecryptLocationOutcomeFfi, String> {
-        let event: nostr::Event =
-            serde_json::from_str(&event_json).map_err(|e| format!("Invalid event JSON: {e}"))?;
-        let evt_prefix: String = event.id.to_hex().chars().take(8).collect();
-
-        let ingest = self
-            .inner
-            .decrypt_location_collecting_commits(&event)
+        own_latitude: Option<f64>,
+        own_longitude: Option<f64>,
+        now_unix_secs: i64,
+    ) -> Result<GlanceableSnapshotFfi, HavenErrorFfi> {
+        let own_position = own_latitude.zip(own_longitude);
+        self.inner
+            .get_glanceable_snapshot(own_position, now_unix_secs)
             .await
-            .map_err(|e| haven_core::nostr::mls::redact_hex_sequences(&e.to_string()))?;
-
-        let results: Vec<LocationMessageResultFfi> = ingest
-            .results
-            .into_iter()
-            .map(convert_location_result)
-            .collect();
-        // F3: a convert failure MUST NOT drop the OTHER surfaced auto-commit
-        // pendings on the floor. Collect every pending up-front so a mid-stream
-        // failure rolls each staged receive-side auto-commit back item-by-item
-        // (`publish_failed`) instead of leaking them — otherwise a group the
-        // leaver departed silently re-forks (Rule 13 / security F13).
-        let all_pendings: Vec<PendingStateRef> =
-            ingest.auto_commits.iter().map(|c| c.pending).collect();
-        let mut auto_commits: Vec<CommitToPublishFfi> =
-            Vec::with_capacity(ingest.auto_commits.len());
-        let mut convert_err: Option<String> = None;
-        for commit in ingest.auto_commits {
-            match convert_commit_to_publish(commit) {
-                Ok(ffi) => auto_commits.push(ffi),
-                Err(e) => {
-                    convert_err = Some(e);
-                    break;
-                }
-            }
-        }
-        if let Some(e) = convert_err {
-            for pending in all_pendings {
-                let _ = self.inner.publish_failed(pending).await;
-            }
-            return Err(e);
-        }
+            .map(GlanceableSnapshotFfi::from)
+            .map_err(HavenErrorFfi::from)
+    }
 
-        log::debug!(
-            "[FFI decrypt] evt={evt_prefix} → {} result(s), {} auto-commit(s)",
-            results.len(),
-            auto_commits.len()
-        );
+    /// Removes the last-known location for a single sender in a circle.
+    ///
+    /// Called when a member is removed from the circle.
+    pub async fn remove_last_known_member(
+        &self,
+        nostr_group_id: Vec<u8>,
+        sender_pubkey: String,
+    ) -> Result<(), HavenErrorFfi> {
+        let ngid = parse_nostr_group_id(&nostr_group_id)?;
+        validate_pubkey_hex(&sender_pubkey, "sender_pubkey")?;
+        let sender_pubkey = normalize_pubkey_hex(&sender_pubkey);
 
-        Ok(DecryptLocationOutcomeFfi {
-            results,
-            auto_commits,
+        let inner = self.inner.clone();
+        run_blocking(move || {
+            inner
+                .remove_last_known_member(&ngid, &sender_pubkey)
+                .map_err(HavenErrorFfi::from)
         })
+        .await
     }
 
-    // ==================== Sync Cursors ====================
-
-    /// Reads the persisted relay sync cursor (raw ms) for `stream`.
-    ///
-    /// Returns `None` when the stream has never been seeded — callers MUST
-    /// seed a floor before opening a subscription. See
-    /// [`haven_core::relay::cursor`] for stream keys and semantics.
-    ///
-    /// # Errors
-    ///
-    /// Returns an error string if the storage read fails.
+    /// Removes every last-known location row for a circle.
     ///
-    /// Storage errors here can only reference the constant `stream` key, the
-    /// `sync_cursors` table, or an `i64`, never secret material — but they are
-    /// still re-redacted via `redact_hex_sequences` so every error crossing
-    /// this FFI boundary honors the same no-raw-hex invariant uniformly.
-    pub async fn cursor_get(&self, stream: String) -> Result<Option<i64>, String> {
+    /// Called when the user leaves or deletes a circle.
+    pub async fn remove_last_known_circle(
+        &self,
+        nostr_group_id: Vec<u8>,
+    ) -> Result<(), HavenErrorFfi> {
+        let ngid = parse_nostr_group_id(&nostr_group_id)?;
+
         let inner = self.inner.clone();
         run_blocking(move || {
             inner
-                .read_sync_cursor(&stream)
-                .map_err(|e| haven_core::nostr::mls::redact_hex_sequences(&e.to_string()))
+                .remove_last_known_circle(&ngid)
+                .map_err(HavenErrorFfi::from)
         })
         .await
     }
 
-    /// Seeds `stream`'s cursor to `ms` only if it is currently unseeded.
-    ///
-    /// Idempotent: an already-seeded cursor is never moved.
-    ///
-    /// # Errors
+    /// Wipes every last-known location row.
     ///
-    /// Returns a redacted error string if the storage write fails.
-    pub async fn cursor_seed_if_unset(&self, stream: String, ms: i64) -> Result<(), String> {
+    /// Called from the identity-deletion path so no stale location data
+    /// survives a full account wipe.
+    pub async fn wipe_all_last_known_locations(&self) -> Result<(), HavenErrorFfi> {
         let inner = self.inner.clone();
         run_blocking(move || {
             inner
-                .seed_sync_cursor_if_unset(&stream, ms)
-                .map_err(|e| haven_core::nostr::mls::redact_hex_sequences(&e.to_string()))
+                .wipe_all_last_known_locations()
+                .map_err(HavenErrorFfi::from)
         })
         .await
     }
 
-    /// Advances `stream`'s cursor to `ms` (monotonic max; never backward).
-    ///
-    /// `ms` is a millisecond timestamp. Prefer the seconds-taking semantic
-    /// wrappers [`Self::cursor_advance_group_to_event`] /
-    /// [`Self::cursor_advance_inbox_to_wrap`], which own the stream key and the
-    /// seconds→milliseconds conversion; reach for this generic form only when
-    /// the caller already holds a millisecond value. There is intentionally no
-    /// unconditional setter: the cursor only moves forward, and only for a
-    /// successfully-processed event.
-    ///
-    /// # Errors
-    ///
-    /// Returns a redacted error string if the storage write fails.
-    pub async fn cursor_advance(&self, stream: String, ms: i64) -> Result<(), String> {
+    // NOTE (Dark Matter): `wipe_all_staged_commits` is RETIRED. The M7
+    // staged-commit marker table is deleted — the engine owns pending-commit
+    // durability via `EpochState::PendingPublish` / `PendingStateRef` /
+    // `PendingCommitRecovered` on hydrate, so there is no Haven-side marker for
+    // a returning identity to inherit. Logout wipes the whole `session.sqlite`.
+
+    /// Prunes the gift-wrap dedup cache (`processed_gift_wraps`): drops rows
+    /// past the retention window, then enforces the row cap. Returns the number
+    /// of rows removed. Best-effort maintenance, safe to call on every poll
+    /// cycle. `now_unix_secs` is the current Unix **seconds** clock. Errors are
+    /// redacted.
+    pub async fn prune_processed_gift_wraps(
+        &self,
+        now_unix_secs: i64,
+    ) -> Result<u64, HavenErrorFfi> {
         let inner = self.inner.clone();
         run_blocking(move || {
             inner
-                .advance_sync_cursor(&stream, ms)
+                .prune_processed_gift_wraps(now_unix_secs)
                 .map_err(|e| haven_core::nostr::mls::redact_hex_sequences(&e.to_string()))
         })
         .await
     }
 
-    /// Resets `stream`'s cursor to the unseeded state (wipe-on-logout).
-    ///
-    /// # Errors
-    ///
-    /// Returns a redacted error string if the storage write fails.
-    pub async fn cursor_reset(&self, stream: String) -> Result<(), String> {
+    /// Resets ALL sync cursors (bulk) for the wipe-on-logout path, so a
+    /// returning identity re-seeds cleanly instead of resuming at a stale
+    /// floor. Errors are redacted.
+    pub async fn reset_all_sync_cursors(&self) -> Result<(), HavenErrorFfi> {
         let inner = self.inner.clone();
         run_blocking(move || {
             inner
-                .reset_sync_cursor(&stream)
+                .reset_all_sync_cursors()
                 .map_err(|e| haven_core::nostr::mls::redact_hex_sequences(&e.to_string()))
         })
         .await
     }
 
-    /// Advances the `group_445` cursor to a fully-processed `kind:445` event's
-    /// `created_at` (Unix **seconds**).
+    /// Deletes every row whose `purge_after < now_unix_secs`.
     ///
-    /// Convenience over [`Self::cursor_advance`] that owns BOTH the stream key
-    /// ([`haven_core::relay::STREAM_GROUP_445`]) and the seconds→milliseconds
-    /// conversion in one place, so the Dart caller passes a plain event
-    /// timestamp and cannot drift the key or the unit. Monotonic-max: only ever
-    /// moves the cursor forward.
+    /// Returns the number of rows removed.
+    pub async fn prune_expired_last_known(&self, now_unix_secs: i64) -> Result<u32, HavenErrorFfi> {
+        let inner = self.inner.clone();
+        let removed = run_blocking(move || {
+            inner
+                .prune_expired_last_known(now_unix_secs)
+                .map_err(HavenErrorFfi::from)
+        })
+        .await?;
+        // Reasonable: never expect billions of rows.
+        Ok(u32::try_from(removed).unwrap_or(u32::MAX))
+    }
+
+    /// Folds `circles.db`'s write-ahead log back into the database file.
     ///
-    /// # Errors
+    /// Call at quiet moments (app backgrounded, after a large sync). Returns
+    /// `false` if part of the log was still in use; the rest is folded next
+    /// time.
+    pub async fn checkpoint_storage(&self) -> Result<bool, HavenErrorFfi> {
+        let inner = self.inner.clone();
+        run_blocking(move || inner.checkpoint_storage().map_err(HavenErrorFfi::from)).await
+    }
+
+    // ==================== Relay preferences (kind 10050 / 10051) ====================
+
+    /// Seeds the user's relay lists with the default relay list returned by
+    /// [`haven_core::circle::default_relays`] on first launch.
     ///
-    /// Returns a redacted error string if the storage write fails.
-    pub async fn cursor_advance_group_to_event(
-        &self,
-        event_created_at_secs: i64,
-    ) -> Result<(), String> {
-        let ms = event_secs_to_cursor_ms(event_created_at_secs);
+    /// Idempotent: short-circuits via the `relay_prefs_seeded_v1` sentinel
+    /// in `user_settings`. Crucially, the sentinel is the signal — never
+    /// row presence in `user_relays`. A user who removes a default relay
+    /// must not have it re-added by the next defensive seed.
+    ///
+    /// # Returns
+    ///
+    /// `true` if seeding actually wrote rows; `false` if the sentinel was
+    /// already set.
+    pub async fn seed_relay_defaults_if_unseeded(&self) -> Result<bool, HavenErrorFfi> {
         let inner = self.inner.clone();
         run_blocking(move || {
             inner
-                .advance_sync_cursor(haven_core::relay::STREAM_GROUP_445, ms)
-                .map_err(|e| haven_core::nostr::mls::redact_hex_sequences(&e.to_string()))
+                .seed_relay_defaults_if_unseeded()
+                .map_err(HavenErrorFfi::from)
         })
         .await
     }
 
-    /// Advances the `inbox_1059` cursor to a processed gift-wrap's `created_at`
-    /// (Unix **seconds**).
-    ///
-    /// As [`Self::cursor_advance_group_to_event`], but for the gift-wrap inbox
-    /// stream ([`haven_core::relay::STREAM_INBOX_1059`]). The 7-day inbox
-    /// lookback applied at REQ time (see [`haven_core::relay::cursor`]) absorbs
-    /// NIP-59's wrapper backdating, so advancing on the outer wrapper timestamp
-    /// is safe.
-    ///
-    /// # Errors
-    ///
-    /// Returns a redacted error string if the storage write fails.
-    pub async fn cursor_advance_inbox_to_wrap(
-        &self,
-        wrap_created_at_secs: i64,
-    ) -> Result<(), String> {
-        let ms = event_secs_to_cursor_ms(wrap_created_at_secs);
+    /// Returns whether the community relay blacklist is enabled (default
+    /// `false`).
+    pub async fn get_community_blacklist_enabled(&self) -> Result<bool, HavenErrorFfi> {
         let inner = self.inner.clone();
         run_blocking(move || {
             inner
-                .advance_sync_cursor(haven_core::relay::STREAM_INBOX_1059, ms)
-                .map_err(|e| haven_core::nostr::mls::redact_hex_sequences(&e.to_string()))
+                .get_community_blacklist_enabled()
+                .map_err(HavenErrorFfi::from)
         })
         .await
     }
 
-    // ==================== Last-Known Location Cache ====================
+    /// Opts in to (or out of) the community relay blacklist and re-applies
+    /// the merged blacklist immediately.
+    pub async fn set_community_blacklist_enabled(
+        &self,
+        enabled: bool,
+    ) -> Result<(), HavenErrorFfi> {
+        let inner = self.inner.clone();
+        run_blocking(move || {
+            inner
+                .set_community_blacklist_enabled(enabled)
+                .and_then(|()| inner.relay_blacklist())
+                .map(haven_core::relay::install_relay_blacklist)
+                .map_err(HavenErrorFfi::from)
+        })
+        .await
+    }
 
-    /// Persists a last-known location row.
-    ///
-    /// Input is validated at the FFI boundary; the core manager is the
-    /// authoritative enforcement point for `purge_after` derivation. The
-    /// `purge_after` value supplied by the caller is advisory only — the
-    /// core recomputes it as `timestamp + LOCATION_RETENTION_SECS`.
-    pub async fn upsert_last_known_location(
+    /// Blocks, allows, or (with `None`) clears the user's decision for one
+    /// relay, then re-applies the merged blacklist. A local allow overrides
+    /// the community list.
+    pub async fn set_relay_override(
         &self,
-        location: LastKnownLocationFfi,
-    ) -> Result<(), String> {
-        let ngid = parse_nostr_group_id(&location.nostr_group_id)?;
-        validate_pubkey_hex(&location.sender_pubkey, "sender_pubkey")?;
-        let sender_pubkey = normalize_pubkey_hex(&location.sender_pubkey);
+        url: String,
+        decision: Option<RelayOverrideFfi>,
+    ) -> Result<(), HavenErrorFfi> {
+        let inner = self.inner.clone();
+        run_blocking(move || {
+            inner
+                .set_relay_override(&url, decision.map(Into::into))
+                .and_then(|()| inner.relay_blacklist())
+                .map(haven_core::relay::install_relay_blacklist)
+                .map_err(HavenErrorFfi::from)
+        })
+        .await
+    }
 
-        let core = haven_core::circle::LastKnownLocation {
-            nostr_group_id: ngid,
-            sender_pubkey,
-            latitude: location.latitude,
-            longitude: location.longitude,
-            geohash: location.geohash,
-            display_name: location.display_name,
-            timestamp: location.timestamp,
-            expires_at: location.expires_at,
-            // Core re-derives this from timestamp + LOCATION_RETENTION_SECS;
-            // caller value is ignored.
-            purge_after: location.purge_after,
-            updated_at: location.updated_at,
-        };
+    /// Returns whether the member key audit is enabled (default `false`).
+    pub async fn get_key_audit_enabled(&self) -> Result<bool, HavenErrorFfi> {
+        let inner = self.inner.clone();
+        run_blocking(move || inner.get_key_audit_enabled().map_err(HavenErrorFfi::from)).await
+    }
 
+    /// Enables or disables the member key audit: recording the MLS signature
+    /// key of every member this device adds and flagging changes.
+    pub async fn set_key_audit_enabled(&self, enabled: bool) -> Result<(), HavenErrorFfi> {
         let inner = self.inner.clone();
         run_blocking(move || {
             inner
-                .upsert_last_known_location(&core)
-                .map_err(|e| haven_core::nostr::mls::redact_hex_sequences(&e.to_string()))
+                .set_key_audit_enabled(enabled)
+                .map_err(HavenErrorFfi::from)
         })
         .await
     }
 
-    /// Returns all non-purged last-known locations for a circle.
-    pub async fn snapshot_last_known_for_circle(
+    /// Returns the privacy settings for published events.
+    pub async fn get_privacy_settings(&self) -> Result<PrivacySettingsFfi, HavenErrorFfi> {
+        let inner = self.inner.clone();
+        run_blocking(move || {
+            inner
+                .get_privacy_settings()
+                .map(PrivacySettingsFfi::from)
+                .map_err(HavenErrorFfi::from)
+        })
+        .await
+    }
+
+    /// Saves the privacy settings; they apply to events published from now
+    /// on. Fails for a fuzz window above 10 minutes or more than 12 dummy
+    /// events an hour.
+    pub async fn set_privacy_settings(
         &self,
-        nostr_group_id: Vec<u8>,
-        now_unix_secs: i64,
-    ) -> Result<Vec<LastKnownLocationFfi>, String> {
-        let ngid = parse_nostr_group_id(&nostr_group_id)?;
+        settings: PrivacySettingsFfi,
+    ) -> Result<(), HavenErrorFfi> {
+        let inner = self.inner.clone();
+        run_blocking(move || {
+            inner
+                .set_privacy_settings(&settings.into())
+                .map_err(HavenErrorFfi::from)
+        })
+        .await
+    }
 
+    /// Pairs with the NIP-46 remote signer ("bunker") at `bunker_uri` and
+    /// routes key package, relay list and one-shot gift-wrap signing through
+    /// it from now on, replacing any previous pairing.
+    ///
+    /// The signer device may ask the user to approve the pairing; each
+    /// request waits up to a minute. Fails closed, storing nothing, if the
+    /// bunker signs as another identity than this device's.
+    pub async fn configure_remote_signer(
+        &self,
+        bunker_uri: String,
+    ) -> Result<RemoteSignerFfi, HavenErrorFfi> {
+        let bunker_uri = zeroize::Zeroizing::new(bunker_uri);
+        let uri = haven_core::nostr::nip46::BunkerUri::parse(&bunker_uri)?;
+        let now = i64::try_from(nostr::Timestamp::now().as_secs()).unwrap_or(0);
+        // Checked before anything is stored: a bunker holding another key
+        // would sign relay lists and key packages as someone else.
+        let identity = self.inner.identity_pubkey();
+        let (signer, config) =
+            haven_core::nostr::nip46::pair(CoreRelayManager::new(), &uri, &identity, now).await?;
         let inner = self.inner.clone();
-        let rows = run_blocking(move || {
+        let paired = RemoteSignerFfi::from(&config);
+        run_blocking(move || {
             inner
-                .snapshot_last_known_for_circle(&ngid, now_unix_secs)
-                .map_err(|e| e.to_string())
+                .set_remote_signer(&config)
+                .map_err(HavenErrorFfi::from)
         })
         .await?;
+        install_remote_signer(Some(signer));
+        Ok(paired)
+    }
 
-        Ok(rows
-            .into_iter()
-            .map(|loc| LastKnownLocationFfi {
-                nostr_group_id: loc.nostr_group_id.to_vec(),
-                sender_pubkey: loc.sender_pubkey,
-                latitude: loc.latitude,
-                longitude: loc.longitude,
-                geohash: loc.geohash,
-                display_name: loc.display_name,
-                timestamp: loc.timestamp,
-                expires_at: loc.expires_at,
-                purge_after: loc.purge_after,
-                updated_at: loc.updated_at,
-            })
-            .collect())
+    /// The paired remote signer, or `None` if signing uses the local key.
+    pub async fn get_remote_signer(&self) -> Result<Option<RemoteSignerFfi>, HavenErrorFfi> {
+        let inner = self.inner.clone();
+        run_blocking(move || {
+            inner
+                .get_remote_signer()
+                .map(|config| config.as_ref().map(RemoteSignerFfi::from))
+                .map_err(HavenErrorFfi::from)
+        })
+        .await
     }
 
-    /// Removes the last-known location for a single sender in a circle.
-    ///
-    /// Called when a member is removed from the circle.
-    pub async fn remove_last_known_member(
+    /// Forgets the remote signer; signing goes back to the local key.
+    /// Returns `false` if none was paired.
+    pub async fn clear_remote_signer(&self) -> Result<bool, HavenErrorFfi> {
+        let inner = self.inner.clone();
+        let cleared =
+            run_blocking(move || inner.clear_remote_signer().map_err(HavenErrorFfi::from)).await?;
+        install_remote_signer(None);
+        Ok(cleared)
+    }
+
+    /// Lists member key changes, newest first. With
+    /// `include_acknowledged == false` only changes awaiting review.
+    pub async fn list_member_key_changes(
         &self,
-        nostr_group_id: Vec<u8>,
-        sender_pubkey: String,
-    ) -> Result<(), String> {
-        let ngid = parse_nostr_group_id(&nostr_group_id)?;
-        validate_pubkey_hex(&sender_pubkey, "sender_pubkey")?;
-        let sender_pubkey = normalize_pubkey_hex(&sender_pubkey);
+        include_acknowledged: bool,
+    ) -> Result<Vec<MemberKeyChangeFfi>, HavenErrorFfi> {
+        let inner = self.inner.clone();
+        run_blocking(move || {
+            inner
+                .list_member_key_changes(include_acknowledged)
+                .map(|changes| changes.into_iter().map(Into::into).collect())
+                .map_err(HavenErrorFfi::from)
+        })
+        .await
+    }
+
+    /// Marks a member key change as reviewed.
+    pub async fn acknowledge_member_key_change(&self, id: i64) -> Result<(), HavenErrorFfi> {
+        let inner = self.inner.clone();
+        run_blocking(move || {
+            inner
+                .acknowledge_member_key_change(id)
+                .map_err(HavenErrorFfi::from)
+        })
+        .await
+    }
+
+    /// Lists the user's per-relay block/allow decisions.
+    pub async fn list_relay_overrides(&self) -> Result<Vec<RelayOverrideEntryFfi>, HavenErrorFfi> {
+        let inner = self.inner.clone();
+        run_blocking(move || {
+            inner
+                .list_relay_overrides()
+                .map(|rows| {
+                    rows.into_iter()
+                        .map(|(url, decision)| RelayOverrideEntryFfi {
+                            url,
+                            decision: decision.into(),
+                        })
+                        .collect()
+                })
+                .map_err(HavenErrorFfi::from)
+        })
+        .await
+    }
 
+    /// Fetches the newest community relay blacklist signed by the maintainer
+    /// key pinned in haven-core from `relays`, verifies it, stores it if
+    /// newer, and re-applies the merged blacklist.
+    ///
+    /// Returns `true` if a newer list was stored. Fails when the user has not
+    /// opted in, so nothing is fetched without consent.
+    pub async fn refresh_community_blacklist(
+        &self,
+        relays: Vec<String>,
+    ) -> Result<bool, HavenErrorFfi> {
         let inner = self.inner.clone();
+        let enabled = {
+            let inner = inner.clone();
+            run_blocking(move || {
+                inner
+                    .get_community_blacklist_enabled()
+                    .map_err(HavenErrorFfi::from)
+            })
+            .await?
+        };
+        if !enabled {
+            return Err(HavenErrorFfi::invalid_input(
+                "Community relay blacklist is not enabled",
+            ));
+        }
+
+        let relay = haven_core::relay::RelayManager::new();
+        let Some(event) = relay
+            .fetch_community_blacklist(&relays)
+            .await
+            .map_err(HavenErrorFfi::from)?
+        else {
+            return Ok(false);
+        };
+
         run_blocking(move || {
-            inner
-                .remove_last_known_member(&ngid, &sender_pubkey)
-                .map_err(|e| e.to_string())
+            let replaced = inner
+                .ingest_community_blacklist(&event)
+                .map_err(HavenErrorFfi::from)?;
+            haven_core::relay::install_relay_blacklist(
+                inner.relay_blacklist().map_err(HavenErrorFfi::from)?,
+            );
+            Ok(replaced)
         })
         .await
     }
 
-    /// Removes every last-known location row for a circle.
-    ///
-    /// Called when the user leaves or deletes a circle.
-    pub async fn remove_last_known_circle(&self, nostr_group_id: Vec<u8>) -> Result<(), String> {
-        let ngid = parse_nostr_group_id(&nostr_group_id)?;
-
+    /// Returns the privacy facts derived from the live configuration
+    /// (retention windows, precision, relay sets, network routing).
+    pub async fn get_privacy_facts(&self) -> Result<PrivacyFactsFfi, HavenErrorFfi> {
         let inner = self.inner.clone();
         run_blocking(move || {
             inner
-                .remove_last_known_circle(&ngid)
-                .map_err(|e| e.to_string())
+                .privacy_facts()
+                .map(PrivacyFactsFfi::from)
+                .map_err(HavenErrorFfi::from)
         })
         .await
     }
 
-    /// Wipes every last-known location row.
-    ///
-    /// Called from the identity-deletion path so no stale location data
-    /// survives a full account wipe.
-    pub async fn wipe_all_last_known_locations(&self) -> Result<(), String> {
+    /// Records a local error breadcrumb. Both arguments must be short
+    /// lowercase slugs — never pass an error message.
+    pub async fn record_breadcrumb(
+        &self,
+        module: String,
+        error_kind: String,
+    ) -> Result<(), HavenErrorFfi> {
         let inner = self.inner.clone();
         run_blocking(move || {
             inner
-                .wipe_all_last_known_locations()
-                .map_err(|e| e.to_string())
+                .record_breadcrumb(&module, &error_kind)
+                .map_err(HavenErrorFfi::from)
         })
         .await
     }
 
-    // NOTE (Dark Matter): `wipe_all_staged_commits` is RETIRED. The M7
-    // staged-commit marker table is deleted — the engine owns pending-commit
-    // durability via `EpochState::PendingPublish` / `PendingStateRef` /
-    // `PendingCommitRecovered` on hydrate, so there is no Haven-side marker for
-    // a returning identity to inherit. Logout wipes the whole `session.sqlite`.
-
-    /// Prunes the gift-wrap dedup cache (`processed_gift_wraps`): drops rows
-    /// past the retention window, then enforces the row cap. Returns the number
-    /// of rows removed. Best-effort maintenance, safe to call on every poll
-    /// cycle. `now_unix_secs` is the current Unix **seconds** clock. Errors are
-    /// redacted.
-    pub async fn prune_processed_gift_wraps(&self, now_unix_secs: i64) -> Result<u64, String> {
+    /// Returns stored breadcrumbs, oldest first.
+    pub async fn list_breadcrumbs(&self) -> Result<Vec<BreadcrumbFfi>, HavenErrorFfi> {
         let inner = self.inner.clone();
         run_blocking(move || {
             inner
-                .prune_processed_gift_wraps(now_unix_secs)
-                .map_err(|e| haven_core::nostr::mls::redact_hex_sequences(&e.to_string()))
+                .list_breadcrumbs()
+                .map(|v| v.into_iter().map(BreadcrumbFfi::from).collect())
+                .map_err(HavenErrorFfi::from)
         })
         .await
     }
 
-    /// Resets ALL sync cursors (bulk) for the wipe-on-logout path, so a
-    /// returning identity re-seeds cleanly instead of resuming at a stale
-    /// floor. Errors are redacted.
-    pub async fn reset_all_sync_cursors(&self) -> Result<(), String> {
+    /// Deletes every stored breadcrumb.
+    pub async fn clear_breadcrumbs(&self) -> Result<(), HavenErrorFfi> {
+        let inner = self.inner.clone();
+        run_blocking(move || inner.clear_breadcrumbs().map_err(HavenErrorFfi::from)).await
+    }
+
+    /// Records storage and MLS calls taking at least `threshold_ms` as
+    /// breadcrumbs (statement or method name only); `None` turns it off.
+    #[frb(sync)]
+    pub fn set_slow_op_threshold_ms(&self, threshold_ms: Option<u64>) {
+        self.inner.set_slow_op_threshold_ms(threshold_ms);
+    }
+
+    /// Returns the current settings as one `HavenConfig` JSON document, for
+    /// export or display.
+    pub async fn get_haven_config(&self) -> Result<String, HavenErrorFfi> {
         let inner = self.inner.clone();
         run_blocking(move || {
-            inner
-                .reset_all_sync_cursors()
-                .map_err(|e| haven_core::nostr::mls::redact_hex_sequences(&e.to_string()))
+            let config = inner.haven_config().map_err(HavenErrorFfi::from)?;
+            config
+                .to_json()
+                .map_err(|e| HavenErrorFfi::internal(format!("Failed to serialize config: {e}")))
         })
         .await
     }
 
-    /// Deletes every row whose `purge_after < now_unix_secs`.
-    ///
-    /// Returns the number of rows removed.
-    pub async fn prune_expired_last_known(&self, now_unix_secs: i64) -> Result<u32, String> {
+    /// Imports a `HavenConfig` JSON document, replacing every setting it
+    /// covers. Missing fields take their defaults.
+    pub async fn set_haven_config(&self, config_json: String) -> Result<(), HavenErrorFfi> {
+        let config = haven_core::HavenConfig::from_json(&config_json)
+            .map_err(|e| HavenErrorFfi::invalid_input(format!("Invalid config: {e}")))?;
         let inner = self.inner.clone();
-        let removed = run_blocking(move || {
-            inner
-                .prune_expired_last_known(now_unix_secs)
-                .map_err(|e| e.to_string())
-        })
-        .await?;
-        // Reasonable: never expect billions of rows.
-        Ok(u32::try_from(removed).unwrap_or(u32::MAX))
+        run_blocking(move || inner.set_haven_config(&config).map_err(HavenErrorFfi::from)).await
     }
 
-    // ==================== Relay preferences (kind 10050 / 10051) ====================
+    /// Applies a JSON merge patch to the current settings (`null` resets a
+    /// setting to its default) and returns the resulting config JSON.
+    pub async fn apply_config_patch(&self, patch_json: String) -> Result<String, HavenErrorFfi> {
+        let patch: serde_json::Value = serde_json::from_str(&patch_json)
+            .map_err(|e| HavenErrorFfi::invalid_input(format!("Invalid config patch: {e}")))?;
+        let inner = self.inner.clone();
+        run_blocking(move || {
+            let config = inner
+                .apply_config_patch(&patch)
+                .map_err(HavenErrorFfi::from)?;
+            config
+                .to_json()
+                .map_err(|e| HavenErrorFfi::internal(format!("Failed to serialize config: {e}")))
+        })
+        .await
+    }
 
-    /// Seeds the user's relay lists with the default relay list returned by
-    /// [`haven_core::circle::default_relays`] on first launch.
-    ///
-    /// Idempotent: short-circuits via the `relay_prefs_seeded_v1` sentinel
-    /// in `user_settings`. Crucially, the sentinel is the signal — never
-    /// row presence in `user_relays`. A user who removes a default relay
-    /// must not have it re-added by the next defensive seed.
-    ///
-    /// # Returns
-    ///
-    /// `true` if seeding actually wrote rows; `false` if the sentinel was
-    /// already set.
-    pub async fn seed_relay_defaults_if_unseeded(&self) -> Result<bool, String> {
+    /// Returns the health snapshot (privacy facts + breadcrumbs) as JSON for
+    /// the user to share. Nothing is sent anywhere by this call.
+    pub async fn export_health_snapshot(&self) -> Result<String, HavenErrorFfi> {
         let inner = self.inner.clone();
         run_blocking(move || {
             inner
-                .seed_relay_defaults_if_unseeded()
-                .map_err(|e| e.to_string())
+                .health_snapshot()
+                .map_err(HavenErrorFfi::from)?
+                .to_json()
+                .map_err(HavenErrorFfi::from)
         })
         .await
     }
 
     /// Returns the user's relays for one category, ordered by insertion time.
-    pub async fn list_user_relays(&self, relay_type: RelayTypeFfi) -> Result<Vec<String>, String> {
+    pub async fn list_user_relays(
+        &self,
+        relay_type: RelayTypeFfi,
+    ) -> Result<Vec<String>, HavenErrorFfi> {
         let core_type = haven_core::circle::RelayType::from(relay_type);
         let inner = self.inner.clone();
-        run_blocking(move || inner.list_user_relays(core_type).map_err(|e| e.to_string())).await
+        run_blocking(move || {
+            inner
+                .list_user_relays(core_type)
+                .map_err(HavenErrorFfi::from)
+        })
+        .await
     }
 
     /// Adds a relay to one category (idempotent).
@@ -3890,13 +9140,13 @@ impl CircleManagerFfi {
         &self,
         url: String,
         relay_type: RelayTypeFfi,
-    ) -> Result<(), String> {
+    ) -> Result<(), HavenErrorFfi> {
         let core_type = haven_core::circle::RelayType::from(relay_type);
         let inner = self.inner.clone();
         run_blocking(move || {
             inner
                 .add_user_relay(&url, core_type)
-                .map_err(|e| e.to_string())
+                .map_err(HavenErrorFfi::from)
         })
         .await
     }
@@ -3910,13 +9160,13 @@ impl CircleManagerFfi {
         &self,
         url: String,
         relay_type: RelayTypeFfi,
-    ) -> Result<bool, String> {
+    ) -> Result<bool, HavenErrorFfi> {
         let core_type = haven_core::circle::RelayType::from(relay_type);
         let inner = self.inner.clone();
         run_blocking(move || {
             inner
                 .remove_user_relay(&url, core_type)
-                .map_err(|e| e.to_string())
+                .map_err(HavenErrorFfi::from)
         })
         .await
     }
@@ -3926,13 +9176,16 @@ impl CircleManagerFfi {
     /// Adds any missing default relays via `INSERT OR IGNORE`. Existing
     /// user-added custom relays are preserved. Use
     /// [`Self::wipe_and_reset_defaults_for`] for the destructive variant.
-    pub async fn restore_defaults_for(&self, relay_type: RelayTypeFfi) -> Result<(), String> {
+    pub async fn restore_defaults_for(
+        &self,
+        relay_type: RelayTypeFfi,
+    ) -> Result<(), HavenErrorFfi> {
         let core_type = haven_core::circle::RelayType::from(relay_type);
         let inner = self.inner.clone();
         run_blocking(move || {
             inner
                 .restore_relay_defaults_for(core_type)
-                .map_err(|e| e.to_string())
+                .map_err(HavenErrorFfi::from)
         })
         .await
     }
@@ -3946,20 +9199,23 @@ impl CircleManagerFfi {
     pub async fn wipe_and_reset_defaults_for(
         &self,
         relay_type: RelayTypeFfi,
-    ) -> Result<(), String> {
+    ) -> Result<(), HavenErrorFfi> {
         let core_type = haven_core::circle::RelayType::from(relay_type);
         let inner = self.inner.clone();
         run_blocking(move || {
             inner
                 .wipe_and_reset_relay_defaults_for(core_type)
-                .map_err(|e| e.to_string())
+                .map_err(HavenErrorFfi::from)
         })
         .await
     }
 
     /// Returns whether this user wants to publish their relay list for the
     /// given category. Defaults to `true` when never set.
-    pub async fn get_publish_relay_list(&self, relay_type: RelayTypeFfi) -> Result<bool, String> {
+    pub async fn get_publish_relay_list(
+        &self,
+        relay_type: RelayTypeFfi,
+    ) -> Result<bool, HavenErrorFfi> {
         let inner = self.inner.clone();
         run_blocking(move || {
             match relay_type {
@@ -3967,7 +9223,7 @@ impl CircleManagerFfi {
                 // `Nip65` shares the persisted `KeyPackage` toggle (W2).
                 RelayTypeFfi::Nip65 => inner.get_publish_kp_relay_list(),
             }
-            .map_err(|e| e.to_string())
+            .map_err(HavenErrorFfi::from)
         })
         .await
     }
@@ -3978,14 +9234,14 @@ impl CircleManagerFfi {
         &self,
         relay_type: RelayTypeFfi,
         value: bool,
-    ) -> Result<(), String> {
+    ) -> Result<(), HavenErrorFfi> {
         let inner = self.inner.clone();
         run_blocking(move || {
             match relay_type {
                 RelayTypeFfi::Inbox => inner.set_publish_inbox_relay_list(value),
                 RelayTypeFfi::Nip65 => inner.set_publish_kp_relay_list(value),
             }
-            .map_err(|e| e.to_string())
+            .map_err(HavenErrorFfi::from)
         })
         .await
     }
@@ -4001,13 +9257,13 @@ impl CircleManagerFfi {
     pub async fn relay_publish_targets(
         &self,
         relay_type: RelayTypeFfi,
-    ) -> Result<Vec<String>, String> {
+    ) -> Result<Vec<String>, HavenErrorFfi> {
         let core_type = haven_core::circle::RelayType::from(relay_type);
         let inner = self.inner.clone();
         run_blocking(move || {
             let user = inner
                 .list_user_relays(core_type)
-                .map_err(|e| e.to_string())?;
+                .map_err(HavenErrorFfi::from)?;
             Ok::<Vec<String>, String>(haven_core::relay::dedup_relay_targets(&user))
         })
         .await
@@ -4026,26 +9282,49 @@ impl CircleManagerFfi {
     /// returned `targets`, Dart should call
     /// [`Self::record_published_relay_list`] so the unpublish path can
     /// later issue a NIP-09 deletion referencing the event id.
+    ///
+    /// `pow_difficulty` mines NIP-13 work into the event before returning it
+    /// (capped at [`haven_core::relay::MAX_POW_DIFFICULTY`]); `None` for
+    /// relays that do not require it.
+    ///
+    /// With a remote signer paired ([`Self::configure_remote_signer`]), the
+    /// event is signed by it and `secret` is not used.
     pub async fn build_relay_list_publish(
         &self,
-        identity_secret_bytes: Vec<u8>,
+        secret: &SecretHandle,
         relay_type: RelayTypeFfi,
-    ) -> Result<BuiltRelayListEventFfi, String> {
-        let identity_secret_bytes = zeroize::Zeroizing::new(identity_secret_bytes);
-        if identity_secret_bytes.len() != 32 {
-            return Err("Invalid secret bytes length".to_string());
-        }
-        let secret_key = nostr::SecretKey::from_slice(&identity_secret_bytes)
-            .map_err(|e| format!("Invalid secret key: {e}"))?;
-        let keys = nostr::Keys::new(secret_key);
+        pow_difficulty: Option<u8>,
+    ) -> Result<BuiltRelayListEventFfi, HavenErrorFfi> {
+        let signer = IdentitySigner::resolve(|| secret.keys())?;
+        self.build_relay_list_publish_signed(signer, relay_type, pow_difficulty)
+            .await
+    }
+
+    /// [`Self::build_relay_list_publish`], signed by a key held outside this
+    /// process (see [`ExternalSignerFfi`]).
+    pub async fn build_relay_list_publish_with_signer(
+        &self,
+        signer: &ExternalSignerFfi,
+        relay_type: RelayTypeFfi,
+        pow_difficulty: Option<u8>,
+    ) -> Result<BuiltRelayListEventFfi, HavenErrorFfi> {
+        self.build_relay_list_publish_signed(signer.inner.clone(), relay_type, pow_difficulty)
+            .await
+    }
 
+    async fn build_relay_list_publish_signed<S: Signer + Clone + 'static>(
+        &self,
+        signer: S,
+        relay_type: RelayTypeFfi,
+        pow_difficulty: Option<u8>,
+    ) -> Result<BuiltRelayListEventFfi, HavenErrorFfi> {
         let core_type = haven_core::circle::RelayType::from(relay_type);
         // Dark Matter W2: the recorded / on-wire kind is 10002 for Nip65, 10050
         // for Inbox (NOT the persisted-slot `to_kind()`, which is 10051 for the
         // KeyPackage slot).
         let wire_kind_u16 = relay_list_wire_kind(relay_type).as_u16();
         let inner = self.inner.clone();
-        let own_pk = keys.public_key();
+        let own_pk = signer.public_key();
 
         // Read toggle + list + compute targets + the previous publication's
         // `created_at` all under one blocking dispatch.
@@ -4054,7 +9333,7 @@ impl CircleManagerFfi {
                 haven_core::circle::RelayType::Inbox => inner.get_publish_inbox_relay_list(),
                 haven_core::circle::RelayType::KeyPackage => inner.get_publish_kp_relay_list(),
             }
-            .map_err(|e| e.to_string())?;
+            .map_err(HavenErrorFfi::from)?;
             if !publish {
                 return Ok::<(bool, Vec<String>, Vec<String>, Option<i64>), String>((
                     false,
@@ -4065,14 +9344,14 @@ impl CircleManagerFfi {
             }
             let user = inner
                 .list_user_relays(core_type)
-                .map_err(|e| e.to_string())?;
+                .map_err(HavenErrorFfi::from)?;
             let targets = haven_core::relay::dedup_relay_targets(&user);
             // The previous publication's `created_at` so the republish supersedes
             // it even on a same-second re-edit (NIP-01 replaceable-event
             // determinism — a `created_at` tie can otherwise keep the old list).
             let last_published_at = inner
                 .last_published_event(wire_kind_u16, "", &own_pk)
-                .map_err(|e| e.to_string())?
+                .map_err(HavenErrorFfi::from)?
                 .map(|r| r.published_at);
             Ok((true, user, targets, last_published_at))
         })
@@ -4090,14 +9369,23 @@ impl CircleManagerFfi {
             });
         }
 
-        let event = build_relay_list_event_for(
-            relay_type,
-            &keys,
-            &user_list,
-            Some(haven_core::relay::superseding_created_at(last_published_at)),
-        )?;
-        let event_json = serde_json::to_string(&event)
-            .map_err(|e| format!("Failed to serialize relay list event: {e}"))?;
+        // Signed on the blocking pool: an external signer waits on the app.
+        let event = run_blocking({
+            let signer = signer.clone();
+            move || {
+                build_relay_list_event_for(
+                    relay_type,
+                    &signer,
+                    &user_list,
+                    Some(haven_core::relay::superseding_created_at(last_published_at)),
+                )
+            }
+        })
+        .await?;
+        let event = with_pow(event, &signer, pow_difficulty).await?;
+        let event_json = serde_json::to_string(&event).map_err(|e| {
+            HavenErrorFfi::internal(format!("Failed to serialize relay list event: {e}"))
+        })?;
         let event_id_hex = event.id.to_hex();
         let kind_u16 = event.kind.as_u16();
         // Capture the signed event's `created_at` so the caller can pass
@@ -4132,21 +9420,29 @@ impl CircleManagerFfi {
         kind: u16,
         event_id_hex: String,
         published_at_secs: i64,
-    ) -> Result<(), String> {
+    ) -> Result<(), HavenErrorFfi> {
         let pubkey = nostr::PublicKey::parse(&identity_pubkey_hex)
-            .map_err(|e| format!("Invalid pubkey: {e}"))?;
+            .map_err(|e| HavenErrorFfi::invalid_key(format!("Invalid pubkey: {e}")))?;
         let event_id = nostr::EventId::from_hex(&event_id_hex)
-            .map_err(|e| format!("Invalid event id: {e}"))?;
+            .map_err(|e| HavenErrorFfi::invalid_input(format!("Invalid event id: {e}")))?;
         let inner = self.inner.clone();
         let now = published_at_secs;
         run_blocking(move || {
             inner
                 .record_published_event(kind, "", &event_id, &pubkey, now)
-                .map_err(|e| e.to_string())
+                .map_err(HavenErrorFfi::from)
         })
         .await
     }
 
+    /// When the next debounced relay-list republish is due (Unix seconds),
+    /// or `None` if no relay-preference edit is pending. Dart schedules
+    /// [`RelayManagerFfi::flush_relay_list_republishes`] for then.
+    #[frb(sync)]
+    pub fn next_relay_list_publish_at(&self) -> Option<i64> {
+        self.inner.next_relay_list_publish_at()
+    }
+
     /// Builds the events needed to unpublish a relay list category.
     ///
     /// Produces (1) an empty-replacement event with `created_at` chosen to
@@ -4157,19 +9453,17 @@ impl CircleManagerFfi {
     /// Dart should publish both events to the returned `targets` and then
     /// also flip the toggle off via `set_publish_relay_list`. This method
     /// itself does not change the toggle.
+    ///
+    /// `pow_difficulty` mines NIP-13 work into both events, and a paired
+    /// remote signer signs them, as for [`Self::build_relay_list_publish`].
     pub async fn build_unpublish_relay_list(
         &self,
-        identity_secret_bytes: Vec<u8>,
+        secret: &SecretHandle,
         relay_type: RelayTypeFfi,
-    ) -> Result<BuiltUnpublishFfi, String> {
-        let identity_secret_bytes = zeroize::Zeroizing::new(identity_secret_bytes);
-        if identity_secret_bytes.len() != 32 {
-            return Err("Invalid secret bytes length".to_string());
-        }
-        let secret_key = nostr::SecretKey::from_slice(&identity_secret_bytes)
-            .map_err(|e| format!("Invalid secret key: {e}"))?;
-        let keys = nostr::Keys::new(secret_key);
-        let pubkey = keys.public_key();
+        pow_difficulty: Option<u8>,
+    ) -> Result<BuiltUnpublishFfi, HavenErrorFfi> {
+        let signer = IdentitySigner::resolve(|| secret.keys())?;
+        let pubkey = signer.public_key();
 
         let core_type = haven_core::circle::RelayType::from(relay_type);
         let inner = self.inner.clone();
@@ -4184,10 +9478,10 @@ impl CircleManagerFfi {
         ) = run_blocking(move || {
             let user = inner
                 .list_user_relays(core_type)
-                .map_err(|e| e.to_string())?;
+                .map_err(HavenErrorFfi::from)?;
             let last = inner
                 .last_published_event(kind_u16, "", &pubkey)
-                .map_err(|e| e.to_string())?;
+                .map_err(HavenErrorFfi::from)?;
             Ok::<
                 (
                     Option<haven_core::circle::PublishedEventRecord>,
@@ -4202,19 +9496,23 @@ impl CircleManagerFfi {
         let targets = haven_core::relay::dedup_relay_targets(&user_list);
 
         let last_published_at = last_event.as_ref().map(|r| r.published_at);
-        let replacement = build_relay_list_unpublish_for(relay_type, &keys, last_published_at)?;
-        let replacement_json = serde_json::to_string(&replacement)
-            .map_err(|e| format!("Failed to serialize replacement: {e}"))?;
+        let replacement = build_relay_list_unpublish_for(relay_type, &signer, last_published_at)?;
+        let replacement = with_pow(replacement, &signer, pow_difficulty).await?;
+        let replacement_json = serde_json::to_string(&replacement).map_err(|e| {
+            HavenErrorFfi::internal(format!("Failed to serialize replacement: {e}"))
+        })?;
 
         let deletion_json = match last_event {
             Some(record) => {
                 let deletion =
-                    haven_core::relay::build_nip09_deletion(&keys, record.event_id, wire_kind)
-                        .map_err(|e| format!("Failed to build deletion: {e}"))?;
-                Some(
-                    serde_json::to_string(&deletion)
-                        .map_err(|e| format!("Failed to serialize deletion: {e}"))?,
-                )
+                    haven_core::relay::build_nip09_deletion(&signer, record.event_id, wire_kind)
+                        .map_err(|e| {
+                            HavenErrorFfi::internal(format!("Failed to build deletion: {e}"))
+                        })?;
+                let deletion = with_pow(deletion, &signer, pow_difficulty).await?;
+                Some(serde_json::to_string(&deletion).map_err(|e| {
+                    HavenErrorFfi::internal(format!("Failed to serialize deletion: {e}"))
+                })?)
             }
             None => None,
         };
@@ -4248,19 +9546,17 @@ impl CircleManagerFfi {
     /// last-published event is the stale one being scrubbed. Returns
     /// `suppressed=true` with no event when nothing was ever published for
     /// this kind (the dropped relays never received our list).
+    ///
+    /// `pow_difficulty` mines NIP-13 work into the deletion, as for
+    /// [`Self::build_relay_list_publish`].
     pub async fn build_relay_removal_scrub(
         &self,
-        identity_secret_bytes: Vec<u8>,
+        secret: &SecretHandle,
         relay_type: RelayTypeFfi,
         dropped_relays: Vec<String>,
-    ) -> Result<BuiltUnpublishFfi, String> {
-        let identity_secret_bytes = zeroize::Zeroizing::new(identity_secret_bytes);
-        if identity_secret_bytes.len() != 32 {
-            return Err("Invalid secret bytes length".to_string());
-        }
-        let secret_key = nostr::SecretKey::from_slice(&identity_secret_bytes)
-            .map_err(|e| format!("Invalid secret key: {e}"))?;
-        let keys = nostr::Keys::new(secret_key);
+        pow_difficulty: Option<u8>,
+    ) -> Result<BuiltUnpublishFfi, HavenErrorFfi> {
+        let keys = secret.keys()?;
         let pubkey = keys.public_key();
 
         if dropped_relays.is_empty() {
@@ -4280,7 +9576,7 @@ impl CircleManagerFfi {
         let last = run_blocking(move || {
             inner
                 .last_published_event(kind_u16, "", &pubkey)
-                .map_err(|e| e.to_string())
+                .map_err(HavenErrorFfi::from)
         })
         .await?;
 
@@ -4295,10 +9591,12 @@ impl CircleManagerFfi {
             });
         };
 
-        let deletion = haven_core::relay::build_nip09_deletion(&keys, record.event_id, wire_kind)
-            .map_err(|e| format!("Failed to build deletion: {e}"))?;
+        let deletion =
+            haven_core::relay::build_nip09_deletion(&keys, record.event_id, wire_kind)
+                .map_err(|e| HavenErrorFfi::internal(format!("Failed to build deletion: {e}")))?;
+        let deletion = with_pow(deletion, &keys, pow_difficulty).await?;
         let deletion_json = serde_json::to_string(&deletion)
-            .map_err(|e| format!("Failed to serialize deletion: {e}"))?;
+            .map_err(|e| HavenErrorFfi::internal(format!("Failed to serialize deletion: {e}")))?;
 
         Ok(BuiltUnpublishFfi {
             replacement_event_json: None,
@@ -4322,12 +9620,12 @@ impl CircleManagerFfi {
     ///
     /// Returns an error if the group does not exist or the MDK query fails.
     #[cfg(debug_assertions)]
-    pub async fn group_epoch_for_test(&self, mls_group_id: Vec<u8>) -> Result<u64, String> {
+    pub async fn group_epoch_for_test(&self, mls_group_id: Vec<u8>) -> Result<u64, HavenErrorFfi> {
         let group_id = GroupId::from_slice(&mls_group_id);
         self.inner
             .group_epoch(&group_id)
             .await
-            .map_err(|e| e.to_string())
+            .map_err(HavenErrorFfi::from)
     }
 
     /// Release-build stub for [`group_epoch_for_test`](Self::group_epoch_for_test).
@@ -4339,8 +9637,10 @@ impl CircleManagerFfi {
     ///
     /// Always returns an error.
     #[cfg(not(debug_assertions))]
-    pub async fn group_epoch_for_test(&self, _mls_group_id: Vec<u8>) -> Result<u64, String> {
-        Err("group_epoch_for_test is disabled in release builds".to_string())
+    pub async fn group_epoch_for_test(&self, _mls_group_id: Vec<u8>) -> Result<u64, HavenErrorFfi> {
+        Err(HavenErrorFfi::unsupported(
+            "group_epoch_for_test is disabled in release builds",
+        ))
     }
 }
 
@@ -4362,9 +9662,9 @@ impl CircleManagerFfi {
 use haven_core::profile::{
     blossom_server, build_blank_metadata_event, build_metadata_event, build_nip09_deletion,
     download_profile_picture, fetch_profiles, merge_edits, picture_sync_action,
-    profile_read_relays, publish_metadata, resolve_write_relays, self_merge_base_relays,
-    upload_profile_picture, CachedProfile, PictureSyncAction, ProfileEdits, ProfileMetadata,
-    ProfileState, PROFILE_TTL_SECS,
+    profile_read_relays, publish_metadata, resolve_nip05 as core_resolve_nip05,
+    resolve_write_relays, self_merge_base_relays, upload_profile_picture, CachedProfile,
+    PictureSyncAction, ProfileEdits, ProfileMetadata, ProfileState, PROFILE_TTL_SECS,
 };
 
 /// Redacts hex sequences (>= 16 chars) from an error before it crosses the FFI.
@@ -4372,8 +9672,8 @@ use haven_core::profile::{
 /// Profile errors carry hex identifiers (pubkeys, event/blob hashes), so
 /// [`haven_core::util::redact_hex_sequences`] scrubs them — no key material or
 /// internal id reaches the Dart layer (Security Rules #6/#8, plan §4.4).
-fn redact_profile_err(e: impl std::fmt::Display) -> String {
-    haven_core::util::redact_hex_sequences(&e.to_string())
+fn redact_profile_err(e: impl Into<HavenErrorFfi>) -> HavenErrorFfi {
+    e.into().redact_hex()
 }
 
 /// Returns the current Unix time in whole seconds, saturating (never negative).
@@ -4438,7 +9738,7 @@ impl ProfileMetadataFfi {
     fn from_cached(cached: &CachedProfile, has_picture: bool) -> Self {
         Self {
             pubkey_hex: cached.pubkey_hex.clone(),
-            npub: hex_to_npub(&cached.pubkey_hex),
+            npub: npub_or_hex(&cached.pubkey_hex),
             display_name: cached.metadata.display_name().map(ToString::to_string),
             name: cached.metadata.name().map(ToString::to_string),
             about: cached.metadata.about().map(ToString::to_string),
@@ -4451,7 +9751,7 @@ impl ProfileMetadataFfi {
     /// An `Unknown` placeholder for a pubkey with no resolved kind-0.
     fn unknown(pubkey_hex: String) -> Self {
         Self {
-            npub: hex_to_npub(&pubkey_hex),
+            npub: npub_or_hex(&pubkey_hex),
             pubkey_hex,
             display_name: None,
             name: None,
@@ -4510,7 +9810,7 @@ impl CircleManagerFfi {
         &self,
         pubkeys_hex: Vec<String>,
         force: bool,
-    ) -> Result<Vec<ProfileMetadataFfi>, String> {
+    ) -> Result<Vec<ProfileMetadataFfi>, HavenErrorFfi> {
         let now = profile_now_secs();
 
         // Parse hex → PublicKey, dropping malformed ids (never fail the whole
@@ -4623,7 +9923,7 @@ impl CircleManagerFfi {
     /// # Errors
     ///
     /// Returns a redacted error string on download or database failure.
-    pub async fn download_member_picture(&self, pubkey_hex: String) -> Result<(), String> {
+    pub async fn download_member_picture(&self, pubkey_hex: String) -> Result<(), HavenErrorFfi> {
         let Some(cached) = self
             .inner
             .get_profile(&pubkey_hex)
@@ -4681,7 +9981,7 @@ impl CircleManagerFfi {
     pub fn get_cached_profile(
         &self,
         pubkey_hex: String,
-    ) -> Result<Option<ProfileMetadataFfi>, String> {
+    ) -> Result<Option<ProfileMetadataFfi>, HavenErrorFfi> {
         let Some(cached) = self
             .inner
             .get_profile(&pubkey_hex)
@@ -4704,7 +10004,7 @@ impl CircleManagerFfi {
     pub async fn get_profile_thumbnail(
         &self,
         pubkey_hex: String,
-    ) -> Result<Option<Vec<u8>>, String> {
+    ) -> Result<Option<Vec<u8>>, HavenErrorFfi> {
         let inner = self.inner.clone();
         run_blocking(move || {
             inner
@@ -4720,7 +10020,10 @@ impl CircleManagerFfi {
     /// # Errors
     ///
     /// Returns a redacted error string on database failure.
-    pub async fn get_profile_picture(&self, pubkey_hex: String) -> Result<Option<Vec<u8>>, String> {
+    pub async fn get_profile_picture(
+        &self,
+        pubkey_hex: String,
+    ) -> Result<Option<Vec<u8>>, HavenErrorFfi> {
         let inner = self.inner.clone();
         run_blocking(move || {
             inner
@@ -4740,9 +10043,12 @@ impl CircleManagerFfi {
     /// # Errors
     ///
     /// Returns a redacted error string on relay or database failure.
-    pub async fn fetch_my_profile(&self, pubkey_hex: String) -> Result<ProfileMetadataFfi, String> {
+    pub async fn fetch_my_profile(
+        &self,
+        pubkey_hex: String,
+    ) -> Result<ProfileMetadataFfi, HavenErrorFfi> {
         let own_pk = nostr::PublicKey::from_hex(&pubkey_hex)
-            .map_err(|_| "Invalid pubkey_hex".to_string())?;
+            .map_err(|_| HavenErrorFfi::invalid_key("Invalid pubkey_hex"))?;
         // Canonical lowercase key (matches how fetched rows are keyed), so the
         // newer-wins gate and the winning-row re-read line up.
         let own_hex = own_pk.to_hex();
@@ -4798,19 +10104,11 @@ impl CircleManagerFfi {
     /// Returns a redacted error string on relay or database failure.
     pub async fn publish_my_profile(
         &self,
-        identity_secret_bytes: Vec<u8>,
+        secret: &SecretHandle,
         display_name: Option<String>,
         about: Option<String>,
-    ) -> Result<ProfileMetadataFfi, String> {
-        // Zeroize immediately so early-return paths don't leak secret bytes.
-        let identity_secret_bytes = zeroize::Zeroizing::new(identity_secret_bytes);
-        if identity_secret_bytes.len() != 32 {
-            return Err("Invalid secret bytes length".to_string());
-        }
-        let keys = nostr::Keys::new(
-            nostr::SecretKey::from_slice(&identity_secret_bytes)
-                .map_err(|e| format!("Invalid secret key: {e}"))?,
-        );
+    ) -> Result<ProfileMetadataFfi, HavenErrorFfi> {
+        let keys = secret.keys()?;
         let own_pk = keys.public_key();
         let own_hex = own_pk.to_hex();
         let now = profile_now_secs();
@@ -4895,26 +10193,19 @@ impl CircleManagerFfi {
     /// Returns a redacted error string on upload, relay, or database failure.
     pub async fn upload_my_profile_picture(
         &self,
-        identity_secret_bytes: Vec<u8>,
+        secret: &SecretHandle,
         raw: Vec<u8>,
-    ) -> Result<ProfilePictureRefFfi, String> {
-        let identity_secret_bytes = zeroize::Zeroizing::new(identity_secret_bytes);
-        if identity_secret_bytes.len() != 32 {
-            return Err("Invalid secret bytes length".to_string());
-        }
+    ) -> Result<ProfilePictureRefFfi, HavenErrorFfi> {
         // Minimize the cleartext image lifetime on the FFI side: wipe on drop.
         let raw = zeroize::Zeroizing::new(raw);
-        let keys = nostr::Keys::new(
-            nostr::SecretKey::from_slice(&identity_secret_bytes)
-                .map_err(|e| format!("Invalid secret key: {e}"))?,
-        );
+        let keys = secret.keys()?;
         let own_pk = keys.public_key();
         let own_hex = own_pk.to_hex();
         let now = profile_now_secs();
 
-        let server = blossom_server()
-            .parse::<url::Url>()
-            .map_err(|e| format!("Invalid Blossom server URL: {e}"))?;
+        let server = blossom_server().parse::<url::Url>().map_err(|e| {
+            HavenErrorFfi::invalid_input(format!("Invalid Blossom server URL: {e}"))
+        })?;
         let picture = upload_profile_picture(&keys, &server, &raw)
             .await
             .map_err(redact_profile_err)?;
@@ -5000,16 +10291,9 @@ impl CircleManagerFfi {
     /// Returns a redacted error string on relay or database failure.
     pub async fn remove_my_profile_picture(
         &self,
-        identity_secret_bytes: Vec<u8>,
-    ) -> Result<ProfileMetadataFfi, String> {
-        let identity_secret_bytes = zeroize::Zeroizing::new(identity_secret_bytes);
-        if identity_secret_bytes.len() != 32 {
-            return Err("Invalid secret bytes length".to_string());
-        }
-        let keys = nostr::Keys::new(
-            nostr::SecretKey::from_slice(&identity_secret_bytes)
-                .map_err(|e| format!("Invalid secret key: {e}"))?,
-        );
+        secret: &SecretHandle,
+    ) -> Result<ProfileMetadataFfi, HavenErrorFfi> {
+        let keys = secret.keys()?;
         let own_pk = keys.public_key();
         let own_hex = own_pk.to_hex();
         let now = profile_now_secs();
@@ -5103,16 +10387,9 @@ impl CircleManagerFfi {
     /// Returns a redacted error string on relay or database failure.
     pub async fn delete_my_public_profile(
         &self,
-        identity_secret_bytes: Vec<u8>,
-    ) -> Result<(), String> {
-        let identity_secret_bytes = zeroize::Zeroizing::new(identity_secret_bytes);
-        if identity_secret_bytes.len() != 32 {
-            return Err("Invalid secret bytes length".to_string());
-        }
-        let keys = nostr::Keys::new(
-            nostr::SecretKey::from_slice(&identity_secret_bytes)
-                .map_err(|e| format!("Invalid secret key: {e}"))?,
-        );
+        secret: &SecretHandle,
+    ) -> Result<(), HavenErrorFfi> {
+        let keys = secret.keys()?;
         let own_pk = keys.public_key();
         let now = profile_now_secs();
 
@@ -5192,7 +10469,7 @@ impl CircleManagerFfi {
         &self,
         pubkey_hex: String,
         nickname: Option<String>,
-    ) -> Result<(), String> {
+    ) -> Result<(), HavenErrorFfi> {
         // Preserve any existing notes; only the display_name (petname) changes.
         let notes = self
             .inner
@@ -5206,6 +10483,57 @@ impl CircleManagerFfi {
     }
 }
 
+/// A resolved NIP-05 identifier (FFI mirror of
+/// [`haven_core::profile::Nip05Resolution`]).
+#[derive(Clone)]
+pub struct Nip05ResolutionFfi {
+    /// The identifier, normalized (`name@domain`).
+    pub identifier: String,
+    /// Public key as 64-character hex string.
+    pub pubkey_hex: String,
+    /// Public key in NIP-19 bech32 format (npub1...).
+    pub npub: String,
+    /// `wss://` relay hints for the key.
+    pub relays: Vec<String>,
+    /// `true` if the domain was contacted for this lookup and so saw the
+    /// device's IP address; show the user a notice.
+    pub privacy_warning: bool,
+}
+
+impl std::fmt::Debug for Nip05ResolutionFfi {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Nip05ResolutionFfi")
+            .field("pubkey_hex", &"<redacted>")
+            .field("relays", &self.relays.len())
+            .field("privacy_warning", &self.privacy_warning)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Resolves a NIP-05 identifier (`name@domain`) to a public key and relay
+/// hints, so a contact can be added without pasting an npub.
+///
+/// Goes directly to the domain (Haven has no Tor client) unless a fresh
+/// answer is cached; see [`Nip05ResolutionFfi::privacy_warning`].
+///
+/// # Errors
+///
+/// Returns an error if the identifier is malformed, the domain does not list
+/// it, or the lookup fails.
+pub async fn resolve_nip05(identifier: String) -> Result<Nip05ResolutionFfi, HavenErrorFfi> {
+    let resolved = core_resolve_nip05(&identifier, profile_now_secs())
+        .await
+        .map_err(redact_profile_err)?;
+    let pubkey_hex = resolved.pubkey.to_hex();
+    Ok(Nip05ResolutionFfi {
+        identifier: resolved.identifier,
+        npub: npub_or_hex(&pubkey_hex),
+        pubkey_hex,
+        relays: resolved.relays,
+        privacy_warning: resolved.privacy_warning,
+    })
+}
+
 // ==================== Top-level sync helpers ====================
 
 /// Returns the canonical default relay list shared by Rust and Dart.
@@ -5237,7 +10565,7 @@ pub fn default_relays() -> Vec<String> {
 ///   always returns an error.
 #[cfg(debug_assertions)]
 #[frb(sync)]
-pub fn set_default_relays_for_test(relays: Vec<String>) -> Result<(), String> {
+pub fn set_default_relays_for_test(relays: Vec<String>) -> Result<(), HavenErrorFfi> {
     haven_core::circle::set_default_relays_for_test(relays)
 }
 
@@ -5255,8 +10583,10 @@ pub fn set_default_relays_for_test(relays: Vec<String>) -> Result<(), String> {
 /// Always returns an error.
 #[cfg(not(debug_assertions))]
 #[frb(sync)]
-pub fn set_default_relays_for_test(_relays: Vec<String>) -> Result<(), String> {
-    Err("set_default_relays_for_test is disabled in release builds".to_string())
+pub fn set_default_relays_for_test(_relays: Vec<String>) -> Result<(), HavenErrorFfi> {
+    Err(HavenErrorFfi::unsupported(
+        "set_default_relays_for_test is disabled in release builds",
+    ))
 }
 
 /// Returns the read-only discovery-plane relay list (public indexers).
@@ -5291,7 +10621,7 @@ pub fn discovery_relays() -> Vec<String> {
 ///   returns an error.
 #[cfg(debug_assertions)]
 #[frb(sync)]
-pub fn set_discovery_relays_for_test(relays: Vec<String>) -> Result<(), String> {
+pub fn set_discovery_relays_for_test(relays: Vec<String>) -> Result<(), HavenErrorFfi> {
     haven_core::relay::set_discovery_relays_for_test(relays)
 }
 
@@ -5306,8 +10636,10 @@ pub fn set_discovery_relays_for_test(relays: Vec<String>) -> Result<(), String>
 /// Always returns an error.
 #[cfg(not(debug_assertions))]
 #[frb(sync)]
-pub fn set_discovery_relays_for_test(_relays: Vec<String>) -> Result<(), String> {
-    Err("set_discovery_relays_for_test is disabled in release builds".to_string())
+pub fn set_discovery_relays_for_test(_relays: Vec<String>) -> Result<(), HavenErrorFfi> {
+    Err(HavenErrorFfi::unsupported(
+        "set_discovery_relays_for_test is disabled in release builds",
+    ))
 }
 
 /// Opt in to plaintext `ws://` URLs targeting loopback / emulator-host
@@ -5334,7 +10666,7 @@ pub fn set_discovery_relays_for_test(_relays: Vec<String>) -> Result<(), String>
 ///   always returns an error.
 #[cfg(debug_assertions)]
 #[frb(sync)]
-pub fn allow_ws_loopback_for_test() -> Result<(), String> {
+pub fn allow_ws_loopback_for_test() -> Result<(), HavenErrorFfi> {
     haven_core::relay::allow_ws_loopback_for_test()
 }
 
@@ -5348,8 +10680,10 @@ pub fn allow_ws_loopback_for_test() -> Result<(), String> {
 /// Always returns an error.
 #[cfg(not(debug_assertions))]
 #[frb(sync)]
-pub fn allow_ws_loopback_for_test() -> Result<(), String> {
-    Err("allow_ws_loopback_for_test is disabled in release builds".to_string())
+pub fn allow_ws_loopback_for_test() -> Result<(), HavenErrorFfi> {
+    Err(HavenErrorFfi::unsupported(
+        "allow_ws_loopback_for_test is disabled in release builds",
+    ))
 }
 
 /// Opt in to dialing a loopback / emulator-host Blossom server for hermetic
@@ -5375,7 +10709,7 @@ pub fn allow_ws_loopback_for_test() -> Result<(), String> {
 ///   returns an error.
 #[cfg(debug_assertions)]
 #[frb(sync)]
-pub fn allow_private_blossom_for_test() -> Result<(), String> {
+pub fn allow_private_blossom_for_test() -> Result<(), HavenErrorFfi> {
     haven_core::profile::allow_private_blossom_for_test()
 }
 
@@ -5388,8 +10722,10 @@ pub fn allow_private_blossom_for_test() -> Result<(), String> {
 /// Always returns an error.
 #[cfg(not(debug_assertions))]
 #[frb(sync)]
-pub fn allow_private_blossom_for_test() -> Result<(), String> {
-    Err("allow_private_blossom_for_test is disabled in release builds".to_string())
+pub fn allow_private_blossom_for_test() -> Result<(), HavenErrorFfi> {
+    Err(HavenErrorFfi::unsupported(
+        "allow_private_blossom_for_test is disabled in release builds",
+    ))
 }
 
 /// Overrides the Blossom upload server for hermetic public-profile E2E tests.
@@ -5411,7 +10747,7 @@ pub fn allow_private_blossom_for_test() -> Result<(), String> {
 ///   returns an error.
 #[cfg(debug_assertions)]
 #[frb(sync)]
-pub fn set_blossom_server_for_test(url: String) -> Result<(), String> {
+pub fn set_blossom_server_for_test(url: String) -> Result<(), HavenErrorFfi> {
     haven_core::profile::set_blossom_server_for_test(url)
 }
 
@@ -5424,8 +10760,10 @@ pub fn set_blossom_server_for_test(url: String) -> Result<(), String> {
 /// Always returns an error.
 #[cfg(not(debug_assertions))]
 #[frb(sync)]
-pub fn set_blossom_server_for_test(_url: String) -> Result<(), String> {
-    Err("set_blossom_server_for_test is disabled in release builds".to_string())
+pub fn set_blossom_server_for_test(_url: String) -> Result<(), HavenErrorFfi> {
+    Err(HavenErrorFfi::unsupported(
+        "set_blossom_server_for_test is disabled in release builds",
+    ))
 }
 
 impl std::fmt::Debug for CircleManagerFfi {
@@ -5439,6 +10777,8 @@ impl std::fmt::Debug for CircleManagerFfi {
 // ============================================================================
 
 use haven_core::relay::{
+    OutboxEntry as CoreOutboxEntry, OutboxFlush as CoreOutboxFlush,
+    OutboxPriority as CoreOutboxPriority, PublishQuorum as CorePublishQuorum,
     PublishResult as CorePublishResult, RelayConnectionStatus as CoreRelayConnectionStatus,
     RelayEventCheck as CoreRelayEventCheck, RelayManager as CoreRelayManager,
     RelayStatus as CoreRelayStatus,
@@ -5495,30 +10835,357 @@ pub struct RelayRejectionFfi {
     pub reason: String,
 }
 
-/// Result of checking whether events exist on a specific relay (FFI-friendly).
-#[derive(Debug, Clone)]
-pub struct RelayEventCheckFfi {
-    /// The relay URL that was checked.
-    pub relay_url: String,
-    /// Whether at least one matching event was found.
-    pub found: bool,
-    /// Number of matching events found.
-    pub event_count: u32,
-    /// Newest event timestamp (Unix seconds), if any.
-    pub newest_timestamp: Option<i64>,
+/// Result of [`RelayManagerFfi::consume_key_package`].
+#[derive(Debug, Clone)]
+pub struct ConsumedKeyPackageFfi {
+    /// Whether at least one relay accepted the deletion of the consumed
+    /// package.
+    pub deletion_published: bool,
+    /// Event id (hex) of the published replacement, or `None` if no relay
+    /// accepted it (the next maintenance tick mints one).
+    pub replacement_event_id: Option<String>,
+}
+
+/// What happened to a Welcome on one relay (FFI mirror of
+/// [`haven_core::relay::WelcomeRelayOutcome`]).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WelcomeRelayOutcomeFfi {
+    /// The relay accepted the Welcome.
+    Accepted,
+    /// The relay cannot take an event this size.
+    TooLargeForRelay,
+    /// The relay rejected the Welcome for another reason.
+    Rejected,
+    /// The relay could not be reached.
+    Unreachable,
+}
+
+/// One relay's part in a Welcome delivery (FFI-friendly).
+#[derive(Debug, Clone)]
+pub struct WelcomeRelayDeliveryFfi {
+    /// The relay URL.
+    pub relay_url: String,
+    /// What happened there.
+    pub outcome: WelcomeRelayOutcomeFfi,
+    /// For `TooLargeForRelay`: the advertised limit exceeded, if known.
+    pub limit: Option<u64>,
+    /// For `TooLargeForRelay`: the Welcome's serialized size, in bytes.
+    pub event_bytes: Option<u64>,
+    /// For `Rejected`: the relay's reason.
+    pub reason: Option<String>,
+    /// Whether the relay came from the fallback tiers.
+    pub fallback: bool,
+}
+
+impl From<haven_core::relay::WelcomeRelayDelivery> for WelcomeRelayDeliveryFfi {
+    fn from(d: haven_core::relay::WelcomeRelayDelivery) -> Self {
+        use haven_core::relay::WelcomeRelayOutcome;
+        let (outcome, limit, event_bytes, reason) = match d.outcome {
+            WelcomeRelayOutcome::Accepted => (WelcomeRelayOutcomeFfi::Accepted, None, None, None),
+            WelcomeRelayOutcome::TooLargeForRelay { limit, event_bytes } => (
+                WelcomeRelayOutcomeFfi::TooLargeForRelay,
+                limit,
+                u64::try_from(event_bytes).ok(),
+                None,
+            ),
+            WelcomeRelayOutcome::Rejected { reason } => {
+                (WelcomeRelayOutcomeFfi::Rejected, None, None, Some(reason))
+            }
+            WelcomeRelayOutcome::Unreachable => {
+                (WelcomeRelayOutcomeFfi::Unreachable, None, None, None)
+            }
+        };
+        Self {
+            relay_url: d.relay_url,
+            outcome,
+            limit,
+            event_bytes,
+            reason,
+            fallback: d.fallback,
+        }
+    }
+}
+
+/// The result of delivering one Welcome (FFI mirror of
+/// [`haven_core::relay::WelcomeDeliveryReport`]).
+#[derive(Clone)]
+pub struct WelcomeDeliveryReportFfi {
+    /// The recipient's Nostr public key (hex).
+    pub recipient_pubkey: String,
+    /// Every relay considered, in the order they were tried.
+    pub deliveries: Vec<WelcomeRelayDeliveryFfi>,
+    /// Whether at least one relay accepted the Welcome.
+    pub delivered: bool,
+}
+
+impl std::fmt::Debug for WelcomeDeliveryReportFfi {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("WelcomeDeliveryReportFfi")
+            .field("recipient_pubkey", &"<redacted>")
+            .field("deliveries", &self.deliveries)
+            .field("delivered", &self.delivered)
+            .finish()
+    }
+}
+
+/// Result of checking whether events exist on a specific relay (FFI-friendly).
+#[derive(Debug, Clone)]
+pub struct RelayEventCheckFfi {
+    /// The relay URL that was checked.
+    pub relay_url: String,
+    /// Whether at least one matching event was found.
+    pub found: bool,
+    /// Number of matching events found.
+    pub event_count: u32,
+    /// Newest event timestamp (Unix seconds), if any.
+    pub newest_timestamp: Option<i64>,
+}
+
+impl From<CoreRelayEventCheck> for RelayEventCheckFfi {
+    fn from(c: CoreRelayEventCheck) -> Self {
+        Self {
+            relay_url: c.relay_url,
+            found: c.found,
+            event_count: c.event_count as u32,
+            newest_timestamp: c.newest_timestamp,
+        }
+    }
+}
+
+/// One offline-outbox entry (FFI-friendly).
+#[derive(Debug, Clone)]
+pub struct OutboxEntryFfi {
+    /// The queued event's ID (64-char hex).
+    pub event_id: String,
+    /// Event kind.
+    pub kind: u16,
+    /// Relays the event is sent to.
+    pub relays: Vec<String>,
+    /// Delivery priority: "sos", "live_share", "location", or
+    /// "housekeeping".
+    pub priority: String,
+    /// Delivery state: "pending", "sent", "expired", "abandoned", or
+    /// "dropped" (location sharing was paused).
+    pub status: String,
+    /// Failed sends so far, including the original publish.
+    pub attempts: u32,
+    /// Next scheduled retry (Unix timestamp), meaningful while pending.
+    pub next_attempt_at: i64,
+    /// Unix timestamp after which the event is no longer sent.
+    pub expires_at: i64,
+    /// Redacted reason of the last failure, if any.
+    pub last_error: Option<String>,
+    /// Unix timestamp the event was queued.
+    pub created_at: i64,
+}
+
+impl From<CoreOutboxEntry> for OutboxEntryFfi {
+    fn from(e: CoreOutboxEntry) -> Self {
+        Self {
+            event_id: e.event_id,
+            kind: e.kind,
+            relays: e.relays,
+            priority: e.priority.as_str().to_string(),
+            status: e.status.as_str().to_string(),
+            attempts: e.attempts,
+            next_attempt_at: e.next_attempt_at,
+            expires_at: e.expires_at,
+            last_error: e.last_error,
+            created_at: e.created_at,
+        }
+    }
+}
+
+/// Counts from one outbox retry pass (FFI-friendly).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct OutboxFlushFfi {
+    /// Events a relay accepted.
+    pub sent: u32,
+    /// Events that failed again and were rescheduled.
+    pub rescheduled: u32,
+    /// Events dropped because they expired.
+    pub expired: u32,
+    /// Events abandoned after too many failures.
+    pub abandoned: u32,
+}
+
+impl From<CoreOutboxFlush> for OutboxFlushFfi {
+    fn from(f: CoreOutboxFlush) -> Self {
+        Self {
+            sent: u32::try_from(f.sent).unwrap_or(u32::MAX),
+            rescheduled: u32::try_from(f.rescheduled).unwrap_or(u32::MAX),
+            expired: u32::try_from(f.expired).unwrap_or(u32::MAX),
+            abandoned: u32::try_from(f.abandoned).unwrap_or(u32::MAX),
+        }
+    }
+}
+
+/// Events and bytes in each direction (FFI-friendly).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct UsageFfi {
+    /// Events sent.
+    pub events_sent: u64,
+    /// Bytes sent.
+    pub bytes_sent: u64,
+    /// Events received.
+    pub events_received: u64,
+    /// Bytes received.
+    pub bytes_received: u64,
+}
+
+impl From<haven_core::relay::Usage> for UsageFfi {
+    fn from(u: haven_core::relay::Usage) -> Self {
+        Self {
+            events_sent: u.events_sent,
+            bytes_sent: u.bytes_sent,
+            events_received: u.events_received,
+            bytes_received: u.bytes_received,
+        }
+    }
+}
+
+/// Traffic with one relay (FFI-friendly).
+#[derive(Debug, Clone)]
+pub struct RelayUsageFfi {
+    /// Relay URL.
+    pub url: String,
+    /// Traffic with this relay.
+    pub usage: UsageFfi,
+}
+
+/// Traffic carrying one circle's events (FFI-friendly).
+#[derive(Debug, Clone)]
+pub struct CircleUsageFfi {
+    /// The circle's Nostr group id (hex).
+    pub nostr_group_id: String,
+    /// Traffic across all relays.
+    pub usage: UsageFfi,
+}
+
+/// Bandwidth used since process start or the last reset (FFI-friendly).
+#[derive(Debug, Clone)]
+pub struct BandwidthUsageFfi {
+    /// Unix timestamp the counters started.
+    pub since: i64,
+    /// All traffic.
+    pub total: UsageFfi,
+    /// Per relay, heaviest first.
+    pub relays: Vec<RelayUsageFfi>,
+    /// Received traffic whose relay is unknown (multi-relay fetches).
+    pub unattributed: UsageFfi,
+    /// Per circle, heaviest first.
+    pub circles: Vec<CircleUsageFfi>,
+}
+
+impl From<haven_core::relay::BandwidthSummary> for BandwidthUsageFfi {
+    fn from(s: haven_core::relay::BandwidthSummary) -> Self {
+        Self {
+            since: s.since,
+            total: s.total.into(),
+            relays: s
+                .relays
+                .into_iter()
+                .map(|r| RelayUsageFfi {
+                    url: r.url,
+                    usage: r.usage.into(),
+                })
+                .collect(),
+            unattributed: s.unattributed.into(),
+            circles: s
+                .circles
+                .into_iter()
+                .map(|c| CircleUsageFfi {
+                    nostr_group_id: c.nostr_group_id,
+                    usage: c.usage.into(),
+                })
+                .collect(),
+        }
+    }
+}
+
+/// Returns the bytes sent to and received from relays, per relay and per
+/// circle, since the process started or [`reset_bandwidth_usage`].
+///
+/// Counted in memory only; nothing is persisted or sent anywhere.
+#[frb(sync)]
+#[must_use]
+pub fn get_bandwidth_usage() -> BandwidthUsageFfi {
+    haven_core::relay::bandwidth_usage().into()
+}
+
+/// Zeroes the bandwidth counters (e.g. at the start of a billing period).
+#[frb(sync)]
+pub fn reset_bandwidth_usage() {
+    haven_core::relay::reset_bandwidth_usage();
 }
 
-impl From<CoreRelayEventCheck> for RelayEventCheckFfi {
-    fn from(c: CoreRelayEventCheck) -> Self {
+/// Health counters of one relay (FFI mirror of
+/// [`haven_core::relay::RelayStats`]).
+#[derive(Debug, Clone)]
+pub struct RelayMetricsFfi {
+    /// Relay URL.
+    pub url: String,
+    /// Publishes the relay accepted.
+    pub publish_ok: u64,
+    /// Publishes the relay rejected, failed or timed out on.
+    pub publish_failed: u64,
+    /// Share of publishes that failed, from 0.0 to 1.0.
+    pub failure_rate: f64,
+    /// Average publish latency in milliseconds, if any was measured.
+    pub average_latency_ms: Option<u64>,
+    /// Bytes of events sent to the relay.
+    pub bytes_sent: u64,
+    /// Bytes of events received from the relay.
+    pub bytes_received: u64,
+    /// Unix timestamp of the last accepted publish.
+    pub last_ok_at: Option<i64>,
+    /// Unix timestamp of the last failed publish.
+    pub last_error_at: Option<i64>,
+    /// Why the last failed publish failed (redacted).
+    pub last_error: Option<String>,
+}
+
+impl From<haven_core::relay::RelayStats> for RelayMetricsFfi {
+    fn from(s: haven_core::relay::RelayStats) -> Self {
         Self {
-            relay_url: c.relay_url,
-            found: c.found,
-            event_count: c.event_count as u32,
-            newest_timestamp: c.newest_timestamp,
+            failure_rate: s.failure_rate(),
+            average_latency_ms: s.average_latency_ms(),
+            url: s.url,
+            publish_ok: s.publish_ok,
+            publish_failed: s.publish_failed,
+            bytes_sent: s.bytes_sent,
+            bytes_received: s.bytes_received,
+            last_ok_at: s.last_ok_at,
+            last_error_at: s.last_error_at,
+            last_error: s.last_error,
         }
     }
 }
 
+/// Returns every relay's health counters, by URL, accumulated since they
+/// were last reset (across restarts once a circle manager is open).
+#[frb(sync)]
+#[must_use]
+pub fn get_relay_metrics() -> Vec<RelayMetricsFfi> {
+    haven_core::relay::RelayManager::relay_metrics()
+        .into_iter()
+        .map(Into::into)
+        .collect()
+}
+
+/// Writes the relay health counters to disk now. Call when the app is
+/// backgrounded so the last minute of counts is not lost.
+#[frb(sync)]
+pub fn flush_relay_metrics() {
+    haven_core::relay::flush_relay_stats();
+}
+
+/// Zeroes the relay health counters, in memory and on disk.
+#[frb(sync)]
+pub fn reset_relay_metrics() -> Result<(), HavenErrorFfi> {
+    haven_core::relay::reset_relay_metrics().map_err(HavenErrorFfi::from)
+}
+
 /// Per-relay outcome of a gift-wrap fetch (FFI-friendly).
 ///
 /// `responded` is true when the relay completed the WebSocket handshake (it
@@ -5553,6 +11220,56 @@ impl From<CorePublishResult> for PublishResultFfi {
     }
 }
 
+/// Discriminator for [`PublishQuorumFfi`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PublishQuorumKindFfi {
+    /// Any one relay.
+    Any,
+    /// More than half of the relays.
+    Majority,
+    /// Every relay.
+    All,
+    /// At least `at_least` relays.
+    AtLeast,
+}
+
+/// How many relays must accept a quorum publish (mirrors
+/// `haven_core::relay::PublishQuorum`).
+///
+/// Struct shape (rather than tagged enum) avoids pulling in Dart `freezed`
+/// for sealed classes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PublishQuorumFfi {
+    /// Which quorum rule applies.
+    pub kind: PublishQuorumKindFfi,
+    /// The relay count when `kind == AtLeast`; ignored otherwise.
+    pub at_least: u32,
+}
+
+impl From<PublishQuorumFfi> for CorePublishQuorum {
+    fn from(q: PublishQuorumFfi) -> Self {
+        match q.kind {
+            PublishQuorumKindFfi::Any => Self::Any,
+            PublishQuorumKindFfi::Majority => Self::Majority,
+            PublishQuorumKindFfi::All => Self::All,
+            PublishQuorumKindFfi::AtLeast => {
+                Self::AtLeast(usize::try_from(q.at_least).unwrap_or(usize::MAX))
+            }
+        }
+    }
+}
+
+/// One report from [`RelayManagerFfi::publish_event_with_quorum`].
+#[derive(Debug, Clone)]
+pub struct QuorumPublishUpdateFfi {
+    /// The relays' answers so far.
+    pub result: PublishResultFfi,
+    /// Whether the quorum was met.
+    pub quorum_reached: bool,
+    /// `false` for the early report, `true` once every relay answered.
+    pub is_final: bool,
+}
+
 /// Relay manager (FFI wrapper).
 ///
 /// Handles all Nostr relay communication via direct WSS connections.
@@ -5577,6 +11294,54 @@ pub struct RelayManagerFfi {
     inner: CoreRelayManager,
 }
 
+/// A relay's advertised NIP-11 capabilities (FFI).
+#[derive(Debug, Clone)]
+pub struct RelayInfoFfi {
+    /// The relay's self-declared name.
+    pub name: Option<String>,
+    /// NIPs the relay says it supports.
+    pub supported_nips: Vec<u16>,
+    /// Largest message the relay accepts, in bytes.
+    pub max_message_length: Option<u64>,
+    /// Largest event content the relay accepts, in characters.
+    pub max_content_length: Option<u64>,
+    /// The relay requires NIP-42 authentication.
+    pub auth_required: bool,
+    /// The relay requires payment.
+    pub payment_required: bool,
+    /// The relay only accepts writes from some users.
+    pub restricted_writes: bool,
+    /// Minimum NIP-13 difficulty the relay demands.
+    pub min_pow_difficulty: Option<u8>,
+    /// `false` if Haven skips this relay when publishing a typical
+    /// `KeyPackage` (payment, auth, or limits too small).
+    pub usable_for_key_packages: bool,
+}
+
+/// Serialized size assumed for a `KeyPackage` event when judging whether a
+/// relay can take one.
+const TYPICAL_KEY_PACKAGE_EVENT_BYTES: usize = 4 * 1024;
+
+impl RelayInfoFfi {
+    fn from_core(info: &haven_core::relay::RelayInfo) -> Self {
+        let needs = haven_core::relay::PublishNeeds {
+            event_bytes: TYPICAL_KEY_PACKAGE_EVENT_BYTES,
+            content_chars: TYPICAL_KEY_PACKAGE_EVENT_BYTES,
+        };
+        Self {
+            name: info.name.clone(),
+            supported_nips: info.supported_nips.clone(),
+            max_message_length: info.max_message_length,
+            max_content_length: info.max_content_length,
+            auth_required: info.auth_required,
+            payment_required: info.payment_required,
+            restricted_writes: info.restricted_writes,
+            min_pow_difficulty: info.min_pow_difficulty,
+            usable_for_key_packages: haven_core::relay::score_relay(Some(info), needs).is_some(),
+        }
+    }
+}
+
 /// Presence-only result of an M7 receive-only catch-up sweep. All counters —
 /// no group ids, coordinates, or secrets — so it is leak-free (Rule 4).
 pub struct CatchupResultFfi {
@@ -5594,6 +11359,12 @@ pub struct CatchupResultFfi {
     pub deadline_hit: bool,
     /// Relay fetches that returned no response / errored (never fatal).
     pub relay_errors: u32,
+    /// Event signatures verified.
+    pub signatures_verified: u32,
+    /// Events accepted from the verified-event cache without re-verifying.
+    pub signatures_cached: u32,
+    /// Events dropped for a mismatched id or invalid signature.
+    pub signatures_rejected: u32,
 }
 
 impl From<haven_core::relay::CatchupOutcome> for CatchupResultFfi {
@@ -5606,6 +11377,248 @@ impl From<haven_core::relay::CatchupOutcome> for CatchupResultFfi {
             cursors_advanced: c(o.cursors_advanced),
             deadline_hit: o.deadline_hit,
             relay_errors: c(o.relay_errors),
+            signatures_verified: c(o.signatures_verified),
+            signatures_cached: c(o.signatures_cached),
+            signatures_rejected: c(o.signatures_rejected),
+        }
+    }
+}
+
+/// Who joined or left a circle (FFI mirror of
+/// [`haven_core::nostr::mls::MembershipDelta`]).
+#[derive(Clone, Default)]
+pub struct MembershipDeltaFfi {
+    /// Lowercase hex pubkeys of members who joined.
+    pub added: Vec<String>,
+    /// Lowercase hex pubkeys of members who left.
+    pub removed: Vec<String>,
+}
+
+impl std::fmt::Debug for MembershipDeltaFfi {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("MembershipDeltaFfi")
+            .field("added", &self.added.len())
+            .field("removed", &self.removed.len())
+            .finish()
+    }
+}
+
+impl From<haven_core::nostr::mls::MembershipDelta> for MembershipDeltaFfi {
+    fn from(d: haven_core::nostr::mls::MembershipDelta) -> Self {
+        let normalize = |keys: Vec<String>| -> Vec<String> {
+            keys.iter().map(|k| normalize_pubkey_hex(k)).collect()
+        };
+        Self {
+            added: normalize(d.added),
+            removed: normalize(d.removed),
+        }
+    }
+}
+
+/// What a group sync changed in one circle (FFI mirror of
+/// [`haven_core::relay::CircleSyncDigest`]).
+pub struct CircleSyncDigestFfi {
+    /// The circle's MLS group id (raw bytes).
+    pub mls_group_id: Vec<u8>,
+    /// Distinct events fetched across the circle's relays.
+    pub events_fetched: u32,
+    /// Events whose ingest failed (re-fetched next sync).
+    pub events_failed: u32,
+    /// Members whose last-known location was updated.
+    pub locations_updated: u32,
+    /// Whether group state (membership, name, relays) changed.
+    pub group_updated: bool,
+    /// Who joined or left, across every group update in the sync.
+    pub membership: MembershipDeltaFfi,
+    /// The decrypted results, in event order.
+    pub results: Vec<LocationMessageResultFfi>,
+}
+
+impl From<haven_core::relay::CircleSyncDigest> for CircleSyncDigestFfi {
+    fn from(d: haven_core::relay::CircleSyncDigest) -> Self {
+        let c = |n: usize| u32::try_from(n).unwrap_or(u32::MAX);
+        Self {
+            mls_group_id: d.mls_group_id.as_slice().to_vec(),
+            events_fetched: c(d.events_fetched),
+            events_failed: c(d.events_failed),
+            locations_updated: c(d.locations_updated),
+            group_updated: d.group_updated,
+            membership: d.membership.into(),
+            results: d.results.into_iter().map(convert_location_result).collect(),
+        }
+    }
+}
+
+/// What a group sync changed across all circles (FFI mirror of
+/// [`haven_core::relay::SyncDigest`]).
+pub struct SyncDigestFfi {
+    /// Accepted circles with at least one relay.
+    pub circles_synced: u32,
+    /// `(circle, relay)` fetches issued.
+    pub relays_polled: u32,
+    /// `(circle, relay)` fetches that answered.
+    pub relays_responded: u32,
+    /// Distinct events fetched.
+    pub events_fetched: u32,
+    /// Events whose ingest failed.
+    pub events_failed: u32,
+    /// Event signatures verified.
+    pub signatures_verified: u32,
+    /// Events accepted from the verified-event cache without re-verifying.
+    pub signatures_cached: u32,
+    /// Events dropped for a mismatched id or invalid signature.
+    pub signatures_rejected: u32,
+    /// Last-known locations updated.
+    pub locations_updated: u32,
+    /// Receive-side auto-commits published and confirmed.
+    pub auto_commits_published: u32,
+    /// Per-circle detail, for circles that fetched at least one event.
+    pub circles: Vec<CircleSyncDigestFfi>,
+}
+
+impl From<haven_core::relay::SyncDigest> for SyncDigestFfi {
+    fn from(d: haven_core::relay::SyncDigest) -> Self {
+        let c = |n: usize| u32::try_from(n).unwrap_or(u32::MAX);
+        Self {
+            circles_synced: c(d.circles_synced),
+            relays_polled: c(d.relays_polled),
+            relays_responded: c(d.relays_responded),
+            events_fetched: c(d.events_fetched),
+            events_failed: c(d.events_failed),
+            signatures_verified: c(d.signatures_verified),
+            signatures_cached: c(d.signatures_cached),
+            signatures_rejected: c(d.signatures_rejected),
+            locations_updated: c(d.locations_updated),
+            auto_commits_published: c(d.auto_commits_published),
+            circles: d
+                .circles
+                .into_iter()
+                .map(CircleSyncDigestFfi::from)
+                .collect(),
+        }
+    }
+}
+
+/// What a backfill did (FFI mirror of
+/// [`haven_core::relay::BackfillDigest`]).
+pub struct BackfillDigestFfi {
+    /// Pages fetched.
+    pub pages_fetched: u32,
+    /// Distinct events fetched across all pages.
+    pub events_fetched: u32,
+    /// Events skipped because a sync had already ingested them.
+    pub events_skipped: u32,
+    /// Events whose ingest failed (including events from before joining).
+    pub events_failed: u32,
+    /// Events dropped for a mismatched id or invalid signature.
+    pub signatures_rejected: u32,
+    /// Last-known locations updated.
+    pub locations_updated: u32,
+    /// Whether the walk reached the target time.
+    pub complete: bool,
+    /// The decrypted results, oldest first.
+    pub results: Vec<LocationMessageResultFfi>,
+}
+
+impl From<haven_core::relay::BackfillDigest> for BackfillDigestFfi {
+    fn from(d: haven_core::relay::BackfillDigest) -> Self {
+        let c = |n: usize| u32::try_from(n).unwrap_or(u32::MAX);
+        Self {
+            pages_fetched: c(d.pages_fetched),
+            events_fetched: c(d.events_fetched),
+            events_skipped: c(d.events_skipped),
+            events_failed: c(d.events_failed),
+            signatures_rejected: c(d.signatures_rejected),
+            locations_updated: c(d.locations_updated),
+            complete: d.complete,
+            results: d.results.into_iter().map(convert_location_result).collect(),
+        }
+    }
+}
+
+/// One page of group messages (FFI mirror of
+/// [`haven_core::relay::GroupMessagePage`]).
+#[derive(Debug, Clone)]
+pub struct GroupMessagePageFfi {
+    /// Distinct events as JSON, oldest first.
+    pub events_json: Vec<String>,
+    /// The `until` for the next (older) page; `None` when history is
+    /// exhausted.
+    pub next_until: Option<i64>,
+    /// Relays queried.
+    pub relays_polled: u32,
+    /// Relays that answered.
+    pub relays_responded: u32,
+}
+
+/// Result of an inbox poll (FFI mirror of
+/// [`haven_core::relay::InboxSyncSummary`]).
+#[derive(Debug, Clone)]
+pub struct InboxSyncSummaryFfi {
+    /// Relays queried.
+    pub relays_polled: u32,
+    /// Relays that answered.
+    pub relays_responded: u32,
+    /// Distinct gift wraps fetched across all relays.
+    pub wraps_fetched: u32,
+    /// Wraps skipped as already processed or already pending.
+    pub duplicates: u32,
+    /// Wraps that could not be processed.
+    pub failed: u32,
+    /// Invitations surfaced by this poll, oldest first.
+    pub new_invitations: Vec<InvitationFfi>,
+    /// Unexpired one-shot locations received this poll, oldest first.
+    pub one_shot_locations: Vec<OneShotLocationFfi>,
+    /// Rejoin requests this device can grant, oldest first.
+    pub rejoin_requests: Vec<RejoinRequestFfi>,
+}
+
+/// A location a contact sent once, outside any circle (FFI mirror of
+/// [`haven_core::one_shot::OneShotLocation`]).
+#[derive(Debug, Clone)]
+pub struct OneShotLocationFfi {
+    /// The location and its sender.
+    pub location: DecryptedLocationFfi,
+    /// Hex id of the gift wrap it arrived in.
+    pub wrapper_event_id: String,
+    /// Unix timestamp after which it must no longer be shown.
+    pub expires_at: i64,
+}
+
+impl From<haven_core::one_shot::OneShotLocation> for OneShotLocationFfi {
+    fn from(o: haven_core::one_shot::OneShotLocation) -> Self {
+        Self {
+            location: DecryptedLocationFfi::from_location(&o.sender_pubkey.to_hex(), o.location),
+            wrapper_event_id: o.wrapper_event_id.to_hex(),
+            expires_at: o.expires_at,
+        }
+    }
+}
+
+impl From<haven_core::relay::InboxSyncSummary> for InboxSyncSummaryFfi {
+    fn from(s: haven_core::relay::InboxSyncSummary) -> Self {
+        let c = |n: usize| u32::try_from(n).unwrap_or(u32::MAX);
+        Self {
+            relays_polled: c(s.relays_polled),
+            relays_responded: c(s.relays_responded),
+            wraps_fetched: c(s.wraps_fetched),
+            duplicates: c(s.duplicates),
+            failed: c(s.failed),
+            new_invitations: s
+                .new_invitations
+                .into_iter()
+                .map(InvitationFfi::from)
+                .collect(),
+            one_shot_locations: s
+                .one_shot_locations
+                .into_iter()
+                .map(OneShotLocationFfi::from)
+                .collect(),
+            rejoin_requests: s
+                .rejoin_requests
+                .into_iter()
+                .map(RejoinRequestFfi::from)
+                .collect(),
         }
     }
 }
@@ -5673,6 +11686,84 @@ impl From<haven_core::relay::maintenance::KpMaintenanceOutcome> for KpMaintenanc
     }
 }
 
+/// Whether one relay serves the published `KeyPackage` (FFI mirror of
+/// [`haven_core::relay::maintenance::KpRelayStatus`]).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum KpRelayStatusFfi {
+    /// The relay returned the package.
+    Served,
+    /// The relay answered but did not return the package.
+    Missing,
+    /// The relay did not answer.
+    Unreachable,
+}
+
+impl From<haven_core::relay::maintenance::KpRelayStatus> for KpRelayStatusFfi {
+    fn from(s: haven_core::relay::maintenance::KpRelayStatus) -> Self {
+        use haven_core::relay::maintenance::KpRelayStatus as S;
+        match s {
+            S::Served => Self::Served,
+            S::Missing => Self::Missing,
+            S::Unreachable => Self::Unreachable,
+        }
+    }
+}
+
+/// One relay's `KeyPackage` verification verdict.
+#[derive(Debug, Clone)]
+pub struct KpRelayVerificationFfi {
+    /// The relay URL.
+    pub relay_url: String,
+    /// Whether it serves the package.
+    pub status: KpRelayStatusFfi,
+}
+
+/// The latest fetch-back verification of the published `KeyPackage`.
+#[derive(Debug, Clone)]
+pub struct KpVerificationFfi {
+    /// Hex id of the verified kind-30443 event.
+    pub event_id: String,
+    /// Unix timestamp of the check.
+    pub checked_at: i64,
+    /// Number of relays serving the package.
+    pub served_count: u32,
+    /// Whether at least one relay serves it (the user can be invited).
+    pub reachable: bool,
+    /// Per-relay verdicts.
+    pub relays: Vec<KpRelayVerificationFfi>,
+}
+
+impl From<haven_core::relay::maintenance::KpVerification> for KpVerificationFfi {
+    fn from(v: haven_core::relay::maintenance::KpVerification) -> Self {
+        Self {
+            served_count: u32::try_from(v.served_count()).unwrap_or(u32::MAX),
+            reachable: v.is_reachable(),
+            event_id: v.event_id,
+            checked_at: v.checked_at,
+            relays: v
+                .relays
+                .into_iter()
+                .map(|r| KpRelayVerificationFfi {
+                    relay_url: r.relay_url,
+                    status: r.status.into(),
+                })
+                .collect(),
+        }
+    }
+}
+
+/// Recommended delay (seconds) between publishing a `KeyPackage` and
+/// [`RelayManagerFfi::verify_key_package`], from the active environment
+/// profile.
+#[frb(sync)]
+#[must_use]
+pub fn kp_verify_delay_secs() -> u64 {
+    haven_core::environment::active_environment()
+        .timing
+        .kp_verify_delay
+        .as_secs()
+}
+
 /// What an M8-1 relay-list maintenance tick did for one category (FFI mirror of
 /// [`haven_core::relay::maintenance::RelayListAction`]).
 ///
@@ -5726,6 +11817,18 @@ impl From<haven_core::relay::maintenance::RelayListCategoryOutcome>
     }
 }
 
+/// Presence-only result of a debounced relay-list republish
+/// ([`RelayManagerFfi::flush_relay_list_republishes`]).
+#[derive(Debug, Clone, Copy)]
+pub struct RelayListFlushFfi {
+    /// Lists signed and accepted by at least one relay.
+    pub published: u32,
+    /// Lists whose sign or publish failed; they are requeued.
+    pub failed: u32,
+    /// When the next pending republish becomes due (Unix seconds), if any.
+    pub next_due_at: Option<i64>,
+}
+
 /// Presence-only result of an M8-1 relay-list maintenance tick (both
 /// categories). Counters + action enums only — leak-free (Security Rule 4/6).
 #[derive(Debug, Clone, Copy)]
@@ -5826,7 +11929,7 @@ pub struct LegacyRetractionOutcomeFfi {
 
 impl RelayManagerFfi {
     /// Creates a new relay manager.
-    pub async fn new_instance() -> Result<Self, String> {
+    pub async fn new_instance() -> Result<Self, HavenErrorFfi> {
         Ok(Self {
             inner: CoreRelayManager::new(),
         })
@@ -5842,16 +11945,177 @@ impl RelayManagerFfi {
         &self,
         event_json: String,
         relays: Vec<String>,
-    ) -> Result<PublishResultFfi, String> {
+    ) -> Result<PublishResultFfi, HavenErrorFfi> {
         // Parse the event from JSON
-        let event: nostr::Event =
-            serde_json::from_str(&event_json).map_err(|e| format!("Invalid event JSON: {e}"))?;
+        let event: nostr::Event = serde_json::from_str(&event_json)
+            .map_err(|e| HavenErrorFfi::invalid_input(format!("Invalid event JSON: {e}")))?;
 
         let result = self
             .inner
             .publish_event(&event, &relays)
             .await
-            .map_err(|e| e.to_string())?;
+            .map_err(HavenErrorFfi::from)?;
+        Ok(PublishResultFfi::from(result))
+    }
+
+    /// Publishes a signed event and reports back as soon as `quorum` relays
+    /// accepted it, instead of waiting on every relay.
+    ///
+    /// The stream yields the early report (`is_final == false`) once the
+    /// quorum is met, then the final one (`is_final == true`) when every
+    /// relay answered or missed its own deadline. When nothing was pending
+    /// at the early report, only the final one is sent. A single attempt:
+    /// use [`Self::publish_event`] for retries.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the event JSON or a relay URL is invalid, or if
+    /// every relay answered and none accepted.
+    pub async fn publish_event_with_quorum(
+        &self,
+        event_json: String,
+        relays: Vec<String>,
+        quorum: PublishQuorumFfi,
+        sink: crate::frb_generated::StreamSink<QuorumPublishUpdateFfi>,
+    ) -> Result<(), HavenErrorFfi> {
+        let event: nostr::Event = serde_json::from_str(&event_json)
+            .map_err(|e| HavenErrorFfi::invalid_input(format!("Invalid event JSON: {e}")))?;
+        let publish = self
+            .inner
+            .publish_event_with_quorum(&event, &relays, quorum.into())
+            .await
+            .map_err(HavenErrorFfi::from)?;
+        if publish.pending > 0
+            && sink
+                .add(QuorumPublishUpdateFfi {
+                    result: publish.result.into(),
+                    quorum_reached: publish.quorum_reached,
+                    is_final: false,
+                })
+                .is_err()
+        {
+            return Ok(()); // Dart closed the stream
+        }
+        if let Ok(done) = publish.completion.await {
+            let required = CorePublishQuorum::from(quorum).required(done.total_attempted());
+            let _ = sink.add(QuorumPublishUpdateFfi {
+                quorum_reached: done.accepted_by.len() >= required,
+                result: done.into(),
+                is_final: true,
+            });
+        }
+        Ok(())
+    }
+
+    /// Publishes a gift-wrapped Welcome, skipping relays too small for it.
+    ///
+    /// Relays whose cached NIP-11 document advertises a limit below the
+    /// Welcome's size are not sent to; if no relay in `recipient_relays`
+    /// takes it, `fallback_relays` are tried. Each relay's outcome, including
+    /// `TooLargeForRelay`, is in the report. Confirm the pending commit only
+    /// if `delivered` (Rule 13).
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `welcome.event_json` is not a valid event.
+    pub async fn publish_welcome(
+        &self,
+        circle: &CircleManagerFfi,
+        welcome: GiftWrappedWelcomeFfi,
+    ) -> Result<WelcomeDeliveryReportFfi, HavenErrorFfi> {
+        let event: nostr::Event = serde_json::from_str(&welcome.event_json)
+            .map_err(|e| HavenErrorFfi::invalid_input(format!("Invalid event JSON: {e}")))?;
+        let welcome = haven_core::circle::GiftWrappedWelcome {
+            recipient_pubkey: welcome.recipient_pubkey,
+            recipient_relays: welcome.recipient_relays,
+            fallback_relays: welcome.fallback_relays,
+            event,
+        };
+        let infos = run_blocking({
+            let mgr = circle.inner.clone();
+            let relays: Vec<String> = welcome
+                .recipient_relays
+                .iter()
+                .chain(&welcome.fallback_relays)
+                .cloned()
+                .collect();
+            move || {
+                let now = i64::try_from(nostr::Timestamp::now().as_secs()).unwrap_or(0);
+                mgr.fresh_relay_infos(&relays, now)
+                    .map_err(HavenErrorFfi::from)
+            }
+        })
+        .await
+        .unwrap_or_default();
+        let report = haven_core::relay::publish_welcome(&self.inner, &welcome, &infos).await;
+        Ok(WelcomeDeliveryReportFfi {
+            delivered: report.delivered(),
+            recipient_pubkey: report.recipient_pubkey,
+            deliveries: report
+                .deliveries
+                .into_iter()
+                .map(WelcomeRelayDeliveryFfi::from)
+                .collect(),
+        })
+    }
+
+    /// Publishes an identity-signed event, adding NIP-13 proof of work.
+    ///
+    /// Mines to `target_difficulty` before sending (if set), and re-mines
+    /// and re-sends when a relay rejects with a `pow:` reason. The event is
+    /// re-signed with the identity key, so `event_json` must be signed by it.
+    /// The returned `event_id` is the id relays accepted — record that one,
+    /// not the id of `event_json`.
+    pub async fn publish_event_with_pow(
+        &self,
+        event_json: String,
+        relays: Vec<String>,
+        secret: &SecretHandle,
+        target_difficulty: Option<u8>,
+    ) -> Result<PublishResultFfi, HavenErrorFfi> {
+        let keys = secret.keys()?;
+        let event: nostr::Event = serde_json::from_str(&event_json)
+            .map_err(|e| HavenErrorFfi::invalid_input(format!("Invalid event JSON: {e}")))?;
+        let policy = haven_core::relay::PowPolicy {
+            keys,
+            target_difficulty,
+        };
+
+        let result = self
+            .inner
+            .publish_event_with_pow(&event, &relays, Some(&policy))
+            .await
+            .map_err(HavenErrorFfi::from)?;
+        Ok(PublishResultFfi::from(result))
+    }
+
+    /// Requests deletion (NIP-09, kind 5) of identity-signed events.
+    ///
+    /// Signs an id-only deletion of `event_ids` with the identity key and
+    /// publishes it to `relays`. Relays only honor deletions from the events'
+    /// author, so this cannot delete a location (see
+    /// [`CircleManagerFfi::retract_location`]).
+    pub async fn delete_events(
+        &self,
+        secret: &SecretHandle,
+        event_ids: Vec<String>,
+        reason: Option<String>,
+        relays: Vec<String>,
+    ) -> Result<PublishResultFfi, HavenErrorFfi> {
+        let keys = secret.keys()?;
+        let ids = event_ids
+            .iter()
+            .map(|id| {
+                nostr::EventId::from_hex(id).map_err(|e| {
+                    HavenErrorFfi::invalid_input(format!("Invalid event ID '{id}': {e}"))
+                })
+            })
+            .collect::<Result<Vec<_>, HavenErrorFfi>>()?;
+        let result = self
+            .inner
+            .delete_events(&keys, &ids, reason.as_deref(), &relays)
+            .await
+            .map_err(HavenErrorFfi::from)?;
         Ok(PublishResultFfi::from(result))
     }
 
@@ -5863,13 +12127,82 @@ impl RelayManagerFfi {
         &self,
         event_json: String,
         relays: Vec<String>,
-    ) -> Result<(), String> {
-        let event: nostr::Event =
-            serde_json::from_str(&event_json).map_err(|e| format!("Invalid event JSON: {e}"))?;
+    ) -> Result<(), HavenErrorFfi> {
+        let event: nostr::Event = serde_json::from_str(&event_json)
+            .map_err(|e| HavenErrorFfi::invalid_input(format!("Invalid event JSON: {e}")))?;
 
         self.inner
             .publish_event_background(event, &relays)
-            .map_err(|e| e.to_string())
+            .map_err(HavenErrorFfi::from)
+    }
+
+    /// Publishes a location update, keeping it in the offline outbox when no
+    /// relay accepts it.
+    ///
+    /// On failure the error is still returned, but the signed event is
+    /// re-sent automatically once a relay reconnects (see
+    /// [`Self::get_outbox_status`]). Never use this for commits or welcomes:
+    /// those are rolled back when their publish fails.
+    ///
+    /// Queued as a regular location update; see
+    /// [`Self::publish_event_queued_with_priority`].
+    pub async fn publish_event_queued(
+        &self,
+        event_json: String,
+        relays: Vec<String>,
+    ) -> Result<PublishResultFfi, HavenErrorFfi> {
+        self.publish_event_queued_with_priority(
+            event_json,
+            relays,
+            CoreOutboxPriority::Location.as_str().to_string(),
+        )
+        .await
+    }
+
+    /// Same as [`Self::publish_event_queued`], queued at `priority`: "sos",
+    /// "live_share", "location", or "housekeeping".
+    ///
+    /// Higher priorities are retried first, and each is dropped unsent once
+    /// its delivery deadline passes (SOS 1 hour, live share 2 minutes,
+    /// location 15 minutes, housekeeping 24 hours).
+    pub async fn publish_event_queued_with_priority(
+        &self,
+        event_json: String,
+        relays: Vec<String>,
+        priority: String,
+    ) -> Result<PublishResultFfi, HavenErrorFfi> {
+        let priority = CoreOutboxPriority::parse(&priority).ok_or_else(|| {
+            HavenErrorFfi::invalid_input(format!("Unknown outbox priority: {priority}"))
+        })?;
+        let event: nostr::Event = serde_json::from_str(&event_json)
+            .map_err(|e| HavenErrorFfi::invalid_input(format!("Invalid event JSON: {e}")))?;
+
+        let result = self
+            .inner
+            .publish_event_queued(&event, &relays, priority)
+            .await
+            .map_err(HavenErrorFfi::from)?;
+        Ok(PublishResultFfi::from(result))
+    }
+
+    /// Lists the offline outbox: events still waiting for a retry and those
+    /// sent, expired, or abandoned within the last hour. Newest first.
+    pub async fn get_outbox_status(&self) -> Result<Vec<OutboxEntryFfi>, HavenErrorFfi> {
+        let Some(queue) = haven_core::relay::active_publish_queue() else {
+            return Ok(Vec::new());
+        };
+        let entries = run_blocking(move || queue.status().map_err(HavenErrorFfi::from)).await?;
+        Ok(entries.into_iter().map(OutboxEntryFfi::from).collect())
+    }
+
+    /// Re-sends every pending outbox event now. Call when the platform
+    /// reports that connectivity is back.
+    pub async fn retry_outbox(&self) -> Result<OutboxFlushFfi, HavenErrorFfi> {
+        self.inner
+            .retry_outbox()
+            .await
+            .map(OutboxFlushFfi::from)
+            .map_err(HavenErrorFfi::from)
     }
 
     /// Gets the connection status of all relays.
@@ -5898,11 +12231,11 @@ impl RelayManagerFfi {
     /// Returns `Ok(())` even when the relay was never connected — the
     /// caller's intent ("stop talking to this relay") is satisfied either
     /// way.
-    pub async fn disconnect_relay(&self, url: String) -> Result<(), String> {
+    pub async fn disconnect_relay(&self, url: String) -> Result<(), HavenErrorFfi> {
         self.inner
             .remove_relay(&url)
             .await
-            .map_err(|e| e.to_string())
+            .map_err(HavenErrorFfi::from)
     }
 
     // ==================== Event Checking ====================
@@ -5917,9 +12250,9 @@ impl RelayManagerFfi {
         relay_url: String,
         author_pubkey: String,
         event_kind: u16,
-    ) -> Result<RelayEventCheckFfi, String> {
+    ) -> Result<RelayEventCheckFfi, HavenErrorFfi> {
         let pk = nostr::PublicKey::parse(&author_pubkey)
-            .map_err(|e| format!("Invalid author pubkey: {e}"))?;
+            .map_err(|e| HavenErrorFfi::invalid_key(format!("Invalid author pubkey: {e}")))?;
 
         let filter = nostr::Filter::new()
             .kind(nostr::Kind::Custom(event_kind))
@@ -5930,13 +12263,54 @@ impl RelayManagerFfi {
             .inner
             .check_event_on_relay(&relay_url, filter)
             .await
-            .map_err(|e| e.to_string())?;
+            .map_err(HavenErrorFfi::from)?;
 
         Ok(RelayEventCheckFfi::from(result))
     }
 
-    // ==================== Event Fetching ====================
-
+    // ==================== Event Fetching ====================
+
+    /// Returns `url`'s NIP-11 capabilities, from `circles.db` when fresh.
+    ///
+    /// Fetches the document over HTTPS (and persists it) when none was
+    /// fetched within the TTL, or always with `refresh`. Publish paths read
+    /// the persisted documents to order and skip relays.
+    pub async fn fetch_relay_info(
+        &self,
+        circle: &CircleManagerFfi,
+        url: String,
+        refresh: bool,
+    ) -> Result<RelayInfoFfi, HavenErrorFfi> {
+        let now = i64::try_from(nostr::Timestamp::now().as_secs()).unwrap_or(i64::MAX);
+        if !refresh {
+            let cached = run_blocking({
+                let mgr = circle.inner.clone();
+                let url = url.clone();
+                move || mgr.fresh_relay_info(&url, now).map_err(HavenErrorFfi::from)
+            })
+            .await?;
+            if let Some(info) = cached {
+                return Ok(RelayInfoFfi::from_core(&info));
+            }
+        }
+
+        let info = self
+            .inner
+            .fetch_relay_info(&url)
+            .await
+            .map_err(HavenErrorFfi::from)?;
+        run_blocking({
+            let mgr = circle.inner.clone();
+            let info = info.clone();
+            move || {
+                mgr.save_relay_info(&url, &info, now)
+                    .map_err(HavenErrorFfi::from)
+            }
+        })
+        .await?;
+        Ok(RelayInfoFfi::from_core(&info))
+    }
+
     /// Fetches a user's `KeyPackage` relay list (kind 10051).
     ///
     /// Returns the relay URLs where the user publishes their KeyPackages.
@@ -5949,11 +12323,14 @@ impl RelayManagerFfi {
     /// # Returns
     ///
     /// List of relay URLs, or empty if no relay list is published.
-    pub async fn fetch_keypackage_relays(&self, pubkey: String) -> Result<Vec<String>, String> {
+    pub async fn fetch_keypackage_relays(
+        &self,
+        pubkey: String,
+    ) -> Result<Vec<String>, HavenErrorFfi> {
         self.inner
             .fetch_keypackage_relays(&pubkey)
             .await
-            .map_err(|e| e.to_string())
+            .map_err(HavenErrorFfi::from)
     }
 
     /// Fetches a user's key package (kind 30443; legacy 443 is detected but
@@ -5971,12 +12348,12 @@ impl RelayManagerFfi {
     ///
     /// The KeyPackage event as JSON, or `None` if not found.
     /// Returns the event JSON so Flutter can cache and use it for circle creation.
-    pub async fn fetch_keypackage(&self, pubkey: String) -> Result<Option<String>, String> {
+    pub async fn fetch_keypackage(&self, pubkey: String) -> Result<Option<String>, HavenErrorFfi> {
         let event = self
             .inner
             .fetch_keypackage(&pubkey)
             .await
-            .map_err(|e| e.to_string())?;
+            .map_err(HavenErrorFfi::from)?;
 
         Ok(event.map(|e| serde_json::to_string(&e).expect("Failed to serialize event")))
     }
@@ -6007,59 +12384,23 @@ impl RelayManagerFfi {
     pub async fn fetch_member_keypackage(
         &self,
         pubkey: String,
-    ) -> Result<Option<MemberKeyPackageFfi>, String> {
-        // Fetch all three relay lists concurrently. Each arm is tolerated
-        // independently: a transient failure on one list is logged and treated
-        // as an empty list so the cascade can still fall through to later
-        // tiers rather than aborting the whole operation.
-        let (keypackage_result, inbox_result, nip65_result) = tokio::join!(
-            self.inner.fetch_keypackage_relays(&pubkey),
-            self.inner.fetch_inbox_relays(&pubkey),
-            self.inner.fetch_nip65_relays(&pubkey),
-        );
-
-        let keypackage_relays = keypackage_result.unwrap_or_else(|e| {
-            log::debug!(
-                "[fetch_member_keypackage] kind 10051 fetch failed: {}",
-                haven_core::nostr::mls::redact_hex_sequences(&e.to_string())
-            );
-            Vec::new()
-        });
-        let inbox_relays = inbox_result.unwrap_or_else(|e| {
-            log::debug!(
-                "[fetch_member_keypackage] kind 10050 fetch failed: {}",
-                haven_core::nostr::mls::redact_hex_sequences(&e.to_string())
-            );
-            Vec::new()
-        });
-        let nip65_relays = nip65_result.unwrap_or_else(|e| {
-            log::debug!(
-                "[fetch_member_keypackage] kind 10002 fetch failed: {}",
-                haven_core::nostr::mls::redact_hex_sequences(&e.to_string())
-            );
-            Vec::new()
-        });
-
-        // Delegate the KeyPackage discovery cascade to the core helper so the
-        // 10051 → NIP-65 → defaults logic lives in exactly one place.
-        let event = self
+    ) -> Result<Option<MemberKeyPackageFfi>, HavenErrorFfi> {
+        let Some(member) = self
             .inner
-            .fetch_keypackage_with_cascade(&pubkey, &keypackage_relays, &nip65_relays)
+            .fetch_member_key_package(&pubkey)
             .await
-            .map_err(|e| e.to_string())?;
-
-        match event {
-            Some(e) => {
-                let key_package_json = serde_json::to_string(&e)
-                    .map_err(|e| format!("Failed to serialize key package event: {e}"))?;
-                Ok(Some(MemberKeyPackageFfi {
-                    key_package_json,
-                    inbox_relays,
-                    nip65_relays,
-                }))
-            }
-            None => Ok(None),
-        }
+            .map_err(HavenErrorFfi::from)?
+        else {
+            return Ok(None);
+        };
+        let key_package_json = serde_json::to_string(&member.key_package_event).map_err(|e| {
+            HavenErrorFfi::internal(format!("Failed to serialize key package event: {e}"))
+        })?;
+        Ok(Some(MemberKeyPackageFfi {
+            key_package_json,
+            inbox_relays: member.inbox_relays,
+            nip65_relays: member.nip65_relays,
+        }))
     }
 
     /// Fetches a user's NIP-65 relay list (kind 10002).
@@ -6074,11 +12415,11 @@ impl RelayManagerFfi {
     /// # Returns
     ///
     /// List of relay URLs from "r" tags, or empty if no relay list is published.
-    pub async fn fetch_nip65_relays(&self, pubkey: String) -> Result<Vec<String>, String> {
+    pub async fn fetch_nip65_relays(&self, pubkey: String) -> Result<Vec<String>, HavenErrorFfi> {
         self.inner
             .fetch_nip65_relays(&pubkey)
             .await
-            .map_err(|e| e.to_string())
+            .map_err(HavenErrorFfi::from)
     }
 
     /// Fetches gift-wrapped events (kind 1059) addressed to a recipient.
@@ -6101,9 +12442,9 @@ impl RelayManagerFfi {
         recipient_pubkey: String,
         relays: Vec<String>,
         since: Option<i64>,
-    ) -> Result<Vec<String>, String> {
+    ) -> Result<Vec<String>, HavenErrorFfi> {
         let pk = nostr::PublicKey::parse(&recipient_pubkey)
-            .map_err(|e| format!("Invalid recipient pubkey: {e}"))?;
+            .map_err(|e| HavenErrorFfi::invalid_key(format!("Invalid recipient pubkey: {e}")))?;
 
         let mut filter = nostr::Filter::new()
             .kind(nostr::Kind::GiftWrap)
@@ -6119,12 +12460,14 @@ impl RelayManagerFfi {
             .inner
             .fetch_events(filter, &relays, None)
             .await
-            .map_err(|e| e.to_string())?;
+            .map_err(HavenErrorFfi::from)?;
 
         events
             .into_iter()
             .map(|e| {
-                serde_json::to_string(&e).map_err(|err| format!("Failed to serialize event: {err}"))
+                serde_json::to_string(&e).map_err(|err| {
+                    HavenErrorFfi::internal(format!("Failed to serialize event: {err}"))
+                })
             })
             .collect::<Result<Vec<_>, _>>()
     }
@@ -6154,9 +12497,9 @@ impl RelayManagerFfi {
         recipient_pubkey: String,
         relays: Vec<String>,
         since: Option<i64>,
-    ) -> Result<Vec<RelayGiftWrapFetchFfi>, String> {
+    ) -> Result<Vec<RelayGiftWrapFetchFfi>, HavenErrorFfi> {
         let pk = nostr::PublicKey::parse(&recipient_pubkey)
-            .map_err(|e| format!("Invalid recipient pubkey: {e}"))?;
+            .map_err(|e| HavenErrorFfi::invalid_key(format!("Invalid recipient pubkey: {e}")))?;
 
         // The per-relay count is shown to the user as exact, so the cap is a
         // generous flood-guard rather than a paging limit: a real inbox holds
@@ -6176,7 +12519,7 @@ impl RelayManagerFfi {
             .inner
             .fetch_events_per_relay(filter, &relays)
             .await
-            .map_err(|e| e.to_string())?;
+            .map_err(HavenErrorFfi::from)?;
 
         outcomes
             .into_iter()
@@ -6185,8 +12528,9 @@ impl RelayManagerFfi {
                     .events
                     .into_iter()
                     .map(|e| {
-                        serde_json::to_string(&e)
-                            .map_err(|err| format!("Failed to serialize event: {err}"))
+                        serde_json::to_string(&e).map_err(|err| {
+                            HavenErrorFfi::internal(format!("Failed to serialize event: {err}"))
+                        })
                     })
                     .collect::<Result<Vec<_>, _>>()?;
                 Ok(RelayGiftWrapFetchFfi {
@@ -6195,7 +12539,7 @@ impl RelayManagerFfi {
                     events,
                 })
             })
-            .collect::<Result<Vec<_>, String>>()
+            .collect::<Result<Vec<_>, HavenErrorFfi>>()
     }
 
     /// Runs an M7 receive-only catch-up sweep over every visible circle.
@@ -6210,10 +12554,10 @@ impl RelayManagerFfi {
         circle: &CircleManagerFfi,
         own_pubkey_hex: String,
         max_duration_secs: u64,
-    ) -> Result<CatchupResultFfi, String> {
+    ) -> Result<CatchupResultFfi, HavenErrorFfi> {
         let circle_mgr = circle.inner.clone();
         let own_pk = nostr::PublicKey::parse(&own_pubkey_hex)
-            .map_err(|e| format!("invalid own pubkey: {e}"))?;
+            .map_err(|e| HavenErrorFfi::invalid_key(format!("invalid own pubkey: {e}")))?;
         let outcome = haven_core::relay::catchup::run_catchup_all_circles(
             &circle_mgr,
             &self.inner,
@@ -6224,17 +12568,368 @@ impl RelayManagerFfi {
         Ok(CatchupResultFfi::from(outcome))
     }
 
+    /// Syncs group messages for every accepted circle.
+    ///
+    /// Replaces the Dart-side per-circle `fetch_group_messages` →
+    /// `decrypt_location_collecting_commits` → upsert loop: each circle's
+    /// relays are queried from their own cursors, events are ingested through
+    /// the foreground session, locations are persisted, receive-side
+    /// auto-commits are published and confirmed here (Rule 13), and a digest
+    /// of the changes is returned.
+    ///
+    /// # Arguments
+    ///
+    /// * `circle` - The foreground circle manager (Rule 14)
+    /// * `own_pubkey_hex` - The local identity's public key, to skip self-echoes
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the pubkey is invalid or the circle list cannot be
+    /// read.
+    pub async fn sync_group_messages(
+        &self,
+        circle: &CircleManagerFfi,
+        own_pubkey_hex: String,
+    ) -> Result<SyncDigestFfi, HavenErrorFfi> {
+        let own_pk = nostr::PublicKey::parse(&own_pubkey_hex)
+            .map_err(|e| HavenErrorFfi::invalid_key(format!("invalid own pubkey: {e}")))?;
+        let circle_mgr = circle.inner.clone();
+        let digest = haven_core::relay::SyncManager::new(&circle_mgr, &self.inner)
+            .sync_all(&own_pk.to_hex())
+            .await
+            .map_err(HavenErrorFfi::from)?;
+        Ok(SyncDigestFfi::from(digest))
+    }
+
+    /// Backfills one circle's history down to `target_secs`.
+    ///
+    /// Walks backwards page by page, skips what a sync already ingested,
+    /// ingests the rest oldest first and persists locations. Meant for a
+    /// newly joined member: pass now minus a day to fill in the last day of
+    /// locations. Sync cursors are not moved.
+    ///
+    /// # Arguments
+    ///
+    /// * `circle` - The foreground circle manager (Rule 14)
+    /// * `mls_group_id` - The circle's MLS group id
+    /// * `target_secs` - Unix timestamp (seconds) to walk back to
+    /// * `own_pubkey_hex` - The local identity's public key, to skip self-echoes
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the pubkey is invalid or the circle does not
+    /// exist.
+    pub async fn backfill_circle(
+        &self,
+        circle: &CircleManagerFfi,
+        mls_group_id: Vec<u8>,
+        target_secs: i64,
+        own_pubkey_hex: String,
+    ) -> Result<BackfillDigestFfi, HavenErrorFfi> {
+        let own_pk = nostr::PublicKey::parse(&own_pubkey_hex)
+            .map_err(|e| HavenErrorFfi::invalid_key(format!("invalid own pubkey: {e}")))?;
+        let group_id = GroupId::from_slice(&mls_group_id);
+        let circle_mgr = circle.inner.clone();
+        let digest = haven_core::relay::SyncManager::new(&circle_mgr, &self.inner)
+            .backfill_circle(&group_id, target_secs, &own_pk.to_hex())
+            .await
+            .map_err(HavenErrorFfi::from)?;
+        Ok(BackfillDigestFfi::from(digest))
+    }
+
+    /// Polls the inbox relays for gift-wrapped invitations and processes them.
+    ///
+    /// Replaces the Dart-side `fetch_gift_wraps_per_relay` →
+    /// `process_gift_wrapped_invitation` → `cursor_advance_inbox_to_wrap` loop:
+    /// each relay is queried from its own cursor, wraps are deduplicated by id,
+    /// welcomes are held in `circle`'s invitation store, and the new
+    /// invitations are returned. Best-effort — relay and per-wrap failures are
+    /// counted, not raised.
+    ///
+    /// # Arguments
+    ///
+    /// * `circle` - The foreground circle manager (Rule 14)
+    /// * `secret` - The recipient's identity
+    /// * `relays` - The user's inbox relay URLs
+    pub async fn sync_inbox(
+        &self,
+        circle: &CircleManagerFfi,
+        secret: &SecretHandle,
+        relays: Vec<String>,
+    ) -> Result<InboxSyncSummaryFfi, HavenErrorFfi> {
+        let keys = secret.keys()?;
+        let circle_mgr = circle.inner.clone();
+        let summary = haven_core::relay::InboxProcessor::new(&circle_mgr, &self.inner)
+            .sync(&keys, &relays)
+            .await;
+        Ok(InboxSyncSummaryFfi::from(summary))
+    }
+
+    /// Sends the user's location once to a contact, outside any circle.
+    ///
+    /// The location is gift-wrapped to the contact's inbox relays and expires
+    /// after `ttl_secs` (default one hour, clamped to 5 minutes..24 hours).
+    /// No circle or MLS state is created on either side; the contact receives
+    /// it through [`Self::sync_inbox`]. See [`haven_core::one_shot`].
+    ///
+    /// With a remote signer paired
+    /// ([`CircleManagerFfi::configure_remote_signer`]), the gift wrap's seal
+    /// is encrypted and signed by it instead.
+    ///
+    /// # Arguments
+    ///
+    /// * `secret` - The sender's identity
+    /// * `contact_pubkey_hex` - The contact's public key (hex)
+    /// * `latitude`, `longitude` - The coordinates to send, exactly as given
+    /// * `ttl_secs` - Lifetime in seconds, or `None` for the default
+    pub async fn send_one_shot_location(
+        &self,
+        secret: &SecretHandle,
+        contact_pubkey_hex: String,
+        latitude: f64,
+        longitude: f64,
+        ttl_secs: Option<u64>,
+    ) -> Result<PublishResultFfi, HavenErrorFfi> {
+        let signer = IdentitySigner::resolve(|| secret.keys())?;
+        self.send_one_shot_location_signed(
+            signer,
+            contact_pubkey_hex,
+            latitude,
+            longitude,
+            ttl_secs,
+        )
+        .await
+    }
+
+    /// [`Self::send_one_shot_location`], with the gift wrap's seal encrypted
+    /// and signed by a key held outside this process (see
+    /// [`ExternalSignerFfi`]).
+    pub async fn send_one_shot_location_with_signer(
+        &self,
+        signer: &ExternalSignerFfi,
+        contact_pubkey_hex: String,
+        latitude: f64,
+        longitude: f64,
+        ttl_secs: Option<u64>,
+    ) -> Result<PublishResultFfi, HavenErrorFfi> {
+        let signer = IdentitySigner::External(signer.inner.clone());
+        self.send_one_shot_location_signed(
+            signer,
+            contact_pubkey_hex,
+            latitude,
+            longitude,
+            ttl_secs,
+        )
+        .await
+    }
+
+    async fn send_one_shot_location_signed(
+        &self,
+        signer: IdentitySigner,
+        contact_pubkey_hex: String,
+        latitude: f64,
+        longitude: f64,
+        ttl_secs: Option<u64>,
+    ) -> Result<PublishResultFfi, HavenErrorFfi> {
+        if !latitude.is_finite() || !(-90.0..=90.0).contains(&latitude) {
+            return Err(HavenErrorFfi::invalid_input(
+                "latitude must be between -90 and 90",
+            ));
+        }
+        if !longitude.is_finite() || !(-180.0..=180.0).contains(&longitude) {
+            return Err(HavenErrorFfi::invalid_input(
+                "longitude must be between -180 and 180",
+            ));
+        }
+        let contact = nostr::PublicKey::parse(&contact_pubkey_hex)
+            .map_err(|e| HavenErrorFfi::invalid_key(format!("Invalid contact pubkey: {e}")))?;
+        let location = haven_core::location::LocationMessage::new(latitude, longitude);
+
+        let result = haven_core::one_shot::send_one_shot_location(
+            &self.inner,
+            &signer,
+            &contact,
+            &location,
+            ttl_secs.unwrap_or(haven_core::one_shot::DEFAULT_ONE_SHOT_TTL_SECS),
+        )
+        .await
+        .map_err(HavenErrorFfi::from)?;
+        Ok(PublishResultFfi::from(result))
+    }
+
+    /// Sends this identity and its circles to the new device that shows
+    /// `payload` (its `haven-link:` QR code). See [`haven_core::device_link`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the payload is not a live device link or the
+    /// publish fails.
+    pub async fn send_device_link(
+        &self,
+        circle: &CircleManagerFfi,
+        secret: &SecretHandle,
+        payload: String,
+    ) -> Result<PublishResultFfi, HavenErrorFfi> {
+        let keys = secret.keys()?;
+        let offer = haven_core::device_link::DeviceLinkOffer::parse(&payload)
+            .map_err(HavenErrorFfi::from)?;
+        let circles = circle
+            .inner
+            .device_link_circles()
+            .await
+            .map_err(HavenErrorFfi::from)?;
+        haven_core::device_link::send_device_link(&self.inner, &keys, &offer, &circles)
+            .await
+            .map(PublishResultFfi::from)
+            .map_err(HavenErrorFfi::from)
+    }
+
+    /// Checks the link's relays for the identity sent by the old device.
+    ///
+    /// Returns `None` until it arrives; poll until
+    /// [`DeviceLinkSessionFfi::expires_at`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error once the link expired, or if the fetch fails.
+    pub async fn complete_device_link(
+        &self,
+        session: &DeviceLinkSessionFfi,
+    ) -> Result<Option<LinkedIdentityFfi>, HavenErrorFfi> {
+        haven_core::device_link::receive_device_link(&self.inner, &session.inner)
+            .await
+            .map(|linked| linked.map(LinkedIdentityFfi::from))
+            .map_err(HavenErrorFfi::from)
+    }
+
+    /// Asks the admins of each of `circles` to re-add this device with its
+    /// freshly published `KeyPackage` (`key_package_json`).
+    ///
+    /// Returns how many requests were delivered.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the secret or `KeyPackage` JSON is invalid.
+    pub async fn request_rejoin(
+        &self,
+        secret: &SecretHandle,
+        circles: Vec<LinkedCircleFfi>,
+        key_package_json: String,
+    ) -> Result<u32, HavenErrorFfi> {
+        let keys = secret.keys()?;
+        let key_package_event: nostr::Event = serde_json::from_str(&key_package_json)
+            .map_err(|e| HavenErrorFfi::invalid_input(format!("Invalid key package JSON: {e}")))?;
+        let circles: Vec<haven_core::device_link::LinkedCircle> =
+            circles.into_iter().map(Into::into).collect();
+        let delivered = haven_core::device_link::request_rejoin(
+            &self.inner,
+            &keys,
+            &circles,
+            &key_package_event,
+        )
+        .await;
+        Ok(u32::try_from(delivered).unwrap_or(u32::MAX))
+    }
+
+    /// Retires a `KeyPackage` this device published once it was used to add
+    /// the user to a group, and publishes a replacement.
+    ///
+    /// Publishes a NIP-09 deletion of `event_id_hex` and a fresh package for
+    /// the same slot to the user's own `KeyPackage` relays, records the
+    /// replacement on success, and deletes its material on failure (mdk#160).
+    /// The consumed package's material is deleted either way.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the secret or event id is invalid, no `KeyPackage`
+    /// relays are configured, or the event is not a tracked package.
+    pub async fn consume_key_package(
+        &self,
+        circle: &CircleManagerFfi,
+        secret: &SecretHandle,
+        event_id_hex: String,
+    ) -> Result<ConsumedKeyPackageFfi, HavenErrorFfi> {
+        let keys = secret.keys()?;
+        let event_id = nostr::EventId::from_hex(&event_id_hex)
+            .map_err(|e| HavenErrorFfi::invalid_input(format!("Invalid event id: {e}")))?;
+        let circle_mgr = circle.inner.clone();
+        let own_relays: Vec<String> = run_blocking({
+            let mgr = circle_mgr.clone();
+            move || {
+                let user = mgr
+                    .list_user_relays(haven_core::circle::RelayType::KeyPackage)
+                    .map_err(HavenErrorFfi::from)?;
+                Ok(haven_core::relay::dedup_relay_targets(&user))
+            }
+        })
+        .await?;
+        if own_relays.is_empty() {
+            return Err(HavenErrorFfi::invalid_input(
+                "No KeyPackage relays configured",
+            ));
+        }
+
+        let consumed = circle_mgr
+            .consume_key_package(&keys, &event_id, &own_relays)
+            .await
+            .map_err(HavenErrorFfi::from)?;
+        let deletion_published = self
+            .inner
+            .publish_event(&consumed.deletion, &own_relays)
+            .await
+            .is_ok_and(|r| !r.accepted_by.is_empty());
+
+        let replacement = consumed.replacement;
+        let replacement_event_id = match self
+            .inner
+            .publish_event(&replacement.event, &own_relays)
+            .await
+        {
+            Ok(result) if !result.accepted_by.is_empty() => Some(result.event_id.to_hex()),
+            _ => None,
+        };
+        if let Some(event_id) = &replacement_event_id {
+            let row = haven_core::circle::PublishedKeyPackageRow {
+                event_id: event_id.clone(),
+                d_tag: replacement.d_tag.clone(),
+                key_package: replacement.key_package.bytes().to_vec(),
+                created_at: i64::try_from(nostr::Timestamp::now().as_secs()).unwrap_or(0),
+            };
+            let mgr = circle_mgr.clone();
+            run_blocking(move || {
+                mgr.record_published_key_package(&row)
+                    .map_err(HavenErrorFfi::from)
+            })
+            .await?;
+        } else if let Err(e) = circle_mgr
+            .delete_key_package(&replacement.key_package)
+            .await
+        {
+            log::debug!(
+                "[consume_key_package] replacement KP delete failed: {}",
+                haven_core::nostr::mls::redact_hex_sequences(&e.to_string())
+            );
+        }
+
+        Ok(ConsumedKeyPackageFfi {
+            deletion_published,
+            replacement_event_id,
+        })
+    }
+
     /// `KeyPackage` maintenance (Dark Matter DM-2b) — republish-if-missing into
     /// a stable NIP-33 `d` slot on the user's own NIP-65 relays. Also the
     /// FIRST-publish path (onboarding / login): a responding relay serving
     /// nothing + no tracked slot mints a fresh package.
     ///
-    /// Dart-timer-driven (the identity secret lives only in Dart, Security Rule
-    /// 9): the secret bytes are consumed per-call and zeroized. Fail-soft and
-    /// idempotent — one tick of the periodic maintenance loop.
+    /// Dart-timer-driven; the identity comes in as a [`SecretHandle`] and the
+    /// derived keys live for this call only. Fail-soft and idempotent — one
+    /// tick of the periodic maintenance loop.
     ///
     /// Steps:
-    /// 1. Derive `Keys`/pubkey from the secret bytes (zeroized after).
+    /// 1. Derive `Keys`/pubkey from `secret`, or use
+    ///    the paired remote signer ([`CircleManagerFfi::configure_remote_signer`]),
+    ///    which then signs the key package event.
     /// 2. Probe the user's OWN NIP-65 relays (dedup'd, own-relays-only — never a
     ///    default union) for kind-30443 events authored by self.
     /// 3. Build the presence snapshot (`(d, event_id)` per responder) — under
@@ -6252,15 +12947,34 @@ impl RelayManagerFfi {
     pub async fn maintain_key_package(
         &self,
         circle: &CircleManagerFfi,
-        identity_secret_bytes: Vec<u8>,
-    ) -> Result<KpMaintenanceOutcomeFfi, String> {
+        secret: &SecretHandle,
+    ) -> Result<KpMaintenanceOutcomeFfi, HavenErrorFfi> {
+        let signer = IdentitySigner::resolve(|| secret.keys())?;
+        self.maintain_key_package_signed(circle, signer).await
+    }
+
+    /// [`Self::maintain_key_package`], with the key package event signed by a
+    /// key held outside this process (see [`ExternalSignerFfi`]).
+    pub async fn maintain_key_package_with_signer(
+        &self,
+        circle: &CircleManagerFfi,
+        signer: &ExternalSignerFfi,
+    ) -> Result<KpMaintenanceOutcomeFfi, HavenErrorFfi> {
+        let signer = IdentitySigner::External(signer.inner.clone());
+        self.maintain_key_package_signed(circle, signer).await
+    }
+
+    async fn maintain_key_package_signed(
+        &self,
+        circle: &CircleManagerFfi,
+        signer: IdentitySigner,
+    ) -> Result<KpMaintenanceOutcomeFfi, HavenErrorFfi> {
         use haven_core::relay::maintenance::{
             decide_kp_maintenance, KpMaintenanceAction, KpMaintenanceDecision,
             KpMaintenanceOutcome, RelayKpEntry, RelayKpPerRelay, RelayKpSnapshot,
         };
 
-        let keys = keys_from_secret_bytes(identity_secret_bytes)?;
-        let own_pk = keys.public_key();
+        let own_pk = signer.public_key();
 
         // Own NIP-65 (KeyPackage-discovery) relays only — no default union, no
         // discovery plane. Persisted under the `KeyPackage` slot (W2).
@@ -6270,7 +12984,7 @@ impl RelayManagerFfi {
             move || {
                 let user = mgr
                     .list_user_relays(haven_core::circle::RelayType::KeyPackage)
-                    .map_err(|e| e.to_string())?;
+                    .map_err(HavenErrorFfi::from)?;
                 Ok::<Vec<String>, String>(haven_core::relay::dedup_relay_targets(&user))
             }
         })
@@ -6332,7 +13046,7 @@ impl RelayManagerFfi {
         // Read the stored stable slot and decide.
         let stored_stable_d = run_blocking({
             let mgr = circle_mgr.clone();
-            move || mgr.latest_canonical_d_tag().map_err(|e| e.to_string())
+            move || mgr.latest_canonical_d_tag().map_err(HavenErrorFfi::from)
         })
         .await?;
         let decision = decide_kp_maintenance(&snapshot, stored_stable_d.as_deref());
@@ -6367,7 +13081,7 @@ impl RelayManagerFfi {
                                 created_at: now,
                             },
                         )
-                        .map_err(|e| e.to_string())
+                        .map_err(HavenErrorFfi::from)
                     }
                 })
                 .await;
@@ -6383,7 +13097,7 @@ impl RelayManagerFfi {
                 let (act, healed) = self
                     .republish_key_package(
                         &circle_mgr,
-                        &keys,
+                        &signer,
                         existing_d.as_deref(),
                         &targets,
                         &mut relay_errors,
@@ -6403,6 +13117,32 @@ impl RelayManagerFfi {
         }))
     }
 
+    /// Verifies that the published `KeyPackage` is actually retrievable.
+    ///
+    /// Waits `delay_secs` (use [`kp_verify_delay_secs`] after a publish), then
+    /// fetches the tracked kind-30443 event back from each of the user's
+    /// `KeyPackage` relays by id and records per relay whether it is served,
+    /// missing, or unreachable. A relay that acks a write but never serves it
+    /// leaves the user uninvitable there; this makes that visible.
+    ///
+    /// Returns `None` if no package has been published yet or no
+    /// `KeyPackage` relays are configured.
+    pub async fn verify_key_package(
+        &self,
+        circle: &CircleManagerFfi,
+        delay_secs: u64,
+    ) -> Result<Option<KpVerificationFfi>, HavenErrorFfi> {
+        let circle_mgr = circle.inner.clone();
+        let verification = haven_core::relay::maintenance::verify_key_package_publish(
+            &circle_mgr,
+            &self.inner,
+            std::time::Duration::from_secs(delay_secs),
+        )
+        .await
+        .map_err(HavenErrorFfi::from)?;
+        Ok(verification.map(KpVerificationFfi::from))
+    }
+
     /// Reuses-or-mints, signs, publishes (to the confirmed-drop TARGET relays
     /// only), and records a `KeyPackage` republish for
     /// [`Self::maintain_key_package`]. Publish-first: only after a relay write
@@ -6420,11 +13160,11 @@ impl RelayManagerFfi {
     async fn republish_key_package(
         &self,
         circle_mgr: &Arc<CoreCircleManager>,
-        keys: &nostr::Keys,
+        signer: &IdentitySigner,
         existing_d: Option<&str>,
         targets: &[String],
         relay_errors: &mut usize,
-    ) -> Result<(haven_core::relay::maintenance::KpMaintenanceAction, usize), String> {
+    ) -> Result<(haven_core::relay::maintenance::KpMaintenanceAction, usize), HavenErrorFfi> {
         use haven_core::relay::maintenance::{
             build_kp_maintenance_events, build_kp_maintenance_events_reusing, KpMaintenanceAction,
         };
@@ -6436,7 +13176,7 @@ impl RelayManagerFfi {
             let mgr = circle_mgr.clone();
             move || {
                 mgr.latest_published_key_package()
-                    .map_err(|e| e.to_string())
+                    .map_err(HavenErrorFfi::from)
             }
         })
         .await?;
@@ -6454,34 +13194,62 @@ impl RelayManagerFfi {
         let events = if let Some(bytes) = reuse_bytes {
             // HEAL: re-advertise the cached last-resort package into the same slot.
             let d = existing_d.unwrap_or_default().to_owned();
-            build_kp_maintenance_events_reusing(keys, &bytes, targets, &d)
-                .map_err(|e| format!("build (reuse) key package events: {e}"))?
+            build_kp_maintenance_events_reusing(signer, &bytes, targets, &d).map_err(|e| {
+                HavenErrorFfi::internal(format!("build (reuse) key package events: {e}"))
+            })?
         } else {
             // MINT: a fresh last-resort package into `existing_d` (or a new slot).
             // Reuses the single process-global session (Rule 14) via the manager.
-            build_kp_maintenance_events(circle_mgr.session(), keys, targets, existing_d)
+            build_kp_maintenance_events(circle_mgr.session(), signer, targets, existing_d)
                 .await
-                .map_err(|e| format!("build (mint) key package events: {e}"))?
+                .map_err(|e| {
+                    HavenErrorFfi::internal(format!("build (mint) key package events: {e}"))
+                })?
         };
 
-        let event_id = events.event.id.to_hex();
         let d_tag = events.d_tag.clone();
         let kp_bytes = events.key_package.bytes().to_vec();
 
+        // Order the targets by their cached NIP-11 documents, dropping relays
+        // that advertise they cannot take a package this size (or want
+        // payment / auth). Unknown relays stay in, scored neutrally.
+        let needs = haven_core::relay::PublishNeeds::of(&events.event);
+        let infos = run_blocking({
+            let mgr = circle_mgr.clone();
+            let targets = targets.to_vec();
+            move || {
+                let now = i64::try_from(nostr::Timestamp::now().as_secs()).unwrap_or(0);
+                mgr.fresh_relay_infos(&targets, now)
+                    .map_err(HavenErrorFfi::from)
+            }
+        })
+        .await
+        .unwrap_or_default();
+        let targets = haven_core::relay::rank_relays(targets, &infos, needs);
+
         // Publish-first to the TARGET relays only (targets ⊆ configured ⊆ own).
-        let published = match self.inner.publish_event(&events.event, targets).await {
-            Ok(_) => true,
+        // Identity-signed, so a relay demanding NIP-13 work gets it (unless
+        // a remote signer signed it); the tracked id is the one actually
+        // accepted (it changes once mined).
+        let pow = signer.pow_policy();
+        let published = match self
+            .inner
+            .publish_event_with_pow(&events.event, &targets, pow.as_ref())
+            .await
+        {
+            Ok(result) => Some(result.event_id.to_hex()),
             Err(e) => {
                 *relay_errors += 1;
                 log::debug!(
                     "[maintain_key_package] 30443 publish failed: {}",
                     haven_core::nostr::mls::redact_hex_sequences(&e.to_string())
                 );
-                false
+                None
             }
         };
 
-        if published {
+        let publish_ok = published.is_some();
+        if let Some(event_id) = published {
             // Record the single-row-per-slot tracking row (drops the prior row).
             let now = i64::try_from(nostr::Timestamp::now().as_secs()).unwrap_or(0);
             let record = run_blocking({
@@ -6493,7 +13261,7 @@ impl RelayManagerFfi {
                         key_package: kp_bytes,
                         created_at: now,
                     })
-                    .map_err(|e| e.to_string())
+                    .map_err(HavenErrorFfi::from)
                 }
             })
             .await;
@@ -6537,7 +13305,7 @@ impl RelayManagerFfi {
             KpMaintenanceAction::RepublishedStableD
         };
         // `relays_healed` = the target count only when the write succeeded.
-        let healed = if published { targets.len() } else { 0 };
+        let healed = if publish_ok { targets.len() } else { 0 };
         Ok((action, healed))
     }
 
@@ -6566,13 +13334,13 @@ impl RelayManagerFfi {
     pub async fn retract_legacy_key_material(
         &self,
         circle: &CircleManagerFfi,
-        identity_secret_bytes: Vec<u8>,
-    ) -> Result<LegacyRetractionOutcomeFfi, String> {
+        secret: &SecretHandle,
+    ) -> Result<LegacyRetractionOutcomeFfi, HavenErrorFfi> {
         use haven_core::relay::maintenance::{
             build_key_package_relay_list_retraction, build_legacy_key_package_retraction,
         };
 
-        let keys = keys_from_secret_bytes(identity_secret_bytes)?;
+        let keys = secret.keys()?;
         let own_pk = keys.public_key();
         let own_hex = own_pk.to_hex();
         let circle_mgr = circle.inner.clone();
@@ -6580,7 +13348,7 @@ impl RelayManagerFfi {
         // Sentinel gate: fire at most once.
         let done = run_blocking({
             let mgr = circle_mgr.clone();
-            move || mgr.legacy_kp_retraction_done().map_err(|e| e.to_string())
+            move || mgr.legacy_kp_retraction_done().map_err(HavenErrorFfi::from)
         })
         .await?;
         if done {
@@ -6599,7 +13367,7 @@ impl RelayManagerFfi {
             move || {
                 let user = mgr
                     .list_user_relays(haven_core::circle::RelayType::KeyPackage)
-                    .map_err(|e| e.to_string())?;
+                    .map_err(HavenErrorFfi::from)?;
                 Ok::<Vec<String>, String>(haven_core::relay::dedup_relay_targets(&user))
             }
         })
@@ -6714,7 +13482,7 @@ impl RelayManagerFfi {
                 let mgr = circle_mgr.clone();
                 move || {
                     mgr.mark_legacy_kp_retraction_done()
-                        .map_err(|e| e.to_string())
+                        .map_err(HavenErrorFfi::from)
                 }
             })
             .await;
@@ -6730,8 +13498,8 @@ impl RelayManagerFfi {
     /// user's kind 10050 (inbox) + 10051 (`KeyPackage`) relay lists, honoring
     /// the per-category privacy toggle.
     ///
-    /// Dart-timer-driven; the secret bytes are consumed per-call and zeroized
-    /// (Security Rule 9). Fail-soft + idempotent.
+    /// Dart-timer-driven; the identity comes in as a [`SecretHandle`] and the
+    /// derived keys live for this call only. Fail-soft + idempotent.
     ///
     /// Per category: reads the user's OWN configured relays, NETWORK-PROBES the
     /// user's OWN relays for a currently-reachable list (never a local-timestamp
@@ -6748,43 +13516,125 @@ impl RelayManagerFfi {
     pub async fn maintain_relay_list(
         &self,
         circle: &CircleManagerFfi,
-        identity_secret_bytes: Vec<u8>,
-    ) -> Result<RelayListMaintenanceOutcomeFfi, String> {
+        secret: &SecretHandle,
+    ) -> Result<RelayListMaintenanceOutcomeFfi, HavenErrorFfi> {
         use haven_core::relay::maintenance::RelayListMaintenanceOutcome;
 
-        let (keys, own_pk) = {
-            let identity_secret_bytes = zeroize::Zeroizing::new(identity_secret_bytes);
-            if identity_secret_bytes.len() != 32 {
-                return Err("Invalid secret bytes length".to_string());
+        let signer = IdentitySigner::resolve(|| secret.keys())?;
+        let own_pk = signer.public_key();
+
+        let circle_mgr = circle.inner.clone();
+        let inbox = self
+            .maintain_relay_list_category(
+                &circle_mgr,
+                &signer,
+                &own_pk,
+                haven_core::circle::RelayType::Inbox,
+            )
+            .await;
+        let key_package = self
+            .maintain_relay_list_category(
+                &circle_mgr,
+                &signer,
+                &own_pk,
+                haven_core::circle::RelayType::KeyPackage,
+            )
+            .await;
+
+        Ok(RelayListMaintenanceOutcomeFfi::from(
+            RelayListMaintenanceOutcome { inbox, key_package },
+        ))
+    }
+
+    /// Publishes the relay lists whose edits have settled (debounced; see
+    /// [`haven_core::relay::relay_list_publisher`]).
+    ///
+    /// Relay-preference edits made through [`CircleManagerFfi`] are coalesced
+    /// in core; Dart calls this at
+    /// [`CircleManagerFfi::next_relay_list_publish_at`] (or on any later
+    /// tick). Each due category is signed once and published to the union of
+    /// the relays it was last published to and the current ones, then
+    /// recorded. A failed sign or publish requeues the category. Secret
+    /// bytes are zeroized after use; a paired remote signer signs instead.
+    pub async fn flush_relay_list_republishes(
+        &self,
+        circle: &CircleManagerFfi,
+        secret: &SecretHandle,
+    ) -> Result<RelayListFlushFfi, HavenErrorFfi> {
+        let signer = IdentitySigner::resolve(|| secret.keys())?;
+        let own_pk = signer.public_key();
+        let circle_mgr = circle.inner.clone();
+        let now = i64::try_from(nostr::Timestamp::now().as_secs()).unwrap_or(i64::MAX);
+
+        let plans = run_blocking({
+            let mgr = circle_mgr.clone();
+            move || {
+                mgr.take_due_relay_list_republishes(now)
+                    .map_err(HavenErrorFfi::from)
+            }
+        })
+        .await?;
+
+        let mut published: u32 = 0;
+        let mut failed: u32 = 0;
+        let pow = signer.pow_policy();
+        for plan in plans {
+            let relay_type = plan.relay_type;
+            let kind_u16 = plan.wire_kind().as_u16();
+            let last_published_at = {
+                let mgr = circle_mgr.clone();
+                run_blocking(move || {
+                    mgr.last_published_event(kind_u16, "", &own_pk)
+                        .map_err(HavenErrorFfi::from)
+                })
+                .await
+                .ok()
+                .flatten()
+                .map(|r| r.published_at)
+            };
+            let sent = match plan.sign(&signer, last_published_at) {
+                Ok(event) => self
+                    .inner
+                    .publish_event_with_pow(&event, &plan.targets, pow.as_ref())
+                    .await
+                    .map(|result| {
+                        (
+                            result.event_id,
+                            i64::try_from(event.created_at.as_secs()).unwrap_or(0),
+                        )
+                    })
+                    .map_err(|e| e.to_string()),
+                Err(e) => Err(e.to_string()),
+            };
+            match sent {
+                Ok((event_id, created_at)) => {
+                    published += 1;
+                    let mgr = circle_mgr.clone();
+                    let relays = plan.relays;
+                    let _ = run_blocking(move || {
+                        mgr.record_relay_list_published(
+                            relay_type, &relays, &event_id, &own_pk, created_at,
+                        )
+                        .map_err(HavenErrorFfi::from)
+                    })
+                    .await;
+                }
+                Err(e) => {
+                    failed += 1;
+                    log::debug!(
+                        "[flush_relay_list_republishes] publish failed: {}",
+                        haven_core::nostr::mls::redact_hex_sequences(&e)
+                    );
+                    circle_mgr.requeue_relay_list_republish(relay_type, now);
+                }
             }
-            let secret_key = nostr::SecretKey::from_slice(&identity_secret_bytes)
-                .map_err(|e| format!("Invalid secret key: {e}"))?;
-            let keys = nostr::Keys::new(secret_key);
-            let pk = keys.public_key();
-            (keys, pk)
-        };
-
-        let circle_mgr = circle.inner.clone();
-        let inbox = self
-            .maintain_relay_list_category(
-                &circle_mgr,
-                &keys,
-                &own_pk,
-                haven_core::circle::RelayType::Inbox,
-            )
-            .await;
-        let key_package = self
-            .maintain_relay_list_category(
-                &circle_mgr,
-                &keys,
-                &own_pk,
-                haven_core::circle::RelayType::KeyPackage,
-            )
-            .await;
+        }
 
-        Ok(RelayListMaintenanceOutcomeFfi::from(
-            RelayListMaintenanceOutcome { inbox, key_package },
-        ))
+        Ok(RelayListFlushFfi {
+            published,
+            failed,
+            next_due_at: circle_mgr.next_relay_list_publish_at(),
+        })
     }
 
     /// Runs [`Self::maintain_relay_list`] for one category. Fail-soft: any
@@ -6792,7 +13642,7 @@ impl RelayManagerFfi {
     async fn maintain_relay_list_category(
         &self,
         circle_mgr: &Arc<CoreCircleManager>,
-        keys: &nostr::Keys,
+        signer: &IdentitySigner,
         own_pk: &nostr::PublicKey,
         relay_type: haven_core::circle::RelayType,
     ) -> haven_core::relay::maintenance::RelayListCategoryOutcome {
@@ -6804,17 +13654,17 @@ impl RelayManagerFfi {
         let mut relay_errors: usize = 0;
 
         // Toggle + configured relays (own-relays-only, dedup'd).
-        let prep: Result<(bool, Vec<String>), String> = run_blocking({
+        let prep: Result<(bool, Vec<String>), HavenErrorFfi> = run_blocking({
             let mgr = circle_mgr.clone();
             move || {
                 let enabled = match relay_type {
                     haven_core::circle::RelayType::Inbox => mgr.get_publish_inbox_relay_list(),
                     haven_core::circle::RelayType::KeyPackage => mgr.get_publish_kp_relay_list(),
                 }
-                .map_err(|e| e.to_string())?;
+                .map_err(HavenErrorFfi::from)?;
                 let user = mgr
                     .list_user_relays(relay_type)
-                    .map_err(|e| e.to_string())?;
+                    .map_err(HavenErrorFfi::from)?;
                 Ok((enabled, haven_core::relay::dedup_relay_targets(&user)))
             }
         })
@@ -6909,7 +13759,7 @@ impl RelayManagerFfi {
                     let pk = own_pk.to_owned();
                     run_blocking(move || {
                         cm.last_published_event(kind_u16, "", &pk)
-                            .map_err(|e| e.to_string())
+                            .map_err(HavenErrorFfi::from)
                     })
                     .await
                     .ok()
@@ -6921,7 +13771,7 @@ impl RelayManagerFfi {
                 // publish TARGET is the responded-and-unhealthy subset.
                 let event = match build_relay_list_event_for(
                     ffi_type,
-                    keys,
+                    signer,
                     &configured,
                     Some(haven_core::relay::superseding_created_at(last_published_at)),
                 ) {
@@ -6935,19 +13785,30 @@ impl RelayManagerFfi {
                         };
                     }
                 };
-                let event_id = event.id;
                 let created_at = i64::try_from(event.created_at.as_secs()).unwrap_or(0);
+                let pow = signer.pow_policy();
 
-                match self.inner.publish_event(&event, &targets).await {
-                    Ok(_) => {
+                match self
+                    .inner
+                    .publish_event_with_pow(&event, &targets, pow.as_ref())
+                    .await
+                {
+                    Ok(result) => {
+                        let event_id = result.event_id;
                         let pk = *own_pk;
                         // Single per-kind published_events row (NOT per-relay):
                         // the replaceable list is one addressable event.
                         let rec = run_blocking({
                             let mgr = circle_mgr.clone();
                             move || {
-                                mgr.record_published_event(kind_u16, "", &event_id, &pk, created_at)
-                                    .map_err(|e| e.to_string())
+                                mgr.record_relay_list_published(
+                                    relay_type,
+                                    &configured,
+                                    &event_id,
+                                    &pk,
+                                    created_at,
+                                )
+                                .map_err(HavenErrorFfi::from)
                             }
                         })
                         .await;
@@ -6995,12 +13856,106 @@ impl RelayManagerFfi {
         relays: Vec<String>,
         since: Option<i64>,
         limit: Option<u32>,
-    ) -> Result<Vec<String>, String> {
+    ) -> Result<Vec<String>, HavenErrorFfi> {
+        let events = self
+            .fetch_group_message_events(&nostr_group_id, &relays, since, limit)
+            .await?;
+
+        events
+            .into_iter()
+            .map(|e| {
+                serde_json::to_string(&e).map_err(|err| {
+                    HavenErrorFfi::internal(format!("Failed to serialize event: {err}"))
+                })
+            })
+            .collect::<Result<Vec<_>, _>>()
+    }
+
+    /// Same query as [`fetch_group_messages`](Self::fetch_group_messages), but
+    /// returns [`CompactEventFfi`]s instead of JSON strings.
+    ///
+    /// Each event's fields are moved (not re-serialized) into the result, so a
+    /// large catch-up batch is held once rather than twice. Pass the results to
+    /// `CircleManagerFfi::decrypt_location_compact`.
+    pub async fn fetch_group_messages_compact(
+        &self,
+        nostr_group_id: Vec<u8>,
+        relays: Vec<String>,
+        since: Option<i64>,
+        limit: Option<u32>,
+    ) -> Result<Vec<CompactEventFfi>, HavenErrorFfi> {
+        let events = self
+            .fetch_group_message_events(&nostr_group_id, &relays, since, limit)
+            .await?;
+        Ok(events
+            .into_iter()
+            .map(|e| CompactEventFfi::from(haven_core::relay::CompactEvent::from_event(e)))
+            .collect())
+    }
+
+    /// Fetches one page of group messages (kind 445), walking backwards.
+    ///
+    /// Returns up to `limit` events per relay created at or before `until`
+    /// (now if `None`) and at or after `since`. Pass the returned
+    /// `next_until` as the next `until` until it is `None`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the group id is not 32 bytes or `limit` is zero.
+    pub async fn fetch_group_messages_paged(
+        &self,
+        nostr_group_id: Vec<u8>,
+        relays: Vec<String>,
+        until: Option<i64>,
+        since: Option<i64>,
+        limit: u32,
+    ) -> Result<GroupMessagePageFfi, HavenErrorFfi> {
+        let ngid: [u8; 32] = nostr_group_id.as_slice().try_into().map_err(|_| {
+            HavenErrorFfi::invalid_input(format!(
+                "Invalid nostr_group_id length: expected 32, got {}",
+                nostr_group_id.len()
+            ))
+        })?;
+        let page = self
+            .inner
+            .fetch_group_messages_paged(
+                &ngid,
+                &relays,
+                until,
+                since,
+                usize::try_from(limit).unwrap_or(usize::MAX),
+            )
+            .await
+            .map_err(HavenErrorFfi::from)?;
+        let events_json = page
+            .events
+            .iter()
+            .map(|e| {
+                serde_json::to_string(e).map_err(|err| {
+                    HavenErrorFfi::internal(format!("Failed to serialize event: {err}"))
+                })
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(GroupMessagePageFfi {
+            events_json,
+            next_until: page.next_until,
+            relays_polled: u32::try_from(page.relays_polled).unwrap_or(u32::MAX),
+            relays_responded: u32::try_from(page.relays_responded).unwrap_or(u32::MAX),
+        })
+    }
+
+    async fn fetch_group_message_events(
+        &self,
+        nostr_group_id: &[u8],
+        relays: &[String],
+        since: Option<i64>,
+        limit: Option<u32>,
+    ) -> Result<Vec<nostr::Event>, HavenErrorFfi> {
         if nostr_group_id.len() != 32 {
-            return Err(format!(
+            return Err(HavenErrorFfi::invalid_input(format!(
                 "Invalid nostr_group_id length: expected 32, got {}",
                 nostr_group_id.len()
-            ));
+            )));
         }
 
         let group_id_hex: String = nostr_group_id.iter().map(|b| format!("{b:02x}")).collect();
@@ -7021,18 +13976,10 @@ impl RelayManagerFfi {
             filter = filter.limit(lim as usize);
         }
 
-        let events = self
-            .inner
-            .fetch_events(filter, &relays, None)
+        self.inner
+            .fetch_events(filter, relays, None)
             .await
-            .map_err(|e| e.to_string())?;
-
-        events
-            .into_iter()
-            .map(|e| {
-                serde_json::to_string(&e).map_err(|err| format!("Failed to serialize event: {err}"))
-            })
-            .collect::<Result<Vec<_>, _>>()
+            .map_err(HavenErrorFfi::from)
     }
 }
 
@@ -7076,16 +14023,122 @@ mod tests {
         assert!(parsed.timestamp > 0 && parsed.expires_at > parsed.timestamp);
     }
 
+    #[test]
+    fn parse_engine_location_surfaces_device_status() {
+        let json = r#"{"latitude":1.5,"longitude":2.5,"geohash":"u4pruyd","timestamp":"2026-06-30T12:00:00Z","expires_at":"2026-06-30T12:15:00Z","device_status":{"battery_percent":12,"charging":false,"motion":"driving"}}"#;
+        let parsed = parse_engine_location(json.to_string(), "ab".repeat(32)).expect("parse");
+        assert_eq!(
+            parsed.device_status,
+            Some(DeviceStatusFfi {
+                battery_percent: Some(12),
+                charging: Some(false),
+                motion: Some(MotionStateFfi::Driving),
+            })
+        );
+    }
+
+    #[test]
+    fn parse_engine_location_surfaces_vertical_position() {
+        let json = r#"{"latitude":1.5,"longitude":2.5,"geohash":"u4pruyd","timestamp":"2026-06-30T12:00:00Z","expires_at":"2026-06-30T12:15:00Z","vertical_position":{"altitude_m":31.6,"floor":4}}"#;
+        let parsed = parse_engine_location(json.to_string(), "ab".repeat(32)).expect("parse");
+        assert_eq!(
+            parsed.vertical_position,
+            Some(VerticalPositionFfi {
+                altitude_m: Some(32.0),
+                floor: Some(4),
+            })
+        );
+    }
+
     #[test]
     fn parse_engine_location_rejects_invalid_content() {
         assert!(parse_engine_location("not json".to_string(), "ab".repeat(32)).is_err());
     }
 
     #[test]
-    fn hex_to_npub_matches_known_vector() {
+    fn decode_haven_payload_reads_envelopes_and_legacy_content() {
+        let sender = "ab".repeat(32);
+        let enveloped = r#"{"v":1,"type":"status","battery_percent":150,"charging":true}"#;
+        let payload =
+            decode_haven_payload(enveloped.to_string(), None, sender.clone()).expect("decode");
+        assert_eq!(payload.kind, HavenPayloadKindFfi::Status);
+        assert_eq!(payload.schema_version, 1);
+        assert_eq!(payload.device_status.unwrap().battery_percent, Some(100));
+
+        let legacy = r#"{"latitude":1.5,"longitude":2.5,"geohash":"s00","timestamp":"2026-06-30T12:00:00Z","expires_at":"2026-06-30T12:15:00Z","message":"help"}"#;
+        let payload =
+            decode_haven_payload(legacy.to_string(), Some("sos".to_string()), sender.clone())
+                .expect("decode");
+        assert_eq!(payload.kind, HavenPayloadKindFfi::Sos);
+        assert_eq!(payload.schema_version, 0);
+        assert_eq!(payload.sos_message.as_deref(), Some("help"));
+        assert!(payload.location.is_some());
+
+        let future = r#"{"v":2,"type":"hologram"}"#;
+        let payload = decode_haven_payload(future.to_string(), None, sender).expect("decode");
+        assert_eq!(payload.kind, HavenPayloadKindFfi::Unknown);
+    }
+
+    #[test]
+    fn core_errors_map_to_localizable_messages() {
+        let err = HavenErrorFfi::from(haven_core::relay::RelayError::Rejected {
+            relay: "wss://relay.example.com".to_string(),
+            reason: "pow: 20".to_string(),
+        });
+        assert_eq!(err.message.code, "relay.rejected");
+        assert_eq!(
+            err.message.params,
+            vec![
+                MessageParamFfi {
+                    name: "relay".to_string(),
+                    value: "wss://relay.example.com".to_string(),
+                },
+                MessageParamFfi {
+                    name: "reason".to_string(),
+                    value: "pow: 20".to_string(),
+                },
+            ]
+        );
+        assert_eq!(
+            err.to_string(),
+            "Relay wss://relay.example.com rejected event: pow: 20"
+        );
+
+        // Inner detail stays in the log text, out of the user message.
+        let err = HavenErrorFfi::from(haven_core::circle::CircleError::Mls(
+            "epoch 7 mismatch".to_string(),
+        ));
+        assert_eq!(err.message.code, "circle.group_error");
+        assert!(err.message.params.is_empty());
+        assert!(!err.message.english.contains("epoch"));
+        assert!(err.detail.contains("epoch 7 mismatch"));
+    }
+
+    #[test]
+    fn plain_string_errors_are_invalid_input() {
+        let err: HavenErrorFfi = validate_pubkey_hex("zz", "pubkey").unwrap_err().into();
+        assert_eq!(err.message.code, "invalid_input");
+        assert!(SecretHandle::from_slice(&[0; 3])
+            .is_err_and(|e| e.message.code == "identity.invalid_key"));
+    }
+
+    #[test]
+    fn redact_profile_err_scrubs_params_and_detail() {
+        let hex = "ab".repeat(32);
+        let err = redact_profile_err(haven_core::relay::RelayError::Rejected {
+            relay: "wss://relay.example.com".to_string(),
+            reason: format!("duplicate: {hex}"),
+        });
+        assert!(!err.detail.contains(&hex));
+        assert!(!err.message.english.contains(&hex));
+        assert!(err.message.params.iter().all(|p| !p.value.contains(&hex)));
+    }
+
+    #[test]
+    fn npub_or_hex_matches_known_vector() {
         // Canonical NIP-19 spec public key -> npub test vector (fixed, no rng).
         let hex = "7e7e9c42a91bfef19fa929e5fda1b72e0ebc1a4c1141673e2794234d86addf4e";
-        let npub = hex_to_npub(hex);
+        let npub = npub_or_hex(hex);
         assert!(npub.starts_with("npub1"));
         assert_eq!(
             npub,
@@ -7094,10 +14147,10 @@ mod tests {
     }
 
     #[test]
-    fn hex_to_npub_falls_back_to_hex_on_invalid_input() {
+    fn npub_or_hex_falls_back_to_hex_on_invalid_input() {
         // Not valid hex/npub -> UI still gets a non-empty identifier.
         let bogus = "not-a-pubkey";
-        assert_eq!(hex_to_npub(bogus), bogus);
+        assert_eq!(npub_or_hex(bogus), bogus);
     }
 
     #[test]
@@ -7160,6 +14213,26 @@ mod tests {
         assert!(dbg.contains("is_admin: true"), "debug output: {dbg}");
     }
 
+    #[test]
+    fn secret_handle_signs_as_the_identity_without_exposing_it() {
+        let identity = NostrIdentityManager::new();
+        let public = identity.create_identity().unwrap();
+        let handle = identity.secret_handle().unwrap();
+        assert_eq!(handle.pubkey_hex().unwrap(), public.pubkey_hex);
+
+        let secret_hex = hex::encode(identity.get_secret_bytes().unwrap());
+        assert!(!format!("{handle:?}").contains(&secret_hex));
+
+        let event: nostr::Event = serde_json::from_str(
+            &sign_deletion(&handle.keys().unwrap(), &["ab".repeat(32)]).unwrap(),
+        )
+        .unwrap();
+        assert_eq!(event.pubkey.to_hex(), public.pubkey_hex);
+
+        assert!(SecretHandle::from_slice(&[0; 31]).is_err());
+        assert!(SecretHandle::from_slice(&[0; 32]).is_err());
+    }
+
     /// Verifies that `init_keyring_store()` succeeds when a keyring backend
     /// is available.
     #[test]
@@ -7412,15 +14485,13 @@ mod tests {
             .expect("wipe_all_mls_state must not error");
     }
 
-    /// A generic 32-byte identity secret for constructing a real
-    /// `CircleManagerFfi` under test. The Dark Matter session binds the device
-    /// identity as a HARD construction requirement (DM-4), so `new` now demands
-    /// the secret bytes; the keys never touch a real network here.
-    fn wipe_test_secret_bytes() -> Vec<u8> {
-        nostr::Keys::generate()
-            .secret_key()
-            .to_secret_bytes()
-            .to_vec()
+    /// A generic identity for constructing a real `CircleManagerFfi` under
+    /// test. The Dark Matter session binds the device identity as a HARD
+    /// construction requirement (DM-4), so `new` now demands a secret handle;
+    /// the keys never touch a real network here.
+    fn wipe_test_secret() -> SecretHandle {
+        SecretHandle::from_slice(&nostr::Keys::generate().secret_key().to_secret_bytes())
+            .expect("generated key is valid")
     }
 
     /// Drives one full M10 wipe end-to-end against a REAL `CircleManagerFfi`:
@@ -7454,7 +14525,7 @@ mod tests {
 
         // --- 1. Create a REAL manager: this mints both DB files + both keys. ---
         {
-            let manager = CircleManagerFfi::new(data_dir.clone(), wipe_test_secret_bytes())
+            let manager = CircleManagerFfi::new(data_dir.clone(), &wipe_test_secret())
                 .expect("CircleManagerFfi::new must create real MLS + circle DBs");
             // Hold the handle only long enough to prove the files/keys landed,
             // then drop it so both SQLite connections close before the wipe.
@@ -7599,7 +14670,9 @@ mod tests {
             "a genuine (non-NotFound) delete failure must be surfaced, not swallowed"
         );
         // Opaque: the surfaced error must not leak the path / filename (Security).
-        let msg = result.unwrap_err();
+        let err = result.unwrap_err();
+        assert_eq!(err.message.code, "storage");
+        let msg = err.to_string();
         assert!(
             !msg.contains(CIRCLES_DB_FILENAME) && !msg.to_lowercase().contains("circles"),
             "wipe error must be generic/opaque (no path or filename leak)"
@@ -7705,6 +14778,7 @@ mod tests {
             (
                 R::GroupUpdate {
                     group_id: gid.clone(),
+                    membership: haven_core::nostr::mls::MembershipDelta::default(),
                 },
                 LocationMessageResultKindFfi::GroupUpdate,
             ),
@@ -7734,6 +14808,92 @@ mod tests {
         }
     }
 
+    /// An SOS result folds to `Sos`, parses the flattened location, and
+    /// surfaces the sanitized note.
+    #[test]
+    fn convert_sos_variant_surfaces_location_and_note() {
+        use haven_core::nostr::mls::types::{GroupId, LocationMessageResult as R};
+        let sos = haven_core::emergency::SosMessage::new(
+            &haven_core::location::LocationMessage::new(37.7749, -122.4194),
+            Some("help"),
+        );
+        let outcome = convert_location_result(R::Sos {
+            sender_pubkey: "ABCDEF0123".to_string(),
+            content: sos.to_string().unwrap(),
+            group_id: GroupId::from_slice(&[3]),
+            epoch: 5,
+        });
+        assert_eq!(outcome.kind, LocationMessageResultKindFfi::Sos);
+        assert_eq!(outcome.epoch, 5);
+        assert_eq!(outcome.sos_message.as_deref(), Some("help"));
+        let loc = outcome.location.expect("location present");
+        assert_eq!(loc.sender_pubkey, "abcdef0123");
+        assert!((loc.latitude - 37.7749).abs() < 1e-9);
+    }
+
+    /// A meet pin folds to `MeetPin` with its exact point and no location.
+    #[test]
+    fn convert_meet_pin_variant_carries_the_pin() {
+        use haven_core::nostr::mls::types::{GroupId, LocationMessageResult as R};
+        let pin = haven_core::meet::MeetPin::new(37.7749, -122.4194, Some("gate B"), 600, 0)
+            .expect("pin");
+        let outcome = convert_location_result(R::MeetPin {
+            sender_pubkey: "ABCDEF0123".to_string(),
+            content: pin.to_string().unwrap(),
+            group_id: GroupId::from_slice(&[3]),
+            epoch: 6,
+        });
+        assert_eq!(outcome.kind, LocationMessageResultKindFfi::MeetPin);
+        assert!(
+            outcome.location.is_none(),
+            "a pin is not the sender's position"
+        );
+        let ffi = outcome.meet_pin.expect("pin present");
+        assert_eq!(ffi.sender_pubkey, "abcdef0123");
+        assert_eq!(ffi.pin_id, pin.pin_id);
+        assert_eq!(ffi.label.as_deref(), Some("gate B"));
+        assert!((ffi.latitude - 37.7749).abs() < 1e-9);
+    }
+
+    /// A check-in request folds to `CheckinRequest` with both pubkeys
+    /// normalized and no location.
+    #[test]
+    fn convert_checkin_request_variant_carries_pubkeys() {
+        use haven_core::nostr::mls::types::{GroupId, LocationMessageResult as R};
+        let outcome = convert_location_result(R::CheckinRequest {
+            sender_pubkey: "ABCDEF".to_string(),
+            target_pubkey: "0123AB".to_string(),
+            group_id: GroupId::from_slice(&[3]),
+            epoch: 2,
+        });
+        assert_eq!(outcome.kind, LocationMessageResultKindFfi::CheckinRequest);
+        assert!(outcome.location.is_none());
+        assert_eq!(outcome.epoch, 2);
+        assert_eq!(outcome.checkin_requester_pubkey.as_deref(), Some("abcdef"));
+        assert_eq!(outcome.checkin_target_pubkey.as_deref(), Some("0123ab"));
+    }
+
+    /// A precision anomaly folds to `PrecisionAnomaly` with both classes and
+    /// a normalized, Debug-redacted sender.
+    #[test]
+    fn convert_precision_anomaly_variant_carries_classes() {
+        use haven_core::location::PrecisionClass;
+        use haven_core::nostr::mls::types::{GroupId, LocationMessageResult as R};
+        let outcome = convert_location_result(R::PrecisionAnomaly {
+            sender_pubkey: "ABCDEF".to_string(),
+            group_id: GroupId::from_slice(&[3]),
+            baseline: PrecisionClass::Coarse,
+            observed: PrecisionClass::Exact,
+        });
+        assert_eq!(outcome.kind, LocationMessageResultKindFfi::PrecisionAnomaly);
+        assert!(outcome.location.is_none());
+        let anomaly = outcome.precision_anomaly.expect("anomaly present");
+        assert_eq!(anomaly.sender_pubkey, "abcdef");
+        assert_eq!(anomaly.baseline, PrecisionClassFfi::Coarse);
+        assert_eq!(anomaly.observed, PrecisionClassFfi::Exact);
+        assert!(!format!("{anomaly:?}").contains("abcdef"));
+    }
+
     /// Security Rule 4/8: `LocationMessageResultFfi`'s `Debug` must redact the
     /// raw MLS group id and the decrypted location, exposing only presence + the
     /// non-secret epoch counter. FFI debug lines routinely surface via
@@ -7848,6 +15008,70 @@ mod tests {
         assert_eq!(event_secs_to_cursor_ms(-5), -5000);
         assert_eq!(event_secs_to_cursor_ms(i64::MIN), i64::MIN, "saturates low");
     }
+
+    /// A [`CallbackSigner`] whose "Dart" side signs with `keys` and refuses
+    /// every NIP-44 request.
+    fn callback_signer(keys: &nostr::Keys) -> CallbackSigner {
+        let signing = keys.clone();
+        CallbackSigner {
+            public_key: keys.public_key(),
+            sign_event_hash: Arc::new(move |hash: Vec<u8>| {
+                let keys = signing.clone();
+                Box::pin(async move {
+                    let hash: [u8; 32] = hash.try_into().expect("32-byte event id");
+                    Signer::sign_event_hash(&keys, &hash)
+                        .expect("sign")
+                        .to_string()
+                }) as DartFnFuture<String>
+            }),
+            nip44_encrypt: Arc::new(|_: String, _: String| {
+                Box::pin(async { String::new() }) as DartFnFuture<String>
+            }),
+        }
+    }
+
+    /// The callback wait must not panic when signing runs on an async worker
+    /// (the key package and gift-wrap paths sign outside `run_blocking`).
+    #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+    async fn callback_signer_signs_from_an_async_task() {
+        let keys = nostr::Keys::generate();
+        let signer = IdentitySigner::External(callback_signer(&keys));
+        let task_signer = signer.clone();
+        let event = tokio::spawn(async move {
+            haven_core::nostr::identity::sign_event(
+                nostr::EventBuilder::text_note("external"),
+                &task_signer,
+            )
+        })
+        .await
+        .expect("signing task must not panic")
+        .expect("externally signed event");
+        assert_eq!(event.pubkey, keys.public_key());
+        event.verify().expect("signature verifies");
+        assert!(
+            signer.pow_policy().is_none(),
+            "external events are not mined"
+        );
+    }
+
+    /// On a current-thread runtime the wait cannot park, so signing fails
+    /// with an error instead of panicking.
+    #[tokio::test]
+    async fn callback_signer_errors_on_a_current_thread_runtime() {
+        let keys = nostr::Keys::generate();
+        let signer = callback_signer(&keys);
+        let err = Signer::sign_event_hash(&signer, &[7u8; 32]).unwrap_err();
+        assert!(matches!(err, IdentityError::Signing(_)));
+    }
+
+    /// An empty callback answer is a refusal.
+    #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+    async fn callback_signer_empty_answer_is_a_refusal() {
+        let keys = nostr::Keys::generate();
+        let signer = callback_signer(&keys);
+        let err = Signer::nip44_encrypt(&signer, &keys.public_key(), "hi").unwrap_err();
+        assert!(matches!(err, IdentityError::Encryption(_)));
+    }
 }
 
 // ========================= M3c: Live-Sync Engine FFI =========================
@@ -7868,10 +15092,10 @@ use haven_core::relay::live_sync::{
 /// `None` is the SEND-path's "engine off" signal: the Dart caller falls back to
 /// the legacy eager finalize path. A poisoned lock surfaces as `Err`, never a
 /// panic across the FFI boundary.
-fn live_session_core() -> Result<Option<Arc<LiveSyncCore>>, String> {
+fn live_session_core() -> Result<Option<Arc<LiveSyncCore>>, HavenErrorFfi> {
     Ok(SESSION
         .read()
-        .map_err(|_| "session lock poisoned".to_string())?
+        .map_err(|_| HavenErrorFfi::internal("session lock poisoned"))?
         .as_ref()
         .map(Arc::clone))
 }
@@ -7930,6 +15154,15 @@ const fn sync_reason_to_ffi(reason: CoreSyncStatusReason) -> FfiSyncStatusReason
 pub enum FfiRelayEventKind {
     /// A decrypted location.
     Location,
+    /// A decrypted emergency (SOS) broadcast; `content` is `SosMessage` JSON.
+    Sos,
+    /// A decrypted check-in request from `sender_pubkey` to `target_pubkey`.
+    CheckinRequest,
+    /// A decrypted, still-live meet pin; `content` is `MeetPin` JSON.
+    MeetPin,
+    /// `sender_pubkey` started sharing finer locations; see
+    /// `precision_anomaly`.
+    PrecisionAnomaly,
     /// A group membership/epoch update.
     GroupUpdate,
     /// A raw gift-wrapped invitation (`kind:1059`); the consumer unwraps it.
@@ -7949,6 +15182,10 @@ pub struct FfiRelayEvent {
     pub nostr_group_id: Option<Vec<u8>>,
     /// Sender's hex Nostr public key (Location).
     pub sender_pubkey: Option<String>,
+    /// Hex Nostr public key of the member asked to check in (CheckinRequest).
+    pub target_pubkey: Option<String>,
+    /// Baseline and observed precision (PrecisionAnomaly).
+    pub precision_anomaly: Option<PrecisionAnomalyFfi>,
     /// Decrypted location content JSON (Location).
     pub content: Option<String>,
     /// Source event `created_at` seconds (Location).
@@ -7962,6 +15199,9 @@ pub struct FfiRelayEvent {
     pub wrap_created_at_secs: Option<i64>,
     /// Closed status reason (Status).
     pub status_reason: Option<FfiSyncStatusReason>,
+    /// Localizable notification text, for events that warrant one (Sos,
+    /// MeetPin, CheckinRequest, PrecisionAnomaly, Welcome).
+    pub notification: Option<UserMessageFfi>,
 }
 
 impl std::fmt::Debug for FfiRelayEvent {
@@ -7972,27 +15212,37 @@ impl std::fmt::Debug for FfiRelayEvent {
             .field("kind", &self.kind)
             .field("has_nostr_group_id", &self.nostr_group_id.is_some())
             .field("has_sender_pubkey", &self.sender_pubkey.is_some())
+            .field("has_target_pubkey", &self.target_pubkey.is_some())
+            .field("precision_anomaly", &self.precision_anomaly)
             .field("has_content", &self.content.is_some())
             .field("event_created_at_secs", &self.event_created_at_secs)
             .field("has_evolution_event", &self.evolution_event_json.is_some())
             .field("has_gift_wrap", &self.gift_wrap_json.is_some())
             .field("wrap_created_at_secs", &self.wrap_created_at_secs)
             .field("status_reason", &self.status_reason)
+            .field(
+                "notification",
+                &self.notification.as_ref().map(|n| n.code.as_str()),
+            )
             .finish()
     }
 }
 
 fn live_event_to_ffi(event: CoreLiveSyncEvent) -> FfiRelayEvent {
+    let notification = haven_core::messages::notification_for(&event).map(Into::into);
     let mut out = FfiRelayEvent {
         kind: FfiRelayEventKind::Status,
         nostr_group_id: None,
         sender_pubkey: None,
+        target_pubkey: None,
+        precision_anomaly: None,
         content: None,
         event_created_at_secs: None,
         evolution_event_json: None,
         gift_wrap_json: None,
         wrap_created_at_secs: None,
         status_reason: None,
+        notification,
     };
     match event {
         CoreLiveSyncEvent::Location {
@@ -8007,6 +15257,59 @@ fn live_event_to_ffi(event: CoreLiveSyncEvent) -> FfiRelayEvent {
             out.content = Some(content);
             out.event_created_at_secs = Some(event_created_at_secs);
         }
+        CoreLiveSyncEvent::Sos {
+            nostr_group_id,
+            sender_pubkey,
+            content,
+            event_created_at_secs,
+        } => {
+            out.kind = FfiRelayEventKind::Sos;
+            out.nostr_group_id = Some(nostr_group_id);
+            out.sender_pubkey = Some(sender_pubkey);
+            out.content = Some(content);
+            out.event_created_at_secs = Some(event_created_at_secs);
+        }
+        CoreLiveSyncEvent::MeetPin {
+            nostr_group_id,
+            sender_pubkey,
+            content,
+            event_created_at_secs,
+        } => {
+            out.kind = FfiRelayEventKind::MeetPin;
+            out.nostr_group_id = Some(nostr_group_id);
+            out.sender_pubkey = Some(sender_pubkey);
+            out.content = Some(content);
+            out.event_created_at_secs = Some(event_created_at_secs);
+        }
+        CoreLiveSyncEvent::CheckinRequest {
+            nostr_group_id,
+            sender_pubkey,
+            target_pubkey,
+            event_created_at_secs,
+        } => {
+            out.kind = FfiRelayEventKind::CheckinRequest;
+            out.nostr_group_id = Some(nostr_group_id);
+            out.sender_pubkey = Some(sender_pubkey);
+            out.target_pubkey = Some(target_pubkey);
+            out.event_created_at_secs = Some(event_created_at_secs);
+        }
+        CoreLiveSyncEvent::PrecisionAnomaly {
+            nostr_group_id,
+            sender_pubkey,
+            baseline,
+            observed,
+            event_created_at_secs,
+        } => {
+            out.kind = FfiRelayEventKind::PrecisionAnomaly;
+            out.nostr_group_id = Some(nostr_group_id);
+            out.precision_anomaly = Some(PrecisionAnomalyFfi {
+                sender_pubkey: normalize_pubkey_hex(&sender_pubkey),
+                baseline: baseline.into(),
+                observed: observed.into(),
+            });
+            out.sender_pubkey = Some(sender_pubkey);
+            out.event_created_at_secs = Some(event_created_at_secs);
+        }
         CoreLiveSyncEvent::GroupUpdate {
             nostr_group_id,
             evolution_event_json,
@@ -8055,10 +15358,13 @@ impl LiveSyncFfi {
     /// # Errors
     ///
     /// Returns an error if `own_pubkey_hex` is not a valid Nostr public key.
-    pub fn new_instance(circle: &CircleManagerFfi, own_pubkey_hex: String) -> Result<Self, String> {
+    pub fn new_instance(
+        circle: &CircleManagerFfi,
+        own_pubkey_hex: String,
+    ) -> Result<Self, HavenErrorFfi> {
         validate_pubkey_hex(&own_pubkey_hex, "own_pubkey")?;
         let pk = nostr::PublicKey::from_hex(&normalize_pubkey_hex(&own_pubkey_hex))
-            .map_err(|e| e.to_string())?;
+            .map_err(HavenErrorFfi::from)?;
         Ok(Self {
             circle: Arc::clone(&circle.inner),
             own_pubkey: pk,
@@ -8076,7 +15382,7 @@ impl LiveSyncFfi {
         &self,
         groups: Vec<FfiGroupSpec>,
         inbox_relays: Vec<String>,
-    ) -> Result<(), String> {
+    ) -> Result<(), HavenErrorFfi> {
         // Validate + map specs BEFORE reserving the session slot.
         let mut circles = Vec::with_capacity(groups.len());
         for g in groups {
@@ -8116,7 +15422,7 @@ impl LiveSyncFfi {
         let previous = {
             SESSION
                 .write()
-                .map_err(|_| "session lock poisoned".to_string())?
+                .map_err(|_| HavenErrorFfi::internal("session lock poisoned"))?
                 .take()
         };
         if let Some(previous) = previous {
@@ -8125,7 +15431,7 @@ impl LiveSyncFfi {
         {
             let mut guard = SESSION
                 .write()
-                .map_err(|_| "session lock poisoned".to_string())?;
+                .map_err(|_| HavenErrorFfi::internal("session lock poisoned"))?;
             *guard = Some(Arc::clone(&core));
         }
 
@@ -8150,7 +15456,7 @@ impl LiveSyncFfi {
                     *guard = None;
                 }
             }
-            return Err(e.to_string());
+            return Err(e.into());
         }
         Ok(())
     }
@@ -8160,10 +15466,10 @@ impl LiveSyncFfi {
     /// # Errors
     ///
     /// Returns an error only if the session lock is poisoned.
-    pub async fn stop_session(&self) -> Result<(), String> {
+    pub async fn stop_session(&self) -> Result<(), HavenErrorFfi> {
         let core = SESSION
             .write()
-            .map_err(|_| "session lock poisoned".to_string())?
+            .map_err(|_| HavenErrorFfi::internal("session lock poisoned"))?
             .take();
         if let Some(core) = core {
             core.stop().await;
@@ -8177,18 +15483,18 @@ impl LiveSyncFfi {
     ///
     /// Returns an error if there is no active session, the lock is poisoned, or
     /// a re-subscription fails.
-    pub async fn resume_after_background(&self) -> Result<(), String> {
+    pub async fn resume_after_background(&self) -> Result<(), HavenErrorFfi> {
         let core = SESSION
             .read()
-            .map_err(|_| "session lock poisoned".to_string())?
+            .map_err(|_| HavenErrorFfi::internal("session lock poisoned"))?
             .as_ref()
             .map(Arc::clone);
         match core {
             Some(core) => core
                 .resume_after_background()
                 .await
-                .map_err(|e| e.to_string()),
-            None => Err("no active live-sync session".to_string()),
+                .map_err(HavenErrorFfi::from),
+            None => Err(HavenErrorFfi::sync_not_running()),
         }
     }
 
@@ -8202,18 +15508,18 @@ impl LiveSyncFfi {
     /// Returns an error if `spec.nostr_group_id` is malformed, there is no active
     /// session, the lock is poisoned, a relay fails the WSS gate, or the
     /// subscription fails. The Dart caller falls back to a full restart on error.
-    pub async fn subscribe_circle(&self, spec: FfiGroupSpec) -> Result<(), String> {
+    pub async fn subscribe_circle(&self, spec: FfiGroupSpec) -> Result<(), HavenErrorFfi> {
         let id = parse_nostr_group_id(&spec.nostr_group_id)?;
         let circle = CoreCircleSpec {
             group_id_hex: hex::encode(id),
             relays: spec.relays,
         };
         let Some(core) = live_session_core()? else {
-            return Err("no active live-sync session".to_string());
+            return Err(HavenErrorFfi::sync_not_running());
         };
         core.subscribe_circle(&circle)
             .await
-            .map_err(|e| e.to_string())
+            .map_err(HavenErrorFfi::from)
     }
 
     /// Unsubscribes the running session from ONE circle (delta only), dropping
@@ -8224,7 +15530,7 @@ impl LiveSyncFfi {
     ///
     /// Returns an error if `nostr_group_id` is malformed, the lock is poisoned, or
     /// a multiplexed-bucket re-issue fails (the Dart caller then full-restarts).
-    pub async fn unsubscribe_circle(&self, nostr_group_id: Vec<u8>) -> Result<(), String> {
+    pub async fn unsubscribe_circle(&self, nostr_group_id: Vec<u8>) -> Result<(), HavenErrorFfi> {
         let id = parse_nostr_group_id(&nostr_group_id)?;
         let hex = hex::encode(id);
         let Some(core) = live_session_core()? else {
@@ -8232,7 +15538,7 @@ impl LiveSyncFfi {
         };
         core.unsubscribe_circle(&hex)
             .await
-            .map_err(|e| e.to_string())
+            .map_err(HavenErrorFfi::from)
     }
 
     /// Whether a live session is currently running.
@@ -8260,13 +15566,13 @@ impl LiveSyncFfi {
     pub async fn live_events(
         &self,
         sink: crate::frb_generated::StreamSink<FfiRelayEvent>,
-    ) -> Result<(), String> {
+    ) -> Result<(), HavenErrorFfi> {
         let core = SESSION
             .read()
-            .map_err(|_| "session lock poisoned".to_string())?
+            .map_err(|_| HavenErrorFfi::internal("session lock poisoned"))?
             .as_ref()
             .map(Arc::clone)
-            .ok_or_else(|| "no active live-sync session".to_string())?;
+            .ok_or_else(HavenErrorFfi::sync_not_running)?;
         let mut rx = core.bus().subscribe();
         // Drop our Arc so the bus closes promptly when stop_session takes the
         // session (otherwise this clone would keep the core — and its bus —
@@ -8285,6 +15591,64 @@ impl LiveSyncFfi {
         }
         Ok(())
     }
+
+    /// Streams the locations the live session decrypts for ONE circle, already
+    /// parsed, so a map widget can listen instead of polling `decrypt_location`
+    /// per fetched event. Everything else on the bus (SOS, status, other
+    /// circles, content that is not a location) is skipped; use
+    /// [`Self::live_events`] for those.
+    ///
+    /// Same lifetime as [`Self::live_events`]: it ends when Dart closes the sink
+    /// or the session stops, and a lag is skipped (catch-up replays it).
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `nostr_group_id` is malformed, there is no active
+    /// session, or the lock is poisoned.
+    pub async fn location_updates_stream(
+        &self,
+        nostr_group_id: Vec<u8>,
+        sink: crate::frb_generated::StreamSink<DecryptedLocationFfi>,
+    ) -> Result<(), HavenErrorFfi> {
+        let id = parse_nostr_group_id(&nostr_group_id)?;
+        let mut rx = live_session_core()?
+            .ok_or_else(HavenErrorFfi::sync_not_running)?
+            .bus()
+            .subscribe();
+        loop {
+            match rx.recv().await {
+                Ok(event) => {
+                    let Some(location) = location_update_for(&event, &id) else {
+                        continue;
+                    };
+                    if sink.add(location).is_err() {
+                        break; // Dart closed the stream
+                    }
+                }
+                Err(tokio::sync::broadcast::error::RecvError::Lagged(_)) => continue,
+                Err(tokio::sync::broadcast::error::RecvError::Closed) => break,
+            }
+        }
+        Ok(())
+    }
+}
+
+/// The parsed location `event` carries for the circle `nostr_group_id`, if any.
+fn location_update_for(
+    event: &CoreLiveSyncEvent,
+    nostr_group_id: &[u8],
+) -> Option<DecryptedLocationFfi> {
+    match event {
+        CoreLiveSyncEvent::Location {
+            nostr_group_id: event_group,
+            sender_pubkey,
+            content,
+            ..
+        } if event_group.as_slice() == nostr_group_id => {
+            decode_engine_location(content, sender_pubkey)
+        }
+        _ => None,
+    }
 }
 
 /// M8-4 subscription-health maintenance tick (Dart-timer-driven, no secret).
@@ -8307,22 +15671,22 @@ impl LiveSyncFfi {
 ///
 /// Returns a redacted error string if the `SESSION` lock is poisoned or a
 /// re-anchor's re-subscription fails.
-pub async fn maintain_subscription_health() -> Result<SubscriptionHealthOutcomeFfi, String> {
+pub async fn maintain_subscription_health() -> Result<SubscriptionHealthOutcomeFfi, HavenErrorFfi> {
     let Some(core) = live_session_core()? else {
         return Ok(haven_core::relay::live_sync::SubscriptionHealthOutcome::engine_off().into());
     };
     let outcome = core
         .maintain_subscription_health()
         .await
-        .map_err(|e| e.to_string())?;
+        .map_err(HavenErrorFfi::from)?;
     Ok(outcome.into())
 }
 
 #[cfg(test)]
 mod live_sync_ffi_tests {
     use super::{
-        live_event_to_ffi, sync_reason_to_ffi, FfiRelayEventKind, FfiSyncStatusReason,
-        SubscriptionHealthActionFfi, SubscriptionHealthOutcomeFfi,
+        live_event_to_ffi, location_update_for, sync_reason_to_ffi, FfiRelayEventKind,
+        FfiSyncStatusReason, SubscriptionHealthActionFfi, SubscriptionHealthOutcomeFfi,
     };
     use haven_core::relay::live_sync::{LiveSyncEvent as Ev, SyncStatusReason as R};
 
@@ -8342,6 +15706,32 @@ mod live_sync_ffi_tests {
         assert!(f.status_reason.is_none() && f.gift_wrap_json.is_none());
     }
 
+    #[test]
+    fn location_updates_keep_only_parsed_locations_of_the_circle() {
+        let content = haven_core::location::LocationMessage::new(51.5, -0.12)
+            .to_string()
+            .unwrap();
+        let location = |group: u8, content: &str| Ev::Location {
+            nostr_group_id: vec![group; 32],
+            sender_pubkey: "AB".repeat(32),
+            content: content.to_string(),
+            event_created_at_secs: 100,
+        };
+
+        let update = location_update_for(&location(1, &content), &[1; 32]).expect("location");
+        assert_eq!(update.sender_pubkey, "ab".repeat(32), "normalized");
+        assert!((update.latitude - 51.5).abs() < 1e-9);
+        assert!(location_update_for(&location(2, &content), &[1; 32]).is_none());
+        assert!(location_update_for(&location(1, "not json"), &[1; 32]).is_none());
+        let sos = Ev::Sos {
+            nostr_group_id: vec![1; 32],
+            sender_pubkey: "ab".repeat(32),
+            content,
+            event_created_at_secs: 100,
+        };
+        assert!(location_update_for(&sos, &[1; 32]).is_none());
+    }
+
     #[test]
     fn maps_group_update_welcome_and_status() {
         let g = live_event_to_ffi(Ev::GroupUpdate {
@@ -8369,6 +15759,29 @@ mod live_sync_ffi_tests {
         );
     }
 
+    #[test]
+    fn attention_events_carry_a_notification() {
+        let sos = live_event_to_ffi(Ev::Sos {
+            nostr_group_id: vec![1],
+            sender_pubkey: "SENDER_PK".to_string(),
+            content: "{}".to_string(),
+            event_created_at_secs: 5,
+        });
+        let note = sos.notification.as_ref().expect("sos notifies");
+        assert_eq!(note.code, "notification.sos");
+        assert_eq!(note.params[0].name, "sender_pubkey");
+        assert_eq!(note.params[0].value, "SENDER_PK");
+        assert!(!format!("{sos:?}").contains("SENDER_PK"));
+
+        let loc = live_event_to_ffi(Ev::Location {
+            nostr_group_id: vec![1],
+            sender_pubkey: "SENDER_PK".to_string(),
+            content: "{}".to_string(),
+            event_created_at_secs: 5,
+        });
+        assert!(loc.notification.is_none());
+    }
+
     #[test]
     fn ffi_relay_event_debug_is_presence_only() {
         let f = live_event_to_ffi(Ev::Location {
@@ -8559,10 +15972,11 @@ mod maintenance_real_ffi_tests {
         let _ = allow_ws_loopback_for_test();
     }
 
-    /// A generic 32-byte identity secret. Deterministic per test call is fine —
+    /// A handle to a generic identity. Deterministic per test call is fine —
     /// the keys never touch a real network beyond the local MockRelay.
-    fn secret_bytes(keys: &Keys) -> Vec<u8> {
-        keys.secret_key().to_secret_bytes().to_vec()
+    fn secret_handle(keys: &Keys) -> super::SecretHandle {
+        super::SecretHandle::from_slice(&keys.secret_key().to_secret_bytes())
+            .expect("generated key is valid")
     }
 
     /// The NIP-33 `d` of a kind-30443 event (public tag; used only to assert the
@@ -8615,12 +16029,11 @@ mod maintenance_real_ffi_tests {
 
         let keys = Keys::generate();
         let author = keys.public_key();
-        let secret = secret_bytes(&keys);
+        let secret = secret_handle(&keys);
 
         // REAL manager (real Dark Matter session + circles.db) + REAL relay mgr.
         let dir = DataDir::new("healthy");
-        let circle =
-            CircleManagerFfi::new(dir.as_str(), secret.clone()).expect("CircleManagerFfi::new");
+        let circle = CircleManagerFfi::new(dir.as_str(), &secret).expect("CircleManagerFfi::new");
         let relay_mgr = RelayManagerFfi::new_instance()
             .await
             .expect("RelayManagerFfi::new_instance");
@@ -8636,7 +16049,7 @@ mod maintenance_real_ffi_tests {
         // FIRST tick = the real first-publish path (replaces the deleted manual
         // sign/publish/record): mint a fresh KeyPackage into a new slot and
         // publish it to the own relay.
-        let first = circle_maintain(&relay_mgr, &circle, secret.clone()).await;
+        let first = circle_maintain(&relay_mgr, &circle, &secret).await;
         assert_eq!(
             first.action,
             KpMaintenanceActionFfi::RepublishedFreshD,
@@ -8660,7 +16073,7 @@ mod maintenance_real_ffi_tests {
 
         // SECOND tick: DRIVE THE REAL FFI again. The live, tracked KP must read
         // healthy — no rotation.
-        let outcome = circle_maintain(&relay_mgr, &circle, secret).await;
+        let outcome = circle_maintain(&relay_mgr, &circle, &secret).await;
         assert_eq!(
             outcome.action,
             KpMaintenanceActionFfi::AlreadyHealthy,
@@ -8735,11 +16148,10 @@ mod maintenance_real_ffi_tests {
 
         let keys = Keys::generate();
         let author = keys.public_key();
-        let secret = secret_bytes(&keys);
+        let secret = secret_handle(&keys);
 
         let dir = DataDir::new("isolation");
-        let circle =
-            CircleManagerFfi::new(dir.as_str(), secret.clone()).expect("CircleManagerFfi::new");
+        let circle = CircleManagerFfi::new(dir.as_str(), &secret).expect("CircleManagerFfi::new");
         let relay_mgr = RelayManagerFfi::new_instance()
             .await
             .expect("RelayManagerFfi::new_instance");
@@ -8750,7 +16162,7 @@ mod maintenance_real_ffi_tests {
             .add_user_relay(url_live.clone(), RelayTypeFfi::Nip65)
             .await
             .expect("register live own relay");
-        let first = circle_maintain(&relay_mgr, &circle, secret.clone()).await;
+        let first = circle_maintain(&relay_mgr, &circle, &secret).await;
         assert_eq!(
             first.action,
             KpMaintenanceActionFfi::RepublishedFreshD,
@@ -8778,7 +16190,7 @@ mod maintenance_real_ffi_tests {
             .expect("register empty own relay");
 
         // DRIVE THE REAL FFI across both relays.
-        let outcome = circle_maintain(&relay_mgr, &circle, secret).await;
+        let outcome = circle_maintain(&relay_mgr, &circle, &secret).await;
         assert_eq!(
             outcome.responders_probed, 2,
             "both own relays must have responded"
@@ -8826,7 +16238,7 @@ mod maintenance_real_ffi_tests {
     async fn circle_maintain(
         relay_mgr: &RelayManagerFfi,
         circle: &CircleManagerFfi,
-        secret: Vec<u8>,
+        secret: &super::SecretHandle,
     ) -> super::KpMaintenanceOutcomeFfi {
         relay_mgr
             .maintain_key_package(circle, secret)
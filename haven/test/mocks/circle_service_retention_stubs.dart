@@ -9,6 +9,7 @@
 /// the cache surface. Override individual members as needed.
 library;
 
+import 'package:haven/src/rust/api.dart' show SecretHandle;
 import 'package:haven/src/services/circle_service.dart';
 
 /// No-op stubs for last-known-location methods.
@@ -83,7 +84,7 @@ mixin CircleServiceRetentionStubs {
   }) async => true;
 
   Future<AddMemberResult> addMember({
-    required Future<List<int>> Function() secretProvider,
+    required Future<SecretHandle> Function() secretProvider,
     required List<int> mlsGroupId,
     required List<KeyPackageData> memberKeyPackages,
     List<String> creatorFallbackRelays = const [],
@@ -0,0 +1,24 @@
+/// Fake [SecretHandle] for tests that never reach the FFI bridge.
+library;
+
+import 'package:haven/src/rust/api.dart';
+
+/// A [SecretHandle] that holds no key and records its disposal.
+class FakeSecretHandle implements SecretHandle {
+  /// Creates a handle reporting [pubkey] as its public key.
+  FakeSecretHandle({this.pubkey = ''});
+
+  /// The hex public key [pubkeyHex] returns.
+  final String pubkey;
+
+  bool _disposed = false;
+
+  @override
+  String pubkeyHex() => pubkey;
+
+  @override
+  bool get isDisposed => _disposed;
+
+  @override
+  void dispose() => _disposed = true;
+}
@@ -4,6 +4,7 @@
 /// Rust FFI or system keyring access.
 library;
 
+import 'package:haven/src/rust/api.dart' show SecretHandle;
 import 'package:haven/src/services/circle_service.dart';
 
 /// A mock [CircleService] for testing.
@@ -119,7 +120,7 @@ class MockCircleService implements CircleService {
 
   @override
   Future<CircleCreationResult> createCircle({
-    required List<int> identitySecretBytes,
+    required SecretHandle secret,
     required List<KeyPackageData> memberKeyPackages,
     required String name,
     required CircleType circleType,
@@ -148,22 +149,21 @@ class MockCircleService implements CircleService {
     methodCalls.add('declineInvitation');
   }
 
-  /// A REFERENCE (not a copy) to the last `identitySecretBytes` passed to
-  /// [processGiftWrappedInvitation], so a test can assert the caller zeroized
-  /// the buffer after use (Security Rule 9).
-  List<int>? processGiftWrappedInvitationSecretRef;
+  /// The last `secret` handle passed to [processGiftWrappedInvitation], so a
+  /// test can assert the caller disposed it after use (Security Rule 9).
+  SecretHandle? processGiftWrappedInvitationSecretRef;
 
   /// When true, [processGiftWrappedInvitation] throws (to prove the caller's
-  /// zeroize still runs on the error path).
+  /// dispose still runs on the error path).
   bool shouldThrowOnProcessGiftWrappedInvitation = false;
 
   @override
   Future<Invitation?> processGiftWrappedInvitation({
-    required List<int> identitySecretBytes,
+    required SecretHandle secret,
     required String giftWrapEventJson,
   }) async {
     methodCalls.add('processGiftWrappedInvitation');
-    processGiftWrappedInvitationSecretRef = identitySecretBytes;
+    processGiftWrappedInvitationSecretRef = secret;
     if (shouldThrowOnProcessGiftWrappedInvitation) {
       throw Exception('mock process failure');
     }
@@ -299,7 +299,7 @@ class MockCircleService implements CircleService {
 
   @override
   Future<AddMemberResult> addMember({
-    required Future<List<int>> Function() secretProvider,
+    required Future<SecretHandle> Function() secretProvider,
     required List<int> mlsGroupId,
     required List<KeyPackageData> memberKeyPackages,
     List<String> creatorFallbackRelays = const [],
@@ -530,7 +530,7 @@ class MockCircleService implements CircleService {
 
   @override
   Future<String> signDeletionEvent({
-    required List<int> identitySecretBytes,
+    required SecretHandle secret,
     required List<String> eventIds,
   }) async {
     methodCalls.add('signDeletionEvent');
@@ -5,8 +5,7 @@
 /// counts via `addCalls`, `removeCalls`, etc.
 library;
 
-import 'dart:typed_data';
-
+import 'package:haven/src/rust/api.dart' show SecretHandle;
 import 'package:haven/src/services/relay_preferences_service.dart';
 
 /// A mock [RelayPreferencesService] for tests.
@@ -144,7 +143,7 @@ class MockRelayPreferencesService implements RelayPreferencesService {
 
   @override
   Future<BuiltRelayListPublish> buildRelayListPublish({
-    required Uint8List identitySecretBytes,
+    required SecretHandle secret,
     required RelayCategory category,
   }) async {
     final enabled = _toggles[category] ?? true;
@@ -176,7 +175,7 @@ class MockRelayPreferencesService implements RelayPreferencesService {
 
   @override
   Future<BuiltUnpublish> buildUnpublishRelayList({
-    required Uint8List identitySecretBytes,
+    required SecretHandle secret,
     required RelayCategory category,
   }) async {
     return BuiltUnpublish(
@@ -188,7 +187,7 @@ class MockRelayPreferencesService implements RelayPreferencesService {
 
   @override
   Future<BuiltUnpublish> buildRelayRemovalScrub({
-    required Uint8List identitySecretBytes,
+    required SecretHandle secret,
     required RelayCategory category,
     required List<String> droppedRelays,
   }) async {
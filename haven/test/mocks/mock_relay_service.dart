@@ -19,13 +19,13 @@ class MockRelayService implements RelayService {
   @override
   Future<KeyPackageMaintenanceResult> maintainKeyPackage({
     required CircleManagerFfi circle,
-    required List<int> identitySecretBytes,
+    required SecretHandle secret,
   }) async => const KeyPackageMaintenanceResult.empty();
 
   @override
   Future<RelayListMaintenanceResult> maintainRelayList({
     required CircleManagerFfi circle,
-    required List<int> identitySecretBytes,
+    required SecretHandle secret,
   }) async => const RelayListMaintenanceResult.empty();
 
   @override
@@ -35,7 +35,7 @@ class MockRelayService implements RelayService {
   @override
   Future<LegacyRetractionResult> retractLegacyKeyMaterial({
     required CircleManagerFfi circle,
-    required List<int> identitySecretBytes,
+    required SecretHandle secret,
   }) async => const LegacyRetractionResult.empty();
 
   /// Creates a [MockRelayService].
@@ -22,11 +22,13 @@ import 'package:haven/src/providers/join_watcher_provider.dart';
 import 'package:haven/src/providers/location_sharing_provider.dart';
 import 'package:haven/src/providers/relay_preferences_provider.dart';
 import 'package:haven/src/providers/service_providers.dart';
+import 'package:haven/src/rust/api.dart' show SecretHandle;
 import 'package:haven/src/services/circle_service.dart';
 import 'package:haven/src/services/identity_service.dart';
 import 'package:haven/src/test_keys.dart';
 import 'package:lucide_icons_flutter/lucide_icons.dart';
 
+import '../../mocks/fake_secret_handle.dart';
 import '../../mocks/mock_circle_service.dart';
 import '../../mocks/mock_relay_service.dart';
 
@@ -137,7 +139,7 @@ class _MockIdentityService implements IdentityService {
   Future<String> getPubkeyHex() async => _testIdentity.pubkeyHex;
 
   @override
-  Future<List<int>> getSecretBytes() async => List<int>.filled(32, 0);
+  Future<SecretHandle> getSecretHandle() async => FakeSecretHandle();
 
   @override
   Future<String?> getDisplayName() async => null;
@@ -153,13 +155,13 @@ class _MockIdentityService implements IdentityService {
 // Fake IdentityNotifier
 // ---------------------------------------------------------------------------
 
-/// A fake [IdentityNotifier] that exposes [getSecretBytes] without FFI.
+/// A fake [IdentityNotifier] that exposes [getSecretHandle] without FFI.
 class _FakeIdentityNotifier extends IdentityNotifier {
   @override
   Future<Identity?> build() async => _testIdentity;
 
   @override
-  Future<List<int>> getSecretBytes() async => List<int>.filled(32, 0);
+  Future<SecretHandle> getSecretHandle() async => FakeSecretHandle();
 }
 
 // ---------------------------------------------------------------------------
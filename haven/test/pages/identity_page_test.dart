@@ -17,6 +17,7 @@ import 'package:flutter_test/flutter_test.dart';
 import 'package:haven/l10n/app_localizations.dart';
 import 'package:haven/src/pages/identity_page.dart';
 import 'package:haven/src/providers/service_providers.dart';
+import 'package:haven/src/rust/api.dart' show SecretHandle;
 import 'package:haven/src/services/identity_service.dart';
 import 'package:haven/src/test_keys.dart';
 import 'package:haven/src/widgets/identity/display_name_card.dart';
@@ -57,7 +58,7 @@ class _FakeIdentityService implements IdentityService {
   Future<String> getPubkeyHex() async => _identity.pubkeyHex;
 
   @override
-  Future<List<int>> getSecretBytes() => throw UnimplementedError();
+  Future<SecretHandle> getSecretHandle() => throw UnimplementedError();
 
   @override
   Future<void> deleteIdentity() async {}
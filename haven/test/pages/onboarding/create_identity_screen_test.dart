@@ -18,6 +18,7 @@ import 'package:haven/src/pages/onboarding/import_nsec_screen.dart';
 import 'package:haven/src/providers/onboarding_provider.dart';
 import 'package:haven/src/providers/relay_preferences_provider.dart';
 import 'package:haven/src/providers/service_providers.dart';
+import 'package:haven/src/rust/api.dart' show SecretHandle;
 import 'package:haven/src/services/identity_service.dart';
 import 'package:haven/src/services/location_service.dart';
 import 'package:haven/src/test_keys.dart';
@@ -656,7 +657,7 @@ class _RecordingIdentityService implements IdentityService {
   Future<String> getPubkeyHex() async => _identity.pubkeyHex;
 
   @override
-  Future<List<int>> getSecretBytes() async => throw UnimplementedError();
+  Future<SecretHandle> getSecretHandle() async => throw UnimplementedError();
 
   @override
   Future<void> clearCache() async {}
@@ -8,6 +8,7 @@ import 'package:flutter_riverpod/flutter_riverpod.dart';
 import 'package:flutter_test/flutter_test.dart';
 import 'package:haven/src/pages/onboarding/import_nsec_screen.dart';
 import 'package:haven/src/providers/service_providers.dart';
+import 'package:haven/src/rust/api.dart' show SecretHandle;
 import 'package:haven/src/services/identity_service.dart';
 import 'package:shared_preferences/shared_preferences.dart';
 
@@ -189,7 +190,7 @@ class _RecordingIdentityService implements IdentityService {
   Future<String> getPubkeyHex() async => throw UnimplementedError();
 
   @override
-  Future<List<int>> getSecretBytes() async => throw UnimplementedError();
+  Future<SecretHandle> getSecretHandle() async => throw UnimplementedError();
 
   @override
   Future<String?> getDisplayName() async => null;
@@ -14,6 +14,7 @@ import 'package:haven/src/pages/onboarding/intro_screen.dart';
 import 'package:haven/src/pages/onboarding/onboarding_shell.dart';
 import 'package:haven/src/providers/onboarding_provider.dart';
 import 'package:haven/src/providers/service_providers.dart';
+import 'package:haven/src/rust/api.dart' show SecretHandle;
 import 'package:haven/src/services/identity_service.dart';
 import 'package:shared_preferences/shared_preferences.dart';
 
@@ -101,7 +102,7 @@ class _StubIdentityService implements IdentityService {
   Future<String> getPubkeyHex() async => throw UnimplementedError();
 
   @override
-  Future<List<int>> getSecretBytes() async => throw UnimplementedError();
+  Future<SecretHandle> getSecretHandle() async => throw UnimplementedError();
 
   @override
   Future<String?> getDisplayName() async => null;
@@ -13,11 +13,14 @@ import 'package:haven/src/constants/location.dart';
 import 'package:haven/src/providers/background_location_provider.dart';
 import 'package:haven/src/providers/identity_provider.dart';
 import 'package:haven/src/providers/service_providers.dart';
+import 'package:haven/src/rust/api.dart' show SecretHandle;
 import 'package:haven/src/services/background_location_manager.dart';
 import 'package:haven/src/services/identity_service.dart';
 import 'package:haven/src/services/ios_location_auth_service.dart';
 import 'package:shared_preferences/shared_preferences.dart';
 
+import '../mocks/fake_secret_handle.dart';
+
 // =============================================================================
 // Stub helpers
 // =============================================================================
@@ -64,7 +67,7 @@ class _FakeIdentityService implements IdentityService {
       '0000000000000000000000000000000000000000000000000000000000000000';
 
   @override
-  Future<List<int>> getSecretBytes() async => List.filled(32, 0);
+  Future<SecretHandle> getSecretHandle() async => FakeSecretHandle();
 
   @override
   Future<void> deleteIdentity() async {}
@@ -10,6 +10,7 @@ import 'package:flutter_riverpod/flutter_riverpod.dart';
 import 'package:flutter_test/flutter_test.dart';
 import 'package:haven/src/providers/circles_provider.dart';
 import 'package:haven/src/providers/service_providers.dart';
+import 'package:haven/src/rust/api.dart' show SecretHandle;
 import 'package:haven/src/services/circle_service.dart';
 
 import '../mocks/mock_circle_service.dart';
@@ -279,7 +280,7 @@ class _ThrowingCircleService implements CircleService {
 
   @override
   Future<CircleCreationResult> createCircle({
-    required List<int> identitySecretBytes,
+    required SecretHandle secret,
     required List<KeyPackageData> memberKeyPackages,
     required String name,
     required CircleType circleType,
@@ -300,7 +301,7 @@ class _ThrowingCircleService implements CircleService {
 
   @override
   Future<Invitation?> processGiftWrappedInvitation({
-    required List<int> identitySecretBytes,
+    required SecretHandle secret,
     required String giftWrapEventJson,
   }) async {
     throw UnimplementedError();
@@ -329,7 +330,7 @@ class _ThrowingCircleService implements CircleService {
 
   @override
   Future<AddMemberResult> addMember({
-    required Future<List<int>> Function() secretProvider,
+    required Future<SecretHandle> Function() secretProvider,
     required List<int> mlsGroupId,
     required List<KeyPackageData> memberKeyPackages,
     List<String> creatorFallbackRelays = const [],
@@ -403,7 +404,7 @@ class _ThrowingCircleService implements CircleService {
 
   @override
   Future<String> signDeletionEvent({
-    required List<int> identitySecretBytes,
+    required SecretHandle secret,
     required List<String> eventIds,
   }) async => throw UnimplementedError();
 
@@ -456,7 +457,7 @@ class _ThrowingErrorCircleService implements CircleService {
 
   @override
   Future<CircleCreationResult> createCircle({
-    required List<int> identitySecretBytes,
+    required SecretHandle secret,
     required List<KeyPackageData> memberKeyPackages,
     required String name,
     required CircleType circleType,
@@ -477,7 +478,7 @@ class _ThrowingErrorCircleService implements CircleService {
 
   @override
   Future<Invitation?> processGiftWrappedInvitation({
-    required List<int> identitySecretBytes,
+    required SecretHandle secret,
     required String giftWrapEventJson,
   }) async {
     throw UnimplementedError();
@@ -506,7 +507,7 @@ class _ThrowingErrorCircleService implements CircleService {
 
   @override
   Future<AddMemberResult> addMember({
-    required Future<List<int>> Function() secretProvider,
+    required Future<SecretHandle> Function() secretProvider,
     required List<int> mlsGroupId,
     required List<KeyPackageData> memberKeyPackages,
     List<String> creatorFallbackRelays = const [],
@@ -580,7 +581,7 @@ class _ThrowingErrorCircleService implements CircleService {
 
   @override
   Future<String> signDeletionEvent({
-    required List<int> identitySecretBytes,
+    required SecretHandle secret,
     required List<String> eventIds,
   }) async => throw UnimplementedError();
 
@@ -25,7 +25,7 @@ import 'package:haven/src/providers/identity_provider.dart';
 import 'package:haven/src/providers/live_sync_provider.dart' show liveSyncEnabled;
 import 'package:haven/src/providers/service_providers.dart';
 import 'package:haven/src/providers/tile_prefetch_provider.dart';
-import 'package:haven/src/rust/api.dart' show FfiGroupSpec;
+import 'package:haven/src/rust/api.dart' show FfiGroupSpec, SecretHandle;
 import 'package:haven/src/services/circle_service.dart';
 import 'package:haven/src/services/identity_service.dart';
 import 'package:haven/src/services/pending_mls_wipe_service.dart';
@@ -35,6 +35,7 @@ import 'package:haven/src/services/tile_prefetch_service.dart';
 import 'package:latlong2/latlong.dart';
 import 'package:shared_preferences/shared_preferences.dart';
 
+import '../mocks/fake_secret_handle.dart';
 import '../mocks/mock_circle_service.dart';
 
 void main() {
@@ -1223,11 +1224,11 @@ class _MockIdentityService implements IdentityService {
   }
 
   @override
-  Future<List<int>> getSecretBytes() async {
+  Future<SecretHandle> getSecretHandle() async {
     if (_currentIdentity == null) {
       throw const IdentityServiceException('No identity');
     }
-    return List<int>.generate(32, (i) => i);
+    return FakeSecretHandle(pubkey: _currentIdentity!.pubkeyHex);
   }
 
   @override
@@ -16,9 +16,11 @@ import 'package:haven/src/models/relay_ring_slot.dart';
 import 'package:haven/src/providers/invitation_poll_status_provider.dart';
 import 'package:haven/src/providers/relay_preferences_provider.dart';
 import 'package:haven/src/providers/service_providers.dart';
+import 'package:haven/src/rust/api.dart' show SecretHandle;
 import 'package:haven/src/services/identity_service.dart';
 import 'package:haven/src/services/relay_service.dart';
 
+import '../mocks/fake_secret_handle.dart';
 import '../mocks/mock_circle_service.dart';
 import '../mocks/mock_relay_service.dart';
 
@@ -478,7 +480,7 @@ void main() {
         InvitationPollOutcome.upToDate,
       );
       // The superseded refresh must never have read the identity secret.
-      expect(identity.secretBytesCalls, 0);
+      expect(identity.secretHandleCalls, 0);
     });
   });
 }
@@ -494,15 +496,15 @@ class _StubInboxRelays extends InboxRelaysNotifier {
   Future<List<String>> build() async => _relays;
 }
 
-/// Minimal identity service: one test identity (or none) plus secret bytes.
+/// Minimal identity service: one test identity (or none) plus a secret handle.
 class _MockIdentityService implements IdentityService {
   _MockIdentityService({required this.identityExists});
 
   final bool identityExists;
 
-  /// Number of times [getSecretBytes] was invoked — lets a test assert that a
+  /// Number of times [getSecretHandle] was invoked — lets a test assert that a
   /// superseded refresh never reaches the secret fetch (Security Rule #9).
-  int secretBytesCalls = 0;
+  int secretHandleCalls = 0;
 
   static final _identity = Identity(
     pubkeyHex:
@@ -515,9 +517,9 @@ class _MockIdentityService implements IdentityService {
   Future<Identity?> getIdentity() async => identityExists ? _identity : null;
 
   @override
-  Future<List<int>> getSecretBytes() async {
-    secretBytesCalls++;
-    return List<int>.generate(32, (i) => i);
+  Future<SecretHandle> getSecretHandle() async {
+    secretHandleCalls++;
+    return FakeSecretHandle();
   }
 
   @override
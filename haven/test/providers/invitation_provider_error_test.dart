@@ -19,6 +19,7 @@ import 'package:haven/src/services/relay_preferences_service.dart';
 import 'package:haven/src/services/relay_service.dart';
 
 import '../mocks/circle_service_retention_stubs.dart';
+import '../mocks/fake_secret_handle.dart';
 import '../mocks/mock_relay_preferences_service.dart';
 
 Override _relayPrefsOverride() {
@@ -158,15 +159,13 @@ class _MockIdentityService implements IdentityService {
     createdAt: DateTime(2024),
   );
 
-  static final _testSecretBytes = List<int>.generate(32, (i) => i);
-
   @override
   Future<Identity?> getIdentity() async {
     return identityExists ? _testIdentity : null;
   }
 
   @override
-  Future<List<int>> getSecretBytes() async => _testSecretBytes;
+  Future<SecretHandle> getSecretHandle() async => FakeSecretHandle();
 
   @override
   Future<bool> hasIdentity() async => identityExists;
@@ -211,13 +210,13 @@ class _MockRelayService implements RelayService {
   @override
   Future<KeyPackageMaintenanceResult> maintainKeyPackage({
     required CircleManagerFfi circle,
-    required List<int> identitySecretBytes,
+    required SecretHandle secret,
   }) async => const KeyPackageMaintenanceResult.empty();
 
   @override
   Future<RelayListMaintenanceResult> maintainRelayList({
     required CircleManagerFfi circle,
-    required List<int> identitySecretBytes,
+    required SecretHandle secret,
   }) async => const RelayListMaintenanceResult.empty();
 
   @override
@@ -227,7 +226,7 @@ class _MockRelayService implements RelayService {
   @override
   Future<LegacyRetractionResult> retractLegacyKeyMaterial({
     required CircleManagerFfi circle,
-    required List<int> identitySecretBytes,
+    required SecretHandle secret,
   }) async => const LegacyRetractionResult.empty();
   _MockRelayService({this.giftWraps = const []});
 
@@ -321,7 +320,7 @@ class _MockCircleServiceThrowsErrorOnProcess
 
   @override
   Future<CircleCreationResult> createCircle({
-    required List<int> identitySecretBytes,
+    required SecretHandle secret,
     required List<KeyPackageData> memberKeyPackages,
     required String name,
     required CircleType circleType,
@@ -354,7 +353,7 @@ class _MockCircleServiceThrowsErrorOnProcess
 
   @override
   Future<Invitation?> processGiftWrappedInvitation({
-    required List<int> identitySecretBytes,
+    required SecretHandle secret,
     required String giftWrapEventJson,
   }) async {
     methodCalls.add('processGiftWrappedInvitation');
@@ -378,7 +377,7 @@ class _MockCircleServiceThrowsErrorOnProcess
 
   @override
   Future<String> signDeletionEvent({
-    required List<int> identitySecretBytes,
+    required SecretHandle secret,
     required List<String> eventIds,
   }) async => throw UnimplementedError();
 
@@ -405,7 +404,7 @@ class _MockCircleServiceThrowsOnFirst
 
   @override
   Future<CircleCreationResult> createCircle({
-    required List<int> identitySecretBytes,
+    required SecretHandle secret,
     required List<KeyPackageData> memberKeyPackages,
     required String name,
     required CircleType circleType,
@@ -438,7 +437,7 @@ class _MockCircleServiceThrowsOnFirst
 
   @override
   Future<Invitation?> processGiftWrappedInvitation({
-    required List<int> identitySecretBytes,
+    required SecretHandle secret,
     required String giftWrapEventJson,
   }) async {
     methodCalls.add('processGiftWrappedInvitation');
@@ -476,7 +475,7 @@ class _MockCircleServiceThrowsOnFirst
 
   @override
   Future<String> signDeletionEvent({
-    required List<int> identitySecretBytes,
+    required SecretHandle secret,
     required List<String> eventIds,
   }) async => throw UnimplementedError();
 
@@ -503,7 +502,7 @@ class _MockCircleServiceMixedErrors
 
   @override
   Future<CircleCreationResult> createCircle({
-    required List<int> identitySecretBytes,
+    required SecretHandle secret,
     required List<KeyPackageData> memberKeyPackages,
     required String name,
     required CircleType circleType,
@@ -536,7 +535,7 @@ class _MockCircleServiceMixedErrors
 
   @override
   Future<Invitation?> processGiftWrappedInvitation({
-    required List<int> identitySecretBytes,
+    required SecretHandle secret,
     required String giftWrapEventJson,
   }) async {
     methodCalls.add('processGiftWrappedInvitation');
@@ -577,7 +576,7 @@ class _MockCircleServiceMixedErrors
 
   @override
   Future<String> signDeletionEvent({
-    required List<int> identitySecretBytes,
+    required SecretHandle secret,
     required List<String> eventIds,
   }) async => throw UnimplementedError();
 
@@ -21,6 +21,7 @@ import 'package:haven/src/services/relay_preferences_service.dart';
 import 'package:haven/src/services/relay_service.dart';
 
 import '../mocks/circle_service_retention_stubs.dart';
+import '../mocks/fake_secret_handle.dart';
 import '../mocks/mock_circle_service.dart';
 import '../mocks/mock_relay_preferences_service.dart';
 
@@ -549,7 +550,7 @@ class _MockCircleServiceWithInvitations
 
   @override
   Future<CircleCreationResult> createCircle({
-    required List<int> identitySecretBytes,
+    required SecretHandle secret,
     required List<KeyPackageData> memberKeyPackages,
     required String name,
     required CircleType circleType,
@@ -582,7 +583,7 @@ class _MockCircleServiceWithInvitations
 
   @override
   Future<Invitation?> processGiftWrappedInvitation({
-    required List<int> identitySecretBytes,
+    required SecretHandle secret,
     required String giftWrapEventJson,
   }) async {
     return _createTestInvitation();
@@ -605,7 +606,7 @@ class _MockCircleServiceWithInvitations
 
   @override
   Future<String> signDeletionEvent({
-    required List<int> identitySecretBytes,
+    required SecretHandle secret,
     required List<String> eventIds,
   }) async => throw UnimplementedError();
 
@@ -642,7 +643,7 @@ class _ThrowingCircleServiceInvitations
 
   @override
   Future<CircleCreationResult> createCircle({
-    required List<int> identitySecretBytes,
+    required SecretHandle secret,
     required List<KeyPackageData> memberKeyPackages,
     required String name,
     required CircleType circleType,
@@ -675,7 +676,7 @@ class _ThrowingCircleServiceInvitations
 
   @override
   Future<Invitation?> processGiftWrappedInvitation({
-    required List<int> identitySecretBytes,
+    required SecretHandle secret,
     required String giftWrapEventJson,
   }) async {
     throw UnimplementedError();
@@ -698,7 +699,7 @@ class _ThrowingCircleServiceInvitations
 
   @override
   Future<String> signDeletionEvent({
-    required List<int> identitySecretBytes,
+    required SecretHandle secret,
     required List<String> eventIds,
   }) async => throw UnimplementedError();
 
@@ -726,7 +727,7 @@ class _MockCircleServiceThrowsOnProcess
 
   @override
   Future<CircleCreationResult> createCircle({
-    required List<int> identitySecretBytes,
+    required SecretHandle secret,
     required List<KeyPackageData> memberKeyPackages,
     required String name,
     required CircleType circleType,
@@ -759,7 +760,7 @@ class _MockCircleServiceThrowsOnProcess
 
   @override
   Future<Invitation?> processGiftWrappedInvitation({
-    required List<int> identitySecretBytes,
+    required SecretHandle secret,
     required String giftWrapEventJson,
   }) async {
     throw exception;
@@ -782,7 +783,7 @@ class _MockCircleServiceThrowsOnProcess
 
   @override
   Future<String> signDeletionEvent({
-    required List<int> identitySecretBytes,
+    required SecretHandle secret,
     required List<String> eventIds,
   }) async => throw UnimplementedError();
 
@@ -802,15 +803,13 @@ class _MockIdentityService implements IdentityService {
     createdAt: DateTime(2024),
   );
 
-  static final _testSecretBytes = List<int>.generate(32, (i) => i);
-
   @override
   Future<Identity?> getIdentity() async {
     return identityExists ? _testIdentity : null;
   }
 
   @override
-  Future<List<int>> getSecretBytes() async => _testSecretBytes;
+  Future<SecretHandle> getSecretHandle() async => FakeSecretHandle();
 
   @override
   Future<bool> hasIdentity() async => identityExists;
@@ -855,13 +854,13 @@ class _MockRelayService implements RelayService {
   @override
   Future<KeyPackageMaintenanceResult> maintainKeyPackage({
     required CircleManagerFfi circle,
-    required List<int> identitySecretBytes,
+    required SecretHandle secret,
   }) async => const KeyPackageMaintenanceResult.empty();
 
   @override
   Future<RelayListMaintenanceResult> maintainRelayList({
     required CircleManagerFfi circle,
-    required List<int> identitySecretBytes,
+    required SecretHandle secret,
   }) async => const RelayListMaintenanceResult.empty();
 
   @override
@@ -871,7 +870,7 @@ class _MockRelayService implements RelayService {
   @override
   Future<LegacyRetractionResult> retractLegacyKeyMaterial({
     required CircleManagerFfi circle,
-    required List<int> identitySecretBytes,
+    required SecretHandle secret,
   }) async => const LegacyRetractionResult.empty();
   _MockRelayService({
     this.giftWraps = const [],
@@ -974,7 +973,7 @@ class _MockCircleServiceReturnsNull
 
   @override
   Future<CircleCreationResult> createCircle({
-    required List<int> identitySecretBytes,
+    required SecretHandle secret,
     required List<KeyPackageData> memberKeyPackages,
     required String name,
     required CircleType circleType,
@@ -1004,7 +1003,7 @@ class _MockCircleServiceReturnsNull
 
   @override
   Future<Invitation?> processGiftWrappedInvitation({
-    required List<int> identitySecretBytes,
+    required SecretHandle secret,
     required String giftWrapEventJson,
   }) async => null; // Already-processed — silent no-op.
 
@@ -1025,7 +1024,7 @@ class _MockCircleServiceReturnsNull
 
   @override
   Future<String> signDeletionEvent({
-    required List<int> identitySecretBytes,
+    required SecretHandle secret,
     required List<String> eventIds,
   }) async => throw UnimplementedError();
 
@@ -1052,7 +1051,7 @@ class _MockCircleServiceMixedBatch
 
   @override
   Future<CircleCreationResult> createCircle({
-    required List<int> identitySecretBytes,
+    required SecretHandle secret,
     required List<KeyPackageData> memberKeyPackages,
     required String name,
     required CircleType circleType,
@@ -1082,7 +1081,7 @@ class _MockCircleServiceMixedBatch
 
   @override
   Future<Invitation?> processGiftWrappedInvitation({
-    required List<int> identitySecretBytes,
+    required SecretHandle secret,
     required String giftWrapEventJson,
   }) async {
     _callCount++;
@@ -1117,7 +1116,7 @@ class _MockCircleServiceMixedBatch
 
   @override
   Future<String> signDeletionEvent({
-    required List<int> identitySecretBytes,
+    required SecretHandle secret,
     required List<String> eventIds,
   }) async => throw UnimplementedError();
 
@@ -34,6 +34,7 @@ import 'package:haven/src/services/identity_service.dart';
 import 'package:haven/src/services/maintenance_service.dart';
 import 'package:haven/src/services/relay_service.dart';
 
+import '../mocks/fake_secret_handle.dart';
 import '../mocks/mock_relay_service.dart';
 
 /// A fake circle-manager FFI handle (never invoked by [_RecordingRelay]).
@@ -57,7 +58,7 @@ class _RecordingRelay extends MockRelayService {
   @override
   Future<KeyPackageMaintenanceResult> maintainKeyPackage({
     required CircleManagerFfi circle,
-    required List<int> identitySecretBytes,
+    required SecretHandle secret,
   }) async {
     kpCalls++;
     return kpResult;
@@ -66,7 +67,7 @@ class _RecordingRelay extends MockRelayService {
   @override
   Future<RelayListMaintenanceResult> maintainRelayList({
     required CircleManagerFfi circle,
-    required List<int> identitySecretBytes,
+    required SecretHandle secret,
   }) async {
     relayListCalls++;
     return const RelayListMaintenanceResult.empty();
@@ -86,14 +87,12 @@ class _MockIdentityService implements IdentityService {
     createdAt: DateTime(2024),
   );
 
-  static final _testSecretBytes = List<int>.generate(32, (i) => i);
-
   @override
   Future<Identity?> getIdentity() async =>
       identityExists ? _testIdentity : null;
 
   @override
-  Future<List<int>> getSecretBytes() async => _testSecretBytes;
+  Future<SecretHandle> getSecretHandle() async => FakeSecretHandle();
 
   @override
   Future<bool> hasIdentity() async => identityExists;
@@ -141,7 +140,7 @@ void main() {
             MaintenanceService(
               relayService: relay,
               circleManagerFactory: () async => _FakeCircleManager(),
-              identitySecretBytes: () async => List.generate(32, (i) => i),
+              identitySecret: () async => FakeSecretHandle(),
             ),
           ),
         ],
@@ -172,7 +171,7 @@ void main() {
               MaintenanceService(
                 relayService: relay,
                 circleManagerFactory: () async => _FakeCircleManager(),
-                identitySecretBytes: () async => List.generate(32, (i) => i),
+                identitySecret: () async => FakeSecretHandle(),
               ),
             ),
           ],
@@ -200,7 +199,7 @@ void main() {
             MaintenanceService(
               relayService: relay,
               circleManagerFactory: () async => _FakeCircleManager(),
-              identitySecretBytes: () async => List.generate(32, (i) => i),
+              identitySecret: () async => FakeSecretHandle(),
             ),
           ),
         ],
@@ -228,7 +227,7 @@ void main() {
               MaintenanceService(
                 relayService: relay,
                 circleManagerFactory: () async => _FakeCircleManager(),
-                identitySecretBytes: () async => List.generate(32, (i) => i),
+                identitySecret: () async => FakeSecretHandle(),
               ),
             ),
           ],
@@ -263,7 +262,7 @@ void main() {
                 relayService: relay,
                 circleManagerFactory: () async =>
                     throw StateError('circle manager unavailable'),
-                identitySecretBytes: () async => List.generate(32, (i) => i),
+                identitySecret: () async => FakeSecretHandle(),
               ),
             ),
           ],
@@ -291,7 +290,7 @@ void main() {
               MaintenanceService(
                 relayService: relay,
                 circleManagerFactory: () async => _FakeCircleManager(),
-                identitySecretBytes: () async => List.generate(32, (i) => i),
+                identitySecret: () async => FakeSecretHandle(),
               ),
             ),
           ],
@@ -20,6 +20,7 @@ import 'package:haven/src/constants/location.dart';
 import 'package:haven/src/providers/circles_provider.dart';
 import 'package:haven/src/providers/location_sharing_provider.dart';
 import 'package:haven/src/providers/service_providers.dart';
+import 'package:haven/src/rust/api.dart' show SecretHandle;
 import 'package:haven/src/services/circle_service.dart';
 import 'package:haven/src/services/identity_service.dart';
 import 'package:haven/src/services/location_service.dart';
@@ -684,7 +685,7 @@ class _MockIdentityService implements IdentityService {
       identity?.pubkeyHex ?? (throw UnimplementedError());
 
   @override
-  Future<List<int>> getSecretBytes() async => throw UnimplementedError();
+  Future<SecretHandle> getSecretHandle() async => throw UnimplementedError();
 
   @override
   Future<String?> getDisplayName() async => null;
@@ -10,6 +10,7 @@ import 'package:haven/src/rust/api.dart';
 import 'package:haven/src/services/maintenance_service.dart';
 import 'package:haven/src/services/relay_service.dart';
 
+import '../mocks/fake_secret_handle.dart';
 import '../mocks/mock_relay_service.dart';
 
 /// A fake circle-manager FFI handle (never actually invoked).
@@ -30,7 +31,7 @@ class _FakeMaintenanceService extends MaintenanceService {
       : super(
           relayService: MockRelayService(),
           circleManagerFactory: () async => _FakeCircleManager(),
-          identitySecretBytes: () async => const <int>[],
+          identitySecret: () async => FakeSecretHandle(),
         );
 
   int kpCalls = 0;
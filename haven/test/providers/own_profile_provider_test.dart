@@ -18,9 +18,11 @@ import 'package:flutter_riverpod/flutter_riverpod.dart';
 import 'package:flutter_test/flutter_test.dart';
 import 'package:haven/src/providers/own_profile_provider.dart';
 import 'package:haven/src/providers/service_providers.dart';
+import 'package:haven/src/rust/api.dart' show SecretHandle;
 import 'package:haven/src/services/identity_service.dart';
 import 'package:haven/src/services/profile_service.dart';
 
+import '../mocks/fake_secret_handle.dart';
 import '../mocks/mock_profile_service.dart';
 
 // ---------------------------------------------------------------------------
@@ -54,7 +56,7 @@ class _FakeIdentityService implements IdentityService {
   Future<String> getPubkeyHex() async => identity!.pubkeyHex;
 
   @override
-  Future<List<int>> getSecretBytes() async => List<int>.filled(32, 0x11);
+  Future<SecretHandle> getSecretHandle() async => FakeSecretHandle();
 
   @override
   Future<void> deleteIdentity() async {}
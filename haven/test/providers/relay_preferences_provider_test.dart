@@ -10,9 +10,11 @@ import 'package:flutter_test/flutter_test.dart';
 import 'package:haven/src/providers/identity_provider.dart';
 import 'package:haven/src/providers/relay_preferences_provider.dart';
 import 'package:haven/src/providers/service_providers.dart';
+import 'package:haven/src/rust/api.dart' show SecretHandle;
 import 'package:haven/src/services/identity_service.dart';
 import 'package:haven/src/services/relay_preferences_service.dart';
 
+import '../mocks/fake_secret_handle.dart';
 import '../mocks/mock_relay_preferences_service.dart';
 import '../mocks/mock_relay_service.dart';
 
@@ -24,7 +26,7 @@ class _FakeIdentityNotifier extends IdentityNotifier {
   Future<Identity?> build() async => null;
 
   @override
-  Future<List<int>> getSecretBytes() async => List<int>.filled(32, 7);
+  Future<SecretHandle> getSecretHandle() async => FakeSecretHandle();
 }
 
 void main() {
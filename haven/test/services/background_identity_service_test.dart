@@ -2,7 +2,7 @@
 ///
 /// The adapter is the only identity surface exposed to the background
 /// isolate's `LocationSharingService`. These tests guard the privacy
-/// invariant that secret material (sign / getSecretBytes / exportNsec)
+/// invariant that secret material (sign / getSecretHandle / exportNsec)
 /// is unreachable through this adapter — any future caller that adds
 /// such a code path into the bg isolate must explicitly opt in by
 /// implementing the method, rather than getting a silent fallback.
@@ -91,8 +91,8 @@ void main() {
     // ------------------------------------------------------------------
     // PRIVACY INVARIANT: anything that exposes secret material or mutates
     // identity state MUST throw UnimplementedError. Adding such a code
-    // path into the bg isolate would weaken Haven's secret-bytes
-    // exposure window. A future contributor that wires getSecretBytes
+    // path into the bg isolate would weaken Haven's secret
+    // exposure window. A future contributor that wires getSecretHandle
     // into a bg call site should hit this test failure first and have
     // to deliberately decide.
     // ------------------------------------------------------------------
@@ -123,9 +123,9 @@ void main() {
       );
     });
 
-    test('getSecretBytes throws UnimplementedError — bg isolate must not '
+    test('getSecretHandle throws UnimplementedError — bg isolate must not '
         'expose secret material', () {
-      expect(adapter.getSecretBytes, throwsA(isA<UnimplementedError>()));
+      expect(adapter.getSecretHandle, throwsA(isA<UnimplementedError>()));
     });
 
     test('deleteIdentity throws UnimplementedError', () {
@@ -2378,7 +2378,7 @@ class _ThrowOnFirstDecryptService
 
   @override
   Future<CircleCreationResult> createCircle({
-    required List<int> identitySecretBytes,
+    required SecretHandle secret,
     required List<KeyPackageData> memberKeyPackages,
     required String name,
     required CircleType circleType,
@@ -2420,13 +2420,13 @@ class _ThrowOnFirstDecryptService
 
   @override
   Future<Invitation?> processGiftWrappedInvitation({
-    required List<int> identitySecretBytes,
+    required SecretHandle secret,
     required String giftWrapEventJson,
   }) async => throw UnimplementedError();
 
   @override
   Future<String> signDeletionEvent({
-    required List<int> identitySecretBytes,
+    required SecretHandle secret,
     required List<String> eventIds,
   }) async => throw UnimplementedError();
 }
@@ -2443,13 +2443,13 @@ class _MutableMockRelayService implements RelayService {
   @override
   Future<KeyPackageMaintenanceResult> maintainKeyPackage({
     required CircleManagerFfi circle,
-    required List<int> identitySecretBytes,
+    required SecretHandle secret,
   }) async => const KeyPackageMaintenanceResult.empty();
 
   @override
   Future<RelayListMaintenanceResult> maintainRelayList({
     required CircleManagerFfi circle,
-    required List<int> identitySecretBytes,
+    required SecretHandle secret,
   }) async => const RelayListMaintenanceResult.empty();
 
   @override
@@ -2459,7 +2459,7 @@ class _MutableMockRelayService implements RelayService {
   @override
   Future<LegacyRetractionResult> retractLegacyKeyMaterial({
     required CircleManagerFfi circle,
-    required List<int> identitySecretBytes,
+    required SecretHandle secret,
   }) async => const LegacyRetractionResult.empty();
   _MutableMockRelayService({List<String>? initialMessages})
     : _messages = initialMessages ?? [];
@@ -2561,13 +2561,13 @@ class _PauseRacingRelayService implements RelayService {
   @override
   Future<KeyPackageMaintenanceResult> maintainKeyPackage({
     required CircleManagerFfi circle,
-    required List<int> identitySecretBytes,
+    required SecretHandle secret,
   }) async => const KeyPackageMaintenanceResult.empty();
 
   @override
   Future<RelayListMaintenanceResult> maintainRelayList({
     required CircleManagerFfi circle,
-    required List<int> identitySecretBytes,
+    required SecretHandle secret,
   }) async => const RelayListMaintenanceResult.empty();
 
   @override
@@ -2577,7 +2577,7 @@ class _PauseRacingRelayService implements RelayService {
   @override
   Future<LegacyRetractionResult> retractLegacyKeyMaterial({
     required CircleManagerFfi circle,
-    required List<int> identitySecretBytes,
+    required SecretHandle secret,
   }) async => const LegacyRetractionResult.empty();
   _PauseRacingRelayService({required this.messages});
 
@@ -2670,13 +2670,13 @@ class _SinceCapturingRelayService implements RelayService {
   @override
   Future<KeyPackageMaintenanceResult> maintainKeyPackage({
     required CircleManagerFfi circle,
-    required List<int> identitySecretBytes,
+    required SecretHandle secret,
   }) async => const KeyPackageMaintenanceResult.empty();
 
   @override
   Future<RelayListMaintenanceResult> maintainRelayList({
     required CircleManagerFfi circle,
-    required List<int> identitySecretBytes,
+    required SecretHandle secret,
   }) async => const RelayListMaintenanceResult.empty();
 
   @override
@@ -2686,7 +2686,7 @@ class _SinceCapturingRelayService implements RelayService {
   @override
   Future<LegacyRetractionResult> retractLegacyKeyMaterial({
     required CircleManagerFfi circle,
-    required List<int> identitySecretBytes,
+    required SecretHandle secret,
   }) async => const LegacyRetractionResult.empty();
 
   /// The `since` captured from the most recent `fetchGroupMessages` call.
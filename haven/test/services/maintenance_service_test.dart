@@ -5,6 +5,7 @@ import 'package:haven/src/rust/api.dart';
 import 'package:haven/src/services/maintenance_service.dart';
 import 'package:haven/src/services/relay_service.dart';
 
+import '../mocks/fake_secret_handle.dart';
 import '../mocks/mock_relay_service.dart';
 
 /// A relay service that records the maintenance calls + the secret it received.
@@ -15,11 +16,11 @@ class _RecordingRelay extends MockRelayService {
   int retractCalls = 0;
   bool throwOnHealth = false;
 
-  /// The exact `identitySecretBytes` reference passed to the last KP call, so
-  /// the test can assert the orchestrator scrubbed it afterwards.
-  List<int>? capturedKpSecret;
-  List<int>? capturedRelayListSecret;
-  List<int>? capturedRetractSecret;
+  /// The exact `secret` handle passed to the last KP call, so the test can
+  /// assert the orchestrator disposed it afterwards.
+  SecretHandle? capturedKpSecret;
+  SecretHandle? capturedRelayListSecret;
+  SecretHandle? capturedRetractSecret;
 
   LegacyRetractionResult retractResult = const LegacyRetractionResult(
     legacy443Scrubbed: 1,
@@ -38,10 +39,10 @@ class _RecordingRelay extends MockRelayService {
   @override
   Future<KeyPackageMaintenanceResult> maintainKeyPackage({
     required CircleManagerFfi circle,
-    required List<int> identitySecretBytes,
+    required SecretHandle secret,
   }) async {
     kpCalls++;
-    capturedKpSecret = identitySecretBytes;
+    capturedKpSecret = secret;
     if (kpGate != null) await kpGate!.future;
     return kpResult;
   }
@@ -49,10 +50,10 @@ class _RecordingRelay extends MockRelayService {
   @override
   Future<RelayListMaintenanceResult> maintainRelayList({
     required CircleManagerFfi circle,
-    required List<int> identitySecretBytes,
+    required SecretHandle secret,
   }) async {
     relayListCalls++;
-    capturedRelayListSecret = identitySecretBytes;
+    capturedRelayListSecret = secret;
     return const RelayListMaintenanceResult(
       inbox: RelayListCategoryResult(
         action: RelayListMaintenanceAction.republished,
@@ -74,10 +75,10 @@ class _RecordingRelay extends MockRelayService {
   @override
   Future<LegacyRetractionResult> retractLegacyKeyMaterial({
     required CircleManagerFfi circle,
-    required List<int> identitySecretBytes,
+    required SecretHandle secret,
   }) async {
     retractCalls++;
-    capturedRetractSecret = identitySecretBytes;
+    capturedRetractSecret = secret;
     return retractResult;
   }
 }
@@ -96,7 +97,7 @@ void main() {
       final service = MaintenanceService(
         relayService: relay,
         circleManagerFactory: () async => _FakeCircleManager(),
-        identitySecretBytes: () async => [1, 2, 3, 4],
+        identitySecret: () async => FakeSecretHandle(),
       );
 
       final result = await service.maintainKeyPackage();
@@ -107,24 +108,22 @@ void main() {
       expect(result.relayErrors, 1);
     });
 
-    test('scrubs the secret buffer after the call (Rule 9)', () async {
+    test('disposes the secret handle after the call (Rule 9)', () async {
       final relay = _RecordingRelay();
       final service = MaintenanceService(
         relayService: relay,
         circleManagerFactory: () async => _FakeCircleManager(),
-        identitySecretBytes: () async => [9, 8, 7, 6, 5],
+        identitySecret: () async => FakeSecretHandle(),
       );
 
       await service.maintainKeyPackage();
 
-      // The orchestrator copies the secret into a buffer it owns and
-      // `fillRange`s it in `finally` — the reference the relay captured must
-      // now be all zeros (the security property; the concrete buffer type is
-      // an implementation detail we intentionally do not assert on).
+      // The orchestrator disposes the handle in `finally`, which zeroizes the
+      // Rust-side key copy — the handle the relay captured must be disposed.
       final captured = relay.capturedKpSecret;
       expect(captured, isNotNull);
-      expect(captured!.every((b) => b == 0), isTrue,
-          reason: 'secret buffer must be zeroized after the FFI consumes it');
+      expect(captured!.isDisposed, isTrue,
+          reason: 'secret handle must be disposed once the FFI returns');
     });
 
     test('returns empty (never throws) when the handle factory throws',
@@ -133,7 +132,7 @@ void main() {
       final service = MaintenanceService(
         relayService: relay,
         circleManagerFactory: () async => throw StateError('no manager'),
-        identitySecretBytes: () async => [1, 2, 3],
+        identitySecret: () async => FakeSecretHandle(),
       );
 
       final result = await service.maintainKeyPackage();
@@ -149,7 +148,7 @@ void main() {
       final service = MaintenanceService(
         relayService: relay,
         circleManagerFactory: () async => _FakeCircleManager(),
-        identitySecretBytes: () async => throw StateError('no secret'),
+        identitySecret: () async => throw StateError('no secret'),
       );
 
       final result = await service.maintainKeyPackage();
@@ -165,7 +164,7 @@ void main() {
       final service = MaintenanceService(
         relayService: relay,
         circleManagerFactory: () async => _FakeCircleManager(),
-        identitySecretBytes: () async => [1, 2, 3],
+        identitySecret: () async => FakeSecretHandle(),
       );
 
       final result = await service.maintainRelayList();
@@ -174,19 +173,19 @@ void main() {
       expect(result.inbox.action, RelayListMaintenanceAction.republished);
     });
 
-    test('scrubs the secret buffer after the call', () async {
+    test('disposes the secret handle after the call', () async {
       final relay = _RecordingRelay();
       final service = MaintenanceService(
         relayService: relay,
         circleManagerFactory: () async => _FakeCircleManager(),
-        identitySecretBytes: () async => [4, 5, 6],
+        identitySecret: () async => FakeSecretHandle(),
       );
 
       await service.maintainRelayList();
 
       final captured = relay.capturedRelayListSecret;
       expect(captured, isNotNull);
-      expect(captured!.every((b) => b == 0), isTrue);
+      expect(captured!.isDisposed, isTrue);
     });
 
     test('returns empty (never throws) when a dependency throws', () async {
@@ -194,7 +193,7 @@ void main() {
       final service = MaintenanceService(
         relayService: relay,
         circleManagerFactory: () async => throw StateError('boom'),
-        identitySecretBytes: () async => [1],
+        identitySecret: () async => FakeSecretHandle(),
       );
 
       final result = await service.maintainRelayList();
@@ -211,7 +210,7 @@ void main() {
         relayService: relay,
         // These MUST NOT be resolved for the health task — it needs neither.
         circleManagerFactory: () async => throw StateError('must not resolve'),
-        identitySecretBytes: () async => throw StateError('must not resolve'),
+        identitySecret: () async => throw StateError('must not resolve'),
       );
 
       final result = await service.maintainSubscriptionHealth();
@@ -227,7 +226,7 @@ void main() {
       final service = MaintenanceService(
         relayService: relay,
         circleManagerFactory: () async => _FakeCircleManager(),
-        identitySecretBytes: () async => const [],
+        identitySecret: () async => FakeSecretHandle(),
       );
 
       final result = await service.maintainSubscriptionHealth();
@@ -242,7 +241,7 @@ void main() {
       final service = MaintenanceService(
         relayService: relay,
         circleManagerFactory: () async => _FakeCircleManager(),
-        identitySecretBytes: () async => [1, 2, 3, 4],
+        identitySecret: () async => FakeSecretHandle(),
       );
 
       final result = await service.retractLegacyKeyMaterial();
@@ -252,12 +251,12 @@ void main() {
       expect(result.relayListRetracted, isTrue);
     });
 
-    test('scrubs the secret buffer after the call (Rule 9)', () async {
+    test('disposes the secret handle after the call (Rule 9)', () async {
       final relay = _RecordingRelay();
       final service = MaintenanceService(
         relayService: relay,
         circleManagerFactory: () async => _FakeCircleManager(),
-        identitySecretBytes: () async => [9, 8, 7, 6, 5],
+        identitySecret: () async => FakeSecretHandle(),
       );
 
       await service.retractLegacyKeyMaterial();
@@ -265,9 +264,9 @@ void main() {
       final captured = relay.capturedRetractSecret;
       expect(captured, isNotNull);
       expect(
-        captured!.every((b) => b == 0),
+        captured!.isDisposed,
         isTrue,
-        reason: 'secret buffer must be zeroized after the FFI consumes it',
+        reason: 'secret handle must be disposed once the FFI returns',
       );
     });
 
@@ -277,7 +276,7 @@ void main() {
       final service = MaintenanceService(
         relayService: relay,
         circleManagerFactory: () async => throw StateError('no manager'),
-        identitySecretBytes: () async => [1, 2, 3],
+        identitySecret: () async => FakeSecretHandle(),
       );
 
       final result = await service.retractLegacyKeyMaterial();
@@ -294,7 +293,7 @@ void main() {
       final service = MaintenanceService(
         relayService: relay,
         circleManagerFactory: () async => _FakeCircleManager(),
-        identitySecretBytes: () async => [1, 2, 3],
+        identitySecret: () async => FakeSecretHandle(),
       );
 
       final result = await service.retractLegacyKeyMaterial();
@@ -24,6 +24,8 @@ import 'package:haven/src/services/nostr_circle_service.dart';
 import 'package:haven/src/services/nostr_relay_service.dart';
 import 'package:haven/src/services/relay_service.dart';
 
+import '../mocks/fake_secret_handle.dart';
+
 // ---------------------------------------------------------------------------
 // Minimal stubs used by NostrCircleService initialization tests
 // ---------------------------------------------------------------------------
@@ -43,13 +45,13 @@ class _StubRelayService implements RelayService {
   @override
   Future<KeyPackageMaintenanceResult> maintainKeyPackage({
     required CircleManagerFfi circle,
-    required List<int> identitySecretBytes,
+    required SecretHandle secret,
   }) async => const KeyPackageMaintenanceResult.empty();
 
   @override
   Future<RelayListMaintenanceResult> maintainRelayList({
     required CircleManagerFfi circle,
-    required List<int> identitySecretBytes,
+    required SecretHandle secret,
   }) async => const RelayListMaintenanceResult.empty();
 
   @override
@@ -59,7 +61,7 @@ class _StubRelayService implements RelayService {
   @override
   Future<LegacyRetractionResult> retractLegacyKeyMaterial({
     required CircleManagerFfi circle,
-    required List<int> identitySecretBytes,
+    required SecretHandle secret,
   }) async => const LegacyRetractionResult.empty();
 
   @override
@@ -742,7 +744,7 @@ void main() {
           // the "no identity available" gate — checked right after keyring
           // init, before the data-directory I/O — doesn't short-circuit
           // before `getDataDirectory()` is ever reached.
-          identitySecretBytesProvider: () async => List<int>.filled(32, 7),
+          identitySecretProvider: () async => FakeSecretHandle(),
         );
 
         // Initialization will fail when data directory throws — that is fine.
@@ -6,6 +6,7 @@ import 'package:haven/src/rust/api.dart';
 import 'package:haven/src/services/nostr_subscription_service.dart';
 import 'package:haven/src/services/subscription_service.dart';
 
+import '../mocks/fake_secret_handle.dart';
 import '../mocks/mock_circle_service.dart';
 
 /// A fake [LiveSyncFfi] engine. Only the 7 methods the service drives are
@@ -96,7 +97,7 @@ class _SpyRouter extends LiveEventRouter {
     : super(
         circleService: MockCircleService(),
         circlesSnapshot: () async => const [],
-        secretBytes: () async => const [],
+        secretProvider: () async => FakeSecretHandle(),
         parseLocation: (_, _) async => null,
         ingestLocation: (_, _) async {},
         reconcileRoster: (_) async {},
@@ -30,10 +30,13 @@ library;
 import 'dart:typed_data';
 
 import 'package:flutter_test/flutter_test.dart';
+import 'package:haven/src/rust/api.dart' show SecretHandle;
 import 'package:haven/src/services/identity_service.dart';
 import 'package:haven/src/services/nostr_profile_service.dart';
 import 'package:haven/src/services/profile_service.dart';
 
+import '../mocks/fake_secret_handle.dart';
+
 // ---------------------------------------------------------------------------
 // Fakes
 // ---------------------------------------------------------------------------
@@ -88,7 +91,7 @@ class _FakeIdentityService implements IdentityService {
   Future<String> getPubkeyHex() async => identity!.pubkeyHex;
 
   @override
-  Future<List<int>> getSecretBytes() async => List<int>.filled(32, 0x11);
+  Future<SecretHandle> getSecretHandle() async => FakeSecretHandle();
 
   @override
   Future<void> deleteIdentity() async {}
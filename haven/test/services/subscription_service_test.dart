@@ -5,6 +5,7 @@ import 'package:haven/src/rust/api.dart';
 import 'package:haven/src/services/circle_service.dart';
 import 'package:haven/src/services/subscription_service.dart';
 
+import '../mocks/fake_secret_handle.dart';
 import '../mocks/mock_circle_service.dart';
 
 /// A captured set of router side effects, for assertions.
@@ -54,9 +55,9 @@ void main() {
     LiveEventRouter buildRouter() => LiveEventRouter(
       circleService: circleService,
       circlesSnapshot: () async => circles,
-      secretBytes: () async {
+      secretProvider: () async {
         spy.secretFetches++;
-        return List<int>.filled(32, 7);
+        return FakeSecretHandle();
       },
       parseLocation: (content, sender) async => parseResult,
       ingestLocation: (circle, decrypted) async => spy.ingested.add(decrypted),
@@ -148,7 +149,7 @@ void main() {
       expect(spy.invitationReceived, 1);
     });
 
-    test('Welcome zeroizes the identity secret after use (Rule 9)', () async {
+    test('Welcome disposes the identity secret after use (Rule 9)', () async {
       await buildRouter().handleEvent(
         const FfiRelayEvent(
           kind: FfiRelayEventKind.welcome,
@@ -156,16 +157,18 @@ void main() {
           wrapCreatedAtSecs: 4242,
         ),
       );
-      // The router copies the (non-zero, 32×7) secret into a Uint8List, passes
-      // it to processGiftWrappedInvitation (the mock captures that reference),
-      // then scrubs it in a finally.
+      // The router passes the fetched handle to processGiftWrappedInvitation
+      // (the mock captures it), then disposes it in a finally.
       final ref = circleService.processGiftWrappedInvitationSecretRef;
       expect(ref, isNotNull);
-      expect(ref!.length, 32);
-      expect(ref, everyElement(0), reason: 'secret buffer scrubbed after use');
+      expect(
+        ref!.isDisposed,
+        isTrue,
+        reason: 'secret handle disposed after use',
+      );
     });
 
-    test('Welcome zeroizes the secret even when processing throws', () async {
+    test('Welcome disposes the secret even when processing throws', () async {
       circleService.shouldThrowOnProcessGiftWrappedInvitation = true;
       // handleEvent never rethrows (every side effect is guarded).
       await buildRouter().handleEvent(
@@ -177,9 +180,9 @@ void main() {
       final ref = circleService.processGiftWrappedInvitationSecretRef;
       expect(ref, isNotNull);
       expect(
-        ref,
-        everyElement(0),
-        reason: 'finally scrubs the secret on the error path too',
+        ref!.isDisposed,
+        isTrue,
+        reason: 'finally disposes the secret on the error path too',
       );
     });
 
@@ -197,7 +200,7 @@ void main() {
       final router = LiveEventRouter(
         circleService: circleService,
         circlesSnapshot: () async => circles,
-        secretBytes: () async => List<int>.filled(32, 0),
+        secretProvider: () async => FakeSecretHandle(),
         parseLocation: (c, s) async => _decrypted('peer'),
         ingestLocation: (c, d) async {},
         reconcileRoster: (c) async {},
@@ -20,6 +20,7 @@ import 'package:haven/l10n/app_localizations.dart';
 import 'package:haven/src/pages/onboarding/onboarding_shell.dart';
 import 'package:haven/src/providers/onboarding_provider.dart';
 import 'package:haven/src/providers/service_providers.dart';
+import 'package:haven/src/rust/api.dart' show SecretHandle;
 import 'package:haven/src/services/identity_service.dart';
 import 'package:haven/src/widgets/app_router.dart';
 import 'package:shared_preferences/shared_preferences.dart';
@@ -121,7 +122,7 @@ class _StubIdentityService implements IdentityService {
   Future<String> getPubkeyHex() async => throw UnimplementedError();
 
   @override
-  Future<List<int>> getSecretBytes() async => throw UnimplementedError();
+  Future<SecretHandle> getSecretHandle() async => throw UnimplementedError();
 
   @override
   Future<String?> getDisplayName() async => null;